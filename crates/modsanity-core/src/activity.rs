@@ -0,0 +1,212 @@
+//! Activity log
+//!
+//! Installs, enabling, disabling, removing and restoring mods, priority
+//! changes, deploys, game launches, profile switches, plugin sorting and
+//! cleaning are recorded with their time, the active profile and their
+//! parameters. Reading the log back
+//! answers "what changed since it last worked" when a setup breaks.
+
+use crate::db::{ActivityRecord, Database};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+/// A kind of recorded action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activity {
+    Install,
+    Enable,
+    Disable,
+    Remove,
+    Restore,
+    Priority,
+    SortMods,
+    Deploy,
+    Purge,
+    Launch,
+    ProfileSwitch,
+    SortPlugins,
+    SavePlugins,
+    CleanPlugin,
+}
+
+impl Activity {
+    pub const ALL: &'static [Activity] = &[
+        Activity::Install,
+        Activity::Enable,
+        Activity::Disable,
+        Activity::Remove,
+        Activity::Restore,
+        Activity::Priority,
+        Activity::SortMods,
+        Activity::Deploy,
+        Activity::Purge,
+        Activity::Launch,
+        Activity::ProfileSwitch,
+        Activity::SortPlugins,
+        Activity::SavePlugins,
+        Activity::CleanPlugin,
+    ];
+
+    /// Stable ID stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Activity::Install => "install",
+            Activity::Enable => "enable",
+            Activity::Disable => "disable",
+            Activity::Remove => "remove",
+            Activity::Restore => "restore",
+            Activity::Priority => "priority",
+            Activity::SortMods => "sort-mods",
+            Activity::Deploy => "deploy",
+            Activity::Purge => "purge",
+            Activity::Launch => "launch",
+            Activity::ProfileSwitch => "profile-switch",
+            Activity::SortPlugins => "sort-plugins",
+            Activity::SavePlugins => "save-plugins",
+            Activity::CleanPlugin => "clean-plugin",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|a| a.as_str().eq_ignore_ascii_case(id))
+    }
+}
+
+/// An activity log entry with its details parsed
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityEntry {
+    pub time: DateTime<Utc>,
+    pub profile: Option<String>,
+    pub action: String,
+    pub target: Option<String>,
+    pub details: Value,
+}
+
+impl ActivityEntry {
+    fn from_record(record: ActivityRecord) -> Self {
+        Self {
+            time: DateTime::parse_from_rfc3339(&record.created_at)
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_default(),
+            profile: record.profile,
+            action: record.action,
+            target: record.target,
+            details: record
+                .details
+                .and_then(|d| serde_json::from_str(&d).ok())
+                .unwrap_or(Value::Null),
+        }
+    }
+
+    /// Details as `key=value` pairs, for one-line display
+    pub fn details_summary(&self) -> String {
+        let Value::Object(map) = &self.details else {
+            return String::new();
+        };
+        map.iter()
+            .filter(|(_, v)| !v.is_null())
+            .map(|(k, v)| match v {
+                Value::String(s) => format!("{}={}", k, s),
+                other => format!("{}={}", k, other),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Record an action. A failure is logged rather than returned, so the log
+/// never fails the action it describes.
+pub fn record(
+    db: &Database,
+    game_id: &str,
+    profile: Option<&str>,
+    activity: Activity,
+    target: Option<&str>,
+    details: Value,
+) {
+    let entry = ActivityRecord {
+        id: None,
+        game_id: game_id.to_string(),
+        profile: profile.map(str::to_string),
+        action: activity.as_str().to_string(),
+        target: target.map(str::to_string),
+        details: (!details.is_null()).then(|| details.to_string()),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = db.insert_activity(&entry) {
+        tracing::warn!("Failed to record {} activity: {}", activity.as_str(), e);
+    }
+}
+
+/// Newest entries for a game, optionally of one action
+pub fn recent(
+    db: &Database,
+    game_id: &str,
+    activity: Option<Activity>,
+    limit: usize,
+) -> Result<Vec<ActivityEntry>> {
+    Ok(db
+        .get_activity(game_id, activity.map(|a| a.as_str()), limit)?
+        .into_iter()
+        .map(ActivityEntry::from_record)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_record_and_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("modsanity.db")).unwrap();
+
+        record(
+            &db,
+            "skyrimse",
+            Some("Default"),
+            Activity::Install,
+            Some("SkyUI"),
+            json!({ "version": "5.2", "nexus_mod_id": 12604 }),
+        );
+        record(
+            &db,
+            "skyrimse",
+            Some("Default"),
+            Activity::Deploy,
+            None,
+            Value::Null,
+        );
+        record(
+            &db,
+            "fallout4",
+            None,
+            Activity::Enable,
+            Some("MCM"),
+            Value::Null,
+        );
+
+        let entries = recent(&db, "skyrimse", None, 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, "deploy");
+        assert_eq!(entries[0].details, Value::Null);
+        assert_eq!(entries[1].target.as_deref(), Some("SkyUI"));
+        assert_eq!(
+            entries[1].details_summary(),
+            "nexus_mod_id=12604 version=5.2"
+        );
+
+        let installs = recent(&db, "skyrimse", Some(Activity::Install), 10).unwrap();
+        assert_eq!(installs.len(), 1);
+        assert_eq!(
+            Activity::from_id("Profile-Switch"),
+            Some(Activity::ProfileSwitch)
+        );
+    }
+}
@@ -0,0 +1,1127 @@
+//! Configuration management for ModSanity
+//!
+//! Uses XDG-compliant paths:
+//! - Config: ~/.config/modsanity/config.toml
+//! - Data: ~/.local/share/modsanity/
+//! - Cache: ~/.cache/modsanity/
+
+mod paths;
+mod validate;
+
+pub use paths::Paths;
+pub use validate::{validate, ConfigIssue, ConfigReport, Severity};
+
+use crate::error::Invalid;
+use crate::games::GameType;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Variables available in `downloads_dir_override` and `staging_dir_override`
+pub const PATH_VARIABLES: &[&str] = &["game", "profile", "home"];
+
+/// Expand `{name}` variables in a directory template. A variable the
+/// template uses that has no value is an error: `/mnt/mods/{game}/staging`
+/// with no active game has no sensible folder, and a parent folder would
+/// hold other games' files.
+pub fn expand_path_template(template: &str, vars: &[(&str, Option<&str>)]) -> Result<PathBuf> {
+    let mut out = template.to_string();
+    for (name, value) in vars {
+        let placeholder = format!("{{{}}}", name);
+        if !out.contains(&placeholder) {
+            continue;
+        }
+        match value {
+            Some(value) => out = out.replace(&placeholder, value),
+            None => bail!(Invalid::new(format!(
+                "'{}' uses {{{}}}, but {}",
+                template,
+                name,
+                match *name {
+                    "game" => "no game is selected",
+                    "profile" => "no profile is active",
+                    _ => "it has no value",
+                }
+            ))),
+        }
+    }
+    Ok(PathBuf::from(out))
+}
+
+/// Refuse `{profile}` in a staging template: installed mods belong to a
+/// game, not a profile, so a staging folder per profile can't hold them
+pub fn check_staging_template(template: &str) -> Result<()> {
+    if template.contains("{profile}") {
+        bail!(Invalid::new(
+            "The staging directory can't use {profile}: installed mods are shared by all profiles of a game"
+        ));
+    }
+    Ok(())
+}
+
+/// Variables available in `[[custom_tools]]` arguments and working directories
+pub const TOOL_VARIABLES: &[&str] = &["game", "profile", "home", "game_dir", "data_dir"];
+
+/// Expand `{name}` variables in a tool argument. Variables without a value
+/// are left as they are.
+pub fn expand_tool_template(template: &str, vars: &[(&str, Option<&str>)]) -> String {
+    let mut out = template.to_string();
+    for (name, value) in vars {
+        if let Some(value) = value {
+            out = out.replace(&format!("{{{}}}", name), value);
+        }
+    }
+    out
+}
+
+/// Split a line of tool arguments on whitespace; double quotes keep spaces
+/// in an argument
+pub fn split_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut started = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if started {
+                    args.push(std::mem::take(&mut current));
+                    started = false;
+                }
+            }
+            c => {
+                current.push(c);
+                started = true;
+            }
+        }
+    }
+    if started {
+        args.push(current);
+    }
+    args
+}
+
+/// Join arguments into a line [`split_args`] reads back
+pub fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                format!("\"{}\"", arg)
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `{name}` variables in a template that aren't in [`PATH_VARIABLES`]
+pub fn unknown_path_variables(template: &str) -> Vec<String> {
+    unknown_variables(template, PATH_VARIABLES)
+}
+
+/// `{name}` variables in a template that aren't in [`TOOL_VARIABLES`]
+pub fn unknown_tool_variables(template: &str) -> Vec<String> {
+    unknown_variables(template, TOOL_VARIABLES)
+}
+
+fn unknown_variables(template: &str, known: &[&str]) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        if !known.contains(&name) {
+            unknown.push(name.to_string());
+        }
+        rest = &rest[start + len + 1..];
+    }
+    unknown
+}
+
+/// Main configuration structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+#[derive(Default)]
+pub struct Config {
+    /// Active game identifier (e.g., "skyrimse")
+    pub active_game: Option<String>,
+
+    /// Active profile name
+    pub active_profile: Option<String>,
+
+    /// Nexus Mods API key
+    pub nexus_api_key: Option<String>,
+
+    /// Override for the Nexus API host (e.g. a mock server in tests)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nexus_base_url: Option<String>,
+
+    /// Deployment settings
+    pub deployment: DeploymentConfig,
+
+    /// TUI settings
+    pub tui: TuiConfig,
+
+    /// External tools configuration (Proton + Windows tool executables)
+    pub external_tools: ExternalToolsConfig,
+
+    /// Retention of installed archives for version rollback
+    pub archive_retention: ArchiveRetentionConfig,
+
+    /// How long removed mods are kept for `mod restore`
+    pub trash: TrashConfig,
+
+    /// Archive installation settings
+    pub install: InstallConfig,
+
+    /// Preferences for random mod discovery ("surprise me")
+    pub discovery: DiscoveryConfig,
+
+    /// Log file rotation
+    pub logging: LoggingConfig,
+
+    /// Named filters for the Mods screen (`[[mod_filters]]`)
+    pub mod_filters: Vec<ModFilterConfig>,
+
+    /// Override for downloaded archives directory; may use `{game}`,
+    /// `{profile}` and `{home}` (see [`PATH_VARIABLES`])
+    pub downloads_dir_override: Option<String>,
+
+    /// Override for installed/staging mods root directory; may use the same
+    /// variables. With `{game}`, the expanded path is the game's staging dir.
+    pub staging_dir_override: Option<String>,
+
+    /// Additional user-defined game installations (GOG/manual paths).
+    pub custom_games: Vec<CustomGameConfig>,
+
+    /// User-defined external tools (`[[custom_tools]]`)
+    pub custom_tools: Vec<CustomToolConfig>,
+
+    /// Whether guided initialization has completed at least once.
+    pub first_run_completed: bool,
+
+    /// RFC3339 timestamp for last successful init completion.
+    pub first_run_completed_at: Option<String>,
+
+    /// Paths configuration
+    #[serde(skip)]
+    pub paths: Paths,
+}
+
+/// User-specified game install entry (for GOG/manual support).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomGameConfig {
+    /// Game ID (e.g., "skyrimse", "fallout4")
+    pub game_id: String,
+    /// Install path containing the game executable and Data folder.
+    pub install_path: String,
+    /// Platform/source label: steam, gog, manual
+    pub platform: String,
+    /// Optional explicit Proton prefix path.
+    pub proton_prefix: Option<String>,
+}
+
+impl Default for CustomGameConfig {
+    fn default() -> Self {
+        Self {
+            game_id: String::new(),
+            install_path: String::new(),
+            platform: "manual".to_string(),
+            proton_prefix: None,
+        }
+    }
+}
+
+/// User-defined external tool, launched like the built-in ones
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomToolConfig {
+    /// Name used by `tool run` and shown in Settings
+    pub name: String,
+    /// Executable path
+    pub path: String,
+    /// Arguments; may use the [`TOOL_VARIABLES`], e.g. `-D:{data_dir}`
+    pub args: Vec<String>,
+    /// Launch through Proton (default) or run natively
+    pub runtime_mode: ToolRuntimeMode,
+    /// Working directory, defaulting to the executable's folder; may use the
+    /// same variables
+    pub working_dir: Option<String>,
+    /// Extra environment variables
+    pub env: BTreeMap<String, String>,
+}
+
+/// Deployment configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeploymentConfig {
+    /// Deployment method
+    pub method: DeploymentMethod,
+
+    /// Backup original files
+    pub backup_originals: bool,
+
+    /// Purge deployment on exit
+    pub purge_on_exit: bool,
+
+    /// Glob patterns of mod files never deployed (e.g. `*.txt`, `docs/**`)
+    pub exclude: Vec<String>,
+
+    /// Turn on archive invalidation in the game's INI files when loose files
+    /// are deployed (Fallout 3, New Vegas and 4)
+    pub archive_invalidation: bool,
+}
+
+impl Default for DeploymentConfig {
+    fn default() -> Self {
+        Self {
+            method: DeploymentMethod::Symlink,
+            backup_originals: true,
+            purge_on_exit: false,
+            exclude: Vec::new(),
+            archive_invalidation: true,
+        }
+    }
+}
+
+/// Archive retention configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchiveRetentionConfig {
+    /// Keep a copy of every installed archive so mods can be rolled back
+    pub enabled: bool,
+
+    /// Maximum number of archives kept per mod (oldest are pruned first)
+    pub max_versions: usize,
+}
+
+impl Default for ArchiveRetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_versions: 3,
+        }
+    }
+}
+
+/// Trash for removed mods
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrashConfig {
+    /// Days a removed mod stays restorable before it is deleted (0 deletes right away)
+    pub retention_days: u64,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self { retention_days: 7 }
+    }
+}
+
+/// Archive installation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InstallConfig {
+    /// Archives extracted at once during bulk installs (0 = one per CPU core, up to 4)
+    pub extraction_workers: usize,
+
+    /// Display name for new installs, e.g. "{nexus_name} [{version}]" or "{archive_stem}"
+    pub name_template: String,
+
+    /// What happens when the name is already taken
+    pub name_conflict: NameConflictPolicy,
+
+    /// Offer to install archives as they appear in the downloads folder (TUI)
+    pub watch_downloads: bool,
+}
+
+impl Default for InstallConfig {
+    fn default() -> Self {
+        Self {
+            extraction_workers: 0,
+            name_template: "{name}".to_string(),
+            name_conflict: NameConflictPolicy::Error,
+            watch_downloads: false,
+        }
+    }
+}
+
+/// Handling of install names that are already taken
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NameConflictPolicy {
+    /// Refuse the install
+    #[default]
+    Error,
+    /// Install as "Name (2)", "Name (3)", ...
+    Rename,
+}
+
+impl InstallConfig {
+    /// Number of extraction workers to run, resolving 0 to the CPU count.
+    /// The automatic value is capped because extraction is mostly disk bound.
+    pub fn worker_count(&self) -> usize {
+        match self.extraction_workers {
+            0 => std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+                .min(4),
+            n => n,
+        }
+    }
+}
+
+/// Random mod discovery preferences
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscoveryConfig {
+    /// Nexus category names to draw from (e.g. "Armour", "Quests"); empty means any
+    pub categories: Vec<String>,
+
+    /// Only suggest mods with at least this many endorsements
+    pub min_endorsements: i64,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            categories: Vec::new(),
+            min_endorsements: 100,
+        }
+    }
+}
+
+/// A saved Mods screen filter. Every predicate that is set must match;
+/// unset ones match any mod.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModFilterConfig {
+    /// Name shown in the filter dropdown
+    pub name: String,
+
+    /// Category name, case-insensitive; "Uncategorized" matches mods without one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+
+    /// Text the mod name must contain, case-insensitive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+
+    /// Only enabled (`true`) or disabled (`false`) mods
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Only mods with (`true`) or without (`false`) a known Nexus update
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_update: Option<bool>,
+
+    /// Only mods linked (`true`) or not linked (`false`) to a Nexus mod id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nexus: Option<bool>,
+}
+
+/// Log file rotation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Rotate the log once it grows past this many megabytes (0 = never)
+    pub max_size_mb: u64,
+
+    /// Rotated logs kept next to the current one
+    pub max_files: usize,
+
+    /// Delete rotated logs older than this many days (0 = keep them)
+    pub max_age_days: u64,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            max_size_mb: 10,
+            max_files: 5,
+            max_age_days: 14,
+        }
+    }
+}
+
+impl LoggingConfig {
+    /// Size limit in bytes, if any
+    pub fn max_bytes(&self) -> Option<u64> {
+        (self.max_size_mb > 0).then(|| self.max_size_mb * 1024 * 1024)
+    }
+}
+
+/// Deployment method
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentMethod {
+    #[default]
+    Symlink,
+    Hardlink,
+    Copy,
+}
+
+impl DeploymentMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeploymentMethod::Symlink => "symlink",
+            DeploymentMethod::Hardlink => "hardlink",
+            DeploymentMethod::Copy => "copy",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DeploymentMethod::Symlink => "Symlink",
+            DeploymentMethod::Hardlink => "Hardlink",
+            DeploymentMethod::Copy => "Full Copy",
+        }
+    }
+
+    pub fn from_cli(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "symlink" => Ok(DeploymentMethod::Symlink),
+            "hardlink" => Ok(DeploymentMethod::Hardlink),
+            "copy" | "fullcopy" | "full-copy" | "full_copy" => Ok(DeploymentMethod::Copy),
+            other => bail!(
+                "Invalid deployment method '{}'. Valid values: symlink, hardlink, copy",
+                other
+            ),
+        }
+    }
+}
+
+/// TUI configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuiConfig {
+    /// Show help panel by default
+    pub show_help: bool,
+
+    /// Confirm before destructive actions
+    pub confirm_destructive: bool,
+
+    /// Theme (future: light/dark/custom)
+    pub theme: String,
+
+    /// Default directory for bulk mod installation
+    pub default_mod_directory: Option<String>,
+
+    /// Reduce heavy color usage in the TUI for accessibility/low-color terminals.
+    pub minimal_color_mode: bool,
+
+    /// Hours since the last play session before the TUI shows what changed (0 disables)
+    pub changelog_gap_hours: u64,
+
+    /// FOMOD image previews: auto, kitty, iterm2, sixel or ascii
+    pub image_previews: String,
+
+    /// Show the current activity and progress in the terminal title
+    pub terminal_title: bool,
+
+    /// Taskbar progress via OSC 9;4: auto (detect the terminal), on or off
+    pub taskbar_progress: String,
+}
+
+/// Supported external tools that can be launched via Proton.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExternalTool {
+    XEdit,
+    SSEEdit,
+    FNIS,
+    Nemesis,
+    Synthesis,
+    BodySlide,
+    OutfitStudio,
+    /// Garden of Eden Creation Kit, the Fallout 3/New Vegas editor
+    Geck,
+}
+
+impl ExternalTool {
+    pub fn as_id(&self) -> &'static str {
+        match self {
+            ExternalTool::XEdit => "xedit",
+            ExternalTool::SSEEdit => "ssedit",
+            ExternalTool::FNIS => "fnis",
+            ExternalTool::Nemesis => "nemesis",
+            ExternalTool::Synthesis => "symphony",
+            ExternalTool::BodySlide => "bodyslide",
+            ExternalTool::OutfitStudio => "outfitstudio",
+            ExternalTool::Geck => "geck",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ExternalTool::XEdit => "xEdit",
+            ExternalTool::SSEEdit => "SSEEdit",
+            ExternalTool::FNIS => "FNIS",
+            ExternalTool::Nemesis => "Nemesis",
+            ExternalTool::Synthesis => "Synthesis",
+            ExternalTool::BodySlide => "BodySlide",
+            ExternalTool::OutfitStudio => "Outfit Studio",
+            ExternalTool::Geck => "GECK",
+        }
+    }
+
+    /// Executable shipped in the game folder, used when no path is set
+    pub fn bundled_executable(&self, game_type: GameType) -> Option<&'static str> {
+        match (self, game_type) {
+            (ExternalTool::Geck, GameType::Fallout3 | GameType::FalloutNV) => Some("GECK.exe"),
+            _ => None,
+        }
+    }
+
+    /// Whether the tool works with `game_type`; only the GECK is game-specific
+    pub fn supports(&self, game_type: GameType) -> bool {
+        match self {
+            ExternalTool::Geck => matches!(game_type, GameType::Fallout3 | GameType::FalloutNV),
+            _ => true,
+        }
+    }
+
+    pub fn all() -> &'static [ExternalTool] {
+        &[
+            ExternalTool::XEdit,
+            ExternalTool::SSEEdit,
+            ExternalTool::FNIS,
+            ExternalTool::Nemesis,
+            ExternalTool::Synthesis,
+            ExternalTool::BodySlide,
+            ExternalTool::OutfitStudio,
+            ExternalTool::Geck,
+        ]
+    }
+
+    pub fn from_cli(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "xedit" | "x" => Ok(ExternalTool::XEdit),
+            "ssedit" | "sseedit" | "sse" => Ok(ExternalTool::SSEEdit),
+            "fnis" => Ok(ExternalTool::FNIS),
+            "nemesis" => Ok(ExternalTool::Nemesis),
+            "symphony" => Ok(ExternalTool::Synthesis),
+            "bodyslide" | "bs" => Ok(ExternalTool::BodySlide),
+            "outfitstudio" | "outfit-studio" | "os" => Ok(ExternalTool::OutfitStudio),
+            "geck" => Ok(ExternalTool::Geck),
+            other => bail!(
+                "Unknown tool '{}'. Valid tools: xedit, ssedit, fnis, nemesis, symphony, bodyslide, outfitstudio, geck",
+                other
+            ),
+        }
+    }
+}
+
+/// A tool that can be launched: a built-in one or a `[[custom_tools]]` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolRef {
+    Builtin(ExternalTool),
+    /// Name of a custom tool
+    Custom(String),
+}
+
+impl ToolRef {
+    pub fn display_name(&self) -> &str {
+        match self {
+            ToolRef::Builtin(tool) => tool.display_name(),
+            ToolRef::Custom(name) => name,
+        }
+    }
+}
+
+impl From<ExternalTool> for ToolRef {
+    fn from(tool: ExternalTool) -> Self {
+        ToolRef::Builtin(tool)
+    }
+}
+
+/// Runtime mode for launching external tools.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolRuntimeMode {
+    /// Launch through Proton/Wine runtime.
+    #[default]
+    Proton,
+    /// Launch executable directly on host.
+    Native,
+}
+
+impl ToolRuntimeMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolRuntimeMode::Proton => "proton",
+            ToolRuntimeMode::Native => "native",
+        }
+    }
+
+    pub fn from_cli(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "proton" => Ok(ToolRuntimeMode::Proton),
+            "native" => Ok(ToolRuntimeMode::Native),
+            other => bail!("Unknown runtime mode '{}'. Valid: proton, native", other),
+        }
+    }
+}
+
+/// External tool paths and Proton command configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalToolsConfig {
+    /// Proton launcher command or full path (e.g., `proton`, `/path/to/proton`)
+    pub proton_command: String,
+    /// Optional selected Steam-managed Proton runtime ID (e.g., `steam:proton_experimental`).
+    pub proton_runtime: Option<String>,
+    pub xedit_path: Option<String>,
+    pub ssedit_path: Option<String>,
+    pub fnis_path: Option<String>,
+    pub nemesis_path: Option<String>,
+    pub symphony_path: Option<String>,
+    pub bodyslide_path: Option<String>,
+    pub outfitstudio_path: Option<String>,
+    pub geck_path: Option<String>,
+    pub xedit_runtime_mode: Option<ToolRuntimeMode>,
+    pub ssedit_runtime_mode: Option<ToolRuntimeMode>,
+    pub fnis_runtime_mode: Option<ToolRuntimeMode>,
+    pub nemesis_runtime_mode: Option<ToolRuntimeMode>,
+    pub symphony_runtime_mode: Option<ToolRuntimeMode>,
+    pub bodyslide_runtime_mode: Option<ToolRuntimeMode>,
+    pub outfitstudio_runtime_mode: Option<ToolRuntimeMode>,
+    pub geck_runtime_mode: Option<ToolRuntimeMode>,
+    /// Launch defaults by tool ID (`[external_tools.options.xedit]`)
+    pub options: BTreeMap<String, ToolOptions>,
+}
+
+/// Launch defaults for a built-in tool
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolOptions {
+    /// Arguments passed before the profile's and those given at launch,
+    /// e.g. `-quickautoclean`; may use the [`TOOL_VARIABLES`]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Working directory instead of the executable's folder; may use the
+    /// same variables
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    /// Extra environment variables
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+impl ToolOptions {
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty() && self.working_dir.is_none() && self.env.is_empty()
+    }
+
+    /// One line for listings, e.g. `args: -quickautoclean; env: WINEDEBUG=-all`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.args.is_empty() {
+            parts.push(format!("args: {}", join_args(&self.args)));
+        }
+        if let Some(dir) = &self.working_dir {
+            parts.push(format!("working dir: {}", dir));
+        }
+        if !self.env.is_empty() {
+            parts.push(format!("env: {}", env_line(&self.env)));
+        }
+        parts.join("; ")
+    }
+}
+
+/// Environment variables as `KEY=VALUE` pairs separated by spaces
+pub fn env_line(env: &BTreeMap<String, String>) -> String {
+    join_args(
+        &env.iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Parse `KEY=VALUE` pairs, as written by [`env_line`]
+pub fn parse_env(pairs: &[String]) -> Result<BTreeMap<String, String>> {
+    pairs
+        .iter()
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.to_string()))
+            }
+            _ => bail!(Invalid::new(format!("Expected KEY=VALUE, got '{}'", pair))),
+        })
+        .collect()
+}
+
+impl Default for ExternalToolsConfig {
+    fn default() -> Self {
+        Self {
+            proton_command: "proton".to_string(),
+            proton_runtime: None,
+            xedit_path: None,
+            ssedit_path: None,
+            fnis_path: None,
+            nemesis_path: None,
+            symphony_path: None,
+            bodyslide_path: None,
+            outfitstudio_path: None,
+            geck_path: None,
+            xedit_runtime_mode: None,
+            ssedit_runtime_mode: None,
+            fnis_runtime_mode: None,
+            nemesis_runtime_mode: None,
+            symphony_runtime_mode: None,
+            bodyslide_runtime_mode: None,
+            outfitstudio_runtime_mode: None,
+            geck_runtime_mode: None,
+            options: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            show_help: true,
+            confirm_destructive: true,
+            theme: "default".to_string(),
+            default_mod_directory: None,
+            minimal_color_mode: false,
+            changelog_gap_hours: 24,
+            image_previews: "auto".to_string(),
+            terminal_title: true,
+            taskbar_progress: "auto".to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// Nexus API host to talk to, honouring `nexus_base_url`
+    pub fn nexus_base_url(&self) -> &str {
+        self.nexus_base_url
+            .as_deref()
+            .map(|url| url.trim_end_matches('/'))
+            .filter(|url| !url.is_empty())
+            .unwrap_or(crate::nexus::NEXUS_API_BASE)
+    }
+
+    pub fn external_tool_path(&self, tool: ExternalTool) -> Option<&str> {
+        match tool {
+            ExternalTool::XEdit => self.external_tools.xedit_path.as_deref(),
+            ExternalTool::SSEEdit => self.external_tools.ssedit_path.as_deref(),
+            ExternalTool::FNIS => self.external_tools.fnis_path.as_deref(),
+            ExternalTool::Nemesis => self.external_tools.nemesis_path.as_deref(),
+            ExternalTool::Synthesis => self.external_tools.symphony_path.as_deref(),
+            ExternalTool::BodySlide => self.external_tools.bodyslide_path.as_deref(),
+            ExternalTool::OutfitStudio => self.external_tools.outfitstudio_path.as_deref(),
+            ExternalTool::Geck => self.external_tools.geck_path.as_deref(),
+        }
+    }
+
+    pub fn set_external_tool_path(&mut self, tool: ExternalTool, path: Option<String>) {
+        match tool {
+            ExternalTool::XEdit => self.external_tools.xedit_path = path,
+            ExternalTool::SSEEdit => self.external_tools.ssedit_path = path,
+            ExternalTool::FNIS => self.external_tools.fnis_path = path,
+            ExternalTool::Nemesis => self.external_tools.nemesis_path = path,
+            ExternalTool::Synthesis => self.external_tools.symphony_path = path,
+            ExternalTool::BodySlide => self.external_tools.bodyslide_path = path,
+            ExternalTool::OutfitStudio => self.external_tools.outfitstudio_path = path,
+            ExternalTool::Geck => self.external_tools.geck_path = path,
+        }
+    }
+
+    pub fn external_tool_runtime_mode(&self, tool: ExternalTool) -> ToolRuntimeMode {
+        match tool {
+            ExternalTool::XEdit => self.external_tools.xedit_runtime_mode,
+            ExternalTool::SSEEdit => self.external_tools.ssedit_runtime_mode,
+            ExternalTool::FNIS => self.external_tools.fnis_runtime_mode,
+            ExternalTool::Nemesis => self.external_tools.nemesis_runtime_mode,
+            ExternalTool::Synthesis => self.external_tools.symphony_runtime_mode,
+            ExternalTool::BodySlide => self.external_tools.bodyslide_runtime_mode,
+            ExternalTool::OutfitStudio => self.external_tools.outfitstudio_runtime_mode,
+            ExternalTool::Geck => self.external_tools.geck_runtime_mode,
+        }
+        .unwrap_or(ToolRuntimeMode::Proton)
+    }
+
+    pub fn set_external_tool_runtime_mode(
+        &mut self,
+        tool: ExternalTool,
+        mode: Option<ToolRuntimeMode>,
+    ) {
+        match tool {
+            ExternalTool::XEdit => self.external_tools.xedit_runtime_mode = mode,
+            ExternalTool::SSEEdit => self.external_tools.ssedit_runtime_mode = mode,
+            ExternalTool::FNIS => self.external_tools.fnis_runtime_mode = mode,
+            ExternalTool::Nemesis => self.external_tools.nemesis_runtime_mode = mode,
+            ExternalTool::Synthesis => self.external_tools.symphony_runtime_mode = mode,
+            ExternalTool::BodySlide => self.external_tools.bodyslide_runtime_mode = mode,
+            ExternalTool::OutfitStudio => self.external_tools.outfitstudio_runtime_mode = mode,
+            ExternalTool::Geck => self.external_tools.geck_runtime_mode = mode,
+        }
+    }
+    /// Launch defaults set for `tool`
+    pub fn external_tool_options(&self, tool: ExternalTool) -> Option<&ToolOptions> {
+        self.external_tools.options.get(tool.as_id())
+    }
+
+    /// Replace the launch defaults for `tool`; empty options remove them
+    pub fn set_external_tool_options(&mut self, tool: ExternalTool, options: ToolOptions) {
+        if options.is_empty() {
+            self.external_tools.options.remove(tool.as_id());
+        } else {
+            self.external_tools
+                .options
+                .insert(tool.as_id().to_string(), options);
+        }
+    }
+
+    /// The custom tool called `name`, ignoring case
+    pub fn custom_tool(&self, name: &str) -> Option<&CustomToolConfig> {
+        let name = name.trim();
+        self.custom_tools
+            .iter()
+            .find(|t| t.name.trim().eq_ignore_ascii_case(name))
+    }
+
+    /// Resolve a tool name given on the command line: built-in tools
+    /// first, then custom tools
+    pub fn resolve_tool(&self, name: &str) -> Result<ToolRef> {
+        if let Ok(tool) = ExternalTool::from_cli(name) {
+            return Ok(ToolRef::Builtin(tool));
+        }
+        if let Some(custom) = self.custom_tool(name) {
+            return Ok(ToolRef::Custom(custom.name.clone()));
+        }
+        let mut valid: Vec<&str> = ExternalTool::all().iter().map(|t| t.as_id()).collect();
+        valid.extend(self.custom_tools.iter().map(|t| t.name.as_str()));
+        bail!(Invalid::new(format!(
+            "Unknown tool '{}'. Valid tools: {}",
+            name,
+            valid.join(", ")
+        )))
+    }
+
+    /// Resolve configured downloads directory (override or default XDG path)
+    pub fn downloads_dir(&self) -> Result<PathBuf> {
+        match self.downloads_dir_override.as_deref() {
+            Some(template) => self.expand_path(template, self.active_game.as_deref()),
+            None => Ok(self.paths.downloads_dir()),
+        }
+    }
+
+    /// Resolve configured staging root directory (override or default XDG path)
+    pub fn staging_dir(&self) -> Result<PathBuf> {
+        match self.staging_dir_override.as_deref() {
+            Some(template) => {
+                check_staging_template(template)?;
+                self.expand_path(template, self.active_game.as_deref())
+            }
+            None => Ok(self.paths.mods_dir()),
+        }
+    }
+
+    /// Resolve staging directory for a specific game
+    pub fn game_staging_dir(&self, game_id: &str) -> Result<PathBuf> {
+        match self.staging_dir_override.as_deref() {
+            Some(template) if template.contains("{game}") => {
+                check_staging_template(template)?;
+                self.expand_path(template, Some(game_id))
+            }
+            _ => Ok(self.staging_dir()?.join(game_id)),
+        }
+    }
+
+    /// Expand a directory override for `game` and the active profile
+    fn expand_path(&self, template: &str, game: Option<&str>) -> Result<PathBuf> {
+        let home = std::env::var("HOME").ok();
+        expand_path_template(
+            template,
+            &[
+                ("game", game),
+                ("profile", self.active_profile.as_deref()),
+                ("home", home.as_deref()),
+            ],
+        )
+    }
+
+    /// Resolve retained archive directory for a specific game
+    pub fn game_archives_dir(&self, game_id: &str) -> PathBuf {
+        self.paths.archives_dir().join(game_id)
+    }
+
+    /// Directories scanned for extensions (XDG data dir first, then ~/.modsanity)
+    pub fn extension_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![self.paths.extensions_dir()];
+        dirs.extend(self.paths.home_extensions_dir());
+        dirs
+    }
+
+    /// Ensure required directories exist, including overrides. Overrides
+    /// using a game or profile that isn't active yet are created once they
+    /// resolve.
+    pub fn ensure_dirs(&self) -> Result<()> {
+        self.paths
+            .ensure_dirs()
+            .context("Failed to create default application directories")?;
+        if let Ok(dir) = self.downloads_dir() {
+            std::fs::create_dir_all(dir).context("Failed to create downloads directory")?;
+        }
+        if let Ok(dir) = self.staging_dir() {
+            std::fs::create_dir_all(dir).context("Failed to create staging directory")?;
+        }
+        Ok(())
+    }
+
+    /// Load configuration from disk or create default
+    pub async fn load() -> Result<Self> {
+        let paths = Paths::new();
+        let config_path = paths.config_file();
+
+        let mut config = if config_path.exists() {
+            let content = fs::read_to_string(&config_path)
+                .await
+                .context("Failed to read config file")?;
+            toml::from_str(&content).context("Failed to parse config file")?
+        } else {
+            // Create default config
+            let config = Config::default();
+            config.save().await?;
+            config
+        };
+
+        config.paths = paths;
+        Ok(config)
+    }
+
+    /// Save configuration to disk
+    pub async fn save(&self) -> Result<()> {
+        let config_path = self.paths.config_file();
+
+        // Ensure config directory exists
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .context("Failed to create config directory")?;
+        }
+
+        let content = toml::to_string_pretty(self).context("Failed to serialize config")?;
+        fs::write(&config_path, content)
+            .await
+            .context("Failed to write config file")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_templates() {
+        let vars = [
+            ("game", Some("skyrimse")),
+            ("profile", None),
+            ("home", Some("/home/me")),
+        ];
+        assert_eq!(
+            expand_path_template("{home}/mods/{game}/staging", &vars).unwrap(),
+            PathBuf::from("/home/me/mods/skyrimse/staging")
+        );
+        let err = expand_path_template("/mnt/{game}-{profile}/downloads", &vars).unwrap_err();
+        assert!(err.downcast_ref::<Invalid>().is_some());
+        assert_eq!(unknown_path_variables("/mnt/{game}/{gmae}"), vec!["gmae"]);
+
+        let mut config = Config {
+            staging_dir_override: Some("/mnt/mods/{game}/staging".to_string()),
+            downloads_dir_override: Some("/mnt/downloads/{game}".to_string()),
+            active_game: Some("fallout4".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.game_staging_dir("skyrimse").unwrap(),
+            PathBuf::from("/mnt/mods/skyrimse/staging")
+        );
+        assert_eq!(
+            config.downloads_dir().unwrap(),
+            PathBuf::from("/mnt/downloads/fallout4")
+        );
+        config.staging_dir_override = Some("/mnt/mods".to_string());
+        assert_eq!(
+            config.game_staging_dir("skyrimse").unwrap(),
+            PathBuf::from("/mnt/mods/skyrimse")
+        );
+
+        // Unset variables don't fall back to a parent folder
+        config.active_game = None;
+        config.staging_dir_override = Some("/mnt/mods/{game}/staging".to_string());
+        assert!(config.downloads_dir().is_err());
+        assert!(config.staging_dir().is_err());
+        assert!(config.game_staging_dir("skyrimse").is_ok());
+
+        // Mods aren't per profile, so neither is staging
+        config.active_profile = Some("Survival".to_string());
+        config.staging_dir_override = Some("/mnt/mods/{game}/{profile}".to_string());
+        assert!(config.game_staging_dir("skyrimse").is_err());
+        config.downloads_dir_override = Some("/mnt/downloads/{profile}".to_string());
+        assert_eq!(
+            config.downloads_dir().unwrap(),
+            PathBuf::from("/mnt/downloads/Survival")
+        );
+    }
+
+    #[test]
+    fn test_resolve_custom_tools() {
+        let config = Config {
+            custom_tools: vec![CustomToolConfig {
+                name: "Wrye Bash".to_string(),
+                path: "/tools/Wrye Bash.exe".to_string(),
+                ..Default::default()
+            }],
+            ..Config::default()
+        };
+        assert_eq!(
+            config.resolve_tool("sse").unwrap(),
+            ToolRef::Builtin(ExternalTool::SSEEdit)
+        );
+        assert_eq!(
+            config.resolve_tool("wrye bash").unwrap(),
+            ToolRef::Custom("Wrye Bash".to_string())
+        );
+        let err = config.resolve_tool("loot").unwrap_err().to_string();
+        assert!(err.ends_with("geck, Wrye Bash"), "{}", err);
+
+        let vars = [("data_dir", Some("/games/Skyrim/Data")), ("profile", None)];
+        assert_eq!(
+            expand_tool_template("-D:{data_dir}", &vars),
+            "-D:/games/Skyrim/Data"
+        );
+        assert_eq!(expand_tool_template("{profile}", &vars), "{profile}");
+        assert_eq!(unknown_tool_variables("{data_dir}/{gmae}"), vec!["gmae"]);
+    }
+
+    #[test]
+    fn test_split_and_join_args() {
+        let args = split_args("-quickautoclean  -D:\"/games/Skyrim Special Edition\" \"\"");
+        assert_eq!(
+            args,
+            vec!["-quickautoclean", "-D:/games/Skyrim Special Edition", ""]
+        );
+        assert_eq!(split_args(&join_args(&args)), args);
+
+        let env = parse_env(&split_args("WINEDEBUG=-all DXVK_HUD=")).unwrap();
+        assert_eq!(env["WINEDEBUG"], "-all");
+        assert_eq!(env["DXVK_HUD"], "");
+        assert_eq!(env_line(&env), "DXVK_HUD= WINEDEBUG=-all");
+        assert!(parse_env(&["WINEDEBUG".to_string()]).is_err());
+    }
+}
@@ -1,13 +1,14 @@
 //! XDG-compliant path management
 
 use directories::ProjectDirs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Manages all application paths using XDG base directory specification
 #[derive(Debug, Clone)]
 pub struct Paths {
-    /// Base directories from XDG
-    dirs: ProjectDirs,
+    config_dir: PathBuf,
+    data_dir: PathBuf,
+    cache_dir: PathBuf,
 }
 
 impl Default for Paths {
@@ -21,14 +22,28 @@ impl Paths {
     pub fn new() -> Self {
         let dirs = ProjectDirs::from("", "", "modsanity")
             .expect("Failed to determine project directories");
-        Self { dirs }
+        Self {
+            config_dir: dirs.config_dir().to_path_buf(),
+            data_dir: dirs.data_dir().to_path_buf(),
+            cache_dir: dirs.cache_dir().to_path_buf(),
+        }
+    }
+
+    /// Paths rooted under a single directory (`<root>/config`, `<root>/data`,
+    /// `<root>/cache`), used for sandboxed runs such as the test harness
+    pub fn rooted(root: &Path) -> Self {
+        Self {
+            config_dir: root.join("config"),
+            data_dir: root.join("data"),
+            cache_dir: root.join("cache"),
+        }
     }
 
     // ========== Config Paths ==========
 
     /// Config directory: ~/.config/modsanity/
     pub fn config_dir(&self) -> PathBuf {
-        self.dirs.config_dir().to_path_buf()
+        self.config_dir.clone()
     }
 
     /// Main config file: ~/.config/modsanity/config.toml
@@ -36,11 +51,16 @@ impl Paths {
         self.config_dir().join("config.toml")
     }
 
+    /// User game definitions: ~/.config/modsanity/games.d/
+    pub fn games_dir(&self) -> PathBuf {
+        self.config_dir().join("games.d")
+    }
+
     // ========== Data Paths ==========
 
     /// Data directory: ~/.local/share/modsanity/
     pub fn data_dir(&self) -> PathBuf {
-        self.dirs.data_dir().to_path_buf()
+        self.data_dir.clone()
     }
 
     /// Database file: ~/.local/share/modsanity/modsanity.db
@@ -48,6 +68,16 @@ impl Paths {
         self.data_dir().join("modsanity.db")
     }
 
+    /// Instance lock: ~/.local/share/modsanity/modsanity.lock
+    pub fn instance_lock_file(&self) -> PathBuf {
+        self.data_dir().join("modsanity.lock")
+    }
+
+    /// Daemon control socket: ~/.local/share/modsanity/modsanity.sock
+    pub fn daemon_socket(&self) -> PathBuf {
+        self.data_dir().join("modsanity.sock")
+    }
+
     /// Mods staging directory: ~/.local/share/modsanity/mods/
     pub fn mods_dir(&self) -> PathBuf {
         self.data_dir().join("mods")
@@ -78,16 +108,60 @@ impl Paths {
         self.profiles_dir().join(game_id)
     }
 
+    /// A profile's own copies of the game's INI files
+    pub fn profile_ini_dir(&self, game_id: &str, profile: &str) -> PathBuf {
+        self.game_profiles_dir(game_id).join("ini").join(profile)
+    }
+
+    /// A profile's save folder, for games whose save folder is symlinked
+    /// to it
+    pub fn profile_saves_dir(&self, game_id: &str, profile: &str) -> PathBuf {
+        self.game_profiles_dir(game_id).join("saves").join(profile)
+    }
+
+    /// INI values written for the active profile and the values they
+    /// replaced: ~/.local/share/modsanity/ini-changes/<game_id>.json
+    pub fn ini_changes_file(&self, game_id: &str) -> PathBuf {
+        self.data_dir()
+            .join("ini-changes")
+            .join(format!("{}.json", game_id))
+    }
+
     /// Backups directory: ~/.local/share/modsanity/backups/
     pub fn backups_dir(&self) -> PathBuf {
         self.data_dir().join("backups")
     }
 
+    /// INI backups for a game: ~/.local/share/modsanity/backups/ini/<game_id>/
+    pub fn ini_backups_dir(&self, game_id: &str) -> PathBuf {
+        self.backups_dir().join("ini").join(game_id)
+    }
+
+    /// Save game backups for a game: `~/.local/share/modsanity/backups/saves/<game>/`
+    pub fn saves_backups_dir(&self, game_id: &str) -> PathBuf {
+        self.backups_dir().join("saves").join(game_id)
+    }
+
+    /// Retained mod archives for rollback: ~/.local/share/modsanity/archives/
+    pub fn archives_dir(&self) -> PathBuf {
+        self.data_dir().join("archives")
+    }
+
+    /// Extensions directory: ~/.local/share/modsanity/extensions/
+    pub fn extensions_dir(&self) -> PathBuf {
+        self.data_dir().join("extensions")
+    }
+
+    /// Home-directory extensions folder: ~/.modsanity/extensions/
+    pub fn home_extensions_dir(&self) -> Option<PathBuf> {
+        directories::BaseDirs::new().map(|d| d.home_dir().join(".modsanity").join("extensions"))
+    }
+
     // ========== Cache Paths ==========
 
     /// Cache directory: ~/.cache/modsanity/
     pub fn cache_dir(&self) -> PathBuf {
-        self.dirs.cache_dir().to_path_buf()
+        self.cache_dir.clone()
     }
 
     /// NexusMods API cache: ~/.cache/modsanity/nexus/
@@ -115,6 +189,7 @@ impl Paths {
         std::fs::create_dir_all(self.downloads_dir())?;
         std::fs::create_dir_all(self.profiles_dir())?;
         std::fs::create_dir_all(self.backups_dir())?;
+        std::fs::create_dir_all(self.archives_dir())?;
         std::fs::create_dir_all(self.cache_dir())?;
         std::fs::create_dir_all(self.nexus_cache_dir())?;
         Ok(())
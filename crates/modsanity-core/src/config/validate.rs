@@ -0,0 +1,522 @@
+//! config.toml validation
+//!
+//! Loading the config ignores keys it doesn't know, so a typo such as
+//! `staging_dir_overide` silently falls back to the default. Validation
+//! compares the file against the keys `Config` serializes, then checks the
+//! values that deserialize fine but can't work (missing tool paths, unknown
+//! game ids, ...).
+
+use super::{Config, CustomGameConfig, CustomToolConfig, ExternalTool, ModFilterConfig};
+use crate::games::{GameDefinition, GameType};
+use serde::Serialize;
+use std::path::Path;
+
+/// How bad a config problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The config can't be loaded, or a setting can't work
+    Error,
+    /// A setting is ignored or probably wrong
+    Warning,
+}
+
+/// One problem found in config.toml
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigIssue {
+    pub severity: Severity,
+    /// Dotted key path, e.g. `deployment.method` or `custom_games[0].install_path`
+    pub key: String,
+    pub message: String,
+}
+
+/// Result of validating config.toml
+#[derive(Debug, Default, Serialize)]
+pub struct ConfigReport {
+    pub issues: Vec<ConfigIssue>,
+    /// The parsed config, when the file could be loaded
+    #[serde(skip)]
+    pub config: Option<Config>,
+}
+
+impl ConfigReport {
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+
+    fn push(&mut self, severity: Severity, key: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ConfigIssue {
+            severity,
+            key: key.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Image preview modes accepted by `tui.image_previews`
+const IMAGE_PREVIEW_MODES: &[&str] = &["auto", "kitty", "iterm2", "sixel", "ascii"];
+
+/// Values accepted by `tui.taskbar_progress`
+const TASKBAR_PROGRESS_MODES: &[&str] = &["auto", "on", "off"];
+
+/// Validate config.toml `content`. Game ids may also name `definitions`.
+pub fn validate(content: &str, definitions: &[GameDefinition]) -> ConfigReport {
+    let mut report = ConfigReport::default();
+    let value: toml::Value = match toml::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            report.push(
+                Severity::Error,
+                "",
+                format!("Invalid TOML: {}", e.message()),
+            );
+            return report;
+        }
+    };
+
+    let schema = serde_json::to_value(schema_sample()).unwrap_or_default();
+    check_keys(&value, &schema, "", &mut report);
+
+    let config: Config = match toml::from_str(content) {
+        Ok(config) => config,
+        Err(e) => {
+            // Wrong types and unknown enum values (e.g. `method = "symlinks"`)
+            report.push(Severity::Error, "", e.message().to_string());
+            return report;
+        }
+    };
+    check_values(&config, definitions, &mut report);
+    report.config = Some(config);
+    report
+}
+
+/// A config with every optional section filled in, so that serializing it
+/// lists every key the file may contain
+fn schema_sample() -> Config {
+    let mut sample = Config {
+        nexus_base_url: Some(String::new()),
+        ..Config::default()
+    };
+    sample.custom_games.push(CustomGameConfig::default());
+    sample.custom_tools.push(CustomToolConfig {
+        working_dir: Some(String::new()),
+        ..CustomToolConfig::default()
+    });
+    sample.mod_filters.push(ModFilterConfig {
+        category: Some(String::new()),
+        search: Some(String::new()),
+        enabled: Some(true),
+        has_update: Some(true),
+        nexus: Some(true),
+        ..ModFilterConfig::default()
+    });
+    sample
+}
+
+/// Report keys in `value` that `schema` doesn't have
+fn check_keys(
+    value: &toml::Value,
+    schema: &serde_json::Value,
+    path: &str,
+    report: &mut ConfigReport,
+) {
+    match (value, schema) {
+        (toml::Value::Table(table), serde_json::Value::Object(known)) => {
+            // Free-form maps such as a custom tool's `env` serialize empty
+            if known.is_empty() {
+                return;
+            }
+            for (key, child) in table {
+                let child_path = join_key(path, key);
+                match known.get(key) {
+                    Some(child_schema) => check_keys(child, child_schema, &child_path, report),
+                    None => {
+                        let message = match closest_key(key, known.keys()) {
+                            Some(suggestion) => {
+                                format!("Unknown key (did you mean '{}'?)", suggestion)
+                            }
+                            None => "Unknown key, ignored".to_string(),
+                        };
+                        report.push(Severity::Warning, child_path, message);
+                    }
+                }
+            }
+        }
+        (toml::Value::Array(items), serde_json::Value::Array(known)) => {
+            if let Some(item_schema) = known.first() {
+                for (i, item) in items.iter().enumerate() {
+                    check_keys(item, item_schema, &format!("{}[{}]", path, i), report);
+                }
+            }
+        }
+        // Scalars, and mismatched types that deserializing reports
+        _ => {}
+    }
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// The known key closest to a typo, if any is close enough
+fn closest_key<'a>(key: &str, known: impl Iterator<Item = &'a String>) -> Option<&'a str> {
+    known
+        .map(|k| (edit_distance(key, k), k))
+        .filter(|(d, k)| *d <= 2.max(k.len() / 4))
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k.as_str())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(cur).min(row[j])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Checks on values that load but can't work
+fn check_values(config: &Config, definitions: &[GameDefinition], report: &mut ConfigReport) {
+    let known_game = |id: &str| {
+        GameType::from_id(id).is_some() || definitions.iter().any(|d| d.id.eq_ignore_ascii_case(id))
+    };
+
+    if let Some(game) = config.active_game.as_deref() {
+        if !known_game(game) {
+            report.push(
+                Severity::Warning,
+                "active_game",
+                format!("Unknown game '{}'", game),
+            );
+        }
+    }
+
+    for (key, value) in [
+        ("downloads_dir_override", &config.downloads_dir_override),
+        ("staging_dir_override", &config.staging_dir_override),
+    ] {
+        let Some(value) = value.as_deref() else {
+            continue;
+        };
+        let unknown = super::unknown_path_variables(value);
+        let expanded = super::expand_path_template(
+            value,
+            &[
+                ("game", Some("game")),
+                ("profile", Some("profile")),
+                ("home", Some("/")),
+            ],
+        )
+        .unwrap_or_default();
+        let staging_error = (key == "staging_dir_override")
+            .then(|| super::check_staging_template(value).err())
+            .flatten();
+        if value.trim().is_empty() {
+            report.push(Severity::Error, key, "Path is empty");
+        } else if !unknown.is_empty() {
+            report.push(
+                Severity::Error,
+                key,
+                format!(
+                    "Unknown variable {{{}}}. Valid variables: {}",
+                    unknown.join("}, {"),
+                    super::PATH_VARIABLES
+                        .iter()
+                        .map(|v| format!("{{{}}}", v))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        } else if let Some(e) = staging_error {
+            report.push(Severity::Error, key, e.to_string());
+        } else if !expanded.is_absolute() {
+            report.push(
+                Severity::Warning,
+                key,
+                format!("'{}' is relative to the current directory", value),
+            );
+        } else if !value.contains('{') && !Path::new(value).exists() {
+            report.push(
+                Severity::Warning,
+                key,
+                format!("{} doesn't exist yet and will be created", value),
+            );
+        }
+    }
+
+    for (key, value, allowed) in [
+        (
+            "tui.image_previews",
+            &config.tui.image_previews,
+            IMAGE_PREVIEW_MODES,
+        ),
+        (
+            "tui.taskbar_progress",
+            &config.tui.taskbar_progress,
+            TASKBAR_PROGRESS_MODES,
+        ),
+    ] {
+        if !allowed.iter().any(|a| a.eq_ignore_ascii_case(value)) {
+            report.push(
+                Severity::Error,
+                key,
+                format!(
+                    "Invalid value '{}'. Valid values: {}",
+                    value,
+                    allowed.join(", ")
+                ),
+            );
+        }
+    }
+
+    for tool in ExternalTool::all() {
+        let Some(path) = config.external_tool_path(*tool) else {
+            continue;
+        };
+        if !Path::new(path).exists() {
+            report.push(
+                Severity::Error,
+                format!("external_tools.{}_path", tool.as_id()),
+                format!("{} not found at {}", tool.display_name(), path),
+            );
+        }
+    }
+    let proton = config.external_tools.proton_command.trim();
+    if proton.is_empty() {
+        report.push(
+            Severity::Error,
+            "external_tools.proton_command",
+            "Command is empty",
+        );
+    } else if which::which(proton).is_err() {
+        report.push(
+            Severity::Warning,
+            "external_tools.proton_command",
+            format!("'{}' not found", proton),
+        );
+    }
+
+    for (i, entry) in config.custom_games.iter().enumerate() {
+        let key = |field: &str| format!("custom_games[{}].{}", i, field);
+        if !known_game(&entry.game_id) {
+            report.push(
+                Severity::Error,
+                key("game_id"),
+                format!("Unknown game '{}'", entry.game_id),
+            );
+        }
+        if !Path::new(entry.install_path.trim()).exists() {
+            report.push(
+                Severity::Error,
+                key("install_path"),
+                format!("{} doesn't exist", entry.install_path),
+            );
+        }
+        if !["steam", "gog", "manual"]
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(&entry.platform))
+        {
+            report.push(
+                Severity::Warning,
+                key("platform"),
+                format!(
+                    "Unknown platform '{}', treated as manual. Valid values: steam, gog, manual",
+                    entry.platform
+                ),
+            );
+        }
+        if let Some(prefix) = entry.proton_prefix.as_deref() {
+            if !Path::new(prefix.trim()).exists() {
+                report.push(
+                    Severity::Warning,
+                    key("proton_prefix"),
+                    format!("{} doesn't exist", prefix),
+                );
+            }
+        }
+    }
+
+    for (id, options) in &config.external_tools.options {
+        let key = |field: &str| format!("external_tools.options.{}.{}", id, field);
+        if !ExternalTool::all().iter().any(|t| t.as_id() == id) {
+            report.push(
+                Severity::Warning,
+                format!("external_tools.options.{}", id),
+                format!(
+                    "Unknown tool '{}', ignored. Valid tools: {}",
+                    id,
+                    ExternalTool::all()
+                        .iter()
+                        .map(|t| t.as_id())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        }
+        let unknown: Vec<String> = options
+            .args
+            .iter()
+            .chain(options.working_dir.as_ref())
+            .flat_map(|t| super::unknown_tool_variables(t))
+            .collect();
+        if !unknown.is_empty() {
+            report.push(
+                Severity::Warning,
+                key("args"),
+                format!(
+                    "Unknown variable {{{}}}, passed as is",
+                    unknown.join("}, {")
+                ),
+            );
+        }
+    }
+
+    let mut tool_names = std::collections::HashSet::new();
+    for (i, tool) in config.custom_tools.iter().enumerate() {
+        let key = |field: &str| format!("custom_tools[{}].{}", i, field);
+        let name = tool.name.trim();
+        if name.is_empty() {
+            report.push(Severity::Error, key("name"), "Tool has no name");
+        } else if ExternalTool::from_cli(name).is_ok() {
+            report.push(
+                Severity::Warning,
+                key("name"),
+                format!(
+                    "'{}' is a built-in tool, which `tool run` picks instead",
+                    name
+                ),
+            );
+        } else if !tool_names.insert(name.to_ascii_lowercase()) {
+            report.push(
+                Severity::Error,
+                key("name"),
+                format!("Another custom tool is called '{}'", name),
+            );
+        }
+        if tool.path.trim().is_empty() {
+            report.push(Severity::Error, key("path"), "Path is empty");
+        } else if !Path::new(tool.path.trim()).exists() {
+            report.push(
+                Severity::Error,
+                key("path"),
+                format!("{} not found", tool.path),
+            );
+        }
+        let unknown: Vec<String> = tool
+            .args
+            .iter()
+            .chain(tool.working_dir.as_ref())
+            .flat_map(|t| super::unknown_tool_variables(t))
+            .collect();
+        if !unknown.is_empty() {
+            report.push(
+                Severity::Warning,
+                key("args"),
+                format!(
+                    "Unknown variable {{{}}}, passed as is. Valid variables: {}",
+                    unknown.join("}, {"),
+                    super::TOOL_VARIABLES
+                        .iter()
+                        .map(|v| format!("{{{}}}", v))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        }
+    }
+
+    for (i, filter) in config.mod_filters.iter().enumerate() {
+        if filter.name.trim().is_empty() {
+            report.push(
+                Severity::Warning,
+                format!("mod_filters[{}].name", i),
+                "Filter has no name and can't be picked",
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_unknown_keys_and_bad_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = format!(
+            r#"
+active_game = "skyrimse"
+staging_dir_overide = "/mnt/mods"
+downloads_dir_override = "{downloads}"
+
+[deployment]
+metod = "copy"
+
+[tui]
+image_previews = "jpeg"
+
+[[custom_games]]
+game_id = "skyrimse"
+install_path = "{downloads}"
+platfrom = "gog"
+
+[[custom_tools]]
+name = "xedit"
+path = "{downloads}"
+runtime = "native"
+
+[custom_tools.env]
+WINEDEBUG = "-all"
+"#,
+            downloads = dir.path().display()
+        );
+
+        let report = validate(&content, &[]);
+        let found: Vec<(Severity, &str)> = report
+            .issues
+            .iter()
+            .map(|i| (i.severity, i.key.as_str()))
+            .collect();
+        assert!(found.contains(&(Severity::Warning, "staging_dir_overide")));
+        assert!(found.contains(&(Severity::Warning, "deployment.metod")));
+        assert!(found.contains(&(Severity::Warning, "custom_games[0].platfrom")));
+        assert!(found.contains(&(Severity::Warning, "custom_tools[0].runtime")));
+        assert!(found.contains(&(Severity::Warning, "custom_tools[0].name")));
+        assert!(!found.iter().any(|(_, key)| key.contains("WINEDEBUG")));
+        assert!(found.contains(&(Severity::Error, "tui.image_previews")));
+        let typo = report
+            .issues
+            .iter()
+            .find(|i| i.key == "deployment.metod")
+            .unwrap();
+        assert_eq!(typo.message, "Unknown key (did you mean 'method'?)");
+        assert!(!found.iter().any(|(_, key)| key.starts_with("downloads")));
+        assert!(report.config.is_some());
+    }
+
+    #[test]
+    fn test_reports_values_that_fail_to_load() {
+        let report = validate("[deployment]\nmethod = \"symlinks\"\n", &[]);
+        assert!(report.has_errors());
+        assert!(report.config.is_none());
+        assert!(report.issues[0].message.contains("symlinks"));
+
+        let report = validate("active_game = \"skyrimse\"\n[tui\n", &[]);
+        assert!(report.issues[0].message.starts_with("Invalid TOML"));
+    }
+}
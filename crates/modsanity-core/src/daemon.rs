@@ -0,0 +1,348 @@
+//! Daemon control socket
+//!
+//! `modsanity daemon` holds the instance lock and serves JSON-RPC 2.0 on a
+//! unix socket, so other tools drive one long-lived instance instead of
+//! fighting over the lock. Each line written to the socket is one request and
+//! is answered with one response line; a connection can send any number of
+//! requests.
+//!
+//! Failures carry the CLI exit code of their
+//! [`ErrorKind`](crate::error::ErrorKind) in `error.data`, so clients can tell
+//! bad input from network errors and conflicts.
+
+use crate::error::{Conflict, Invalid, Remote};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+use std::path::Path;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+/// Methods served by the daemon
+pub const METHODS: &[&str] = &["status", "queue.add", "queue.process", "deploy", "shutdown"];
+
+/// JSON-RPC error codes
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// A method ran and failed
+pub const SERVER_ERROR: i64 = -32000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    /// Echoed in the response; absent for notifications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub params: Value,
+}
+
+impl Request {
+    pub fn new(id: u64, method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(id.into()),
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    /// Parameters as `T`; missing parameters parse as an empty object
+    pub fn params<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        let params = match &self.params {
+            Value::Null => Value::Object(Default::default()),
+            params => params.clone(),
+        };
+        serde_json::from_value(params).map_err(|e| {
+            RpcError::new(
+                INVALID_PARAMS,
+                format!("Invalid params for {}: {}", self.method, e),
+            )
+            .into()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Error response for a failed method. Protocol errors keep their code;
+    /// anything else is a server error with the exit code in `data`.
+    pub fn from_anyhow(err: &anyhow::Error) -> Self {
+        if let Some(rpc) = err.downcast_ref::<RpcError>() {
+            return rpc.clone();
+        }
+        let mut error = Self::new(SERVER_ERROR, format!("{:#}", err));
+        error.data = Some(serde_json::json!({
+            "exit_code": crate::error::exit_code(err),
+        }));
+        error
+    }
+
+    /// Back to an error the CLI maps to the same exit code
+    pub fn into_anyhow(self) -> anyhow::Error {
+        let code = self
+            .data
+            .as_ref()
+            .and_then(|d| d.get("exit_code"))
+            .and_then(Value::as_u64);
+        match code {
+            Some(2) => Invalid::new(self.message).into(),
+            Some(3) => Remote::new(self.message).into(),
+            Some(4) => Conflict::new(self.message).into(),
+            Some(_) => anyhow::anyhow!(self.message),
+            None if matches!(self.code, INVALID_PARAMS | METHOD_NOT_FOUND) => {
+                Invalid::new(self.message).into()
+            }
+            None => anyhow::anyhow!(self.message),
+        }
+    }
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl Response {
+    pub fn new(id: Value, result: Result<Value>) -> Self {
+        let (result, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(err) => (None, Some(RpcError::from_anyhow(&err))),
+        };
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+/// Parse one request line, or the error response to send back instead
+fn parse_request(line: &str) -> std::result::Result<Request, Box<Response>> {
+    let value: Value = serde_json::from_str(line).map_err(|e| {
+        Box::new(Response::new(
+            Value::Null,
+            Err(RpcError::new(PARSE_ERROR, format!("Parse error: {}", e)).into()),
+        ))
+    })?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let error = match serde_json::from_value::<Request>(value) {
+        Ok(request) if request.jsonrpc == "2.0" => return Ok(request),
+        Ok(_) => RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+        Err(e) => RpcError::new(INVALID_REQUEST, format!("Invalid request: {}", e)),
+    };
+    Err(Box::new(Response::new(id, Err(error.into()))))
+}
+
+/// Bind the control socket at `path`. A socket file left behind by a daemon
+/// that died is replaced; one that still answers is a conflict.
+pub async fn bind(path: &Path) -> Result<UnixListener> {
+    if path.exists() {
+        if UnixStream::connect(path).await.is_ok() {
+            bail!(Conflict::new(format!(
+                "A daemon is already listening on {}",
+                path.display()
+            )));
+        }
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    UnixListener::bind(path).with_context(|| format!("Failed to bind {}", path.display()))
+}
+
+/// Answer requests on `listener` with `handler` until `shutdown` completes.
+/// Connections are served concurrently.
+pub async fn serve<H, F>(listener: UnixListener, handler: H, shutdown: impl Future<Output = ()>)
+where
+    H: Fn(Request) -> F + Clone + Send + Sync + 'static,
+    F: Future<Output = Result<Value>> + Send,
+{
+    tokio::pin!(shutdown);
+    loop {
+        let stream = tokio::select! {
+            _ = &mut shutdown => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::warn!("Failed to accept daemon connection: {}", e);
+                    continue;
+                }
+            },
+        };
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_connection(stream, handler).await {
+                tracing::debug!("Daemon connection closed: {:#}", e);
+            }
+        });
+    }
+}
+
+async fn serve_connection<H, F>(stream: UnixStream, handler: H) -> Result<()>
+where
+    H: Fn(Request) -> F,
+    F: Future<Output = Result<Value>>,
+{
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match parse_request(&line) {
+            Ok(request) => {
+                tracing::debug!("Daemon request: {}", request.method);
+                let id = request.id.clone();
+                let response =
+                    Response::new(id.clone().unwrap_or(Value::Null), handler(request).await);
+                if id.is_none() {
+                    continue;
+                }
+                response
+            }
+            Err(response) => *response,
+        };
+        let mut out = serde_json::to_vec(&response)?;
+        out.push(b'\n');
+        writer.write_all(&out).await?;
+    }
+    Ok(())
+}
+
+/// Call `method` on the daemon listening at `socket`
+pub async fn call(socket: &Path, method: &str, params: Value) -> Result<Value> {
+    let stream = UnixStream::connect(socket).await.with_context(|| {
+        format!(
+            "No daemon listening on {} (start one with 'modsanity daemon')",
+            socket.display()
+        )
+    })?;
+    let (reader, mut writer) = stream.into_split();
+    let mut out = serde_json::to_vec(&Request::new(1, method, params))?;
+    out.push(b'\n');
+    writer.write_all(&out).await?;
+
+    let line = BufReader::new(reader)
+        .lines()
+        .next_line()
+        .await?
+        .context("The daemon closed the connection without answering")?;
+    let response: Response =
+        serde_json::from_str(&line).context("Invalid response from the daemon")?;
+    match response.error {
+        Some(error) => Err(error.into_anyhow()),
+        None => Ok(response.result.unwrap_or(Value::Null)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    async fn echo(request: Request) -> Result<Value> {
+        match request.method.as_str() {
+            "status" => Ok(json!({ "game": "skyrimse" })),
+            "queue.add" => {
+                #[derive(Deserialize)]
+                struct Params {
+                    mod_id: i64,
+                }
+                let params: Params = request.params()?;
+                Ok(json!({ "mod_id": params.mod_id }))
+            }
+            "deploy" => bail!(Conflict::new("Deployment already running")),
+            method => {
+                Err(RpcError::new(METHOD_NOT_FOUND, format!("Unknown method '{}'", method)).into())
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_request("{not json").unwrap_err();
+        assert_eq!(err.error.unwrap().code, PARSE_ERROR);
+
+        let err = parse_request(r#"{"jsonrpc":"1.0","id":7,"method":"status"}"#).unwrap_err();
+        assert_eq!(err.id, json!(7));
+        assert_eq!(err.error.unwrap().code, INVALID_REQUEST);
+
+        let request = parse_request(r#"{"jsonrpc":"2.0","id":"a","method":"status"}"#).unwrap();
+        assert_eq!(request.method, "status");
+        assert_eq!(request.params, Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_call_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("modsanity.sock");
+        let listener = bind(&socket).await.unwrap();
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(listener, echo, async {
+            let _ = stopped.await;
+        }));
+
+        assert_eq!(
+            call(&socket, "status", Value::Null).await.unwrap(),
+            json!({ "game": "skyrimse" })
+        );
+        assert_eq!(
+            call(&socket, "queue.add", json!({ "mod_id": 12604 }))
+                .await
+                .unwrap(),
+            json!({ "mod_id": 12604 })
+        );
+
+        let err = call(&socket, "queue.add", json!({})).await.unwrap_err();
+        assert_eq!(crate::error::exit_code(&err), 2);
+        let err = call(&socket, "deploy", Value::Null).await.unwrap_err();
+        assert_eq!(err.to_string(), "Deployment already running");
+        assert_eq!(crate::error::exit_code(&err), 4);
+        let err = call(&socket, "purge", Value::Null).await.unwrap_err();
+        assert_eq!(err.to_string(), "Unknown method 'purge'");
+
+        // A second daemon can't take over a live socket
+        let err = bind(&socket).await.unwrap_err();
+        assert_eq!(crate::error::exit_code(&err), 4);
+
+        stop.send(()).unwrap();
+        server.await.unwrap();
+    }
+}
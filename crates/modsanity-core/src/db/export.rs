@@ -0,0 +1,237 @@
+//! Portable JSON export of the mod library
+//!
+//! The bundle holds the rows of every library table (mods with their file
+//! index, categories, profiles, plugin order, FOMOD choices, modlists, ...)
+//! as column/value objects, so it stays readable and can be loaded into a
+//! database on another machine. Caches that can be fetched again (the Nexus
+//! catalog, download queue, mod pages) are left out.
+
+use super::Database;
+use crate::error::Invalid;
+use anyhow::{bail, Context, Result};
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+
+/// Value of [`LibraryBundle::format`]
+pub const BUNDLE_FORMAT: &str = "modsanity-library";
+
+/// Exported tables, parents before the tables that refer to them
+const BUNDLE_TABLES: &[&str] = &[
+    "categories",
+    "mods",
+    "mod_files",
+    "mod_plugins",
+    "mod_requirements",
+    "mod_exclusions",
+    "mod_archives",
+    "profiles",
+    "profile_mods",
+    "plugins",
+    "fomod_choices",
+    "modlists",
+    "modlist_entries",
+];
+
+/// A JSON dump of the library tables
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LibraryBundle {
+    pub format: String,
+    /// Schema version of the exporting database
+    pub schema_version: i32,
+    pub exported_at: String,
+    /// Rows by table name
+    pub tables: BTreeMap<String, Vec<Map<String, Value>>>,
+}
+
+impl LibraryBundle {
+    /// Row count per table
+    pub fn counts(&self) -> Vec<(&str, usize)> {
+        self.tables
+            .iter()
+            .map(|(table, rows)| (table.as_str(), rows.len()))
+            .collect()
+    }
+}
+
+impl Database {
+    /// Dump the library tables
+    pub fn export_bundle(&self) -> Result<LibraryBundle> {
+        let schema_version = self.schema_version()?;
+        let conn = self.conn.lock().unwrap();
+        let mut tables = BTreeMap::new();
+        for table in BUNDLE_TABLES {
+            let mut stmt = conn.prepare(&format!("SELECT * FROM {} ORDER BY rowid", table))?;
+            let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+            let rows = stmt
+                .query_map([], |row| {
+                    let mut object = Map::new();
+                    for (i, column) in columns.iter().enumerate() {
+                        object.insert(column.clone(), to_json(row.get_ref(i)?));
+                    }
+                    Ok(object)
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            tables.insert(table.to_string(), rows);
+        }
+        Ok(LibraryBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            schema_version,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            tables,
+        })
+    }
+
+    /// Replace the library tables with the bundle's rows, in one
+    /// transaction. Tables the bundle doesn't have are left alone, and
+    /// columns this schema doesn't have are dropped.
+    pub fn import_bundle(&self, bundle: &LibraryBundle) -> Result<()> {
+        if bundle.format != BUNDLE_FORMAT {
+            bail!(Invalid::new(format!(
+                "Not a ModSanity library export (format '{}')",
+                bundle.format
+            )));
+        }
+        let current = self.schema_version()?;
+        if bundle.schema_version > current {
+            bail!(Invalid::new(format!(
+                "The export uses database schema v{}, but this version of ModSanity only supports up to v{}. Upgrade ModSanity first.",
+                bundle.schema_version,
+                current
+            )));
+        }
+        if let Some(unknown) = bundle
+            .tables
+            .keys()
+            .find(|t| !BUNDLE_TABLES.contains(&t.as_str()))
+        {
+            bail!(Invalid::new(format!(
+                "The export has an unknown table '{}'",
+                unknown
+            )));
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for table in BUNDLE_TABLES.iter().rev() {
+            if bundle.tables.contains_key(*table) {
+                tx.execute(&format!("DELETE FROM {}", table), [])?;
+            }
+        }
+        for table in BUNDLE_TABLES {
+            let Some(rows) = bundle.tables.get(*table) else {
+                continue;
+            };
+            let known: HashSet<String> = tx
+                .prepare(&format!("SELECT name FROM pragma_table_info('{}')", table))?
+                .query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            for (i, row) in rows.iter().enumerate() {
+                let (columns, values): (Vec<&String>, Vec<SqlValue>) = row
+                    .iter()
+                    .filter(|(column, _)| known.contains(*column))
+                    .map(|(column, value)| (column, to_sql(value)))
+                    .unzip();
+                if columns.is_empty() {
+                    continue;
+                }
+                let sql = format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    table,
+                    columns
+                        .iter()
+                        .map(|c| format!("\"{}\"", c))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    vec!["?"; columns.len()].join(", ")
+                );
+                tx.execute(&sql, rusqlite::params_from_iter(values))
+                    .with_context(|| format!("Failed to import row {} of {}", i + 1, table))?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn to_json(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Value::from(f),
+        ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).into_owned()),
+        ValueRef::Blob(b) => Value::Array(b.iter().map(|byte| Value::from(*byte)).collect()),
+    }
+}
+
+fn to_sql(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        Value::Array(items) => SqlValue::Blob(
+            items
+                .iter()
+                .filter_map(|v| v.as_u64().map(|b| b as u8))
+                .collect(),
+        ),
+        Value::Object(_) => SqlValue::Text(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::ModRecord;
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = Database::open(&dir.path().join("source.db")).unwrap();
+        let now = chrono::Utc::now().to_rfc3339();
+        source
+            .insert_mod(&ModRecord {
+                id: None,
+                game_id: "skyrimse".to_string(),
+                name: "SkyUI".to_string(),
+                version: "5.2".to_string(),
+                author: None,
+                description: None,
+                nexus_mod_id: Some(12604),
+                nexus_file_id: None,
+                install_path: "/staging/SkyUI".to_string(),
+                enabled: true,
+                priority: 3,
+                file_count: 0,
+                installed_at: now.clone(),
+                updated_at: now,
+                category_id: None,
+            })
+            .unwrap();
+
+        let bundle = source.export_bundle().unwrap();
+        let json = serde_json::to_string(&bundle).unwrap();
+        let bundle: LibraryBundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(bundle.tables["mods"].len(), 1);
+        assert!(!bundle.tables["categories"].is_empty());
+
+        let target = Database::open(&dir.path().join("target.db")).unwrap();
+        target.import_bundle(&bundle).unwrap();
+        let m = target.get_mod("skyrimse", "SkyUI").unwrap().unwrap();
+        assert_eq!(m.nexus_mod_id, Some(12604));
+        assert_eq!(m.priority, 3);
+        assert_eq!(
+            target.export_bundle().unwrap().tables,
+            source.export_bundle().unwrap().tables
+        );
+
+        let mut newer = bundle;
+        newer.schema_version += 1;
+        assert!(target.import_bundle(&newer).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! Database backup, integrity check and vacuum
+//!
+//! The database holds every mod's metadata, so it is copied before any
+//! migration touches an existing file. Backups go through SQLite's online
+//! backup API, which gives a consistent copy even while another connection
+//! is writing.
+
+use super::Database;
+use anyhow::{Context, Result};
+use rusqlite::backup::Backup;
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+impl Database {
+    /// Copy the database to `dest` with SQLite's backup API
+    pub fn backup_to(&self, dest: &Path) -> Result<()> {
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = self.conn.lock().unwrap();
+        backup_connection(&conn, dest)
+    }
+
+    /// Run `PRAGMA integrity_check`; an empty list means the database is fine
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows.into_iter().filter(|r| r != "ok").collect())
+    }
+
+    /// Rebuild the database file to reclaim free pages
+    pub fn vacuum(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch("VACUUM").context("VACUUM failed")
+    }
+
+    /// Path of the open database file, if it isn't in memory
+    pub fn file_path(&self) -> Option<PathBuf> {
+        let conn = self.conn.lock().unwrap();
+        conn.path().filter(|p| !p.is_empty()).map(PathBuf::from)
+    }
+
+    /// Back up an existing database whose schema is older than `target`,
+    /// before migrations change it. Returns the backup path.
+    pub(super) fn backup_before_migration(
+        &self,
+        path: &Path,
+        target: i32,
+    ) -> Result<Option<PathBuf>> {
+        let conn = self.conn.lock().unwrap();
+        let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        let tables: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'",
+            [],
+            |row| row.get(0),
+        )?;
+        // New databases have nothing to lose
+        if version >= target || tables == 0 {
+            return Ok(None);
+        }
+
+        let dest = backup_path(path, &format!("pre-migration-v{}", version));
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        backup_connection(&conn, &dest)
+            .with_context(|| format!("Failed to back up database to {}", dest.display()))?;
+        tracing::info!(
+            "Backed up database (schema v{}) to {} before migrating",
+            version,
+            dest.display()
+        );
+        Ok(Some(dest))
+    }
+}
+
+/// Default backup location for the database at `db_path`:
+/// `backups/modsanity-<label>-<timestamp>.db` next to it
+pub fn backup_path(db_path: &Path, label: &str) -> PathBuf {
+    let dir = db_path
+        .parent()
+        .map(|p| p.join("backups"))
+        .unwrap_or_else(|| PathBuf::from("backups"));
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    if label.is_empty() {
+        dir.join(format!("modsanity-{}.db", stamp))
+    } else {
+        dir.join(format!("modsanity-{}-{}.db", label, stamp))
+    }
+}
+
+fn backup_connection(conn: &Connection, dest: &Path) -> Result<()> {
+    let mut target =
+        Connection::open(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+    let backup = Backup::new(conn, &mut target)?;
+    backup.run_to_completion(256, Duration::from_millis(10), None)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_before_migration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("modsanity.db");
+
+        // A fresh database is created without a backup
+        Database::open(&path).unwrap();
+        let backups = dir.path().join("backups");
+        assert!(!backups.exists());
+        std::fs::remove_file(&path).unwrap();
+
+        // An older schema is backed up when opened
+        Database {
+            conn: std::sync::Mutex::new(Connection::open(&path).unwrap()),
+        }
+        .run_migrations(&path, &[])
+        .unwrap();
+        let db = Database::open(&path).unwrap();
+        let copies: Vec<_> = std::fs::read_dir(&backups).unwrap().flatten().collect();
+        assert_eq!(copies.len(), 1);
+        let name = copies[0].file_name().to_string_lossy().to_string();
+        assert!(name.starts_with("modsanity-pre-migration-v1-"));
+
+        let copy = Connection::open(copies[0].path()).unwrap();
+        let tables: i64 = copy
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'mods'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(tables, 1);
+
+        assert!(db.integrity_check().unwrap().is_empty());
+        db.vacuum().unwrap();
+        assert_eq!(db.file_path(), Some(path));
+    }
+}
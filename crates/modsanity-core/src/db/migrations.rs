@@ -0,0 +1,251 @@
+//! Numbered schema migrations
+//!
+//! The schema version lives in `PRAGMA user_version`, and every applied
+//! migration is recorded in `schema_version` as `NNNN_name`. Version 1 is the
+//! baseline: the schema built by the `migrate_*` steps in `db/mod.rs`, which
+//! also upgrade any database created before numbered migrations existed.
+//! Every later version is an SQL file in `migrations/`, embedded at build time
+//! and applied in its own transaction, so a failed upgrade leaves the previous
+//! version intact.
+//!
+//! Migrations only go forward. An existing database is backed up before any
+//! pending migration runs, and one stamped with a version newer than this
+//! build knows is refused rather than written with an older idea of its
+//! tables.
+
+use super::Database;
+use anyhow::{bail, Context, Result};
+use rusqlite::params;
+use std::path::Path;
+
+/// A schema change after the baseline
+pub(super) struct Migration {
+    pub version: i32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Version of the schema built by [`Database::baseline_schema`]
+const BASELINE_VERSION: i32 = 1;
+
+/// Migrations after the baseline, in version order. Never edit or remove a
+/// released entry; add a new one instead.
+pub(super) const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 2,
+        name: "catalog_search",
+        sql: include_str!("migrations/0002_catalog_search.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "activity_log",
+        sql: include_str!("migrations/0003_activity_log.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "mod_trash",
+        sql: include_str!("migrations/0004_mod_trash.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "cleaned_plugins",
+        sql: include_str!("migrations/0005_cleaned_plugins.sql"),
+    },
+];
+
+/// Schema version this build creates and upgrades to
+pub const SCHEMA_VERSION: i32 = BASELINE_VERSION + MIGRATIONS.len() as i32;
+
+impl Database {
+    /// Schema version recorded in the database file
+    pub fn schema_version(&self) -> Result<i32> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+    }
+
+    /// Bring the database at `path` up to the last of `migrations`
+    pub(super) fn run_migrations(&self, path: &Path, migrations: &[Migration]) -> Result<()> {
+        let latest = migrations.last().map_or(BASELINE_VERSION, |m| m.version);
+        let current = self.schema_version()?;
+        if current > latest {
+            bail!(
+                "{} uses database schema v{}, but this version of ModSanity only supports up to v{}. \
+                 Upgrade ModSanity, or restore a backup from {}.",
+                path.display(),
+                current,
+                latest,
+                path.parent()
+                    .map(|p| p.join("backups"))
+                    .unwrap_or_default()
+                    .display()
+            );
+        }
+        if current == latest {
+            return Ok(());
+        }
+
+        self.backup_before_migration(path, latest)?;
+
+        if current < BASELINE_VERSION {
+            self.baseline_schema()?;
+            let conn = self.conn.lock().unwrap();
+            record_version(&conn, BASELINE_VERSION, "baseline")?;
+        }
+
+        for migration in migrations.iter().filter(|m| m.version > current) {
+            let mut conn = self.conn.lock().unwrap();
+            let tx = conn.transaction()?;
+            tx.execute_batch(migration.sql).with_context(|| {
+                format!(
+                    "Database migration {:04}_{} failed",
+                    migration.version, migration.name
+                )
+            })?;
+            record_version(&tx, migration.version, migration.name)?;
+            tx.commit()?;
+            tracing::info!(
+                "Applied database migration {:04}_{}",
+                migration.version,
+                migration.name
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Stamp `version` and record it in `schema_version`
+fn record_version(conn: &rusqlite::Connection, version: i32, name: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+        params![format!("{:04}_{}", version, name)],
+    )?;
+    conn.pragma_update(None, "user_version", version)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::NexusCatalogRecord;
+
+    #[test]
+    fn test_migrations_are_numbered_in_order() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, BASELINE_VERSION + 1 + i as i32);
+        }
+    }
+
+    #[test]
+    fn test_run_migrations_forward_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("modsanity.db");
+        let db = Database::open(&path).unwrap();
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
+
+        let next = SCHEMA_VERSION + 1;
+        let pending = [Migration {
+            version: next,
+            name: "test_notes",
+            sql: "CREATE TABLE test_notes (id INTEGER PRIMARY KEY, note TEXT NOT NULL);",
+        }];
+        db.run_migrations(&path, &pending).unwrap();
+        assert_eq!(db.schema_version().unwrap(), next);
+        assert!(dir.path().join("backups").exists());
+        {
+            let conn = db.conn.lock().unwrap();
+            let recorded: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                    params![format!("{:04}_test_notes", next)],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(recorded, 1);
+        }
+
+        // A failing migration rolls back as a whole
+        let broken = [Migration {
+            version: next + 1,
+            name: "broken",
+            sql: "CREATE TABLE half_done (id INTEGER); SELECT * FROM missing_table;",
+        }];
+        assert!(db.run_migrations(&path, &broken).is_err());
+        assert_eq!(db.schema_version().unwrap(), next);
+        {
+            let conn = db.conn.lock().unwrap();
+            let tables: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM sqlite_master WHERE name = 'half_done'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(tables, 0);
+        }
+        drop(db);
+
+        // This build doesn't know the newer schema and refuses to open it
+        let err = Database::open(&path).err().unwrap().to_string();
+        assert!(err.contains(&format!("schema v{}", next)));
+    }
+
+    #[test]
+    fn test_catalog_search_migration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("modsanity.db");
+
+        // A catalog synced before the full-text index existed
+        let db = Database {
+            conn: std::sync::Mutex::new(rusqlite::Connection::open(&path).unwrap()),
+        };
+        db.run_migrations(&path, &[]).unwrap();
+        {
+            let conn = db.conn.lock().unwrap();
+            conn.execute_batch(
+                r#"
+                INSERT INTO nexus_catalog (game_domain, mod_id, name, summary, author, synced_at) VALUES
+                    ('skyrimspecialedition', 1, 'Skyrim 2020 Parallax', 'Landscape texture pack', 'Pfuscher', 'now'),
+                    ('skyrimspecialedition', 2, 'Noble Skyrim Textures', 'Architecture textures', 'Gamwich', 'now'),
+                    ('skyrimspecialedition', 3, 'SkyUI', 'Interface', 'schlangster', 'now'),
+                    ('fallout4', 4, 'Vivid Fallout Textures', 'Landscape textures', 'Hein84', 'now');
+                "#,
+            )
+            .unwrap();
+        }
+        db.run_migrations(&path, MIGRATIONS).unwrap();
+
+        let names = |query: &str| -> Vec<String> {
+            db.search_catalog("skyrimspecialedition", query, 10)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.name)
+                .collect()
+        };
+        // Stems match, name matches rank above summary matches
+        assert_eq!(
+            names("texture"),
+            vec!["Noble Skyrim Textures", "Skyrim 2020 Parallax"]
+        );
+        assert_eq!(names("skyui"), vec!["SkyUI"]);
+        assert_eq!(names("schlang"), vec!["SkyUI"]);
+        assert!(names("\"OR NEAR(").is_empty());
+
+        // The index follows catalog updates
+        db.upsert_catalog_page(
+            "skyrimspecialedition",
+            &[NexusCatalogRecord {
+                game_domain: "skyrimspecialedition".to_string(),
+                mod_id: 3,
+                name: "SkyUI Weapons".to_string(),
+                summary: None,
+                description: None,
+                author: None,
+                updated_time: None,
+                synced_at: String::new(),
+            }],
+        )
+        .unwrap();
+        assert_eq!(names("weapon"), vec!["SkyUI Weapons"]);
+        assert!(names("schlangster").is_empty());
+    }
+}
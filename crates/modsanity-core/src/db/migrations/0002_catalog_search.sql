@@ -0,0 +1,58 @@
+-- Full-text search over the Nexus catalog.
+--
+-- The index refers to catalog rows by rowid, and VACUUM may renumber the
+-- implicit rowid of a table without an INTEGER PRIMARY KEY, so the catalog
+-- is rebuilt with an explicit one first.
+
+CREATE TABLE nexus_catalog_new (
+    id INTEGER PRIMARY KEY,
+    game_domain TEXT NOT NULL,
+    mod_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    summary TEXT,
+    description TEXT,
+    author TEXT,
+    updated_time INTEGER,
+    synced_at TEXT NOT NULL,
+    UNIQUE (game_domain, mod_id)
+);
+
+INSERT INTO nexus_catalog_new
+    (game_domain, mod_id, name, summary, description, author, updated_time, synced_at)
+SELECT game_domain, mod_id, name, summary, description, author, updated_time, synced_at
+FROM nexus_catalog;
+
+DROP TABLE nexus_catalog;
+ALTER TABLE nexus_catalog_new RENAME TO nexus_catalog;
+
+CREATE INDEX idx_catalog_name ON nexus_catalog(game_domain, name);
+CREATE INDEX idx_catalog_updated ON nexus_catalog(game_domain, updated_time);
+
+-- Porter stemming matches word variants ("textures" finds "texture")
+CREATE VIRTUAL TABLE nexus_catalog_fts USING fts5(
+    name,
+    summary,
+    author,
+    content = 'nexus_catalog',
+    content_rowid = 'id',
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER nexus_catalog_fts_insert AFTER INSERT ON nexus_catalog BEGIN
+    INSERT INTO nexus_catalog_fts (rowid, name, summary, author)
+    VALUES (new.id, new.name, new.summary, new.author);
+END;
+
+CREATE TRIGGER nexus_catalog_fts_delete AFTER DELETE ON nexus_catalog BEGIN
+    INSERT INTO nexus_catalog_fts (nexus_catalog_fts, rowid, name, summary, author)
+    VALUES ('delete', old.id, old.name, old.summary, old.author);
+END;
+
+CREATE TRIGGER nexus_catalog_fts_update AFTER UPDATE ON nexus_catalog BEGIN
+    INSERT INTO nexus_catalog_fts (nexus_catalog_fts, rowid, name, summary, author)
+    VALUES ('delete', old.id, old.name, old.summary, old.author);
+    INSERT INTO nexus_catalog_fts (rowid, name, summary, author)
+    VALUES (new.id, new.name, new.summary, new.author);
+END;
+
+INSERT INTO nexus_catalog_fts (nexus_catalog_fts) VALUES ('rebuild');
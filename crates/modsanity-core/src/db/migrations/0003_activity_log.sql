@@ -0,0 +1,14 @@
+-- Every state-changing action, for "what changed since it last worked"
+
+CREATE TABLE activity_log (
+    id INTEGER PRIMARY KEY,
+    game_id TEXT NOT NULL,
+    profile TEXT,
+    action TEXT NOT NULL,
+    target TEXT,
+    -- JSON object with the action's parameters
+    details TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_activity_log_game ON activity_log(game_id, id);
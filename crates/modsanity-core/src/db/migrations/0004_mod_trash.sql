@@ -0,0 +1,3 @@
+-- Removed mods stay in the table until their trash retention runs out
+ALTER TABLE mods ADD COLUMN deleted_at TEXT;
+CREATE INDEX idx_mods_deleted ON mods(game_id, deleted_at);
@@ -0,0 +1,17 @@
+-- Plugins cleaned with xEdit QuickAutoClean, with their checksums before and after
+
+CREATE TABLE cleaned_plugins (
+    id INTEGER PRIMARY KEY,
+    game_id TEXT NOT NULL,
+    plugin TEXT NOT NULL,
+    -- Mod whose staged copy was cleaned; NULL for game files
+    mod_name TEXT,
+    crc_before INTEGER NOT NULL,
+    crc_after INTEGER NOT NULL,
+    itm INTEGER NOT NULL DEFAULT 0,
+    udr INTEGER NOT NULL DEFAULT 0,
+    nav INTEGER NOT NULL DEFAULT 0,
+    cleaned_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_cleaned_plugins_game ON cleaned_plugins(game_id, plugin);
@@ -1,7 +1,13 @@
 //! SQLite database for mod tracking
 
+mod export;
+mod maintenance;
+mod migrations;
 mod schema;
 
+pub use export::{LibraryBundle, BUNDLE_FORMAT};
+pub use maintenance::backup_path;
+pub use migrations::SCHEMA_VERSION;
 pub use schema::*;
 
 use anyhow::{Context, Result};
@@ -28,17 +34,36 @@ impl Database {
             conn: Mutex::new(conn),
         };
 
-        db.init_schema()?;
-        db.migrate_downloads_for_import()?;
-        db.migrate_categories()?;
-        db.init_default_categories()?;
-        db.restore_category_mappings()?;
-        db.migrate_nexus_catalog()?;
-        db.migrate_modlists()?;
-        db.migrate_mod_plugin_index()?;
+        db.run_migrations(path, migrations::MIGRATIONS)?;
         Ok(db)
     }
 
+    /// Build the baseline schema (migration 1). Databases created before
+    /// numbered migrations are brought up to it by the same idempotent steps.
+    fn baseline_schema(&self) -> Result<()> {
+        self.init_schema()?;
+        self.migrate_downloads_for_import()?;
+        self.migrate_categories()?;
+        self.init_default_categories()?;
+        self.restore_category_mappings()?;
+        self.migrate_nexus_catalog()?;
+        self.migrate_modlists()?;
+        self.migrate_mod_plugin_index()?;
+        self.migrate_mod_archives()?;
+        self.migrate_downloads_update_target()?;
+        self.migrate_mod_requirements()?;
+        self.migrate_sessions()?;
+        self.migrate_nexus_file_status()?;
+        self.migrate_mod_file_index()?;
+        self.migrate_download_hashes()?;
+        self.migrate_download_hash_versions()?;
+        self.migrate_downloads_priority()?;
+        self.migrate_nexus_mod_pages()?;
+        self.migrate_mod_exclusions()?;
+        self.migrate_queue_batch_controls()?;
+        Ok(())
+    }
+
     /// Initialize database schema
     fn init_schema(&self) -> Result<()> {
         let conn = self.conn.lock().unwrap();
@@ -296,6 +321,12 @@ impl Database {
     /// Insert a new mod
     pub fn insert_mod(&self, m: &ModRecord) -> Result<i64> {
         let conn = self.conn.lock().unwrap();
+        // A new install supersedes a trashed mod of the same name; its
+        // folder is left for the next trash purge
+        conn.execute(
+            "DELETE FROM mods WHERE game_id = ?1 AND name = ?2 AND deleted_at IS NOT NULL",
+            params![m.game_id, m.name],
+        )?;
         conn.execute(
             r#"
             INSERT INTO mods (game_id, name, version, author, description, nexus_mod_id,
@@ -326,9 +357,9 @@ impl Database {
     pub fn get_mod(&self, game_id: &str, name: &str) -> Result<Option<ModRecord>> {
         let conn = self.conn.lock().unwrap();
         conn.query_row(
-            "SELECT * FROM mods WHERE game_id = ?1 AND name = ?2",
+            "SELECT * FROM mods WHERE game_id = ?1 AND name = ?2 AND deleted_at IS NULL",
             params![game_id, name],
-            |row| ModRecord::from_row(row),
+            ModRecord::from_row,
         )
         .optional()
         .context("Failed to query mod")
@@ -338,15 +369,36 @@ impl Database {
     pub fn get_mods_for_game(&self, game_id: &str) -> Result<Vec<ModRecord>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt =
-            conn.prepare("SELECT * FROM mods WHERE game_id = ?1 ORDER BY priority ASC, name ASC")?;
+            conn.prepare(
+                "SELECT * FROM mods WHERE game_id = ?1 AND deleted_at IS NULL ORDER BY priority ASC, name ASC",
+            )?;
 
         let mods = stmt
-            .query_map(params![game_id], |row| ModRecord::from_row(row))?
+            .query_map(params![game_id], ModRecord::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(mods)
     }
 
+    /// Total indexed file size of each of a game's mods, by mod id
+    pub fn get_mod_sizes(&self, game_id: &str) -> Result<std::collections::HashMap<i64, u64>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT mf.mod_id, COALESCE(SUM(mf.size), 0) FROM mod_files mf
+             JOIN mods m ON m.id = mf.mod_id
+             WHERE m.game_id = ?1 AND m.deleted_at IS NULL
+             GROUP BY mf.mod_id",
+        )?;
+
+        let sizes = stmt
+            .query_map(params![game_id], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?.max(0) as u64))
+            })?
+            .collect::<Result<std::collections::HashMap<_, _>, _>>()?;
+
+        Ok(sizes)
+    }
+
     /// Update mod enabled status
     pub fn set_mod_enabled(&self, mod_id: i64, enabled: bool) -> Result<()> {
         let conn = self.conn.lock().unwrap();
@@ -370,6 +422,14 @@ impl Database {
     /// Update mod display name.
     pub fn set_mod_name(&self, mod_id: i64, name: &str) -> Result<()> {
         let conn = self.conn.lock().unwrap();
+        // Keep retained archives attached to the renamed mod
+        conn.execute(
+            r#"
+            UPDATE mod_archives SET mod_name = ?1
+            WHERE (game_id, mod_name) = (SELECT game_id, name FROM mods WHERE id = ?2)
+            "#,
+            params![name, mod_id],
+        )?;
         conn.execute(
             "UPDATE mods SET name = ?1, updated_at = datetime('now') WHERE id = ?2",
             params![name, mod_id],
@@ -384,6 +444,30 @@ impl Database {
         Ok(())
     }
 
+    /// Flag a mod as removed (`deleted_at`), or clear the flag to restore it
+    pub fn set_mod_trashed(&self, mod_id: i64, deleted_at: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE mods SET deleted_at = ?1 WHERE id = ?2",
+            params![deleted_at, mod_id],
+        )?;
+        Ok(())
+    }
+
+    /// Removed mods of a game with their removal time, most recent first
+    pub fn get_trashed_mods(&self, game_id: &str) -> Result<Vec<(ModRecord, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM mods WHERE game_id = ?1 AND deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        )?;
+        let mods = stmt
+            .query_map(params![game_id], |row| {
+                Ok((ModRecord::from_row(row)?, row.get("deleted_at")?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(mods)
+    }
+
     /// Get a mod by ID
     pub fn get_mod_by_id(&self, mod_id: i64) -> Result<Option<ModRecord>> {
         let conn = self.conn.lock().unwrap();
@@ -463,7 +547,7 @@ impl Database {
         let mut stmt = conn.prepare("SELECT * FROM mod_files WHERE mod_id = ?1")?;
 
         let files = stmt
-            .query_map(params![mod_id], |row| ModFileRecord::from_row(row))?
+            .query_map(params![mod_id], ModFileRecord::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(files)
@@ -519,6 +603,7 @@ impl Database {
             JOIN mods m ON m.id = mp.mod_id
             WHERE mp.game_id = ?1
               AND mp.plugin_name_lower = ?2
+              AND m.deleted_at IS NULL
             ORDER BY
                 CASE WHEN m.nexus_mod_id IS NULL THEN 1 ELSE 0 END,
                 m.priority DESC,
@@ -549,10 +634,11 @@ impl Database {
             r#"
             SELECT f1.relative_path, m1.name as mod1, m2.name as mod2, m1.priority as p1, m2.priority as p2
             FROM mod_files f1
-            JOIN mod_files f2 ON f1.relative_path = f2.relative_path AND f1.mod_id < f2.mod_id
+            JOIN mod_files f2 ON f1.relative_path = f2.relative_path COLLATE NOCASE AND f1.mod_id < f2.mod_id
             JOIN mods m1 ON f1.mod_id = m1.id
             JOIN mods m2 ON f2.mod_id = m2.id
             WHERE m1.game_id = ?1 AND m2.game_id = ?1 AND m1.enabled = 1 AND m2.enabled = 1
+              AND m1.deleted_at IS NULL AND m2.deleted_at IS NULL
             ORDER BY f1.relative_path
             "#,
         )?;
@@ -580,7 +666,7 @@ impl Database {
             SELECT f.relative_path, m.name, m.id
             FROM mod_files f
             JOIN mods m ON f.mod_id = m.id
-            WHERE m.game_id = ?1 AND m.enabled = 1
+            WHERE m.game_id = ?1 AND m.enabled = 1 AND m.deleted_at IS NULL
             ORDER BY f.relative_path
             "#,
         )?;
@@ -598,6 +684,64 @@ impl Database {
         Ok(files)
     }
 
+    /// Mods providing `path` (case-insensitive, '/' separated). Falls back
+    /// to files whose path ends in `path` when nothing matches exactly.
+    pub fn find_file_providers(&self, game_id: &str, path: &str) -> Result<Vec<FileProvider>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT f.relative_path, m.id, m.name, m.enabled, m.priority, f.size, f.hash
+            FROM mod_files f
+            JOIN mods m ON f.mod_id = m.id
+            WHERE m.game_id = ?1 AND m.deleted_at IS NULL AND f.relative_path = ?2 COLLATE NOCASE
+            ORDER BY m.priority DESC
+            "#,
+        )?;
+        let row_to_provider = |row: &rusqlite::Row<'_>| {
+            Ok(FileProvider {
+                relative_path: row.get(0)?,
+                mod_id: row.get(1)?,
+                mod_name: row.get(2)?,
+                enabled: row.get(3)?,
+                priority: row.get(4)?,
+                size: row.get(5)?,
+                hash: row.get(6)?,
+            })
+        };
+
+        let native = path.replace('/', std::path::MAIN_SEPARATOR_STR);
+        let providers = stmt
+            .query_map(params![game_id, native], row_to_provider)?
+            .collect::<Result<Vec<_>, _>>()?;
+        if !providers.is_empty() {
+            return Ok(providers);
+        }
+
+        let escaped = native
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT f.relative_path, m.id, m.name, m.enabled, m.priority, f.size, f.hash
+            FROM mod_files f
+            JOIN mods m ON f.mod_id = m.id
+            WHERE m.game_id = ?1 AND m.deleted_at IS NULL AND f.relative_path LIKE ?2 ESCAPE '\'
+            ORDER BY f.relative_path, m.priority DESC
+            "#,
+        )?;
+        let providers = stmt
+            .query_map(
+                params![
+                    game_id,
+                    format!("%{}{}", std::path::MAIN_SEPARATOR, escaped)
+                ],
+                row_to_provider,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(providers)
+    }
+
     // ========== Profile Operations ==========
 
     /// Insert a new profile
@@ -619,7 +763,7 @@ impl Database {
         let mut stmt = conn.prepare("SELECT * FROM profiles WHERE game_id = ?1 ORDER BY name")?;
 
         let profiles = stmt
-            .query_map(params![game_id], |row| ProfileRecord::from_row(row))?
+            .query_map(params![game_id], ProfileRecord::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(profiles)
@@ -653,7 +797,7 @@ impl Database {
         let mut stmt = conn.prepare("SELECT * FROM categories ORDER BY display_order ASC")?;
 
         let categories = stmt
-            .query_map([], |row| CategoryRecord::from_row(row))?
+            .query_map([], CategoryRecord::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(categories)
@@ -665,7 +809,7 @@ impl Database {
         conn.query_row(
             "SELECT * FROM categories WHERE id = ?1",
             params![category_id],
-            |row| CategoryRecord::from_row(row),
+            CategoryRecord::from_row,
         )
         .optional()
         .context("Failed to query category")
@@ -677,7 +821,7 @@ impl Database {
         conn.query_row(
             "SELECT * FROM categories WHERE name = ?1",
             params![name],
-            |row| CategoryRecord::from_row(row),
+            CategoryRecord::from_row,
         )
         .optional()
         .context("Failed to query category")
@@ -702,17 +846,17 @@ impl Database {
         let conn = self.conn.lock().unwrap();
 
         let query = match category_id {
-            Some(_) => "SELECT * FROM mods WHERE game_id = ?1 AND category_id = ?2 ORDER BY priority ASC, name ASC",
-            None => "SELECT * FROM mods WHERE game_id = ?1 AND category_id IS NULL ORDER BY priority ASC, name ASC",
+            Some(_) => "SELECT * FROM mods WHERE game_id = ?1 AND category_id = ?2 AND deleted_at IS NULL ORDER BY priority ASC, name ASC",
+            None => "SELECT * FROM mods WHERE game_id = ?1 AND category_id IS NULL AND deleted_at IS NULL ORDER BY priority ASC, name ASC",
         };
 
         let mut stmt = conn.prepare(query)?;
 
         let mods = if let Some(cat_id) = category_id {
-            stmt.query_map(params![game_id, cat_id], |row| ModRecord::from_row(row))?
+            stmt.query_map(params![game_id, cat_id], ModRecord::from_row)?
                 .collect::<Result<Vec<_>, _>>()?
         } else {
-            stmt.query_map(params![game_id], |row| ModRecord::from_row(row))?
+            stmt.query_map(params![game_id], ModRecord::from_row)?
                 .collect::<Result<Vec<_>, _>>()?
         };
 
@@ -1441,8 +1585,8 @@ impl Database {
             INSERT INTO downloads (
                 game_id, nexus_mod_id, nexus_file_id, name, filename, status,
                 queue_position, plugin_name, match_confidence, import_batch_id,
-                selected_file_id, auto_install, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, datetime('now'))
+                selected_file_id, auto_install, replaces_mod, priority, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, datetime('now'))
             "#,
             params![
                 entry.game_id,
@@ -1457,6 +1601,8 @@ impl Database {
                 entry.import_batch_id,
                 entry.selected_file_id,
                 entry.auto_install as i32,
+                entry.replaces_mod,
+                entry.priority as i32,
             ],
         )?;
         Ok(conn.last_insert_rowid())
@@ -1469,17 +1615,41 @@ impl Database {
             r#"
             SELECT * FROM downloads
             WHERE import_batch_id = ?1
-            ORDER BY queue_position ASC
+            ORDER BY priority DESC, queue_position ASC
             "#,
         )?;
 
         let entries = stmt
-            .query_map(params![batch_id], |row| DownloadQueueEntry::from_row(row))?
+            .query_map(params![batch_id], DownloadQueueEntry::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(entries)
     }
 
+    /// Mark a queue entry as processed before the rest of its batch
+    pub fn update_download_priority(&self, download_id: i64, priority: bool) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE downloads SET priority = ?1 WHERE id = ?2",
+            params![priority as i32, download_id],
+        )?;
+        Ok(())
+    }
+
+    /// Rewrite queue positions in one transaction
+    pub fn update_queue_positions(&self, positions: &[(i64, i32)]) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let tx = conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare("UPDATE downloads SET queue_position = ?1 WHERE id = ?2")?;
+            for (download_id, position) in positions {
+                stmt.execute(params![position, download_id])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     /// Resolve a queue entry by assigning/modifying its Nexus mod target and setting status.
     pub fn resolve_queue_entry(
         &self,
@@ -1492,7 +1662,8 @@ impl Database {
         conn.execute(
             r#"
             UPDATE downloads
-            SET nexus_mod_id = ?1,
+            SET selected_file_id = CASE WHEN nexus_mod_id = ?1 THEN selected_file_id END,
+                nexus_mod_id = ?1,
                 name = ?2,
                 status = ?3,
                 error = NULL
@@ -1675,6 +1846,16 @@ impl Database {
         Ok(())
     }
 
+    /// Pin the Nexus file a download fetches (`None` goes back to the main file)
+    pub fn update_download_file(&self, download_id: i64, file_id: Option<i64>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE downloads SET selected_file_id = ?1 WHERE id = ?2",
+            params![file_id, download_id],
+        )?;
+        Ok(())
+    }
+
     /// Insert match alternatives for a download
     pub fn insert_match_alternatives(
         &self,
@@ -1735,9 +1916,46 @@ impl Database {
             "DELETE FROM downloads WHERE import_batch_id = ?1",
             params![batch_id],
         )?;
+        conn.execute(
+            "DELETE FROM queue_batch_controls WHERE batch_id = ?1",
+            params![batch_id],
+        )?;
+        Ok(())
+    }
+
+    /// Set a batch's pause/cancel request, or clear it with `None`
+    pub fn set_batch_control(&self, batch_id: &str, state: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        match state {
+            Some(state) => conn.execute(
+                r#"
+                INSERT INTO queue_batch_controls (batch_id, state, updated_at)
+                VALUES (?1, ?2, datetime('now'))
+                ON CONFLICT(batch_id) DO UPDATE SET state = excluded.state,
+                                                    updated_at = excluded.updated_at
+                "#,
+                params![batch_id, state],
+            )?,
+            None => conn.execute(
+                "DELETE FROM queue_batch_controls WHERE batch_id = ?1",
+                params![batch_id],
+            )?,
+        };
         Ok(())
     }
 
+    /// A batch's pause/cancel request, if any
+    pub fn get_batch_control(&self, batch_id: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT state FROM queue_batch_controls WHERE batch_id = ?1",
+            params![batch_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
     // ========== Nexus Catalog Operations ==========
 
     /// Migrate database schema for Nexus catalog
@@ -1812,7 +2030,7 @@ impl Database {
             .query_row(
                 "SELECT * FROM catalog_sync_state WHERE game_domain = ?1",
                 params![game_domain],
-                |row| CatalogSyncState::from_row(row),
+                CatalogSyncState::from_row,
             )
             .optional()?
         {
@@ -1970,8 +2188,10 @@ impl Database {
         Ok(count)
     }
 
-    /// Search the local catalog for mods matching a query string
-    /// Searches across name, summary, and description fields with token-based matching
+    /// Search the local catalog for mods matching a query string, best match
+    /// first. Uses the full-text index over name, summary and author: any
+    /// word may match, words match as prefixes and stems ("texture" finds
+    /// "Textures"), and name matches rank above summary and author matches.
     pub fn search_catalog(
         &self,
         game_domain: &str,
@@ -1981,100 +2201,44 @@ impl Database {
         let conn = self.conn.lock().unwrap();
 
         // Normalize query: remove punctuation, split into tokens
-        let query_normalized = query
-            .to_lowercase()
-            .replace('+', " ")
-            .replace('-', " ")
-            .replace('_', " ")
-            .replace(':', " ");
+        let query_normalized = query.to_lowercase().replace(['+', '-', '_', ':'], " ");
 
         let tokens: Vec<&str> = query_normalized
             .split_whitespace()
-            .map(|t| t.trim_matches(|c: char| !c.is_ascii_alphanumeric()))
-            .filter(|t| t.len() >= 2) // Ignore single characters
+            .map(|t| t.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|t| t.chars().count() >= 2) // Ignore single characters
             .collect();
 
         if tokens.is_empty() {
             return Ok(Vec::new());
         }
 
-        // Build flexible query that searches name, summary, and description
-        // Use multiple search strategies for better recall:
-        // 1. Exact name match (highest priority)
-        // 2. Name contains all tokens
-        // 3. Name contains any token
-        // 4. Summary or description contains tokens
-        let mut sql = String::from(
-            "SELECT DISTINCT game_domain, mod_id, name, summary, description, author, updated_time, synced_at, \
-             CASE \
-               WHEN LOWER(name) = ?2 THEN 1000 \
-               WHEN LOWER(REPLACE(REPLACE(REPLACE(name, '-', ' '), '_', ' '), ':', ' ')) = ?2 THEN 900 "
-        );
-
-        // Add scoring for each token match in name
-        for (i, _) in tokens.iter().enumerate() {
-            sql.push_str(&format!(
-                "WHEN LOWER(name) LIKE '%' || ?{} || '%' THEN {} ",
-                i + 3,
-                800_usize.saturating_sub(i * 50)
-            ));
-        }
-
-        // Add scoring for summary/description matches
-        for (i, _) in tokens.iter().enumerate() {
-            sql.push_str(&format!(
-                "WHEN LOWER(summary) LIKE '%' || ?{} || '%' OR LOWER(description) LIKE '%' || ?{} || '%' THEN {} ",
-                i + 3,
-                i + 3,
-                400_usize.saturating_sub(i * 50)
-            ));
-        }
-
-        sql.push_str(
-            "ELSE 0 \
-             END as relevance \
-             FROM nexus_catalog \
-             WHERE game_domain = ?1 AND (",
-        );
-
-        // Build WHERE clause: match any token in name, summary, or description
-        let mut conditions = Vec::new();
-        for (i, _) in tokens.iter().enumerate() {
-            conditions.push(format!(
-                "(LOWER(name) LIKE '%' || ?{} || '%' OR \
-                  LOWER(summary) LIKE '%' || ?{} || '%' OR \
-                  LOWER(description) LIKE '%' || ?{} || '%')",
-                i + 3,
-                i + 3,
-                i + 3
-            ));
-        }
-        sql.push_str(&conditions.join(" OR "));
-
-        let limit_param_idx = tokens.len() + 3;
-        sql.push_str(&format!(
-            ") \
-             ORDER BY relevance DESC, updated_time DESC \
-             LIMIT ?{}",
-            limit_param_idx
-        ));
-
-        let mut stmt = conn.prepare(&sql)?;
-
-        // Build params: ?1=game_domain, ?2=query_normalized, ?3..=tokens, last=limit
-        let mut params_vec: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-        params_vec.push(Box::new(game_domain.to_string()));
-        params_vec.push(Box::new(query_normalized.clone()));
-
-        for token in &tokens {
-            params_vec.push(Box::new(token.to_string()));
-        }
-        params_vec.push(Box::new(limit));
-
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            params_vec.iter().map(|p| p.as_ref()).collect();
+        // Quote each token so FTS5 operators in the query are taken literally
+        let match_expr = tokens
+            .iter()
+            .map(|t| format!("\"{}\"*", t.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        let exact_name = tokens.join(" ");
 
-        let rows = stmt.query_map(&param_refs[..], |row| NexusCatalogRecord::from_row(row))?;
+        // bm25 weights: name, summary, author (lower scores rank higher)
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT c.game_domain, c.mod_id, c.name, c.summary, c.description, c.author,
+                   c.updated_time, c.synced_at
+            FROM nexus_catalog_fts
+            JOIN nexus_catalog c ON c.id = nexus_catalog_fts.rowid
+            WHERE nexus_catalog_fts MATCH ?2 AND c.game_domain = ?1
+            ORDER BY LOWER(REPLACE(REPLACE(REPLACE(c.name, '-', ' '), '_', ' '), ':', ' ')) = ?3 DESC,
+                     bm25(nexus_catalog_fts, 10.0, 2.0, 1.0),
+                     c.updated_time DESC
+            LIMIT ?4
+            "#,
+        )?;
+        let rows = stmt.query_map(
+            params![game_domain, match_expr, exact_name, limit],
+            NexusCatalogRecord::from_row,
+        )?;
 
         let mut results = Vec::new();
         for row in rows {
@@ -2098,7 +2262,7 @@ impl Database {
             WHERE game_domain = ?1 AND mod_id = ?2
             "#,
             params![game_domain, mod_id],
-            |row| NexusCatalogRecord::from_row(row),
+            NexusCatalogRecord::from_row,
         )
         .optional()
         .context("Failed to query catalog mod by id")
@@ -2125,7 +2289,7 @@ impl Database {
                 .map(|(i, _)| format!("?{}", i + 2))
                 .collect();
             let query = format!(
-                "SELECT * FROM mods WHERE game_id = ?1 AND nexus_mod_id IN ({})",
+                "SELECT * FROM mods WHERE game_id = ?1 AND deleted_at IS NULL AND nexus_mod_id IN ({})",
                 placeholders.join(", ")
             );
 
@@ -2141,7 +2305,7 @@ impl Database {
             let param_refs: Vec<&dyn rusqlite::types::ToSql> =
                 params_vec.iter().map(|p| p.as_ref()).collect();
 
-            let rows = stmt.query_map(param_refs.as_slice(), |row| ModRecord::from_row(row))?;
+            let rows = stmt.query_map(param_refs.as_slice(), ModRecord::from_row)?;
 
             for row in rows {
                 let record = row?;
@@ -2158,9 +2322,9 @@ impl Database {
     pub fn find_mod_by_name(&self, game_id: &str, name: &str) -> Result<Option<ModRecord>> {
         let conn = self.conn.lock().unwrap();
         conn.query_row(
-            "SELECT * FROM mods WHERE game_id = ?1 AND name = ?2 COLLATE NOCASE",
+            "SELECT * FROM mods WHERE game_id = ?1 AND name = ?2 COLLATE NOCASE AND deleted_at IS NULL",
             params![game_id, name],
-            |row| ModRecord::from_row(row),
+            ModRecord::from_row,
         )
         .optional()
         .context("Failed to query mod by name")
@@ -2316,6 +2480,900 @@ impl Database {
         Ok(())
     }
 
+    /// Migration: Add retained archive table for version rollback
+    fn migrate_mod_archives(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "mod_archives_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        tracing::info!("Applying mod archives migration");
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mod_archives (
+                id INTEGER PRIMARY KEY,
+                game_id TEXT NOT NULL,
+                mod_name TEXT NOT NULL,
+                version TEXT NOT NULL,
+                nexus_file_id INTEGER,
+                archive_path TEXT NOT NULL,
+                active INTEGER NOT NULL DEFAULT 0,
+                retained_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_mod_archives_mod ON mod_archives(game_id, mod_name);
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+
+        tracing::info!("Mod archives migration completed successfully");
+        Ok(())
+    }
+
+    /// Add the column marking queue entries as in-place updates of an installed mod
+    fn migrate_downloads_update_target(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "downloads_replaces_mod_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('downloads') WHERE name='replaces_mod'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+        if !has_column {
+            conn.execute("ALTER TABLE downloads ADD COLUMN replaces_mod TEXT", [])?;
+        }
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    /// Migration: Add user-tagged launch requirements (SKSE, ENB, ...) per mod
+    fn migrate_mod_requirements(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "mod_requirements_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        // Keyed by mod name so tags survive reinstalls and updates
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mod_requirements (
+                game_id TEXT NOT NULL,
+                mod_name TEXT NOT NULL,
+                requirement TEXT NOT NULL,
+                PRIMARY KEY (game_id, mod_name, requirement)
+            );
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    /// Migration: Add per-mod deploy exclusion patterns
+    fn migrate_mod_exclusions(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "mod_exclusions_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        // Keyed by mod name like requirements, so patterns survive reinstalls
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mod_exclusions (
+                game_id TEXT NOT NULL,
+                mod_name TEXT NOT NULL,
+                pattern TEXT NOT NULL,
+                PRIMARY KEY (game_id, mod_name, pattern)
+            );
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    /// Migration: Add pause/cancel requests for queue batches
+    fn migrate_queue_batch_controls(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "queue_batch_controls_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        // Kept in the database so another process can pause a running batch
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS queue_batch_controls (
+                batch_id TEXT PRIMARY KEY,
+                state TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    /// Migration: Add deploy/play session history for usage stats and changelogs
+    fn migrate_sessions(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "sessions_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS sessions (
+                id INTEGER PRIMARY KEY,
+                game_id TEXT NOT NULL,
+                profile TEXT,
+                kind TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                snapshot TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_sessions_game ON sessions(game_id, kind, id);
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    fn migrate_nexus_file_status(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "nexus_file_status_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS nexus_file_status (
+                mod_id INTEGER PRIMARY KEY,
+                nexus_file_id INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                replacement_file_id INTEGER,
+                replacement_name TEXT,
+                replacement_version TEXT,
+                checked_at TEXT NOT NULL,
+                FOREIGN KEY (mod_id) REFERENCES mods(id) ON DELETE CASCADE
+            );
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    fn migrate_mod_file_index(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "mod_file_index_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        // File lookups and conflict detection compare paths case-insensitively
+        conn.execute_batch(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_mod_files_path_nocase
+                ON mod_files(relative_path COLLATE NOCASE);
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    fn migrate_download_hashes(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "download_hashes_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS download_hashes (
+                path TEXT PRIMARY KEY,
+                size INTEGER NOT NULL,
+                modified INTEGER NOT NULL,
+                md5 TEXT NOT NULL,
+                game_domain TEXT,
+                nexus_mod_id INTEGER,
+                nexus_file_id INTEGER,
+                identified_at TEXT
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_download_hashes_file
+                ON download_hashes(game_domain, nexus_mod_id, nexus_file_id);
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    /// Migration: Let queue entries jump ahead of the rest of their batch
+    fn migrate_downloads_priority(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "downloads_priority_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('downloads') WHERE name='priority'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+        if !has_column {
+            conn.execute(
+                "ALTER TABLE downloads ADD COLUMN priority INTEGER DEFAULT 0",
+                [],
+            )?;
+        }
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    fn migrate_nexus_mod_pages(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "nexus_mod_pages_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS nexus_mod_pages (
+                game_domain TEXT NOT NULL,
+                nexus_mod_id INTEGER NOT NULL,
+                summary TEXT,
+                description TEXT,
+                changelog TEXT NOT NULL DEFAULT '[]',
+                fetched_at TEXT NOT NULL,
+                PRIMARY KEY (game_domain, nexus_mod_id)
+            );
+            "#,
+        )?;
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    /// Migration: Keep the version MO2's `.meta` sidecar records for an archive
+    fn migrate_download_hash_versions(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let migration_name = "download_hashes_version_v1";
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_version WHERE migration_name = ?1",
+                params![migration_name],
+                |row| {
+                    let count: i64 = row.get(0)?;
+                    Ok(count > 0)
+                },
+            )
+            .unwrap_or(false);
+
+        if already_applied {
+            return Ok(());
+        }
+
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('download_hashes') WHERE name='version'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+        if !has_column {
+            conn.execute("ALTER TABLE download_hashes ADD COLUMN version TEXT", [])?;
+        }
+
+        conn.execute(
+            "INSERT INTO schema_version (migration_name, applied_at) VALUES (?1, datetime('now'))",
+            params![migration_name],
+        )?;
+        Ok(())
+    }
+
+    // ========== Nexus File Status Operations ==========
+
+    /// Record that a mod's Nexus file is gone, or clear the flag with `None`
+    pub fn set_nexus_file_status(
+        &self,
+        mod_id: i64,
+        status: Option<&NexusFileStatusRecord>,
+    ) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM nexus_file_status WHERE mod_id = ?1",
+            params![mod_id],
+        )?;
+        if let Some(s) = status {
+            conn.execute(
+                "INSERT INTO nexus_file_status (mod_id, nexus_file_id, status, replacement_file_id, replacement_name, replacement_version, checked_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    mod_id,
+                    s.nexus_file_id,
+                    s.status,
+                    s.replacement_file_id,
+                    s.replacement_name,
+                    s.replacement_version,
+                    s.checked_at,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Unavailable-file flags for a game's mods, ignoring flags for files the
+    /// mod no longer uses
+    pub fn get_nexus_file_statuses(&self, game_id: &str) -> Result<Vec<NexusFileStatusRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT s.* FROM nexus_file_status s JOIN mods m ON m.id = s.mod_id
+             WHERE m.game_id = ?1 AND m.deleted_at IS NULL AND m.nexus_file_id = s.nexus_file_id",
+        )?;
+        let statuses = stmt
+            .query_map(params![game_id], NexusFileStatusRecord::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(statuses)
+    }
+
+    // ========== Nexus Mod Page Operations ==========
+
+    /// Cached page text for a Nexus mod
+    pub fn get_nexus_mod_page(
+        &self,
+        game_domain: &str,
+        nexus_mod_id: i64,
+    ) -> Result<Option<NexusModPageRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let record = conn
+            .query_row(
+                "SELECT * FROM nexus_mod_pages WHERE game_domain = ?1 AND nexus_mod_id = ?2",
+                params![game_domain, nexus_mod_id],
+                NexusModPageRecord::from_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// Insert or replace the cached page text for a Nexus mod
+    pub fn upsert_nexus_mod_page(&self, record: &NexusModPageRecord) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO nexus_mod_pages (game_domain, nexus_mod_id, summary, description, changelog, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                record.game_domain,
+                record.nexus_mod_id,
+                record.summary,
+                record.description,
+                record.changelog,
+                record.fetched_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ========== Download Hash Operations ==========
+
+    /// Hash recorded for an archive path
+    pub fn get_download_hash(&self, path: &str) -> Result<Option<DownloadHashRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let record = conn
+            .query_row(
+                "SELECT * FROM download_hashes WHERE path = ?1",
+                params![path],
+                DownloadHashRecord::from_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// Insert or replace the hash recorded for an archive path
+    pub fn upsert_download_hash(&self, record: &DownloadHashRecord) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO download_hashes (path, size, modified, md5, game_domain, nexus_mod_id, nexus_file_id, identified_at, version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                record.path,
+                record.size,
+                record.modified,
+                record.md5,
+                record.game_domain,
+                record.nexus_mod_id,
+                record.nexus_file_id,
+                record.identified_at,
+                record.version,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Archives on disk identified as a Nexus file
+    pub fn find_download_hashes(
+        &self,
+        game_domain: &str,
+        nexus_mod_id: i64,
+        nexus_file_id: i64,
+    ) -> Result<Vec<DownloadHashRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM download_hashes
+             WHERE game_domain = ?1 AND nexus_mod_id = ?2 AND nexus_file_id = ?3",
+        )?;
+        let records = stmt
+            .query_map(
+                params![game_domain, nexus_mod_id, nexus_file_id],
+                DownloadHashRecord::from_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(records)
+    }
+
+    /// Forget the hash of an archive that no longer exists
+    pub fn delete_download_hash(&self, path: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM download_hashes WHERE path = ?1", params![path])?;
+        Ok(())
+    }
+
+    // ========== Session Operations ==========
+
+    /// Record a deploy or play session
+    pub fn insert_session(&self, session: &SessionRecord) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO sessions (game_id, profile, kind, started_at, snapshot) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                session.game_id,
+                session.profile,
+                session.kind,
+                session.started_at,
+                session.snapshot,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// All sessions for a game (newest first)
+    pub fn get_sessions(&self, game_id: &str) -> Result<Vec<SessionRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt =
+            conn.prepare("SELECT * FROM sessions WHERE game_id = ?1 ORDER BY id DESC")?;
+        let sessions = stmt
+            .query_map(params![game_id], SessionRecord::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(sessions)
+    }
+
+    /// Most recent session of one kind for a game
+    pub fn last_session(&self, game_id: &str, kind: &str) -> Result<Option<SessionRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let session = conn
+            .query_row(
+                "SELECT * FROM sessions WHERE game_id = ?1 AND kind = ?2 ORDER BY id DESC LIMIT 1",
+                params![game_id, kind],
+                SessionRecord::from_row,
+            )
+            .optional()?;
+        Ok(session)
+    }
+
+    // ========== Activity Log Operations ==========
+
+    /// Append an entry to the activity log
+    pub fn insert_activity(&self, entry: &ActivityRecord) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO activity_log (game_id, profile, action, target, details, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.game_id,
+                entry.profile,
+                entry.action,
+                entry.target,
+                entry.details,
+                entry.created_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Newest activity log entries for a game, optionally of one action
+    pub fn get_activity(
+        &self,
+        game_id: &str,
+        action: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ActivityRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM activity_log WHERE game_id = ?1 AND (?2 IS NULL OR action = ?2) ORDER BY id DESC LIMIT ?3",
+        )?;
+        let entries = stmt
+            .query_map(
+                params![game_id, action, limit as i64],
+                ActivityRecord::from_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    // ========== Cleaned Plugin Operations ==========
+
+    /// Record a plugin cleaning
+    pub fn insert_cleaned_plugin(&self, record: &CleanedPluginRecord) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO cleaned_plugins (game_id, plugin, mod_name, crc_before, crc_after, itm, udr, nav, cleaned_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                record.game_id,
+                record.plugin,
+                record.mod_name,
+                record.crc_before,
+                record.crc_after,
+                record.itm,
+                record.udr,
+                record.nav,
+                record.cleaned_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Plugin cleanings for a game, newest first
+    pub fn get_cleaned_plugins(&self, game_id: &str) -> Result<Vec<CleanedPluginRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt =
+            conn.prepare("SELECT * FROM cleaned_plugins WHERE game_id = ?1 ORDER BY id DESC")?;
+        let records = stmt
+            .query_map(params![game_id], CleanedPluginRecord::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(records)
+    }
+
+    // ========== Mod Requirement Operations ==========
+
+    /// Tag a mod with a launch requirement
+    pub fn add_mod_requirement(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        requirement: &str,
+    ) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO mod_requirements (game_id, mod_name, requirement) VALUES (?1, ?2, ?3)",
+            params![game_id, mod_name, requirement],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a launch requirement tag from a mod
+    pub fn remove_mod_requirement(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        requirement: &str,
+    ) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM mod_requirements WHERE game_id = ?1 AND mod_name = ?2 AND requirement = ?3",
+            params![game_id, mod_name, requirement],
+        )?;
+        Ok(())
+    }
+
+    /// All tagged launch requirements for a game as (mod name, requirement)
+    pub fn get_mod_requirements(&self, game_id: &str) -> Result<Vec<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT mod_name, requirement FROM mod_requirements WHERE game_id = ?1 ORDER BY mod_name",
+        )?;
+        let rows = stmt
+            .query_map(params![game_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Add a deploy exclusion pattern to a mod
+    pub fn add_mod_exclusion(&self, game_id: &str, mod_name: &str, pattern: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO mod_exclusions (game_id, mod_name, pattern) VALUES (?1, ?2, ?3)",
+            params![game_id, mod_name, pattern],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a deploy exclusion pattern from a mod
+    pub fn remove_mod_exclusion(&self, game_id: &str, mod_name: &str, pattern: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM mod_exclusions WHERE game_id = ?1 AND mod_name = ?2 AND pattern = ?3",
+            params![game_id, mod_name, pattern],
+        )?;
+        Ok(())
+    }
+
+    /// All deploy exclusion patterns for a game as (mod name, pattern)
+    pub fn get_mod_exclusions(&self, game_id: &str) -> Result<Vec<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT mod_name, pattern FROM mod_exclusions WHERE game_id = ?1 ORDER BY mod_name, pattern",
+        )?;
+        let rows = stmt
+            .query_map(params![game_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    // ========== Retained Archive Operations ==========
+
+    /// Record a retained archive for a mod
+    pub fn insert_mod_archive(&self, archive: &ModArchiveRecord) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO mod_archives (game_id, mod_name, version, nexus_file_id, archive_path,
+                                      active, retained_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                archive.game_id,
+                archive.mod_name,
+                archive.version,
+                archive.nexus_file_id,
+                archive.archive_path,
+                archive.active as i32,
+                archive.retained_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Get retained archives for a mod (newest first)
+    pub fn get_mod_archives(&self, game_id: &str, mod_name: &str) -> Result<Vec<ModArchiveRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM mod_archives WHERE game_id = ?1 AND mod_name = ?2 ORDER BY id DESC",
+        )?;
+
+        let archives = stmt
+            .query_map(params![game_id, mod_name], ModArchiveRecord::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(archives)
+    }
+
+    /// Retained archives that are the installed version of their mod
+    pub fn get_active_mod_archives(&self, game_id: &str) -> Result<Vec<ModArchiveRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt =
+            conn.prepare("SELECT * FROM mod_archives WHERE game_id = ?1 AND active = 1")?;
+
+        let archives = stmt
+            .query_map(params![game_id], ModArchiveRecord::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(archives)
+    }
+
+    /// Mark one retained archive as the installed version of its mod
+    pub fn set_active_mod_archive(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        archive_id: i64,
+    ) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE mod_archives SET active = (id = ?3) WHERE game_id = ?1 AND mod_name = ?2",
+            params![game_id, mod_name, archive_id],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a retained archive record
+    pub fn delete_mod_archive(&self, archive_id: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM mod_archives WHERE id = ?1",
+            params![archive_id],
+        )?;
+        Ok(())
+    }
+
     /// Create a new modlist
     pub fn create_modlist(
         &self,
@@ -2448,7 +3506,7 @@ impl Database {
             conn.prepare("SELECT * FROM modlists WHERE game_id = ?1 ORDER BY updated_at DESC")?;
 
         let lists = stmt
-            .query_map(params![game_id], |row| ModlistRecord::from_row(row))?
+            .query_map(params![game_id], ModlistRecord::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(lists)
@@ -2461,7 +3519,7 @@ impl Database {
             .prepare("SELECT * FROM modlist_entries WHERE modlist_id = ?1 ORDER BY position ASC")?;
 
         let entries = stmt
-            .query_map(params![modlist_id], |row| ModlistEntryRecord::from_row(row))?
+            .query_map(params![modlist_id], ModlistEntryRecord::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(entries)
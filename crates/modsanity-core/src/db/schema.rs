@@ -218,6 +218,18 @@ pub struct InstalledFile {
     pub mod_id: i64,
 }
 
+/// A mod providing a file, from the content index
+#[derive(Debug, Clone)]
+pub struct FileProvider {
+    pub relative_path: String,
+    pub mod_id: i64,
+    pub mod_name: String,
+    pub enabled: bool,
+    pub priority: i32,
+    pub size: Option<i64>,
+    pub hash: Option<String>,
+}
+
 /// Download queue entry (extended downloads record)
 #[derive(Debug, Clone)]
 pub struct DownloadQueueEntry {
@@ -234,6 +246,10 @@ pub struct DownloadQueueEntry {
     pub import_batch_id: Option<String>,
     pub selected_file_id: Option<i64>,
     pub auto_install: bool,
+    /// Processed before the rest of its batch
+    pub priority: bool,
+    /// Installed mod this download updates in place
+    pub replaces_mod: Option<String>,
     pub downloaded: i64,
     pub size: Option<i64>,
     pub error: Option<String>,
@@ -246,7 +262,8 @@ impl DownloadQueueEntry {
     pub fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
         // Column order: id, game_id, nexus_mod_id, nexus_file_id, name, filename, url, size,
         // downloaded, status, error, started_at, completed_at, created_at,
-        // queue_position, plugin_name, match_confidence, import_batch_id, selected_file_id, auto_install
+        // queue_position, plugin_name, match_confidence, import_batch_id, selected_file_id, auto_install,
+        // replaces_mod, priority
         Ok(Self {
             id: Some(row.get(0)?),
             game_id: row.get(1)?,
@@ -273,6 +290,12 @@ impl DownloadQueueEntry {
                 .flatten()
                 .map(|v| v != 0)
                 .unwrap_or(true),
+            replaces_mod: row.get(20).ok().flatten(),
+            priority: row
+                .get::<_, Option<i32>>(21)
+                .ok()
+                .flatten()
+                .is_some_and(|v| v != 0),
         })
     }
 }
@@ -447,3 +470,207 @@ impl CatalogSyncState {
         })
     }
 }
+
+/// Retained mod archive (kept for version rollback)
+#[derive(Debug, Clone)]
+pub struct ModArchiveRecord {
+    pub id: Option<i64>,
+    pub game_id: String,
+    pub mod_name: String,
+    pub version: String,
+    pub nexus_file_id: Option<i64>,
+    pub archive_path: String,
+    pub active: bool,
+    pub retained_at: String,
+}
+
+impl ModArchiveRecord {
+    pub fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: Some(row.get(0)?),
+            game_id: row.get(1)?,
+            mod_name: row.get(2)?,
+            version: row.get(3)?,
+            nexus_file_id: row.get(4)?,
+            archive_path: row.get(5)?,
+            active: row.get::<_, i32>(6)? != 0,
+            retained_at: row.get(7)?,
+        })
+    }
+}
+
+/// A recorded deploy or play session with the setup at that moment
+#[derive(Debug, Clone)]
+pub struct SessionRecord {
+    pub id: Option<i64>,
+    pub game_id: String,
+    pub profile: Option<String>,
+    /// "deploy" or "play"
+    pub kind: String,
+    pub started_at: String,
+    /// JSON snapshot of mods and plugin order
+    pub snapshot: String,
+}
+
+impl SessionRecord {
+    pub fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: Some(row.get(0)?),
+            game_id: row.get(1)?,
+            profile: row.get(2)?,
+            kind: row.get(3)?,
+            started_at: row.get(4)?,
+            snapshot: row.get(5)?,
+        })
+    }
+}
+
+/// One entry of the activity log
+#[derive(Debug, Clone)]
+pub struct ActivityRecord {
+    pub id: Option<i64>,
+    pub game_id: String,
+    pub profile: Option<String>,
+    /// Action ID, e.g. "install" or "profile-switch"
+    pub action: String,
+    /// Mod, profile or plugin the action applied to
+    pub target: Option<String>,
+    /// JSON object with the action's parameters
+    pub details: Option<String>,
+    pub created_at: String,
+}
+
+impl ActivityRecord {
+    pub fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: Some(row.get(0)?),
+            game_id: row.get(1)?,
+            profile: row.get(2)?,
+            action: row.get(3)?,
+            target: row.get(4)?,
+            details: row.get(5)?,
+            created_at: row.get(6)?,
+        })
+    }
+}
+
+/// A plugin cleaned with xEdit QuickAutoClean
+#[derive(Debug, Clone)]
+pub struct CleanedPluginRecord {
+    pub id: Option<i64>,
+    pub game_id: String,
+    pub plugin: String,
+    /// Mod whose staged copy was cleaned; `None` for game files
+    pub mod_name: Option<String>,
+    pub crc_before: u32,
+    pub crc_after: u32,
+    pub itm: u32,
+    pub udr: u32,
+    pub nav: u32,
+    pub cleaned_at: String,
+}
+
+impl CleanedPluginRecord {
+    pub fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: Some(row.get(0)?),
+            game_id: row.get(1)?,
+            plugin: row.get(2)?,
+            mod_name: row.get(3)?,
+            crc_before: row.get(4)?,
+            crc_after: row.get(5)?,
+            itm: row.get(6)?,
+            udr: row.get(7)?,
+            nav: row.get(8)?,
+            cleaned_at: row.get(9)?,
+        })
+    }
+}
+
+/// A mod whose installed Nexus file was archived or deleted
+#[derive(Debug, Clone)]
+pub struct NexusFileStatusRecord {
+    pub mod_id: i64,
+    pub nexus_file_id: i64,
+    /// "archived" or "removed"
+    pub status: String,
+    /// Current file suggested instead, if the mod still has one
+    pub replacement_file_id: Option<i64>,
+    pub replacement_name: Option<String>,
+    pub replacement_version: Option<String>,
+    pub checked_at: String,
+}
+
+/// Cached text of a mod's Nexus page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NexusModPageRecord {
+    pub game_domain: String,
+    pub nexus_mod_id: i64,
+    pub summary: Option<String>,
+    /// BBCode as fetched
+    pub description: Option<String>,
+    /// JSON list of `{version, changes}`, newest first
+    pub changelog: String,
+    pub fetched_at: String,
+}
+
+impl NexusModPageRecord {
+    pub fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            game_domain: row.get(0)?,
+            nexus_mod_id: row.get(1)?,
+            summary: row.get(2)?,
+            description: row.get(3)?,
+            changelog: row.get(4)?,
+            fetched_at: row.get(5)?,
+        })
+    }
+}
+
+impl NexusFileStatusRecord {
+    pub fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            mod_id: row.get(0)?,
+            nexus_file_id: row.get(1)?,
+            status: row.get(2)?,
+            replacement_file_id: row.get(3)?,
+            replacement_name: row.get(4)?,
+            replacement_version: row.get(5)?,
+            checked_at: row.get(6)?,
+        })
+    }
+}
+
+/// Hash of an archive on disk and the Nexus file it was identified as
+#[derive(Debug, Clone)]
+pub struct DownloadHashRecord {
+    pub path: String,
+    pub size: i64,
+    /// Modification time (unix seconds) when hashed
+    pub modified: i64,
+    pub md5: String,
+    /// Game domain the archive was identified for
+    pub game_domain: Option<String>,
+    pub nexus_mod_id: Option<i64>,
+    pub nexus_file_id: Option<i64>,
+    /// Set once the archive was looked up on Nexus, even without a match
+    pub identified_at: Option<String>,
+    /// File version, when an MO2 `.meta` sidecar records it
+    pub version: Option<String>,
+}
+
+impl DownloadHashRecord {
+    pub fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            path: row.get(0)?,
+            size: row.get(1)?,
+            modified: row.get(2)?,
+            md5: row.get(3)?,
+            game_domain: row.get(4)?,
+            nexus_mod_id: row.get(5)?,
+            nexus_file_id: row.get(6)?,
+            identified_at: row.get(7)?,
+            version: row.get(8)?,
+        })
+    }
+}
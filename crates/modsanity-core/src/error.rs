@@ -0,0 +1,115 @@
+//! Failure categories
+//!
+//! Errors are plain `anyhow` chains. The few categories that scripts need to
+//! tell apart are recognised anywhere in the chain: [`Invalid`] marks bad
+//! input, [`Remote`] and HTTP transport failures are network errors, and
+//! [`Conflict`] or [`InstanceLocked`] mean the current state blocks the
+//! operation.
+
+use crate::lock::InstanceLocked;
+
+/// What kind of failure an error is, for the CLI exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Bad arguments, unknown names or missing required settings
+    Validation,
+    /// Nexus or another remote could not be reached or answered with an error
+    Network,
+    /// Another instance holds the lock, or something already exists
+    Conflict,
+}
+
+impl ErrorKind {
+    /// Process exit code; 1 is left for failures without a category
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::Validation => 2,
+            ErrorKind::Network => 3,
+            ErrorKind::Conflict => 4,
+        }
+    }
+
+    /// Category of `err`, from the first cause that has one
+    pub fn of(err: &anyhow::Error) -> Option<Self> {
+        err.chain().find_map(|cause| {
+            if cause.is::<Invalid>() {
+                Some(ErrorKind::Validation)
+            } else if cause.is::<Conflict>() || cause.is::<InstanceLocked>() {
+                Some(ErrorKind::Conflict)
+            } else if cause.is::<Remote>() || cause.is::<reqwest::Error>() {
+                Some(ErrorKind::Network)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Exit code for a failed command
+pub fn exit_code(err: &anyhow::Error) -> u8 {
+    ErrorKind::of(err).map_or(1, ErrorKind::exit_code)
+}
+
+/// Error types that only carry a message and a category
+macro_rules! category_error {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(message: impl Into<String>) -> Self {
+                Self(message.into())
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl std::error::Error for $name {}
+    };
+}
+
+category_error!(
+    /// Invalid input: `bail!(Invalid::new("Unknown source 'x'"))`
+    Invalid
+);
+category_error!(
+    /// The remote answered with an error status
+    Remote
+);
+category_error!(
+    /// The current state blocks the operation, e.g. the name is already taken
+    Conflict
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, bail, Context, Result};
+
+    #[test]
+    fn test_exit_codes_follow_the_chain() {
+        fn no_game() -> Result<()> {
+            bail!(Invalid::new("No game selected"))
+        }
+        let err = no_game().context("Failed to deploy").unwrap_err();
+        assert_eq!(err.to_string(), "Failed to deploy");
+        assert_eq!(exit_code(&err), 2);
+
+        let err = anyhow!(Conflict::new("Mod 'SkyUI' is already installed"));
+        assert_eq!(err.to_string(), "Mod 'SkyUI' is already installed");
+        assert_eq!(exit_code(&err), 4);
+
+        let locked = anyhow::Error::new(InstanceLocked { holder: None });
+        assert_eq!(exit_code(&locked.context("queue process")), 4);
+
+        let err = anyhow!(Remote::new("Download failed with status: 503"));
+        assert_eq!(exit_code(&err.context("Failed to download SkyUI")), 3);
+
+        assert_eq!(exit_code(&anyhow!("disk full")), 1);
+    }
+}
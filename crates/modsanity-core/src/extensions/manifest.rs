@@ -0,0 +1,114 @@
+//! Extension manifest (`extension.toml`) parsing
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Manifest file name expected in every extension directory
+pub const MANIFEST_FILE: &str = "extension.toml";
+
+/// Protocol version spoken by this build of ModSanity
+pub const EXTENSION_API_VERSION: u32 = 1;
+
+/// Parsed `extension.toml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtensionManifest {
+    /// Unique extension name (used on the command line)
+    pub name: String,
+
+    #[serde(default)]
+    pub version: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    /// Executable to run, relative to the extension directory
+    pub executable: String,
+
+    /// Protocol version the extension was written against
+    #[serde(default = "default_api_version")]
+    pub api_version: u32,
+
+    /// Commands exposed to the CLI and TUI
+    #[serde(default)]
+    pub commands: Vec<CommandSpec>,
+
+    /// Read-only text screens shown in the TUI Extensions screen
+    #[serde(default)]
+    pub screens: Vec<ScreenSpec>,
+
+    /// Name matchers consulted during modlist import
+    #[serde(default)]
+    pub matchers: Vec<MatcherSpec>,
+}
+
+fn default_api_version() -> u32 {
+    EXTENSION_API_VERSION
+}
+
+/// Extension command declaration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandSpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Extension screen declaration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenSpec {
+    pub name: String,
+    #[serde(default)]
+    pub title: String,
+}
+
+/// Extension matcher declaration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatcherSpec {
+    pub name: String,
+    /// Game IDs this matcher applies to (empty = all games)
+    #[serde(default)]
+    pub games: Vec<String>,
+}
+
+impl MatcherSpec {
+    /// Whether this matcher should run for the given game
+    pub fn applies_to(&self, game_id: &str) -> bool {
+        self.games.is_empty() || self.games.iter().any(|g| g.eq_ignore_ascii_case(game_id))
+    }
+}
+
+impl ExtensionManifest {
+    /// Parse a manifest from TOML text
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(content).context("Invalid extension manifest")?;
+
+        if manifest.name.trim().is_empty() {
+            bail!("Extension manifest is missing a name");
+        }
+        if manifest.executable.trim().is_empty() {
+            bail!(
+                "Extension '{}' does not declare an executable",
+                manifest.name
+            );
+        }
+        if manifest.api_version > EXTENSION_API_VERSION {
+            bail!(
+                "Extension '{}' requires API version {} (supported: {})",
+                manifest.name,
+                manifest.api_version,
+                EXTENSION_API_VERSION
+            );
+        }
+
+        Ok(manifest)
+    }
+
+    /// Load the manifest from an extension directory
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(MANIFEST_FILE);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content)
+    }
+}
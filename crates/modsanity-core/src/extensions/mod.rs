@@ -0,0 +1,424 @@
+//! Extensions - third-party commands, screens and matchers
+//!
+//! An extension is a directory containing an `extension.toml` manifest and an
+//! executable. Extensions are discovered from `~/.local/share/modsanity/extensions/`
+//! and `~/.modsanity/extensions/`; the executable is spawned once per request,
+//! reads one [`ExtensionRequest`] as JSON on stdin and answers with one
+//! [`ExtensionResponse`] on stdout.
+
+mod manifest;
+mod protocol;
+
+pub use manifest::*;
+pub use protocol::*;
+
+use crate::config::Config;
+use crate::games::Game;
+use crate::import::matcher::MatchedMod;
+use crate::import::{MatchConfidence, MatchResult, PluginEntry};
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// How long an extension may run before it is killed
+const EXTENSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A discovered extension
+#[derive(Debug, Clone)]
+pub struct Extension {
+    pub manifest: ExtensionManifest,
+    pub dir: PathBuf,
+}
+
+impl Extension {
+    /// Extension name from its manifest
+    pub fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    /// Absolute path of the extension executable
+    pub fn executable(&self) -> PathBuf {
+        self.dir.join(&self.manifest.executable)
+    }
+
+    /// Spawn the extension and exchange one request/response pair
+    pub async fn invoke(&self, request: &ExtensionRequest) -> Result<ExtensionResponse> {
+        let executable = self.executable();
+        if !executable.exists() {
+            bail!(
+                "Extension '{}' executable not found: {}",
+                self.name(),
+                executable.display()
+            );
+        }
+
+        let payload = serde_json::to_string(request).context("Failed to encode request")?;
+
+        let mut child = tokio::process::Command::new(&executable)
+            .current_dir(&self.dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start extension '{}'", self.name()))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(payload.as_bytes()).await?;
+            stdin.write_all(b"\n").await?;
+        }
+
+        let output = tokio::time::timeout(EXTENSION_TIMEOUT, child.wait_with_output())
+            .await
+            .with_context(|| format!("Extension '{}' timed out", self.name()))??;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        for line in stderr.lines().filter(|l| !l.trim().is_empty()) {
+            tracing::info!("[ext:{}] {}", self.name(), line);
+        }
+
+        if !output.status.success() {
+            bail!(
+                "Extension '{}' exited with {}: {}",
+                self.name(),
+                output.status,
+                stderr.trim()
+            );
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let response: ExtensionResponse = serde_json::from_str(stdout.trim())
+            .with_context(|| format!("Extension '{}' returned invalid JSON", self.name()))?;
+
+        if let Some(error) = &response.error {
+            bail!("Extension '{}' failed: {}", self.name(), error);
+        }
+
+        Ok(response)
+    }
+}
+
+/// All extensions found on disk, plus any manifests that failed to load
+#[derive(Debug, Clone, Default)]
+pub struct ExtensionRegistry {
+    extensions: Vec<Extension>,
+    errors: Vec<(PathBuf, String)>,
+}
+
+impl ExtensionRegistry {
+    /// Discover extensions in the configured extension directories
+    pub fn discover_from_config(config: &Config) -> Self {
+        Self::discover(&config.extension_dirs())
+    }
+
+    /// Discover extensions in the given directories.
+    ///
+    /// Each immediate subdirectory with an `extension.toml` is one extension.
+    /// When two directories provide the same name, the first one wins.
+    pub fn discover(dirs: &[PathBuf]) -> Self {
+        let mut registry = Self::default();
+
+        for root in dirs {
+            let Ok(entries) = std::fs::read_dir(root) else {
+                continue;
+            };
+
+            let mut candidates: Vec<PathBuf> = entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.join(MANIFEST_FILE).is_file())
+                .collect();
+            candidates.sort();
+
+            for dir in candidates {
+                match ExtensionManifest::load(&dir) {
+                    Ok(manifest) => {
+                        if registry.get(&manifest.name).is_some() {
+                            tracing::warn!(
+                                "Skipping duplicate extension '{}' in {}",
+                                manifest.name,
+                                dir.display()
+                            );
+                            continue;
+                        }
+                        registry.extensions.push(Extension { manifest, dir });
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to load extension in {}: {:#}", dir.display(), e);
+                        registry.errors.push((dir, format!("{:#}", e)));
+                    }
+                }
+            }
+        }
+
+        registry
+    }
+
+    /// Loaded extensions, in discovery order
+    pub fn extensions(&self) -> &[Extension] {
+        &self.extensions
+    }
+
+    /// Extension directories that could not be loaded, with the reason
+    pub fn errors(&self) -> &[(PathBuf, String)] {
+        &self.errors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    /// Find an extension by name (case-insensitive)
+    pub fn get(&self, name: &str) -> Option<&Extension> {
+        self.extensions
+            .iter()
+            .find(|e| e.manifest.name.eq_ignore_ascii_case(name))
+    }
+
+    /// All declared screens as (extension, screen) pairs
+    pub fn screens(&self) -> Vec<(&Extension, &ScreenSpec)> {
+        self.extensions
+            .iter()
+            .flat_map(|e| e.manifest.screens.iter().map(move |s| (e, s)))
+            .collect()
+    }
+
+    /// All declared commands as (extension, command) pairs
+    pub fn commands(&self) -> Vec<(&Extension, &CommandSpec)> {
+        self.extensions
+            .iter()
+            .flat_map(|e| e.manifest.commands.iter().map(move |c| (e, c)))
+            .collect()
+    }
+
+    /// Run a command declared by an extension
+    pub async fn run_command(
+        &self,
+        extension: &str,
+        command: &str,
+        args: &[String],
+        context: ExtensionContext,
+    ) -> Result<ExtensionResponse> {
+        let ext = self
+            .get(extension)
+            .ok_or_else(|| anyhow::anyhow!("Extension '{}' not found", extension))?;
+        if !ext.manifest.commands.iter().any(|c| c.name == command) {
+            bail!("Extension '{}' has no command '{}'", ext.name(), command);
+        }
+
+        ext.invoke(&ExtensionRequest::Command {
+            command: command.to_string(),
+            args: args.to_vec(),
+            context,
+        })
+        .await
+    }
+
+    /// Render a screen declared by an extension
+    pub async fn render_screen(
+        &self,
+        extension: &str,
+        screen: &str,
+        context: ExtensionContext,
+    ) -> Result<ExtensionResponse> {
+        let ext = self
+            .get(extension)
+            .ok_or_else(|| anyhow::anyhow!("Extension '{}' not found", extension))?;
+
+        ext.invoke(&ExtensionRequest::Screen {
+            screen: screen.to_string(),
+            context,
+        })
+        .await
+    }
+
+    /// Ask extension matchers for a plugin that built-in matching could not resolve.
+    ///
+    /// Returns the best candidate across all matchers for the game, if any.
+    pub async fn match_plugin(
+        &self,
+        plugin: &PluginEntry,
+        context: &ExtensionContext,
+    ) -> Option<MatchResult> {
+        let game_id = context.game_id.as_deref().unwrap_or_default();
+        let query = plugin.extract_mod_name();
+        let mut best: Option<ExtensionMatch> = None;
+
+        for ext in &self.extensions {
+            for matcher in ext
+                .manifest
+                .matchers
+                .iter()
+                .filter(|m| m.applies_to(game_id))
+            {
+                let request = ExtensionRequest::Match {
+                    matcher: matcher.name.clone(),
+                    query: query.clone(),
+                    plugin: Some(plugin.plugin_name.clone()),
+                    context: context.clone(),
+                };
+                match ext.invoke(&request).await {
+                    Ok(response) => {
+                        for candidate in response.matches {
+                            if best
+                                .as_ref()
+                                .is_none_or(|b| candidate.confidence > b.confidence)
+                            {
+                                best = Some(candidate);
+                            }
+                        }
+                    }
+                    Err(e) => tracing::warn!("Extension matcher {}: {:#}", matcher.name, e),
+                }
+            }
+        }
+
+        let best = best.filter(|b| b.mod_id > 0)?;
+        Some(MatchResult {
+            plugin: plugin.clone(),
+            mod_name: query,
+            confidence: confidence_for(best.confidence),
+            best_match: Some(MatchedMod {
+                mod_id: best.mod_id,
+                name: best.name,
+                author: best.author,
+                summary: best.summary,
+                downloads: 0,
+                version: best.version,
+            }),
+            alternatives: Vec::new(),
+        })
+    }
+}
+
+impl ExtensionContext {
+    /// Build the request context from the current config and active game
+    pub fn new(config: &Config, game: Option<&Game>) -> Self {
+        Self {
+            api_version: EXTENSION_API_VERSION,
+            modsanity_version: crate::CORE_VERSION.to_string(),
+            game_id: game.map(|g| g.id.clone()),
+            game_path: game.map(|g| path_string(&g.install_path)),
+            staging_dir: game
+                .and_then(|g| config.game_staging_dir(&g.id).ok())
+                .map(|dir| path_string(&dir)),
+            data_dir: path_string(&config.paths.data_dir()),
+        }
+    }
+}
+
+/// Map an extension-reported score onto the importer's confidence bands
+fn confidence_for(score: f32) -> MatchConfidence {
+    let score = score.clamp(0.0, 1.0);
+    if score > 0.8 {
+        MatchConfidence::High(score)
+    } else if score >= 0.6 {
+        MatchConfidence::Medium(score)
+    } else {
+        MatchConfidence::Low(score)
+    }
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn write_extension(root: &Path, name: &str, script: &str) -> PathBuf {
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(MANIFEST_FILE),
+            format!(
+                "name = \"{}\"\nexecutable = \"run.sh\"\n\n[[commands]]\nname = \"hello\"\n",
+                name
+            ),
+        )
+        .unwrap();
+        let exe = dir.join("run.sh");
+        std::fs::write(&exe, script).unwrap();
+        std::fs::set_permissions(&exe, std::fs::Permissions::from_mode(0o755)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_manifest_parse() {
+        let manifest = ExtensionManifest::parse(
+            r#"
+            name = "anomaly"
+            executable = "bin/anomaly"
+
+            [[screens]]
+            name = "status"
+            title = "Anomaly Status"
+
+            [[matchers]]
+            name = "moddb"
+            games = ["stalkeranomaly"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.name, "anomaly");
+        assert_eq!(manifest.api_version, EXTENSION_API_VERSION);
+        assert_eq!(manifest.screens[0].title, "Anomaly Status");
+        assert!(manifest.matchers[0].applies_to("StalkerAnomaly"));
+        assert!(!manifest.matchers[0].applies_to("skyrimse"));
+
+        assert!(ExtensionManifest::parse("name = \"x\"\nexecutable = \"\"").is_err());
+        assert!(
+            ExtensionManifest::parse("name = \"x\"\nexecutable = \"a\"\napi_version = 99").is_err()
+        );
+    }
+
+    #[test]
+    fn test_discover_skips_invalid() {
+        let temp = tempfile::tempdir().unwrap();
+        write_extension(temp.path(), "good", "#!/bin/sh\n");
+        let broken = temp.path().join("broken");
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::write(broken.join(MANIFEST_FILE), "not = [valid").unwrap();
+
+        let registry = ExtensionRegistry::discover(&[temp.path().to_path_buf()]);
+        assert_eq!(registry.extensions().len(), 1);
+        assert_eq!(registry.errors().len(), 1);
+        assert!(registry.get("GOOD").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_run_command_roundtrip() {
+        let temp = tempfile::tempdir().unwrap();
+        write_extension(
+            temp.path(),
+            "echoer",
+            "#!/bin/sh\ncat > /dev/null\necho '{\"message\":\"ok\",\"lines\":[\"hello\"]}'\n",
+        );
+        write_extension(
+            temp.path(),
+            "failing",
+            "#!/bin/sh\ncat > /dev/null\necho '{\"error\":\"boom\"}'\n",
+        );
+
+        let registry = ExtensionRegistry::discover(&[temp.path().to_path_buf()]);
+        let response = registry
+            .run_command("echoer", "hello", &[], ExtensionContext::default())
+            .await
+            .unwrap();
+        assert_eq!(response.lines, vec!["hello".to_string()]);
+
+        assert!(registry
+            .run_command("echoer", "missing", &[], ExtensionContext::default())
+            .await
+            .is_err());
+        assert!(registry
+            .run_command("failing", "hello", &[], ExtensionContext::default())
+            .await
+            .is_err());
+    }
+}
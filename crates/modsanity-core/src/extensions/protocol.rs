@@ -0,0 +1,69 @@
+//! Extension process protocol
+//!
+//! ModSanity spawns the extension executable, writes a single JSON request to
+//! its stdin and reads a single JSON response from its stdout. Anything the
+//! extension prints to stderr is logged.
+
+use serde::{Deserialize, Serialize};
+
+/// Information about the running ModSanity instance passed to every request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtensionContext {
+    pub api_version: u32,
+    pub modsanity_version: String,
+    pub game_id: Option<String>,
+    pub game_path: Option<String>,
+    pub staging_dir: Option<String>,
+    pub data_dir: String,
+}
+
+/// Request sent to an extension
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExtensionRequest {
+    /// Run a declared command
+    Command {
+        command: String,
+        args: Vec<String>,
+        context: ExtensionContext,
+    },
+    /// Render a declared screen
+    Screen {
+        screen: String,
+        context: ExtensionContext,
+    },
+    /// Suggest Nexus mods for an unmatched modlist entry
+    Match {
+        matcher: String,
+        query: String,
+        plugin: Option<String>,
+        context: ExtensionContext,
+    },
+}
+
+/// Response returned by an extension
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtensionResponse {
+    /// Short status message
+    pub message: Option<String>,
+    /// Output lines (command output or screen body)
+    pub lines: Vec<String>,
+    /// Match candidates (for `match` requests)
+    pub matches: Vec<ExtensionMatch>,
+    /// Set when the request failed
+    pub error: Option<String>,
+}
+
+/// Match candidate suggested by an extension matcher
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtensionMatch {
+    pub mod_id: i64,
+    pub name: String,
+    pub author: String,
+    pub summary: String,
+    pub version: String,
+    /// Confidence from 0.0 to 1.0
+    pub confidence: f32,
+}
@@ -0,0 +1,275 @@
+//! User game definitions (`games.d/*.toml`)
+//!
+//! A definition describes a game that isn't built in, or a variant of one,
+//! on top of a built-in `base` game whose plugin, load order and script
+//! extender handling it shares. Everything else (name, Nexus domain, Steam
+//! app, folders, executable) comes from the file:
+//!
+//! ```toml
+//! id = "skyrimtogether"
+//! name = "Skyrim Together"
+//! base = "skyrimse"
+//! nexus_domain = "skyrimspecialedition"
+//! steam_app_id = 489830
+//! steam_folder = "Skyrim Together"
+//! data_path = "Data"
+//! plugins_txt = "Skyrim Together/Plugins.txt"
+//! executable = "SkyrimTogether.exe"
+//! ```
+
+use super::{steam, Game, GameDetector, GamePlatform, GameType};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// AppData/Local inside a Proton prefix
+const PREFIX_LOCAL_APPDATA: &str = "pfx/drive_c/users/steamuser/AppData/Local";
+
+/// A game described by a `games.d/*.toml` file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GameDefinition {
+    /// Short identifier used for profiles, mods and `game select`
+    pub id: String,
+
+    /// Display name
+    pub name: String,
+
+    /// Built-in game this one behaves like (e.g. "skyrimse")
+    pub base: String,
+
+    /// NexusMods game domain (defaults to the base game's)
+    pub nexus_domain: Option<String>,
+
+    /// Numeric NexusMods game ID (defaults to the base game's when the
+    /// domain isn't overridden)
+    pub nexus_id: Option<i64>,
+
+    /// Steam App ID, used to find the Proton prefix (defaults to the base game's)
+    pub steam_app_id: Option<u32>,
+
+    /// Folder under `steamapps/common` in any Steam library
+    pub steam_folder: Option<String>,
+
+    /// Install folder for games outside Steam
+    pub install_path: Option<PathBuf>,
+
+    /// Data folder, relative to the install folder
+    pub data_path: Option<String>,
+
+    /// plugins.txt location: absolute, or relative to AppData/Local in the
+    /// Proton prefix. loadorder.txt is expected next to it.
+    pub plugins_txt: Option<PathBuf>,
+
+    /// Game executable, relative to the install folder
+    pub executable: Option<String>,
+}
+
+impl GameDefinition {
+    /// Parse and validate a definition
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let definition: Self = toml::from_str(content)?;
+        if definition.id.trim().is_empty() || definition.id.contains(char::is_whitespace) {
+            anyhow::bail!("id must be a single word");
+        }
+        if GameType::from_id(&definition.id).is_some() {
+            anyhow::bail!("id '{}' is already a built-in game", definition.id);
+        }
+        if definition.base_type().is_none() {
+            anyhow::bail!("unknown base game '{}'", definition.base);
+        }
+        Ok(definition)
+    }
+
+    /// Load every `*.toml` definition in `dir`, in file name order. Invalid
+    /// files and duplicate ids are skipped with a warning.
+    pub fn load_dir(dir: &Path) -> Vec<Self> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        files.sort();
+
+        let mut definitions: Vec<Self> = Vec::new();
+        for path in files {
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Self::parse(&content));
+            match parsed {
+                Ok(definition) => {
+                    if definitions
+                        .iter()
+                        .any(|d| d.id.eq_ignore_ascii_case(&definition.id))
+                    {
+                        tracing::warn!(
+                            "Ignoring {}: game '{}' is already defined",
+                            path.display(),
+                            definition.id
+                        );
+                    } else {
+                        definitions.push(definition);
+                    }
+                }
+                Err(e) => tracing::warn!("Ignoring game definition {}: {}", path.display(), e),
+            }
+        }
+        definitions
+    }
+
+    /// The built-in game this definition is based on
+    pub fn base_type(&self) -> Option<GameType> {
+        GameType::from_id(&self.base)
+    }
+
+    /// The game installed at `install_path`, without a Proton prefix
+    pub fn game(&self, install_path: PathBuf) -> Game {
+        let base = self.base_type().unwrap_or(GameType::SkyrimSE);
+        let mut game = Game::new(base, install_path);
+        game.id = self.id.clone();
+        game.name = self.name.clone();
+        if let Some(domain) = &self.nexus_domain {
+            game.nexus_game_id = domain.clone();
+            game.nexus_numeric_id = self.nexus_id.unwrap_or(0);
+        } else if let Some(nexus_id) = self.nexus_id {
+            game.nexus_numeric_id = nexus_id;
+        }
+        if let Some(app_id) = self.steam_app_id {
+            game.steam_app_id = app_id;
+        }
+        if let Some(data_path) = &self.data_path {
+            game.data_path = game.install_path.join(data_path);
+        }
+        if let Some(executable) = &self.executable {
+            game.executable = executable.clone();
+        }
+        if let Some(plugins_txt) = self.plugins_txt.as_ref().filter(|p| p.is_absolute()) {
+            game.set_plugins_txt(plugins_txt.clone());
+        }
+        game
+    }
+
+    /// Set up the game's Proton paths, resolving a relative plugins.txt
+    /// inside the prefix
+    pub fn with_proton_prefix(&self, game: Game, prefix: PathBuf) -> Game {
+        let mut game = game.with_proton_prefix(prefix.clone());
+        if let Some(plugins_txt) = self.plugins_txt.as_ref().filter(|p| p.is_relative()) {
+            game.set_plugins_txt(prefix.join(PREFIX_LOCAL_APPDATA).join(plugins_txt));
+        }
+        game
+    }
+
+    /// Installs of this game in any Steam library or at `install_path`
+    pub fn detect(&self, libraries: &[PathBuf]) -> Vec<Game> {
+        let mut games = Vec::new();
+        if let Some(folder) = &self.steam_folder {
+            for steamapps in libraries {
+                let install_path = steamapps.join("common").join(folder);
+                if !install_path.exists() {
+                    continue;
+                }
+                let game = self.game(install_path);
+                let prefix = steam::find_compatdata(game.steam_app_id, Some(steamapps), libraries);
+                games.push(self.finish(game, prefix));
+            }
+        }
+        if let Some(install_path) = self.install_path.as_ref().filter(|p| p.exists()) {
+            let game = self
+                .game(install_path.clone())
+                .with_platform(GamePlatform::Manual);
+            let prefix = GameDetector::infer_prefix_from_install_path(install_path);
+            games.push(self.finish(game, prefix));
+        }
+        games
+    }
+
+    fn finish(&self, game: Game, prefix: Option<PathBuf>) -> Game {
+        match prefix {
+            Some(prefix) => self.with_proton_prefix(game, prefix),
+            None => game,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition_game_in_steam_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let steamapps = dir.path().join("steamapps");
+        let install = steamapps.join("common/Skyrim Together");
+        std::fs::create_dir_all(&install).unwrap();
+        std::fs::create_dir_all(steamapps.join("compatdata/489830")).unwrap();
+
+        let definition = GameDefinition::parse(
+            r#"
+id = "skyrimtogether"
+name = "Skyrim Together"
+base = "skyrimse"
+steam_folder = "Skyrim Together"
+data_path = "Game/Data"
+plugins_txt = "Skyrim Together/Plugins.txt"
+executable = "SkyrimTogether.exe"
+"#,
+        )
+        .unwrap();
+
+        let games = definition.detect(std::slice::from_ref(&steamapps));
+        assert_eq!(games.len(), 1);
+        let game = &games[0];
+        assert_eq!(game.id, "skyrimtogether");
+        assert_eq!(game.game_type, GameType::SkyrimSE);
+        assert_eq!(game.nexus_game_id, "skyrimspecialedition");
+        assert_eq!(game.nexus_numeric_id, 1704);
+        assert_eq!(game.data_path, install.join("Game/Data"));
+        assert_eq!(game.executable, "SkyrimTogether.exe");
+        let local = steamapps
+            .join("compatdata/489830")
+            .join(PREFIX_LOCAL_APPDATA);
+        assert_eq!(
+            game.plugins_txt_path,
+            Some(local.join("Skyrim Together/Plugins.txt"))
+        );
+        assert_eq!(
+            game.loadorder_txt_path,
+            Some(local.join("Skyrim Together/loadorder.txt"))
+        );
+    }
+
+    #[test]
+    fn test_load_dir_skips_invalid_definitions() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.toml"),
+            "id = \"newgame\"\nname = \"New Game\"\nbase = \"fallout4\"\nnexus_domain = \"newgame\"\nnexus_id = 9999\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.toml"),
+            "id = \"newgame\"\nname = \"Duplicate\"\nbase = \"fallout4\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("c.toml"),
+            "id = \"skyrimse\"\nname = \"Shadowed\"\nbase = \"skyrimse\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("d.toml"),
+            "id = \"other\"\nname = \"Other\"\nbase = \"unknown\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a definition").unwrap();
+
+        let definitions = GameDefinition::load_dir(dir.path());
+        assert_eq!(definitions.len(), 1);
+        let game = definitions[0].game(dir.path().join("New Game"));
+        assert_eq!(game.name, "New Game");
+        assert_eq!(game.nexus_game_id, "newgame");
+        assert_eq!(game.nexus_numeric_id, 9999);
+        assert_eq!(game.game_type, GameType::Fallout4);
+    }
+}
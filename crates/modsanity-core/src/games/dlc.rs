@@ -0,0 +1,269 @@
+//! Official DLC detection
+//!
+//! Each official DLC ships one master plugin in the game's Data folder, so
+//! a DLC counts as installed when that plugin is there.
+
+use super::{Game, GameType};
+use std::collections::HashSet;
+
+/// An official DLC and the plugin it ships
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dlc {
+    pub name: &'static str,
+    pub plugin: &'static str,
+}
+
+const SKYRIM_DLC: &[Dlc] = &[
+    Dlc {
+        name: "Dawnguard",
+        plugin: "Dawnguard.esm",
+    },
+    Dlc {
+        name: "Hearthfire",
+        plugin: "HearthFires.esm",
+    },
+    Dlc {
+        name: "Dragonborn",
+        plugin: "Dragonborn.esm",
+    },
+];
+
+const FALLOUT4_DLC: &[Dlc] = &[
+    Dlc {
+        name: "Automatron",
+        plugin: "DLCRobot.esm",
+    },
+    Dlc {
+        name: "Wasteland Workshop",
+        plugin: "DLCworkshop01.esm",
+    },
+    Dlc {
+        name: "Far Harbor",
+        plugin: "DLCCoast.esm",
+    },
+    Dlc {
+        name: "Contraptions Workshop",
+        plugin: "DLCworkshop02.esm",
+    },
+    Dlc {
+        name: "Vault-Tec Workshop",
+        plugin: "DLCworkshop03.esm",
+    },
+    Dlc {
+        name: "Nuka-World",
+        plugin: "DLCNukaWorld.esm",
+    },
+    Dlc {
+        name: "High Resolution Texture Pack",
+        plugin: "DLCUltraHighResolution.esm",
+    },
+];
+
+const STARFIELD_DLC: &[Dlc] = &[Dlc {
+    name: "Shattered Space",
+    plugin: "ShatteredSpace.esm",
+}];
+
+const FALLOUT3_DLC: &[Dlc] = &[
+    Dlc {
+        name: "Operation: Anchorage",
+        plugin: "Anchorage.esm",
+    },
+    Dlc {
+        name: "The Pitt",
+        plugin: "ThePitt.esm",
+    },
+    Dlc {
+        name: "Broken Steel",
+        plugin: "BrokenSteel.esm",
+    },
+    Dlc {
+        name: "Point Lookout",
+        plugin: "PointLookout.esm",
+    },
+    Dlc {
+        name: "Mothership Zeta",
+        plugin: "Zeta.esm",
+    },
+];
+
+const FALLOUT_NV_DLC: &[Dlc] = &[
+    Dlc {
+        name: "Dead Money",
+        plugin: "DeadMoney.esm",
+    },
+    Dlc {
+        name: "Honest Hearts",
+        plugin: "HonestHearts.esm",
+    },
+    Dlc {
+        name: "Old World Blues",
+        plugin: "OldWorldBlues.esm",
+    },
+    Dlc {
+        name: "Lonesome Road",
+        plugin: "LonesomeRoad.esm",
+    },
+    Dlc {
+        name: "Gun Runners' Arsenal",
+        plugin: "GunRunnersArsenal.esm",
+    },
+    Dlc {
+        name: "Courier's Stash",
+        plugin: "CaravanPack.esm",
+    },
+];
+
+const OBLIVION_DLC: &[Dlc] = &[
+    Dlc {
+        name: "Shivering Isles",
+        plugin: "DLCShiveringIsles.esp",
+    },
+    Dlc {
+        name: "Knights of the Nine",
+        plugin: "Knights.esp",
+    },
+    Dlc {
+        name: "Battlehorn Castle",
+        plugin: "DLCBattlehornCastle.esp",
+    },
+    Dlc {
+        name: "Frostcrag Spire",
+        plugin: "DLCFrostcrag.esp",
+    },
+    Dlc {
+        name: "Horse Armor Pack",
+        plugin: "DLCHorseArmor.esp",
+    },
+    Dlc {
+        name: "Mehrunes' Razor",
+        plugin: "DLCMehrunesRazor.esp",
+    },
+    Dlc {
+        name: "The Orrery",
+        plugin: "DLCOrrery.esp",
+    },
+    Dlc {
+        name: "Spell Tomes",
+        plugin: "DLCSpellTomes.esp",
+    },
+    Dlc {
+        name: "Thieves Den",
+        plugin: "DLCThievesDen.esp",
+    },
+    Dlc {
+        name: "Vile Lair",
+        plugin: "DLCVileLair.esp",
+    },
+];
+
+const MORROWIND_DLC: &[Dlc] = &[
+    Dlc {
+        name: "Tribunal",
+        plugin: "Tribunal.esm",
+    },
+    Dlc {
+        name: "Bloodmoon",
+        plugin: "Bloodmoon.esm",
+    },
+];
+
+impl GameType {
+    /// Official DLC released for this game. Fallout 4 VR ships its DLC
+    /// merged into the base game and Oblivion Remastered folds its DLC into
+    /// the base masters, so they have none to detect.
+    pub fn dlc(&self) -> &'static [Dlc] {
+        match self {
+            GameType::SkyrimSE | GameType::SkyrimVR => SKYRIM_DLC,
+            GameType::Fallout4 => FALLOUT4_DLC,
+            GameType::Fallout4VR => &[],
+            GameType::Starfield => STARFIELD_DLC,
+            GameType::Fallout3 => FALLOUT3_DLC,
+            GameType::FalloutNV => FALLOUT_NV_DLC,
+            GameType::Oblivion => OBLIVION_DLC,
+            GameType::OblivionRemastered => &[],
+            GameType::Morrowind | GameType::OpenMW => MORROWIND_DLC,
+            GameType::Enderal | GameType::EnderalSE => &[],
+        }
+    }
+}
+
+/// The DLC that ships `plugin`, if it is one
+pub fn dlc_for_plugin(game_type: GameType, plugin: &str) -> Option<&'static Dlc> {
+    game_type
+        .dlc()
+        .iter()
+        .find(|d| d.plugin.eq_ignore_ascii_case(plugin))
+}
+
+/// The DLC Nexus lists as `name` ("Dawnguard", "Fallout 4: Far Harbor", ...)
+pub fn dlc_by_name(game_type: GameType, name: &str) -> Option<&'static Dlc> {
+    let key = |s: &str| {
+        s.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase()
+    };
+    let wanted = key(name);
+    if wanted.is_empty() {
+        return None;
+    }
+    game_type.dlc().iter().find(|d| {
+        let known = key(d.name);
+        wanted.contains(&known) || known.contains(&wanted)
+    })
+}
+
+/// Official DLC present in `game`'s Data folder, or `None` when the folder
+/// can't be read (and so nothing is known either way)
+pub fn installed_dlc(game: &Game) -> Option<Vec<&'static Dlc>> {
+    let present: HashSet<String> = std::fs::read_dir(&game.data_path)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_lowercase())
+        .collect();
+    Some(
+        game.game_type
+            .dlc()
+            .iter()
+            .filter(|d| present.contains(&d.plugin.to_lowercase()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_match_dlc() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut game = Game::new(GameType::SkyrimSE, dir.path().to_path_buf());
+        assert_eq!(installed_dlc(&game), None);
+
+        std::fs::create_dir_all(&game.data_path).unwrap();
+        std::fs::write(game.data_path.join("dawnguard.esm"), b"").unwrap();
+        std::fs::write(game.data_path.join("Skyrim.esm"), b"").unwrap();
+        let installed = installed_dlc(&game).unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].name, "Dawnguard");
+
+        assert_eq!(
+            dlc_for_plugin(GameType::SkyrimSE, "hearthfires.esm").map(|d| d.name),
+            Some("Hearthfire")
+        );
+        assert_eq!(dlc_for_plugin(GameType::SkyrimSE, "Skyrim.esm"), None);
+        assert_eq!(
+            dlc_by_name(GameType::Fallout4, "Fallout 4: Far Harbor").map(|d| d.plugin),
+            Some("DLCCoast.esm")
+        );
+        assert_eq!(
+            dlc_by_name(GameType::SkyrimSE, "HearthFires").map(|d| d.name),
+            Some("Hearthfire")
+        );
+        assert_eq!(dlc_by_name(GameType::SkyrimSE, "Far Harbor"), None);
+
+        game.game_type = GameType::Fallout4VR;
+        assert_eq!(installed_dlc(&game), Some(Vec::new()));
+    }
+}
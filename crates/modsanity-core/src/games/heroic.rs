@@ -0,0 +1,156 @@
+//! Heroic Games Launcher (GOG) installs
+//!
+//! Heroic records the GOG games it installed in `gog_store/installed.json`
+//! and each game's Wine settings in `GamesConfig/<appName>.json`. A prefix
+//! Heroic runs through Proton has the usual `pfx` folder and is used as the
+//! game's Proton prefix; a plain Wine prefix is not.
+
+use super::{Game, GamePlatform, GameType};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Games with a GOG release, matched by their executable
+const GOG_GAMES: &[GameType] = &[
+    GameType::SkyrimSE,
+    GameType::Fallout4,
+    GameType::Fallout3,
+    GameType::FalloutNV,
+    GameType::Oblivion,
+    GameType::Morrowind,
+];
+
+#[derive(Deserialize)]
+struct InstalledList {
+    #[serde(default)]
+    installed: Vec<InstalledGame>,
+}
+
+#[derive(Deserialize)]
+struct InstalledGame {
+    #[serde(rename = "appName")]
+    app_name: String,
+    install_path: PathBuf,
+    #[serde(default)]
+    platform: String,
+}
+
+/// Heroic config folders that exist: native and Flatpak
+pub fn config_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .unwrap_or_else(|| home.join(".config"));
+    [
+        config.join("heroic"),
+        home.join(".var/app/com.heroicgameslauncher.hgl/config/heroic"),
+    ]
+    .into_iter()
+    .filter(|dir| dir.exists())
+    .collect()
+}
+
+/// Supported games installed through the Heroic config in `heroic_dir`
+pub fn detect_games(heroic_dir: &Path) -> Vec<Game> {
+    let path = heroic_dir.join("gog_store/installed.json");
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let list: InstalledList = match serde_json::from_str(&content) {
+        Ok(list) => list,
+        Err(e) => {
+            tracing::warn!("Failed to parse {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+
+    let mut games = Vec::new();
+    for entry in list.installed {
+        if !entry.platform.is_empty() && !entry.platform.eq_ignore_ascii_case("windows") {
+            continue;
+        }
+        let Some(mut game) = GOG_GAMES
+            .iter()
+            .map(|game_type| {
+                Game::new(*game_type, entry.install_path.clone()).with_platform(GamePlatform::Gog)
+            })
+            .find(|game| game.executable_path(&game.executable).exists())
+        else {
+            continue;
+        };
+        match wine_prefix(heroic_dir, &entry.app_name) {
+            Some(prefix) if prefix.join("pfx").exists() => {
+                game = game.with_proton_prefix(prefix);
+            }
+            Some(prefix) => tracing::info!(
+                "{} uses the Wine prefix {}, which isn't a Proton prefix; set one with 'game add-path'",
+                game.name,
+                prefix.display()
+            ),
+            None => {}
+        }
+        games.push(game);
+    }
+    games
+}
+
+/// `winePrefix` from the game's Heroic settings
+fn wine_prefix(heroic_dir: &Path, app_name: &str) -> Option<PathBuf> {
+    let path = heroic_dir
+        .join("GamesConfig")
+        .join(format!("{}.json", app_name));
+    let settings: Value = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    let prefix = settings.get(app_name)?.get("winePrefix")?.as_str()?;
+    Some(PathBuf::from(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_heroic_gog_games() {
+        let dir = tempfile::tempdir().unwrap();
+        let heroic = dir.path().join("heroic");
+        let skyrim = dir.path().join("Games/Heroic/Skyrim Anniversary Edition");
+        let fallout = dir.path().join("Games/Heroic/Fallout New Vegas");
+        let prefix = dir.path().join("Games/Heroic/Prefixes/Skyrim");
+        std::fs::create_dir_all(&skyrim).unwrap();
+        std::fs::write(skyrim.join("SkyrimSE.exe"), "").unwrap();
+        std::fs::create_dir_all(&fallout).unwrap();
+        std::fs::write(fallout.join("FalloutNV.exe"), "").unwrap();
+        std::fs::create_dir_all(prefix.join("pfx/drive_c")).unwrap();
+        std::fs::create_dir_all(heroic.join("gog_store")).unwrap();
+        std::fs::create_dir_all(heroic.join("GamesConfig")).unwrap();
+
+        let installed = serde_json::json!({
+            "installed": [
+                { "appName": "1711230643", "install_path": skyrim, "platform": "windows" },
+                { "appName": "1454587428", "install_path": fallout, "platform": "windows" },
+                { "appName": "1207658930", "install_path": dir.path().join("Other"), "platform": "windows" },
+            ]
+        });
+        std::fs::write(
+            heroic.join("gog_store/installed.json"),
+            installed.to_string(),
+        )
+        .unwrap();
+        let settings = serde_json::json!({ "1711230643": { "winePrefix": prefix } });
+        std::fs::write(
+            heroic.join("GamesConfig/1711230643.json"),
+            settings.to_string(),
+        )
+        .unwrap();
+
+        let games = detect_games(&heroic);
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].id, "skyrimse");
+        assert_eq!(games[0].platform, GamePlatform::Gog);
+        assert_eq!(games[0].proton_prefix, Some(prefix));
+        assert_eq!(games[1].id, "falloutnv");
+        assert_eq!(games[1].proton_prefix, None);
+    }
+}
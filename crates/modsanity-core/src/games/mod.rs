@@ -0,0 +1,878 @@
+//! Game detection and management
+
+mod definitions;
+mod dlc;
+pub mod heroic;
+pub mod openmw;
+pub mod prefix;
+mod proton;
+mod proton_runtime;
+pub mod skyrimse;
+pub mod steam;
+mod version;
+
+pub use dlc::{dlc_by_name, dlc_for_plugin, installed_dlc, Dlc};
+pub use proton::ProtonHelper;
+pub use proton_runtime::{detect_proton_runtimes, ProtonRuntime};
+pub use version::{
+    check_script_extender, find_script_extender, read_pe_version, BinaryVersion, GameBuild,
+    ScriptExtenderCheck, ScriptExtenderInstall,
+};
+
+use crate::config::CustomGameConfig;
+pub use definitions::GameDefinition;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Supported games
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameType {
+    SkyrimSE,
+    SkyrimVR,
+    Fallout4,
+    Fallout4VR,
+    Starfield,
+    Fallout3,
+    FalloutNV,
+    Oblivion,
+    OblivionRemastered,
+    Morrowind,
+    /// Morrowind played through OpenMW, which reads mods from openmw.cfg
+    OpenMW,
+    /// Enderal: Forgotten Stories, the Skyrim total conversion
+    Enderal,
+    EnderalSE,
+}
+
+/// How a game stores its load order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadOrderMethod {
+    /// plugins.txt marks active plugins with `*`; the order is in loadorder.txt
+    Asterisk,
+    /// plugins.txt lists only the active plugins; the game loads them by file
+    /// modification time, so the order is written to the plugin timestamps
+    Timestamp,
+    /// plugins.txt lists the active plugins in load order, without `*`
+    PluginsTxt,
+}
+
+impl GameType {
+    /// Parse from stable game ID.
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id.to_ascii_lowercase().as_str() {
+            "skyrimse" => Some(GameType::SkyrimSE),
+            "skyrimvr" => Some(GameType::SkyrimVR),
+            "fallout4" => Some(GameType::Fallout4),
+            "fallout4vr" => Some(GameType::Fallout4VR),
+            "starfield" => Some(GameType::Starfield),
+            "fallout3" => Some(GameType::Fallout3),
+            "falloutnv" => Some(GameType::FalloutNV),
+            "oblivion" => Some(GameType::Oblivion),
+            "oblivionremastered" => Some(GameType::OblivionRemastered),
+            "morrowind" => Some(GameType::Morrowind),
+            "openmw" => Some(GameType::OpenMW),
+            "enderal" => Some(GameType::Enderal),
+            "enderalse" => Some(GameType::EnderalSE),
+            _ => None,
+        }
+    }
+
+    /// Get the Steam App ID for this game
+    pub fn steam_app_id(&self) -> u32 {
+        match self {
+            GameType::SkyrimSE => 489830,
+            GameType::SkyrimVR => 611670,
+            GameType::Fallout4 => 377160,
+            GameType::Fallout4VR => 611660,
+            GameType::Starfield => 1716740,
+            GameType::Fallout3 => 22370,
+            GameType::FalloutNV => 22380,
+            GameType::Oblivion => 22330,
+            GameType::OblivionRemastered => 2623190,
+            GameType::Morrowind | GameType::OpenMW => 22320,
+            GameType::Enderal => 933480,
+            GameType::EnderalSE => 976620,
+        }
+    }
+
+    /// Get the NexusMods game domain
+    pub fn nexus_game_id(&self) -> &'static str {
+        match self {
+            GameType::SkyrimSE => "skyrimspecialedition",
+            GameType::SkyrimVR => "skyrimspecialedition", // Uses same mods
+            GameType::Fallout4 => "fallout4",
+            GameType::Fallout4VR => "fallout4", // Uses same mods
+            GameType::Starfield => "starfield",
+            GameType::Fallout3 => "fallout3",
+            GameType::FalloutNV => "newvegas",
+            GameType::Oblivion => "oblivion",
+            GameType::OblivionRemastered => "oblivionremastered",
+            GameType::Morrowind | GameType::OpenMW => "morrowind",
+            GameType::Enderal => "enderal",
+            GameType::EnderalSE => "enderalspecialedition",
+        }
+    }
+
+    /// Get the numeric NexusMods game ID (for GraphQL queries)
+    pub fn nexus_numeric_id(&self) -> i64 {
+        match self {
+            GameType::SkyrimSE => 1704,
+            GameType::SkyrimVR => 1704,
+            GameType::Fallout4 => 1151,
+            GameType::Fallout4VR => 1151,
+            GameType::Starfield => 4187,
+            GameType::Fallout3 => 120,
+            GameType::FalloutNV => 130,
+            GameType::Oblivion => 101,
+            GameType::OblivionRemastered => 7587,
+            GameType::Morrowind | GameType::OpenMW => 100,
+            GameType::Enderal => 2736,
+            GameType::EnderalSE => 3174,
+        }
+    }
+
+    /// Get the display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            GameType::SkyrimSE => "Skyrim Special Edition",
+            GameType::SkyrimVR => "Skyrim VR",
+            GameType::Fallout4 => "Fallout 4",
+            GameType::Fallout4VR => "Fallout 4 VR",
+            GameType::Starfield => "Starfield",
+            GameType::Fallout3 => "Fallout 3",
+            GameType::FalloutNV => "Fallout: New Vegas",
+            GameType::Oblivion => "Oblivion",
+            GameType::OblivionRemastered => "Oblivion Remastered",
+            GameType::Morrowind => "Morrowind",
+            GameType::OpenMW => "Morrowind (OpenMW)",
+            GameType::Enderal => "Enderal",
+            GameType::EnderalSE => "Enderal Special Edition",
+        }
+    }
+
+    /// Get the game ID string
+    pub fn id(&self) -> &'static str {
+        match self {
+            GameType::SkyrimSE => "skyrimse",
+            GameType::SkyrimVR => "skyrimvr",
+            GameType::Fallout4 => "fallout4",
+            GameType::Fallout4VR => "fallout4vr",
+            GameType::Starfield => "starfield",
+            GameType::Fallout3 => "fallout3",
+            GameType::FalloutNV => "falloutnv",
+            GameType::Oblivion => "oblivion",
+            GameType::OblivionRemastered => "oblivionremastered",
+            GameType::Morrowind => "morrowind",
+            GameType::OpenMW => "openmw",
+            GameType::Enderal => "enderal",
+            GameType::EnderalSE => "enderalse",
+        }
+    }
+
+    /// INI files the game reads from its My Games folder, main file first
+    pub fn ini_files(&self) -> &'static [&'static str] {
+        match self {
+            GameType::SkyrimSE => &["Skyrim.ini", "SkyrimPrefs.ini", "SkyrimCustom.ini"],
+            GameType::SkyrimVR => &["SkyrimVR.ini", "SkyrimPrefs.ini"],
+            GameType::Fallout4 => &["Fallout4.ini", "Fallout4Prefs.ini", "Fallout4Custom.ini"],
+            GameType::Fallout4VR => &["Fallout4VR.ini", "Fallout4Prefs.ini"],
+            GameType::Starfield => &["StarfieldPrefs.ini", "StarfieldCustom.ini"],
+            GameType::Fallout3 | GameType::FalloutNV => &["Fallout.ini", "FalloutPrefs.ini"],
+            GameType::Oblivion => &["Oblivion.ini"],
+            GameType::OblivionRemastered => &["Altar.ini"],
+            GameType::Morrowind => &["Morrowind.ini"],
+            GameType::OpenMW => &["settings.cfg"],
+            GameType::Enderal | GameType::EnderalSE => &["Enderal.ini", "EnderalPrefs.ini"],
+        }
+    }
+
+    /// Script extender short name (SKSE, F4SE, ...)
+    pub fn script_extender_name(&self) -> &'static str {
+        match self {
+            GameType::SkyrimSE | GameType::Enderal | GameType::EnderalSE => "SKSE",
+            GameType::SkyrimVR => "SKSEVR",
+            GameType::Fallout4 => "F4SE",
+            GameType::Fallout4VR => "F4SEVR",
+            GameType::Starfield => "SFSE",
+            GameType::Fallout3 => "FOSE",
+            GameType::FalloutNV => "NVSE",
+            GameType::Oblivion => "OBSE",
+            GameType::OblivionRemastered => "OBSE64",
+            GameType::Morrowind | GameType::OpenMW => "MWSE",
+        }
+    }
+
+    /// Executable that starts the game with the script extender loaded;
+    /// None for OpenMW, which doesn't run script extenders
+    pub fn script_extender_loader(&self) -> Option<&'static str> {
+        Some(match self {
+            GameType::SkyrimSE | GameType::EnderalSE => "skse64_loader.exe",
+            GameType::Enderal => "skse_loader.exe",
+            GameType::SkyrimVR => "sksevr_loader.exe",
+            GameType::Fallout4 => "f4se_loader.exe",
+            GameType::Fallout4VR => "f4sevr_loader.exe",
+            GameType::Starfield => "sfse_loader.exe",
+            GameType::Fallout3 => "fose_loader.exe",
+            GameType::FalloutNV => "nvse_loader.exe",
+            GameType::Oblivion => "obse_loader.exe",
+            GameType::OblivionRemastered => "obse64_loader.exe",
+            // MWSE hooks into the game executable itself
+            GameType::Morrowind => "Morrowind.exe",
+            GameType::OpenMW => return None,
+        })
+    }
+
+    /// Data subfolder holding script extender plugins
+    pub fn script_extender_dir(&self) -> &'static str {
+        match self {
+            GameType::SkyrimSE | GameType::SkyrimVR | GameType::Enderal | GameType::EnderalSE => {
+                "skse"
+            }
+            GameType::Fallout4 | GameType::Fallout4VR => "f4se",
+            GameType::Starfield => "sfse",
+            GameType::Fallout3 => "fose",
+            GameType::FalloutNV => "nvse",
+            GameType::Oblivion | GameType::OblivionRemastered => "obse",
+            GameType::Morrowind | GameType::OpenMW => "mwse",
+        }
+    }
+
+    /// Plugin file extensions the game loads; light plugins (.esl) arrived
+    /// with Skyrim SE and Fallout 4
+    pub fn plugin_extensions(&self) -> &'static [&'static str] {
+        match self {
+            GameType::Fallout3
+            | GameType::FalloutNV
+            | GameType::Oblivion
+            | GameType::OblivionRemastered
+            | GameType::Morrowind
+            | GameType::Enderal => &["esm", "esp"],
+            GameType::OpenMW => &["esm", "esp", "omwaddon", "omwscripts"],
+            _ => &["esm", "esp", "esl"],
+        }
+    }
+
+    /// Whether the game reads light plugins (.esl and ESL-flagged .esp)
+    pub fn supports_light_plugins(&self) -> bool {
+        self.plugin_extensions().contains(&"esl")
+    }
+
+    pub fn load_order_method(&self) -> LoadOrderMethod {
+        match self {
+            GameType::Fallout3 | GameType::FalloutNV | GameType::Oblivion | GameType::Morrowind => {
+                LoadOrderMethod::Timestamp
+            }
+            GameType::OblivionRemastered | GameType::OpenMW | GameType::Enderal => {
+                LoadOrderMethod::PluginsTxt
+            }
+            _ => LoadOrderMethod::Asterisk,
+        }
+    }
+
+    /// Whether loose files only override the game's BSA archives once
+    /// archive invalidation is set up in the INI
+    pub fn needs_archive_invalidation(&self) -> bool {
+        matches!(
+            self,
+            GameType::Fallout3
+                | GameType::FalloutNV
+                | GameType::Fallout4
+                | GameType::Fallout4VR
+                | GameType::Oblivion
+                | GameType::Morrowind
+        )
+    }
+
+    /// Data folder relative to the install folder
+    pub fn data_dir(&self) -> &'static str {
+        match self {
+            GameType::OblivionRemastered => "OblivionRemastered/Content/Dev/ObvData/Data",
+            GameType::Morrowind | GameType::OpenMW => "Data Files",
+            _ => "Data",
+        }
+    }
+
+    /// Folder holding the game executable and script extender, relative to
+    /// the install folder; empty when that is the install folder itself
+    pub fn binaries_dir(&self) -> &'static str {
+        match self {
+            GameType::OblivionRemastered => "OblivionRemastered/Binaries/Win64",
+            _ => "",
+        }
+    }
+
+    /// Top-level mod folders that deploy next to the game executable instead
+    /// of into Data (Unreal games keep script extender and UE4SS files there)
+    pub fn binary_mod_dirs(&self) -> &'static [&'static str] {
+        match self {
+            GameType::OblivionRemastered => &["OBSE", "ue4ss"],
+            _ => &[],
+        }
+    }
+
+    /// Folder, relative to the install folder, that Unreal `.pak` mods
+    /// (with their `.ucas`/`.utoc` companions) deploy to
+    pub fn pak_mods_dir(&self) -> Option<&'static str> {
+        match self {
+            GameType::OblivionRemastered => Some("OblivionRemastered/Content/Paks/~mods"),
+            _ => None,
+        }
+    }
+
+    /// File name of the active plugin list; the Oblivions capitalise it and
+    /// Morrowind and OpenMW keep it in their main config file
+    pub fn plugins_txt_name(&self) -> &'static str {
+        match self {
+            GameType::Oblivion | GameType::OblivionRemastered => "Plugins.txt",
+            GameType::Morrowind => "Morrowind.ini",
+            GameType::OpenMW => "openmw.cfg",
+            _ => "plugins.txt",
+        }
+    }
+
+    /// Whether the game runs as a native Linux program instead of through Proton
+    pub fn runs_natively(&self) -> bool {
+        matches!(self, GameType::OpenMW)
+    }
+
+    /// Folders under `steamapps/common` the game installs to, with the app
+    /// ID of each edition (the Proton prefix is named after it)
+    pub fn steam_installs(&self) -> &'static [(&'static str, u32)] {
+        match self {
+            GameType::SkyrimSE => &[("Skyrim Special Edition", 489830)],
+            GameType::SkyrimVR => &[("SkyrimVR", 611670)],
+            GameType::Fallout4 => &[("Fallout 4", 377160)],
+            GameType::Fallout4VR => &[("Fallout 4 VR", 611660)],
+            GameType::Starfield => &[("Starfield", 1716740)],
+            GameType::Fallout3 => &[("Fallout 3 goty", 22370), ("Fallout 3", 22300)],
+            GameType::FalloutNV => &[("Fallout New Vegas", 22380)],
+            GameType::Oblivion => &[("Oblivion", 22330)],
+            GameType::OblivionRemastered => &[("Oblivion Remastered", 2623190)],
+            GameType::Morrowind | GameType::OpenMW => &[("Morrowind", 22320)],
+            GameType::Enderal => &[("Enderal", 933480)],
+            GameType::EnderalSE => &[("Enderal Special Edition", 976620)],
+        }
+    }
+
+    /// Get all supported game types
+    pub fn all() -> &'static [GameType] {
+        &[
+            GameType::SkyrimSE,
+            GameType::SkyrimVR,
+            GameType::Fallout4,
+            GameType::Fallout4VR,
+            GameType::Starfield,
+            GameType::Fallout3,
+            GameType::FalloutNV,
+            GameType::Oblivion,
+            GameType::OblivionRemastered,
+            GameType::Morrowind,
+            GameType::OpenMW,
+            GameType::Enderal,
+            GameType::EnderalSE,
+        ]
+    }
+}
+
+/// Source platform for a detected game install.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GamePlatform {
+    #[default]
+    Steam,
+    Gog,
+    Manual,
+}
+
+impl GamePlatform {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            GamePlatform::Steam => "Steam",
+            GamePlatform::Gog => "GOG",
+            GamePlatform::Manual => "Manual",
+        }
+    }
+}
+
+/// Represents a detected game installation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Game {
+    /// Game type
+    pub game_type: GameType,
+
+    /// Short identifier (e.g., "skyrimse")
+    pub id: String,
+
+    /// Display name
+    pub name: String,
+
+    /// NexusMods game domain
+    pub nexus_game_id: String,
+
+    /// Numeric NexusMods game ID (for GraphQL queries)
+    #[serde(default)]
+    pub nexus_numeric_id: i64,
+
+    /// Steam App ID
+    pub steam_app_id: u32,
+
+    /// Game installation path
+    pub install_path: PathBuf,
+
+    /// Data folder path (where mods go)
+    pub data_path: PathBuf,
+
+    /// Proton prefix path (if using Proton)
+    pub proton_prefix: Option<PathBuf>,
+
+    /// AppData/Local path (for plugins.txt, etc.)
+    pub appdata_path: Option<PathBuf>,
+
+    /// plugins.txt location
+    pub plugins_txt_path: Option<PathBuf>,
+
+    /// loadorder.txt location
+    pub loadorder_txt_path: Option<PathBuf>,
+
+    /// Game executable name
+    pub executable: String,
+
+    /// Is this a VR game?
+    pub is_vr: bool,
+
+    /// Installation source platform.
+    #[serde(default)]
+    pub platform: GamePlatform,
+}
+
+impl Game {
+    /// Create a new Game from a detected installation
+    pub fn new(game_type: GameType, install_path: PathBuf) -> Self {
+        let data_path = install_path.join(game_type.data_dir());
+        let executable = match game_type {
+            GameType::SkyrimSE => "SkyrimSE.exe".to_string(),
+            GameType::SkyrimVR => "SkyrimVR.exe".to_string(),
+            GameType::Fallout4 => "Fallout4.exe".to_string(),
+            GameType::Fallout4VR => "Fallout4VR.exe".to_string(),
+            GameType::Starfield => "Starfield.exe".to_string(),
+            GameType::Fallout3 => "Fallout3.exe".to_string(),
+            GameType::FalloutNV => "FalloutNV.exe".to_string(),
+            GameType::Oblivion => "Oblivion.exe".to_string(),
+            GameType::OblivionRemastered => "OblivionRemastered-Win64-Shipping.exe".to_string(),
+            GameType::Morrowind => "Morrowind.exe".to_string(),
+            GameType::OpenMW => "openmw".to_string(),
+            GameType::Enderal => "TESV.exe".to_string(),
+            GameType::EnderalSE => "SkyrimSE.exe".to_string(),
+        };
+        // Games that don't keep their plugin list in AppData: Oblivion
+        // Remastered reads it from the Data folder, Morrowind from
+        // Morrowind.ini and OpenMW from openmw.cfg
+        let list_dir = match game_type {
+            GameType::OblivionRemastered => Some(data_path.clone()),
+            GameType::Morrowind => Some(install_path.clone()),
+            GameType::OpenMW => Some(openmw::config_dir()),
+            _ => None,
+        };
+        let plugins_txt_path = list_dir
+            .as_ref()
+            .map(|dir| dir.join(game_type.plugins_txt_name()));
+        let loadorder_txt_path = match game_type {
+            GameType::OblivionRemastered => data_path.parent().map(|p| p.join("loadorder.txt")),
+            _ => list_dir.as_ref().map(|dir| dir.join("loadorder.txt")),
+        };
+        let appdata_path = match game_type {
+            GameType::OpenMW => list_dir,
+            _ => None,
+        };
+
+        Self {
+            game_type,
+            id: game_type.id().to_string(),
+            name: game_type.display_name().to_string(),
+            nexus_game_id: game_type.nexus_game_id().to_string(),
+            nexus_numeric_id: game_type.nexus_numeric_id(),
+            steam_app_id: game_type.steam_app_id(),
+            install_path,
+            data_path,
+            proton_prefix: None,
+            appdata_path,
+            plugins_txt_path,
+            loadorder_txt_path,
+            executable,
+            is_vr: matches!(game_type, GameType::SkyrimVR | GameType::Fallout4VR),
+            platform: GamePlatform::Steam,
+        }
+    }
+
+    /// Set up Proton-related paths
+    pub fn with_proton_prefix(mut self, prefix: PathBuf) -> Self {
+        // AppData path inside the Proton prefix
+        let appdata = prefix
+            .join("pfx/drive_c/users/steamuser/AppData/Local")
+            .join(self.appdata_folder_name());
+
+        if self.plugins_txt_path.is_none() {
+            self.plugins_txt_path = Some(appdata.join(self.game_type.plugins_txt_name()));
+            self.loadorder_txt_path = Some(appdata.join("loadorder.txt"));
+        }
+        self.appdata_path.get_or_insert(appdata);
+        self.proton_prefix = Some(prefix);
+
+        self
+    }
+
+    /// Use `path` as plugins.txt, with loadorder.txt and the AppData folder next to it
+    fn set_plugins_txt(&mut self, path: PathBuf) {
+        let dir = path.parent().map(Path::to_path_buf);
+        self.loadorder_txt_path = dir.as_ref().map(|dir| dir.join("loadorder.txt"));
+        self.appdata_path = dir;
+        self.plugins_txt_path = Some(path);
+    }
+
+    /// Get the AppData folder name for this game
+    fn appdata_folder_name(&self) -> &str {
+        match self.game_type {
+            GameType::SkyrimSE | GameType::SkyrimVR => "Skyrim Special Edition",
+            GameType::Fallout4 | GameType::Fallout4VR => "Fallout4",
+            GameType::Starfield => "Starfield",
+            GameType::Fallout3 => "Fallout3",
+            GameType::FalloutNV => "FalloutNV",
+            GameType::Oblivion => "Oblivion",
+            GameType::OblivionRemastered => "Oblivion Remastered",
+            GameType::Morrowind | GameType::OpenMW => "Morrowind",
+            GameType::Enderal => "enderal",
+            GameType::EnderalSE => "Enderal Special Edition",
+        }
+    }
+
+    /// Directory holding the game's INI files (Documents/My Games inside the Proton prefix)
+    pub fn ini_dir(&self) -> Option<PathBuf> {
+        let folder = match self.game_type {
+            GameType::Morrowind => return Some(self.install_path.clone()),
+            GameType::OpenMW => return self.appdata_path.clone(),
+            GameType::SkyrimSE => "Skyrim Special Edition",
+            GameType::SkyrimVR => "Skyrim VR",
+            GameType::Fallout4 => "Fallout4",
+            GameType::Fallout4VR => "Fallout4VR",
+            GameType::Starfield => "Starfield",
+            GameType::Fallout3 => "Fallout3",
+            GameType::FalloutNV => "FalloutNV",
+            GameType::Oblivion => "Oblivion",
+            GameType::OblivionRemastered => "Oblivion Remastered/Saved/Config/Windows",
+            GameType::Enderal => "Enderal",
+            GameType::EnderalSE => "Enderal Special Edition",
+        };
+        self.proton_prefix.as_ref().map(|prefix| {
+            prefix
+                .join("pfx/drive_c/users/steamuser/Documents/My Games")
+                .join(folder)
+        })
+    }
+
+    /// Folder holding the game executable and script extender loader
+    pub fn binaries_path(&self) -> PathBuf {
+        self.install_path.join(self.game_type.binaries_dir())
+    }
+
+    /// Path of `exe`, looked up in the install folder first since launchers
+    /// and some tools live there even when the game binary doesn't
+    pub fn executable_path(&self, exe: &str) -> PathBuf {
+        if self.game_type.runs_natively() && !exe.ends_with(".exe") {
+            return find_in_path(exe).unwrap_or_else(|| PathBuf::from(exe));
+        }
+        let in_root = self.install_path.join(exe);
+        if in_root.exists() || self.game_type.binaries_dir().is_empty() {
+            in_root
+        } else {
+            self.binaries_path().join(exe)
+        }
+    }
+
+    /// Get the NexusMods game domain for API calls
+    pub fn nexus_game_domain(&self) -> String {
+        self.nexus_game_id.clone()
+    }
+
+    /// Set source platform.
+    pub fn with_platform(mut self, platform: GamePlatform) -> Self {
+        self.platform = platform;
+        self
+    }
+}
+
+/// Game detection utilities
+pub struct GameDetector;
+
+impl GameDetector {
+    /// Detect all supported games
+    pub async fn detect_all() -> Vec<Game> {
+        let mut games = Vec::new();
+
+        // Find Steam library folders on every drive
+        let libraries = steam::steam_libraries();
+
+        for steamapps in &libraries {
+            for game_type in GameType::all() {
+                if let Some(game) = Self::detect_game(steamapps, *game_type, &libraries) {
+                    games.push(game);
+                }
+            }
+        }
+
+        // GOG games installed through Heroic, then common GOG install locations.
+        let gog_games = heroic::config_dirs()
+            .iter()
+            .flat_map(|dir| heroic::detect_games(dir))
+            .chain(
+                GameType::all()
+                    .iter()
+                    .filter_map(|game_type| Self::detect_gog_game(*game_type)),
+            )
+            .collect::<Vec<_>>();
+        for game in gog_games {
+            if !games
+                .iter()
+                .any(|g| g.id == game.id && g.install_path == game.install_path)
+            {
+                games.push(game);
+            }
+        }
+
+        Self::dedupe_games(games)
+    }
+
+    /// Detect Steam + user game definitions + custom configured entries.
+    pub async fn detect_all_with_custom(
+        custom: &[CustomGameConfig],
+        definitions: &[GameDefinition],
+    ) -> Vec<Game> {
+        let mut games = Self::detect_all().await;
+        let libraries = steam::steam_libraries();
+
+        games.extend(
+            definitions
+                .iter()
+                .flat_map(|definition| definition.detect(&libraries)),
+        );
+
+        for entry in custom {
+            let definition = definitions
+                .iter()
+                .find(|d| d.id.eq_ignore_ascii_case(&entry.game_id));
+            let game_type = GameType::from_id(&entry.game_id);
+
+            let install_path = PathBuf::from(entry.install_path.trim());
+            if !install_path.exists() {
+                tracing::warn!(
+                    "Ignoring custom game '{}' because path does not exist: {}",
+                    entry.game_id,
+                    install_path.display()
+                );
+                continue;
+            }
+
+            let platform = match entry.platform.to_ascii_lowercase().as_str() {
+                "steam" => GamePlatform::Steam,
+                "gog" => GamePlatform::Gog,
+                _ => GamePlatform::Manual,
+            };
+
+            let game = match (game_type, definition) {
+                (Some(game_type), _) => Game::new(game_type, install_path.clone()),
+                (None, Some(definition)) => definition.game(install_path.clone()),
+                (None, None) => {
+                    tracing::warn!("Ignoring custom game with unknown id '{}'", entry.game_id);
+                    continue;
+                }
+            }
+            .with_platform(platform);
+            let prefix = if let Some(prefix) = entry
+                .proton_prefix
+                .as_deref()
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+            {
+                Some(PathBuf::from(prefix)).filter(|prefix| prefix.exists())
+            } else if let Some(prefix) = Self::infer_prefix_from_install_path(&install_path) {
+                Some(prefix)
+            } else if platform == GamePlatform::Steam {
+                // A Steam game added by path still has its prefix in a library
+                steam::find_compatdata(game.steam_app_id, None, &libraries)
+            } else {
+                None
+            };
+            let game = match (prefix, definition) {
+                (Some(prefix), Some(definition)) if game_type.is_none() => {
+                    definition.with_proton_prefix(game, prefix)
+                }
+                (Some(prefix), _) => game.with_proton_prefix(prefix),
+                (None, _) => game,
+            };
+
+            if !games
+                .iter()
+                .any(|g| g.id == game.id && g.install_path == game.install_path)
+            {
+                games.push(game);
+            }
+        }
+
+        Self::dedupe_games(games)
+    }
+
+    fn dedupe_games(games: Vec<Game>) -> Vec<Game> {
+        let mut out = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for game in games {
+            let canonical = game
+                .install_path
+                .canonicalize()
+                .unwrap_or_else(|_| game.install_path.clone());
+            let key = format!("{}:{}", game.id, canonical.display());
+            if seen.insert(key) {
+                out.push(game);
+            }
+        }
+        out
+    }
+
+    /// Detect a specific game in a Steam library. Its Proton prefix is looked
+    /// up in the same library first, then in the others.
+    fn detect_game(steamapps: &Path, game_type: GameType, libraries: &[PathBuf]) -> Option<Game> {
+        let common = steamapps.join("common");
+        let (install_path, app_id) = game_type
+            .steam_installs()
+            .iter()
+            .map(|(folder, app_id)| (common.join(folder), *app_id))
+            .find(|(path, _)| path.exists())?;
+
+        let mut game = Game::new(game_type, install_path).with_platform(GamePlatform::Steam);
+        game.steam_app_id = app_id;
+        if game_type == GameType::OpenMW && !Self::openmw_configured(&game) {
+            return None;
+        }
+
+        // Check for Proton prefix
+        if let Some(compatdata) = steam::find_compatdata(app_id, Some(steamapps), libraries) {
+            game = game.with_proton_prefix(compatdata);
+        }
+
+        Some(game)
+    }
+
+    /// Detect GOG installs in common Linux paths and wine prefixes.
+    fn detect_gog_game(game_type: GameType) -> Option<Game> {
+        let home = dirs::home_dir().unwrap_or_default();
+        let title = game_type.display_name();
+        let mut candidates = vec![
+            home.join(format!("GOG Games/{}", title)),
+            home.join(format!("Games/GOG Games/{}", title)),
+            home.join(format!("Games/{}", title)),
+            home.join(format!(
+                ".local/share/Steam/steamapps/compatdata/{}/pfx/drive_c/GOG Games/{}",
+                game_type.steam_app_id(),
+                title
+            )),
+        ];
+
+        // Known extra aliases observed in the wild.
+        if matches!(game_type, GameType::SkyrimSE) {
+            candidates.push(home.join("GOG Games/Skyrim Anniversary Edition"));
+            candidates.push(home.join(".local/share/Steam/steamapps/compatdata/1711230/pfx/drive_c/GOG Games/Skyrim Special Edition"));
+            candidates.push(home.join(".local/share/Steam/steamapps/compatdata/1711230/pfx/drive_c/GOG Games/Skyrim Anniversary Edition"));
+        }
+        match game_type {
+            GameType::Fallout3 => candidates.push(home.join("GOG Games/Fallout 3 GOTY")),
+            GameType::FalloutNV => candidates.push(home.join("GOG Games/Fallout New Vegas")),
+            GameType::Oblivion => {
+                candidates.push(home.join("GOG Games/Oblivion GOTY"));
+                candidates.push(home.join("GOG Games/The Elder Scrolls IV Oblivion GOTY"));
+            }
+            _ => {}
+        }
+
+        for install_path in candidates {
+            if !install_path.exists() {
+                continue;
+            }
+            let mut game =
+                Game::new(game_type, install_path.clone()).with_platform(GamePlatform::Gog);
+            if game_type == GameType::OpenMW {
+                if !Self::openmw_configured(&game) {
+                    continue;
+                }
+            } else if !game.executable_path(&game.executable).exists() {
+                continue;
+            }
+            if let Some(prefix) = Self::infer_prefix_from_install_path(&install_path) {
+                game = game.with_proton_prefix(prefix);
+            }
+            return Some(game);
+        }
+
+        None
+    }
+
+    /// Whether OpenMW has been set up for this Morrowind install: its
+    /// openmw.cfg lists the install's Data Files
+    fn openmw_configured(game: &Game) -> bool {
+        let Some(cfg) = &game.plugins_txt_path else {
+            return false;
+        };
+        openmw::read_entries(cfg, "data")
+            .unwrap_or_default()
+            .iter()
+            .any(|dir| Path::new(dir) == game.data_path)
+    }
+
+    /// Infer Proton prefix root from an install path inside a wine prefix.
+    fn infer_prefix_from_install_path(install_path: &Path) -> Option<PathBuf> {
+        let mut cur = Some(install_path);
+        while let Some(path) = cur {
+            if path.ends_with("pfx/drive_c") {
+                return path.parent().map(std::path::Path::to_path_buf);
+            }
+            cur = path.parent();
+        }
+        None
+    }
+}
+
+/// First `name` on `PATH`
+fn find_in_path(name: &str) -> Option<PathBuf> {
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+// Re-export for convenience
+mod dirs {
+    pub fn home_dir() -> Option<std::path::PathBuf> {
+        std::env::var_os("HOME").map(std::path::PathBuf::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_enderal_separately_from_skyrim() {
+        let dir = tempfile::tempdir().unwrap();
+        let steamapps = dir.path().join("steamapps");
+        std::fs::create_dir_all(steamapps.join("common/Enderal Special Edition")).unwrap();
+        std::fs::create_dir_all(steamapps.join("compatdata/976620")).unwrap();
+
+        let libraries = [steamapps.clone()];
+        assert!(GameDetector::detect_game(&steamapps, GameType::SkyrimSE, &libraries).is_none());
+        let game = GameDetector::detect_game(&steamapps, GameType::EnderalSE, &libraries).unwrap();
+        assert_eq!(game.id, "enderalse");
+        assert_eq!(game.nexus_game_id, "enderalspecialedition");
+        assert_eq!(
+            game.plugins_txt_path.unwrap(),
+            steamapps.join(
+                "compatdata/976620/pfx/drive_c/users/steamuser/AppData/Local/Enderal Special Edition/plugins.txt"
+            )
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! OpenMW configuration (openmw.cfg)
+//!
+//! OpenMW reads game data from every `data=` folder listed in openmw.cfg,
+//! later folders overriding earlier ones, and loads the `content=` files in
+//! the order listed. Mods are enabled by listing their staging folders as
+//! `data=` entries instead of linking files into Data Files.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Folder holding openmw.cfg and settings.cfg: the Flatpak config folder if
+/// OpenMW is installed that way, otherwise `$XDG_CONFIG_HOME/openmw`
+pub fn config_dir() -> PathBuf {
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let flatpak = home.join(".var/app/org.openmw.OpenMW/config/openmw");
+    if flatpak.join("openmw.cfg").exists() {
+        return flatpak;
+    }
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .unwrap_or_else(|| home.join(".config"))
+        .join("openmw")
+}
+
+/// Values of every `key=` line in `cfg`, in file order. A missing file has none.
+pub fn read_entries(cfg: &Path, key: &str) -> Result<Vec<String>> {
+    if !cfg.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(cfg)
+        .with_context(|| format!("Failed to read {}", cfg.display()))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| entry_value(line, key))
+        .map(unquote)
+        .collect())
+}
+
+/// Replace the `key=` lines in `cfg` with `values`, written where the first
+/// old line was (or appended). Other lines are kept as they are.
+pub fn write_entries(cfg: &Path, key: &str, values: &[String]) -> Result<()> {
+    let content = match std::fs::read_to_string(cfg) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", cfg.display())),
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut insert_at = None;
+    for line in content.lines() {
+        if entry_value(line, key).is_some() {
+            insert_at.get_or_insert(lines.len());
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    let new_lines = values.iter().map(|value| match key {
+        // Paths are quoted so spaces and leading/trailing whitespace survive
+        "data" => format!("{}={}", key, quote(value)),
+        _ => format!("{}={}", key, value),
+    });
+    let at = insert_at.unwrap_or(lines.len());
+    lines.splice(at..at, new_lines);
+
+    if let Some(parent) = cfg.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    std::fs::write(cfg, out).with_context(|| format!("Failed to write {}", cfg.display()))
+}
+
+fn entry_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let (k, v) = line.trim().split_once('=')?;
+    (k.trim() == key).then(|| v.trim())
+}
+
+/// openmw.cfg escapes `&` and `"` inside quoted values as `&&` and `&"`
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('&', "&&").replace('"', "&\""))
+}
+
+fn unquote(value: &str) -> String {
+    let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+        return value.to_string();
+    };
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('&', Some(next @ ('&' | '"'))) => {
+                out.push(next);
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = dir.path().join("openmw.cfg");
+        std::fs::write(
+            &cfg,
+            "# OpenMW config\n\
+             data=\"/games/Morrowind/Data Files\"\n\
+             fallback-archive=Morrowind.bsa\n\
+             content=Morrowind.esm\n\
+             content=Tribunal.esm\n\
+             encoding=win1252\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_entries(&cfg, "data").unwrap(),
+            vec!["/games/Morrowind/Data Files"]
+        );
+        let content = ["Morrowind.esm", "Tribunal.esm", "Mod.esp"].map(String::from);
+        write_entries(&cfg, "content", &content).unwrap();
+        let data = ["/games/Morrowind/Data Files", "/mods/Q&A \"Fixes\""].map(String::from);
+        write_entries(&cfg, "data", &data).unwrap();
+
+        assert_eq!(read_entries(&cfg, "content").unwrap(), content);
+        assert_eq!(read_entries(&cfg, "data").unwrap(), data);
+        assert_eq!(
+            std::fs::read_to_string(&cfg).unwrap(),
+            "# OpenMW config\n\
+             data=\"/games/Morrowind/Data Files\"\n\
+             data=\"/mods/Q&&A &\"Fixes&\"\"\n\
+             fallback-archive=Morrowind.bsa\n\
+             content=Morrowind.esm\n\
+             content=Tribunal.esm\n\
+             content=Mod.esp\n\
+             encoding=win1252\n"
+        );
+    }
+}
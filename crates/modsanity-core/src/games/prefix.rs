@@ -0,0 +1,134 @@
+//! Wine prefix dependencies of modding tools
+//!
+//! Tools like Synthesis, FNIS and BodySlide need Windows runtimes that a
+//! fresh Proton prefix doesn't have. They are installed with winetricks
+//! verbs (directly or through protontricks), and winetricks records every
+//! verb it installed in the prefix's `winetricks.log`.
+
+use super::{Game, GamePlatform};
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+/// A runtime installed into the prefix with a winetricks verb
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefixDependency {
+    pub verb: &'static str,
+    pub name: &'static str,
+    /// Tools that need it, for display
+    pub needed_by: &'static str,
+}
+
+/// Runtimes the supported tools need, in install order
+pub const PREFIX_DEPENDENCIES: &[PrefixDependency] = &[
+    PrefixDependency {
+        verb: "dotnet48",
+        name: ".NET Framework 4.8",
+        needed_by: "Synthesis, FNIS, xEdit scripts",
+    },
+    PrefixDependency {
+        verb: "dotnetdesktop8",
+        name: ".NET Desktop Runtime 8",
+        needed_by: "Synthesis",
+    },
+    PrefixDependency {
+        verb: "vcrun2022",
+        name: "Visual C++ 2015-2022 runtime",
+        needed_by: "xEdit, BodySlide, Outfit Studio, Nemesis",
+    },
+    PrefixDependency {
+        verb: "d3dcompiler_47",
+        name: "Direct3D shader compiler",
+        needed_by: "BodySlide, Outfit Studio",
+    },
+];
+
+/// Look up a dependency by its winetricks verb
+pub fn prefix_dependency(verb: &str) -> Option<&'static PrefixDependency> {
+    PREFIX_DEPENDENCIES
+        .iter()
+        .find(|dep| dep.verb.eq_ignore_ascii_case(verb))
+}
+
+/// Verbs winetricks has installed into a Wine prefix (the `pfx` folder)
+pub fn installed_verbs(pfx: &Path) -> HashSet<String> {
+    std::fs::read_to_string(pfx.join("winetricks.log"))
+        .map(|log| parse_winetricks_log(&log))
+        .unwrap_or_default()
+}
+
+fn parse_winetricks_log(log: &str) -> HashSet<String> {
+    log.lines()
+        .map(str::trim)
+        // Options like "-q" are logged alongside the verbs
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Dependencies missing from the game's Proton prefix, or None when the game
+/// has no prefix
+pub fn missing_dependencies(game: &Game) -> Option<Vec<&'static PrefixDependency>> {
+    let prefix = game.proton_prefix.as_ref()?;
+    let installed = installed_verbs(&prefix.join("pfx"));
+    Some(
+        PREFIX_DEPENDENCIES
+            .iter()
+            .filter(|dep| !installed.contains(dep.verb))
+            .collect(),
+    )
+}
+
+/// Command that installs `verbs` into the game's prefix: protontricks for
+/// Steam games when it's installed, winetricks on the prefix otherwise.
+/// None when the game has no prefix or neither helper is installed.
+pub fn install_command(game: &Game, verbs: &[&str]) -> Option<Command> {
+    let prefix = game.proton_prefix.as_ref()?;
+    if game.platform == GamePlatform::Steam {
+        if let Ok(protontricks) = which::which("protontricks") {
+            let mut cmd = Command::new(protontricks);
+            cmd.arg(game.steam_app_id.to_string()).arg("-q").args(verbs);
+            return Some(cmd);
+        }
+    }
+    let winetricks = which::which("winetricks").ok()?;
+    let mut cmd = Command::new(winetricks);
+    cmd.env("WINEPREFIX", prefix.join("pfx"))
+        .arg("-q")
+        .args(verbs);
+    Some(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::GameType;
+
+    #[test]
+    fn test_missing_dependencies_from_winetricks_log() {
+        assert_eq!(
+            parse_winetricks_log("-q\nvcrun2022\n\nDotNet48\n"),
+            HashSet::from(["vcrun2022".to_string(), "dotnet48".to_string()])
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut game = Game::new(GameType::SkyrimSE, dir.path().join("game"));
+        assert!(missing_dependencies(&game).is_none());
+
+        game.proton_prefix = Some(dir.path().join("compatdata/489830"));
+        let pfx = dir.path().join("compatdata/489830/pfx");
+        std::fs::create_dir_all(&pfx).unwrap();
+        assert_eq!(
+            missing_dependencies(&game).unwrap().len(),
+            PREFIX_DEPENDENCIES.len()
+        );
+        std::fs::write(pfx.join("winetricks.log"), "dotnet48\nvcrun2022\n").unwrap();
+        let missing: Vec<_> = missing_dependencies(&game)
+            .unwrap()
+            .iter()
+            .map(|dep| dep.verb)
+            .collect();
+        assert_eq!(missing, vec!["dotnetdesktop8", "d3dcompiler_47"]);
+        assert_eq!(prefix_dependency("VCRUN2022").unwrap().verb, "vcrun2022");
+    }
+}
@@ -43,7 +43,7 @@ pub fn detect_proton_runtimes() -> Vec<ProtonRuntime> {
         );
     }
 
-    runtimes.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    runtimes.sort_by_key(|a| a.name.to_lowercase());
     runtimes
 }
 
@@ -0,0 +1,249 @@
+//! Steam library discovery
+//!
+//! Every Steam install lists its libraries (one per drive or folder the user
+//! added) in `steamapps/libraryfolders.vdf`. Games and their Proton prefixes
+//! (`steamapps/compatdata/<appid>`) can live in any of them.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A parsed VDF (Valve KeyValues) value
+#[derive(Debug)]
+enum Vdf {
+    String(String),
+    Object(BTreeMap<String, Vdf>),
+}
+
+/// Folders a Steam client may be installed in: native, the `~/.steam`
+/// symlinks, Flatpak and distro packages
+fn steam_roots() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    vec![
+        home.join(".steam/steam"),
+        home.join(".steam/root"),
+        home.join(".local/share/Steam"),
+        home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"),
+        PathBuf::from("/usr/share/steam"),
+    ]
+}
+
+/// The `steamapps` folder of every Steam library on this machine
+pub fn steam_libraries() -> Vec<PathBuf> {
+    let mut libraries: Vec<PathBuf> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut push = |path: PathBuf| {
+        if !path.exists() {
+            return;
+        }
+        // The ~/.steam symlinks point at the same install
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if seen.insert(canonical) {
+            libraries.push(path);
+        }
+    };
+
+    for root in steam_roots() {
+        let steamapps = root.join("steamapps");
+        push(steamapps.clone());
+        let vdf = steamapps.join("libraryfolders.vdf");
+        if let Ok(content) = std::fs::read_to_string(&vdf) {
+            for path in library_folders(&content) {
+                push(path.join("steamapps"));
+            }
+        }
+    }
+    libraries
+}
+
+/// Proton prefix of `app_id` in any of `libraries`, preferring `preferred`
+/// (the library the game is installed in)
+pub fn find_compatdata(
+    app_id: u32,
+    preferred: Option<&Path>,
+    libraries: &[PathBuf],
+) -> Option<PathBuf> {
+    preferred
+        .into_iter()
+        .chain(libraries.iter().map(PathBuf::as_path))
+        .map(|steamapps| steamapps.join("compatdata").join(app_id.to_string()))
+        .find(|prefix| prefix.exists())
+}
+
+/// Library folders listed in a libraryfolders.vdf. Handles the current
+/// format (`"0" { "path" "..." }`) and the old one (`"1" "..."`).
+pub fn library_folders(content: &str) -> Vec<PathBuf> {
+    let Some(Vdf::Object(root)) = parse(content) else {
+        return Vec::new();
+    };
+    let Some(Vdf::Object(folders)) = root
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("libraryfolders"))
+        .map(|(_, value)| value)
+    else {
+        return Vec::new();
+    };
+
+    let mut entries: Vec<(&String, &Vdf)> = folders.iter().collect();
+    // Numbered entries in library order; "contentstatsid" and friends are skipped below
+    entries.sort_by_key(|(key, _)| key.parse::<u32>().unwrap_or(u32::MAX));
+    entries
+        .into_iter()
+        .filter(|(key, _)| key.parse::<u32>().is_ok())
+        .filter_map(|(_, value)| match value {
+            Vdf::String(path) => Some(PathBuf::from(path)),
+            Vdf::Object(folder) => match folder.get("path") {
+                Some(Vdf::String(path)) => Some(PathBuf::from(path)),
+                _ => None,
+            },
+        })
+        .collect()
+}
+
+/// Parse VDF text into an object of its top-level keys
+fn parse(content: &str) -> Option<Vdf> {
+    let tokens = tokenize(content)?;
+    let root = parse_object(&mut tokens.into_iter())?;
+    Some(Vdf::Object(root))
+}
+
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+fn parse_object(tokens: &mut std::vec::IntoIter<Token>) -> Option<BTreeMap<String, Vdf>> {
+    let mut map = BTreeMap::new();
+    loop {
+        let key = match tokens.next() {
+            Some(Token::Str(key)) => key,
+            Some(Token::Close) | None => return Some(map),
+            Some(Token::Open) => return None,
+        };
+        let value = match tokens.next()? {
+            Token::Str(value) => Vdf::String(value),
+            Token::Open => Vdf::Object(parse_object(tokens)?),
+            Token::Close => return None,
+        };
+        map.insert(key, value);
+    }
+}
+
+fn tokenize(content: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            'n' => s.push('\n'),
+                            't' => s.push('\t'),
+                            other => s.push(other),
+                        },
+                        other => s.push(other),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                // Comment to end of line
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {}
+            _ => {
+                // Unquoted token
+                let mut s = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '{' | '}' | '"') {
+                        break;
+                    }
+                    s.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Str(s));
+            }
+        }
+    }
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_folders() {
+        let current = r#"
+"libraryfolders"
+{
+	"0"
+	{
+		"path"		"/home/user/.local/share/Steam"
+		"label"		""
+		"contentid"		"4242"
+		"apps"
+		{
+			"489830"		"16247371212"
+		}
+	}
+	"1"
+	{
+		"path"		"/mnt/Games Drive/SteamLibrary"
+		"apps"
+		{
+			"377160"		"33161719434"
+		}
+	}
+	"contentstatsid"		"-1234"
+}
+"#;
+        assert_eq!(
+            library_folders(current),
+            vec![
+                PathBuf::from("/home/user/.local/share/Steam"),
+                PathBuf::from("/mnt/Games Drive/SteamLibrary"),
+            ]
+        );
+
+        let old = "\"LibraryFolders\"\n{\n\t\"TimeNextStatsReport\"\t\"1600000000\"\n\t\"ContentStatsID\"\t\"-1\"\n\t\"1\"\t\"/media/ssd/Steam\"\n}\n";
+        assert_eq!(
+            library_folders(old),
+            vec![PathBuf::from("/media/ssd/Steam")]
+        );
+
+        // Windows-style libraries under a Wine/Proton Steam escape backslashes
+        assert_eq!(
+            library_folders("\"libraryfolders\" { \"0\" { \"path\" \"D:\\\\Steam\" } }"),
+            vec![PathBuf::from("D:\\Steam")]
+        );
+        assert!(library_folders("not a vdf {").is_empty());
+    }
+
+    #[test]
+    fn test_find_compatdata_in_other_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main/steamapps");
+        let second = dir.path().join("second/steamapps");
+        std::fs::create_dir_all(main.join("compatdata/489830")).unwrap();
+        std::fs::create_dir_all(second.join("common")).unwrap();
+
+        let libraries = vec![main.clone(), second.clone()];
+        assert_eq!(
+            find_compatdata(489830, Some(&second), &libraries),
+            Some(main.join("compatdata/489830"))
+        );
+        assert_eq!(find_compatdata(377160, Some(&second), &libraries), None);
+    }
+}
@@ -0,0 +1,404 @@
+//! Game binary and script extender version detection
+//!
+//! Versions come from the `VS_FIXEDFILEINFO` block in a PE file's resource
+//! section. Script extender runtimes also encode the game version they were
+//! built for in their file name (`skse64_1_6_1170.dll`).
+//!
+//! Some games split their mods by build: Skyrim SE 1.6 (Anniversary
+//! Edition) needs different SKSE plugins than 1.5.97, and Fallout 4's
+//! next-gen update did the same for F4SE. Nexus files for those games are
+//! matched against the installed build by name.
+
+use super::{Game, GameType};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const FIXED_FILE_INFO_SIGNATURE: u32 = 0xFEEF_04BD;
+
+/// A four-part Windows file version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BinaryVersion(pub [u16; 4]);
+
+impl BinaryVersion {
+    /// Major, minor and build; the fourth part never differs between game releases
+    pub fn release(&self) -> [u16; 3] {
+        [self.0[0], self.0[1], self.0[2]]
+    }
+}
+
+impl std::fmt::Display for BinaryVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [a, b, c, d] = self.0;
+        if d == 0 {
+            write!(f, "{}.{}.{}", a, b, c)
+        } else {
+            write!(f, "{}.{}.{}.{}", a, b, c, d)
+        }
+    }
+}
+
+/// A game build that mods publish separate files for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameBuild {
+    /// Skyrim SE 1.6 and later
+    SkyrimAE,
+    /// Skyrim SE before 1.6 (1.5.97)
+    SkyrimSE,
+    /// Fallout 4 1.10.980 and later
+    Fallout4NextGen,
+    /// Fallout 4 before the next-gen update (1.10.163)
+    Fallout4OldGen,
+}
+
+impl GameBuild {
+    /// The build of `game_type` at `version`, for games whose mods ship per-build files
+    pub fn detect(game_type: GameType, version: BinaryVersion) -> Option<Self> {
+        match game_type {
+            GameType::SkyrimSE if version.release() >= [1, 6, 0] => Some(Self::SkyrimAE),
+            GameType::SkyrimSE => Some(Self::SkyrimSE),
+            GameType::Fallout4 if version.release() >= [1, 10, 980] => Some(Self::Fallout4NextGen),
+            GameType::Fallout4 => Some(Self::Fallout4OldGen),
+            _ => None,
+        }
+    }
+
+    /// Short label used in mod file names
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SkyrimAE => "AE",
+            Self::SkyrimSE => "SE",
+            Self::Fallout4NextGen => "NG",
+            Self::Fallout4OldGen => "OG",
+        }
+    }
+
+    /// Words (lowercase) that mark a file as being for this build
+    fn markers(&self) -> &'static [&'static str] {
+        match self {
+            Self::SkyrimAE => &["ae", "anniversary edition"],
+            Self::SkyrimSE => &["se", "1 5 97"],
+            Self::Fallout4NextGen => &["ng", "next gen", "nextgen"],
+            Self::Fallout4OldGen => &["og", "old gen", "oldgen", "pre ng", "pre next gen"],
+        }
+    }
+
+    /// The other build of the same game
+    fn other(&self) -> Self {
+        match self {
+            Self::SkyrimAE => Self::SkyrimSE,
+            Self::SkyrimSE => Self::SkyrimAE,
+            Self::Fallout4NextGen => Self::Fallout4OldGen,
+            Self::Fallout4OldGen => Self::Fallout4NextGen,
+        }
+    }
+
+    /// Whether a file name says it is for this build
+    pub fn matches(&self, name: &str) -> bool {
+        let words: Vec<String> = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        let padded = format!(" {} ", words.join(" "));
+        self.markers()
+            .iter()
+            .any(|marker| padded.contains(&format!(" {} ", marker)))
+    }
+
+    /// Whether a file name says it is only for the other build
+    pub fn excludes(&self, name: &str) -> bool {
+        self.other().matches(name) && !self.matches(name)
+    }
+}
+
+impl Game {
+    /// Version of the game executable, if it can be read
+    pub fn version(&self) -> Option<BinaryVersion> {
+        read_pe_version(&self.executable_path(&self.executable))
+    }
+
+    /// The installed build, for games whose mods ship per-build files
+    pub fn build(&self) -> Option<GameBuild> {
+        GameBuild::detect(self.game_type, self.version()?)
+    }
+}
+
+/// Read the file version of a Windows executable or DLL
+pub fn read_pe_version(path: &Path) -> Option<BinaryVersion> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let rsrc = read_resource_section(&mut file)?;
+    find_fixed_file_info(&rsrc)
+}
+
+/// Raw bytes of the `.rsrc` section
+fn read_resource_section<R: Read + Seek>(r: &mut R) -> Option<Vec<u8>> {
+    let mut dos = [0u8; 64];
+    r.read_exact(&mut dos).ok()?;
+    if &dos[..2] != b"MZ" {
+        return None;
+    }
+    let pe_offset = u32::from_le_bytes(dos[60..64].try_into().ok()?) as u64;
+
+    let mut header = [0u8; 24];
+    r.seek(SeekFrom::Start(pe_offset)).ok()?;
+    r.read_exact(&mut header).ok()?;
+    if &header[..4] != b"PE\0\0" {
+        return None;
+    }
+    let sections = u16::from_le_bytes([header[6], header[7]]) as usize;
+    let optional_size = u16::from_le_bytes([header[20], header[21]]) as i64;
+    r.seek(SeekFrom::Current(optional_size)).ok()?;
+
+    for _ in 0..sections {
+        let mut section = [0u8; 40];
+        r.read_exact(&mut section).ok()?;
+        if !section.starts_with(b".rsrc") {
+            continue;
+        }
+        let size = u32::from_le_bytes(section[16..20].try_into().ok()?) as usize;
+        let offset = u32::from_le_bytes(section[20..24].try_into().ok()?) as u64;
+        let mut data = vec![0u8; size];
+        r.seek(SeekFrom::Start(offset)).ok()?;
+        r.read_exact(&mut data).ok()?;
+        return Some(data);
+    }
+    None
+}
+
+fn find_fixed_file_info(data: &[u8]) -> Option<BinaryVersion> {
+    let signature = FIXED_FILE_INFO_SIGNATURE.to_le_bytes();
+    let start = data.chunks_exact(4).position(|chunk| chunk == signature)? * 4;
+    let word = |at: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+    };
+    let ms = word(start + 8)?;
+    let ls = word(start + 12)?;
+    Some(BinaryVersion([
+        (ms >> 16) as u16,
+        ms as u16,
+        (ls >> 16) as u16,
+        ls as u16,
+    ]))
+}
+
+/// An installed script extender runtime
+#[derive(Debug, Clone)]
+pub struct ScriptExtenderInstall {
+    /// The runtime DLL, e.g. `skse64_1_6_1170.dll`
+    pub path: PathBuf,
+    /// The extender's own version, when its DLL carries one
+    pub version: Option<String>,
+    /// Game version the runtime was built for
+    pub target: [u16; 3],
+}
+
+/// Game and script extender versions for one install
+#[derive(Debug, Clone)]
+pub struct ScriptExtenderCheck {
+    pub game_type: GameType,
+    pub game_version: Option<BinaryVersion>,
+    pub extender: Option<ScriptExtenderInstall>,
+}
+
+impl ScriptExtenderCheck {
+    /// A warning when the script extender targets a different game version
+    pub fn mismatch(&self) -> Option<String> {
+        let game = self.game_version?;
+        let extender = self.extender.as_ref()?;
+        if game.release() == extender.target {
+            return None;
+        }
+        let name = self.game_type.script_extender_name();
+        let label = match &extender.version {
+            Some(v) => format!("{} {}", name, v),
+            None => name.to_string(),
+        };
+        let [a, b, c] = extender.target;
+        Some(format!(
+            "{} requires {} {}.{}.{}, you have {}",
+            label,
+            self.game_type.display_name(),
+            a,
+            b,
+            c,
+            game
+        ))
+    }
+}
+
+/// Find a script extender runtime DLL directly inside `dir`
+pub fn find_script_extender(game_type: GameType, dir: &Path) -> Option<ScriptExtenderInstall> {
+    let mut found: Vec<(PathBuf, [u16; 3])> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            runtime_target(game_type, &name).map(|target| (e.path(), target))
+        })
+        .collect();
+    // Several runtimes may be present after upgrades; the newest is the one in use
+    found.sort_by_key(|(_, target)| *target);
+    let (path, target) = found.pop()?;
+    let version = read_pe_version(&path).map(extender_version);
+    Some(ScriptExtenderInstall {
+        path,
+        version,
+        target,
+    })
+}
+
+/// Check the game executable against the script extender in the game root,
+/// then in `extra_dirs` (mod staging folders that deploy it)
+pub fn check_script_extender(game: &Game, extra_dirs: &[PathBuf]) -> ScriptExtenderCheck {
+    let extender = std::iter::once(&game.binaries_path())
+        .chain(extra_dirs)
+        .find_map(|dir| find_script_extender(game.game_type, dir));
+    ScriptExtenderCheck {
+        game_type: game.game_type,
+        game_version: game.version(),
+        extender,
+    }
+}
+
+/// Game version from a runtime DLL name like `skse64_1_6_1170.dll`, or
+/// `nvse_1_4.dll` for the older extenders that name only major and minor
+fn runtime_target(game_type: GameType, file_name: &str) -> Option<[u16; 3]> {
+    let prefix = game_type
+        .script_extender_loader()?
+        .strip_suffix("_loader.exe")?;
+    let lower = file_name.to_ascii_lowercase();
+    let digits = lower.strip_prefix(prefix)?.strip_prefix('_')?;
+    let digits = digits.strip_suffix(".dll")?;
+    let parts: Vec<u16> = digits
+        .split('_')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [a, b, c] => Some([*a, *b, *c]),
+        [a, b] => Some([*a, *b, 0]),
+        _ => None,
+    }
+}
+
+/// Script extenders stamp their DLLs as 0.major.minor.beta
+fn extender_version(v: BinaryVersion) -> String {
+    let [a, b, c, d] = v.0;
+    if a == 0 {
+        format!("{}.{}.{}", b, c, d)
+    } else {
+        v.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Minimal PE image with a `.rsrc` section holding a VS_FIXEDFILEINFO
+    fn pe_bytes(version: [u16; 4]) -> Vec<u8> {
+        let mut rsrc = vec![0u8; 16];
+        rsrc.extend_from_slice(&FIXED_FILE_INFO_SIGNATURE.to_le_bytes());
+        rsrc.extend_from_slice(&0x0001_0000u32.to_le_bytes());
+        rsrc.extend_from_slice(&(((version[0] as u32) << 16) | version[1] as u32).to_le_bytes());
+        rsrc.extend_from_slice(&(((version[2] as u32) << 16) | version[3] as u32).to_le_bytes());
+
+        let mut out = vec![0u8; 64];
+        out[..2].copy_from_slice(b"MZ");
+        out[60..64].copy_from_slice(&64u32.to_le_bytes());
+        out.extend_from_slice(b"PE\0\0");
+        let mut coff = [0u8; 20];
+        coff[2..4].copy_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&coff);
+        let rsrc_offset = (out.len() + 40) as u32;
+        let mut section = [0u8; 40];
+        section[..5].copy_from_slice(b".rsrc");
+        section[16..20].copy_from_slice(&(rsrc.len() as u32).to_le_bytes());
+        section[20..24].copy_from_slice(&rsrc_offset.to_le_bytes());
+        out.extend_from_slice(&section);
+        out.extend_from_slice(&rsrc);
+        out
+    }
+
+    #[test]
+    fn test_read_pe_version() {
+        let bytes = pe_bytes([1, 6, 1170, 0]);
+        let rsrc = read_resource_section(&mut Cursor::new(bytes)).unwrap();
+        let version = find_fixed_file_info(&rsrc).unwrap();
+        assert_eq!(version, BinaryVersion([1, 6, 1170, 0]));
+        assert_eq!(version.to_string(), "1.6.1170");
+        assert!(read_resource_section(&mut Cursor::new(b"not a pe".to_vec())).is_none());
+    }
+
+    #[test]
+    fn test_runtime_target() {
+        assert_eq!(
+            runtime_target(GameType::SkyrimSE, "skse64_1_6_1170.dll"),
+            Some([1, 6, 1170])
+        );
+        assert_eq!(
+            runtime_target(GameType::Fallout4, "F4SE_1_10_984.dll"),
+            Some([1, 10, 984])
+        );
+        assert_eq!(
+            runtime_target(GameType::FalloutNV, "nvse_1_4.dll"),
+            Some([1, 4, 0])
+        );
+        assert_eq!(
+            runtime_target(GameType::SkyrimSE, "skse64_steam_loader.dll"),
+            None
+        );
+        assert_eq!(
+            runtime_target(GameType::SkyrimSE, "sksevr_1_4_15.dll"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_game_build() {
+        let build = |game_type, v| GameBuild::detect(game_type, BinaryVersion(v));
+        assert_eq!(
+            build(GameType::SkyrimSE, [1, 6, 1170, 0]),
+            Some(GameBuild::SkyrimAE)
+        );
+        assert_eq!(
+            build(GameType::SkyrimSE, [1, 5, 97, 0]),
+            Some(GameBuild::SkyrimSE)
+        );
+        assert_eq!(
+            build(GameType::Fallout4, [1, 10, 984, 0]),
+            Some(GameBuild::Fallout4NextGen)
+        );
+        assert_eq!(build(GameType::Starfield, [1, 14, 70, 0]), None);
+
+        let se = GameBuild::SkyrimSE;
+        assert!(se.matches("Address Library for SKSE Plugins - SE"));
+        assert!(se.matches("SSE Engine Fixes (1.5.97)"));
+        assert!(se.excludes("powerofthree's Tweaks AE"));
+        assert!(!se.excludes("SkyUI"));
+        assert!(!se.excludes("Engine Fixes AE and SE"));
+        assert!(!GameBuild::SkyrimAE.matches("Main File - Base"));
+        assert!(GameBuild::Fallout4OldGen.excludes("Buffout 4 Next-Gen"));
+    }
+
+    #[test]
+    fn test_mismatch_message() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("skse64_1_6_1170.dll"),
+            pe_bytes([0, 2, 2, 3]),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("SkyrimSE.exe"), pe_bytes([1, 5, 97, 0])).unwrap();
+
+        let game = Game::new(GameType::SkyrimSE, dir.path().to_path_buf());
+        let check = check_script_extender(&game, &[]);
+        assert_eq!(
+            check.mismatch().as_deref(),
+            Some("SKSE 2.2.3 requires Skyrim Special Edition 1.6.1170, you have 1.5.97")
+        );
+
+        std::fs::write(dir.path().join("SkyrimSE.exe"), pe_bytes([1, 6, 1170, 0])).unwrap();
+        assert!(check_script_extender(&game, &[]).mismatch().is_none());
+    }
+}
@@ -18,8 +18,9 @@ impl PluginFilter {
             "fallout4" | "fallout4vr" => fallout4_skip_patterns(),
             "starfield" => starfield_skip_patterns(),
             "fallout3" | "falloutnv" => fallout3_skip_patterns(),
-            "oblivion" => oblivion_skip_patterns(),
-            "morrowind" => morrowind_skip_patterns(),
+            "oblivion" | "oblivionremastered" => oblivion_skip_patterns(),
+            "morrowind" | "openmw" => morrowind_skip_patterns(),
+            "enderal" | "enderalse" | "enderalspecialedition" => enderal_skip_patterns(),
             _ => HashSet::new(),
         };
 
@@ -142,7 +143,7 @@ fn fallout3_skip_patterns() -> HashSet<String> {
     patterns.into_iter().map(|s| s.to_string()).collect()
 }
 
-/// Oblivion base game and DLC plugins
+/// Oblivion (classic and Remastered) base game and DLC plugins
 fn oblivion_skip_patterns() -> HashSet<String> {
     let patterns = vec![
         "oblivion.esm",
@@ -152,10 +153,16 @@ fn oblivion_skip_patterns() -> HashSet<String> {
         "dlcvilelair.esp",
         "dlcmehrunesrazor.esp",
         "dlcspelltomes.esp",
-        "dlcthievesd.esp",
+        "dlcthievesden.esp",
         "dlcorrery.esp",
-        "dlchorsearmorpack.esp",
+        "dlchorsearmor.esp",
         "dlcbattlehorncastle.esp",
+        // Oblivion Remastered
+        "altarespmain.esp",
+        "altardeluxe.esp",
+        "altaresplocal.esp",
+        "altargymnavigation.esp",
+        "tamrielleveledregion.esp",
     ];
 
     patterns.into_iter().map(|s| s.to_string()).collect()
@@ -168,6 +175,17 @@ fn morrowind_skip_patterns() -> HashSet<String> {
     patterns.into_iter().map(|s| s.to_string()).collect()
 }
 
+/// Enderal base game plugins (Skyrim's masters plus its own)
+fn enderal_skip_patterns() -> HashSet<String> {
+    let patterns = vec![
+        "skyrim.esm",
+        "update.esm",
+        "enderal - forgotten stories.esm",
+    ];
+
+    patterns.into_iter().map(|s| s.to_string()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,7 +37,11 @@ impl ModMatcher {
             "fallout3" => ("fallout3", "fallout3"),
             "falloutnv" => ("falloutnv", "falloutnv"),
             "oblivion" => ("oblivion", "oblivion"),
+            "oblivionremastered" => ("oblivionremastered", "oblivionremastered"),
             "morrowind" => ("morrowind", "morrowind"),
+            "openmw" => ("openmw", "morrowind"),
+            "enderal" => ("enderal", "enderal"),
+            "enderalse" | "enderalspecialedition" => ("enderalse", "enderalspecialedition"),
             other => (other, other),
         };
 
@@ -429,16 +433,9 @@ fn normalize_name(name: &str) -> String {
 
     // Normalize punctuation to spaces
     normalized = normalized
-        .replace('+', " ")
-        .replace('-', " ")
-        .replace('_', " ")
-        .replace('\'', "")
-        .replace('\"', "")
-        .replace(':', "")
-        .replace('(', " ")
-        .replace(')', " ")
-        .replace('[', " ")
-        .replace(']', " ");
+        .replace(['+', '-', '_'], " ")
+        .replace(['\'', '\"', ':'], "")
+        .replace(['(', ')', '[', ']'], " ");
 
     // Remove common edition markers for comparison
     normalized = normalized
@@ -478,11 +475,11 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
 
     let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
 
-    for i in 0..=len1 {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=len2 {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
     let s1_chars: Vec<char> = s1.chars().collect();
@@ -625,10 +622,7 @@ fn calculate_match_score(query: &str, result: &ModSearchResult, mod_id_hint: Opt
 }
 
 fn extract_plugin_filename(raw: &str) -> Option<String> {
-    let trimmed = raw
-        .trim()
-        .trim_start_matches(|c| c == '+' || c == '-' || c == '*')
-        .trim();
+    let trimmed = raw.trim().trim_start_matches(['+', '-', '*']).trim();
     if trimmed.is_empty() {
         return None;
     }
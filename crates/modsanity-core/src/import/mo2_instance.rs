@@ -0,0 +1,447 @@
+//! Importing a whole Mod Organizer 2 instance
+//!
+//! An instance directory holds `ModOrganizer.ini`, a `mods/` folder with one
+//! extracted folder per mod and `profiles/<name>/` with each profile's
+//! `modlist.txt`, `plugins.txt`, `loadorder.txt` and, with profile-specific
+//! game settings, its own INI files. Mod folders are adopted into staging
+//! under their folder name (the name `modlist.txt` uses) and every profile
+//! becomes a ModSanity profile.
+
+use super::modlist_parser::is_separator_entry;
+use crate::games::GameType;
+use crate::mods::{AdoptMode, ModManager};
+use crate::profiles::{parse_ini, IniSetting, Profile, ProfileManager};
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// An MO2 instance on disk
+#[derive(Debug, Clone)]
+pub struct Mo2Instance {
+    pub root: PathBuf,
+    pub mods_dir: PathBuf,
+    pub profiles_dir: PathBuf,
+    /// Profile selected in MO2 when the instance was last used
+    pub selected_profile: Option<String>,
+}
+
+/// One MO2 profile
+#[derive(Debug, Clone, Default)]
+pub struct Mo2Profile {
+    pub name: String,
+    /// Mods and their enabled state, highest priority first (MO2's file order)
+    pub mods: Vec<(String, bool)>,
+    /// Plugins and their enabled state from `plugins.txt`
+    pub plugins: Vec<(String, bool)>,
+    pub load_order: Vec<String>,
+    /// Settings from the profile's own INI files
+    pub ini: Vec<IniSetting>,
+}
+
+impl Mo2Instance {
+    /// Open an instance directory, following the mod and profile folders set
+    /// in `ModOrganizer.ini` when they exist on this system
+    pub fn open(path: &Path) -> Result<Self> {
+        if !path.is_dir() {
+            bail!("Not a directory: {}", path.display());
+        }
+        let root = path.to_path_buf();
+        let ini = std::fs::read_to_string(root.join("ModOrganizer.ini")).unwrap_or_default();
+        let settings = parse_ini("ModOrganizer.ini", &ini);
+        let value = |section: &str, key: &str| {
+            settings
+                .iter()
+                .find(|s| {
+                    s.section.eq_ignore_ascii_case(section) && s.key.eq_ignore_ascii_case(key)
+                })
+                .map(|s| unquote(&s.value))
+        };
+
+        let base = value("Settings", "base_directory")
+            .map(|b| to_local_path(&b))
+            .filter(|b| b.is_dir())
+            .unwrap_or_else(|| root.clone());
+        let dir = |key: &str, default: &str| {
+            value("Settings", key)
+                .map(|v| to_local_path(&v.replace("%BASE_DIR%", &base.to_string_lossy())))
+                .filter(|p| p.is_dir())
+                .unwrap_or_else(|| base.join(default))
+        };
+        let mods_dir = dir("mod_directory", "mods");
+        let profiles_dir = dir("profiles_directory", "profiles");
+        if !mods_dir.is_dir() && !profiles_dir.is_dir() {
+            bail!(
+                "{} is not an MO2 instance (no mods or profiles folder)",
+                root.display()
+            );
+        }
+
+        Ok(Self {
+            root,
+            mods_dir,
+            profiles_dir,
+            selected_profile: value("General", "selected_profile")
+                .map(|p| strip_byte_array(&p))
+                .filter(|p| !p.is_empty()),
+        })
+    }
+
+    /// Mod folders, sorted by name. Separators are skipped.
+    pub fn mod_folders(&self) -> Result<Vec<PathBuf>> {
+        let mut folders = Vec::new();
+        if !self.mods_dir.is_dir() {
+            return Ok(folders);
+        }
+        for entry in std::fs::read_dir(&self.mods_dir)
+            .with_context(|| format!("Failed to read {}", self.mods_dir.display()))?
+        {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if path.is_dir() && !name.starts_with('.') && !is_separator_entry(name) {
+                folders.push(path);
+            }
+        }
+        folders.sort();
+        Ok(folders)
+    }
+
+    /// Every profile, with the INI files `game_type` uses when the profile
+    /// has its own copies
+    pub fn profiles(&self, game_type: Option<GameType>) -> Result<Vec<Mo2Profile>> {
+        let mut profiles = Vec::new();
+        if !self.profiles_dir.is_dir() {
+            return Ok(profiles);
+        }
+        for entry in std::fs::read_dir(&self.profiles_dir)
+            .with_context(|| format!("Failed to read {}", self.profiles_dir.display()))?
+        {
+            let dir = entry?.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            let name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let read = |file: &str| std::fs::read_to_string(dir.join(file)).unwrap_or_default();
+
+            let mut ini = Vec::new();
+            for file in game_type.map(|g| g.ini_files()).unwrap_or_default() {
+                let content = read(file);
+                if !content.is_empty() {
+                    ini.extend(parse_ini(file, &content));
+                }
+            }
+            profiles.push(Mo2Profile {
+                name,
+                mods: parse_mo2_modlist(&read("modlist.txt")),
+                plugins: parse_plugins_txt(&read("plugins.txt")),
+                load_order: read("loadorder.txt")
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(str::to_string)
+                    .collect(),
+                ini,
+            });
+        }
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(profiles)
+    }
+}
+
+impl Mo2Profile {
+    /// ModSanity profile for `game_id`, keeping only mods in `known`.
+    /// MO2 lists the winning mod first; ModSanity gives it the highest priority.
+    pub fn to_profile(&self, game_id: &str, known: &HashSet<String>) -> Profile {
+        let mut profile = Profile::new(&self.name, game_id);
+        let mods: Vec<&(String, bool)> = self
+            .mods
+            .iter()
+            .filter(|(name, _)| known.contains(name))
+            .collect();
+        for (index, (name, enabled)) in mods.iter().enumerate() {
+            profile.add_mod(name.as_str(), *enabled, (mods.len() - index - 1) as i32);
+        }
+        profile.set_enabled_plugins(
+            self.plugins
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| name.clone())
+                .collect(),
+        );
+        let load_order = if self.load_order.is_empty() {
+            self.plugins.iter().map(|(name, _)| name.clone()).collect()
+        } else {
+            self.load_order.clone()
+        };
+        profile.set_load_order(load_order);
+        profile.set_ini_overrides(&self.ini);
+        profile
+    }
+}
+
+/// Parse an MO2 profile `modlist.txt`: `+Name` enabled, `-Name` disabled.
+/// Unmanaged entries (`*Name`) and separators are skipped.
+pub fn parse_mo2_modlist(content: &str) -> Vec<(String, bool)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            let enabled = match line.chars().next()? {
+                '+' => true,
+                '-' => false,
+                _ => return None,
+            };
+            let name = line[1..].trim();
+            (!name.is_empty() && !is_separator_entry(name)).then(|| (name.to_string(), enabled))
+        })
+        .collect()
+}
+
+/// Parse a `plugins.txt`: `*Name.esp` enabled, `Name.esp` disabled
+pub fn parse_plugins_txt(content: &str) -> Vec<(String, bool)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| match l.strip_prefix('*') {
+            Some(name) => (name.trim().to_string(), true),
+            None => (l.to_string(), false),
+        })
+        .collect()
+}
+
+/// What an instance import did, or would do with `dry_run`
+#[derive(Debug, Clone, Default)]
+pub struct Mo2ImportReport {
+    pub dry_run: bool,
+    /// Mod folders adopted into staging
+    pub mods_adopted: Vec<String>,
+    /// Mod folders skipped because a mod with that name is installed
+    pub mods_existing: Vec<String>,
+    pub mods_failed: Vec<(String, String)>,
+    pub profiles: Vec<Mo2ProfileSummary>,
+    pub selected_profile: Option<String>,
+}
+
+/// One imported profile
+#[derive(Debug, Clone)]
+pub struct Mo2ProfileSummary {
+    pub name: String,
+    pub mods: usize,
+    pub enabled_mods: usize,
+    /// Mods listed in `modlist.txt` without a folder or installed mod
+    pub missing_mods: usize,
+    pub plugins: usize,
+    pub ini_settings: usize,
+    /// An existing ModSanity profile with this name was overwritten
+    pub replaced: bool,
+}
+
+impl Mo2ImportReport {
+    /// Human-readable report, one line per entry
+    pub fn lines(&self) -> Vec<String> {
+        let adopt = if self.dry_run {
+            "Would adopt"
+        } else {
+            "Adopted"
+        };
+        let mut lines = vec![
+            format!("{} {} mod folder(s)", adopt, self.mods_adopted.len()),
+            format!("Already installed: {}", self.mods_existing.len()),
+        ];
+        for (name, error) in &self.mods_failed {
+            lines.push(format!("Failed: {} ({})", name, error));
+        }
+        for p in &self.profiles {
+            let mut line = format!(
+                "Profile '{}': {} mods ({} enabled), {} plugins, {} INI settings",
+                p.name, p.mods, p.enabled_mods, p.plugins, p.ini_settings
+            );
+            if p.missing_mods > 0 {
+                line.push_str(&format!(", {} missing mods skipped", p.missing_mods));
+            }
+            if p.replaced {
+                line.push_str(if self.dry_run {
+                    " [would replace existing]"
+                } else {
+                    " [replaced existing]"
+                });
+            }
+            lines.push(line);
+        }
+        if let Some(name) = &self.selected_profile {
+            lines.push(format!("MO2's selected profile was '{}'", name));
+        }
+        lines
+    }
+}
+
+/// Import an MO2 instance for `game_id`. Mod folders are copied, moved or
+/// linked into staging per `mode`, then every profile is created or
+/// replaced. `progress` receives (stage, current, total, item). With
+/// `dry_run`, nothing is written and the report describes the import.
+pub async fn import_mo2_instance<F>(
+    mods: &ModManager,
+    profiles: &ProfileManager,
+    game_id: &str,
+    instance: &Mo2Instance,
+    mode: AdoptMode,
+    dry_run: bool,
+    mut progress: F,
+) -> Result<Mo2ImportReport>
+where
+    F: FnMut(&str, usize, usize, &str),
+{
+    let mut report = Mo2ImportReport {
+        dry_run,
+        selected_profile: instance.selected_profile.clone(),
+        ..Default::default()
+    };
+    let mut known: HashSet<String> = mods
+        .list_mods(game_id)
+        .await?
+        .into_iter()
+        .map(|m| m.name)
+        .collect();
+
+    let folders = instance.mod_folders()?;
+    for (index, folder) in folders.iter().enumerate() {
+        let name = folder
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        progress("Adopting mods", index + 1, folders.len(), &name);
+        if known.contains(&name) {
+            report.mods_existing.push(name);
+            continue;
+        }
+        if dry_run {
+            known.insert(name.clone());
+            report.mods_adopted.push(name);
+            continue;
+        }
+        match mods
+            .adopt_directory(game_id, folder, mode, Some(&name))
+            .await
+        {
+            Ok(installed) => {
+                known.insert(installed.name.clone());
+                report.mods_adopted.push(installed.name);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to adopt {}: {}", folder.display(), e);
+                report.mods_failed.push((name, e.to_string()));
+            }
+        }
+    }
+
+    let existing: HashSet<String> = profiles
+        .list_profiles(game_id)
+        .await?
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    let mo2_profiles = instance.profiles(GameType::from_id(game_id))?;
+    for (index, mo2) in mo2_profiles.iter().enumerate() {
+        progress(
+            "Importing profiles",
+            index + 1,
+            mo2_profiles.len(),
+            &mo2.name,
+        );
+        let profile = mo2.to_profile(game_id, &known);
+        let replaced = existing.contains(&profile.name);
+        if !dry_run {
+            profiles.save_imported_profile(&profile).await?;
+        }
+        report.profiles.push(Mo2ProfileSummary {
+            name: profile.name.clone(),
+            mods: profile.mods.len(),
+            enabled_mods: profile.mods.values().filter(|m| m.enabled).count(),
+            missing_mods: mo2.mods.len() - profile.mods.len(),
+            plugins: mo2.plugins.len(),
+            ini_settings: profile.ini_overrides.len(),
+            replaced,
+        });
+    }
+
+    Ok(report)
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
+
+/// `@ByteArray(Default)` -> `Default`
+fn strip_byte_array(value: &str) -> String {
+    value
+        .strip_prefix("@ByteArray(")
+        .and_then(|v| v.strip_suffix(')'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// Paths written by MO2 on Windows or under Wine: `Z:\home\x` -> `/home/x`
+fn to_local_path(value: &str) -> PathBuf {
+    let value = value.replace('\\', "/");
+    let bytes = value.as_bytes();
+    if bytes.len() >= 2 && bytes[1] == b':' && bytes[0].eq_ignore_ascii_case(&b'z') {
+        return PathBuf::from(&value[2..]);
+    }
+    PathBuf::from(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_instance_and_convert_profile() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for dir in ["mods/SkyUI", "mods/Lux", "mods/Visuals_separator"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let profile = root.join("profiles/Survival");
+        std::fs::create_dir_all(&profile).unwrap();
+        std::fs::write(
+            root.join("ModOrganizer.ini"),
+            "[General]\nselected_profile=@ByteArray(Survival)\n[Settings]\nmod_directory=C:/missing/mods\n",
+        )
+        .unwrap();
+        std::fs::write(
+            profile.join("modlist.txt"),
+            "# This file was automatically generated by Mod Organizer.\n+Lux\n-Visuals_separator\n-SkyUI\n*DLC: Dawnguard\n+Gone\n",
+        )
+        .unwrap();
+        std::fs::write(profile.join("plugins.txt"), "*Lux.esp\nSkyUI_SE.esp\n").unwrap();
+        std::fs::write(profile.join("Skyrim.ini"), "[Display]\nfGamma=1.2\n").unwrap();
+
+        let instance = Mo2Instance::open(root).unwrap();
+        assert_eq!(instance.mods_dir, root.join("mods"));
+        assert_eq!(instance.selected_profile.as_deref(), Some("Survival"));
+        let folders: Vec<_> = instance
+            .mod_folders()
+            .unwrap()
+            .into_iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(folders, vec!["Lux", "SkyUI"]);
+
+        let mo2 = instance.profiles(Some(GameType::SkyrimSE)).unwrap();
+        assert_eq!(mo2.len(), 1);
+        assert_eq!(mo2[0].ini.len(), 1);
+
+        let known: HashSet<String> = ["Lux", "SkyUI"].iter().map(|s| s.to_string()).collect();
+        let profile = mo2[0].to_profile("skyrimse", &known);
+        assert_eq!(profile.mods.len(), 2);
+        assert!(profile.mods["Lux"].enabled);
+        assert!(profile.mods["Lux"].priority > profile.mods["SkyUI"].priority);
+        assert_eq!(profile.enabled_plugins, vec!["Lux.esp"]);
+        assert_eq!(profile.load_order, vec!["Lux.esp", "SkyUI_SE.esp"]);
+    }
+}
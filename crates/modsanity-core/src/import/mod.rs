@@ -7,18 +7,29 @@
 pub mod filters;
 pub mod library_check;
 pub mod matcher;
+pub mod mo2_instance;
+pub mod modlist_diff;
 pub mod modlist_format;
 pub mod modlist_parser;
+pub mod report;
+pub mod watch;
 
 pub use filters::PluginFilter;
 pub use library_check::{check_library, LibraryCheckResult};
 pub use matcher::{MatchConfidence, MatchResult, ModMatcher};
+pub use mo2_instance::{import_mo2_instance, Mo2ImportReport, Mo2Instance, Mo2Profile};
+pub use modlist_diff::{
+    diff_modlists, resolve_modlist, ModlistDiff, ModlistSnapshot, ModlistSource,
+};
 pub use modlist_format::{
     detect_format, ModSanityModlist, ModlistEntry, ModlistFormat, ModlistMeta, PluginOrderEntry,
 };
 pub use modlist_parser::{ModlistParser, PluginEntry};
+pub use report::{MatchReport, ReportFormat};
+pub use watch::{is_mod_archive, DownloadWatcher};
 
 use crate::db::Database;
+use crate::extensions::{ExtensionContext, ExtensionRegistry};
 use anyhow::Result;
 use std::path::Path;
 use std::sync::Arc;
@@ -28,6 +39,7 @@ pub struct ModlistImporter {
     parser: ModlistParser,
     filter: PluginFilter,
     matcher: ModMatcher,
+    extensions: Option<(ExtensionRegistry, ExtensionContext)>,
 }
 
 impl ModlistImporter {
@@ -46,7 +58,20 @@ impl ModlistImporter {
             parser: ModlistParser::new(),
             filter: PluginFilter::for_game(game_id),
             matcher: ModMatcher::with_catalog(game_id.to_string(), nexus_client, db),
+            extensions: None,
+        }
+    }
+
+    /// Consult extension matchers for plugins the built-in matcher cannot resolve
+    pub fn with_extensions(
+        mut self,
+        registry: ExtensionRegistry,
+        context: ExtensionContext,
+    ) -> Self {
+        if !registry.is_empty() {
+            self.extensions = Some((registry, context));
         }
+        self
     }
 
     /// Import a modlist.txt file
@@ -94,6 +119,13 @@ impl ModlistImporter {
             }
 
             match self.matcher.match_plugin(&plugin).await {
+                Ok(result) if result.confidence.is_none() => {
+                    let fallback = match &self.extensions {
+                        Some((registry, context)) => registry.match_plugin(&plugin, context).await,
+                        None => None,
+                    };
+                    matches.push(fallback.unwrap_or(result));
+                }
                 Ok(result) => matches.push(result),
                 Err(e) => {
                     tracing::warn!("Failed to match plugin {}: {}", plugin.plugin_name, e);
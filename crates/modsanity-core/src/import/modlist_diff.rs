@@ -0,0 +1,430 @@
+//! Comparing modlists
+//!
+//! Saved modlists, modlist files (native JSON, MO2 `modlist.txt` or
+//! `plugins.txt`) and the current setup are reduced to a [`ModlistSnapshot`]
+//! and compared: mods added or removed, version and enabled-state changes,
+//! and plugins that were added, removed or moved in the load order.
+
+use super::mo2_instance::{parse_mo2_modlist, parse_plugins_txt};
+use super::modlist_format::{detect_format, load_native, ModlistFormat};
+use crate::db::Database;
+use crate::games::Game;
+use crate::mods::ModManager;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Where a modlist to compare comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModlistSource {
+    /// Installed mods and the game's plugin order
+    Current,
+    /// A modlist saved in the database, by name
+    Saved(String),
+    /// A modlist file
+    File(PathBuf),
+}
+
+impl ModlistSource {
+    /// Parse `current`, `saved:<name>`, a path to an existing file, or a
+    /// saved modlist name
+    pub fn parse(spec: &str) -> Self {
+        if spec.eq_ignore_ascii_case("current") {
+            return Self::Current;
+        }
+        if let Some(name) = spec.strip_prefix("saved:") {
+            return Self::Saved(name.to_string());
+        }
+        let path = Path::new(spec);
+        if path.is_file() {
+            return Self::File(path.to_path_buf());
+        }
+        Self::Saved(spec.to_string())
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Current => "current setup".to_string(),
+            Self::Saved(name) => format!("modlist '{}'", name),
+            Self::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+/// A mod as far as modlist comparison is concerned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnapshotMod {
+    pub name: String,
+    pub version: Option<String>,
+    pub nexus_mod_id: Option<i64>,
+    pub enabled: bool,
+}
+
+/// Mods and enabled plugins (in load order) of one modlist
+#[derive(Debug, Clone, Default)]
+pub struct ModlistSnapshot {
+    pub mods: Vec<SnapshotMod>,
+    pub plugins: Vec<String>,
+}
+
+impl ModlistSnapshot {
+    /// Read a native JSON modlist, MO2 `modlist.txt` or `plugins.txt`
+    pub fn from_file(path: &Path) -> Result<Self> {
+        if detect_format(path)? == ModlistFormat::Native {
+            let modlist = load_native(path)?;
+            let mut plugins: Vec<_> = modlist.plugins.iter().filter(|p| p.enabled).collect();
+            plugins.sort_by_key(|p| p.load_order);
+            return Ok(Self {
+                mods: modlist
+                    .mods
+                    .iter()
+                    .map(|m| SnapshotMod {
+                        name: m.name.clone(),
+                        version: Some(m.version.clone()).filter(|v| !v.is_empty()),
+                        nexus_mod_id: m.nexus_mod_id,
+                        enabled: m.enabled,
+                    })
+                    .collect(),
+                plugins: plugins.iter().map(|p| p.filename.clone()).collect(),
+            });
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut snapshot = Self::default();
+        for (name, enabled) in parse_mo2_modlist(&content) {
+            if is_plugin(&name) {
+                if enabled {
+                    snapshot.plugins.push(name);
+                }
+            } else {
+                snapshot.mods.push(SnapshotMod {
+                    name,
+                    version: None,
+                    nexus_mod_id: None,
+                    enabled,
+                });
+            }
+        }
+        if snapshot.mods.is_empty() && snapshot.plugins.is_empty() {
+            // No +/- entries: a plugins.txt
+            snapshot.plugins = parse_plugins_txt(&content)
+                .into_iter()
+                .filter(|(name, enabled)| *enabled && is_plugin(name))
+                .map(|(name, _)| name)
+                .collect();
+        }
+        Ok(snapshot)
+    }
+}
+
+/// Resolve `source` for `game`
+pub async fn resolve_modlist(
+    mods: &ModManager,
+    db: &Database,
+    game: &Game,
+    source: &ModlistSource,
+) -> Result<ModlistSnapshot> {
+    match source {
+        ModlistSource::Current => {
+            let mut installed = mods.list_mods(&game.id).await?;
+            installed.sort_by_key(|m| m.priority);
+            let plugins = crate::plugins::get_plugins(game)?
+                .into_iter()
+                .filter(|p| p.enabled)
+                .map(|p| p.filename)
+                .collect();
+            Ok(ModlistSnapshot {
+                mods: installed
+                    .into_iter()
+                    .map(|m| SnapshotMod {
+                        name: m.name,
+                        version: Some(m.version),
+                        nexus_mod_id: m.nexus_mod_id,
+                        enabled: m.enabled,
+                    })
+                    .collect(),
+                plugins,
+            })
+        }
+        ModlistSource::Saved(name) => {
+            let Some(modlist) = db
+                .get_modlists_for_game(&game.id)?
+                .into_iter()
+                .find(|m| m.name.eq_ignore_ascii_case(name))
+            else {
+                bail!("No saved modlist or file named '{}'", name);
+            };
+            let mut entries = db.get_modlist_entries(modlist.id.unwrap_or_default())?;
+            entries.sort_by_key(|e| e.position);
+            Ok(ModlistSnapshot {
+                plugins: entries
+                    .iter()
+                    .filter(|e| e.enabled)
+                    .filter_map(|e| e.plugin_name.clone())
+                    .filter(|p| is_plugin(p))
+                    .collect(),
+                mods: entries
+                    .into_iter()
+                    .map(|e| SnapshotMod {
+                        name: e.name,
+                        version: e.version.filter(|v| !v.is_empty()),
+                        nexus_mod_id: e.nexus_mod_id,
+                        enabled: e.enabled,
+                    })
+                    .collect(),
+            })
+        }
+        ModlistSource::File(path) => ModlistSnapshot::from_file(path),
+    }
+}
+
+/// A mod present on both sides with a different version or enabled state
+#[derive(Debug, Clone, Serialize)]
+pub struct ModChange {
+    pub name: String,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+    pub from_enabled: bool,
+    pub to_enabled: bool,
+}
+
+impl ModChange {
+    pub fn version_changed(&self) -> bool {
+        match (&self.from_version, &self.to_version) {
+            (Some(a), Some(b)) => a != b,
+            _ => false,
+        }
+    }
+}
+
+/// A plugin whose load order position differs. `from`/`to` are 1-based
+/// positions among enabled plugins; `None` on the side that lacks it.
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginMove {
+    pub plugin: String,
+    pub from: Option<usize>,
+    pub to: Option<usize>,
+}
+
+/// Differences from one modlist to another
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModlistDiff {
+    pub added: Vec<SnapshotMod>,
+    pub removed: Vec<SnapshotMod>,
+    pub changed: Vec<ModChange>,
+    pub plugins_added: Vec<PluginMove>,
+    pub plugins_removed: Vec<PluginMove>,
+    /// Plugins present on both sides that changed place relative to the others
+    pub plugins_moved: Vec<PluginMove>,
+}
+
+impl ModlistDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.plugins_added.is_empty()
+            && self.plugins_removed.is_empty()
+            && self.plugins_moved.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize modlist diff")
+    }
+}
+
+/// Compare `left` to `right`. Mods match by Nexus ID when both sides have
+/// one, otherwise by name (case-insensitive).
+pub fn diff_modlists(left: &ModlistSnapshot, right: &ModlistSnapshot) -> ModlistDiff {
+    let mut diff = ModlistDiff::default();
+
+    let find = |side: &[SnapshotMod], m: &SnapshotMod| -> Option<usize> {
+        side.iter()
+            .position(|o| match (o.nexus_mod_id, m.nexus_mod_id) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            })
+            .or_else(|| {
+                side.iter()
+                    .position(|o| o.name.eq_ignore_ascii_case(&m.name))
+            })
+    };
+
+    let mut matched = vec![false; right.mods.len()];
+    for m in &left.mods {
+        match find(&right.mods, m).filter(|&i| !matched[i]) {
+            Some(i) => {
+                matched[i] = true;
+                let other = &right.mods[i];
+                let change = ModChange {
+                    name: other.name.clone(),
+                    from_version: m.version.clone(),
+                    to_version: other.version.clone(),
+                    from_enabled: m.enabled,
+                    to_enabled: other.enabled,
+                };
+                if change.version_changed() || m.enabled != other.enabled {
+                    diff.changed.push(change);
+                }
+            }
+            None => diff.removed.push(m.clone()),
+        }
+    }
+    diff.added = right
+        .mods
+        .iter()
+        .zip(&matched)
+        .filter(|(_, matched)| !**matched)
+        .map(|(m, _)| m.clone())
+        .collect();
+
+    let position = |plugins: &[String]| -> HashMap<String, usize> {
+        plugins
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.to_lowercase(), i + 1))
+            .collect()
+    };
+    let (left_pos, right_pos) = (position(&left.plugins), position(&right.plugins));
+    for p in &left.plugins {
+        if !right_pos.contains_key(&p.to_lowercase()) {
+            diff.plugins_removed.push(PluginMove {
+                plugin: p.clone(),
+                from: left_pos.get(&p.to_lowercase()).copied(),
+                to: None,
+            });
+        }
+    }
+    for p in &right.plugins {
+        if !left_pos.contains_key(&p.to_lowercase()) {
+            diff.plugins_added.push(PluginMove {
+                plugin: p.clone(),
+                from: None,
+                to: right_pos.get(&p.to_lowercase()).copied(),
+            });
+        }
+    }
+
+    // Plugins on both sides outside their longest common ordering moved
+    let common_left: Vec<&String> = left
+        .plugins
+        .iter()
+        .filter(|p| right_pos.contains_key(&p.to_lowercase()))
+        .collect();
+    let common_right: Vec<&String> = right
+        .plugins
+        .iter()
+        .filter(|p| left_pos.contains_key(&p.to_lowercase()))
+        .collect();
+    let kept = longest_common_order(&common_left, &common_right);
+    for p in common_right {
+        let key = p.to_lowercase();
+        if !kept.contains(&key) {
+            diff.plugins_moved.push(PluginMove {
+                plugin: p.clone(),
+                from: left_pos.get(&key).copied(),
+                to: right_pos.get(&key).copied(),
+            });
+        }
+    }
+
+    diff
+}
+
+/// Lowercased names in the longest common subsequence of `a` and `b`
+fn longest_common_order(a: &[&String], b: &[&String]) -> std::collections::HashSet<String> {
+    let (n, m) = (a.len(), b.len());
+    let eq = |i: usize, j: usize| a[i].eq_ignore_ascii_case(b[j]);
+    // lengths[i][j]: LCS of a[i..] and b[j..]
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if eq(i, j) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let mut kept = std::collections::HashSet::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if eq(i, j) {
+            kept.insert(a[i].to_lowercase());
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    kept
+}
+
+fn is_plugin(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.ends_with(".esp") || lower.ends_with(".esm") || lower.ends_with(".esl")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(mods: &[(&str, &str, bool)], plugins: &[&str]) -> ModlistSnapshot {
+        ModlistSnapshot {
+            mods: mods
+                .iter()
+                .map(|(name, version, enabled)| SnapshotMod {
+                    name: name.to_string(),
+                    version: Some(version.to_string()),
+                    nexus_mod_id: None,
+                    enabled: *enabled,
+                })
+                .collect(),
+            plugins: plugins.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_mods_and_plugin_moves() {
+        let left = snapshot(
+            &[
+                ("SkyUI", "5.1", true),
+                ("Lux", "1.0", true),
+                ("Old", "1", true),
+            ],
+            &["Skyrim.esm", "A.esp", "B.esp", "C.esp", "Gone.esp"],
+        );
+        let right = snapshot(
+            &[
+                ("skyui", "5.2", true),
+                ("Lux", "1.0", false),
+                ("New", "2", true),
+            ],
+            &["Skyrim.esm", "B.esp", "C.esp", "A.esp", "Fresh.esp"],
+        );
+
+        let diff = diff_modlists(&left, &right);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "New");
+        assert_eq!(diff.removed[0].name, "Old");
+        assert_eq!(diff.changed.len(), 2);
+        assert!(diff.changed[0].version_changed());
+        assert!(!diff.changed[1].version_changed() && !diff.changed[1].to_enabled);
+
+        assert_eq!(diff.plugins_added[0].plugin, "Fresh.esp");
+        assert_eq!(diff.plugins_removed[0].plugin, "Gone.esp");
+        // Only A.esp moved; B and C keep their relative order
+        assert_eq!(diff.plugins_moved.len(), 1);
+        assert_eq!(diff.plugins_moved[0].plugin, "A.esp");
+        assert_eq!(
+            (diff.plugins_moved[0].from, diff.plugins_moved[0].to),
+            (Some(2), Some(4))
+        );
+
+        assert!(diff_modlists(&left, &left).is_empty());
+    }
+}
@@ -8,6 +8,12 @@ use std::path::Path;
 /// Parser for Mod Organizer 2 modlist.txt files
 pub struct ModlistParser;
 
+impl Default for ModlistParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ModlistParser {
     pub fn new() -> Self {
         Self
@@ -152,7 +158,7 @@ impl PluginEntry {
         let name = re.replace_all(name, "");
 
         // Replace underscores and hyphens with spaces
-        let name = name.replace('_', " ").replace('-', " ");
+        let name = name.replace(['_', '-'], " ");
 
         // Clean up multiple spaces
         let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
@@ -218,7 +224,7 @@ fn strip_leading_state_marker(name: &str) -> &str {
     trimmed
 }
 
-fn is_separator_entry(name: &str) -> bool {
+pub(super) fn is_separator_entry(name: &str) -> bool {
     name.trim_end().to_ascii_lowercase().ends_with("_separator")
 }
 
@@ -0,0 +1,257 @@
+//! Match reports for reviewing an import outside ModSanity
+//!
+//! Large modlists are easier to check in a spreadsheet than in a terminal.
+//! A report lists every plugin with its match status, the chosen Nexus mod
+//! and the alternatives the matcher considered, as JSON or CSV.
+
+use super::{ImportResult, MatchResult};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// File format of a match report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl ReportFormat {
+    /// CSV for `.csv` files, JSON otherwise
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => ReportFormat::Csv,
+            _ => ReportFormat::Json,
+        }
+    }
+}
+
+/// A candidate the matcher considered but didn't pick
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportAlternative {
+    pub nexus_mod_id: i64,
+    pub name: String,
+    pub author: String,
+    pub downloads: i64,
+    pub score: f32,
+}
+
+/// One plugin of the imported modlist
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportEntry {
+    /// "matched", "needs_review" or "unmatched"
+    pub status: &'static str,
+    pub plugin: String,
+    pub load_order: i32,
+    pub enabled: bool,
+    /// Mod name searched for
+    pub mod_name: String,
+    pub confidence: f32,
+    pub nexus_mod_id: Option<i64>,
+    pub nexus_name: Option<String>,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub url: Option<String>,
+    pub alternatives: Vec<ReportAlternative>,
+}
+
+/// Matching results of one import
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchReport {
+    pub source: String,
+    pub game_id: String,
+    pub generated_at: String,
+    pub total_plugins: usize,
+    pub matched: usize,
+    pub needs_review: usize,
+    pub unmatched: usize,
+    pub entries: Vec<ReportEntry>,
+}
+
+impl MatchReport {
+    /// Build a report of `result`. `game_domain` is used for Nexus links.
+    pub fn new(result: &ImportResult, source: &str, game_id: &str, game_domain: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            game_id: game_id.to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            total_plugins: result.total_plugins,
+            matched: result.auto_matched().count(),
+            needs_review: result.needs_review().count(),
+            unmatched: result.no_matches().count(),
+            entries: result
+                .matches
+                .iter()
+                .map(|m| report_entry(m, game_domain))
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// One row per plugin; alternatives are joined into a single column
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "status,plugin,load_order,enabled,mod_name,confidence,nexus_mod_id,nexus_name,author,version,url,alternatives\n",
+        );
+        for e in &self.entries {
+            let alternatives = e
+                .alternatives
+                .iter()
+                .map(|a| format!("{} ({}, {:.2})", a.name, a.nexus_mod_id, a.score))
+                .collect::<Vec<_>>()
+                .join("; ");
+            let fields = [
+                e.status.to_string(),
+                e.plugin.clone(),
+                e.load_order.to_string(),
+                e.enabled.to_string(),
+                e.mod_name.clone(),
+                format!("{:.2}", e.confidence),
+                e.nexus_mod_id.map(|id| id.to_string()).unwrap_or_default(),
+                e.nexus_name.clone().unwrap_or_default(),
+                e.author.clone().unwrap_or_default(),
+                e.version.clone().unwrap_or_default(),
+                e.url.clone().unwrap_or_default(),
+                alternatives,
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Write the report to `path` in the format its extension names
+    pub fn write(&self, path: &Path) -> Result<ReportFormat> {
+        let format = ReportFormat::from_path(path);
+        let content = match format {
+            ReportFormat::Json => self.to_json()?,
+            ReportFormat::Csv => self.to_csv(),
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(format)
+    }
+}
+
+fn report_entry(m: &MatchResult, game_domain: &str) -> ReportEntry {
+    let status = if m.confidence.is_high() {
+        "matched"
+    } else if m.confidence.needs_review() {
+        "needs_review"
+    } else {
+        "unmatched"
+    };
+    let best = m.best_match.as_ref().filter(|b| b.mod_id > 0);
+    ReportEntry {
+        status,
+        plugin: m.plugin.plugin_name.clone(),
+        load_order: m.plugin.load_order,
+        enabled: m.plugin.enabled,
+        mod_name: m.mod_name.clone(),
+        confidence: m.confidence.score(),
+        nexus_mod_id: best.map(|b| b.mod_id),
+        nexus_name: best.map(|b| b.name.clone()),
+        author: best.map(|b| b.author.clone()),
+        version: best.map(|b| b.version.clone()),
+        url: best.map(|b| {
+            format!(
+                "https://www.nexusmods.com/{}/mods/{}",
+                game_domain, b.mod_id
+            )
+        }),
+        alternatives: m
+            .alternatives
+            .iter()
+            .map(|a| ReportAlternative {
+                nexus_mod_id: a.mod_id,
+                name: a.name.clone(),
+                author: a.author.clone(),
+                downloads: a.downloads,
+                score: a.score,
+            })
+            .collect(),
+    }
+}
+
+/// Quote a CSV field when it holds a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::matcher::{MatchAlternative, MatchedMod};
+    use crate::import::{MatchConfidence, PluginEntry};
+
+    #[test]
+    fn test_report_rows_and_csv_quoting() {
+        let plugin = |name: &str, load_order| PluginEntry {
+            plugin_name: name.to_string(),
+            load_order,
+            enabled: true,
+        };
+        let result = ImportResult {
+            total_plugins: 2,
+            matches: vec![
+                MatchResult {
+                    plugin: plugin("SkyUI_SE.esp", 0),
+                    mod_name: "SkyUI".to_string(),
+                    best_match: Some(MatchedMod {
+                        mod_id: 12604,
+                        name: "SkyUI".to_string(),
+                        author: "schlangster".to_string(),
+                        summary: String::new(),
+                        downloads: 1,
+                        version: "5.2SE".to_string(),
+                    }),
+                    alternatives: vec![MatchAlternative {
+                        mod_id: 3863,
+                        name: "SkyUI, Legacy".to_string(),
+                        summary: String::new(),
+                        author: "x".to_string(),
+                        downloads: 1,
+                        score: 0.5,
+                    }],
+                    confidence: MatchConfidence::High(0.95),
+                },
+                MatchResult::no_match(plugin("Unknown.esp", 1)),
+            ],
+        };
+
+        let report = MatchReport::new(&result, "modlist.txt", "skyrimse", "skyrimspecialedition");
+        assert_eq!((report.matched, report.unmatched), (1, 1));
+        assert_eq!(
+            report.entries[0].url.as_deref(),
+            Some("https://www.nexusmods.com/skyrimspecialedition/mods/12604")
+        );
+
+        let csv = report.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].starts_with("matched,SkyUI_SE.esp,0,true,SkyUI,0.95,12604,"));
+        assert!(rows[1].ends_with(",\"SkyUI, Legacy (3863, 0.50)\""));
+        assert!(rows[2].starts_with("unmatched,Unknown.esp,1,true,"));
+
+        assert_eq!(
+            ReportFormat::from_path(Path::new("r.CSV")),
+            ReportFormat::Csv
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("r.json")),
+            ReportFormat::Json
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Watching the downloads folder for new mod archives
+//!
+//! Browsers download to a temporary name (`.part`, `.crdownload`) and rename
+//! the file once it is complete, while other tools write the final name
+//! directly. An archive is reported when it is moved into the folder or
+//! closed after writing, so half-finished downloads are never offered.
+
+use anyhow::{bail, Context, Result};
+use futures::StreamExt;
+use inotify::{EventStream, Inotify, WatchMask};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Extensions installed from the downloads folder
+pub const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z", "rar"];
+
+/// Whether `path` names a mod archive (by extension, case-insensitive)
+pub fn is_mod_archive(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| {
+            ARCHIVE_EXTENSIONS
+                .iter()
+                .any(|a| ext.eq_ignore_ascii_case(a))
+        })
+}
+
+/// inotify watch on a downloads directory
+pub struct DownloadWatcher {
+    dir: PathBuf,
+    events: EventStream<Vec<u8>>,
+    /// Archives already reported; a file closed twice is only offered once
+    seen: HashSet<PathBuf>,
+}
+
+impl DownloadWatcher {
+    /// Start watching `dir`. Files already in it are not reported.
+    pub fn new(dir: &Path) -> Result<Self> {
+        if !dir.is_dir() {
+            bail!("Downloads directory not found: {}", dir.display());
+        }
+        let inotify = Inotify::init().context("Failed to initialise inotify")?;
+        inotify
+            .watches()
+            .add(dir, WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO)
+            .with_context(|| format!("Failed to watch {}", dir.display()))?;
+        let events = inotify
+            .into_event_stream(vec![0; 4096])
+            .context("Failed to read inotify events")?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            events,
+            seen: HashSet::new(),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Wait for the next complete archive to appear. Returns `None` if the
+    /// watch ends, e.g. because the directory was removed.
+    pub async fn next_archive(&mut self) -> Result<Option<PathBuf>> {
+        while let Some(event) = self.events.next().await {
+            let event = event.context("Failed to read inotify event")?;
+            let Some(name) = event.name else {
+                continue;
+            };
+            let path = self.dir.join(name);
+            if is_mod_archive(&path) && path.is_file() && self.seen.insert(path.clone()) {
+                return Ok(Some(path));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_is_mod_archive() {
+        assert!(is_mod_archive(Path::new("SkyUI_5_2_SE-12604-5-2SE.7z")));
+        assert!(is_mod_archive(Path::new("/dl/USSEP.ZIP")));
+        assert!(!is_mod_archive(Path::new("SkyUI.7z.part")));
+        assert!(!is_mod_archive(Path::new("SkyUI.zip.crdownload")));
+        assert!(!is_mod_archive(Path::new("readme")));
+    }
+
+    async fn next(watcher: &mut DownloadWatcher) -> PathBuf {
+        tokio::time::timeout(Duration::from_secs(5), watcher.next_archive())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reports_finished_archives_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut watcher = DownloadWatcher::new(dir.path()).unwrap();
+
+        std::fs::write(dir.path().join("notes.txt"), "x").unwrap();
+        let partial = dir.path().join("SkyUI.7z.part");
+        std::fs::write(&partial, "data").unwrap();
+        std::fs::rename(&partial, dir.path().join("SkyUI.7z")).unwrap();
+        std::fs::write(dir.path().join("USSEP.zip"), "data").unwrap();
+
+        assert_eq!(next(&mut watcher).await, dir.path().join("SkyUI.7z"));
+        assert_eq!(next(&mut watcher).await, dir.path().join("USSEP.zip"));
+    }
+}
@@ -0,0 +1,60 @@
+//! ModSanity core library
+//!
+//! Frontend-agnostic building blocks for managing Bethesda game mods on Linux.
+//! The `modsanity` TUI/CLI is one consumer of this crate; other frontends
+//! (GTK, Qt, scripts) can be built on the same API without forking.
+//!
+//! Modules:
+//! - [`activity`]: log of state-changing actions (installs, deploys, profile switches, ...)
+//! - [`config`]: user configuration and on-disk path layout
+//! - [`daemon`]: JSON-RPC control socket served by `modsanity daemon`
+//! - [`db`]: SQLite persistence for mods, plugins, modlists, catalog and queue
+//! - [`error`]: failure categories behind the CLI exit codes
+//! - [`extensions`]: third-party commands, screens and matchers run as external processes
+//! - [`games`]: game detection, Steam/Proton discovery
+//! - [`profiles::ini`]: reading and editing the game's INI files, with backups
+//! - [`mods`]: archive extraction, installation, FOMOD and deployment
+//! - [`plugins`]: plugin parsing and load order management
+//! - [`profiles`]: profile snapshots of enabled mods and plugins
+//! - [`nexus`]: NexusMods REST/GraphQL clients and catalog population
+//! - [`collections`]: Nexus collection manifests
+//! - [`import`]: modlist parsing and catalog matching
+//! - [`lock`]: single-instance lock for commands that write
+//! - [`queue`]: persistent download/install queue processing
+//! - [`saves`]: the game's save files and their headers
+//! - `testing`: mock Nexus server and sandbox (`test-harness` feature)
+
+#![allow(
+    clippy::collapsible_match,
+    clippy::too_many_arguments,
+    clippy::type_complexity,
+    clippy::should_implement_trait,
+    clippy::inherent_to_string,
+    clippy::large_enum_variant
+)]
+
+pub const CORE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub mod activity;
+pub mod collections;
+pub mod config;
+pub mod daemon;
+pub mod db;
+pub mod error;
+pub mod extensions;
+pub mod games;
+pub mod import;
+pub mod lock;
+pub mod mods;
+pub mod nexus;
+pub mod plugins;
+pub mod profiles;
+pub mod queue;
+pub mod saves;
+#[cfg(any(test, feature = "test-harness"))]
+pub mod testing;
+
+pub use config::Config;
+pub use db::Database;
+pub use games::{Game, GameType};
+pub use mods::{InstalledMod, ModManager};
@@ -0,0 +1,129 @@
+//! Single-instance lock
+//!
+//! Two ModSanity processes changing staging, the database and plugins.txt at
+//! the same time leave them inconsistent, so anything that writes holds an
+//! exclusive lock on `modsanity.lock` in the data directory. It is an OS file
+//! lock: it goes away with the process, so a crash can't leave it stale. The
+//! file itself only records who holds the lock, for the error message.
+
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::Path;
+
+/// The process holding the lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockHolder {
+    pub pid: u32,
+    /// What it is doing, e.g. `TUI` or `queue process`
+    pub command: String,
+    pub started_at: String,
+}
+
+impl LockHolder {
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines();
+        Some(Self {
+            pid: lines.next()?.trim().parse().ok()?,
+            command: lines.next()?.to_string(),
+            started_at: lines.next().unwrap_or_default().to_string(),
+        })
+    }
+}
+
+/// Error when another instance holds the lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceLocked {
+    pub holder: Option<LockHolder>,
+}
+
+impl std::fmt::Display for InstanceLocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.holder {
+            Some(holder) => write!(
+                f,
+                "Another ModSanity instance is running (pid {}: {}, since {})",
+                holder.pid, holder.command, holder.started_at
+            )?,
+            None => f.write_str("Another ModSanity instance is running")?,
+        }
+        f.write_str(
+            ". Wait for it to finish or close it first; a running queue can be paused or \
+             cancelled from here with 'modsanity queue pause|cancel'.",
+        )
+    }
+}
+
+impl std::error::Error for InstanceLocked {}
+
+/// Exclusive lock held until dropped
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Take the lock at `path` for `command`, failing with [`InstanceLocked`]
+    /// if another process has it
+    pub fn acquire(path: &Path, command: &str) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let holder = std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| LockHolder::parse(&content));
+                return Err(InstanceLocked { holder }.into());
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", path.display()))
+            }
+        }
+
+        file.set_len(0)?;
+        writeln!(
+            file,
+            "{}\n{}\n{}",
+            std::process::id(),
+            command,
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+        )?;
+        Ok(Self { _file: file })
+    }
+}
+
+/// Whether `err` is an [`InstanceLocked`] error
+pub fn is_instance_locked(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<InstanceLocked>().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_instance_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("modsanity.lock");
+
+        let lock = InstanceLock::acquire(&path, "queue process").unwrap();
+        let err = InstanceLock::acquire(&path, "TUI").unwrap_err();
+        assert!(is_instance_locked(&err));
+        let holder = err.downcast_ref::<InstanceLocked>().unwrap().holder.clone();
+        assert_eq!(holder.unwrap().command, "queue process");
+
+        drop(lock);
+        InstanceLock::acquire(&path, "TUI").unwrap();
+    }
+}
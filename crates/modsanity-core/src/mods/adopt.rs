@@ -0,0 +1,205 @@
+//! Adopting already-extracted folders as managed mods
+//!
+//! Tool output such as DynDOLOD or Nemesis, or a hand-assembled mod, never
+//! came from an archive. Adopting registers such a folder in staging and the
+//! database the same way an archive install would.
+
+use super::{
+    collect_files, find_data_root, mod_files_size, plugin_filenames_from_mod_files,
+    scan_mod_metadata, InstalledMod,
+};
+use crate::db::ModRecord;
+use crate::error::Conflict;
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// How an adopted folder ends up in staging
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdoptMode {
+    /// Copy the files, leaving the original untouched
+    #[default]
+    Copy,
+    /// Move the folder into staging
+    Move,
+    /// Symlink staging to the folder so tools can keep regenerating it in place
+    Link,
+}
+
+impl AdoptMode {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AdoptMode::Copy => "copied",
+            AdoptMode::Move => "moved",
+            AdoptMode::Link => "linked",
+        }
+    }
+}
+
+impl super::ModManager {
+    /// Register an existing directory as a mod.
+    ///
+    /// Name, version and Nexus IDs come from `name_hint`, an MO2 `meta.ini`
+    /// or the folder name, in that order. Plugins are indexed like any install.
+    pub async fn adopt_directory(
+        &self,
+        game_id: &str,
+        path: &Path,
+        mode: AdoptMode,
+        name_hint: Option<&str>,
+    ) -> Result<InstalledMod> {
+        if !path.is_dir() {
+            bail!("Not a directory: {}", path.display());
+        }
+        let source = path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", path.display()))?;
+
+        let staging_root = self.staging_dir(game_id).await?;
+        if let Ok(root) = staging_root.canonicalize() {
+            if source.starts_with(&root) {
+                bail!(
+                    "{} is already inside the staging folder; use 'mod rescan' instead",
+                    source.display()
+                );
+            }
+        }
+
+        let scanned = scan_mod_metadata(&source);
+        let name = name_hint
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .unwrap_or(scanned.name);
+        if self.db.get_mod(game_id, &name)?.is_some() {
+            bail!(Conflict::new(format!(
+                "Mod '{}' is already installed",
+                name
+            )));
+        }
+
+        let staging = staging_root.join(&name);
+        if tokio::fs::symlink_metadata(&staging).await.is_ok() {
+            bail!(Conflict::new(format!(
+                "Staging entry already exists: {}",
+                staging.display()
+            )));
+        }
+        tokio::fs::create_dir_all(&staging_root)
+            .await
+            .context("Failed to create staging directory")?;
+
+        let data_root = find_data_root(&source)?;
+        if collect_files(&data_root)?.is_empty() {
+            bail!("{} contains no files", data_root.display());
+        }
+
+        tracing::info!(
+            "Adopting {} as '{}' ({})",
+            data_root.display(),
+            name,
+            mode.display_name()
+        );
+        match mode {
+            AdoptMode::Copy => {
+                let (from, to) = (data_root.clone(), staging.clone());
+                tokio::task::spawn_blocking(move || copy_tree(&from, &to)).await??;
+            }
+            AdoptMode::Move => {
+                // Renames fail across filesystems; fall back to copy + delete
+                if tokio::fs::rename(&data_root, &staging).await.is_err() {
+                    let (from, to) = (data_root.clone(), staging.clone());
+                    tokio::task::spawn_blocking(move || copy_tree(&from, &to)).await??;
+                    tokio::fs::remove_dir_all(&data_root)
+                        .await
+                        .with_context(|| format!("Failed to remove {}", data_root.display()))?;
+                }
+            }
+            AdoptMode::Link => {
+                std::os::unix::fs::symlink(&data_root, &staging)
+                    .with_context(|| format!("Failed to link {}", staging.display()))?;
+            }
+        }
+
+        let files = super::index::list_files(&staging)?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let record = ModRecord {
+            id: None,
+            game_id: game_id.to_string(),
+            name: name.clone(),
+            version: scanned.version.clone(),
+            author: None,
+            description: scanned.description,
+            nexus_mod_id: scanned.nexus_mod_id,
+            nexus_file_id: scanned.nexus_file_id,
+            install_path: staging.to_string_lossy().to_string(),
+            enabled: true,
+            priority: self.next_priority(game_id).await?,
+            file_count: files.len() as i32,
+            installed_at: now.clone(),
+            updated_at: now,
+            category_id: None,
+        };
+        let mod_id = self.db.insert_mod(&record)?;
+
+        let file_records = super::index::index_files(&staging, &files, mod_id).await?;
+        self.db.insert_mod_files(mod_id, &file_records)?;
+        let plugin_files = plugin_filenames_from_mod_files(&file_records);
+        self.db
+            .replace_mod_plugins(mod_id, game_id, &plugin_files)?;
+
+        Ok(InstalledMod {
+            id: mod_id,
+            name,
+            version: scanned.version,
+            author: None,
+            enabled: true,
+            priority: record.priority,
+            nexus_mod_id: scanned.nexus_mod_id,
+            nexus_file_id: scanned.nexus_file_id,
+            file_count: file_records.len() as i32,
+            install_path: staging,
+            category_id: None,
+            installed_at: record.installed_at.clone(),
+            size: mod_files_size(&file_records),
+        })
+    }
+}
+
+/// Recursively copy `from` into `to`, creating `to`
+fn copy_tree(from: &Path, to: &Path) -> Result<()> {
+    for entry in WalkDir::new(from).follow_links(true) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(from)?;
+        let dest = to.join(relative);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&dest)?;
+        } else {
+            std::fs::copy(entry.path(), &dest)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_tree_preserves_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("DynDOLOD Output");
+        std::fs::create_dir_all(src.join("meshes/lod")).unwrap();
+        std::fs::write(src.join("DynDOLOD.esp"), b"plugin").unwrap();
+        std::fs::write(src.join("meshes/lod/tree.bto"), b"lod").unwrap();
+
+        let dest = dir.path().join("staging/DynDOLOD");
+        copy_tree(&src, &dest).unwrap();
+
+        let mut files = collect_files(&dest).unwrap();
+        files.sort();
+        assert_eq!(files, vec!["DynDOLOD.esp", "meshes/lod/tree.bto"]);
+        assert!(src.join("DynDOLOD.esp").exists());
+    }
+}
@@ -2,12 +2,34 @@
 
 use anyhow::{Context, Result};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 /// Progress callback for extraction
 /// Parameters: (current_file, processed_count, total_count)
 pub type ProgressCallback = Arc<dyn Fn(String, usize, usize) + Send + Sync>;
 
+/// Error of an extraction, install or download stopped by its cancel flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Whether `err` is a [`Cancelled`] error
+pub fn is_cancelled(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<Cancelled>().is_some()
+}
+
+fn cancel_requested(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|c| c.load(Ordering::Relaxed))
+}
+
 /// Supported archive formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ArchiveFormat {
@@ -40,25 +62,60 @@ pub async fn extract_archive(
     archive: &Path,
     dest: &Path,
     progress_callback: Option<ProgressCallback>,
+) -> Result<()> {
+    extract_archive_cancellable(archive, dest, progress_callback, None).await
+}
+
+/// Extract an archive, stopping with [`Cancelled`] at the next archive entry
+/// once `cancel` is set. What was extracted so far stays in `dest`.
+pub async fn extract_archive_cancellable(
+    archive: &Path,
+    dest: &Path,
+    progress_callback: Option<ProgressCallback>,
+    cancel: Option<Arc<AtomicBool>>,
 ) -> Result<()> {
     let format = ArchiveFormat::from_path(archive);
 
     // Ensure destination exists
     tokio::fs::create_dir_all(dest).await?;
 
+    // Extraction is blocking work; keep it off the async workers so several
+    // archives can be extracted at once
+    let archive = archive.to_path_buf();
+    let dest = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        extract_blocking(
+            &archive,
+            &dest,
+            format,
+            progress_callback,
+            cancel.as_deref(),
+        )
+    })
+    .await
+    .context("Extraction task panicked")?
+}
+
+fn extract_blocking(
+    archive: &Path,
+    dest: &Path,
+    format: ArchiveFormat,
+    progress_callback: Option<ProgressCallback>,
+    cancel: Option<&AtomicBool>,
+) -> Result<()> {
     match format {
-        ArchiveFormat::Zip => extract_zip(archive, dest, progress_callback),
-        ArchiveFormat::SevenZip => extract_7z(archive, dest, progress_callback),
-        ArchiveFormat::Rar => extract_rar(archive, dest, progress_callback),
+        ArchiveFormat::Zip => extract_zip(archive, dest, progress_callback, cancel),
+        ArchiveFormat::SevenZip => extract_7z(archive, dest, progress_callback, cancel),
+        ArchiveFormat::Rar => extract_rar(archive, dest, progress_callback, cancel),
         ArchiveFormat::Unknown => {
             // Try to detect from magic bytes
             let bytes = std::fs::read(archive)?;
             if bytes.starts_with(&[0x50, 0x4B]) {
-                extract_zip(archive, dest, progress_callback)
+                extract_zip(archive, dest, progress_callback, cancel)
             } else if bytes.starts_with(&[0x37, 0x7A, 0xBC, 0xAF]) {
-                extract_7z(archive, dest, progress_callback)
+                extract_7z(archive, dest, progress_callback, cancel)
             } else if bytes.starts_with(&[0x52, 0x61, 0x72, 0x21]) {
-                extract_rar(archive, dest, progress_callback)
+                extract_rar(archive, dest, progress_callback, cancel)
             } else {
                 anyhow::bail!("Unknown archive format")
             }
@@ -71,6 +128,7 @@ fn extract_zip(
     archive: &Path,
     dest: &Path,
     progress_callback: Option<ProgressCallback>,
+    cancel: Option<&AtomicBool>,
 ) -> Result<()> {
     let file = std::fs::File::open(archive).context("Failed to open archive")?;
     let mut zip = zip::ZipArchive::new(file).context("Failed to read ZIP archive")?;
@@ -78,6 +136,9 @@ fn extract_zip(
     let total = zip.len();
 
     for i in 0..zip.len() {
+        if cancel_requested(cancel) {
+            return Err(Cancelled.into());
+        }
         let mut entry = zip.by_index(i)?;
         let entry_name = entry.name().to_string();
         let outpath = dest.join(sanitize_path(&entry_name));
@@ -115,6 +176,7 @@ fn extract_7z(
     archive: &Path,
     dest: &Path,
     progress_callback: Option<ProgressCallback>,
+    cancel: Option<&AtomicBool>,
 ) -> Result<()> {
     // Note: sevenz_rust doesn't support progress callbacks yet
     // We'll just report at start and end
@@ -122,7 +184,17 @@ fn extract_7z(
         cb("Extracting 7z archive...".to_string(), 0, 100);
     }
 
-    sevenz_rust::decompress_file(archive, dest).context("Failed to extract 7z archive")?;
+    let result =
+        sevenz_rust::decompress_file_with_extract_fn(archive, dest, |entry, reader, path| {
+            if cancel_requested(cancel) {
+                return Err(sevenz_rust::Error::other("cancelled"));
+            }
+            sevenz_rust::default_entry_extract_fn(entry, reader, path)
+        });
+    if cancel_requested(cancel) {
+        return Err(Cancelled.into());
+    }
+    result.context("Failed to extract 7z archive")?;
 
     if let Some(ref cb) = progress_callback {
         cb("Complete".to_string(), 100, 100);
@@ -136,7 +208,13 @@ fn extract_rar(
     archive: &Path,
     dest: &Path,
     progress_callback: Option<ProgressCallback>,
+    cancel: Option<&AtomicBool>,
 ) -> Result<()> {
+    // unrar runs as one process, so it can only be cancelled before it starts
+    if cancel_requested(cancel) {
+        return Err(Cancelled.into());
+    }
+
     // Note: unrar command-line tool doesn't provide easy progress tracking
     // We'll just report at start and end
     if let Some(ref cb) = progress_callback {
@@ -208,6 +286,35 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_cancelled_extraction_stops() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("mod.zip");
+        std::fs::write(
+            &archive,
+            crate::testing::zip_bytes(&[("a.esp", b"a"), ("b.esp", b"b")]).unwrap(),
+        )
+        .unwrap();
+
+        let cancel = Arc::new(AtomicBool::new(true));
+        let err = extract_archive_cancellable(
+            &archive,
+            &tmp.path().join("out"),
+            None,
+            Some(cancel.clone()),
+        )
+        .await
+        .unwrap_err();
+        assert!(is_cancelled(&err));
+        assert!(!tmp.path().join("out/a.esp").exists());
+
+        cancel.store(false, Ordering::Relaxed);
+        extract_archive_cancellable(&archive, &tmp.path().join("out"), None, Some(cancel))
+            .await
+            .unwrap();
+        assert!(tmp.path().join("out/b.esp").exists());
+    }
+
     #[test]
     fn test_sanitize_path() {
         assert_eq!(sanitize_path("foo/bar/baz.esp"), "foo/bar/baz.esp");
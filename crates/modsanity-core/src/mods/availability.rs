@@ -0,0 +1,184 @@
+//! Nexus file availability
+//!
+//! Authors archive or delete old files on Nexus. A mod installed from such a
+//! file can't be downloaded again, so it is flagged together with the current
+//! file that replaces it, when the mod still has one.
+
+use crate::db::NexusFileStatusRecord;
+use crate::games::Game;
+use crate::nexus::{nexus_game_id, ModFile, NexusClient};
+use anyhow::{bail, Result};
+
+/// Why an installed file can't be downloaded any more
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileAvailability {
+    /// Still listed on the mod page, but archived
+    Archived,
+    /// No longer listed at all
+    Removed,
+}
+
+impl FileAvailability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileAvailability::Archived => "archived",
+            FileAvailability::Removed => "removed",
+        }
+    }
+}
+
+/// An installed file that Nexus no longer serves
+#[derive(Debug, Clone)]
+pub struct UnavailableFile {
+    pub file_id: i64,
+    pub availability: FileAvailability,
+    /// Current file to install instead
+    pub replacement: Option<ModFile>,
+}
+
+impl UnavailableFile {
+    /// One-line explanation including the suggested replacement
+    pub fn describe(&self) -> String {
+        let mut text = format!(
+            "File {} is no longer available on Nexus ({})",
+            self.file_id,
+            self.availability.as_str()
+        );
+        match &self.replacement {
+            Some(file) => text.push_str(&format!(
+                "; current file: {} v{} (file {})",
+                file.name, file.version, file.file_id
+            )),
+            None => text.push_str("; the mod has no current main file"),
+        }
+        text
+    }
+
+    fn to_record(&self, mod_id: i64) -> NexusFileStatusRecord {
+        NexusFileStatusRecord {
+            mod_id,
+            nexus_file_id: self.file_id,
+            status: self.availability.as_str().to_string(),
+            replacement_file_id: self.replacement.as_ref().map(|f| f.file_id),
+            replacement_name: self.replacement.as_ref().map(|f| f.name.clone()),
+            replacement_version: self.replacement.as_ref().map(|f| f.version.clone()),
+            checked_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Check that `file_id` can still be downloaded from a mod's file list
+#[allow(clippy::result_large_err)]
+pub fn check_file(files: &[ModFile], file_id: i64) -> Result<(), UnavailableFile> {
+    let current = files.iter().find(|f| f.file_id == file_id);
+    if current.is_some_and(|f| f.is_available()) {
+        return Ok(());
+    }
+    Err(UnavailableFile {
+        file_id,
+        availability: if current.is_some() {
+            FileAvailability::Archived
+        } else {
+            FileAvailability::Removed
+        },
+        replacement: suggest_replacement(files, current).cloned(),
+    })
+}
+
+/// Newest downloadable MAIN file, preferring one with the old file's name so
+/// an SE build is replaced by an SE build
+fn suggest_replacement<'a>(files: &'a [ModFile], old: Option<&ModFile>) -> Option<&'a ModFile> {
+    let mains = || {
+        files
+            .iter()
+            .filter(|f| f.is_available() && f.category.eq_ignore_ascii_case("MAIN"))
+    };
+    old.and_then(|old| {
+        mains()
+            .filter(|f| f.name.eq_ignore_ascii_case(&old.name))
+            .max_by_key(|f| f.file_id)
+    })
+    .or_else(|| mains().max_by_key(|f| f.file_id))
+}
+
+impl super::ModManager {
+    /// Check every Nexus-installed mod's file against Nexus and update the
+    /// "file no longer available" flags. Returns the flagged mods.
+    pub async fn check_file_availability(
+        &self,
+        game: &Game,
+        nexus: &NexusClient,
+    ) -> Result<Vec<(String, UnavailableFile)>> {
+        let domain = game.nexus_game_domain();
+        let Some(nexus_game) = nexus_game_id(&domain) else {
+            bail!("Unsupported game domain for file lookup: {}", domain);
+        };
+
+        let mut unavailable = Vec::new();
+        for m in self.db.get_mods_for_game(&game.id)? {
+            let (Some(id), Some(mod_id), Some(file_id)) = (m.id, m.nexus_mod_id, m.nexus_file_id)
+            else {
+                continue;
+            };
+            let files = match nexus.get_mod_files(nexus_game, mod_id).await {
+                Ok(files) => files,
+                Err(e) => {
+                    tracing::warn!("Could not list Nexus files for {}: {}", m.name, e);
+                    continue;
+                }
+            };
+            match check_file(&files, file_id) {
+                Ok(()) => self.db.set_nexus_file_status(id, None)?,
+                Err(status) => {
+                    self.db
+                        .set_nexus_file_status(id, Some(&status.to_record(id)))?;
+                    unavailable.push((m.name, status));
+                }
+            }
+        }
+        Ok(unavailable)
+    }
+
+    /// Mods flagged by the last availability check, still on the flagged file
+    pub fn unavailable_files(&self, game_id: &str) -> Result<Vec<NexusFileStatusRecord>> {
+        self.db.get_nexus_file_statuses(game_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(file_id: i64, name: &str, category: &str) -> ModFile {
+        ModFile {
+            file_id,
+            name: name.to_string(),
+            version: format!("1.{}", file_id),
+            category: category.to_string(),
+            size_bytes: 0,
+            file_name: format!("{}.7z", name),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_check_file_suggests_matching_replacement() {
+        let files = vec![
+            file(10, "Lux SE", "ARCHIVED"),
+            file(20, "Lux AE", "MAIN"),
+            file(30, "Lux SE", "MAIN"),
+            file(40, "Lux SE", "OPTIONAL"),
+        ];
+        assert!(check_file(&files, 30).is_ok());
+
+        let archived = check_file(&files, 10).unwrap_err();
+        assert_eq!(archived.availability, FileAvailability::Archived);
+        assert_eq!(archived.replacement.unwrap().file_id, 30);
+
+        // Deleted files have no name to match, so the newest MAIN file is offered
+        let removed = check_file(&files, 5).unwrap_err();
+        assert_eq!(removed.availability, FileAvailability::Removed);
+        assert_eq!(removed.replacement.unwrap().file_id, 30);
+        assert!(check_file(&[], 5).unwrap_err().replacement.is_none());
+    }
+}
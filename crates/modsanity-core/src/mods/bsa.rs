@@ -0,0 +1,232 @@
+//! Minimal reader for Bethesda archive file listings
+//!
+//! Only the directory/name tables are read, which is enough to know which
+//! files an archive provides. Supports TES4-style BSA (Oblivion, Skyrim LE and
+//! SE, Fallout 3/NV) and BA2 (Fallout 4, Starfield).
+
+use anyhow::{bail, Context, Result};
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+const BSA_MAGIC: &[u8; 4] = b"BSA\0";
+const BA2_MAGIC: &[u8; 4] = b"BTDX";
+
+const FLAG_DIRECTORY_NAMES: u32 = 0x1;
+const FLAG_FILE_NAMES: u32 = 0x2;
+
+/// Whether `path` has a Bethesda archive extension
+pub fn is_archive_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("bsa") || e.eq_ignore_ascii_case("ba2"))
+        .unwrap_or(false)
+}
+
+/// List the files in a BSA or BA2 archive as lowercase `dir/file` paths
+pub fn list_archive_files(path: &Path) -> Result<Vec<String>> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    read_file_list(&mut BufReader::new(file))
+        .with_context(|| format!("Failed to read archive {}", path.display()))
+}
+
+fn read_file_list<R: Read + Seek>(r: &mut R) -> Result<Vec<String>> {
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic)?;
+    match &magic {
+        BSA_MAGIC => read_bsa(r),
+        BA2_MAGIC => read_ba2(r),
+        _ => bail!("Not a BSA/BA2 archive"),
+    }
+}
+
+fn read_bsa<R: Read + Seek>(r: &mut R) -> Result<Vec<String>> {
+    let version = read_u32(r)?;
+    if !matches!(version, 103..=105) {
+        bail!("Unsupported BSA version {}", version);
+    }
+    let folder_offset = read_u32(r)?;
+    let flags = read_u32(r)?;
+    let folder_count = read_u32(r)? as usize;
+    let file_count = read_u32(r)? as usize;
+    let _total_folder_name_len = read_u32(r)?;
+    let total_file_name_len = read_u32(r)? as usize;
+
+    if flags & FLAG_DIRECTORY_NAMES == 0 || flags & FLAG_FILE_NAMES == 0 {
+        bail!("BSA has no name tables");
+    }
+
+    // Folder records: hash, file count, then an offset we don't need
+    r.seek(SeekFrom::Start(folder_offset as u64))?;
+    let record_tail = if version == 105 { 12 } else { 4 };
+    let mut folder_counts = Vec::with_capacity(folder_count);
+    for _ in 0..folder_count {
+        skip(r, 8)?;
+        folder_counts.push(read_u32(r)? as usize);
+        skip(r, record_tail)?;
+    }
+
+    // File record blocks: folder name followed by 16-byte file records
+    let mut folders = Vec::with_capacity(file_count);
+    for count in folder_counts {
+        let mut len = [0u8; 1];
+        r.read_exact(&mut len)?;
+        let mut name = vec![0u8; len[0] as usize];
+        r.read_exact(&mut name)?;
+        let name = decode_name(&name);
+        skip(r, 16 * count as i64)?;
+        folders.extend(vec![name; count]);
+    }
+
+    // File name block: null-terminated names in file record order
+    let mut names = vec![0u8; total_file_name_len];
+    r.read_exact(&mut names)?;
+    let files: Vec<String> = names
+        .split(|b| *b == 0)
+        .take(file_count)
+        .zip(folders)
+        .map(|(file, folder)| {
+            let file = decode_name(file);
+            if folder.is_empty() || folder == "." {
+                file
+            } else {
+                format!("{}/{}", folder, file)
+            }
+        })
+        .collect();
+
+    if files.len() != file_count {
+        bail!("Truncated BSA name table");
+    }
+    Ok(files)
+}
+
+fn read_ba2<R: Read + Seek>(r: &mut R) -> Result<Vec<String>> {
+    let _version = read_u32(r)?;
+    let mut kind = [0u8; 4];
+    r.read_exact(&mut kind)?;
+    if &kind != b"GNRL" && &kind != b"DX10" {
+        bail!("Unsupported BA2 type");
+    }
+    let file_count = read_u32(r)? as usize;
+    let mut offset = [0u8; 8];
+    r.read_exact(&mut offset)?;
+    let name_table = u64::from_le_bytes(offset);
+    if name_table == 0 {
+        bail!("BA2 has no name table");
+    }
+
+    r.seek(SeekFrom::Start(name_table))?;
+    let mut files = Vec::with_capacity(file_count);
+    for _ in 0..file_count {
+        let mut len = [0u8; 2];
+        r.read_exact(&mut len)?;
+        let mut name = vec![0u8; u16::from_le_bytes(len) as usize];
+        r.read_exact(&mut name)?;
+        files.push(decode_name(&name));
+    }
+    Ok(files)
+}
+
+fn decode_name(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+    String::from_utf8_lossy(bytes)
+        .replace('\\', "/")
+        .to_lowercase()
+}
+
+fn read_u32<R: Read>(r: &mut R) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn skip<R: Seek>(r: &mut R, n: i64) -> Result<()> {
+    r.seek(SeekFrom::Current(n))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Build an SSE (v105) BSA holding the given folders and empty files
+    fn bsa_bytes(folders: &[(&str, &[&str])]) -> Vec<u8> {
+        let file_count: usize = folders.iter().map(|(_, f)| f.len()).sum();
+        let file_names: Vec<u8> = folders
+            .iter()
+            .flat_map(|(_, files)| files.iter())
+            .flat_map(|f| f.bytes().chain([0]))
+            .collect();
+
+        let mut out = Vec::new();
+        out.extend_from_slice(BSA_MAGIC);
+        for v in [
+            105,
+            36,
+            FLAG_DIRECTORY_NAMES | FLAG_FILE_NAMES,
+            folders.len() as u32,
+            file_count as u32,
+            0,
+            file_names.len() as u32,
+            0,
+        ] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        for (_, files) in folders {
+            out.extend_from_slice(&0u64.to_le_bytes());
+            out.extend_from_slice(&(files.len() as u32).to_le_bytes());
+            out.extend_from_slice(&0u32.to_le_bytes());
+            out.extend_from_slice(&0u64.to_le_bytes());
+        }
+        for (folder, files) in folders {
+            out.push(folder.len() as u8 + 1);
+            out.extend_from_slice(folder.as_bytes());
+            out.push(0);
+            out.resize(out.len() + 16 * files.len(), 0);
+        }
+        out.extend_from_slice(&file_names);
+        out
+    }
+
+    #[test]
+    fn test_read_bsa_listing() {
+        let bytes = bsa_bytes(&[
+            ("scripts", &["Actor.pex", "ObjectReference.pex"]),
+            ("meshes\\armor", &["cuirass.nif"]),
+        ]);
+        let files = read_file_list(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                "scripts/actor.pex",
+                "scripts/objectreference.pex",
+                "meshes/armor/cuirass.nif"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_ba2_listing() {
+        let names = ["Scripts\\Actor.pex", "Scripts\\Quest.pex"];
+        let mut out = Vec::new();
+        out.extend_from_slice(BA2_MAGIC);
+        out.extend_from_slice(&1u32.to_le_bytes());
+        out.extend_from_slice(b"GNRL");
+        out.extend_from_slice(&(names.len() as u32).to_le_bytes());
+        out.extend_from_slice(&24u64.to_le_bytes());
+        for name in names {
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(name.as_bytes());
+        }
+
+        let files = read_file_list(&mut Cursor::new(out)).unwrap();
+        assert_eq!(files, vec!["scripts/actor.pex", "scripts/quest.pex"]);
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert!(read_file_list(&mut Cursor::new(b"PK\x03\x04rest".to_vec())).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Installing many archives at once
+//!
+//! Archives are extracted by a bounded pool of workers (`[install]
+//! extraction_workers`). Archives that would install under the same mod name
+//! share a staging folder, so all but the first of them run one at a time
+//! after the pool. FOMOD installers that need the wizard come back as
+//! [`InstallResult::RequiresWizard`] for the caller to answer one by one.
+
+use super::{InstallResult, ProgressCallback};
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Result of one archive in a bulk install
+#[derive(Debug)]
+pub struct BulkInstallOutcome {
+    /// Position of the archive in the input list
+    pub index: usize,
+    pub archive: PathBuf,
+    pub result: Result<InstallResult>,
+}
+
+impl super::ModManager {
+    /// Number of archives extracted at once, from the install config
+    pub async fn extraction_workers(&self) -> usize {
+        self.config.read().await.install.worker_count()
+    }
+
+    /// Install `archives` with up to [`Self::extraction_workers`] running at
+    /// once. `progress` supplies the extraction callback for each archive and
+    /// `on_done` is called as each one finishes. Once `cancel` is set,
+    /// archives not yet started are left out and those being extracted stop
+    /// with [`super::Cancelled`].
+    pub async fn install_archives<P, F>(
+        &self,
+        game_id: &str,
+        archives: &[PathBuf],
+        cancel: &Arc<AtomicBool>,
+        progress: P,
+        mut on_done: F,
+    ) where
+        P: Fn(usize, &Path) -> Option<ProgressCallback>,
+        F: FnMut(BulkInstallOutcome),
+    {
+        let workers = self.extraction_workers().await;
+        let (parallel, serial) = split_by_mod_name(archives);
+        tracing::info!(
+            "Installing {} archives with {} extraction worker(s)",
+            archives.len(),
+            workers
+        );
+
+        let install = |index: usize| {
+            let archive = archives[index].clone();
+            let callback = progress(index, &archive);
+            async move {
+                if cancel.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let result = self
+                    .install_from_archive_cancellable(
+                        game_id,
+                        &archive.to_string_lossy(),
+                        callback,
+                        None,
+                        None,
+                        None,
+                        Some(Arc::clone(cancel)),
+                    )
+                    .await;
+                Some(BulkInstallOutcome {
+                    index,
+                    archive,
+                    result,
+                })
+            }
+        };
+
+        let mut pool = stream::iter(parallel)
+            .map(&install)
+            .buffer_unordered(workers);
+        while let Some(outcome) = pool.next().await {
+            if let Some(outcome) = outcome {
+                on_done(outcome);
+            }
+        }
+        drop(pool);
+
+        for index in serial {
+            if let Some(outcome) = install(index).await {
+                on_done(outcome);
+            }
+        }
+    }
+}
+
+/// Split archive indices into those that can run in parallel and those that
+/// repeat an earlier archive's mod name and must wait for it
+fn split_by_mod_name(archives: &[PathBuf]) -> (Vec<usize>, Vec<usize>) {
+    let mut seen = HashSet::new();
+    (0..archives.len()).partition(|&i| {
+        let stem = archives[i]
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown");
+        let (name, _) = super::ModManager::parse_mod_name(stem);
+        seen.insert(name.to_lowercase())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_by_mod_name_defers_repeated_names() {
+        let archives: Vec<PathBuf> = [
+            "SkyUI_5_2_SE-12604-5-2SE.7z",
+            "Lux-43158-5-7.zip",
+            "SkyUI_5_2_SE-12604-5-1SE.7z",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let (parallel, serial) = split_by_mod_name(&archives);
+        assert_eq!(parallel, vec![0, 1]);
+        assert_eq!(serial, vec![2]);
+    }
+}
@@ -0,0 +1,341 @@
+//! Capturing files external tools write into Data
+//!
+//! FNIS, Nemesis and BodySlide write their output straight into the game's
+//! Data folder, where the next purge would strand it. A snapshot taken before
+//! the tool runs is compared afterwards, and anything it created or rewrote is
+//! moved into an output mod so it deploys like the rest of the setup.
+//!
+//! Every deploy also saves a snapshot of Data as the deployment manifest, so
+//! files a tool left behind can be found and swept into a mod afterwards
+//! even when no output mod was configured before the run.
+
+use super::AdoptMode;
+use crate::error::Invalid;
+use crate::games::Game;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// Deployment manifest in the game's staging folder; hidden, so rescans skip it
+const DEPLOYMENT_MANIFEST: &str = ".deployment-manifest.json";
+
+/// Regular files under a Data folder and their modification times. Deployed
+/// symlinks are skipped: they already belong to a mod.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataSnapshot {
+    root: PathBuf,
+    files: HashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl DataSnapshot {
+    pub fn take(data_dir: &Path) -> Self {
+        let files = WalkDir::new(data_dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let relative = e.path().strip_prefix(data_dir).ok()?.to_path_buf();
+                let modified = e.metadata().ok().and_then(|m| m.modified().ok());
+                Some((relative, modified))
+            })
+            .collect();
+        Self {
+            root: data_dir.to_path_buf(),
+            files,
+        }
+    }
+
+    /// Files created or modified since the snapshot, relative to Data.
+    /// Files that only got a new load order timestamp (Fallout 3, New Vegas
+    /// and Oblivion plugins ModSanity reordered) are not counted.
+    pub fn changed_files(&self) -> Vec<PathBuf> {
+        let after = Self::take(&self.root);
+        let mut changed: Vec<PathBuf> = after
+            .files
+            .into_iter()
+            .filter(|(path, modified)| match self.files.get(path) {
+                Some(before) if before == modified => false,
+                Some(_) => !modified.is_some_and(crate::plugins::is_load_order_timestamp),
+                None => true,
+            })
+            .map(|(path, _)| path)
+            .collect();
+        changed.sort();
+        changed
+    }
+}
+
+/// Rename, or copy and delete across filesystems
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to).with_context(|| format!("Failed to copy {}", from.display()))?;
+        std::fs::remove_file(from)
+            .with_context(|| format!("Failed to remove {}", from.display()))?;
+    }
+    Ok(())
+}
+
+impl super::ModManager {
+    /// Record what Data holds right after a deploy
+    pub(super) async fn save_deployment_manifest(&self, game: &Game) -> Result<()> {
+        self.write_deployment_manifest(game, &DataSnapshot::take(&game.data_path))
+            .await
+    }
+
+    /// Take a file ModSanity itself rewrote in Data into the manifest, so it
+    /// isn't mistaken for tool output
+    pub(super) async fn update_deployment_manifest(
+        &self,
+        game: &Game,
+        relative: &Path,
+    ) -> Result<()> {
+        let Some(mut manifest) = self.deployment_manifest(game).await? else {
+            return Ok(());
+        };
+        match std::fs::symlink_metadata(game.data_path.join(relative)) {
+            Ok(meta) if meta.is_file() => {
+                manifest
+                    .files
+                    .insert(relative.to_path_buf(), meta.modified().ok());
+            }
+            _ => {
+                manifest.files.remove(relative);
+            }
+        }
+        self.write_deployment_manifest(game, &manifest).await
+    }
+
+    async fn write_deployment_manifest(&self, game: &Game, snapshot: &DataSnapshot) -> Result<()> {
+        let path = self.staging_dir(&game.id).await?.join(DEPLOYMENT_MANIFEST);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec(snapshot)?;
+        std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Data as it was after the last deploy, if ModSanity recorded it
+    pub async fn deployment_manifest(&self, game: &Game) -> Result<Option<DataSnapshot>> {
+        let path = self.staging_dir(&game.id).await?.join(DEPLOYMENT_MANIFEST);
+        let Ok(json) = std::fs::read(&path) else {
+            return Ok(None);
+        };
+        let mut snapshot: DataSnapshot = serde_json::from_slice(&json)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        // The manifest outlives a changed install path
+        snapshot.root = game.data_path.clone();
+        Ok(Some(snapshot))
+    }
+
+    async fn require_deployment_manifest(&self, game: &Game) -> Result<DataSnapshot> {
+        match self.deployment_manifest(game).await? {
+            Some(snapshot) => Ok(snapshot),
+            None => bail!(Invalid::new(format!(
+                "No deployment manifest for {}; deploy once so new files in Data can be told apart",
+                game.name
+            ))),
+        }
+    }
+
+    /// Files created or rewritten in Data since the last deploy, relative
+    /// to Data: output tools left behind
+    pub async fn new_data_files(&self, game: &Game) -> Result<Vec<PathBuf>> {
+        Ok(self
+            .require_deployment_manifest(game)
+            .await?
+            .changed_files())
+    }
+
+    /// Move the files written to Data since the last deploy into
+    /// `output_mod`, creating it if needed
+    pub async fn capture_new_data_files(
+        &self,
+        game: &Game,
+        output_mod: &str,
+    ) -> Result<Vec<PathBuf>> {
+        let manifest = self.require_deployment_manifest(game).await?;
+        self.capture_output(game, &manifest, output_mod).await
+    }
+
+    /// Move files written to Data since `before` into `output_mod`, creating
+    /// it if needed. Returns the captured paths, relative to Data.
+    pub async fn capture_output(
+        &self,
+        game: &Game,
+        before: &DataSnapshot,
+        output_mod: &str,
+    ) -> Result<Vec<PathBuf>> {
+        let changed = before.changed_files();
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+
+        match self.db.get_mod(&game.id, output_mod)? {
+            Some(mut existing) => {
+                let install_path = PathBuf::from(&existing.install_path);
+                for relative in &changed {
+                    move_file(&game.data_path.join(relative), &install_path.join(relative))?;
+                }
+                self.reindex_mod(&mut existing).await?;
+            }
+            None => {
+                // Gathered next to the game's staging folder so adopting is a rename
+                let staging = self.staging_dir(&game.id).await?;
+                let gathered =
+                    staging.with_file_name(format!(".capture-{}-{}", game.id, std::process::id()));
+                for relative in &changed {
+                    move_file(&game.data_path.join(relative), &gathered.join(relative))?;
+                }
+                let adopted = self
+                    .adopt_directory(&game.id, &gathered, AdoptMode::Move, Some(output_mod))
+                    .await;
+                if gathered.exists() {
+                    let _ = std::fs::remove_dir_all(&gathered);
+                }
+                adopted?;
+            }
+        }
+
+        tracing::info!(
+            "Captured {} tool output file(s) into '{}'",
+            changed.len(),
+            output_mod
+        );
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_finds_new_and_rewritten_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path();
+        std::fs::create_dir_all(data.join("meshes")).unwrap();
+        std::fs::write(data.join("Skyrim.esm"), b"master").unwrap();
+        std::fs::write(data.join("meshes/body.nif"), b"old").unwrap();
+        std::os::unix::fs::symlink(data.join("Skyrim.esm"), data.join("linked.esp")).unwrap();
+
+        let before = DataSnapshot::take(data);
+        assert!(before.changed_files().is_empty());
+
+        let body = std::fs::File::options()
+            .write(true)
+            .open(data.join("meshes/body.nif"))
+            .unwrap();
+        body.set_modified(SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+        std::fs::write(data.join("FNIS.esp"), b"generated").unwrap();
+
+        assert_eq!(
+            before.changed_files(),
+            vec![PathBuf::from("FNIS.esp"), PathBuf::from("meshes/body.nif")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capture_new_files_since_deploy() {
+        use crate::config::Config;
+        use crate::db::Database;
+        use std::sync::Arc;
+        use tokio::sync::RwLock;
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::open(&dir.path().join("test.db")).unwrap());
+        let config = Config {
+            staging_dir_override: Some(dir.path().join("staging").to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let mods = super::super::ModManager::new(Arc::new(RwLock::new(config)), db);
+        let game = Game::new(
+            crate::games::GameType::SkyrimSE,
+            dir.path().join("Skyrim Special Edition"),
+        );
+        std::fs::create_dir_all(&game.data_path).unwrap();
+        std::fs::write(game.data_path.join("Skyrim.esm"), b"master").unwrap();
+
+        assert!(mods.new_data_files(&game).await.is_err());
+        mods.save_deployment_manifest(&game).await.unwrap();
+        assert!(mods.new_data_files(&game).await.unwrap().is_empty());
+
+        std::fs::create_dir_all(game.data_path.join("meshes/actors")).unwrap();
+        std::fs::write(
+            game.data_path.join("meshes/actors/behavior.hkx"),
+            b"nemesis",
+        )
+        .unwrap();
+        assert_eq!(
+            mods.new_data_files(&game).await.unwrap(),
+            vec![PathBuf::from("meshes/actors/behavior.hkx")]
+        );
+
+        let captured = mods
+            .capture_new_data_files(&game, "Nemesis Output")
+            .await
+            .unwrap();
+        assert_eq!(captured.len(), 1);
+        assert!(!game.data_path.join("meshes/actors/behavior.hkx").exists());
+        assert!(game.data_path.join("Skyrim.esm").exists());
+        let installed = mods.list_mods(&game.id).await.unwrap();
+        assert_eq!(installed[0].name, "Nemesis Output");
+        assert!(mods.new_data_files(&game).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reordering_timestamp_plugins_is_not_tool_output() {
+        use crate::config::DeploymentMethod;
+        use crate::db::Database;
+        use crate::games::GameType;
+        use crate::testing::Sandbox;
+        use std::sync::Arc;
+        use tokio::sync::RwLock;
+
+        let sandbox = Sandbox::with_game(GameType::FalloutNV).unwrap();
+        let db = Arc::new(Database::open(&sandbox.root().join("test.db")).unwrap());
+        let mut config = sandbox.config(None);
+        config.deployment.method = DeploymentMethod::Copy;
+        let mods = super::super::ModManager::new(Arc::new(RwLock::new(config)), db);
+        let game = sandbox
+            .game()
+            .with_proton_prefix(sandbox.root().join("compatdata"));
+        std::fs::write(game.data_path.join("FalloutNV.esm"), b"master").unwrap();
+        let archive = sandbox
+            .write_archive("Tweaks-1-1-0.zip", &[("Tweaks.esp", b"plugin")])
+            .unwrap();
+        mods.install_from_archive(&game.id, &archive.to_string_lossy(), None, None, None, None)
+            .await
+            .unwrap();
+        mods.deploy(&game).await.unwrap();
+        assert!(!game.data_path.join("Tweaks.esp").is_symlink());
+
+        // Reordering restamps both plugins after the manifest was saved
+        let order = ["Tweaks.esp", "FalloutNV.esm"].map(String::from);
+        crate::plugins::write_loadorder_txt(&game, &order).unwrap();
+        let before_tool = DataSnapshot::take(&game.data_path);
+        assert!(before_tool.changed_files().is_empty());
+        assert!(mods.new_data_files(&game).await.unwrap().is_empty());
+        assert!(mods
+            .capture_new_data_files(&game, "Tool Output")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(game.data_path.join("FalloutNV.esm").exists());
+        assert!(game.data_path.join("Tweaks.esp").exists());
+
+        // What the tool writes is still found
+        std::fs::write(game.data_path.join("Merged Patch.esp"), b"output").unwrap();
+        assert_eq!(
+            mods.new_data_files(&game).await.unwrap(),
+            vec![PathBuf::from("Merged Patch.esp")]
+        );
+    }
+}
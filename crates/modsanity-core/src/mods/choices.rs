@@ -0,0 +1,202 @@
+//! Saved FOMOD choices per mod and profile
+//!
+//! Completed wizards are stored for the active profile. Re-running an
+//! installer starts from the profile's saved choices, falling back to the
+//! choices saved without a profile. Choices can be exported as a
+//! [`FomodPreset`] and imported into another mod or profile.
+
+use super::fomod::persistence::FomodChoiceManager;
+use super::fomod::planner::InstallPlan;
+use super::fomod::{self, FomodPreset};
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+
+impl super::ModManager {
+    /// Database id of the active profile, if one is set for this game
+    pub async fn active_profile_id(&self, game_id: &str) -> Result<Option<i64>> {
+        let name = self.config.read().await.active_profile.clone();
+        match name {
+            Some(name) => self.profile_id(game_id, &name),
+            None => Ok(None),
+        }
+    }
+
+    fn profile_id(&self, game_id: &str, name: &str) -> Result<Option<i64>> {
+        Ok(self
+            .db
+            .get_profiles_for_game(game_id)?
+            .into_iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .and_then(|p| p.id))
+    }
+
+    /// Profile named on the command line, or the active one
+    async fn choice_profile(&self, game_id: &str, profile: Option<&str>) -> Result<Option<i64>> {
+        match profile {
+            Some(name) => match self.profile_id(game_id, name)? {
+                Some(id) => Ok(Some(id)),
+                None => bail!("Profile '{}' not found", name),
+            },
+            None => self.active_profile_id(game_id).await,
+        }
+    }
+
+    /// Choices saved for a mod under the active profile, else without a profile
+    pub async fn saved_fomod_choice(
+        &self,
+        game_id: &str,
+        mod_id: i64,
+    ) -> Result<Option<InstallPlan>> {
+        let manager = FomodChoiceManager::new(&self.db);
+        if let Some(profile_id) = self.active_profile_id(game_id).await? {
+            if let Some(plan) = manager.load_choice(mod_id, Some(profile_id))? {
+                return Ok(Some(plan));
+            }
+        }
+        manager.load_choice(mod_id, None)
+    }
+
+    /// Write a mod's saved choices to `path` as a preset
+    pub async fn export_fomod_preset(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        profile: Option<&str>,
+        path: &Path,
+    ) -> Result<FomodPreset> {
+        let m = self
+            .db
+            .get_mod(game_id, mod_name)?
+            .ok_or_else(|| anyhow::anyhow!("Mod '{}' not found", mod_name))?;
+        let mod_id = m.id.context("Mod has no id")?;
+
+        let plan = match profile {
+            Some(_) => {
+                let profile_id = self.choice_profile(game_id, profile).await?;
+                FomodChoiceManager::new(&self.db).load_choice(mod_id, profile_id)?
+            }
+            None => self.saved_fomod_choice(game_id, mod_id).await?,
+        };
+        let Some(plan) = plan else {
+            bail!("No saved FOMOD choices for '{}'", m.name);
+        };
+
+        let mut preset = FomodPreset::from_plan(&plan);
+        preset.module_name = m.name;
+        preset.save(path)?;
+        Ok(preset)
+    }
+
+    /// Check a preset against a mod's installer and save it as the mod's
+    /// choices for `profile` (the active profile by default). The choices are
+    /// used the next time the installer is run.
+    pub async fn import_fomod_preset(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        profile: Option<&str>,
+        path: &Path,
+    ) -> Result<()> {
+        let m = self
+            .db
+            .get_mod(game_id, mod_name)?
+            .ok_or_else(|| anyhow::anyhow!("Mod '{}' not found", mod_name))?;
+        let mod_id = m.id.context("Mod has no id")?;
+        let install_path = Path::new(&m.install_path);
+        if !fomod::has_fomod(install_path) {
+            bail!("'{}' does not have a FOMOD installer", m.name);
+        }
+
+        let preset = FomodPreset::load(path)?;
+        let installer = fomod::FomodInstaller::load(install_path)?;
+        let env = self.fomod_environment(game_id)?;
+        let wizard = fomod::answer_wizard(&installer.config, env, Some(&preset))?;
+        let plan = InstallPlan::from_wizard_state(
+            &wizard,
+            &installer,
+            m.name.clone(),
+            install_path,
+            install_path,
+        )?;
+
+        let profile_id = self.choice_profile(game_id, profile).await?;
+        FomodChoiceManager::new(&self.db).save_choice(mod_id, profile_id, &plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::db::{Database, ModRecord, ProfileRecord};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn plan(mod_name: &str) -> InstallPlan {
+        InstallPlan {
+            mod_name: mod_name.to_string(),
+            profile_id: None,
+            timestamp: String::new(),
+            config_hash: "hash".to_string(),
+            selected_options: Vec::new(),
+            flags_set: Default::default(),
+            file_operations: Vec::new(),
+            estimated_file_count: 0,
+            estimated_size_bytes: 0,
+            conflicts: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saved_choice_prefers_active_profile() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::open(&tmp.path().join("test.db")).unwrap());
+        let now = chrono::Utc::now().to_rfc3339();
+        let mod_id = db
+            .insert_mod(&ModRecord {
+                id: None,
+                game_id: "skyrimse".to_string(),
+                name: "Lux".to_string(),
+                version: "1.0".to_string(),
+                author: None,
+                description: None,
+                nexus_mod_id: None,
+                nexus_file_id: None,
+                install_path: tmp.path().to_string_lossy().to_string(),
+                enabled: true,
+                priority: 0,
+                file_count: 0,
+                installed_at: now.clone(),
+                updated_at: now.clone(),
+                category_id: None,
+            })
+            .unwrap();
+        let profile_id = db
+            .insert_profile(&ProfileRecord {
+                id: None,
+                game_id: "skyrimse".to_string(),
+                name: "Survival".to_string(),
+                description: None,
+                created_at: now.clone(),
+                updated_at: now,
+            })
+            .unwrap();
+
+        let config = Config {
+            active_profile: Some("survival".to_string()),
+            ..Default::default()
+        };
+        let mods = super::super::ModManager::new(Arc::new(RwLock::new(config)), db.clone());
+        let manager = FomodChoiceManager::new(&db);
+
+        manager.save_choice(mod_id, None, &plan("default")).unwrap();
+        let saved = mods.saved_fomod_choice("skyrimse", mod_id).await.unwrap();
+        assert_eq!(saved.unwrap().mod_name, "default");
+
+        manager
+            .save_choice(mod_id, Some(profile_id), &plan("survival"))
+            .unwrap();
+        let saved = mods.saved_fomod_choice("skyrimse", mod_id).await.unwrap();
+        assert_eq!(saved.unwrap().mod_name, "survival");
+    }
+}
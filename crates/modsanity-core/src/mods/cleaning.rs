@@ -0,0 +1,209 @@
+//! Bookkeeping for xEdit QuickAutoClean runs
+//!
+//! xEdit saves a cleaned plugin by replacing the file, which turns a deployed
+//! link into a plain file in Data that the next deploy would throw away. After
+//! each run the cleaned copy is moved over the mod's staged plugin and
+//! deployed again, and the cleaning is recorded with the plugin's checksums.
+
+use crate::activity::Activity;
+use crate::db::CleanedPluginRecord;
+use crate::error::Invalid;
+use crate::games::Game;
+use crate::plugins::clean::{file_crc, find_dirty_plugins, DirtyPlugin};
+use crate::plugins::masterlist::{build_metadata_map, find_masterlist, load_masterlist};
+use anyhow::{bail, Context, Result};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+/// Whether two paths are the same file, following links
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+impl super::ModManager {
+    /// Plugins in Data the game's LOOT masterlist reports as dirty.
+    /// `masterlist` overrides the lookup in the LOOT cache folder.
+    pub async fn dirty_plugins(
+        &self,
+        game: &Game,
+        masterlist: Option<&Path>,
+    ) -> Result<Vec<DirtyPlugin>> {
+        let path = match masterlist {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let cache = self.config.read().await.paths.loot_cache_dir();
+                match find_masterlist(&cache, &game.id) {
+                    Some(path) => path,
+                    None => bail!(Invalid::new(format!(
+                        "No LOOT masterlist for {}; save the game's masterlist.yaml as {}",
+                        game.name,
+                        cache.join(&game.id).join("masterlist.yaml").display()
+                    ))),
+                }
+            }
+        };
+        let metadata = build_metadata_map(&load_masterlist(&path)?);
+        let game = game.clone();
+        tokio::task::spawn_blocking(move || find_dirty_plugins(&game, &metadata))
+            .await
+            .context("Plugin checksum task failed")?
+    }
+
+    /// Record a QuickAutoClean run on `plugin`, keeping the cleaned copy in
+    /// the mod that provides it. Fails if xEdit left the plugin unchanged.
+    pub async fn finish_cleaning(
+        &self,
+        game: &Game,
+        plugin: &DirtyPlugin,
+    ) -> Result<CleanedPluginRecord> {
+        let data_file = game.data_path.join(&plugin.filename);
+        let crc_after = file_crc(&data_file)
+            .with_context(|| format!("Failed to read {}", data_file.display()))?;
+        if crc_after == plugin.crc {
+            bail!("xEdit left {} unchanged", plugin.filename);
+        }
+
+        let owner = self
+            .which_file(&game.id, &plugin.filename)?
+            .into_iter()
+            .find(|lookup| lookup.path.eq_ignore_ascii_case(&plugin.filename))
+            .and_then(|lookup| lookup.winner().cloned());
+        let mut mod_name = None;
+        if let Some(owner) = owner {
+            if let Some(mut record) = self.db.get_mod(&game.id, &owner.mod_name)? {
+                let staged = PathBuf::from(&record.install_path)
+                    .join(owner.relative_path.replace('\\', "/"));
+                // Written through the link (or hardlink): the staged copy is cleaned
+                if !same_file(&data_file, &staged) {
+                    std::fs::copy(&data_file, &staged)
+                        .with_context(|| format!("Failed to update {}", staged.display()))?;
+                    std::fs::remove_file(&data_file)?;
+                    let method = self.config.read().await.deployment.method;
+                    super::deploy::deploy_file(&method, &staged, &data_file, false).await?;
+                }
+                self.reindex_mod(&mut record).await?;
+                mod_name = Some(record.name);
+            }
+        }
+        if let Err(e) = self
+            .update_deployment_manifest(game, Path::new(&plugin.filename))
+            .await
+        {
+            tracing::warn!("Failed to update deployment manifest: {}", e);
+        }
+
+        let mut record = CleanedPluginRecord {
+            id: None,
+            game_id: game.id.clone(),
+            plugin: plugin.filename.clone(),
+            mod_name,
+            crc_before: plugin.crc,
+            crc_after,
+            itm: plugin.itm,
+            udr: plugin.udr,
+            nav: plugin.nav,
+            cleaned_at: chrono::Utc::now().to_rfc3339(),
+        };
+        record.id = Some(self.db.insert_cleaned_plugin(&record)?);
+        self.record_activity(
+            &game.id,
+            Activity::CleanPlugin,
+            Some(&plugin.filename),
+            serde_json::json!({
+                "mod": record.mod_name,
+                "itm": plugin.itm,
+                "udr": plugin.udr,
+            }),
+        )
+        .await;
+        Ok(record)
+    }
+
+    /// Plugins cleaned for a game, most recent first
+    pub fn cleaned_plugins(&self, game_id: &str) -> Result<Vec<CleanedPluginRecord>> {
+        self.db.get_cleaned_plugins(game_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::db::{Database, ModRecord};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_finish_cleaning_keeps_cleaned_copy_in_mod() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::open(&dir.path().join("test.db")).unwrap());
+        let config = Config {
+            staging_dir_override: Some(dir.path().join("staging").to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let mods = super::super::ModManager::new(Arc::new(RwLock::new(config)), db.clone());
+        let game = Game::new(
+            crate::games::GameType::SkyrimSE,
+            dir.path().join("Skyrim Special Edition"),
+        );
+        std::fs::create_dir_all(&game.data_path).unwrap();
+
+        let staged = dir.path().join("staging/skyrimse/Dirty Mod");
+        std::fs::create_dir_all(&staged).unwrap();
+        std::fs::write(staged.join("Dirty.esp"), b"dirty").unwrap();
+        let now = chrono::Utc::now().to_rfc3339();
+        let mod_id = db
+            .insert_mod(&ModRecord {
+                id: None,
+                game_id: "skyrimse".to_string(),
+                name: "Dirty Mod".to_string(),
+                version: "1.0".to_string(),
+                author: None,
+                description: None,
+                nexus_mod_id: None,
+                nexus_file_id: None,
+                install_path: staged.to_string_lossy().to_string(),
+                enabled: true,
+                priority: 0,
+                file_count: 1,
+                installed_at: now.clone(),
+                updated_at: now,
+                category_id: None,
+            })
+            .unwrap();
+        let files = super::super::index::list_files(&staged).unwrap();
+        let records = super::super::index::index_files(&staged, &files, mod_id)
+            .await
+            .unwrap();
+        db.insert_mod_files(mod_id, &records).unwrap();
+        mods.deploy(&game).await.unwrap();
+
+        let plugin = DirtyPlugin {
+            filename: "Dirty.esp".to_string(),
+            path: game.data_path.join("Dirty.esp"),
+            crc: file_crc(&game.data_path.join("Dirty.esp")).unwrap(),
+            util: "SSEEdit v4.0.3".to_string(),
+            itm: 4,
+            udr: 1,
+            nav: 0,
+        };
+        assert!(mods.finish_cleaning(&game, &plugin).await.is_err());
+
+        // xEdit replaces the deployed link with the cleaned file
+        std::fs::remove_file(&plugin.path).unwrap();
+        std::fs::write(&plugin.path, b"clean").unwrap();
+        let record = mods.finish_cleaning(&game, &plugin).await.unwrap();
+        assert_eq!(record.mod_name.as_deref(), Some("Dirty Mod"));
+        assert_ne!(record.crc_after, record.crc_before);
+        assert_eq!(std::fs::read(staged.join("Dirty.esp")).unwrap(), b"clean");
+        assert!(std::fs::symlink_metadata(&plugin.path)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert!(mods.new_data_files(&game).await.unwrap().is_empty());
+        assert_eq!(mods.cleaned_plugins("skyrimse").unwrap().len(), 1);
+    }
+}
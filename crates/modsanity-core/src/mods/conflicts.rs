@@ -0,0 +1,339 @@
+//! Mod conflict detection and resolution
+
+use crate::db::{Database, FileConflict};
+use crate::mods::fomod::planner::{ConflictItem, ConflictSeverity, InstallPlan};
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+
+/// Conflict resolution strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Higher priority mod wins (default)
+    Priority,
+    /// Newer mod wins
+    Newer,
+    /// Keep both (not applicable for identical paths)
+    Manual,
+}
+
+/// Conflict summary for a mod pair
+#[derive(Debug, Clone)]
+pub struct ModConflict {
+    pub mod1: String,
+    pub mod2: String,
+    pub files: Vec<String>,
+    pub winner: String,
+}
+
+/// Get all conflicts for a game, grouped by mod pair
+pub fn get_conflicts_grouped(db: &Database, game_id: &str) -> Result<Vec<ModConflict>> {
+    let raw_conflicts = db.find_conflicts(game_id)?;
+
+    // Group by mod pair
+    let mut grouped: HashMap<(String, String), Vec<FileConflict>> = HashMap::new();
+
+    for conflict in raw_conflicts {
+        let key = if conflict.mod1 < conflict.mod2 {
+            (conflict.mod1.clone(), conflict.mod2.clone())
+        } else {
+            (conflict.mod2.clone(), conflict.mod1.clone())
+        };
+        grouped.entry(key).or_default().push(conflict);
+    }
+
+    // Convert to ModConflict
+    let mut result = Vec::new();
+    for ((mod1, mod2), conflicts) in grouped {
+        let winner = conflicts
+            .first()
+            .map(|c| c.winner().to_string())
+            .unwrap_or_default();
+        let files = conflicts.into_iter().map(|c| c.path).collect();
+
+        result.push(ModConflict {
+            mod1,
+            mod2,
+            files,
+            winner,
+        });
+    }
+
+    // Sort by number of conflicts (most first)
+    result.sort_by_key(|c| std::cmp::Reverse(c.files.len()));
+
+    Ok(result)
+}
+
+/// Broad asset category used to group conflicts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AssetType {
+    Plugins,
+    Interface,
+    Scripts,
+    Meshes,
+    Textures,
+    Sounds,
+    Other,
+}
+
+impl AssetType {
+    /// Classify a Data-relative path by its top-level folder, then extension
+    pub fn from_path(path: &str) -> Self {
+        let lower = path.replace('\\', "/").to_lowercase();
+        let top = lower.split('/').next().unwrap_or_default();
+        let ext = lower.rsplit('.').next().unwrap_or_default();
+        match (top, ext) {
+            (_, "esp" | "esm" | "esl") => AssetType::Plugins,
+            ("interface", _) | (_, "swf") => AssetType::Interface,
+            ("scripts" | "source", _) | (_, "pex" | "psc") => AssetType::Scripts,
+            ("meshes", _) | (_, "nif" | "tri" | "hkx") => AssetType::Meshes,
+            ("textures", _) | (_, "dds") => AssetType::Textures,
+            ("sound" | "music", _) | (_, "wav" | "xwm" | "fuz" | "lip") => AssetType::Sounds,
+            _ => AssetType::Other,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AssetType::Plugins => "Plugins",
+            AssetType::Interface => "Interface",
+            AssetType::Scripts => "Scripts",
+            AssetType::Meshes => "Meshes",
+            AssetType::Textures => "Textures",
+            AssetType::Sounds => "Sounds & voice",
+            AssetType::Other => "Other",
+        }
+    }
+}
+
+/// Conflicts of one asset type, with which mods end up providing the files
+#[derive(Debug, Clone)]
+pub struct AssetTypeConflicts {
+    pub asset_type: AssetType,
+    /// Number of conflicting files of this type
+    pub files: usize,
+    /// Mods whose copy is deployed and how many files they win, most first
+    pub winners: Vec<(String, usize)>,
+    /// Mods whose copies are overwritten and how many files they lose, most first
+    pub losers: Vec<(String, usize)>,
+}
+
+/// Get all conflicts for a game, grouped by asset type
+pub fn get_conflicts_by_asset_type(
+    db: &Database,
+    game_id: &str,
+) -> Result<Vec<AssetTypeConflicts>> {
+    Ok(group_by_asset_type(&db.find_conflicts(game_id)?))
+}
+
+fn group_by_asset_type(raw: &[FileConflict]) -> Vec<AssetTypeConflicts> {
+    // Every mod providing each conflicting path, with its priority
+    let mut providers: HashMap<&str, HashMap<&str, i32>> = HashMap::new();
+    for c in raw {
+        let entry = providers.entry(c.path.as_str()).or_default();
+        entry.insert(c.mod1.as_str(), c.priority1);
+        entry.insert(c.mod2.as_str(), c.priority2);
+    }
+
+    let mut by_type: BTreeMap<AssetType, (usize, HashMap<&str, usize>, HashMap<&str, usize>)> =
+        BTreeMap::new();
+    for (path, mods) in &providers {
+        let Some((winner, _)) = mods
+            .iter()
+            .max_by_key(|(name, p)| (**p, std::cmp::Reverse(**name)))
+        else {
+            continue;
+        };
+        let (files, winners, losers) = by_type.entry(AssetType::from_path(path)).or_default();
+        *files += 1;
+        *winners.entry(*winner).or_default() += 1;
+        for name in mods.keys().filter(|name| *name != winner) {
+            *losers.entry(*name).or_default() += 1;
+        }
+    }
+
+    let ranked = |counts: HashMap<&str, usize>| {
+        let mut out: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(m, n)| (m.to_string(), n))
+            .collect();
+        out.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        out
+    };
+    by_type
+        .into_iter()
+        .map(
+            |(asset_type, (files, winners, losers))| AssetTypeConflicts {
+                asset_type,
+                files,
+                winners: ranked(winners),
+                losers: ranked(losers),
+            },
+        )
+        .collect()
+}
+
+/// Check for potential issues in mod setup
+pub fn check_mod_issues(db: &Database, game_id: &str) -> Result<Vec<String>> {
+    let mut issues = Vec::new();
+
+    // Check for conflicts
+    let conflicts = get_conflicts_grouped(db, game_id)?;
+    if !conflicts.is_empty() {
+        let total_files: usize = conflicts.iter().map(|c| c.files.len()).sum();
+        issues.push(format!(
+            "{} file conflicts between {} mod pairs",
+            total_files,
+            conflicts.len()
+        ));
+    }
+
+    let interface = super::check_interface_compat(db, game_id)?;
+    for warning in &interface.incompatible {
+        issues.push(format!(
+            "{} and {} are incompatible: {}",
+            warning.mods[0], warning.mods[1], warning.reason
+        ));
+    }
+    if !interface.swf_conflicts.is_empty() {
+        issues.push(format!(
+            "{} interface files (.swf) shipped by several mods",
+            interface.swf_conflicts.len()
+        ));
+    }
+
+    // Check for missing masters could go here (plugin analysis)
+
+    Ok(issues)
+}
+
+/// Format conflict information for display
+pub fn format_conflict(conflict: &ModConflict) -> String {
+    let mut lines = Vec::new();
+    lines.push(format!(
+        "Conflict: {} vs {} ({} files)",
+        conflict.mod1,
+        conflict.mod2,
+        conflict.files.len()
+    ));
+    lines.push(format!("Winner: {}", conflict.winner));
+
+    // Show first few files
+    for file in conflict.files.iter().take(5) {
+        lines.push(format!("  - {}", file));
+    }
+    if conflict.files.len() > 5 {
+        lines.push(format!("  ... and {} more", conflict.files.len() - 5));
+    }
+
+    lines.join("\n")
+}
+
+/// Check for conflicts with a FOMOD install plan
+///
+/// Analyzes the install plan against existing installed files to detect conflicts.
+pub fn check_fomod_conflicts(
+    plan: &InstallPlan,
+    game_id: &str,
+    db: &Database,
+) -> Result<Vec<ConflictItem>> {
+    let mut conflicts = Vec::new();
+
+    // Get all installed files for this game
+    let installed_files = db.get_all_files(game_id)?;
+    let mut file_map: HashMap<String, String> = HashMap::new();
+
+    for file_record in installed_files {
+        file_map.insert(file_record.path, file_record.mod_name);
+    }
+
+    // Check each file operation against existing files
+    for operation in &plan.file_operations {
+        let dest_path = operation.destination.to_string_lossy().to_string();
+
+        if let Some(existing_mod) = file_map.get(&dest_path) {
+            // This file already exists from another mod
+            if existing_mod != &plan.mod_name {
+                let severity = if dest_path.ends_with(".esp")
+                    || dest_path.ends_with(".esm")
+                    || dest_path.ends_with(".esl")
+                {
+                    ConflictSeverity::High // Plugin conflicts are high severity
+                } else if dest_path.ends_with(".ini") || dest_path.ends_with(".xml") {
+                    ConflictSeverity::Medium // Config file conflicts are medium
+                } else {
+                    ConflictSeverity::Low // Asset conflicts are low
+                };
+
+                conflicts.push(ConflictItem {
+                    path: operation.destination.clone(),
+                    existing_mod: Some(existing_mod.clone()),
+                    severity,
+                    description: format!("File already installed by '{}'", existing_mod),
+                });
+            }
+        }
+    }
+
+    Ok(conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conflict(path: &str, mod1: &str, p1: i32, mod2: &str, p2: i32) -> FileConflict {
+        FileConflict {
+            path: path.to_string(),
+            mod1: mod1.to_string(),
+            mod2: mod2.to_string(),
+            priority1: p1,
+            priority2: p2,
+        }
+    }
+
+    #[test]
+    fn test_asset_type_from_path() {
+        assert_eq!(
+            AssetType::from_path("Meshes\\armor\\a.nif"),
+            AssetType::Meshes
+        );
+        assert_eq!(
+            AssetType::from_path("interface/skyui/map.swf"),
+            AssetType::Interface
+        );
+        assert_eq!(
+            AssetType::from_path("Sound/Voice/x.esp/npc/line.fuz"),
+            AssetType::Sounds
+        );
+        assert_eq!(AssetType::from_path("SkyUI_SE.esp"), AssetType::Plugins);
+        assert_eq!(
+            AssetType::from_path("scripts/actor.pex"),
+            AssetType::Scripts
+        );
+        assert_eq!(AssetType::from_path("readme.txt"), AssetType::Other);
+    }
+
+    #[test]
+    fn test_group_by_asset_type_picks_overall_winner() {
+        let raw = vec![
+            conflict("interface/map.swf", "SkyUI", 1, "Flat Map", 5),
+            conflict("interface/map.swf", "SkyUI", 1, "Better Map", 3),
+            conflict("interface/map.swf", "Better Map", 3, "Flat Map", 5),
+            conflict("interface/hud.swf", "SkyUI", 1, "Better Map", 3),
+            conflict("textures/sky.dds", "SkyUI", 1, "Flat Map", 5),
+        ];
+        let grouped = group_by_asset_type(&raw);
+        assert_eq!(grouped.len(), 2);
+
+        let interface = &grouped[0];
+        assert_eq!(interface.asset_type, AssetType::Interface);
+        assert_eq!(interface.files, 2);
+        assert_eq!(
+            interface.winners,
+            vec![("Better Map".to_string(), 1), ("Flat Map".to_string(), 1)]
+        );
+        assert_eq!(interface.losers[0], ("SkyUI".to_string(), 2));
+        assert_eq!(grouped[1].asset_type, AssetType::Textures);
+    }
+}
@@ -0,0 +1,764 @@
+//! Symlink-based mod deployment
+
+use super::exclusions::{DeployExclusions, ExcludedFile};
+use crate::config::{Config, DeploymentMethod};
+use crate::db::{Database, ModRecord};
+use crate::games::{openmw, Game, GameType};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::os::unix::fs::symlink;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use walkdir::WalkDir;
+
+/// Deployment statistics
+#[derive(Debug, Default)]
+pub struct DeploymentStats {
+    pub mods_deployed: usize,
+    pub files_deployed: usize,
+    pub conflicts_resolved: usize,
+    pub errors: Vec<String>,
+    /// Things deployed differently than configured, e.g. plugins left out,
+    /// and recent saves needing plugins that aren't loaded
+    pub warnings: Vec<String>,
+    /// Files left out by exclusion patterns
+    pub excluded: Vec<ExcludedFile>,
+    /// Files deployed loose into Data's subfolders (meshes, textures, ...)
+    pub loose_files: usize,
+    /// INI file archive invalidation was turned on in by this deploy
+    pub archive_invalidation: Option<PathBuf>,
+}
+
+/// Deploy mods to the game directory
+///
+/// # Priority System
+///
+/// Mods are deployed in priority order (ascending). When multiple mods contain
+/// the same file path:
+/// - The mod with the **highest priority number wins** (overwrites earlier files)
+/// - Lower priority mods deploy first, higher priority mods overwrite them
+/// - This implements "last write wins" conflict resolution
+///
+/// Example: If both ModA (priority 5) and ModB (priority 10) have `textures/sky.dds`,
+/// ModB's version will be deployed because 10 > 5.
+///
+/// Files matching an exclusion pattern don't take part. With `dry_run`
+/// nothing is written and the stats describe what a deploy would do.
+pub async fn deploy_mods(
+    config: &Arc<RwLock<Config>>,
+    db: &Arc<Database>,
+    game: &Game,
+    dry_run: bool,
+) -> Result<DeploymentStats> {
+    let config = config.read().await;
+    let mut stats = DeploymentStats::default();
+
+    // Get all enabled mods sorted by priority
+    let mods = db.get_mods_for_game(&game.id)?;
+    let enabled_mods: Vec<_> = mods.into_iter().filter(|m| m.enabled).collect();
+
+    if enabled_mods.is_empty() {
+        if dry_run {
+            return Ok(stats);
+        }
+        tracing::info!("No enabled mods - purging deployment to restore factory state");
+        // Purge all deployed files to restore game to clean state
+        let staging_dir = config.game_staging_dir(&game.id)?;
+        purge_deployment(game, &config.deployment.method, &staging_dir).await?;
+        purge_script_extender_root_files(game).await?;
+        tracing::info!("Game restored to factory state (all mod files removed)");
+        return Ok(stats);
+    }
+
+    if game.game_type == GameType::OpenMW {
+        let staging_dir = config.game_staging_dir(&game.id)?;
+        return deploy_openmw(game, &enabled_mods, &staging_dir, dry_run);
+    }
+
+    let exclusions = DeployExclusions::load(db, &game.id, &config.deployment.exclude)?;
+    let mut file_map = resolve_winning_files(&enabled_mods, &exclusions, &mut stats);
+
+    // Plugins needing DLC the game doesn't have would stop it from starting
+    match super::dlc::find_missing_dlc(db, game) {
+        Ok(missing) => {
+            for m in missing.iter().filter(|m| m.enabled) {
+                for plugin in &m.plugins {
+                    let key = normalize_relative_path(Path::new(plugin));
+                    if file_map
+                        .get(&key)
+                        .is_some_and(|(_, winner, _, _)| *winner == m.mod_name)
+                    {
+                        file_map.remove(&key);
+                    }
+                }
+                stats.warnings.push(format!(
+                    "Skipped {} from {} (needs {})",
+                    m.plugins.join(", "),
+                    m.mod_name,
+                    m.dlc.join(", ")
+                ));
+            }
+        }
+        Err(e) => tracing::warn!("DLC check failed: {}", e),
+    }
+
+    // Recent saves made with plugins that aren't loaded would break
+    match crate::saves::check_save_plugins(game) {
+        Ok(issues) => stats
+            .warnings
+            .extend(issues.iter().map(|issue| issue.to_string())),
+        Err(e) => tracing::warn!("Save check failed: {}", e),
+    }
+
+    if dry_run {
+        stats.files_deployed = file_map.len();
+        return Ok(stats);
+    }
+
+    // Clear existing deployment
+    let staging_dir = config.game_staging_dir(&game.id)?;
+    purge_deployment(game, &config.deployment.method, &staging_dir).await?;
+    purge_script_extender_root_files(game).await?;
+
+    // Create all symlinks/hardlinks/copies
+    for (source, mod_name, _, canonical_relative) in file_map.values() {
+        let (dest, force_copy) = resolve_deploy_destination(game, canonical_relative);
+        if let Err(e) = deploy_file(&config.deployment.method, source, &dest, force_copy).await {
+            stats.errors.push(format!(
+                "Failed to deploy {} from {}: {}",
+                dest.display(),
+                mod_name,
+                e
+            ));
+        } else {
+            stats.files_deployed += 1;
+            if is_loose_asset(game, &dest) {
+                stats.loose_files += 1;
+            }
+        }
+    }
+
+    for warning in &stats.warnings {
+        tracing::warn!("{}", warning);
+    }
+    tracing::info!(
+        "Deployed {} files from {} mods ({} conflicts resolved)",
+        stats.files_deployed,
+        stats.mods_deployed,
+        stats.conflicts_resolved
+    );
+
+    Ok(stats)
+}
+
+/// Whether `dest` is an asset in a folder under Data, which the game only
+/// loads over its archives with archive invalidation
+fn is_loose_asset(game: &Game, dest: &Path) -> bool {
+    dest.strip_prefix(&game.data_path)
+        .is_ok_and(|relative| relative.components().count() > 1)
+}
+
+/// Deploy for OpenMW: list each enabled mod's folder as a `data=` entry in
+/// openmw.cfg after the user's own folders. OpenMW resolves conflicts itself,
+/// so nothing is linked and exclusion patterns don't apply.
+fn deploy_openmw(
+    game: &Game,
+    enabled_mods: &[ModRecord],
+    staging_dir: &Path,
+    dry_run: bool,
+) -> Result<DeploymentStats> {
+    let mut stats = DeploymentStats::default();
+    let cfg = game
+        .plugins_txt_path
+        .as_ref()
+        .context("openmw.cfg path not configured")?;
+    let mut data = openmw_user_data_dirs(cfg, staging_dir)?;
+    for mod_record in enabled_mods {
+        if !Path::new(&mod_record.install_path).exists() {
+            stats
+                .errors
+                .push(format!("Mod directory not found: {}", mod_record.name));
+            continue;
+        }
+        data.push(mod_record.install_path.clone());
+        stats.mods_deployed += 1;
+        stats.files_deployed += mod_record.file_count.max(0) as usize;
+    }
+
+    if !dry_run {
+        openmw::write_entries(cfg, "data", &data)?;
+        tracing::info!(
+            "Listed {} mod folders in {}",
+            stats.mods_deployed,
+            cfg.display()
+        );
+    }
+    Ok(stats)
+}
+
+/// `data=` folders in openmw.cfg that aren't mod staging folders
+fn openmw_user_data_dirs(cfg: &Path, staging_dir: &Path) -> Result<Vec<String>> {
+    Ok(openmw::read_entries(cfg, "data")?
+        .into_iter()
+        .filter(|dir| !Path::new(dir).starts_with(staging_dir))
+        .collect())
+}
+
+/// Winning file per deployed path: normalized relative path ->
+/// (source, mod_name, priority, canonical_relative_path).
+///
+/// Higher priority mods overwrite lower priority.
+pub(super) fn resolve_winning_files(
+    enabled_mods: &[ModRecord],
+    exclusions: &DeployExclusions,
+    stats: &mut DeploymentStats,
+) -> HashMap<PathBuf, (PathBuf, String, i32, PathBuf)> {
+    let mut file_map: HashMap<PathBuf, (PathBuf, String, i32, PathBuf)> = HashMap::new();
+    let mut dir_case_map: HashMap<PathBuf, PathBuf> = HashMap::new();
+
+    for mod_record in enabled_mods {
+        let mod_path = PathBuf::from(&mod_record.install_path);
+        if !mod_path.exists() {
+            stats
+                .errors
+                .push(format!("Mod directory not found: {}", mod_record.name));
+            continue;
+        }
+
+        for entry in WalkDir::new(&mod_path).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative = entry
+                .path()
+                .strip_prefix(&mod_path)
+                .expect("Path should be relative to mod path");
+
+            if let Some(pattern) = exclusions.matching(&mod_record.name, relative) {
+                stats.excluded.push(ExcludedFile {
+                    mod_name: mod_record.name.clone(),
+                    path: relative.to_string_lossy().to_string(),
+                    pattern: pattern.to_string(),
+                });
+                continue;
+            }
+
+            let source = entry.path().to_path_buf();
+            let normalized_relative = normalize_relative_path(relative);
+            let canonical_relative = canonicalize_relative_path(relative, &mut dir_case_map);
+
+            // Check if we already have this file from a lower priority mod (case-insensitive path)
+            if let Some((existing_source, existing_mod, existing_priority, _)) =
+                file_map.get_mut(&normalized_relative)
+            {
+                if mod_record.priority > *existing_priority {
+                    stats.conflicts_resolved += 1;
+                    tracing::debug!(
+                        "Conflict: {} overwrites {} for {}",
+                        mod_record.name,
+                        existing_mod,
+                        relative.display()
+                    );
+                    *existing_source = source;
+                    *existing_mod = mod_record.name.clone();
+                    *existing_priority = mod_record.priority;
+                } else {
+                    // Keep existing (higher or equal priority)
+                    continue;
+                }
+            } else {
+                file_map.insert(
+                    normalized_relative,
+                    (
+                        source,
+                        mod_record.name.clone(),
+                        mod_record.priority,
+                        canonical_relative,
+                    ),
+                );
+            }
+        }
+
+        stats.mods_deployed += 1;
+    }
+
+    file_map
+}
+
+/// Resolve destination path for a deployed file and whether deployment must be a hard copy.
+///
+/// Rules:
+/// - Paths rooted at `Data/` are normalized into the game's `Data` folder.
+/// - Script extender runtime binaries (`skse*.exe` / `skse*.dll`, `obse*`, ...) at mod root
+///   deploy next to the game EXE.
+/// - Any script extender path (filename starts with `skse` or path contains `SKSE`) is always
+///   copied.
+/// - Unreal games: the game's binary mod folders (`OBSE/`, `ue4ss/`) deploy next to the game
+///   EXE and `.pak`/`.ucas`/`.utoc` archives go to the pak mods folder. Paths that start with
+///   the game's project folder (`OblivionRemastered/`) are relative to the install folder.
+pub(super) fn resolve_deploy_destination(game: &Game, relative: &Path) -> (PathBuf, bool) {
+    let relative = strip_leading_data_component(relative);
+    let prefix = game.game_type.script_extender_dir();
+    let filename = relative
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let is_root_level = relative.components().count() == 1;
+    let is_runtime_binary = is_root_level
+        && filename.starts_with(prefix)
+        && (filename.ends_with(".exe") || filename.ends_with(".dll"));
+
+    let mut force_copy = filename.starts_with(prefix);
+    if !force_copy {
+        force_copy = relative.components().any(|c| {
+            matches!(c, Component::Normal(part) if part.to_string_lossy().eq_ignore_ascii_case(prefix))
+        });
+    }
+
+    let top_level = match relative.components().next() {
+        Some(Component::Normal(part)) if !is_root_level => part.to_string_lossy().to_string(),
+        _ => String::new(),
+    };
+    let is_binary_mod = game
+        .game_type
+        .binary_mod_dirs()
+        .iter()
+        .any(|dir| dir.eq_ignore_ascii_case(&top_level));
+    let pak_dir = game.game_type.pak_mods_dir().filter(|_| {
+        [".pak", ".ucas", ".utoc"]
+            .iter()
+            .any(|ext| filename.ends_with(ext))
+    });
+
+    let is_project_path = game
+        .game_type
+        .binaries_dir()
+        .split('/')
+        .next()
+        .is_some_and(|dir| !dir.is_empty() && dir.eq_ignore_ascii_case(&top_level));
+
+    let dest = if is_project_path {
+        game.install_path.join(relative)
+    } else if is_runtime_binary || is_binary_mod {
+        game.binaries_path().join(relative)
+    } else if let Some(pak_dir) = pak_dir {
+        let name = relative.file_name().unwrap_or_default();
+        game.install_path.join(pak_dir).join(name)
+    } else {
+        game.data_path.join(relative)
+    };
+
+    (dest, force_copy)
+}
+
+/// Folders deployment writes into: Data plus any binary mod and pak folders
+fn deploy_roots(game: &Game) -> Vec<PathBuf> {
+    let mut roots = vec![game.data_path.clone()];
+    for dir in game.game_type.binary_mod_dirs() {
+        roots.push(game.binaries_path().join(dir));
+    }
+    if let Some(pak_dir) = game.game_type.pak_mods_dir() {
+        roots.push(game.install_path.join(pak_dir));
+    }
+    roots
+}
+
+/// Strip a leading `Data` (or Morrowind's `Data Files`) component from a relative path
+/// (case-insensitive).
+fn strip_leading_data_component(relative: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    let iter = relative.components();
+    let mut skipped = false;
+
+    for component in iter {
+        if !skipped {
+            if let Component::Normal(part) = component {
+                let part = part.to_string_lossy();
+                if part.eq_ignore_ascii_case("data") || part.eq_ignore_ascii_case("data files") {
+                    skipped = true;
+                    continue;
+                }
+            }
+            skipped = true;
+        }
+        out.push(component.as_os_str());
+    }
+
+    if out.as_os_str().is_empty() {
+        relative.to_path_buf()
+    } else {
+        out
+    }
+}
+
+/// Normalize a relative path for case-insensitive matching.
+pub(super) fn normalize_relative_path(relative: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in relative.components() {
+        if let Component::Normal(part) = component {
+            normalized.push(part.to_string_lossy().to_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Canonicalize path casing by reusing the first seen casing for each path segment,
+/// including the filename.
+fn canonicalize_relative_path(
+    relative: &Path,
+    dir_case_map: &mut HashMap<PathBuf, PathBuf>,
+) -> PathBuf {
+    let mut normalized_path = PathBuf::new();
+    let mut canonical_path = PathBuf::new();
+
+    for component in relative.components() {
+        if let Component::Normal(part) = component {
+            normalized_path.push(part.to_string_lossy().to_lowercase());
+            let stored = dir_case_map
+                .entry(normalized_path.clone())
+                .or_insert_with(|| canonical_path.join(part))
+                .clone();
+            canonical_path = stored;
+        }
+    }
+
+    canonical_path
+}
+
+/// Deploy a single file
+pub(super) async fn deploy_file(
+    method: &DeploymentMethod,
+    source: &Path,
+    dest: &Path,
+    force_copy: bool,
+) -> Result<()> {
+    // Ensure parent directory exists
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .context("Failed to create parent directory")?;
+    }
+
+    // Remove existing file/link if present (including broken symlinks)
+    // Use symlink_metadata to detect symlinks without following them
+    if let Ok(_metadata) = tokio::fs::symlink_metadata(dest).await {
+        // Remove file or symlink (symlink_metadata doesn't follow symlinks)
+        tokio::fs::remove_file(dest).await.ok();
+    }
+
+    if force_copy {
+        tokio::fs::copy(source, dest)
+            .await
+            .context("Failed to copy file")?;
+    } else {
+        match method {
+            DeploymentMethod::Symlink => {
+                symlink(source, dest).context("Failed to create symlink")?;
+            }
+            DeploymentMethod::Hardlink => {
+                std::fs::hard_link(source, dest).context("Failed to create hardlink")?;
+            }
+            DeploymentMethod::Copy => {
+                tokio::fs::copy(source, dest)
+                    .await
+                    .context("Failed to copy file")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove script extender runtime binaries from the game root so redeploys don't leave stale
+/// copies.
+async fn purge_script_extender_root_files(game: &Game) -> Result<()> {
+    let binaries_path = game.binaries_path();
+    if !binaries_path.exists() {
+        return Ok(());
+    }
+
+    let prefix = game.game_type.script_extender_dir();
+    let mut removed = 0usize;
+    for entry in WalkDir::new(&binaries_path)
+        .max_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let lower = name.to_ascii_lowercase();
+        if lower.starts_with(prefix) && (lower.ends_with(".exe") || lower.ends_with(".dll")) {
+            tokio::fs::remove_file(path).await.ok();
+            removed += 1;
+        }
+    }
+
+    if removed > 0 {
+        tracing::info!(
+            "Removed {} {} runtime binaries from game root",
+            removed,
+            game.game_type.script_extender_name()
+        );
+    }
+    Ok(())
+}
+
+/// Remove all deployed mod files (symlinks only)
+///
+/// Safety: Only removes symlinks that point to paths under `staging_dir` to avoid
+/// accidentally deleting unrelated symlinks.
+pub async fn purge_deployment(
+    game: &Game,
+    method: &DeploymentMethod,
+    staging_dir: &Path,
+) -> Result<()> {
+    if game.game_type == GameType::OpenMW {
+        if let Some(cfg) = game.plugins_txt_path.as_ref().filter(|p| p.exists()) {
+            let data = openmw_user_data_dirs(cfg, staging_dir)?;
+            openmw::write_entries(cfg, "data", &data)?;
+        }
+        return Ok(());
+    }
+
+    if *method != DeploymentMethod::Symlink {
+        tracing::warn!(
+            "Purge only works reliably with symlink deployment. \
+             Manual cleanup may be needed for hardlinks/copies."
+        );
+    }
+
+    // Canonicalize staging directory for accurate comparison
+    let canonical_staging = staging_dir
+        .canonicalize()
+        .unwrap_or_else(|_| staging_dir.to_path_buf());
+
+    let mut removed = 0;
+
+    let roots: Vec<PathBuf> = deploy_roots(game)
+        .into_iter()
+        .filter(|root| root.exists())
+        .collect();
+    for entry in roots
+        .iter()
+        .flat_map(|root| WalkDir::new(root).into_iter().filter_map(|e| e.ok()))
+    {
+        let path = entry.path();
+
+        // Only remove symlinks using symlink_metadata to avoid following the link
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if metadata.file_type().is_symlink() {
+                // Check if it points to our staging directory
+                if let Ok(target) = std::fs::read_link(path) {
+                    // Resolve relative symlinks
+                    let target_absolute = if target.is_absolute() {
+                        target
+                    } else {
+                        path.parent().unwrap_or(path).join(&target)
+                    };
+
+                    // Canonicalize and check if under our staging directory
+                    if let Ok(canonical_target) = target_absolute.canonicalize() {
+                        if canonical_target.starts_with(&canonical_staging) {
+                            tokio::fs::remove_file(path).await.ok();
+                            removed += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Clean up empty directories
+    for root in &roots {
+        clean_empty_dirs(root).await?;
+    }
+
+    tracing::info!("Purged {} symlinks from game directory", removed);
+    Ok(())
+}
+
+/// Remove empty directories recursively
+async fn clean_empty_dirs(path: &Path) -> Result<()> {
+    for entry in WalkDir::new(path)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if entry.file_type().is_dir() {
+            // Try to remove - will fail if not empty
+            tokio::fs::remove_dir(entry.path()).await.ok();
+        }
+    }
+    Ok(())
+}
+
+// Add deploy method to ModManager
+impl super::ModManager {
+    /// Deploy all enabled mods to the game directory
+    pub async fn deploy(&self, game: &Game) -> Result<DeploymentStats> {
+        let mut stats = deploy_mods(&self.config, &self.db, game, false).await?;
+        // Without a prefix the INI location is unknown; doctor reports it
+        if stats.loose_files > 0 && game.ini_dir().is_some() {
+            let config = self.config.read().await;
+            if config.deployment.archive_invalidation {
+                let backup_dir = config.paths.ini_backups_dir(&game.id);
+                match crate::profiles::enable_archive_invalidation(game, &backup_dir) {
+                    Ok(write) => stats.archive_invalidation = write.map(|w| w.path),
+                    Err(e) => stats
+                        .warnings
+                        .push(format!("Could not turn on archive invalidation: {}", e)),
+                }
+            }
+        }
+        if let Err(e) = self.save_deployment_manifest(game).await {
+            tracing::warn!("Failed to save deployment manifest: {}", e);
+        }
+        if let Err(e) = self.record_session(game, super::SessionKind::Deploy).await {
+            tracing::warn!("Failed to record deploy session: {}", e);
+        }
+        let method = self.config.read().await.deployment.method;
+        self.record_activity(
+            &game.id,
+            crate::activity::Activity::Deploy,
+            None,
+            serde_json::json!({
+                "method": method,
+                "mods": stats.mods_deployed,
+                "files": stats.files_deployed,
+                "errors": stats.errors.len(),
+            }),
+        )
+        .await;
+        Ok(stats)
+    }
+
+    /// What `deploy` would do, without touching the game directory
+    pub async fn deploy_dry_run(&self, game: &Game) -> Result<DeploymentStats> {
+        deploy_mods(&self.config, &self.db, game, true).await
+    }
+
+    /// Remove all deployed mods
+    pub async fn purge(&self, game: &Game) -> Result<()> {
+        {
+            let config = self.config.read().await;
+            let staging_dir = config.game_staging_dir(&game.id)?;
+            purge_deployment(game, &config.deployment.method, &staging_dir).await?;
+        }
+        self.record_activity(
+            &game.id,
+            crate::activity::Activity::Purge,
+            None,
+            serde_json::Value::Null,
+        )
+        .await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_relative_path_is_case_insensitive() {
+        assert_eq!(
+            normalize_relative_path(Path::new("Bodyslides/Foo.nif")),
+            normalize_relative_path(Path::new("bodyslides/foo.NIF"))
+        );
+    }
+
+    #[test]
+    fn canonicalize_relative_path_reuses_first_seen_directory_casing() {
+        let mut dir_case_map = HashMap::new();
+
+        let first =
+            canonicalize_relative_path(Path::new("Bodyslides/ShapeA.nif"), &mut dir_case_map);
+        let second =
+            canonicalize_relative_path(Path::new("bodyslides/ShapeB.nif"), &mut dir_case_map);
+
+        assert_eq!(first, PathBuf::from("Bodyslides/ShapeA.nif"));
+        assert_eq!(second, PathBuf::from("Bodyslides/ShapeB.nif"));
+    }
+
+    #[test]
+    fn canonicalize_relative_path_reuses_first_seen_filename_casing() {
+        let mut dir_case_map = HashMap::new();
+
+        let first = canonicalize_relative_path(
+            Path::new("Meshes/Bodyslides/Body_0.NIF"),
+            &mut dir_case_map,
+        );
+        let second = canonicalize_relative_path(
+            Path::new("meshes/bodyslides/body_0.nif"),
+            &mut dir_case_map,
+        );
+
+        assert_eq!(first, PathBuf::from("Meshes/Bodyslides/Body_0.NIF"));
+        assert_eq!(second, PathBuf::from("Meshes/Bodyslides/Body_0.NIF"));
+    }
+
+    #[test]
+    fn resolve_deploy_destination_routes_script_extender_and_unreal_files() {
+        use crate::games::GameType;
+
+        let skyrim = Game::new(GameType::SkyrimSE, PathBuf::from("/games/Skyrim"));
+        assert_eq!(
+            resolve_deploy_destination(&skyrim, Path::new("skse64_loader.exe")),
+            (PathBuf::from("/games/Skyrim/skse64_loader.exe"), true)
+        );
+        assert_eq!(
+            resolve_deploy_destination(&skyrim, Path::new("Data/SKSE/Plugins/a.dll")),
+            (PathBuf::from("/games/Skyrim/Data/SKSE/Plugins/a.dll"), true)
+        );
+
+        let oblivion = Game::new(GameType::Oblivion, PathBuf::from("/games/Oblivion"));
+        assert_eq!(
+            resolve_deploy_destination(&oblivion, Path::new("obse_1_2_416.dll")),
+            (PathBuf::from("/games/Oblivion/obse_1_2_416.dll"), true)
+        );
+
+        let remastered = Game::new(
+            GameType::OblivionRemastered,
+            PathBuf::from("/games/Oblivion Remastered"),
+        );
+        let bin = "/games/Oblivion Remastered/OblivionRemastered/Binaries/Win64";
+        assert_eq!(
+            resolve_deploy_destination(&remastered, Path::new("obse64_loader.exe")).0,
+            Path::new(bin).join("obse64_loader.exe")
+        );
+        assert_eq!(
+            resolve_deploy_destination(&remastered, Path::new("OBSE/Plugins/a.dll")),
+            (Path::new(bin).join("OBSE/Plugins/a.dll"), true)
+        );
+        assert_eq!(
+            resolve_deploy_destination(&remastered, Path::new("ue4ss/Mods/A/main.lua")).0,
+            Path::new(bin).join("ue4ss/Mods/A/main.lua")
+        );
+        assert_eq!(
+            resolve_deploy_destination(&remastered, Path::new("Paks/Mod_P.pak")).0,
+            PathBuf::from(
+                "/games/Oblivion Remastered/OblivionRemastered/Content/Paks/~mods/Mod_P.pak"
+            )
+        );
+        assert_eq!(
+            resolve_deploy_destination(&remastered, Path::new("Data/Mod.esp")).0,
+            remastered.data_path.join("Mod.esp")
+        );
+        assert_eq!(
+            resolve_deploy_destination(
+                &remastered,
+                Path::new("OblivionRemastered/Binaries/Win64/dwmapi.dll")
+            )
+            .0,
+            Path::new(bin).join("dwmapi.dll")
+        );
+    }
+}
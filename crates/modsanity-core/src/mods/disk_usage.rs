@@ -0,0 +1,473 @@
+//! Disk usage reporting and cleanup
+//!
+//! Measures staging, downloads, retained archives and copy-deployment
+//! overhead for a game, and finds space that can be reclaimed: downloaded
+//! archives that are superseded or already retained, and staging entries no
+//! installed mod refers to.
+
+use super::deploy::{normalize_relative_path, resolve_deploy_destination};
+use crate::config::DeploymentMethod;
+use crate::db::ModRecord;
+use crate::games::Game;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Staging footprint of one installed mod
+#[derive(Debug, Clone)]
+pub struct ModDiskUsage {
+    pub name: String,
+    pub enabled: bool,
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub files: usize,
+}
+
+/// Why a downloaded archive is considered stale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaleReason {
+    /// A different version of the mod is installed
+    Superseded { installed_version: String },
+    /// The installed version is already kept in the retained archives
+    Retained,
+}
+
+impl StaleReason {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Superseded { installed_version } => {
+                format!("superseded (v{} installed)", installed_version)
+            }
+            Self::Retained => "installed, copy retained".to_string(),
+        }
+    }
+}
+
+/// A downloaded archive that can be removed
+#[derive(Debug, Clone)]
+pub struct StaleArchive {
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub mod_name: String,
+    pub reason: StaleReason,
+}
+
+/// A staging entry that no installed mod refers to
+#[derive(Debug, Clone)]
+pub struct OrphanedEntry {
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub is_dir: bool,
+}
+
+/// Disk usage for one game
+#[derive(Debug, Clone)]
+pub struct DiskUsageReport {
+    pub staging_dir: PathBuf,
+    /// Installed mods, largest first
+    pub mods: Vec<ModDiskUsage>,
+    /// Everything under the game's staging directory, orphans included
+    pub staging_bytes: u64,
+    pub downloads_dir: PathBuf,
+    pub downloads_bytes: u64,
+    pub download_count: usize,
+    /// Retained archives kept for rollback
+    pub archives_bytes: u64,
+    pub deployment_method: DeploymentMethod,
+    /// Bytes duplicated into the game directory by copy deployment
+    pub copy_overhead_bytes: u64,
+    pub stale_archives: Vec<StaleArchive>,
+    pub orphaned: Vec<OrphanedEntry>,
+}
+
+impl DiskUsageReport {
+    pub fn stale_bytes(&self) -> u64 {
+        self.stale_archives.iter().map(|a| a.bytes).sum()
+    }
+
+    pub fn orphaned_bytes(&self) -> u64 {
+        self.orphaned.iter().map(|o| o.bytes).sum()
+    }
+
+    /// Space the cleanup actions would free
+    pub fn reclaimable_bytes(&self) -> u64 {
+        self.stale_bytes() + self.orphaned_bytes()
+    }
+}
+
+/// Result of a cleanup action
+#[derive(Debug, Default, Clone)]
+pub struct CleanupStats {
+    pub removed: usize,
+    pub bytes_freed: u64,
+    pub errors: Vec<String>,
+}
+
+impl super::ModManager {
+    /// Measure disk usage for a game
+    pub async fn disk_usage(&self, game: &Game) -> Result<DiskUsageReport> {
+        let (staging_dir, downloads_dir, archives_dir, method) = {
+            let config = self.config.read().await;
+            (
+                config.game_staging_dir(&game.id)?,
+                config.downloads_dir()?,
+                config.game_archives_dir(&game.id),
+                config.deployment.method,
+            )
+        };
+
+        let records = self.db.get_mods_for_game(&game.id)?;
+
+        let mut mods: Vec<ModDiskUsage> = records
+            .iter()
+            .map(|m| {
+                let path = PathBuf::from(&m.install_path);
+                let (bytes, files) = dir_size(&path);
+                ModDiskUsage {
+                    name: m.name.clone(),
+                    enabled: m.enabled,
+                    path,
+                    bytes,
+                    files,
+                }
+            })
+            .collect();
+        mods.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+
+        let mut retained = HashSet::new();
+        for m in &records {
+            let has_copy = self
+                .db
+                .get_mod_archives(&game.id, &m.name)?
+                .iter()
+                .any(|a| a.version == m.version && Path::new(&a.archive_path).exists());
+            if has_copy {
+                retained.insert(m.name.to_lowercase());
+            }
+        }
+
+        let mut downloads_bytes = 0;
+        let mut download_count = 0;
+        let mut stale_archives = Vec::new();
+        for path in list_archives(&downloads_dir) {
+            let bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            downloads_bytes += bytes;
+            download_count += 1;
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if let Some((mod_name, reason)) = classify_download(stem, &records, &retained) {
+                stale_archives.push(StaleArchive {
+                    path,
+                    bytes,
+                    mod_name,
+                    reason,
+                });
+            }
+        }
+
+        Ok(DiskUsageReport {
+            staging_bytes: dir_size(&staging_dir).0,
+            orphaned: find_orphans(&staging_dir, &records),
+            staging_dir,
+            mods,
+            downloads_dir,
+            downloads_bytes,
+            download_count,
+            archives_bytes: dir_size(&archives_dir).0,
+            deployment_method: method,
+            copy_overhead_bytes: copy_overhead(game, method, &records),
+            stale_archives,
+        })
+    }
+
+    /// Delete the stale downloaded archives listed in `report`
+    pub async fn remove_stale_archives(&self, report: &DiskUsageReport) -> CleanupStats {
+        let mut stats = CleanupStats::default();
+        for archive in &report.stale_archives {
+            match tokio::fs::remove_file(&archive.path).await {
+                Ok(()) => {
+                    stats.removed += 1;
+                    stats.bytes_freed += archive.bytes;
+                }
+                Err(e) => stats
+                    .errors
+                    .push(format!("{}: {}", archive.path.display(), e)),
+            }
+        }
+        stats
+    }
+
+    /// Delete the orphaned staging entries listed in `report`.
+    ///
+    /// Entries are re-checked against the database first so a mod installed
+    /// since the report was taken is never removed.
+    pub async fn remove_orphaned_staging(
+        &self,
+        game_id: &str,
+        report: &DiskUsageReport,
+    ) -> Result<CleanupStats> {
+        let records = self.db.get_mods_for_game(game_id)?;
+        let still_orphaned: HashSet<PathBuf> = find_orphans(&report.staging_dir, &records)
+            .into_iter()
+            .map(|o| o.path)
+            .collect();
+
+        let mut stats = CleanupStats::default();
+        for entry in &report.orphaned {
+            if !still_orphaned.contains(&entry.path) {
+                continue;
+            }
+            let result = if entry.is_dir {
+                tokio::fs::remove_dir_all(&entry.path).await
+            } else {
+                tokio::fs::remove_file(&entry.path).await
+            };
+            match result {
+                Ok(()) => {
+                    stats.removed += 1;
+                    stats.bytes_freed += entry.bytes;
+                }
+                Err(e) => stats
+                    .errors
+                    .push(format!("{}: {}", entry.path.display(), e)),
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// Format a byte count for display
+pub fn format_bytes(bytes: u64) -> String {
+    let b = bytes as f64;
+    if b >= 1_073_741_824.0 {
+        format!("{:.1} GB", b / 1_073_741_824.0)
+    } else if b >= 1_048_576.0 {
+        format!("{:.1} MB", b / 1_048_576.0)
+    } else if b >= 1024.0 {
+        format!("{:.1} KB", b / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Total size and file count of a directory tree (0 if missing)
+fn dir_size(path: &Path) -> (u64, usize) {
+    let mut bytes = 0;
+    let mut files = 0;
+    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+        if entry.file_type().is_file() {
+            bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            files += 1;
+        }
+    }
+    (bytes, files)
+}
+
+fn list_archives(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut archives: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.is_file()
+                && p.extension()
+                    .and_then(|e| e.to_str())
+                    .map(|e| matches!(e.to_ascii_lowercase().as_str(), "zip" | "7z" | "rar"))
+                    .unwrap_or(false)
+        })
+        .collect();
+    archives.sort();
+    archives
+}
+
+/// Decide whether a downloaded archive (by file stem) is stale for this game.
+///
+/// Archives are matched to installed mods by Nexus mod ID first, then by
+/// parsed name. A match with a different file ID or version is superseded;
+/// an exact match is only stale when a retained copy already exists.
+fn classify_download(
+    stem: &str,
+    mods: &[ModRecord],
+    retained: &HashSet<String>,
+) -> Option<(String, StaleReason)> {
+    if let Some((mod_id, file_id)) = super::ModManager::parse_nexus_ids(stem) {
+        if let Some(m) = mods.iter().find(|m| m.nexus_mod_id == Some(mod_id)) {
+            let is_current = m.nexus_file_id.map(|id| id == file_id).unwrap_or(true);
+            return stale_reason(m, is_current, retained);
+        }
+    }
+
+    let (name, version) = super::ModManager::parse_mod_name(stem);
+    let m = mods.iter().find(|m| m.name.eq_ignore_ascii_case(&name))?;
+    stale_reason(m, m.version == version, retained)
+}
+
+fn stale_reason(
+    m: &ModRecord,
+    is_current: bool,
+    retained: &HashSet<String>,
+) -> Option<(String, StaleReason)> {
+    if !is_current {
+        Some((
+            m.name.clone(),
+            StaleReason::Superseded {
+                installed_version: m.version.clone(),
+            },
+        ))
+    } else if retained.contains(&m.name.to_lowercase()) {
+        Some((m.name.clone(), StaleReason::Retained))
+    } else {
+        None
+    }
+}
+
+/// Top-level staging entries that are not an installed mod's directory.
+/// Hidden entries are skipped; they are in-progress reinstall workspaces.
+fn find_orphans(staging_dir: &Path, mods: &[ModRecord]) -> Vec<OrphanedEntry> {
+    let Ok(entries) = std::fs::read_dir(staging_dir) else {
+        return Vec::new();
+    };
+    let known: HashSet<PathBuf> = mods
+        .iter()
+        .map(|m| canonical(Path::new(&m.install_path)))
+        .collect();
+
+    let mut orphans: Vec<OrphanedEntry> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter(|e| !known.contains(&canonical(&e.path())))
+        .map(|e| {
+            let path = e.path();
+            let is_dir = path.is_dir();
+            let bytes = if is_dir {
+                dir_size(&path).0
+            } else {
+                e.metadata().map(|m| m.len()).unwrap_or(0)
+            };
+            OrphanedEntry {
+                path,
+                bytes,
+                is_dir,
+            }
+        })
+        .collect();
+    orphans.sort_by(|a, b| a.path.cmp(&b.path));
+    orphans
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Bytes written as real copies into the game directory by a deployment:
+/// every winning file with copy deployment, otherwise only forced copies
+/// (SKSE binaries).
+fn copy_overhead(game: &Game, method: DeploymentMethod, mods: &[ModRecord]) -> u64 {
+    let mut enabled: Vec<&ModRecord> = mods.iter().filter(|m| m.enabled).collect();
+    enabled.sort_by_key(|m| m.priority);
+
+    // Later (higher priority) mods overwrite earlier ones, as in deployment
+    let mut winners: HashMap<PathBuf, (u64, bool)> = HashMap::new();
+    for m in enabled {
+        let root = Path::new(&m.install_path);
+        for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(root) else {
+                continue;
+            };
+            let (dest, force_copy) = resolve_deploy_destination(game, relative);
+            let bytes = entry.metadata().map(|md| md.len()).unwrap_or(0);
+            winners.insert(normalize_relative_path(&dest), (bytes, force_copy));
+        }
+    }
+
+    winners
+        .values()
+        .filter(|(_, force_copy)| *force_copy || method == DeploymentMethod::Copy)
+        .map(|(bytes, _)| bytes)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Sandbox;
+
+    fn record(name: &str, version: &str, nexus: Option<(i64, i64)>, path: &Path) -> ModRecord {
+        ModRecord {
+            id: None,
+            game_id: "skyrimse".to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+            author: None,
+            description: None,
+            nexus_mod_id: nexus.map(|n| n.0),
+            nexus_file_id: nexus.map(|n| n.1),
+            install_path: path.to_string_lossy().to_string(),
+            enabled: true,
+            priority: 0,
+            file_count: 0,
+            installed_at: String::new(),
+            updated_at: String::new(),
+            category_id: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_download() {
+        let path = Path::new("/nonexistent");
+        let mods = vec![
+            record("SkyUI", "5.2", None, path),
+            record("Gore", "1.7.5", Some((85298, 1739059080)), path),
+        ];
+        let mut retained = HashSet::new();
+
+        assert_eq!(
+            classify_download("SkyUI-5.1", &mods, &retained).map(|r| r.1),
+            Some(StaleReason::Superseded {
+                installed_version: "5.2".to_string()
+            })
+        );
+        assert!(classify_download("SkyUI-5.2", &mods, &retained).is_none());
+        assert!(classify_download("Unrelated-1.0", &mods, &retained).is_none());
+        assert!(matches!(
+            classify_download("Gore-85298-1-7-4-1700000000", &mods, &retained),
+            Some((_, StaleReason::Superseded { .. }))
+        ));
+
+        retained.insert("skyui".to_string());
+        assert_eq!(
+            classify_download("SkyUI-5.2", &mods, &retained),
+            Some(("SkyUI".to_string(), StaleReason::Retained))
+        );
+    }
+
+    #[test]
+    fn test_orphans_and_copy_overhead() {
+        let sandbox = Sandbox::new().unwrap();
+        let staging = sandbox.root().join("staging");
+        let kept = staging.join("Kept");
+        std::fs::create_dir_all(kept.join("textures")).unwrap();
+        std::fs::write(kept.join("textures/a.dds"), [0u8; 100]).unwrap();
+        std::fs::write(kept.join("skse64_loader.exe"), [0u8; 10]).unwrap();
+        std::fs::create_dir_all(staging.join("Leftover")).unwrap();
+        std::fs::write(staging.join("Leftover/b.esp"), [0u8; 7]).unwrap();
+        std::fs::create_dir_all(staging.join(".Kept.reinstall")).unwrap();
+
+        let mods = vec![record("Kept", "1.0", None, &kept)];
+        let orphans = find_orphans(&staging, &mods);
+        assert_eq!(orphans.len(), 1);
+        assert!(orphans[0].path.ends_with("Leftover"));
+        assert_eq!(orphans[0].bytes, 7);
+
+        let game = sandbox.game();
+        assert_eq!(copy_overhead(&game, DeploymentMethod::Copy, &mods), 110);
+        assert_eq!(copy_overhead(&game, DeploymentMethod::Symlink, &mods), 10);
+    }
+}
@@ -0,0 +1,117 @@
+//! Mods that need DLC the game doesn't have
+//!
+//! A plugin's header lists the masters it needs. When one of them is an
+//! official DLC master missing from the game's Data folder the game won't
+//! start with the plugin enabled, so such plugins are left out of deploy.
+
+use crate::db::Database;
+use crate::games::{dlc_for_plugin, installed_dlc, Dlc, Game, GameType};
+use crate::plugins::parse_plugin_header;
+use anyhow::Result;
+use std::path::Path;
+
+/// A mod whose plugins need DLC that isn't installed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingDlc {
+    pub mod_id: i64,
+    pub mod_name: String,
+    pub enabled: bool,
+    /// Names of the missing DLC
+    pub dlc: Vec<String>,
+    /// The mod's plugins that need them, as staged (relative to the mod)
+    pub plugins: Vec<String>,
+}
+
+/// Masters in `masters` that are DLC not in `installed`
+fn needed_dlc(game_type: GameType, masters: &[String], installed: &[&Dlc]) -> Vec<&'static Dlc> {
+    masters
+        .iter()
+        .filter_map(|master| dlc_for_plugin(game_type, master))
+        .filter(|dlc| !installed.contains(dlc))
+        .collect()
+}
+
+/// Mods of `game` with plugins needing missing DLC. Empty when the Data
+/// folder can't be read.
+pub(super) fn find_missing_dlc(db: &Database, game: &Game) -> Result<Vec<MissingDlc>> {
+    let Some(installed) = installed_dlc(game) else {
+        return Ok(Vec::new());
+    };
+    if installed.len() == game.game_type.dlc().len() {
+        return Ok(Vec::new());
+    }
+
+    let mut out = Vec::new();
+    for m in db.get_mods_for_game(&game.id)? {
+        let Some(mod_id) = m.id else {
+            continue;
+        };
+        let mut dlc: Vec<String> = Vec::new();
+        let mut plugins = Vec::new();
+        for file in db.get_mod_files(mod_id)? {
+            let relative = file.relative_path;
+            let is_plugin = Path::new(&relative)
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| matches!(e.to_lowercase().as_str(), "esp" | "esm" | "esl"));
+            // Only plugins at the top of the mod end up in Data
+            if !is_plugin || relative.contains(['/', '\\']) {
+                continue;
+            }
+            let Ok(header) = parse_plugin_header(&Path::new(&m.install_path).join(&relative))
+            else {
+                continue;
+            };
+            let needed = needed_dlc(game.game_type, &header.masters, &installed);
+            if needed.is_empty() {
+                continue;
+            }
+            for d in needed {
+                if !dlc.iter().any(|name| name == d.name) {
+                    dlc.push(d.name.to_string());
+                }
+            }
+            plugins.push(relative);
+        }
+        if !plugins.is_empty() {
+            out.push(MissingDlc {
+                mod_id,
+                mod_name: m.name,
+                enabled: m.enabled,
+                dlc,
+                plugins,
+            });
+        }
+    }
+    Ok(out)
+}
+
+impl super::ModManager {
+    /// Mods whose plugins need official DLC missing from the game
+    pub fn missing_dlc(&self, game: &Game) -> Result<Vec<MissingDlc>> {
+        find_missing_dlc(&self.db, game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needed_dlc_ignores_base_game_and_owned_dlc() {
+        let dawnguard = dlc_for_plugin(GameType::SkyrimSE, "Dawnguard.esm").unwrap();
+        let masters = vec![
+            "Skyrim.esm".to_string(),
+            "Update.esm".to_string(),
+            "Dawnguard.esm".to_string(),
+            "Dragonborn.esm".to_string(),
+            "SkyUI_SE.esp".to_string(),
+        ];
+        let needed = needed_dlc(GameType::SkyrimSE, &masters, &[dawnguard]);
+        assert_eq!(
+            needed.iter().map(|d| d.name).collect::<Vec<_>>(),
+            vec!["Dragonborn"]
+        );
+        assert!(needed_dlc(GameType::SkyrimSE, &masters[..2], &[]).is_empty());
+    }
+}
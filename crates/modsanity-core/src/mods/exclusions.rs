@@ -0,0 +1,229 @@
+//! Deployment exclusion patterns
+//!
+//! Readmes, screenshots and source art ship in many archives but have no
+//! business in Data. Glob patterns set globally in the config or per mod in
+//! the database keep matching files out of deploy (and deployment exports).
+//!
+//! A pattern without `/` matches file names anywhere in the mod (`*.txt`);
+//! one with `/` matches the path from the mod root (`docs/**`). `*` and `?`
+//! stay within a folder, `**` spans folders, and matching ignores case.
+
+use crate::db::Database;
+use anyhow::{bail, Result};
+use regex_lite::Regex;
+use std::collections::HashMap;
+use std::path::{Component, Path};
+
+/// A compiled exclusion glob
+#[derive(Debug, Clone)]
+pub struct ExclusionPattern {
+    pattern: String,
+    regex: Regex,
+    /// Matches the whole path rather than the file name
+    anchored: bool,
+}
+
+impl ExclusionPattern {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let trimmed = pattern.trim().replace('\\', "/");
+        let mut glob = trimmed.trim_start_matches('/').to_lowercase();
+        if glob.is_empty() {
+            bail!("Empty exclusion pattern");
+        }
+        // "docs/" means everything in docs
+        if glob.ends_with('/') {
+            glob.push_str("**");
+        }
+        let anchored = glob.contains('/');
+
+        let mut re = String::from("^");
+        let chars: Vec<char> = glob.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '*' if chars.get(i + 1) == Some(&'*') => {
+                    if chars.get(i + 2) == Some(&'/') {
+                        re.push_str("(?:.*/)?");
+                        i += 3;
+                    } else {
+                        re.push_str(".*");
+                        i += 2;
+                    }
+                    continue;
+                }
+                '*' => re.push_str("[^/]*"),
+                '?' => re.push_str("[^/]"),
+                c => re.push_str(&regex_lite::escape(&c.to_string())),
+            }
+            i += 1;
+        }
+        re.push('$');
+
+        Ok(Self {
+            pattern: trimmed,
+            regex: Regex::new(&re)?,
+            anchored,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether `relative` (a path inside a mod) matches
+    pub fn matches(&self, relative: &Path) -> bool {
+        let parts: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy().to_lowercase()),
+                _ => None,
+            })
+            .collect();
+        // Paths are matched as deployed, so a leading Data folder doesn't count
+        let parts = match parts.split_first() {
+            Some((first, rest)) if first == "data" && !rest.is_empty() => rest,
+            _ => &parts[..],
+        };
+        if self.anchored {
+            self.regex.is_match(&parts.join("/"))
+        } else {
+            parts.last().is_some_and(|name| self.regex.is_match(name))
+        }
+    }
+}
+
+/// Global and per-mod exclusions of one game
+#[derive(Debug, Clone, Default)]
+pub struct DeployExclusions {
+    global: Vec<ExclusionPattern>,
+    /// Keyed by lowercased mod name
+    per_mod: HashMap<String, Vec<ExclusionPattern>>,
+}
+
+impl DeployExclusions {
+    /// Load the per-mod patterns of `game_id` alongside the `global` ones.
+    /// Patterns that don't compile are skipped.
+    pub fn load(db: &Database, game_id: &str, global: &[String]) -> Result<Self> {
+        let compile = |pattern: &str| match ExclusionPattern::new(pattern) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                tracing::warn!("Ignoring exclusion pattern '{}': {}", pattern, e);
+                None
+            }
+        };
+        let mut exclusions = Self {
+            global: global.iter().filter_map(|p| compile(p)).collect(),
+            per_mod: HashMap::new(),
+        };
+        for (mod_name, pattern) in db.get_mod_exclusions(game_id)? {
+            if let Some(p) = compile(&pattern) {
+                exclusions
+                    .per_mod
+                    .entry(mod_name.to_lowercase())
+                    .or_default()
+                    .push(p);
+            }
+        }
+        Ok(exclusions)
+    }
+
+    /// The pattern excluding `relative` of `mod_name`, if any
+    pub fn matching(&self, mod_name: &str, relative: &Path) -> Option<&str> {
+        self.per_mod
+            .get(&mod_name.to_lowercase())
+            .into_iter()
+            .flatten()
+            .chain(&self.global)
+            .find(|p| p.matches(relative))
+            .map(ExclusionPattern::as_str)
+    }
+}
+
+/// A file left out of deploy by an exclusion pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExcludedFile {
+    pub mod_name: String,
+    /// Path inside the mod
+    pub path: String,
+    pub pattern: String,
+}
+
+impl super::ModManager {
+    /// Add or remove a deploy exclusion pattern for one mod
+    pub async fn set_mod_exclusion(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        pattern: &str,
+        excluded: bool,
+    ) -> Result<()> {
+        let Some(m) = self.db.get_mod(game_id, mod_name)? else {
+            bail!("Mod '{}' not found", mod_name);
+        };
+        let pattern = ExclusionPattern::new(pattern)?;
+        if excluded {
+            self.db
+                .add_mod_exclusion(game_id, &m.name, pattern.as_str())?;
+        } else {
+            self.db
+                .remove_mod_exclusion(game_id, &m.name, pattern.as_str())?;
+        }
+        Ok(())
+    }
+
+    /// Deploy exclusion patterns set for one mod
+    pub fn mod_exclusions(&self, game_id: &str, mod_name: &str) -> Result<Vec<String>> {
+        Ok(self
+            .db
+            .get_mod_exclusions(game_id)?
+            .into_iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case(mod_name))
+            .map(|(_, pattern)| pattern)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclusion_globs() {
+        let matches = |pattern: &str, path: &str| {
+            ExclusionPattern::new(pattern)
+                .unwrap()
+                .matches(Path::new(path))
+        };
+
+        assert!(matches("*.txt", "readme.txt"));
+        assert!(matches("*.txt", "Docs/Changelog.TXT"));
+        assert!(!matches("*.txt", "scripts/foo.pex"));
+        assert!(matches("docs/**", "docs/images/a.png"));
+        assert!(matches("docs/", "Data/docs/a.png"));
+        assert!(!matches("docs/**", "textures/docs/a.png"));
+        assert!(matches("**/*.psd", "textures/armor/src/a.psd"));
+        assert!(matches("**/*.psd", "a.psd"));
+        assert!(matches("textures/*.dds", "textures/sky.dds"));
+        assert!(!matches("textures/*.dds", "textures/sky/sky.dds"));
+        assert!(matches("fomod?.xml", "fomod1.xml"));
+        assert!(matches("a+b (1).txt", "a+b (1).txt"));
+        assert!(ExclusionPattern::new("  ").is_err());
+
+        let exclusions = DeployExclusions {
+            global: vec![ExclusionPattern::new("*.txt").unwrap()],
+            per_mod: HashMap::from([(
+                "skyui".to_string(),
+                vec![ExclusionPattern::new("*.psd").unwrap()],
+            )]),
+        };
+        assert_eq!(
+            exclusions.matching("SkyUI", Path::new("src/a.psd")),
+            Some("*.psd")
+        );
+        assert_eq!(exclusions.matching("Other", Path::new("src/a.psd")), None);
+        assert_eq!(
+            exclusions.matching("Other", Path::new("readme.txt")),
+            Some("*.txt")
+        );
+    }
+}
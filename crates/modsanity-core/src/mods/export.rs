@@ -0,0 +1,411 @@
+//! Portable deployment export
+//!
+//! Packages the resolved deployment (the winning copy of every file, laid out
+//! relative to the game folder) into a tar archive that can be extracted onto
+//! a machine without ModSanity. A manifest inside the archive lists every
+//! extracted file so the overlay can be removed again later.
+
+use super::deploy::{resolve_deploy_destination, resolve_winning_files, DeploymentStats};
+use super::exclusions::DeployExclusions;
+use crate::games::Game;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Manifest file name at the archive root
+pub const EXPORT_MANIFEST: &str = "modsanity-manifest.json";
+
+const BLOCK: usize = 512;
+
+/// What an export contains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub game_id: String,
+    pub created_at: String,
+    pub files: Vec<ExportedFile>,
+}
+
+/// One file in an export, relative to the game folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedFile {
+    pub path: String,
+    pub mod_name: String,
+    pub size: u64,
+}
+
+/// Result of an export
+#[derive(Debug, Default)]
+pub struct ExportStats {
+    pub mods_exported: usize,
+    pub files_exported: usize,
+    pub bytes_exported: u64,
+    pub errors: Vec<String>,
+}
+
+/// Result of removing an extracted export
+#[derive(Debug, Default)]
+pub struct CleanExportStats {
+    pub files_removed: usize,
+    /// Listed files that were already gone
+    pub files_missing: usize,
+}
+
+impl super::ModManager {
+    /// Write the resolved deployment for `game` to a tar archive at `dest`
+    pub async fn export_deployment(&self, game: &Game, dest: &Path) -> Result<ExportStats> {
+        let enabled: Vec<_> = self
+            .db
+            .get_mods_for_game(&game.id)?
+            .into_iter()
+            .filter(|m| m.enabled)
+            .collect();
+        if enabled.is_empty() {
+            bail!("No enabled mods to export");
+        }
+
+        let global = self.config.read().await.deployment.exclude.clone();
+        let exclusions = DeployExclusions::load(&self.db, &game.id, &global)?;
+        let mut deploy_stats = DeploymentStats::default();
+        let file_map = resolve_winning_files(&enabled, &exclusions, &mut deploy_stats);
+        let mut files: Vec<(String, PathBuf, String)> = file_map
+            .into_values()
+            .map(|(source, mod_name, _, relative)| {
+                (archive_path(game, &relative), source, mod_name)
+            })
+            .collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut stats = ExportStats {
+            mods_exported: deploy_stats.mods_deployed,
+            errors: deploy_stats.errors,
+            ..Default::default()
+        };
+        let mut manifest = ExportManifest {
+            game_id: game.id.clone(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            files: Vec::new(),
+        };
+
+        let out =
+            File::create(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+        let mut tar = TarWriter::new(BufWriter::new(out));
+        for (path, source, mod_name) in files {
+            let mut file = match File::open(&source) {
+                Ok(f) => f,
+                Err(e) => {
+                    stats
+                        .errors
+                        .push(format!("{} from {}: {}", path, mod_name, e));
+                    continue;
+                }
+            };
+            let meta = file.metadata()?;
+            tar.append(&path, meta.len(), mtime(&meta), &mut file)
+                .with_context(|| format!("Failed to write {} to archive", path))?;
+            stats.files_exported += 1;
+            stats.bytes_exported += meta.len();
+            manifest.files.push(ExportedFile {
+                path,
+                mod_name,
+                size: meta.len(),
+            });
+        }
+
+        let json = serde_json::to_vec_pretty(&manifest)?;
+        tar.append(
+            EXPORT_MANIFEST,
+            json.len() as u64,
+            chrono::Utc::now().timestamp().max(0) as u64,
+            &mut json.as_slice(),
+        )?;
+        tar.finish()?;
+        Ok(stats)
+    }
+}
+
+/// Remove the files an export put into `game_dir`, using its manifest
+pub fn clean_export(game_dir: &Path) -> Result<CleanExportStats> {
+    let manifest_path = game_dir.join(EXPORT_MANIFEST);
+    let json = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("No export manifest at {}", manifest_path.display()))?;
+    let manifest: ExportManifest = serde_json::from_str(&json)?;
+
+    let mut stats = CleanExportStats::default();
+    let mut dirs = std::collections::BTreeSet::new();
+    for file in &manifest.files {
+        let relative = Path::new(&file.path);
+        // Never follow a tampered manifest outside the game folder
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            continue;
+        }
+        let path = game_dir.join(relative);
+        match std::fs::remove_file(&path) {
+            Ok(()) => stats.files_removed += 1,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => stats.files_missing += 1,
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+        }
+        let mut parent = relative.parent();
+        while let Some(dir) = parent.filter(|d| !d.as_os_str().is_empty()) {
+            dirs.insert(game_dir.join(dir));
+            parent = dir.parent();
+        }
+    }
+
+    // Deepest first; only folders the export emptied are removed
+    for dir in dirs.iter().rev() {
+        let _ = std::fs::remove_dir(dir);
+    }
+    std::fs::remove_file(&manifest_path)?;
+    Ok(stats)
+}
+
+/// Path inside the archive: where the file lands relative to the game folder
+fn archive_path(game: &Game, relative: &Path) -> String {
+    let (dest, _) = resolve_deploy_destination(game, relative);
+    let rel = match dest.strip_prefix(&game.install_path) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => Path::new("Data").join(dest.strip_prefix(&game.data_path).unwrap_or(relative)),
+    };
+    rel.to_string_lossy().replace('\\', "/")
+}
+
+fn mtime(meta: &std::fs::Metadata) -> u64 {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Minimal ustar writer; paths that don't fit the header use a PAX record
+struct TarWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> TarWriter<W> {
+    fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    fn append(&mut self, path: &str, size: u64, mtime: u64, data: &mut dyn Read) -> Result<()> {
+        let (name, prefix) = match split_ustar_path(path) {
+            Some(split) => split,
+            None => {
+                let record = pax_record("path", path);
+                let mut header = header(b"././@PaxHeader", b"", record.len() as u64, mtime, b'x');
+                self.write_block(&mut header, record.as_bytes())?;
+                (truncate(path.as_bytes(), 100), &[][..])
+            }
+        };
+        let mut header = header(name, prefix, size, mtime, b'0');
+        checksum(&mut header);
+        self.out.write_all(&header)?;
+
+        let written = std::io::copy(&mut data.take(size), &mut self.out)?;
+        if written != size {
+            bail!("{} changed size while exporting", path);
+        }
+        self.pad(size)
+    }
+
+    fn write_block(&mut self, header: &mut [u8; BLOCK], body: &[u8]) -> Result<()> {
+        checksum(header);
+        self.out.write_all(header)?;
+        self.out.write_all(body)?;
+        self.pad(body.len() as u64)
+    }
+
+    fn pad(&mut self, size: u64) -> Result<()> {
+        let rem = (size % BLOCK as u64) as usize;
+        if rem != 0 {
+            self.out.write_all(&[0u8; BLOCK][..BLOCK - rem])?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<()> {
+        self.out.write_all(&[0u8; BLOCK * 2])?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+fn header(name: &[u8], prefix: &[u8], size: u64, mtime: u64, kind: u8) -> [u8; BLOCK] {
+    let mut h = [0u8; BLOCK];
+    h[..name.len()].copy_from_slice(name);
+    octal(&mut h[100..108], 0o644);
+    octal(&mut h[108..116], 0);
+    octal(&mut h[116..124], 0);
+    octal(&mut h[124..136], size);
+    octal(&mut h[136..148], mtime);
+    h[156] = kind;
+    h[257..263].copy_from_slice(b"ustar\0");
+    h[263..265].copy_from_slice(b"00");
+    h[345..345 + prefix.len()].copy_from_slice(prefix);
+    h
+}
+
+/// Zero-padded octal with a trailing NUL
+fn octal(field: &mut [u8], value: u64) {
+    let n = field.len() - 1;
+    let digits = format!("{:0n$o}", value);
+    field[..n].copy_from_slice(&digits.as_bytes()[digits.len() - n..]);
+}
+
+fn checksum(h: &mut [u8; BLOCK]) {
+    h[148..156].fill(b' ');
+    let sum: u32 = h.iter().map(|&b| b as u32).sum();
+    let digits = format!("{:06o}\0 ", sum);
+    h[148..156].copy_from_slice(digits.as_bytes());
+}
+
+/// Split into ustar (name, prefix) fields, or `None` when it doesn't fit
+fn split_ustar_path(path: &str) -> Option<(&[u8], &[u8])> {
+    let bytes = path.as_bytes();
+    if bytes.len() <= 100 {
+        return Some((bytes, &[]));
+    }
+    (0..bytes.len())
+        .find(|&i| bytes[i] == b'/' && i <= 155 && bytes.len() - i - 1 <= 100)
+        .map(|i| (&bytes[i + 1..], &bytes[..i]))
+}
+
+fn truncate(bytes: &[u8], max: usize) -> &[u8] {
+    &bytes[..bytes.len().min(max)]
+}
+
+/// A PAX extended header record; its length prefix counts itself
+fn pax_record(key: &str, value: &str) -> String {
+    let body = format!(" {}={}\n", key, value);
+    let mut len = body.len() + 1;
+    while len.to_string().len() + body.len() != len {
+        len = len.to_string().len() + body.len();
+    }
+    format!("{}{}", len, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (path, contents) of every entry, resolving PAX path records
+    fn read_tar(bytes: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let mut entries = Vec::new();
+        let mut pax_path = None;
+        let mut pos = 0;
+        while pos + BLOCK <= bytes.len() && bytes[pos] != 0 {
+            let h = &bytes[pos..pos + BLOCK];
+            let field = |r: std::ops::Range<usize>| {
+                let f = &h[r];
+                String::from_utf8_lossy(&f[..f.iter().position(|&b| b == 0).unwrap_or(f.len())])
+                    .to_string()
+            };
+            let size = u64::from_str_radix(field(124..135).trim(), 8).unwrap() as usize;
+            let data = bytes[pos + BLOCK..pos + BLOCK + size].to_vec();
+            pos += BLOCK + size.div_ceil(BLOCK) * BLOCK;
+            if h[156] == b'x' {
+                let record = String::from_utf8(data).unwrap();
+                pax_path = record
+                    .split_once("path=")
+                    .map(|(_, p)| p.trim_end().to_string());
+                continue;
+            }
+            let prefix = field(345..500);
+            let name = if prefix.is_empty() {
+                field(0..100)
+            } else {
+                format!("{}/{}", prefix, field(0..100))
+            };
+            entries.push((pax_path.take().unwrap_or(name), data));
+        }
+        entries
+    }
+
+    #[test]
+    fn test_tar_paths_round_trip() {
+        let long_dir = "a".repeat(120);
+        let split = format!("{}/{}", long_dir, "file.dds");
+        let unsplittable = format!("Data/{}", "b".repeat(150));
+
+        let mut buf = Vec::new();
+        let mut tar = TarWriter::new(&mut buf);
+        for (path, data) in [
+            ("Data/short.esp", &b"plugin"[..]),
+            (split.as_str(), &b"split"[..]),
+            (unsplittable.as_str(), &[7u8; 600][..]),
+        ] {
+            tar.append(path, data.len() as u64, 0, &mut &data[..])
+                .unwrap();
+        }
+        tar.finish().unwrap();
+
+        assert_eq!(buf.len() % BLOCK, 0);
+        let entries = read_tar(&buf);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0],
+            ("Data/short.esp".to_string(), b"plugin".to_vec())
+        );
+        assert_eq!(entries[1].0, split);
+        assert_eq!(entries[2].0, unsplittable);
+        assert_eq!(entries[2].1, vec![7u8; 600]);
+    }
+
+    #[test]
+    fn test_pax_record_length() {
+        let record = pax_record("path", "x");
+        assert_eq!(record, "9 path=x\n");
+        assert_eq!(record.len(), 9);
+        let long = pax_record("path", &"y".repeat(95));
+        assert_eq!(long.len().to_string(), long.split(' ').next().unwrap());
+    }
+
+    #[test]
+    fn test_clean_export_removes_listed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("Data/textures/sky")).unwrap();
+        std::fs::write(root.join("Data/textures/sky/a.dds"), b"a").unwrap();
+        std::fs::write(root.join("Data/Skyrim.esm"), b"base game").unwrap();
+        let manifest = ExportManifest {
+            game_id: "skyrimse".to_string(),
+            created_at: String::new(),
+            files: vec![
+                ExportedFile {
+                    path: "Data/textures/sky/a.dds".to_string(),
+                    mod_name: "Sky".to_string(),
+                    size: 1,
+                },
+                ExportedFile {
+                    path: "Data/gone.esp".to_string(),
+                    mod_name: "Gone".to_string(),
+                    size: 0,
+                },
+                ExportedFile {
+                    path: "../outside.txt".to_string(),
+                    mod_name: "Evil".to_string(),
+                    size: 0,
+                },
+            ],
+        };
+        std::fs::write(
+            root.join(EXPORT_MANIFEST),
+            serde_json::to_string(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let stats = clean_export(root).unwrap();
+        assert_eq!(stats.files_removed, 1);
+        assert_eq!(stats.files_missing, 1);
+        assert!(!root.join("Data/textures").exists());
+        assert!(root.join("Data/Skyrim.esm").exists());
+        assert!(!root.join(EXPORT_MANIFEST).exists());
+    }
+}
@@ -65,7 +65,7 @@ impl Version {
             return None;
         }
 
-        let major = parts.get(0)?.parse().ok()?;
+        let major = parts.first()?.parse().ok()?;
         let minor = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(0);
         let patch = parts.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
 
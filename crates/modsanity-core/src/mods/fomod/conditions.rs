@@ -1,10 +1,13 @@
 //! FOMOD condition evaluation engine
 //!
 //! Evaluates boolean expressions for plugin visibility and dependencies.
-//! Supports AND/OR/NOT logic, flag dependencies, and file dependencies.
+//! Supports AND/OR/NOT logic, flag dependencies, file dependencies, and
+//! game / script extender version dependencies.
 
-use super::{Dependencies, Pattern, Plugin};
+use super::{ComparisonOperator, Dependencies, DependencyItem, Pattern, Plugin, Version};
+use crate::games::{check_script_extender, read_pe_version, Game};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// File states for dependency checking
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -71,39 +74,124 @@ pub enum Condition {
     FlagDependency { flag: String, value: String },
     /// File must have specific state
     FileDependency { file: String, state: FileState },
+    /// Installed game must be at least this version
+    GameVersion { version: String },
+    /// Installed script extender must be at least this version
+    ScriptExtenderVersion { version: String },
 }
 
 impl Condition {
     /// Build condition tree from Dependencies
     pub fn from_dependencies(deps: &Dependencies) -> Self {
-        let mut conditions = Vec::new();
-
-        // Add flag dependencies
-        for flag_dep in &deps.flag_dependencies {
-            conditions.push(Condition::FlagDependency {
-                flag: flag_dep.flag.clone(),
-                value: flag_dep.value.clone(),
-            });
-        }
-
-        // Add file dependencies
-        for file_dep in &deps.file_dependencies {
-            conditions.push(Condition::FileDependency {
-                file: file_dep.file.clone(),
-                state: FileState::from_str(&file_dep.state),
-            });
-        }
+        let conditions: Vec<Condition> = deps.items.iter().map(Self::from_item).collect();
 
-        // If multiple dependencies, they are implicitly AND-ed
+        // No dependencies - always true
         if conditions.is_empty() {
-            // No dependencies - always true
-            Condition::And(vec![])
-        } else if conditions.len() == 1 {
-            conditions.into_iter().next().unwrap()
+            return Condition::And(vec![]);
+        }
+        if conditions.len() == 1 {
+            return conditions.into_iter().next().unwrap();
+        }
+        if deps.operator.eq_ignore_ascii_case("or") {
+            Condition::Or(conditions)
         } else {
             Condition::And(conditions)
         }
     }
+
+    fn from_item(item: &DependencyItem) -> Self {
+        match item {
+            DependencyItem::File(dep) => Condition::FileDependency {
+                file: dep.file.clone(),
+                state: FileState::from_str(&dep.state),
+            },
+            DependencyItem::Flag(dep) => Condition::FlagDependency {
+                flag: dep.flag.clone(),
+                value: dep.value.clone(),
+            },
+            DependencyItem::Game(dep) => Condition::GameVersion {
+                version: dep.version.clone(),
+            },
+            DependencyItem::ScriptExtender(dep) => Condition::ScriptExtenderVersion {
+                version: dep.version.clone(),
+            },
+            // We are the mod manager, and new enough for any installer
+            DependencyItem::Manager(_) => Condition::And(vec![]),
+            DependencyItem::Nested(deps) => Self::from_dependencies(deps),
+        }
+    }
+}
+
+/// What FOMOD conditions are tested against: the state of plugins and
+/// files in the load order, and the installed game and script extender versions
+#[derive(Debug, Clone, Default)]
+pub struct FomodEnvironment {
+    /// Data-relative paths, lowercase with forward slashes
+    pub files: HashMap<String, FileState>,
+    pub game_version: Option<String>,
+    pub script_extender_version: Option<String>,
+}
+
+impl FomodEnvironment {
+    /// Record a file provided by an installed mod
+    pub fn add_file(&mut self, path: &str, state: FileState) {
+        let entry = self
+            .files
+            .entry(normalize_file(path))
+            .or_insert(FileState::Missing);
+        // A file shipped by both an enabled and a disabled mod is active
+        if *entry != FileState::Active {
+            *entry = state;
+        }
+    }
+
+    /// Add the game's own Data files and its version details
+    pub fn with_game(mut self, game: &Game) -> Self {
+        if let Ok(entries) = std::fs::read_dir(&game.data_path) {
+            for entry in entries.flatten() {
+                if entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+                    let name = entry.file_name().to_string_lossy().to_lowercase();
+                    self.files.entry(name).or_insert(FileState::Active);
+                }
+            }
+        }
+        self.game_version =
+            read_pe_version(&game.executable_path(&game.executable)).map(|v| v.to_string());
+        self.script_extender_version = check_script_extender(game, &[])
+            .extender
+            .and_then(|e| e.version);
+        self
+    }
+
+    pub fn file_state(&self, file: &str) -> FileState {
+        self.files
+            .get(&normalize_file(file))
+            .copied()
+            .unwrap_or(FileState::Missing)
+    }
+}
+
+fn normalize_file(path: &str) -> String {
+    let path = path.replace('\\', "/").to_lowercase();
+    path.strip_prefix("data/").unwrap_or(&path).to_string()
+}
+
+/// Whether `installed` satisfies a minimum `required` version.
+///
+/// Only major.minor.patch are compared. An unknown installed version passes,
+/// so undetectable setups still see every option rather than none.
+fn meets_version(installed: Option<&str>, required: &str) -> bool {
+    let release = |v: &str| -> Option<Version> {
+        let parts: Vec<&str> = v.trim().split('.').take(3).collect();
+        Version::parse(&parts.join("."))
+    };
+    let Some(required) = release(required) else {
+        return true;
+    };
+    match installed.and_then(release) {
+        Some(installed) => installed.compare_with(&required, ComparisonOperator::GreaterOrEqual),
+        None => true,
+    }
 }
 
 /// Condition evaluator with current state
@@ -111,7 +199,9 @@ pub struct ConditionEvaluator {
     /// Current flag values
     flags: HashMap<String, String>,
     /// File state checker function
-    file_checker: Box<dyn Fn(&str) -> FileState + Send + Sync>,
+    file_checker: Arc<dyn Fn(&str) -> FileState + Send + Sync>,
+    game_version: Option<String>,
+    script_extender_version: Option<String>,
 }
 
 impl std::fmt::Debug for ConditionEvaluator {
@@ -125,11 +215,11 @@ impl std::fmt::Debug for ConditionEvaluator {
 
 impl Clone for ConditionEvaluator {
     fn clone(&self) -> Self {
-        // Clone flags but create new default file checker
-        // (function pointers can't be cloned)
         Self {
             flags: self.flags.clone(),
-            file_checker: Box::new(|_| FileState::Missing),
+            file_checker: Arc::clone(&self.file_checker),
+            game_version: self.game_version.clone(),
+            script_extender_version: self.script_extender_version.clone(),
         }
     }
 }
@@ -137,10 +227,7 @@ impl Clone for ConditionEvaluator {
 impl ConditionEvaluator {
     /// Create new evaluator with default file checker
     pub fn new() -> Self {
-        Self {
-            flags: HashMap::new(),
-            file_checker: Box::new(|_| FileState::Missing),
-        }
+        Self::with_file_checker(|_| FileState::Missing)
     }
 
     /// Create evaluator with custom file checker
@@ -150,10 +237,28 @@ impl ConditionEvaluator {
     {
         Self {
             flags: HashMap::new(),
-            file_checker: Box::new(file_checker),
+            file_checker: Arc::new(file_checker),
+            game_version: None,
+            script_extender_version: None,
+        }
+    }
+
+    /// Create evaluator that checks files and versions against `env`
+    pub fn with_environment(env: FomodEnvironment) -> Self {
+        let game_version = env.game_version.clone();
+        let script_extender_version = env.script_extender_version.clone();
+        Self {
+            game_version,
+            script_extender_version,
+            ..Self::with_file_checker(move |file| env.file_state(file))
         }
     }
 
+    /// All flags set so far
+    pub fn flags(&self) -> &HashMap<String, String> {
+        &self.flags
+    }
+
     /// Set a flag value
     pub fn set_flag(&mut self, name: String, value: String) {
         self.flags.insert(name, value);
@@ -188,12 +293,19 @@ impl ConditionEvaluator {
             }
             Condition::Not(condition) => !self.evaluate(condition),
             Condition::FlagDependency { flag, value } => {
-                self.flags.get(flag).map(|v| v == value).unwrap_or(false)
+                // An unset flag reads as empty, so value="" tests for "never set"
+                self.flags.get(flag).map(String::as_str).unwrap_or("") == value
             }
             Condition::FileDependency { file, state } => {
                 let actual_state = (self.file_checker)(file);
                 actual_state == *state
             }
+            Condition::GameVersion { version } => {
+                meets_version(self.game_version.as_deref(), version)
+            }
+            Condition::ScriptExtenderVersion { version } => {
+                meets_version(self.script_extender_version.as_deref(), version)
+            }
         }
     }
 
@@ -458,6 +570,63 @@ mod tests {
         assert!(evaluator.evaluate_dependencies(&None));
     }
 
+    #[test]
+    fn test_composite_dependencies_from_xml() {
+        let xml = r#"<dependencies operator="Or">
+            <dependencies operator="And">
+                <flagDependency flag="weather" value="vivid"/>
+                <fileDependency file="Data\Vivid.esp" state="Active"/>
+            </dependencies>
+            <flagDependency flag="force" value="On"/>
+        </dependencies>"#;
+        let deps: Dependencies = quick_xml::de::from_str(xml).unwrap();
+
+        let env = FomodEnvironment {
+            files: HashMap::from([("vivid.esp".to_string(), FileState::Active)]),
+            ..Default::default()
+        };
+        let mut evaluator = ConditionEvaluator::with_environment(env);
+        assert!(!evaluator.evaluate_dependencies(&Some(deps.clone())));
+
+        evaluator.set_flag("weather".to_string(), "vivid".to_string());
+        assert!(evaluator.evaluate_dependencies(&Some(deps.clone())));
+
+        evaluator.clear_flags();
+        evaluator.set_flag("force".to_string(), "On".to_string());
+        assert!(evaluator.clone().evaluate_dependencies(&Some(deps)));
+    }
+
+    #[test]
+    fn test_version_dependencies() {
+        let xml = r#"<dependencies>
+            <gameDependency version="1.6.640"/>
+            <foseDependency version="2.2"/>
+            <fommDependency version="0.13.21"/>
+        </dependencies>"#;
+        let deps = Some(quick_xml::de::from_str::<Dependencies>(xml).unwrap());
+
+        let env = |game: &str, extender: &str| FomodEnvironment {
+            game_version: Some(game.to_string()),
+            script_extender_version: Some(extender.to_string()),
+            ..Default::default()
+        };
+        let check = |env| ConditionEvaluator::with_environment(env).evaluate_dependencies(&deps);
+        assert!(check(env("1.6.1170", "2.2.6")));
+        assert!(!check(env("1.5.97", "2.2.6")));
+        assert!(!check(env("1.6.1170", "2.1.5")));
+        // Undetected versions don't hide options
+        assert!(check(FomodEnvironment::default()));
+    }
+
+    #[test]
+    fn test_unset_flag_matches_empty_value() {
+        let evaluator = ConditionEvaluator::new();
+        assert!(evaluator.evaluate(&Condition::FlagDependency {
+            flag: "never_set".to_string(),
+            value: String::new(),
+        }));
+    }
+
     #[test]
     fn test_file_state_from_str() {
         assert_eq!(FileState::from_str("Active"), FileState::Active);
@@ -156,17 +156,6 @@ impl ComplexityLevel {
             Self::Complex => "Complex with conditions",
         }
     }
-
-    pub fn color(&self) -> ratatui::style::Color {
-        use ratatui::style::Color;
-        match self {
-            Self::Simple => Color::Green,
-            Self::Basic => Color::Cyan,
-            Self::Moderate => Color::Yellow,
-            Self::Advanced => Color::Magenta,
-            Self::Complex => Color::Red,
-        }
-    }
 }
 
 #[cfg(test)]
@@ -10,6 +10,8 @@ pub mod helpers;
 mod parser;
 pub mod persistence;
 pub mod planner;
+pub mod preset;
+pub mod preview;
 pub mod validation;
 pub mod wizard;
 
@@ -20,6 +22,8 @@ pub use helpers::*;
 pub use parser::*;
 pub use persistence::*;
 pub use planner::*;
+pub use preset::*;
+pub use preview::*;
 pub use validation::*;
 pub use wizard::*;
 
@@ -267,7 +271,7 @@ pub fn has_numbered_folders(mod_path: &Path) -> bool {
                     if name_str.len() >= 2
                         && name_str
                             .chars()
-                            .nth(0)
+                            .next()
                             .map(|c| c.is_ascii_digit())
                             .unwrap_or(false)
                         && name_str
@@ -304,7 +308,7 @@ pub fn get_numbered_components(mod_path: &Path) -> anyhow::Result<Vec<FomodCompo
         if name_str.len() >= 2
             && name_str
                 .chars()
-                .nth(0)
+                .next()
                 .map(|c| c.is_ascii_digit())
                 .unwrap_or(false)
             && name_str
@@ -429,4 +433,54 @@ impl FomodInstaller {
     pub fn requires_wizard(&self) -> bool {
         !self.config.install_steps.steps.is_empty()
     }
+
+    /// Resolve a path from ModuleConfig.xml (e.g. an option image) inside the
+    /// mod, matching each component case-insensitively like Windows would
+    pub fn resolve_path(&self, relative: &str) -> Option<PathBuf> {
+        let mut current = self.mod_path.clone();
+        for part in relative
+            .split(['\\', '/'])
+            .filter(|p| !p.is_empty() && *p != ".")
+        {
+            if part == ".." {
+                return None;
+            }
+            let exact = current.join(part);
+            if exact.exists() {
+                current = exact;
+                continue;
+            }
+            let part_lower = part.to_lowercase();
+            current = std::fs::read_dir(&current)
+                .ok()?
+                .filter_map(|e| e.ok())
+                .find(|e| e.file_name().to_string_lossy().to_lowercase() == part_lower)?
+                .path();
+        }
+        current.is_file().then_some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_path_ignores_case_and_separators() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("fomod/Images")).unwrap();
+        std::fs::write(dir.path().join("fomod/Images/Option A.PNG"), b"png").unwrap();
+        let installer = FomodInstaller {
+            config: parse_module_config("<config><moduleName>T</moduleName></config>").unwrap(),
+            mod_path: dir.path().to_path_buf(),
+        };
+
+        assert_eq!(
+            installer.resolve_path(r"FOMOD\images\option a.png"),
+            Some(dir.path().join("fomod/Images/Option A.PNG"))
+        );
+        assert_eq!(installer.resolve_path("fomod/images"), None);
+        assert_eq!(installer.resolve_path("fomod/missing.png"), None);
+        assert_eq!(installer.resolve_path("../fomod/images/option a.png"), None);
+    }
 }
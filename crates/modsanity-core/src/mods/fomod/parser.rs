@@ -34,14 +34,37 @@ pub struct ModuleImage {
     pub path: String,
 }
 
-/// Dependency list
-#[derive(Debug, Clone, Deserialize)]
+/// Composite dependency: child conditions combined with `operator`
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct Dependencies {
-    #[serde(rename = "fileDependency", default)]
-    pub file_dependencies: Vec<FileDependency>,
+    /// "And" (default) or "Or"
+    #[serde(rename = "@operator", default)]
+    pub operator: String,
 
-    #[serde(rename = "flagDependency", default)]
-    pub flag_dependencies: Vec<FlagDependency>,
+    /// Child conditions in document order
+    #[serde(rename = "$value", default)]
+    pub items: Vec<DependencyItem>,
+}
+
+/// A single condition inside a composite dependency
+#[derive(Debug, Clone, Deserialize)]
+pub enum DependencyItem {
+    #[serde(rename = "fileDependency")]
+    File(FileDependency),
+    #[serde(rename = "flagDependency")]
+    Flag(FlagDependency),
+    /// Minimum game version
+    #[serde(rename = "gameDependency")]
+    Game(VersionDependency),
+    /// Minimum script extender version
+    #[serde(rename = "foseDependency")]
+    ScriptExtender(VersionDependency),
+    /// Minimum mod manager version
+    #[serde(rename = "fommDependency")]
+    Manager(VersionDependency),
+    /// Nested composite with its own operator
+    #[serde(rename = "dependencies")]
+    Nested(Dependencies),
 }
 
 /// File dependency
@@ -60,10 +83,18 @@ pub struct FlagDependency {
     #[serde(rename = "@flag")]
     pub flag: String,
 
-    #[serde(rename = "@value")]
+    /// Empty matches a flag that was never set
+    #[serde(rename = "@value", default)]
     pub value: String,
 }
 
+/// Game, script extender or mod manager version dependency
+#[derive(Debug, Clone, Deserialize)]
+pub struct VersionDependency {
+    #[serde(rename = "@version")]
+    pub version: String,
+}
+
 /// Install steps wrapper
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct InstallSteps {
@@ -80,6 +111,10 @@ pub struct InstallStep {
     #[serde(rename = "@name")]
     pub name: String,
 
+    /// Conditions under which the step is shown
+    #[serde(rename = "visible", default)]
+    pub visible: Option<Dependencies>,
+
     #[serde(rename = "optionalFileGroups", default)]
     pub groups: OptionGroups,
 }
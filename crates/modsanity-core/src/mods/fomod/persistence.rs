@@ -157,6 +157,7 @@ mod tests {
                 order: String::new(),
                 steps: vec![InstallStep {
                     name: "Step 1".to_string(),
+                    visible: None,
                     groups: OptionGroups {
                         order: String::new(),
                         groups: vec![OptionGroup {
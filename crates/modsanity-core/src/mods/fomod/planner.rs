@@ -2,7 +2,7 @@
 //!
 //! Compiles wizard selections into explicit install plans with conflict detection.
 
-use super::{FileInstruction, FomodInstaller, ModuleConfig, WizardState};
+use super::{FileInstruction, FomodEnvironment, FomodInstaller, ModuleConfig, WizardState};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -193,6 +193,40 @@ impl InstallPlan {
             .iter()
             .any(|c| c.severity == ConflictSeverity::High)
     }
+
+    /// Rebuild wizard selections from this plan so it can be replayed.
+    ///
+    /// Returns `None` when the installer's configuration changed since the
+    /// plan was recorded, in which case the wizard must be run again.
+    pub fn to_wizard_state(
+        &self,
+        installer: &FomodInstaller,
+        env: FomodEnvironment,
+    ) -> Option<WizardState> {
+        if self.config_hash != compute_config_hash(&installer.config) {
+            return None;
+        }
+
+        let mut wizard = WizardState::with_environment(env);
+
+        let steps = installer.steps();
+        for opt in &self.selected_options {
+            let group = steps
+                .get(opt.step_idx)
+                .and_then(|step| step.groups.groups.get(opt.group_idx))?;
+
+            let mut selected = std::collections::HashSet::new();
+            for &idx in &opt.plugin_indices {
+                group.plugins.plugins.get(idx)?;
+                selected.insert(idx);
+            }
+            wizard.set_selection(opt.step_idx, opt.group_idx, selected);
+        }
+
+        // Flags come from the selections, re-evaluated against today's load order
+        wizard.refresh_flags(&installer.config);
+        Some(wizard)
+    }
 }
 
 /// Collect all selections from wizard state
@@ -227,11 +261,8 @@ fn collect_selections(wizard: &WizardState, installer: &FomodInstaller) -> Vec<O
 }
 
 /// Collect all flags from wizard evaluator
-fn collect_flags(_wizard: &WizardState) -> HashMap<String, String> {
-    // For now, we'll need to track flags as they're set
-    // The evaluator doesn't expose its internal flags map
-    // This is a limitation we'll need to address
-    HashMap::new()
+fn collect_flags(wizard: &WizardState) -> HashMap<String, String> {
+    wizard.evaluator.flags().clone()
 }
 
 /// Compile file operations from wizard selections
@@ -0,0 +1,244 @@
+//! Non-interactive FOMOD answers
+//!
+//! A preset names the options to pick per step and group, so an installer
+//! can be answered without the wizard (scripted and batch installs). Groups a
+//! preset doesn't mention get the installer's default selections.
+
+use super::planner::InstallPlan;
+use super::validation::validate_step;
+use super::{FomodEnvironment, ModuleConfig, WizardState};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Saved answers for a FOMOD installer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FomodPreset {
+    /// Installer the preset was made for (informational)
+    #[serde(default)]
+    pub module_name: String,
+    pub selections: Vec<PresetSelection>,
+}
+
+/// Options picked in one group, matched by name (case-insensitive)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetSelection {
+    pub step: String,
+    pub group: String,
+    pub plugins: Vec<String>,
+}
+
+impl FomodPreset {
+    /// Read a preset file; a saved install plan is accepted as well
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        if let Ok(preset) = serde_json::from_str::<Self>(&json) {
+            return Ok(preset);
+        }
+        let plan: InstallPlan = serde_json::from_str(&json)
+            .with_context(|| format!("{} is not a FOMOD preset", path.display()))?;
+        Ok(Self::from_plan(&plan))
+    }
+
+    /// Write the preset as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Preset with the choices recorded in an install plan
+    pub fn from_plan(plan: &InstallPlan) -> Self {
+        Self {
+            module_name: plan.mod_name.clone(),
+            selections: plan
+                .selected_options
+                .iter()
+                .map(|opt| PresetSelection {
+                    step: opt.step_name.clone(),
+                    group: opt.group_name.clone(),
+                    plugins: opt.plugin_names.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn find(&self, step: &str, group: &str) -> Option<&PresetSelection> {
+        self.selections.iter().find(|s| {
+            s.step.trim().eq_ignore_ascii_case(step.trim())
+                && s.group.trim().eq_ignore_ascii_case(group.trim())
+        })
+    }
+}
+
+/// Answer every visible step of `config` without the wizard, using `preset`
+/// where it names a group and the installer defaults otherwise.
+///
+/// Fails when the preset names steps, groups or options the installer
+/// doesn't have, or when the result breaks a group's selection rules.
+pub fn answer_wizard(
+    config: &ModuleConfig,
+    env: FomodEnvironment,
+    preset: Option<&FomodPreset>,
+) -> Result<WizardState> {
+    let steps = &config.install_steps.steps;
+    if let Some(preset) = preset {
+        for sel in &preset.selections {
+            let known = steps.iter().any(|step| {
+                step.name.trim().eq_ignore_ascii_case(sel.step.trim())
+                    && step
+                        .groups
+                        .groups
+                        .iter()
+                        .any(|g| g.name.trim().eq_ignore_ascii_case(sel.group.trim()))
+            });
+            if !known {
+                bail!(
+                    "Preset group '{}' / '{}' does not exist in this installer",
+                    sel.step,
+                    sel.group
+                );
+            }
+        }
+    }
+
+    let mut wizard = WizardState::with_environment(env);
+    for (step_idx, step) in steps.iter().enumerate() {
+        // Earlier answers decide which later steps are shown
+        if !wizard.is_step_visible(config, step_idx) {
+            continue;
+        }
+        for (group_idx, group) in step.groups.groups.iter().enumerate() {
+            let selections = match preset.and_then(|p| p.find(&step.name, &group.name)) {
+                Some(sel) => {
+                    let mut picked = HashSet::new();
+                    for name in &sel.plugins {
+                        let idx = group
+                            .plugins
+                            .plugins
+                            .iter()
+                            .position(|p| p.name.trim().eq_ignore_ascii_case(name.trim()))
+                            .with_context(|| {
+                                format!("Option '{}' not found in group '{}'", name, group.name)
+                            })?;
+                        picked.insert(idx);
+                    }
+                    picked
+                }
+                None => wizard.default_selection(group),
+            };
+            wizard.set_selection(step_idx, group_idx, selections);
+            wizard.refresh_flags(config);
+        }
+    }
+
+    let errors: Vec<String> = steps
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| wizard.is_step_visible(config, *i))
+        .flat_map(|(i, step)| {
+            validate_step(step, &wizard, i)
+                .into_iter()
+                .map(move |e| format!("{}: {}", step.name, e))
+        })
+        .collect();
+    if !errors.is_empty() {
+        bail!("FOMOD answers are incomplete:\n  {}", errors.join("\n  "));
+    }
+    Ok(wizard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mods::fomod::parse_module_config;
+
+    const CONFIG: &str = r#"
+        <config>
+            <moduleName>Lighting</moduleName>
+            <installSteps order="Explicit">
+                <installStep name="Main">
+                    <optionalFileGroups>
+                        <group name="Preset" type="SelectExactlyOne">
+                            <plugins>
+                                <plugin name="Bright">
+                                    <description>Bright</description>
+                                    <files><file source="bright.esp"/></files>
+                                </plugin>
+                                <plugin name="Dark">
+                                    <description>Dark</description>
+                                    <conditionFlags><flag name="dark">On</flag></conditionFlags>
+                                    <files><file source="dark.esp"/></files>
+                                </plugin>
+                            </plugins>
+                        </group>
+                    </optionalFileGroups>
+                </installStep>
+                <installStep name="Dark Options">
+                    <visible><flagDependency flag="dark" value="On"/></visible>
+                    <optionalFileGroups>
+                        <group name="Interiors" type="SelectAtLeastOne">
+                            <plugins>
+                                <plugin name="Caves">
+                                    <description>Caves</description>
+                                    <files><file source="caves.esp"/></files>
+                                </plugin>
+                            </plugins>
+                        </group>
+                    </optionalFileGroups>
+                </installStep>
+            </installSteps>
+        </config>
+    "#;
+
+    fn preset(selections: &[(&str, &str, &[&str])]) -> FomodPreset {
+        FomodPreset {
+            module_name: String::new(),
+            selections: selections
+                .iter()
+                .map(|(step, group, plugins)| PresetSelection {
+                    step: step.to_string(),
+                    group: group.to_string(),
+                    plugins: plugins.iter().map(|p| p.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_defaults_and_preset_answers() {
+        let config = parse_module_config(CONFIG).unwrap();
+
+        let defaults = answer_wizard(&config, FomodEnvironment::default(), None).unwrap();
+        assert_eq!(defaults.get_selections(0, 0), HashSet::from([0]));
+        assert!(!defaults.is_step_visible(&config, 1));
+
+        // Picking Dark reveals the next step, answered by name regardless of case
+        let answers = preset(&[
+            ("main", "preset", &["DARK"]),
+            ("Dark Options", "Interiors", &["Caves"]),
+        ]);
+        let wizard = answer_wizard(&config, FomodEnvironment::default(), Some(&answers)).unwrap();
+        assert_eq!(wizard.get_selections(0, 0), HashSet::from([1]));
+        assert_eq!(wizard.get_selections(1, 0), HashSet::from([0]));
+        assert_eq!(wizard.get_files_to_install(&config).len(), 2);
+    }
+
+    #[test]
+    fn test_preset_errors() {
+        let config = parse_module_config(CONFIG).unwrap();
+        let env = FomodEnvironment::default;
+
+        let unknown_option = preset(&[("Main", "Preset", &["Dim"])]);
+        assert!(answer_wizard(&config, env(), Some(&unknown_option)).is_err());
+
+        let unknown_group = preset(&[("Main", "Colours", &["Bright"])]);
+        assert!(answer_wizard(&config, env(), Some(&unknown_group)).is_err());
+
+        // Dark shows a step whose SelectAtLeastOne group has no default
+        let incomplete = preset(&[("Main", "Preset", &["Dark"])]);
+        let err = answer_wizard(&config, env(), Some(&incomplete)).unwrap_err();
+        assert!(err.to_string().contains("Interiors"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Preview of what an install plan will write
+//!
+//! Expands a plan's file operations the way the executor runs them: folders
+//! are copied recursively, sources missing from staging are skipped, and a
+//! later operation replaces a file an earlier one wrote. When reconfiguring,
+//! the result is compared with the mod's currently staged files.
+
+use super::planner::{FileOpType, InstallPlan};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// How a previewed file relates to the currently staged version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    /// Not staged yet (always the case for new installs)
+    Added,
+    /// Staged with different contents
+    Modified,
+    /// Staged with the same contents
+    Unchanged,
+}
+
+/// One file the plan will install
+#[derive(Debug, Clone)]
+pub struct PreviewFile {
+    /// Path inside the mod folder, '/' separated
+    pub destination: String,
+    /// Source file in staging that ends up at `destination`
+    pub source: String,
+    /// Sources of earlier operations this file replaces
+    pub overwrites: Vec<String>,
+    pub change: FileChange,
+}
+
+/// Files an install plan will write, sorted by destination
+#[derive(Debug, Clone, Default)]
+pub struct InstallPreview {
+    pub files: Vec<PreviewFile>,
+    /// Currently staged files the plan no longer installs
+    pub removed: Vec<String>,
+}
+
+impl InstallPreview {
+    /// Expand `plan` against `staging_path`. With `current`, files are
+    /// compared with the staged version in that folder.
+    pub fn from_plan(plan: &InstallPlan, staging_path: &Path, current: Option<&Path>) -> Self {
+        let mut files: BTreeMap<String, PreviewFile> = BTreeMap::new();
+        let mut write = |destination: String, source: String| {
+            let file = files.entry(destination.clone()).or_insert(PreviewFile {
+                destination,
+                source: String::new(),
+                overwrites: Vec::new(),
+                change: FileChange::Added,
+            });
+            if !file.source.is_empty() {
+                let previous = std::mem::replace(&mut file.source, source);
+                file.overwrites.push(previous);
+            } else {
+                file.source = source;
+            }
+        };
+
+        for op in &plan.file_operations {
+            let src = staging_path.join(&op.source);
+            match op.op_type {
+                FileOpType::CopyFile if src.is_file() => {
+                    write(slash_path(&op.destination), slash_path(&op.source));
+                }
+                FileOpType::CopyDir if src.is_dir() => {
+                    for entry in WalkDir::new(&src).into_iter().filter_map(|e| e.ok()) {
+                        if !entry.file_type().is_file() {
+                            continue;
+                        }
+                        let Ok(rel) = entry.path().strip_prefix(&src) else {
+                            continue;
+                        };
+                        write(
+                            slash_path(&op.destination.join(rel)),
+                            slash_path(&op.source.join(rel)),
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut removed = Vec::new();
+        if let Some(current) = current {
+            for file in files.values_mut() {
+                let staged = current.join(&file.destination);
+                file.change = if !staged.is_file() {
+                    FileChange::Added
+                } else if same_contents(&staging_path.join(&file.source), &staged) {
+                    FileChange::Unchanged
+                } else {
+                    FileChange::Modified
+                };
+            }
+
+            let installed: BTreeSet<&str> = files.keys().map(String::as_str).collect();
+            for entry in WalkDir::new(current).into_iter().filter_map(|e| e.ok()) {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                if let Ok(rel) = entry.path().strip_prefix(current) {
+                    let rel = slash_path(rel);
+                    if !installed.contains(rel.as_str()) {
+                        removed.push(rel);
+                    }
+                }
+            }
+            removed.sort();
+        }
+
+        Self {
+            files: files.into_values().collect(),
+            removed,
+        }
+    }
+
+    /// Number of files with the given change
+    pub fn count(&self, change: FileChange) -> usize {
+        self.files.iter().filter(|f| f.change == change).count()
+    }
+
+    /// Number of files that replace one written by an earlier option
+    pub fn overwrite_count(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| !f.overwrites.is_empty())
+            .count()
+    }
+}
+
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn same_contents(a: &Path, b: &Path) -> bool {
+    let (Ok(meta_a), Ok(meta_b)) = (a.metadata(), b.metadata()) else {
+        return false;
+    };
+    if meta_a.len() != meta_b.len() {
+        return false;
+    }
+    let (Ok(file_a), Ok(file_b)) = (File::open(a), File::open(b)) else {
+        return false;
+    };
+    let (mut reader_a, mut reader_b) = (BufReader::new(file_a), BufReader::new(file_b));
+    let (mut buf_a, mut buf_b) = ([0u8; 8192], [0u8; 8192]);
+    loop {
+        let Ok(n) = reader_a.read(&mut buf_a) else {
+            return false;
+        };
+        if n == 0 {
+            return true;
+        }
+        if reader_b.read_exact(&mut buf_b[..n]).is_err() || buf_a[..n] != buf_b[..n] {
+            return false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mods::fomod::planner::FileOperation;
+    use std::path::PathBuf;
+
+    fn op(op_type: FileOpType, source: &str, destination: &str) -> FileOperation {
+        FileOperation {
+            op_type,
+            source: PathBuf::from(source),
+            destination: PathBuf::from(destination),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_preview_expands_folders_and_diffs_staged_files() {
+        let staging = tempfile::tempdir().unwrap();
+        let write = |path: &str, data: &str| {
+            let path = staging.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, data).unwrap();
+        };
+        write("core/meshes/a.nif", "a");
+        write("core/lux.esp", "core");
+        write("dark/lux.esp", "dark");
+
+        let current = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(current.path().join("meshes")).unwrap();
+        std::fs::write(current.path().join("meshes/a.nif"), "a").unwrap();
+        std::fs::write(current.path().join("lux.esp"), "core").unwrap();
+        std::fs::write(current.path().join("old.esp"), "old").unwrap();
+
+        let plan = InstallPlan {
+            mod_name: "Lux".to_string(),
+            profile_id: None,
+            timestamp: String::new(),
+            config_hash: String::new(),
+            selected_options: Vec::new(),
+            flags_set: Default::default(),
+            file_operations: vec![
+                op(FileOpType::CopyDir, "core", ""),
+                op(FileOpType::CopyFile, "dark/lux.esp", "lux.esp"),
+                op(FileOpType::CopyFile, "missing.esp", "missing.esp"),
+            ],
+            estimated_file_count: 0,
+            estimated_size_bytes: 0,
+            conflicts: Vec::new(),
+        };
+
+        let preview = InstallPreview::from_plan(&plan, staging.path(), Some(current.path()));
+        let dests: Vec<&str> = preview
+            .files
+            .iter()
+            .map(|f| f.destination.as_str())
+            .collect();
+        assert_eq!(dests, vec!["lux.esp", "meshes/a.nif"]);
+
+        let esp = &preview.files[0];
+        assert_eq!(esp.source, "dark/lux.esp");
+        assert_eq!(esp.overwrites, vec!["core/lux.esp"]);
+        assert_eq!(esp.change, FileChange::Modified);
+        assert_eq!(preview.files[1].change, FileChange::Unchanged);
+        assert_eq!(preview.removed, vec!["old.esp"]);
+    }
+}
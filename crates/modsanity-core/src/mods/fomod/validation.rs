@@ -1,6 +1,7 @@
 //! FOMOD group validation logic
 
-use super::OptionGroup;
+use super::wizard::WizardState;
+use super::{InstallStep, OptionGroup};
 use std::collections::HashSet;
 
 /// Validate a group's selections against its constraints
@@ -58,34 +59,21 @@ pub fn validate_group(
     Ok(())
 }
 
-/// Check if the wizard can proceed to the next step
-pub fn can_proceed_to_next_step(wizard_state: &FomodWizardState) -> bool {
-    wizard_state.validation_errors.is_empty()
-}
-
-/// Validate all groups in the current step
-pub fn validate_current_step(wizard_state: &FomodWizardState) -> Vec<String> {
+/// Validate all groups in an install step against the wizard's selections
+pub fn validate_step(step: &InstallStep, wizard: &WizardState, step_idx: usize) -> Vec<String> {
     let mut errors = Vec::new();
 
-    if let Some(step) = wizard_state.current_install_step() {
-        for (group_idx, group) in step.groups.groups.iter().enumerate() {
-            let selections = wizard_state
-                .wizard
-                .get_selections(wizard_state.current_step, group_idx);
+    for (group_idx, group) in step.groups.groups.iter().enumerate() {
+        let selections = wizard.get_selections(step_idx, group_idx);
 
-            if let Err(e) = validate_group(group, &selections, wizard_state.current_step, group_idx)
-            {
-                errors.push(e);
-            }
+        if let Err(e) = validate_group(group, &selections, step_idx, group_idx) {
+            errors.push(e);
         }
     }
 
     errors
 }
 
-// Import FomodWizardState for validation
-use crate::app::state::FomodWizardState;
-
 #[cfg(test)]
 mod tests {
     use super::*;
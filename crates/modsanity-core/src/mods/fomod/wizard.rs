@@ -1,6 +1,6 @@
 //! FOMOD installation wizard logic
 
-use super::{ConditionEvaluator, ModuleConfig, Plugin};
+use super::{ConditionEvaluator, FomodEnvironment, ModuleConfig, OptionGroup, Plugin};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
@@ -18,6 +18,12 @@ pub struct WizardState {
     pub evaluator: ConditionEvaluator,
 }
 
+impl Default for WizardState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WizardState {
     pub fn new() -> Self {
         Self {
@@ -39,6 +45,120 @@ impl WizardState {
         }
     }
 
+    /// Create wizard state that checks conditions against an install environment
+    pub fn with_environment(env: FomodEnvironment) -> Self {
+        Self {
+            current_step: 0,
+            selections: HashMap::new(),
+            evaluator: ConditionEvaluator::with_environment(env),
+        }
+    }
+
+    /// Whether a step's `visible` conditions hold for the current flags
+    pub fn is_step_visible(&self, config: &ModuleConfig, step: usize) -> bool {
+        config
+            .install_steps
+            .steps
+            .get(step)
+            .map(|s| self.evaluator.evaluate_dependencies(&s.visible))
+            .unwrap_or(false)
+    }
+
+    /// First visible step after `step`
+    pub fn next_visible_step(&self, config: &ModuleConfig, step: usize) -> Option<usize> {
+        (step + 1..config.install_steps.steps.len()).find(|&i| self.is_step_visible(config, i))
+    }
+
+    /// Last visible step before `step`
+    pub fn prev_visible_step(&self, config: &ModuleConfig, step: usize) -> Option<usize> {
+        (0..step.min(config.install_steps.steps.len()))
+            .rev()
+            .find(|&i| self.is_step_visible(config, i))
+    }
+
+    /// Rebuild condition flags from the current selections.
+    ///
+    /// Flags are applied in step order, skipping steps hidden by earlier
+    /// choices, so deselecting a plugin also drops the flags it had set.
+    pub fn refresh_flags(&mut self, config: &ModuleConfig) {
+        self.evaluator.clear_flags();
+        for (step_idx, step) in config.install_steps.steps.iter().enumerate() {
+            if !self.is_step_visible(config, step_idx) {
+                continue;
+            }
+            for (group_idx, group) in step.groups.groups.iter().enumerate() {
+                let mut selected: Vec<usize> = self
+                    .get_selections(step_idx, group_idx)
+                    .into_iter()
+                    .collect();
+                selected.sort_unstable();
+                for plugin_idx in selected {
+                    if let Some(plugin) = group.plugins.plugins.get(plugin_idx) {
+                        self.apply_flags(plugin);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Default choices for a group (required and recommended options, the
+    /// first option of a SelectExactlyOne group, everything for SelectAll),
+    /// setting their condition flags
+    pub fn default_selection(&mut self, group: &OptionGroup) -> HashSet<usize> {
+        let mut selections = HashSet::new();
+
+        for (plugin_idx, plugin) in group.plugins.plugins.iter().enumerate() {
+            // Check if plugin is visible based on conditions
+            if !self.evaluator.is_plugin_visible(plugin) {
+                continue;
+            }
+
+            // Get plugin type to determine if it should be selected by default
+            let plugin_type = self.evaluator.get_plugin_type(plugin);
+            let should_select = matches!(
+                plugin_type,
+                super::PluginType::Required | super::PluginType::Recommended
+            );
+
+            if should_select {
+                selections.insert(plugin_idx);
+                self.apply_flags(plugin);
+            }
+        }
+
+        // For SelectExactlyOne, ensure at least first visible option is selected if none recommended
+        if group.group_type == "SelectExactlyOne" && selections.is_empty() {
+            for (plugin_idx, plugin) in group.plugins.plugins.iter().enumerate() {
+                if self.evaluator.is_plugin_visible(plugin) {
+                    selections.insert(plugin_idx);
+                    self.apply_flags(plugin);
+                    break;
+                }
+            }
+        }
+
+        // For SelectAll, select all visible options
+        if group.group_type == "SelectAll" {
+            for (plugin_idx, plugin) in group.plugins.plugins.iter().enumerate() {
+                if self.evaluator.is_plugin_visible(plugin) {
+                    selections.insert(plugin_idx);
+                    self.apply_flags(plugin);
+                }
+            }
+        }
+
+        selections
+    }
+
+    fn apply_flags(&mut self, plugin: &Plugin) {
+        if let Some(cflags) = &plugin.condition_flags {
+            for flag in &cflags.flags {
+                self.evaluator
+                    .set_flag(flag.name.clone(), flag.value.clone());
+            }
+        }
+    }
+
     /// Get selections for a group
     pub fn get_selections(&self, step: usize, group: usize) -> HashSet<usize> {
         self.selections
@@ -102,12 +222,7 @@ impl WizardState {
         let is_now_selected = selections.contains(&plugin_idx);
         if is_now_selected && !was_selected {
             // Set flags for newly selected plugin
-            if let Some(cflags) = &plugin.condition_flags {
-                for flag in &cflags.flags {
-                    self.evaluator
-                        .set_flag(flag.name.clone(), flag.value.clone());
-                }
-            }
+            self.apply_flags(plugin);
         }
     }
 
@@ -146,8 +261,11 @@ impl WizardState {
             }
         }
 
-        // Selected optional files
+        // Selected optional files, ignoring choices left on hidden steps
         for (step_idx, step) in config.install_steps.steps.iter().enumerate() {
+            if !self.is_step_visible(config, step_idx) {
+                continue;
+            }
             for (group_idx, group) in step.groups.groups.iter().enumerate() {
                 let selections = self.get_selections(step_idx, group_idx);
 
@@ -293,78 +411,126 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> anyhow::Result<Vec<PathBuf>> {
 
 /// Initialize wizard with default selections
 pub fn init_wizard_state(config: &ModuleConfig) -> WizardState {
-    let mut state = WizardState::new();
+    init_wizard_state_with_env(config, FomodEnvironment::default())
+}
+
+/// Initialize wizard with default selections, evaluating file and version
+/// conditions against `env`
+pub fn init_wizard_state_with_env(config: &ModuleConfig, env: FomodEnvironment) -> WizardState {
+    let mut state = WizardState::with_environment(env);
 
     for (step_idx, step) in config.install_steps.steps.iter().enumerate() {
+        // Visibility depends on flags set by the defaults of earlier steps
+        if !state.is_step_visible(config, step_idx) {
+            continue;
+        }
         for (group_idx, group) in step.groups.groups.iter().enumerate() {
-            let mut selections = HashSet::new();
-
-            for (plugin_idx, plugin) in group.plugins.plugins.iter().enumerate() {
-                // Check if plugin is visible based on conditions
-                if !state.evaluator.is_plugin_visible(plugin) {
-                    continue;
-                }
-
-                // Get plugin type to determine if it should be selected by default
-                let plugin_type = state.evaluator.get_plugin_type(plugin);
-                let should_select = matches!(
-                    plugin_type,
-                    super::PluginType::Required | super::PluginType::Recommended
-                );
-
-                if should_select {
-                    selections.insert(plugin_idx);
-                    // Set flags for selected plugin
-                    if let Some(cflags) = &plugin.condition_flags {
-                        for flag in &cflags.flags {
-                            state
-                                .evaluator
-                                .set_flag(flag.name.clone(), flag.value.clone());
-                        }
-                    }
-                }
-            }
-
-            // For SelectExactlyOne, ensure at least first visible option is selected if none recommended
-            if group.group_type == "SelectExactlyOne" && selections.is_empty() {
-                for (plugin_idx, plugin) in group.plugins.plugins.iter().enumerate() {
-                    if state.evaluator.is_plugin_visible(plugin) {
-                        selections.insert(plugin_idx);
-                        // Set flags for selected plugin
-                        if let Some(cflags) = &plugin.condition_flags {
-                            for flag in &cflags.flags {
-                                state
-                                    .evaluator
-                                    .set_flag(flag.name.clone(), flag.value.clone());
-                            }
-                        }
-                        break;
-                    }
-                }
-            }
-
-            // For SelectAll, select all visible options
-            if group.group_type == "SelectAll" {
-                for (plugin_idx, plugin) in group.plugins.plugins.iter().enumerate() {
-                    if state.evaluator.is_plugin_visible(plugin) {
-                        selections.insert(plugin_idx);
-                        // Set flags for selected plugin
-                        if let Some(cflags) = &plugin.condition_flags {
-                            for flag in &cflags.flags {
-                                state
-                                    .evaluator
-                                    .set_flag(flag.name.clone(), flag.value.clone());
-                            }
-                        }
-                    }
-                }
-            }
-
+            let selections = state.default_selection(group);
             if !selections.is_empty() {
                 state.set_selection(step_idx, group_idx, selections);
             }
         }
     }
 
+    // Start on the first step the installer actually shows
+    state.current_step = (0..config.install_steps.steps.len())
+        .find(|&i| state.is_step_visible(config, i))
+        .unwrap_or(0);
     state
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mods::fomod::{parse_module_config, FileState};
+
+    const CONFIG: &str = r#"
+        <config>
+            <moduleName>Weather Hub</moduleName>
+            <installSteps order="Explicit">
+                <installStep name="Base">
+                    <optionalFileGroups>
+                        <group name="Patches" type="SelectAny">
+                            <plugins>
+                                <plugin name="Patch">
+                                    <description>Patch</description>
+                                    <conditionFlags><flag name="patch">On</flag></conditionFlags>
+                                </plugin>
+                            </plugins>
+                        </group>
+                    </optionalFileGroups>
+                </installStep>
+                <installStep name="Patch Options">
+                    <visible>
+                        <flagDependency flag="patch" value="On"/>
+                    </visible>
+                    <optionalFileGroups>
+                        <group name="Style" type="SelectAny">
+                            <plugins>
+                                <plugin name="Extra">
+                                    <description>Extra</description>
+                                    <files><file source="extra.esp"/></files>
+                                </plugin>
+                            </plugins>
+                        </group>
+                    </optionalFileGroups>
+                </installStep>
+            </installSteps>
+            <conditionalFileInstalls>
+                <patterns>
+                    <pattern>
+                        <dependencies operator="Or">
+                            <flagDependency flag="patch" value="On"/>
+                            <fileDependency file="Vivid.esp" state="Active"/>
+                        </dependencies>
+                        <files><file source="patch.esp"/></files>
+                    </pattern>
+                </patterns>
+            </conditionalFileInstalls>
+        </config>
+    "#;
+
+    fn sources(wizard: &WizardState, config: &ModuleConfig) -> Vec<String> {
+        wizard
+            .get_files_to_install(config)
+            .into_iter()
+            .map(|i| match i {
+                FileInstruction::File { source, .. } | FileInstruction::Folder { source, .. } => {
+                    source
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_flags_drive_step_visibility_and_patterns() {
+        let config = parse_module_config(CONFIG).unwrap();
+        let mut wizard = init_wizard_state(&config);
+        assert!(!wizard.is_step_visible(&config, 1));
+        assert_eq!(wizard.next_visible_step(&config, 0), None);
+
+        let plugin = &config.install_steps.steps[0].groups.groups[0]
+            .plugins
+            .plugins[0];
+        wizard.toggle_selection(0, 0, 0, "SelectAny", plugin);
+        wizard.set_selection(1, 0, HashSet::from([0]));
+        wizard.refresh_flags(&config);
+        assert_eq!(wizard.next_visible_step(&config, 0), Some(1));
+        assert_eq!(sources(&wizard, &config), vec!["extra.esp", "patch.esp"]);
+
+        // Deselecting drops the flag, hiding the step and its choices again
+        wizard.toggle_selection(0, 0, 0, "SelectAny", plugin);
+        wizard.refresh_flags(&config);
+        assert!(wizard.evaluator.flags().is_empty());
+        assert!(sources(&wizard, &config).is_empty());
+    }
+
+    #[test]
+    fn test_file_dependency_uses_environment() {
+        let config = parse_module_config(CONFIG).unwrap();
+        let mut env = FomodEnvironment::default();
+        env.add_file("Vivid.esp", FileState::Active);
+        let wizard = init_wizard_state_with_env(&config, env);
+        assert_eq!(sources(&wizard, &config), vec!["patch.esp"]);
+    }
+}
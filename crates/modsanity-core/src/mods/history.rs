@@ -0,0 +1,577 @@
+//! Deploy/play history, usage stats and the load order changelog
+//!
+//! Every deploy and game launch records a snapshot of the installed mods and
+//! the enabled plugin order. Comparing the snapshot taken when the game was
+//! last played with the current setup gives a "what changed since I last
+//! played" report: mods added, updated or removed and plugins that moved.
+
+use crate::activity::{self, Activity};
+use crate::db::SessionRecord;
+use crate::games::Game;
+use crate::plugins;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// What a recorded session was
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionKind {
+    Deploy,
+    Play,
+}
+
+impl SessionKind {
+    /// Stable ID stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionKind::Deploy => "deploy",
+            SessionKind::Play => "play",
+        }
+    }
+}
+
+/// Installed mods and enabled plugin order at one point in time
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetupSnapshot {
+    pub mods: Vec<SnapshotMod>,
+    /// Enabled plugins in load order
+    pub plugins: Vec<String>,
+    /// Deploy exclusion patterns, global ones as-is and per-mod ones as
+    /// `mod: pattern`
+    #[serde(default)]
+    pub exclusions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotMod {
+    pub name: String,
+    pub version: String,
+    pub enabled: bool,
+    /// Conflict priority; older sessions don't have it
+    #[serde(default)]
+    pub priority: i32,
+    /// Changes when the mod's files are reinstalled or updated
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionChange {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// A plugin whose position changed relative to the others (1-based positions)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginMove {
+    pub name: String,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Differences between two setup snapshots
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetupChanges {
+    pub mods_added: Vec<String>,
+    pub mods_removed: Vec<String>,
+    pub mods_updated: Vec<VersionChange>,
+    pub mods_enabled: Vec<String>,
+    pub mods_disabled: Vec<String>,
+    pub plugins_added: Vec<String>,
+    pub plugins_removed: Vec<String>,
+    pub plugins_moved: Vec<PluginMove>,
+}
+
+impl SetupChanges {
+    pub fn is_empty(&self) -> bool {
+        self.mods_added.is_empty()
+            && self.mods_removed.is_empty()
+            && self.mods_updated.is_empty()
+            && self.mods_enabled.is_empty()
+            && self.mods_disabled.is_empty()
+            && self.plugins_added.is_empty()
+            && self.plugins_removed.is_empty()
+            && self.plugins_moved.is_empty()
+    }
+
+    /// One-line count of each kind of change, e.g. "2 mods added, 1 updated"
+    pub fn summary(&self) -> String {
+        let counts = [
+            (self.mods_added.len(), "mods added"),
+            (self.mods_updated.len(), "updated"),
+            (self.mods_removed.len(), "removed"),
+            (self.mods_enabled.len(), "enabled"),
+            (self.mods_disabled.len(), "disabled"),
+            (self.plugins_added.len(), "plugins added"),
+            (self.plugins_removed.len(), "plugins removed"),
+            (self.plugins_moved.len(), "plugins moved"),
+        ];
+        let parts: Vec<String> = counts
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, label)| format!("{} {}", n, label))
+            .collect();
+        if parts.is_empty() {
+            "no changes".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// One line per change, marked + added, - removed, ^ updated, ~ moved
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        lines.extend(self.mods_added.iter().map(|n| format!("+ {}", n)));
+        lines.extend(
+            self.mods_updated
+                .iter()
+                .map(|c| format!("^ {} {} -> {}", c.name, c.from, c.to)),
+        );
+        lines.extend(self.mods_removed.iter().map(|n| format!("- {}", n)));
+        lines.extend(
+            self.mods_enabled
+                .iter()
+                .map(|n| format!("+ {} (enabled)", n)),
+        );
+        lines.extend(
+            self.mods_disabled
+                .iter()
+                .map(|n| format!("- {} (disabled)", n)),
+        );
+        lines.extend(self.plugins_added.iter().map(|n| format!("+ {}", n)));
+        lines.extend(self.plugins_removed.iter().map(|n| format!("- {}", n)));
+        lines.extend(
+            self.plugins_moved
+                .iter()
+                .map(|m| format!("~ {} #{} -> #{}", m.name, m.from, m.to)),
+        );
+        lines
+    }
+}
+
+/// Compare two snapshots
+pub fn diff_snapshots(old: &SetupSnapshot, new: &SetupSnapshot) -> SetupChanges {
+    let mut changes = SetupChanges::default();
+
+    let old_mods: HashMap<&str, &SnapshotMod> =
+        old.mods.iter().map(|m| (m.name.as_str(), m)).collect();
+    let new_names: HashSet<&str> = new.mods.iter().map(|m| m.name.as_str()).collect();
+    for m in &new.mods {
+        match old_mods.get(m.name.as_str()) {
+            None => changes.mods_added.push(m.name.clone()),
+            Some(prev) => {
+                if prev.version != m.version {
+                    changes.mods_updated.push(VersionChange {
+                        name: m.name.clone(),
+                        from: prev.version.clone(),
+                        to: m.version.clone(),
+                    });
+                }
+                match (prev.enabled, m.enabled) {
+                    (false, true) => changes.mods_enabled.push(m.name.clone()),
+                    (true, false) => changes.mods_disabled.push(m.name.clone()),
+                    _ => {}
+                }
+            }
+        }
+    }
+    changes.mods_removed = old
+        .mods
+        .iter()
+        .filter(|m| !new_names.contains(m.name.as_str()))
+        .map(|m| m.name.clone())
+        .collect();
+
+    let old_plugins: HashSet<String> = old.plugins.iter().map(|p| p.to_lowercase()).collect();
+    let new_plugins: HashSet<String> = new.plugins.iter().map(|p| p.to_lowercase()).collect();
+    changes.plugins_added = new
+        .plugins
+        .iter()
+        .filter(|p| !old_plugins.contains(&p.to_lowercase()))
+        .cloned()
+        .collect();
+    changes.plugins_removed = old
+        .plugins
+        .iter()
+        .filter(|p| !new_plugins.contains(&p.to_lowercase()))
+        .cloned()
+        .collect();
+    changes.plugins_moved = moved_plugins(&old.plugins, &new.plugins, &old_plugins, &new_plugins);
+
+    changes
+}
+
+/// Whether deploying `new` would put different files in Data than deploying
+/// `old` did: besides the changes [`diff_snapshots`] reports, the priority
+/// and files of enabled mods and the exclusions decide the conflict winners
+pub fn deploys_differently(old: &SetupSnapshot, new: &SetupSnapshot) -> bool {
+    fn deployed(snapshot: &SetupSnapshot) -> BTreeMap<&str, (i32, &str)> {
+        snapshot
+            .mods
+            .iter()
+            .filter(|m| m.enabled)
+            .map(|m| (m.name.as_str(), (m.priority, m.updated_at.as_str())))
+            .collect()
+    }
+    !diff_snapshots(old, new).is_empty()
+        || deployed(old) != deployed(new)
+        || old.exclusions != new.exclusions
+}
+
+/// Plugins present in both orders that aren't part of their longest common
+/// subsequence, so inserting one plugin doesn't report everything after it
+fn moved_plugins(
+    old: &[String],
+    new: &[String],
+    old_set: &HashSet<String>,
+    new_set: &HashSet<String>,
+) -> Vec<PluginMove> {
+    let a: Vec<(usize, String)> = old
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, p.to_lowercase()))
+        .filter(|(_, p)| new_set.contains(p))
+        .collect();
+    let b: Vec<(usize, String)> = new
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, p.to_lowercase()))
+        .filter(|(_, p)| old_set.contains(p))
+        .collect();
+
+    // lcs[i][j] = LCS length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i].1 == b[j].1 {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut stable = HashSet::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i].1 == b[j].1 {
+            stable.insert(a[i].1.clone());
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    let old_pos: HashMap<&str, usize> = a.iter().map(|(i, p)| (p.as_str(), *i)).collect();
+    b.iter()
+        .filter(|(_, p)| !stable.contains(p))
+        .map(|(to, p)| PluginMove {
+            name: new[*to].clone(),
+            from: old_pos[p.as_str()] + 1,
+            to: to + 1,
+        })
+        .collect()
+}
+
+/// The last play session and what changed since
+#[derive(Debug, Clone)]
+pub struct SinceLastPlayed {
+    pub played_at: DateTime<Utc>,
+    pub profile: Option<String>,
+    pub changes: SetupChanges,
+}
+
+impl SinceLastPlayed {
+    /// Whether the last session is at least `hours` old
+    pub fn is_after_gap(&self, hours: u64, now: DateTime<Utc>) -> bool {
+        now - self.played_at >= Duration::hours(hours as i64)
+    }
+}
+
+/// How often one profile has been played
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileUsage {
+    pub profile: String,
+    pub plays: usize,
+    pub last_played: DateTime<Utc>,
+}
+
+/// Deploy and play counts for a game
+#[derive(Debug, Clone, Default)]
+pub struct UsageStats {
+    pub deploys: usize,
+    pub plays: usize,
+    pub plays_last_30_days: usize,
+    pub last_deploy: Option<DateTime<Utc>>,
+    pub last_play: Option<DateTime<Utc>>,
+    /// Most played first
+    pub profiles: Vec<ProfileUsage>,
+}
+
+/// Summarize recorded sessions
+pub fn usage_stats(sessions: &[SessionRecord], now: DateTime<Utc>) -> UsageStats {
+    let mut stats = UsageStats::default();
+    let mut profiles: BTreeMap<String, ProfileUsage> = BTreeMap::new();
+
+    for session in sessions {
+        let Some(at) = parse_time(&session.started_at) else {
+            continue;
+        };
+        if session.kind == SessionKind::Deploy.as_str() {
+            stats.deploys += 1;
+            stats.last_deploy = stats.last_deploy.max(Some(at));
+            continue;
+        }
+        if session.kind != SessionKind::Play.as_str() {
+            continue;
+        }
+        stats.plays += 1;
+        stats.last_play = stats.last_play.max(Some(at));
+        if now - at <= Duration::days(30) {
+            stats.plays_last_30_days += 1;
+        }
+        let name = session
+            .profile
+            .clone()
+            .unwrap_or_else(|| "(no profile)".to_string());
+        let entry = profiles.entry(name.clone()).or_insert(ProfileUsage {
+            profile: name,
+            plays: 0,
+            last_played: at,
+        });
+        entry.plays += 1;
+        entry.last_played = entry.last_played.max(at);
+    }
+
+    stats.profiles = profiles.into_values().collect();
+    stats.profiles.sort_by_key(|p| std::cmp::Reverse(p.plays));
+    stats
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+impl super::ModManager {
+    /// Snapshot the installed mods, enabled plugin order and exclusions
+    pub async fn setup_snapshot(&self, game: &Game) -> Result<SetupSnapshot> {
+        let mods = self
+            .db
+            .get_mods_for_game(&game.id)?
+            .into_iter()
+            .map(|m| SnapshotMod {
+                name: m.name,
+                version: m.version,
+                enabled: m.enabled,
+                priority: m.priority,
+                updated_at: m.updated_at,
+            })
+            .collect();
+        let plugins = plugins::get_plugins(game)?
+            .into_iter()
+            .filter(|p| p.enabled)
+            .map(|p| p.filename)
+            .collect();
+        let mut exclusions = self.config.read().await.deployment.exclude.clone();
+        exclusions.extend(
+            self.db
+                .get_mod_exclusions(&game.id)?
+                .into_iter()
+                .map(|(mod_name, pattern)| format!("{}: {}", mod_name, pattern)),
+        );
+        exclusions.sort();
+        Ok(SetupSnapshot {
+            mods,
+            plugins,
+            exclusions,
+        })
+    }
+
+    /// Record a deploy or play session with the current setup
+    pub async fn record_session(&self, game: &Game, kind: SessionKind) -> Result<()> {
+        let profile = self.config.read().await.active_profile.clone();
+        let snapshot = self.setup_snapshot(game).await?;
+        self.db.insert_session(&SessionRecord {
+            id: None,
+            game_id: game.id.clone(),
+            profile,
+            kind: kind.as_str().to_string(),
+            started_at: Utc::now().to_rfc3339(),
+            snapshot: serde_json::to_string(&snapshot)?,
+        })?;
+        if kind == SessionKind::Play {
+            self.record_activity(&game.id, Activity::Launch, None, serde_json::Value::Null)
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Add an action to the activity log under the active profile
+    pub(crate) async fn record_activity(
+        &self,
+        game_id: &str,
+        activity: Activity,
+        target: Option<&str>,
+        details: serde_json::Value,
+    ) {
+        let profile = self.config.read().await.active_profile.clone();
+        activity::record(
+            &self.db,
+            game_id,
+            profile.as_deref(),
+            activity,
+            target,
+            details,
+        );
+    }
+
+    /// What changed since the game was last played, or `None` if it never was
+    pub async fn since_last_played(&self, game: &Game) -> Result<Option<SinceLastPlayed>> {
+        let Some(session) = self.db.last_session(&game.id, SessionKind::Play.as_str())? else {
+            return Ok(None);
+        };
+        let Some(played_at) = parse_time(&session.started_at) else {
+            return Ok(None);
+        };
+        let then: SetupSnapshot = serde_json::from_str(&session.snapshot)?;
+        let now = self.setup_snapshot(game).await?;
+        Ok(Some(SinceLastPlayed {
+            played_at,
+            profile: session.profile,
+            changes: diff_snapshots(&then, &now),
+        }))
+    }
+
+    /// Whether installed mods, their priority or files, the exclusions or
+    /// enabled plugins changed since the last deploy. A game that was never
+    /// deployed counts as outdated.
+    pub async fn deployment_outdated(&self, game: &Game) -> Result<bool> {
+        let Some(session) = self
+            .db
+            .last_session(&game.id, SessionKind::Deploy.as_str())?
+        else {
+            return Ok(true);
+        };
+        let then: SetupSnapshot = serde_json::from_str(&session.snapshot)?;
+        let now = self.setup_snapshot(game).await?;
+        Ok(deploys_differently(&then, &now))
+    }
+
+    /// Deploy and play counts for a game
+    pub fn usage_stats(&self, game_id: &str) -> Result<UsageStats> {
+        let sessions = self.db.get_sessions(game_id)?;
+        Ok(usage_stats(&sessions, Utc::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(mods: &[(&str, &str, bool)], plugins: &[&str]) -> SetupSnapshot {
+        SetupSnapshot {
+            mods: mods
+                .iter()
+                .map(|(name, version, enabled)| SnapshotMod {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    enabled: *enabled,
+                    priority: 0,
+                    updated_at: String::new(),
+                })
+                .collect(),
+            plugins: plugins.iter().map(|p| p.to_string()).collect(),
+            exclusions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_mods() {
+        let old = snapshot(
+            &[
+                ("SkyUI", "5.2", true),
+                ("Old", "1.0", true),
+                ("Off", "1.0", false),
+            ],
+            &[],
+        );
+        let new = snapshot(
+            &[
+                ("SkyUI", "5.3", true),
+                ("Off", "1.0", true),
+                ("New", "2.0", true),
+            ],
+            &[],
+        );
+        let changes = diff_snapshots(&old, &new);
+        assert_eq!(changes.mods_added, vec!["New"]);
+        assert_eq!(changes.mods_removed, vec!["Old"]);
+        assert_eq!(changes.mods_enabled, vec!["Off"]);
+        assert_eq!(
+            changes.mods_updated,
+            vec![VersionChange {
+                name: "SkyUI".to_string(),
+                from: "5.2".to_string(),
+                to: "5.3".to_string(),
+            }]
+        );
+        assert_eq!(
+            changes.summary(),
+            "1 mods added, 1 updated, 1 removed, 1 enabled"
+        );
+    }
+
+    #[test]
+    fn test_diff_plugin_order() {
+        let old = snapshot(&[], &["A.esm", "B.esp", "C.esp", "D.esp", "E.esp"]);
+        // New.esp inserted and E moved up; B..D shift but didn't move
+        let new = snapshot(
+            &[],
+            &["A.esm", "new.esp", "e.esp", "B.esp", "C.esp", "D.esp"],
+        );
+        let changes = diff_snapshots(&old, &new);
+        assert_eq!(changes.plugins_added, vec!["new.esp"]);
+        assert!(changes.plugins_removed.is_empty());
+        assert_eq!(
+            changes.plugins_moved,
+            vec![PluginMove {
+                name: "e.esp".to_string(),
+                from: 5,
+                to: 3,
+            }]
+        );
+        assert!(diff_snapshots(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_usage_stats() {
+        let now = Utc::now();
+        let session = |kind: SessionKind, profile: Option<&str>, days: i64| SessionRecord {
+            id: None,
+            game_id: "skyrimse".to_string(),
+            profile: profile.map(str::to_string),
+            kind: kind.as_str().to_string(),
+            started_at: (now - Duration::days(days)).to_rfc3339(),
+            snapshot: "{}".to_string(),
+        };
+        let sessions = vec![
+            session(SessionKind::Play, Some("Survival"), 1),
+            session(SessionKind::Deploy, Some("Survival"), 1),
+            session(SessionKind::Play, Some("Survival"), 40),
+            session(SessionKind::Play, Some("Vanilla+"), 2),
+        ];
+        let stats = usage_stats(&sessions, now);
+        assert_eq!(stats.deploys, 1);
+        assert_eq!(stats.plays, 3);
+        assert_eq!(stats.plays_last_30_days, 2);
+        assert_eq!(stats.profiles[0].profile, "Survival");
+        assert_eq!(stats.profiles[0].plays, 2);
+        assert_eq!(stats.profiles[1].profile, "Vanilla+");
+    }
+}
@@ -0,0 +1,356 @@
+//! Content index of staged mod files
+//!
+//! Installs and rescans record every staged file's path, size and content
+//! hash in `mod_files`. File lookups ("which mod provides this file") and
+//! conflict detection read the index instead of walking staging folders, and
+//! verification compares staging folders against it.
+
+use crate::db::{FileProvider, ModFileRecord};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Files under `root` as relative paths with their sizes, without hashing
+pub(super) fn list_files(root: &Path) -> Result<Vec<(String, i64)>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if let Ok(relative) = entry.path().strip_prefix(root) {
+            let size = entry.metadata().map(|m| m.len() as i64).unwrap_or(0);
+            files.push((relative.to_string_lossy().to_string(), size));
+        }
+    }
+    Ok(files)
+}
+
+/// Index records for listed files, hashing each one on a blocking thread.
+/// Files that can't be read are recorded without a hash.
+pub(super) async fn index_files(
+    root: &Path,
+    files: &[(String, i64)],
+    mod_id: i64,
+) -> Result<Vec<ModFileRecord>> {
+    let root = root.to_path_buf();
+    let files = files.to_vec();
+    tokio::task::spawn_blocking(move || hash_records(&root, &files, mod_id))
+        .await
+        .context("File indexing task failed")
+}
+
+fn hash_records(root: &Path, files: &[(String, i64)], mod_id: i64) -> Vec<ModFileRecord> {
+    files
+        .iter()
+        .map(|(path, size)| ModFileRecord {
+            id: None,
+            mod_id,
+            relative_path: path.clone(),
+            hash: match hash_file(&root.join(path)) {
+                Ok(hash) => Some(hash),
+                Err(e) => {
+                    tracing::warn!("Failed to hash {}: {}", path, e);
+                    None
+                }
+            },
+            size: Some(*size),
+        })
+        .collect()
+}
+
+/// 64-bit FNV-1a hash of a file's contents as hex. Stable across builds, so
+/// stored hashes stay comparable.
+pub fn hash_file(path: &Path) -> Result<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        for byte in &buf[..n] {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    Ok(format!("{:016x}", hash))
+}
+
+/// Normalize a path typed by the user: '/' separators, no leading `Data/`
+pub fn normalize_lookup_path(path: &str) -> String {
+    let path = path.trim().replace('\\', "/");
+    let path = path.trim_start_matches("./").trim_start_matches('/');
+    match path.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("data/") => path[5..].to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// Mods providing one file, highest priority (the one deployed) first
+#[derive(Debug, Clone)]
+pub struct FileLookup {
+    pub path: String,
+    pub providers: Vec<FileProvider>,
+}
+
+impl FileLookup {
+    /// Enabled provider whose copy is deployed
+    pub fn winner(&self) -> Option<&FileProvider> {
+        self.providers.iter().find(|p| p.enabled)
+    }
+
+    /// Whether every provider with a known hash has the same contents
+    pub fn identical(&self) -> bool {
+        let mut hashes = self.providers.iter().filter_map(|p| p.hash.as_deref());
+        match hashes.next() {
+            Some(first) => hashes.all(|h| h == first),
+            None => false,
+        }
+    }
+}
+
+/// How a staged file differs from the index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileDrift {
+    /// Indexed but no longer in the staging folder
+    Missing,
+    /// Size differs from the indexed size
+    SizeChanged,
+    /// Same size but different contents (full checks only)
+    ContentChanged,
+    /// In the staging folder but not indexed (full checks only)
+    Unindexed,
+}
+
+impl FileDrift {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::SizeChanged => "size changed",
+            Self::ContentChanged => "modified",
+            Self::Unindexed => "not indexed",
+        }
+    }
+}
+
+/// A staged file that doesn't match the index
+#[derive(Debug, Clone, Serialize)]
+pub struct FileMismatch {
+    pub path: String,
+    pub drift: FileDrift,
+}
+
+/// Result of checking one mod's staging folder against its index
+#[derive(Debug, Clone, Serialize)]
+pub struct ModVerification {
+    pub mod_name: String,
+    /// Indexed files checked
+    pub files: usize,
+    pub mismatches: Vec<FileMismatch>,
+}
+
+/// Compare the files under `root` with their index records. The quick check
+/// only stats indexed files; `full` also hashes them and looks for files
+/// the index doesn't list.
+fn verify_files(root: &Path, indexed: &[ModFileRecord], full: bool) -> Vec<FileMismatch> {
+    let mut mismatches = Vec::new();
+    for record in indexed {
+        let drift = match std::fs::metadata(root.join(&record.relative_path)) {
+            Err(_) => Some(FileDrift::Missing),
+            Ok(meta) if record.size.is_some_and(|size| size != meta.len() as i64) => {
+                Some(FileDrift::SizeChanged)
+            }
+            Ok(_) if full => match (&record.hash, hash_file(&root.join(&record.relative_path))) {
+                (Some(expected), Ok(actual)) if *expected != actual => {
+                    Some(FileDrift::ContentChanged)
+                }
+                _ => None,
+            },
+            Ok(_) => None,
+        };
+        if let Some(drift) = drift {
+            mismatches.push(FileMismatch {
+                path: record.relative_path.clone(),
+                drift,
+            });
+        }
+    }
+
+    mismatches.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if full {
+        let known: HashSet<&str> = indexed.iter().map(|r| r.relative_path.as_str()).collect();
+        let mut extra: Vec<String> = list_files(root)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| !known.contains(path.as_str()))
+            .collect();
+        extra.sort();
+        mismatches.extend(extra.into_iter().map(|path| FileMismatch {
+            path,
+            drift: FileDrift::Unindexed,
+        }));
+    }
+    mismatches
+}
+
+impl super::ModManager {
+    /// Index a mod's staged files again after ModSanity changed them, and
+    /// update its file count and plugins
+    pub(super) async fn reindex_mod(&self, record: &mut crate::db::ModRecord) -> Result<()> {
+        let mod_id = record.id.context("Mod has no database id")?;
+        let install_path = PathBuf::from(&record.install_path);
+        let files = list_files(&install_path)?;
+        record.file_count = files.len() as i32;
+        record.updated_at = chrono::Utc::now().to_rfc3339();
+        self.db.update_mod(record)?;
+        let file_records = index_files(&install_path, &files, mod_id).await?;
+        self.db.delete_mod_files(mod_id)?;
+        self.db.insert_mod_files(mod_id, &file_records)?;
+        self.db.replace_mod_plugins(
+            mod_id,
+            &record.game_id,
+            &super::plugin_filenames_from_mod_files(&file_records),
+        )?;
+        Ok(())
+    }
+
+    /// Check staged files of one mod, or of every mod of the game, against
+    /// the file index. The quick check only stats indexed files; `full` also
+    /// hashes them and looks for files the index doesn't list.
+    pub async fn verify_mods(
+        &self,
+        game_id: &str,
+        name: Option<&str>,
+        full: bool,
+    ) -> Result<Vec<ModVerification>> {
+        let mods = match name {
+            Some(name) => match self.db.get_mod(game_id, name)? {
+                Some(m) => vec![m],
+                None => bail!("Mod '{}' not found", name),
+            },
+            None => self.db.get_mods_for_game(game_id)?,
+        };
+
+        let mut reports = Vec::new();
+        for m in mods {
+            let Some(mod_id) = m.id else {
+                continue;
+            };
+            let indexed = self.db.get_mod_files(mod_id)?;
+            let root = PathBuf::from(&m.install_path);
+            let files = indexed.len();
+            let mismatches =
+                tokio::task::spawn_blocking(move || verify_files(&root, &indexed, full))
+                    .await
+                    .context("File verification task failed")?;
+            reports.push(ModVerification {
+                mod_name: m.name,
+                files,
+                mismatches,
+            });
+        }
+        Ok(reports)
+    }
+
+    /// Mods that provide `path`, grouped per matching file. Matches are
+    /// case-insensitive; when nothing matches the full path, files ending in
+    /// it are returned (so `armor/x.dds` finds `textures/armor/x.dds`).
+    pub fn which_file(&self, game_id: &str, path: &str) -> Result<Vec<FileLookup>> {
+        let path = normalize_lookup_path(path);
+        let mut lookups: Vec<FileLookup> = Vec::new();
+        for provider in self.db.find_file_providers(game_id, &path)? {
+            let key = provider.relative_path.replace('\\', "/");
+            match lookups
+                .iter_mut()
+                .find(|l| l.path.eq_ignore_ascii_case(&key))
+            {
+                Some(lookup) => lookup.providers.push(provider),
+                None => lookups.push(FileLookup {
+                    path: key,
+                    providers: vec![provider],
+                }),
+            }
+        }
+        for lookup in &mut lookups {
+            lookup
+                .providers
+                .sort_by_key(|p| std::cmp::Reverse(p.priority));
+        }
+        Ok(lookups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_normalize_and_hash() {
+        assert_eq!(
+            normalize_lookup_path("Data\\Textures\\Armor\\x.dds"),
+            "Textures/Armor/x.dds"
+        );
+        assert_eq!(normalize_lookup_path("/meshes/a.nif"), "meshes/a.nif");
+        assert_eq!(normalize_lookup_path("database.esp"), "database.esp");
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a.txt"), "").unwrap();
+        std::fs::write(tmp.path().join("b.txt"), "a").unwrap();
+        // Published FNV-1a 64 test vectors
+        assert_eq!(
+            hash_file(&tmp.path().join("a.txt")).unwrap(),
+            "cbf29ce484222325"
+        );
+        assert_eq!(
+            hash_file(&tmp.path().join("b.txt")).unwrap(),
+            "af63dc4c8601ec8c"
+        );
+
+        let files = list_files(tmp.path()).unwrap();
+        let records = index_files(tmp.path(), &files, 7).await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.mod_id == 7 && r.hash.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_verify_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        for name in ["a.esp", "b.esp", "c.esp"] {
+            std::fs::write(tmp.path().join(name), "abcd").unwrap();
+        }
+        let files = list_files(tmp.path()).unwrap();
+        let records = index_files(tmp.path(), &files, 1).await.unwrap();
+        assert!(verify_files(tmp.path(), &records, true).is_empty());
+
+        std::fs::remove_file(tmp.path().join("a.esp")).unwrap();
+        std::fs::write(tmp.path().join("b.esp"), "abcdef").unwrap();
+        std::fs::write(tmp.path().join("c.esp"), "dcba").unwrap();
+        std::fs::write(tmp.path().join("d.esp"), "").unwrap();
+
+        let drift = |full: bool| -> Vec<(String, FileDrift)> {
+            verify_files(tmp.path(), &records, full)
+                .into_iter()
+                .map(|m| (m.path, m.drift))
+                .collect()
+        };
+        // Same-size edits and new files need a full check
+        assert_eq!(
+            drift(false),
+            vec![
+                ("a.esp".to_string(), FileDrift::Missing),
+                ("b.esp".to_string(), FileDrift::SizeChanged),
+            ]
+        );
+        let full = drift(true);
+        assert!(full.contains(&("c.esp".to_string(), FileDrift::ContentChanged)));
+        assert!(full.contains(&("d.esp".to_string(), FileDrift::Unindexed)));
+    }
+}
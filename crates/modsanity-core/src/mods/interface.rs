@@ -0,0 +1,212 @@
+//! Interface (.swf) mod compatibility checks
+//!
+//! UI mods replace Scaleform menus wholesale, so two mods shipping the same
+//! .swf means one menu silently loses. A small bundled dataset also flags
+//! UI overhauls that are known not to work together even when their files
+//! happen not to overlap.
+
+use crate::db::{Database, FileConflict};
+use crate::games::GameType;
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+/// A pair of UI mods that are known not to work together
+#[derive(Debug, Clone, Copy)]
+pub struct InterfaceIncompatibility {
+    /// Case-insensitive substrings matched against installed mod names
+    pub mods: [&'static str; 2],
+    pub games: &'static [GameType],
+    pub reason: &'static str,
+}
+
+impl InterfaceIncompatibility {
+    pub fn supports(&self, game_type: GameType) -> bool {
+        self.games.contains(&game_type)
+    }
+}
+
+const SKYRIM: &[GameType] = &[GameType::SkyrimSE, GameType::SkyrimVR, GameType::EnderalSE];
+
+const INCOMPATIBILITIES: &[InterfaceIncompatibility] = &[
+    InterfaceIncompatibility {
+        mods: ["RaceMenu", "Enhanced Character Edit"],
+        games: SKYRIM,
+        reason: "Both replace the character creation menu; use one or the other",
+    },
+    InterfaceIncompatibility {
+        mods: ["Dear Diary", "Untarnished UI"],
+        games: SKYRIM,
+        reason: "Both are full menu overhauls; menus will mix styles or break",
+    },
+    InterfaceIncompatibility {
+        mods: ["Dear Diary", "Nordic UI"],
+        games: SKYRIM,
+        reason: "Both are full menu overhauls; menus will mix styles or break",
+    },
+    InterfaceIncompatibility {
+        mods: ["Nordic UI", "Untarnished UI"],
+        games: SKYRIM,
+        reason: "Both are full menu overhauls; menus will mix styles or break",
+    },
+    InterfaceIncompatibility {
+        mods: ["SkyHUD", "Less Intrusive HUD"],
+        games: SKYRIM,
+        reason: "Both replace hudmenu.swf; only one layout can apply",
+    },
+    InterfaceIncompatibility {
+        mods: ["DEF_UI", "FallUI"],
+        games: &[GameType::Fallout4, GameType::Fallout4VR],
+        reason: "Both replace the Pip-Boy and inventory menus; pick one UI suite",
+    },
+];
+
+/// An interface file shipped by more than one enabled mod
+#[derive(Debug, Clone)]
+pub struct SwfConflict {
+    /// Path relative to Data, e.g. "interface/inventorymenu.swf"
+    pub path: String,
+    /// Providing mods, lowest priority first
+    pub mods: Vec<String>,
+}
+
+impl SwfConflict {
+    /// The mod whose menu the game actually loads
+    pub fn winner(&self) -> &str {
+        self.mods.last().map(String::as_str).unwrap_or_default()
+    }
+}
+
+/// A known-incompatible combination found among enabled mods
+#[derive(Debug, Clone)]
+pub struct InterfaceWarning {
+    pub mods: [String; 2],
+    pub reason: &'static str,
+}
+
+/// Interface compatibility findings for one game
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceReport {
+    pub swf_conflicts: Vec<SwfConflict>,
+    pub incompatible: Vec<InterfaceWarning>,
+}
+
+impl InterfaceReport {
+    pub fn is_empty(&self) -> bool {
+        self.swf_conflicts.is_empty() && self.incompatible.is_empty()
+    }
+}
+
+/// Check enabled mods for shared .swf files and known-bad UI combinations
+pub fn check_interface_compat(db: &Database, game_id: &str) -> Result<InterfaceReport> {
+    let enabled: Vec<String> = db
+        .get_mods_for_game(game_id)?
+        .into_iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.name)
+        .collect();
+    let incompatible = match GameType::from_id(game_id) {
+        Some(game_type) => match_incompatibilities(game_type, &enabled),
+        None => Vec::new(),
+    };
+
+    Ok(InterfaceReport {
+        swf_conflicts: group_swf_conflicts(&db.find_conflicts(game_id)?),
+        incompatible,
+    })
+}
+
+fn group_swf_conflicts(raw: &[FileConflict]) -> Vec<SwfConflict> {
+    let mut providers: BTreeMap<String, BTreeMap<&str, i32>> = BTreeMap::new();
+    for c in raw {
+        let path = c.path.replace('\\', "/").to_lowercase();
+        if !path.ends_with(".swf") {
+            continue;
+        }
+        let entry = providers.entry(path).or_default();
+        entry.insert(c.mod1.as_str(), c.priority1);
+        entry.insert(c.mod2.as_str(), c.priority2);
+    }
+
+    providers
+        .into_iter()
+        .map(|(path, mods)| {
+            let mut mods: Vec<(&str, i32)> = mods.into_iter().collect();
+            mods.sort_by_key(|(_, priority)| *priority);
+            SwfConflict {
+                path,
+                mods: mods.into_iter().map(|(m, _)| m.to_string()).collect(),
+            }
+        })
+        .collect()
+}
+
+fn match_incompatibilities(game_type: GameType, enabled: &[String]) -> Vec<InterfaceWarning> {
+    let find = |pattern: &str| {
+        let pattern = pattern.to_lowercase();
+        enabled
+            .iter()
+            .find(|name| name.to_lowercase().contains(&pattern))
+    };
+
+    INCOMPATIBILITIES
+        .iter()
+        .filter(|rule| rule.supports(game_type))
+        .filter_map(|rule| {
+            let first = find(rule.mods[0])?;
+            let second = find(rule.mods[1])?;
+            (first != second).then(|| InterfaceWarning {
+                mods: [first.clone(), second.clone()],
+                reason: rule.reason,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conflict(path: &str, mod1: &str, p1: i32, mod2: &str, p2: i32) -> FileConflict {
+        FileConflict {
+            path: path.to_string(),
+            mod1: mod1.to_string(),
+            mod2: mod2.to_string(),
+            priority1: p1,
+            priority2: p2,
+        }
+    }
+
+    #[test]
+    fn test_group_swf_conflicts() {
+        let raw = vec![
+            conflict("Interface/InventoryMenu.swf", "SkyUI", 1, "Dear Diary", 5),
+            conflict("interface/inventorymenu.swf", "SkyUI", 1, "Other UI", 3),
+            conflict("textures/foo.dds", "A", 1, "B", 2),
+        ];
+        let grouped = group_swf_conflicts(&raw);
+        assert_eq!(grouped.len(), 1);
+        assert_eq!(grouped[0].path, "interface/inventorymenu.swf");
+        assert_eq!(grouped[0].mods, vec!["SkyUI", "Other UI", "Dear Diary"]);
+        assert_eq!(grouped[0].winner(), "Dear Diary");
+    }
+
+    #[test]
+    fn test_match_incompatibilities() {
+        let enabled = vec![
+            "RaceMenu SE".to_string(),
+            "ECE - Enhanced Character Edit".to_string(),
+            "Nordic UI".to_string(),
+        ];
+        let warnings = match_incompatibilities(GameType::SkyrimSE, &enabled);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].mods,
+            [
+                "RaceMenu SE".to_string(),
+                "ECE - Enhanced Character Edit".to_string()
+            ]
+        );
+
+        assert!(match_incompatibilities(GameType::Fallout4, &enabled).is_empty());
+    }
+}
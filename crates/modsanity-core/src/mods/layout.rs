@@ -0,0 +1,297 @@
+//! Install-time archive layout checks
+//!
+//! Catches common packaging mistakes after extraction: game data buried two
+//! or more folders deep, plugins at the archive root with their assets in a
+//! wrapper folder, and FOMOD trees with several option folders but no
+//! installer to pick between them. Fixable layouts are rearranged before the
+//! mod is registered; the rest refuse to install instead of deploying junk.
+
+use super::{find_data_root, fomod, is_data_entry, is_plugin_name, move_contents, DATA_INDICATORS};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Deepest level searched for a buried Data folder
+const MAX_NESTING: usize = 4;
+
+/// A packaging mistake found in an extracted archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutIssue {
+    /// Game data sits two or more folders below the archive root
+    NestedDataDir { path: String },
+    /// Plugins at the archive root while their assets are in a wrapper folder
+    SplitPluginAssets { wrapper: String },
+    /// Several candidate Data folders and nothing to choose between them
+    AmbiguousDataDirs { fomod: bool, options: Vec<String> },
+}
+
+impl LayoutIssue {
+    pub fn is_fixable(&self) -> bool {
+        !matches!(self, LayoutIssue::AmbiguousDataDirs { .. })
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            LayoutIssue::NestedDataDir { path } => {
+                format!("game data is nested in '{}'; moved it to the mod root", path)
+            }
+            LayoutIssue::SplitPluginAssets { wrapper } => format!(
+                "plugins are at the archive root but assets are in '{}'; merged them",
+                wrapper
+            ),
+            LayoutIssue::AmbiguousDataDirs { fomod: true, options } => format!(
+                "FOMOD option folders ({}) without a usable installer; repackage it or install one folder with 'mod add-dir'",
+                options.join(", ")
+            ),
+            LayoutIssue::AmbiguousDataDirs {
+                fomod: false,
+                options,
+            } => format!(
+                "several separate Data folders ({}); install the one you want with 'mod add-dir'",
+                options.join(", ")
+            ),
+        }
+    }
+}
+
+/// Result of checking an extracted archive
+#[derive(Debug, Clone)]
+pub struct LayoutCheck {
+    /// Folder whose contents become the mod root
+    pub data_root: PathBuf,
+    /// Wrapper folder to merge into the root alongside loose plugins
+    pub merge: Option<PathBuf>,
+    pub issues: Vec<LayoutIssue>,
+}
+
+impl LayoutCheck {
+    /// The first issue that can't be fixed automatically
+    pub fn blocking_issue(&self) -> Option<&LayoutIssue> {
+        self.issues.iter().find(|i| !i.is_fixable())
+    }
+}
+
+/// Inspect an extracted archive at `root`
+pub fn check_layout(root: &Path) -> Result<LayoutCheck> {
+    let mut check = LayoutCheck {
+        data_root: find_data_root(root)?,
+        merge: None,
+        issues: Vec::new(),
+    };
+
+    if check.data_root != root || has_data(root) {
+        if check.data_root == root {
+            if let Some(wrapper) = split_asset_wrapper(root) {
+                check.issues.push(LayoutIssue::SplitPluginAssets {
+                    wrapper: relative(root, &wrapper),
+                });
+                check.merge = Some(wrapper);
+            }
+        } else if has_fomod_dir(root) && !fomod::has_numbered_folders(root) {
+            // find_data_root takes the first data folder; with several named
+            // FOMOD options that silently installs an arbitrary one
+            let options: Vec<PathBuf> = subdirs(root).into_iter().filter(|d| has_data(d)).collect();
+            if options.len() > 1 {
+                check.issues.push(LayoutIssue::AmbiguousDataDirs {
+                    fomod: true,
+                    options: options.iter().map(|d| relative(root, d)).collect(),
+                });
+            }
+        }
+        return Ok(check);
+    }
+
+    let mut candidates = Vec::new();
+    for dir in subdirs(root) {
+        find_nested_data(&dir, 2, &mut candidates);
+    }
+    match candidates.len() {
+        0 => {}
+        1 => {
+            check.issues.push(LayoutIssue::NestedDataDir {
+                path: relative(root, &candidates[0]),
+            });
+            check.data_root = candidates.remove(0);
+        }
+        _ => check.issues.push(LayoutIssue::AmbiguousDataDirs {
+            fomod: has_fomod_dir(root),
+            options: candidates.iter().map(|d| relative(root, d)).collect(),
+        }),
+    }
+    Ok(check)
+}
+
+/// Rearrange `root` according to a fixable check
+pub async fn apply_layout(root: &Path, check: &LayoutCheck) -> Result<()> {
+    if let Some(wrapper) = &check.merge {
+        move_contents(wrapper, root).await?;
+        let _ = tokio::fs::remove_dir(wrapper).await;
+    } else if check.data_root != root {
+        move_contents(&check.data_root, root).await?;
+    }
+    Ok(())
+}
+
+fn has_data(dir: &Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .any(|e| is_data_entry(&e.file_name().to_string_lossy().to_lowercase()))
+        })
+        .unwrap_or(false)
+}
+
+/// Depth-first search below `dir` for folders holding game data. Stops
+/// descending at the first match so a Data folder's own subfolders don't count.
+fn find_nested_data(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    if depth > MAX_NESTING || is_fomod_dir(dir) {
+        return;
+    }
+    for sub in subdirs(dir) {
+        if has_data(&sub) {
+            out.push(sub);
+        } else {
+            find_nested_data(&sub, depth + 1, out);
+        }
+    }
+}
+
+/// A single folder holding the assets for plugins placed at the archive root
+fn split_asset_wrapper(root: &Path) -> Option<PathBuf> {
+    let names: Vec<String> = std::fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_lowercase())
+        .collect();
+    let has_plugins = names.iter().any(|n| is_plugin_name(n));
+    let has_assets = names.iter().any(|n| DATA_INDICATORS.contains(&n.as_str()));
+    if !has_plugins || has_assets {
+        return None;
+    }
+
+    let mut wrappers = subdirs(root).into_iter().filter(|d| {
+        std::fs::read_dir(d)
+            .map(|entries| {
+                entries.flatten().any(|e| {
+                    DATA_INDICATORS
+                        .contains(&e.file_name().to_string_lossy().to_lowercase().as_str())
+                })
+            })
+            .unwrap_or(false)
+    });
+    let wrapper = wrappers.next()?;
+    wrappers.next().is_none().then_some(wrapper)
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut out: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                .map(|e| e.path())
+                .filter(|p| !is_fomod_dir(p))
+                .collect()
+        })
+        .unwrap_or_default();
+    out.sort();
+    out
+}
+
+fn is_fomod_dir(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy().eq_ignore_ascii_case("fomod"))
+        .unwrap_or(false)
+}
+
+fn has_fomod_dir(root: &Path) -> bool {
+    std::fs::read_dir(root)
+        .map(|entries| entries.flatten().any(|e| is_fomod_dir(&e.path())))
+        .unwrap_or(false)
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(root: &Path, rel: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, b"").unwrap();
+    }
+
+    #[test]
+    fn test_nested_data_dir_is_hoisted() {
+        let dir = tempfile::tempdir().unwrap();
+        touch(dir.path(), "My Mod/My Mod v1.2/Data/meshes/a.nif");
+        touch(dir.path(), "My Mod/My Mod v1.2/Data/MyMod.esp");
+
+        let check = check_layout(dir.path()).unwrap();
+        assert_eq!(
+            check.issues,
+            vec![LayoutIssue::NestedDataDir {
+                path: "My Mod/My Mod v1.2/Data".to_string()
+            }]
+        );
+        assert_eq!(check.data_root, dir.path().join("My Mod/My Mod v1.2/Data"));
+    }
+
+    #[tokio::test]
+    async fn test_plugin_at_root_merges_wrapper() {
+        let dir = tempfile::tempdir().unwrap();
+        touch(dir.path(), "MyMod.esp");
+        touch(dir.path(), "MyMod/textures/a.dds");
+        touch(dir.path(), "MyMod/meshes/a.nif");
+
+        let check = check_layout(dir.path()).unwrap();
+        assert_eq!(
+            check.issues,
+            vec![LayoutIssue::SplitPluginAssets {
+                wrapper: "MyMod".to_string()
+            }]
+        );
+        apply_layout(dir.path(), &check).await.unwrap();
+        assert!(dir.path().join("textures/a.dds").exists());
+        assert!(dir.path().join("meshes/a.nif").exists());
+        assert!(!dir.path().join("MyMod").exists());
+    }
+
+    #[test]
+    fn test_fomod_option_folders_are_ambiguous() {
+        let dir = tempfile::tempdir().unwrap();
+        touch(dir.path(), "fomod/info.xml");
+        touch(dir.path(), "Option A/textures/a.dds");
+        touch(dir.path(), "Option B/textures/a.dds");
+
+        let check = check_layout(dir.path()).unwrap();
+        assert!(check.blocking_issue().is_some());
+        assert_eq!(
+            check.issues,
+            vec![LayoutIssue::AmbiguousDataDirs {
+                fomod: true,
+                options: vec!["Option A".to_string(), "Option B".to_string()]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_regular_layouts_have_no_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        touch(dir.path(), "MyMod.esp");
+        touch(dir.path(), "meshes/a.nif");
+        assert!(check_layout(dir.path()).unwrap().issues.is_empty());
+
+        let wrapped = tempfile::tempdir().unwrap();
+        touch(wrapped.path(), "Data/textures/a.dds");
+        let check = check_layout(wrapped.path()).unwrap();
+        assert!(check.issues.is_empty());
+        assert_eq!(check.data_root, wrapped.path().join("Data"));
+    }
+}
@@ -0,0 +1,109 @@
+//! MO2 download metadata
+//!
+//! Mod Organizer 2 writes a `<archive>.meta` file next to every archive it
+//! downloads, recording the Nexus mod and file the archive came from. Reading
+//! it lets archives from an MO2 downloads folder be installed and matched
+//! without hashing or searching Nexus.
+
+use std::path::{Path, PathBuf};
+
+/// Nexus details from an MO2 `.meta` sidecar
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mo2DownloadMeta {
+    /// MO2's game name, e.g. "SkyrimSE"
+    pub game_name: Option<String>,
+    pub nexus_mod_id: Option<i64>,
+    pub nexus_file_id: Option<i64>,
+    pub version: Option<String>,
+    /// Nexus mod name (`modName`)
+    pub mod_name: Option<String>,
+    /// Nexus file name (`name`)
+    pub file_name: Option<String>,
+}
+
+impl Mo2DownloadMeta {
+    /// Sidecar path for `archive`: `SkyUI.7z` -> `SkyUI.7z.meta`
+    pub fn sidecar_path(archive: &Path) -> PathBuf {
+        let mut name = archive.as_os_str().to_os_string();
+        name.push(".meta");
+        PathBuf::from(name)
+    }
+
+    /// Read the sidecar of `archive`, if there is one naming a Nexus file
+    pub fn for_archive(archive: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(Self::sidecar_path(archive)).ok()?;
+        let meta = Self::parse(&content);
+        meta.nexus_mod_id.is_some().then_some(meta)
+    }
+
+    /// Parse a `.meta` file. MO2 writes `0` for unknown IDs, which are
+    /// treated as missing.
+    pub fn parse(content: &str) -> Self {
+        let mut meta = Self::default();
+        let mut in_general = true;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_general = section.trim().eq_ignore_ascii_case("General");
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            if !in_general || value.is_empty() {
+                continue;
+            }
+            let id = || value.parse::<i64>().ok().filter(|id| *id > 0);
+            match key.trim().to_lowercase().as_str() {
+                "gamename" => meta.game_name = Some(value.to_string()),
+                "modid" => meta.nexus_mod_id = id(),
+                "fileid" => meta.nexus_file_id = id(),
+                "version" => meta.version = Some(value.to_string()),
+                "modname" => meta.mod_name = Some(value.to_string()),
+                "name" => meta.file_name = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        meta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_download_meta() {
+        let meta = Mo2DownloadMeta::parse(
+            "[General]\ngameName=SkyrimSE\nmodID=12604\nfileID=35407\n\
+             url=\"https://www.nexusmods.com/skyrimspecialedition/mods/12604\"\n\
+             name=SkyUI_5_2_SE\nmodName=SkyUI\nversion=5.2SE\ninstalled=true\n",
+        );
+        assert_eq!(
+            meta,
+            Mo2DownloadMeta {
+                game_name: Some("SkyrimSE".to_string()),
+                nexus_mod_id: Some(12604),
+                nexus_file_id: Some(35407),
+                version: Some("5.2SE".to_string()),
+                mod_name: Some("SkyUI".to_string()),
+                file_name: Some("SkyUI_5_2_SE".to_string()),
+            }
+        );
+
+        // Manual downloads MO2 couldn't identify
+        let unknown = Mo2DownloadMeta::parse("[General]\nmodID=0\nfileID=0\nversion=\n");
+        assert_eq!(unknown.nexus_mod_id, None);
+        assert_eq!(unknown.nexus_file_id, None);
+        assert_eq!(unknown.version, None);
+
+        assert_eq!(
+            Mo2DownloadMeta::sidecar_path(Path::new("/dl/SkyUI.7z")),
+            PathBuf::from("/dl/SkyUI.7z.meta")
+        );
+    }
+}
@@ -1,23 +1,68 @@
 //! Mod management - installation, deployment, and conflict handling
 
+mod adopt;
 mod archive;
 pub mod auto_categorize;
+mod availability;
+pub mod bsa;
+mod bulk;
+mod capture;
+mod choices;
+mod cleaning;
 mod conflicts;
 mod deploy;
+mod disk_usage;
+mod dlc;
+mod exclusions;
+mod export;
 pub mod fomod;
-
+mod history;
+mod index;
+mod interface;
+mod layout;
+mod mo2_meta;
+mod naming;
+mod requirements;
+mod retention;
+mod scripts;
+mod setup_script;
+mod trash;
+mod version;
+
+pub use adopt::*;
 pub use archive::*;
 pub use auto_categorize::*;
+pub use availability::*;
+pub use bulk::*;
+pub use capture::*;
 pub use conflicts::*;
 pub use deploy::*;
-
+pub use disk_usage::*;
+pub use dlc::*;
+pub use exclusions::*;
+pub use export::*;
+pub use history::*;
+pub use index::*;
+pub use interface::*;
+pub use layout::*;
+pub use mo2_meta::*;
+pub use naming::*;
+pub use requirements::*;
+pub use scripts::*;
+pub use setup_script::*;
+pub use trash::*;
+pub use version::*;
+
+use crate::activity::Activity;
 use crate::config::Config;
 use crate::db::{Database, ModFileRecord, ModRecord};
 use anyhow::{bail, Context, Result};
 use regex_lite::Regex;
+use serde_json::Value;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use walkdir::WalkDir;
 
 /// Result of an installation attempt
@@ -75,6 +120,10 @@ pub struct InstalledMod {
     pub file_count: i32,
     pub install_path: PathBuf,
     pub category_id: Option<i64>,
+    /// RFC 3339 time of the first install
+    pub installed_at: String,
+    /// Total size of the mod's indexed files in bytes
+    pub size: u64,
 }
 
 /// Summary of a staging rescan operation.
@@ -100,6 +149,8 @@ impl From<ModRecord> for InstalledMod {
             file_count: r.file_count,
             install_path: PathBuf::from(r.install_path),
             category_id: r.category_id,
+            installed_at: r.installed_at,
+            size: 0,
         }
     }
 }
@@ -108,23 +159,38 @@ impl From<ModRecord> for InstalledMod {
 pub struct ModManager {
     config: Arc<RwLock<Config>>,
     db: Arc<Database>,
+    /// Held while an install picks its priority and writes its records, so
+    /// concurrent installs don't end up with the same priority
+    register_lock: Mutex<()>,
 }
 
 impl ModManager {
     /// Create a new ModManager
     pub fn new(config: Arc<RwLock<Config>>, db: Arc<Database>) -> Self {
-        Self { config, db }
+        Self {
+            config,
+            db,
+            register_lock: Mutex::new(()),
+        }
     }
 
     /// Get staging directory for a game
-    async fn staging_dir(&self, game_id: &str) -> PathBuf {
+    async fn staging_dir(&self, game_id: &str) -> Result<PathBuf> {
         self.config.read().await.game_staging_dir(game_id)
     }
 
     /// List all installed mods for a game
     pub async fn list_mods(&self, game_id: &str) -> Result<Vec<InstalledMod>> {
         let records = self.db.get_mods_for_game(game_id)?;
-        Ok(records.into_iter().map(InstalledMod::from).collect())
+        let sizes = self.db.get_mod_sizes(game_id)?;
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let mut installed = InstalledMod::from(record);
+                installed.size = sizes.get(&installed.id).copied().unwrap_or(0);
+                installed
+            })
+            .collect())
     }
 
     /// Get a specific mod
@@ -145,6 +211,31 @@ impl ModManager {
         nexus_mod_id: Option<i64>,
         nexus_file_id: Option<i64>,
         mod_name_hint: Option<&str>,
+    ) -> Result<InstallResult> {
+        self.install_from_archive_cancellable(
+            game_id,
+            archive_path,
+            progress_callback,
+            nexus_mod_id,
+            nexus_file_id,
+            mod_name_hint,
+            None,
+        )
+        .await
+    }
+
+    /// Install a mod from an archive, stopping with [`Cancelled`] while it
+    /// is extracted once `cancel` is set. A cancelled install leaves nothing
+    /// behind; once extracted, the install runs to the end.
+    pub async fn install_from_archive_cancellable(
+        &self,
+        game_id: &str,
+        archive_path: &str,
+        progress_callback: Option<ProgressCallback>,
+        nexus_mod_id: Option<i64>,
+        nexus_file_id: Option<i64>,
+        mod_name_hint: Option<&str>,
+        cancel: Option<Arc<AtomicBool>>,
     ) -> Result<InstallResult> {
         let archive_path = Path::new(archive_path);
         if !archive_path.exists() {
@@ -159,15 +250,38 @@ impl ModManager {
 
         // Parse mod name and version from filename
         let (parsed_name, version) = Self::parse_mod_name(archive_name);
-        let name = mod_name_hint
+
+        // Archives from an MO2 downloads folder name their Nexus file in a sidecar
+        let mo2_meta = Mo2DownloadMeta::for_archive(archive_path)
+            .filter(|m| nexus_file_id.is_none() || m.nexus_file_id == nexus_file_id)
+            .unwrap_or_default();
+        let nexus_mod_id = nexus_mod_id.or(mo2_meta.nexus_mod_id);
+        let nexus_file_id = nexus_file_id.or(mo2_meta.nexus_file_id);
+        let version = mo2_meta.version.unwrap_or(version);
+        let hint = mod_name_hint
+            .or(mo2_meta.mod_name.as_deref())
             .map(str::trim)
             .filter(|s| !s.is_empty())
-            .map(|s| s.replace('_', " ").replace('-', " ").trim().to_string())
-            .unwrap_or(parsed_name);
+            .map(|s| s.replace(['_', '-'], " ").trim().to_string());
 
         // Resolve Nexus ID from explicit argument first, then filename fallback.
-        let resolved_nexus_mod_id =
-            nexus_mod_id.or_else(|| Self::parse_nexus_ids(archive_name).map(|(mod_id, _)| mod_id));
+        let parsed_ids = Self::parse_nexus_ids(archive_name);
+        let resolved_nexus_mod_id = nexus_mod_id.or(parsed_ids.map(|(mod_id, _)| mod_id));
+
+        let install_config = self.config.read().await.install.clone();
+        validate_name_template(&install_config.name_template)
+            .context("Invalid [install] name_template")?;
+        let name = render_mod_name(
+            &install_config.name_template,
+            &NameParts {
+                archive_stem: archive_name,
+                archive_name: &parsed_name,
+                nexus_name: hint.as_deref(),
+                version: &version,
+                nexus_mod_id: resolved_nexus_mod_id,
+                nexus_file_id: nexus_file_id.or(parsed_ids.map(|(_, file_id)| file_id)),
+            },
+        );
 
         // Guard against duplicate installs of the same Nexus mod under different names.
         // This also upgrades legacy unresolved numeric-name installs (e.g. "165498")
@@ -228,12 +342,12 @@ impl ModManager {
         }
 
         // Check if already installed by resolved display name.
-        if self.db.get_mod(game_id, &name)?.is_some() {
-            bail!("Mod '{}' is already installed", name);
-        }
+        let name = naming::resolve_name_conflict(name, install_config.name_conflict, |n| {
+            Ok(self.db.get_mod(game_id, n)?.is_some())
+        })?;
 
         // Create staging directory for this mod
-        let staging = self.staging_dir(game_id).await.join(&name);
+        let staging = self.staging_dir(game_id).await?.join(&name);
         tokio::fs::create_dir_all(&staging)
             .await
             .context("Failed to create staging directory")?;
@@ -244,7 +358,22 @@ impl ModManager {
             archive_path.display(),
             staging.display()
         );
-        extract_archive(archive_path, &staging, progress_callback).await?;
+        if let Err(e) =
+            extract_archive_cancellable(archive_path, &staging, progress_callback, cancel).await
+        {
+            if is_cancelled(&e) {
+                let _ = tokio::fs::remove_dir_all(&staging).await;
+            }
+            return Err(e);
+        }
+
+        // Keep a copy of the archive so this version can be rolled back to later
+        if let Err(e) = self
+            .retain_archive(game_id, &name, &version, nexus_file_id, archive_path, true)
+            .await
+        {
+            tracing::warn!("Failed to retain archive for {}: {}", name, e);
+        }
 
         // Check for FOMOD installer (including nested structures)
         if fomod::has_fomod(&staging) {
@@ -281,18 +410,22 @@ impl ModManager {
             }
         }
 
-        // Find the data root (handle nested folders)
-        let data_root = find_data_root(&staging)?;
-
-        // If data root is different, move files
-        if data_root != staging {
-            move_contents(&data_root, &staging).await?;
+        // Find the data root, fixing common packaging mistakes on the way
+        let layout = check_layout(&staging)?;
+        if let Some(issue) = layout.blocking_issue() {
+            let _ = tokio::fs::remove_dir_all(&staging).await;
+            bail!("Cannot install '{}': {}", name, issue.description());
+        }
+        for issue in &layout.issues {
+            tracing::warn!("Fixed layout of '{}': {}", name, issue.description());
         }
+        apply_layout(&staging, &layout).await?;
 
         // Collect file list
-        let files = collect_files(&staging)?;
+        let files = index::list_files(&staging)?;
 
         // Create database record
+        let _registering = self.register_lock.lock().await;
         let now = chrono::Utc::now().to_rfc3339();
         let record = ModRecord {
             id: None,
@@ -315,17 +448,7 @@ impl ModManager {
         let mod_id = self.db.insert_mod(&record)?;
 
         // Insert file records
-        let file_records: Vec<ModFileRecord> = files
-            .into_iter()
-            .map(|path| ModFileRecord {
-                id: None,
-                mod_id,
-                relative_path: path,
-                hash: None,
-                size: None,
-            })
-            .collect();
-
+        let file_records = index::index_files(&staging, &files, mod_id).await?;
         self.db.insert_mod_files(mod_id, &file_records)?;
         let plugin_files = plugin_filenames_from_mod_files(&file_records);
         self.db
@@ -343,7 +466,21 @@ impl ModManager {
             file_count: file_records.len() as i32,
             install_path: staging,
             category_id: None,
+            installed_at: record.installed_at.clone(),
+            size: mod_files_size(&file_records),
         };
+        self.record_activity(
+            game_id,
+            Activity::Install,
+            Some(&installed.name),
+            serde_json::json!({
+                "version": installed.version,
+                "archive": archive_path.file_name().map(|n| n.to_string_lossy()),
+                "nexus_mod_id": installed.nexus_mod_id,
+                "nexus_file_id": installed.nexus_file_id,
+            }),
+        )
+        .await;
 
         Ok(InstallResult::Completed(installed))
     }
@@ -360,6 +497,8 @@ impl ModManager {
         }
 
         self.db.set_mod_enabled(m.id.unwrap(), true)?;
+        self.record_activity(game_id, Activity::Enable, Some(&m.name), Value::Null)
+            .await;
         Ok(())
     }
 
@@ -375,9 +514,30 @@ impl ModManager {
         }
 
         self.db.set_mod_enabled(m.id.unwrap(), false)?;
+        self.record_activity(game_id, Activity::Disable, Some(&m.name), Value::Null)
+            .await;
         Ok(())
     }
 
+    /// Files installed mods provide, for FOMOD file dependency checks.
+    ///
+    /// Files from enabled mods are active, those only in disabled mods inactive.
+    pub fn fomod_environment(&self, game_id: &str) -> Result<fomod::FomodEnvironment> {
+        let mut env = fomod::FomodEnvironment::default();
+        for m in self.db.get_mods_for_game(game_id)? {
+            let Some(id) = m.id else { continue };
+            let state = if m.enabled {
+                fomod::FileState::Active
+            } else {
+                fomod::FileState::Inactive
+            };
+            for file in self.db.get_mod_files(id)? {
+                env.add_file(&file.relative_path, state);
+            }
+        }
+        Ok(env)
+    }
+
     /// Complete a FOMOD installation after wizard selections
     pub async fn complete_fomod_install(
         &self,
@@ -416,7 +576,7 @@ impl ModManager {
         tracing::info!("FOMOD installation completed successfully");
 
         // Collect installed files
-        let files = collect_files(&target_path)?;
+        let files = index::list_files(&target_path)?;
 
         let mod_id = if let Some(existing_id) = context.existing_mod_id {
             // Reconfiguration: Update existing mod
@@ -475,16 +635,7 @@ impl ModManager {
         };
 
         // Insert new file records
-        let file_records: Vec<ModFileRecord> = files
-            .into_iter()
-            .map(|path| ModFileRecord {
-                id: None,
-                mod_id,
-                relative_path: path,
-                hash: None,
-                size: None,
-            })
-            .collect();
+        let file_records = index::index_files(&target_path, &files, mod_id).await?;
 
         self.db.insert_mod_files(mod_id, &file_records)?;
         let plugin_files = plugin_filenames_from_mod_files(&file_records);
@@ -492,7 +643,7 @@ impl ModManager {
             .replace_mod_plugins(mod_id, &context.game_id, &plugin_files)?;
 
         // Save FOMOD choices for re-run
-        let profile_id = None; // TODO: Get current profile ID
+        let profile_id = self.active_profile_id(&context.game_id).await?;
         let manager = fomod::persistence::FomodChoiceManager::new(&self.db);
         manager.save_choice(mod_id, profile_id, &plan)?;
 
@@ -508,7 +659,16 @@ impl ModManager {
             file_count: file_records.len() as i32,
             install_path: target_path,
             category_id: None,
+            installed_at: chrono::Utc::now().to_rfc3339(),
+            size: mod_files_size(&file_records),
         };
+        self.record_activity(
+            &context.game_id,
+            Activity::Install,
+            Some(&installed.name),
+            serde_json::json!({ "version": installed.version, "fomod": true }),
+        )
+        .await;
 
         Ok(installed)
     }
@@ -520,17 +680,25 @@ impl ModManager {
             .get_mod(game_id, name)?
             .ok_or_else(|| anyhow::anyhow!("Mod '{}' not found", name))?;
 
-        // Delete staging directory
-        let staging = self.staging_dir(game_id).await.join(name);
-        if staging.exists() {
-            tokio::fs::remove_dir_all(&staging)
-                .await
-                .context("Failed to remove mod directory")?;
+        // Keep it restorable unless the trash is turned off
+        let trashed = self.config.read().await.trash.retention_days > 0;
+        if trashed {
+            self.trash_mod(game_id, &m).await?;
+        } else {
+            let staging = self.staging_dir(game_id).await?.join(name);
+            self.discard_mod(game_id, &m, &staging).await?;
         }
+        self.record_activity(
+            game_id,
+            Activity::Remove,
+            Some(&m.name),
+            serde_json::json!({ "version": m.version, "trashed": trashed }),
+        )
+        .await;
 
-        // Delete from database
-        self.db.delete_mod(m.id.unwrap())?;
-
+        if let Err(e) = self.purge_trash(game_id, false).await {
+            tracing::warn!("Failed to purge expired mods from the trash: {}", e);
+        }
         Ok(())
     }
 
@@ -560,19 +728,17 @@ impl ModManager {
             .get_mod(game_id, mod_name)?
             .ok_or_else(|| anyhow::anyhow!("Mod '{}' not found", mod_name))?;
 
-        let staging = self.staging_dir(game_id).await.join(mod_name);
+        let staging = self.staging_dir(game_id).await?.join(mod_name);
 
         // Find all plugin files in this mod
         let mut mod_plugins = Vec::new();
-        for entry in WalkDir::new(&staging).max_depth(3) {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if let Some(ext) = path.extension() {
-                    let ext_str = ext.to_str().unwrap_or("").to_lowercase();
-                    if matches!(ext_str.as_str(), "esp" | "esm" | "esl") {
-                        if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                            mod_plugins.push(filename.to_string());
-                        }
+        for entry in WalkDir::new(&staging).max_depth(3).into_iter().flatten() {
+            let path = entry.path();
+            if let Some(ext) = path.extension() {
+                let ext_str = ext.to_str().unwrap_or("").to_lowercase();
+                if matches!(ext_str.as_str(), "esp" | "esm" | "esl") {
+                    if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
+                        mod_plugins.push(filename.to_string());
                     }
                 }
             }
@@ -582,16 +748,18 @@ impl ModManager {
         let mut installed_plugins = std::collections::HashSet::new();
         let all_mods = self.db.get_mods_for_game(game_id)?;
         for m in all_mods {
-            let staging_path = self.staging_dir(game_id).await.join(&m.name);
-            for entry in WalkDir::new(&staging_path).max_depth(3) {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if let Some(ext) = path.extension() {
-                        let ext_str = ext.to_str().unwrap_or("").to_lowercase();
-                        if matches!(ext_str.as_str(), "esp" | "esm" | "esl") {
-                            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                                installed_plugins.insert(filename.to_lowercase());
-                            }
+            let staging_path = self.staging_dir(game_id).await?.join(&m.name);
+            for entry in WalkDir::new(&staging_path)
+                .max_depth(3)
+                .into_iter()
+                .flatten()
+            {
+                let path = entry.path();
+                if let Some(ext) = path.extension() {
+                    let ext_str = ext.to_str().unwrap_or("").to_lowercase();
+                    if matches!(ext_str.as_str(), "esp" | "esm" | "esl") {
+                        if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
+                            installed_plugins.insert(filename.to_lowercase());
                         }
                     }
                 }
@@ -621,6 +789,13 @@ impl ModManager {
 
         let new_priority = (m.priority + delta).max(0);
         self.db.set_mod_priority(m.id.unwrap(), new_priority)?;
+        self.record_activity(
+            game_id,
+            Activity::Priority,
+            Some(&m.name),
+            serde_json::json!({ "from": m.priority, "to": new_priority }),
+        )
+        .await;
         Ok(new_priority)
     }
 
@@ -632,14 +807,34 @@ impl ModManager {
             .ok_or_else(|| anyhow::anyhow!("Mod '{}' not found", name))?;
 
         self.db.set_mod_priority(m.id.unwrap(), priority)?;
+        self.record_activity(
+            game_id,
+            Activity::Priority,
+            Some(&m.name),
+            serde_json::json!({ "from": m.priority, "to": priority }),
+        )
+        .await;
         Ok(())
     }
 
     /// Batch-save a complete priority ordering from the Load Order screen.
     /// Takes a slice of (mod_id, new_priority) pairs.
     pub async fn save_priority_order(&self, order: &[(i64, i32)]) -> Result<()> {
+        let mut game_id = None;
         for &(mod_id, priority) in order {
             self.db.set_mod_priority(mod_id, priority)?;
+            if game_id.is_none() {
+                game_id = self.db.get_mod_by_id(mod_id)?.map(|m| m.game_id);
+            }
+        }
+        if let Some(game_id) = game_id {
+            self.record_activity(
+                &game_id,
+                Activity::Priority,
+                None,
+                serde_json::json!({ "mods": order.len() }),
+            )
+            .await;
         }
         Ok(())
     }
@@ -672,6 +867,13 @@ impl ModManager {
                 self.db.set_mod_priority(id, new_priority as i32)?;
             }
         }
+        self.record_activity(
+            game_id,
+            Activity::SortMods,
+            None,
+            serde_json::json!({ "by": "category", "mods": sorted_mods.len() }),
+        )
+        .await;
 
         Ok(())
     }
@@ -689,7 +891,7 @@ impl ModManager {
         game_id: &str,
         progress_callback: Option<Box<dyn Fn(usize, usize, String) + Send + Sync>>,
     ) -> Result<RescanStats> {
-        let mods_dir = self.staging_dir(game_id).await;
+        let mods_dir = self.staging_dir(game_id).await?;
 
         if !mods_dir.exists() {
             bail!("Mods directory not found: {}", mods_dir.display());
@@ -701,6 +903,7 @@ impl ModManager {
         let total = std::fs::read_dir(&mods_dir)?
             .filter_map(|e| e.ok())
             .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
             .count();
 
         tracing::info!("Found {} mod directories to scan", total);
@@ -727,7 +930,8 @@ impl ModManager {
                 }
             };
 
-            if !is_dir {
+            // Hidden entries are the trash and in-progress reinstalls
+            if !is_dir || entry.file_name().to_string_lossy().starts_with('.') {
                 continue;
             }
 
@@ -747,7 +951,7 @@ impl ModManager {
             tracing::info!("Processing {}/{}: {}", current, total, mod_name);
 
             let scanned = scan_mod_metadata(&mod_path);
-            let files = match collect_files(&mod_path) {
+            let files = match index::list_files(&mod_path) {
                 Ok(f) => f,
                 Err(e) => {
                     tracing::warn!("Failed to catalog files for '{}': {}", mod_name, e);
@@ -757,13 +961,12 @@ impl ModManager {
             };
             let file_records: Vec<ModFileRecord> = files
                 .iter()
-                .cloned()
-                .map(|path| ModFileRecord {
+                .map(|(path, size)| ModFileRecord {
                     id: None,
                     mod_id: 0,
-                    relative_path: path,
+                    relative_path: path.clone(),
                     hash: None,
-                    size: None,
+                    size: Some(*size),
                 })
                 .collect();
             let plugin_files = plugin_filenames_from_mod_files(&file_records);
@@ -793,10 +996,8 @@ impl ModManager {
 
                     match self.db.insert_mod(&record) {
                         Ok(mod_id) => {
-                            let mut inserted_files = file_records.clone();
-                            for rec in &mut inserted_files {
-                                rec.mod_id = mod_id;
-                            }
+                            let inserted_files =
+                                index::index_files(&mod_path, &files, mod_id).await?;
                             if let Err(e) = self.db.insert_mod_files(mod_id, &inserted_files) {
                                 tracing::warn!(
                                     "Failed to save file index for '{}': {}",
@@ -829,11 +1030,12 @@ impl ModManager {
                         continue;
                     }
 
-                    let mut existing_files = self
-                        .db
-                        .get_mod_files(mod_id)?
+                    let indexed = self.db.get_mod_files(mod_id)?;
+                    // Files indexed before hashes were recorded get hashed now
+                    let unhashed = indexed.iter().any(|f| f.hash.is_none());
+                    let mut existing_files = indexed
                         .into_iter()
-                        .map(|f| f.relative_path)
+                        .map(|f| (f.relative_path, f.size.unwrap_or(-1)))
                         .collect::<Vec<_>>();
                     existing_files.sort();
 
@@ -855,7 +1057,7 @@ impl ModManager {
                         || existing_mod.description != resolved_description
                         || existing_files != scanned_files;
 
-                    if changed {
+                    if changed || unhashed {
                         existing_mod.version = scanned.version.clone();
                         existing_mod.install_path = mod_path.to_string_lossy().to_string();
                         existing_mod.nexus_mod_id = resolved_nexus_mod_id;
@@ -877,10 +1079,7 @@ impl ModManager {
                                 e
                             );
                         }
-                        let mut updated_files = file_records.clone();
-                        for rec in &mut updated_files {
-                            rec.mod_id = mod_id;
-                        }
+                        let updated_files = index::index_files(&mod_path, &files, mod_id).await?;
                         if let Err(e) = self.db.insert_mod_files(mod_id, &updated_files) {
                             tracing::warn!(
                                 "Failed indexing files for '{}': {}",
@@ -896,7 +1095,11 @@ impl ModManager {
                                 e
                             );
                         }
-                        stats.updated += 1;
+                        if changed {
+                            stats.updated += 1;
+                        } else {
+                            stats.unchanged += 1;
+                        }
                     } else {
                         // Keep plugin index in sync even when core mod record is unchanged.
                         if let Err(e) = self.db.replace_mod_plugins(mod_id, game_id, &plugin_files)
@@ -942,17 +1145,14 @@ impl ModManager {
                 if let Some(caps) = re.captures(filename) {
                     let version = caps.get(1).map(|m| m.as_str()).unwrap_or("1.0.0");
                     let name = filename[..caps.get(0).unwrap().start()].to_string();
-                    let name = name.replace('-', " ").replace('_', " ").trim().to_string();
+                    let name = name.replace(['-', '_'], " ").trim().to_string();
                     return (name, version.to_string());
                 }
             }
         }
 
         // No version found
-        (
-            filename.replace('-', " ").replace('_', " "),
-            "1.0.0".to_string(),
-        )
+        (filename.replace(['-', '_'], " "), "1.0.0".to_string())
     }
 
     /// Extract Nexus mod ID from filename
@@ -985,7 +1185,7 @@ impl ModManager {
             // Has timestamp - find first substantial number (3+ digits) as mod_id
             for num in &numbers[..numbers.len() - 1] {
                 let digits = num.to_string().len();
-                if digits >= 3 && digits <= 7 {
+                if (3..=7).contains(&digits) {
                     tracing::debug!(
                         "Parsed '{}' -> mod_id: {}, file_id: {}",
                         filename,
@@ -995,7 +1195,7 @@ impl ModManager {
                     return Some((*num, last_num));
                 }
             }
-        } else if last_digits >= 3 && last_digits <= 7 {
+        } else if (3..=7).contains(&last_digits) {
             // No timestamp - last number is probably the mod_id
             tracing::debug!(
                 "Parsed '{}' (no timestamp) -> mod_id: {}",
@@ -1229,6 +1429,41 @@ impl ModManager {
     }
 }
 
+/// Top-level folders that mark a directory as a game Data folder.
+/// Includes BodySlide/CBBE specific directories.
+const DATA_INDICATORS: &[&str] = &[
+    "meshes",
+    "textures",
+    "scripts",
+    "interface",
+    "sound",
+    "skse",
+    "calientetools", // BodySlide files
+    "shapedata",     // BodySlide presets
+    "tools",         // Various mod tools
+    "strings",       // Translation files
+    "seq",           // Animation sequences
+    "music",         // Music files
+    "video",         // Video files
+    "shadersfx",     // Shader effects
+    "bookart",       // Morrowind book images
+    "icons",         // Morrowind inventory icons
+    "splash",        // Morrowind loading screens
+];
+
+/// Whether a lowercase entry name is a plugin or a Data folder indicator
+fn is_data_entry(name: &str) -> bool {
+    DATA_INDICATORS.contains(&name) || is_plugin_name(name)
+}
+
+fn is_plugin_name(name: &str) -> bool {
+    name.ends_with(".esp")
+        || name.ends_with(".esm")
+        || name.ends_with(".esl")
+        || name.ends_with(".omwaddon")
+        || name.ends_with(".omwscripts")
+}
+
 /// Find the actual data root (handles nested folders like "ModName/Data/")
 ///
 /// # FOMOD Support Status
@@ -1248,25 +1483,6 @@ impl ModManager {
 fn find_data_root(path: &Path) -> Result<PathBuf> {
     use crate::mods::fomod;
 
-    // Check for common data indicators
-    // Includes BodySlide/CBBE specific directories
-    let data_indicators = [
-        "meshes",
-        "textures",
-        "scripts",
-        "interface",
-        "sound",
-        "skse",
-        "calientetools", // BodySlide files
-        "shapedata",     // BodySlide presets
-        "tools",         // Various mod tools
-        "strings",       // Translation files
-        "seq",           // Animation sequences
-        "music",         // Music files
-        "video",         // Video files
-        "shadersfx",     // Shader effects
-    ];
-
     // If this is a FOMOD with numbered folders, look for "00" Required folder
     if fomod::has_numbered_folders(path) {
         for entry in std::fs::read_dir(path)? {
@@ -1292,11 +1508,7 @@ fn find_data_root(path: &Path) -> Result<PathBuf> {
     for entry in std::fs::read_dir(path)? {
         let entry = entry?;
         let name = entry.file_name().to_string_lossy().to_lowercase();
-        if data_indicators.contains(&name.as_str())
-            || name.ends_with(".esp")
-            || name.ends_with(".esm")
-            || name.ends_with(".esl")
-        {
+        if is_data_entry(&name) {
             return Ok(path.to_path_buf());
         }
     }
@@ -1312,7 +1524,7 @@ fn find_data_root(path: &Path) -> Result<PathBuf> {
             if dir_name.len() >= 2
                 && dir_name
                     .chars()
-                    .nth(0)
+                    .next()
                     .map(|c| c.is_ascii_digit())
                     .unwrap_or(false)
                 && dir_name
@@ -1326,11 +1538,7 @@ fn find_data_root(path: &Path) -> Result<PathBuf> {
             for subentry in std::fs::read_dir(&subdir)? {
                 let subentry = subentry?;
                 let name = subentry.file_name().to_string_lossy().to_lowercase();
-                if data_indicators.contains(&name.as_str())
-                    || name.ends_with(".esp")
-                    || name.ends_with(".esm")
-                    || name.ends_with(".esl")
-                {
+                if is_data_entry(&name) {
                     return Ok(subdir);
                 }
             }
@@ -1357,6 +1565,15 @@ fn collect_files(root: &Path) -> Result<Vec<String>> {
 }
 
 /// Extract plugin filenames (.esp/.esm/.esl) from mod file records.
+/// Total size of indexed files
+fn mod_files_size(files: &[ModFileRecord]) -> u64 {
+    files
+        .iter()
+        .filter_map(|f| f.size)
+        .map(|s| s.max(0) as u64)
+        .sum()
+}
+
 fn plugin_filenames_from_mod_files(files: &[ModFileRecord]) -> Vec<String> {
     let mut plugins = std::collections::BTreeSet::new();
     for file in files {
@@ -0,0 +1,191 @@
+//! Display names for newly installed mods
+//!
+//! The name comes from `[install] name_template`, filled in from the archive
+//! and whatever Nexus metadata the caller knows. `[install] name_conflict`
+//! decides whether a name that's already taken fails the install or gets a
+//! numbered suffix.
+
+use crate::config::NameConflictPolicy;
+use crate::error::Conflict;
+use anyhow::{bail, Result};
+
+/// Placeholders `[install] name_template` understands
+pub const NAME_PLACEHOLDERS: &[&str] = &[
+    "name",
+    "nexus_name",
+    "archive_name",
+    "archive_stem",
+    "version",
+    "nexus_id",
+    "file_id",
+];
+
+/// Values for the name template's placeholders
+#[derive(Debug, Clone, Default)]
+pub struct NameParts<'a> {
+    /// Archive file name without extension
+    pub archive_stem: &'a str,
+    /// Name parsed from the archive (version and separators stripped)
+    pub archive_name: &'a str,
+    /// Name the caller knows the mod by, usually its Nexus name
+    pub nexus_name: Option<&'a str>,
+    pub version: &'a str,
+    pub nexus_mod_id: Option<i64>,
+    pub nexus_file_id: Option<i64>,
+}
+
+/// Fill in `template`. Unknown placeholders are kept as written; brackets
+/// left empty by a missing value are dropped along with extra spaces. Falls
+/// back to `{name}` when the result is empty.
+pub fn render_mod_name(template: &str, parts: &NameParts) -> String {
+    let name = parts.nexus_name.unwrap_or(parts.archive_name);
+    let value = |key: &str| -> Option<String> {
+        Some(match key {
+            "name" => name.to_string(),
+            "nexus_name" => parts.nexus_name.unwrap_or_default().to_string(),
+            "archive_name" => parts.archive_name.to_string(),
+            "archive_stem" => parts.archive_stem.to_string(),
+            "version" => parts.version.to_string(),
+            "nexus_id" => parts
+                .nexus_mod_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            "file_id" => parts
+                .nexus_file_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            _ => return None,
+        })
+    };
+
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after
+            .find('}')
+            .and_then(|end| Some((value(&after[..end])?, end)))
+        {
+            Some((text, end)) => {
+                out.push_str(&text);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+
+    for empty in ["[]", "()", "[ ]", "( )"] {
+        out = out.replace(empty, "");
+    }
+    // Path separators would nest the staging folder
+    let out = out.replace(['/', '\\'], " ");
+    let out = out.split_whitespace().collect::<Vec<_>>().join(" ");
+    let out = out.trim_matches(|c: char| c == '-' || c == '_' || c.is_whitespace());
+    if out.is_empty() {
+        name.to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+/// Check that `template` only uses known placeholders
+pub fn validate_name_template(template: &str) -> Result<()> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            bail!("Unclosed '{{' in name template '{}'", template);
+        };
+        let key = &after[..end];
+        if !NAME_PLACEHOLDERS.contains(&key) {
+            bail!(
+                "Unknown placeholder '{{{}}}' in name template (known: {})",
+                key,
+                NAME_PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{}}}", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        rest = &after[end + 1..];
+    }
+    Ok(())
+}
+
+/// `name`, or under [`NameConflictPolicy::Rename`] the first free
+/// "`name` (2)", "`name` (3)", ... when `taken` reports it's in use
+pub(super) fn resolve_name_conflict(
+    name: String,
+    policy: NameConflictPolicy,
+    mut taken: impl FnMut(&str) -> Result<bool>,
+) -> Result<String> {
+    if !taken(&name)? {
+        return Ok(name);
+    }
+    match policy {
+        NameConflictPolicy::Error => {
+            bail!(Conflict::new(format!(
+                "Mod '{}' is already installed",
+                name
+            )))
+        }
+        NameConflictPolicy::Rename => {
+            for n in 2.. {
+                let candidate = format!("{} ({})", name, n);
+                if !taken(&candidate)? {
+                    return Ok(candidate);
+                }
+            }
+            unreachable!("ran out of numbered names")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_and_resolve_conflicts() {
+        let parts = NameParts {
+            archive_stem: "SkyUI_5_2_SE-12604-5-2SE",
+            archive_name: "SkyUI",
+            nexus_name: Some("SkyUI"),
+            version: "5.2",
+            nexus_mod_id: Some(12604),
+            nexus_file_id: None,
+        };
+        assert_eq!(render_mod_name("{name}", &parts), "SkyUI");
+        assert_eq!(
+            render_mod_name("{nexus_name} [{version}]", &parts),
+            "SkyUI [5.2]"
+        );
+        assert_eq!(
+            render_mod_name("{archive_stem}", &parts),
+            "SkyUI_5_2_SE-12604-5-2SE"
+        );
+        // Missing values don't leave empty brackets behind
+        assert_eq!(render_mod_name("{name} ({file_id})", &parts), "SkyUI");
+        assert_eq!(render_mod_name("{oops} {name}", &parts), "{oops} SkyUI");
+        assert!(validate_name_template("{nexus_name} [{version}]").is_ok());
+        assert!(validate_name_template("{oops}").is_err());
+
+        let installed = ["SkyUI", "SkyUI (2)"];
+        let taken = |name: &str| Ok(installed.contains(&name));
+        assert_eq!(
+            resolve_name_conflict("SkyUI".into(), NameConflictPolicy::Rename, taken).unwrap(),
+            "SkyUI (3)"
+        );
+        assert!(resolve_name_conflict("SkyUI".into(), NameConflictPolicy::Error, taken).is_err());
+        assert_eq!(
+            resolve_name_conflict("Lux".into(), NameConflictPolicy::Error, taken).unwrap(),
+            "Lux"
+        );
+    }
+}
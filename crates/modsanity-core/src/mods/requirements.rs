@@ -0,0 +1,346 @@
+//! Per-mod launch requirements and the pre-launch guard
+//!
+//! Mods can be tagged as needing the script extender or an ENB. Mods that
+//! ship script extender plugins or ENB presets are detected automatically.
+//! Before launching, the requirements of enabled mods are checked against
+//! the executable about to be started and the files present in the game root.
+
+use crate::db::ModRecord;
+use crate::games::{check_script_extender, Game, GameType, ScriptExtenderCheck};
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Something a mod needs at runtime beyond its own files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LaunchRequirement {
+    /// SKSE / F4SE / SFSE, started through its loader
+    ScriptExtender,
+    /// ENB binaries (d3d11.dll) in the game root
+    Enb,
+}
+
+impl LaunchRequirement {
+    /// Stable ID stored in the database
+    pub fn id(&self) -> &'static str {
+        match self {
+            LaunchRequirement::ScriptExtender => "script-extender",
+            LaunchRequirement::Enb => "enb",
+        }
+    }
+
+    /// Parse a stored ID or a user-facing alias such as "skse"
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "script-extender" | "se" | "skse" | "skse64" | "sksevr" | "f4se" | "f4sevr"
+            | "sfse" => Some(LaunchRequirement::ScriptExtender),
+            "enb" => Some(LaunchRequirement::Enb),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self, game_type: GameType) -> &'static str {
+        match self {
+            LaunchRequirement::ScriptExtender => game_type.script_extender_name(),
+            LaunchRequirement::Enb => "ENB",
+        }
+    }
+}
+
+/// A launch requirement of one enabled mod
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModRequirement {
+    pub mod_name: String,
+    pub requirement: LaunchRequirement,
+    /// Inferred from the mod's files rather than tagged by the user
+    pub detected: bool,
+}
+
+/// A reason not to launch with the chosen executable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchProblem {
+    pub requirement: LaunchRequirement,
+    /// Mods that need the missing requirement
+    pub mods: Vec<String>,
+    pub message: String,
+}
+
+impl super::ModManager {
+    /// Tag or untag a mod with a launch requirement
+    pub async fn set_mod_requirement(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        requirement: LaunchRequirement,
+        required: bool,
+    ) -> Result<()> {
+        let Some(m) = self.db.get_mod(game_id, mod_name)? else {
+            bail!("Mod '{}' not found", mod_name);
+        };
+        if required {
+            self.db
+                .add_mod_requirement(game_id, &m.name, requirement.id())?;
+        } else {
+            self.db
+                .remove_mod_requirement(game_id, &m.name, requirement.id())?;
+        }
+        Ok(())
+    }
+
+    /// Requirements of all enabled mods, tagged or detected from their files
+    pub fn launch_requirements(&self, game: &Game) -> Result<Vec<ModRequirement>> {
+        self.launch_requirements_of(game, |m| m.enabled)
+    }
+
+    /// Requirements of the mods `enabled` selects, e.g. those a profile
+    /// would enable
+    pub fn launch_requirements_of(
+        &self,
+        game: &Game,
+        enabled: impl Fn(&ModRecord) -> bool,
+    ) -> Result<Vec<ModRequirement>> {
+        let mut tagged: BTreeMap<String, Vec<LaunchRequirement>> = BTreeMap::new();
+        for (mod_name, id) in self.db.get_mod_requirements(&game.id)? {
+            if let Some(req) = LaunchRequirement::parse(&id) {
+                tagged.entry(mod_name).or_default().push(req);
+            }
+        }
+
+        let mut out = Vec::new();
+        for m in self.db.get_mods_for_game(&game.id)? {
+            let Some(mod_id) = m.id.filter(|_| enabled(&m)) else {
+                continue;
+            };
+            let explicit = tagged.remove(&m.name).unwrap_or_default();
+            let files = self.db.get_mod_files(mod_id)?;
+            let detected = detect_requirements(
+                game.game_type,
+                files.iter().map(|f| f.relative_path.as_str()),
+            );
+            for requirement in [LaunchRequirement::ScriptExtender, LaunchRequirement::Enb] {
+                let is_tagged = explicit.contains(&requirement);
+                if is_tagged || detected.contains(&requirement) {
+                    out.push(ModRequirement {
+                        mod_name: m.name.clone(),
+                        requirement,
+                        detected: !is_tagged,
+                    });
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Compare the game version with the script extender in the game root
+    /// or shipped by an enabled mod
+    pub fn script_extender_check(&self, game: &Game) -> Result<ScriptExtenderCheck> {
+        let mod_dirs: Vec<PathBuf> = self
+            .db
+            .get_mods_for_game(&game.id)?
+            .into_iter()
+            .filter(|m| m.enabled)
+            .map(|m| PathBuf::from(m.install_path))
+            .collect();
+        Ok(check_script_extender(game, &mod_dirs))
+    }
+}
+
+/// Infer requirements from a mod's Data-relative file paths
+pub fn detect_requirements<'a>(
+    game_type: GameType,
+    files: impl IntoIterator<Item = &'a str>,
+) -> Vec<LaunchRequirement> {
+    let plugin_dir = format!("{}/plugins/", game_type.script_extender_dir());
+    let mut out = Vec::new();
+    for file in files {
+        let lower = file.replace('\\', "/").to_lowercase();
+        let lower = lower.strip_prefix("data/").unwrap_or(&lower);
+        let requirement = if lower.starts_with(&plugin_dir) && lower.ends_with(".dll") {
+            LaunchRequirement::ScriptExtender
+        } else if lower == "enbseries.ini" || lower.starts_with("enbseries/") {
+            LaunchRequirement::Enb
+        } else {
+            continue;
+        };
+        if !out.contains(&requirement) {
+            out.push(requirement);
+        }
+    }
+    out.sort();
+    out
+}
+
+/// The executable to start by default: the script extender loader when
+/// any enabled mod needs it, otherwise the plain game executable
+pub fn default_launch_executable(game: &Game, requirements: &[ModRequirement]) -> String {
+    let needs_loader = requirements
+        .iter()
+        .any(|r| r.requirement == LaunchRequirement::ScriptExtender);
+    match game.game_type.script_extender_loader() {
+        Some(loader) if needs_loader => loader.to_string(),
+        _ => game.executable.clone(),
+    }
+}
+
+/// Check that launching `executable` satisfies every requirement
+pub fn check_launch(
+    game: &Game,
+    executable: &str,
+    requirements: &[ModRequirement],
+) -> Vec<LaunchProblem> {
+    let mods_needing = |requirement: LaunchRequirement| -> Vec<String> {
+        requirements
+            .iter()
+            .filter(|r| r.requirement == requirement)
+            .map(|r| r.mod_name.clone())
+            .collect()
+    };
+    let game_type = game.game_type;
+    let loader = game_type.script_extender_loader();
+    let extender = game_type.script_extender_name();
+    let mut problems = Vec::new();
+
+    let mods = mods_needing(LaunchRequirement::ScriptExtender);
+    if !mods.is_empty() {
+        let exe_name = Path::new(executable)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match loader {
+            None => problems.push(LaunchProblem {
+                requirement: LaunchRequirement::ScriptExtender,
+                message: format!(
+                    "{} mod(s) need {}, which {} doesn't run",
+                    mods.len(),
+                    extender,
+                    game.name
+                ),
+                mods,
+            }),
+            Some(loader) if find_root_file(&game.binaries_path(), loader).is_none() => {
+                problems.push(LaunchProblem {
+                    requirement: LaunchRequirement::ScriptExtender,
+                    message: format!(
+                        "{} mod(s) need {}, but {} is not in the game folder. Install {} first.",
+                        mods.len(),
+                        extender,
+                        loader,
+                        extender
+                    ),
+                    mods,
+                });
+            }
+            Some(loader) if !exe_name.eq_ignore_ascii_case(loader) => {
+                problems.push(LaunchProblem {
+                    requirement: LaunchRequirement::ScriptExtender,
+                    message: format!(
+                        "{} mod(s) need {}; launch {} instead of {}",
+                        mods.len(),
+                        extender,
+                        loader,
+                        exe_name
+                    ),
+                    mods,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    let mods = mods_needing(LaunchRequirement::Enb);
+    if !mods.is_empty() && find_root_file(&game.binaries_path(), "d3d11.dll").is_none() {
+        problems.push(LaunchProblem {
+            requirement: LaunchRequirement::Enb,
+            message: format!(
+                "{} mod(s) need ENB, but d3d11.dll is not in the game folder. Install the ENB binaries first.",
+                mods.len()
+            ),
+            mods,
+        });
+    }
+
+    problems
+}
+
+/// Case-insensitive lookup of a file directly inside `dir`
+fn find_root_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|e| e.file_name().to_string_lossy().eq_ignore_ascii_case(name))
+        .map(|e| e.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirement(name: &str, requirement: LaunchRequirement) -> ModRequirement {
+        ModRequirement {
+            mod_name: name.to_string(),
+            requirement,
+            detected: true,
+        }
+    }
+
+    #[test]
+    fn test_detect_requirements() {
+        let files = [
+            "SKSE/Plugins/po3_Tweaks.dll",
+            "SKSE/Plugins/po3_Tweaks.ini",
+            "meshes/foo.nif",
+        ];
+        assert_eq!(
+            detect_requirements(GameType::SkyrimSE, files),
+            vec![LaunchRequirement::ScriptExtender]
+        );
+        assert_eq!(
+            detect_requirements(GameType::Fallout4, files),
+            Vec::<LaunchRequirement>::new()
+        );
+        assert_eq!(
+            detect_requirements(GameType::SkyrimSE, ["enbseries/effect.txt.ini"]),
+            vec![LaunchRequirement::Enb]
+        );
+    }
+
+    #[test]
+    fn test_check_launch_requires_loader() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::SkyrimSE, dir.path().to_path_buf());
+        let reqs = vec![requirement("Tweaks", LaunchRequirement::ScriptExtender)];
+
+        // Loader missing entirely
+        let problems = check_launch(&game, "SkyrimSE.exe", &reqs);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("not in the game folder"));
+
+        std::fs::write(dir.path().join("SKSE64_Loader.exe"), b"").unwrap();
+        assert_eq!(default_launch_executable(&game, &reqs), "skse64_loader.exe");
+        let problems = check_launch(&game, "SkyrimSE.exe", &reqs);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("launch skse64_loader.exe"));
+        assert!(check_launch(&game, "skse64_loader.exe", &reqs).is_empty());
+        assert!(check_launch(&game, "SkyrimSE.exe", &[]).is_empty());
+
+        // OpenMW has no loader to switch to
+        let openmw = Game::new(GameType::OpenMW, dir.path().to_path_buf());
+        assert_eq!(default_launch_executable(&openmw, &reqs), openmw.executable);
+        let problems = check_launch(&openmw, &openmw.executable, &reqs);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("doesn't run"));
+    }
+
+    #[test]
+    fn test_parse_aliases() {
+        assert_eq!(
+            LaunchRequirement::parse("SKSE"),
+            Some(LaunchRequirement::ScriptExtender)
+        );
+        assert_eq!(
+            LaunchRequirement::parse(LaunchRequirement::Enb.id()),
+            Some(LaunchRequirement::Enb)
+        );
+        assert_eq!(LaunchRequirement::parse("reshade"), None);
+    }
+}
@@ -0,0 +1,639 @@
+//! Archive retention and version rollback
+//!
+//! Every installed archive is kept under `archives/<game>/<mod>/` so a mod can
+//! be reinstalled at an earlier version without re-downloading it.
+
+use super::{
+    apply_layout, check_layout, extract_archive, fomod, plugin_filenames_from_mod_files,
+    FomodInstallContext, InstallResult, InstalledMod, ProgressCallback,
+};
+use crate::db::{ModArchiveRecord, ModRecord};
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+impl super::ModManager {
+    /// Keep a copy of an installed archive for later rollback.
+    ///
+    /// Returns the retained archive ID, or `None` when retention is disabled.
+    pub async fn retain_archive(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        version: &str,
+        nexus_file_id: Option<i64>,
+        archive_path: &Path,
+        active: bool,
+    ) -> Result<Option<i64>> {
+        let (enabled, max_versions, archives_dir) = {
+            let config = self.config.read().await;
+            (
+                config.archive_retention.enabled,
+                config.archive_retention.max_versions,
+                config.game_archives_dir(game_id),
+            )
+        };
+        if !enabled {
+            return Ok(None);
+        }
+
+        let existing = self.db.get_mod_archives(game_id, mod_name)?;
+        let source = archive_path.to_string_lossy().to_string();
+        let archive_id = if let Some(known) = existing.iter().find(|a| a.archive_path == source) {
+            // Installing from an already-retained archive; nothing to copy
+            known.id.unwrap_or_default()
+        } else {
+            let file_name = archive_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .context("Archive path has no file name")?;
+            let dest_dir = archives_dir.join(mod_name);
+            tokio::fs::create_dir_all(&dest_dir)
+                .await
+                .context("Failed to create archive retention directory")?;
+
+            // Prefix with a timestamp so re-used archive names never collide
+            let dest = dest_dir.join(format!(
+                "{}-{}",
+                chrono::Utc::now().format("%Y%m%d%H%M%S"),
+                file_name
+            ));
+            if std::fs::hard_link(archive_path, &dest).is_err() {
+                tokio::fs::copy(archive_path, &dest)
+                    .await
+                    .with_context(|| format!("Failed to retain archive {}", dest.display()))?;
+            }
+
+            self.db.insert_mod_archive(&ModArchiveRecord {
+                id: None,
+                game_id: game_id.to_string(),
+                mod_name: mod_name.to_string(),
+                version: version.to_string(),
+                nexus_file_id,
+                archive_path: dest.to_string_lossy().to_string(),
+                active: false,
+                retained_at: chrono::Utc::now().to_rfc3339(),
+            })?
+        };
+
+        if active {
+            self.db
+                .set_active_mod_archive(game_id, mod_name, archive_id)?;
+        }
+
+        self.prune_archives(game_id, mod_name, max_versions.max(1))
+            .await?;
+        Ok(Some(archive_id))
+    }
+
+    /// List retained archives for a mod (newest first)
+    pub async fn list_retained_archives(
+        &self,
+        game_id: &str,
+        name: &str,
+    ) -> Result<Vec<ModArchiveRecord>> {
+        self.db.get_mod_archives(game_id, name)
+    }
+
+    /// Update an installed mod from a newer archive.
+    ///
+    /// The previous archive stays retained, and enabled state, priority,
+    /// category and FOMOD choices carry over to the new version.
+    pub async fn update_from_archive(
+        &self,
+        game_id: &str,
+        name: &str,
+        archive_path: &str,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<InstallResult> {
+        let archive_name = archive_stem(Path::new(archive_path)).unwrap_or("unknown");
+        let (_, version) = Self::parse_mod_name(archive_name);
+        let nexus_file_id = Self::parse_nexus_ids(archive_name).map(|(_, file_id)| file_id);
+        self.update_to_version(
+            game_id,
+            name,
+            archive_path,
+            &version,
+            nexus_file_id,
+            progress_callback,
+        )
+        .await
+    }
+
+    /// Update an installed mod from an archive whose version is already known,
+    /// such as a Nexus download saved under a generated file name.
+    pub async fn update_to_version(
+        &self,
+        game_id: &str,
+        name: &str,
+        archive_path: &str,
+        version: &str,
+        nexus_file_id: Option<i64>,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<InstallResult> {
+        let archive_path = Path::new(archive_path);
+        if !archive_path.exists() {
+            bail!("Archive not found: {}", archive_path.display());
+        }
+
+        let existing = self
+            .db
+            .get_mod(game_id, name)?
+            .ok_or_else(|| anyhow::anyhow!("Mod '{}' not found", name))?;
+
+        let archive_id = self
+            .retain_archive(game_id, name, version, nexus_file_id, archive_path, false)
+            .await?;
+        let retained_path = match archive_id {
+            Some(id) => self
+                .db
+                .get_mod_archives(game_id, name)?
+                .into_iter()
+                .find(|a| a.id == Some(id))
+                .map(|a| PathBuf::from(a.archive_path))
+                .unwrap_or_else(|| archive_path.to_path_buf()),
+            None => archive_path.to_path_buf(),
+        };
+
+        let result = self
+            .reinstall_from_archive(
+                &existing,
+                &retained_path,
+                version,
+                nexus_file_id,
+                progress_callback,
+            )
+            .await?;
+
+        if let Some(id) = archive_id {
+            self.db.set_active_mod_archive(game_id, name, id)?;
+        }
+        Ok(result)
+    }
+
+    /// Reinstall the previously retained version of a mod.
+    pub async fn rollback_mod(
+        &self,
+        game_id: &str,
+        name: &str,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<InstallResult> {
+        let existing = self
+            .db
+            .get_mod(game_id, name)?
+            .ok_or_else(|| anyhow::anyhow!("Mod '{}' not found", name))?;
+
+        let archives: Vec<ModArchiveRecord> = self
+            .db
+            .get_mod_archives(game_id, name)?
+            .into_iter()
+            .filter(|a| Path::new(&a.archive_path).exists())
+            .collect();
+        let target = rollback_target(&archives)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No earlier archive retained for '{}'", name))?;
+
+        tracing::info!(
+            "Rolling back {} from {} to {}",
+            name,
+            existing.version,
+            target.version
+        );
+
+        let result = self
+            .reinstall_from_archive(
+                &existing,
+                Path::new(&target.archive_path),
+                &target.version,
+                target.nexus_file_id,
+                progress_callback,
+            )
+            .await?;
+
+        if let Some(id) = target.id {
+            self.db.set_active_mod_archive(game_id, name, id)?;
+        }
+        Ok(result)
+    }
+
+    /// Wipe a mod's staging folder and re-extract it from its original archive.
+    ///
+    /// Priority, category, Nexus IDs and description are kept as-is.
+    pub async fn reinstall_mod(
+        &self,
+        game_id: &str,
+        name: &str,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<InstallResult> {
+        let existing = self
+            .db
+            .get_mod(game_id, name)?
+            .ok_or_else(|| anyhow::anyhow!("Mod '{}' not found", name))?;
+
+        let (archive_path, retained_id) = match self.find_original_archive(&existing).await? {
+            Some(found) => found,
+            None => bail!(
+                "No archive found for '{}' (checked retained archives and the downloads folder)",
+                name
+            ),
+        };
+
+        tracing::info!(
+            "Reinstalling {} v{} from {}",
+            existing.name,
+            existing.version,
+            archive_path.display()
+        );
+
+        let result = self
+            .reinstall_from_archive(
+                &existing,
+                &archive_path,
+                &existing.version,
+                existing.nexus_file_id,
+                progress_callback,
+            )
+            .await?;
+
+        match retained_id {
+            Some(id) => self.db.set_active_mod_archive(game_id, name, id)?,
+            None => {
+                // Found in downloads; keep a copy so the next reinstall does not depend on it
+                self.retain_archive(
+                    game_id,
+                    name,
+                    &existing.version,
+                    existing.nexus_file_id,
+                    &archive_path,
+                    true,
+                )
+                .await?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Locate the archive an installed mod came from.
+    ///
+    /// Retained archives are preferred (the active one, then one with the same
+    /// version); otherwise the downloads folder is searched by Nexus file ID,
+    /// then by parsed name and version. Returns the path and, for retained
+    /// archives, the retention record ID.
+    async fn find_original_archive(
+        &self,
+        existing: &ModRecord,
+    ) -> Result<Option<(PathBuf, Option<i64>)>> {
+        let retained: Vec<ModArchiveRecord> = self
+            .db
+            .get_mod_archives(&existing.game_id, &existing.name)?
+            .into_iter()
+            .filter(|a| Path::new(&a.archive_path).exists())
+            .collect();
+        if let Some(archive) = retained
+            .iter()
+            .find(|a| a.active)
+            .or_else(|| retained.iter().find(|a| a.version == existing.version))
+        {
+            return Ok(Some((PathBuf::from(&archive.archive_path), archive.id)));
+        }
+
+        let downloads_dir = self.config.read().await.downloads_dir()?;
+        let Ok(entries) = std::fs::read_dir(&downloads_dir) else {
+            return Ok(None);
+        };
+        let candidates: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.is_file()
+                    && p.extension()
+                        .and_then(|e| e.to_str())
+                        .map(|e| matches!(e.to_ascii_lowercase().as_str(), "zip" | "7z" | "rar"))
+                        .unwrap_or(false)
+            })
+            .collect();
+
+        let by_file_id = existing.nexus_file_id.and_then(|file_id| {
+            candidates.iter().find(|p| {
+                archive_stem(p)
+                    .and_then(Self::parse_nexus_ids)
+                    .map(|(_, id)| id == file_id)
+                    .unwrap_or(false)
+            })
+        });
+        let found = by_file_id.or_else(|| {
+            candidates.iter().find(|p| {
+                archive_stem(p)
+                    .map(|stem| {
+                        let (parsed_name, version) = Self::parse_mod_name(stem);
+                        parsed_name.eq_ignore_ascii_case(&existing.name)
+                            && version == existing.version
+                    })
+                    .unwrap_or(false)
+            })
+        });
+
+        Ok(found.map(|p| (p.clone(), None)))
+    }
+
+    /// Replace an installed mod's files with the contents of `archive_path`,
+    /// keeping its database identity (enabled, priority, category, Nexus IDs).
+    async fn reinstall_from_archive(
+        &self,
+        existing: &ModRecord,
+        archive_path: &Path,
+        version: &str,
+        nexus_file_id: Option<i64>,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<InstallResult> {
+        let mod_id = existing
+            .id
+            .ok_or_else(|| anyhow::anyhow!("Mod '{}' has no database ID", existing.name))?;
+        let install_path = PathBuf::from(&existing.install_path);
+        let parent = install_path
+            .parent()
+            .context("Mod install path has no parent directory")?;
+
+        // Extract next to the current install so a failed extraction leaves it untouched
+        let temp = parent.join(format!(".{}.reinstall", existing.name));
+        if temp.exists() {
+            tokio::fs::remove_dir_all(&temp).await?;
+        }
+        tokio::fs::create_dir_all(&temp)
+            .await
+            .context("Failed to create temporary install directory")?;
+        if let Err(e) = extract_archive(archive_path, &temp, progress_callback.clone()).await {
+            let _ = tokio::fs::remove_dir_all(&temp).await;
+            return Err(e);
+        }
+
+        let mut installer = None;
+        if fomod::has_fomod(&temp) {
+            match fomod::FomodInstaller::load(&temp) {
+                Ok(loaded) if loaded.requires_wizard() => installer = Some(loaded),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to parse FOMOD installer: {}, falling back to simple install",
+                    e
+                ),
+            }
+        }
+        if installer.is_none() {
+            let layout = check_layout(&temp)?;
+            if let Some(issue) = layout.blocking_issue() {
+                let _ = tokio::fs::remove_dir_all(&temp).await;
+                bail!(
+                    "Cannot install '{}': {}",
+                    existing.name,
+                    issue.description()
+                );
+            }
+            for issue in &layout.issues {
+                tracing::warn!(
+                    "Fixed layout of '{}': {}",
+                    existing.name,
+                    issue.description()
+                );
+            }
+            apply_layout(&temp, &layout).await?;
+        }
+
+        // Swap the freshly extracted tree into place
+        if install_path.exists() {
+            tokio::fs::remove_dir_all(&install_path)
+                .await
+                .context("Failed to remove previous mod files")?;
+        }
+        tokio::fs::rename(&temp, &install_path)
+            .await
+            .context("Failed to move reinstalled files into place")?;
+
+        if let Some(installer) = installer {
+            // Installer paths were resolved against the temporary directory
+            let installer = fomod::FomodInstaller::load(&install_path).unwrap_or(installer);
+            let context = FomodInstallContext {
+                game_id: existing.game_id.clone(),
+                mod_name: existing.name.clone(),
+                version: version.to_string(),
+                staging_path: install_path.clone(),
+                installer,
+                priority: existing.priority,
+                existing_mod_id: Some(mod_id),
+                nexus_mod_id: existing.nexus_mod_id,
+                nexus_file_id,
+            };
+
+            let env = self.fomod_environment(&existing.game_id)?;
+            let saved = self
+                .saved_fomod_choice(&existing.game_id, mod_id)
+                .await?
+                .and_then(|plan| plan.to_wizard_state(&context.installer, env));
+            let Some(wizard) = saved else {
+                tracing::info!(
+                    "No reusable FOMOD choices for {}, wizard required",
+                    existing.name
+                );
+                return Ok(InstallResult::RequiresWizard(context));
+            };
+
+            self.complete_fomod_install(&context, &wizard, progress_callback)
+                .await?;
+            if let Some(mut record) = self.db.get_mod_by_id(mod_id)? {
+                record.nexus_file_id = nexus_file_id.or(record.nexus_file_id);
+                self.db.update_mod(&record)?;
+                return Ok(InstallResult::Completed(InstalledMod::from(record)));
+            }
+            bail!("Mod '{}' disappeared during reinstall", existing.name);
+        }
+
+        let files = super::index::list_files(&install_path)?;
+        let mut record = existing.clone();
+        record.version = version.to_string();
+        record.nexus_file_id = nexus_file_id.or(existing.nexus_file_id);
+        record.file_count = files.len() as i32;
+        record.updated_at = chrono::Utc::now().to_rfc3339();
+        self.db.update_mod(&record)?;
+
+        let file_records = super::index::index_files(&install_path, &files, mod_id).await?;
+        self.db.delete_mod_files(mod_id)?;
+        self.db.insert_mod_files(mod_id, &file_records)?;
+        let plugin_files = plugin_filenames_from_mod_files(&file_records);
+        self.db
+            .replace_mod_plugins(mod_id, &existing.game_id, &plugin_files)?;
+
+        Ok(InstallResult::Completed(InstalledMod::from(record)))
+    }
+
+    /// Delete every retained archive of a mod (used when the mod is removed).
+    pub(super) async fn discard_retained_archives(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+    ) -> Result<()> {
+        self.prune_archives(game_id, mod_name, 0).await
+    }
+
+    /// Drop the oldest retained archives beyond the configured limit.
+    ///
+    /// A limit of zero removes all of them, including the active archive.
+    async fn prune_archives(
+        &self,
+        game_id: &str,
+        mod_name: &str,
+        max_versions: usize,
+    ) -> Result<()> {
+        let archives = self.db.get_mod_archives(game_id, mod_name)?;
+        let doomed = if max_versions == 0 {
+            archives.iter().collect()
+        } else {
+            archives_to_prune(&archives, max_versions)
+        };
+        for archive in doomed {
+            let path = Path::new(&archive.archive_path);
+            if path.exists() {
+                if let Err(e) = tokio::fs::remove_file(path).await {
+                    tracing::warn!(
+                        "Failed to remove retained archive {}: {}",
+                        path.display(),
+                        e
+                    );
+                    continue;
+                }
+            }
+            if let Some(id) = archive.id {
+                self.db.delete_mod_archive(id)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn archive_stem(path: &Path) -> Option<&str> {
+    path.file_stem().and_then(|s| s.to_str())
+}
+
+/// Pick the archive to roll back to from a newest-first list.
+///
+/// This is the newest archive older than the active one; without an active
+/// marker the newest archive is assumed to be installed.
+fn rollback_target(archives: &[ModArchiveRecord]) -> Option<&ModArchiveRecord> {
+    let current = archives.iter().position(|a| a.active).unwrap_or(0);
+    archives.get(current + 1)
+}
+
+/// Archives beyond `max_versions` (newest first), never including the active one.
+fn archives_to_prune(archives: &[ModArchiveRecord], max_versions: usize) -> Vec<&ModArchiveRecord> {
+    // The active archive always survives and takes one of the slots
+    let has_active = archives.iter().any(|a| a.active);
+    let mut slots = max_versions.saturating_sub(usize::from(has_active));
+    archives
+        .iter()
+        .filter(|a| {
+            if a.active {
+                false
+            } else if slots > 0 {
+                slots -= 1;
+                false
+            } else {
+                true
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(id: i64, active: bool) -> ModArchiveRecord {
+        ModArchiveRecord {
+            id: Some(id),
+            game_id: "skyrimse".to_string(),
+            mod_name: "SkyUI".to_string(),
+            version: format!("{}.0", id),
+            nexus_file_id: None,
+            archive_path: format!("/tmp/{}.7z", id),
+            active,
+            retained_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_rollback_target() {
+        let archives = vec![archive(3, false), archive(2, true), archive(1, false)];
+        assert_eq!(rollback_target(&archives).unwrap().id, Some(1));
+
+        let archives = vec![archive(3, false), archive(2, false)];
+        assert_eq!(rollback_target(&archives).unwrap().id, Some(2));
+
+        let archives = vec![archive(1, true)];
+        assert!(rollback_target(&archives).is_none());
+    }
+
+    #[test]
+    fn test_archives_to_prune_keeps_active() {
+        let archives = vec![
+            archive(4, false),
+            archive(3, false),
+            archive(2, false),
+            archive(1, true),
+        ];
+        let pruned: Vec<i64> = archives_to_prune(&archives, 2)
+            .iter()
+            .filter_map(|a| a.id)
+            .collect();
+        assert_eq!(pruned, vec![3, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_reinstall_restores_files_and_keeps_metadata() {
+        use crate::db::Database;
+        use crate::testing::Sandbox;
+        use std::sync::Arc;
+        use tokio::sync::RwLock;
+
+        let sandbox = Sandbox::new().unwrap();
+        let db = Arc::new(Database::open(&sandbox.root().join("test.db")).unwrap());
+        let mods =
+            super::super::ModManager::new(Arc::new(RwLock::new(sandbox.config(None))), db.clone());
+        let archive = sandbox
+            .write_archive(
+                "Tweaks-100-1-0-200.zip",
+                &[("Tweaks.esp", b"plugin"), ("textures/a.dds", b"texture")],
+            )
+            .unwrap();
+        mods.install_from_archive(
+            "skyrimse",
+            &archive.to_string_lossy(),
+            None,
+            Some(100),
+            Some(200),
+            Some("Tweaks"),
+        )
+        .await
+        .unwrap();
+
+        let mut record = db.get_mod("skyrimse", "Tweaks").unwrap().unwrap();
+        record.priority = 7;
+        record.description = Some("kept".to_string());
+        db.update_mod(&record).unwrap();
+        let staged = PathBuf::from(&record.install_path);
+        std::fs::remove_file(staged.join("textures/a.dds")).unwrap();
+        std::fs::write(staged.join("stray.txt"), b"left behind").unwrap();
+
+        mods.reinstall_mod("skyrimse", "Tweaks", None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read(staged.join("textures/a.dds")).unwrap(),
+            b"texture"
+        );
+        assert!(!staged.join("stray.txt").exists());
+        let reinstalled = db.get_mod("skyrimse", "Tweaks").unwrap().unwrap();
+        assert_eq!(reinstalled.id, record.id);
+        assert_eq!(reinstalled.priority, 7);
+        assert_eq!(reinstalled.description.as_deref(), Some("kept"));
+        assert_eq!(reinstalled.nexus_mod_id, Some(100));
+        assert_eq!(reinstalled.nexus_file_id, Some(200));
+    }
+}
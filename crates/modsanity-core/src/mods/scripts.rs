@@ -0,0 +1,268 @@
+//! Papyrus script (.pex) override detection
+//!
+//! Indexes compiled scripts shipped by mods (loose or inside their archives)
+//! and by the base game, then reports loose overrides of vanilla scripts —
+//! a common cause of broken saves — and scripts provided by several mods.
+
+use super::bsa::{is_archive_path, list_archive_files};
+use crate::games::Game;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// A mod that provides a script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptProvider {
+    pub mod_name: String,
+    pub priority: i32,
+    /// Archive the script is packed in, `None` for a loose file
+    pub archive: Option<String>,
+}
+
+impl ScriptProvider {
+    pub fn is_loose(&self) -> bool {
+        self.archive.is_none()
+    }
+}
+
+/// A script provided by more than one source
+#[derive(Debug, Clone)]
+pub struct ScriptOverride {
+    /// Lowercase path relative to Data, e.g. "scripts/actor.pex"
+    pub path: String,
+    /// Base game archive (or "loose") the script also comes from
+    pub vanilla_source: Option<String>,
+    /// Providing mods, lowest priority first
+    pub providers: Vec<ScriptProvider>,
+}
+
+impl ScriptOverride {
+    /// Whether a mod ships a loose copy of a vanilla script
+    pub fn overrides_vanilla(&self) -> bool {
+        self.vanilla_source.is_some() && self.providers.iter().any(|p| p.is_loose())
+    }
+
+    /// The provider whose copy the game loads: loose files beat archives,
+    /// then higher priority wins
+    pub fn winner(&self) -> Option<&ScriptProvider> {
+        self.providers
+            .iter()
+            .filter(|p| p.is_loose())
+            .max_by_key(|p| p.priority)
+            .or_else(|| self.providers.iter().max_by_key(|p| p.priority))
+    }
+}
+
+/// Script index summary for one game
+#[derive(Debug, Clone, Default)]
+pub struct ScriptReport {
+    /// Scripts found in the base game (archives and unmanaged loose files)
+    pub game_scripts: usize,
+    /// Distinct scripts provided by enabled mods
+    pub mod_scripts: usize,
+    /// Vanilla overrides first, then scripts shared by several mods
+    pub entries: Vec<ScriptOverride>,
+}
+
+impl ScriptReport {
+    pub fn vanilla_overrides(&self) -> impl Iterator<Item = &ScriptOverride> {
+        self.entries.iter().filter(|e| e.overrides_vanilla())
+    }
+
+    /// Scripts provided by more than one mod
+    pub fn mod_conflicts(&self) -> impl Iterator<Item = &ScriptOverride> {
+        self.entries.iter().filter(|e| e.providers.len() > 1)
+    }
+}
+
+impl super::ModManager {
+    /// Index .pex scripts across enabled mods and the base game
+    pub fn script_overrides(&self, game: &Game) -> Result<ScriptReport> {
+        let mods = self.db.get_mods_for_game(&game.id)?;
+
+        // Every path any mod owns, so deployed files aren't mistaken for vanilla
+        let mut owned = HashSet::new();
+        let mut provided: BTreeMap<String, Vec<ScriptProvider>> = BTreeMap::new();
+        for m in &mods {
+            let Some(mod_id) = m.id else { continue };
+            for file in self.db.get_mod_files(mod_id)? {
+                let relative = normalize_data_path(&file.relative_path);
+                owned.insert(relative.clone());
+                if !m.enabled {
+                    continue;
+                }
+                if is_script(&relative) {
+                    provided.entry(relative).or_default().push(ScriptProvider {
+                        mod_name: m.name.clone(),
+                        priority: m.priority,
+                        archive: None,
+                    });
+                } else if is_archive_path(Path::new(&relative)) {
+                    let path = Path::new(&m.install_path).join(&file.relative_path);
+                    for script in archive_scripts(&path) {
+                        provided.entry(script).or_default().push(ScriptProvider {
+                            mod_name: m.name.clone(),
+                            priority: m.priority,
+                            archive: Some(file_name(&file.relative_path)),
+                        });
+                    }
+                }
+            }
+        }
+
+        let vanilla = index_game_scripts(&game.data_path, &owned);
+
+        let mut entries: Vec<ScriptOverride> = provided
+            .iter()
+            .filter_map(|(path, providers)| {
+                let vanilla_source = vanilla.get(path).cloned();
+                let mut providers = providers.clone();
+                providers.sort_by_key(|p| p.priority);
+                let entry = ScriptOverride {
+                    path: path.clone(),
+                    vanilla_source,
+                    providers,
+                };
+                (entry.overrides_vanilla() || entry.providers.len() > 1).then_some(entry)
+            })
+            .collect();
+        entries.sort_by_key(|e| !e.overrides_vanilla());
+
+        Ok(ScriptReport {
+            game_scripts: vanilla.len(),
+            mod_scripts: provided.len(),
+            entries,
+        })
+    }
+}
+
+/// Scripts shipped by the base game: unmanaged archives in Data and unmanaged
+/// loose files under Data/Scripts. Maps script path to its source.
+fn index_game_scripts(data_path: &Path, owned: &HashSet<String>) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    let Ok(entries) = std::fs::read_dir(data_path) else {
+        return out;
+    };
+
+    let mut archives: Vec<PathBuf> = Vec::new();
+    let mut scripts_dir = None;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        if path.is_dir() && name == "scripts" {
+            scripts_dir = Some(path);
+        } else if is_archive_path(&path) && !owned.contains(&name) {
+            archives.push(path);
+        }
+    }
+    archives.sort();
+
+    for archive in archives {
+        let label = file_name(&archive.to_string_lossy());
+        for script in archive_scripts(&archive) {
+            out.entry(script).or_insert_with(|| label.clone());
+        }
+    }
+
+    if let Some(dir) = scripts_dir {
+        for entry in walkdir::WalkDir::new(&dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let Ok(relative) = entry.path().strip_prefix(data_path) else {
+                continue;
+            };
+            let relative = normalize_data_path(&relative.to_string_lossy());
+            if entry.file_type().is_file() && is_script(&relative) && !owned.contains(&relative) {
+                out.entry(relative).or_insert_with(|| "loose".to_string());
+            }
+        }
+    }
+    out
+}
+
+fn archive_scripts(path: &Path) -> Vec<String> {
+    match list_archive_files(path) {
+        Ok(files) => files.into_iter().filter(|f| is_script(f)).collect(),
+        Err(e) => {
+            tracing::warn!("Skipping archive {}: {:#}", path.display(), e);
+            Vec::new()
+        }
+    }
+}
+
+fn is_script(relative: &str) -> bool {
+    relative.ends_with(".pex")
+}
+
+/// Lowercase, forward-slash path relative to Data
+fn normalize_data_path(relative: &str) -> String {
+    let lower = relative.replace('\\', "/").to_lowercase();
+    let trimmed = lower.trim_start_matches("./");
+    trimmed.strip_prefix("data/").unwrap_or(trimmed).to_string()
+}
+
+fn file_name(path: &str) -> String {
+    path.rsplit(['/', '\\']).next().unwrap_or(path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(name: &str, priority: i32, archive: Option<&str>) -> ScriptProvider {
+        ScriptProvider {
+            mod_name: name.to_string(),
+            priority,
+            archive: archive.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_winner_prefers_loose_then_priority() {
+        let entry = ScriptOverride {
+            path: "scripts/actor.pex".to_string(),
+            vanilla_source: Some("Skyrim - Misc.bsa".to_string()),
+            providers: vec![
+                provider("Loose Low", 1, None),
+                provider("Packed High", 9, Some("PackedHigh.bsa")),
+                provider("Loose Mid", 5, None),
+            ],
+        };
+        assert!(entry.overrides_vanilla());
+        assert_eq!(entry.winner().unwrap().mod_name, "Loose Mid");
+
+        let packed_only = ScriptOverride {
+            providers: vec![provider("Packed", 1, Some("Packed.bsa"))],
+            ..entry
+        };
+        assert!(!packed_only.overrides_vanilla());
+    }
+
+    #[test]
+    fn test_index_game_scripts_skips_managed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let scripts = dir.path().join("Scripts");
+        std::fs::create_dir_all(&scripts).unwrap();
+        std::fs::write(scripts.join("Actor.pex"), b"").unwrap();
+        std::fs::write(scripts.join("ModScript.pex"), b"").unwrap();
+        std::fs::write(dir.path().join("Broken.bsa"), b"junk").unwrap();
+
+        let owned = HashSet::from(["scripts/modscript.pex".to_string()]);
+        let index = index_game_scripts(dir.path(), &owned);
+        assert_eq!(index.len(), 1);
+        assert_eq!(
+            index.get("scripts/actor.pex").map(String::as_str),
+            Some("loose")
+        );
+    }
+
+    #[test]
+    fn test_normalize_data_path() {
+        assert_eq!(
+            normalize_data_path("Data\\Scripts\\Foo.pex"),
+            "scripts/foo.pex"
+        );
+        assert_eq!(normalize_data_path("scripts/foo.PEX"), "scripts/foo.pex");
+    }
+}
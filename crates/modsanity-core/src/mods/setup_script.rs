@@ -0,0 +1,302 @@
+//! Setup scripts: a list of mod operations run as one unit
+//!
+//! A script is a text file with one step per line (`#` starts a comment), or
+//! a TOML file with the same lines in a `steps` array:
+//!
+//! ```text
+//! install "archives/SkyUI 5.2.7z"
+//! enable SkyUI
+//! priority SkyUI 10
+//! deploy
+//! ```
+//!
+//! Every line is parsed before anything runs. If a step fails, the mods the
+//! script installed are removed and the enabled state and priority of the
+//! others are put back, so a script either applies completely or not at all.
+
+use super::{InstallResult, InstalledMod, ModManager};
+use crate::games::Game;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// One operation in a setup script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptStep {
+    /// Install an archive (relative paths are relative to the script)
+    Install(PathBuf),
+    Enable(String),
+    Disable(String),
+    Priority(String, i32),
+    Deploy,
+}
+
+impl ScriptStep {
+    /// Mod the step refers to by name, if any
+    pub fn mod_name(&self) -> Option<&str> {
+        match self {
+            ScriptStep::Enable(name) | ScriptStep::Disable(name) => Some(name),
+            ScriptStep::Priority(name, _) => Some(name),
+            ScriptStep::Install(_) | ScriptStep::Deploy => None,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            ScriptStep::Install(path) => format!("install {}", path.display()),
+            ScriptStep::Enable(name) => format!("enable {}", name),
+            ScriptStep::Disable(name) => format!("disable {}", name),
+            ScriptStep::Priority(name, priority) => format!("priority {} {}", name, priority),
+            ScriptStep::Deploy => "deploy".to_string(),
+        }
+    }
+}
+
+/// A step with the script line it came from (1-based)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptLine {
+    pub line: usize,
+    pub step: ScriptStep,
+}
+
+#[derive(Deserialize)]
+struct TomlScript {
+    steps: Vec<String>,
+}
+
+/// Read a setup script, picking the format by extension (`.toml` or text).
+/// Relative archive paths are resolved against the script's folder.
+pub fn load_setup_script(path: &Path) -> Result<Vec<ScriptLine>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut lines = if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+    {
+        let script: TomlScript =
+            toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?;
+        parse_setup_script(&script.steps.join("\n"))?
+    } else {
+        parse_setup_script(&text)?
+    };
+
+    let base = path.parent().unwrap_or(Path::new("."));
+    for line in &mut lines {
+        if let ScriptStep::Install(archive) = &mut line.step {
+            if archive.is_relative() {
+                *archive = base.join(&*archive);
+            }
+        }
+    }
+    Ok(lines)
+}
+
+/// Parse script text, one step per line. Fails on the first bad line.
+pub fn parse_setup_script(text: &str) -> Result<Vec<ScriptLine>> {
+    let mut steps = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let words = split_words(raw).with_context(|| format!("Line {}", line))?;
+        let Some((command, args)) = words.split_first() else {
+            continue;
+        };
+        let step = match (command.to_ascii_lowercase().as_str(), args) {
+            ("install", [path]) => ScriptStep::Install(PathBuf::from(path)),
+            ("enable", [name]) => ScriptStep::Enable(name.clone()),
+            ("disable", [name]) => ScriptStep::Disable(name.clone()),
+            ("priority", [name, priority]) => {
+                let priority = priority
+                    .parse()
+                    .with_context(|| format!("Line {}: '{}' is not a priority", line, priority))?;
+                ScriptStep::Priority(name.clone(), priority)
+            }
+            ("deploy", []) => ScriptStep::Deploy,
+            ("install" | "enable" | "disable" | "priority" | "deploy", _) => bail!(
+                "Line {}: wrong arguments for '{}' (quote names with spaces)",
+                line,
+                command
+            ),
+            _ => bail!(
+                "Line {}: unknown command '{}'. Use: install, enable, disable, priority, deploy",
+                line,
+                command
+            ),
+        };
+        steps.push(ScriptLine { line, step });
+    }
+    Ok(steps)
+}
+
+/// Split a line into words, honouring "double" and 'single' quotes and
+/// dropping a trailing `#` comment
+fn split_words(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, '#') => break,
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        bail!("unterminated quote");
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Problems found before running: missing archives and mods that are
+/// neither installed nor installed earlier by the script. Names can only be
+/// checked up to the first install, since an archive's mod name is known
+/// once it is extracted.
+pub fn check_setup_script(lines: &[ScriptLine], installed: &[InstalledMod]) -> Vec<String> {
+    let names: HashSet<String> = installed.iter().map(|m| m.name.to_lowercase()).collect();
+    let mut problems = Vec::new();
+    let mut after_install = false;
+    for line in lines {
+        if let ScriptStep::Install(path) = &line.step {
+            if !path.is_file() {
+                problems.push(format!(
+                    "Line {}: archive not found: {}",
+                    line.line,
+                    path.display()
+                ));
+            }
+            after_install = true;
+        }
+        if let Some(name) = line.step.mod_name() {
+            if !after_install && !names.contains(&name.to_lowercase()) {
+                problems.push(format!(
+                    "Line {}: mod '{}' is not installed",
+                    line.line, name
+                ));
+            }
+        }
+    }
+    problems
+}
+
+impl ModManager {
+    /// Run one script step, returning what it did
+    pub async fn run_script_step(&self, game: &Game, step: &ScriptStep) -> Result<String> {
+        match step {
+            ScriptStep::Install(path) => {
+                let archive = path.to_string_lossy();
+                match self
+                    .install_from_archive(&game.id, &archive, None, None, None, None)
+                    .await?
+                {
+                    InstallResult::Completed(installed) => Ok(format!(
+                        "installed {} (v{})",
+                        installed.name, installed.version
+                    )),
+                    InstallResult::RequiresWizard(_) => bail!(
+                        "{} has a FOMOD installer; install it with 'modsanity mod install --fomod-preset' first",
+                        path.display()
+                    ),
+                }
+            }
+            ScriptStep::Enable(name) => {
+                self.enable_mod(&game.id, name).await?;
+                Ok(format!("enabled {}", name))
+            }
+            ScriptStep::Disable(name) => {
+                self.disable_mod(&game.id, name).await?;
+                Ok(format!("disabled {}", name))
+            }
+            ScriptStep::Priority(name, priority) => {
+                self.set_priority(&game.id, name, *priority).await?;
+                Ok(format!("set {} to priority {}", name, priority))
+            }
+            ScriptStep::Deploy => {
+                let stats = self.deploy(game).await?;
+                Ok(format!(
+                    "deployed {} files from {} mods",
+                    stats.files_deployed, stats.mods_deployed
+                ))
+            }
+        }
+    }
+
+    /// Undo a partly run script: remove mods that were not in `before` and
+    /// restore the enabled state and priority of those that were
+    pub async fn restore_mod_states(&self, game_id: &str, before: &[InstalledMod]) -> Result<()> {
+        for m in self.list_mods(game_id).await? {
+            match before.iter().find(|b| b.id == m.id) {
+                None => self.remove_mod(game_id, &m.name).await?,
+                Some(b) => {
+                    if b.enabled != m.enabled {
+                        self.db.set_mod_enabled(m.id, b.enabled)?;
+                    }
+                    if b.priority != m.priority {
+                        self.db.set_mod_priority(m.id, b.priority)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_setup_script() {
+        let text = "# Base setup\n\
+                    install \"archives/Sky UI.7z\"  # menus\n\
+                    \n\
+                    enable 'Sky UI'\n\
+                    PRIORITY SkyUI -2\n\
+                    deploy\n";
+        let lines = parse_setup_script(text).unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                ScriptLine {
+                    line: 2,
+                    step: ScriptStep::Install(PathBuf::from("archives/Sky UI.7z")),
+                },
+                ScriptLine {
+                    line: 4,
+                    step: ScriptStep::Enable("Sky UI".to_string()),
+                },
+                ScriptLine {
+                    line: 5,
+                    step: ScriptStep::Priority("SkyUI".to_string(), -2),
+                },
+                ScriptLine {
+                    line: 6,
+                    step: ScriptStep::Deploy,
+                },
+            ]
+        );
+
+        let err = parse_setup_script("deploy\nenable Sky UI\n").unwrap_err();
+        assert!(err.to_string().starts_with("Line 2"));
+        assert!(parse_setup_script("remove SkyUI").is_err());
+        assert!(parse_setup_script("priority SkyUI high").is_err());
+        assert!(parse_setup_script("enable \"SkyUI").is_err());
+    }
+}
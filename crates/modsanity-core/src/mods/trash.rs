@@ -0,0 +1,282 @@
+//! Trash for removed mods
+//!
+//! Removing a mod moves its staging folder to `.trash/<id>` in the staging
+//! directory and flags its database row instead of deleting either, so
+//! `mod restore` brings it back with its file index, priority and retained
+//! archives. Trashed mods are deleted for good once they are older than
+//! `[trash] retention_days`.
+
+use super::InstalledMod;
+use crate::activity::Activity;
+use crate::db::ModRecord;
+use crate::error::{Conflict, Invalid};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Folder in the staging directory holding trashed mods. It is hidden, so
+/// rescans and orphan scans skip it.
+const TRASH_DIR: &str = ".trash";
+
+/// A removed mod that can still be restored
+#[derive(Debug, Clone, Serialize)]
+pub struct TrashedMod {
+    pub name: String,
+    pub version: String,
+    pub removed_at: DateTime<Utc>,
+    /// When the mod is deleted for good
+    pub expires_at: DateTime<Utc>,
+}
+
+impl super::ModManager {
+    /// Move a mod's staging entry to the trash and flag its record
+    pub(super) async fn trash_mod(&self, game_id: &str, m: &ModRecord) -> Result<()> {
+        let id = m.id.context("Mod has no database id")?;
+        let staging_dir = self.staging_dir(game_id).await?;
+        let entry = staging_dir.join(&m.name);
+        if tokio::fs::symlink_metadata(&entry).await.is_ok() {
+            let trash = staging_dir.join(TRASH_DIR);
+            tokio::fs::create_dir_all(&trash)
+                .await
+                .context("Failed to create trash directory")?;
+            let dest = trash.join(id.to_string());
+            // Left behind by a superseded mod whose id was reused
+            if tokio::fs::symlink_metadata(&dest).await.is_ok() {
+                remove_entry(&dest).await?;
+            }
+            // Moving a linked mod moves the link, never the linked folder
+            tokio::fs::rename(&entry, &dest)
+                .await
+                .with_context(|| format!("Failed to move '{}' to the trash", m.name))?;
+        }
+        self.db
+            .set_mod_trashed(id, Some(&Utc::now().to_rfc3339()))?;
+        Ok(())
+    }
+
+    /// Removed mods that can still be restored, most recently removed first
+    pub async fn trashed_mods(&self, game_id: &str) -> Result<Vec<TrashedMod>> {
+        let retention = self.config.read().await.trash.retention_days;
+        Ok(self
+            .db
+            .get_trashed_mods(game_id)?
+            .into_iter()
+            .map(|(m, deleted_at)| {
+                let removed_at = parse_time(&deleted_at);
+                TrashedMod {
+                    name: m.name,
+                    version: m.version,
+                    removed_at,
+                    expires_at: removed_at + Duration::days(retention as i64),
+                }
+            })
+            .collect())
+    }
+
+    /// Bring a removed mod back from the trash
+    pub async fn restore_mod(&self, game_id: &str, name: &str) -> Result<InstalledMod> {
+        let trashed = self.db.get_trashed_mods(game_id)?;
+        let Some((record, _)) = trashed
+            .iter()
+            .find(|(m, _)| m.name == name)
+            .or_else(|| {
+                trashed
+                    .iter()
+                    .find(|(m, _)| m.name.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+        else {
+            bail!(Invalid::new(format!(
+                "No removed mod named '{}' in the trash",
+                name
+            )));
+        };
+        let id = record.id.context("Mod has no database id")?;
+        if self.db.get_mod(game_id, &record.name)?.is_some() {
+            bail!(Conflict::new(format!(
+                "A mod named '{}' is installed; rename or remove it before restoring",
+                record.name
+            )));
+        }
+
+        let staging_dir = self.staging_dir(game_id).await?;
+        let source = staging_dir.join(TRASH_DIR).join(id.to_string());
+        if tokio::fs::symlink_metadata(&source).await.is_ok() {
+            let dest = staging_dir.join(&record.name);
+            if tokio::fs::symlink_metadata(&dest).await.is_ok() {
+                bail!(Conflict::new(format!("{} already exists", dest.display())));
+            }
+            tokio::fs::rename(&source, &dest)
+                .await
+                .with_context(|| format!("Failed to restore '{}' from the trash", record.name))?;
+        }
+        self.db.set_mod_trashed(id, None)?;
+
+        self.record_activity(
+            game_id,
+            Activity::Restore,
+            Some(&record.name),
+            serde_json::json!({ "version": record.version }),
+        )
+        .await;
+        Ok(InstalledMod::from(record))
+    }
+
+    /// Delete trashed mods past the retention period, or all of them.
+    /// Returns the number of mods deleted.
+    pub async fn purge_trash(&self, game_id: &str, all: bool) -> Result<usize> {
+        let (retention, staging_dir) = {
+            let config = self.config.read().await;
+            (
+                config.trash.retention_days,
+                config.game_staging_dir(game_id)?,
+            )
+        };
+        let cutoff = Utc::now() - Duration::days(retention as i64);
+        let trash = staging_dir.join(TRASH_DIR);
+
+        let mut purged = 0;
+        let mut kept = HashSet::new();
+        for (record, deleted_at) in self.db.get_trashed_mods(game_id)? {
+            let id = record.id.unwrap_or_default();
+            if !all && parse_time(&deleted_at) > cutoff {
+                kept.insert(id.to_string());
+                continue;
+            }
+            self.discard_mod(game_id, &record, &trash.join(id.to_string()))
+                .await?;
+            purged += 1;
+        }
+
+        // Folders of trashed mods superseded by a new install of the same name
+        if let Ok(entries) = std::fs::read_dir(&trash) {
+            for entry in entries.flatten() {
+                if !kept.contains(entry.file_name().to_string_lossy().as_ref()) {
+                    remove_entry(&entry.path()).await?;
+                }
+            }
+        }
+        if purged > 0 {
+            tracing::info!("Deleted {} mod(s) from the trash", purged);
+        }
+        Ok(purged)
+    }
+
+    /// Delete a mod for good: its staging `entry`, its record and its
+    /// retained archives
+    pub(super) async fn discard_mod(
+        &self,
+        game_id: &str,
+        m: &ModRecord,
+        entry: &Path,
+    ) -> Result<()> {
+        remove_entry(entry).await?;
+        if let Some(id) = m.id {
+            self.db.delete_mod(id)?;
+        }
+        // Archives follow the name; keep them if a mod of that name is installed
+        if self.db.get_mod(game_id, &m.name)?.is_none() {
+            if let Err(e) = self.discard_retained_archives(game_id, &m.name).await {
+                tracing::warn!("Failed to discard retained archives for {}: {}", m.name, e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Delete a staging entry. Adopted mods are links: drop the link, never the
+/// linked folder.
+async fn remove_entry(path: &Path) -> Result<()> {
+    let Ok(meta) = tokio::fs::symlink_metadata(path).await else {
+        return Ok(());
+    };
+    if meta.is_dir() {
+        tokio::fs::remove_dir_all(path)
+            .await
+            .with_context(|| format!("Failed to remove {}", path.display()))
+    } else {
+        tokio::fs::remove_file(path)
+            .await
+            .with_context(|| format!("Failed to remove {}", path.display()))
+    }
+}
+
+/// Parse a stored RFC 3339 time; unreadable times count as long ago
+fn parse_time(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+    use crate::db::{Database, ModRecord};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn record(name: &str, install_path: &std::path::Path) -> ModRecord {
+        let now = chrono::Utc::now().to_rfc3339();
+        ModRecord {
+            id: None,
+            game_id: "skyrimse".to_string(),
+            name: name.to_string(),
+            version: "1.0".to_string(),
+            author: None,
+            description: None,
+            nexus_mod_id: None,
+            nexus_file_id: None,
+            install_path: install_path.to_string_lossy().to_string(),
+            enabled: true,
+            priority: 0,
+            file_count: 1,
+            installed_at: now.clone(),
+            updated_at: now,
+            category_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_restore_and_purge() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::open(&tmp.path().join("test.db")).unwrap());
+        let config = Config {
+            staging_dir_override: Some(tmp.path().join("staging").to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let mods = super::super::ModManager::new(Arc::new(RwLock::new(config)), db.clone());
+        let staging = tmp.path().join("staging/skyrimse/SkyUI");
+        std::fs::create_dir_all(&staging).unwrap();
+        std::fs::write(staging.join("SkyUI_SE.esp"), b"plugin").unwrap();
+        db.insert_mod(&record("SkyUI", &staging)).unwrap();
+
+        mods.remove_mod("skyrimse", "SkyUI").await.unwrap();
+        assert!(!staging.exists());
+        assert!(mods.list_mods("skyrimse").await.unwrap().is_empty());
+        let trashed = mods.trashed_mods("skyrimse").await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert!(trashed[0].expires_at > trashed[0].removed_at);
+
+        let restored = mods.restore_mod("skyrimse", "skyui").await.unwrap();
+        assert_eq!(restored.name, "SkyUI");
+        assert!(staging.join("SkyUI_SE.esp").exists());
+        assert_eq!(mods.list_mods("skyrimse").await.unwrap().len(), 1);
+        assert!(mods.restore_mod("skyrimse", "SkyUI").await.is_err());
+
+        // A new install of the same name supersedes the trashed copy
+        mods.remove_mod("skyrimse", "SkyUI").await.unwrap();
+        std::fs::create_dir_all(&staging).unwrap();
+        db.insert_mod(&record("SkyUI", &staging)).unwrap();
+        assert!(mods.trashed_mods("skyrimse").await.unwrap().is_empty());
+        assert_eq!(mods.purge_trash("skyrimse", false).await.unwrap(), 0);
+        let trash = tmp.path().join("staging/skyrimse/.trash");
+        assert_eq!(std::fs::read_dir(&trash).unwrap().count(), 0);
+
+        mods.remove_mod("skyrimse", "SkyUI").await.unwrap();
+        assert_eq!(mods.purge_trash("skyrimse", true).await.unwrap(), 1);
+        assert_eq!(std::fs::read_dir(&trash).unwrap().count(), 0);
+        assert!(mods.restore_mod("skyrimse", "SkyUI").await.is_err());
+    }
+}
@@ -0,0 +1,61 @@
+//! Version string ordering
+//!
+//! Nexus versions are free text ("1.2", "v1.10b", "2.0.0-beta"), so they are
+//! compared piecewise rather than parsed as semver.
+
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Digits at the front of `chars`, without leading zeros
+fn take_number(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.peek().copied().filter(char::is_ascii_digit) {
+        digits.push(c);
+        chars.next();
+    }
+    digits.trim_start_matches('0').to_string()
+}
+
+/// Order version strings, comparing digit runs as numbers: `1.9` < `1.10`,
+/// `v2` < `V10`. Other characters compare case-insensitively.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (x, y) = (take_number(&mut a), take_number(&mut b));
+                let ordering = x.len().cmp(&y.len()).then(x.cmp(&y));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_ascii_lowercase().cmp(&y.to_ascii_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.9", "1.10"), Ordering::Less);
+        assert_eq!(compare_versions("v2", "V10"), Ordering::Less);
+        assert_eq!(compare_versions("1.02", "1.2"), Ordering::Equal);
+        assert_eq!(compare_versions("2.0.1", "2.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0b", "1.0a"), Ordering::Greater);
+    }
+}
@@ -0,0 +1,133 @@
+//! Weighted random mod discovery ("surprise me")
+//!
+//! Pulls a page of well-endorsed mods from Nexus, optionally limited to one
+//! of the user's preferred categories, and picks one at random with the odds
+//! proportional to its endorsement count. Mods that are already installed
+//! are never suggested.
+
+use super::graphql::{ModSearchParams, ModSearchResult, NexusClient, SortBy};
+use anyhow::Result;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Mods fetched per discovery page
+const PAGE_SIZE: i32 = 100;
+
+/// How many top-endorsed pages a pick can come from
+const MAX_POOL_PAGES: i64 = 5;
+
+/// Filters for a discovery pick
+#[derive(Debug, Clone, Default)]
+pub struct SurpriseOptions {
+    /// Nexus category names; one is chosen at random per pick, empty means any
+    pub categories: Vec<String>,
+    /// Mods with fewer endorsements are never picked
+    pub min_endorsements: i64,
+    /// Nexus mod IDs to skip, typically the installed ones
+    pub exclude: HashSet<i64>,
+}
+
+impl NexusClient {
+    /// Pick a random well-endorsed mod for `game_domain`
+    pub async fn surprise_mod(
+        &self,
+        game_domain: &str,
+        options: &SurpriseOptions,
+    ) -> Result<Option<ModSearchResult>> {
+        // ThreadRng isn't Send, so it is never held across an await
+        let category = options.categories.choose(&mut rand::thread_rng()).cloned();
+        let params = |offset: i32| ModSearchParams {
+            game_domain: Some(game_domain.to_string()),
+            query: None,
+            author: None,
+            category: category.clone(),
+            sort_by: SortBy::Endorsements,
+            offset: Some(offset),
+            limit: Some(PAGE_SIZE),
+        };
+
+        let first = self.search_mods(params(0)).await?;
+        let pages = ((first.total_count + PAGE_SIZE as i64 - 1) / PAGE_SIZE as i64)
+            .clamp(1, MAX_POOL_PAGES);
+        let page = rand::thread_rng().gen_range(0..pages) as i32;
+        let results = if page == 0 {
+            first.results
+        } else {
+            self.search_mods(params(page * PAGE_SIZE)).await?.results
+        };
+
+        Ok(pick_weighted(results, options, &mut rand::thread_rng()))
+    }
+}
+
+/// Pick one result, weighted by endorsements, after applying the filters
+pub fn pick_weighted<R: Rng>(
+    results: Vec<ModSearchResult>,
+    options: &SurpriseOptions,
+    rng: &mut R,
+) -> Option<ModSearchResult> {
+    let mut candidates: Vec<ModSearchResult> = results
+        .into_iter()
+        .filter(|r| r.endorsements > 0 && r.endorsements >= options.min_endorsements)
+        .filter(|r| !options.exclude.contains(&r.mod_id))
+        .collect();
+    let weights = WeightedIndex::new(candidates.iter().map(|r| r.endorsements as u64)).ok()?;
+    Some(candidates.swap_remove(weights.sample(rng)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn result(mod_id: i64, endorsements: i64) -> ModSearchResult {
+        ModSearchResult {
+            mod_id,
+            name: format!("Mod {}", mod_id),
+            summary: String::new(),
+            version: "1.0".to_string(),
+            author: "someone".to_string(),
+            category: "Armour".to_string(),
+            downloads: 0,
+            endorsements,
+            picture_url: None,
+            thumbnail_url: None,
+            updated_at: String::new(),
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_pick_respects_filters() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let options = SurpriseOptions {
+            categories: Vec::new(),
+            min_endorsements: 50,
+            exclude: HashSet::from([1]),
+        };
+        for _ in 0..20 {
+            let results = vec![result(1, 5000), result(2, 10), result(3, 100)];
+            let picked = pick_weighted(results, &options, &mut rng).unwrap();
+            assert_eq!(picked.mod_id, 3);
+        }
+
+        let results = vec![result(1, 5000), result(2, 0)];
+        assert!(pick_weighted(results, &options, &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_pick_favours_endorsed_mods() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let options = SurpriseOptions::default();
+        let popular = (0..1000)
+            .filter(|_| {
+                let results = vec![result(1, 9900), result(2, 100)];
+                pick_weighted(results, &options, &mut rng).unwrap().mod_id == 1
+            })
+            .count();
+        assert!(popular > 950, "popular mod picked {} times", popular);
+    }
+}
@@ -1,23 +1,32 @@
 //! Nexus Mods GraphQL v2 API client
 
+use crate::error::Remote;
+use crate::queue::QueueControl;
 use anyhow::{Context, Result};
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-const GRAPHQL_ENDPOINT: &str = "https://api.nexusmods.com/v2/graphql";
-const REST_API_BASE: &str = "https://api.nexusmods.com/v1";
+/// Public Nexus Mods API host
+pub const NEXUS_API_BASE: &str = "https://api.nexusmods.com";
 
 /// Nexus Mods GraphQL client
 #[derive(Clone)]
 pub struct NexusClient {
     client: Arc<reqwest::Client>,
     api_key: String,
+    base_url: String,
 }
 
 impl NexusClient {
     /// Create a new Nexus Mods GraphQL client
     pub fn new(api_key: String) -> Result<Self> {
+        Self::with_base_url(api_key, NEXUS_API_BASE)
+    }
+
+    /// Create a client against a different API host (GraphQL at `/v2/graphql`,
+    /// REST at `/v1`), e.g. a mock server
+    pub fn with_base_url(api_key: String, base_url: &str) -> Result<Self> {
         let api_key = api_key.trim().to_string();
 
         let mut headers = HeaderMap::new();
@@ -39,9 +48,18 @@ impl NexusClient {
         Ok(Self {
             client: Arc::new(client),
             api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
         })
     }
 
+    fn graphql_endpoint(&self) -> String {
+        format!("{}/v2/graphql", self.base_url)
+    }
+
+    fn rest_base(&self) -> String {
+        format!("{}/v1", self.base_url)
+    }
+
     /// Execute a GraphQL query (with authentication)
     async fn query<V, R>(&self, query: &str, variables: V) -> Result<R>
     where
@@ -100,7 +118,7 @@ impl NexusClient {
         };
 
         let response = client
-            .post(GRAPHQL_ENDPOINT)
+            .post(self.graphql_endpoint())
             .header(CONTENT_TYPE, "application/json")
             .json(&request)
             .send()
@@ -114,11 +132,10 @@ impl NexusClient {
             .context("Failed to read response body")?;
 
         if !status.is_success() {
-            anyhow::bail!(
+            anyhow::bail!(Remote::new(format!(
                 "GraphQL request failed with status {}: {}",
-                status,
-                response_text
-            );
+                status, response_text
+            )));
         }
 
         let graphql_response: GraphQLResponse<R> = serde_json::from_str(&response_text)
@@ -230,7 +247,10 @@ impl NexusClient {
             "fallout3" => 120,
             "falloutnv" => 130,
             "oblivion" => 101,
+            "oblivionremastered" => 7587,
             "morrowind" => 100,
+            "enderal" => 2736,
+            "enderalspecialedition" => 3174,
             _ => anyhow::bail!("Unknown game domain: {}", game_domain),
         };
 
@@ -332,10 +352,7 @@ impl NexusClient {
         }
 
         let variables = Variables {
-            ids: vec![CompositeIdInput {
-                game_id: game_id,
-                mod_id: mod_id,
-            }],
+            ids: vec![CompositeIdInput { game_id, mod_id }],
         };
 
         tracing::debug!(
@@ -794,7 +811,10 @@ impl NexusClient {
     ) -> Result<Vec<DownloadLink>> {
         let url = format!(
             "{}/games/{}/mods/{}/files/{}/download_link.json",
-            REST_API_BASE, game_domain, mod_id, file_id
+            self.rest_base(),
+            game_domain,
+            mod_id,
+            file_id
         );
 
         #[derive(Deserialize)]
@@ -818,9 +838,12 @@ impl NexusClient {
         if !status.is_success() {
             let text = response.text().await.unwrap_or_default();
             if status.as_u16() == 403 {
-                anyhow::bail!("Download requires Nexus Mods Premium membership. Visit the mod page to download manually.");
+                anyhow::bail!(Remote::new("Download requires Nexus Mods Premium membership. Visit the mod page to download manually."));
             }
-            anyhow::bail!("Failed to get download link ({}): {}", status, text);
+            anyhow::bail!(Remote::new(format!(
+                "Failed to get download link ({}): {}",
+                status, text
+            )));
         }
 
         let links: Vec<LinkInfo> = response
@@ -852,7 +875,9 @@ impl NexusClient {
 
         let url = format!(
             "{}/games/{}/mods/{}.json",
-            REST_API_BASE, game_domain, mod_id
+            self.rest_base(),
+            game_domain,
+            mod_id
         );
         let response = reqwest::Client::new()
             .get(&url)
@@ -869,10 +894,10 @@ impl NexusClient {
             return Ok(None);
         }
         if !response.status().is_success() {
-            anyhow::bail!(
+            anyhow::bail!(Remote::new(format!(
                 "Failed to fetch mod details (status: {})",
                 response.status()
-            );
+            )));
         }
 
         let details: ModDetails = response
@@ -886,11 +911,195 @@ impl NexusClient {
             .filter(|n| !n.is_empty()))
     }
 
+    /// Summary and BBCode description of a mod page (REST API v1). `None`
+    /// when the mod doesn't exist or was hidden.
+    pub async fn get_mod_page(&self, game_domain: &str, mod_id: i64) -> Result<Option<ModPage>> {
+        let url = format!(
+            "{}/games/{}/mods/{}.json",
+            self.rest_base(),
+            game_domain,
+            mod_id
+        );
+        let response = reqwest::Client::new()
+            .get(&url)
+            .header("apikey", &self.api_key)
+            .header("accept", "application/json")
+            .header("user-agent", "ModSanity/0.1.0")
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch mod page for {}:{}", game_domain, mod_id))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!(Remote::new(format!(
+                "Failed to fetch mod page (status: {})",
+                response.status()
+            )));
+        }
+
+        let page: ModPage = response
+            .json()
+            .await
+            .context("Failed to parse mod page response")?;
+        Ok(Some(page))
+    }
+
+    /// Changelog of a mod (REST API v1), newest version first
+    pub async fn get_mod_changelogs(
+        &self,
+        game_domain: &str,
+        mod_id: i64,
+    ) -> Result<Vec<ChangelogEntry>> {
+        let url = format!(
+            "{}/games/{}/mods/{}/changelogs.json",
+            self.rest_base(),
+            game_domain,
+            mod_id
+        );
+        let response = reqwest::Client::new()
+            .get(&url)
+            .header("apikey", &self.api_key)
+            .header("accept", "application/json")
+            .header("user-agent", "ModSanity/0.1.0")
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch changelog for {}:{}", game_domain, mod_id))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            anyhow::bail!(Remote::new(format!(
+                "Failed to fetch changelog (status: {})",
+                response.status()
+            )));
+        }
+
+        // An object of version -> change lines; an empty changelog comes back as `[]`
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse changelog response")?;
+        let mut entries: Vec<ChangelogEntry> = body
+            .as_object()
+            .map(|versions| {
+                versions
+                    .iter()
+                    .map(|(version, changes)| ChangelogEntry {
+                        version: version.clone(),
+                        changes: changes
+                            .as_array()
+                            .map(|lines| {
+                                lines
+                                    .iter()
+                                    .filter_map(|l| l.as_str())
+                                    .map(str::to_string)
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort_by(|a, b| crate::mods::compare_versions(&b.version, &a.version));
+        Ok(entries)
+    }
+
+    /// Identify an archive by its MD5 (REST API v1). Returns the Nexus files
+    /// with that hash for the game; empty when Nexus doesn't know the file.
+    pub async fn md5_search(&self, game_domain: &str, md5: &str) -> Result<Vec<Md5Match>> {
+        #[derive(Deserialize)]
+        struct SearchHit {
+            #[serde(rename = "mod")]
+            mod_info: HitMod,
+            file_details: HitFile,
+        }
+
+        #[derive(Deserialize)]
+        struct HitMod {
+            mod_id: i64,
+        }
+
+        #[derive(Deserialize)]
+        struct HitFile {
+            file_id: i64,
+            name: Option<String>,
+            version: Option<String>,
+        }
+
+        let url = format!(
+            "{}/games/{}/mods/md5_search/{}.json",
+            self.rest_base(),
+            game_domain,
+            md5
+        );
+        let response = reqwest::Client::new()
+            .get(&url)
+            .header("apikey", &self.api_key)
+            .header("accept", "application/json")
+            .header("user-agent", "ModSanity/0.1.0")
+            .send()
+            .await
+            .context("Failed to search file hash")?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            anyhow::bail!(Remote::new(format!(
+                "MD5 search failed (status: {})",
+                response.status()
+            )));
+        }
+
+        let hits: Vec<SearchHit> = response
+            .json()
+            .await
+            .context("Failed to parse MD5 search response")?;
+
+        Ok(hits
+            .into_iter()
+            .map(|hit| Md5Match {
+                mod_id: hit.mod_info.mod_id,
+                file_id: hit.file_details.file_id,
+                file_name: hit.file_details.name.unwrap_or_default(),
+                version: hit.file_details.version.unwrap_or_default(),
+            })
+            .collect())
+    }
+
     /// Download a file from a URL to a local path, reporting progress via callback
     pub async fn download_file(
         url: &str,
         dest: &std::path::Path,
         progress_cb: impl Fn(u64, u64) + Send + 'static,
+    ) -> Result<()> {
+        Self::download_file_controlled(url, dest, &QueueControl::new(), progress_cb).await
+    }
+
+    /// Download like [`Self::download_file`], holding while `control` is
+    /// paused. A cancel, even during a stalled read, removes the partial file
+    /// and fails with [`crate::mods::Cancelled`].
+    pub async fn download_file_controlled(
+        url: &str,
+        dest: &std::path::Path,
+        control: &QueueControl,
+        progress_cb: impl Fn(u64, u64) + Send + 'static,
+    ) -> Result<()> {
+        let result = Self::download_to(url, dest, control, progress_cb).await;
+        if result.as_ref().is_err_and(crate::mods::is_cancelled) {
+            let _ = tokio::fs::remove_file(dest).await;
+        }
+        result
+    }
+
+    async fn download_to(
+        url: &str,
+        dest: &std::path::Path,
+        control: &QueueControl,
+        progress_cb: impl Fn(u64, u64) + Send + 'static,
     ) -> Result<()> {
         let response = reqwest::Client::new()
             .get(url)
@@ -899,7 +1108,10 @@ impl NexusClient {
             .context("Failed to start download")?;
 
         if !response.status().is_success() {
-            anyhow::bail!("Download failed with status: {}", response.status());
+            anyhow::bail!(Remote::new(format!(
+                "Download failed with status: {}",
+                response.status()
+            )));
         }
 
         let total_size = response.content_length().unwrap_or(0);
@@ -914,7 +1126,13 @@ impl NexusClient {
         use futures::StreamExt;
         use tokio::io::AsyncWriteExt;
 
-        while let Some(chunk) = stream.next().await {
+        loop {
+            control.checkpoint().await?;
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = control.cancelled() => return Err(crate::mods::Cancelled.into()),
+            };
+            let Some(chunk) = chunk else { break };
             let chunk = chunk.context("Error reading download stream")?;
             file.write_all(&chunk)
                 .await
@@ -928,6 +1146,30 @@ impl NexusClient {
     }
 }
 
+/// A Nexus file matching an archive's MD5
+#[derive(Debug, Clone)]
+pub struct Md5Match {
+    pub mod_id: i64,
+    pub file_id: i64,
+    pub file_name: String,
+    pub version: String,
+}
+
+/// Text of a mod's Nexus page
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ModPage {
+    pub summary: Option<String>,
+    /// BBCode, as written by the author
+    pub description: Option<String>,
+}
+
+/// Changes listed for one version of a mod
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub version: String,
+    pub changes: Vec<String>,
+}
+
 /// Information about a mod update
 #[derive(Debug, Clone)]
 pub struct ModUpdateInfo {
@@ -1006,6 +1248,28 @@ pub struct ModFile {
     pub description: Option<String>,
 }
 
+impl ModFile {
+    /// Whether Nexus still serves this file; archived and removed files
+    /// can't be downloaded
+    pub fn is_available(&self) -> bool {
+        !matches!(
+            self.category.to_ascii_uppercase().as_str(),
+            "ARCHIVED" | "REMOVED" | "DELETED"
+        )
+    }
+}
+
+/// Numeric Nexus game ID used by file queries, for a game domain
+pub fn nexus_game_id(game_domain: &str) -> Option<i64> {
+    match game_domain {
+        "skyrimspecialedition" => Some(1704),
+        "skyrim" => Some(110),
+        "fallout4" => Some(1151),
+        "starfield" => Some(4187),
+        _ => None,
+    }
+}
+
 /// Download link information
 #[derive(Debug, Clone)]
 pub struct DownloadLink {
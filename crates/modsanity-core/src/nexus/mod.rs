@@ -0,0 +1,18 @@
+//! Nexus Mods API integration
+
+pub mod discover;
+pub mod graphql;
+pub mod page;
+pub mod populate;
+pub mod rest;
+
+pub use discover::{pick_weighted, SurpriseOptions};
+pub use graphql::{
+    nexus_game_id, ChangelogEntry, DownloadLink, Md5Match, ModFile, ModPage, ModRequirement,
+    ModSearchPage, ModSearchParams, ModSearchResult, ModUpdateInfo, NexusClient, SortBy,
+    NEXUS_API_BASE,
+};
+pub use page::{bbcode_to_text, load_mod_page, ModPageText};
+
+pub use populate::{CatalogPopulator, PopulateOptions, PopulateStats};
+pub use rest::{ModInfo, NexusRestClient};
@@ -0,0 +1,243 @@
+//! Mod page text: summary, description and changelog
+//!
+//! Fetched from Nexus when a mod's details are opened and cached in the
+//! database, so the details screen works offline and doesn't refetch on every
+//! visit. Descriptions are BBCode; [`bbcode_to_text`] turns them into plain
+//! text for the terminal.
+
+use super::graphql::{ChangelogEntry, NexusClient};
+use crate::db::{Database, NexusModPageRecord};
+use anyhow::Result;
+
+/// Cached pages older than this are fetched again
+const MAX_AGE_DAYS: i64 = 7;
+
+/// A mod page ready to display
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModPageText {
+    pub summary: Option<String>,
+    /// Description as plain text
+    pub description: String,
+    pub changelog: Vec<ChangelogEntry>,
+    pub fetched_at: String,
+}
+
+impl ModPageText {
+    fn from_record(record: &NexusModPageRecord) -> Self {
+        Self {
+            summary: record
+                .summary
+                .as_deref()
+                .map(bbcode_to_text)
+                .filter(|s| !s.is_empty()),
+            description: record
+                .description
+                .as_deref()
+                .map(bbcode_to_text)
+                .unwrap_or_default(),
+            changelog: serde_json::from_str(&record.changelog).unwrap_or_default(),
+            fetched_at: record.fetched_at.clone(),
+        }
+    }
+
+    /// The latest changelog entry, if the author keeps one
+    pub fn latest_changes(&self) -> Option<&ChangelogEntry> {
+        self.changelog.first()
+    }
+}
+
+fn is_stale(fetched_at: &str) -> bool {
+    chrono::DateTime::parse_from_rfc3339(fetched_at)
+        .map(|at| {
+            chrono::Utc::now().signed_duration_since(at) > chrono::Duration::days(MAX_AGE_DAYS)
+        })
+        .unwrap_or(true)
+}
+
+/// Page text for a mod: from the cache when it's recent, otherwise from Nexus.
+/// `refresh` always fetches. Falls back to a stale cached copy when Nexus
+/// can't be reached or no client is configured.
+pub async fn load_mod_page(
+    db: &Database,
+    client: Option<&NexusClient>,
+    game_domain: &str,
+    mod_id: i64,
+    refresh: bool,
+) -> Result<Option<ModPageText>> {
+    let cached = db.get_nexus_mod_page(game_domain, mod_id)?;
+    if let Some(record) = &cached {
+        if !refresh && !is_stale(&record.fetched_at) {
+            return Ok(Some(ModPageText::from_record(record)));
+        }
+    }
+    let Some(client) = client else {
+        return Ok(cached.as_ref().map(ModPageText::from_record));
+    };
+
+    let fetched = async {
+        let page = client.get_mod_page(game_domain, mod_id).await?;
+        let changelog = match page {
+            Some(_) => client.get_mod_changelogs(game_domain, mod_id).await?,
+            None => Vec::new(),
+        };
+        anyhow::Ok(page.map(|page| (page, changelog)))
+    }
+    .await;
+
+    match fetched {
+        Ok(Some((page, changelog))) => {
+            let record = NexusModPageRecord {
+                game_domain: game_domain.to_string(),
+                nexus_mod_id: mod_id,
+                summary: page.summary,
+                description: page.description,
+                changelog: serde_json::to_string(&changelog)?,
+                fetched_at: chrono::Utc::now().to_rfc3339(),
+            };
+            db.upsert_nexus_mod_page(&record)?;
+            Ok(Some(ModPageText::from_record(&record)))
+        }
+        Ok(None) => Ok(cached.as_ref().map(ModPageText::from_record)),
+        Err(e) if cached.is_some() => {
+            tracing::warn!("Using cached page for {}:{}: {}", game_domain, mod_id, e);
+            Ok(cached.as_ref().map(ModPageText::from_record))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Plain text from Nexus BBCode: formatting tags are dropped, line breaks and
+/// list items kept, links shown with their target and HTML entities decoded.
+pub fn bbcode_to_text(bbcode: &str) -> String {
+    let text = bbcode
+        .replace("<br />", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br>", "\n")
+        .replace("\r\n", "\n");
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+    // Target of the [url=...] being read, shown after its text
+    let mut link: Option<String> = None;
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let Some(end) = after.find(']') else {
+            out.push_str(after);
+            rest = "";
+            break;
+        };
+        let tag = &after[1..end];
+        let name = tag
+            .trim_start_matches('/')
+            .split(['=', ' '])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let closing = tag.starts_with('/');
+        rest = &after[end + 1..];
+
+        match name.as_str() {
+            "*" => out.push_str("\n• "),
+            "br" | "line" | "hr" => out.push('\n'),
+            "list" | "quote" | "center" | "left" | "right" | "spoiler" | "code" => out.push('\n'),
+            "url" if !closing => {
+                link = tag
+                    .split_once('=')
+                    .map(|(_, url)| url.trim_matches('"').to_string())
+            }
+            "url" => {
+                if let Some(url) = link.take().filter(|url| !out.ends_with(url.as_str())) {
+                    out.push_str(&format!(" ({})", url));
+                }
+            }
+            "img" | "youtube" if !closing => {
+                // Skip the embedded URL
+                let close = format!("[/{}]", name);
+                match rest.to_ascii_lowercase().find(&close) {
+                    Some(i) => rest = &rest[i + close.len()..],
+                    None => rest = "",
+                }
+            }
+            "b" | "i" | "u" | "s" | "size" | "color" | "font" | "heading" | "img" | "youtube"
+            | "email" | "ol" | "ul" | "li" => {}
+            // Not a tag we know: keep it as text
+            _ => out.push_str(&after[..end + 1]),
+        }
+    }
+    out.push_str(rest);
+
+    let out = out
+        .replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+
+    // Trim each line and collapse runs of blank lines
+    let mut lines: Vec<&str> = Vec::new();
+    for line in out.lines().map(str::trim) {
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bbcode_to_text() {
+        let bbcode = "[center][size=5][b]SkyUI[/b][/size][/center]<br />\
+            Elements &amp; menus<br /><br /><br />\
+            [img]https://example.com/a.png[/img]\
+            [list][*]Search [i]everything[/i][*]See [url=https://example.com/wiki]the wiki[/url][/list]\
+            Needs [url]https://skse.silverlock.org[/url] [unknown]";
+        assert_eq!(
+            bbcode_to_text(bbcode),
+            "SkyUI\n\nElements & menus\n\n• Search everything\n• See the wiki (https://example.com/wiki)\n\
+             Needs https://skse.silverlock.org [unknown]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cached_page_used_without_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        assert_eq!(
+            load_mod_page(&db, None, "skyrimspecialedition", 12604, false)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let changelog = vec![ChangelogEntry {
+            version: "5.2".into(),
+            changes: vec!["Fixed the map".into()],
+        }];
+        db.upsert_nexus_mod_page(&NexusModPageRecord {
+            game_domain: "skyrimspecialedition".into(),
+            nexus_mod_id: 12604,
+            summary: Some("Elegant, mouse-friendly UI".into()),
+            description: Some("[b]SkyUI[/b]".into()),
+            changelog: serde_json::to_string(&changelog).unwrap(),
+            fetched_at: "2020-01-01T00:00:00Z".into(),
+        })
+        .unwrap();
+
+        // Stale, but there's no client to refresh it with
+        let page = load_mod_page(&db, None, "skyrimspecialedition", 12604, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(page.description, "SkyUI");
+        assert_eq!(page.latest_changes(), changelog.first());
+    }
+}
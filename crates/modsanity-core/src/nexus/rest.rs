@@ -1,5 +1,6 @@
 //! Nexus Mods catalog client using GraphQL v2 API
 
+use crate::error::Remote;
 use anyhow::{bail, Context, Result};
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use serde::{Deserialize, Serialize};
@@ -7,7 +8,8 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 
-const GRAPHQL_ENDPOINT: &str = "https://api.nexusmods.com/v2/graphql";
+use super::graphql::NEXUS_API_BASE;
+
 const MAX_RETRIES: u32 = 5;
 const BASE_RETRY_DELAY_MS: u64 = 2000;
 const MAX_RETRY_DELAY_MS: u64 = 60000;
@@ -16,11 +18,17 @@ const MAX_RETRY_DELAY_MS: u64 = 60000;
 #[derive(Clone)]
 pub struct NexusRestClient {
     client: Arc<reqwest::Client>,
+    graphql_endpoint: String,
 }
 
 impl NexusRestClient {
     /// Create a new catalog client
     pub fn new(api_key: &str) -> Result<Self> {
+        Self::with_base_url(api_key, NEXUS_API_BASE)
+    }
+
+    /// Create a catalog client against a different API host
+    pub fn with_base_url(api_key: &str, base_url: &str) -> Result<Self> {
         let api_key = api_key.trim();
 
         let mut headers = HeaderMap::new();
@@ -39,6 +47,7 @@ impl NexusRestClient {
 
         Ok(Self {
             client: Arc::new(client),
+            graphql_endpoint: format!("{}/v2/graphql", base_url.trim_end_matches('/')),
         })
     }
 
@@ -99,7 +108,7 @@ impl NexusRestClient {
 
             let response = self
                 .client
-                .post(GRAPHQL_ENDPOINT)
+                .post(&self.graphql_endpoint)
                 .json(&request)
                 .send()
                 .await
@@ -110,7 +119,10 @@ impl NexusRestClient {
             // Handle rate limiting (429)
             if status == 429 {
                 if attempt >= MAX_RETRIES {
-                    bail!("Rate limited after {} retries", MAX_RETRIES);
+                    bail!(Remote::new(format!(
+                        "Rate limited after {} retries",
+                        MAX_RETRIES
+                    )));
                 }
 
                 // Check for Retry-After header
@@ -143,7 +155,10 @@ impl NexusRestClient {
             // Handle server errors (5xx) with retry
             if status.is_server_error() {
                 if attempt >= MAX_RETRIES {
-                    bail!("Server error after {} retries: {}", MAX_RETRIES, status);
+                    bail!(Remote::new(format!(
+                        "Server error after {} retries: {}",
+                        MAX_RETRIES, status
+                    )));
                 }
 
                 let delay = BASE_RETRY_DELAY_MS * (1 << (attempt - 1));
@@ -162,7 +177,10 @@ impl NexusRestClient {
             // Handle client errors (4xx) - don't retry
             if status.is_client_error() {
                 let error_text = response.text().await.unwrap_or_default();
-                bail!("Client error {}: {}", status, error_text);
+                bail!(Remote::new(format!(
+                    "Client error {}: {}",
+                    status, error_text
+                )));
             }
 
             // Success - parse response
@@ -218,7 +236,10 @@ impl NexusRestClient {
             }
 
             // Unexpected status
-            bail!("Unexpected response status: {}", status);
+            bail!(Remote::new(format!(
+                "Unexpected response status: {}",
+                status
+            )));
         }
     }
 }
@@ -0,0 +1,180 @@
+//! Dirty plugin detection for xEdit cleaning
+//!
+//! The LOOT masterlist lists the CRC-32 of plugin versions known to carry
+//! identical-to-master records (ITM), deleted references (UDR) or deleted
+//! navmeshes. A plugin in Data whose checksum matches one of those entries is
+//! dirty, and xEdit's QuickAutoClean mode fixes the ITM and UDR records.
+
+use super::masterlist::{check_dirty, PluginMetadata};
+use crate::games::{Game, GameType};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// A plugin whose checksum the masterlist reports as dirty
+#[derive(Debug, Clone, Serialize)]
+pub struct DirtyPlugin {
+    pub filename: String,
+    pub path: PathBuf,
+    pub crc: u32,
+    /// Cleaning utility the masterlist entry was made with, e.g. "SSEEdit v4.0.3"
+    pub util: String,
+    pub itm: u32,
+    pub udr: u32,
+    /// Deleted navmeshes; QuickAutoClean can't fix them
+    pub nav: u32,
+}
+
+impl DirtyPlugin {
+    /// "ITM 12, UDR 3" for display
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.itm > 0 {
+            parts.push(format!("ITM {}", self.itm));
+        }
+        if self.udr > 0 {
+            parts.push(format!("UDR {}", self.udr));
+        }
+        if self.nav > 0 {
+            parts.push(format!("deleted navmeshes {}", self.nav));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Lookup table for [`crc32`], built at compile time
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn update_crc(crc: u32, bytes: &[u8]) -> u32 {
+    bytes.iter().fold(crc, |crc, byte| {
+        CRC_TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// CRC-32 (IEEE), the checksum LOOT identifies plugin versions by
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !update_crc(!0, bytes)
+}
+
+/// CRC-32 of a file, following a deployed link to the staged copy
+pub fn file_crc(path: &Path) -> Result<u32> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut crc = !0u32;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        crc = update_crc(crc, &buf[..n]);
+    }
+    Ok(!crc)
+}
+
+/// Plugins in Data the masterlist reports as dirty, in load order. Only
+/// plugins with dirty entries are checksummed.
+pub fn find_dirty_plugins(
+    game: &Game,
+    metadata: &HashMap<String, PluginMetadata>,
+) -> Result<Vec<DirtyPlugin>> {
+    let mut dirty = Vec::new();
+    for plugin in super::get_plugins(game)? {
+        let has_entries = metadata
+            .get(&plugin.filename.to_lowercase())
+            .is_some_and(|m| !m.dirty.is_empty());
+        if !has_entries {
+            continue;
+        }
+        let crc = match file_crc(&plugin.path) {
+            Ok(crc) => crc,
+            Err(e) => {
+                tracing::warn!("Failed to checksum {}: {}", plugin.filename, e);
+                continue;
+            }
+        };
+        if let Some(info) = check_dirty(&plugin.filename, crc, metadata) {
+            dirty.push(DirtyPlugin {
+                filename: plugin.filename,
+                path: plugin.path,
+                crc,
+                util: info.util,
+                itm: info.itm,
+                udr: info.udr,
+                nav: info.nav,
+            });
+        }
+    }
+    Ok(dirty)
+}
+
+/// xEdit game mode switch, so a plain `xEdit.exe` opens the right game
+pub fn xedit_game_mode(game_type: GameType) -> Option<&'static str> {
+    match game_type {
+        GameType::SkyrimSE => Some("-sse"),
+        GameType::SkyrimVR => Some("-tes5vr"),
+        GameType::Fallout4 => Some("-fo4"),
+        GameType::Fallout4VR => Some("-fo4vr"),
+        GameType::Starfield => Some("-sf1"),
+        GameType::Fallout3 => Some("-fo3"),
+        GameType::FalloutNV => Some("-fnv"),
+        GameType::Oblivion => Some("-tes4"),
+        GameType::Morrowind => Some("-tes3"),
+        GameType::Enderal => Some("-enderal"),
+        GameType::EnderalSE => Some("-enderalse"),
+        GameType::OblivionRemastered | GameType::OpenMW => None,
+    }
+}
+
+/// xEdit arguments that QuickAutoClean one plugin and exit
+pub fn quick_auto_clean_args(game_type: GameType, plugin: &str) -> Vec<String> {
+    xedit_game_mode(game_type)
+        .into_iter()
+        .chain(["-qac", "-autoexit", "-autoload"])
+        .map(str::to_string)
+        .chain(std::iter::once(plugin.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_and_args() {
+        // Standard CRC-32 check value
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.esp"), b"123456789").unwrap();
+        assert_eq!(file_crc(&dir.path().join("a.esp")).unwrap(), 0xCBF4_3926);
+
+        assert_eq!(
+            quick_auto_clean_args(GameType::SkyrimSE, "Dawnguard.esm"),
+            vec!["-sse", "-qac", "-autoexit", "-autoload", "Dawnguard.esm"]
+        );
+        assert_eq!(
+            quick_auto_clean_args(GameType::OpenMW, "a.esp"),
+            vec!["-qac", "-autoexit", "-autoload", "a.esp"]
+        );
+    }
+}
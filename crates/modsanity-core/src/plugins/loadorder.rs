@@ -0,0 +1,403 @@
+//! Load order management (plugins.txt and loadorder.txt)
+//!
+//! Fallout 3, New Vegas and classic Oblivion ignore loadorder.txt and load
+//! plugins by file modification time, so for them the order is also written
+//! to (and read back from) the plugin timestamps. Oblivion Remastered loads
+//! plugins.txt top to bottom; loadorder.txt keeps the place of inactive ones.
+//!
+//! Morrowind lists its active plugins in the `[Game Files]` section of
+//! Morrowind.ini and OpenMW as `content=` lines in openmw.cfg; for them those
+//! files stand in for plugins.txt.
+
+use crate::games::{openmw, Game, GameType, LoadOrderMethod};
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// First plugin timestamp for timestamp load orders (2008-01-01); a fixed
+/// base keeps rewriting the same order from touching the files' times
+const TIMESTAMP_BASE: u64 = 1_199_145_600;
+
+/// Read plugins.txt and return list of enabled plugins (lowercase)
+pub fn read_plugins_txt(game: &Game) -> Result<Vec<String>> {
+    let path = match &game.plugins_txt_path {
+        Some(p) => p.clone(),
+        None => return Ok(Vec::new()),
+    };
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    if game.game_type == GameType::OpenMW {
+        let content = openmw::read_entries(&path, "content")?;
+        return Ok(content.iter().map(|p| p.to_lowercase()).collect());
+    }
+
+    let content = std::fs::read_to_string(&path).context("Failed to read plugins.txt")?;
+    if game.game_type == GameType::Morrowind {
+        return Ok(read_game_files(&content));
+    }
+
+    let plugins: Vec<String> = content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            // Handle *PluginName.esp format (asterisk means enabled)
+            let name = line.trim_start_matches('*');
+            // Only return if it was marked as enabled (with asterisk) or no asterisk system
+            if line.starts_with('*') || !content.contains('*') {
+                Some(name.to_lowercase())
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    Ok(plugins)
+}
+
+/// Read loadorder.txt and return ordered list of plugins
+pub fn read_loadorder_txt(game: &Game) -> Result<Vec<String>> {
+    let path = match &game.loadorder_txt_path {
+        Some(p) => p.clone(),
+        None => return Ok(Vec::new()),
+    };
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path).context("Failed to read loadorder.txt")?;
+
+    let plugins: Vec<String> = content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            Some(line.to_string())
+        })
+        .collect();
+
+    Ok(plugins)
+}
+
+/// Plugin order the game will use: loadorder.txt, or the plugin timestamps
+/// for games that load by modification time. Falls back to the plugins.txt
+/// order for games that keep it there.
+pub fn read_load_order(game: &Game) -> Result<Vec<String>> {
+    match game.game_type.load_order_method() {
+        LoadOrderMethod::Asterisk => read_loadorder_txt(game),
+        LoadOrderMethod::Timestamp => read_plugin_timestamps(game),
+        LoadOrderMethod::PluginsTxt => {
+            let order = read_loadorder_txt(game)?;
+            if order.is_empty() {
+                read_plugins_txt(game)
+            } else {
+                Ok(order)
+            }
+        }
+    }
+}
+
+/// Plugins in the Data folder ordered by modification time
+fn read_plugin_timestamps(game: &Game) -> Result<Vec<String>> {
+    if !game.data_path.exists() {
+        return Ok(Vec::new());
+    }
+    let extensions = game.game_type.plugin_extensions();
+    let mut plugins = Vec::new();
+    for entry in std::fs::read_dir(&game.data_path)? {
+        let path = entry?.path();
+        let is_plugin = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)));
+        // metadata() follows symlinks, which is what the game sees
+        let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+            continue;
+        };
+        if let (true, Some(name)) = (is_plugin, path.file_name().and_then(|n| n.to_str())) {
+            plugins.push((modified, name.to_lowercase(), name.to_string()));
+        }
+    }
+    plugins.sort();
+    Ok(plugins.into_iter().map(|(_, _, name)| name).collect())
+}
+
+/// Whether `modified` is one of the times [`write_loadorder_txt`] gives
+/// plugins of timestamp load order games
+pub fn is_load_order_timestamp(modified: SystemTime) -> bool {
+    let Ok(since) = modified.duration_since(SystemTime::UNIX_EPOCH) else {
+        return false;
+    };
+    since.subsec_nanos() == 0
+        && since
+            .as_secs()
+            .checked_sub(TIMESTAMP_BASE)
+            .is_some_and(|secs| secs % 60 == 0)
+}
+
+/// Give the plugins in `data_path` ascending modification times, one minute
+/// apart, in `plugins` order. Plugins not in the folder are skipped.
+fn write_plugin_timestamps(data_path: &Path, plugins: &[String]) -> Result<()> {
+    let base = SystemTime::UNIX_EPOCH + Duration::from_secs(TIMESTAMP_BASE);
+    for (i, name) in plugins.iter().enumerate() {
+        let path = data_path.join(name);
+        // Deployed plugins are often symlinks; opening follows them to the file
+        let Ok(file) = std::fs::File::open(&path) else {
+            continue;
+        };
+        file.set_modified(base + Duration::from_secs(60 * i as u64))
+            .with_context(|| format!("Failed to set the timestamp of {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Write plugins.txt with enabled plugins
+pub fn write_plugins_txt(game: &Game, enabled_plugins: &[String]) -> Result<()> {
+    let path = match &game.plugins_txt_path {
+        Some(p) => p.clone(),
+        None => anyhow::bail!("plugins.txt path not configured"),
+    };
+
+    // Ensure directory exists
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    match game.game_type {
+        GameType::OpenMW => return openmw::write_entries(&path, "content", enabled_plugins),
+        GameType::Morrowind => return write_game_files(&path, enabled_plugins),
+        _ => {}
+    }
+
+    // Windows line endings; only the asterisk format marks active plugins
+    let content: String = match game.game_type.load_order_method() {
+        LoadOrderMethod::Asterisk => enabled_plugins
+            .iter()
+            .map(|p| format!("*{}", p))
+            .collect::<Vec<_>>()
+            .join("\r\n"),
+        LoadOrderMethod::Timestamp | LoadOrderMethod::PluginsTxt => enabled_plugins.join("\r\n"),
+    };
+
+    std::fs::write(&path, content)?;
+
+    Ok(())
+}
+
+/// Plugins in the `[Game Files]` section of Morrowind.ini (lowercase)
+fn read_game_files(ini: &str) -> Vec<String> {
+    let mut in_section = false;
+    let mut plugins = Vec::new();
+    for line in ini.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_section = line.eq_ignore_ascii_case("[Game Files]");
+        } else if let Some((key, value)) = line.split_once('=').filter(|_| in_section) {
+            if key.trim().to_ascii_lowercase().starts_with("gamefile") {
+                plugins.push(value.trim().to_lowercase());
+            }
+        }
+    }
+    plugins
+}
+
+/// Replace the `[Game Files]` section of Morrowind.ini with `plugins`,
+/// keeping the rest of the file
+fn write_game_files(path: &Path, plugins: &[String]) -> Result<()> {
+    let ini = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut lines = Vec::new();
+    let mut in_section = false;
+    let mut written = false;
+    let section: Vec<String> = std::iter::once("[Game Files]".to_string())
+        .chain(
+            plugins
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("GameFile{}={}", i, p)),
+        )
+        .collect();
+    for line in ini.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_section = trimmed.eq_ignore_ascii_case("[Game Files]");
+            if in_section {
+                lines.extend(section.iter().cloned());
+                written = true;
+                continue;
+            }
+        }
+        if !in_section {
+            lines.push(line.to_string());
+        }
+    }
+    if !written {
+        lines.extend(section);
+    }
+    std::fs::write(path, lines.join("\r\n") + "\r\n")
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Write loadorder.txt
+pub fn write_loadorder_txt(game: &Game, plugins: &[String]) -> Result<()> {
+    let path = match &game.loadorder_txt_path {
+        Some(p) => p.clone(),
+        None => anyhow::bail!("loadorder.txt path not configured"),
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let content = plugins.join("\r\n");
+    std::fs::write(&path, content)?;
+
+    if game.game_type.load_order_method() == LoadOrderMethod::Timestamp {
+        write_plugin_timestamps(&game.data_path, plugins)?;
+    }
+
+    Ok(())
+}
+
+/// Check for missing masters in enabled plugins
+pub fn check_missing_masters(plugins: &[super::PluginInfo]) -> Vec<(String, Vec<String>)> {
+    let enabled_names: std::collections::HashSet<_> = plugins
+        .iter()
+        .filter(|p| p.enabled)
+        .map(|p| p.filename.to_lowercase())
+        .collect();
+
+    let mut missing = Vec::new();
+
+    for plugin in plugins.iter().filter(|p| p.enabled) {
+        let plugin_missing: Vec<String> = plugin
+            .masters
+            .iter()
+            .filter(|m| !enabled_names.contains(&m.to_lowercase()))
+            .cloned()
+            .collect();
+
+        if !plugin_missing.is_empty() {
+            missing.push((plugin.filename.clone(), plugin_missing));
+        }
+    }
+
+    missing
+}
+
+/// Validate load order (masters before dependents)
+pub fn validate_load_order(plugins: &[super::PluginInfo]) -> Vec<String> {
+    let mut issues = Vec::new();
+    let enabled: Vec<_> = plugins.iter().filter(|p| p.enabled).collect();
+
+    // Build index map
+    let index_map: std::collections::HashMap<String, usize> = enabled
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.filename.to_lowercase(), i))
+        .collect();
+
+    for (i, plugin) in enabled.iter().enumerate() {
+        for master in &plugin.masters {
+            if let Some(&master_idx) = index_map.get(&master.to_lowercase()) {
+                if master_idx > i {
+                    issues.push(format!(
+                        "{} loads before its master {}",
+                        plugin.filename, master
+                    ));
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::GameType;
+
+    #[test]
+    fn test_timestamp_load_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::FalloutNV, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("prefix"));
+        std::fs::create_dir_all(&game.data_path).unwrap();
+        for name in ["FalloutNV.esm", "DeadMoney.esm", "Mod.esp", "Light.esl"] {
+            std::fs::write(game.data_path.join(name), "").unwrap();
+        }
+
+        let order = ["FalloutNV.esm", "DeadMoney.esm", "Mod.esp"].map(String::from);
+        write_plugins_txt(&game, &order[..2]).unwrap();
+        write_loadorder_txt(&game, &order).unwrap();
+
+        let plugins_txt = std::fs::read_to_string(game.plugins_txt_path.as_ref().unwrap()).unwrap();
+        assert_eq!(plugins_txt, "FalloutNV.esm\r\nDeadMoney.esm");
+        assert_eq!(
+            read_plugins_txt(&game).unwrap(),
+            vec!["falloutnv.esm", "deadmoney.esm"]
+        );
+        // .esl files are not plugins for New Vegas
+        assert_eq!(read_load_order(&game).unwrap(), order);
+
+        let reordered = ["FalloutNV.esm", "Mod.esp", "DeadMoney.esm"].map(String::from);
+        write_loadorder_txt(&game, &reordered).unwrap();
+        assert_eq!(read_load_order(&game).unwrap(), reordered);
+    }
+
+    #[test]
+    fn test_plugins_txt_load_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::OblivionRemastered, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("prefix"));
+        assert_eq!(
+            game.plugins_txt_path,
+            Some(game.data_path.join("Plugins.txt"))
+        );
+
+        let order = ["Oblivion.esm", "Mod.esp", "Off.esp"].map(String::from);
+        write_plugins_txt(&game, &order[..2]).unwrap();
+        let plugins_txt = std::fs::read_to_string(game.plugins_txt_path.as_ref().unwrap()).unwrap();
+        assert_eq!(plugins_txt, "Oblivion.esm\r\nMod.esp");
+        assert_eq!(
+            read_load_order(&game).unwrap(),
+            vec!["oblivion.esm", "mod.esp"]
+        );
+
+        write_loadorder_txt(&game, &order).unwrap();
+        assert_eq!(read_load_order(&game).unwrap(), order);
+    }
+
+    #[test]
+    fn test_morrowind_game_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::Morrowind, dir.path().join("Morrowind"));
+        let ini = game.plugins_txt_path.clone().unwrap();
+        std::fs::create_dir_all(&game.data_path).unwrap();
+        std::fs::write(
+            &ini,
+            "[General]\r\nSubtitles=0\r\n[Game Files]\r\nGameFile0=Morrowind.esm\r\n[Archives]\r\nArchive 0=Tribunal.bsa\r\n",
+        )
+        .unwrap();
+
+        let enabled = ["Morrowind.esm", "Tribunal.esm", "Mod.esp"].map(String::from);
+        write_plugins_txt(&game, &enabled).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&ini).unwrap(),
+            "[General]\r\nSubtitles=0\r\n[Game Files]\r\nGameFile0=Morrowind.esm\r\n\
+             GameFile1=Tribunal.esm\r\nGameFile2=Mod.esp\r\n[Archives]\r\nArchive 0=Tribunal.bsa\r\n"
+        );
+        assert_eq!(
+            read_plugins_txt(&game).unwrap(),
+            vec!["morrowind.esm", "tribunal.esm", "mod.esp"]
+        );
+    }
+}
@@ -14,7 +14,7 @@ use crate::games::Game;
 /// Check if LOOT is installed and available
 pub fn is_loot_available() -> bool {
     // Try to find LOOT executable
-    if let Some(_) = find_loot_executable() {
+    if find_loot_executable().is_some() {
         return true;
     }
     false
@@ -91,7 +91,11 @@ fn map_game_to_loot(game_id: &str) -> Result<String> {
         "fallout4vr" => "Fallout4VR",
         "starfield" => "Starfield",
         "oblivion" => "Oblivion",
+        "oblivionremastered" => "OblivionRemastered",
         "morrowind" => "Morrowind",
+        "openmw" => "OpenMW",
+        "enderal" => "Enderal",
+        "enderalse" => "EnderalSE",
         _ => bail!("Game '{}' is not supported by LOOT", game_id),
     };
 
@@ -3,7 +3,7 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// LOOT masterlist root structure
 #[derive(Debug, Deserialize, Serialize)]
@@ -176,6 +176,19 @@ pub fn load_masterlist(path: &Path) -> Result<Masterlist> {
     Ok(masterlist)
 }
 
+/// Masterlist for a game: `<loot cache>/<game id>/masterlist.yaml`, then
+/// `<loot cache>/masterlist.yaml`, then the working directory
+pub fn find_masterlist(loot_cache_dir: &Path, game_id: &str) -> Option<PathBuf> {
+    [
+        loot_cache_dir.join(game_id).join("masterlist.yaml"),
+        loot_cache_dir.join("masterlist.yaml"),
+        PathBuf::from("masterlist.yaml"),
+        PathBuf::from("loot-master/masterlist.yaml"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
 /// Build a lookup map for quick plugin metadata access
 pub fn build_metadata_map(masterlist: &Masterlist) -> HashMap<String, PluginMetadata> {
     let mut map = HashMap::new();
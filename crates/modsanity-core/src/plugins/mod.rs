@@ -1,5 +1,6 @@
 //! Plugin (ESP/ESM/ESL) management
 
+pub mod clean;
 mod loadorder;
 pub mod loot;
 pub mod masterlist;
@@ -9,7 +10,7 @@ pub mod sort;
 pub use loadorder::*;
 pub use parser::*;
 
-use crate::games::Game;
+use crate::games::{openmw, Game, GameType};
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -30,6 +31,8 @@ impl PluginType {
             "esm" => Some(Self::Master),
             "esp" => Some(Self::Plugin),
             "esl" => Some(Self::Light),
+            // OpenMW content files
+            "omwaddon" | "omwscripts" => Some(Self::Plugin),
             _ => None,
         }
     }
@@ -69,17 +72,20 @@ pub struct PluginInfo {
 /// Get all plugins for a game
 pub fn get_plugins(game: &Game) -> Result<Vec<PluginInfo>> {
     let mut plugins = Vec::new();
+    let extensions = game.game_type.plugin_extensions();
 
     // Read plugins.txt for enabled status
     let enabled_plugins = read_plugins_txt(game)?;
 
-    // Scan data directory for plugin files
-    let data_path = &game.data_path;
-    if !data_path.exists() {
-        return Ok(plugins);
-    }
-
-    for entry in std::fs::read_dir(data_path)? {
+    // Scan data directories for plugin files
+    for entry in plugin_dirs(game)?
+        .iter()
+        .filter(|dir| dir.exists())
+        .map(std::fs::read_dir)
+        .collect::<std::io::Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+    {
         let entry = entry?;
         let path = entry.path();
 
@@ -94,8 +100,8 @@ pub fn get_plugins(game: &Game) -> Result<Vec<PluginInfo>> {
             .to_lowercase();
 
         let plugin_type = match PluginType::from_extension(&ext) {
-            Some(t) => t,
-            None => continue,
+            Some(t) if extensions.contains(&ext.as_str()) => t,
+            _ => continue,
         };
 
         let filename = path
@@ -109,9 +115,12 @@ pub fn get_plugins(game: &Game) -> Result<Vec<PluginInfo>> {
         // Try to parse header
         let header = parse_plugin_header(&path).ok();
 
-        let is_light = plugin_type == PluginType::Light
-            || header.as_ref().map(|h| h.is_light).unwrap_or(false);
+        let is_light = game.game_type.supports_light_plugins()
+            && (plugin_type == PluginType::Light
+                || header.as_ref().map(|h| h.is_light).unwrap_or(false));
 
+        // A later OpenMW data folder overrides an earlier one
+        plugins.retain(|p: &PluginInfo| !p.filename.eq_ignore_ascii_case(&filename));
         plugins.push(PluginInfo {
             filename: filename.clone(),
             path,
@@ -134,10 +143,38 @@ pub fn get_plugins(game: &Game) -> Result<Vec<PluginInfo>> {
     Ok(plugins)
 }
 
+/// Folders holding the game's plugins: Data, or for OpenMW every `data=`
+/// folder in openmw.cfg, lowest priority first
+fn plugin_dirs(game: &Game) -> Result<Vec<PathBuf>> {
+    match (&game.game_type, &game.plugins_txt_path) {
+        (GameType::OpenMW, Some(cfg)) if cfg.exists() => Ok(openmw::read_entries(cfg, "data")?
+            .into_iter()
+            .map(PathBuf::from)
+            .collect()),
+        _ => Ok(vec![game.data_path.clone()]),
+    }
+}
+
+/// Rewrite plugins.txt and loadorder.txt from the plugins present in the
+/// Data folder, dropping entries whose files are gone. Returns the number of
+/// enabled plugins.
+pub fn sync_plugin_files(game: &Game) -> Result<usize> {
+    let plugins = get_plugins(game)?;
+    let enabled: Vec<String> = plugins
+        .iter()
+        .filter(|p| p.enabled)
+        .map(|p| p.filename.clone())
+        .collect();
+    let all: Vec<String> = plugins.iter().map(|p| p.filename.clone()).collect();
+    write_plugins_txt(game, &enabled)?;
+    write_loadorder_txt(game, &all)?;
+    Ok(enabled.len())
+}
+
 /// Sort plugins according to load order rules
 fn sort_plugins(plugins: &mut [PluginInfo], game: &Game) -> Result<()> {
-    // Read loadorder.txt if it exists
-    let load_order = read_loadorder_txt(game)?;
+    // Read loadorder.txt (or the timestamps) if there is one
+    let load_order = read_load_order(game)?;
 
     // Create order map
     let order_map: std::collections::HashMap<String, usize> = load_order
@@ -0,0 +1,1602 @@
+//! Game INI settings: per-profile overrides, presets, diffing and editing
+//!
+//! Profiles carry a list of INI overrides that are written into the game's
+//! INI files when the profile is activated. Section and key names are matched
+//! case-insensitively, like the game engine does. The values the overrides
+//! replaced are recorded ([`IniChanges`]), so leaving the profile puts the
+//! game's own values back.
+//!
+//! The game's INI files (Skyrim.ini, SkyrimPrefs.ini and the Fallout
+//! equivalents in the My Games folder inside the Proton prefix) can also be
+//! edited directly. Commonly tuned keys are known with their type, so values
+//! are checked and written the way the game writes them; any other key can
+//! still be set as raw text. Every write first copies the file into a
+//! per-game backup folder.
+
+use crate::config::Paths;
+use crate::error::Invalid;
+use crate::games::{Game, GameType};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A single INI value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IniSetting {
+    /// INI file name, e.g. "SkyrimPrefs.ini"
+    pub file: String,
+    pub section: String,
+    pub key: String,
+    pub value: String,
+}
+
+impl IniSetting {
+    pub fn new(
+        file: impl Into<String>,
+        section: impl Into<String>,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        Self {
+            file: file.into(),
+            section: section.into(),
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Whether both settings address the same file/section/key
+    pub fn same_key(&self, other: &IniSetting) -> bool {
+        self.file.eq_ignore_ascii_case(&other.file)
+            && self.section.eq_ignore_ascii_case(&other.section)
+            && self.key.eq_ignore_ascii_case(&other.key)
+    }
+
+    fn sort_key(&self) -> (String, String, String) {
+        (
+            self.file.to_lowercase(),
+            self.section.to_lowercase(),
+            self.key.to_lowercase(),
+        )
+    }
+}
+
+/// Overlay `overrides` onto `base`, replacing values for matching keys
+pub fn layer_ini(base: &[IniSetting], overrides: &[IniSetting]) -> Vec<IniSetting> {
+    let mut out = base.to_vec();
+    for o in overrides {
+        match out.iter_mut().find(|s| s.same_key(o)) {
+            Some(existing) => existing.value = o.value.clone(),
+            None => out.push(o.clone()),
+        }
+    }
+    out
+}
+
+/// A curated set of INI overrides
+#[derive(Debug, Clone, Copy)]
+pub struct IniPreset {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub games: &'static [GameType],
+    /// INI file the entries belong to
+    pub file: &'static str,
+    /// (section, key, value)
+    entries: &'static [(&'static str, &'static str, &'static str)],
+}
+
+impl IniPreset {
+    /// The preset's settings
+    pub fn settings(&self) -> Vec<IniSetting> {
+        self.entries
+            .iter()
+            .map(|(section, key, value)| IniSetting::new(self.file, *section, *key, *value))
+            .collect()
+    }
+
+    /// Whether the preset targets `game_type`
+    pub fn supports(&self, game_type: GameType) -> bool {
+        self.games.contains(&game_type)
+    }
+}
+
+const PRESET_GAMES: &[GameType] = &[GameType::SkyrimSE, GameType::SkyrimVR];
+
+const PRESETS: &[IniPreset] = &[
+    IniPreset {
+        name: "potato",
+        description: "Lowest settings for weak GPUs and handhelds",
+        games: PRESET_GAMES,
+        file: "SkyrimPrefs.ini",
+        entries: &[
+            ("Display", "iShadowMapResolution", "512"),
+            ("Display", "fShadowDistance", "2000.0000"),
+            ("Display", "iMaxAnisotropy", "0"),
+            ("Display", "bSAOEnable", "0"),
+            ("Display", "iTexMipMapSkip", "1"),
+            ("Display", "fLODFadeOutMultObjects", "4.0000"),
+            ("Display", "fLODFadeOutMultItems", "2.0000"),
+            ("Display", "fLODFadeOutMultActors", "4.0000"),
+            ("Grass", "fGrassStartFadeDistance", "0.0000"),
+            ("Decals", "uMaxDecals", "50"),
+            ("Decals", "uMaxSkinDecals", "10"),
+            ("Particles", "iMaxDesired", "250"),
+            ("TerrainManager", "fBlockMaximumDistance", "75000.0000"),
+            ("Water", "bReflectLODObjects", "0"),
+            ("Water", "bReflectLODLand", "0"),
+        ],
+    },
+    IniPreset {
+        name: "performance",
+        description: "Balanced settings that favour frame rate",
+        games: PRESET_GAMES,
+        file: "SkyrimPrefs.ini",
+        entries: &[
+            ("Display", "iShadowMapResolution", "1024"),
+            ("Display", "fShadowDistance", "3000.0000"),
+            ("Display", "iMaxAnisotropy", "4"),
+            ("Display", "bSAOEnable", "0"),
+            ("Display", "iTexMipMapSkip", "0"),
+            ("Display", "fLODFadeOutMultObjects", "8.0000"),
+            ("Display", "fLODFadeOutMultItems", "4.0000"),
+            ("Display", "fLODFadeOutMultActors", "6.0000"),
+            ("Grass", "fGrassStartFadeDistance", "3500.0000"),
+            ("Decals", "uMaxDecals", "100"),
+            ("Decals", "uMaxSkinDecals", "35"),
+            ("Particles", "iMaxDesired", "750"),
+            ("TerrainManager", "fBlockMaximumDistance", "150000.0000"),
+            ("Water", "bReflectLODObjects", "0"),
+            ("Water", "bReflectLODLand", "1"),
+        ],
+    },
+    IniPreset {
+        name: "quality",
+        description: "High settings for capable GPUs",
+        games: PRESET_GAMES,
+        file: "SkyrimPrefs.ini",
+        entries: &[
+            ("Display", "iShadowMapResolution", "4096"),
+            ("Display", "fShadowDistance", "8000.0000"),
+            ("Display", "iMaxAnisotropy", "16"),
+            ("Display", "bSAOEnable", "1"),
+            ("Display", "iTexMipMapSkip", "0"),
+            ("Display", "fLODFadeOutMultObjects", "15.0000"),
+            ("Display", "fLODFadeOutMultItems", "10.0000"),
+            ("Display", "fLODFadeOutMultActors", "15.0000"),
+            ("Grass", "fGrassStartFadeDistance", "7000.0000"),
+            ("Decals", "uMaxDecals", "1000"),
+            ("Decals", "uMaxSkinDecals", "100"),
+            ("Particles", "iMaxDesired", "4000"),
+            ("TerrainManager", "fBlockMaximumDistance", "250000.0000"),
+            ("Water", "bReflectLODObjects", "1"),
+            ("Water", "bReflectLODLand", "1"),
+        ],
+    },
+];
+
+/// All built-in presets
+pub fn ini_presets() -> &'static [IniPreset] {
+    PRESETS
+}
+
+/// Look up a built-in preset by name (case-insensitive)
+pub fn find_ini_preset(name: &str) -> Option<&'static IniPreset> {
+    PRESETS.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+/// One side of an INI comparison
+#[derive(Debug, Clone)]
+pub enum IniSource {
+    /// The game's INI files as they are on disk
+    Current,
+    /// A profile's overrides on top of the current INI files
+    Profile(String),
+    /// A preset on top of the current INI files
+    Preset(&'static IniPreset),
+}
+
+impl IniSource {
+    /// Parse `current`, `preset:<name>`, `profile:<name>` or a bare profile name
+    pub fn parse(spec: &str) -> Result<Self> {
+        if let Some(name) = spec.strip_prefix("preset:") {
+            return match find_ini_preset(name) {
+                Some(preset) => Ok(Self::Preset(preset)),
+                None => bail!(
+                    "Unknown INI preset '{}' (available: {})",
+                    name,
+                    PRESETS
+                        .iter()
+                        .map(|p| p.name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+        }
+        if let Some(name) = spec.strip_prefix("profile:") {
+            return Ok(Self::Profile(name.to_string()));
+        }
+        if spec.eq_ignore_ascii_case("current") {
+            return Ok(Self::Current);
+        }
+        Ok(Self::Profile(spec.to_string()))
+    }
+
+    /// Display label
+    pub fn label(&self) -> String {
+        match self {
+            Self::Current => "current".to_string(),
+            Self::Profile(name) => name.clone(),
+            Self::Preset(preset) => format!("preset:{}", preset.name),
+        }
+    }
+}
+
+/// How a key differs between two INI sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IniChange {
+    /// Only set on the right
+    Added,
+    /// Only set on the left
+    Removed,
+    /// Set on both sides with different values
+    Changed,
+}
+
+/// One differing key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniDiffEntry {
+    pub file: String,
+    pub section: String,
+    pub key: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl IniDiffEntry {
+    pub fn change(&self) -> IniChange {
+        match (&self.left, &self.right) {
+            (None, _) => IniChange::Added,
+            (_, None) => IniChange::Removed,
+            _ => IniChange::Changed,
+        }
+    }
+}
+
+/// Keys whose values differ between `left` and `right`, sorted by file, section and key
+pub fn diff_ini(left: &[IniSetting], right: &[IniSetting]) -> Vec<IniDiffEntry> {
+    let mut keys: BTreeMap<(String, String, String), (&IniSetting, Option<&str>, Option<&str>)> =
+        BTreeMap::new();
+    for s in left {
+        keys.entry(s.sort_key()).or_insert((s, None, None)).1 = Some(s.value.as_str());
+    }
+    for s in right {
+        keys.entry(s.sort_key()).or_insert((s, None, None)).2 = Some(s.value.as_str());
+    }
+
+    keys.into_values()
+        .filter(|(_, l, r)| match (l, r) {
+            (Some(l), Some(r)) => !values_equal(l, r),
+            _ => true,
+        })
+        .map(|(s, l, r)| IniDiffEntry {
+            file: s.file.clone(),
+            section: s.section.clone(),
+            key: s.key.clone(),
+            left: l.map(str::to_string),
+            right: r.map(str::to_string),
+        })
+        .collect()
+}
+
+/// Compare values numerically when both parse, so "8000" equals "8000.0000"
+fn values_equal(a: &str, b: &str) -> bool {
+    let (a, b) = (a.trim(), b.trim());
+    if a.eq_ignore_ascii_case(b) {
+        return true;
+    }
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(x), Ok(y)) => x == y,
+        _ => false,
+    }
+}
+
+/// Parse INI text into settings attributed to `file`
+pub fn parse_ini(file: &str, content: &str) -> Vec<IniSetting> {
+    let mut out: Vec<IniSetting> = Vec::new();
+    let mut section = String::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let setting = IniSetting::new(file, section.clone(), key.trim(), value.trim());
+        // Later duplicates win, as in the game
+        match out.iter_mut().find(|s| s.same_key(&setting)) {
+            Some(existing) => existing.value = setting.value,
+            None => out.push(setting),
+        }
+    }
+    out
+}
+
+/// Read every known INI file of `game`. Missing files are skipped.
+pub fn read_game_ini(game: &Game) -> Result<Vec<IniSetting>> {
+    if game.ini_dir().is_none() {
+        return Ok(Vec::new());
+    }
+    let mut out = Vec::new();
+    for file in game.game_type.ini_files() {
+        out.extend(read_ini_settings(game, file)?);
+    }
+    Ok(out)
+}
+
+/// Set `settings` in INI text, keeping comments, ordering and unrelated keys
+pub fn apply_ini_text(content: &str, settings: &[&IniSetting]) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    for setting in settings {
+        let mut in_section = false;
+        let mut section_end = None;
+        let mut replaced = false;
+        for (i, line) in lines.iter_mut().enumerate() {
+            let trimmed = line.trim();
+            if let Some(name) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                if in_section {
+                    break;
+                }
+                in_section = name.trim().eq_ignore_ascii_case(&setting.section);
+                if in_section {
+                    section_end = Some(i + 1);
+                }
+                continue;
+            }
+            if !in_section {
+                continue;
+            }
+            if !trimmed.is_empty() {
+                section_end = Some(i + 1);
+            }
+            if let Some((key, _)) = trimmed.split_once('=') {
+                if key.trim().eq_ignore_ascii_case(&setting.key) {
+                    *line = format!("{}={}", key.trim(), setting.value);
+                    replaced = true;
+                    break;
+                }
+            }
+        }
+        if replaced {
+            continue;
+        }
+
+        let entry = format!("{}={}", setting.key, setting.value);
+        match section_end {
+            Some(at) => lines.insert(at, entry),
+            None => {
+                if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.push(format!("[{}]", setting.section));
+                lines.push(entry);
+            }
+        }
+    }
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Remove the keys of `settings` from INI text, keeping everything else
+pub fn remove_ini_text(content: &str, settings: &[&IniSetting]) -> String {
+    let mut section = String::new();
+    let mut out = String::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+        } else if let Some((key, _)) = trimmed.split_once('=') {
+            let removed = settings.iter().any(|s| {
+                s.section.eq_ignore_ascii_case(&section) && s.key.eq_ignore_ascii_case(key.trim())
+            });
+            if removed {
+                continue;
+            }
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Write `settings` into the game's INI files, creating files as needed and
+/// backing up each one first
+pub fn write_game_ini(
+    game: &Game,
+    backup_dir: &Path,
+    settings: &[IniSetting],
+) -> Result<Vec<IniWrite>> {
+    let mut by_file: BTreeMap<&str, Vec<&IniSetting>> = BTreeMap::new();
+    for s in settings {
+        by_file.entry(s.file.as_str()).or_default().push(s);
+    }
+    let mut writes = Vec::new();
+    for (file, file_settings) in by_file {
+        let content = apply_ini_text(&read_ini_text(game, file)?, &file_settings);
+        writes.push(write_ini_text(game, backup_dir, file, &content)?);
+    }
+    Ok(writes)
+}
+
+/// Backups kept per INI file; older ones are deleted
+const BACKUPS_KEPT: usize = 20;
+
+/// Type of a known INI value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IniValueKind {
+    /// Written as 0 or 1
+    Bool,
+    Int,
+    /// Written with four decimals, as the game does
+    Float,
+    Text,
+}
+
+impl IniValueKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IniValueKind::Bool => "bool",
+            IniValueKind::Int => "int",
+            IniValueKind::Float => "float",
+            IniValueKind::Text => "text",
+        }
+    }
+
+    /// Type named by a Bethesda key's prefix: bFoo, iFoo/uFoo, fFoo, sFoo
+    pub fn for_key(key: &str) -> Self {
+        match key.chars().next().map(|c| c.to_ascii_lowercase()) {
+            Some('b') => IniValueKind::Bool,
+            Some('i' | 'u') => IniValueKind::Int,
+            Some('f') => IniValueKind::Float,
+            _ => IniValueKind::Text,
+        }
+    }
+
+    /// Check `value` and convert it to the form the game writes
+    pub fn normalize(&self, value: &str) -> Result<String> {
+        let value = value.trim();
+        match self {
+            IniValueKind::Bool => match value.to_ascii_lowercase().as_str() {
+                "1" | "true" | "on" | "yes" => Ok("1".to_string()),
+                "0" | "false" | "off" | "no" => Ok("0".to_string()),
+                _ => bail!(Invalid::new(format!(
+                    "'{}' is not a boolean (use 1/0, true/false or on/off)",
+                    value
+                ))),
+            },
+            IniValueKind::Int => match value.parse::<i64>() {
+                Ok(n) => Ok(n.to_string()),
+                Err(_) => bail!(Invalid::new(format!("'{}' is not a whole number", value))),
+            },
+            IniValueKind::Float => match value.parse::<f64>() {
+                Ok(n) if n.is_finite() => Ok(format!("{:.4}", n)),
+                _ => bail!(Invalid::new(format!("'{}' is not a number", value))),
+            },
+            IniValueKind::Text => Ok(value.to_string()),
+        }
+    }
+}
+
+/// Which of the game's INI files a known key lives in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IniFileRole {
+    /// Skyrim.ini, Fallout4.ini, Fallout.ini, ...
+    Main,
+    /// SkyrimPrefs.ini, Fallout4Prefs.ini, ... (written by the launcher)
+    Prefs,
+}
+
+impl IniFileRole {
+    /// File name for `game_type`, if it has one in this role
+    pub fn file(&self, game_type: GameType) -> Option<&'static str> {
+        let files = game_type.ini_files();
+        match self {
+            IniFileRole::Main => files.first().copied().filter(|f| !f.ends_with("Prefs.ini")),
+            IniFileRole::Prefs => files.iter().copied().find(|f| f.ends_with("Prefs.ini")),
+        }
+    }
+}
+
+/// A commonly tuned INI key with its type
+#[derive(Debug, Clone, Copy)]
+pub struct KnownIniKey {
+    pub role: IniFileRole,
+    pub section: &'static str,
+    pub key: &'static str,
+    pub kind: IniValueKind,
+    pub description: &'static str,
+}
+
+impl KnownIniKey {
+    /// Whether this is `file`/`section`/`key` (case-insensitive, like the game)
+    pub fn matches(&self, game_type: GameType, file: &str, section: &str, key: &str) -> bool {
+        self.role
+            .file(game_type)
+            .is_some_and(|f| f.eq_ignore_ascii_case(file))
+            && self.section.eq_ignore_ascii_case(section)
+            && self.key.eq_ignore_ascii_case(key)
+    }
+}
+
+/// Known keys shared by a set of games
+struct KnownKeyGroup {
+    games: &'static [GameType],
+    role: IniFileRole,
+    /// (section, key, description); the type comes from the key's prefix
+    keys: &'static [(&'static str, &'static str, &'static str)],
+}
+
+const SKYRIM: &[GameType] = &[
+    GameType::SkyrimSE,
+    GameType::SkyrimVR,
+    GameType::Enderal,
+    GameType::EnderalSE,
+];
+const CREATION: &[GameType] = &[
+    GameType::SkyrimSE,
+    GameType::SkyrimVR,
+    GameType::Enderal,
+    GameType::EnderalSE,
+    GameType::Fallout4,
+    GameType::Fallout4VR,
+];
+const BETHESDA: &[GameType] = &[
+    GameType::SkyrimSE,
+    GameType::SkyrimVR,
+    GameType::Enderal,
+    GameType::EnderalSE,
+    GameType::Fallout4,
+    GameType::Fallout4VR,
+    GameType::Fallout3,
+    GameType::FalloutNV,
+];
+const FALLOUT: &[GameType] = &[
+    GameType::Fallout4,
+    GameType::Fallout4VR,
+    GameType::Fallout3,
+    GameType::FalloutNV,
+];
+
+const KNOWN_KEYS: &[KnownKeyGroup] = &[
+    KnownKeyGroup {
+        games: BETHESDA,
+        role: IniFileRole::Prefs,
+        keys: &[
+            ("Display", "iSize W", "Render width"),
+            ("Display", "iSize H", "Render height"),
+            ("Display", "bFull Screen", "Exclusive fullscreen"),
+            ("Display", "iMaxAnisotropy", "Anisotropic filtering (0-16)"),
+        ],
+    },
+    KnownKeyGroup {
+        games: CREATION,
+        role: IniFileRole::Prefs,
+        keys: &[
+            ("Display", "bBorderless", "Borderless window"),
+            ("Display", "iPresentInterval", "VSync (0 or 1)"),
+            ("Display", "iShadowMapResolution", "Shadow map size"),
+            ("Display", "fShadowDistance", "Shadow draw distance"),
+        ],
+    },
+    KnownKeyGroup {
+        games: SKYRIM,
+        role: IniFileRole::Prefs,
+        keys: &[
+            ("Display", "bSAOEnable", "Ambient occlusion"),
+            ("Grass", "fGrassStartFadeDistance", "Grass fade distance"),
+        ],
+    },
+    KnownKeyGroup {
+        games: BETHESDA,
+        role: IniFileRole::Main,
+        keys: &[
+            ("General", "sLanguage", "Game language, e.g. ENGLISH"),
+            ("General", "SLocalSavePath", "Save folder under My Games"),
+        ],
+    },
+    KnownKeyGroup {
+        games: FALLOUT,
+        role: IniFileRole::Main,
+        keys: &[("Archive", "bInvalidateOlderFiles", "Loose files win")],
+    },
+    KnownKeyGroup {
+        games: SKYRIM,
+        role: IniFileRole::Main,
+        keys: &[
+            ("Grass", "bAllowCreateGrass", "Generate grass"),
+            ("Grass", "bAllowLoadGrass", "Load precached grass"),
+        ],
+    },
+    KnownKeyGroup {
+        games: CREATION,
+        role: IniFileRole::Main,
+        keys: &[
+            ("Papyrus", "bEnableLogging", "Write Papyrus logs"),
+            ("Papyrus", "bEnableTrace", "Stack traces in logs"),
+            ("Papyrus", "bLoadDebugInformation", "Line numbers in logs"),
+        ],
+    },
+];
+
+/// Known keys for `game_type`, Prefs file first
+pub fn known_ini_keys(game_type: GameType) -> Vec<KnownIniKey> {
+    let mut keys: Vec<KnownIniKey> = KNOWN_KEYS
+        .iter()
+        .filter(|group| group.games.contains(&game_type))
+        .flat_map(|group| {
+            group
+                .keys
+                .iter()
+                .map(|(section, key, description)| KnownIniKey {
+                    role: group.role,
+                    section,
+                    key,
+                    kind: IniValueKind::for_key(key),
+                    description,
+                })
+        })
+        .collect();
+    keys.sort_by_key(|k| k.role == IniFileRole::Main);
+    keys
+}
+
+/// The known key at `file`/`section`/`key`, if any
+pub fn find_known_ini_key(
+    game_type: GameType,
+    file: &str,
+    section: &str,
+    key: &str,
+) -> Option<KnownIniKey> {
+    known_ini_keys(game_type)
+        .into_iter()
+        .find(|k| k.matches(game_type, file, section, key))
+}
+
+/// Resolve a file argument: one of the game's INI file names
+/// (case-insensitive) or `main`, `prefs` or `custom`
+pub fn resolve_ini_file(game_type: GameType, name: &str) -> Result<&'static str> {
+    let files = game_type.ini_files();
+    let found = match name.to_ascii_lowercase().as_str() {
+        "main" => IniFileRole::Main.file(game_type),
+        "prefs" => IniFileRole::Prefs.file(game_type),
+        "custom" => files.iter().copied().find(|f| f.ends_with("Custom.ini")),
+        _ => files.iter().copied().find(|f| f.eq_ignore_ascii_case(name)),
+    };
+    match found {
+        Some(file) => Ok(file),
+        None => bail!(Invalid::new(format!(
+            "Unknown INI file '{}' (available: {})",
+            name,
+            files.join(", ")
+        ))),
+    }
+}
+
+/// Folder holding the game's INI files
+pub fn ini_dir(game: &Game) -> Result<PathBuf> {
+    match game.ini_dir() {
+        Some(dir) => Ok(dir),
+        None => bail!(Invalid::new(format!(
+            "INI location for {} is unknown (no Proton prefix)",
+            game.name
+        ))),
+    }
+}
+
+/// One of the game's INI files
+#[derive(Debug, Clone)]
+pub struct IniFile {
+    pub name: &'static str,
+    pub path: PathBuf,
+    pub exists: bool,
+}
+
+/// The game's INI files, main file first
+pub fn game_ini_files(game: &Game) -> Result<Vec<IniFile>> {
+    let dir = ini_dir(game)?;
+    Ok(game
+        .game_type
+        .ini_files()
+        .iter()
+        .map(|name| {
+            let path = dir.join(name);
+            IniFile {
+                name,
+                exists: path.exists(),
+                path,
+            }
+        })
+        .collect())
+}
+
+/// Text of an INI file; empty when it doesn't exist yet
+pub fn read_ini_text(game: &Game, file: &str) -> Result<String> {
+    let path = ini_dir(game)?.join(file);
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    let bytes =
+        std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Settings in one INI file
+pub fn read_ini_settings(game: &Game, file: &str) -> Result<Vec<IniSetting>> {
+    Ok(parse_ini(file, &read_ini_text(game, file)?))
+}
+
+/// A key of one INI file, for listing and editing
+#[derive(Debug, Clone)]
+pub struct IniEntry {
+    pub section: String,
+    pub key: String,
+    /// None when a known key isn't set
+    pub value: Option<String>,
+    pub known: Option<KnownIniKey>,
+}
+
+/// Keys of `file`: its known keys, set or not, then every other key in the
+/// file in file order
+pub fn ini_entries(game: &Game, file: &str) -> Result<Vec<IniEntry>> {
+    let settings = read_ini_settings(game, file)?;
+    let known: Vec<KnownIniKey> = known_ini_keys(game.game_type)
+        .into_iter()
+        .filter(|k| k.role.file(game.game_type) == Some(file))
+        .collect();
+    let mut entries: Vec<IniEntry> = known
+        .iter()
+        .map(|k| {
+            let value = settings
+                .iter()
+                .find(|s| k.matches(game.game_type, file, &s.section, &s.key))
+                .map(|s| s.value.clone());
+            IniEntry {
+                section: k.section.to_string(),
+                key: k.key.to_string(),
+                value,
+                known: Some(*k),
+            }
+        })
+        .collect();
+    entries.extend(
+        settings
+            .into_iter()
+            .filter(|s| {
+                !known
+                    .iter()
+                    .any(|k| k.matches(game.game_type, file, &s.section, &s.key))
+            })
+            .map(|s| IniEntry {
+                section: s.section,
+                key: s.key,
+                value: Some(s.value),
+                known: None,
+            }),
+    );
+    Ok(entries)
+}
+
+/// Result of writing an INI file
+#[derive(Debug, Clone)]
+pub struct IniWrite {
+    pub path: PathBuf,
+    /// Copy of the file before the write; None when it didn't exist
+    pub backup: Option<PathBuf>,
+}
+
+/// Copy `file` into `backup_dir` as `<name>-<timestamp>.ini`, keeping the
+/// newest `BACKUPS_KEPT`. None when the file doesn't exist.
+pub fn backup_ini(game: &Game, backup_dir: &Path, file: &str) -> Result<Option<PathBuf>> {
+    let path = ini_dir(game)?.join(file);
+    if !path.exists() {
+        return Ok(None);
+    }
+    std::fs::create_dir_all(backup_dir)
+        .with_context(|| format!("Failed to create {}", backup_dir.display()))?;
+    let stem = file.trim_end_matches(".ini");
+    let dest = backup_dir.join(format!(
+        "{}-{}.ini",
+        stem,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    // Several writes within a second keep the copy from before the first
+    if !dest.exists() {
+        std::fs::copy(&path, &dest)
+            .with_context(|| format!("Failed to back up {}", path.display()))?;
+    }
+    for old in ini_backups(backup_dir, file)?
+        .into_iter()
+        .skip(BACKUPS_KEPT)
+    {
+        let _ = std::fs::remove_file(old);
+    }
+    Ok(Some(dest))
+}
+
+/// Backups of `file` in `backup_dir`, newest first
+pub fn ini_backups(backup_dir: &Path, file: &str) -> Result<Vec<PathBuf>> {
+    let Ok(entries) = std::fs::read_dir(backup_dir) else {
+        return Ok(Vec::new());
+    };
+    let prefix = format!("{}-", file.trim_end_matches(".ini")).to_lowercase();
+    let mut backups: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().to_lowercase())
+                .is_some_and(|n| {
+                    n.strip_prefix(&prefix)
+                        .and_then(|rest| rest.strip_suffix(".ini"))
+                        // The timestamp, not e.g. "SkyrimPrefs" for "Skyrim"
+                        .is_some_and(|stamp| stamp.starts_with(|c: char| c.is_ascii_digit()))
+                })
+        })
+        .collect();
+    backups.sort();
+    backups.reverse();
+    Ok(backups)
+}
+
+/// Replace the whole text of `file`, backing it up first
+pub fn write_ini_text(
+    game: &Game,
+    backup_dir: &Path,
+    file: &str,
+    content: &str,
+) -> Result<IniWrite> {
+    let dir = ini_dir(game)?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let backup = backup_ini(game, backup_dir, file)?;
+    let path = dir.join(file);
+    std::fs::write(&path, content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(IniWrite { path, backup })
+}
+
+/// Check a value for `file`/`section`/`key`: known keys are converted to
+/// the form the game writes, others are taken as text
+pub fn normalize_ini_value(
+    game_type: GameType,
+    file: &str,
+    section: &str,
+    key: &str,
+    value: &str,
+) -> Result<String> {
+    match find_known_ini_key(game_type, file, section, key) {
+        Some(known) => known.kind.normalize(value),
+        None => Ok(value.trim().to_string()),
+    }
+}
+
+/// Set one key, keeping the rest of the file as it is. Known keys are
+/// checked against their type first.
+pub fn set_ini_value(
+    game: &Game,
+    backup_dir: &Path,
+    file: &str,
+    section: &str,
+    key: &str,
+    value: &str,
+) -> Result<IniWrite> {
+    if section.trim().is_empty() || key.trim().is_empty() {
+        bail!(Invalid::new("INI section and key can't be empty"));
+    }
+    let value = normalize_ini_value(game.game_type, file, section, key, value)?;
+    let setting = IniSetting::new(file, section.trim(), key.trim(), value);
+    let content = apply_ini_text(&read_ini_text(game, file)?, &[&setting]);
+    write_ini_text(game, backup_dir, file, &content)
+}
+
+/// Put a backup made by [`backup_ini`] back in place, backing up the
+/// current file first
+pub fn restore_ini_backup(game: &Game, backup_dir: &Path, backup: &Path) -> Result<IniWrite> {
+    let name = backup
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file = game.game_type.ini_files().iter().copied().find(|f| {
+        ini_backups(backup.parent().unwrap_or(backup_dir), f)
+            .is_ok_and(|b| b.iter().any(|p| p == backup))
+    });
+    let Some(file) = file else {
+        bail!(Invalid::new(format!(
+            "'{}' is not a backup of one of {}'s INI files",
+            name, game.name
+        )));
+    };
+    let bytes =
+        std::fs::read(backup).with_context(|| format!("Failed to read {}", backup.display()))?;
+    write_ini_text(game, backup_dir, file, &String::from_utf8_lossy(&bytes))
+}
+
+/// Copy the game's INI files into `dir`, replacing what it held. Returns the
+/// files copied.
+pub fn store_ini_files(game: &Game, dir: &Path) -> Result<Vec<&'static str>> {
+    let source = ini_dir(game)?;
+    let files: Vec<&'static str> = game
+        .game_type
+        .ini_files()
+        .iter()
+        .copied()
+        .filter(|f| source.join(f).is_file())
+        .collect();
+    if files.is_empty() {
+        bail!(Invalid::new(format!(
+            "No INI files in {}; start the game once to create them",
+            source.display()
+        )));
+    }
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)
+            .with_context(|| format!("Failed to clear {}", dir.display()))?;
+    }
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    for file in &files {
+        std::fs::copy(source.join(file), dir.join(file))
+            .with_context(|| format!("Failed to copy {}", file))?;
+    }
+    Ok(files)
+}
+
+/// Settings of the INI files stored in `dir`
+pub fn stored_ini_settings(game_type: GameType, dir: &Path) -> Result<Vec<IniSetting>> {
+    let mut out = Vec::new();
+    for file in game_type.ini_files() {
+        let path = dir.join(file);
+        if !path.is_file() {
+            continue;
+        }
+        let bytes =
+            std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        out.extend(parse_ini(file, &String::from_utf8_lossy(&bytes)));
+    }
+    Ok(out)
+}
+
+/// Write the INI files stored in `dir` over the game's, backing each one up
+/// first. Files `dir` doesn't have, or that already match, are left alone.
+pub fn install_ini_files(game: &Game, backup_dir: &Path, dir: &Path) -> Result<Vec<IniWrite>> {
+    let mut writes = Vec::new();
+    for file in game.game_type.ini_files() {
+        let stored = dir.join(file);
+        if !stored.is_file() {
+            continue;
+        }
+        let bytes = std::fs::read(&stored)
+            .with_context(|| format!("Failed to read {}", stored.display()))?;
+        let content = String::from_utf8_lossy(&bytes);
+        if read_ini_text(game, file)? == content {
+            continue;
+        }
+        writes.push(write_ini_text(game, backup_dir, file, &content)?);
+    }
+    Ok(writes)
+}
+
+/// An INI value written for a profile, with the value it replaced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedIniValue {
+    pub setting: IniSetting,
+    /// None when the key wasn't set
+    pub original: Option<String>,
+}
+
+/// INI values written for the active profile, recorded so they can be
+/// undone when it's left. Every write backs the file up first.
+#[derive(Debug, Clone)]
+pub struct IniChanges {
+    backup_dir: PathBuf,
+    record: PathBuf,
+}
+
+impl IniChanges {
+    pub fn new(backup_dir: impl Into<PathBuf>, record: impl Into<PathBuf>) -> Self {
+        Self {
+            backup_dir: backup_dir.into(),
+            record: record.into(),
+        }
+    }
+
+    /// Backups and record of `game_id` under `paths`
+    pub fn for_game(paths: &Paths, game_id: &str) -> Self {
+        Self::new(
+            paths.ini_backups_dir(game_id),
+            paths.ini_changes_file(game_id),
+        )
+    }
+
+    /// Whether any change is waiting to be undone
+    pub fn is_empty(&self) -> bool {
+        !self.record.exists()
+    }
+
+    /// Changes not undone yet
+    pub fn recorded(&self) -> Result<Vec<ChangedIniValue>> {
+        if !self.record.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&self.record)
+            .with_context(|| format!("Failed to read {}", self.record.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", self.record.display()))
+    }
+
+    fn save(&self, changes: &[ChangedIniValue]) -> Result<()> {
+        if changes.is_empty() {
+            if self.record.exists() {
+                std::fs::remove_file(&self.record)
+                    .with_context(|| format!("Failed to remove {}", self.record.display()))?;
+            }
+            return Ok(());
+        }
+        if let Some(parent) = self.record.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&self.record, serde_json::to_string_pretty(changes)?)
+            .with_context(|| format!("Failed to write {}", self.record.display()))
+    }
+
+    /// Write `settings`, recording the values they replace. A key changed
+    /// before keeps the value it had before the first change.
+    pub fn apply(&self, game: &Game, settings: &[IniSetting]) -> Result<Vec<IniWrite>> {
+        if settings.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut changes = self.recorded()?;
+        for setting in settings {
+            match changes.iter_mut().find(|c| c.setting.same_key(setting)) {
+                Some(change) => change.setting.value = setting.value.clone(),
+                None => {
+                    let original = read_ini_settings(game, &setting.file)?
+                        .into_iter()
+                        .find(|s| s.same_key(setting))
+                        .map(|s| s.value);
+                    changes.push(ChangedIniValue {
+                        setting: setting.clone(),
+                        original,
+                    });
+                }
+            }
+        }
+        // Recorded first: a key the write doesn't reach is left alone on undo
+        self.save(&changes)?;
+        write_game_ini(game, &self.backup_dir, settings)
+    }
+
+    /// Undo the recorded changes `undo` selects. Keys the player has set to
+    /// something else since are left as they are.
+    pub fn revert(&self, game: &Game, undo: impl Fn(&IniSetting) -> bool) -> Result<Vec<IniWrite>> {
+        let (selected, kept): (Vec<ChangedIniValue>, Vec<ChangedIniValue>) =
+            self.recorded()?.into_iter().partition(|c| undo(&c.setting));
+        if selected.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut writes = Vec::new();
+        for file in game.game_type.ini_files() {
+            let current = read_ini_text(game, file)?;
+            let content = undo_ini_text(&current, file, &selected);
+            if content != current {
+                writes.push(write_ini_text(game, &self.backup_dir, file, &content)?);
+            }
+        }
+        self.save(&kept)?;
+        Ok(writes)
+    }
+
+    /// [`store_ini_files`], with the recorded changes undone in the copies
+    pub fn store_ini_files(&self, game: &Game, dir: &Path) -> Result<Vec<&'static str>> {
+        let files = store_ini_files(game, dir)?;
+        let changes = self.recorded()?;
+        for file in &files {
+            let path = dir.join(file);
+            let bytes = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let content = String::from_utf8_lossy(&bytes);
+            let unchanged = undo_ini_text(&content, file, &changes);
+            if unchanged != content {
+                std::fs::write(&path, unchanged)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+        }
+        Ok(files)
+    }
+
+    /// The game's INI values as they'd be without the recorded changes
+    pub fn unchanged_game_ini(&self, game: &Game) -> Result<Vec<IniSetting>> {
+        if game.ini_dir().is_none() {
+            return Ok(Vec::new());
+        }
+        let changes = self.recorded()?;
+        let mut out = Vec::new();
+        for file in game.game_type.ini_files() {
+            let content = undo_ini_text(&read_ini_text(game, file)?, file, &changes);
+            out.extend(parse_ini(file, &content));
+        }
+        Ok(out)
+    }
+}
+
+/// Undo the `changes` made to `file` in its text `content`
+fn undo_ini_text(content: &str, file: &str, changes: &[ChangedIniValue]) -> String {
+    let current = parse_ini(file, content);
+    let mut restore = Vec::new();
+    let mut remove = Vec::new();
+    for change in changes {
+        let still_set = current
+            .iter()
+            .any(|s| s.same_key(&change.setting) && s.value == change.setting.value.trim());
+        if !still_set {
+            continue;
+        }
+        match &change.original {
+            Some(value) => restore.push(IniSetting {
+                value: value.clone(),
+                ..change.setting.clone()
+            }),
+            None => remove.push(&change.setting),
+        }
+    }
+    if restore.is_empty() && remove.is_empty() {
+        return content.to_string();
+    }
+    let content = apply_ini_text(content, &restore.iter().collect::<Vec<_>>());
+    remove_ini_text(&content, &remove)
+}
+
+/// INI entries that let loose files in Data override the game's archives
+/// ("archive invalidation"), or none when the game doesn't need them.
+/// Fallout 4 only prefers loose files with an empty `sResourceDataDirsFinal`;
+/// Fallout 3 and New Vegas ignore loose textures and meshes without
+/// `bInvalidateOlderFiles`.
+pub fn archive_invalidation_settings(game_type: GameType) -> Vec<IniSetting> {
+    let (file, list_key) = match game_type {
+        GameType::Fallout4 | GameType::Fallout4VR => {
+            let files = game_type.ini_files();
+            let file = files
+                .iter()
+                .copied()
+                .find(|f| f.ends_with("Custom.ini"))
+                .unwrap_or(files[0]);
+            (file, "sResourceDataDirsFinal")
+        }
+        GameType::Fallout3 | GameType::FalloutNV => ("Fallout.ini", "SInvalidationFile"),
+        _ => return Vec::new(),
+    };
+    vec![
+        IniSetting::new(file, "Archive", "bInvalidateOlderFiles", "1"),
+        IniSetting::new(file, "Archive", list_key, ""),
+    ]
+}
+
+/// Archive invalidation entries missing from the game's INI files; None
+/// when the game doesn't need them
+pub fn missing_archive_invalidation(game: &Game) -> Result<Option<Vec<IniSetting>>> {
+    let wanted = archive_invalidation_settings(game.game_type);
+    let Some(file) = wanted.first().map(|s| s.file.clone()) else {
+        return Ok(None);
+    };
+    let current = read_ini_settings(game, &file)?;
+    Ok(Some(
+        wanted
+            .into_iter()
+            .filter(|want| {
+                !current
+                    .iter()
+                    .any(|s| s.same_key(want) && s.value.trim() == want.value)
+            })
+            .collect(),
+    ))
+}
+
+/// Write the missing archive invalidation entries, backing the file up
+/// first. None when nothing had to change.
+pub fn enable_archive_invalidation(game: &Game, backup_dir: &Path) -> Result<Option<IniWrite>> {
+    let missing = match missing_archive_invalidation(game)? {
+        Some(missing) if !missing.is_empty() => missing,
+        _ => return Ok(None),
+    };
+    Ok(write_game_ini(game, backup_dir, &missing)?
+        .into_iter()
+        .next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_layer() {
+        let base = parse_ini(
+            "SkyrimPrefs.ini",
+            "; comment\n[Display]\niShadowMapResolution=2048\nfShadowDistance=4000.0000\n\n[Grass]\nbAllowCreateGrass=1\n",
+        );
+        assert_eq!(base.len(), 3);
+        assert_eq!(base[0].section, "Display");
+
+        let layered = layer_ini(
+            &base,
+            &[IniSetting::new(
+                "skyrimprefs.ini",
+                "display",
+                "ISHADOWMAPRESOLUTION",
+                "1024",
+            )],
+        );
+        assert_eq!(layered.len(), 3);
+        assert_eq!(layered[0].value, "1024");
+    }
+
+    #[test]
+    fn test_diff_ini() {
+        let left = vec![
+            IniSetting::new("SkyrimPrefs.ini", "Display", "fShadowDistance", "8000"),
+            IniSetting::new("SkyrimPrefs.ini", "Display", "iMaxAnisotropy", "16"),
+            IniSetting::new("SkyrimPrefs.ini", "Water", "bReflectLODLand", "1"),
+        ];
+        let right = vec![
+            IniSetting::new("SkyrimPrefs.ini", "Display", "fShadowDistance", "8000.0000"),
+            IniSetting::new("SkyrimPrefs.ini", "Display", "iMaxAnisotropy", "4"),
+            IniSetting::new("SkyrimPrefs.ini", "Grass", "fGrassStartFadeDistance", "0"),
+        ];
+
+        let diff = diff_ini(&left, &right);
+        let keys: Vec<(&str, IniChange)> =
+            diff.iter().map(|d| (d.key.as_str(), d.change())).collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("iMaxAnisotropy", IniChange::Changed),
+                ("fGrassStartFadeDistance", IniChange::Added),
+                ("bReflectLODLand", IniChange::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_ini_text_preserves_layout() {
+        let content = "[Display]\n; shadows\niShadowMapResolution=2048\n\n[Water]\nbReflectSky=1\n";
+        let a = IniSetting::new("SkyrimPrefs.ini", "display", "ishadowmapresolution", "512");
+        let b = IniSetting::new("SkyrimPrefs.ini", "Display", "iMaxAnisotropy", "0");
+        let c = IniSetting::new("SkyrimPrefs.ini", "Decals", "uMaxDecals", "50");
+
+        let out = apply_ini_text(content, &[&a, &b, &c]);
+        assert_eq!(
+            out,
+            "[Display]\n; shadows\niShadowMapResolution=512\niMaxAnisotropy=0\n\n[Water]\nbReflectSky=1\n\n[Decals]\nuMaxDecals=50\n"
+        );
+        assert_eq!(
+            remove_ini_text(&out, &[&b, &c]),
+            "[Display]\n; shadows\niShadowMapResolution=512\n\n[Water]\nbReflectSky=1\n\n[Decals]\n"
+        );
+    }
+
+    #[test]
+    fn test_ini_source_parse() {
+        assert!(matches!(
+            IniSource::parse("current").unwrap(),
+            IniSource::Current
+        ));
+        assert!(matches!(
+            IniSource::parse("preset:quality").unwrap(),
+            IniSource::Preset(p) if p.name == "quality"
+        ));
+        assert!(matches!(
+            IniSource::parse("profile:current").unwrap(),
+            IniSource::Profile(n) if n == "current"
+        ));
+        assert!(IniSource::parse("preset:ultra").is_err());
+    }
+
+    #[test]
+    fn test_presets_cover_same_keys() {
+        let names: Vec<&str> = ini_presets().iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["potato", "performance", "quality"]);
+
+        let potato = find_ini_preset("Potato").unwrap().settings();
+        for preset in ini_presets() {
+            assert!(preset.supports(GameType::SkyrimSE));
+            let settings = preset.settings();
+            assert_eq!(settings.len(), potato.len());
+            assert!(settings
+                .iter()
+                .all(|s| potato.iter().any(|p| p.same_key(s))));
+        }
+    }
+
+    #[test]
+    fn test_known_keys_and_normalize() {
+        let fo4 = GameType::Fallout4;
+        assert_eq!(resolve_ini_file(fo4, "main").unwrap(), "Fallout4.ini");
+        assert_eq!(resolve_ini_file(fo4, "PREFS").unwrap(), "Fallout4Prefs.ini");
+        assert_eq!(
+            resolve_ini_file(fo4, "custom").unwrap(),
+            "Fallout4Custom.ini"
+        );
+        assert!(resolve_ini_file(GameType::SkyrimVR, "custom").is_err());
+
+        let invalidate =
+            find_known_ini_key(fo4, "fallout4.ini", "archive", "binvalidateolderfiles").unwrap();
+        assert_eq!(invalidate.kind, IniValueKind::Bool);
+        assert!(find_known_ini_key(
+            GameType::SkyrimSE,
+            "Skyrim.ini",
+            "Archive",
+            "bInvalidateOlderFiles"
+        )
+        .is_none());
+
+        assert_eq!(IniValueKind::for_key("iSize W"), IniValueKind::Int);
+        assert_eq!(IniValueKind::for_key("SLocalSavePath"), IniValueKind::Text);
+        assert!(known_ini_keys(GameType::SkyrimSE)
+            .iter()
+            .all(|k| k.role.file(GameType::SkyrimSE).is_some()));
+        assert_eq!(IniValueKind::Bool.normalize("on").unwrap(), "1");
+        assert!(IniValueKind::Bool.normalize("2").is_err());
+        assert_eq!(IniValueKind::Float.normalize("8000").unwrap(), "8000.0000");
+        assert!(IniValueKind::Int.normalize("1.5").is_err());
+        assert_eq!(
+            normalize_ini_value(fo4, "Fallout4.ini", "Mods", "sAnything", " x ").unwrap(),
+            "x"
+        );
+    }
+
+    #[test]
+    fn test_set_value_backs_up_and_restores() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::SkyrimSE, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("compatdata"));
+        let backups = dir.path().join("backups");
+
+        // A new file has nothing to back up
+        let write = set_ini_value(
+            &game,
+            &backups,
+            "SkyrimPrefs.ini",
+            "Display",
+            "fShadowDistance",
+            "4000",
+        )
+        .unwrap();
+        assert!(write.backup.is_none());
+        assert_eq!(
+            read_ini_text(&game, "SkyrimPrefs.ini").unwrap(),
+            "[Display]\nfShadowDistance=4000.0000\n"
+        );
+        assert!(set_ini_value(
+            &game,
+            &backups,
+            "SkyrimPrefs.ini",
+            "Display",
+            "bSAOEnable",
+            "maybe"
+        )
+        .is_err());
+
+        let write = set_ini_value(
+            &game,
+            &backups,
+            "SkyrimPrefs.ini",
+            "Display",
+            "bSAOEnable",
+            "true",
+        )
+        .unwrap();
+        let backup = write.backup.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&backup).unwrap(),
+            "[Display]\nfShadowDistance=4000.0000\n"
+        );
+        assert_eq!(
+            ini_backups(&backups, "SkyrimPrefs.ini").unwrap(),
+            vec![backup.clone()]
+        );
+        assert!(ini_backups(&backups, "Skyrim.ini").unwrap().is_empty());
+
+        std::fs::write(
+            game.ini_dir().unwrap().join("SkyrimPrefs.ini"),
+            "[Display]\nbSAOEnable=1\n[Launcher]\nuLastAspectRatio=1\n",
+        )
+        .unwrap();
+        let entries = ini_entries(&game, "SkyrimPrefs.ini").unwrap();
+        let sao = entries.iter().find(|e| e.key == "bSAOEnable").unwrap();
+        assert_eq!(sao.value.as_deref(), Some("1"));
+        assert!(entries
+            .iter()
+            .any(|e| e.known.is_some() && e.value.is_none()));
+        let last = entries.last().unwrap();
+        assert_eq!(
+            (last.key.as_str(), last.known.is_none()),
+            ("uLastAspectRatio", true)
+        );
+
+        restore_ini_backup(&game, &backups, &backup).unwrap();
+        assert_eq!(
+            read_ini_settings(&game, "SkyrimPrefs.ini").unwrap(),
+            vec![IniSetting::new(
+                "SkyrimPrefs.ini",
+                "Display",
+                "fShadowDistance",
+                "4000.0000"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_store_and_install_ini_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let backups = dir.path().join("backups");
+        let stored = dir.path().join("profiles/ini/Quality");
+        let game = Game::new(GameType::SkyrimSE, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("compatdata"));
+        assert!(store_ini_files(&game, &stored).is_err());
+
+        let ini = game.ini_dir().unwrap();
+        std::fs::create_dir_all(&ini).unwrap();
+        std::fs::write(ini.join("Skyrim.ini"), "[General]\nsLanguage=ENGLISH\n").unwrap();
+        std::fs::write(
+            ini.join("SkyrimPrefs.ini"),
+            "[Display]\nfShadowDistance=8000.0000\n",
+        )
+        .unwrap();
+        assert_eq!(
+            store_ini_files(&game, &stored).unwrap(),
+            vec!["Skyrim.ini", "SkyrimPrefs.ini"]
+        );
+        assert!(install_ini_files(&game, &backups, &stored)
+            .unwrap()
+            .is_empty());
+
+        std::fs::write(
+            ini.join("SkyrimPrefs.ini"),
+            "[Display]\nfShadowDistance=2000.0000\n",
+        )
+        .unwrap();
+        let writes = install_ini_files(&game, &backups, &stored).unwrap();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(writes[0].backup.as_ref().unwrap()).unwrap(),
+            "[Display]\nfShadowDistance=2000.0000\n"
+        );
+        assert_eq!(
+            read_ini_text(&game, "SkyrimPrefs.ini").unwrap(),
+            "[Display]\nfShadowDistance=8000.0000\n"
+        );
+    }
+
+    #[test]
+    fn test_ini_changes_undo_to_the_original_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::SkyrimSE, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("compatdata"));
+        let changes = IniChanges::new(dir.path().join("backups"), dir.path().join("changes.json"));
+        let ini = game.ini_dir().unwrap();
+        std::fs::create_dir_all(&ini).unwrap();
+        std::fs::write(
+            ini.join("SkyrimPrefs.ini"),
+            "[Display]\nfShadowDistance=8000.0000\n",
+        )
+        .unwrap();
+        assert!(changes.is_empty());
+        assert!(changes.revert(&game, |_| true).unwrap().is_empty());
+
+        let writes = changes
+            .apply(
+                &game,
+                &[
+                    IniSetting::new("SkyrimPrefs.ini", "Display", "fShadowDistance", "2000"),
+                    IniSetting::new("SkyrimPrefs.ini", "Display", "bSAOEnable", "0"),
+                ],
+            )
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(writes[0].backup.as_ref().unwrap()).unwrap(),
+            "[Display]\nfShadowDistance=8000.0000\n"
+        );
+        assert_eq!(
+            read_ini_text(&game, "SkyrimPrefs.ini").unwrap(),
+            "[Display]\nfShadowDistance=2000\nbSAOEnable=0\n"
+        );
+        assert_eq!(
+            changes.unchanged_game_ini(&game).unwrap(),
+            vec![IniSetting::new(
+                "SkyrimPrefs.ini",
+                "Display",
+                "fShadowDistance",
+                "8000.0000"
+            )]
+        );
+
+        // A second write keeps the value from before the first
+        changes
+            .apply(
+                &game,
+                &[IniSetting::new(
+                    "SkyrimPrefs.ini",
+                    "Display",
+                    "fShadowDistance",
+                    "1000",
+                )],
+            )
+            .unwrap();
+        let recorded = changes.recorded().unwrap();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[0].setting.value, "1000");
+        assert_eq!(recorded[0].original.as_deref(), Some("8000.0000"));
+        assert_eq!(recorded[1].original, None);
+
+        // A value the player changed since is theirs
+        std::fs::write(
+            ini.join("SkyrimPrefs.ini"),
+            "[Display]\nfShadowDistance=1000\nbSAOEnable=1\n",
+        )
+        .unwrap();
+        assert_eq!(changes.revert(&game, |_| true).unwrap().len(), 1);
+        assert_eq!(
+            read_ini_text(&game, "SkyrimPrefs.ini").unwrap(),
+            "[Display]\nfShadowDistance=8000.0000\nbSAOEnable=1\n"
+        );
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_enable_archive_invalidation() {
+        let dir = tempfile::tempdir().unwrap();
+        let backups = dir.path().join("backups");
+        let skyrim = Game::new(GameType::SkyrimSE, dir.path().join("skyrim"))
+            .with_proton_prefix(dir.path().join("skyrim-pfx"));
+        assert!(missing_archive_invalidation(&skyrim).unwrap().is_none());
+        assert!(enable_archive_invalidation(&skyrim, &backups)
+            .unwrap()
+            .is_none());
+
+        let game = Game::new(GameType::Fallout4, dir.path().join("fo4"))
+            .with_proton_prefix(dir.path().join("fo4-pfx"));
+        let ini = game.ini_dir().unwrap();
+        std::fs::create_dir_all(&ini).unwrap();
+        std::fs::write(
+            ini.join("Fallout4Custom.ini"),
+            "[Archive]\nbInvalidateOlderFiles=1\n[Display]\nbBorderless=1\n",
+        )
+        .unwrap();
+        assert_eq!(
+            missing_archive_invalidation(&game).unwrap().unwrap().len(),
+            1
+        );
+
+        let write = enable_archive_invalidation(&game, &backups)
+            .unwrap()
+            .unwrap();
+        assert!(write.backup.is_some());
+        assert_eq!(
+            std::fs::read_to_string(ini.join("Fallout4Custom.ini")).unwrap(),
+            "[Archive]\nbInvalidateOlderFiles=1\nsResourceDataDirsFinal=\n[Display]\nbBorderless=1\n"
+        );
+        assert!(missing_archive_invalidation(&game)
+            .unwrap()
+            .unwrap()
+            .is_empty());
+        assert!(enable_archive_invalidation(&game, &backups)
+            .unwrap()
+            .is_none());
+    }
+}
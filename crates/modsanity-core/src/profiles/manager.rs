@@ -0,0 +1,635 @@
+//! Profile manager
+
+use super::{IniChanges, IniPreset, IniSetting, IniSource, Profile, ToolOverride};
+use crate::activity::{self, Activity};
+use crate::config::{Config, ExternalTool};
+use crate::db::{Database, ProfileRecord};
+use crate::error::{Conflict, Invalid};
+use crate::games::{Game, GameDetector, GameType};
+use crate::plugins;
+use anyhow::{bail, Context, Result};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Profile manager handles profile CRUD operations
+pub struct ProfileManager {
+    config: Arc<RwLock<Config>>,
+    db: Arc<Database>,
+}
+
+impl ProfileManager {
+    /// Create a new ProfileManager
+    pub fn new(config: Arc<RwLock<Config>>, db: Arc<Database>) -> Self {
+        Self { config, db }
+    }
+
+    /// List all profiles for a game
+    pub async fn list_profiles(&self, game_id: &str) -> Result<Vec<Profile>> {
+        let records = self.db.get_profiles_for_game(game_id)?;
+
+        let mut profiles = Vec::new();
+        for record in records {
+            // Load full profile from file
+            let profile_path = self
+                .config
+                .read()
+                .await
+                .paths
+                .game_profiles_dir(game_id)
+                .join(format!("{}.json", record.name));
+
+            if profile_path.exists() {
+                let content = tokio::fs::read_to_string(&profile_path).await?;
+                let profile: Profile = serde_json::from_str(&content)?;
+                profiles.push(profile);
+            } else {
+                // Create minimal profile from DB record
+                profiles.push(Profile {
+                    name: record.name,
+                    description: record.description,
+                    game_id: record.game_id,
+                    mods: Default::default(),
+                    load_order: Vec::new(),
+                    enabled_plugins: Vec::new(),
+                    ini_overrides: Vec::new(),
+                    own_ini_files: false,
+                    own_saves: false,
+                    tool_overrides: Default::default(),
+                    created_at: record.created_at,
+                    updated_at: record.updated_at,
+                });
+            }
+        }
+
+        Ok(profiles)
+    }
+
+    /// Create a new profile
+    pub async fn create_profile(&self, game_id: &str, name: &str) -> Result<Profile> {
+        // Check if exists
+        let existing = self.db.get_profiles_for_game(game_id)?;
+        if existing.iter().any(|p| p.name == name) {
+            bail!(Conflict::new(format!("Profile '{}' already exists", name)));
+        }
+
+        // Create profile
+        let profile = Profile::new(name, game_id);
+
+        // Save to database
+        let now = chrono::Utc::now().to_rfc3339();
+        let record = ProfileRecord {
+            id: None,
+            game_id: game_id.to_string(),
+            name: name.to_string(),
+            description: None,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        self.db.insert_profile(&record)?;
+
+        // Save to file
+        self.save_profile(&profile).await?;
+
+        Ok(profile)
+    }
+
+    /// Delete a profile
+    pub async fn delete_profile(&self, game_id: &str, name: &str) -> Result<()> {
+        let records = self.db.get_profiles_for_game(game_id)?;
+        let record = records
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", name))?;
+
+        // Delete from database
+        self.db.delete_profile(record.id.unwrap())?;
+
+        // Delete file
+        let (profile_path, ini_dir) = {
+            let paths = &self.config.read().await.paths;
+            (
+                paths
+                    .game_profiles_dir(game_id)
+                    .join(format!("{}.json", name)),
+                paths.profile_ini_dir(game_id, name),
+            )
+        };
+
+        if profile_path.exists() {
+            tokio::fs::remove_file(profile_path).await?;
+        }
+        if ini_dir.exists() {
+            tokio::fs::remove_dir_all(ini_dir).await?;
+        }
+        // The profile's saves are the player's; they stay where they are
+
+        Ok(())
+    }
+
+    /// Switch to a profile
+    pub async fn switch_profile(&self, game_id: &str, name: &str) -> Result<()> {
+        // Load the profile
+        let profiles = self.list_profiles(game_id).await?;
+        let profile = profiles
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", name))?;
+        let active = self.config.read().await.active_profile.clone();
+        let previous = profiles
+            .iter()
+            .find(|p| Some(&p.name) == active.as_ref() && p.own_ini_files);
+        let leaving_own_saves = profiles
+            .iter()
+            .any(|p| Some(&p.name) == active.as_ref() && p.own_saves);
+        let changes = self.ini_changes(game_id).await;
+
+        // Apply profile settings to mods
+        let all_mods = self.db.get_mods_for_game(game_id)?;
+
+        for mod_record in all_mods {
+            let mod_id = mod_record.id.unwrap();
+
+            if let Some(mod_state) = profile.mods.get(&mod_record.name) {
+                // Mod is in profile - apply its settings
+                if mod_record.enabled != mod_state.enabled {
+                    self.db.set_mod_enabled(mod_id, mod_state.enabled)?;
+                }
+                if mod_record.priority != mod_state.priority {
+                    self.db.set_mod_priority(mod_id, mod_state.priority)?;
+                }
+            } else {
+                // Mod not in profile - disable it
+                if mod_record.enabled {
+                    self.db.set_mod_enabled(mod_id, false)?;
+                }
+            }
+        }
+
+        // Apply plugin state/load order files and INI files/overrides if we can resolve the game installation.
+        if !profile.enabled_plugins.is_empty()
+            || !profile.load_order.is_empty()
+            || !profile.ini_overrides.is_empty()
+            || profile.own_ini_files
+            || previous.is_some()
+            || !changes.is_empty()
+            || profile.own_saves
+            || leaving_own_saves
+        {
+            let detected = GameDetector::detect_all().await;
+            if let Some(game) = detected.into_iter().find(|g| g.id == game_id) {
+                if !profile.enabled_plugins.is_empty() {
+                    plugins::write_plugins_txt(&game, &profile.enabled_plugins)
+                        .context("Failed to write plugins.txt for profile switch")?;
+                }
+
+                if !profile.load_order.is_empty() {
+                    plugins::write_loadorder_txt(&game, &profile.load_order)
+                        .context("Failed to write loadorder.txt for profile switch")?;
+                }
+
+                self.apply_profile_ini(&game, previous, profile).await?;
+
+                // After the INI files, which may carry another SLocalSavePath
+                self.apply_save_folder(&game, profile).await?;
+            } else {
+                tracing::warn!(
+                    "Profile '{}' has plugin/INI state, but game '{}' is not currently detected; skipping plugins/loadorder write",
+                    name,
+                    game_id
+                );
+            }
+        }
+
+        // Update config
+        let mut config = self.config.write().await;
+        let previous = config.active_profile.replace(name.to_string());
+        config.save().await?;
+        activity::record(
+            &self.db,
+            game_id,
+            Some(name),
+            Activity::ProfileSwitch,
+            Some(name),
+            serde_json::json!({ "from": previous }),
+        );
+
+        Ok(())
+    }
+
+    /// Put a profile's INI in place. Its own INI files, if it has them, are
+    /// the base and its overrides go on top. The previous profile's
+    /// overrides are undone first, so the INI files kept for a profile
+    /// never carry overrides.
+    async fn apply_profile_ini(
+        &self,
+        game: &Game,
+        previous: Option<&Profile>,
+        profile: &Profile,
+    ) -> Result<()> {
+        let paths = self.config.read().await.paths.clone();
+        let changes = IniChanges::for_game(&paths, &game.id);
+        changes
+            .revert(game, |_| true)
+            .context("Failed to undo the previous profile's INI overrides")?;
+
+        // Tweaks made while the previous profile was active stay with it
+        if let Some(previous) = previous {
+            let dir = paths.profile_ini_dir(&game.id, &previous.name);
+            if let Err(e) = changes.store_ini_files(game, &dir) {
+                tracing::warn!(
+                    "Failed to keep INI files of profile '{}': {}",
+                    previous.name,
+                    e
+                );
+            }
+        }
+        if profile.own_ini_files {
+            let dir = paths.profile_ini_dir(&game.id, &profile.name);
+            if dir.is_dir() {
+                super::install_ini_files(game, &paths.ini_backups_dir(&game.id), &dir)
+                    .context("Failed to put the profile's INI files in place")?;
+            } else {
+                tracing::warn!("Profile '{}' has no stored INI files", profile.name);
+            }
+        }
+
+        changes
+            .apply(game, &profile.ini_overrides)
+            .context("Failed to write INI overrides for profile switch")?;
+        Ok(())
+    }
+
+    /// Point the game at the profile's save folder, or at the shared one
+    async fn apply_save_folder(&self, game: &Game, profile: &Profile) -> Result<()> {
+        let changes = self.ini_changes(&game.id).await;
+        if profile.own_saves {
+            let target = self
+                .config
+                .read()
+                .await
+                .paths
+                .profile_saves_dir(&game.id, &profile.name);
+            crate::saves::isolate_saves(game, &profile.name, &target, &changes)
+                .context("Failed to switch to the profile's save folder")?;
+        } else {
+            crate::saves::share_saves(game, &changes)
+                .context("Failed to switch to the shared save folder")?;
+        }
+        Ok(())
+    }
+
+    /// Give a profile its own save folder, or make it share the game's
+    /// again. Takes effect now if the profile is active, else on switch.
+    pub async fn set_profile_saves(&self, game: &Game, name: &str, own: bool) -> Result<()> {
+        let mut profile = self.get_profile(&game.id, name).await?;
+        if own && crate::saves::save_isolation(game.game_type).is_none() {
+            bail!(Invalid::new(format!(
+                "{} saves can't be kept per profile",
+                game.name
+            )));
+        }
+        if profile.own_saves != own {
+            profile.own_saves = own;
+            profile.updated_at = chrono::Utc::now().to_rfc3339();
+            self.save_profile(&profile).await?;
+        }
+        let active = self.config.read().await.active_profile.clone();
+        if active.as_deref() == Some(name) {
+            self.apply_save_folder(game, &profile).await?;
+        }
+        Ok(())
+    }
+
+    /// Store the game's current INI files, without the active profile's
+    /// overrides, as the profile's own, swapped in whenever it's activated.
+    /// Returns the files stored.
+    pub async fn save_profile_ini(&self, game: &Game, name: &str) -> Result<Vec<&'static str>> {
+        let mut profile = self.get_profile(&game.id, name).await?;
+        let dir = self
+            .config
+            .read()
+            .await
+            .paths
+            .profile_ini_dir(&game.id, name);
+        let files = self
+            .ini_changes(&game.id)
+            .await
+            .store_ini_files(game, &dir)?;
+        if !profile.own_ini_files {
+            profile.own_ini_files = true;
+            profile.updated_at = chrono::Utc::now().to_rfc3339();
+            self.save_profile(&profile).await?;
+        }
+        Ok(files)
+    }
+
+    /// Drop a profile's own INI files; it uses whatever is in the game's INI
+    /// folder again
+    pub async fn clear_profile_ini(&self, game_id: &str, name: &str) -> Result<()> {
+        let mut profile = self.get_profile(game_id, name).await?;
+        let dir = self
+            .config
+            .read()
+            .await
+            .paths
+            .profile_ini_dir(game_id, name);
+        if dir.exists() {
+            tokio::fs::remove_dir_all(&dir).await?;
+        }
+        if profile.own_ini_files {
+            profile.own_ini_files = false;
+            profile.updated_at = chrono::Utc::now().to_rfc3339();
+            self.save_profile(&profile).await?;
+        }
+        Ok(())
+    }
+
+    /// Get a single profile by name
+    pub async fn get_profile(&self, game_id: &str, name: &str) -> Result<Profile> {
+        self.list_profiles(game_id)
+            .await?
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", name))
+    }
+
+    /// Merge an INI preset into a profile's overrides
+    pub async fn apply_ini_preset(
+        &self,
+        game_id: &str,
+        name: &str,
+        preset: &IniPreset,
+    ) -> Result<Profile> {
+        if let Some(game_type) = GameType::from_id(game_id) {
+            if !preset.supports(game_type) {
+                bail!(
+                    "Preset '{}' does not support {}",
+                    preset.name,
+                    game_type.display_name()
+                );
+            }
+        }
+
+        let mut profile = self.get_profile(game_id, name).await?;
+        profile.set_ini_overrides(&preset.settings());
+        self.save_profile(&profile).await?;
+        Ok(profile)
+    }
+
+    /// Replace a profile's override for one external tool
+    pub async fn set_tool_override(
+        &self,
+        game_id: &str,
+        name: &str,
+        tool: ExternalTool,
+        ovr: ToolOverride,
+    ) -> Result<Profile> {
+        let mut profile = self.get_profile(game_id, name).await?;
+        profile.set_tool_override(tool, ovr);
+        self.save_profile(&profile).await?;
+        Ok(profile)
+    }
+
+    /// Effective INI values for one side of a comparison. A profile's
+    /// overrides are layered over its own INI files, or like presets over
+    /// the game's INI files without the active profile's overrides.
+    pub async fn resolve_ini(&self, game: &Game, source: &IniSource) -> Result<Vec<IniSetting>> {
+        Ok(match source {
+            IniSource::Current => super::read_game_ini(game)?,
+            IniSource::Profile(name) => {
+                let profile = self.get_profile(&game.id, name).await?;
+                let dir = self
+                    .config
+                    .read()
+                    .await
+                    .paths
+                    .profile_ini_dir(&game.id, name);
+                let base = if profile.own_ini_files && dir.is_dir() {
+                    super::stored_ini_settings(game.game_type, &dir)?
+                } else {
+                    self.ini_changes(&game.id).await.unchanged_game_ini(game)?
+                };
+                super::layer_ini(&base, &profile.ini_overrides)
+            }
+            IniSource::Preset(preset) => {
+                let base = self.ini_changes(&game.id).await.unchanged_game_ini(game)?;
+                super::layer_ini(&base, &preset.settings())
+            }
+        })
+    }
+
+    /// INI values written for the game's active profile
+    async fn ini_changes(&self, game_id: &str) -> IniChanges {
+        IniChanges::for_game(&self.config.read().await.paths, game_id)
+    }
+
+    /// Export a profile to a file
+    pub async fn export_profile(&self, game_id: &str, name: &str, path: &str) -> Result<()> {
+        let profiles = self.list_profiles(game_id).await?;
+        let profile = profiles
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", name))?;
+
+        let content = serde_json::to_string_pretty(profile)?;
+        tokio::fs::write(path, content).await?;
+
+        Ok(())
+    }
+
+    /// Import a profile from a file
+    pub async fn import_profile(&self, game_id: &str, path: &str) -> Result<Profile> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .context("Failed to read profile file")?;
+
+        let mut profile: Profile =
+            serde_json::from_str(&content).context("Failed to parse profile")?;
+
+        // Update game ID to match target
+        profile.game_id = game_id.to_string();
+        // Exported profiles don't carry their INI files
+        profile.own_ini_files = false;
+
+        // Check for name conflict
+        let existing = self.db.get_profiles_for_game(game_id)?;
+        if existing.iter().any(|p| p.name == profile.name) {
+            // Append suffix
+            let mut i = 1;
+            let base_name = profile.name.clone();
+            while existing.iter().any(|p| p.name == profile.name) {
+                profile.name = format!("{} ({})", base_name, i);
+                i += 1;
+            }
+        }
+
+        // Save to database
+        let now = chrono::Utc::now().to_rfc3339();
+        let record = ProfileRecord {
+            id: None,
+            game_id: game_id.to_string(),
+            name: profile.name.clone(),
+            description: profile.description.clone(),
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        self.db.insert_profile(&record)?;
+
+        // Save to file
+        self.save_profile(&profile).await?;
+
+        Ok(profile)
+    }
+
+    /// Create a profile with the given state, or overwrite an existing one
+    /// with the same name
+    pub async fn save_imported_profile(&self, profile: &Profile) -> Result<()> {
+        let existing = self.db.get_profiles_for_game(&profile.game_id)?;
+        if !existing.iter().any(|p| p.name == profile.name) {
+            let now = chrono::Utc::now().to_rfc3339();
+            self.db.insert_profile(&ProfileRecord {
+                id: None,
+                game_id: profile.game_id.clone(),
+                name: profile.name.clone(),
+                description: profile.description.clone(),
+                created_at: now.clone(),
+                updated_at: now,
+            })?;
+        }
+        self.save_profile(profile).await
+    }
+
+    /// Save a profile to disk
+    async fn save_profile(&self, profile: &Profile) -> Result<()> {
+        let profiles_dir = self
+            .config
+            .read()
+            .await
+            .paths
+            .game_profiles_dir(&profile.game_id);
+
+        tokio::fs::create_dir_all(&profiles_dir).await?;
+
+        let profile_path = profiles_dir.join(format!("{}.json", profile.name));
+        let content = serde_json::to_string_pretty(profile)?;
+        tokio::fs::write(profile_path, content).await?;
+
+        Ok(())
+    }
+
+    /// Capture current mod state into a profile
+    pub async fn capture_current_state(
+        &self,
+        game_id: &str,
+        profile_name: &str,
+        mods: &[crate::mods::InstalledMod],
+    ) -> Result<Profile> {
+        let mut profile = Profile::new(profile_name, game_id);
+
+        for m in mods {
+            profile.add_mod(&m.name, m.enabled, m.priority);
+        }
+
+        self.save_profile(&profile).await?;
+
+        Ok(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Sandbox;
+
+    fn manager(sandbox: &Sandbox) -> ProfileManager {
+        let config = sandbox.config(None);
+        let db = Database::open(&config.paths.database_file()).unwrap();
+        ProfileManager::new(Arc::new(RwLock::new(config)), Arc::new(db))
+    }
+
+    fn prefs(value: &str) -> IniSetting {
+        IniSetting::new("SkyrimPrefs.ini", "Display", "fShadowDistance", value)
+    }
+
+    #[tokio::test]
+    async fn test_profile_ini_layers_overrides_over_own_files() {
+        let sandbox = Sandbox::new().unwrap();
+        let profiles = manager(&sandbox);
+        let game = sandbox
+            .game()
+            .with_proton_prefix(sandbox.root().join("compatdata"));
+        let ini = game.ini_dir().unwrap();
+        std::fs::create_dir_all(&ini).unwrap();
+        std::fs::write(
+            ini.join("SkyrimPrefs.ini"),
+            "[Display]\nfShadowDistance=8000.0000\n",
+        )
+        .unwrap();
+        let read = || std::fs::read_to_string(ini.join("SkyrimPrefs.ini")).unwrap();
+
+        let mut low = profiles.create_profile(&game.id, "Low").await.unwrap();
+        low.set_ini_overrides(&[prefs("2000")]);
+        profiles.save_profile(&low).await.unwrap();
+        profiles.apply_profile_ini(&game, None, &low).await.unwrap();
+        assert_eq!(read(), "[Display]\nfShadowDistance=2000\n");
+
+        // Own INI files are stored without the active overrides
+        profiles.create_profile(&game.id, "High").await.unwrap();
+        profiles.save_profile_ini(&game, "High").await.unwrap();
+        let stored = sandbox
+            .config(None)
+            .paths
+            .profile_ini_dir(&game.id, "High")
+            .join("SkyrimPrefs.ini");
+        assert_eq!(
+            std::fs::read_to_string(&stored).unwrap(),
+            "[Display]\nfShadowDistance=8000.0000\n"
+        );
+        std::fs::write(&stored, "[Display]\nfShadowDistance=5000\n").unwrap();
+        let mut high = profiles.get_profile(&game.id, "High").await.unwrap();
+        high.set_ini_overrides(&[IniSetting::new(
+            "SkyrimPrefs.ini",
+            "Display",
+            "bSAOEnable",
+            "0",
+        )]);
+        profiles.save_profile(&high).await.unwrap();
+
+        profiles
+            .apply_profile_ini(&game, None, &high)
+            .await
+            .unwrap();
+        assert_eq!(read(), "[Display]\nfShadowDistance=5000\nbSAOEnable=0\n");
+
+        profiles
+            .apply_profile_ini(&game, Some(&high), &low)
+            .await
+            .unwrap();
+        assert_eq!(read(), "[Display]\nfShadowDistance=2000\n");
+        assert_eq!(
+            std::fs::read_to_string(&stored).unwrap(),
+            "[Display]\nfShadowDistance=5000\n"
+        );
+
+        let resolved = profiles
+            .resolve_ini(&game, &IniSource::Profile("High".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resolved[0], prefs("5000"));
+        assert_eq!(resolved.len(), 2);
+        let current = profiles
+            .resolve_ini(&game, &IniSource::Current)
+            .await
+            .unwrap();
+        assert_eq!(current, vec![prefs("2000")]);
+        // Leaving Low puts back what High's files had
+        assert_eq!(
+            profiles
+                .ini_changes(&game.id)
+                .await
+                .unchanged_game_ini(&game)
+                .unwrap(),
+            vec![prefs("5000")]
+        );
+    }
+}
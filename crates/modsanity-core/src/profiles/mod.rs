@@ -1,11 +1,15 @@
 //! Profile management for mod configurations
 
+pub mod ini;
 mod manager;
+mod tools;
 
+pub use ini::*;
 pub use manager::*;
+pub use tools::*;
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// A profile stores a specific mod configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +32,24 @@ pub struct Profile {
     /// Enabled plugins
     pub enabled_plugins: Vec<String>,
 
+    /// INI overrides written to the game's INI files on activation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ini_overrides: Vec<IniSetting>,
+
+    /// Whether the profile keeps its own copies of the game's INI files,
+    /// swapped into the game's INI folder on activation
+    #[serde(default)]
+    pub own_ini_files: bool,
+
+    /// Whether the profile saves to a folder of its own, so characters of
+    /// different modlists don't mix
+    #[serde(default)]
+    pub own_saves: bool,
+
+    /// External tool overrides, keyed by tool id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_overrides: BTreeMap<String, ToolOverride>,
+
     /// Creation timestamp
     pub created_at: String,
 
@@ -56,6 +78,10 @@ impl Profile {
             mods: HashMap::new(),
             load_order: Vec::new(),
             enabled_plugins: Vec::new(),
+            ini_overrides: Vec::new(),
+            own_ini_files: false,
+            own_saves: false,
+            tool_overrides: BTreeMap::new(),
             created_at: now.clone(),
             updated_at: now,
         }
@@ -80,6 +106,12 @@ impl Profile {
         self.updated_at = chrono::Utc::now().to_rfc3339();
     }
 
+    /// Merge INI overrides into the profile, replacing values for existing keys
+    pub fn set_ini_overrides(&mut self, settings: &[IniSetting]) {
+        self.ini_overrides = layer_ini(&self.ini_overrides, settings);
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+
     /// Set enabled plugins
     pub fn set_enabled_plugins(&mut self, plugins: Vec<String>) {
         self.enabled_plugins = plugins;
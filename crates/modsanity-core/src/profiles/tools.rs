@@ -0,0 +1,163 @@
+//! Per-profile external tool settings
+//!
+//! A profile can point a tool at a different executable, pass it extra
+//! arguments (a BodySlide preset folder, say) and name a mod that should
+//! receive whatever the tool writes into Data. Anything a profile leaves
+//! unset falls back to the global `[external_tools]` settings, whose default
+//! arguments come before the profile's.
+
+use super::Profile;
+use crate::config::{Config, CustomToolConfig, ExternalTool, ToolRuntimeMode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Settings a profile overrides for one tool
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolOverride {
+    /// Executable used instead of the global tool path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Arguments passed before any given at launch
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// Mod that receives files the tool writes into Data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_mod: Option<String>,
+}
+
+impl ToolOverride {
+    pub fn is_empty(&self) -> bool {
+        self.path.is_none() && self.args.is_empty() && self.output_mod.is_none()
+    }
+
+    /// One line for listings, e.g. `args: -v; output: BodySlide Output`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(path) = &self.path {
+            parts.push(format!("path: {}", path));
+        }
+        if !self.args.is_empty() {
+            parts.push(format!("args: {}", self.args.join(" ")));
+        }
+        if let Some(output_mod) = &self.output_mod {
+            parts.push(format!("output: {}", output_mod));
+        }
+        parts.join("; ")
+    }
+}
+
+/// How a tool will actually be launched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolSettings {
+    pub path: Option<String>,
+    pub runtime_mode: ToolRuntimeMode,
+    pub args: Vec<String>,
+    pub output_mod: Option<String>,
+    /// Whether the path comes from the profile rather than the global config
+    pub profile_path: bool,
+    /// Working directory template; the executable's folder when unset
+    pub working_dir: Option<String>,
+    /// Extra environment variables
+    pub env: BTreeMap<String, String>,
+}
+
+impl ToolSettings {
+    /// Global settings for `tool` with `profile`'s overrides applied
+    pub fn resolve(config: &Config, profile: Option<&Profile>, tool: ExternalTool) -> Self {
+        let global = config.external_tool_path(tool).map(str::to_string);
+        let options = config
+            .external_tool_options(tool)
+            .cloned()
+            .unwrap_or_default();
+        let ovr = profile.and_then(|p| p.tool_override(tool));
+        let profile_path = ovr.and_then(|o| o.path.clone());
+        let mut args = options.args;
+        args.extend(ovr.map(|o| o.args.clone()).unwrap_or_default());
+        Self {
+            profile_path: profile_path.is_some(),
+            path: profile_path.or(global),
+            runtime_mode: config.external_tool_runtime_mode(tool),
+            args,
+            output_mod: ovr.and_then(|o| o.output_mod.clone()),
+            working_dir: options.working_dir,
+            env: options.env,
+        }
+    }
+
+    /// Settings of a `[[custom_tools]]` entry; its arguments and working
+    /// directory are still templates
+    pub fn custom(tool: &CustomToolConfig) -> Self {
+        Self {
+            path: Some(tool.path.clone()),
+            runtime_mode: tool.runtime_mode,
+            args: tool.args.clone(),
+            output_mod: None,
+            profile_path: false,
+            working_dir: tool.working_dir.clone(),
+            env: tool.env.clone(),
+        }
+    }
+}
+
+impl Profile {
+    /// This profile's override for `tool`, if it has one
+    pub fn tool_override(&self, tool: ExternalTool) -> Option<&ToolOverride> {
+        self.tool_overrides.get(tool.as_id())
+    }
+
+    /// Replace the override for `tool`; an empty override removes it
+    pub fn set_tool_override(&mut self, tool: ExternalTool, ovr: ToolOverride) {
+        if ovr.is_empty() {
+            self.tool_overrides.remove(tool.as_id());
+        } else {
+            self.tool_overrides.insert(tool.as_id().to_string(), ovr);
+        }
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ToolOptions;
+
+    #[test]
+    fn test_profile_overrides_fall_back_to_global() {
+        let mut config = Config::default();
+        config.set_external_tool_path(ExternalTool::BodySlide, Some("/tools/BodySlide.exe".into()));
+        config.set_external_tool_options(
+            ExternalTool::BodySlide,
+            ToolOptions {
+                args: vec!["-v".into()],
+                working_dir: Some("{data_dir}".into()),
+                ..Default::default()
+            },
+        );
+
+        let mut profile = Profile::new("CBBE", "skyrimse");
+        profile.set_tool_override(
+            ExternalTool::BodySlide,
+            ToolOverride {
+                path: None,
+                args: vec!["--preset-dir".into(), "presets/cbbe".into()],
+                output_mod: Some("BodySlide Output".into()),
+            },
+        );
+
+        let settings = ToolSettings::resolve(&config, Some(&profile), ExternalTool::BodySlide);
+        assert_eq!(settings.path.as_deref(), Some("/tools/BodySlide.exe"));
+        assert!(!settings.profile_path);
+        assert_eq!(settings.args, vec!["-v", "--preset-dir", "presets/cbbe"]);
+        assert_eq!(settings.working_dir.as_deref(), Some("{data_dir}"));
+        assert_eq!(settings.output_mod.as_deref(), Some("BodySlide Output"));
+
+        let global = ToolSettings::resolve(&config, None, ExternalTool::BodySlide);
+        assert_eq!(global.args, vec!["-v"]);
+        assert!(global.output_mod.is_none());
+
+        profile.set_tool_override(ExternalTool::BodySlide, ToolOverride::default());
+        assert!(profile.tool_overrides.is_empty());
+    }
+}
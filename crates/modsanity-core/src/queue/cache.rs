@@ -0,0 +1,328 @@
+//! Archives already on disk
+//!
+//! Archives in the downloads folder and the retained archives of installed
+//! mods are hashed with MD5 (the hash Nexus publishes) and identified once
+//! through Nexus' MD5 search. Hashes are kept until an archive's size or
+//! modification time changes, so the queue can skip downloads that are
+//! already present and entries whose file is already installed. Archives
+//! with an MO2 `.meta` sidecar are identified from it instead of searching.
+
+use crate::db::{Database, DownloadHashRecord};
+use crate::mods::Mo2DownloadMeta;
+use crate::nexus::NexusClient;
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use walkdir::WalkDir;
+
+/// An archive on disk holding a queued Nexus file
+#[derive(Debug, Clone)]
+pub struct CachedArchive {
+    pub path: PathBuf,
+    pub md5: String,
+    /// Mod installed from this archive, when it's a mod's retained archive
+    pub installed_mod: Option<String>,
+}
+
+/// Hash index of archives on disk for one game
+#[derive(Clone)]
+pub struct DownloadCache {
+    db: Arc<Database>,
+    nexus: NexusClient,
+    game_domain: String,
+    game_id: String,
+}
+
+impl DownloadCache {
+    pub fn new(
+        db: Arc<Database>,
+        nexus: NexusClient,
+        game_domain: String,
+        game_id: String,
+    ) -> Self {
+        Self {
+            db,
+            nexus,
+            game_domain,
+            game_id,
+        }
+    }
+
+    /// Hash new or changed archives in `download_dir` and the game's
+    /// retained archives, then identify unknown hashes on Nexus. Returns the
+    /// number of archives hashed.
+    pub async fn refresh(&self, download_dir: &Path) -> Result<usize> {
+        let mut known = Vec::new();
+        for archive in self.db.get_active_mod_archives(&self.game_id)? {
+            let nexus_mod_id = self
+                .db
+                .get_mod(&self.game_id, &archive.mod_name)?
+                .and_then(|m| m.nexus_mod_id);
+            let ids = nexus_mod_id.zip(archive.nexus_file_id);
+            known.push((PathBuf::from(archive.archive_path), ids, None));
+        }
+        let mut paths: Vec<(PathBuf, Option<(i64, i64)>, Option<String>)> =
+            WalkDir::new(download_dir)
+                .max_depth(1)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file() && is_archive(e.path()))
+                .map(|e| {
+                    let meta = Mo2DownloadMeta::for_archive(e.path()).unwrap_or_default();
+                    let ids = meta.nexus_mod_id.zip(meta.nexus_file_id);
+                    (e.into_path(), ids, meta.version)
+                })
+                .collect();
+        paths.extend(known);
+
+        let mut hashed = 0;
+        for (path, ids, version) in paths {
+            match self.hash(&path, ids, version).await {
+                Ok(true) => hashed += 1,
+                Ok(false) => {}
+                Err(e) => tracing::warn!("Failed to hash {}: {}", path.display(), e),
+            }
+        }
+        if hashed > 0 {
+            tracing::info!("Hashed {} archive(s) on disk", hashed);
+        }
+        Ok(hashed)
+    }
+
+    /// Record a freshly downloaded archive of a known Nexus file
+    pub async fn record(&self, path: &Path, nexus_mod_id: i64, nexus_file_id: i64) -> Result<()> {
+        self.hash(path, Some((nexus_mod_id, nexus_file_id)), None)
+            .await?;
+        Ok(())
+    }
+
+    /// Archive on disk that holds `nexus_file_id`, preferring the retained
+    /// archive of an installed mod
+    pub fn find(&self, nexus_mod_id: i64, nexus_file_id: i64) -> Result<Option<CachedArchive>> {
+        let installed: Vec<(String, String)> = self
+            .db
+            .get_active_mod_archives(&self.game_id)?
+            .into_iter()
+            .map(|a| (a.archive_path, a.mod_name))
+            .collect();
+
+        let mut found: Option<CachedArchive> = None;
+        for record in
+            self.db
+                .find_download_hashes(&self.game_domain, nexus_mod_id, nexus_file_id)?
+        {
+            let path = PathBuf::from(&record.path);
+            if file_stamp(&path) != Some((record.size, record.modified)) {
+                // Deleted or replaced since it was hashed
+                self.db.delete_download_hash(&record.path)?;
+                continue;
+            }
+            let installed_mod = installed
+                .iter()
+                .find(|(archive, _)| *archive == record.path)
+                .map(|(_, name)| name.clone());
+            if found.is_none() || installed_mod.is_some() {
+                found = Some(CachedArchive {
+                    path,
+                    md5: record.md5,
+                    installed_mod,
+                });
+            }
+        }
+        Ok(found)
+    }
+
+    /// Hash `path` unless its recorded hash is current, and identify it.
+    /// Returns whether the file was hashed.
+    async fn hash(
+        &self,
+        path: &Path,
+        ids: Option<(i64, i64)>,
+        version: Option<String>,
+    ) -> Result<bool> {
+        let Some((size, modified)) = file_stamp(path) else {
+            return Ok(false);
+        };
+        let key = path.to_string_lossy().to_string();
+        let existing = self
+            .db
+            .get_download_hash(&key)?
+            .filter(|r| r.size == size && r.modified == modified);
+
+        let (mut record, hashed) = match existing {
+            Some(record) => (record, false),
+            None => {
+                let owned = path.to_path_buf();
+                let md5 = tokio::task::spawn_blocking(move || md5_file(&owned))
+                    .await
+                    .context("Hashing task failed")??;
+                let record = DownloadHashRecord {
+                    path: key,
+                    size,
+                    modified,
+                    md5,
+                    game_domain: None,
+                    nexus_mod_id: None,
+                    nexus_file_id: None,
+                    identified_at: None,
+                    version: None,
+                };
+                (record, true)
+            }
+        };
+
+        let identified_here = record.identified_at.is_some()
+            && record.game_domain.as_deref() == Some(self.game_domain.as_str());
+        if let Some((mod_id, file_id)) = ids {
+            record.game_domain = Some(self.game_domain.clone());
+            record.nexus_mod_id = Some(mod_id);
+            record.nexus_file_id = Some(file_id);
+            record.identified_at = Some(chrono::Utc::now().to_rfc3339());
+            record.version = version.or(record.version);
+        } else if !identified_here && record.nexus_file_id.is_none() {
+            // Keep the hash when the lookup fails; it's retried next refresh
+            match self.nexus.md5_search(&self.game_domain, &record.md5).await {
+                Ok(hits) => {
+                    let hit = hits.first();
+                    record.game_domain = Some(self.game_domain.clone());
+                    record.nexus_mod_id = hit.map(|h| h.mod_id);
+                    record.nexus_file_id = hit.map(|h| h.file_id);
+                    record.identified_at = Some(chrono::Utc::now().to_rfc3339());
+                }
+                Err(e) => tracing::warn!("Could not identify {}: {}", path.display(), e),
+            }
+        } else if !hashed {
+            return Ok(false);
+        }
+
+        self.db.upsert_download_hash(&record)?;
+        Ok(hashed)
+    }
+}
+
+fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_ascii_lowercase().as_str(), "zip" | "7z" | "rar"))
+}
+
+/// Size and modification time (unix seconds) of a file
+fn file_stamp(path: &Path) -> Option<(i64, i64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let modified = meta
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs() as i64;
+    Some((meta.len() as i64, modified))
+}
+
+/// MD5 of a file's contents as lowercase hex
+pub fn md5_file(path: &Path) -> Result<String> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    md5_hex(BufReader::new(file)).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn md5_hex(mut reader: impl Read) -> std::io::Result<String> {
+    const S: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5,
+        9, 14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10,
+        15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+    const K: [u32; 64] = [
+        0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613,
+        0xfd469501, 0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193,
+        0xa679438e, 0x49b40821, 0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d,
+        0x02441453, 0xd8a1e681, 0xe7d3fbc8, 0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+        0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a, 0xfffa3942, 0x8771f681, 0x6d9d6122,
+        0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70, 0x289b7ec6, 0xeaa127fa,
+        0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665, 0xf4292244,
+        0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+        0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb,
+        0xeb86d391,
+    ];
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+    let mut compress = |block: &[u8]| {
+        let mut m = [0u32; 16];
+        for (word, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        let [mut a, mut b, mut c, mut d] = state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let f = f.wrapping_add(a).wrapping_add(K[i]).wrapping_add(m[g]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(f.rotate_left(S[i]));
+        }
+        for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+            *s = s.wrapping_add(v);
+        }
+    };
+
+    let mut total: u64 = 0;
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut pending = Vec::with_capacity(buf.len() + 64);
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        total += n as u64;
+        pending.extend_from_slice(&buf[..n]);
+        let whole = pending.len() - pending.len() % 64;
+        for block in pending[..whole].chunks_exact(64) {
+            compress(block);
+        }
+        pending.drain(..whole);
+    }
+
+    // Padding: 0x80, zeros, then the message length in bits
+    pending.push(0x80);
+    while pending.len() % 64 != 56 {
+        pending.push(0);
+    }
+    pending.extend_from_slice(&total.wrapping_mul(8).to_le_bytes());
+    for block in pending.chunks_exact(64) {
+        compress(block);
+    }
+
+    Ok(state
+        .iter()
+        .flat_map(|s| s.to_le_bytes())
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_md5_matches_reference_digests() {
+        assert_eq!(
+            md5_hex(&b""[..]).unwrap(),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+        assert_eq!(
+            md5_hex(&b"The quick brown fox jumps over the lazy dog"[..]).unwrap(),
+            "9e107d9d372bb6826bd81d3542a419d6"
+        );
+        // Longer than one block, so padding spills into a second block
+        let digits = "1234567890".repeat(8);
+        assert_eq!(
+            md5_hex(digits.as_bytes()).unwrap(),
+            "57edf4a22be3c955ac49da2e2107b67a"
+        );
+    }
+}
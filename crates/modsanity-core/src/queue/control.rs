@@ -0,0 +1,189 @@
+//! Pausing and cancelling queue processing
+//!
+//! A [`QueueControl`] is shared by a running [`super::QueueProcessor`] and
+//! its tasks. Downloads check it between chunks and installs between archive
+//! entries: pausing holds downloads where they are and starts nothing new,
+//! cancelling stops them and puts their entries back to matched.
+//!
+//! Requests are stored per batch in the database, so `modsanity queue pause`
+//! reaches a batch processed by the TUI or another `queue process` run.
+
+use super::QueueManager;
+use crate::mods::Cancelled;
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often a paused task or a batch watcher looks again
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Requested state of a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchControl {
+    Running,
+    Paused,
+    Cancelled,
+}
+
+impl BatchControl {
+    pub fn from_str(s: Option<&str>) -> Self {
+        match s {
+            Some("paused") => BatchControl::Paused,
+            Some("cancelled") => BatchControl::Cancelled,
+            _ => BatchControl::Running,
+        }
+    }
+
+    /// Stored value; `None` for running
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            BatchControl::Running => None,
+            BatchControl::Paused => Some("paused"),
+            BatchControl::Cancelled => Some("cancelled"),
+        }
+    }
+}
+
+/// Pause and cancel switches of a running batch
+#[derive(Debug, Clone, Default)]
+pub struct QueueControl {
+    paused: Arc<AtomicBool>,
+    cancelled: Arc<AtomicBool>,
+    /// Control this one was split from; its pause and cancel apply here too
+    parent: Option<Box<QueueControl>>,
+}
+
+impl QueueControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A control for one task of this one, which can be cancelled on its own
+    pub fn child(&self) -> Self {
+        Self {
+            parent: Some(Box::new(self.clone())),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed) || self.parent.as_ref().is_some_and(|p| p.is_paused())
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self.parent.as_ref().is_some_and(|p| p.is_cancelled())
+    }
+
+    /// Cancel this control and its children, leaving its parent running
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Mirror a batch's requested state
+    pub fn apply(&self, control: BatchControl) {
+        self.paused
+            .store(control == BatchControl::Paused, Ordering::Relaxed);
+        if control == BatchControl::Cancelled {
+            self.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Flag set once this control itself is cancelled, for extraction
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.cancelled)
+    }
+
+    /// Resolves once cancelled
+    pub async fn cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Wait while paused. Fails with [`Cancelled`] once cancelled.
+    pub async fn checkpoint(&self) -> Result<()> {
+        loop {
+            if self.is_cancelled() {
+                return Err(Cancelled.into());
+            }
+            if !self.is_paused() {
+                return Ok(());
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl QueueManager {
+    /// Requested state of a batch
+    pub fn batch_control(&self, batch_id: &str) -> Result<BatchControl> {
+        Ok(BatchControl::from_str(
+            self.db.get_batch_control(batch_id)?.as_deref(),
+        ))
+    }
+
+    /// Pause, resume (`Running`) or cancel a batch
+    pub fn set_batch_control(&self, batch_id: &str, control: BatchControl) -> Result<()> {
+        self.db.set_batch_control(batch_id, control.as_str())
+    }
+
+    /// Keep `control` in step with the batch's stored state until the
+    /// returned task is aborted
+    pub(super) fn watch_batch(
+        &self,
+        batch_id: &str,
+        control: QueueControl,
+    ) -> tokio::task::JoinHandle<()> {
+        let queue_manager = QueueManager::new(Arc::clone(&self.db));
+        let batch_id = batch_id.to_string();
+        tokio::spawn(async move {
+            loop {
+                match queue_manager.batch_control(&batch_id) {
+                    Ok(state) => control.apply(state),
+                    Err(e) => tracing::warn!("Failed to read state of batch {}: {}", batch_id, e),
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::mods::is_cancelled;
+
+    #[tokio::test]
+    async fn test_pause_resume_and_cancel() {
+        let tmp = tempfile::tempdir().unwrap();
+        let queue = QueueManager::new(Arc::new(Database::open(&tmp.path().join("t.db")).unwrap()));
+        assert_eq!(queue.batch_control("b").unwrap(), BatchControl::Running);
+        queue.set_batch_control("b", BatchControl::Paused).unwrap();
+        assert_eq!(queue.batch_control("b").unwrap(), BatchControl::Paused);
+
+        let control = QueueControl::new();
+        control.apply(BatchControl::Paused);
+        let waiting = tokio::spawn({
+            let control = control.clone();
+            async move { control.checkpoint().await }
+        });
+        tokio::time::sleep(POLL_INTERVAL * 2).await;
+        assert!(!waiting.is_finished());
+
+        control.apply(BatchControl::Running);
+        assert!(waiting.await.unwrap().is_ok());
+
+        control.apply(BatchControl::Cancelled);
+        assert!(control.cancel_flag().load(Ordering::Relaxed));
+        assert!(is_cancelled(&control.checkpoint().await.unwrap_err()));
+        // A resume doesn't undo a cancel
+        control.apply(BatchControl::Running);
+        assert!(control.is_cancelled());
+
+        queue.set_batch_control("b", BatchControl::Running).unwrap();
+        assert_eq!(queue.batch_control("b").unwrap(), BatchControl::Running);
+    }
+}
@@ -0,0 +1,331 @@
+//! Queue batches as files
+//!
+//! A batch can be written to a JSON file and read back on another machine,
+//! so a download list resolved at home can be processed somewhere with
+//! better bandwidth. The file keeps each entry's status, chosen file and
+//! match alternatives; downloads themselves stay behind.
+
+use super::{QueueAlternative, QueueEntry, QueueManager, QueueStatus};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Version of the queue file format
+pub const QUEUE_FILE_VERSION: u32 = 1;
+
+/// An exported queue batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueFile {
+    pub version: u32,
+    pub game_id: String,
+    /// Batch the file was exported from
+    pub batch_id: String,
+    pub exported_at: String,
+    pub entries: Vec<QueueFileEntry>,
+}
+
+/// One entry of an exported batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueFileEntry {
+    pub position: i32,
+    #[serde(default)]
+    pub plugin_name: String,
+    pub mod_name: String,
+    pub nexus_mod_id: i64,
+    #[serde(default)]
+    pub selected_file_id: Option<i64>,
+    #[serde(default)]
+    pub auto_install: bool,
+    #[serde(default)]
+    pub priority: bool,
+    #[serde(default)]
+    pub replaces_mod: Option<String>,
+    #[serde(default)]
+    pub match_confidence: Option<f32>,
+    pub status: String,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub alternatives: Vec<QueueFileAlternative>,
+}
+
+/// A match alternative of an exported entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueFileAlternative {
+    pub mod_id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub downloads: i64,
+    pub score: f32,
+    #[serde(default)]
+    pub thumbnail_url: Option<String>,
+}
+
+impl QueueFile {
+    /// Read and check a queue file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let file: Self = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not a queue file", path.display()))?;
+        if file.version > QUEUE_FILE_VERSION {
+            bail!(
+                "{} uses queue file version {}, newer than this ModSanity supports ({})",
+                path.display(),
+                file.version,
+                QUEUE_FILE_VERSION
+            );
+        }
+        Ok(file)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Status an imported entry starts in. Downloads don't travel with the
+/// file, so anything past matching is matched again and gets downloaded on
+/// this machine; review decisions and skips are kept.
+fn imported_status(status: QueueStatus, nexus_mod_id: i64) -> QueueStatus {
+    match status {
+        QueueStatus::Searching => QueueStatus::Pending,
+        QueueStatus::Downloading
+        | QueueStatus::Downloaded
+        | QueueStatus::Installing
+        | QueueStatus::Completed
+        | QueueStatus::Failed => {
+            if nexus_mod_id > 0 {
+                QueueStatus::Matched
+            } else {
+                QueueStatus::NeedsManual
+            }
+        }
+        other => other,
+    }
+}
+
+impl QueueManager {
+    /// Write `batch_id` to `path`, leaving out completed and skipped entries
+    /// with `pending_only`. Returns the number of entries written.
+    pub fn export_batch(&self, batch_id: &str, path: &Path, pending_only: bool) -> Result<usize> {
+        let mut entries = self.get_batch(batch_id)?;
+        let Some(first) = entries.first() else {
+            bail!("Batch {} has no entries", batch_id);
+        };
+        let game_id = first.game_id.clone();
+        if pending_only {
+            entries.retain(|e| !matches!(e.status, QueueStatus::Completed | QueueStatus::Skipped));
+            if entries.is_empty() {
+                bail!("Batch {} has no pending entries", batch_id);
+            }
+        }
+        let file = QueueFile {
+            version: QUEUE_FILE_VERSION,
+            game_id,
+            batch_id: batch_id.to_string(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            entries: entries
+                .iter()
+                .map(|e| QueueFileEntry {
+                    position: e.queue_position,
+                    plugin_name: e.plugin_name.clone(),
+                    mod_name: e.mod_name.clone(),
+                    nexus_mod_id: e.nexus_mod_id,
+                    selected_file_id: e.selected_file_id,
+                    auto_install: e.auto_install,
+                    priority: e.priority,
+                    replaces_mod: e.replaces_mod.clone(),
+                    match_confidence: e.match_confidence,
+                    status: e.status.to_string(),
+                    error: e.error.clone(),
+                    alternatives: e
+                        .alternatives
+                        .iter()
+                        .map(|a| QueueFileAlternative {
+                            mod_id: a.mod_id,
+                            name: a.name.clone(),
+                            summary: a.summary.clone(),
+                            downloads: a.downloads,
+                            score: a.score,
+                            thumbnail_url: a.thumbnail_url.clone(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        };
+        file.save(path)?;
+        Ok(file.entries.len())
+    }
+
+    /// Add the batch in `path` to the queue of `game_id` as a new batch.
+    /// Returns the new batch ID and the number of entries added.
+    pub fn import_batch(&self, path: &Path, game_id: &str) -> Result<(String, usize)> {
+        let file = QueueFile::load(path)?;
+        if file.game_id != game_id {
+            bail!(
+                "{} was exported for {}, but the active game is {}",
+                path.display(),
+                file.game_id,
+                game_id
+            );
+        }
+
+        let batch_id = self.create_batch();
+        for entry in &file.entries {
+            let status = imported_status(QueueStatus::from_str(&entry.status), entry.nexus_mod_id);
+            self.add_entry(QueueEntry {
+                id: 0,
+                batch_id: batch_id.clone(),
+                game_id: game_id.to_string(),
+                queue_position: entry.position,
+                plugin_name: entry.plugin_name.clone(),
+                mod_name: entry.mod_name.clone(),
+                nexus_mod_id: entry.nexus_mod_id,
+                selected_file_id: entry.selected_file_id,
+                auto_install: entry.auto_install,
+                priority: entry.priority,
+                replaces_mod: entry.replaces_mod.clone(),
+                match_confidence: entry.match_confidence,
+                alternatives: entry
+                    .alternatives
+                    .iter()
+                    .map(|a| QueueAlternative {
+                        mod_id: a.mod_id,
+                        name: a.name.clone(),
+                        summary: a.summary.clone(),
+                        downloads: a.downloads,
+                        score: a.score,
+                        thumbnail_url: a.thumbnail_url.clone(),
+                    })
+                    .collect(),
+                status,
+                progress: 0.0,
+                error: None,
+            })?;
+        }
+        Ok((batch_id, file.entries.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use std::sync::Arc;
+
+    fn entry(
+        batch_id: &str,
+        position: i32,
+        name: &str,
+        nexus_mod_id: i64,
+        status: QueueStatus,
+    ) -> QueueEntry {
+        QueueEntry {
+            id: 0,
+            batch_id: batch_id.to_string(),
+            game_id: "skyrimse".to_string(),
+            queue_position: position,
+            plugin_name: format!("{}.esp", name),
+            mod_name: name.to_string(),
+            nexus_mod_id,
+            selected_file_id: (nexus_mod_id > 0).then_some(nexus_mod_id * 10),
+            auto_install: true,
+            priority: false,
+            replaces_mod: None,
+            match_confidence: Some(0.9),
+            alternatives: vec![QueueAlternative {
+                mod_id: 1,
+                name: "Other".to_string(),
+                summary: String::new(),
+                downloads: 5,
+                score: 0.4,
+                thumbnail_url: None,
+            }],
+            status,
+            progress: 0.0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_export_and_import_batch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::open(&tmp.path().join("test.db")).unwrap());
+        let queue = QueueManager::new(db);
+
+        let batch_id = queue.create_batch();
+        queue
+            .add_entry(entry(&batch_id, 0, "SkyUI", 12604, QueueStatus::Completed))
+            .unwrap();
+        queue
+            .add_entry(entry(&batch_id, 1, "Unknown", 0, QueueStatus::NeedsReview))
+            .unwrap();
+
+        let path = tmp.path().join("out").join("queue.json");
+        assert_eq!(queue.export_batch(&batch_id, &path, false).unwrap(), 2);
+        assert!(queue.import_batch(&path, "fallout4").is_err());
+
+        let (imported_id, count) = queue.import_batch(&path, "skyrimse").unwrap();
+        assert_ne!(imported_id, batch_id);
+        assert_eq!(count, 2);
+
+        let imported = queue.get_batch(&imported_id).unwrap();
+        assert_eq!(imported[0].mod_name, "SkyUI");
+        assert_eq!(imported[0].selected_file_id, Some(126040));
+        assert_eq!(imported[0].status, QueueStatus::Matched);
+        assert_eq!(imported[0].alternatives.len(), 1);
+        assert_eq!(imported[1].status, QueueStatus::NeedsReview);
+        assert_eq!(imported[1].plugin_name, "Unknown.esp");
+    }
+
+    #[test]
+    fn test_export_pending_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::open(&tmp.path().join("test.db")).unwrap());
+        let queue = QueueManager::new(db);
+
+        let batch_id = queue.create_batch();
+        for (position, name, status) in [
+            (0, "SkyUI", QueueStatus::Completed),
+            (1, "USSEP", QueueStatus::Skipped),
+            (2, "Relighting", QueueStatus::Matched),
+            (3, "Lanterns", QueueStatus::Failed),
+        ] {
+            queue
+                .add_entry(entry(
+                    &batch_id,
+                    position,
+                    name,
+                    100 + position as i64,
+                    status,
+                ))
+                .unwrap();
+        }
+
+        let path = tmp.path().join("pending.json");
+        assert_eq!(queue.export_batch(&batch_id, &path, true).unwrap(), 2);
+        let names: Vec<String> = QueueFile::load(&path)
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|e| e.mod_name)
+            .collect();
+        assert_eq!(names, vec!["Relighting", "Lanterns"]);
+
+        // Nothing left to share
+        let done = queue.create_batch();
+        queue
+            .add_entry(entry(&done, 0, "SkyUI", 12604, QueueStatus::Completed))
+            .unwrap();
+        assert!(queue.export_batch(&done, &path, true).is_err());
+        assert_eq!(queue.export_batch(&done, &path, false).unwrap(), 1);
+    }
+}
@@ -0,0 +1,484 @@
+//! Download Queue Management System
+//!
+//! Manages the download queue for mods, including state tracking,
+//! persistence, and processing.
+
+pub mod cache;
+pub mod control;
+pub mod export;
+pub mod processor;
+pub mod state;
+pub mod stats;
+pub mod transfers;
+
+pub use cache::{CachedArchive, DownloadCache};
+pub use control::{BatchControl, QueueControl};
+pub use export::QueueFile;
+pub use processor::QueueProcessor;
+pub use state::{QueueState, QueueStatus};
+pub use stats::{BatchProgress, QueueRate, Throughput};
+pub use transfers::{Transfer, TransferHandle, TransferState, Transfers};
+
+use crate::db::{Database, DownloadQueueEntry, MatchAlternativeRecord, QueueBatchSummary};
+use crate::nexus::graphql::ModUpdateInfo;
+use anyhow::Result;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Queue manager for CRUD operations on download queue
+pub struct QueueManager {
+    db: Arc<Database>,
+}
+
+impl QueueManager {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Create a new import batch
+    pub fn create_batch(&self) -> String {
+        Uuid::new_v4().to_string()
+    }
+
+    /// Add entry to queue
+    pub fn add_entry(&self, entry: QueueEntry) -> Result<i64> {
+        let db_entry = DownloadQueueEntry {
+            id: None,
+            game_id: entry.game_id.clone(),
+            nexus_mod_id: entry.nexus_mod_id,
+            nexus_file_id: entry.selected_file_id,
+            name: entry.mod_name.clone(),
+            filename: None,
+            status: entry.status.to_string(),
+            queue_position: Some(entry.queue_position),
+            plugin_name: Some(entry.plugin_name.clone()),
+            match_confidence: entry.match_confidence,
+            import_batch_id: Some(entry.batch_id.clone()),
+            selected_file_id: entry.selected_file_id,
+            auto_install: entry.auto_install,
+            priority: entry.priority,
+            replaces_mod: entry.replaces_mod.clone(),
+            downloaded: 0,
+            size: None,
+            error: None,
+            started_at: None,
+            completed_at: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let id = self.db.insert_download_queue_entry(&db_entry)?;
+
+        // Insert alternatives if any
+        if !entry.alternatives.is_empty() {
+            let alt_records: Vec<_> = entry
+                .alternatives
+                .iter()
+                .map(|alt| MatchAlternativeRecord {
+                    id: None,
+                    download_id: id,
+                    nexus_mod_id: alt.mod_id,
+                    mod_name: alt.name.clone(),
+                    match_score: alt.score,
+                    summary: Some(alt.summary.clone()),
+                    downloads_count: Some(alt.downloads),
+                    thumbnail_url: alt.thumbnail_url.clone(),
+                })
+                .collect();
+
+            self.db.insert_match_alternatives(id, &alt_records)?;
+        }
+
+        Ok(id)
+    }
+
+    /// Queue every out-of-date mod as an in-place update in a new batch.
+    ///
+    /// The processor picks the matching MAIN file when it runs. Returns the
+    /// batch ID and entry count, or `None` when nothing needs updating.
+    pub fn queue_updates(
+        &self,
+        game_id: &str,
+        updates: &[ModUpdateInfo],
+    ) -> Result<Option<(String, usize)>> {
+        let mod_ids: Vec<i64> = updates.iter().map(|u| u.mod_id).collect();
+        let installed = self.db.find_mods_by_nexus_ids(game_id, &mod_ids)?;
+        if installed.is_empty() {
+            return Ok(None);
+        }
+
+        let batch_id = self.create_batch();
+        let mut queued = 0;
+        for update in updates {
+            let Some(existing) = installed.get(&update.mod_id) else {
+                continue;
+            };
+            self.add_entry(QueueEntry {
+                id: 0,
+                batch_id: batch_id.clone(),
+                game_id: game_id.to_string(),
+                queue_position: queued as i32,
+                plugin_name: String::new(),
+                mod_name: existing.name.clone(),
+                nexus_mod_id: update.mod_id,
+                selected_file_id: None,
+                auto_install: true,
+                priority: false,
+                replaces_mod: Some(existing.name.clone()),
+                match_confidence: Some(1.0),
+                alternatives: Vec::new(),
+                status: QueueStatus::Matched,
+                progress: 0.0,
+                error: None,
+            })?;
+            queued += 1;
+        }
+        Ok(Some((batch_id, queued)))
+    }
+
+    /// Queue a single Nexus mod for download and install in a new batch.
+    ///
+    /// The processor picks the MAIN file when it runs. Returns the batch ID.
+    pub fn queue_mod(&self, game_id: &str, nexus_mod_id: i64, mod_name: &str) -> Result<String> {
+        let batch_id = self.create_batch();
+        self.add_entry(QueueEntry {
+            id: 0,
+            batch_id: batch_id.clone(),
+            game_id: game_id.to_string(),
+            queue_position: 0,
+            plugin_name: String::new(),
+            mod_name: mod_name.to_string(),
+            nexus_mod_id,
+            selected_file_id: None,
+            auto_install: true,
+            priority: false,
+            replaces_mod: None,
+            match_confidence: Some(1.0),
+            alternatives: Vec::new(),
+            status: QueueStatus::Matched,
+            progress: 0.0,
+            error: None,
+        })?;
+        Ok(batch_id)
+    }
+
+    /// Get all entries for a batch
+    pub fn get_batch(&self, batch_id: &str) -> Result<Vec<QueueEntry>> {
+        let db_entries = self.db.get_queue_entries(batch_id)?;
+
+        let mut entries = Vec::new();
+        for db_entry in db_entries {
+            let alternatives_records = self.db.get_match_alternatives(db_entry.id.unwrap())?;
+            let alternatives = alternatives_records
+                .into_iter()
+                .map(|alt| QueueAlternative {
+                    mod_id: alt.nexus_mod_id,
+                    name: alt.mod_name,
+                    summary: alt.summary.unwrap_or_default(),
+                    downloads: alt.downloads_count.unwrap_or(0),
+                    score: alt.match_score,
+                    thumbnail_url: alt.thumbnail_url,
+                })
+                .collect();
+
+            entries.push(QueueEntry {
+                id: db_entry.id.unwrap(),
+                batch_id: db_entry.import_batch_id.unwrap_or_default(),
+                game_id: db_entry.game_id,
+                queue_position: db_entry.queue_position.unwrap_or(0),
+                plugin_name: db_entry.plugin_name.unwrap_or_default(),
+                mod_name: db_entry.name,
+                nexus_mod_id: db_entry.nexus_mod_id,
+                selected_file_id: db_entry.selected_file_id,
+                auto_install: db_entry.auto_install,
+                priority: db_entry.priority,
+                replaces_mod: db_entry.replaces_mod,
+                match_confidence: db_entry.match_confidence,
+                alternatives,
+                status: QueueStatus::from_str(&db_entry.status),
+                progress: if let Some(size) = db_entry.size {
+                    (db_entry.downloaded as f32 / size as f32).min(1.0)
+                } else {
+                    0.0
+                },
+                error: db_entry.error,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Entry counts and downloaded bytes of a batch, for speed and ETA
+    pub fn batch_progress(&self, batch_id: &str) -> Result<BatchProgress> {
+        Ok(BatchProgress::from_entries(
+            &self.db.get_queue_entries(batch_id)?,
+        ))
+    }
+
+    /// Update entry status
+    pub fn update_status(
+        &self,
+        entry_id: i64,
+        status: QueueStatus,
+        error: Option<String>,
+    ) -> Result<()> {
+        self.db
+            .update_download_status(entry_id, &status.to_string(), error.as_deref())
+    }
+
+    /// Update download progress
+    pub fn update_progress(
+        &self,
+        entry_id: i64,
+        downloaded: i64,
+        total: Option<i64>,
+    ) -> Result<()> {
+        self.db
+            .update_download_progress(entry_id, downloaded, total)
+    }
+
+    /// Update queue entry display name.
+    pub fn update_name(&self, entry_id: i64, name: &str) -> Result<()> {
+        self.db.update_download_name(entry_id, name)
+    }
+
+    /// Download a specific Nexus file for an entry instead of its main file
+    pub fn select_file(&self, entry_id: i64, file_id: Option<i64>) -> Result<()> {
+        self.db.update_download_file(entry_id, file_id)
+    }
+
+    /// Mark an entry to be processed before the rest of its batch
+    pub fn set_priority(&self, entry_id: i64, priority: bool) -> Result<()> {
+        self.db.update_download_priority(entry_id, priority)
+    }
+
+    /// Move an entry within its batch. Entries only move among those with the
+    /// same priority, since priority entries always come first. Returns
+    /// whether the entry moved.
+    pub fn move_entry(&self, batch_id: &str, entry_id: i64, to: QueueMove) -> Result<bool> {
+        let mut entries = self.get_batch(batch_id)?;
+        let Some(from) = entries.iter().position(|e| e.id == entry_id) else {
+            anyhow::bail!("Entry {} is not in batch {}", entry_id, batch_id);
+        };
+        let priority = entries[from].priority;
+        let first = entries
+            .iter()
+            .position(|e| e.priority == priority)
+            .unwrap_or(from);
+        let last = entries
+            .iter()
+            .rposition(|e| e.priority == priority)
+            .unwrap_or(from);
+        let target = match to {
+            QueueMove::Up => from.saturating_sub(1).max(first),
+            QueueMove::Down => (from + 1).min(last),
+            QueueMove::Top => first,
+            QueueMove::Bottom => last,
+        };
+        if target == from {
+            return Ok(false);
+        }
+
+        let entry = entries.remove(from);
+        entries.insert(target, entry);
+        let positions: Vec<(i64, i32)> = entries
+            .iter()
+            .enumerate()
+            .map(|(position, e)| (e.id, position as i32))
+            .collect();
+        self.db.update_queue_positions(&positions)?;
+        Ok(true)
+    }
+
+    /// Delete an entry
+    pub fn delete_entry(&self, entry_id: i64) -> Result<()> {
+        self.db.delete_download(entry_id)
+    }
+
+    /// Clear entire batch
+    pub fn clear_batch(&self, batch_id: &str) -> Result<()> {
+        self.db.clear_batch(batch_id)
+    }
+
+    /// List queue batches with summary counts
+    pub fn list_batches(&self, game_id: Option<&str>) -> Result<Vec<QueueBatchSummary>> {
+        self.db.list_queue_batches(game_id)
+    }
+
+    /// Get batches that have failed entries
+    pub fn failed_batches(&self, game_id: Option<&str>) -> Result<Vec<String>> {
+        self.db.get_failed_batches(game_id)
+    }
+
+    /// Reset failed entries in a batch to pending
+    pub fn retry_failed_in_batch(&self, batch_id: &str) -> Result<usize> {
+        self.db.retry_failed_in_batch(batch_id)
+    }
+
+    /// Resolve an entry by assigning a Nexus target and status. A file
+    /// chosen for a different mod is dropped.
+    pub fn resolve_entry(
+        &self,
+        entry_id: i64,
+        nexus_mod_id: i64,
+        mod_name: &str,
+        status: QueueStatus,
+    ) -> Result<()> {
+        self.db
+            .resolve_queue_entry(entry_id, nexus_mod_id, mod_name, &status.to_string())
+    }
+}
+
+/// Where to move a queue entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueMove {
+    Up,
+    Down,
+    Top,
+    Bottom,
+}
+
+impl QueueMove {
+    pub fn from_cli(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "up" => Ok(QueueMove::Up),
+            "down" => Ok(QueueMove::Down),
+            "top" | "first" => Ok(QueueMove::Top),
+            "bottom" | "last" => Ok(QueueMove::Bottom),
+            other => anyhow::bail!(
+                "Unknown direction '{}'. Use up, down, top or bottom.",
+                other
+            ),
+        }
+    }
+}
+
+/// A queue entry
+#[derive(Debug, Clone)]
+pub struct QueueEntry {
+    pub id: i64,
+    pub batch_id: String,
+    pub game_id: String,
+    pub queue_position: i32,
+    pub plugin_name: String,
+    pub mod_name: String,
+    pub nexus_mod_id: i64,
+    pub selected_file_id: Option<i64>,
+    pub auto_install: bool,
+    /// Processed before the rest of the batch
+    pub priority: bool,
+    /// Installed mod to update in place instead of installing alongside
+    pub replaces_mod: Option<String>,
+    pub match_confidence: Option<f32>,
+    pub alternatives: Vec<QueueAlternative>,
+    pub status: QueueStatus,
+    pub progress: f32,
+    pub error: Option<String>,
+}
+
+/// Alternative match for a queue entry
+#[derive(Debug, Clone)]
+pub struct QueueAlternative {
+    pub mod_id: i64,
+    pub name: String,
+    pub summary: String,
+    pub downloads: i64,
+    pub score: f32,
+    pub thumbnail_url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(batch_id: &str, position: i32, name: &str) -> QueueEntry {
+        QueueEntry {
+            id: 0,
+            batch_id: batch_id.to_string(),
+            game_id: "skyrimse".to_string(),
+            queue_position: position,
+            plugin_name: String::new(),
+            mod_name: name.to_string(),
+            nexus_mod_id: position as i64 + 1,
+            selected_file_id: None,
+            auto_install: true,
+            priority: false,
+            replaces_mod: None,
+            match_confidence: Some(1.0),
+            alternatives: Vec::new(),
+            status: QueueStatus::Matched,
+            progress: 0.0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_move_entry_and_priority() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::open(&tmp.path().join("test.db")).unwrap());
+        let queue = QueueManager::new(db);
+
+        let batch_id = queue.create_batch();
+        let ids: Vec<i64> = ["A", "B", "C", "SKSE"]
+            .iter()
+            .enumerate()
+            .map(|(position, name)| {
+                queue
+                    .add_entry(entry(&batch_id, position as i32, name))
+                    .unwrap()
+            })
+            .collect();
+        let names = || -> Vec<String> {
+            queue
+                .get_batch(&batch_id)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.mod_name)
+                .collect()
+        };
+
+        assert!(queue.move_entry(&batch_id, ids[2], QueueMove::Top).unwrap());
+        assert_eq!(names(), ["C", "A", "B", "SKSE"]);
+        assert!(!queue
+            .move_entry(&batch_id, ids[3], QueueMove::Down)
+            .unwrap());
+
+        // Priority entries come first and move among themselves only
+        queue.set_priority(ids[3], true).unwrap();
+        assert_eq!(names(), ["SKSE", "C", "A", "B"]);
+        assert!(!queue.move_entry(&batch_id, ids[2], QueueMove::Up).unwrap());
+        assert!(queue
+            .move_entry(&batch_id, ids[0], QueueMove::Down)
+            .unwrap());
+        assert_eq!(names(), ["SKSE", "C", "B", "A"]);
+    }
+
+    #[test]
+    fn test_select_file_kept_until_resolved_to_another_mod() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::open(&tmp.path().join("test.db")).unwrap());
+        let queue = QueueManager::new(db);
+
+        let batch_id = queue.create_batch();
+        let id = queue.add_entry(entry(&batch_id, 0, "SkyUI")).unwrap();
+        let selected = || queue.get_batch(&batch_id).unwrap()[0].selected_file_id;
+
+        queue.select_file(id, Some(42)).unwrap();
+        assert_eq!(selected(), Some(42));
+
+        // Re-resolving to the same mod keeps the chosen file
+        queue
+            .resolve_entry(id, 1, "SkyUI", QueueStatus::Matched)
+            .unwrap();
+        assert_eq!(selected(), Some(42));
+
+        // A different mod has different files
+        queue
+            .resolve_entry(id, 2, "SkyUI SE", QueueStatus::Matched)
+            .unwrap();
+        assert_eq!(selected(), None);
+
+        queue.select_file(id, Some(7)).unwrap();
+        queue.select_file(id, None).unwrap();
+        assert_eq!(selected(), None);
+    }
+}
@@ -0,0 +1,737 @@
+//! Queue processor for downloading and installing mods
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use crate::db::Database;
+use crate::games::GameBuild;
+use crate::mods::{check_file, is_cancelled, InstallResult, ModManager};
+use crate::nexus::{nexus_game_id, ModFile, NexusClient};
+use crate::queue::{
+    BatchControl, DownloadCache, QueueControl, QueueEntry, QueueManager, QueueStatus, Transfers,
+};
+
+/// Queue processor handles downloading and installing queued mods
+pub struct QueueProcessor {
+    queue_manager: QueueManager,
+    nexus_client: NexusClient,
+    game_domain: String,
+    game_id: String,
+    download_dir: PathBuf,
+    mods: Arc<ModManager>,
+    cache: DownloadCache,
+    max_concurrent: usize,
+    /// Where downloads are reported while they run
+    transfers: Option<Transfers>,
+    /// Installed game build, used to pick between per-build main files
+    game_build: Option<GameBuild>,
+}
+
+impl QueueProcessor {
+    pub fn new(
+        db: Arc<Database>,
+        nexus_client: NexusClient,
+        game_domain: String,
+        game_id: String,
+        download_dir: PathBuf,
+        mods: Arc<ModManager>,
+    ) -> Self {
+        let cache = DownloadCache::new(
+            db.clone(),
+            nexus_client.clone(),
+            game_domain.clone(),
+            game_id.clone(),
+        );
+        Self {
+            queue_manager: QueueManager::new(db),
+            nexus_client,
+            game_domain,
+            game_id,
+            download_dir,
+            mods,
+            cache,
+            max_concurrent: 3, // Download 3 mods at once
+            transfers: None,
+            game_build: None,
+        }
+    }
+
+    /// Report downloads to `transfers`, where each can also be cancelled
+    pub fn with_transfers(mut self, transfers: Transfers) -> Self {
+        self.transfers = Some(transfers);
+        self
+    }
+
+    /// Prefer main files made for this game build (e.g. AE over SE)
+    pub fn with_game_build(mut self, build: Option<GameBuild>) -> Self {
+        self.game_build = build;
+        self
+    }
+
+    /// Process all entries in a batch.
+    ///
+    /// Pause and cancel requests for the batch (see [`QueueManager::set_batch_control`])
+    /// are picked up while it runs.
+    pub async fn process_batch(&self, batch_id: &str, download_only: bool) -> Result<()> {
+        let entries = self.queue_manager.get_batch(batch_id)?;
+
+        tracing::info!(
+            "Processing batch {} with {} entries",
+            batch_id,
+            entries.len()
+        );
+
+        // Filter entries that are ready to download.
+        // NeedsReview entries are processable when the user decides to proceed.
+        let downloadable: Vec<_> = entries
+            .into_iter()
+            .filter(|e| {
+                e.status == QueueStatus::Matched
+                    || e.status == QueueStatus::Pending
+                    || e.status == QueueStatus::NeedsReview
+            })
+            .collect();
+
+        if downloadable.is_empty() {
+            tracing::info!("No entries ready to download in batch {}", batch_id);
+            return Ok(());
+        }
+
+        // Archives already on disk are reused instead of downloaded again
+        if let Err(e) = self.cache.refresh(&self.download_dir).await {
+            tracing::warn!("Failed to hash archives on disk: {}", e);
+        }
+
+        // A cancel left over from an earlier run doesn't apply to this one
+        if self.queue_manager.batch_control(batch_id)? == BatchControl::Cancelled {
+            self.queue_manager
+                .set_batch_control(batch_id, BatchControl::Running)?;
+        }
+        let control = QueueControl::new();
+        let watcher = self.queue_manager.watch_batch(batch_id, control.clone());
+
+        // Priority entries (frameworks like SKSE) finish before the rest start
+        let (priority, rest): (Vec<_>, Vec<_>) = downloadable.into_iter().partition(|e| e.priority);
+        for phase in [priority, rest] {
+            self.process_entries(phase, download_only, &control).await?;
+        }
+        watcher.abort();
+
+        if control.is_cancelled() {
+            self.queue_manager
+                .set_batch_control(batch_id, BatchControl::Running)?;
+            tracing::info!("Batch {} processing cancelled", batch_id);
+        } else {
+            tracing::info!("Batch {} processing complete", batch_id);
+        }
+        Ok(())
+    }
+
+    /// Process `entries` concurrently, up to `max_concurrent` at a time
+    async fn process_entries(
+        &self,
+        entries: Vec<QueueEntry>,
+        download_only: bool,
+        control: &QueueControl,
+    ) -> Result<()> {
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrent));
+        let mut handles = Vec::new();
+
+        for entry in entries {
+            let semaphore = Arc::clone(&semaphore);
+            let processor = self.clone_for_task();
+            let control = control.clone();
+
+            let handle = tokio::spawn(async move {
+                let _permit = semaphore.acquire().await.unwrap();
+                // Nothing new starts while paused; unstarted entries stay as they are on cancel
+                control.checkpoint().await?;
+                processor
+                    .process_entry(entry, download_only, &control)
+                    .await
+            });
+
+            handles.push(handle);
+        }
+
+        // Wait for all downloads to complete
+        for handle in handles {
+            match handle.await? {
+                Err(e) if !is_cancelled(&e) => tracing::error!("Failed to process entry: {}", e),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Process a single queue entry
+    async fn process_entry(
+        &self,
+        entry: QueueEntry,
+        download_only: bool,
+        control: &QueueControl,
+    ) -> Result<()> {
+        tracing::info!(
+            "Processing entry: {} (mod_id: {})",
+            entry.mod_name,
+            entry.nexus_mod_id
+        );
+
+        let resolved_name = self
+            .resolve_mod_name(&entry)
+            .await
+            .unwrap_or_else(|| entry.mod_name.clone());
+        if resolved_name != entry.mod_name {
+            let _ = self.queue_manager.update_name(entry.id, &resolved_name);
+        }
+
+        if entry.nexus_mod_id <= 0 {
+            let msg = "No Nexus mod ID (manual resolution required)".to_string();
+            self.queue_manager
+                .update_status(entry.id, QueueStatus::Failed, Some(msg.clone()))?;
+            anyhow::bail!(msg);
+        }
+
+        // Updates replace an installed mod on purpose, so the duplicate guards don't apply
+        let update_target = match &entry.replaces_mod {
+            Some(name) => match self.queue_manager.db.get_mod(&self.game_id, name)? {
+                Some(record) => Some(record),
+                None => {
+                    let msg = format!("'{}' is no longer installed", name);
+                    self.queue_manager
+                        .update_status(entry.id, QueueStatus::Skipped, Some(msg))?;
+                    return Ok(());
+                }
+            },
+            None => None,
+        };
+
+        // Skip entries that already exist in library by Nexus ID.
+        let existing = self
+            .queue_manager
+            .db
+            .find_mods_by_nexus_ids(&self.game_id, &[entry.nexus_mod_id])?;
+        if update_target.is_none() && existing.contains_key(&entry.nexus_mod_id) {
+            self.queue_manager.update_status(
+                entry.id,
+                QueueStatus::Skipped,
+                Some("Already installed".to_string()),
+            )?;
+            tracing::info!("Skipping {} (already installed)", entry.mod_name);
+            return Ok(());
+        }
+
+        // Legacy fallback: unresolved installs may exist under a numeric name
+        // without nexus_mod_id populated (e.g. "165498"). Treat these as installed
+        // and upgrade metadata so they are not re-added as duplicates.
+        let legacy_name = entry.nexus_mod_id.to_string();
+        if let Some(mut legacy_mod) = self
+            .queue_manager
+            .db
+            .get_mod(&self.game_id, &legacy_name)?
+            .filter(|_| update_target.is_none())
+        {
+            if legacy_mod.nexus_mod_id.is_none()
+                || legacy_mod.nexus_mod_id == Some(entry.nexus_mod_id)
+            {
+                let target_name_conflict = !legacy_mod.name.eq_ignore_ascii_case(&resolved_name)
+                    && self
+                        .queue_manager
+                        .db
+                        .get_mod(&self.game_id, &resolved_name)?
+                        .is_some();
+
+                if !target_name_conflict && !resolved_name.trim().is_empty() {
+                    legacy_mod.name = resolved_name.clone();
+                }
+                legacy_mod.nexus_mod_id = Some(entry.nexus_mod_id);
+                if legacy_mod.nexus_file_id.is_none() {
+                    legacy_mod.nexus_file_id = entry.selected_file_id;
+                }
+                legacy_mod.updated_at = chrono::Utc::now().to_rfc3339();
+                if let Err(e) = self.queue_manager.db.update_mod(&legacy_mod) {
+                    tracing::warn!("Failed to upgrade legacy mod record {}: {}", legacy_name, e);
+                }
+
+                self.queue_manager.update_status(
+                    entry.id,
+                    QueueStatus::Skipped,
+                    Some("Already installed (legacy entry upgraded)".to_string()),
+                )?;
+                tracing::info!(
+                    "Skipping {} (legacy install already present)",
+                    entry.mod_name
+                );
+                return Ok(());
+            }
+        }
+
+        // Step 1: Get file ID if not already selected
+        let mut file_version = None;
+        let file_id = if let (Some(fid), None) = (entry.selected_file_id, &update_target) {
+            fid
+        } else {
+            // Get files and select main file
+            let installed_file_id = update_target.as_ref().and_then(|m| m.nexus_file_id);
+            match self
+                .select_main_file(
+                    entry.nexus_mod_id,
+                    entry.selected_file_id,
+                    installed_file_id,
+                )
+                .await
+            {
+                Ok(file) => {
+                    file_version = Some(file.version);
+                    file.file_id
+                }
+                Err(e) => {
+                    tracing::error!("Failed to select file for {}: {}", entry.mod_name, e);
+                    self.queue_manager.update_status(
+                        entry.id,
+                        QueueStatus::Failed,
+                        Some(format!("No downloadable files found: {}", e)),
+                    )?;
+                    return Err(e);
+                }
+            }
+        };
+
+        if let Some(target) = &update_target {
+            if target.nexus_file_id == Some(file_id) {
+                self.queue_manager.update_status(
+                    entry.id,
+                    QueueStatus::Skipped,
+                    Some("Already up to date".to_string()),
+                )?;
+                return Ok(());
+            }
+        }
+
+        // Step 2: Reuse an archive of this file that is already on disk
+        let cached = match self.cache.find(entry.nexus_mod_id, file_id) {
+            Ok(cached) => cached,
+            Err(e) => {
+                tracing::warn!("Download cache lookup failed for {}: {}", entry.mod_name, e);
+                None
+            }
+        };
+        if let Some(cached) = &cached {
+            if let (Some(name), None) = (&cached.installed_mod, &update_target) {
+                // Installed from the same archive without Nexus metadata
+                if let Some(mut record) = self.queue_manager.db.get_mod(&self.game_id, name)? {
+                    record.nexus_mod_id = record.nexus_mod_id.or(Some(entry.nexus_mod_id));
+                    record.nexus_file_id = record.nexus_file_id.or(Some(file_id));
+                    record.updated_at = chrono::Utc::now().to_rfc3339();
+                    self.queue_manager.db.update_mod(&record)?;
+                }
+                self.queue_manager.update_status(
+                    entry.id,
+                    QueueStatus::Skipped,
+                    Some(format!("Already installed as '{}' (same archive)", name)),
+                )?;
+                tracing::info!("Skipping {} (archive matches '{}')", entry.mod_name, name);
+                return Ok(());
+            }
+        }
+
+        let dest_path = match cached {
+            Some(cached) => {
+                tracing::info!(
+                    "Using {} for {} (MD5 {})",
+                    cached.path.display(),
+                    entry.mod_name,
+                    cached.md5
+                );
+                self.queue_manager.update_status(
+                    entry.id,
+                    QueueStatus::Downloaded,
+                    Some("Found in downloads folder".to_string()),
+                )?;
+                cached.path
+            }
+            None => self.download(&entry, file_id, control).await?,
+        };
+
+        // Step 4: Install if requested
+        if !download_only && entry.auto_install {
+            if let Err(e) = control.checkpoint().await {
+                self.queue_manager
+                    .update_status(entry.id, QueueStatus::Matched, None)?;
+                return Err(e);
+            }
+            self.queue_manager
+                .update_status(entry.id, QueueStatus::Installing, None)?;
+
+            let install_path = dest_path.to_string_lossy().to_string();
+            let result = match &update_target {
+                // Reinstalls in place, replaying saved FOMOD choices when they still apply
+                Some(target) => {
+                    let version = file_version.unwrap_or_else(|| target.version.clone());
+                    self.mods
+                        .update_to_version(
+                            &self.game_id,
+                            &target.name,
+                            &install_path,
+                            &version,
+                            Some(file_id),
+                            None,
+                        )
+                        .await
+                }
+                None => {
+                    self.mods
+                        .install_from_archive_cancellable(
+                            &self.game_id,
+                            &install_path,
+                            None,
+                            Some(entry.nexus_mod_id),
+                            Some(file_id),
+                            Some(&resolved_name),
+                            Some(control.cancel_flag()),
+                        )
+                        .await
+                }
+            };
+            match result {
+                Ok(InstallResult::Completed(installed)) => {
+                    self.queue_manager
+                        .update_status(entry.id, QueueStatus::Completed, None)?;
+                    tracing::info!("Installed {} as {}", resolved_name, installed.name);
+                }
+                Ok(InstallResult::RequiresWizard(_)) => {
+                    let msg = if update_target.is_some() {
+                        "Saved FOMOD choices no longer match (reconfigure with 'f' in the TUI)"
+                    } else {
+                        "FOMOD wizard interaction required (use TUI install)"
+                    };
+                    self.queue_manager.update_status(
+                        entry.id,
+                        QueueStatus::Failed,
+                        Some(msg.to_string()),
+                    )?;
+                }
+                Err(e) if is_cancelled(&e) => {
+                    // The archive stays in downloads, so the next run installs it
+                    self.queue_manager
+                        .update_status(entry.id, QueueStatus::Matched, None)?;
+                    return Err(e);
+                }
+                Err(e) => {
+                    let msg = e.to_string();
+                    if msg.contains("already installed") {
+                        self.queue_manager.update_status(
+                            entry.id,
+                            QueueStatus::Skipped,
+                            Some(msg),
+                        )?;
+                    } else {
+                        self.queue_manager.update_status(
+                            entry.id,
+                            QueueStatus::Failed,
+                            Some(msg.clone()),
+                        )?;
+                    }
+                    return Err(e);
+                }
+            }
+        } else {
+            self.queue_manager
+                .update_status(entry.id, QueueStatus::Completed, None)?;
+            tracing::info!("Downloaded {} (install skipped)", entry.mod_name);
+        }
+
+        Ok(())
+    }
+
+    /// Download a queued file into the downloads folder
+    async fn download(
+        &self,
+        entry: &QueueEntry,
+        file_id: i64,
+        control: &QueueControl,
+    ) -> Result<PathBuf> {
+        // Get download link
+        self.queue_manager
+            .update_status(entry.id, QueueStatus::Downloading, None)?;
+
+        let download_links = match self
+            .nexus_client
+            .get_download_link(&self.game_domain, entry.nexus_mod_id, file_id)
+            .await
+        {
+            Ok(links) => links,
+            Err(e) => {
+                tracing::error!("Failed to get download link for {}: {}", entry.mod_name, e);
+                self.queue_manager.update_status(
+                    entry.id,
+                    QueueStatus::Failed,
+                    Some(format!("Failed to get download link: {}", e)),
+                )?;
+                return Err(e);
+            }
+        };
+
+        if download_links.is_empty() {
+            let err = anyhow::anyhow!("No download links available");
+            self.queue_manager.update_status(
+                entry.id,
+                QueueStatus::Failed,
+                Some(err.to_string()),
+            )?;
+            return Err(err);
+        }
+
+        // Download file
+        let download_url = &download_links[0].url;
+        let filename = format!("{}-{}.zip", entry.nexus_mod_id, file_id);
+        let dest_path = self.download_dir.join(&filename);
+
+        tracing::info!("Downloading {} to {:?}", entry.mod_name, dest_path);
+
+        let entry_id = entry.id;
+        let queue_manager = self.queue_manager.clone();
+        let transfer = self
+            .transfers
+            .as_ref()
+            .map(|t| t.start(&entry.mod_name, &dest_path, control.child()));
+        let reporter = transfer.clone();
+
+        let result = NexusClient::download_file_controlled(
+            download_url,
+            &dest_path,
+            transfer.as_ref().map_or(control, |t| &t.control),
+            move |downloaded, total| {
+                let _ =
+                    queue_manager.update_progress(entry_id, downloaded as i64, Some(total as i64));
+                if let Some(transfer) = &reporter {
+                    transfer.progress(downloaded, total);
+                }
+            },
+        )
+        .await;
+        if let Some(transfer) = &transfer {
+            transfer.finish(&result);
+        }
+
+        match result {
+            Ok(_) => {
+                tracing::info!("Downloaded {} successfully", entry.mod_name);
+                self.queue_manager
+                    .update_status(entry.id, QueueStatus::Downloaded, None)?;
+            }
+            Err(e) if is_cancelled(&e) => {
+                tracing::info!("Download of {} cancelled", entry.mod_name);
+                self.queue_manager
+                    .update_status(entry.id, QueueStatus::Matched, None)?;
+                return Err(e);
+            }
+            Err(e) => {
+                tracing::error!("Failed to download {}: {}", entry.mod_name, e);
+                self.queue_manager.update_status(
+                    entry.id,
+                    QueueStatus::Failed,
+                    Some(format!("Download failed: {}", e)),
+                )?;
+                return Err(e);
+            }
+        }
+
+        if let Err(e) = self
+            .cache
+            .record(&dest_path, entry.nexus_mod_id, file_id)
+            .await
+        {
+            tracing::warn!("Failed to hash {}: {}", dest_path.display(), e);
+        }
+        Ok(dest_path)
+    }
+
+    async fn resolve_mod_name(&self, entry: &QueueEntry) -> Option<String> {
+        if entry.nexus_mod_id <= 0 {
+            return Some(entry.mod_name.clone());
+        }
+
+        if let Ok(Some(catalog)) = self
+            .queue_manager
+            .db
+            .get_catalog_mod_by_id(&self.game_domain, entry.nexus_mod_id)
+        {
+            let n = catalog.name.trim().to_string();
+            if !n.is_empty() {
+                return Some(n);
+            }
+        }
+
+        match self
+            .nexus_client
+            .get_mod_name_by_id(&self.game_domain, entry.nexus_mod_id)
+            .await
+        {
+            Ok(Some(name)) if !name.trim().is_empty() => Some(name.trim().to_string()),
+            _ => {
+                let fallback = entry.mod_name.trim();
+                if fallback.is_empty() {
+                    None
+                } else {
+                    Some(fallback.to_string())
+                }
+            }
+        }
+    }
+
+    /// Select the main file for a mod.
+    ///
+    /// `selected` pins a specific file; `installed_file_id` steers updates to
+    /// the newest MAIN file sharing the installed file's name.
+    async fn select_main_file(
+        &self,
+        mod_id: i64,
+        selected: Option<i64>,
+        installed_file_id: Option<i64>,
+    ) -> Result<ModFile> {
+        let Some(game_id) = nexus_game_id(&self.game_domain) else {
+            anyhow::bail!(
+                "Unsupported game domain for file lookup: {}",
+                self.game_domain
+            );
+        };
+
+        let files = self.nexus_client.get_mod_files(game_id, mod_id).await?;
+        let file = match selected {
+            Some(file_id) => {
+                // A pinned file that Nexus archived or deleted can't be downloaded
+                if let Err(status) = check_file(&files, file_id) {
+                    anyhow::bail!("{}", status.describe());
+                }
+                files.iter().find(|f| f.file_id == file_id)
+            }
+            None => pick_main_file(&files, installed_file_id, self.game_build),
+        };
+        file.cloned().context("No files available for mod")
+    }
+
+    /// Clone necessary fields for async task
+    fn clone_for_task(&self) -> Self {
+        Self {
+            queue_manager: QueueManager::new(self.queue_manager.db.clone()),
+            nexus_client: self.nexus_client.clone(),
+            game_domain: self.game_domain.clone(),
+            game_id: self.game_id.clone(),
+            download_dir: self.download_dir.clone(),
+            mods: Arc::clone(&self.mods),
+            cache: self.cache.clone(),
+            max_concurrent: self.max_concurrent,
+            transfers: self.transfers.clone(),
+            game_build: self.game_build,
+        }
+    }
+}
+
+impl QueueManager {
+    fn clone(&self) -> Self {
+        Self {
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+/// Pick the file to download for a mod.
+///
+/// For updates, the newest MAIN file with the same name as the installed one
+/// wins, so e.g. an SE build is never swapped for an AE build. Otherwise the
+/// first MAIN file made for the installed game `build` is used, then the
+/// first MAIN file not made for another build, falling back to the first
+/// downloadable file.
+pub fn pick_main_file(
+    files: &[ModFile],
+    installed_file_id: Option<i64>,
+    build: Option<GameBuild>,
+) -> Option<&ModFile> {
+    let is_main = |f: &&ModFile| f.category.eq_ignore_ascii_case("MAIN");
+    let installed_name = installed_file_id
+        .and_then(|id| files.iter().find(|f| f.file_id == id))
+        .map(|f| f.name.as_str());
+
+    installed_name
+        .and_then(|name| {
+            files
+                .iter()
+                .filter(is_main)
+                .filter(|f| f.name.eq_ignore_ascii_case(name))
+                .max_by_key(|f| f.file_id)
+        })
+        .or_else(|| {
+            let build = build?;
+            files
+                .iter()
+                .filter(is_main)
+                .find(|f| build.matches(&f.name))
+                .or_else(|| {
+                    files
+                        .iter()
+                        .filter(is_main)
+                        .find(|f| !build.excludes(&f.name))
+                })
+        })
+        .or_else(|| files.iter().find(is_main))
+        .or_else(|| files.iter().find(|f| f.is_available()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(file_id: i64, name: &str, category: &str) -> ModFile {
+        ModFile {
+            file_id,
+            name: name.to_string(),
+            version: file_id.to_string(),
+            category: category.to_string(),
+            size_bytes: 0,
+            file_name: format!("{}.7z", name),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_pick_main_file_follows_installed_variant() {
+        let files = vec![
+            file(10, "SkyUI AE", "MAIN"),
+            file(11, "SkyUI SE", "OLD_VERSION"),
+            file(20, "SkyUI SE", "MAIN"),
+            file(21, "SkyUI AE", "MAIN"),
+        ];
+        assert_eq!(pick_main_file(&files, Some(11), None).unwrap().file_id, 20);
+        assert_eq!(pick_main_file(&files, Some(10), None).unwrap().file_id, 21);
+        // Fresh installs keep the first MAIN file
+        assert_eq!(pick_main_file(&files, None, None).unwrap().file_id, 10);
+    }
+
+    #[test]
+    fn test_pick_main_file_falls_back_to_first_file() {
+        let files = vec![
+            file(5, "Patch", "OPTIONAL"),
+            file(6, "Extra", "MISCELLANEOUS"),
+        ];
+        assert_eq!(pick_main_file(&files, Some(99), None).unwrap().file_id, 5);
+        assert!(pick_main_file(&[], None, None).is_none());
+    }
+
+    #[test]
+    fn test_pick_main_file_for_game_build() {
+        let files = vec![file(10, "SkyUI AE", "MAIN"), file(20, "SkyUI SE", "MAIN")];
+        let se = Some(GameBuild::SkyrimSE);
+        let ae = Some(GameBuild::SkyrimAE);
+        assert_eq!(pick_main_file(&files, None, se).unwrap().file_id, 20);
+        assert_eq!(pick_main_file(&files, None, ae).unwrap().file_id, 10);
+        // The installed file's variant still wins on update
+        assert_eq!(pick_main_file(&files, Some(10), se).unwrap().file_id, 10);
+
+        // Unlabelled files are fine for either build, but AE-only ones aren't
+        let files = vec![file(30, "Tweaks AE", "MAIN"), file(31, "Tweaks", "MAIN")];
+        assert_eq!(pick_main_file(&files, None, se).unwrap().file_id, 31);
+    }
+}
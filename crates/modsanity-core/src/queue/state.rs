@@ -74,6 +74,12 @@ pub struct QueueState {
     pub needs_review: usize,
 }
 
+impl Default for QueueState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl QueueState {
     pub fn new() -> Self {
         Self {
@@ -0,0 +1,236 @@
+//! Queue throughput and ETA
+//!
+//! While a batch is processed its entries' downloaded bytes are sampled
+//! periodically. The speed is averaged over the last few seconds, and the ETA
+//! divides the bytes still to download by it. Entries whose size isn't known
+//! yet (not started) are counted at the average size of the others. Install
+//! time isn't estimated.
+
+use super::QueueStatus;
+use crate::db::DownloadQueueEntry;
+use crate::mods::format_bytes;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How far back the speed is averaged
+const SPEED_WINDOW: Duration = Duration::from_secs(10);
+
+/// Counts and bytes of a batch at one moment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchProgress {
+    pub completed: usize,
+    pub failed: usize,
+    /// Entries still to download or install
+    pub remaining: usize,
+    pub downloaded_bytes: u64,
+    /// Bytes left to download for remaining entries with a known size
+    pub pending_bytes: u64,
+    /// Remaining entries whose size isn't known yet
+    pub unknown_size: usize,
+    /// Average size of the entries with a known size
+    pub average_size: u64,
+}
+
+impl BatchProgress {
+    pub fn from_entries(entries: &[DownloadQueueEntry]) -> Self {
+        let mut progress = Self::default();
+        let mut sized = (0u64, 0u64);
+        for entry in entries {
+            let downloaded = entry.downloaded.max(0) as u64;
+            let size = entry.size.filter(|s| *s > 0).map(|s| s as u64);
+            if let Some(size) = size {
+                sized = (sized.0 + size, sized.1 + 1);
+            }
+            progress.downloaded_bytes += downloaded;
+
+            match QueueStatus::from_str(&entry.status) {
+                QueueStatus::Completed => progress.completed += 1,
+                QueueStatus::Failed => progress.failed += 1,
+                QueueStatus::Pending
+                | QueueStatus::Matched
+                | QueueStatus::NeedsReview
+                | QueueStatus::Downloading
+                | QueueStatus::Downloaded
+                | QueueStatus::Installing => {
+                    progress.remaining += 1;
+                    match size {
+                        Some(size) => progress.pending_bytes += size.saturating_sub(downloaded),
+                        None => progress.unknown_size += 1,
+                    }
+                }
+                QueueStatus::Searching | QueueStatus::NeedsManual | QueueStatus::Skipped => {}
+            }
+        }
+        progress.average_size = sized.0.checked_div(sized.1).unwrap_or(0);
+        progress
+    }
+
+    /// Bytes still to download, guessing unstarted entries at the average size
+    pub fn bytes_left(&self) -> u64 {
+        self.pending_bytes + self.unknown_size as u64 * self.average_size
+    }
+
+    pub fn total(&self) -> usize {
+        self.completed + self.failed + self.remaining
+    }
+}
+
+/// Progress of a batch with its current speed and ETA
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QueueRate {
+    pub progress: BatchProgress,
+    pub bytes_per_sec: Option<f64>,
+    pub eta: Option<Duration>,
+}
+
+impl QueueRate {
+    /// e.g. `8.2 MB/s | ETA 4m 10s`; empty until a speed is known
+    pub fn speed_and_eta(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(speed) = self.bytes_per_sec.filter(|s| *s >= 1.0) {
+            parts.push(format!("{}/s", format_bytes(speed as u64)));
+        }
+        if let Some(eta) = self.eta {
+            parts.push(format!("ETA {}", format_eta(eta)));
+        }
+        parts.join(" | ")
+    }
+
+    /// e.g. `12/40 done, 1 failed | 8.2 MB/s | ETA 4m 10s`
+    pub fn summary(&self) -> String {
+        let progress = &self.progress;
+        let mut text = format!(
+            "{}/{} done",
+            progress.completed + progress.failed,
+            progress.total()
+        );
+        if progress.failed > 0 {
+            text.push_str(&format!(", {} failed", progress.failed));
+        }
+        let speed = self.speed_and_eta();
+        if !speed.is_empty() {
+            text.push_str(" | ");
+            text.push_str(&speed);
+        }
+        text
+    }
+}
+
+/// Download speed over a sliding window of samples
+#[derive(Debug, Clone, Default)]
+pub struct Throughput {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl Throughput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a sample taken at `at` and return the resulting rate
+    pub fn record(&mut self, at: Instant, progress: BatchProgress) -> QueueRate {
+        // Bytes going down means an entry restarted; old samples no longer apply
+        if self
+            .samples
+            .back()
+            .is_some_and(|(_, bytes)| *bytes > progress.downloaded_bytes)
+        {
+            self.samples.clear();
+        }
+        self.samples.push_back((at, progress.downloaded_bytes));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(time, _)| at.duration_since(*time) > SPEED_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+
+        let bytes_per_sec = match (self.samples.front(), self.samples.back()) {
+            (Some((start, first)), Some((end, last))) if end > start => {
+                Some((last - first) as f64 / end.duration_since(*start).as_secs_f64())
+            }
+            _ => None,
+        };
+        let eta = match bytes_per_sec {
+            _ if progress.remaining == 0 => None,
+            Some(speed) if speed >= 1.0 => Some(Duration::from_secs_f64(
+                progress.bytes_left() as f64 / speed,
+            )),
+            _ => None,
+        };
+        QueueRate {
+            progress,
+            bytes_per_sec,
+            eta,
+        }
+    }
+}
+
+/// `1h 05m`, `4m 10s` or `35s`
+pub fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs >= 3600 {
+        format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(status: &str, downloaded: i64, size: Option<i64>) -> DownloadQueueEntry {
+        DownloadQueueEntry {
+            id: None,
+            game_id: "skyrimse".to_string(),
+            nexus_mod_id: 1,
+            nexus_file_id: None,
+            name: "Mod".to_string(),
+            filename: None,
+            status: status.to_string(),
+            queue_position: None,
+            plugin_name: None,
+            match_confidence: None,
+            import_batch_id: None,
+            selected_file_id: None,
+            auto_install: true,
+            priority: false,
+            replaces_mod: None,
+            downloaded,
+            size,
+            error: None,
+            started_at: None,
+            completed_at: None,
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_speed_and_eta() {
+        let mut progress = BatchProgress::from_entries(&[
+            entry("completed", 1000, Some(1000)),
+            entry("downloading", 0, Some(3000)),
+            entry("matched", 0, None),
+            entry("needs_manual", 0, None),
+        ]);
+        assert_eq!(progress.total(), 3);
+        assert_eq!(progress.average_size, 2000);
+        assert_eq!(progress.bytes_left(), 5000);
+
+        let start = Instant::now();
+        let mut throughput = Throughput::new();
+        assert_eq!(throughput.record(start, progress).eta, None);
+
+        progress.downloaded_bytes += 1000;
+        progress.pending_bytes -= 1000;
+        let rate = throughput.record(start + Duration::from_secs(2), progress);
+        assert_eq!(rate.bytes_per_sec, Some(500.0));
+        assert_eq!(rate.eta, Some(Duration::from_secs(8)));
+        assert_eq!(rate.summary(), "1/3 done | 500 B/s | ETA 8s");
+        assert_eq!(format_eta(Duration::from_secs(3725)), "1h 02m");
+    }
+}
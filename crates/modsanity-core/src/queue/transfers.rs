@@ -0,0 +1,258 @@
+//! Live download transfers
+//!
+//! [`Transfers`] tracks every download in flight, whether started by the
+//! queue processor or a one-off download, along with those that finished
+//! recently. Each transfer has its own [`QueueControl`] so it can be cancelled
+//! without stopping the rest of its batch.
+
+use super::QueueControl;
+use crate::mods::is_cancelled;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Finished transfers kept for display
+const MAX_FINISHED: usize = 100;
+
+/// Shortest interval the transfer speed is measured over
+const SPEED_SAMPLE_SECS: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferState {
+    Active,
+    Completed,
+    Failed(String),
+    Cancelled,
+}
+
+/// A download as last reported
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    pub id: u64,
+    pub name: String,
+    pub destination: PathBuf,
+    pub downloaded_bytes: u64,
+    /// 0 when the server didn't say
+    pub total_bytes: u64,
+    /// Bytes per second, smoothed
+    pub speed: f64,
+    pub state: TransferState,
+    pub started: Instant,
+    sample: (Instant, u64),
+}
+
+impl Transfer {
+    pub fn percent(&self) -> Option<u16> {
+        (self.total_bytes > 0)
+            .then(|| ((self.downloaded_bytes * 100) / self.total_bytes).min(100) as u16)
+    }
+
+    /// Seconds left at the current speed
+    pub fn eta_secs(&self) -> Option<u64> {
+        if self.state != TransferState::Active || self.speed < 1.0 || self.total_bytes == 0 {
+            return None;
+        }
+        let left = self.total_bytes.saturating_sub(self.downloaded_bytes);
+        Some((left as f64 / self.speed).round() as u64)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.state == TransferState::Active
+    }
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    next_id: u64,
+    /// Bumped on every change so viewers know when to look again
+    revision: u64,
+    /// Oldest first, with each one's control
+    transfers: Vec<(Transfer, QueueControl)>,
+}
+
+/// Shared list of downloads; clones refer to the same list
+#[derive(Debug, Clone, Default)]
+pub struct Transfers {
+    registry: Arc<Mutex<Registry>>,
+}
+
+impl Transfers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_registry<T>(&self, f: impl FnOnce(&mut Registry) -> T) -> T {
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut registry)
+    }
+
+    /// Register a download of `name` to `destination`. Cancelling the
+    /// transfer cancels `control`, which the download should run under.
+    pub fn start(&self, name: &str, destination: &Path, control: QueueControl) -> TransferHandle {
+        let id = self.with_registry(|r| {
+            r.next_id += 1;
+            r.revision += 1;
+            let now = Instant::now();
+            r.transfers.push((
+                Transfer {
+                    id: r.next_id,
+                    name: name.to_string(),
+                    destination: destination.to_path_buf(),
+                    downloaded_bytes: 0,
+                    total_bytes: 0,
+                    speed: 0.0,
+                    state: TransferState::Active,
+                    started: now,
+                    sample: (now, 0),
+                },
+                control.clone(),
+            ));
+
+            // Forget the oldest finished transfers
+            let finished = r.transfers.iter().filter(|(t, _)| !t.is_active()).count();
+            let mut excess = finished.saturating_sub(MAX_FINISHED);
+            r.transfers.retain(|(t, _)| {
+                let drop = excess > 0 && !t.is_active();
+                excess -= drop as usize;
+                !drop
+            });
+            r.next_id
+        });
+        TransferHandle {
+            transfers: self.clone(),
+            id,
+            control,
+        }
+    }
+
+    fn update(&self, id: u64, f: impl FnOnce(&mut Transfer)) {
+        self.with_registry(|r| {
+            if let Some((transfer, _)) = r.transfers.iter_mut().find(|(t, _)| t.id == id) {
+                f(transfer);
+                r.revision += 1;
+            }
+        });
+    }
+
+    /// Every transfer, oldest first
+    pub fn snapshot(&self) -> Vec<Transfer> {
+        self.with_registry(|r| r.transfers.iter().map(|(t, _)| t.clone()).collect())
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.with_registry(|r| r.revision)
+    }
+
+    /// Cancel an active transfer, returning whether there was one to cancel
+    pub fn cancel(&self, id: u64) -> bool {
+        self.with_registry(|r| {
+            match r
+                .transfers
+                .iter()
+                .find(|(t, _)| t.id == id && t.is_active())
+            {
+                Some((_, control)) => {
+                    control.cancel();
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+
+    /// Forget finished transfers
+    pub fn clear_finished(&self) {
+        self.with_registry(|r| {
+            r.transfers.retain(|(t, _)| t.is_active());
+            r.revision += 1;
+        });
+    }
+}
+
+/// Reporting side of one transfer
+#[derive(Debug, Clone)]
+pub struct TransferHandle {
+    transfers: Transfers,
+    id: u64,
+    /// Control to download under
+    pub control: QueueControl,
+}
+
+impl TransferHandle {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Record progress, as passed to a download's progress callback
+    pub fn progress(&self, downloaded: u64, total: u64) {
+        self.transfers.update(self.id, |t| {
+            t.downloaded_bytes = downloaded;
+            if total > 0 {
+                t.total_bytes = total;
+            }
+            let (at, bytes) = t.sample;
+            let elapsed = at.elapsed().as_secs_f64();
+            if elapsed >= SPEED_SAMPLE_SECS {
+                let rate = downloaded.saturating_sub(bytes) as f64 / elapsed;
+                t.speed = if t.speed == 0.0 {
+                    rate
+                } else {
+                    t.speed * 0.7 + rate * 0.3
+                };
+                t.sample = (Instant::now(), downloaded);
+            }
+        });
+    }
+
+    /// Record how the download ended
+    pub fn finish(&self, result: &anyhow::Result<()>) {
+        self.transfers.update(self.id, |t| {
+            t.speed = 0.0;
+            t.state = match result {
+                Ok(()) => {
+                    t.total_bytes = t.total_bytes.max(t.downloaded_bytes);
+                    TransferState::Completed
+                }
+                Err(e) if is_cancelled(e) => TransferState::Cancelled,
+                Err(e) => TransferState::Failed(e.to_string()),
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_lifecycle() {
+        let transfers = Transfers::new();
+        let batch = QueueControl::new();
+        let a = transfers.start("SkyUI", Path::new("/tmp/a.7z"), batch.child());
+        let b = transfers.start("USSEP", Path::new("/tmp/b.7z"), batch.child());
+        let revision = transfers.revision();
+
+        a.progress(50, 200);
+        assert!(transfers.revision() > revision);
+        assert_eq!(transfers.snapshot()[0].percent(), Some(25));
+
+        // Cancelling one transfer leaves the batch and the other running
+        assert!(transfers.cancel(b.id()));
+        assert!(b.control.is_cancelled());
+        assert!(!a.control.is_cancelled());
+        assert!(!batch.is_cancelled());
+        b.finish(&Err(crate::mods::Cancelled.into()));
+        assert!(!transfers.cancel(b.id()));
+
+        a.finish(&Ok(()));
+        let snapshot = transfers.snapshot();
+        assert_eq!(snapshot[0].state, TransferState::Completed);
+        assert_eq!(snapshot[1].state, TransferState::Cancelled);
+
+        let c = transfers.start("SKSE", Path::new("/tmp/c.7z"), QueueControl::new());
+        transfers.clear_finished();
+        let snapshot = transfers.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].id, c.id());
+    }
+}
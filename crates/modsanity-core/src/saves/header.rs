@@ -0,0 +1,544 @@
+//! Save file headers
+//!
+//! Every save starts with a magic string and a header naming the character,
+//! their level and location and the play time, followed by a screenshot and
+//! the plugins the save was made with. Skyrim Special Edition compresses
+//! everything after the screenshot, LZ4 by default or zlib.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read to parse a header; enough for the header, the screenshot and
+/// the plugin list of every supported game
+const HEADER_READ_LIMIT: u64 = 8 * 1024 * 1024;
+
+/// Decompressed bytes needed for the plugin lists of a Skyrim SE save
+const PLUGIN_LIST_LIMIT: usize = 1024 * 1024;
+
+/// What a save's header says about it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SaveHeader {
+    pub save_number: u32,
+    pub character: String,
+    pub level: u32,
+    pub location: String,
+    /// Play time as the game shows it, e.g. "012.04.33"
+    pub playtime: String,
+    /// When the save was made (game's local time); not stored by Fallout 3
+    /// and New Vegas
+    pub saved_at: Option<NaiveDateTime>,
+    /// Plugins the save was made with, in load order, light plugins last
+    pub plugins: Vec<String>,
+}
+
+/// Parse the header of the save at `path`
+pub fn read_save_header(path: &Path) -> Result<SaveHeader> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut data = Vec::new();
+    file.take(HEADER_READ_LIMIT)
+        .read_to_end(&mut data)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_save_header(&data)
+}
+
+/// Parse a save header from the start of a save file
+pub fn parse_save_header(data: &[u8]) -> Result<SaveHeader> {
+    if let Some(rest) = data.strip_prefix(b"TESV_SAVEGAME") {
+        parse_skyrim(rest)
+    } else if let Some(rest) = data.strip_prefix(b"FO4_SAVEGAME") {
+        parse_fallout4(rest)
+    } else if let Some(rest) = data.strip_prefix(b"FO3SAVEGAME") {
+        parse_fallout3(rest)
+    } else if let Some(rest) = data.strip_prefix(b"TES4SAVEGAME") {
+        parse_oblivion(rest)
+    } else {
+        bail!("Unknown save format")
+    }
+}
+
+/// Little-endian reader over a save file
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).context("Save file ends early")?;
+        let bytes = self
+            .data
+            .get(self.pos..end)
+            .context("Save file ends early")?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into()?))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.bytes(4)?.try_into()?))
+    }
+
+    /// String with a 16-bit length
+    fn wstring(&mut self) -> Result<String> {
+        let len = self.u16()? as usize;
+        Ok(decode(self.bytes(len)?))
+    }
+
+    /// String with an 8-bit length, possibly null-terminated
+    fn bstring(&mut self) -> Result<String> {
+        let len = self.u8()? as usize;
+        Ok(decode(self.bytes(len)?))
+    }
+
+    /// Fallout 3/New Vegas field separator
+    fn bar(&mut self) -> Result<()> {
+        if self.u8()? != b'|' {
+            bail!("Unexpected data in save header");
+        }
+        Ok(())
+    }
+
+    /// Fallout 3/New Vegas string: length, separator, text, separator
+    fn bar_string(&mut self) -> Result<String> {
+        let len = self.u16()? as usize;
+        self.bar()?;
+        let text = decode(self.bytes(len)?);
+        self.bar()?;
+        Ok(text)
+    }
+
+    fn bar_u32(&mut self) -> Result<u32> {
+        let value = self.u32()?;
+        self.bar()?;
+        Ok(value)
+    }
+}
+
+/// Save strings are Windows-1252
+fn decode(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_suffix(b"\0").unwrap_or(bytes);
+    encoding_rs::WINDOWS_1252.decode(bytes).0.into_owned()
+}
+
+/// Windows FILETIME (100ns ticks since 1601) as a date
+fn filetime(ticks: u64) -> Option<NaiveDateTime> {
+    const UNIX_EPOCH_SECS: i64 = 11_644_473_600;
+    let secs = (ticks / 10_000_000) as i64 - UNIX_EPOCH_SECS;
+    DateTime::from_timestamp(secs, 0).map(|d| d.naive_utc())
+}
+
+/// Windows SYSTEMTIME (year, month, weekday, day, hour, minute, second, ms)
+fn systemtime(r: &mut Reader) -> Result<Option<NaiveDateTime>> {
+    let mut fields = [0u16; 8];
+    for field in &mut fields {
+        *field = r.u16()?;
+    }
+    let [year, month, _, day, hour, minute, second, _] = fields.map(u32::from);
+    Ok(NaiveDate::from_ymd_opt(year as i32, month, day)
+        .and_then(|d| d.and_hms_opt(hour, minute, second)))
+}
+
+/// Fields Skyrim and Fallout 4 share, after the header size
+fn parse_creation_header(r: &mut Reader) -> Result<(u32, SaveHeader, u32, u32)> {
+    let version = r.u32()?;
+    let save_number = r.u32()?;
+    let character = r.wstring()?;
+    let level = r.u32()?;
+    let location = r.wstring()?;
+    let playtime = r.wstring()?;
+    let _race = r.wstring()?;
+    let _sex = r.u16()?;
+    let _experience = r.f32()?;
+    let _level_up_experience = r.f32()?;
+    let saved_at = filetime(r.u64()?);
+    let width = r.u32()?;
+    let height = r.u32()?;
+    let header = SaveHeader {
+        save_number,
+        character,
+        level,
+        location,
+        playtime,
+        saved_at,
+        plugins: Vec::new(),
+    };
+    Ok((version, header, width, height))
+}
+
+/// Plugin lists of Skyrim and Fallout 4: full plugins, then (in newer
+/// saves) light plugins
+fn read_plugins(r: &mut Reader, light: bool) -> Result<Vec<String>> {
+    let _size = r.u32()?;
+    let count = r.u8()?;
+    let mut plugins = (0..count)
+        .map(|_| r.wstring())
+        .collect::<Result<Vec<_>>>()?;
+    if light {
+        let count = r.u16()?;
+        for _ in 0..count {
+            plugins.push(r.wstring()?);
+        }
+    }
+    Ok(plugins)
+}
+
+/// Bytes taken by a `width` x `height` screenshot of `pixel` bytes each
+fn screenshot_len(width: u32, height: u32, pixel: usize) -> Result<usize> {
+    (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|len| len.checked_mul(pixel))
+        .context("Screenshot in save header is too large")
+}
+
+fn parse_skyrim(data: &[u8]) -> Result<SaveHeader> {
+    let mut r = Reader::new(data);
+    let header_size = r.u32()? as usize;
+    let header_start = r.pos;
+    let (version, mut header, width, height) = parse_creation_header(&mut r)?;
+    let compression = if version >= 12 { r.u16()? } else { 0 };
+
+    // Special Edition screenshots are RGBA, the original's RGB
+    r.pos = header_start + header_size;
+    let pixel = if version >= 12 { 4 } else { 3 };
+    r.skip(screenshot_len(width, height, pixel)?)?;
+
+    let body;
+    let mut body_reader = match compression {
+        0 => r,
+        1 | 2 => {
+            let _uncompressed = r.u32()?;
+            let compressed = r.u32()? as usize;
+            let input = &r.data[r.pos..(r.pos + compressed).min(r.data.len())];
+            body = if compression == 1 {
+                let mut out = Vec::new();
+                flate2::read::ZlibDecoder::new(input)
+                    .take(PLUGIN_LIST_LIMIT as u64)
+                    .read_to_end(&mut out)
+                    .context("Corrupt zlib data in save")?;
+                out
+            } else {
+                lz4_decompress(input, PLUGIN_LIST_LIMIT)?
+            };
+            Reader::new(&body)
+        }
+        other => bail!("Unknown save compression {}", other),
+    };
+    let form_version = body_reader.u8()?;
+    header.plugins = read_plugins(&mut body_reader, form_version >= 78)?;
+    Ok(header)
+}
+
+fn parse_fallout4(data: &[u8]) -> Result<SaveHeader> {
+    let mut r = Reader::new(data);
+    let header_size = r.u32()? as usize;
+    let header_start = r.pos;
+    let (_, mut header, width, height) = parse_creation_header(&mut r)?;
+    r.pos = header_start + header_size;
+    r.skip(screenshot_len(width, height, 4)?)?;
+    let form_version = r.u8()?;
+    let _game_version = r.wstring()?;
+    header.plugins = read_plugins(&mut r, form_version >= 68)?;
+    Ok(header)
+}
+
+fn parse_fallout3(data: &[u8]) -> Result<SaveHeader> {
+    let mut r = Reader::new(data);
+    let _header_size = r.u32()?;
+    let _version = r.u32()?;
+    r.bar()?;
+    // New Vegas has a block (the game's language) before the screenshot
+    // size that Fallout 3 doesn't; find the width|height|number|name fields
+    // right after a separator
+    let fields_at = (r.pos..r.data.len().min(r.pos + 128))
+        .find(|&at| {
+            r.data[at - 1] == b'|'
+                && [4, 9, 14, 17]
+                    .iter()
+                    .all(|o| r.data.get(at + o) == Some(&b'|'))
+        })
+        .context("Unexpected data in save header")?;
+    r.pos = fields_at;
+    let width = r.bar_u32()?;
+    let height = r.bar_u32()?;
+    let save_number = r.bar_u32()?;
+    let character = r.bar_string()?;
+    let _karma = r.bar_string()?;
+    let level = r.bar_u32()?;
+    let location = r.bar_string()?;
+    let playtime = r.bar_string()?;
+    r.skip(screenshot_len(width, height, 3)?)?;
+
+    // Form version and plugin data size come next, with or without
+    // separators depending on the game; the list starts at its count
+    let list_at = (r.pos..r.data.len().min(r.pos + 16))
+        .find(|&at| {
+            let mut probe = Reader::new(&r.data[at..]);
+            probe.u8().is_ok_and(|count| count > 0)
+                && probe.bar().is_ok()
+                && probe.bar_string().is_ok_and(|name| {
+                    let name = name.to_ascii_lowercase();
+                    name.ends_with(".esm") || name.ends_with(".esp")
+                })
+        })
+        .context("Plugin list not found in save")?;
+    r.pos = list_at;
+    let count = r.u8()?;
+    r.bar()?;
+    let plugins = (0..count)
+        .map(|_| r.bar_string())
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(SaveHeader {
+        save_number,
+        character,
+        level,
+        location,
+        playtime,
+        saved_at: None,
+        plugins,
+    })
+}
+
+fn parse_oblivion(data: &[u8]) -> Result<SaveHeader> {
+    let mut r = Reader::new(data);
+    let _major = r.u8()?;
+    let _minor = r.u8()?;
+    let _exe_time = systemtime(&mut r)?;
+    let _header_version = r.u32()?;
+    let _header_size = r.u32()?;
+    let save_number = r.u32()?;
+    let character = r.bstring()?;
+    let level = u32::from(r.u16()?);
+    let location = r.bstring()?;
+    let _game_days = r.f32()?;
+    let ticks = r.u32()? / 1000;
+    let saved_at = systemtime(&mut r)?;
+    let screenshot_size = r.u32()? as usize;
+    r.skip(screenshot_size)?;
+    let count = r.u8()?;
+    let plugins = (0..count)
+        .map(|_| r.bstring())
+        .collect::<Result<Vec<_>>>()?;
+    Ok(SaveHeader {
+        save_number,
+        character,
+        level,
+        location,
+        playtime: format!(
+            "{:03}.{:02}.{:02}",
+            ticks / 3600,
+            ticks / 60 % 60,
+            ticks % 60
+        ),
+        saved_at,
+        plugins,
+    })
+}
+
+/// Decompress an LZ4 block, stopping once `limit` bytes are out or the
+/// input runs out
+fn lz4_decompress(input: &[u8], limit: usize) -> Result<Vec<u8>> {
+    fn length(input: &[u8], at: &mut usize, mut len: usize) -> Result<usize> {
+        if len == 15 {
+            loop {
+                let byte = *input.get(*at).context("Corrupt LZ4 data in save")?;
+                *at += 1;
+                len += byte as usize;
+                if byte != 255 {
+                    break;
+                }
+            }
+        }
+        Ok(len)
+    }
+
+    let mut out = Vec::new();
+    let mut at = 0;
+    while at < input.len() && out.len() < limit {
+        let token = input[at];
+        at += 1;
+        let literals = length(input, &mut at, (token >> 4) as usize)?;
+        let end = (at + literals).min(input.len());
+        out.extend_from_slice(&input[at..end]);
+        at = end;
+        // The last sequence is literals only
+        if at + 2 > input.len() {
+            break;
+        }
+        let offset = u16::from_le_bytes([input[at], input[at + 1]]) as usize;
+        at += 2;
+        if offset == 0 || offset > out.len() {
+            bail!("Corrupt LZ4 data in save");
+        }
+        let len = length(input, &mut at, (token & 0x0f) as usize)? + 4;
+        let start = out.len() - offset;
+        // Matches may overlap what they copy
+        for i in 0..len {
+            out.push(out[start + i]);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    fn wstring(out: &mut Vec<u8>, s: &str) {
+        out.extend((s.len() as u16).to_le_bytes());
+        out.extend(s.as_bytes());
+    }
+
+    /// A Skyrim save: `version` 9 for the original, 12 for Special Edition
+    /// (left uncompressed)
+    pub(crate) fn skyrim_save(version: u32, plugins: &[&str], light: &[&str]) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend(version.to_le_bytes());
+        header.extend(7u32.to_le_bytes());
+        wstring(&mut header, "Lydia");
+        header.extend(23u32.to_le_bytes());
+        wstring(&mut header, "Whiterun");
+        wstring(&mut header, "012.04.33");
+        wstring(&mut header, "NordRace");
+        header.extend(1u16.to_le_bytes());
+        header.extend(0f32.to_le_bytes());
+        header.extend(100f32.to_le_bytes());
+        // 2024-03-01 12:00:00
+        header.extend(133537680000000000u64.to_le_bytes());
+        header.extend(2u32.to_le_bytes());
+        header.extend(1u32.to_le_bytes());
+        if version >= 12 {
+            header.extend(0u16.to_le_bytes());
+        }
+
+        let mut data = b"TESV_SAVEGAME".to_vec();
+        data.extend((header.len() as u32).to_le_bytes());
+        data.extend(header);
+        data.extend(vec![0u8; 2 * if version >= 12 { 4 } else { 3 }]);
+        data.push(if version >= 12 { 78 } else { 74 });
+        data.extend(0u32.to_le_bytes());
+        data.push(plugins.len() as u8);
+        for plugin in plugins {
+            wstring(&mut data, plugin);
+        }
+        if version >= 12 {
+            data.extend((light.len() as u16).to_le_bytes());
+            for plugin in light {
+                wstring(&mut data, plugin);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_skyrim_header() {
+        let header = parse_save_header(&skyrim_save(
+            12,
+            &["Skyrim.esm", "Update.esm"],
+            &["Light.esp"],
+        ))
+        .unwrap();
+        assert_eq!(header.character, "Lydia");
+        assert_eq!(header.level, 23);
+        assert_eq!(header.location, "Whiterun");
+        assert_eq!(header.playtime, "012.04.33");
+        assert_eq!(header.save_number, 7);
+        assert_eq!(header.saved_at.unwrap().to_string(), "2024-03-01 12:00:00");
+        assert_eq!(header.plugins, ["Skyrim.esm", "Update.esm", "Light.esp"]);
+
+        let header = parse_save_header(&skyrim_save(9, &["Skyrim.esm"], &[])).unwrap();
+        assert_eq!(header.plugins, ["Skyrim.esm"]);
+        assert!(parse_save_header(b"TESV_SAVEGAME\x10").is_err());
+        assert!(parse_save_header(b"not a save").is_err());
+    }
+
+    #[test]
+    fn test_oversized_screenshot_is_a_parse_error() {
+        let mut data = skyrim_save(12, &["Skyrim.esm"], &[]);
+        // Width and height sit just before the compression type
+        let header_end = 17 + u32::from_le_bytes(data[13..17].try_into().unwrap()) as usize;
+        data[header_end - 10..header_end - 2].fill(0xff);
+        let err = parse_save_header(&data).unwrap_err();
+        assert!(format!("{:#}", err).contains("too large"), "{:#}", err);
+    }
+
+    #[test]
+    fn test_parse_fallout3_header() {
+        let mut data = b"FO3SAVEGAME".to_vec();
+        data.extend(0u32.to_le_bytes());
+        data.extend(0x30u32.to_le_bytes());
+        data.push(b'|');
+        // New Vegas' language block
+        data.extend(b"ENGLISH\0\0\0|");
+        let bar_string = |out: &mut Vec<u8>, s: &str| {
+            out.extend((s.len() as u16).to_le_bytes());
+            out.push(b'|');
+            out.extend(s.as_bytes());
+            out.push(b'|');
+        };
+        for value in [1u32, 1, 3] {
+            data.extend(value.to_le_bytes());
+            data.push(b'|');
+        }
+        bar_string(&mut data, "Courier");
+        bar_string(&mut data, "Wanderer");
+        data.extend(8u32.to_le_bytes());
+        data.push(b'|');
+        bar_string(&mut data, "Goodsprings");
+        bar_string(&mut data, "02.15.07");
+        data.extend([0u8; 3]);
+        data.push(0x30);
+        data.extend(40u32.to_le_bytes());
+        data.push(b'|');
+        data.push(2);
+        data.push(b'|');
+        bar_string(&mut data, "FalloutNV.esm");
+        bar_string(&mut data, "YUP.esm");
+
+        let header = parse_save_header(&data).unwrap();
+        assert_eq!(
+            (header.character.as_str(), header.level, header.save_number),
+            ("Courier", 8, 3)
+        );
+        assert_eq!(header.location, "Goodsprings");
+        assert_eq!(header.playtime, "02.15.07");
+        assert_eq!(header.plugins, ["FalloutNV.esm", "YUP.esm"]);
+    }
+
+    #[test]
+    fn test_lz4_decompress() {
+        // "abcabcabcabc!": 3 literals, a 9-byte match at offset 3, then "!"
+        let block = [0x35, b'a', b'b', b'c', 3, 0, 0x10, b'!'];
+        assert_eq!(
+            lz4_decompress(&block, PLUGIN_LIST_LIMIT).unwrap(),
+            b"abcabcabcabc!"
+        );
+        assert!(lz4_decompress(&[0x01, b'a', 9, 0], PLUGIN_LIST_LIMIT).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+//! Save games
+//!
+//! Lists the saves in the game's save folder (inside the Proton prefix)
+//! with what their headers say, and backs them up or deletes them together
+//! with their script extender co-saves. Saves also list the plugins they
+//! were made with, so saves that need plugins the load order lacks can be
+//! found before they are loaded. Profiles can keep their saves apart (see
+//! [`isolate_saves`]).
+
+mod header;
+mod profile;
+
+pub use header::{parse_save_header, read_save_header, SaveHeader};
+pub use profile::{isolate_saves, profile_save_path, save_isolation, share_saves, SaveIsolation};
+
+use crate::error::Invalid;
+use crate::games::{Game, GameType};
+use crate::import::filters::PluginFilter;
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDateTime;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Saves `check_save_plugins` reads, newest first
+const RECENT_SAVES: usize = 10;
+
+/// Save file extension and script extender co-save extension of a game
+pub fn save_extensions(game_type: GameType) -> Option<(&'static str, Option<&'static str>)> {
+    Some(match game_type {
+        GameType::SkyrimSE | GameType::SkyrimVR | GameType::Enderal | GameType::EnderalSE => {
+            ("ess", Some("skse"))
+        }
+        GameType::Fallout4 | GameType::Fallout4VR => ("fos", Some("f4se")),
+        GameType::Fallout3 => ("fos", Some("fose")),
+        GameType::FalloutNV => ("fos", Some("nvse")),
+        GameType::Oblivion => ("ess", Some("obse")),
+        GameType::Starfield => ("sfs", Some("sfse")),
+        GameType::OblivionRemastered => ("sav", None),
+        GameType::Morrowind => ("ess", None),
+        // Saves live in per-character folders outside the prefix
+        GameType::OpenMW => return None,
+    })
+}
+
+/// Folder the game saves to: `SLocalSavePath` under the game's My Games
+/// folder (`Saves` unless the INI says otherwise)
+pub fn saves_dir(game: &Game) -> Option<PathBuf> {
+    save_extensions(game.game_type)?;
+    let ini_dir = game.ini_dir()?;
+    match game.game_type {
+        GameType::Morrowind => return Some(game.install_path.join("Saves")),
+        // ini_dir is Saved/Config/Windows
+        GameType::OblivionRemastered => {
+            return ini_dir
+                .parent()?
+                .parent()
+                .map(|saved| saved.join("SaveGames"))
+        }
+        _ => {}
+    }
+    let local = local_save_path(game).unwrap_or_else(|| "Saves".to_string());
+    Some(ini_dir.join(local))
+}
+
+/// `SLocalSavePath` from the game's INI files, as a relative path
+fn local_save_path(game: &Game) -> Option<String> {
+    let mut value = None;
+    // Later files (Custom) override earlier ones, as in the game
+    for file in game.game_type.ini_files() {
+        if let Ok(settings) = crate::profiles::read_ini_settings(game, file) {
+            if let Some(setting) = settings.iter().find(|s| {
+                s.section.eq_ignore_ascii_case("General")
+                    && s.key.eq_ignore_ascii_case("SLocalSavePath")
+            }) {
+                value = Some(setting.value.clone());
+            }
+        }
+    }
+    let value = value?.replace('\\', "/");
+    let value = value.trim_matches('/');
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// A save file in the game's save folder
+#[derive(Debug, Clone)]
+pub struct SaveGame {
+    /// File name, e.g. "Save 12 - Lydia  Whiterun  012.04.33.ess"
+    pub name: String,
+    pub path: PathBuf,
+    /// Script extender co-save next to it
+    pub cosave: Option<PathBuf>,
+    pub size: u64,
+    /// File modification time (local)
+    pub modified: Option<NaiveDateTime>,
+    pub header: Option<SaveHeader>,
+    /// Why the header couldn't be read
+    pub error: Option<String>,
+}
+
+impl SaveGame {
+    /// When the save was made: from the header, or the file's time
+    pub fn saved_at(&self) -> Option<NaiveDateTime> {
+        self.header
+            .as_ref()
+            .and_then(|h| h.saved_at)
+            .or(self.modified)
+    }
+
+    /// Name without the extension
+    pub fn stem(&self) -> &str {
+        self.name
+            .rsplit_once('.')
+            .map(|(stem, _)| stem)
+            .unwrap_or(&self.name)
+    }
+}
+
+/// Read one save file
+pub fn read_save(game_type: GameType, path: &Path) -> Result<SaveGame> {
+    let meta =
+        std::fs::metadata(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let cosave = save_extensions(game_type)
+        .and_then(|(_, cosave)| cosave)
+        .map(|ext| path.with_extension(ext))
+        .filter(|p| p.is_file());
+    let modified = meta
+        .modified()
+        .ok()
+        .map(|t| chrono::DateTime::<chrono::Local>::from(t).naive_local());
+    let (header, error) = match read_save_header(path) {
+        Ok(header) => (Some(header), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    Ok(SaveGame {
+        name,
+        path: path.to_path_buf(),
+        cosave,
+        size: meta.len(),
+        modified,
+        header,
+        error,
+    })
+}
+
+/// Save files in the game's save folder, most recently written first
+fn save_files(game: &Game) -> Result<Vec<PathBuf>> {
+    let Some(dir) = saves_dir(game) else {
+        bail!(Invalid::new(format!(
+            "Save folder of {} is unknown (no Proton prefix, or not supported)",
+            game.name
+        )));
+    };
+    let Some((ext, _)) = save_extensions(game.game_type) else {
+        return Ok(Vec::new());
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut files: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.is_file()
+                && p.extension()
+                    .is_some_and(|e| e.to_string_lossy().eq_ignore_ascii_case(ext))
+        })
+        .map(|p| {
+            let modified = std::fs::metadata(&p).and_then(|m| m.modified()).ok();
+            (modified, p)
+        })
+        .collect();
+    files.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    Ok(files.into_iter().map(|(_, p)| p).collect())
+}
+
+/// Saves in the game's save folder, newest first
+pub fn list_saves(game: &Game) -> Result<Vec<SaveGame>> {
+    let mut saves = save_files(game)?
+        .iter()
+        .map(|p| read_save(game.game_type, p))
+        .collect::<Result<Vec<_>>>()?;
+    saves.sort_by(|a, b| b.saved_at().cmp(&a.saved_at()).then(a.name.cmp(&b.name)));
+    Ok(saves)
+}
+
+/// Find a save by file name, with or without its extension
+pub fn find_save(game: &Game, name: &str) -> Result<SaveGame> {
+    let saves = list_saves(game)?;
+    let name = name.trim();
+    match saves
+        .into_iter()
+        .find(|s| s.name.eq_ignore_ascii_case(name) || s.stem().eq_ignore_ascii_case(name))
+    {
+        Some(save) => Ok(save),
+        None => bail!(Invalid::new(format!(
+            "No save named '{}'; see 'modsanity saves list'",
+            name
+        ))),
+    }
+}
+
+/// Copy a save and its co-save into `<backup_dir>/<timestamp>/`. Returns
+/// the folder.
+pub fn backup_save(save: &SaveGame, backup_dir: &Path) -> Result<PathBuf> {
+    let dest = backup_dir.join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+    std::fs::create_dir_all(&dest)
+        .with_context(|| format!("Failed to create {}", dest.display()))?;
+    for file in std::iter::once(&save.path).chain(save.cosave.as_ref()) {
+        let name = file.file_name().unwrap_or_default();
+        std::fs::copy(file, dest.join(name))
+            .with_context(|| format!("Failed to back up {}", file.display()))?;
+    }
+    Ok(dest)
+}
+
+/// Delete a save and its co-save
+pub fn delete_save(save: &SaveGame) -> Result<()> {
+    std::fs::remove_file(&save.path)
+        .with_context(|| format!("Failed to delete {}", save.path.display()))?;
+    if let Some(cosave) = &save.cosave {
+        std::fs::remove_file(cosave)
+            .with_context(|| format!("Failed to delete {}", cosave.display()))?;
+    }
+    Ok(())
+}
+
+/// A save made with plugins the game won't load
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingSavePlugins {
+    /// Save file name
+    pub save: String,
+    pub character: String,
+    /// Plugins in the save's header that aren't loaded, in header order
+    pub missing: Vec<String>,
+}
+
+impl std::fmt::Display for MissingSavePlugins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Save '{}' ({}) needs plugins not in the load order: {}",
+            self.save,
+            self.character,
+            self.missing.join(", ")
+        )
+    }
+}
+
+/// Plugins the game loads, lowercased: those enabled in plugins.txt, and
+/// the base game, DLC and Creation Club plugins in Data, which load without
+/// being listed
+pub fn loaded_plugins(game: &Game) -> Result<HashSet<String>> {
+    let mut loaded: HashSet<String> = crate::plugins::read_plugins_txt(game)?
+        .into_iter()
+        .collect();
+    let filter = PluginFilter::for_game(game.game_type.id());
+    if let Ok(entries) = std::fs::read_dir(&game.data_path) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if filter.should_skip(&name) {
+                loaded.insert(name);
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+/// Plugins of `header` missing from `loaded` (lowercased names)
+pub fn missing_plugins(header: &SaveHeader, loaded: &HashSet<String>) -> Vec<String> {
+    header
+        .plugins
+        .iter()
+        .filter(|p| !loaded.contains(&p.to_lowercase()))
+        .cloned()
+        .collect()
+}
+
+/// Check the newest save of each character among the recent saves against
+/// the plugins the game loads now. Empty for games whose saves or load
+/// order can't be read.
+pub fn check_save_plugins(game: &Game) -> Result<Vec<MissingSavePlugins>> {
+    if saves_dir(game).is_none() || game.plugins_txt_path.is_none() {
+        return Ok(Vec::new());
+    }
+    let loaded = loaded_plugins(game)?;
+    let mut characters = HashSet::new();
+    let mut out = Vec::new();
+    for path in save_files(game)?.iter().take(RECENT_SAVES) {
+        let Ok(header) = read_save_header(path) else {
+            continue;
+        };
+        // Older saves of a character are less likely to be loaded again
+        if !characters.insert(header.character.clone()) {
+            continue;
+        }
+        let missing = missing_plugins(&header, &loaded);
+        if !missing.is_empty() {
+            out.push(MissingSavePlugins {
+                save: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                character: header.character,
+                missing,
+            });
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_backup_and_delete_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::SkyrimSE, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("compatdata"));
+        assert!(list_saves(&game).unwrap().is_empty());
+
+        let saves = saves_dir(&game).unwrap();
+        assert!(saves.ends_with("My Games/Skyrim Special Edition/Saves"));
+        std::fs::create_dir_all(&saves).unwrap();
+        let save = header::tests::skyrim_save(12, &["Skyrim.esm"], &[]);
+        std::fs::write(saves.join("Save 1 - Lydia.ess"), &save).unwrap();
+        std::fs::write(saves.join("Save 1 - Lydia.skse"), b"co-save").unwrap();
+        std::fs::write(saves.join("Broken.ess"), b"TESV_SAVEGAME").unwrap();
+        std::fs::write(saves.join("notes.txt"), b"").unwrap();
+
+        let listed = list_saves(&game).unwrap();
+        assert_eq!(listed.len(), 2);
+        let broken = listed.iter().find(|s| s.name == "Broken.ess").unwrap();
+        assert!(broken.header.is_none() && broken.error.is_some());
+
+        let lydia = find_save(&game, "save 1 - lydia").unwrap();
+        assert_eq!(lydia.header.as_ref().unwrap().character, "Lydia");
+        assert!(lydia.cosave.is_some());
+        assert!(find_save(&game, "Save 2").is_err());
+
+        let backup = backup_save(&lydia, &dir.path().join("backups")).unwrap();
+        assert!(backup.join("Save 1 - Lydia.ess").is_file());
+        assert!(backup.join("Save 1 - Lydia.skse").is_file());
+        delete_save(&lydia).unwrap();
+        assert!(!saves.join("Save 1 - Lydia.ess").exists());
+        assert!(!saves.join("Save 1 - Lydia.skse").exists());
+
+        // Saves follow SLocalSavePath
+        std::fs::write(
+            game.ini_dir().unwrap().join("Skyrim.ini"),
+            "[General]\nSLocalSavePath=Saves\\Profile\\\n",
+        )
+        .unwrap();
+        assert!(saves_dir(&game).unwrap().ends_with("Saves/Profile"));
+    }
+
+    #[test]
+    fn test_check_save_plugins() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::SkyrimSE, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("compatdata"));
+        std::fs::create_dir_all(&game.data_path).unwrap();
+        std::fs::write(game.data_path.join("Skyrim.esm"), b"").unwrap();
+        let plugins_txt = game.plugins_txt_path.clone().unwrap();
+        std::fs::create_dir_all(plugins_txt.parent().unwrap()).unwrap();
+        std::fs::write(&plugins_txt, "*SkyUI_SE.esp\n").unwrap();
+
+        let saves = saves_dir(&game).unwrap();
+        std::fs::create_dir_all(&saves).unwrap();
+        let save = header::tests::skyrim_save(12, &["Skyrim.esm", "SkyUI_SE.esp"], &[]);
+        std::fs::write(saves.join("Save 1.ess"), &save).unwrap();
+        assert!(check_save_plugins(&game).unwrap().is_empty());
+
+        let save = header::tests::skyrim_save(12, &["Skyrim.esm", "Gone.esp"], &["Light.esl"]);
+        std::fs::write(saves.join("Save 2.ess"), &save).unwrap();
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(saves.join("Save 2.ess"))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        // Only the newest save of a character is checked
+        let issues = check_save_plugins(&game).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].save, "Save 2.ess");
+        assert_eq!(issues[0].missing, ["Gone.esp", "Light.esl"]);
+    }
+}
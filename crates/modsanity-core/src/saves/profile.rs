@@ -0,0 +1,252 @@
+//! Per-profile save folders
+//!
+//! Games that read `SLocalSavePath` get a save folder per profile under
+//! `Saves\Profiles\`, set in their INI; the value it replaced comes back
+//! when the profiles share saves again. Morrowind and Oblivion Remastered
+//! don't read it, so their save folder is replaced by a symlink to the
+//! profile's folder; the shared saves are kept next to it as
+//! `<folder>.shared` until the profiles share saves again.
+
+use super::saves_dir;
+use crate::error::Invalid;
+use crate::games::{Game, GameType};
+use crate::profiles::{IniChanges, IniSetting};
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Folder under `Saves` that holds the per-profile save folders
+const PROFILES_FOLDER: &str = "Profiles";
+
+/// How a game's saves are kept apart per profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveIsolation {
+    /// `SLocalSavePath` in this INI file
+    Ini(&'static str),
+    /// The save folder is a symlink to the profile's folder
+    Symlink,
+}
+
+/// How saves of `game_type` are kept apart, if they can be
+pub fn save_isolation(game_type: GameType) -> Option<SaveIsolation> {
+    match game_type {
+        GameType::Morrowind | GameType::OblivionRemastered => Some(SaveIsolation::Symlink),
+        GameType::OpenMW => None,
+        // The Custom INI wins over the others, as the game reads it last
+        _ => {
+            let files = game_type.ini_files();
+            let file = files
+                .iter()
+                .find(|f| f.to_lowercase().ends_with("custom.ini"))
+                .or(files.first())?;
+            Some(SaveIsolation::Ini(file))
+        }
+    }
+}
+
+/// `profile` as a folder name, without characters Windows rejects
+fn folder_name(profile: &str) -> String {
+    profile
+        .chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim_end_matches(['.', ' '])
+        .to_string()
+}
+
+/// `SLocalSavePath` of a profile's save folder
+pub fn profile_save_path(profile: &str) -> String {
+    format!("Saves\\{}\\{}\\", PROFILES_FOLDER, folder_name(profile))
+}
+
+/// Point the game's save folder at the profile's. `link_target` is where a
+/// symlinked save folder points; INI-redirected saves stay in the prefix.
+/// The INI value is written through `changes`, which backs the file up and
+/// keeps the value it replaced. Returns the save folder the game now uses.
+pub fn isolate_saves(
+    game: &Game,
+    profile: &str,
+    link_target: &Path,
+    changes: &IniChanges,
+) -> Result<PathBuf> {
+    match save_isolation(game.game_type) {
+        Some(SaveIsolation::Ini(file)) => {
+            let value = profile_save_path(profile);
+            changes
+                .apply(
+                    game,
+                    &[IniSetting::new(file, "General", "SLocalSavePath", &value)],
+                )
+                .context("Failed to set SLocalSavePath")?;
+            let dir = saves_dir(game).context("Save folder is unknown")?;
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            Ok(dir)
+        }
+        Some(SaveIsolation::Symlink) => {
+            let Some(dir) = saves_dir(game) else {
+                bail!(Invalid::new(format!(
+                    "Save folder of {} is unknown (no Proton prefix)",
+                    game.name
+                )));
+            };
+            link_save_dir(&dir, link_target)?;
+            Ok(dir)
+        }
+        None => bail!(Invalid::new(format!(
+            "{} saves can't be kept per profile",
+            game.name
+        ))),
+    }
+}
+
+/// Undo `isolate_saves`: the game saves where it did before, with the
+/// `SLocalSavePath` recorded in `changes` put back. Does nothing if saves
+/// aren't redirected to a profile's folder.
+pub fn share_saves(game: &Game, changes: &IniChanges) -> Result<()> {
+    match save_isolation(game.game_type) {
+        Some(SaveIsolation::Ini(file)) => {
+            changes
+                .revert(game, |s| {
+                    s.file.eq_ignore_ascii_case(file)
+                        && s.key.eq_ignore_ascii_case("SLocalSavePath")
+                })
+                .context("Failed to restore SLocalSavePath")?;
+            Ok(())
+        }
+        Some(SaveIsolation::Symlink) => match saves_dir(game) {
+            Some(dir) => unlink_save_dir(&dir),
+            None => Ok(()),
+        },
+        None => Ok(()),
+    }
+}
+
+/// `dir` with `.shared` appended, where the shared saves wait
+fn shared_dir(dir: &Path) -> PathBuf {
+    let mut name = dir.file_name().unwrap_or_default().to_os_string();
+    name.push(".shared");
+    dir.with_file_name(name)
+}
+
+/// Make `dir` a symlink to `target`, moving a real folder aside
+fn link_save_dir(dir: &Path, target: &Path) -> Result<()> {
+    std::fs::create_dir_all(target)
+        .with_context(|| format!("Failed to create {}", target.display()))?;
+    if dir.is_symlink() {
+        std::fs::remove_file(dir).with_context(|| format!("Failed to unlink {}", dir.display()))?;
+    } else if dir.exists() {
+        let shared = shared_dir(dir);
+        if shared.exists() {
+            bail!(Invalid::new(format!(
+                "Both {} and {} exist; move one of them away first",
+                dir.display(),
+                shared.display()
+            )));
+        }
+        std::fs::rename(dir, &shared)
+            .with_context(|| format!("Failed to move {} aside", dir.display()))?;
+    } else if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::os::unix::fs::symlink(target, dir)
+        .with_context(|| format!("Failed to link {}", dir.display()))
+}
+
+/// Put the shared save folder back in place of the symlink
+fn unlink_save_dir(dir: &Path) -> Result<()> {
+    if !dir.is_symlink() {
+        return Ok(());
+    }
+    std::fs::remove_file(dir).with_context(|| format!("Failed to unlink {}", dir.display()))?;
+    let shared = shared_dir(dir);
+    if shared.is_dir() {
+        std::fs::rename(&shared, dir)
+            .with_context(|| format!("Failed to move {} back", shared.display()))?;
+    } else {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolate_saves_through_ini() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::SkyrimSE, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("compatdata"));
+        let changes = IniChanges::new(dir.path().join("backups"), dir.path().join("changes.json"));
+        assert_eq!(
+            save_isolation(GameType::SkyrimSE),
+            Some(SaveIsolation::Ini("SkyrimCustom.ini"))
+        );
+
+        let saves = isolate_saves(&game, "Survival: Hard", Path::new("/unused"), &changes).unwrap();
+        assert!(saves.ends_with("Saves/Profiles/Survival_ Hard"));
+        assert!(saves.is_dir());
+        let custom = game.ini_dir().unwrap().join("SkyrimCustom.ini");
+        let ini = std::fs::read_to_string(&custom).unwrap();
+        assert!(ini.contains("SLocalSavePath=Saves\\Profiles\\Survival_ Hard\\"));
+
+        // A key that wasn't set is removed again
+        share_saves(&game, &changes).unwrap();
+        assert_eq!(std::fs::read_to_string(&custom).unwrap(), "[General]\n");
+        assert!(saves_dir(&game)
+            .unwrap()
+            .ends_with("My Games/Skyrim Special Edition/Saves"));
+
+        // The player's own save folder comes back
+        std::fs::write(&custom, "[General]\nSLocalSavePath=Modded\\\n").unwrap();
+        isolate_saves(&game, "Hard", Path::new("/unused"), &changes).unwrap();
+        assert!(saves_dir(&game).unwrap().ends_with("Saves/Profiles/Hard"));
+        assert!(
+            !crate::profiles::ini_backups(&dir.path().join("backups"), "SkyrimCustom.ini")
+                .unwrap()
+                .is_empty()
+        );
+        share_saves(&game, &changes).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&custom).unwrap(),
+            "[General]\nSLocalSavePath=Modded\\\n"
+        );
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_isolate_saves_through_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = Game::new(GameType::Morrowind, dir.path().join("game"))
+            .with_proton_prefix(dir.path().join("compatdata"));
+        let saves = saves_dir(&game).unwrap();
+        std::fs::create_dir_all(&saves).unwrap();
+        std::fs::write(saves.join("shared.ess"), b"").unwrap();
+
+        let changes = IniChanges::new(dir.path().join("backups"), dir.path().join("changes.json"));
+        let target = dir.path().join("profiles/saves/Modded");
+        isolate_saves(&game, "Modded", &target, &changes).unwrap();
+        assert!(saves.is_symlink());
+        assert!(!saves.join("shared.ess").exists());
+        std::fs::write(saves.join("modded.ess"), b"").unwrap();
+        assert!(target.join("modded.ess").is_file());
+
+        // Switching between isolated profiles keeps the shared saves aside
+        isolate_saves(
+            &game,
+            "Other",
+            &dir.path().join("profiles/saves/Other"),
+            &changes,
+        )
+        .unwrap();
+        share_saves(&game, &changes).unwrap();
+        assert!(!saves.is_symlink());
+        assert!(saves.join("shared.ess").is_file());
+        assert!(target.join("modded.ess").is_file());
+    }
+}
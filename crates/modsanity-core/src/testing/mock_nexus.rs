@@ -0,0 +1,459 @@
+//! In-process mock of the Nexus Mods API
+//!
+//! Serves the subset of endpoints ModSanity uses from canned data:
+//! - `POST /v2/graphql`: responses registered per operation name; `ModFiles`
+//!   is answered from the registered files
+//! - `GET /v1/games/{domain}/mods/{id}.json`: mod details
+//! - `GET /v1/games/{domain}/mods/{id}/files/{file}/download_link.json`
+//! - `GET /files/{file_id}/{name}`: the archive bytes themselves
+//!
+//! Every request is recorded so tests can assert on what was called.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Upper bound for request heads; anything larger is not from our clients
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// GraphQL operation name (`query Foo(...)` -> `Foo`), if this was a GraphQL call
+    pub fn graphql_operation(&self) -> Option<String> {
+        let body: Value = serde_json::from_str(&self.body).ok()?;
+        operation_name(body.get("query")?.as_str()?)
+    }
+}
+
+/// A downloadable file registered with the mock
+#[derive(Debug, Clone)]
+struct MockFile {
+    game_domain: String,
+    mod_id: i64,
+    file_id: i64,
+    name: String,
+    version: String,
+    category: String,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    graphql: HashMap<String, Value>,
+    mods: HashMap<(String, i64), String>,
+    files: Vec<MockFile>,
+    premium: bool,
+    requests: Vec<RecordedRequest>,
+}
+
+/// Mock Nexus server bound to an ephemeral localhost port
+///
+/// The server runs until the handle is dropped.
+pub struct MockNexus {
+    base_url: String,
+    state: Arc<Mutex<MockState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockNexus {
+    /// Start a server with no canned data (premium downloads enabled)
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("Failed to bind mock Nexus server")?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let state = Arc::new(Mutex::new(MockState {
+            premium: true,
+            ..Default::default()
+        }));
+
+        let task = {
+            let state = state.clone();
+            let base_url = base_url.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let state = state.clone();
+                    let base_url = base_url.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = serve(stream, &state, &base_url).await {
+                            tracing::debug!("mock Nexus connection error: {:#}", e);
+                        }
+                    });
+                }
+            })
+        };
+
+        Ok(Self {
+            base_url,
+            state,
+            task,
+        })
+    }
+
+    /// Base URL to use as `Config::nexus_base_url`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Answer GraphQL `operation` with `{"data": data}`
+    pub fn on_graphql(&self, operation: &str, data: Value) -> &Self {
+        self.lock().graphql.insert(operation.to_string(), data);
+        self
+    }
+
+    /// Register mod details returned by the REST mod endpoint
+    pub fn add_mod(&self, game_domain: &str, mod_id: i64, name: &str) -> &Self {
+        self.lock()
+            .mods
+            .insert((game_domain.to_string(), mod_id), name.to_string());
+        self
+    }
+
+    /// Register a downloadable main file for a mod
+    pub fn add_file(
+        &self,
+        game_domain: &str,
+        mod_id: i64,
+        file_id: i64,
+        name: &str,
+        version: &str,
+        bytes: Vec<u8>,
+    ) -> &Self {
+        self.lock().files.push(MockFile {
+            game_domain: game_domain.to_string(),
+            mod_id,
+            file_id,
+            name: name.to_string(),
+            version: version.to_string(),
+            category: "MAIN".to_string(),
+            bytes,
+        });
+        self
+    }
+
+    /// Toggle premium behaviour; non-premium download links return 403
+    pub fn set_premium(&self, premium: bool) -> &Self {
+        self.lock().premium = premium;
+        self
+    }
+
+    /// All requests received so far, in arrival order
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Number of requests whose path starts with `prefix`
+    pub fn request_count(&self, prefix: &str) -> usize {
+        self.lock()
+            .requests
+            .iter()
+            .filter(|r| r.path.starts_with(prefix))
+            .count()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for MockNexus {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Response produced by a route
+struct Reply {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Reply {
+    fn json(status: u16, value: Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: value.to_string().into_bytes(),
+        }
+    }
+
+    fn bytes(body: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            content_type: "application/octet-stream",
+            body,
+        }
+    }
+
+    fn not_found() -> Self {
+        Self::json(404, json!({ "message": "Not Found" }))
+    }
+}
+
+async fn serve(mut stream: TcpStream, state: &Mutex<MockState>, base_url: &str) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            anyhow::bail!("request head too large");
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let content_length = lines
+        .filter_map(|l| l.split_once(':'))
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let mut body = buf[head_end..].to_vec();
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    let body = String::from_utf8_lossy(&body).to_string();
+
+    let reply = {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        let request = RecordedRequest { method, path, body };
+        let reply = route(&state, &request, base_url);
+        state.requests.push(request);
+        reply
+    };
+
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        reply.status,
+        reason(reply.status),
+        reply.content_type,
+        reply.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&reply.body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn route(state: &MockState, request: &RecordedRequest, base_url: &str) -> Reply {
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["v2", "graphql"]) => graphql(state, &request.body),
+        ("GET", ["v1", "games", domain, "mods", mod_json]) => {
+            let Some(mod_id) = mod_json
+                .strip_suffix(".json")
+                .and_then(|id| id.parse::<i64>().ok())
+            else {
+                return Reply::not_found();
+            };
+            match state.mods.get(&(domain.to_string(), mod_id)) {
+                Some(name) => Reply::json(200, json!({ "mod_id": mod_id, "name": name })),
+                None => Reply::not_found(),
+            }
+        }
+        (
+            "GET",
+            ["v1", "games", domain, "mods", mod_id, "files", file_id, "download_link.json"],
+        ) => {
+            let file = state.files.iter().find(|f| {
+                f.game_domain == *domain
+                    && f.mod_id.to_string() == *mod_id
+                    && f.file_id.to_string() == *file_id
+            });
+            match file {
+                Some(_) if !state.premium => Reply::json(
+                    403,
+                    json!({ "message": "You don't have permission to get download links" }),
+                ),
+                Some(f) => Reply::json(
+                    200,
+                    json!([{
+                        "URI": format!("{}/files/{}/{}", base_url, f.file_id, f.name),
+                        "name": "Mock CDN",
+                        "short_name": "Mock",
+                    }]),
+                ),
+                None => Reply::not_found(),
+            }
+        }
+        ("GET", ["files", file_id, ..]) => {
+            match state
+                .files
+                .iter()
+                .find(|f| f.file_id.to_string() == *file_id)
+            {
+                Some(f) => Reply::bytes(f.bytes.clone()),
+                None => Reply::not_found(),
+            }
+        }
+        _ => Reply::not_found(),
+    }
+}
+
+fn graphql(state: &MockState, body: &str) -> Reply {
+    let Ok(request) = serde_json::from_str::<Value>(body) else {
+        return Reply::json(400, json!({ "errors": [{ "message": "invalid JSON" }] }));
+    };
+    let operation = request
+        .get("query")
+        .and_then(Value::as_str)
+        .and_then(operation_name)
+        .unwrap_or_default();
+
+    if let Some(data) = state.graphql.get(&operation) {
+        return Reply::json(200, json!({ "data": data }));
+    }
+
+    if operation == "ModFiles" {
+        let mod_id = request
+            .pointer("/variables/modId")
+            .and_then(Value::as_str)
+            .and_then(|id| id.parse::<i64>().ok());
+        let files: Vec<Value> = state
+            .files
+            .iter()
+            .filter(|f| Some(f.mod_id) == mod_id)
+            .map(|f| {
+                json!({
+                    "fileId": f.file_id,
+                    "name": f.name,
+                    "version": f.version,
+                    "size": (f.bytes.len() / 1024) as i64,
+                    "sizeInBytes": f.bytes.len().to_string(),
+                    "uri": f.name,
+                    "category": f.category,
+                    "description": null,
+                })
+            })
+            .collect();
+        return Reply::json(200, json!({ "data": { "modFiles": files } }));
+    }
+
+    Reply::json(
+        200,
+        json!({
+            "data": null,
+            "errors": [{ "message": format!("mock has no response for '{}'", operation) }],
+        }),
+    )
+}
+
+/// Extract the operation name from a GraphQL document
+fn operation_name(query: &str) -> Option<String> {
+    let trimmed = query.trim_start();
+    let rest = trimmed
+        .strip_prefix("query")
+        .or_else(|| trimmed.strip_prefix("mutation"))?;
+    let name: String = rest
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus::NexusClient;
+
+    #[test]
+    fn test_operation_name() {
+        assert_eq!(
+            operation_name("\n  query ModFiles($modId: ID!) { x }").as_deref(),
+            Some("ModFiles")
+        );
+        assert_eq!(
+            operation_name("mutation TrackMod { x }").as_deref(),
+            Some("TrackMod")
+        );
+        assert_eq!(operation_name("{ x }"), None);
+    }
+
+    #[tokio::test]
+    async fn test_client_roundtrip() {
+        let mock = MockNexus::start().await.unwrap();
+        mock.add_mod("skyrimspecialedition", 42, "Mock Mod")
+            .add_file(
+                "skyrimspecialedition",
+                42,
+                7,
+                "mock-1.0.zip",
+                "1.0",
+                b"PK".to_vec(),
+            );
+
+        let client = NexusClient::with_base_url("key".to_string(), mock.base_url()).unwrap();
+
+        let files = client.get_mod_files(1704, 42).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].file_id, 7);
+
+        let name = client
+            .get_mod_name_by_id("skyrimspecialedition", 42)
+            .await
+            .unwrap();
+        assert_eq!(name.as_deref(), Some("Mock Mod"));
+
+        let links = client
+            .get_download_link("skyrimspecialedition", 42, 7)
+            .await
+            .unwrap();
+        let temp = tempfile::tempdir().unwrap();
+        let dest = temp.path().join("mock.zip");
+        NexusClient::download_file(&links[0].url, &dest, |_, _| {})
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), b"PK");
+
+        mock.set_premium(false);
+        assert!(client
+            .get_download_link("skyrimspecialedition", 42, 7)
+            .await
+            .is_err());
+
+        assert_eq!(mock.request_count("/v1/"), 3);
+        assert_eq!(
+            mock.requests()[0].graphql_operation().as_deref(),
+            Some("ModFiles")
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Test harness for deterministic end-to-end runs (`test-harness` feature)
+//!
+//! Provides a [`MockNexus`] server and a [`Sandbox`] that keeps config, data,
+//! downloads and a fake game install under one temporary directory, so flows
+//! can be exercised without network access or touching the user's setup.
+//! Frontends and downstream packagers can enable the feature to run the same
+//! regression tests against their builds.
+
+mod mock_nexus;
+
+pub use mock_nexus::{MockNexus, RecordedRequest};
+
+use crate::config::{Config, Paths};
+use crate::games::{Game, GamePlatform, GameType};
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Placeholder API key handed to clients talking to the mock
+pub const MOCK_API_KEY: &str = "modsanity-test-key";
+
+/// Isolated directory tree for one test run
+pub struct Sandbox {
+    root: tempfile::TempDir,
+    game_type: GameType,
+}
+
+impl Sandbox {
+    /// Create a sandbox with a fake Skyrim SE install
+    pub fn new() -> Result<Self> {
+        Self::with_game(GameType::SkyrimSE)
+    }
+
+    /// Create a sandbox with a fake install of `game_type`
+    pub fn with_game(game_type: GameType) -> Result<Self> {
+        let root = tempfile::tempdir().context("Failed to create sandbox directory")?;
+        let sandbox = Self { root, game_type };
+        std::fs::create_dir_all(sandbox.game().data_path)
+            .context("Failed to create sandbox game directory")?;
+        Ok(sandbox)
+    }
+
+    /// Root of the sandbox
+    pub fn root(&self) -> &Path {
+        self.root.path()
+    }
+
+    /// Fake game install inside the sandbox
+    pub fn game(&self) -> Game {
+        Game::new(self.game_type, self.root().join("game")).with_platform(GamePlatform::Manual)
+    }
+
+    /// Configuration rooted in the sandbox with the fake game active.
+    /// When `mock` is given, Nexus traffic goes to it.
+    pub fn config(&self, mock: Option<&MockNexus>) -> Config {
+        let game = self.game();
+        let mut config = Config {
+            paths: Paths::rooted(self.root()),
+            active_game: Some(game.id.clone()),
+            first_run_completed: true,
+            ..Default::default()
+        };
+        if let Some(mock) = mock {
+            config.nexus_api_key = Some(MOCK_API_KEY.to_string());
+            config.nexus_base_url = Some(mock.base_url().to_string());
+        }
+        config
+    }
+
+    /// Write a ZIP archive into the sandbox downloads directory
+    pub fn write_archive(&self, name: &str, files: &[(&str, &[u8])]) -> Result<PathBuf> {
+        let dir = Paths::rooted(self.root()).downloads_dir();
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(name);
+        std::fs::write(&path, zip_bytes(files)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Build an in-memory ZIP archive from `(path, contents)` pairs
+pub fn zip_bytes(files: &[(&str, &[u8])]) -> Result<Vec<u8>> {
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    for (name, contents) in files {
+        writer.start_file(*name, options)?;
+        writer.write_all(contents)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_config_is_isolated() {
+        let sandbox = Sandbox::new().unwrap();
+        let config = sandbox.config(None);
+
+        assert!(config.paths.database_file().starts_with(sandbox.root()));
+        assert!(config.downloads_dir().unwrap().starts_with(sandbox.root()));
+        assert_eq!(config.active_game.as_deref(), Some("skyrimse"));
+        assert!(sandbox.game().data_path.is_dir());
+
+        let archive = sandbox
+            .write_archive("a.zip", &[("Data/a.esp", b"TES4")])
+            .unwrap();
+        assert!(archive.starts_with(config.downloads_dir().unwrap()));
+    }
+}
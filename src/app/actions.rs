@@ -1,15 +1,82 @@
 //! CLI command action handlers
 
-use super::App;
+use super::state::CatalogProgress;
+use super::{dir_display, App};
 use crate::config::{DeploymentMethod, ExternalTool, ToolRuntimeMode};
-use crate::games::{GameDetector, GamePlatform};
+use crate::error::{Conflict, Invalid};
+use crate::games::{prefix, GameDefinition, GameDetector, GamePlatform};
+use crate::profiles::ini;
+use crate::saves;
 use anyhow::{bail, Context, Result};
 use std::io::{self, IsTerminal, Write};
 use std::time::{Duration, Instant};
 
+/// Width of the `[####----]` bar in CLI progress lines
+const PROGRESS_BAR_WIDTH: usize = 24;
+
+/// `[#########---------------]` for `percent` (0-100)
+fn progress_bar(percent: u16) -> String {
+    let filled = PROGRESS_BAR_WIDTH * percent.min(100) as usize / 100;
+    format!(
+        "[{}{}]",
+        "#".repeat(filled),
+        "-".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
+
+/// `text` cut to `max` characters for a table column, marked with "…"
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+/// Print `value` to stdout for `--json`
+fn print_json(value: &serde_json::Value) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Where an INI write went and where the previous file was saved
+fn print_ini_write(write: &ini::IniWrite) {
+    match &write.backup {
+        Some(backup) => println!("Backup: {}", backup.display()),
+        None => println!("Created {}", write.path.display()),
+    }
+}
+
+/// Files left in Data by a tool, one per line, the first few only
+fn print_new_data_files(files: &[std::path::PathBuf]) {
+    const SHOWN: usize = 10;
+    for file in files.iter().take(SHOWN) {
+        println!("  {}", file.display());
+    }
+    if files.len() > SHOWN {
+        println!("  ... and {} more", files.len() - SHOWN);
+    }
+}
+
+/// Launch problems with the first few mods behind each
+fn print_launch_problems(problems: &[crate::mods::LaunchProblem]) {
+    for problem in problems {
+        println!("! {}", problem.message);
+        for name in problem.mods.iter().take(5) {
+            println!("    - {}", name);
+        }
+        if problem.mods.len() > 5 {
+            println!("    ... and {} more", problem.mods.len() - 5);
+        }
+    }
+}
+
+/// Progress line for long CLI commands: redrawn in place on a terminal,
+/// printed as plain lines when output is redirected
 struct CliStatusReporter {
     interactive: bool,
-    last_line_len: usize,
+    last_line: String,
     last_emit: Instant,
     min_emit_interval: Duration,
 }
@@ -18,63 +85,74 @@ impl CliStatusReporter {
     fn new(min_emit_interval: Duration) -> Self {
         Self {
             interactive: io::stdout().is_terminal(),
-            last_line_len: 0,
+            last_line: String::new(),
             last_emit: Instant::now() - min_emit_interval,
             min_emit_interval,
         }
     }
 
-    fn emit_catalog_progress(
-        &mut self,
-        pages: i32,
-        inserted: i64,
-        updated: i64,
-        total_count: i64,
-    ) -> io::Result<()> {
+    fn emit(&mut self, line: String) -> io::Result<()> {
         let now = Instant::now();
-        if now.duration_since(self.last_emit) < self.min_emit_interval {
+        if now.duration_since(self.last_emit) < self.min_emit_interval || line == self.last_line {
             return Ok(());
         }
         self.last_emit = now;
 
-        let processed = inserted + updated;
-        let percent = if total_count > 0 {
-            ((processed as f64 / total_count as f64) * 100.0).min(100.0)
-        } else {
-            0.0
-        };
-
-        let line = if total_count > 0 {
-            format!(
-                "Progress: pages={} processed={}/{} ({:.1}%) inserted={} updated={}",
-                pages, processed, total_count, percent, inserted, updated
-            )
-        } else {
-            format!(
-                "Progress: pages={} processed={} inserted={} updated={}",
-                pages, processed, inserted, updated
-            )
-        };
-
         if self.interactive {
-            print!(
-                "\r{line:<width$}",
-                width = self.last_line_len.max(line.len())
-            );
+            print!("\r{line:<width$}", width = self.last_line.chars().count());
             io::stdout().flush()?;
-            self.last_line_len = self.last_line_len.max(line.len());
         } else {
             println!("{line}");
         }
-
+        self.last_line = line;
         Ok(())
     }
 
+    fn emit_catalog_progress(&mut self, progress: &CatalogProgress) -> io::Result<()> {
+        let stored = progress.mods_inserted + progress.mods_updated;
+        let counts = format!(
+            "pages {} | stored {} ({} new, {} updated)",
+            progress.pages_fetched, stored, progress.mods_inserted, progress.mods_updated
+        );
+        let line = match progress.percent() {
+            Some(percent) => format!(
+                "{} {:>3}% {}/{} mods | {}",
+                progress_bar(percent),
+                percent,
+                progress.current_offset,
+                progress.total_count,
+                counts
+            ),
+            None => counts,
+        };
+        self.emit(line)
+    }
+
+    /// Batch bar by entries done, plus the download in flight
+    fn emit_queue_progress(
+        &mut self,
+        rate: &crate::queue::QueueRate,
+        downloading: Option<&crate::db::DownloadQueueEntry>,
+    ) -> io::Result<()> {
+        let progress = &rate.progress;
+        let done = progress.completed + progress.failed;
+        let percent = (done * 100).checked_div(progress.total()).unwrap_or(0) as u16;
+        let mut line = format!("{} {}", progress_bar(percent), rate.summary());
+        if let Some(entry) = downloading {
+            line.push_str(&format!(" | {}", entry.name));
+            if let Some(size) = entry.size.filter(|s| *s > 0) {
+                line.push_str(&format!(" {}%", (entry.downloaded * 100 / size).min(100)));
+            }
+        }
+        self.emit(line)
+    }
+
     fn finish(&mut self) -> io::Result<()> {
-        if self.interactive {
+        if self.interactive && !self.last_line.is_empty() {
             println!();
             io::stdout().flush()?;
         }
+        self.last_line.clear();
         Ok(())
     }
 }
@@ -103,6 +181,23 @@ impl App {
     // ========== Game Commands ==========
 
     pub async fn cmd_game_list(&self) -> Result<()> {
+        if self.json_output {
+            let active = self.config.read().await.active_game.clone();
+            let games: Vec<_> = self
+                .games
+                .iter()
+                .map(|g| {
+                    serde_json::json!({
+                        "id": g.id,
+                        "name": g.name,
+                        "platform": g.platform.display_name(),
+                        "install_path": g.install_path,
+                        "active": Some(&g.id) == active.as_ref(),
+                    })
+                })
+                .collect();
+            return print_json(&serde_json::json!(games));
+        }
         if self.games.is_empty() {
             println!("No games detected. Run 'modsanity game scan' to scan for games.");
             return Ok(());
@@ -134,8 +229,14 @@ impl App {
 
     pub async fn cmd_game_scan(&mut self) -> Result<()> {
         println!("Scanning for games...");
-        let custom = self.config.read().await.custom_games.clone();
-        self.games = GameDetector::detect_all_with_custom(&custom).await;
+        let (custom, definitions) = {
+            let config = self.config.read().await;
+            (
+                config.custom_games.clone(),
+                GameDefinition::load_dir(&config.paths.games_dir()),
+            )
+        };
+        self.games = GameDetector::detect_all_with_custom(&custom, &definitions).await;
 
         if self.games.is_empty() {
             println!("No games found.");
@@ -143,8 +244,38 @@ impl App {
             println!("Found {} game(s):", self.games.len());
             for game in &self.games {
                 println!("  - {} at {}", game.name, game.install_path.display());
+                if let Some(prefix) = &game.proton_prefix {
+                    println!("      Proton prefix: {}", prefix.display());
+                }
+            }
+        }
+
+        // Remember prefixes found in Steam libraries for games added by path
+        let mut config = self.config.write().await;
+        let mut recorded = 0;
+        for entry in config
+            .custom_games
+            .iter_mut()
+            .filter(|e| e.proton_prefix.is_none())
+        {
+            let found = self.games.iter().find(|g| {
+                g.id.eq_ignore_ascii_case(&entry.game_id)
+                    && g.install_path == std::path::Path::new(entry.install_path.trim())
+            });
+            if let Some(prefix) = found.and_then(|g| g.proton_prefix.as_ref()) {
+                println!(
+                    "Recorded Proton prefix for {} ({}): {}",
+                    entry.game_id,
+                    entry.install_path,
+                    prefix.display()
+                );
+                entry.proton_prefix = Some(prefix.to_string_lossy().to_string());
+                recorded += 1;
             }
         }
+        if recorded > 0 {
+            config.save().await?;
+        }
         Ok(())
     }
 
@@ -159,7 +290,10 @@ impl App {
             "steam" => GamePlatform::Steam,
             "gog" => GamePlatform::Gog,
             "manual" => GamePlatform::Manual,
-            other => bail!("Unknown platform '{}'. Use: steam, gog, manual", other),
+            other => bail!(Invalid::new(format!(
+                "Unknown platform '{}'. Use: steam, gog, manual",
+                other
+            ))),
         };
         self.add_custom_game_path(game_id, path, platform, proton_prefix)
             .await?;
@@ -191,10 +325,10 @@ impl App {
                 println!("Selected: {} ({})", g.name, g.id);
                 self.set_active_game(Some(g)).await?;
             }
-            None => bail!(
+            None => bail!(Invalid::new(format!(
                 "Game '{}' not found. Run 'modsanity game list' to see available games.",
                 name
-            ),
+            ))),
         }
         Ok(())
     }
@@ -202,9 +336,33 @@ impl App {
     pub async fn cmd_game_info(&self) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
+        if self.json_output {
+            let dlc: Option<Vec<_>> = crate::games::installed_dlc(&game).map(|installed| {
+                game.game_type
+                    .dlc()
+                    .iter()
+                    .map(|d| serde_json::json!({ "name": d.name, "installed": installed.contains(&d) }))
+                    .collect()
+            });
+            return print_json(&serde_json::json!({
+                "id": game.id,
+                "name": game.name,
+                "platform": game.platform.display_name(),
+                "install_path": game.install_path,
+                "data_path": game.data_path,
+                "proton_prefix": game.proton_prefix,
+                "appdata_path": game.appdata_path,
+                "version": game.version().map(|v| v.to_string()),
+                "build": game.build().map(|b| b.label()),
+                "dlc": dlc,
+            }));
+        }
+
         println!("Game Information");
         println!("{:-<40}", "");
         println!("Name:         {}", game.name);
@@ -212,12 +370,43 @@ impl App {
         println!("Platform:     {}", game.platform.display_name());
         println!("Install Path: {}", game.install_path.display());
         println!("Data Path:    {}", game.data_path.display());
+        if let Some(version) = game.version() {
+            match game.build() {
+                Some(build) => println!("Version:      {} ({})", version, build.label()),
+                None => println!("Version:      {}", version),
+            }
+        }
         if let Some(prefix) = &game.proton_prefix {
             println!("Proton Prefix: {}", prefix.display());
         }
         if let Some(appdata) = &game.appdata_path {
             println!("AppData:      {}", appdata.display());
         }
+        if !game.game_type.dlc().is_empty() {
+            match crate::games::installed_dlc(&game) {
+                Some(installed) => {
+                    let dlc: Vec<String> = game
+                        .game_type
+                        .dlc()
+                        .iter()
+                        .map(|d| {
+                            let mark = if installed.contains(&d) { "✓" } else { "✗" };
+                            format!("{} {}", mark, d.name)
+                        })
+                        .collect();
+                    println!("DLC:          {}", dlc.join(", "));
+                }
+                None => println!("DLC:          unknown (Data folder not readable)"),
+            }
+            for m in self.mods.missing_dlc(&game)? {
+                println!(
+                    "  ! {} needs {} ({})",
+                    m.mod_name,
+                    m.dlc.join(", "),
+                    m.plugins.join(", ")
+                );
+            }
+        }
         Ok(())
     }
 
@@ -226,11 +415,35 @@ impl App {
     pub async fn cmd_mod_list(&self) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
         let mods = self.mods.list_mods(&game.id).await?;
 
+        if self.json_output {
+            let mods: Vec<_> = mods
+                .iter()
+                .map(|m| {
+                    serde_json::json!({
+                        "name": m.name,
+                        "version": m.version,
+                        "author": m.author,
+                        "enabled": m.enabled,
+                        "priority": m.priority,
+                        "nexus_mod_id": m.nexus_mod_id,
+                        "nexus_file_id": m.nexus_file_id,
+                        "file_count": m.file_count,
+                        "size": m.size,
+                        "install_path": m.install_path,
+                        "installed_at": m.installed_at,
+                    })
+                })
+                .collect();
+            return print_json(&serde_json::json!(mods));
+        }
+
         if mods.is_empty() {
             println!("No mods installed for {}.", game.name);
             return Ok(());
@@ -245,10 +458,17 @@ impl App {
         Ok(())
     }
 
-    pub async fn cmd_mod_install(&self, path: &str) -> Result<()> {
+    pub async fn cmd_mod_install(
+        &self,
+        path: &str,
+        fomod_preset: Option<&str>,
+        fomod_defaults: bool,
+    ) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
         println!("Installing mod from: {}", path);
@@ -263,20 +483,81 @@ impl App {
                 Ok(())
             }
             crate::mods::InstallResult::RequiresWizard(context) => {
+                let preset = match fomod_preset {
+                    Some(file) => Some(crate::mods::fomod::FomodPreset::load(
+                        std::path::Path::new(file),
+                    )?),
+                    None if fomod_defaults => None,
+                    None => {
+                        println!(
+                            "ERROR: {} requires FOMOD wizard interaction",
+                            context.mod_name
+                        );
+                        println!(
+                            "Run the TUI (no arguments), or answer it with --fomod-preset <file> or --fomod-defaults"
+                        );
+                        bail!(Invalid::new("Interactive wizard required"))
+                    }
+                };
+
+                let env = self.mods.fomod_environment(&game.id)?.with_game(&game);
+                let wizard = crate::mods::fomod::answer_wizard(
+                    &context.installer.config,
+                    env,
+                    preset.as_ref(),
+                )?;
                 println!(
-                    "ERROR: {} requires FOMOD wizard interaction",
-                    context.mod_name
+                    "Answering FOMOD installer with {}",
+                    if preset.is_some() {
+                        "preset"
+                    } else {
+                        "default selections"
+                    }
                 );
-                println!("FOMOD wizards are only supported in TUI mode (run without arguments)");
-                bail!("Interactive wizard required")
+                let installed = self
+                    .mods
+                    .complete_fomod_install(&context, &wizard, None)
+                    .await?;
+                println!("Installed: {} (v{})", installed.name, installed.version);
+                println!("Run 'modsanity deploy' to apply changes.");
+                Ok(())
             }
         }
     }
 
+    pub async fn cmd_mod_add_dir(
+        &self,
+        path: &str,
+        mode: crate::mods::AdoptMode,
+        name: Option<&str>,
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+
+        println!("Adopting folder: {}", path);
+        let installed = self
+            .mods
+            .adopt_directory(&game.id, std::path::Path::new(path), mode, name)
+            .await?;
+        println!(
+            "Added: {} (v{}, {} files, {})",
+            installed.name,
+            installed.version,
+            installed.file_count,
+            mode.display_name()
+        );
+        println!("Run 'modsanity deploy' to apply changes.");
+        Ok(())
+    }
+
     pub async fn cmd_mod_enable(&self, name: &str) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
         self.mods.enable_mod(&game.id, name).await?;
@@ -288,7 +569,7 @@ impl App {
     pub async fn cmd_mod_disable(&self, name: &str) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
         self.mods.disable_mod(&game.id, name).await?;
@@ -300,511 +581,2905 @@ impl App {
     pub async fn cmd_mod_remove(&self, name: &str) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
         self.mods.remove_mod(&game.id, name).await?;
-        println!("Removed: {}", name);
+        let retention = self.config.read().await.trash.retention_days;
+        if retention > 0 {
+            println!(
+                "Removed: {} (kept in the trash for {} day(s); undo with 'modsanity mod restore \"{}\"')",
+                name, retention, name
+            );
+        } else {
+            println!("Removed: {}", name);
+        }
         Ok(())
     }
 
-    pub async fn cmd_mod_info(&self, name: &str) -> Result<()> {
+    pub async fn cmd_mod_restore(&self, name: &str) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
-        let m = self.mods.get_mod(&game.id, name).await?;
-
-        println!("Mod Information");
-        println!("{:-<40}", "");
-        println!("Name:     {}", m.name);
-        println!("Version:  {}", m.version);
-        println!("Enabled:  {}", if m.enabled { "Yes" } else { "No" });
-        println!("Priority: {}", m.priority);
-        if let Some(author) = &m.author {
-            println!("Author:   {}", author);
-        }
-        if let Some(nexus_id) = m.nexus_mod_id {
-            println!("Nexus ID: {}", nexus_id);
-        }
-        println!("Files:    {}", m.file_count);
+        let restored = self.mods.restore_mod(&game.id, name).await?;
+        println!(
+            "Restored: {} {} ({}). Run 'modsanity deploy' to apply.",
+            restored.name,
+            restored.version,
+            if restored.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
         Ok(())
     }
 
-    pub async fn cmd_mod_rescan(&self) -> Result<()> {
+    pub async fn cmd_mod_trash(&self, empty: bool) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
-        println!("Scanning staging directory for {}...", game.name);
-        let stats = self.mods.rescan_mods(&game.id, None).await?;
+        if empty {
+            let purged = self.mods.purge_trash(&game.id, true).await?;
+            println!("Deleted {} mod(s) from the trash.", purged);
+            return Ok(());
+        }
+
+        self.mods.purge_trash(&game.id, false).await?;
+        let trashed = self.mods.trashed_mods(&game.id).await?;
+        if self.json_output {
+            return print_json(&serde_json::to_value(&trashed)?);
+        }
+        if trashed.is_empty() {
+            println!("The trash is empty.");
+            return Ok(());
+        }
         println!(
-            "Rescan complete: {} added, {} updated, {} unchanged, {} failed",
-            stats.added, stats.updated, stats.unchanged, stats.failed
+            "{:<40} {:<12} {:<17} Deleted after",
+            "Name", "Version", "Removed"
         );
+        println!("{:-<90}", "");
+        for m in &trashed {
+            println!(
+                "{:<40} {:<12} {:<17} {}",
+                m.name,
+                m.version,
+                m.removed_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M"),
+                m.expires_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+            );
+        }
         Ok(())
     }
 
-    // ========== Profile Commands ==========
-
-    pub async fn cmd_profile_list(&self) -> Result<()> {
+    pub async fn cmd_mod_info(&self, name: &str) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
-        let profiles = self.profiles.list_profiles(&game.id).await?;
-        let active = self.config.read().await.active_profile.clone();
+        let m = self.mods.get_mod(&game.id, name).await?;
 
-        if profiles.is_empty() {
-            println!("No profiles for {}.", game.name);
-            return Ok(());
+        println!("Mod Information");
+        println!("{:-<40}", "");
+        println!("Name:     {}", m.name);
+        println!("Version:  {}", m.version);
+        println!("Enabled:  {}", if m.enabled { "Yes" } else { "No" });
+        println!("Priority: {}", m.priority);
+        if let Some(author) = &m.author {
+            println!("Author:   {}", author);
+        }
+        if let Some(nexus_id) = m.nexus_mod_id {
+            println!("Nexus ID: {}", nexus_id);
+        }
+        println!("Files:    {}", m.file_count);
+        if let Some(flag) = self
+            .mods
+            .unavailable_files(&game.id)?
+            .into_iter()
+            .find(|f| f.mod_id == m.id)
+        {
+            println!(
+                "Warning:  file {} is no longer available on Nexus ({})",
+                flag.nexus_file_id, flag.status
+            );
+            if let (Some(name), Some(file_id)) = (&flag.replacement_name, flag.replacement_file_id)
+            {
+                println!(
+                    "Current:  {} v{} (file {})",
+                    name,
+                    flag.replacement_version.as_deref().unwrap_or("?"),
+                    file_id
+                );
+            }
+        }
+        if let Some(missing) = self
+            .mods
+            .missing_dlc(&game)?
+            .into_iter()
+            .find(|d| d.mod_id == m.id)
+        {
+            println!(
+                "Warning:  needs {} (not installed); {} is left out of deploy",
+                missing.dlc.join(", "),
+                missing.plugins.join(", ")
+            );
+        }
+        let requirements: Vec<String> = self
+            .mods
+            .launch_requirements(&game)?
+            .into_iter()
+            .filter(|r| r.mod_name == m.name)
+            .map(|r| {
+                let name = r.requirement.display_name(game.game_type);
+                if r.detected {
+                    format!("{} (detected)", name)
+                } else {
+                    name.to_string()
+                }
+            })
+            .collect();
+        if !requirements.is_empty() {
+            println!("Requires: {}", requirements.join(", "));
+        }
+        let exclusions = self.mods.mod_exclusions(&game.id, &m.name)?;
+        if !exclusions.is_empty() {
+            println!("Excludes: {}", exclusions.join(", "));
         }
 
-        println!("Profiles for {}:", game.name);
-        println!("{:-<40}", "");
-        for p in profiles {
-            let marker = if Some(&p.name) == active.as_ref() {
-                " [active]"
-            } else {
-                ""
-            };
-            println!("  {}{}", p.name, marker);
+        let archives = self.mods.list_retained_archives(&game.id, &m.name).await?;
+        if !archives.is_empty() {
+            println!();
+            println!("Retained archives:");
+            for a in archives {
+                let marker = if a.active { " [installed]" } else { "" };
+                println!("  v{}{}  {}", a.version, marker, a.archive_path);
+            }
         }
         Ok(())
     }
 
-    pub async fn cmd_profile_create(&self, name: &str) -> Result<()> {
+    pub async fn cmd_mod_require(&self, name: &str, requirement: &str, remove: bool) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let Some(parsed) = crate::mods::LaunchRequirement::parse(requirement) else {
+            bail!(Invalid::new(format!(
+                "Unknown requirement '{}'. Use: script-extender (or skse/f4se/sfse), enb",
+                requirement
+            )));
         };
 
-        self.profiles.create_profile(&game.id, name).await?;
-        println!("Created profile: {}", name);
+        self.mods
+            .set_mod_requirement(&game.id, name, parsed, !remove)
+            .await?;
+        let label = parsed.display_name(game.game_type);
+        if remove {
+            println!("{} no longer marked as requiring {}", name, label);
+        } else {
+            println!("{} now requires {}", name, label);
+        }
         Ok(())
     }
 
-    pub async fn cmd_profile_switch(&self, name: &str) -> Result<()> {
+    pub async fn cmd_mod_exclude(&self, name: &str, pattern: &str, remove: bool) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
-        self.profiles.switch_profile(&game.id, name).await?;
-        println!("Switched to profile: {}", name);
-        println!("Run 'modsanity deploy' to apply changes.");
+        self.mods
+            .set_mod_exclusion(&game.id, name, pattern, !remove)
+            .await?;
+        if remove {
+            println!("{} no longer excludes {}", name, pattern);
+        } else {
+            println!("{} now excludes {} from deploy", name, pattern);
+        }
         Ok(())
     }
 
-    pub async fn cmd_profile_delete(&self, name: &str) -> Result<()> {
+    pub async fn cmd_mod_update(&self, name: &str, path: &str) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
-        self.profiles.delete_profile(&game.id, name).await?;
-        println!("Deleted profile: {}", name);
-        Ok(())
+        println!("Updating {} from: {}", name, path);
+        let result = self
+            .mods
+            .update_from_archive(&game.id, name, path, None)
+            .await?;
+        Self::report_reinstall(result)
     }
 
-    pub async fn cmd_profile_export(&self, name: &str, path: &str) -> Result<()> {
+    pub async fn cmd_mod_update_all(&self) -> Result<()> {
+        use crate::queue::{QueueManager, QueueProcessor, QueueStatus};
+
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let nexus = match &self.nexus {
+            Some(client) => client.clone(),
+            None => bail!(Invalid::new("NexusMods API key not configured.")),
         };
 
-        self.profiles.export_profile(&game.id, name, path).await?;
-        println!("Exported '{}' to: {}", name, path);
+        println!("Checking for updates...");
+        let updates = self.mods.check_for_updates(&game.id, &nexus).await?;
+        let queue_manager = QueueManager::new(self.db.clone());
+        let Some((batch_id, count)) = queue_manager.queue_updates(&game.id, &updates)? else {
+            println!("All mods are up to date.");
+            return Ok(());
+        };
+        for update in &updates {
+            println!(
+                "  - {}: {} -> {}",
+                update.name, update.current_version, update.latest_version
+            );
+        }
+        println!("Queued {} update(s) in batch {}", count, batch_id);
+
+        let download_dir = self.config.read().await.downloads_dir()?;
+        let processor = QueueProcessor::new(
+            self.db.clone(),
+            (*nexus).clone(),
+            game.nexus_game_domain(),
+            game.id.clone(),
+            download_dir,
+            self.mods.clone(),
+        )
+        .with_game_build(game.build());
+        processor.process_batch(&batch_id, false).await?;
+
+        let entries = queue_manager.get_batch(&batch_id)?;
+        let updated = entries
+            .iter()
+            .filter(|e| e.status == QueueStatus::Completed)
+            .count();
+        println!("Updated {} of {} mod(s)", updated, entries.len());
+        for entry in entries
+            .iter()
+            .filter(|e| e.status != QueueStatus::Completed)
+        {
+            println!(
+                "  - {}: {}",
+                entry.mod_name,
+                entry
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| entry.status.to_string())
+            );
+        }
+        if updated > 0 {
+            println!("Run 'modsanity deploy' to apply changes.");
+        }
         Ok(())
     }
 
-    pub async fn cmd_profile_import(&self, path: &str) -> Result<()> {
+    pub async fn cmd_run_script(&self, path: &str, dry_run: bool) -> Result<()> {
+        use crate::mods::{check_setup_script, load_setup_script, ScriptStep};
+
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
+        let lines = load_setup_script(std::path::Path::new(path))?;
+        if lines.is_empty() {
+            println!("{} has no steps.", path);
+            return Ok(());
+        }
 
-        let profile = self.profiles.import_profile(&game.id, path).await?;
-        println!("Imported profile: {}", profile.name);
+        let before = self.mods.list_mods(&game.id).await?;
+        let problems = check_setup_script(&lines, &before);
+        if dry_run {
+            println!("Script {} ({} steps, no changes made)", path, lines.len());
+            println!("{:-<60}", "");
+            for line in &lines {
+                println!("{:>4}  {}", line.line, line.step.describe());
+            }
+            for problem in &problems {
+                println!("  ! {}", problem);
+            }
+            return Ok(());
+        }
+        if !problems.is_empty() {
+            for problem in &problems {
+                println!("  ! {}", problem);
+            }
+            bail!(Invalid::new(format!(
+                "Not running {}: fix the problems above",
+                path
+            )));
+        }
+
+        let mut deployed = false;
+        for (i, line) in lines.iter().enumerate() {
+            match self.mods.run_script_step(&game, &line.step).await {
+                Ok(done) => {
+                    println!("[{}/{}] {}", i + 1, lines.len(), done);
+                    deployed |= line.step == ScriptStep::Deploy;
+                }
+                Err(e) => {
+                    println!(
+                        "[{}/{}] failed: {}",
+                        i + 1,
+                        lines.len(),
+                        line.step.describe()
+                    );
+                    println!("Rolling back...");
+                    self.mods
+                        .restore_mod_states(&game.id, &before)
+                        .await
+                        .context("Rollback failed; check 'modsanity mod list'")?;
+                    if deployed {
+                        self.mods.deploy(&game).await?;
+                    }
+                    return Err(e.context(format!("Line {}", line.line)));
+                }
+            }
+        }
+        println!("Ran {} steps from {}.", lines.len(), path);
         Ok(())
     }
 
-    // ========== Other Commands ==========
-
-    pub async fn cmd_deploy(&self) -> Result<()> {
+    pub async fn cmd_mod_fomod_export(
+        &self,
+        name: &str,
+        path: &str,
+        profile: Option<&str>,
+    ) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected."),
+            None => bail!(Invalid::new("No game selected.")),
         };
 
-        println!("Deploying mods to {}...", game.name);
-        let stats = self.mods.deploy(&game).await?;
+        let preset = self
+            .mods
+            .export_fomod_preset(&game.id, name, profile, std::path::Path::new(path))
+            .await?;
         println!(
-            "Deployed {} files from {} mods.",
-            stats.files_deployed, stats.mods_deployed
+            "Exported {} FOMOD choice group(s) for {} to {}",
+            preset.selections.len(),
+            name,
+            path
         );
         Ok(())
     }
 
-    pub async fn cmd_set_deployment_method(&self, method: &str) -> Result<()> {
-        let parsed = DeploymentMethod::from_cli(method)?;
-        self.set_deployment_method(parsed).await?;
+    pub async fn cmd_mod_fomod_import(
+        &self,
+        name: &str,
+        path: &str,
+        profile: Option<&str>,
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        self.mods
+            .import_fomod_preset(&game.id, name, profile, std::path::Path::new(path))
+            .await?;
+        println!("Saved FOMOD choices for {} from {}", name, path);
         println!(
-            "Deployment method set to: {} ({})",
-            parsed.display_name(),
-            parsed.as_str()
+            "They are restored the next time its installer runs ('f' in the TUI, 'mod reinstall')."
         );
         Ok(())
     }
 
-    pub async fn cmd_deployment_show(&self) -> Result<()> {
-        let config = self.config.read().await;
-        println!("Deployment Settings");
-        println!("{:-<40}", "");
+    pub async fn cmd_mod_check_files(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let nexus = match &self.nexus {
+            Some(client) => client.clone(),
+            None => bail!(Invalid::new("NexusMods API key not configured.")),
+        };
+
+        println!("Checking installed files on Nexus...");
+        let unavailable = self.mods.check_file_availability(&game, &nexus).await?;
+        if unavailable.is_empty() {
+            println!("All installed files are still available.");
+            return Ok(());
+        }
+        for (name, status) in &unavailable {
+            println!("  - {}: {}", name, status.describe());
+        }
         println!(
-            "Method:           {}",
-            config.deployment.method.display_name()
+            "{} mod(s) can't be re-downloaded as installed; install the current file to keep them reproducible.",
+            unavailable.len()
         );
-        println!("Method (raw):     {}", config.deployment.method.as_str());
-        println!(
-            "Backup originals: {}",
-            if config.deployment.backup_originals {
-                "Yes"
-            } else {
-                "No"
+        Ok(())
+    }
+
+    pub async fn cmd_which(&self, path: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        let lookups = self.mods.which_file(&game.id, path)?;
+        if self.json_output {
+            let files: Vec<_> = lookups
+                .iter()
+                .map(|lookup| {
+                    let winner = lookup.winner().map(|p| p.mod_id);
+                    let providers: Vec<_> = lookup
+                        .providers
+                        .iter()
+                        .map(|p| {
+                            serde_json::json!({
+                                "mod": p.mod_name,
+                                "priority": p.priority,
+                                "enabled": p.enabled,
+                                "size": p.size,
+                                "deployed": Some(p.mod_id) == winner,
+                            })
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "path": lookup.path,
+                        "identical": lookup.identical(),
+                        "providers": providers,
+                    })
+                })
+                .collect();
+            return print_json(&serde_json::json!(files));
+        }
+        if lookups.is_empty() {
+            println!("No installed mod provides '{}'.", path);
+            return Ok(());
+        }
+        for lookup in &lookups {
+            println!("{}", lookup.path);
+            let winner = lookup.winner().map(|p| p.mod_id);
+            for provider in &lookup.providers {
+                let marker = if Some(provider.mod_id) == winner {
+                    "*"
+                } else {
+                    " "
+                };
+                let state = if provider.enabled { "" } else { " (disabled)" };
+                let size = provider
+                    .size
+                    .map(|s| format!(" {} bytes", s))
+                    .unwrap_or_default();
+                println!(
+                    "  {} {} [priority {}]{}{}",
+                    marker, provider.mod_name, provider.priority, size, state
+                );
             }
-        );
-        println!(
-            "Purge on exit:    {}",
-            if config.deployment.purge_on_exit {
-                "Yes"
-            } else {
-                "No"
+            if lookup.providers.len() > 1 && lookup.identical() {
+                println!("  (all copies are identical)");
             }
-        );
-        println!("Downloads dir:    {}", config.downloads_dir().display());
-        println!("Staging dir:      {}", config.staging_dir().display());
+        }
+        println!("* = deployed copy");
         Ok(())
     }
 
-    pub async fn cmd_set_downloads_dir(&self, path: &str) -> Result<()> {
-        Self::validate_directory_override(path)?;
-        let override_path = if path.trim().is_empty() {
-            None
-        } else {
-            Some(path)
+    pub async fn cmd_history(&self, action: Option<&str>, limit: usize) -> Result<()> {
+        use crate::activity::{self, Activity};
+
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
         };
-        self.set_downloads_dir_override(override_path).await?;
-        let resolved = self.resolved_downloads_dir().await;
-        if override_path.is_some() {
-            println!(
-                "Downloads directory override set to: {}",
-                resolved.display()
-            );
-        } else {
+        let action = match action {
+            Some(id) => Some(Activity::from_id(id).ok_or_else(|| {
+                Invalid::new(format!(
+                    "Unknown action '{}'. Use: {}",
+                    id,
+                    Activity::ALL
+                        .iter()
+                        .map(|a| a.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            })?),
+            None => None,
+        };
+
+        let entries = activity::recent(&self.db, &game.id, action, limit)?;
+        if self.json_output {
+            return print_json(&serde_json::to_value(&entries)?);
+        }
+        if entries.is_empty() {
+            println!("No activity recorded for {} yet.", game.name);
+            return Ok(());
+        }
+        // Oldest first, so the latest change is next to the prompt
+        for entry in entries.iter().rev() {
+            let profile = entry
+                .profile
+                .as_deref()
+                .map(|p| format!(" [{}]", p))
+                .unwrap_or_default();
             println!(
-                "Downloads directory override cleared (using default): {}",
-                resolved.display()
+                "{}  {:<14} {:<30} {}{}",
+                entry
+                    .time
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M"),
+                entry.action,
+                entry.target.as_deref().unwrap_or("-"),
+                entry.details_summary(),
+                profile
             );
         }
         Ok(())
     }
 
-    pub async fn cmd_set_staging_dir(&self, path: &str) -> Result<()> {
-        Self::validate_directory_override(path)?;
-        let override_path = if path.trim().is_empty() {
-            None
-        } else {
-            Some(path)
+    pub async fn cmd_mod_rollback(&self, name: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
         };
-        self.set_staging_dir_override(override_path).await?;
-        let resolved = self.resolved_staging_dir().await;
-        if override_path.is_some() {
-            println!("Staging directory override set to: {}", resolved.display());
-        } else {
-            println!(
-                "Staging directory override cleared (using default): {}",
-                resolved.display()
-            );
+
+        println!("Rolling back {}...", name);
+        let result = self.mods.rollback_mod(&game.id, name, None).await?;
+        Self::report_reinstall(result)
+    }
+
+    pub async fn cmd_mod_reinstall(&self, name: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        println!("Reinstalling {} from its original archive...", name);
+        let result = self.mods.reinstall_mod(&game.id, name, None).await?;
+        Self::report_reinstall(result)
+    }
+
+    fn report_reinstall(result: crate::mods::InstallResult) -> Result<()> {
+        match result {
+            crate::mods::InstallResult::Completed(installed) => {
+                println!("Installed: {} (v{})", installed.name, installed.version);
+                println!("Run 'modsanity deploy' to apply changes.");
+                Ok(())
+            }
+            crate::mods::InstallResult::RequiresWizard(context) => {
+                println!(
+                    "ERROR: {} requires FOMOD wizard interaction (saved choices no longer match)",
+                    context.mod_name
+                );
+                println!("Reconfigure the mod in TUI mode (run without arguments)");
+                bail!(Invalid::new("Interactive wizard required"))
+            }
         }
-        Ok(())
     }
 
-    pub async fn cmd_migrate_staging(&self, from: &str, to: &str, dry_run: bool) -> Result<()> {
-        use walkdir::WalkDir;
+    pub async fn cmd_mod_scripts(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let report = self.mods.script_overrides(&game)?;
 
-        let src = std::path::Path::new(from);
-        let dst = std::path::Path::new(to);
-        if !src.exists() || !src.is_dir() {
-            bail!(
-                "Source staging directory does not exist or is not a directory: {}",
-                src.display()
+        println!("Script Overrides");
+        println!("{:-<40}", "");
+        println!("Base game scripts: {}", report.game_scripts);
+        println!("Mod scripts:       {}", report.mod_scripts);
+        if report.game_scripts == 0 {
+            println!(
+                "(no base game archives found in {})",
+                game.data_path.display()
             );
         }
-        if src == dst {
-            bail!("Source and destination staging directories are the same path");
-        }
 
-        let mut files_total = 0usize;
-        let mut files_to_copy = 0usize;
-        let mut files_skipped_existing = 0usize;
-        let mut dirs_to_create = 0usize;
+        let vanilla: Vec<_> = report.vanilla_overrides().collect();
+        println!();
+        println!("Vanilla script overrides: {}", vanilla.len());
+        for entry in &vanilla {
+            let winner = entry.winner().map(|w| w.mod_name.as_str()).unwrap_or("?");
+            println!(
+                "  ! {:<40} {} (from {})",
+                entry.path,
+                winner,
+                entry.vanilla_source.as_deref().unwrap_or("?")
+            );
+        }
+        if !vanilla.is_empty() {
+            println!("  Loose overrides of vanilla scripts can break existing saves when removed.");
+        }
 
-        for entry in WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
-            let p = entry.path();
-            if p == src {
-                continue;
-            }
-            let rel = p.strip_prefix(src).unwrap_or(p);
-            let target = dst.join(rel);
-            if entry.file_type().is_dir() {
-                if !target.exists() {
-                    dirs_to_create += 1;
-                }
-                continue;
-            }
-            if entry.file_type().is_file() {
-                files_total += 1;
-                if target.exists() {
-                    files_skipped_existing += 1;
-                } else {
-                    files_to_copy += 1;
-                }
-            }
+        let conflicts: Vec<_> = report.mod_conflicts().collect();
+        println!();
+        println!("Scripts provided by several mods: {}", conflicts.len());
+        for entry in &conflicts {
+            let providers: Vec<String> = entry
+                .providers
+                .iter()
+                .map(|p| match &p.archive {
+                    Some(archive) => format!("{} [{}]", p.mod_name, archive),
+                    None => p.mod_name.clone(),
+                })
+                .collect();
+            let winner = entry.winner().map(|w| w.mod_name.as_str()).unwrap_or("?");
+            println!("  ~ {:<40} winner: {}", entry.path, winner);
+            println!("      {}", providers.join(" < "));
         }
+        Ok(())
+    }
 
-        println!("Staging Migration Plan");
-        println!("{:-<60}", "");
-        println!("From: {}", src.display());
-        println!("To:   {}", dst.display());
-        println!("Directories to create: {}", dirs_to_create);
-        println!("Files total scanned:   {}", files_total);
-        println!("Files to copy:         {}", files_to_copy);
-        println!("Files skipped(existing): {}", files_skipped_existing);
+    pub async fn cmd_mod_rescan(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+
+        println!("Scanning staging directory for {}...", game.name);
+        let stats = self.mods.rescan_mods(&game.id, None).await?;
         println!(
-            "Mode: {}",
-            if dry_run {
-                "dry-run (no writes)"
-            } else {
-                "apply"
-            }
+            "Rescan complete: {} added, {} updated, {} unchanged, {} failed",
+            stats.added, stats.updated, stats.unchanged, stats.failed
         );
+        Ok(())
+    }
 
-        if dry_run {
-            println!("Dry-run complete. Re-run without --dry-run to execute.");
-            return Ok(());
-        }
+    pub async fn cmd_mod_verify(&self, name: Option<&str>, full: bool) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
 
-        std::fs::create_dir_all(dst)
-            .with_context(|| format!("Failed to create destination root: {}", dst.display()))?;
+        let reports = self.mods.verify_mods(&game.id, name, full).await?;
+        if self.json_output {
+            return print_json(&serde_json::to_value(&reports)?);
+        }
 
-        let mut copied = 0usize;
-        for entry in WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
-            let p = entry.path();
-            if p == src {
-                continue;
+        let files: usize = reports.iter().map(|r| r.files).sum();
+        let mut problems = 0;
+        for report in reports.iter().filter(|r| !r.mismatches.is_empty()) {
+            println!("{} ({} file(s)):", report.mod_name, report.mismatches.len());
+            for mismatch in report.mismatches.iter().take(20) {
+                println!("  {:<12} {}", mismatch.drift.label(), mismatch.path);
             }
-            let rel = p.strip_prefix(src).unwrap_or(p);
-            let target = dst.join(rel);
-            if entry.file_type().is_dir() {
-                if !target.exists() {
-                    std::fs::create_dir_all(&target).with_context(|| {
-                        format!("Failed creating directory: {}", target.display())
-                    })?;
-                }
-                continue;
-            }
-            if entry.file_type().is_file() {
-                if target.exists() {
-                    continue;
-                }
-                if let Some(parent) = target.parent() {
-                    std::fs::create_dir_all(parent)
-                        .with_context(|| format!("Failed creating parent: {}", parent.display()))?;
-                }
-                std::fs::copy(p, &target).with_context(|| {
-                    format!(
-                        "Failed copying file: {} -> {}",
-                        p.display(),
-                        target.display()
-                    )
-                })?;
-                copied += 1;
+            if report.mismatches.len() > 20 {
+                println!("  ... and {} more", report.mismatches.len() - 20);
             }
+            problems += report.mismatches.len();
         }
+        if problems == 0 {
+            println!(
+                "Verified {} mod(s), {} file(s): staging matches the file index.",
+                reports.len(),
+                files
+            );
+            return Ok(());
+        }
+        bail!(
+            "{} file(s) differ from the index. Run 'modsanity mod reinstall <name>' to restore a mod's files from its archive, or 'modsanity mod rescan' to accept added, removed or resized files.",
+            problems
+        )
+    }
 
-        println!("Copied {} files.", copied);
-        println!("Updating staging override to destination...");
-        self.cmd_set_staging_dir(&dst.display().to_string()).await?;
-        println!("Migration complete.");
-        println!("Recommended: run 'modsanity mod rescan' to refresh DB indexes.");
+    // ========== Profile Commands ==========
+
+    pub async fn cmd_profile_list(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        let profiles = self.profiles.list_profiles(&game.id).await?;
+        let active = self.config.read().await.active_profile.clone();
+
+        if self.json_output {
+            let profiles: Vec<_> = profiles
+                .iter()
+                .map(|p| {
+                    let overrides: serde_json::Map<_, _> = p
+                        .tool_overrides
+                        .iter()
+                        .map(|(tool, ovr)| (tool.clone(), serde_json::json!(ovr.summary())))
+                        .collect();
+                    serde_json::json!({
+                        "name": p.name,
+                        "active": Some(&p.name) == active.as_ref(),
+                        "own_ini_files": p.own_ini_files,
+                        "own_saves": p.own_saves,
+                        "tool_overrides": overrides,
+                    })
+                })
+                .collect();
+            return print_json(&serde_json::json!(profiles));
+        }
+
+        if profiles.is_empty() {
+            println!("No profiles for {}.", game.name);
+            return Ok(());
+        }
+
+        println!("Profiles for {}:", game.name);
+        println!("{:-<40}", "");
+        for p in profiles {
+            let marker = if Some(&p.name) == active.as_ref() {
+                " [active]"
+            } else {
+                ""
+            };
+            println!("  {}{}", p.name, marker);
+            if p.own_ini_files {
+                println!("      INI files: own copies");
+            }
+            if p.own_saves {
+                println!("      Saves: own folder");
+            }
+            for (tool, ovr) in &p.tool_overrides {
+                println!("      {}: {}", tool, ovr.summary());
+            }
+        }
         Ok(())
     }
 
-    pub async fn cmd_tool_show(&self) -> Result<()> {
-        let config = self.config.read().await;
-        let detected_runtimes = self.detect_proton_runtimes();
-        println!("External Tools");
-        println!("{:-<60}", "");
-        let runtime_display = config
-            .external_tools
-            .proton_runtime
-            .clone()
-            .unwrap_or_else(|| "custom-command".to_string());
-        println!("Proton runtime selection: {}", runtime_display);
-        println!(
-            "Proton command fallback:  {}",
-            config.external_tools.proton_command
-        );
-        match self.resolve_proton_launcher_from_config(&config) {
-            Ok(resolved) => println!("Resolved Proton launcher: {}", resolved),
-            Err(e) => println!("Resolved Proton launcher: <unresolved> ({})", e),
+    pub async fn cmd_profile_tool_set(
+        &self,
+        name: &str,
+        tool: &str,
+        path: Option<String>,
+        output_mod: Option<String>,
+        args: Vec<String>,
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let tool = ExternalTool::from_cli(tool)?;
+
+        let profile = self.profiles.get_profile(&game.id, name).await?;
+        let mut ovr = profile.tool_override(tool).cloned().unwrap_or_default();
+        if let Some(path) = path {
+            ovr.path = Some(path.trim().to_string()).filter(|p| !p.is_empty());
         }
-        println!("Detected Proton runtimes: {}", detected_runtimes.len());
-        for runtime in &detected_runtimes {
+        if let Some(output_mod) = output_mod {
+            ovr.output_mod = Some(output_mod.trim().to_string()).filter(|m| !m.is_empty());
+        }
+        if !args.is_empty() {
+            ovr.args = args;
+        }
+
+        let summary = ovr.summary();
+        self.profiles
+            .set_tool_override(&game.id, name, tool, ovr)
+            .await?;
+        if summary.is_empty() {
             println!(
-                "  - {:<28} {:<24} {}",
-                runtime.id,
-                runtime.name,
-                runtime.proton_path.display()
+                "{} uses the global settings in '{}'",
+                tool.display_name(),
+                name
             );
+        } else {
+            println!("{} in '{}': {}", tool.display_name(), name, summary);
         }
-        for tool in ExternalTool::all() {
-            let value = config.external_tool_path(*tool).unwrap_or("Not set");
-            let mode = config.external_tool_runtime_mode(*tool).as_str();
-            println!("{:>14}: {} (runtime: {})", tool.display_name(), value, mode);
+        Ok(())
+    }
+
+    pub async fn cmd_profile_tool_clear(&self, name: &str, tool: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let tool = ExternalTool::from_cli(tool)?;
+        self.profiles
+            .set_tool_override(&game.id, name, tool, Default::default())
+            .await?;
+        println!(
+            "{} uses the global settings in '{}'",
+            tool.display_name(),
+            name
+        );
+        Ok(())
+    }
+
+    pub async fn cmd_profile_ini_save(&self, name: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let files = self.profiles.save_profile_ini(&game, name).await?;
+        println!(
+            "Stored {} as the INI files of '{}'; they're put in place when it's activated.",
+            files.join(", "),
+            name
+        );
+        Ok(())
+    }
+
+    pub async fn cmd_profile_ini_clear(&self, name: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        self.profiles.clear_profile_ini(&game.id, name).await?;
+        println!("'{}' uses the game's INI files as they are", name);
+        Ok(())
+    }
+
+    pub async fn cmd_profile_saves(&self, name: &str, own: bool) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        self.profiles.set_profile_saves(&game, name, own).await?;
+        let active = self.config.read().await.active_profile.as_deref() == Some(name);
+        match (own, active) {
+            (true, true) => println!(
+                "'{}' saves to {}",
+                name,
+                saves::saves_dir(&game).unwrap_or_default().display()
+            ),
+            (true, false) => println!("'{}' gets its own save folder when it's activated", name),
+            (false, _) => println!("'{}' uses the game's shared save folder", name),
         }
         Ok(())
     }
 
-    pub async fn cmd_tool_set_proton(&self, path: &str) -> Result<()> {
-        self.set_proton_command(path).await?;
-        println!("Proton command set to: {}", path.trim());
-        println!("Proton runtime selection cleared (using custom command/path mode).");
+    pub async fn cmd_profile_create(&self, name: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        self.profiles.create_profile(&game.id, name).await?;
+        println!("Created profile: {}", name);
         Ok(())
     }
 
-    pub async fn cmd_tool_list_proton(&self) -> Result<()> {
-        let runtimes = self.detect_proton_runtimes();
-        let config = self.config.read().await;
-        let selected = config.external_tools.proton_runtime.as_deref();
-        println!("Detected Proton Runtimes");
-        println!("{:-<80}", "");
-        if runtimes.is_empty() {
-            println!("No Steam-managed Proton runtimes were detected.");
-            println!("Install/select a Proton tool in Steam, then rerun this command.");
-            return Ok(());
+    pub async fn cmd_profile_switch(&self, name: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        self.profiles.switch_profile(&game.id, name).await?;
+        println!("Switched to profile: {}", name);
+        match saves::check_save_plugins(&game) {
+            Ok(issues) => {
+                for issue in &issues {
+                    println!("  ! {}", issue);
+                }
+            }
+            Err(e) => tracing::warn!("Save check failed: {}", e),
         }
-        for runtime in runtimes {
-            let marker = if selected == Some(runtime.id.as_str()) {
-                " [selected]"
-            } else {
-                ""
+        println!("Run 'modsanity deploy' to apply changes.");
+        Ok(())
+    }
+
+    pub async fn cmd_ini_presets(&self) -> Result<()> {
+        let game_type = self.active_game().await.map(|g| g.game_type);
+
+        println!("INI presets:");
+        println!("{:-<40}", "");
+        for preset in crate::profiles::ini_presets() {
+            let marker = match game_type {
+                Some(t) if !preset.supports(t) => " (not for this game)",
+                _ => "",
             };
             println!(
-                "{}{}",
-                format!(
-                    "{:<30} {:<22} {} ({})",
-                    runtime.id,
-                    runtime.name,
-                    runtime.proton_path.display(),
-                    runtime.source
-                ),
+                "  {:<12} {} [{} keys]{}",
+                preset.name,
+                preset.description,
+                preset.settings().len(),
                 marker
             );
         }
-        println!("\nUse one with: modsanity tool use-proton <runtime_id>");
-        println!("Or auto-select: modsanity tool use-proton auto");
         Ok(())
     }
 
-    pub async fn cmd_tool_use_proton(&self, runtime: &str) -> Result<()> {
-        self.set_proton_runtime(Some(runtime)).await?;
-        println!("Proton runtime selected: {}", runtime.trim());
+    pub async fn cmd_ini_diff(&self, left: &str, right: &str) -> Result<()> {
+        use crate::profiles::{diff_ini, IniChange, IniSource};
+
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let left = IniSource::parse(left)?;
+        let right = IniSource::parse(right)?;
+        let diff = diff_ini(
+            &self.profiles.resolve_ini(&game, &left).await?,
+            &self.profiles.resolve_ini(&game, &right).await?,
+        );
+
+        if diff.is_empty() {
+            println!(
+                "No INI differences between {} and {}.",
+                left.label(),
+                right.label()
+            );
+            return Ok(());
+        }
+
+        println!("INI diff: {} -> {}", left.label(), right.label());
+        let mut current_file = String::new();
+        let mut current_section = String::new();
+        for d in &diff {
+            if d.file != current_file {
+                println!();
+                println!("{}", d.file);
+                current_file = d.file.clone();
+                current_section.clear();
+            }
+            if d.section != current_section {
+                println!("  [{}]", d.section);
+                current_section = d.section.clone();
+            }
+            let marker = match d.change() {
+                IniChange::Added => "+",
+                IniChange::Removed => "-",
+                IniChange::Changed => "~",
+            };
+            println!(
+                "  {} {:<28} {} -> {}",
+                marker,
+                d.key,
+                d.left.as_deref().unwrap_or("(unset)"),
+                d.right.as_deref().unwrap_or("(unset)")
+            );
+        }
+        println!();
+        println!("{} key(s) differ.", diff.len());
         Ok(())
     }
 
-    pub async fn cmd_tool_clear_proton_runtime(&self) -> Result<()> {
-        self.set_proton_runtime(None).await?;
-        println!("Proton runtime selection cleared (using custom command/path).");
+    pub async fn cmd_ini_apply_preset(&self, preset: &str, profile: Option<&str>) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let Some(preset) = crate::profiles::find_ini_preset(preset) else {
+            bail!(Invalid::new(format!(
+                "Unknown INI preset '{}'. Run 'modsanity ini presets'.",
+                preset
+            )));
+        };
+        let profile = match profile {
+            Some(p) => p.to_string(),
+            None => match self.config.read().await.active_profile.clone() {
+                Some(p) => p,
+                None => bail!(Invalid::new("No active profile. Pass --profile <name>.")),
+            },
+        };
+
+        self.profiles
+            .apply_ini_preset(&game.id, &profile, preset)
+            .await?;
+        println!(
+            "Applied preset '{}' to profile '{}' ({} keys).",
+            preset.name,
+            profile,
+            preset.settings().len()
+        );
+        println!("Switch to the profile to write the overrides to the game INI files.");
+        Ok(())
+    }
+
+    pub async fn cmd_ini_show(&self, file: Option<&str>) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let files: Vec<ini::IniFile> = match file {
+            Some(file) => {
+                let file = ini::resolve_ini_file(game.game_type, file)?;
+                ini::game_ini_files(&game)?
+                    .into_iter()
+                    .filter(|f| f.name == file)
+                    .collect()
+            }
+            None => ini::game_ini_files(&game)?,
+        };
+
+        for (i, f) in files.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            println!(
+                "{}  {}{}",
+                f.name,
+                f.path.display(),
+                if f.exists { "" } else { "  (missing)" }
+            );
+            // Without a file argument, only the known keys
+            let mut entries: Vec<ini::IniEntry> = ini::ini_entries(&game, f.name)?
+                .into_iter()
+                .filter(|e| file.is_some() || e.known.is_some())
+                .collect();
+            let mut sections: Vec<String> = Vec::new();
+            for entry in &entries {
+                if !sections
+                    .iter()
+                    .any(|s| s.eq_ignore_ascii_case(&entry.section))
+                {
+                    sections.push(entry.section.clone());
+                }
+            }
+            entries.sort_by_key(|e| {
+                sections
+                    .iter()
+                    .position(|s| s.eq_ignore_ascii_case(&e.section))
+            });
+            let mut section = String::new();
+            for entry in entries {
+                if !entry.section.eq_ignore_ascii_case(&section) {
+                    println!("  [{}]", entry.section);
+                    section = entry.section.clone();
+                }
+                let value = entry.value.as_deref().unwrap_or("(unset)");
+                match entry.known {
+                    Some(k) => println!(
+                        "    {:<26} {:<12} {:<6} {}",
+                        entry.key,
+                        value,
+                        k.kind.label(),
+                        k.description
+                    ),
+                    None => println!("    {:<26} {}", entry.key, value),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_ini_set(
+        &self,
+        file: &str,
+        section: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let file = ini::resolve_ini_file(game.game_type, file)?;
+        let write = self.set_ini_value(&game, file, section, key, value).await?;
+        let value = ini::read_ini_settings(&game, file)?
+            .into_iter()
+            .find(|s| s.section.eq_ignore_ascii_case(section) && s.key.eq_ignore_ascii_case(key))
+            .map(|s| s.value)
+            .unwrap_or_default();
+        println!("Set [{}] {}={} in {}", section, key, value, file);
+        print_ini_write(&write);
         Ok(())
     }
 
-    pub async fn cmd_tool_set_path(&self, tool: &str, path: &str) -> Result<()> {
-        let parsed = ExternalTool::from_cli(tool)?;
-        self.set_external_tool_path(parsed, Some(path)).await?;
-        println!("{} path set to: {}", parsed.display_name(), path);
+    pub async fn cmd_ini_edit(&self, file: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let file = ini::resolve_ini_file(game.game_type, file)?;
+        match self.edit_ini_in_editor(&game, file).await? {
+            Some(write) => {
+                println!("Saved {}", file);
+                print_ini_write(&write);
+            }
+            None => println!("{} unchanged.", file),
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_ini_backups(&self, file: Option<&str>) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let files = match file {
+            Some(file) => vec![ini::resolve_ini_file(game.game_type, file)?],
+            None => game.game_type.ini_files().to_vec(),
+        };
+        let backup_dir = self.ini_backup_dir(&game).await;
+        let mut any = false;
+        for file in files {
+            let backups = ini::ini_backups(&backup_dir, file)?;
+            if backups.is_empty() {
+                continue;
+            }
+            any = true;
+            println!("{}:", file);
+            for backup in backups {
+                println!(
+                    "  {}",
+                    backup.file_name().unwrap_or_default().to_string_lossy()
+                );
+            }
+        }
+        if any {
+            println!();
+            println!("In {}", backup_dir.display());
+        } else {
+            println!("No INI backups yet.");
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_ini_restore(&self, backup: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let backup_dir = self.ini_backup_dir(&game).await;
+        let path = std::path::PathBuf::from(super::expand_user_path(backup));
+        let path = if path.is_absolute() || path.exists() {
+            path
+        } else {
+            backup_dir.join(backup)
+        };
+        if !path.is_file() {
+            bail!(Invalid::new(format!(
+                "Backup '{}' not found. Run 'modsanity ini backups'.",
+                backup
+            )));
+        }
+        let write = ini::restore_ini_backup(&game, &backup_dir, &path)?;
+        println!("Restored {}", write.path.display());
+        print_ini_write(&write);
+        Ok(())
+    }
+
+    // ========== Save Commands ==========
+
+    pub async fn cmd_saves_list(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let list = saves::list_saves(&game)?;
+        let loaded = saves::loaded_plugins(&game).unwrap_or_default();
+        let missing = |s: &saves::SaveGame| {
+            s.header
+                .as_ref()
+                .map(|h| saves::missing_plugins(h, &loaded))
+                .unwrap_or_default()
+        };
+
+        if self.json_output {
+            let list: Vec<_> = list
+                .iter()
+                .map(|s| {
+                    let header = s.header.as_ref();
+                    serde_json::json!({
+                        "name": s.name,
+                        "path": s.path,
+                        "cosave": s.cosave,
+                        "size": s.size,
+                        "saved_at": s.saved_at().map(|d| d.to_string()),
+                        "character": header.map(|h| &h.character),
+                        "level": header.map(|h| h.level),
+                        "location": header.map(|h| &h.location),
+                        "playtime": header.map(|h| &h.playtime),
+                        "plugins": header.map(|h| &h.plugins),
+                        "missing_plugins": missing(s),
+                        "error": s.error,
+                    })
+                })
+                .collect();
+            return print_json(&serde_json::json!(list));
+        }
+
+        if list.is_empty() {
+            println!(
+                "No saves in {}",
+                saves::saves_dir(&game).unwrap_or_default().display()
+            );
+            return Ok(());
+        }
+        println!("Saves for {} ({}):", game.name, list.len());
+        println!("{:-<60}", "");
+        for save in &list {
+            let date = save
+                .saved_at()
+                .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "unknown date".to_string());
+            println!("  {}", save.name);
+            match &save.header {
+                Some(h) => println!(
+                    "      {}, level {}, {} | played {} | {}",
+                    h.character, h.level, h.location, h.playtime, date
+                ),
+                None => println!(
+                    "      {} | unreadable header: {}",
+                    date,
+                    save.error.as_deref().unwrap_or("unknown format")
+                ),
+            }
+            let missing = missing(save);
+            if !missing.is_empty() {
+                println!("      ! not in the load order: {}", missing.join(", "));
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_saves_backup(&self, name: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let save = saves::find_save(&game, name)?;
+        let dest = saves::backup_save(&save, &self.save_backup_dir(&game).await)?;
+        println!("Backed up {} to {}", save.name, dest.display());
+        Ok(())
+    }
+
+    pub async fn cmd_saves_delete(&self, name: &str, yes: bool) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let save = saves::find_save(&game, name)?;
+        if !yes {
+            if !io::stdin().is_terminal() {
+                bail!(Invalid::new(
+                    "Pass --yes to delete a save without a terminal"
+                ));
+            }
+            print!("Delete {}? [y/N]: ", save.name);
+            io::stdout().flush()?;
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            if !answer.trim().to_ascii_lowercase().starts_with('y') {
+                println!("Kept {}", save.name);
+                return Ok(());
+            }
+        }
+        saves::delete_save(&save)?;
+        println!("Deleted {}", save.name);
+        Ok(())
+    }
+
+    pub async fn cmd_profile_delete(&self, name: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        self.profiles.delete_profile(&game.id, name).await?;
+        println!("Deleted profile: {}", name);
+        Ok(())
+    }
+
+    pub async fn cmd_profile_export(&self, name: &str, path: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        self.profiles.export_profile(&game.id, name, path).await?;
+        println!("Exported '{}' to: {}", name, path);
+        Ok(())
+    }
+
+    pub async fn cmd_profile_import(&self, path: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        let profile = self.profiles.import_profile(&game.id, path).await?;
+        println!("Imported profile: {}", profile.name);
+        Ok(())
+    }
+
+    // ========== Other Commands ==========
+
+    pub async fn cmd_deploy(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        println!("Deploying mods to {}...", game.name);
+        let stats = self.mods.deploy(&game).await?;
+        println!(
+            "Deployed {} files from {} mods.",
+            stats.files_deployed, stats.mods_deployed
+        );
+        for warning in &stats.warnings {
+            println!("  ! {}", warning);
+        }
+        if !stats.excluded.is_empty() {
+            println!("Excluded {} files by pattern.", stats.excluded.len());
+        }
+        if let Some(path) = &stats.archive_invalidation {
+            println!("Turned on archive invalidation in {}.", path.display());
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_deploy_dry_run(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        let stats = self.mods.deploy_dry_run(&game).await?;
+        println!("Deployment dry run for {} (no changes made)", game.name);
+        println!("{:-<60}", "");
+        println!(
+            "Would deploy {} files from {} mods ({} conflicts resolved).",
+            stats.files_deployed, stats.mods_deployed, stats.conflicts_resolved
+        );
+        for warning in &stats.warnings {
+            println!("  ! {}", warning);
+        }
+        for error in &stats.errors {
+            println!("  x {}", error);
+        }
+
+        println!("Excluded files: {}", stats.excluded.len());
+        let mut current_mod = None;
+        for file in &stats.excluded {
+            if current_mod != Some(&file.mod_name) {
+                println!("  {}", file.mod_name);
+                current_mod = Some(&file.mod_name);
+            }
+            println!("    - {} ({})", file.path, file.pattern);
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_deploy_export(&self, path: &str) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+
+        println!("Exporting deployment for {} to {}...", game.name, path);
+        let stats = self
+            .mods
+            .export_deployment(&game, std::path::Path::new(path))
+            .await?;
+        println!(
+            "Exported {} files ({}) from {} mods.",
+            stats.files_exported,
+            crate::mods::format_bytes(stats.bytes_exported),
+            stats.mods_exported
+        );
+        for error in &stats.errors {
+            println!("  ! {}", error);
+        }
+        println!(
+            "Extract it into the game folder; `{}` lists every file.",
+            crate::mods::EXPORT_MANIFEST
+        );
+        Ok(())
+    }
+
+    pub fn cmd_deploy_clean_export(&self, game_dir: &str) -> Result<()> {
+        let stats = crate::mods::clean_export(std::path::Path::new(game_dir))?;
+        println!("Removed {} exported files.", stats.files_removed);
+        if stats.files_missing > 0 {
+            println!("{} listed files were already gone.", stats.files_missing);
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_set_deployment_method(&self, method: &str) -> Result<()> {
+        let parsed = DeploymentMethod::from_cli(method)?;
+        self.set_deployment_method(parsed).await?;
+        println!(
+            "Deployment method set to: {} ({})",
+            parsed.display_name(),
+            parsed.as_str()
+        );
+        Ok(())
+    }
+
+    pub async fn cmd_deployment_show(&self) -> Result<()> {
+        let config = self.config.read().await;
+        if self.json_output {
+            return print_json(&serde_json::json!({
+                "method": config.deployment.method.as_str(),
+                "backup_originals": config.deployment.backup_originals,
+                "purge_on_exit": config.deployment.purge_on_exit,
+                "downloads_dir": config.downloads_dir().ok(),
+                "staging_dir": config.staging_dir().ok(),
+                "exclude": config.deployment.exclude,
+            }));
+        }
+        println!("Deployment Settings");
+        println!("{:-<40}", "");
+        println!(
+            "Method:           {}",
+            config.deployment.method.display_name()
+        );
+        println!("Method (raw):     {}", config.deployment.method.as_str());
+        println!(
+            "Backup originals: {}",
+            if config.deployment.backup_originals {
+                "Yes"
+            } else {
+                "No"
+            }
+        );
+        println!(
+            "Purge on exit:    {}",
+            if config.deployment.purge_on_exit {
+                "Yes"
+            } else {
+                "No"
+            }
+        );
+        println!("Downloads dir:    {}", dir_display(&config.downloads_dir()));
+        println!("Staging dir:      {}", dir_display(&config.staging_dir()));
+        if config.deployment.exclude.is_empty() {
+            println!("Excluded files:   none");
+        } else {
+            println!("Excluded files:   {}", config.deployment.exclude.join(", "));
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_deployment_exclude(&self, pattern: &str, remove: bool) -> Result<()> {
+        let changed = self.set_deploy_exclusion(pattern, !remove).await?;
+        match (remove, changed) {
+            (false, true) => println!("Files matching {} will no longer be deployed.", pattern),
+            (false, false) => println!("{} is already excluded.", pattern),
+            (true, true) => println!("Removed exclusion {}.", pattern),
+            (true, false) => println!("{} was not excluded.", pattern),
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_set_downloads_dir(&self, path: &str) -> Result<()> {
+        Self::validate_directory_override(path)?;
+        let override_path = if path.trim().is_empty() {
+            None
+        } else {
+            Some(path)
+        };
+        self.set_downloads_dir_override(override_path).await?;
+        let resolved = dir_display(&self.resolved_downloads_dir().await);
+        if override_path.is_some() {
+            println!("Downloads directory override set to: {}", resolved);
+        } else {
+            println!(
+                "Downloads directory override cleared (using default): {}",
+                resolved
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_set_staging_dir(&self, path: &str) -> Result<()> {
+        Self::validate_directory_override(path)?;
+        let override_path = if path.trim().is_empty() {
+            None
+        } else {
+            Some(path)
+        };
+        self.set_staging_dir_override(override_path).await?;
+        let resolved = dir_display(&self.resolved_staging_dir().await);
+        if override_path.is_some() {
+            println!("Staging directory override set to: {}", resolved);
+        } else {
+            println!(
+                "Staging directory override cleared (using default): {}",
+                resolved
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_migrate_staging(&self, from: &str, to: &str, dry_run: bool) -> Result<()> {
+        use walkdir::WalkDir;
+
+        let src = std::path::Path::new(from);
+        let dst = std::path::Path::new(to);
+        if !src.exists() || !src.is_dir() {
+            bail!(Invalid::new(format!(
+                "Source staging directory does not exist or is not a directory: {}",
+                src.display()
+            )));
+        }
+        if src == dst {
+            bail!("Source and destination staging directories are the same path");
+        }
+
+        let mut files_total = 0usize;
+        let mut files_to_copy = 0usize;
+        let mut files_skipped_existing = 0usize;
+        let mut dirs_to_create = 0usize;
+
+        for entry in WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
+            let p = entry.path();
+            if p == src {
+                continue;
+            }
+            let rel = p.strip_prefix(src).unwrap_or(p);
+            let target = dst.join(rel);
+            if entry.file_type().is_dir() {
+                if !target.exists() {
+                    dirs_to_create += 1;
+                }
+                continue;
+            }
+            if entry.file_type().is_file() {
+                files_total += 1;
+                if target.exists() {
+                    files_skipped_existing += 1;
+                } else {
+                    files_to_copy += 1;
+                }
+            }
+        }
+
+        println!("Staging Migration Plan");
+        println!("{:-<60}", "");
+        println!("From: {}", src.display());
+        println!("To:   {}", dst.display());
+        println!("Directories to create: {}", dirs_to_create);
+        println!("Files total scanned:   {}", files_total);
+        println!("Files to copy:         {}", files_to_copy);
+        println!("Files skipped(existing): {}", files_skipped_existing);
+        println!(
+            "Mode: {}",
+            if dry_run {
+                "dry-run (no writes)"
+            } else {
+                "apply"
+            }
+        );
+
+        if dry_run {
+            println!("Dry-run complete. Re-run without --dry-run to execute.");
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(dst)
+            .with_context(|| format!("Failed to create destination root: {}", dst.display()))?;
+
+        let mut copied = 0usize;
+        for entry in WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
+            let p = entry.path();
+            if p == src {
+                continue;
+            }
+            let rel = p.strip_prefix(src).unwrap_or(p);
+            let target = dst.join(rel);
+            if entry.file_type().is_dir() {
+                if !target.exists() {
+                    std::fs::create_dir_all(&target).with_context(|| {
+                        format!("Failed creating directory: {}", target.display())
+                    })?;
+                }
+                continue;
+            }
+            if entry.file_type().is_file() {
+                if target.exists() {
+                    continue;
+                }
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed creating parent: {}", parent.display()))?;
+                }
+                std::fs::copy(p, &target).with_context(|| {
+                    format!(
+                        "Failed copying file: {} -> {}",
+                        p.display(),
+                        target.display()
+                    )
+                })?;
+                copied += 1;
+            }
+        }
+
+        println!("Copied {} files.", copied);
+        println!("Updating staging override to destination...");
+        self.cmd_set_staging_dir(&dst.display().to_string()).await?;
+        println!("Migration complete.");
+        println!("Recommended: run 'modsanity mod rescan' to refresh DB indexes.");
+        Ok(())
+    }
+
+    pub async fn cmd_tool_show(&self) -> Result<()> {
+        let config = self.config.read().await;
+        let detected_runtimes = self.detect_proton_runtimes();
+        println!("External Tools");
+        println!("{:-<60}", "");
+        let runtime_display = config
+            .external_tools
+            .proton_runtime
+            .clone()
+            .unwrap_or_else(|| "custom-command".to_string());
+        println!("Proton runtime selection: {}", runtime_display);
+        println!(
+            "Proton command fallback:  {}",
+            config.external_tools.proton_command
+        );
+        match self.resolve_proton_launcher_from_config(&config) {
+            Ok(resolved) => println!("Resolved Proton launcher: {}", resolved),
+            Err(e) => println!("Resolved Proton launcher: <unresolved> ({})", e),
+        }
+        println!("Detected Proton runtimes: {}", detected_runtimes.len());
+        for runtime in &detected_runtimes {
+            println!(
+                "  - {:<28} {:<24} {}",
+                runtime.id,
+                runtime.name,
+                runtime.proton_path.display()
+            );
+        }
+        for tool in ExternalTool::all() {
+            let value = config.external_tool_path(*tool).unwrap_or("Not set");
+            let mode = config.external_tool_runtime_mode(*tool).as_str();
+            println!("{:>14}: {} (runtime: {})", tool.display_name(), value, mode);
+            if let Some(options) = config.external_tool_options(*tool) {
+                println!("{:>14}  {}", "", options.summary());
+            }
+        }
+        if !config.custom_tools.is_empty() {
+            println!();
+            println!("Custom tools:");
+            for tool in &config.custom_tools {
+                println!(
+                    "{:>14}: {} (runtime: {})",
+                    tool.name,
+                    tool.path,
+                    tool.runtime_mode.as_str()
+                );
+                if !tool.args.is_empty() {
+                    println!("{:>14}  args: {}", "", tool.args.join(" "));
+                }
+                if let Some(dir) = &tool.working_dir {
+                    println!("{:>14}  working dir: {}", "", dir);
+                }
+                for (key, value) in &tool.env {
+                    println!("{:>14}  env: {}={}", "", key, value);
+                }
+            }
+        }
+        drop(config);
+
+        let active_profile = self.config.read().await.active_profile.clone();
+        if let (Some(game), Some(name)) = (self.active_game().await, active_profile) {
+            if let Ok(profile) = self.profiles.get_profile(&game.id, &name).await {
+                if !profile.tool_overrides.is_empty() {
+                    println!();
+                    println!("Overrides in profile '{}':", name);
+                    for (tool, ovr) in &profile.tool_overrides {
+                        println!("{:>14}: {}", tool, ovr.summary());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_tool_set_proton(&self, path: &str) -> Result<()> {
+        self.set_proton_command(path).await?;
+        println!("Proton command set to: {}", path.trim());
+        println!("Proton runtime selection cleared (using custom command/path mode).");
+        Ok(())
+    }
+
+    pub async fn cmd_tool_list_proton(&self) -> Result<()> {
+        let runtimes = self.detect_proton_runtimes();
+        let config = self.config.read().await;
+        let selected = config.external_tools.proton_runtime.as_deref();
+        println!("Detected Proton Runtimes");
+        println!("{:-<80}", "");
+        if runtimes.is_empty() {
+            println!("No Steam-managed Proton runtimes were detected.");
+            println!("Install/select a Proton tool in Steam, then rerun this command.");
+            return Ok(());
+        }
+        for runtime in runtimes {
+            let marker = if selected == Some(runtime.id.as_str()) {
+                " [selected]"
+            } else {
+                ""
+            };
+            println!(
+                "{:<30} {:<22} {} ({}){}",
+                runtime.id,
+                runtime.name,
+                runtime.proton_path.display(),
+                runtime.source,
+                marker
+            );
+        }
+        println!("\nUse one with: modsanity tool use-proton <runtime_id>");
+        println!("Or auto-select: modsanity tool use-proton auto");
+        Ok(())
+    }
+
+    pub async fn cmd_tool_use_proton(&self, runtime: &str) -> Result<()> {
+        self.set_proton_runtime(Some(runtime)).await?;
+        println!("Proton runtime selected: {}", runtime.trim());
+        Ok(())
+    }
+
+    pub async fn cmd_tool_clear_proton_runtime(&self) -> Result<()> {
+        self.set_proton_runtime(None).await?;
+        println!("Proton runtime selection cleared (using custom command/path).");
+        Ok(())
+    }
+
+    pub async fn cmd_tool_set_path(&self, tool: &str, path: &str) -> Result<()> {
+        let parsed = ExternalTool::from_cli(tool)?;
+        self.set_external_tool_path(parsed, Some(path)).await?;
+        println!("{} path set to: {}", parsed.display_name(), path);
+        Ok(())
+    }
+
+    pub async fn cmd_tool_clear_path(&self, tool: &str) -> Result<()> {
+        let parsed = ExternalTool::from_cli(tool)?;
+        self.set_external_tool_path(parsed, None).await?;
+        println!("{} path cleared", parsed.display_name());
+        Ok(())
+    }
+
+    pub async fn cmd_tool_set_options(
+        &self,
+        tool: &str,
+        workdir: Option<String>,
+        env: &[String],
+        args: Vec<String>,
+    ) -> Result<()> {
+        let tool = ExternalTool::from_cli(tool)?;
+        let mut options = self
+            .config
+            .read()
+            .await
+            .external_tool_options(tool)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(dir) = workdir {
+            options.working_dir = Some(dir.trim().to_string()).filter(|d| !d.is_empty());
+        }
+        for (key, value) in crate::config::parse_env(env)? {
+            if value.is_empty() {
+                options.env.remove(&key);
+            } else {
+                options.env.insert(key, value);
+            }
+        }
+        if !args.is_empty() {
+            options.args = args;
+        }
+
+        let summary = options.summary();
+        self.set_external_tool_options(tool, options).await?;
+        if summary.is_empty() {
+            println!("{} has no launch options", tool.display_name());
+        } else {
+            println!("{}: {}", tool.display_name(), summary);
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_tool_clear_options(&self, tool: &str) -> Result<()> {
+        let tool = ExternalTool::from_cli(tool)?;
+        self.set_external_tool_options(tool, Default::default())
+            .await?;
+        println!("{} launch options cleared", tool.display_name());
+        Ok(())
+    }
+
+    pub async fn cmd_tool_set_runtime(&self, tool: &str, mode: &str) -> Result<()> {
+        let parsed_tool = ExternalTool::from_cli(tool)?;
+        let parsed_mode = ToolRuntimeMode::from_cli(mode)?;
+        self.set_external_tool_runtime_mode(parsed_tool, Some(parsed_mode))
+            .await?;
+        println!(
+            "{} runtime mode set to: {}",
+            parsed_tool.display_name(),
+            parsed_mode.as_str()
+        );
+        Ok(())
+    }
+
+    pub async fn cmd_tool_clear_runtime(&self, tool: &str) -> Result<()> {
+        let parsed_tool = ExternalTool::from_cli(tool)?;
+        self.set_external_tool_runtime_mode(parsed_tool, None)
+            .await?;
+        println!(
+            "{} runtime mode reset to default: proton",
+            parsed_tool.display_name()
+        );
+        Ok(())
+    }
+
+    /// `deploy` answers the stale deployment question up front; `None` asks
+    /// in a terminal and only warns otherwise
+    pub async fn cmd_tool_run(
+        &self,
+        tool: &str,
+        deploy: Option<bool>,
+        args: &[String],
+    ) -> Result<()> {
+        let parsed = self.config.read().await.resolve_tool(tool)?;
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        self.deploy_before_tool(&game, parsed.display_name(), deploy)
+            .await?;
+        println!("Launching {}...", parsed.display_name());
+        let result = self.launch_external_tool(&parsed, args).await?;
+        println!(
+            "{} exited with code {}",
+            parsed.display_name(),
+            result.exit_code
+        );
+        if !result.captured.is_empty() {
+            println!(
+                "Captured {} output file(s) into the profile's output mod; deploy to link them",
+                result.captured.len()
+            );
+        }
+        if !result.new_files.is_empty() {
+            println!(
+                "{} left {} new file(s) in Data:",
+                parsed.display_name(),
+                result.new_files.len()
+            );
+            print_new_data_files(&result.new_files);
+            let default_mod = format!("{} Output", parsed.display_name());
+            if !io::stdin().is_terminal() {
+                println!(
+                    "Move them into a mod with: modsanity tool capture \"{}\"",
+                    default_mod
+                );
+                return Ok(());
+            }
+            print!(
+                "Move them into a mod? Mod name [{}], or n to leave them: ",
+                default_mod
+            );
+            io::stdout().flush()?;
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            let output_mod = match answer.trim() {
+                "" => default_mod,
+                a if a.eq_ignore_ascii_case("n") || a.eq_ignore_ascii_case("no") => return Ok(()),
+                a => a.to_string(),
+            };
+            let captured = self.mods.capture_new_data_files(&game, &output_mod).await?;
+            println!(
+                "Captured {} file(s) into '{}'; deploy to link them",
+                captured.len(),
+                output_mod
+            );
+        }
+        Ok(())
+    }
+
+    /// Offer to deploy before `tool` runs when mods or plugins changed since
+    /// the last deploy. `deploy` answers up front; `None` asks in a terminal
+    /// and only warns otherwise.
+    async fn deploy_before_tool(
+        &self,
+        game: &crate::games::Game,
+        tool: &str,
+        deploy: Option<bool>,
+    ) -> Result<()> {
+        if deploy == Some(false) || !self.mods.deployment_outdated(game).await? {
+            return Ok(());
+        }
+        let deploy = match deploy {
+            Some(deploy) => deploy,
+            None if io::stdin().is_terminal() => {
+                print!(
+                    "Mods or plugins changed since the last deploy. Deploy before launching {}? [Y/n]: ",
+                    tool
+                );
+                io::stdout().flush()?;
+                let mut answer = String::new();
+                io::stdin().read_line(&mut answer)?;
+                !answer.trim().to_ascii_lowercase().starts_with('n')
+            }
+            None => false,
+        };
+        if deploy {
+            println!("Deploying mods to {}...", game.name);
+            let stats = self.mods.deploy(game).await?;
+            println!(
+                "Deployed {} files from {} mods.",
+                stats.files_deployed, stats.mods_deployed
+            );
+        } else {
+            println!(
+                "Warning: the deployment is out of date, so {} sees the previous Data folder (deploy first, or pass --deploy)",
+                tool
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_tool_clean(
+        &self,
+        masterlist: Option<&str>,
+        only: &[String],
+        list: bool,
+        yes: bool,
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        if !list {
+            self.deploy_before_tool(&game, "xEdit", None).await?;
+        }
+        let masterlist = masterlist
+            .map(super::expand_user_path)
+            .map(std::path::PathBuf::from);
+        let mut dirty = self
+            .mods
+            .dirty_plugins(&game, masterlist.as_deref())
+            .await?;
+        if !only.is_empty() {
+            if let Some(unknown) = only
+                .iter()
+                .find(|name| !dirty.iter().any(|p| p.filename.eq_ignore_ascii_case(name)))
+            {
+                bail!(Invalid::new(format!(
+                    "{} isn't in Data or isn't dirty according to the masterlist",
+                    unknown
+                )));
+            }
+            dirty.retain(|p| {
+                only.iter()
+                    .any(|name| p.filename.eq_ignore_ascii_case(name))
+            });
+        }
+        if dirty.is_empty() {
+            println!("No dirty plugins found.");
+            return Ok(());
+        }
+
+        println!("{} dirty plugin(s):", dirty.len());
+        for plugin in &dirty {
+            println!("  {:<40} {}", plugin.filename, plugin.summary());
+        }
+        if list {
+            return Ok(());
+        }
+
+        let interactive = !yes && io::stdin().is_terminal();
+        let mut cleaned = 0;
+        for plugin in &dirty {
+            if interactive {
+                print!(
+                    "Clean {} ({})? [Y/n/q]: ",
+                    plugin.filename,
+                    plugin.summary()
+                );
+                io::stdout().flush()?;
+                let mut answer = String::new();
+                io::stdin().read_line(&mut answer)?;
+                match answer.trim().to_ascii_lowercase().chars().next() {
+                    Some('q') => break,
+                    Some('n') => continue,
+                    _ => {}
+                }
+            }
+            println!("Cleaning {}...", plugin.filename);
+            let result = self.clean_plugin(plugin).await?;
+            let log = result
+                .launch
+                .log_path
+                .as_ref()
+                .map(|p| format!(" (log: {})", p.display()))
+                .unwrap_or_default();
+            match result.cleaned {
+                Ok(record) => {
+                    cleaned += 1;
+                    match &record.mod_name {
+                        Some(mod_name) => println!(
+                            "  Cleaned {}, updated in mod '{}'{}",
+                            plugin.filename, mod_name, log
+                        ),
+                        None => println!("  Cleaned {}{}", plugin.filename, log),
+                    }
+                    if plugin.nav > 0 {
+                        println!(
+                            "  ! {} deleted navmesh(es) remain; QuickAutoClean can't fix those",
+                            plugin.nav
+                        );
+                    }
+                }
+                Err(e) => println!("  x {}: {}{}", plugin.filename, e, log),
+            }
+        }
+        println!("Cleaned {} of {} dirty plugin(s).", cleaned, dirty.len());
+        Ok(())
+    }
+
+    pub async fn cmd_tool_clean_history(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let records = self.mods.cleaned_plugins(&game.id)?;
+        if records.is_empty() {
+            println!("No plugins cleaned yet.");
+            return Ok(());
+        }
+        println!(
+            "{:<20} {:<40} {:<10} {:<10} Mod",
+            "Cleaned", "Plugin", "CRC before", "CRC after"
+        );
+        println!("{:-<100}", "");
+        for record in &records {
+            let when = chrono::DateTime::parse_from_rfc3339(&record.cleaned_at)
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|_| record.cleaned_at.clone());
+            println!(
+                "{:<20} {:<40} {:<10} {:<10} {}",
+                when,
+                truncate(&record.plugin, 40),
+                format!("{:08X}", record.crc_before),
+                format!("{:08X}", record.crc_after),
+                record.mod_name.as_deref().unwrap_or("(game file)")
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_tool_capture(&self, output_mod: Option<&str>) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let Some(output_mod) = output_mod.map(str::trim).filter(|m| !m.is_empty()) else {
+            let files = self.mods.new_data_files(&game).await?;
+            if files.is_empty() {
+                println!("No new files in Data since the last deploy.");
+            } else {
+                println!("{} new file(s) in Data since the last deploy:", files.len());
+                print_new_data_files(&files);
+            }
+            return Ok(());
+        };
+        let captured = self.mods.capture_new_data_files(&game, output_mod).await?;
+        if captured.is_empty() {
+            println!("No new files in Data since the last deploy.");
+        } else {
+            println!(
+                "Captured {} file(s) into '{}'; deploy to link them",
+                captured.len(),
+                output_mod
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_tool_prefix_setup(
+        &self,
+        verbs: &[String],
+        force: bool,
+        dry_run: bool,
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let Some(compatdata) = &game.proton_prefix else {
+            bail!(Invalid::new(format!(
+                "{} has no Proton prefix; run the game once through Proton first",
+                game.name
+            )));
+        };
+        let installed = prefix::installed_verbs(&compatdata.join("pfx"));
+        let verbs: Vec<&str> = if verbs.is_empty() {
+            prefix::PREFIX_DEPENDENCIES
+                .iter()
+                .map(|dep| dep.verb)
+                .collect()
+        } else {
+            verbs.iter().map(String::as_str).collect()
+        };
+        let verbs: Vec<&str> = verbs
+            .into_iter()
+            .filter(|verb| force || !installed.contains(&verb.to_lowercase()))
+            .collect();
+        if verbs.is_empty() {
+            println!("All prefix dependencies are installed.");
+            return Ok(());
+        }
+
+        let Some(mut cmd) = prefix::install_command(&game, &verbs) else {
+            bail!(Invalid::new(
+                "Neither protontricks nor winetricks is installed"
+            ));
+        };
+        for verb in &verbs {
+            match prefix::prefix_dependency(verb) {
+                Some(dep) => println!("  {:<16} {} (for {})", dep.verb, dep.name, dep.needed_by),
+                None => println!("  {}", verb),
+            }
+        }
+        let program = cmd.get_program().to_string_lossy().to_string();
+        let command_line = std::iter::once(program.clone())
+            .chain(cmd.get_args().map(|a| a.to_string_lossy().to_string()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if dry_run {
+            println!("Would run: {}", command_line);
+            return Ok(());
+        }
+        println!("Running: {}", command_line);
+        println!("This can take a while; installers may open windows.");
+        let status = cmd
+            .status()
+            .with_context(|| format!("Failed to start {}", program))?;
+        if !status.success() {
+            bail!(
+                "{} exited with {}",
+                program,
+                status
+                    .code()
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "a signal".to_string())
+            );
+        }
+
+        let installed = prefix::installed_verbs(&compatdata.join("pfx"));
+        let still_missing: Vec<&str> = verbs
+            .iter()
+            .copied()
+            .filter(|verb| !installed.contains(&verb.to_lowercase()))
+            .collect();
+        if still_missing.is_empty() {
+            println!("Installed: {}", verbs.join(", "));
+        } else {
+            println!(
+                "Finished, but winetricks didn't log: {}",
+                still_missing.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_play(
+        &self,
+        exe: Option<&str>,
+        force: bool,
+        check: bool,
+        args: &[String],
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+
+        let launch = self.launch_check(&game, exe, None).await?;
+        print_launch_problems(&launch.problems);
+        if check {
+            if launch.problems.is_empty() {
+                println!("Launch check passed: {}", launch.executable);
+            }
+            return Ok(());
+        }
+        if !launch.problems.is_empty() && !force {
+            bail!(Conflict::new(format!(
+                "Refusing to launch {}. Fix the issues above or pass --force.",
+                launch.executable
+            )));
+        }
+        self.run_launch(&game, &launch.executable, args).await
+    }
+
+    /// Start `executable` of `game` through Proton and wait for it to exit
+    async fn run_launch(
+        &self,
+        game: &crate::games::Game,
+        executable: &str,
+        args: &[String],
+    ) -> Result<()> {
+        let path = if std::path::Path::new(executable).is_absolute() {
+            std::path::PathBuf::from(executable)
+        } else {
+            game.executable_path(executable)
+        };
+        if !path.exists() {
+            bail!("Executable not found: {}", path.display());
+        }
+
+        println!("Launching {} via Proton...", executable);
+        let code = self.launch_game_executable(game, &path, args).await?;
+        println!("{} exited with code {}", executable, code);
+        Ok(())
+    }
+
+    pub async fn cmd_launch(
+        &self,
+        skse: bool,
+        profile: Option<&str>,
+        force: bool,
+        args: &[String],
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+
+        let exe = match (skse, game.game_type.script_extender_loader()) {
+            (false, _) => None,
+            (true, Some(loader)) => Some(loader),
+            (true, None) => bail!(Invalid::new(format!(
+                "{} has no script extender loader",
+                game.name
+            ))),
+        };
+
+        // Checked against the profile's mods before anything is switched
+        // or deployed
+        let launch = self.launch_check(&game, exe, profile).await?;
+        print_launch_problems(&launch.problems);
+        if !launch.problems.is_empty() && !force {
+            bail!(Conflict::new(format!(
+                "Refusing to launch {}. Fix the issues above or pass --force.",
+                launch.executable
+            )));
+        }
+
+        let (stats, enabled) = self.prepare_launch(&game, &launch, profile, force).await?;
+        if let Some(profile) = profile {
+            println!("Switched to profile: {}", profile);
+        }
+        match stats {
+            Some(stats) => println!(
+                "Deployed {} files from {} mods.",
+                stats.files_deployed, stats.mods_deployed
+            ),
+            None => println!("Deployment is up to date."),
+        }
+        println!("Synced plugins.txt ({} enabled plugins).", enabled);
+
+        self.run_launch(&game, &launch.executable, args).await
+    }
+
+    pub async fn cmd_extension_list(&self) -> Result<()> {
+        let dirs = self.config.read().await.extension_dirs();
+        if self.json_output {
+            let extensions: Vec<_> = self
+                .extensions
+                .extensions()
+                .iter()
+                .map(|ext| {
+                    let m = &ext.manifest;
+                    serde_json::json!({
+                        "name": m.name,
+                        "version": m.version,
+                        "description": m.description,
+                        "commands": m.commands.iter().map(|c| &c.name).collect::<Vec<_>>(),
+                        "screens": m.screens.iter().map(|s| &s.name).collect::<Vec<_>>(),
+                        "matchers": m.matchers.iter().map(|m| &m.name).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            let errors: Vec<_> = self
+                .extensions
+                .errors()
+                .iter()
+                .map(|(dir, err)| serde_json::json!({ "dir": dir, "error": err }))
+                .collect();
+            return print_json(&serde_json::json!({
+                "extensions": extensions,
+                "errors": errors,
+                "dirs": dirs,
+            }));
+        }
+        if self.extensions.is_empty() && self.extensions.errors().is_empty() {
+            println!("No extensions installed.");
+            println!("Extension folders:");
+            for dir in dirs {
+                println!("  {}", dir.display());
+            }
+            return Ok(());
+        }
+
+        println!("Extensions");
+        println!("{:-<40}", "");
+        for ext in self.extensions.extensions() {
+            let m = &ext.manifest;
+            if m.version.is_empty() {
+                println!("{}", m.name);
+            } else {
+                println!("{} v{}", m.name, m.version);
+            }
+            if !m.description.is_empty() {
+                println!("  {}", m.description);
+            }
+            for c in &m.commands {
+                println!("  command  {:<20} {}", c.name, c.description);
+            }
+            for s in &m.screens {
+                println!("  screen   {:<20} {}", s.name, s.title);
+            }
+            for matcher in &m.matchers {
+                let games = if matcher.games.is_empty() {
+                    "all games".to_string()
+                } else {
+                    matcher.games.join(", ")
+                };
+                println!("  matcher  {:<20} {}", matcher.name, games);
+            }
+        }
+
+        for (dir, err) in self.extensions.errors() {
+            println!("FAILED   {}: {}", dir.display(), err);
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_extension_run(
+        &self,
+        extension: &str,
+        command: &str,
+        args: &[String],
+    ) -> Result<()> {
+        let context = self.extension_context().await;
+        let response = self
+            .extensions
+            .run_command(extension, command, args, context)
+            .await?;
+        for line in &response.lines {
+            println!("{}", line);
+        }
+        if let Some(message) = &response.message {
+            println!("{}", message);
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_extension_screen(&self, extension: &str, screen: &str) -> Result<()> {
+        let context = self.extension_context().await;
+        let response = self
+            .extensions
+            .render_screen(extension, screen, context)
+            .await?;
+        for line in &response.lines {
+            println!("{}", line);
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_status(&self) -> Result<()> {
+        if self.json_output {
+            let config = self.config.read().await;
+            let game = self.active_game().await;
+            let mods = match &game {
+                Some(g) => {
+                    let mods = self.mods.list_mods(&g.id).await?;
+                    let enabled = mods.iter().filter(|m| m.enabled).count();
+                    Some(serde_json::json!({ "installed": mods.len(), "enabled": enabled }))
+                }
+                None => None,
+            };
+            return print_json(&serde_json::json!({
+                "game": game.map(|g| serde_json::json!({ "id": g.id, "name": g.name })),
+                "profile": config.active_profile,
+                "deployment_method": config.deployment.method.as_str(),
+                "mods": mods,
+            }));
+        }
+
+        println!("ModSanity Status");
+        println!("{:-<40}", "");
+
+        let config = self.config.read().await;
+
+        // Game status
+        match self.active_game().await {
+            Some(g) => println!("Active Game: {} ({})", g.name, g.id),
+            None => println!("Active Game: None"),
+        };
+
+        // Profile status
+        match &config.active_profile {
+            Some(p) => println!("Profile:     {}", p),
+            None => println!("Profile:     Default"),
+        };
+        println!("Deploy:      {}", config.deployment.method.display_name());
+
+        // Mod counts
+        if let Some(game) = self.active_game().await {
+            let mods = self.mods.list_mods(&game.id).await?;
+            let enabled = mods.iter().filter(|m| m.enabled).count();
+            println!("Mods:        {} installed, {} enabled", mods.len(), enabled);
+        }
+
+        Ok(())
+    }
+
+    pub async fn cmd_status_disk(&self, clean_archives: bool, clean_orphans: bool) -> Result<()> {
+        use crate::mods::format_bytes;
+
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let report = self.mods.disk_usage(&game).await?;
+
+        println!();
+        println!("Disk Usage");
+        println!("{:-<40}", "");
+        println!(
+            "Staging:     {:>10}  {}",
+            format_bytes(report.staging_bytes),
+            report.staging_dir.display()
+        );
+        println!(
+            "Downloads:   {:>10}  {} ({} archives)",
+            format_bytes(report.downloads_bytes),
+            report.downloads_dir.display(),
+            report.download_count
+        );
+        println!("Retained:    {:>10}", format_bytes(report.archives_bytes));
+        println!(
+            "Deployment:  {:>10}  copied into game ({})",
+            format_bytes(report.copy_overhead_bytes),
+            report.deployment_method.display_name()
+        );
+
+        if !report.mods.is_empty() {
+            println!();
+            println!("    {:<40} {:>10} {:>8}", "Mod", "Size", "Files");
+            for m in &report.mods {
+                let marker = if m.enabled { "[✓]" } else { "[ ]" };
+                println!(
+                    "{} {:<40} {:>10} {:>8}",
+                    marker,
+                    m.name,
+                    format_bytes(m.bytes),
+                    m.files
+                );
+            }
+        }
+
+        if !report.stale_archives.is_empty() {
+            println!();
+            println!(
+                "Stale archives ({}, {}):",
+                report.stale_archives.len(),
+                format_bytes(report.stale_bytes())
+            );
+            for a in &report.stale_archives {
+                println!(
+                    "  {:>10}  {} - {}",
+                    format_bytes(a.bytes),
+                    a.path.display(),
+                    a.reason.describe()
+                );
+            }
+        }
+
+        if !report.orphaned.is_empty() {
+            println!();
+            println!(
+                "Orphaned staging entries ({}, {}):",
+                report.orphaned.len(),
+                format_bytes(report.orphaned_bytes())
+            );
+            for o in &report.orphaned {
+                println!("  {:>10}  {}", format_bytes(o.bytes), o.path.display());
+            }
+        }
+
+        if clean_archives {
+            let stats = self.mods.remove_stale_archives(&report).await;
+            println!();
+            println!(
+                "Removed {} stale archive(s), freed {}",
+                stats.removed,
+                format_bytes(stats.bytes_freed)
+            );
+            for e in &stats.errors {
+                println!("  ✗ {}", e);
+            }
+        }
+
+        if clean_orphans {
+            let stats = self.mods.remove_orphaned_staging(&game.id, &report).await?;
+            println!();
+            println!(
+                "Removed {} orphaned staging entr{}, freed {}",
+                stats.removed,
+                if stats.removed == 1 { "y" } else { "ies" },
+                format_bytes(stats.bytes_freed)
+            );
+            for e in &stats.errors {
+                println!("  ✗ {}", e);
+            }
+        }
+
+        if !clean_archives && !clean_orphans && report.reclaimable_bytes() > 0 {
+            println!();
+            println!(
+                "{} reclaimable. Run with --clean-archives and/or --clean-orphans to free it.",
+                format_bytes(report.reclaimable_bytes())
+            );
+        }
+
+        Ok(())
+    }
+
+    pub async fn cmd_status_history(&self) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new("No game selected.")),
+        };
+        let stats = self.mods.usage_stats(&game.id)?;
+        let when = |t: Option<chrono::DateTime<chrono::Utc>>| {
+            t.map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "never".to_string())
+        };
+
+        println!();
+        println!("History");
+        println!("{:-<40}", "");
+        println!(
+            "Played:      {} times ({} in the last 30 days), last {}",
+            stats.plays,
+            stats.plays_last_30_days,
+            when(stats.last_play)
+        );
+        println!(
+            "Deployed:    {} times, last {}",
+            stats.deploys,
+            when(stats.last_deploy)
+        );
+        for p in &stats.profiles {
+            println!(
+                "    {:<30} {:>4} plays, last {}",
+                p.profile,
+                p.plays,
+                when(Some(p.last_played))
+            );
+        }
+
+        let Some(since) = self.mods.since_last_played(&game).await? else {
+            return Ok(());
+        };
+        println!();
+        println!(
+            "Since you last played ({}): {}",
+            when(Some(since.played_at)),
+            since.changes.summary()
+        );
+        for line in since.changes.lines() {
+            println!("  {}", line);
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_logs_tail(&self, lines: usize, follow: bool) -> Result<()> {
+        use std::io::{Read, Seek, SeekFrom, Write};
+
+        let path = crate::logging::log_file();
+        if !path.exists() {
+            println!("No log file at {}", path.display());
+            return Ok(());
+        }
+        for line in crate::logging::tail_lines(&path, lines)? {
+            println!("{}", line);
+        }
+        if !follow {
+            return Ok(());
+        }
+
+        let mut pos = std::fs::metadata(&path)?.len();
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            let len = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            // Rotated or cleared: carry on with the new file
+            if len < pos {
+                pos = 0;
+            }
+            if len > pos {
+                let mut file = std::fs::File::open(&path)?;
+                file.seek(SeekFrom::Start(pos))?;
+                let mut buf = Vec::new();
+                file.read_to_end(&mut buf)?;
+                pos += buf.len() as u64;
+                print!("{}", String::from_utf8_lossy(&buf));
+                std::io::stdout().flush()?;
+            }
+        }
+    }
+
+    pub async fn cmd_logs_clear(&self) -> Result<()> {
+        let path = crate::logging::log_file();
+        let cleared = crate::logging::clear(&path)?;
+        println!(
+            "Cleared {} log file(s) in {}",
+            cleared,
+            crate::logging::log_dir().display()
+        );
         Ok(())
     }
 
-    pub async fn cmd_tool_clear_path(&self, tool: &str) -> Result<()> {
-        let parsed = ExternalTool::from_cli(tool)?;
-        self.set_external_tool_path(parsed, None).await?;
-        println!("{} path cleared", parsed.display_name());
+    pub async fn cmd_db_backup(&self, path: Option<&str>) -> Result<()> {
+        let dest = match path.map(str::trim).filter(|p| !p.is_empty()) {
+            Some(path) => std::path::PathBuf::from(path),
+            None => crate::db::backup_path(&self.config.read().await.paths.database_file(), ""),
+        };
+        if dest.exists() {
+            bail!(Conflict::new(format!("{} already exists", dest.display())));
+        }
+        self.db.backup_to(&dest)?;
+        println!("Backed up database to {}", dest.display());
         Ok(())
     }
 
-    pub async fn cmd_tool_set_runtime(&self, tool: &str, mode: &str) -> Result<()> {
-        let parsed_tool = ExternalTool::from_cli(tool)?;
-        let parsed_mode = ToolRuntimeMode::from_cli(mode)?;
-        self.set_external_tool_runtime_mode(parsed_tool, Some(parsed_mode))
-            .await?;
+    pub async fn cmd_db_check(&self) -> Result<()> {
+        let problems = self.db.integrity_check()?;
         println!(
-            "{} runtime mode set to: {}",
-            parsed_tool.display_name(),
-            parsed_mode.as_str()
+            "Schema version: {} (this build: {})",
+            self.db.schema_version()?,
+            crate::db::SCHEMA_VERSION
         );
-        Ok(())
+        if problems.is_empty() {
+            println!("Database integrity check passed.");
+            return Ok(());
+        }
+        for problem in problems.iter().take(50) {
+            println!("  {}", problem);
+        }
+        if problems.len() > 50 {
+            println!("  ... and {} more", problems.len() - 50);
+        }
+        bail!(
+            "Database integrity check found {} problem(s). Restore a backup from {} or run 'modsanity db backup' before attempting repairs.",
+            problems.len(),
+            self.config.read().await.paths.backups_dir().display()
+        )
     }
 
-    pub async fn cmd_tool_clear_runtime(&self, tool: &str) -> Result<()> {
-        let parsed_tool = ExternalTool::from_cli(tool)?;
-        self.set_external_tool_runtime_mode(parsed_tool, None)
-            .await?;
+    pub async fn cmd_db_vacuum(&self) -> Result<()> {
+        let size = |path: &Option<std::path::PathBuf>| {
+            path.as_ref()
+                .and_then(|p| std::fs::metadata(p).ok())
+                .map_or(0, |m| m.len())
+        };
+        let path = self.db.file_path();
+        let before = size(&path);
+        self.db.vacuum()?;
+        let after = size(&path);
         println!(
-            "{} runtime mode reset to default: proton",
-            parsed_tool.display_name()
+            "Vacuumed database: {} -> {} ({} reclaimed)",
+            crate::mods::format_bytes(before),
+            crate::mods::format_bytes(after),
+            crate::mods::format_bytes(before.saturating_sub(after))
         );
         Ok(())
     }
 
-    pub async fn cmd_tool_run(&self, tool: &str, args: &[String]) -> Result<()> {
-        let parsed = ExternalTool::from_cli(tool)?;
-        println!("Launching {} via Proton...", parsed.display_name());
-        let code = self.launch_external_tool(parsed, args).await?;
-        println!("{} exited with code {}", parsed.display_name(), code);
+    pub async fn cmd_db_export(&self, path: &str) -> Result<()> {
+        let dest = std::path::Path::new(path);
+        if dest.exists() {
+            bail!(Conflict::new(format!("{} already exists", dest.display())));
+        }
+        let bundle = self.db.export_bundle()?;
+        let json = serde_json::to_string_pretty(&bundle)?;
+        std::fs::write(dest, json)
+            .with_context(|| format!("Failed to write {}", dest.display()))?;
+        println!("Exported library to {}", dest.display());
+        for (table, rows) in bundle.counts() {
+            println!("  {:<18} {}", table, rows);
+        }
         Ok(())
     }
 
-    pub async fn cmd_status(&self) -> Result<()> {
-        println!("ModSanity Status");
-        println!("{:-<40}", "");
+    pub async fn cmd_db_import(&self, path: &str) -> Result<()> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+        let bundle: crate::db::LibraryBundle = serde_json::from_str(&content)
+            .map_err(|e| Invalid::new(format!("{} is not a library export: {}", path, e)))?;
 
-        let config = self.config.read().await;
+        let backup = crate::db::backup_path(
+            &self.config.read().await.paths.database_file(),
+            "pre-import",
+        );
+        self.db.backup_to(&backup)?;
+        self.db.import_bundle(&bundle)?;
 
-        // Game status
-        match self.active_game().await {
-            Some(g) => println!("Active Game: {} ({})", g.name, g.id),
-            None => println!("Active Game: None"),
-        };
+        println!(
+            "Imported library from {} (exported {})",
+            path, bundle.exported_at
+        );
+        for (table, rows) in bundle.counts() {
+            println!("  {:<18} {}", table, rows);
+        }
+        println!("Previous database backed up to {}", backup.display());
+        println!("Copy the staging and archive folders to the same paths, or run 'modsanity mod rescan', before deploying.");
+        Ok(())
+    }
 
-        // Profile status
-        match &config.active_profile {
-            Some(p) => println!("Profile:     {}", p),
-            None => println!("Profile:     Default"),
+    /// Validate config.toml and print the settings it resolves to. Runs
+    /// without an `App` so a config that fails to load can still be checked.
+    pub fn cmd_config_validate(
+        paths: &crate::config::Paths,
+        mods_dir: Option<&str>,
+        json: bool,
+    ) -> Result<()> {
+        let config_file = paths.config_file();
+        let content = match std::fs::read_to_string(&config_file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", config_file.display()))
+            }
         };
-        println!("Deploy:      {}", config.deployment.method.display_name());
+        let definitions = GameDefinition::load_dir(&paths.games_dir());
+        let report = crate::config::validate(&content, &definitions);
 
-        // Mod counts
-        if let Some(game) = self.active_game().await {
-            let mods = self.mods.list_mods(&game.id).await?;
-            let enabled = mods.iter().filter(|m| m.enabled).count();
-            println!("Mods:        {} installed, {} enabled", mods.len(), enabled);
+        let effective = report.config.clone().map(|mut config| {
+            config.paths = paths.clone();
+            if let Some(dir) = mods_dir.map(str::trim).filter(|d| !d.is_empty()) {
+                config.staging_dir_override = Some(dir.to_string());
+            }
+            config
+        });
+
+        if json {
+            let effective = effective.as_ref().map(|config| {
+                let tools: serde_json::Map<String, serde_json::Value> = ExternalTool::all()
+                    .iter()
+                    .filter_map(|tool| {
+                        let path = config.external_tool_path(*tool)?;
+                        Some((
+                            tool.as_id().to_string(),
+                            serde_json::json!({
+                                "path": path,
+                                "runtime": config.external_tool_runtime_mode(*tool).as_str(),
+                            }),
+                        ))
+                    })
+                    .collect();
+                serde_json::json!({
+                    "downloads_dir": config.downloads_dir().ok(),
+                    "staging_dir": config.staging_dir().ok(),
+                    "active_game": config.active_game,
+                    "active_profile": config.active_profile,
+                    "deployment_method": config.deployment.method.as_str(),
+                    "proton_command": config.external_tools.proton_command,
+                    "proton_runtime": config.external_tools.proton_runtime,
+                    "tools": tools,
+                    "custom_tools": config
+                        .custom_tools
+                        .iter()
+                        .map(|tool| serde_json::json!({
+                            "name": tool.name,
+                            "path": tool.path,
+                            "runtime": tool.runtime_mode.as_str(),
+                        }))
+                        .collect::<Vec<_>>(),
+                })
+            });
+            print_json(&serde_json::json!({
+                "config_file": config_file,
+                "valid": !report.has_errors(),
+                "issues": report.issues,
+                "effective": effective,
+            }))?;
+        } else {
+            println!("Config: {}", config_file.display());
+            println!("{:-<60}", "");
+            if report.issues.is_empty() {
+                println!("No problems found.");
+            }
+            for issue in &report.issues {
+                let label = match issue.severity {
+                    crate::config::Severity::Error => "[FAIL]",
+                    crate::config::Severity::Warning => "[WARN]",
+                };
+                if issue.key.is_empty() {
+                    println!("{} {}", label, issue.message);
+                } else {
+                    println!("{} {}: {}", label, issue.key, issue.message);
+                }
+            }
+
+            if let Some(config) = &effective {
+                let source = |set: bool| if set { " (override)" } else { "" };
+                println!();
+                println!("Effective settings");
+                println!("{:-<60}", "");
+                println!(
+                    "Downloads:    {}{}",
+                    dir_display(&config.downloads_dir()),
+                    source(config.downloads_dir_override.is_some())
+                );
+                println!(
+                    "Staging:      {}{}",
+                    dir_display(&config.staging_dir()),
+                    source(config.staging_dir_override.is_some())
+                );
+                println!(
+                    "Active game:  {}",
+                    config.active_game.as_deref().unwrap_or("(none)")
+                );
+                println!(
+                    "Profile:      {}",
+                    config.active_profile.as_deref().unwrap_or("(none)")
+                );
+                println!("Deployment:   {}", config.deployment.method.as_str());
+                println!(
+                    "Proton:       {}{}",
+                    config.external_tools.proton_command,
+                    config
+                        .external_tools
+                        .proton_runtime
+                        .as_deref()
+                        .map(|r| format!(" (runtime {})", r))
+                        .unwrap_or_default()
+                );
+                for tool in ExternalTool::all() {
+                    if let Some(path) = config.external_tool_path(*tool) {
+                        println!(
+                            "{:<13} {} ({})",
+                            format!("{}:", tool.display_name()),
+                            path,
+                            config.external_tool_runtime_mode(*tool).as_str()
+                        );
+                    }
+                }
+                for tool in &config.custom_tools {
+                    println!(
+                        "{:<13} {} ({})",
+                        format!("{}:", tool.name),
+                        tool.path,
+                        tool.runtime_mode.as_str()
+                    );
+                }
+                if !definitions.is_empty() {
+                    println!(
+                        "Game definitions: {}",
+                        definitions
+                            .iter()
+                            .map(|d| d.id.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+            }
         }
 
+        if report.has_errors() {
+            let errors = report
+                .issues
+                .iter()
+                .filter(|i| i.severity == crate::config::Severity::Error)
+                .count();
+            bail!(Invalid::new(format!(
+                "{} has {} error(s)",
+                config_file.display(),
+                errors
+            )));
+        }
         Ok(())
     }
 
@@ -893,44 +3568,44 @@ impl App {
         let staging = config.staging_dir();
         print_check(
             "Downloads dir",
-            downloads.exists(),
-            downloads.display().to_string(),
+            downloads.as_ref().is_ok_and(|d| d.exists()),
+            dir_display(&downloads),
             &mut ok,
             &mut fail,
         );
         print_check(
             "Staging dir",
-            staging.exists(),
-            staging.display().to_string(),
+            staging.as_ref().is_ok_and(|d| d.exists()),
+            dir_display(&staging),
             &mut ok,
             &mut fail,
         );
-        let downloads_write = dir_is_writable(&downloads);
+        let downloads_write = downloads.as_ref().is_ok_and(|d| dir_is_writable(d));
         print_check_warn(
             "Downloads writable",
             downloads_write,
-            downloads.display().to_string(),
+            dir_display(&downloads),
             &mut ok,
             &mut warn,
         );
         if !downloads_write {
             hints.push(format!(
                 "Set a writable downloads path: modsanity deployment set-downloads-dir <path> (current: {})",
-                downloads.display()
+                dir_display(&downloads)
             ));
         }
-        let staging_write = dir_is_writable(&staging);
+        let staging_write = staging.as_ref().is_ok_and(|d| dir_is_writable(d));
         print_check_warn(
             "Staging writable",
             staging_write,
-            staging.display().to_string(),
+            dir_display(&staging),
             &mut ok,
             &mut warn,
         );
         if !staging_write {
             hints.push(format!(
                 "Set a writable staging path: modsanity deployment set-staging-dir <path> (current: {})",
-                staging.display()
+                dir_display(&staging)
             ));
         }
 
@@ -970,7 +3645,7 @@ impl App {
         }
 
         if let Some(game) = self.active_game().await {
-            let game_exe = game.install_path.join(&game.executable);
+            let game_exe = game.executable_path(&game.executable);
             print_check(
                 "Active game path",
                 game.install_path.exists(),
@@ -991,6 +3666,52 @@ impl App {
                     game_exe.display()
                 ));
             }
+            if let Ok(check) = self.mods.script_extender_check(&game) {
+                if let Some(version) = check.game_version {
+                    print_check_warn(
+                        "Game version",
+                        true,
+                        match crate::games::GameBuild::detect(game.game_type, version) {
+                            Some(build) => format!("{} ({})", version, build.label()),
+                            None => version.to_string(),
+                        },
+                        &mut ok,
+                        &mut warn,
+                    );
+                }
+                if let Some(extender) = &check.extender {
+                    let mismatch = check.mismatch();
+                    let [a, b, c] = extender.target;
+                    let detail = mismatch.clone().unwrap_or_else(|| {
+                        format!(
+                            "{} {}for {}.{}.{} ({})",
+                            game.game_type.script_extender_name(),
+                            extender
+                                .version
+                                .as_ref()
+                                .map(|v| format!("{} ", v))
+                                .unwrap_or_default(),
+                            a,
+                            b,
+                            c,
+                            extender.path.display()
+                        )
+                    });
+                    print_check_warn(
+                        "Script extender",
+                        mismatch.is_none(),
+                        detail,
+                        &mut ok,
+                        &mut warn,
+                    );
+                    if mismatch.is_some() {
+                        hints.push(format!(
+                            "Install the {} build matching your game version, or downgrade the game to match it.",
+                            game.game_type.script_extender_name()
+                        ));
+                    }
+                }
+            }
             if !dir_is_writable(&game.data_path) {
                 print_check_warn(
                     "Data writable",
@@ -1161,8 +3882,24 @@ impl App {
             }
         }
 
+        let active_game = self.active_game().await;
         for tool in ExternalTool::all() {
-            let configured = config.external_tool_path(*tool).map(|s| s.to_string());
+            if active_game
+                .as_ref()
+                .is_some_and(|g| !tool.supports(g.game_type))
+            {
+                continue;
+            }
+            let configured = config
+                .external_tool_path(*tool)
+                .map(|s| s.to_string())
+                .or_else(|| {
+                    let game = active_game.as_ref()?;
+                    let exe = game
+                        .install_path
+                        .join(tool.bundled_executable(game.game_type)?);
+                    exe.exists().then(|| exe.display().to_string())
+                });
             let present = configured
                 .as_deref()
                 .map(|p| std::path::Path::new(p).exists())
@@ -1193,9 +3930,7 @@ impl App {
                     tool.display_name(),
                     tool.as_id()
                 ));
-            } else if self
-                .active_game()
-                .await
+            } else if active_game
                 .as_ref()
                 .and_then(|g| g.proton_prefix.as_ref())
                 .is_none()
@@ -1271,13 +4006,77 @@ impl App {
                         game.steam_app_id
                     ));
                 }
-                if which::which("protontricks").is_ok() {
+            }
+            if let Some(missing) = prefix::missing_dependencies(&game) {
+                for dep in prefix::PREFIX_DEPENDENCIES {
+                    let installed = !missing.contains(&dep);
+                    print_check_warn(
+                        &format!("Prefix {}", dep.verb),
+                        installed,
+                        if installed {
+                            dep.name.to_string()
+                        } else {
+                            format!("{} missing (needed by {})", dep.name, dep.needed_by)
+                        },
+                        &mut ok,
+                        &mut warn,
+                    );
+                }
+                if !missing.is_empty() {
                     hints.push(format!(
-                        "For Synthesis/dotnet restore issues: protontricks {} -q dotnet48 corefonts",
-                        game.steam_app_id
+                        "Install missing prefix dependencies ({}): modsanity tool prefix-setup",
+                        missing
+                            .iter()
+                            .map(|dep| dep.verb)
+                            .collect::<Vec<_>>()
+                            .join(", ")
                     ));
                 }
             }
+            if game.ini_dir().is_some() {
+                match ini::missing_archive_invalidation(&game) {
+                    Ok(Some(missing)) => {
+                        let file = ini::archive_invalidation_settings(game.game_type)
+                            .first()
+                            .map(|s| s.file.clone())
+                            .unwrap_or_default();
+                        let lacking = missing
+                            .iter()
+                            .map(|s| format!("{}={}", s.key, s.value))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        print_check_warn(
+                            "Archive invalidation",
+                            missing.is_empty(),
+                            if missing.is_empty() {
+                                format!("active in {}", file)
+                            } else {
+                                format!("off; {} lacks {}", file, lacking)
+                            },
+                            &mut ok,
+                            &mut warn,
+                        );
+                        if !missing.is_empty() {
+                            hints.push(if config.deployment.archive_invalidation {
+                                "Loose files don't override archives yet; the next deploy turns archive invalidation on.".to_string()
+                            } else {
+                                format!(
+                                    "Loose files don't override archives; set [Archive] {} in {} or enable deployment.archive_invalidation.",
+                                    lacking, file
+                                )
+                            });
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => print_check_warn(
+                        "Archive invalidation",
+                        false,
+                        format!("could not read INI: {}", e),
+                        &mut ok,
+                        &mut warn,
+                    ),
+                }
+            }
         }
         if config.nexus_api_key.is_none() {
             print_check_warn(
@@ -1387,6 +4186,7 @@ impl App {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn cmd_init(
         &mut self,
         interactive: bool,
@@ -1515,7 +4315,9 @@ impl App {
     pub async fn cmd_audit(&self, dry_run: bool) -> Result<()> {
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
         println!("ModSanity Audit");
@@ -1584,6 +4386,69 @@ impl App {
         if conflicts.len() > conflict_limit {
             println!("  ... and {} more", conflicts.len() - conflict_limit);
         }
+        let by_type = crate::mods::get_conflicts_by_asset_type(&self.db, &game.id)?;
+        if !by_type.is_empty() {
+            println!("Conflicts by asset type:");
+        }
+        for group in by_type {
+            let winners: Vec<String> = group
+                .winners
+                .iter()
+                .take(3)
+                .map(|(name, count)| format!("{} {}", name, count))
+                .collect();
+            println!(
+                "  {}: {} files, winners: {}",
+                group.asset_type.display_name(),
+                group.files,
+                winners.join(", ")
+            );
+        }
+
+        let scripts = self.mods.script_overrides(&game)?;
+        let vanilla_overrides = scripts.vanilla_overrides().count();
+        println!(
+            "Script overrides: {} vanilla, {} shared between mods",
+            vanilla_overrides,
+            scripts.mod_conflicts().count()
+        );
+        for entry in scripts.vanilla_overrides().take(conflict_limit) {
+            let winner = entry.winner().map(|w| w.mod_name.as_str()).unwrap_or("?");
+            println!("  - {} ({})", entry.path, winner);
+        }
+        if vanilla_overrides > conflict_limit {
+            println!(
+                "  ... and {} more (see 'modsanity mod scripts')",
+                vanilla_overrides - conflict_limit
+            );
+        }
+
+        let interface = crate::mods::check_interface_compat(&self.db, &game.id)?;
+        println!(
+            "Interface issues: {} incompatible UI mods, {} shared .swf files",
+            interface.incompatible.len(),
+            interface.swf_conflicts.len()
+        );
+        for warning in &interface.incompatible {
+            println!(
+                "  ! {} + {}: {}",
+                warning.mods[0], warning.mods[1], warning.reason
+            );
+        }
+        for swf in interface.swf_conflicts.iter().take(conflict_limit) {
+            println!(
+                "  - {} ({}; winner: {})",
+                swf.path,
+                swf.mods.join(", "),
+                swf.winner()
+            );
+        }
+        if interface.swf_conflicts.len() > conflict_limit {
+            println!(
+                "  ... and {} more",
+                interface.swf_conflicts.len() - conflict_limit
+            );
+        }
 
         if dry_run {
             println!("Audit complete (no changes were made).");
@@ -1595,6 +4460,91 @@ impl App {
 
     // ========== Modlist Commands ==========
 
+    pub async fn cmd_modlist_diff(&self, a: &str, b: &str) -> Result<()> {
+        use crate::import::{diff_modlists, resolve_modlist, ModlistSource};
+
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+        let left = ModlistSource::parse(a);
+        let right = ModlistSource::parse(b);
+        let diff = diff_modlists(
+            &resolve_modlist(&self.mods, &self.db, &game, &left).await?,
+            &resolve_modlist(&self.mods, &self.db, &game, &right).await?,
+        );
+
+        if self.json_output {
+            println!("{}", diff.to_json()?);
+            return Ok(());
+        }
+        if diff.is_empty() {
+            println!(
+                "No differences between {} and {}.",
+                left.label(),
+                right.label()
+            );
+            return Ok(());
+        }
+
+        let version = |v: &Option<String>| v.clone().unwrap_or_else(|| "?".to_string());
+        println!("Modlist diff: {} -> {}", left.label(), right.label());
+        if !diff.added.is_empty() || !diff.removed.is_empty() || !diff.changed.is_empty() {
+            println!();
+            println!("Mods");
+            for m in &diff.added {
+                println!("  + {:<40} {}", m.name, version(&m.version));
+            }
+            for m in &diff.removed {
+                println!("  - {:<40} {}", m.name, version(&m.version));
+            }
+            for c in &diff.changed {
+                let mut what = Vec::new();
+                if c.version_changed() {
+                    what.push(format!(
+                        "{} -> {}",
+                        version(&c.from_version),
+                        version(&c.to_version)
+                    ));
+                }
+                if c.from_enabled != c.to_enabled {
+                    what.push(if c.to_enabled { "enabled" } else { "disabled" }.to_string());
+                }
+                println!("  ~ {:<40} {}", c.name, what.join(", "));
+            }
+        }
+        if !diff.plugins_added.is_empty()
+            || !diff.plugins_removed.is_empty()
+            || !diff.plugins_moved.is_empty()
+        {
+            let pos = |p: Option<usize>| p.map(|p| format!("#{}", p)).unwrap_or_default();
+            println!();
+            println!("Plugins");
+            for p in &diff.plugins_added {
+                println!("  + {:<40} {}", p.plugin, pos(p.to));
+            }
+            for p in &diff.plugins_removed {
+                println!("  - {:<40} {}", p.plugin, pos(p.from));
+            }
+            for p in &diff.plugins_moved {
+                println!("  > {:<40} {} -> {}", p.plugin, pos(p.from), pos(p.to));
+            }
+        }
+        println!();
+        println!(
+            "{} added, {} removed, {} changed mod(s); {} added, {} removed, {} moved plugin(s).",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.changed.len(),
+            diff.plugins_added.len(),
+            diff.plugins_removed.len(),
+            diff.plugins_moved.len()
+        );
+        Ok(())
+    }
+
     pub async fn cmd_modlist_save(&self, path: &str, format: &str) -> Result<()> {
         use crate::import::modlist_format::{
             ModSanityModlist, ModlistEntry, ModlistMeta, PluginOrderEntry,
@@ -1603,7 +4553,9 @@ impl App {
 
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
         let out_path = std::path::Path::new(path);
@@ -1741,7 +4693,10 @@ impl App {
                 println!("  {} plugins", plugin_list.len());
                 println!("Stored in database as: {}", modlist_name);
             }
-            _ => bail!("Unknown format '{}'. Use 'native' or 'mo2'.", format),
+            _ => bail!(Invalid::new(format!(
+                "Unknown format '{}'. Use 'native' or 'mo2'.",
+                format
+            ))),
         }
 
         Ok(())
@@ -1765,7 +4720,8 @@ impl App {
             }
             ModlistFormat::Mo2 => {
                 println!("Detected MO2 format, delegating to import command...");
-                self.cmd_import_modlist(path, auto_approve, preview).await
+                self.cmd_import_modlist(path, auto_approve, preview, None)
+                    .await
             }
         }
     }
@@ -1782,18 +4738,19 @@ impl App {
 
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
         let modlist = modlist_format::load_native(std::path::Path::new(path))?;
 
         // Validate game matches
         if modlist.meta.game_id != game.id {
-            bail!(
+            bail!(Invalid::new(format!(
                 "Modlist is for game '{}' but active game is '{}'. Select the correct game first.",
-                modlist.meta.game_id,
-                game.id
-            );
+                modlist.meta.game_id, game.id
+            )));
         }
 
         println!("Loading native modlist from: {}", path);
@@ -1892,6 +4849,8 @@ impl App {
                 nexus_mod_id,
                 selected_file_id: entry.nexus_file_id,
                 auto_install: true,
+                priority: false,
+                replaces_mod: None,
                 match_confidence: Some(1.0),
                 alternatives: Vec::new(),
                 status: crate::queue::QueueStatus::Matched,
@@ -1928,32 +4887,36 @@ impl App {
         path: &str,
         auto_approve: bool,
         preview: bool,
+        report: Option<&str>,
     ) -> Result<()> {
-        use crate::import::ModlistImporter;
+        use crate::import::{MatchReport, ModlistImporter};
         use crate::queue::QueueManager;
         use std::path::Path;
 
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
         let nexus = match &self.nexus {
             Some(client) => client.clone(),
-            None => bail!("NexusMods API key not configured. Set NEXUS_API_KEY environment variable or add to config."),
+            None => bail!(Invalid::new("NexusMods API key not configured. Set NEXUS_API_KEY environment variable or add to config.")),
         };
 
         println!("Importing modlist from: {}", path);
         println!("Game: {} ({})", game.name, game.id);
 
         let importer =
-            ModlistImporter::with_catalog(&game.id, (*nexus).clone(), Some(self.db.clone()));
+            ModlistImporter::with_catalog(&game.id, (*nexus).clone(), Some(self.db.clone()))
+                .with_extensions((*self.extensions).clone(), self.extension_context().await);
         let started = std::time::Instant::now();
         let result = importer
             .import_modlist_with_progress(
                 Path::new(path),
                 Some(|current: usize, total: usize, plugin: &str| {
-                    if current == 1 || current % 25 == 0 || current == total {
+                    if current == 1 || current.is_multiple_of(25) || current == total {
                         println!("Matching {:>4}/{}: {}", current, total.max(current), plugin);
                     }
                 }),
@@ -1971,6 +4934,19 @@ impl App {
         println!("Needs review:  {}", result.needs_review().count());
         println!("No matches:    {}", result.no_matches().count());
 
+        if let Some(report_path) = report {
+            let report = MatchReport::new(&result, path, &game.id, &game.nexus_game_domain());
+            let format = report.write(Path::new(report_path))?;
+            println!(
+                "Match report ({}) written to {}",
+                match format {
+                    crate::import::ReportFormat::Csv => "CSV",
+                    crate::import::ReportFormat::Json => "JSON",
+                },
+                report_path
+            );
+        }
+
         if preview {
             println!("\nPreview mode: no database or queue writes");
             let queueable = result
@@ -2086,6 +5062,8 @@ impl App {
                 nexus_mod_id,
                 selected_file_id: None,
                 auto_install: true,
+                priority: false,
+                replaces_mod: None,
                 match_confidence: Some(match_result.confidence.score()),
                 alternatives,
                 status,
@@ -2130,11 +5108,15 @@ impl App {
                 let game_filter = active_game.as_ref().map(|g| g.id.as_str());
                 let batches = queue_manager.list_batches(game_filter)?;
                 if let Some(latest) = batches.first() {
-                    println!(
-                        "No batch ID provided. Showing latest batch: {}",
-                        latest.batch_id
-                    );
+                    if !self.json_output {
+                        println!(
+                            "No batch ID provided. Showing latest batch: {}",
+                            latest.batch_id
+                        );
+                    }
                     latest.batch_id.clone()
+                } else if self.json_output {
+                    return print_json(&serde_json::json!({ "active": false }));
                 } else {
                     println!("No import batches found.");
                     return Ok(());
@@ -2144,6 +5126,28 @@ impl App {
 
         let entries = queue_manager.get_batch(&batch)?;
 
+        if self.json_output {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|entry| {
+                    serde_json::json!({
+                        "plugin": entry.plugin_name,
+                        "mod": entry.mod_name,
+                        "nexus_mod_id": entry.nexus_mod_id,
+                        "status": entry.status.to_string(),
+                        "confidence": entry.match_confidence,
+                        "alternatives": entry.alternatives.len(),
+                        "error": entry.error,
+                    })
+                })
+                .collect();
+            return print_json(&serde_json::json!({
+                "active": true,
+                "batch_id": batch,
+                "entries": entries,
+            }));
+        }
+
         if entries.is_empty() {
             println!("No entries found for batch: {}", batch);
             return Ok(());
@@ -2167,11 +5171,8 @@ impl App {
                 status_icon, entry.plugin_name, entry.mod_name
             );
 
-            if entry.match_confidence.is_some() {
-                println!(
-                    "   Confidence: {:.1}%",
-                    entry.match_confidence.unwrap() * 100.0
-                );
+            if let Some(confidence) = entry.match_confidence {
+                println!("   Confidence: {:.1}%", confidence * 100.0);
             }
 
             if !entry.alternatives.is_empty() {
@@ -2186,6 +5187,93 @@ impl App {
         Ok(())
     }
 
+    pub async fn cmd_import_instance(
+        &self,
+        path: &str,
+        mode: crate::mods::AdoptMode,
+        dry_run: bool,
+    ) -> Result<()> {
+        use crate::import::{import_mo2_instance, Mo2Instance};
+
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+
+        let instance = Mo2Instance::open(std::path::Path::new(path))?;
+        println!("Importing MO2 instance: {}", instance.root.display());
+        println!("  Mods:     {}", instance.mods_dir.display());
+        println!("  Profiles: {}", instance.profiles_dir.display());
+
+        let report = import_mo2_instance(
+            &self.mods,
+            &self.profiles,
+            &game.id,
+            &instance,
+            mode,
+            dry_run,
+            |stage, current, total, name| {
+                println!("  [{}/{}] {}: {}", current, total, stage, name);
+            },
+        )
+        .await?;
+
+        println!();
+        for line in report.lines() {
+            println!("{}", line);
+        }
+        if dry_run {
+            println!("Dry run: nothing was changed.");
+        } else {
+            println!("Switch to an imported profile with 'modsanity profile switch <name>', then run 'modsanity deploy'.");
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_import_watch(&self, yes: bool, fomod_defaults: bool) -> Result<()> {
+        use std::io::{self, Write};
+
+        if self.active_game().await.is_none() {
+            bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            ));
+        }
+        let dir = self.config.read().await.downloads_dir()?;
+        let mut watcher = crate::import::DownloadWatcher::new(&dir)?;
+        println!(
+            "Watching {} for new mod archives (Ctrl+C to stop)",
+            dir.display()
+        );
+
+        while let Some(path) = watcher.next_archive().await? {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !yes {
+                print!("New download: {}. Install? [Y/n]: ", name);
+                io::stdout().flush()?;
+                let mut answer = String::new();
+                io::stdin().read_line(&mut answer)?;
+                if answer.trim().to_ascii_lowercase().starts_with('n') {
+                    println!("Skipped {}", name);
+                    continue;
+                }
+            }
+            // One bad archive shouldn't end the watch
+            if let Err(e) = self
+                .cmd_mod_install(&path.to_string_lossy(), None, fomod_defaults)
+                .await
+            {
+                println!("Failed to install {}: {:#}", name, e);
+            }
+        }
+        println!("Stopped watching: {} is no longer available", dir.display());
+        Ok(())
+    }
+
     pub async fn cmd_import_apply_enabled(&self, path: &str, preview: bool) -> Result<()> {
         use crate::import::ModlistParser;
         use std::collections::{HashMap, HashSet};
@@ -2193,7 +5281,9 @@ impl App {
 
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
         println!("Applying MO2 enabled-state bridge from: {}", path);
@@ -2279,6 +5369,33 @@ impl App {
         let game_id = active_game.as_ref().map(|g| g.id.as_str());
         let batches = queue_manager.list_batches(game_id)?;
 
+        if self.json_output {
+            let mut list = Vec::new();
+            for batch in &batches {
+                let state = match queue_manager.batch_control(&batch.batch_id)? {
+                    crate::queue::BatchControl::Paused => "paused",
+                    crate::queue::BatchControl::Cancelled => "cancel_requested",
+                    crate::queue::BatchControl::Running => "running",
+                };
+                list.push(serde_json::json!({
+                    "batch_id": batch.batch_id,
+                    "game_id": batch.game_id,
+                    "state": state,
+                    "total": batch.total,
+                    "pending": batch.pending,
+                    "matched": batch.matched,
+                    "needs_review": batch.needs_review,
+                    "needs_manual": batch.needs_manual,
+                    "downloading": batch.downloading,
+                    "installing": batch.installing,
+                    "completed": batch.completed,
+                    "failed": batch.failed,
+                    "created_at": batch.created_at,
+                }));
+            }
+            return print_json(&serde_json::json!(list));
+        }
+
         if batches.is_empty() {
             if let Some(game) = active_game {
                 println!("No queue batches found for {}.", game.name);
@@ -2291,9 +5408,15 @@ impl App {
         println!("Queue Batches:");
         println!("{:-<100}", "");
         for batch in batches {
+            let state = match queue_manager.batch_control(&batch.batch_id)? {
+                crate::queue::BatchControl::Paused => " (paused)",
+                crate::queue::BatchControl::Cancelled => " (cancel requested)",
+                crate::queue::BatchControl::Running => "",
+            };
             println!(
-                "Batch: {}\n  Game: {}\n  Total: {} | Pending: {} | Matched: {} | Review: {} | Manual: {}\n  Active: {} downloading, {} installing | Done: {} completed, {} failed\n  Created: {}",
+                "Batch: {}{}\n  Game: {}\n  Total: {} | Pending: {} | Matched: {} | Review: {} | Manual: {}\n  Active: {} downloading, {} installing | Done: {} completed, {} failed\n  Created: {}",
                 batch.batch_id,
+                state,
                 batch.game_id,
                 batch.total,
                 batch.pending,
@@ -2317,30 +5440,16 @@ impl App {
         batch_id: Option<&str>,
         download_only: bool,
     ) -> Result<()> {
-        use crate::queue::{QueueManager, QueueProcessor};
+        use crate::queue::{BatchControl, QueueManager};
 
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
-        };
-
-        let nexus = match &self.nexus {
-            Some(client) => client.clone(),
-            None => bail!("NexusMods API key not configured."),
-        };
-
-        let config = self.config.read().await;
-        let download_dir = config.downloads_dir();
-
-        let game_domain = game.nexus_game_domain();
-        let processor = QueueProcessor::new(
-            self.db.clone(),
-            (*nexus).clone(),
-            game_domain,
-            game.id.clone(),
-            download_dir,
-            self.mods.clone(),
-        );
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+
+        let processor = self.queue_processor(&game).await?;
 
         let batches: Vec<String> = match batch_id {
             Some(id) => vec![id.to_string()],
@@ -2360,21 +5469,107 @@ impl App {
             println!("Download-only mode enabled");
         }
 
+        let queue_manager = QueueManager::new(self.db.clone());
+        let mut processed = 0usize;
         for batch in &batches {
+            if queue_manager.batch_control(batch)? == BatchControl::Paused {
+                println!(
+                    "Skipping paused batch {} (run 'modsanity queue resume {}' first)",
+                    batch, batch
+                );
+                continue;
+            }
             println!("Processing batch: {}", batch);
-            processor.process_batch(batch, download_only).await?;
+            let (stop, stopped) = tokio::sync::oneshot::channel();
+            let reporter = Self::spawn_queue_reporter(self.db.clone(), batch.clone(), stopped);
+            let result = processor.process_batch(batch, download_only).await;
+            let _ = stop.send(());
+            let _ = reporter.await;
+            result?;
+            let progress = queue_manager.batch_progress(batch)?;
+            println!(
+                "Batch {}: {}",
+                batch,
+                crate::queue::QueueRate {
+                    progress,
+                    ..Default::default()
+                }
+                .summary()
+            );
+            processed += 1;
         }
 
-        println!("Processed {} batch(es).", batches.len());
+        println!("Processed {} batch(es).", processed);
         Ok(())
     }
 
+    /// Queue processor downloading into the configured downloads directory
+    pub(crate) async fn queue_processor(
+        &self,
+        game: &crate::games::Game,
+    ) -> Result<crate::queue::QueueProcessor> {
+        let nexus = match &self.nexus {
+            Some(client) => client.clone(),
+            None => bail!(Invalid::new("NexusMods API key not configured.")),
+        };
+        let download_dir = self.config.read().await.downloads_dir()?;
+        Ok(crate::queue::QueueProcessor::new(
+            self.db.clone(),
+            (*nexus).clone(),
+            game.nexus_game_domain(),
+            game.id.clone(),
+            download_dir,
+            self.mods.clone(),
+        )
+        .with_game_build(game.build()))
+    }
+
+    /// Show a batch's progress, speed, ETA and current download until `stop`
+    /// fires: redrawn every second on a terminal, printed every five seconds
+    /// otherwise
+    fn spawn_queue_reporter(
+        db: std::sync::Arc<crate::db::Database>,
+        batch_id: String,
+        mut stop: tokio::sync::oneshot::Receiver<()>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let interval = if io::stdout().is_terminal() {
+                Duration::from_secs(1)
+            } else {
+                Duration::from_secs(5)
+            };
+            let mut status = CliStatusReporter::new(interval);
+            let mut throughput = crate::queue::Throughput::new();
+            let mut ticker = tokio::time::interval(Duration::from_secs(1));
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = &mut stop => break,
+                }
+                // Sampled every second for the speed
+                let Ok(entries) = db.get_queue_entries(&batch_id) else {
+                    continue;
+                };
+                let progress = crate::queue::BatchProgress::from_entries(&entries);
+                let rate = throughput.record(Instant::now(), progress);
+                let downloading = entries.iter().find(|e| {
+                    crate::queue::QueueStatus::from_str(&e.status)
+                        == crate::queue::QueueStatus::Downloading
+                });
+                let _ = status.emit_queue_progress(&rate, downloading);
+            }
+            let _ = status.finish();
+        })
+    }
+
     pub async fn cmd_queue_retry(&self) -> Result<()> {
         use crate::queue::QueueManager;
 
         let game = match self.active_game().await {
             Some(g) => g,
-            None => bail!("No game selected. Use 'modsanity game select <name>' first."),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
         };
 
         let queue_manager = QueueManager::new(self.db.clone());
@@ -2433,6 +5628,185 @@ impl App {
         Ok(())
     }
 
+    pub async fn cmd_queue_export(
+        &self,
+        path: &str,
+        batch_id: Option<&str>,
+        pending_only: bool,
+    ) -> Result<()> {
+        use crate::queue::QueueManager;
+
+        let queue_manager = QueueManager::new(self.db.clone());
+        let batch_id = match batch_id {
+            Some(id) => id.to_string(),
+            None => {
+                let active_game = self.active_game().await;
+                let game_id = active_game.as_ref().map(|g| g.id.as_str());
+                match queue_manager.list_batches(game_id)?.into_iter().next() {
+                    Some(batch) => batch.batch_id,
+                    None => bail!("No queue batches to export."),
+                }
+            }
+        };
+
+        let count =
+            queue_manager.export_batch(&batch_id, std::path::Path::new(path), pending_only)?;
+        println!(
+            "Exported {} entries of batch {} to {}",
+            count, batch_id, path
+        );
+        Ok(())
+    }
+
+    pub async fn cmd_queue_import(&self, path: &str) -> Result<()> {
+        use crate::queue::QueueManager;
+
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+
+        let queue_manager = QueueManager::new(self.db.clone());
+        let (batch_id, count) = queue_manager.import_batch(std::path::Path::new(path), &game.id)?;
+        println!("Imported {} entries as batch {}", count, batch_id);
+        println!(
+            "Run 'modsanity queue process --batch-id {}' to download them.",
+            batch_id
+        );
+        Ok(())
+    }
+
+    /// The batch a queue command targets: `batch_id`, or the most recent one
+    async fn queue_target_batch(&self, batch_id: Option<&str>) -> Result<String> {
+        if let Some(id) = batch_id {
+            return Ok(id.to_string());
+        }
+        let active_game = self.active_game().await;
+        let game_id = active_game.as_ref().map(|g| g.id.as_str());
+        match crate::queue::QueueManager::new(self.db.clone())
+            .list_batches(game_id)?
+            .into_iter()
+            .next()
+        {
+            Some(batch) => Ok(batch.batch_id),
+            None => bail!("No queue batches found."),
+        }
+    }
+
+    /// Find a batch entry by mod name, or by entry ID
+    fn find_queue_entry(
+        entries: &[crate::queue::QueueEntry],
+        entry: &str,
+    ) -> Result<crate::queue::QueueEntry> {
+        entries
+            .iter()
+            .find(|e| e.mod_name.eq_ignore_ascii_case(entry))
+            .or_else(|| {
+                let id: i64 = entry.parse().ok()?;
+                entries.iter().find(|e| e.id == id)
+            })
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No entry '{}' in the batch", entry))
+    }
+
+    fn print_queue_order(entries: &[crate::queue::QueueEntry]) {
+        for (i, e) in entries.iter().enumerate() {
+            let priority = if e.priority { " (priority)" } else { "" };
+            println!("  {:>3}. [#{}] {}{}", i + 1, e.id, e.mod_name, priority);
+        }
+    }
+
+    pub async fn cmd_queue_move(
+        &self,
+        entry: &str,
+        direction: &str,
+        batch_id: Option<&str>,
+    ) -> Result<()> {
+        use crate::queue::{QueueManager, QueueMove};
+
+        let to = QueueMove::from_cli(direction)?;
+        let batch_id = self.queue_target_batch(batch_id).await?;
+        let queue_manager = QueueManager::new(self.db.clone());
+        let target = Self::find_queue_entry(&queue_manager.get_batch(&batch_id)?, entry)?;
+
+        if !queue_manager.move_entry(&batch_id, target.id, to)? {
+            println!("'{}' is already there.", target.mod_name);
+            return Ok(());
+        }
+        println!(
+            "Moved '{}' {}:",
+            target.mod_name,
+            direction.to_ascii_lowercase()
+        );
+        Self::print_queue_order(&queue_manager.get_batch(&batch_id)?);
+        Ok(())
+    }
+
+    pub async fn cmd_queue_priority(
+        &self,
+        entry: &str,
+        priority: bool,
+        batch_id: Option<&str>,
+    ) -> Result<()> {
+        use crate::queue::QueueManager;
+
+        let batch_id = self.queue_target_batch(batch_id).await?;
+        let queue_manager = QueueManager::new(self.db.clone());
+        let target = Self::find_queue_entry(&queue_manager.get_batch(&batch_id)?, entry)?;
+
+        queue_manager.set_priority(target.id, priority)?;
+        if priority {
+            println!("'{}' will be processed first:", target.mod_name);
+        } else {
+            println!("'{}' is no longer prioritized:", target.mod_name);
+        }
+        Self::print_queue_order(&queue_manager.get_batch(&batch_id)?);
+        Ok(())
+    }
+
+    pub async fn cmd_queue_control(
+        &self,
+        batch_id: Option<&str>,
+        control: crate::queue::BatchControl,
+    ) -> Result<()> {
+        use crate::queue::{BatchControl, QueueManager};
+
+        let queue_manager = QueueManager::new(self.db.clone());
+        let batches: Vec<String> = match batch_id {
+            Some(id) => vec![id.to_string()],
+            None => {
+                let active_game = self.active_game().await;
+                let game_id = active_game.as_ref().map(|g| g.id.as_str());
+                queue_manager
+                    .list_batches(game_id)?
+                    .into_iter()
+                    .map(|b| b.batch_id)
+                    .collect()
+            }
+        };
+
+        if batches.is_empty() {
+            println!("No queue batches found.");
+            return Ok(());
+        }
+
+        for batch in &batches {
+            queue_manager.set_batch_control(batch, control)?;
+        }
+        let verb = match control {
+            BatchControl::Running => "Resumed",
+            BatchControl::Paused => "Paused",
+            BatchControl::Cancelled => "Cancelled",
+        };
+        println!("{} {} batch(es).", verb, batches.len());
+        if control == BatchControl::Cancelled {
+            println!("Running downloads and installs stop at their next checkpoint.");
+        }
+        Ok(())
+    }
+
     // ========== Nexus Catalog Commands ==========
 
     pub async fn cmd_nexus_populate(
@@ -2445,14 +5819,17 @@ impl App {
         use crate::nexus::{CatalogPopulator, NexusRestClient, PopulateOptions};
 
         // Get API key
-        let api_key = match &self.config.read().await.nexus_api_key {
-            Some(key) => key.clone(),
-            None => bail!("NexusMods API key not configured. Set NEXUS_API_KEY environment variable or add to config."),
+        let (api_key, base_url) = {
+            let config = self.config.read().await;
+            match &config.nexus_api_key {
+                Some(key) => (key.clone(), config.nexus_base_url().to_string()),
+                None => bail!(Invalid::new("NexusMods API key not configured. Set NEXUS_API_KEY environment variable or add to config.")),
+            }
         };
 
         // Create REST client
-        let rest_client =
-            NexusRestClient::new(&api_key).context("Failed to create REST API client")?;
+        let rest_client = NexusRestClient::with_base_url(&api_key, &base_url)
+            .context("Failed to create REST API client")?;
 
         // Create populator
         let populator =
@@ -2486,9 +5863,11 @@ impl App {
         // Run population with terminal status feedback.
         let reporter = std::sync::Mutex::new(CliStatusReporter::new(Duration::from_millis(300)));
         let progress_callback =
-            |pages: i32, inserted: i64, updated: i64, total: i64, _offset: i32| {
+            |pages: i32, inserted: i64, updated: i64, total: i64, offset: i32| {
                 if let Ok(mut guard) = reporter.lock() {
-                    let _ = guard.emit_catalog_progress(pages, inserted, updated, total);
+                    let _ = guard.emit_catalog_progress(&CatalogProgress::new(
+                        pages, inserted, updated, total, offset,
+                    ));
                 }
             };
 
@@ -2516,7 +5895,21 @@ impl App {
             .chars()
             .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
         {
-            bail!("Invalid game domain: must contain only lowercase letters, numbers, hyphens, and underscores");
+            bail!(Invalid::new("Invalid game domain: must contain only lowercase letters, numbers, hyphens, and underscores"));
+        }
+
+        // Get sync state
+        let state = self.db.get_sync_state(game_domain)?;
+
+        if self.json_output {
+            return print_json(&serde_json::json!({
+                "game_domain": game_domain,
+                "completed": state.completed,
+                "current_page": state.current_page,
+                "last_sync": state.last_sync,
+                "last_error": state.last_error,
+                "total_mods": self.db.count_catalog_mods(game_domain)?,
+            }));
         }
 
         println!("Nexus Catalog Status");
@@ -2524,9 +5917,6 @@ impl App {
         println!("Game domain: {}", game_domain);
         println!();
 
-        // Get sync state
-        let state = self.db.get_sync_state(game_domain)?;
-
         if state.completed {
             println!("Status:      ✓ Completed");
         } else {
@@ -2562,4 +5952,341 @@ impl App {
 
         Ok(())
     }
+    pub async fn cmd_search(
+        &self,
+        query: &str,
+        source: &str,
+        sort: &str,
+        limit: i32,
+    ) -> Result<()> {
+        use crate::nexus::{ModSearchParams, SortBy};
+
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+        let domain = game.nexus_game_domain();
+        let sort_by = match sort.to_ascii_lowercase().as_str() {
+            "relevance" => SortBy::Relevance,
+            "downloads" => SortBy::Downloads,
+            "endorsements" => SortBy::Endorsements,
+            "updated" => SortBy::Updated,
+            other => bail!(Invalid::new(format!(
+                "Unknown sort '{}'. Use: relevance, downloads, endorsements, updated",
+                other
+            ))),
+        };
+
+        // (mod id, name, author, downloads, endorsements)
+        let rows: Vec<(i64, String, String, Option<i64>, Option<i64>)> = match source
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "catalog" => {
+                if matches!(sort_by, SortBy::Downloads | SortBy::Endorsements) {
+                    bail!(Invalid::new(format!(
+                            "The local catalog has no download or endorsement counts; use --source nexus to sort by {}",
+                            sort
+                        )));
+                }
+                let mut records = self.db.search_catalog(&domain, query, limit)?;
+                if matches!(sort_by, SortBy::Updated) {
+                    records.sort_by_key(|r| std::cmp::Reverse(r.updated_time));
+                }
+                if records.is_empty() && self.db.count_catalog_mods(&domain)? == 0 {
+                    bail!(Invalid::new(format!(
+                            "The local catalog for {} is empty. Run 'modsanity nexus populate --game {}' or use --source nexus.",
+                            domain,
+                            domain
+                        )));
+                }
+                records
+                    .into_iter()
+                    .map(|r| (r.mod_id, r.name, r.author.unwrap_or_default(), None, None))
+                    .collect()
+            }
+            "nexus" => {
+                let nexus = match &self.nexus {
+                    Some(client) => client.clone(),
+                    None => bail!(Invalid::new("NexusMods API key not configured.")),
+                };
+                nexus
+                    .search_mods(ModSearchParams {
+                        game_domain: Some(domain.clone()),
+                        query: Some(query.to_string()),
+                        sort_by,
+                        limit: Some(limit),
+                        ..Default::default()
+                    })
+                    .await?
+                    .results
+                    .into_iter()
+                    .map(|r| {
+                        (
+                            r.mod_id,
+                            r.name,
+                            r.author,
+                            Some(r.downloads),
+                            Some(r.endorsements),
+                        )
+                    })
+                    .collect()
+            }
+            other => bail!(Invalid::new(format!(
+                "Unknown source '{}'. Use: catalog, nexus",
+                other
+            ))),
+        };
+
+        if self.json_output {
+            let results: Vec<_> = rows
+                .iter()
+                .map(|(mod_id, name, author, downloads, endorsements)| {
+                    serde_json::json!({
+                        "mod_id": mod_id,
+                        "name": name,
+                        "author": author,
+                        "downloads": downloads,
+                        "endorsements": endorsements,
+                        "url": format!("https://www.nexusmods.com/{}/mods/{}", domain, mod_id),
+                    })
+                })
+                .collect();
+            return print_json(&serde_json::json!(results));
+        }
+        if rows.is_empty() {
+            println!("No mods found for '{}'.", query);
+            return Ok(());
+        }
+
+        let count = |n: &Option<i64>| n.map(|n| n.to_string()).unwrap_or_default();
+        println!(
+            "{:>8}  {:<40} {:<20} {:>10} {:>8}",
+            "ID", "Name", "Author", "Downloads", "Endorse"
+        );
+        println!("{:-<92}", "");
+        for (mod_id, name, author, downloads, endorsements) in &rows {
+            println!(
+                "{:>8}  {:<40} {:<20} {:>10} {:>8}",
+                mod_id,
+                truncate(name, 40),
+                truncate(author, 20),
+                count(downloads),
+                count(endorsements)
+            );
+        }
+        println!();
+        println!("Download one with 'modsanity nexus download <ID> [--install]'.");
+        Ok(())
+    }
+
+    pub async fn cmd_nexus_download(
+        &self,
+        mod_id: i64,
+        file_id: Option<i64>,
+        install: bool,
+    ) -> Result<()> {
+        use crate::queue::{QueueManager, QueueProcessor, QueueStatus};
+
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+        let nexus = match &self.nexus {
+            Some(client) => client.clone(),
+            None => bail!(Invalid::new("NexusMods API key not configured.")),
+        };
+        let domain = game.nexus_game_domain();
+        let Some(game_id) = crate::nexus::nexus_game_id(&domain) else {
+            bail!(Invalid::new(format!(
+                "Unsupported game domain for file lookup: {}",
+                domain
+            )));
+        };
+
+        let name = nexus
+            .get_mod_name_by_id(&domain, mod_id)
+            .await?
+            .with_context(|| format!("Mod {} not found on Nexus for {}", mod_id, game.name))?;
+        let files = nexus.get_mod_files(game_id, mod_id).await?;
+        let build = game.build();
+        // The file that gets downloaded when none is picked
+        let recommended =
+            crate::queue::processor::pick_main_file(&files, None, build).map(|f| f.file_id);
+        println!("{} (mod {})", name, mod_id);
+        if let Some(build) = build {
+            println!("Game build: {}", build.label());
+        }
+        println!("{:-<60}", "");
+        for file in &files {
+            let marker = match file_id {
+                Some(id) if id == file.file_id => "*",
+                None if recommended == Some(file.file_id) => "*",
+                _ => " ",
+            };
+            println!(
+                "{} {:>8}  {:<12} {} v{} ({})",
+                marker,
+                file.file_id,
+                file.category,
+                file.name,
+                file.version,
+                crate::mods::format_bytes(file.size_bytes.max(0) as u64)
+            );
+        }
+        if let Some(file_id) = file_id {
+            match files.iter().find(|f| f.file_id == file_id) {
+                Some(file) if !file.is_available() => bail!(Invalid::new(format!(
+                    "File {} is {} on Nexus and can't be downloaded",
+                    file_id,
+                    file.category.to_lowercase()
+                ))),
+                Some(_) => {}
+                None => bail!("Mod {} has no file {}", mod_id, file_id),
+            }
+        }
+        println!();
+
+        let queue_manager = QueueManager::new(self.db.clone());
+        let batch_id = queue_manager.queue_mod(&game.id, mod_id, &name)?;
+        if let Some(entry) = queue_manager.get_batch(&batch_id)?.first() {
+            queue_manager.select_file(entry.id, file_id)?;
+        }
+
+        let download_dir = self.config.read().await.downloads_dir()?;
+        println!(
+            "{} {}...",
+            if install {
+                "Downloading and installing"
+            } else {
+                "Downloading"
+            },
+            name
+        );
+        let processor = QueueProcessor::new(
+            self.db.clone(),
+            (*nexus).clone(),
+            domain,
+            game.id.clone(),
+            download_dir.clone(),
+            self.mods.clone(),
+        )
+        .with_game_build(game.build());
+        processor.process_batch(&batch_id, !install).await?;
+
+        let Some(entry) = queue_manager.get_batch(&batch_id)?.into_iter().next() else {
+            bail!("Queue batch {} disappeared while processing", batch_id);
+        };
+        match entry.status {
+            QueueStatus::Completed if install => {
+                println!("Installed {}.", name);
+                println!("Run 'modsanity deploy' to apply changes.");
+            }
+            QueueStatus::Completed => {
+                println!("Downloaded {} to {}.", name, download_dir.display());
+                println!(
+                    "Run again with --install, or 'modsanity mod install <archive>' to install it."
+                );
+            }
+            status => bail!(
+                "{}: {}",
+                name,
+                entry.error.unwrap_or_else(|| status.to_string())
+            ),
+        }
+        Ok(())
+    }
+
+    pub async fn cmd_nexus_surprise(
+        &self,
+        categories: Vec<String>,
+        min_endorsements: Option<i64>,
+        queue: bool,
+    ) -> Result<()> {
+        let game = match self.active_game().await {
+            Some(g) => g,
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        };
+        let nexus = match &self.nexus {
+            Some(client) => client.clone(),
+            None => bail!(Invalid::new("NexusMods API key not configured.")),
+        };
+
+        let options = self
+            .surprise_options(&game.id, categories, min_endorsements)
+            .await?;
+        let Some(pick) = nexus
+            .surprise_mod(&game.nexus_game_domain(), &options)
+            .await?
+        else {
+            println!("No matching mods found. Try other categories or a lower --min-endorsements.");
+            return Ok(());
+        };
+
+        println!("{}", pick.name);
+        println!("{:-<60}", "");
+        println!("Author:       {}", pick.author);
+        println!("Category:     {}", pick.category);
+        println!("Version:      {}", pick.version);
+        println!("Endorsements: {}", pick.endorsements);
+        println!("Downloads:    {}", pick.downloads);
+        println!(
+            "Page:         https://www.nexusmods.com/{}/mods/{}",
+            game.nexus_game_domain(),
+            pick.mod_id
+        );
+        if !pick.summary.is_empty() {
+            println!();
+            println!("{}", pick.summary);
+        }
+        println!();
+
+        if queue {
+            let queue_manager = crate::queue::QueueManager::new(self.db.clone());
+            let batch_id = queue_manager.queue_mod(&game.id, pick.mod_id, &pick.name)?;
+            println!(
+                "Queued. Use 'modsanity queue process --batch-id {}' to download and install",
+                batch_id
+            );
+        } else {
+            println!("Run again with --queue to add it to the download queue.");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0), format!("[{}]", "-".repeat(24)));
+        assert_eq!(
+            progress_bar(50),
+            format!("[{}{}]", "#".repeat(12), "-".repeat(12))
+        );
+        assert_eq!(progress_bar(250), format!("[{}]", "#".repeat(24)));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("SkyUI", 5), "SkyUI");
+        assert_eq!(truncate("Unofficial Patch", 8), "Unoffic…");
+        assert_eq!(truncate("Ærø Ünïcode", 4), "Ærø…");
+    }
+
+    #[test]
+    fn test_catalog_progress_percent() {
+        assert_eq!(CatalogProgress::new(1, 20, 0, 0, 20).percent(), None);
+        assert_eq!(CatalogProgress::new(2, 30, 10, 200, 40).percent(), Some(20));
+        assert_eq!(CatalogProgress::new(9, 0, 0, 100, 140).percent(), Some(100));
+    }
 }
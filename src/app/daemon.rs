@@ -0,0 +1,275 @@
+//! `modsanity daemon`: serve the control socket from a long-lived instance
+//!
+//! The daemon process holds the instance lock for as long as it runs, so
+//! queue processing and deployment requested over the socket never race
+//! another ModSanity process. Queue batches are processed in the background;
+//! `status` reports which ones are still running.
+
+use super::App;
+use crate::daemon::{Request, RpcError, METHOD_NOT_FOUND};
+use crate::error::{Conflict, Invalid};
+use crate::queue::{BatchControl, QueueEntry, QueueManager, QueueStatus};
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+struct Daemon {
+    app: App,
+    /// Batches being processed in the background
+    processing: Mutex<BTreeSet<String>>,
+    shutdown: Notify,
+}
+
+#[derive(Deserialize)]
+struct QueueAddParams {
+    mod_id: i64,
+    /// Nexus file to install instead of the main file
+    file_id: Option<i64>,
+    name: Option<String>,
+    /// Append to this batch instead of starting a new one
+    batch_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct QueueProcessParams {
+    batch_id: Option<String>,
+    #[serde(default)]
+    download_only: bool,
+}
+
+impl App {
+    /// Serve the control socket until `shutdown` is called or Ctrl+C
+    pub async fn cmd_daemon(self, socket: Option<&str>) -> Result<()> {
+        let socket = match socket {
+            Some(path) => PathBuf::from(path),
+            None => self.config.read().await.paths.daemon_socket(),
+        };
+        let listener = crate::daemon::bind(&socket).await?;
+        println!("Listening on {}", socket.display());
+
+        let daemon = Arc::new(Daemon {
+            app: self,
+            processing: Mutex::new(BTreeSet::new()),
+            shutdown: Notify::new(),
+        });
+        let handler = {
+            let daemon = Arc::clone(&daemon);
+            move |request: Request| {
+                let daemon = Arc::clone(&daemon);
+                async move { daemon.handle(request).await }
+            }
+        };
+        let stop = async {
+            tokio::select! {
+                _ = daemon.shutdown.notified() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        };
+        crate::daemon::serve(listener, handler, stop).await;
+
+        let _ = std::fs::remove_file(&socket);
+        println!("Daemon stopped.");
+        Ok(())
+    }
+
+    /// Send one request to a running daemon and print the result
+    pub async fn cmd_daemon_call(
+        &self,
+        socket: Option<&str>,
+        method: &str,
+        params: Option<&str>,
+    ) -> Result<()> {
+        let socket = match socket {
+            Some(path) => PathBuf::from(path),
+            None => self.config.read().await.paths.daemon_socket(),
+        };
+        let params = match params {
+            Some(raw) => serde_json::from_str(raw)
+                .map_err(|e| Invalid::new(format!("Params are not valid JSON: {}", e)))?,
+            None => Value::Null,
+        };
+        let result = crate::daemon::call(&socket, method, params).await?;
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        Ok(())
+    }
+}
+
+impl Daemon {
+    async fn handle(self: Arc<Self>, request: Request) -> Result<Value> {
+        match request.method.as_str() {
+            "status" => self.status().await,
+            "queue.add" => self.queue_add(request.params()?).await,
+            "queue.process" => self.queue_process(request.params()?).await,
+            "deploy" => self.deploy().await,
+            "shutdown" => {
+                self.shutdown.notify_one();
+                Ok(json!({ "stopping": true }))
+            }
+            method => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!(
+                    "Unknown method '{}' (expected one of: {})",
+                    method,
+                    crate::daemon::METHODS.join(", ")
+                ),
+            )
+            .into()),
+        }
+    }
+
+    async fn active_game(&self) -> Result<crate::games::Game> {
+        match self.app.active_game().await {
+            Some(game) => Ok(game),
+            None => bail!(Invalid::new(
+                "No game selected. Use 'modsanity game select <name>' first."
+            )),
+        }
+    }
+
+    async fn status(&self) -> Result<Value> {
+        let app = &self.app;
+        let game = app.active_game().await;
+        let mods = match &game {
+            Some(g) => {
+                let mods = app.mods.list_mods(&g.id).await?;
+                let enabled = mods.iter().filter(|m| m.enabled).count();
+                Some(json!({ "installed": mods.len(), "enabled": enabled }))
+            }
+            None => None,
+        };
+        let processing = self.processing.lock().unwrap().clone();
+        let queue_manager = QueueManager::new(app.db.clone());
+        let mut batches = Vec::new();
+        for batch in queue_manager.list_batches(game.as_ref().map(|g| g.id.as_str()))? {
+            let state = if processing.contains(&batch.batch_id) {
+                "processing"
+            } else {
+                match queue_manager.batch_control(&batch.batch_id)? {
+                    BatchControl::Paused => "paused",
+                    BatchControl::Cancelled => "cancel_requested",
+                    BatchControl::Running => "idle",
+                }
+            };
+            batches.push(json!({
+                "batch_id": batch.batch_id,
+                "state": state,
+                "total": batch.total,
+                "pending": batch.pending + batch.matched,
+                "active": batch.downloading + batch.installing,
+                "completed": batch.completed,
+                "failed": batch.failed,
+            }));
+        }
+        let config = app.config.read().await;
+        Ok(json!({
+            "pid": std::process::id(),
+            "version": crate::APP_VERSION,
+            "game": game.map(|g| json!({ "id": g.id, "name": g.name })),
+            "profile": config.active_profile,
+            "deployment_method": config.deployment.method.as_str(),
+            "mods": mods,
+            "queue": batches,
+        }))
+    }
+
+    async fn queue_add(&self, params: QueueAddParams) -> Result<Value> {
+        let game = self.active_game().await?;
+        let queue_manager = QueueManager::new(self.app.db.clone());
+        let (batch_id, queue_position) = match params.batch_id {
+            Some(batch_id) => {
+                let entries = queue_manager.get_batch(&batch_id)?;
+                if entries.is_empty() {
+                    bail!(Invalid::new(format!("No queue batch '{}'", batch_id)));
+                }
+                let position = entries.iter().map(|e| e.queue_position + 1).max();
+                (batch_id, position.unwrap_or_default())
+            }
+            None => (queue_manager.create_batch(), 0),
+        };
+        let entry_id = queue_manager.add_entry(QueueEntry {
+            id: 0,
+            batch_id: batch_id.clone(),
+            game_id: game.id.clone(),
+            queue_position,
+            plugin_name: String::new(),
+            mod_name: params
+                .name
+                .unwrap_or_else(|| format!("Nexus mod {}", params.mod_id)),
+            nexus_mod_id: params.mod_id,
+            selected_file_id: params.file_id,
+            auto_install: true,
+            priority: false,
+            replaces_mod: None,
+            match_confidence: Some(1.0),
+            alternatives: Vec::new(),
+            status: QueueStatus::Matched,
+            progress: 0.0,
+            error: None,
+        })?;
+        Ok(json!({ "batch_id": batch_id, "entry_id": entry_id }))
+    }
+
+    /// Start processing in the background and return the batches started
+    async fn queue_process(self: Arc<Self>, params: QueueProcessParams) -> Result<Value> {
+        let game = self.active_game().await?;
+        let processor = self.app.queue_processor(&game).await?;
+        let queue_manager = QueueManager::new(self.app.db.clone());
+        let candidates = match params.batch_id {
+            Some(id) => vec![id],
+            None => queue_manager
+                .list_batches(Some(&game.id))?
+                .into_iter()
+                .map(|b| b.batch_id)
+                .collect(),
+        };
+
+        let mut started = Vec::new();
+        {
+            let mut processing = self.processing.lock().unwrap();
+            for batch in candidates {
+                if queue_manager.batch_control(&batch)? == BatchControl::Paused
+                    || !processing.insert(batch.clone())
+                {
+                    continue;
+                }
+                started.push(batch);
+            }
+        }
+
+        let daemon = Arc::clone(&self);
+        let batches = started.clone();
+        tokio::spawn(async move {
+            for batch in batches {
+                match processor.process_batch(&batch, params.download_only).await {
+                    Ok(()) => tracing::info!("Daemon finished queue batch {}", batch),
+                    Err(e) => tracing::error!("Queue batch {} failed: {:#}", batch, e),
+                }
+                daemon.processing.lock().unwrap().remove(&batch);
+            }
+        });
+        Ok(json!({ "started": started }))
+    }
+
+    async fn deploy(&self) -> Result<Value> {
+        let game = self.active_game().await?;
+        if !self.processing.lock().unwrap().is_empty() {
+            bail!(Conflict::new(
+                "The queue is being processed; deploy once it finishes"
+            ));
+        }
+        let stats = self.app.mods.deploy(&game).await?;
+        Ok(json!({
+            "mods_deployed": stats.mods_deployed,
+            "files_deployed": stats.files_deployed,
+            "conflicts_resolved": stats.conflicts_resolved,
+            "excluded": stats.excluded.len(),
+            "warnings": stats.warnings,
+            "errors": stats.errors,
+            "archive_invalidation": stats.archive_invalidation,
+        }))
+    }
+}
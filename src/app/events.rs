@@ -0,0 +1,224 @@
+//! State update events
+//!
+//! Background tasks never lock `AppState` themselves. They send `AppEvent`s
+//! through an `EventSender`; the TUI event loop drains the channel and applies
+//! every pending event in one place before taking a render snapshot.
+
+use super::state::{
+    AppState, CatalogProgress, CategorizationProgress, ImportProgress, InstallProgress,
+};
+use tokio::sync::mpsc;
+
+/// Deferred mutation of the application state
+pub type StateUpdate = Box<dyn FnOnce(&mut AppState) + Send + 'static>;
+
+/// An update to the application state produced outside the event loop
+pub enum AppEvent {
+    /// Plain status message
+    Status(String),
+    /// Status message with success icon
+    StatusSuccess(String),
+    /// Status message with error icon
+    StatusError(String),
+    /// Status message with info icon
+    StatusInfo(String),
+    /// Failure with the full error chain behind it
+    Failure {
+        context: String,
+        error: anyhow::Error,
+    },
+    /// Raw command output text (one or more lines)
+    CommandOutput(String),
+    /// Installation progress (None clears it)
+    InstallProgress(Option<InstallProgress>),
+    /// Categorization progress (None clears it)
+    CategorizationProgress(Option<CategorizationProgress>),
+    /// Catalog population progress (None clears it)
+    CatalogProgress(Option<CatalogProgress>),
+    /// Import progress (None clears it)
+    ImportProgress(Option<ImportProgress>),
+    /// Arbitrary state mutation for multi-field results
+    Update(StateUpdate),
+}
+
+impl std::fmt::Debug for AppEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Status(msg) => f.debug_tuple("Status").field(msg).finish(),
+            Self::StatusSuccess(msg) => f.debug_tuple("StatusSuccess").field(msg).finish(),
+            Self::StatusError(msg) => f.debug_tuple("StatusError").field(msg).finish(),
+            Self::StatusInfo(msg) => f.debug_tuple("StatusInfo").field(msg).finish(),
+            Self::Failure { context, error } => f
+                .debug_struct("Failure")
+                .field("context", context)
+                .field("error", &error.to_string())
+                .finish(),
+            Self::CommandOutput(text) => f.debug_tuple("CommandOutput").field(text).finish(),
+            Self::InstallProgress(p) => f.debug_tuple("InstallProgress").field(p).finish(),
+            Self::CategorizationProgress(p) => {
+                f.debug_tuple("CategorizationProgress").field(p).finish()
+            }
+            Self::CatalogProgress(p) => f.debug_tuple("CatalogProgress").field(p).finish(),
+            Self::ImportProgress(p) => f.debug_tuple("ImportProgress").field(p).finish(),
+            Self::Update(_) => f.write_str("Update(<function>)"),
+        }
+    }
+}
+
+/// Create a connected sender/receiver pair
+pub fn channel() -> (EventSender, EventReceiver) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    (EventSender { tx }, EventReceiver { rx })
+}
+
+/// Cloneable handle used by spawned tasks to publish state updates
+#[derive(Debug, Clone)]
+pub struct EventSender {
+    tx: mpsc::UnboundedSender<AppEvent>,
+}
+
+impl EventSender {
+    /// Queue an event for the event loop
+    pub fn send(&self, event: AppEvent) {
+        // The receiver only goes away when the TUI shuts down; late updates
+        // from still-running tasks are irrelevant at that point.
+        let _ = self.tx.send(event);
+    }
+
+    /// Queue an arbitrary state mutation
+    pub fn update(&self, f: impl FnOnce(&mut AppState) + Send + 'static) {
+        self.send(AppEvent::Update(Box::new(f)));
+    }
+
+    pub fn status(&self, msg: impl Into<String>) {
+        self.send(AppEvent::Status(msg.into()));
+    }
+
+    pub fn status_success(&self, msg: impl Into<String>) {
+        self.send(AppEvent::StatusSuccess(msg.into()));
+    }
+
+    pub fn status_error(&self, msg: impl Into<String>) {
+        self.send(AppEvent::StatusError(msg.into()));
+    }
+
+    pub fn status_info(&self, msg: impl Into<String>) {
+        self.send(AppEvent::StatusInfo(msg.into()));
+    }
+
+    /// Report a failure, keeping its error chain for the detail view
+    pub fn failure(&self, context: impl Into<String>, error: impl Into<anyhow::Error>) {
+        self.send(AppEvent::Failure {
+            context: context.into(),
+            error: error.into(),
+        });
+    }
+
+    pub fn command_output(&self, text: impl Into<String>) {
+        self.send(AppEvent::CommandOutput(text.into()));
+    }
+
+    pub fn install_progress(&self, progress: Option<InstallProgress>) {
+        self.send(AppEvent::InstallProgress(progress));
+    }
+
+    pub fn categorization_progress(&self, progress: Option<CategorizationProgress>) {
+        self.send(AppEvent::CategorizationProgress(progress));
+    }
+
+    pub fn catalog_progress(&self, progress: Option<CatalogProgress>) {
+        self.send(AppEvent::CatalogProgress(progress));
+    }
+
+    pub fn import_progress(&self, progress: Option<ImportProgress>) {
+        self.send(AppEvent::ImportProgress(progress));
+    }
+}
+
+/// Receiving end owned by the TUI event loop
+#[derive(Debug)]
+pub struct EventReceiver {
+    rx: mpsc::UnboundedReceiver<AppEvent>,
+}
+
+impl EventReceiver {
+    /// Apply every pending event to `state`, returning how many were applied
+    pub fn drain_into(&mut self, state: &mut AppState) -> usize {
+        let mut applied = 0;
+        while let Ok(event) = self.rx.try_recv() {
+            state.apply(event);
+            applied += 1;
+        }
+        applied
+    }
+}
+
+impl AppState {
+    /// Apply a single event. This is the only place background work mutates state.
+    pub fn apply(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::Status(msg) => self.set_status(msg),
+            AppEvent::StatusSuccess(msg) => self.set_status_success(msg),
+            AppEvent::StatusError(msg) => self.set_status_error(msg),
+            AppEvent::StatusInfo(msg) => self.set_status_info(msg),
+            AppEvent::Failure { context, error } => self.set_status_error_chain(context, &error),
+            AppEvent::CommandOutput(text) => self.push_command_output_text(&text),
+            AppEvent::InstallProgress(p) => self.installation_progress = p,
+            AppEvent::CategorizationProgress(p) => self.categorization_progress = p,
+            AppEvent::CatalogProgress(p) => self.catalog_progress = p,
+            AppEvent::ImportProgress(p) => self.import_progress = p,
+            AppEvent::Update(f) => f(self),
+        }
+        self.revision = self.revision.wrapping_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_applies_events_in_order() {
+        let (tx, mut rx) = channel();
+        let mut state = AppState::new(None);
+
+        tx.status("first");
+        tx.update(|s| s.selected_mod_index = 3);
+        tx.status_error("failed");
+
+        assert_eq!(rx.drain_into(&mut state), 3);
+        assert_eq!(state.selected_mod_index, 3);
+        assert_eq!(state.status_message(), Some("✗ failed"));
+        assert_eq!(state.revision, 3);
+        assert_eq!(rx.drain_into(&mut state), 0);
+
+        tx.failure(
+            "Deploy failed",
+            anyhow::anyhow!("permission denied").context("Failed to link SkyUI.esp"),
+        );
+        rx.drain_into(&mut state);
+        let latest = state.notifications.latest().unwrap();
+        assert_eq!(latest.message, "✗ Deploy failed: Failed to link SkyUI.esp");
+        assert_eq!(
+            latest.detail.as_deref(),
+            Some("Failed to link SkyUI.esp\n\nCaused by:\n    0: permission denied")
+        );
+    }
+
+    #[test]
+    fn progress_events_replace_and_clear() {
+        let (tx, mut rx) = channel();
+        let mut state = AppState::new(None);
+
+        tx.catalog_progress(Some(CatalogProgress::new(1, 10, 0, 100, 10)));
+        rx.drain_into(&mut state);
+        assert_eq!(
+            state.catalog_progress.as_ref().and_then(|p| p.percent()),
+            Some(10)
+        );
+
+        tx.catalog_progress(None);
+        rx.drain_into(&mut state);
+        assert!(state.catalog_progress.is_none());
+    }
+}
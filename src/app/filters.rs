@@ -0,0 +1,204 @@
+//! Saved filters for the Mods screen
+//!
+//! A filter narrows the mod list by category, name, enabled state, update
+//! status and Nexus link. A few smart views are built in; users add their own
+//! as `[[mod_filters]]` in config.toml or save the current view from the
+//! filter dropdown.
+
+use crate::config::ModFilterConfig;
+use crate::db::CategoryRecord;
+use crate::mods::InstalledMod;
+use std::collections::HashMap;
+
+/// Name of the group that mods without a known category fall under
+const UNCATEGORIZED: &str = "Uncategorized";
+
+/// Smart views offered before the user's own filters
+pub fn builtin() -> Vec<ModFilterConfig> {
+    vec![
+        ModFilterConfig {
+            name: "Enabled with updates".to_string(),
+            enabled: Some(true),
+            has_update: Some(true),
+            ..Default::default()
+        },
+        ModFilterConfig {
+            name: "Disabled".to_string(),
+            enabled: Some(false),
+            ..Default::default()
+        },
+        ModFilterConfig {
+            name: "No Nexus id".to_string(),
+            nexus: Some(false),
+            ..Default::default()
+        },
+        ModFilterConfig {
+            name: "Uncategorized".to_string(),
+            category: Some(UNCATEGORIZED.to_string()),
+            ..Default::default()
+        },
+    ]
+}
+
+/// Built-in views followed by the configured filters. A configured filter
+/// with a built-in's name replaces it in place.
+pub fn with_builtin(configured: &[ModFilterConfig]) -> Vec<ModFilterConfig> {
+    let mut filters = builtin();
+    for filter in configured {
+        match filters
+            .iter_mut()
+            .find(|f| f.name.eq_ignore_ascii_case(&filter.name))
+        {
+            Some(existing) => *existing = filter.clone(),
+            None => filters.push(filter.clone()),
+        }
+    }
+    filters
+}
+
+/// Whether `m` passes every predicate `filter` sets. `updates` holds the
+/// Nexus ids of mods with an update available.
+pub fn matches<T>(
+    filter: &ModFilterConfig,
+    m: &InstalledMod,
+    categories: &[CategoryRecord],
+    updates: &HashMap<i64, T>,
+) -> bool {
+    if filter.enabled.is_some_and(|enabled| m.enabled != enabled) {
+        return false;
+    }
+    if filter
+        .nexus
+        .is_some_and(|linked| m.nexus_mod_id.is_some() != linked)
+    {
+        return false;
+    }
+    if let Some(wanted) = filter.has_update {
+        let has_update = m.nexus_mod_id.is_some_and(|id| updates.contains_key(&id));
+        if has_update != wanted {
+            return false;
+        }
+    }
+    if let Some(search) = filter.search.as_deref().filter(|s| !s.is_empty()) {
+        if !m.name.to_lowercase().contains(&search.to_lowercase()) {
+            return false;
+        }
+    }
+    if let Some(category) = filter.category.as_deref() {
+        let name = m
+            .category_id
+            .and_then(|id| categories.iter().find(|c| c.id == Some(id)))
+            .map_or(UNCATEGORIZED, |c| c.name.as_str());
+        if !name.eq_ignore_ascii_case(category) {
+            return false;
+        }
+    }
+    true
+}
+
+/// One-line summary of a filter's predicates, for the dropdown
+pub fn describe(filter: &ModFilterConfig) -> String {
+    let mut parts = Vec::new();
+    if let Some(category) = &filter.category {
+        parts.push(format!("category {}", category));
+    }
+    if let Some(search) = &filter.search {
+        parts.push(format!("name contains \"{}\"", search));
+    }
+    if let Some(enabled) = filter.enabled {
+        parts.push(if enabled { "enabled" } else { "disabled" }.to_string());
+    }
+    if let Some(has_update) = filter.has_update {
+        parts.push(
+            if has_update {
+                "has update"
+            } else {
+                "up to date"
+            }
+            .to_string(),
+        );
+    }
+    if let Some(nexus) = filter.nexus {
+        parts.push(if nexus { "on Nexus" } else { "no Nexus id" }.to_string());
+    }
+    if parts.is_empty() {
+        "all mods".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(id: i64, category_id: Option<i64>, enabled: bool) -> InstalledMod {
+        InstalledMod {
+            id,
+            name: format!("Mod {}", id),
+            version: "1.0".to_string(),
+            author: None,
+            enabled,
+            priority: id as i32,
+            nexus_mod_id: (id % 2 == 0).then_some(id * 100),
+            nexus_file_id: None,
+            file_count: 1,
+            install_path: Default::default(),
+            category_id,
+            installed_at: String::new(),
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let categories = vec![CategoryRecord {
+            id: Some(1),
+            name: "Textures".to_string(),
+            description: None,
+            display_order: 1,
+            color: None,
+            parent_id: None,
+        }];
+        let updates = HashMap::from([(200, ())]);
+        let mods = [
+            installed(1, Some(1), false),
+            installed(2, None, true),
+            installed(3, Some(1), true),
+            installed(4, Some(7), false),
+        ];
+        let ids = |filter: &ModFilterConfig| -> Vec<i64> {
+            mods.iter()
+                .filter(|m| matches(filter, m, &categories, &updates))
+                .map(|m| m.id)
+                .collect()
+        };
+
+        let disabled_textures = ModFilterConfig {
+            name: "Disabled textures".to_string(),
+            category: Some("textures".to_string()),
+            enabled: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(ids(&disabled_textures), vec![1]);
+
+        let builtin = with_builtin(&[]);
+        assert_eq!(ids(&builtin[0]), vec![2]);
+        assert_eq!(ids(&builtin[2]), vec![1, 3]);
+        // An unknown category id counts as uncategorized
+        assert_eq!(ids(&builtin[3]), vec![2, 4]);
+        assert_eq!(ids(&ModFilterConfig::default()), vec![1, 2, 3, 4]);
+
+        let overridden = with_builtin(&[
+            ModFilterConfig {
+                name: "disabled".to_string(),
+                search: Some("mod 4".to_string()),
+                ..Default::default()
+            },
+            disabled_textures,
+        ]);
+        assert_eq!(overridden.len(), builtin.len() + 1);
+        assert_eq!(ids(&overridden[1]), vec![4]);
+        assert_eq!(describe(&overridden[4]), "category textures, disabled");
+    }
+}
@@ -0,0 +1,158 @@
+//! Category groups on the Mods screen
+//!
+//! Mods are listed under a header per category, each showing how many of its
+//! mods are enabled. A group can be collapsed down to its header. The rows
+//! built here, headers included, are what `selected_mod_index` indexes.
+
+use crate::db::CategoryRecord;
+use crate::mods::InstalledMod;
+use std::collections::HashSet;
+
+/// Header of one category's mods
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModGroup {
+    /// `None` for mods without a category
+    pub category_id: Option<i64>,
+    pub name: String,
+    pub enabled: usize,
+    pub total: usize,
+    pub collapsed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum ModRow<'a> {
+    Group(ModGroup),
+    Mod(&'a InstalledMod),
+}
+
+impl<'a> ModRow<'a> {
+    pub fn as_mod(&self) -> Option<&'a InstalledMod> {
+        match self {
+            Self::Mod(m) => Some(m),
+            Self::Group(_) => None,
+        }
+    }
+
+    pub fn as_group(&self) -> Option<&ModGroup> {
+        match self {
+            Self::Group(g) => Some(g),
+            Self::Mod(_) => None,
+        }
+    }
+}
+
+/// Group `mods`, already filtered and sorted, under their categories in the
+/// order of `categories`, with uncategorized mods last. Mods keep their order
+/// within a group. When everything falls in one group the list stays flat.
+pub fn group_mods<'a>(
+    mods: &[&'a InstalledMod],
+    categories: &[CategoryRecord],
+    collapsed: &HashSet<Option<i64>>,
+) -> Vec<ModRow<'a>> {
+    // Mods pointing at a category that no longer exists count as uncategorized
+    let known = |id: Option<i64>| id.filter(|id| categories.iter().any(|c| c.id == Some(*id)));
+
+    let mut groups: Vec<(Option<i64>, String)> =
+        categories.iter().map(|c| (c.id, c.name.clone())).collect();
+    groups.push((None, "Uncategorized".to_string()));
+    groups.retain(|(id, _)| mods.iter().any(|m| known(m.category_id) == *id));
+
+    if groups.len() <= 1 {
+        return mods.iter().map(|m| ModRow::Mod(m)).collect();
+    }
+
+    let mut rows = Vec::with_capacity(mods.len() + groups.len());
+    for (id, name) in groups {
+        let members: Vec<&'a InstalledMod> = mods
+            .iter()
+            .copied()
+            .filter(|m| known(m.category_id) == id)
+            .collect();
+        let is_collapsed = collapsed.contains(&id);
+        rows.push(ModRow::Group(ModGroup {
+            category_id: id,
+            name,
+            enabled: members.iter().filter(|m| m.enabled).count(),
+            total: members.len(),
+            collapsed: is_collapsed,
+        }));
+        if !is_collapsed {
+            rows.extend(members.into_iter().map(ModRow::Mod));
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(id: i64, category_id: Option<i64>, enabled: bool) -> InstalledMod {
+        InstalledMod {
+            id,
+            name: format!("Mod {}", id),
+            version: "1.0".to_string(),
+            author: None,
+            enabled,
+            priority: id as i32,
+            nexus_mod_id: None,
+            nexus_file_id: None,
+            file_count: 1,
+            install_path: Default::default(),
+            category_id,
+            installed_at: String::new(),
+            size: 0,
+        }
+    }
+
+    fn category(id: i64, name: &str) -> CategoryRecord {
+        CategoryRecord {
+            id: Some(id),
+            name: name.to_string(),
+            description: None,
+            display_order: id as i32,
+            color: None,
+            parent_id: None,
+        }
+    }
+
+    #[test]
+    fn test_group_mods() {
+        let categories = vec![category(1, "UI"), category(2, "Textures")];
+        let mods = [
+            installed(10, Some(2), true),
+            installed(11, None, true),
+            installed(12, Some(1), false),
+            installed(13, Some(2), false),
+            installed(14, Some(99), true),
+        ];
+        let refs: Vec<&InstalledMod> = mods.iter().collect();
+
+        let rows = group_mods(&refs, &categories, &HashSet::new());
+        let headers: Vec<(&str, usize, usize)> = rows
+            .iter()
+            .filter_map(ModRow::as_group)
+            .map(|g| (g.name.as_str(), g.enabled, g.total))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![("UI", 0, 1), ("Textures", 1, 2), ("Uncategorized", 2, 2)]
+        );
+        let ids: Vec<i64> = rows
+            .iter()
+            .filter_map(ModRow::as_mod)
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(ids, vec![12, 10, 13, 11, 14]);
+
+        let collapsed = HashSet::from([Some(2)]);
+        let rows = group_mods(&refs, &categories, &collapsed);
+        assert_eq!(rows.len(), 3 + 3);
+        assert!(rows[2].as_group().is_some_and(|g| g.collapsed));
+
+        // A single group isn't worth a header
+        let rows = group_mods(&refs[1..2], &categories, &HashSet::new());
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].as_mod().is_some());
+    }
+}
@@ -1,22 +1,37 @@
 //! Application state and orchestration
 
 mod actions;
+mod daemon;
+pub mod events;
+pub mod filters;
+pub mod groups;
+pub mod notifications;
+pub mod sort;
 pub mod state;
+pub mod undo;
 
+pub use events::{AppEvent, EventReceiver, EventSender};
 pub use state::{AppState, ConfirmAction, ConfirmDialog, InputMode, Screen, UiMode};
 
-use crate::config::{Config, DeploymentMethod, ExternalTool, ToolRuntimeMode};
-use crate::db::Database;
+use crate::config::{
+    Config, DeploymentMethod, ExternalTool, ToolOptions, ToolRef, ToolRuntimeMode,
+};
+use crate::db::{CleanedPluginRecord, Database};
+use crate::error::{Conflict, Invalid};
+use crate::extensions::{ExtensionContext, ExtensionRegistry};
 use crate::games::{
-    detect_proton_runtimes, Game, GameDetector, GamePlatform, GameType, ProtonRuntime,
+    detect_proton_runtimes, Game, GameDefinition, GameDetector, GamePlatform, GameType,
+    ProtonRuntime,
 };
-use crate::mods::ModManager;
-use crate::nexus::NexusClient;
-use crate::profiles::ProfileManager;
+use crate::mods::{DataSnapshot, LaunchProblem, ModManager, SessionKind};
+use crate::nexus::{NexusClient, SurpriseOptions};
+use crate::plugins::clean::DirtyPlugin;
+use crate::profiles::{IniWrite, ProfileManager, ToolSettings};
+use crate::queue::Transfers;
 use crate::tui::Tui;
 
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -28,6 +43,12 @@ pub struct App {
     /// Application state
     pub state: Arc<RwLock<AppState>>,
 
+    /// Sender for state updates from background tasks
+    pub events: EventSender,
+
+    /// Receiving end of `events`, taken by the TUI event loop
+    event_rx: Option<EventReceiver>,
+
     /// Database connection
     pub db: Arc<Database>,
 
@@ -40,11 +61,20 @@ pub struct App {
     /// Nexus Mods API client (optional, requires API key)
     pub nexus: Option<Arc<NexusClient>>,
 
+    /// Downloads in flight and recently finished, shown on the Downloads screen
+    pub transfers: Transfers,
+
     /// Detected games
     pub games: Vec<Game>,
 
+    /// Third-party extensions discovered at startup
+    pub extensions: Arc<ExtensionRegistry>,
+
     /// Global CLI verbosity (`-v`, `-vv`, `-vvv`)
     pub cli_verbosity: u8,
+
+    /// Print listing and status commands as JSON (`--json`)
+    pub json_output: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +82,28 @@ pub struct ExternalToolLaunchResult {
     pub exit_code: i32,
     pub stdout: String,
     pub stderr: String,
+    /// Files moved from Data into the profile's output mod
+    pub captured: Vec<PathBuf>,
+    /// Files the tool left in Data when there is no output mod to move them
+    /// to, relative to Data
+    pub new_files: Vec<PathBuf>,
+    /// Saved copy of the output, for captured launches
+    pub log_path: Option<PathBuf>,
+}
+
+/// Outcome of cleaning one plugin with xEdit
+#[derive(Debug)]
+pub struct PluginCleanResult {
+    pub launch: ExternalToolLaunchResult,
+    /// The recorded cleaning, or why the plugin wasn't cleaned
+    pub cleaned: Result<CleanedPluginRecord>,
+}
+
+/// The executable a launch would start and what stands in its way
+#[derive(Debug, Clone)]
+pub struct LaunchCheck {
+    pub executable: String,
+    pub problems: Vec<LaunchProblem>,
 }
 
 impl App {
@@ -67,8 +119,9 @@ impl App {
             Database::open(&config.paths.database_file()).context("Failed to open database")?;
         let db = Arc::new(db);
 
-        // Detect games (Steam + GOG + user-configured custom paths).
-        let games = GameDetector::detect_all_with_custom(&config.custom_games).await;
+        // Detect games (Steam + GOG + games.d definitions + user-configured custom paths).
+        let definitions = GameDefinition::load_dir(&config.paths.games_dir());
+        let games = GameDetector::detect_all_with_custom(&config.custom_games, &definitions).await;
 
         // Find active game
         let active_game = config
@@ -78,11 +131,12 @@ impl App {
             .cloned();
 
         // Initialize state
-        let state = AppState::new(active_game);
+        let mut state = AppState::new(active_game);
+        state.mod_filters = filters::with_builtin(&config.mod_filters);
 
         // Initialize Nexus API client if API key is available
         let nexus = config.nexus_api_key.as_ref().and_then(|key| {
-            NexusClient::new(key.clone())
+            NexusClient::with_base_url(key.clone(), config.nexus_base_url())
                 .map(Arc::new)
                 .map_err(|e| {
                     tracing::warn!("Failed to initialize Nexus API client: {}", e);
@@ -91,6 +145,14 @@ impl App {
                 .ok()
         });
 
+        // Discover extensions (commands, screens, matchers)
+        let extensions = Arc::new(ExtensionRegistry::discover_from_config(&config));
+        if !extensions.is_empty() {
+            tracing::info!("Loaded {} extension(s)", extensions.extensions().len());
+        }
+
+        let (events, event_rx) = events::channel();
+
         // Wrap config
         let config = Arc::new(RwLock::new(config));
 
@@ -103,12 +165,17 @@ impl App {
         Ok(Self {
             config,
             state: Arc::new(RwLock::new(state)),
+            events,
+            event_rx: Some(event_rx),
             db,
             mods,
             profiles,
             nexus,
+            transfers: Transfers::new(),
             games,
+            extensions,
             cli_verbosity: 0,
+            json_output: false,
         })
     }
 
@@ -116,17 +183,37 @@ impl App {
         self.cli_verbosity = verbosity;
     }
 
+    pub fn set_json_output(&mut self, json: bool) {
+        self.json_output = json;
+    }
+
     /// Run the TUI interface
     pub async fn run_tui(&mut self) -> Result<()> {
+        if let Some(game) = self.active_game().await {
+            if let Err(e) = self.mods.purge_trash(&game.id, false).await {
+                tracing::warn!("Failed to purge expired mods from the trash: {}", e);
+            }
+        }
         let mut tui = Tui::new()?;
         tui.run(self).await
     }
 
+    /// Take the event receiver (only one consumer may own it)
+    pub fn take_event_receiver(&mut self) -> Option<EventReceiver> {
+        self.event_rx.take()
+    }
+
     /// Get the currently active game
     pub async fn active_game(&self) -> Option<Game> {
         self.state.read().await.active_game.clone()
     }
 
+    /// Request context handed to extensions
+    pub async fn extension_context(&self) -> ExtensionContext {
+        let game = self.active_game().await;
+        ExtensionContext::new(&*self.config.read().await, game.as_ref())
+    }
+
     /// Set the active game
     pub async fn set_active_game(&mut self, game: Option<Game>) -> Result<()> {
         let mut state = self.state.write().await;
@@ -147,8 +234,29 @@ impl App {
         Ok(())
     }
 
+    /// Add or remove a global deploy exclusion pattern. Returns whether the
+    /// list changed.
+    pub async fn set_deploy_exclusion(&self, pattern: &str, excluded: bool) -> Result<bool> {
+        let pattern = crate::mods::ExclusionPattern::new(pattern)?;
+        let pattern = pattern.as_str();
+        let mut config = self.config.write().await;
+        let exclude = &mut config.deployment.exclude;
+        let present = exclude.iter().any(|p| p == pattern);
+        if excluded == present {
+            return Ok(false);
+        }
+        if excluded {
+            exclude.push(pattern.to_string());
+        } else {
+            exclude.retain(|p| p != pattern);
+        }
+        config.save().await?;
+        Ok(true)
+    }
+
     /// Set or clear downloads directory override.
     pub async fn set_downloads_dir_override(&self, path: Option<&str>) -> Result<()> {
+        check_path_template(path)?;
         let mut config = self.config.write().await;
         config.downloads_dir_override = path
             .map(str::trim)
@@ -161,6 +269,10 @@ impl App {
 
     /// Set or clear staging directory override.
     pub async fn set_staging_dir_override(&self, path: Option<&str>) -> Result<()> {
+        check_path_template(path)?;
+        if let Some(path) = path {
+            crate::config::check_staging_template(path)?;
+        }
         let mut config = self.config.write().await;
         config.staging_dir_override = path
             .map(str::trim)
@@ -181,15 +293,41 @@ impl App {
     }
 
     /// Resolve configured downloads directory.
-    pub async fn resolved_downloads_dir(&self) -> std::path::PathBuf {
+    pub async fn resolved_downloads_dir(&self) -> Result<std::path::PathBuf> {
         self.config.read().await.downloads_dir()
     }
 
     /// Resolve configured staging directory.
-    pub async fn resolved_staging_dir(&self) -> std::path::PathBuf {
+    pub async fn resolved_staging_dir(&self) -> Result<std::path::PathBuf> {
         self.config.read().await.staging_dir()
     }
 
+    /// Discovery filters for `game_id`: the given categories and minimum, falling
+    /// back to the `[discovery]` config, with installed mods excluded.
+    pub async fn surprise_options(
+        &self,
+        game_id: &str,
+        categories: Vec<String>,
+        min_endorsements: Option<i64>,
+    ) -> Result<SurpriseOptions> {
+        let config = self.config.read().await;
+        let categories = if categories.is_empty() {
+            config.discovery.categories.clone()
+        } else {
+            categories
+        };
+        Ok(SurpriseOptions {
+            categories,
+            min_endorsements: min_endorsements.unwrap_or(config.discovery.min_endorsements),
+            exclude: self
+                .db
+                .get_mods_for_game(game_id)?
+                .into_iter()
+                .filter_map(|m| m.nexus_mod_id)
+                .collect(),
+        })
+    }
+
     /// Validate a path string before saving to config.
     pub fn validate_directory_override(path: &str) -> Result<()> {
         let trimmed = path.trim();
@@ -198,7 +336,7 @@ impl App {
         }
         let p = Path::new(trimmed);
         if p.as_os_str().is_empty() {
-            anyhow::bail!("Directory path cannot be empty");
+            anyhow::bail!(Invalid::new("Directory path cannot be empty"));
         }
         Ok(())
     }
@@ -208,7 +346,7 @@ impl App {
         let mut config = self.config.write().await;
         let trimmed = proton_cmd.trim();
         if trimmed.is_empty() {
-            anyhow::bail!("Proton command cannot be empty");
+            anyhow::bail!(Invalid::new("Proton command cannot be empty"));
         }
         // Custom command/path mode supersedes runtime selection.
         config.external_tools.proton_runtime = None;
@@ -235,10 +373,10 @@ impl App {
             Some(sel) => {
                 let runtimes = detect_proton_runtimes();
                 let Some(found) = find_runtime(&runtimes, sel) else {
-                    anyhow::bail!(
+                    anyhow::bail!(Invalid::new(format!(
                         "Proton runtime '{}' not found. Run 'modsanity tool list-proton' to see detected runtimes.",
                         sel
-                    );
+                    )));
                 };
                 config.external_tools.proton_runtime = Some(found.id.clone());
             }
@@ -263,6 +401,18 @@ impl App {
         Ok(())
     }
 
+    /// Replace a tool's default arguments, working directory and environment.
+    pub async fn set_external_tool_options(
+        &self,
+        tool: ExternalTool,
+        options: ToolOptions,
+    ) -> Result<()> {
+        let mut config = self.config.write().await;
+        config.set_external_tool_options(tool, options);
+        config.save().await?;
+        Ok(())
+    }
+
     /// Set/clear per-tool runtime mode override.
     pub async fn set_external_tool_runtime_mode(
         &self,
@@ -275,31 +425,75 @@ impl App {
         Ok(())
     }
 
-    /// Launch an external tool through Proton, using active game's prefix.
-    pub async fn launch_external_tool(&self, tool: ExternalTool, args: &[String]) -> Result<i32> {
-        let game = self
-            .active_game()
-            .await
-            .ok_or_else(|| anyhow::anyhow!("No game selected"))?;
-        let (proton_cmd, tool_path, runtime_mode) = {
+    /// Settings for `tool`: a built-in tool with the active profile's
+    /// overrides applied, or a custom tool's entry.
+    pub async fn external_tool_settings(
+        &self,
+        game: &Game,
+        tool: &ToolRef,
+    ) -> Result<ToolSettings> {
+        let tool = match tool {
+            ToolRef::Builtin(tool) => *tool,
+            ToolRef::Custom(name) => {
+                let config = self.config.read().await;
+                let custom = config
+                    .custom_tool(name)
+                    .ok_or_else(|| Invalid::new(format!("No custom tool named '{}'", name)))?;
+                return Ok(ToolSettings::custom(custom));
+            }
+        };
+        let active_profile = self.config.read().await.active_profile.clone();
+        let profile = match active_profile {
+            Some(name) => self.profiles.get_profile(&game.id, &name).await.ok(),
+            None => None,
+        };
+        let config = self.config.read().await;
+        Ok(ToolSettings::resolve(&config, profile.as_ref(), tool))
+    }
+
+    /// Build the command for `tool`, with the configured arguments before `args`.
+    async fn external_tool_command(
+        &self,
+        game: &Game,
+        tool: &ToolRef,
+        settings: &ToolSettings,
+        args: &[String],
+    ) -> Result<tokio::process::Command> {
+        let bundled = match tool {
+            ToolRef::Builtin(tool) => tool
+                .bundled_executable(game.game_type)
+                .map(|exe| game.executable_path(exe))
+                .filter(|path| path.exists()),
+            ToolRef::Custom(_) => None,
+        };
+        let tool_path = settings
+            .path
+            .clone()
+            .or_else(|| bundled.map(|path| path.display().to_string()))
+            .ok_or_else(|| {
+                anyhow::anyhow!("Tool path not configured for {}", tool.display_name())
+            })?;
+        let active_profile = self.config.read().await.active_profile.clone();
+        let home = std::env::var("HOME").ok();
+        let game_dir = game.install_path.display().to_string();
+        let data_dir = game.data_path.display().to_string();
+        let vars = [
+            ("game", Some(game.id.as_str())),
+            ("profile", active_profile.as_deref()),
+            ("home", home.as_deref()),
+            ("game_dir", Some(game_dir.as_str())),
+            ("data_dir", Some(data_dir.as_str())),
+        ];
+        let expand = |template: &str| crate::config::expand_tool_template(template, &vars);
+        let proton_cmd = if settings.runtime_mode == ToolRuntimeMode::Proton {
             let config = self.config.read().await;
-            let tool_path = config
-                .external_tool_path(tool)
-                .ok_or_else(|| {
-                    anyhow::anyhow!("Tool path not configured for {}", tool.display_name())
-                })?
-                .to_string();
-            let mode = config.external_tool_runtime_mode(tool);
-            let proton_cmd = if mode == ToolRuntimeMode::Proton {
-                Some(self.resolve_proton_launcher_from_config(&config)?)
-            } else {
-                None
-            };
-            (proton_cmd, tool_path, mode)
+            Some(self.resolve_proton_launcher_from_config(&config)?)
+        } else {
+            None
         };
 
         let resolved_tool_path = expand_user_path(&tool_path);
-        let mut command = if runtime_mode == ToolRuntimeMode::Proton {
+        let mut command = if settings.runtime_mode == ToolRuntimeMode::Proton {
             let proton_prefix = game
                 .proton_prefix
                 .clone()
@@ -307,96 +501,354 @@ impl App {
             let resolved_proton_cmd = expand_user_path(proton_cmd.as_deref().unwrap_or("proton"));
             let mut command = tokio::process::Command::new(&resolved_proton_cmd);
             command.arg("run").arg(&resolved_tool_path);
-            Self::apply_proton_launch_env(
-                &mut command,
-                &game,
-                &proton_prefix,
-                &resolved_proton_cmd,
-            );
+            Self::apply_proton_launch_env(&mut command, game, &proton_prefix, &resolved_proton_cmd);
             command
         } else {
             tokio::process::Command::new(&resolved_tool_path)
         };
-        for arg in args {
-            command.arg(arg);
-        }
-        if let Some(parent) = Path::new(&resolved_tool_path).parent() {
+        command
+            .args(settings.args.iter().map(|arg| expand(arg)))
+            .args(args)
+            .envs(&settings.env);
+        if let Some(dir) = &settings.working_dir {
+            command.current_dir(expand_user_path(&expand(dir)));
+        } else if let Some(parent) = Path::new(&resolved_tool_path).parent() {
             command.current_dir(parent);
         }
+        Ok(command)
+    }
+
+    /// Move what the tool wrote into Data into the profile's output mod, if it has one.
+    async fn capture_external_tool_output(
+        &self,
+        game: &Game,
+        settings: &ToolSettings,
+        snapshot: Option<DataSnapshot>,
+    ) -> Result<Vec<PathBuf>> {
+        match (&settings.output_mod, snapshot) {
+            (Some(output_mod), Some(snapshot)) => self
+                .mods
+                .capture_output(game, &snapshot, output_mod)
+                .await
+                .with_context(|| format!("Failed to capture tool output into '{}'", output_mod)),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Files in Data that weren't there after the last deploy, when the tool
+    /// has no output mod to capture them; they can be swept into one later
+    async fn external_tool_new_files(&self, game: &Game, settings: &ToolSettings) -> Vec<PathBuf> {
+        if settings.output_mod.is_some() {
+            return Vec::new();
+        }
+        match self.mods.new_data_files(game).await {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::debug!("Not checking Data for tool output: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Launch an external tool through Proton, using active game's prefix.
+    /// Output goes straight to the terminal, so `stdout`/`stderr` are empty.
+    pub async fn launch_external_tool(
+        &self,
+        tool: &ToolRef,
+        args: &[String],
+    ) -> Result<ExternalToolLaunchResult> {
+        let game = self
+            .active_game()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("No game selected"))?;
+        let settings = self.external_tool_settings(&game, tool).await?;
+        let mut command = self
+            .external_tool_command(&game, tool, &settings, args)
+            .await?;
+        let snapshot = settings
+            .output_mod
+            .as_ref()
+            .map(|_| DataSnapshot::take(&game.data_path));
 
         let status = command
             .status()
             .await
             .with_context(|| format!("Failed to launch {} via Proton", tool.display_name()))?;
 
-        Ok(status.code().unwrap_or_default())
+        Ok(ExternalToolLaunchResult {
+            exit_code: status.code().unwrap_or_default(),
+            stdout: String::new(),
+            stderr: String::new(),
+            captured: self
+                .capture_external_tool_output(&game, &settings, snapshot)
+                .await?,
+            new_files: self.external_tool_new_files(&game, &settings).await,
+            log_path: None,
+        })
+    }
+
+    /// Run a tool command to completion with its output captured and saved
+    /// to the tool log folder. Returns the exit code, stdout, stderr and log.
+    async fn run_tool_captured(
+        name: &str,
+        command: &mut tokio::process::Command,
+    ) -> Result<(i32, String, String, Option<PathBuf>)> {
+        let output = command
+            .stdin(std::process::Stdio::null())
+            .output()
+            .await
+            .with_context(|| format!("Failed to launch {} via Proton", name))?;
+
+        let exit_code = output.status.code().unwrap_or_default();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let log_path = match crate::logging::save_tool_log(
+            &crate::logging::tool_log_dir(),
+            name,
+            exit_code,
+            &stdout,
+            &stderr,
+        ) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                tracing::warn!("Failed to save {} output: {}", name, e);
+                None
+            }
+        };
+        Ok((exit_code, stdout, stderr, log_path))
+    }
+
+    /// Run xEdit's QuickAutoClean on one dirty plugin and record the result.
+    /// The launch result is returned even when cleaning failed, so its output
+    /// can be shown.
+    pub async fn clean_plugin(&self, plugin: &DirtyPlugin) -> Result<PluginCleanResult> {
+        let game = self
+            .active_game()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("No game selected"))?;
+        let tool = ToolRef::Builtin(ExternalTool::XEdit);
+        let settings = self.external_tool_settings(&game, &tool).await?;
+        let args = crate::plugins::clean::quick_auto_clean_args(game.game_type, &plugin.filename);
+        let mut command = self
+            .external_tool_command(&game, &tool, &settings, &args)
+            .await?;
+        let name = format!("xEdit QuickAutoClean {}", plugin.filename);
+        let (exit_code, stdout, stderr, log_path) =
+            Self::run_tool_captured(&name, &mut command).await?;
+        let cleaned = if exit_code == 0 {
+            self.mods.finish_cleaning(&game, plugin).await
+        } else {
+            Err(anyhow::anyhow!("xEdit exited with code {}", exit_code))
+        };
+        Ok(PluginCleanResult {
+            launch: ExternalToolLaunchResult {
+                exit_code,
+                stdout,
+                stderr,
+                captured: Vec::new(),
+                new_files: Vec::new(),
+                log_path,
+            },
+            cleaned,
+        })
+    }
+
+    /// Folder the game's INI files are backed up to before each write
+    pub async fn ini_backup_dir(&self, game: &Game) -> PathBuf {
+        self.config.read().await.paths.ini_backups_dir(&game.id)
+    }
+
+    /// Folder save backups of `game` go to
+    pub async fn save_backup_dir(&self, game: &Game) -> PathBuf {
+        self.config.read().await.paths.saves_backups_dir(&game.id)
+    }
+
+    /// Set one key in one of the game's INI files, backing the file up first
+    pub async fn set_ini_value(
+        &self,
+        game: &Game,
+        file: &str,
+        section: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<IniWrite> {
+        let backup_dir = self.ini_backup_dir(game).await;
+        crate::profiles::set_ini_value(game, &backup_dir, file, section, key, value)
+    }
+
+    /// Edit one of the game's INI files in `$VISUAL`/`$EDITOR` (vi if
+    /// neither is set). The editor gets a copy, and the file is backed up
+    /// and written only when the copy changed. Needs the terminal, so the
+    /// TUI must be suspended around it.
+    pub async fn edit_ini_in_editor(&self, game: &Game, file: &str) -> Result<Option<IniWrite>> {
+        let original = crate::profiles::read_ini_text(game, file)?;
+        let copy = std::env::temp_dir().join(format!("modsanity-{}-{}", std::process::id(), file));
+        std::fs::write(&copy, &original)
+            .with_context(|| format!("Failed to write {}", copy.display()))?;
+
+        let editor = std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .unwrap_or_else(|_| "vi".to_string());
+        let mut words = crate::config::split_args(&editor);
+        if words.is_empty() {
+            words.push("vi".to_string());
+        }
+        let status = tokio::process::Command::new(&words[0])
+            .args(&words[1..])
+            .arg(&copy)
+            .status()
+            .await
+            .with_context(|| format!("Failed to start editor '{}'", words[0]));
+        let edited = std::fs::read(&copy);
+        let _ = std::fs::remove_file(&copy);
+        let status = status?;
+        if !status.success() {
+            anyhow::bail!(
+                "Editor '{}' exited with {}; {} left unchanged",
+                words[0],
+                status,
+                file
+            );
+        }
+
+        let edited = String::from_utf8_lossy(&edited?).into_owned();
+        if edited == original {
+            return Ok(None);
+        }
+        let backup_dir = self.ini_backup_dir(game).await;
+        crate::profiles::write_ini_text(game, &backup_dir, file, &edited).map(Some)
     }
 
     /// Launch an external tool and capture stdout/stderr (used by TUI to keep output in-app).
     pub async fn launch_external_tool_captured(
         &self,
-        tool: ExternalTool,
+        tool: &ToolRef,
         args: &[String],
     ) -> Result<ExternalToolLaunchResult> {
         let game = self
             .active_game()
             .await
             .ok_or_else(|| anyhow::anyhow!("No game selected"))?;
-        let (proton_cmd, tool_path, runtime_mode) = {
-            let config = self.config.read().await;
-            let tool_path = config
-                .external_tool_path(tool)
-                .ok_or_else(|| {
-                    anyhow::anyhow!("Tool path not configured for {}", tool.display_name())
-                })?
-                .to_string();
-            let mode = config.external_tool_runtime_mode(tool);
-            let proton_cmd = if mode == ToolRuntimeMode::Proton {
-                Some(self.resolve_proton_launcher_from_config(&config)?)
-            } else {
-                None
-            };
-            (proton_cmd, tool_path, mode)
-        };
+        let settings = self.external_tool_settings(&game, tool).await?;
+        let mut command = self
+            .external_tool_command(&game, tool, &settings, args)
+            .await?;
+        let snapshot = settings
+            .output_mod
+            .as_ref()
+            .map(|_| DataSnapshot::take(&game.data_path));
 
-        let resolved_tool_path = expand_user_path(&tool_path);
-        let mut command = if runtime_mode == ToolRuntimeMode::Proton {
+        let (exit_code, stdout, stderr, log_path) =
+            Self::run_tool_captured(tool.display_name(), &mut command).await?;
+        Ok(ExternalToolLaunchResult {
+            exit_code,
+            stdout,
+            stderr,
+            captured: self
+                .capture_external_tool_output(&game, &settings, snapshot)
+                .await?,
+            new_files: self.external_tool_new_files(&game, &settings).await,
+            log_path,
+        })
+    }
+
+    /// Launch a game executable (the game or its script extender loader) through Proton.
+    pub async fn launch_game_executable(
+        &self,
+        game: &Game,
+        executable: &Path,
+        args: &[String],
+    ) -> Result<i32> {
+        let (mut command, via) = if game.game_type.runs_natively() {
+            (tokio::process::Command::new(executable), "")
+        } else {
+            let proton_cmd = {
+                let config = self.config.read().await;
+                self.resolve_proton_launcher_from_config(&config)?
+            };
             let proton_prefix = game
                 .proton_prefix
                 .clone()
                 .ok_or_else(|| anyhow::anyhow!("Active game has no Proton prefix detected"))?;
-            let resolved_proton_cmd = expand_user_path(proton_cmd.as_deref().unwrap_or("proton"));
+            let resolved_proton_cmd = expand_user_path(&proton_cmd);
             let mut command = tokio::process::Command::new(&resolved_proton_cmd);
-            command.arg("run").arg(&resolved_tool_path);
-            Self::apply_proton_launch_env(
-                &mut command,
-                &game,
-                &proton_prefix,
-                &resolved_proton_cmd,
-            );
-            command
-        } else {
-            tokio::process::Command::new(&resolved_tool_path)
+            command.arg("run").arg(executable);
+            Self::apply_proton_launch_env(&mut command, game, &proton_prefix, &resolved_proton_cmd);
+            (command, " via Proton")
         };
-        for arg in args {
-            command.arg(arg);
-        }
-        if let Some(parent) = Path::new(&resolved_tool_path).parent() {
-            command.current_dir(parent);
+        command.args(args).current_dir(&game.install_path);
+
+        if let Err(e) = self.mods.record_session(game, SessionKind::Play).await {
+            tracing::warn!("Failed to record play session: {}", e);
         }
 
-        let output = command
-            .output()
+        let status = command
+            .status()
             .await
-            .with_context(|| format!("Failed to launch {} via Proton", tool.display_name()))?;
+            .with_context(|| format!("Failed to launch {}{}", executable.display(), via))?;
 
-        Ok(ExternalToolLaunchResult {
-            exit_code: output.status.code().unwrap_or_default(),
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        Ok(status.code().unwrap_or_default())
+    }
+
+    /// Check a launch of `exe`, or of the executable the enabled mods need,
+    /// against the mods enabled now or, with `profile`, those the profile
+    /// enables. Changes nothing.
+    pub async fn launch_check(
+        &self,
+        game: &Game,
+        exe: Option<&str>,
+        profile: Option<&str>,
+    ) -> Result<LaunchCheck> {
+        let requirements = match profile {
+            Some(name) => {
+                let profile = self.profiles.get_profile(&game.id, name).await?;
+                self.mods.launch_requirements_of(game, |m| {
+                    profile.mods.get(&m.name).is_some_and(|s| s.enabled)
+                })?
+            }
+            None => self.mods.launch_requirements(game)?,
+        };
+        let executable = exe
+            .map(str::to_string)
+            .unwrap_or_else(|| crate::mods::default_launch_executable(game, &requirements));
+        let problems = crate::mods::check_launch(game, &executable, &requirements);
+        Ok(LaunchCheck {
+            executable,
+            problems,
         })
     }
 
+    /// Get the game ready to launch after `check` passed (or with `force`):
+    /// switch to `profile`, deploy if mods or plugins changed since the last
+    /// deploy, then rewrite plugins.txt and loadorder.txt in the Proton
+    /// prefix. Returns the deploy stats if it deployed and the number of
+    /// enabled plugins.
+    pub async fn prepare_launch(
+        &self,
+        game: &Game,
+        check: &LaunchCheck,
+        profile: Option<&str>,
+        force: bool,
+    ) -> Result<(Option<crate::mods::DeploymentStats>, usize)> {
+        if let Some(problem) = check.problems.first().filter(|_| !force) {
+            anyhow::bail!(Conflict::new(format!(
+                "Not launching {}: {}",
+                check.executable, problem.message
+            )));
+        }
+        if let Some(profile) = profile {
+            self.profiles.switch_profile(&game.id, profile).await?;
+        }
+        let stats = if self.mods.deployment_outdated(game).await? {
+            Some(self.mods.deploy(game).await?)
+        } else {
+            None
+        };
+        let enabled = crate::plugins::sync_plugin_files(game)
+            .context("Failed to write plugins.txt into the Proton prefix")?;
+        Ok((stats, enabled))
+    }
+
     fn apply_proton_launch_env(
         command: &mut tokio::process::Command,
         game: &Game,
@@ -420,11 +872,9 @@ impl App {
             command.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", path);
         }
 
-        if let Some(game_type) = GameType::from_id(&game.id) {
-            let app_id = game_type.steam_app_id().to_string();
-            command.env("SteamAppId", &app_id);
-            command.env("SteamGameId", &app_id);
-        }
+        let app_id = game.steam_app_id.to_string();
+        command.env("SteamAppId", &app_id);
+        command.env("SteamGameId", &app_id);
     }
 
     fn infer_steam_client_install_path(proton_cmd: &str) -> Option<String> {
@@ -446,15 +896,23 @@ impl App {
         platform: GamePlatform,
         proton_prefix: Option<&str>,
     ) -> Result<()> {
-        if GameType::from_id(game_id).is_none() {
-            anyhow::bail!("Unknown game id '{}'", game_id);
+        let definitions = GameDefinition::load_dir(&self.config.read().await.paths.games_dir());
+        if GameType::from_id(game_id).is_none()
+            && !definitions
+                .iter()
+                .any(|d| d.id.eq_ignore_ascii_case(game_id))
+        {
+            anyhow::bail!(Invalid::new(format!("Unknown game id '{}'", game_id)));
         }
         let trimmed_path = install_path.trim();
         if trimmed_path.is_empty() {
-            anyhow::bail!("Install path cannot be empty");
+            anyhow::bail!(Invalid::new("Install path cannot be empty"));
         }
         if !Path::new(trimmed_path).exists() {
-            anyhow::bail!("Install path does not exist: {}", trimmed_path);
+            anyhow::bail!(Invalid::new(format!(
+                "Install path does not exist: {}",
+                trimmed_path
+            )));
         }
 
         let mut config = self.config.write().await;
@@ -485,9 +943,10 @@ impl App {
         }
         config.save().await?;
         let custom_games = config.custom_games.clone();
+        let definitions = GameDefinition::load_dir(&config.paths.games_dir());
         drop(config);
 
-        self.games = GameDetector::detect_all_with_custom(&custom_games).await;
+        self.games = GameDetector::detect_all_with_custom(&custom_games, &definitions).await;
         Ok(())
     }
 
@@ -503,21 +962,43 @@ impl App {
             !(entry.game_id.eq_ignore_ascii_case(game_id) && entry.install_path == install_path)
         });
         if config.custom_games.len() == before {
-            anyhow::bail!(
+            anyhow::bail!(Invalid::new(format!(
                 "No matching custom game path found for {} at {}",
-                game_id,
-                install_path
-            );
+                game_id, install_path
+            )));
         }
         config.save().await?;
         let custom_games = config.custom_games.clone();
+        let definitions = GameDefinition::load_dir(&config.paths.games_dir());
         drop(config);
 
-        self.games = GameDetector::detect_all_with_custom(&custom_games).await;
+        self.games = GameDetector::detect_all_with_custom(&custom_games, &definitions).await;
         Ok(())
     }
 }
 
+/// A directory from the config for display, or why it doesn't resolve
+pub fn dir_display(dir: &Result<std::path::PathBuf>) -> String {
+    match dir {
+        Ok(dir) => dir.display().to_string(),
+        Err(e) => format!("(unresolved: {})", e),
+    }
+}
+
+/// Refuse directory overrides using variables that don't exist
+fn check_path_template(path: Option<&str>) -> Result<()> {
+    let unknown = path
+        .map(crate::config::unknown_path_variables)
+        .unwrap_or_default();
+    if !unknown.is_empty() {
+        anyhow::bail!(Invalid::new(format!(
+            "Unknown path variable {{{}}}. Valid variables: {{game}}, {{profile}}, {{home}}",
+            unknown.join("}, {")
+        )));
+    }
+    Ok(())
+}
+
 fn find_runtime<'a>(runtimes: &'a [ProtonRuntime], selection: &str) -> Option<&'a ProtonRuntime> {
     runtimes.iter().find(|rt| {
         rt.id.eq_ignore_ascii_case(selection)
@@ -560,10 +1041,10 @@ impl App {
                 find_runtime(&runtimes, selected)
             };
             let Some(runtime) = resolved else {
-                anyhow::bail!(
+                anyhow::bail!(Invalid::new(format!(
                     "Configured Proton runtime '{}' was not detected. Run 'modsanity tool list-proton'.",
                     selected
-                );
+                )));
             };
             return Ok(runtime.proton_path.display().to_string());
         }
@@ -579,3 +1060,191 @@ fn expand_user_path(raw: &str) -> String {
     }
     raw.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{zip_bytes, MockNexus, Sandbox};
+    use crate::tui::harness::sandboxed_app;
+
+    #[tokio::test]
+    async fn test_prepare_launch_checks_before_switching_or_deploying() {
+        let sandbox = Sandbox::new().unwrap();
+        let mut app = sandboxed_app(&sandbox, None).await.unwrap();
+        let game = sandbox
+            .game()
+            .with_proton_prefix(sandbox.root().join("compatdata"));
+        app.games = vec![game.clone()];
+        let archive = sandbox
+            .write_archive("Tweaks-1-1-0.zip", &[("SKSE/Plugins/tweaks.dll", b"MZ")])
+            .unwrap();
+        app.mods
+            .install_from_archive(&game.id, &archive.to_string_lossy(), None, None, None, None)
+            .await
+            .unwrap();
+        app.profiles
+            .create_profile(&game.id, "Vanilla")
+            .await
+            .unwrap();
+
+        // SKSE isn't installed, so launching with the mod enabled is refused
+        // before the profile switch or the deploy
+        let refused = app.launch_check(&game, None, None).await.unwrap();
+        assert_eq!(refused.executable, "skse64_loader.exe");
+        assert_eq!(refused.problems.len(), 1);
+        let err = app
+            .prepare_launch(&game, &refused, Some("Vanilla"), false)
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<Conflict>().is_some());
+        assert_eq!(app.config.read().await.active_profile, None);
+        assert!(app.mods.deployment_outdated(&game).await.unwrap());
+
+        // The profile doesn't enable the mod, so its launch is fine
+        let check = app
+            .launch_check(&game, None, Some("Vanilla"))
+            .await
+            .unwrap();
+        assert_eq!(check.executable, game.executable);
+        assert!(check.problems.is_empty());
+        let (stats, _) = app
+            .prepare_launch(&game, &check, Some("Vanilla"), false)
+            .await
+            .unwrap();
+        assert!(stats.is_some());
+        assert_eq!(
+            app.config.read().await.active_profile.as_deref(),
+            Some("Vanilla")
+        );
+        assert!(!app.mods.deployment_outdated(&game).await.unwrap());
+        assert!(!game.data_path.join("SKSE/Plugins/tweaks.dll").exists());
+
+        // Forced launches go ahead anyway
+        assert!(app
+            .prepare_launch(&game, &refused, None, true)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reordering_or_excluding_outdates_the_deployment() {
+        let sandbox = Sandbox::new().unwrap();
+        let app = sandboxed_app(&sandbox, None).await.unwrap();
+        let game = sandbox.game();
+        for name in ["Alpha", "Bravo"] {
+            let archive = sandbox
+                .write_archive(
+                    &format!("{}-1-1-0.zip", name),
+                    &[("textures/shared.dds", name.as_bytes())],
+                )
+                .unwrap();
+            app.mods
+                .install_from_archive(
+                    &game.id,
+                    &archive.to_string_lossy(),
+                    None,
+                    None,
+                    None,
+                    Some(name),
+                )
+                .await
+                .unwrap();
+        }
+        app.mods.deploy(&game).await.unwrap();
+        assert!(!app.mods.deployment_outdated(&game).await.unwrap());
+
+        // Raising the loser changes which one wins textures/shared.dds
+        let mods = app.mods.list_mods(&game.id).await.unwrap();
+        let loser = mods.iter().min_by_key(|m| m.priority).unwrap();
+        let top = mods.iter().map(|m| m.priority).max().unwrap();
+        app.db.set_mod_priority(loser.id, top + 1).unwrap();
+        assert!(app.mods.deployment_outdated(&game).await.unwrap());
+
+        app.mods.deploy(&game).await.unwrap();
+        app.mods
+            .set_mod_exclusion(&game.id, "Alpha", "textures/**", true)
+            .await
+            .unwrap();
+        assert!(app.mods.deployment_outdated(&game).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_nexus_download_installs_the_chosen_file() {
+        let mock = MockNexus::start().await.unwrap();
+        mock.add_mod("skyrimspecialedition", 101, "Mock Weather")
+            .add_file(
+                "skyrimspecialedition",
+                101,
+                9001,
+                "Mock Weather-101-1-0.zip",
+                "1.0",
+                zip_bytes(&[("MockWeather.esp", b"TES4")]).unwrap(),
+            )
+            .add_file(
+                "skyrimspecialedition",
+                101,
+                9002,
+                "Mock Weather Lite-101-1-0.zip",
+                "1.0",
+                zip_bytes(&[("MockWeatherLite.esp", b"TES4")]).unwrap(),
+            );
+        let sandbox = Sandbox::new().unwrap();
+        let app = sandboxed_app(&sandbox, Some(&mock)).await.unwrap();
+
+        assert!(app.cmd_nexus_download(101, Some(1), true).await.is_err());
+        assert!(app.mods.list_mods("skyrimse").await.unwrap().is_empty());
+
+        app.cmd_nexus_download(101, Some(9002), true).await.unwrap();
+        let installed = app.mods.list_mods("skyrimse").await.unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].nexus_mod_id, Some(101));
+        assert_eq!(installed[0].nexus_file_id, Some(9002));
+        assert_eq!(mock.request_count("/files/9002/"), 1);
+        assert_eq!(mock.request_count("/files/9001/"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_local_catalog() {
+        let sandbox = Sandbox::new().unwrap();
+        let app = sandboxed_app(&sandbox, None).await.unwrap();
+
+        for (sort, source) in [
+            ("newest", "catalog"),
+            ("relevance", "web"),
+            ("downloads", "catalog"),
+            ("relevance", "catalog"),
+            ("relevance", "nexus"),
+        ] {
+            let err = app
+                .cmd_search("weather", source, sort, 10)
+                .await
+                .unwrap_err();
+            assert!(
+                err.downcast_ref::<Invalid>().is_some(),
+                "{} / {}: {}",
+                sort,
+                source,
+                err
+            );
+        }
+
+        app.db
+            .upsert_catalog_page(
+                "skyrimspecialedition",
+                &[crate::db::NexusCatalogRecord {
+                    game_domain: "skyrimspecialedition".to_string(),
+                    mod_id: 101,
+                    name: "Mock Weather".to_string(),
+                    summary: None,
+                    description: None,
+                    author: Some("Tester".to_string()),
+                    updated_time: Some(1_700_000_000),
+                    synced_at: String::new(),
+                }],
+            )
+            .unwrap();
+        app.cmd_search("weather", "catalog", "updated", 10)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,217 @@
+//! TUI notifications
+//!
+//! Status messages are queued as toasts instead of overwriting a single line,
+//! so a message from a background task isn't lost when another arrives right
+//! after it. Successes and info fade after a few seconds; errors stay until
+//! dismissed. Every message is also kept in a history the user can open.
+//! A failure can carry its full error chain, which a toast has no room for;
+//! the history opens it in a scrollable view.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a transient toast stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Toasts shown at once; older transient ones make room for new ones
+pub const MAX_TOASTS: usize = 4;
+
+/// Messages kept in the history view
+const HISTORY_CAPACITY: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Success,
+    Error,
+}
+
+impl NotificationLevel {
+    /// Level of a status message, from the icon `set_status_*` puts in front
+    /// of it or, for plain messages, from how it reads
+    pub fn of(message: &str) -> Self {
+        if message.starts_with('✓') {
+            return Self::Success;
+        }
+        if message.starts_with('✗') {
+            return Self::Error;
+        }
+        let lower = message.to_lowercase();
+        if lower.starts_with("failed") || lower.starts_with("error") || lower.contains(" error: ") {
+            Self::Error
+        } else {
+            Self::Info
+        }
+    }
+
+    /// Errors stay on screen until dismissed
+    pub fn is_sticky(self) -> bool {
+        self == Self::Error
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub message: String,
+    /// Full error chain behind the message, if it came from an error
+    pub detail: Option<String>,
+    /// Local time it was raised, for the history view
+    pub time: String,
+    raised: Instant,
+}
+
+/// Toasts on screen and the history of every message
+#[derive(Debug, Clone, Default)]
+pub struct Notifications {
+    /// Oldest first
+    toasts: Vec<Notification>,
+    /// Oldest first
+    history: VecDeque<Notification>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, message: String) {
+        self.push_detailed(message, None);
+    }
+
+    /// Push a message along with the longer text behind it
+    pub fn push_detailed(&mut self, message: String, detail: Option<String>) {
+        let level = NotificationLevel::of(&message);
+        let notification = Notification {
+            level,
+            message,
+            detail,
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            raised: Instant::now(),
+        };
+
+        // A repeated message restarts its toast rather than stacking
+        self.toasts
+            .retain(|t| !(t.message == notification.message && t.level == level));
+        self.toasts.push(notification.clone());
+        while self.toasts.len() > MAX_TOASTS {
+            let oldest = self
+                .toasts
+                .iter()
+                .position(|t| !t.level.is_sticky())
+                .unwrap_or(0);
+            self.toasts.remove(oldest);
+        }
+
+        self.history.push_back(notification);
+        if self.history.len() > HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+    }
+
+    /// Drop transient toasts shown longer than [`TOAST_DURATION`], returning
+    /// whether any were dropped
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let before = self.toasts.len();
+        self.toasts
+            .retain(|t| t.level.is_sticky() || now.duration_since(t.raised) < TOAST_DURATION);
+        self.toasts.len() != before
+    }
+
+    /// Dismiss the error toasts, returning how many there were
+    pub fn dismiss_errors(&mut self) -> usize {
+        let before = self.toasts.len();
+        self.toasts.retain(|t| !t.level.is_sticky());
+        before - self.toasts.len()
+    }
+
+    /// Drop the transient toasts, keeping errors
+    pub fn clear_transient(&mut self) {
+        self.toasts.retain(|t| t.level.is_sticky());
+    }
+
+    /// Toasts on screen, oldest first
+    pub fn toasts(&self) -> &[Notification] {
+        &self.toasts
+    }
+
+    /// Every recent message, oldest first
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Notification> + ExactSizeIterator {
+        self.history.iter()
+    }
+
+    /// The newest message still on screen
+    pub fn latest(&self) -> Option<&Notification> {
+        self.toasts.last()
+    }
+}
+
+/// An error and every cause under it, one per line, the way anyhow's debug
+/// output shows them but without a backtrace
+pub fn error_chain(err: &anyhow::Error) -> String {
+    let mut text = err.to_string();
+    let causes: Vec<String> = err.chain().skip(1).map(|c| c.to_string()).collect();
+    if !causes.is_empty() {
+        text.push_str("\n\nCaused by:");
+        for (i, cause) in causes.iter().enumerate() {
+            text.push_str(&format!("\n    {}: {}", i, cause));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_expire_and_errors_stick() {
+        let mut notifications = Notifications::default();
+        notifications.push("✓ Deployed 12 mods".to_string());
+        notifications.push("Failed to fetch files: timeout".to_string());
+        notifications.push("Queued SkyUI".to_string());
+        notifications.push("Queued SkyUI".to_string());
+
+        let levels: Vec<_> = notifications.toasts().iter().map(|t| t.level).collect();
+        assert_eq!(
+            levels,
+            vec![
+                NotificationLevel::Success,
+                NotificationLevel::Error,
+                NotificationLevel::Info
+            ]
+        );
+        assert_eq!(notifications.history().len(), 4);
+
+        assert!(notifications.expire(Instant::now() + TOAST_DURATION));
+        assert_eq!(notifications.toasts().len(), 1);
+        assert_eq!(
+            notifications.latest().map(|t| t.message.as_str()),
+            Some("Failed to fetch files: timeout")
+        );
+        assert_eq!(notifications.dismiss_errors(), 1);
+        assert!(notifications.latest().is_none());
+
+        // Errors outlast transient toasts when the stack is full
+        notifications.push("✗ Deploy failed".to_string());
+        for i in 0..MAX_TOASTS {
+            notifications.push(format!("Step {}", i));
+        }
+        assert_eq!(notifications.toasts().len(), MAX_TOASTS);
+        assert_eq!(notifications.toasts()[0].message, "✗ Deploy failed");
+    }
+
+    #[test]
+    fn test_error_chain() {
+        use anyhow::Context;
+
+        let err = std::fs::read("/nonexistent/modsanity")
+            .context("Failed to read archive")
+            .context("Install failed")
+            .unwrap_err();
+        let chain = error_chain(&err);
+        let lines: Vec<&str> = chain.lines().collect();
+        assert_eq!(lines[0], "Install failed");
+        assert_eq!(lines[2], "Caused by:");
+        assert_eq!(lines[3], "    0: Failed to read archive");
+        assert!(lines[4].starts_with("    1: "));
+
+        assert_eq!(error_chain(&anyhow::anyhow!("timeout")), "timeout");
+    }
+}
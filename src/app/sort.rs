@@ -0,0 +1,120 @@
+//! Mods screen sort orders
+//!
+//! The mod list is shown in priority order unless another column is picked.
+//! Each order falls back to priority for ties, so the list stays stable while
+//! mods are enabled or reordered.
+
+use crate::mods::{compare_versions, InstalledMod};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Column the mod list is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModSort {
+    #[default]
+    Priority,
+    Name,
+    Installed,
+    Size,
+    Version,
+    /// Mods with an update available first
+    Updates,
+}
+
+impl ModSort {
+    /// The next column, wrapping back to priority
+    pub fn next(self) -> Self {
+        match self {
+            Self::Priority => Self::Name,
+            Self::Name => Self::Installed,
+            Self::Installed => Self::Size,
+            Self::Size => Self::Version,
+            Self::Version => Self::Updates,
+            Self::Updates => Self::Priority,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Priority => "Priority",
+            Self::Name => "Name",
+            Self::Installed => "Installed",
+            Self::Size => "Size",
+            Self::Version => "Version",
+            Self::Updates => "Update available",
+        }
+    }
+
+    /// Sort `mods` by this column, ascending unless `reverse`. `updates`
+    /// holds the Nexus ids of mods with an update available.
+    pub fn sort<T>(self, mods: &mut [&InstalledMod], reverse: bool, updates: &HashMap<i64, T>) {
+        mods.sort_by(|a, b| {
+            let by_column = match self {
+                Self::Priority => Ordering::Equal,
+                Self::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                Self::Installed => a.installed_at.cmp(&b.installed_at),
+                Self::Size => a.size.cmp(&b.size),
+                Self::Version => compare_versions(&a.version, &b.version),
+                Self::Updates => {
+                    let has_update = |m: &InstalledMod| {
+                        m.nexus_mod_id.is_some_and(|id| updates.contains_key(&id))
+                    };
+                    has_update(b).cmp(&has_update(a))
+                }
+            };
+            let ordering = by_column.then(a.priority.cmp(&b.priority));
+            if reverse {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn installed(id: i64, name: &str, version: &str, size: u64) -> InstalledMod {
+        InstalledMod {
+            id,
+            name: name.to_string(),
+            version: version.to_string(),
+            author: None,
+            enabled: true,
+            priority: id as i32,
+            nexus_mod_id: Some(id * 100),
+            nexus_file_id: None,
+            file_count: 1,
+            install_path: PathBuf::new(),
+            category_id: None,
+            installed_at: format!("2026-01-0{}T00:00:00Z", 4 - id),
+            size,
+        }
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        let mods = [
+            installed(1, "USSEP", "4.3.10", 5_000),
+            installed(2, "alternate start", "4.3.9", 1_000),
+            installed(3, "SkyUI", "5.2", 3_000),
+        ];
+        let names = |sort: ModSort, reverse: bool| {
+            let mut list: Vec<&InstalledMod> = mods.iter().collect();
+            let updates = HashMap::from([(300, ())]);
+            sort.sort(&mut list, reverse, &updates);
+            list.iter().map(|m| m.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(ModSort::Priority, false), vec![1, 2, 3]);
+        assert_eq!(names(ModSort::Name, false), vec![2, 3, 1]);
+        assert_eq!(names(ModSort::Installed, false), vec![3, 2, 1]);
+        assert_eq!(names(ModSort::Size, true), vec![1, 3, 2]);
+        assert_eq!(names(ModSort::Version, false), vec![2, 1, 3]);
+        assert_eq!(names(ModSort::Updates, false), vec![3, 1, 2]);
+        assert_eq!(ModSort::Updates.next(), ModSort::Priority);
+    }
+}
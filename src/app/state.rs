@@ -1,9 +1,14 @@
 //! Application state management
 
+use super::filters;
+use super::groups::{group_mods, ModRow};
+use super::notifications::{error_chain, Notification, Notifications};
+use super::sort::ModSort;
+use super::undo::EditHistory;
 use crate::collections::Collection;
 use crate::db::{CategoryRecord, ModlistEntryRecord, ModlistRecord, NexusCatalogRecord};
 use crate::games::Game;
-use crate::mods::fomod::{FileInstruction, FomodInstaller, WizardState};
+use crate::mods::fomod::{FomodInstaller, InstallPlan, InstallPreview, WizardState};
 use crate::mods::InstalledMod;
 use crate::plugins::PluginInfo;
 use crate::profiles::Profile;
@@ -41,6 +46,16 @@ pub enum Screen {
     NexusCatalog,
     ModlistReview,
     ModlistEditor,
+    Extensions,
+    Storage,
+    IniDiff,
+    ModlistDiff,
+    ScriptOverrides,
+    Downloads,
+    History,
+    ToolOutput,
+    IniEditor,
+    Saves,
 }
 
 /// Modlist editor mode
@@ -60,8 +75,15 @@ pub enum UiMode {
 }
 
 /// Application state for TUI
-#[derive(Debug, Default)]
+///
+/// Owned by the event loop: background tasks publish changes as
+/// [`AppEvent`](super::events::AppEvent)s instead of locking it, and the
+/// renderer draws from a cloned snapshot.
+#[derive(Debug, Clone, Default)]
 pub struct AppState {
+    /// Incremented every time an event is applied
+    pub revision: u64,
+
     /// Currently active game
     pub active_game: Option<Game>,
 
@@ -81,6 +103,7 @@ pub struct AppState {
     pub selected_plugin_index: usize,
 
     /// Selected download index
+    pub selected_download_index: usize,
 
     /// Selected profile index
     pub selected_profile_index: usize,
@@ -102,8 +125,24 @@ pub struct AppState {
     /// Profiles (cached for display)
     pub profiles: Vec<Profile>,
 
-    /// Status message
-    pub status_message: Option<String>,
+    /// Status messages: toasts on screen and their history
+    pub notifications: Notifications,
+
+    /// Show the notification history overlay
+    pub show_notifications: bool,
+
+    /// Entries scrolled back in the notification history; the entry on top
+    /// is the one Enter opens
+    pub notifications_scroll: usize,
+
+    /// Notification opened in full from the history
+    pub error_detail: Option<Notification>,
+
+    /// Lines scrolled in `error_detail`
+    pub error_detail_scroll: u16,
+
+    /// Selected match in the command palette
+    pub palette_index: usize,
 
     /// Show help panel
     pub show_help: bool,
@@ -123,6 +162,9 @@ pub struct AppState {
     /// Current input buffer
     pub input_buffer: String,
 
+    /// Launch option edited by `InputMode::ExternalToolOptionsInput`
+    pub tool_option_field: ToolOptionField,
+
     /// Should quit
     pub should_quit: bool,
 
@@ -132,8 +174,8 @@ pub struct AppState {
     /// Error message to display
     pub error_message: Option<String>,
 
-    /// Recent raw command output lines (stderr/stdout snippets).
-    pub command_output_log: Vec<String>,
+    /// Log pane above the status bar
+    pub log_pane: LogPane,
 
     /// Installation progress (0-100)
     pub installation_progress: Option<InstallProgress>,
@@ -141,8 +183,11 @@ pub struct AppState {
     /// Whether a bulk install task is currently active.
     pub bulk_install_running: bool,
 
-    /// Request cooperative cancellation of a bulk install task.
-    pub bulk_install_cancel_requested: bool,
+    /// Cooperative cancellation flag shared with the running bulk install task.
+    pub bulk_install_cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+
+    /// FOMOD installers from a bulk install waiting for the wizard, answered one at a time.
+    pub pending_fomod_installs: std::collections::VecDeque<crate::mods::FomodInstallContext>,
 
     /// Categorization progress
     pub categorization_progress: Option<CategorizationProgress>,
@@ -165,12 +210,38 @@ pub struct AppState {
     /// Selected category index in category list
     pub selected_category_index: usize,
 
-    /// Active category filter (None = show all, Some(id) = filter by category)
-    pub category_filter: Option<i64>,
+    /// Category groups collapsed on the Mods screen (None = uncategorized)
+    pub collapsed_categories: std::collections::HashSet<Option<i64>>,
 
     /// Search query for filtering mods by name
     pub mod_search_query: String,
 
+    /// Saved filters offered on the Mods screen: built-in views, then config
+    pub mod_filters: Vec<crate::config::ModFilterConfig>,
+
+    /// Index into `mod_filters` of the filter applied to the mod list
+    pub active_mod_filter: Option<usize>,
+
+    /// Highlighted entry of the filter dropdown; 0 is "All mods"
+    pub mod_filter_index: usize,
+
+    /// Column the mod list is sorted by
+    pub mod_sort: ModSort,
+
+    /// Sort the mod list descending
+    pub mod_sort_reverse: bool,
+
+    /// Nexus page of the mod on the details screen, as (mod ID, page)
+    pub mod_page: Option<(i64, crate::nexus::ModPageText)>,
+    pub mod_page_loading: bool,
+    pub mod_details_scroll: u16,
+
+    /// Mod IDs marked for bulk operations on the Mods screen
+    pub marked_mod_ids: std::collections::HashSet<i64>,
+
+    /// Anchor index (in the filtered list) for range marking with `V`
+    pub mod_mark_anchor: Option<usize>,
+
     /// Search query for filtering plugins by name
     pub plugin_search_query: String,
 
@@ -189,6 +260,18 @@ pub struct AppState {
     /// Whether we're currently checking for updates
     pub checking_updates: bool,
 
+    /// Mods whose Nexus file was archived or deleted (mod DB id -> flag)
+    pub unavailable_files: std::collections::HashMap<i64, crate::db::NexusFileStatusRecord>,
+
+    /// Mods whose plugins need DLC the game doesn't have (mod DB id -> details)
+    pub missing_dlc: std::collections::HashMap<i64, crate::mods::MissingDlc>,
+
+    /// Script extender built for a different game version than the one installed
+    pub script_extender_warning: Option<String>,
+
+    /// What changed since the last play session, shown after a long enough gap
+    pub since_last_played: Option<crate::mods::SinceLastPlayed>,
+
     /// Browse/search results from Nexus Mods
     pub browse_results: Vec<crate::nexus::graphql::ModSearchResult>,
 
@@ -216,6 +299,9 @@ pub struct AppState {
     /// Whether we're showing default browse content (top mods) vs search results
     pub browse_showing_default: bool,
 
+    /// Whether the browse results hold a single random "surprise me" pick
+    pub browse_surprise: bool,
+
     /// Files available for the selected browse mod
     pub browse_mod_files: Vec<crate::nexus::graphql::ModFile>,
 
@@ -228,8 +314,12 @@ pub struct AppState {
     /// Context for the mod being downloaded (mod_id, mod_name, game_domain)
     pub download_context: Option<DownloadContext>,
 
-    /// Download progress (bytes downloaded, total bytes)
-    pub download_progress: Option<DownloadProgress>,
+    /// Downloads in flight and recently finished, copied from
+    /// [`App::transfers`](super::App::transfers) when they change
+    pub downloads: Vec<crate::queue::Transfer>,
+
+    /// [`Transfers::revision`](crate::queue::Transfers::revision) `downloads` was copied at
+    pub downloads_revision: u64,
 
     /// Whether the user is in reorder mode on the Load Order screen
     pub reorder_mode: bool,
@@ -243,15 +333,27 @@ pub struct AppState {
     /// Whether the plugin list has unsaved changes
     pub plugin_dirty: bool,
 
+    /// Undo history of unsaved plugin edits
+    pub plugin_history: EditHistory<PluginInfo>,
+
     /// Working copy of mods for reordering (snapshot, not persisted until save)
     pub load_order_mods: Vec<InstalledMod>,
 
     /// Cached conflict data for the load order screen
     pub load_order_conflicts: Vec<crate::mods::ModConflict>,
 
+    /// Cached conflicts grouped by asset type (meshes, textures, interface, ...)
+    pub load_order_asset_conflicts: Vec<crate::mods::AssetTypeConflicts>,
+
+    /// Show conflicts by asset type instead of by mod pair
+    pub load_order_group_by_type: bool,
+
     /// Whether the load order has unsaved changes
     pub load_order_dirty: bool,
 
+    /// Undo history of load order edits
+    pub load_order_history: EditHistory<InstalledMod>,
+
     /// FOMOD wizard state (when showing full wizard UI)
     pub fomod_wizard_state: Option<FomodWizardState>,
 
@@ -261,12 +363,20 @@ pub struct AppState {
     pub import_results: Vec<crate::import::MatchResult>,
     pub selected_import_index: usize,
     pub import_progress: Option<ImportProgress>,
+    /// Report of the last MO2 instance import or dry run
+    pub mo2_import_report: Vec<String>,
 
     /// Queue state
     pub queue_entries: Vec<crate::queue::QueueEntry>,
     pub selected_queue_index: usize,
     pub selected_queue_alternative_index: usize,
     pub queue_processing: bool,
+    /// Processing of the current batch is paused
+    pub queue_paused: bool,
+    /// Cancel of the current batch was requested
+    pub queue_cancelling: bool,
+    /// Speed and ETA of the batch being processed
+    pub queue_rate: Option<crate::queue::QueueRate>,
 
     /// Nexus catalog state
     pub catalog_game_domain: String,
@@ -297,6 +407,169 @@ pub struct AppState {
     pub catalog_search_query: String,
     pub catalog_browse_offset: i64,
     pub catalog_total_count: i64,
+
+    /// Extensions screen state
+    pub selected_extension_index: usize,
+    pub extension_output_title: Option<String>,
+    pub extension_output: Vec<String>,
+    pub extension_running: bool,
+
+    /// Storage screen state
+    pub storage_report: Option<crate::mods::DiskUsageReport>,
+    pub storage_scanning: bool,
+    pub selected_storage_index: usize,
+
+    /// INI diff screen state
+    pub ini_diff_profile: String,
+    pub ini_diff_target_index: usize,
+    pub ini_diff_entries: Vec<crate::profiles::IniDiffEntry>,
+    pub ini_diff_scroll: usize,
+
+    /// Modlist diff screen state
+    pub modlist_diff_left: String,
+    pub modlist_diff_target_index: usize,
+    pub modlist_diff: Option<crate::import::ModlistDiff>,
+    pub modlist_diff_scroll: usize,
+
+    /// Script overrides screen state
+    pub script_report: Option<crate::mods::ScriptReport>,
+    pub script_scanning: bool,
+    pub selected_script_index: usize,
+
+    /// History screen state
+    pub activity_entries: Vec<crate::activity::ActivityEntry>,
+    pub activity_filter: Option<crate::activity::Activity>,
+    pub selected_activity_index: usize,
+
+    /// Tool Output screen state, newest launch first
+    pub tool_runs: Vec<ToolRun>,
+    pub selected_tool_run: usize,
+    pub tool_output_scroll: usize,
+
+    /// INI editor screen state: the file shown is
+    /// `ini_files()[ini_file_index]` of the active game
+    pub ini_file_index: usize,
+    pub ini_entries: Vec<crate::profiles::IniEntry>,
+    pub selected_ini_entry: usize,
+    /// Newest backup of the file shown
+    pub ini_last_backup: Option<std::path::PathBuf>,
+
+    /// Saves screen state, newest save first
+    pub saves: Vec<crate::saves::SaveGame>,
+    pub selected_save_index: usize,
+    /// Plugins the game loads now (lowercased), to check saves against
+    pub save_loaded_plugins: std::collections::HashSet<String>,
+}
+
+/// Launch option of a built-in tool edited from Settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToolOptionField {
+    #[default]
+    Args,
+    WorkingDir,
+    Env,
+}
+
+impl ToolOptionField {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ToolOptionField::Args => "default arguments",
+            ToolOptionField::WorkingDir => "working directory",
+            ToolOptionField::Env => "environment",
+        }
+    }
+}
+
+/// Tool launches kept on the Tool Output screen
+const TOOL_RUNS_KEPT: usize = 20;
+
+/// Output of one tool launched from the TUI
+#[derive(Debug, Clone)]
+pub struct ToolRun {
+    pub tool: String,
+    pub started_at: chrono::DateTime<chrono::Local>,
+    /// `None` when the tool failed to start
+    pub exit_code: Option<i32>,
+    /// Output lines, flagged when they came from stderr
+    pub lines: Vec<(bool, String)>,
+    pub log_path: Option<PathBuf>,
+}
+
+impl ToolRun {
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            started_at: chrono::Local::now(),
+            exit_code: None,
+            lines: Vec::new(),
+            log_path: None,
+        }
+    }
+
+    /// Append captured output, dropping carriage returns
+    pub fn push_output(&mut self, stderr: bool, text: &str) {
+        self.lines.extend(
+            text.lines()
+                .map(|line| (stderr, line.replace('\r', "")))
+                .filter(|(_, line)| !line.trim().is_empty()),
+        );
+    }
+}
+
+/// Scrollable view of recent log records, see [`crate::logging::recent_records`]
+#[derive(Debug, Clone)]
+pub struct LogPane {
+    pub visible: bool,
+    /// Lines scrolled back from the newest; 0 follows new records
+    pub scroll: usize,
+    /// Least severe level shown
+    pub level: tracing::Level,
+}
+
+impl Default for LogPane {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            scroll: 0,
+            level: tracing::Level::INFO,
+        }
+    }
+}
+
+impl LogPane {
+    /// Show the pane, following new records
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.scroll = 0;
+    }
+
+    pub fn toggle(&mut self) {
+        if self.visible {
+            self.visible = false;
+        } else {
+            self.show();
+        }
+    }
+
+    /// Info, warn, error, everything, then back to info
+    pub fn cycle_level(&mut self) {
+        use tracing::Level;
+        self.level = match self.level {
+            Level::INFO => Level::WARN,
+            Level::WARN => Level::ERROR,
+            Level::ERROR => Level::TRACE,
+            _ => Level::INFO,
+        };
+        self.scroll = 0;
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_add(lines);
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
 }
 
 /// Context for an active download
@@ -306,14 +579,8 @@ pub struct DownloadContext {
     pub mod_name: String,
     pub game_domain: String,
     pub game_id: i64,
-}
-
-/// Download progress information
-#[derive(Debug, Clone)]
-pub struct DownloadProgress {
-    pub file_name: String,
-    pub downloaded_bytes: u64,
-    pub total_bytes: u64,
+    /// Queue entry whose file is being chosen; the pick is stored instead of downloaded
+    pub queue_entry_id: Option<i64>,
 }
 
 /// Installation progress information
@@ -363,7 +630,7 @@ pub struct ImportProgress {
 }
 
 /// FOMOD wizard state
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FomodWizardState {
     /// The FOMOD installer being used
     pub installer: FomodInstaller,
@@ -385,8 +652,10 @@ pub struct FomodWizardState {
     pub mod_name: String,
     /// Staging path where mod files are extracted
     pub staging_path: PathBuf,
-    /// Preview of files to install (computed lazily)
-    pub preview_files: Option<Vec<FileInstruction>>,
+    /// Preview of files to install (computed when the summary is shown)
+    pub preview_files: Option<InstallPreview>,
+    /// First preview line shown in the summary
+    pub preview_scroll: usize,
     /// If Some, this is a reconfiguration of existing mod with this ID
     pub existing_mod_id: Option<i64>,
 
@@ -426,11 +695,39 @@ impl FomodWizardState {
             mod_name,
             staging_path,
             preview_files: None,
+            preview_scroll: 0,
             existing_mod_id,
             phase: WizardPhase::Overview,
         }
     }
 
+    /// Recompute the file preview for the current selections. When
+    /// reconfiguring, the staging folder holds the installed version, so the
+    /// preview is compared against it.
+    pub fn refresh_preview(&mut self) {
+        self.preview_scroll = 0;
+        self.preview_files = match InstallPlan::from_wizard_state(
+            &self.wizard,
+            &self.installer,
+            self.mod_name.clone(),
+            &self.staging_path,
+            &self.staging_path,
+        ) {
+            Ok(plan) => {
+                let current = self.existing_mod_id.map(|_| self.staging_path.as_path());
+                Some(InstallPreview::from_plan(
+                    &plan,
+                    &self.staging_path,
+                    current,
+                ))
+            }
+            Err(e) => {
+                tracing::warn!("Failed to build FOMOD preview: {}", e);
+                None
+            }
+        };
+    }
+
     /// Get the current install step
     pub fn current_install_step(&self) -> Option<&crate::mods::fomod::InstallStep> {
         self.installer.steps().get(self.current_step)
@@ -519,8 +816,6 @@ impl AppState {
     pub fn goto(&mut self, screen: Screen) {
         self.previous_screen = Some(self.current_screen);
         self.current_screen = screen;
-        // Clear status message when navigating to avoid stale messages
-        self.status_message = None;
     }
 
     /// Go back to previous screen
@@ -530,9 +825,15 @@ impl AppState {
         }
     }
 
-    /// Set status message
+    /// Show a status message as a toast. Errors stay until dismissed, see
+    /// [`NotificationLevel::of`](super::notifications::NotificationLevel::of)
     pub fn set_status(&mut self, msg: impl Into<String>) {
-        self.status_message = Some(msg.into());
+        self.notifications.push(msg.into());
+    }
+
+    /// The newest status message still on screen
+    pub fn status_message(&self) -> Option<&str> {
+        self.notifications.latest().map(|n| n.message.as_str())
     }
 
     /// Set status message with success icon
@@ -545,27 +846,66 @@ impl AppState {
         self.set_status(format!("✗ {}", msg.into()));
     }
 
+    /// Report a failure: `context` and the error go on the status line, and
+    /// the full error chain is kept for the history's detail view
+    pub fn set_status_error_chain(&mut self, context: impl Into<String>, err: &anyhow::Error) {
+        self.notifications.push_detailed(
+            format!("✗ {}: {}", context.into(), err),
+            Some(error_chain(err)),
+        );
+    }
+
     /// Set status message with info icon
     pub fn set_status_info(&mut self, msg: impl Into<String>) {
         self.set_status(format!("ℹ {}", msg.into()));
     }
 
-    /// Clear status message
+    /// Offer a newly downloaded archive for install. The install prompt only
+    /// opens when nothing else is waiting for input; otherwise it's a toast.
+    pub fn offer_download(&mut self, path: std::path::PathBuf) {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let busy = self.input_mode != InputMode::Normal
+            || self.show_confirm.is_some()
+            || self.current_screen == Screen::FomodWizard;
+        if busy {
+            self.set_status_info(format!("New download: {}", name));
+            return;
+        }
+        self.input_mode = InputMode::ModInstallPath;
+        self.input_buffer = path.to_string_lossy().to_string();
+        self.set_status_info(format!(
+            "New download: {} - Enter to install, Esc to skip",
+            name
+        ));
+    }
+
+    /// Clear transient status messages; errors stay until dismissed
     pub fn clear_status(&mut self) {
-        self.status_message = None;
+        self.notifications.clear_transient();
     }
 
+    /// Log a line of tool or command output and bring up the log pane
     pub fn push_command_output_line(&mut self, line: impl Into<String>) {
         let line = line.into().replace('\r', "");
         let trimmed = line.trim();
         if trimmed.is_empty() {
             return;
         }
-        self.command_output_log.push(trimmed.to_string());
-        const MAX_LOG_LINES: usize = 8;
-        if self.command_output_log.len() > MAX_LOG_LINES {
-            let overflow = self.command_output_log.len() - MAX_LOG_LINES;
-            self.command_output_log.drain(0..overflow);
+        tracing::info!(target: "modsanity::output", "{}", trimmed);
+        self.log_pane.show();
+    }
+
+    /// Record a finished tool launch and show it on the Tool Output screen
+    pub fn push_tool_run(&mut self, run: ToolRun) {
+        self.tool_runs.insert(0, run);
+        self.tool_runs.truncate(TOOL_RUNS_KEPT);
+        self.selected_tool_run = 0;
+        self.tool_output_scroll = 0;
+        if self.current_screen != Screen::ToolOutput {
+            self.goto(Screen::ToolOutput);
         }
     }
 
@@ -575,6 +915,67 @@ impl AppState {
         }
     }
 
+    /// Saved filter applied to the Mods screen, if any
+    pub fn active_filter(&self) -> Option<&crate::config::ModFilterConfig> {
+        self.active_mod_filter.and_then(|i| self.mod_filters.get(i))
+    }
+
+    /// Rows of the Mods screen: mods matching the search and the active
+    /// filter, in the chosen sort order, grouped under category headers.
+    /// `selected_mod_index` indexes into this.
+    pub fn mod_rows(&self) -> Vec<ModRow<'_>> {
+        let search_lower = self.mod_search_query.to_lowercase();
+        let mut mods: Vec<&InstalledMod> = self
+            .installed_mods
+            .iter()
+            .filter(|m| search_lower.is_empty() || m.name.to_lowercase().contains(&search_lower))
+            .filter(|m| {
+                self.active_filter().is_none_or(|filter| {
+                    filters::matches(filter, m, &self.categories, &self.available_updates)
+                })
+            })
+            .collect();
+        self.mod_sort
+            .sort(&mut mods, self.mod_sort_reverse, &self.available_updates);
+        group_mods(&mods, &self.categories, &self.collapsed_categories)
+    }
+
+    /// The mod on the selected row, if it isn't a category header
+    pub fn selected_mod(&self) -> Option<&InstalledMod> {
+        self.mod_rows()
+            .get(self.selected_mod_index)
+            .and_then(ModRow::as_mod)
+    }
+
+    /// Row of the mod with this ID, expanding its group if collapsed
+    pub fn reveal_mod(&mut self, id: i64) -> Option<usize> {
+        let category_id = self
+            .installed_mods
+            .iter()
+            .find(|m| m.id == id)
+            .map(|m| m.category_id)?;
+        self.collapsed_categories.remove(&category_id);
+        // Mods in categories that no longer exist are grouped as uncategorized
+        self.collapsed_categories.remove(&None);
+        self.mod_rows()
+            .iter()
+            .position(|r| r.as_mod().is_some_and(|m| m.id == id))
+    }
+
+    /// Mods currently marked for bulk operations, in display order
+    pub fn marked_mods(&self) -> Vec<&InstalledMod> {
+        self.installed_mods
+            .iter()
+            .filter(|m| self.marked_mod_ids.contains(&m.id))
+            .collect()
+    }
+
+    /// Clear all bulk-operation marks and any pending range anchor
+    pub fn clear_mod_marks(&mut self) {
+        self.marked_mod_ids.clear();
+        self.mod_mark_anchor = None;
+    }
+
     pub fn toggle_ui_mode(&mut self) {
         self.ui_mode = match self.ui_mode {
             UiMode::Guided => UiMode::Advanced,
@@ -599,6 +1000,7 @@ pub enum InputMode {
     StagingDirectoryInput,
     ProtonCommandInput,
     ExternalToolPathInput,
+    ExternalToolOptionsInput,
     NexusApiKeyInput,
     FomodComponentSelection,
     CollectionPath,
@@ -614,6 +1016,16 @@ pub enum InputMode {
     ModlistAddCatalogInput,
     ModlistAddDirectoryInput,
     QueueManualModIdInput,
+    /// Ctrl+P command palette; `input_buffer` holds the query
+    CommandPalette,
+    /// Saved filter dropdown on the Mods screen
+    ModFilterPicker,
+    /// Name for saving the current Mods view as a filter
+    ModFilterNameInput,
+    /// New value for the selected key on the INI editor screen
+    IniValueInput,
+    /// `[Section] key=value` to set on the INI editor screen
+    IniKeyInput,
 }
 
 /// Confirmation dialog
@@ -630,11 +1042,37 @@ pub struct ConfirmDialog {
 #[derive(Debug, Clone)]
 pub enum ConfirmAction {
     DeleteMod(String),
+    DeleteMods(Vec<String>),
+    RollbackMod(String),
+    ReinstallMod(String),
     DeleteProfile(String),
+    /// Store the game's current INI files as the profile's own
+    SaveProfileIni(String),
+    /// Drop the profile's own INI files
+    ClearProfileIni(String),
+    /// Give the profile its own save folder (true) or the shared one
+    SetProfileSaves(String, bool),
     Deploy,
     Purge,
     ClearQueue,
     LoadModlist(String),
+    CleanStaleArchives,
+    CleanOrphanedStaging,
+    ApplyIniPreset {
+        profile: String,
+        preset: String,
+    },
+    UpdateAllMods,
+    /// Deploy the stale deployment, then launch the tool
+    DeployAndLaunchTool(crate::config::ToolRef),
+    /// Move the files tools left in Data since the last deploy into this mod
+    CaptureToolOutput(String),
+    /// Run xEdit QuickAutoClean on these plugins, one at a time
+    CleanPlugins(Vec<crate::plugins::clean::DirtyPlugin>),
+    /// Put this INI backup back in place
+    RestoreIniBackup(std::path::PathBuf),
+    /// Delete this save and its co-save
+    DeleteSave(std::path::PathBuf),
     // Will be added in Phase 4 when we implement the planner
     // ExecuteFomodPlan(InstallPlan),
 }
@@ -646,6 +1084,8 @@ pub struct RequirementsDialog {
     pub mod_name: String,
     pub missing_mods: Vec<crate::nexus::graphql::ModRequirement>,
     pub dlc_requirements: Vec<crate::nexus::graphql::ModRequirement>,
+    /// Whether each DLC requirement is installed; `None` when unknown
+    pub dlc_installed: Vec<Option<bool>>,
     pub installed_count: usize,
     pub selected_index: usize,
     pub game_domain: String,
@@ -672,3 +1112,23 @@ pub struct CatalogProgress {
     pub total_count: i64,    // Total mods in catalog
     pub current_offset: i32, // Current offset position
 }
+
+impl CatalogProgress {
+    /// Progress as reported by the populate callback
+    pub fn new(pages: i32, inserted: i64, updated: i64, total: i64, offset: i32) -> Self {
+        Self {
+            pages_fetched: pages,
+            mods_inserted: inserted,
+            mods_updated: updated,
+            current_page: pages + 1,
+            total_count: total,
+            current_offset: offset,
+        }
+    }
+
+    /// Offset against the catalog size, once the size is known
+    pub fn percent(&self) -> Option<u16> {
+        (self.total_count > 0)
+            .then(|| (self.current_offset as i64 * 100 / self.total_count).clamp(0, 100) as u16)
+    }
+}
@@ -0,0 +1,132 @@
+//! Undo history for list editing
+//!
+//! The Plugins and Load Order screens edit a working copy of a list until it
+//! is saved. Before each edit the screen records the list as it was, so a
+//! stray "disable all" or a bad sort can be undone with `u` and redone with
+//! Ctrl+R instead of being rebuilt by hand.
+
+use std::sync::Arc;
+
+/// Edits kept per list; the oldest are forgotten first
+const CAPACITY: usize = 100;
+
+/// A list as it was before an edit, with what the edit was
+#[derive(Debug)]
+struct Snapshot<T> {
+    label: String,
+    items: Arc<Vec<T>>,
+}
+
+// Derived Clone would require `T: Clone` for the Arc
+impl<T> Clone for Snapshot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            items: Arc::clone(&self.items),
+        }
+    }
+}
+
+/// Undo and redo stacks for one list. Snapshots are shared, so cloning the
+/// history with the rest of `AppState` stays cheap.
+#[derive(Debug, Clone)]
+pub struct EditHistory<T> {
+    undo: Vec<Snapshot<T>>,
+    redo: Vec<Snapshot<T>>,
+}
+
+impl<T> Default for EditHistory<T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone> EditHistory<T> {
+    /// Record `items` as they are before the edit described by `label`.
+    /// A new edit discards anything that could be redone.
+    pub fn record(&mut self, label: impl Into<String>, items: &[T]) {
+        self.undo.push(Snapshot {
+            label: label.into(),
+            items: Arc::new(items.to_vec()),
+        });
+        if self.undo.len() > CAPACITY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Undo the last edit: replaces `items` with the list before it and
+    /// returns the edit's label
+    pub fn undo(&mut self, items: &mut Vec<T>) -> Option<String> {
+        let snapshot = self.undo.pop()?;
+        let current = std::mem::replace(items, snapshot.items.as_ref().clone());
+        self.redo.push(Snapshot {
+            label: snapshot.label.clone(),
+            items: Arc::new(current),
+        });
+        Some(snapshot.label)
+    }
+
+    /// Redo the last undone edit, returning its label
+    pub fn redo(&mut self, items: &mut Vec<T>) -> Option<String> {
+        let snapshot = self.redo.pop()?;
+        let current = std::mem::replace(items, snapshot.items.as_ref().clone());
+        self.undo.push(Snapshot {
+            label: snapshot.label.clone(),
+            items: Arc::new(current),
+        });
+        Some(snapshot.label)
+    }
+
+    /// Forget every edit, e.g. when the list is reloaded from disk
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = EditHistory::default();
+        let mut items = vec!["a", "b", "c"];
+
+        history.record("disable all", &items);
+        items = vec!["x"];
+        history.record("sort", &items);
+        items = vec!["y"];
+
+        assert_eq!(history.undo(&mut items).as_deref(), Some("sort"));
+        assert_eq!(items, vec!["x"]);
+        assert_eq!(history.undo(&mut items).as_deref(), Some("disable all"));
+        assert_eq!(items, vec!["a", "b", "c"]);
+        assert!(history.undo(&mut items).is_none());
+
+        assert_eq!(history.redo(&mut items).as_deref(), Some("disable all"));
+        assert_eq!(items, vec!["x"]);
+
+        // A fresh edit drops the redo stack
+        history.record("move", &items);
+        assert!(!history.can_redo());
+        assert!(history.can_undo());
+
+        for _ in 0..CAPACITY + 10 {
+            history.record("move", &items);
+        }
+        assert_eq!(history.undo.len(), CAPACITY);
+    }
+}
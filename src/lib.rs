@@ -6,21 +6,26 @@
 //! - FOMOD installer support
 //! - Plugin load order management
 //! - Profile system for different mod configurations
+//!
+//! The mod management logic lives in the `modsanity-core` crate; this crate
+//! is the TUI/CLI frontend built on top of it. Core modules are re-exported
+//! here so existing `modsanity::db`, `modsanity::mods`, etc. paths keep working.
+
+#![allow(clippy::collapsible_match, clippy::type_complexity)]
 
 pub const APP_VERSION: &str = "0.1.7";
 
 pub mod app;
-pub mod collections;
-pub mod config;
-pub mod db;
-pub mod games;
-pub mod import;
-pub mod mods;
-pub mod nexus;
-pub mod plugins;
-pub mod profiles;
-pub mod queue;
+pub mod logging;
 pub mod tui;
 
+pub use modsanity_core::{
+    activity, collections, config, daemon, db, error, extensions, games, import, lock, mods, nexus,
+    plugins, profiles, queue, saves,
+};
+
+#[cfg(any(test, feature = "test-harness"))]
+pub use modsanity_core::testing;
+
 pub use app::App;
 pub use config::Config;
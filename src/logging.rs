@@ -0,0 +1,507 @@
+//! Log file, rotation and runtime verbosity
+//!
+//! Logs go to `~/.modsanity/modsanity.log`. Once it grows past
+//! `[logging] max_size_mb` it becomes `modsanity.log.1` (older logs shift up
+//! to `max_files`), and rotated logs older than `max_age_days` are deleted.
+//! The TUI can raise the level to debug for the current session without a
+//! restart. The most recent records are also kept in memory for the TUI's log
+//! pane, so nothing needs to be written to the terminal behind it. Output of
+//! tools launched from the TUI is saved per launch under `tools/`.
+
+use crate::config::LoggingConfig;
+use anyhow::{bail, Context, Result};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::{
+    filter::LevelFilter, fmt, layer::Context as LayerContext, layer::SubscriberExt, reload,
+    util::SubscriberInitExt, EnvFilter, Layer, Registry,
+};
+
+const LOG_FILE_NAME: &str = "modsanity.log";
+const DEBUG_FILTER: &str = "modsanity=debug";
+/// Lines kept for the log pane
+const RECENT_CAPACITY: usize = 2000;
+/// Tool output logs kept in [`tool_log_dir`]; older ones are deleted
+const TOOL_LOGS_KEPT: usize = 50;
+
+/// Filter set at startup and the handle to swap it at runtime
+struct LevelControl {
+    handle: reload::Handle<EnvFilter, Registry>,
+    base: String,
+}
+
+static LEVEL: OnceLock<LevelControl> = OnceLock::new();
+static DEBUG: AtomicBool = AtomicBool::new(false);
+static RECENT: Mutex<VecDeque<LogRecord>> = Mutex::new(VecDeque::new());
+
+/// Directory holding the log files
+pub fn log_dir() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".modsanity")
+}
+
+/// The current log file
+pub fn log_file() -> PathBuf {
+    log_dir().join(LOG_FILE_NAME)
+}
+
+/// Directory holding the output of each tool launch
+pub fn tool_log_dir() -> PathBuf {
+    log_dir().join("tools")
+}
+
+/// Save a tool launch's output as `<tool>-<time>.log` in `dir`, deleting the
+/// oldest logs past `TOOL_LOGS_KEPT`. Returns the new file.
+pub fn save_tool_log(
+    dir: &Path,
+    tool: &str,
+    exit_code: i32,
+    stdout: &str,
+    stderr: &str,
+) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let slug: String = tool
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let now = chrono::Local::now();
+    let stem = format!("{}-{}", slug, now.format("%Y%m%d-%H%M%S"));
+    let mut path = dir.join(format!("{}.log", stem));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.log", stem, n));
+        n += 1;
+    }
+
+    let mut content = format!(
+        "# {} at {}, exit code {}\n",
+        tool,
+        now.to_rfc3339(),
+        exit_code
+    );
+    for (name, text) in [("stdout", stdout), ("stderr", stderr)] {
+        if !text.trim().is_empty() {
+            let _ = write!(content, "\n## {}\n{}", name, text);
+            if !text.ends_with('\n') {
+                content.push('\n');
+            }
+        }
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let mut logs: Vec<(SystemTime, PathBuf)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "log"))
+                .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+                .collect()
+        })
+        .unwrap_or_default();
+    if logs.len() > TOOL_LOGS_KEPT {
+        logs.sort();
+        for (_, old) in &logs[..logs.len() - TOOL_LOGS_KEPT] {
+            if old != &path {
+                let _ = fs::remove_file(old);
+            }
+        }
+    }
+    Ok(path)
+}
+
+/// How CLI runs echo log records to stderr
+#[derive(Debug, Clone, Copy)]
+pub struct StderrLog {
+    /// Only warnings and errors (`--quiet`); the log file keeps everything
+    pub quiet: bool,
+    /// ANSI colors, off with `--no-color` or `NO_COLOR`
+    pub color: bool,
+}
+
+/// Install the global subscriber: the rotating log file, plus stderr for CLI runs
+pub fn init(verbosity: u8, stderr: Option<StderrLog>, config: &LoggingConfig) {
+    let filter = match verbosity {
+        0 => "modsanity=info",
+        1 => "modsanity=debug",
+        2 => "modsanity=trace",
+        _ => "trace",
+    };
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| filter.into());
+    let base = env_filter.to_string();
+    let (env_filter, handle) = reload::Layer::new(env_filter);
+
+    // Write logs to a file to avoid corrupting TUI
+    let file = RotatingFile::open(&log_file(), config).expect("Failed to open log file");
+    let file_layer = fmt::layer().with_target(false).with_writer(Arc::new(file));
+
+    let registry = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(file_layer)
+        .with(RecentLayer);
+    if let Some(stderr) = stderr {
+        let level = if stderr.quiet {
+            LevelFilter::WARN
+        } else {
+            LevelFilter::TRACE
+        };
+        registry
+            .with(
+                fmt::layer()
+                    .with_target(false)
+                    .with_ansi(stderr.color)
+                    .with_writer(io::stderr)
+                    .with_filter(level),
+            )
+            .init();
+    } else {
+        registry.init();
+    }
+    let _ = LEVEL.set(LevelControl { handle, base });
+}
+
+/// Whether debug logging was switched on for this session
+pub fn debug_enabled() -> bool {
+    DEBUG.load(Ordering::Relaxed)
+}
+
+/// Switch debug logging on, or back to the startup level
+pub fn set_debug(enabled: bool) -> Result<()> {
+    let Some(level) = LEVEL.get() else {
+        bail!("Logging is not initialized");
+    };
+    let filter = if enabled { DEBUG_FILTER } else { &level.base };
+    level
+        .handle
+        .reload(EnvFilter::new(filter))
+        .context("Failed to change log level")?;
+    DEBUG.store(enabled, Ordering::Relaxed);
+    tracing::info!("Log level set to {}", filter);
+    Ok(())
+}
+
+/// One line of a log record kept in memory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// Local time, `HH:MM:SS`
+    pub time: String,
+    pub level: Level,
+    pub message: String,
+}
+
+impl LogRecord {
+    /// `12:04:31 WARN  message`, as copied to the clipboard
+    pub fn to_line(&self) -> String {
+        format!("{} {:<5} {}", self.time, self.level, self.message)
+    }
+}
+
+/// Add a record to the in-memory buffer, one entry per line of `message`
+fn push_recent(level: Level, message: &str) {
+    let time = chrono::Local::now().format("%H:%M:%S").to_string();
+    let mut recent = RECENT.lock().unwrap_or_else(|e| e.into_inner());
+    for line in message.lines().filter(|l| !l.trim().is_empty()) {
+        if recent.len() >= RECENT_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(LogRecord {
+            time: time.clone(),
+            level,
+            message: line.trim_end().replace('\r', ""),
+        });
+    }
+}
+
+/// Buffered records at `max_level` or more severe, oldest first
+pub fn recent_records(max_level: Level) -> Vec<LogRecord> {
+    RECENT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .filter(|r| r.level <= max_level)
+        .cloned()
+        .collect()
+}
+
+/// Message and fields of an event as one string
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0.insert_str(0, value);
+        } else {
+            let _ = write!(self.0, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{:?}", value));
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Layer feeding the in-memory buffer behind the TUI's log pane
+struct RecentLayer;
+
+impl<S: Subscriber> Layer<S> for RecentLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        push_recent(*event.metadata().level(), &visitor.0);
+    }
+}
+
+/// `modsanity.log.<n>`
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", n));
+    path.with_file_name(name)
+}
+
+/// Rotated logs next to `path`, newest first
+pub fn rotated_files(path: &Path) -> Vec<PathBuf> {
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let prefix = format!(
+        "{}.",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    );
+    let mut files: Vec<(usize, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let n = name.strip_prefix(&prefix)?.parse().ok()?;
+            Some((n, entry.path()))
+        })
+        .collect();
+    files.sort();
+    files.into_iter().map(|(_, p)| p).collect()
+}
+
+/// Move `path` to `.1`, shifting older logs up and dropping the ones past
+/// `max_files`
+fn rotate_files(path: &Path, config: &LoggingConfig) -> io::Result<()> {
+    for (i, old) in rotated_files(path).iter().enumerate().rev() {
+        if i + 1 >= config.max_files {
+            fs::remove_file(old)?;
+        } else {
+            fs::rename(old, rotated_path(path, i + 2))?;
+        }
+    }
+    if config.max_files == 0 {
+        fs::remove_file(path)
+    } else {
+        fs::rename(path, rotated_path(path, 1))
+    }
+}
+
+/// Delete rotated logs older than `max_age_days`
+fn prune_old(path: &Path, config: &LoggingConfig) {
+    if config.max_age_days == 0 {
+        return;
+    }
+    let max_age = Duration::from_secs(config.max_age_days * 24 * 60 * 60);
+    for old in rotated_files(path) {
+        let expired = fs::metadata(&old)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > max_age);
+        if expired {
+            let _ = fs::remove_file(old);
+        }
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Log file that rotates itself once it passes the size limit
+pub struct RotatingFile {
+    path: PathBuf,
+    config: LoggingConfig,
+    /// Open file and its size
+    file: Mutex<(File, u64)>,
+}
+
+impl RotatingFile {
+    pub fn open(path: &Path, config: &LoggingConfig) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if size > 0 && config.max_bytes().is_some_and(|max| size >= max) {
+            rotate_files(path, config)?;
+        }
+        prune_old(path, config);
+
+        let file = open_append(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            config: config.clone(),
+            file: Mutex::new((file, size)),
+        })
+    }
+
+    fn rotate(&self, current: &mut (File, u64)) -> io::Result<()> {
+        current.0.flush()?;
+        rotate_files(&self.path, &self.config)?;
+        prune_old(&self.path, &self.config);
+        *current = (open_append(&self.path)?, 0);
+        Ok(())
+    }
+}
+
+impl Write for &RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut current = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(max) = self.config.max_bytes() {
+            // A failed rotation keeps writing to the old file
+            if current.1 > 0 && current.1 + buf.len() as u64 > max {
+                let _ = self.rotate(&mut current);
+            }
+        }
+        let written = current.0.write(buf)?;
+        current.1 += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .0
+            .flush()
+    }
+}
+
+/// The last `count` lines of `path`
+pub fn tail_lines(path: &Path, count: usize) -> Result<Vec<String>> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let content = String::from_utf8_lossy(&bytes);
+    let lines: Vec<&str> = content.lines().collect();
+    Ok(lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(|l| l.to_string())
+        .collect())
+}
+
+/// Empty the current log and delete the rotated ones. Returns the number of
+/// files cleared.
+pub fn clear(path: &Path) -> Result<usize> {
+    let mut cleared = 0;
+    if path.exists() {
+        // Truncated rather than removed: a running TUI keeps appending to it
+        File::create(path).with_context(|| format!("Failed to clear {}", path.display()))?;
+        cleared += 1;
+    }
+    for old in rotated_files(path) {
+        fs::remove_file(&old).with_context(|| format!("Failed to remove {}", old.display()))?;
+        cleared += 1;
+    }
+    Ok(cleared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_rotation() {
+        let dir = std::env::temp_dir().join(format!("modsanity-log-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join(LOG_FILE_NAME);
+        let config = LoggingConfig {
+            max_size_mb: 1,
+            max_files: 2,
+            max_age_days: 0,
+        };
+
+        let log = RotatingFile::open(&path, &config).unwrap();
+        let line = vec![b'x'; 400 * 1024];
+        for _ in 0..8 {
+            (&log).write_all(&line).unwrap();
+        }
+        (&log).flush().unwrap();
+
+        // 8 × 400 KiB in 1 MiB files: the current one plus two rotated
+        let rotated = rotated_files(&path);
+        assert_eq!(
+            rotated,
+            vec![rotated_path(&path, 1), rotated_path(&path, 2)]
+        );
+        assert!(fs::metadata(&path).unwrap().len() <= 1024 * 1024);
+        assert!(fs::metadata(&rotated[0]).unwrap().len() <= 1024 * 1024);
+
+        fs::write(&path, "one\ntwo\nthree\n").unwrap();
+        assert_eq!(tail_lines(&path, 2).unwrap(), vec!["two", "three"]);
+        assert_eq!(clear(&path).unwrap(), 3);
+        assert!(rotated_files(&path).is_empty());
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_tool_log() {
+        let dir = std::env::temp_dir().join(format!("modsanity-tool-log-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let first = save_tool_log(&dir, "Wrye Bash", 1, "", "boom").unwrap();
+        let name = first.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("wrye-bash-"), "{}", name);
+        let content = fs::read_to_string(&first).unwrap();
+        assert!(content.contains("exit code 1"));
+        assert!(content.ends_with("## stderr\nboom\n"));
+        assert!(!content.contains("## stdout"));
+
+        let second = save_tool_log(&dir, "Wrye Bash", 0, "ok\n", "").unwrap();
+        assert_ne!(first, second);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_recent_records_filter_by_level() {
+        push_recent(Level::WARN, "test-recent: first\n\ntest-recent: second");
+        push_recent(Level::DEBUG, "test-recent: detail");
+
+        let warnings: Vec<String> = recent_records(Level::WARN)
+            .into_iter()
+            .filter(|r| r.message.starts_with("test-recent"))
+            .map(|r| r.message)
+            .collect();
+        assert_eq!(warnings, vec!["test-recent: first", "test-recent: second"]);
+        let all = recent_records(Level::TRACE);
+        assert!(all.iter().any(|r| r.message == "test-recent: detail"));
+        assert!(all
+            .last()
+            .unwrap()
+            .to_line()
+            .ends_with("DEBUG test-recent: detail"));
+    }
+}
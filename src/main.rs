@@ -1,7 +1,8 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use modsanity::error::Invalid;
 use modsanity::{App, Config};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 #[command(name = "modsanity")]
@@ -23,6 +24,18 @@ struct Cli {
     #[arg(long)]
     mods_dir: Option<String>,
 
+    /// Print listing and status commands as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Only print errors (and `--json` output); the exit code tells the result
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Don't color log output (also set by the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -50,6 +63,18 @@ enum Commands {
         action: ProfileCommands,
     },
 
+    /// View, edit and compare game INI settings, and apply presets
+    Ini {
+        #[command(subcommand)]
+        action: IniCommands,
+    },
+
+    /// Browse, back up and delete the game's save files
+    Saves {
+        #[command(subcommand)]
+        action: SavesCommands,
+    },
+
     /// Import and manage mod downloads
     Import {
         #[command(subcommand)]
@@ -86,15 +111,140 @@ enum Commands {
         action: ToolCommands,
     },
 
+    /// List and run third-party extensions
+    #[command(alias = "ext")]
+    Extension {
+        #[command(subcommand)]
+        action: ExtensionCommands,
+    },
+
+    /// Launch the game, checking enabled mods' launch requirements first
+    Play {
+        /// Executable in the game folder (defaults to the script extender loader when needed)
+        #[arg(long)]
+        exe: Option<String>,
+        /// Launch even if requirements are not met
+        #[arg(long)]
+        force: bool,
+        /// Only run the requirement checks
+        #[arg(long, conflicts_with = "force")]
+        check: bool,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Deploy if needed, sync plugins.txt into the Proton prefix and launch the game
+    Launch {
+        /// Launch the script extender loader instead of picking automatically
+        #[arg(long)]
+        skse: bool,
+        /// Switch to this profile first
+        #[arg(long)]
+        profile: Option<String>,
+        /// Launch even if requirements are not met
+        #[arg(long)]
+        force: bool,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Search the local Nexus catalog or Nexus itself for mods of the active game
+    Search {
+        query: String,
+        /// Where to search: catalog (local, see 'nexus populate') or nexus
+        #[arg(long, default_value = "catalog")]
+        source: String,
+        /// Result order: relevance, downloads, endorsements, updated
+        #[arg(long, default_value = "relevance")]
+        sort: String,
+        /// Maximum number of results
+        #[arg(long, default_value_t = 20)]
+        limit: i32,
+    },
+
+    /// Run install/enable/disable/priority/deploy steps from a script file,
+    /// rolling back if a step fails
+    RunScript {
+        /// Script file (text, one step per line, or TOML with a `steps` array)
+        path: String,
+        /// Check the script and list its steps without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show the activity log: installs, enables, deploys, profile switches, sorts
+    History {
+        /// Only show one action (e.g. install, deploy, profile-switch)
+        #[arg(long)]
+        action: Option<String>,
+        /// Maximum number of entries
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+
+    /// Show which mods provide a file (e.g. textures/armor/x.dds)
+    Which {
+        /// Path relative to the game's Data folder
+        path: String,
+    },
+
     /// Deploy mods to game directory
     Deploy {
         /// Optional deployment method override: symlink, hardlink, copy
         #[arg(long)]
         method: Option<String>,
+        /// Report what would be deployed and which files are excluded, without changes
+        #[arg(long, conflicts_with = "method")]
+        dry_run: bool,
+        #[command(subcommand)]
+        action: Option<DeployCommands>,
     },
 
     /// Show current status
-    Status,
+    Status {
+        /// Show disk usage for staging, downloads and deployment
+        #[arg(long)]
+        disk: bool,
+
+        /// With --disk: delete stale downloaded archives
+        #[arg(long, requires = "disk")]
+        clean_archives: bool,
+
+        /// With --disk: delete orphaned staging directories
+        #[arg(long, requires = "disk")]
+        clean_orphans: bool,
+
+        /// Show deploy/play stats and what changed since the game was last played
+        #[arg(long)]
+        history: bool,
+    },
+
+    /// Serve a JSON-RPC control socket, holding the instance lock while running
+    Daemon {
+        /// Socket path (defaults to modsanity.sock in the data directory)
+        #[arg(long)]
+        socket: Option<String>,
+        #[command(subcommand)]
+        action: Option<DaemonCommands>,
+    },
+
+    /// Check config.toml
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+
+    /// Back up, check or compact the database
+    Db {
+        #[command(subcommand)]
+        action: DbCommands,
+    },
+
+    /// View or clear the log file
+    Logs {
+        #[command(subcommand)]
+        action: LogsCommands,
+    },
 
     /// Run system diagnostics (paths, tools, runtime checks)
     Doctor {
@@ -176,17 +326,105 @@ enum ModCommands {
     /// List installed mods
     List,
     /// Install a mod from archive
-    Install { path: String },
+    Install {
+        path: String,
+        /// Answer a FOMOD installer from a preset JSON (or saved plan) instead of the wizard
+        #[arg(long, conflicts_with = "fomod_defaults")]
+        fomod_preset: Option<String>,
+        /// Answer a FOMOD installer with its default selections
+        #[arg(long)]
+        fomod_defaults: bool,
+    },
+    /// Register an already-extracted folder as a mod (copies it by default)
+    AddDir {
+        path: String,
+        /// Move the folder into staging instead of copying it
+        #[arg(long = "move", conflicts_with = "link")]
+        move_dir: bool,
+        /// Symlink the folder into staging so it can be regenerated in place
+        #[arg(long)]
+        link: bool,
+        /// Mod name (defaults to meta.ini or the folder name)
+        #[arg(long)]
+        name: Option<String>,
+    },
     /// Enable a mod
     Enable { name: String },
     /// Disable a mod
     Disable { name: String },
-    /// Remove a mod
+    /// Remove a mod (kept in the trash for `[trash] retention_days`)
     Remove { name: String },
+    /// Restore a removed mod from the trash
+    Restore { name: String },
+    /// List removed mods that can still be restored
+    Trash {
+        /// Delete every mod in the trash now
+        #[arg(long)]
+        empty: bool,
+    },
     /// Show mod info
     Info { name: String },
+    /// Update an installed mod from a newer archive (keeps the old archive)
+    Update {
+        #[arg(required_unless_present = "all")]
+        name: Option<String>,
+        #[arg(required_unless_present = "all")]
+        path: Option<String>,
+        /// Download and install the latest MAIN file of every out-of-date mod
+        #[arg(long, conflicts_with_all = ["name", "path"])]
+        all: bool,
+    },
+    /// Save a mod's FOMOD choices as a preset file
+    FomodExport {
+        name: String,
+        path: String,
+        /// Profile whose choices to export (defaults to the active profile)
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Load a FOMOD preset as a mod's saved choices, used when its installer is next run
+    FomodImport {
+        name: String,
+        path: String,
+        /// Profile to save the choices for (defaults to the active profile)
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Flag installed mods whose Nexus file was archived or deleted
+    CheckFiles,
+    /// Reinstall the previously retained version of a mod
+    Rollback { name: String },
+    /// Re-extract a mod from its original archive, keeping its metadata
+    Reinstall { name: String },
     /// Scan staging folder and sync mods into the database
     Rescan,
+    /// Check staged files against the file index (missing or changed files)
+    Verify {
+        /// Mod to check (defaults to every installed mod)
+        name: Option<String>,
+        /// Also compare content hashes and report files missing from the index
+        #[arg(long)]
+        full: bool,
+    },
+    /// Report loose .pex overrides of vanilla scripts and script conflicts between mods
+    Scripts,
+    /// Mark a mod as needing the script extender or ENB at launch
+    Require {
+        name: String,
+        /// script-extender (or skse, f4se, sfse), enb
+        requirement: String,
+        /// Remove the requirement instead of adding it
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Keep a mod's files matching a glob pattern (e.g. *.psd, docs/**) out of deploy
+    Exclude {
+        name: String,
+        pattern: String,
+        /// Remove the pattern instead of adding it
+        #[arg(long)]
+        remove: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -203,6 +441,97 @@ enum ProfileCommands {
     Export { name: String, path: String },
     /// Import a profile
     Import { path: String },
+    /// Override an external tool's path, arguments or output mod for a profile
+    ///
+    /// Options left out keep the profile's current value; anything the profile
+    /// does not set falls back to the global tool settings.
+    ToolSet {
+        name: String,
+        tool: String,
+        /// Executable to use instead of the global tool path
+        #[arg(long)]
+        path: Option<String>,
+        /// Mod that receives the files the tool writes into Data
+        #[arg(long)]
+        output_mod: Option<String>,
+        /// Arguments passed to the tool, replacing the profile's current ones
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Remove a profile's override for an external tool
+    ToolClear { name: String, tool: String },
+    /// Store the game's current INI files as the profile's own
+    ///
+    /// They are put in place (after a backup) whenever the profile is
+    /// activated, and updated with any changes when another profile is.
+    IniSave { name: String },
+    /// Drop a profile's own INI files
+    IniClear { name: String },
+    /// Give a profile its own save folder, so its characters don't mix with
+    /// other profiles'
+    SavesIsolate { name: String },
+    /// Make a profile use the game's shared save folder again
+    SavesShare { name: String },
+}
+
+#[derive(Subcommand)]
+enum IniCommands {
+    /// List built-in INI presets
+    Presets,
+    /// Show INI keys that differ between two sources
+    ///
+    /// A source is a profile name, `profile:<name>`, `preset:<name>`, or
+    /// `current` for the game's INI files as they are on disk.
+    Diff { left: String, right: String },
+    /// Merge a preset into a profile's INI overrides
+    ApplyPreset {
+        preset: String,
+        /// Target profile (defaults to the active profile)
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Show the game's INI files with their known keys, or every key of one file
+    ///
+    /// FILE is an INI file name or `main`, `prefs` or `custom`.
+    Show { file: Option<String> },
+    /// Set a key in one of the game's INI files, backing the file up first
+    ///
+    /// Known keys are checked against their type (bool, int, float) and
+    /// written the way the game writes them; other keys are set as given.
+    Set {
+        file: String,
+        section: String,
+        key: String,
+        value: String,
+    },
+    /// Edit one of the game's INI files in $VISUAL/$EDITOR, backing it up first
+    Edit { file: String },
+    /// List backups of the game's INI files, newest first
+    Backups { file: Option<String> },
+    /// Put a backup back in place (the current file is backed up first)
+    Restore {
+        /// Backup file name from `ini backups`, or its path
+        backup: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SavesCommands {
+    /// List saves, newest first, with character, level, play time and date
+    List,
+    /// Copy a save and its script extender co-save to the backups folder
+    Backup {
+        /// Save file name, with or without the extension
+        name: String,
+    },
+    /// Delete a save and its script extender co-save
+    Delete {
+        /// Save file name, with or without the extension
+        name: String,
+        /// Delete without asking
+        #[arg(long)]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -217,6 +546,24 @@ enum ImportCommands {
         /// Preview matching results only (no queue/db writes)
         #[arg(long)]
         preview: bool,
+        /// Write the matching report (matched, needs review, unmatched with
+        /// alternatives) to this file: CSV for .csv, JSON otherwise
+        #[arg(long)]
+        report: Option<String>,
+    },
+    /// Import a whole MO2 instance: mods folder, profiles and profile INIs
+    Instance {
+        /// MO2 instance directory (containing ModOrganizer.ini, mods/, profiles/)
+        path: String,
+        /// Move mod folders into staging instead of copying them
+        #[arg(long = "move", conflicts_with = "link")]
+        move_dir: bool,
+        /// Symlink mod folders into staging
+        #[arg(long)]
+        link: bool,
+        /// Report what would be imported without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Show import status for a batch
     Status {
@@ -231,6 +578,15 @@ enum ImportCommands {
         #[arg(long)]
         preview: bool,
     },
+    /// Watch the downloads directory and offer to install new archives
+    Watch {
+        /// Install every new archive without asking
+        #[arg(long)]
+        yes: bool,
+        /// Answer FOMOD installers with their default selections
+        #[arg(long)]
+        fomod_defaults: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -253,6 +609,64 @@ enum QueueCommands {
         /// Batch ID to clear (optional, clears all if not specified)
         batch_id: Option<String>,
     },
+    /// Export a batch to a file for processing on another machine
+    ///
+    /// `queue export <BATCH_ID> <FILE>`, or `queue export <FILE>` for the most
+    /// recent batch.
+    Export {
+        /// Batch ID, or the output file when no file follows
+        #[arg(value_name = "BATCH_ID|FILE")]
+        target: String,
+        /// Path to output file (JSON)
+        file: Option<String>,
+        /// Batch ID to export (optional, the most recent batch if not specified)
+        #[arg(long, conflicts_with = "file")]
+        batch_id: Option<String>,
+        /// Leave out completed and skipped entries
+        #[arg(long)]
+        pending: bool,
+    },
+    /// Import a batch exported with `queue export`
+    Import {
+        /// Path to queue file
+        file: String,
+    },
+    /// Pause processing; running downloads hold and nothing new starts
+    Pause {
+        /// Batch ID to pause (optional, pauses all if not specified)
+        batch_id: Option<String>,
+    },
+    /// Resume paused processing
+    Resume {
+        /// Batch ID to resume (optional, resumes all if not specified)
+        batch_id: Option<String>,
+    },
+    /// Stop processing; interrupted entries go back to matched
+    Cancel {
+        /// Batch ID to cancel (optional, cancels all if not specified)
+        batch_id: Option<String>,
+    },
+    /// Move an entry within its batch
+    Move {
+        /// Mod name or entry ID
+        entry: String,
+        /// up, down, top or bottom
+        direction: String,
+        /// Batch ID (optional, the most recent batch if not specified)
+        #[arg(long)]
+        batch_id: Option<String>,
+    },
+    /// Process an entry before the rest of its batch
+    Priority {
+        /// Mod name or entry ID
+        entry: String,
+        /// Remove the priority flag instead
+        #[arg(long)]
+        off: bool,
+        /// Batch ID (optional, the most recent batch if not specified)
+        #[arg(long)]
+        batch_id: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -276,6 +690,12 @@ enum ModlistCommands {
         #[arg(long)]
         preview: bool,
     },
+    /// Compare two modlists
+    ///
+    /// A modlist is `current` (installed mods and plugin order), a modlist
+    /// file (native JSON, MO2 modlist.txt or plugins.txt), or the name of a
+    /// saved modlist (`saved:<name>` when a file has the same name).
+    Diff { a: String, b: String },
 }
 
 #[derive(Subcommand)]
@@ -301,6 +721,37 @@ enum NexusCommands {
         #[arg(short, long)]
         game: String,
     },
+    /// Download a mod by its Nexus id, optionally installing it
+    Download {
+        /// Nexus mod id (the number in the mod page URL)
+        mod_id: i64,
+        /// File id to download (defaults to the main file)
+        #[arg(long = "file")]
+        file_id: Option<i64>,
+        /// Install the archive after downloading
+        #[arg(long)]
+        install: bool,
+    },
+    /// Show a random well-endorsed mod for the active game
+    Surprise {
+        /// Nexus category to draw from (repeatable; defaults to [discovery] in config)
+        #[arg(short, long)]
+        category: Vec<String>,
+        /// Minimum endorsements (defaults to [discovery] in config)
+        #[arg(long)]
+        min_endorsements: Option<i64>,
+        /// Add the pick to the download queue
+        #[arg(long)]
+        queue: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DeployCommands {
+    /// Package the resolved deployment (winning files only) into a tar archive
+    Export { path: String },
+    /// Remove an extracted export from a game folder using its manifest
+    CleanExport { game_dir: String },
 }
 
 #[derive(Subcommand)]
@@ -309,6 +760,13 @@ enum DeploymentCommands {
     Show,
     /// Set deployment method: symlink, hardlink, copy
     SetMethod { method: String },
+    /// Keep files matching a glob pattern (e.g. *.txt, docs/**) out of every deploy
+    Exclude {
+        pattern: String,
+        /// Remove the pattern instead of adding it
+        #[arg(long)]
+        remove: bool,
+    },
     /// Set downloads directory override
     SetDownloadsDir { path: String },
     /// Clear downloads directory override (use default)
@@ -349,80 +807,452 @@ enum ToolCommands {
     ClearRuntime { tool: String },
     /// Clear tool executable path
     ClearPath { tool: String },
+    /// Set a tool's default arguments, working directory and environment
+    ///
+    /// Options left out keep their current value. The arguments come before
+    /// a profile's and those given to `tool run`.
+    SetOptions {
+        tool: String,
+        /// Working directory, e.g. `{data_dir}` (empty resets to the executable's folder)
+        #[arg(long)]
+        workdir: Option<String>,
+        /// Environment variable as KEY=VALUE, repeatable (KEY= removes it)
+        #[arg(long = "env")]
+        env: Vec<String>,
+        /// Default arguments, replacing the current ones
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Remove a tool's default arguments, working directory and environment
+    ClearOptions { tool: String },
     /// Launch a configured tool using its selected runtime mode
+    ///
+    /// When mods or plugins changed since the last deploy, asks whether to
+    /// deploy first (in a terminal) or warns and launches anyway.
     Run {
+        /// Deploy first without asking if the deployment is out of date
+        #[arg(long, conflicts_with = "no_deploy")]
+        deploy: bool,
+        /// Launch without checking the deployment
+        #[arg(long)]
+        no_deploy: bool,
         tool: String,
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
+    /// Clean plugins the LOOT masterlist reports as dirty with xEdit QuickAutoClean
+    ///
+    /// Each plugin gets its own xEdit run through the tool's runtime. In a
+    /// terminal, asks before each one.
+    Clean {
+        /// Only list the dirty plugins
+        #[arg(long, conflicts_with = "history")]
+        list: bool,
+        /// List plugins cleaned so far
+        #[arg(long)]
+        history: bool,
+        /// LOOT masterlist.yaml to read instead of the one in the LOOT cache folder
+        #[arg(long)]
+        masterlist: Option<String>,
+        /// Clean every dirty plugin without asking
+        #[arg(long)]
+        yes: bool,
+        /// Only clean these plugins
+        plugins: Vec<String>,
+    },
+    /// Install the runtimes tools need (dotnet, vcrun, d3dcompiler) into the game's Proton prefix
+    ///
+    /// Uses protontricks for Steam games, winetricks on the prefix otherwise.
+    /// Without verbs, installs the ones `doctor` reports missing.
+    PrefixSetup {
+        /// Only print the command that would run
+        #[arg(long)]
+        dry_run: bool,
+        /// Install everything, even what winetricks already logged as installed
+        #[arg(long)]
+        force: bool,
+        /// winetricks verbs to install instead of the missing ones
+        verbs: Vec<String>,
+    },
+    /// List files created in Data since the last deploy, or move them into a mod
+    Capture {
+        /// Mod to move the files into, created if it doesn't exist
+        #[arg(value_name = "MOD")]
+        output_mod: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonCommands {
+    /// Call a method on the running daemon and print the JSON result
+    Call {
+        /// status, queue.add, queue.process, deploy or shutdown
+        method: String,
+        /// Parameters as a JSON object, e.g. '{"mod_id": 12604}'
+        params: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Report unknown keys and invalid values, then print the effective settings
+    Validate,
+}
+
+#[derive(Subcommand)]
+enum DbCommands {
+    /// Copy the database with SQLite's backup API
+    Backup {
+        /// Destination file (defaults to backups/modsanity-<timestamp>.db in the data directory)
+        path: Option<String>,
+    },
+    /// Run SQLite's integrity check
+    Check,
+    /// Rebuild the database file to reclaim unused space
+    Vacuum,
+    /// Write mods, categories, profiles, modlists and FOMOD choices to a JSON file
+    Export {
+        /// Destination file
+        path: String,
+    },
+    /// Replace the library with a JSON export (the database is backed up first)
+    Import {
+        /// Export file written by `db export`
+        path: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum LogsCommands {
+    /// Print the end of the current log
+    Tail {
+        /// Number of lines to print
+        #[arg(short = 'n', long, default_value_t = 50)]
+        lines: usize,
+        /// Keep printing new lines as they are written
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Empty the current log and delete rotated logs
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum ExtensionCommands {
+    /// List discovered extensions and what they provide
+    List,
+    /// Run a command provided by an extension
+    Run {
+        extension: String,
+        command: String,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Print an extension screen
+    Screen { extension: String, screen: String },
 }
 
-fn setup_logging(verbosity: u8, also_stderr: bool) {
-    let filter = match verbosity {
-        0 => "modsanity=info",
-        1 => "modsanity=debug",
-        2 => "modsanity=trace",
-        _ => "trace",
-    };
+/// Whether a parsed command is one particular JSON-capable command
+type JsonCommand = fn(&Commands) -> bool;
 
-    // Write logs to a file to avoid corrupting TUI
-    let log_dir = std::env::var_os("HOME")
-        .map(std::path::PathBuf::from)
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join(".modsanity");
-
-    std::fs::create_dir_all(&log_dir).ok();
-    let log_file = log_dir.join("modsanity.log");
-
-    let file = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(log_file)
-        .expect("Failed to open log file");
-
-    let env_filter =
-        tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| filter.into());
-    let file_layer = tracing_subscriber::fmt::layer()
-        .with_target(false)
-        .with_writer(std::sync::Arc::new(file));
-
-    if also_stderr {
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(file_layer)
-            .with(
-                tracing_subscriber::fmt::layer()
-                    .with_target(false)
-                    .with_writer(std::io::stderr),
-            )
-            .init();
-    } else {
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(file_layer)
-            .init();
+/// Commands that can print their output as JSON, by the name the `--json`
+/// rejection lists them under
+const JSON_COMMANDS: &[(&str, JsonCommand)] = &[
+    ("game list", |c| {
+        matches!(
+            c,
+            Commands::Game {
+                action: GameCommands::List
+            }
+        )
+    }),
+    ("game info", |c| {
+        matches!(
+            c,
+            Commands::Game {
+                action: GameCommands::Info
+            }
+        )
+    }),
+    ("mod list", |c| {
+        matches!(
+            c,
+            Commands::Mod {
+                action: ModCommands::List
+            }
+        )
+    }),
+    ("mod verify", |c| {
+        matches!(
+            c,
+            Commands::Mod {
+                action: ModCommands::Verify { .. }
+            }
+        )
+    }),
+    ("mod trash", |c| {
+        matches!(
+            c,
+            Commands::Mod {
+                action: ModCommands::Trash { empty: false }
+            }
+        )
+    }),
+    ("profile list", |c| {
+        matches!(
+            c,
+            Commands::Profile {
+                action: ProfileCommands::List
+            }
+        )
+    }),
+    ("saves list", |c| {
+        matches!(
+            c,
+            Commands::Saves {
+                action: SavesCommands::List
+            }
+        )
+    }),
+    ("status (without --disk/--history)", |c| {
+        matches!(
+            c,
+            Commands::Status {
+                disk: false,
+                history: false,
+                ..
+            }
+        )
+    }),
+    ("which", |c| matches!(c, Commands::Which { .. })),
+    ("history", |c| matches!(c, Commands::History { .. })),
+    ("search", |c| matches!(c, Commands::Search { .. })),
+    ("import status", |c| {
+        matches!(
+            c,
+            Commands::Import {
+                action: ImportCommands::Status { .. }
+            }
+        )
+    }),
+    ("queue list", |c| {
+        matches!(
+            c,
+            Commands::Queue {
+                action: QueueCommands::List
+            }
+        )
+    }),
+    ("modlist diff", |c| {
+        matches!(
+            c,
+            Commands::Modlist {
+                action: ModlistCommands::Diff { .. }
+            }
+        )
+    }),
+    ("nexus status", |c| {
+        matches!(
+            c,
+            Commands::Nexus {
+                action: NexusCommands::Status { .. }
+            }
+        )
+    }),
+    ("deployment show", |c| {
+        matches!(
+            c,
+            Commands::Deployment {
+                action: DeploymentCommands::Show
+            }
+        )
+    }),
+    ("extension list", |c| {
+        matches!(
+            c,
+            Commands::Extension {
+                action: ExtensionCommands::List
+            }
+        )
+    }),
+    ("config validate", |c| {
+        matches!(
+            c,
+            Commands::Config {
+                action: ConfigCommands::Validate
+            }
+        )
+    }),
+];
+
+/// Whether `command` can print its output as JSON
+fn supports_json(command: &Option<Commands>) -> bool {
+    command
+        .as_ref()
+        .is_some_and(|command| JSON_COMMANDS.iter().any(|(_, matches)| matches(command)))
+}
+
+/// Point stdout at /dev/null for `--quiet`, so command output is dropped
+/// while errors on stderr still come through
+fn silence_stdout() -> Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let null = std::fs::OpenOptions::new().write(true).open("/dev/null")?;
+    // SAFETY: both descriptors are open; dup2 atomically replaces stdout
+    if unsafe { libc::dup2(null.as_raw_fd(), libc::STDOUT_FILENO) } < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+/// What to record in the instance lock for `command`, or `None` for commands
+/// that only read or that control another running instance
+fn instance_lock_command(command: &Option<Commands>) -> Option<String> {
+    let exempt = matches!(
+        command,
+        Some(
+            Commands::Which { .. }
+                | Commands::History { .. }
+                | Commands::Search { .. }
+                | Commands::Logs { .. }
+                | Commands::Mod {
+                    action: ModCommands::Verify { .. },
+                }
+                | Commands::Db {
+                    action: DbCommands::Backup { .. }
+                        | DbCommands::Check
+                        | DbCommands::Export { .. },
+                }
+                | Commands::Daemon {
+                    action: Some(DaemonCommands::Call { .. }),
+                    ..
+                }
+                | Commands::Doctor { .. }
+                | Commands::Audit { .. }
+                | Commands::GettingStarted
+                // A launch writes its play session to the database, so only
+                // the requirement check runs alongside another instance
+                | Commands::Play { check: true, .. }
+                | Commands::Status {
+                    clean_archives: false,
+                    clean_orphans: false,
+                    ..
+                }
+                | Commands::Game {
+                    action: GameCommands::List | GameCommands::Info,
+                }
+                | Commands::Mod {
+                    action: ModCommands::List | ModCommands::Info { .. },
+                }
+                | Commands::Profile {
+                    action: ProfileCommands::List,
+                }
+                | Commands::Ini {
+                    action: IniCommands::Presets
+                        | IniCommands::Diff { .. }
+                        | IniCommands::Show { .. }
+                        | IniCommands::Backups { .. },
+                }
+                | Commands::Saves {
+                    action: SavesCommands::List | SavesCommands::Backup { .. },
+                }
+                | Commands::Modlist {
+                    action: ModlistCommands::Diff { .. },
+                }
+                | Commands::Deployment {
+                    action: DeploymentCommands::Show,
+                }
+                | Commands::Tool {
+                    action: ToolCommands::Show | ToolCommands::ListProton,
+                }
+                | Commands::Queue {
+                    action: QueueCommands::List
+                        | QueueCommands::Pause { .. }
+                        | QueueCommands::Resume { .. }
+                        | QueueCommands::Cancel { .. },
+                }
+        )
+    );
+    match command {
+        _ if exempt => None,
+        Some(Commands::Tui) | None => Some("TUI".to_string()),
+        Some(_) => Some(
+            std::iter::once("modsanity".to_string())
+                .chain(std::env::args().skip(1))
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
     }
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(modsanity::error::exit_code(&e))
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
     let is_tui = matches!(cli.command, Some(Commands::Tui) | None);
-    setup_logging(cli.verbose, !is_tui);
+
+    // Validation reads config.toml itself, so it works when the file doesn't load
+    if let Some(Commands::Config {
+        action: ConfigCommands::Validate,
+    }) = cli.command
+    {
+        if cli.quiet && !cli.json {
+            silence_stdout()?;
+        }
+        return App::cmd_config_validate(
+            &modsanity::config::Paths::new(),
+            cli.mods_dir.as_deref(),
+            cli.json,
+        );
+    }
 
     // Load configuration
     let mut config = Config::load().await?;
+    let stderr = (!is_tui).then(|| modsanity::logging::StderrLog {
+        quiet: cli.quiet,
+        color: !cli.no_color && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+    });
+    modsanity::logging::init(cli.verbose, stderr, &config.logging);
     if let Some(mods_dir) = cli.mods_dir.as_deref() {
         let trimmed = mods_dir.trim();
         if trimmed.is_empty() {
-            anyhow::bail!("--mods-dir cannot be empty");
+            anyhow::bail!(Invalid::new("--mods-dir cannot be empty"));
         }
+        modsanity::config::check_staging_template(trimmed)?;
         config.staging_dir_override = Some(trimmed.to_string());
     }
+    if cli.json && !supports_json(&cli.command) {
+        let names: Vec<&str> = JSON_COMMANDS.iter().map(|(name, _)| *name).collect();
+        anyhow::bail!(Invalid::new(format!(
+            "--json is supported by: {}",
+            names.join(", ")
+        )));
+    }
+    if cli.quiet && !cli.json && !is_tui {
+        silence_stdout()?;
+    }
+
+    // Commands that change staging or plugins.txt run one at a time
+    let _instance_lock = match instance_lock_command(&cli.command) {
+        Some(command) => Some(modsanity::lock::InstanceLock::acquire(
+            &config.paths.instance_lock_file(),
+            &command,
+        )?),
+        None => None,
+    };
 
     // Initialize app
     let mut app = App::new(config).await?;
     app.set_cli_verbosity(cli.verbose);
+    app.set_json_output(cli.json);
 
     match cli.command {
         Some(Commands::Tui) | None => {
@@ -449,12 +1279,71 @@ async fn main() -> Result<()> {
         },
         Some(Commands::Mod { action }) => match action {
             ModCommands::List => app.cmd_mod_list().await?,
-            ModCommands::Install { path } => app.cmd_mod_install(&path).await?,
+            ModCommands::Install {
+                path,
+                fomod_preset,
+                fomod_defaults,
+            } => {
+                app.cmd_mod_install(&path, fomod_preset.as_deref(), fomod_defaults)
+                    .await?
+            }
+            ModCommands::AddDir {
+                path,
+                move_dir,
+                link,
+                name,
+            } => {
+                let mode = if link {
+                    modsanity::mods::AdoptMode::Link
+                } else if move_dir {
+                    modsanity::mods::AdoptMode::Move
+                } else {
+                    modsanity::mods::AdoptMode::Copy
+                };
+                app.cmd_mod_add_dir(&path, mode, name.as_deref()).await?
+            }
             ModCommands::Enable { name } => app.cmd_mod_enable(&name).await?,
             ModCommands::Disable { name } => app.cmd_mod_disable(&name).await?,
             ModCommands::Remove { name } => app.cmd_mod_remove(&name).await?,
+            ModCommands::Restore { name } => app.cmd_mod_restore(&name).await?,
+            ModCommands::Trash { empty } => app.cmd_mod_trash(empty).await?,
             ModCommands::Info { name } => app.cmd_mod_info(&name).await?,
+            ModCommands::Update { name, path, all } => match (name, path) {
+                (Some(name), Some(path)) if !all => app.cmd_mod_update(&name, &path).await?,
+                _ => app.cmd_mod_update_all().await?,
+            },
+            ModCommands::FomodExport {
+                name,
+                path,
+                profile,
+            } => {
+                app.cmd_mod_fomod_export(&name, &path, profile.as_deref())
+                    .await?
+            }
+            ModCommands::FomodImport {
+                name,
+                path,
+                profile,
+            } => {
+                app.cmd_mod_fomod_import(&name, &path, profile.as_deref())
+                    .await?
+            }
+            ModCommands::CheckFiles => app.cmd_mod_check_files().await?,
+            ModCommands::Rollback { name } => app.cmd_mod_rollback(&name).await?,
+            ModCommands::Reinstall { name } => app.cmd_mod_reinstall(&name).await?,
             ModCommands::Rescan => app.cmd_mod_rescan().await?,
+            ModCommands::Verify { name, full } => app.cmd_mod_verify(name.as_deref(), full).await?,
+            ModCommands::Scripts => app.cmd_mod_scripts().await?,
+            ModCommands::Require {
+                name,
+                requirement,
+                remove,
+            } => app.cmd_mod_require(&name, &requirement, remove).await?,
+            ModCommands::Exclude {
+                name,
+                pattern,
+                remove,
+            } => app.cmd_mod_exclude(&name, &pattern, remove).await?,
         },
         Some(Commands::Profile { action }) => match action {
             ProfileCommands::List => app.cmd_profile_list().await?,
@@ -463,19 +1352,82 @@ async fn main() -> Result<()> {
             ProfileCommands::Delete { name } => app.cmd_profile_delete(&name).await?,
             ProfileCommands::Export { name, path } => app.cmd_profile_export(&name, &path).await?,
             ProfileCommands::Import { path } => app.cmd_profile_import(&path).await?,
+            ProfileCommands::ToolSet {
+                name,
+                tool,
+                path,
+                output_mod,
+                args,
+            } => {
+                app.cmd_profile_tool_set(&name, &tool, path, output_mod, args)
+                    .await?
+            }
+            ProfileCommands::ToolClear { name, tool } => {
+                app.cmd_profile_tool_clear(&name, &tool).await?
+            }
+            ProfileCommands::IniSave { name } => app.cmd_profile_ini_save(&name).await?,
+            ProfileCommands::IniClear { name } => app.cmd_profile_ini_clear(&name).await?,
+            ProfileCommands::SavesIsolate { name } => app.cmd_profile_saves(&name, true).await?,
+            ProfileCommands::SavesShare { name } => app.cmd_profile_saves(&name, false).await?,
+        },
+        Some(Commands::Saves { action }) => match action {
+            SavesCommands::List => app.cmd_saves_list().await?,
+            SavesCommands::Backup { name } => app.cmd_saves_backup(&name).await?,
+            SavesCommands::Delete { name, yes } => app.cmd_saves_delete(&name, yes).await?,
+        },
+        Some(Commands::Ini { action }) => match action {
+            IniCommands::Presets => app.cmd_ini_presets().await?,
+            IniCommands::Diff { left, right } => app.cmd_ini_diff(&left, &right).await?,
+            IniCommands::ApplyPreset { preset, profile } => {
+                app.cmd_ini_apply_preset(&preset, profile.as_deref())
+                    .await?
+            }
+            IniCommands::Show { file } => app.cmd_ini_show(file.as_deref()).await?,
+            IniCommands::Set {
+                file,
+                section,
+                key,
+                value,
+            } => app.cmd_ini_set(&file, &section, &key, &value).await?,
+            IniCommands::Edit { file } => app.cmd_ini_edit(&file).await?,
+            IniCommands::Backups { file } => app.cmd_ini_backups(file.as_deref()).await?,
+            IniCommands::Restore { backup } => app.cmd_ini_restore(&backup).await?,
         },
         Some(Commands::Import { action }) => match action {
             ImportCommands::Modlist {
                 path,
                 auto_approve,
                 preview,
-            } => app.cmd_import_modlist(&path, auto_approve, preview).await?,
+                report,
+            } => {
+                app.cmd_import_modlist(&path, auto_approve, preview, report.as_deref())
+                    .await?
+            }
             ImportCommands::Status { batch_id } => {
                 app.cmd_import_status(batch_id.as_deref()).await?
             }
+            ImportCommands::Instance {
+                path,
+                move_dir,
+                link,
+                dry_run,
+            } => {
+                let mode = if link {
+                    modsanity::mods::AdoptMode::Link
+                } else if move_dir {
+                    modsanity::mods::AdoptMode::Move
+                } else {
+                    modsanity::mods::AdoptMode::Copy
+                };
+                app.cmd_import_instance(&path, mode, dry_run).await?
+            }
             ImportCommands::ApplyEnabled { path, preview } => {
                 app.cmd_import_apply_enabled(&path, preview).await?
             }
+            ImportCommands::Watch {
+                yes,
+                fomod_defaults,
+            } => app.cmd_import_watch(yes, fomod_defaults).await?,
         },
         Some(Commands::Queue { action }) => match action {
             QueueCommands::List => app.cmd_queue_list().await?,
@@ -488,6 +1440,51 @@ async fn main() -> Result<()> {
             }
             QueueCommands::Retry => app.cmd_queue_retry().await?,
             QueueCommands::Clear { batch_id } => app.cmd_queue_clear(batch_id.as_deref()).await?,
+            QueueCommands::Export {
+                target,
+                file,
+                batch_id,
+                pending,
+            } => {
+                let (batch_id, file) = match file {
+                    Some(file) => (Some(target), file),
+                    None => (batch_id, target),
+                };
+                app.cmd_queue_export(&file, batch_id.as_deref(), pending)
+                    .await?
+            }
+            QueueCommands::Import { file } => app.cmd_queue_import(&file).await?,
+            QueueCommands::Pause { batch_id } => {
+                app.cmd_queue_control(batch_id.as_deref(), modsanity::queue::BatchControl::Paused)
+                    .await?
+            }
+            QueueCommands::Resume { batch_id } => {
+                app.cmd_queue_control(batch_id.as_deref(), modsanity::queue::BatchControl::Running)
+                    .await?
+            }
+            QueueCommands::Cancel { batch_id } => {
+                app.cmd_queue_control(
+                    batch_id.as_deref(),
+                    modsanity::queue::BatchControl::Cancelled,
+                )
+                .await?
+            }
+            QueueCommands::Move {
+                entry,
+                direction,
+                batch_id,
+            } => {
+                app.cmd_queue_move(&entry, &direction, batch_id.as_deref())
+                    .await?
+            }
+            QueueCommands::Priority {
+                entry,
+                off,
+                batch_id,
+            } => {
+                app.cmd_queue_priority(&entry, !off, batch_id.as_deref())
+                    .await?
+            }
         },
         Some(Commands::Modlist { action }) => match action {
             ModlistCommands::Save { path, format } => app.cmd_modlist_save(&path, &format).await?,
@@ -496,6 +1493,7 @@ async fn main() -> Result<()> {
                 auto_approve,
                 preview,
             } => app.cmd_modlist_load(&path, auto_approve, preview).await?,
+            ModlistCommands::Diff { a, b } => app.cmd_modlist_diff(&a, &b).await?,
         },
         Some(Commands::Nexus { action }) => match action {
             NexusCommands::Populate {
@@ -508,12 +1506,28 @@ async fn main() -> Result<()> {
                     .await?
             }
             NexusCommands::Status { game } => app.cmd_nexus_status(&game).await?,
+            NexusCommands::Download {
+                mod_id,
+                file_id,
+                install,
+            } => app.cmd_nexus_download(mod_id, file_id, install).await?,
+            NexusCommands::Surprise {
+                category,
+                min_endorsements,
+                queue,
+            } => {
+                app.cmd_nexus_surprise(category, min_endorsements, queue)
+                    .await?
+            }
         },
         Some(Commands::Deployment { action }) => match action {
             DeploymentCommands::Show => app.cmd_deployment_show().await?,
             DeploymentCommands::SetMethod { method } => {
                 app.cmd_set_deployment_method(&method).await?
             }
+            DeploymentCommands::Exclude { pattern, remove } => {
+                app.cmd_deployment_exclude(&pattern, remove).await?
+            }
             DeploymentCommands::SetDownloadsDir { path } => {
                 app.cmd_set_downloads_dir(&path).await?
             }
@@ -536,15 +1550,138 @@ async fn main() -> Result<()> {
             }
             ToolCommands::ClearRuntime { tool } => app.cmd_tool_clear_runtime(&tool).await?,
             ToolCommands::ClearPath { tool } => app.cmd_tool_clear_path(&tool).await?,
-            ToolCommands::Run { tool, args } => app.cmd_tool_run(&tool, &args).await?,
+            ToolCommands::SetOptions {
+                tool,
+                workdir,
+                env,
+                args,
+            } => app.cmd_tool_set_options(&tool, workdir, &env, args).await?,
+            ToolCommands::ClearOptions { tool } => app.cmd_tool_clear_options(&tool).await?,
+            ToolCommands::Run {
+                deploy,
+                no_deploy,
+                tool,
+                args,
+            } => {
+                let deploy = match (deploy, no_deploy) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                };
+                app.cmd_tool_run(&tool, deploy, &args).await?
+            }
+            ToolCommands::Clean {
+                list,
+                history,
+                masterlist,
+                yes,
+                plugins,
+            } => {
+                if history {
+                    app.cmd_tool_clean_history().await?
+                } else {
+                    app.cmd_tool_clean(masterlist.as_deref(), &plugins, list, yes)
+                        .await?
+                }
+            }
+            ToolCommands::PrefixSetup {
+                dry_run,
+                force,
+                verbs,
+            } => app.cmd_tool_prefix_setup(&verbs, force, dry_run).await?,
+            ToolCommands::Capture { output_mod } => {
+                app.cmd_tool_capture(output_mod.as_deref()).await?
+            }
+        },
+        Some(Commands::Extension { action }) => match action {
+            ExtensionCommands::List => app.cmd_extension_list().await?,
+            ExtensionCommands::Run {
+                extension,
+                command,
+                args,
+            } => app.cmd_extension_run(&extension, &command, &args).await?,
+            ExtensionCommands::Screen { extension, screen } => {
+                app.cmd_extension_screen(&extension, &screen).await?
+            }
         },
-        Some(Commands::Deploy { method }) => {
-            if let Some(method) = method {
-                app.cmd_set_deployment_method(&method).await?;
+        Some(Commands::Deploy {
+            method,
+            dry_run,
+            action,
+        }) => match action {
+            Some(DeployCommands::Export { path }) => app.cmd_deploy_export(&path).await?,
+            Some(DeployCommands::CleanExport { game_dir }) => {
+                app.cmd_deploy_clean_export(&game_dir)?
+            }
+            None if dry_run => app.cmd_deploy_dry_run().await?,
+            None => {
+                if let Some(method) = method {
+                    app.cmd_set_deployment_method(&method).await?;
+                }
+                app.cmd_deploy().await?
+            }
+        },
+        Some(Commands::Status {
+            disk,
+            clean_archives,
+            clean_orphans,
+            history,
+        }) => {
+            app.cmd_status().await?;
+            if disk {
+                app.cmd_status_disk(clean_archives, clean_orphans).await?;
             }
-            app.cmd_deploy().await?
+            if history {
+                app.cmd_status_history().await?;
+            }
+        }
+        Some(Commands::Play {
+            exe,
+            force,
+            check,
+            args,
+        }) => app.cmd_play(exe.as_deref(), force, check, &args).await?,
+        Some(Commands::Launch {
+            skse,
+            profile,
+            force,
+            args,
+        }) => {
+            app.cmd_launch(skse, profile.as_deref(), force, &args)
+                .await?
         }
-        Some(Commands::Status) => app.cmd_status().await?,
+        Some(Commands::Search {
+            query,
+            source,
+            sort,
+            limit,
+        }) => app.cmd_search(&query, &source, &sort, limit).await?,
+        Some(Commands::RunScript { path, dry_run }) => app.cmd_run_script(&path, dry_run).await?,
+        Some(Commands::Which { path }) => app.cmd_which(&path).await?,
+        Some(Commands::History { action, limit }) => {
+            app.cmd_history(action.as_deref(), limit).await?
+        }
+        Some(Commands::Daemon { socket, action }) => match action {
+            None => app.cmd_daemon(socket.as_deref()).await?,
+            Some(DaemonCommands::Call { method, params }) => {
+                app.cmd_daemon_call(socket.as_deref(), &method, params.as_deref())
+                    .await?
+            }
+        },
+        Some(Commands::Config { .. }) => {
+            unreachable!("config commands run before loading the config")
+        }
+        Some(Commands::Logs { action }) => match action {
+            LogsCommands::Tail { lines, follow } => app.cmd_logs_tail(lines, follow).await?,
+            LogsCommands::Clear => app.cmd_logs_clear().await?,
+        },
+        Some(Commands::Db { action }) => match action {
+            DbCommands::Backup { path } => app.cmd_db_backup(path.as_deref()).await?,
+            DbCommands::Check => app.cmd_db_check().await?,
+            DbCommands::Vacuum => app.cmd_db_vacuum().await?,
+            DbCommands::Export { path } => app.cmd_db_export(&path).await?,
+            DbCommands::Import { path } => app.cmd_db_import(&path).await?,
+        },
         Some(Commands::Doctor { verbose }) => app.cmd_doctor(verbose).await?,
         Some(Commands::Init {
             interactive,
@@ -572,3 +1709,39 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supports(args: &[&str]) -> bool {
+        let cli =
+            Cli::try_parse_from(std::iter::once("modsanity").chain(args.iter().copied())).unwrap();
+        assert!(cli.json);
+        supports_json(&cli.command)
+    }
+
+    #[test]
+    fn test_json_flag_on_listing_commands_only() {
+        assert!(supports(&["--json", "mod", "list"]));
+        assert!(supports(&["profile", "list", "--json"]));
+        assert!(supports(&["status", "--json"]));
+        assert!(!supports(&["status", "--disk", "--json"]));
+        assert!(!supports(&["--json", "deploy"]));
+        assert!(!supports(&["--json"]));
+        assert!(supports(&["config", "validate", "--json"]));
+        assert!(supports(&["mod", "trash", "--json"]));
+        assert!(!supports(&["mod", "trash", "--empty", "--json"]));
+    }
+
+    #[test]
+    fn test_play_takes_the_instance_lock_unless_only_checking() {
+        let command = |args: &[&str]| {
+            Cli::try_parse_from(std::iter::once("modsanity").chain(args.iter().copied()))
+                .unwrap()
+                .command
+        };
+        assert!(instance_lock_command(&command(&["play"])).is_some());
+        assert!(instance_lock_command(&command(&["play", "--check"])).is_none());
+    }
+}
@@ -0,0 +1,249 @@
+//! Clipboard access
+//!
+//! Reads the system clipboard through the usual command-line helpers
+//! (wl-paste, xclip, xsel, pbpaste) and works out what a copied link or path
+//! points at: a Nexus mod page or nxm:// link, a local archive, or a modlist
+//! file or URL. Copying (the log pane, yanked links, paths and plugin names)
+//! goes through the matching helpers (wl-copy, xclip, xsel, pbcopy). Text
+//! pasted into a prompt is flattened to the single line prompts take.
+
+use anyhow::{bail, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Something the clipboard can be installed or loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardTarget {
+    /// Nexus mod page or nxm:// link, optionally naming one file
+    NexusMod {
+        game_domain: String,
+        mod_id: i64,
+        file_id: Option<i64>,
+    },
+    /// Mod archive on disk
+    Archive(PathBuf),
+    /// Modlist (MO2 modlist.txt or native JSON) on disk
+    ModlistFile(PathBuf),
+    /// Modlist to download first
+    ModlistUrl(String),
+}
+
+/// Text currently on the clipboard
+pub fn read_text() -> Result<String> {
+    let mut commands: Vec<(&str, &[&str])> = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-paste", &["--no-newline"]));
+    }
+    commands.push(("xclip", &["-selection", "clipboard", "-o"]));
+    commands.push(("xsel", &["--clipboard", "--output"]));
+    commands.push(("pbpaste", &[]));
+
+    for (program, args) in commands {
+        if which::which(program).is_err() {
+            continue;
+        }
+        match Command::new(program).args(args).output() {
+            Ok(output) if output.status.success() => {
+                return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+            }
+            Ok(output) => tracing::debug!("{} exited with {}", program, output.status),
+            Err(e) => tracing::debug!("Failed to run {}: {}", program, e),
+        }
+    }
+    bail!("Couldn't read the clipboard (install wl-clipboard, xclip or xsel)")
+}
+
+/// Put `text` on the clipboard
+pub fn write_text(text: &str) -> Result<()> {
+    let mut commands: Vec<(&str, &[&str])> = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-copy", &[]));
+    }
+    commands.push(("xclip", &["-selection", "clipboard", "-i"]));
+    commands.push(("xsel", &["--clipboard", "--input"]));
+    commands.push(("pbcopy", &[]));
+
+    for (program, args) in commands {
+        if which::which(program).is_err() {
+            continue;
+        }
+        // Output is discarded: the helpers stay around to serve the selection
+        // and must neither hold our pipes nor write over the TUI
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                tracing::debug!("Failed to run {}: {}", program, e);
+                continue;
+            }
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        match child.wait() {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => tracing::debug!("{} exited with {}", program, status),
+            Err(e) => tracing::debug!("Failed to run {}: {}", program, e),
+        }
+    }
+    bail!("Couldn't write to the clipboard (install wl-clipboard, xclip or xsel)")
+}
+
+/// Page of a mod on the Nexus website
+pub fn nexus_mod_url(game_domain: &str, mod_id: i64) -> String {
+    format!("https://www.nexusmods.com/{}/mods/{}", game_domain, mod_id)
+}
+
+/// `text` as one line for a text prompt: trailing line breaks dropped, inner
+/// ones and tabs turned into spaces and other control characters removed
+pub fn pasted_line(text: &str) -> String {
+    text.trim_end_matches(['\r', '\n'])
+        .chars()
+        .filter_map(|c| match c {
+            '\r' | '\n' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// What `text` points at, if it's something ModSanity can install or load
+pub fn classify(text: &str) -> Option<ClipboardTarget> {
+    let text = text.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let text = text.trim_matches(|c| c == '"' || c == '\'' || c == '<' || c == '>');
+
+    if let Some(rest) = text.strip_prefix("nxm://") {
+        return parse_nexus_path(rest);
+    }
+    if let Some(url) = text
+        .strip_prefix("https://")
+        .or_else(|| text.strip_prefix("http://"))
+    {
+        let (host, path) = url.split_once('/').unwrap_or((url, ""));
+        if host.eq_ignore_ascii_case("nexusmods.com") || host.ends_with(".nexusmods.com") {
+            return parse_nexus_path(path);
+        }
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        return has_extension(Path::new(path), &["txt", "json"])
+            .then(|| ClipboardTarget::ModlistUrl(text.to_string()));
+    }
+
+    let path = text.strip_prefix("file://").unwrap_or(text);
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => std::env::var("HOME")
+            .map(|home| PathBuf::from(home).join(rest))
+            .unwrap_or_else(|_| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    };
+    if !path.is_file() {
+        return None;
+    }
+    if has_extension(&path, &["zip", "7z", "rar"]) {
+        Some(ClipboardTarget::Archive(path))
+    } else if has_extension(&path, &["txt", "json"]) {
+        Some(ClipboardTarget::ModlistFile(path))
+    } else {
+        None
+    }
+}
+
+/// `<game>/mods/<id>[/files/<file_id>]`, with the file also accepted as a
+/// `file_id` query parameter (the Files tab)
+fn parse_nexus_path(path: &str) -> Option<ClipboardTarget> {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let path = path.split('#').next().unwrap_or_default();
+    let mut parts = path.split('/').filter(|p| !p.is_empty());
+    let game_domain = parts.next()?.to_lowercase();
+    if parts.next()? != "mods" {
+        return None;
+    }
+    let mod_id = parts.next()?.parse().ok()?;
+    let file_id = match (parts.next(), parts.next()) {
+        (Some("files"), Some(id)) => id.parse().ok(),
+        _ => query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("file_id="))
+            .and_then(|id| id.parse().ok()),
+    };
+    Some(ClipboardTarget::NexusMod {
+        game_domain,
+        mod_id,
+        file_id,
+    })
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.iter().any(|x| e.eq_ignore_ascii_case(x)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_links_and_paths() {
+        assert_eq!(
+            classify("https://www.nexusmods.com/skyrimspecialedition/mods/12604?file_id=42\n"),
+            Some(ClipboardTarget::NexusMod {
+                game_domain: "skyrimspecialedition".to_string(),
+                mod_id: 12604,
+                file_id: Some(42),
+            })
+        );
+        assert_eq!(
+            classify("nxm://fallout4/mods/47/files/1001?key=abc&expires=1"),
+            Some(ClipboardTarget::NexusMod {
+                game_domain: "fallout4".to_string(),
+                mod_id: 47,
+                file_id: Some(1001),
+            })
+        );
+        assert_eq!(
+            classify("https://example.com/guide/modlist.txt"),
+            Some(ClipboardTarget::ModlistUrl(
+                "https://example.com/guide/modlist.txt".to_string()
+            ))
+        );
+        assert_eq!(classify("https://www.nexusmods.com/games"), None);
+        assert_eq!(classify("just some text"), None);
+
+        let dir = std::env::temp_dir().join(format!("modsanity_clipboard_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("SkyUI.7z");
+        std::fs::write(&archive, b"").unwrap();
+        assert_eq!(
+            classify(&format!("'{}'", archive.display())),
+            Some(ClipboardTarget::Archive(archive))
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn pasted_text_becomes_one_line() {
+        assert_eq!(
+            pasted_line("~/Downloads/SkyUI.7z\n"),
+            "~/Downloads/SkyUI.7z"
+        );
+        assert_eq!(pasted_line("a\r\nb\tc\u{1b}[0m"), "a  b c[0m");
+        assert_eq!(
+            nexus_mod_url("skyrimspecialedition", 12604),
+            "https://www.nexusmods.com/skyrimspecialedition/mods/12604"
+        );
+        assert_eq!(
+            classify(&nexus_mod_url("fallout4", 47)),
+            Some(ClipboardTarget::NexusMod {
+                game_domain: "fallout4".to_string(),
+                mod_id: 47,
+                file_id: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,425 @@
+//! Inline image previews
+//!
+//! Screens call [`draw_preview`] while rendering. With a graphics protocol
+//! (kitty, iTerm2, sixel) the area is left blank and an image request is
+//! recorded; [`ImageOverlay`] writes the escape sequence after the frame is
+//! flushed, since the cell buffer cannot carry graphics. Otherwise the image
+//! is drawn as ASCII art, or a placeholder when it can't be decoded.
+
+use crossterm::{cursor::MoveTo, queue, style::Print};
+use image::{imageops::FilterType, DynamicImage, RgbaImage};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Largest image file that will be decoded for a preview
+const MAX_IMAGE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Cell size to assume when the terminal doesn't report pixel dimensions
+const FALLBACK_CELL_PX: (u32, u32) = (10, 20);
+
+const ASCII_RAMP: &[u8] = b" .:-=+*#%@";
+
+/// How images are put on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+    Ascii,
+}
+
+impl GraphicsProtocol {
+    /// Parse the `tui.image_previews` setting; "auto" detects the terminal
+    pub fn from_config(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "kitty" => Self::Kitty,
+            "iterm2" | "iterm" => Self::Iterm2,
+            "sixel" => Self::Sixel,
+            "ascii" | "off" | "none" => Self::Ascii,
+            _ => Self::detect(),
+        }
+    }
+
+    /// Guess the protocol from the environment the terminal exports
+    pub fn detect() -> Self {
+        static DETECTED: OnceLock<GraphicsProtocol> = OnceLock::new();
+        *DETECTED.get_or_init(|| {
+            let var = |key: &str| std::env::var(key).unwrap_or_default().to_lowercase();
+            let (term, program) = (var("TERM"), var("TERM_PROGRAM"));
+
+            // Multiplexers swallow graphics unless explicitly configured
+            if std::env::var_os("TMUX").is_some() || term.starts_with("screen") {
+                Self::Ascii
+            } else if std::env::var_os("KITTY_WINDOW_ID").is_some()
+                || term.contains("kitty")
+                || term.contains("ghostty")
+                || program == "ghostty"
+            {
+                Self::Kitty
+            } else if program == "iterm.app"
+                || program == "wezterm"
+                || std::env::var_os("WEZTERM_EXECUTABLE").is_some()
+            {
+                Self::Iterm2
+            } else if term.contains("foot")
+                || term.contains("mlterm")
+                || term.contains("sixel")
+                || program == "contour"
+            {
+                Self::Sixel
+            } else {
+                Self::Ascii
+            }
+        })
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Kitty => 1,
+            Self::Iterm2 => 2,
+            Self::Sixel => 3,
+            Self::Ascii => 0,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Kitty,
+            2 => Self::Iterm2,
+            3 => Self::Sixel,
+            _ => Self::Ascii,
+        }
+    }
+}
+
+static PROTOCOL: AtomicU8 = AtomicU8::new(0);
+static REQUEST: Mutex<Option<ImageRequest>> = Mutex::new(None);
+static ASCII_CACHE: Mutex<Option<AsciiPreview>> = Mutex::new(None);
+
+/// Set the protocol for this frame and forget the previous frame's request
+pub fn begin_frame(protocol: GraphicsProtocol) {
+    PROTOCOL.store(protocol.to_u8(), Ordering::Relaxed);
+    *REQUEST.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Drop this frame's image request, e.g. when a popup covers the screen
+pub fn suppress() {
+    *REQUEST.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Draw a preview of `path` (an image inside the mod) into `area`
+pub fn draw_preview(f: &mut Frame, area: Rect, path: Option<&Path>, label: &str) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    if inner.width < 2 || inner.height < 2 {
+        return;
+    }
+
+    let protocol = GraphicsProtocol::from_u8(PROTOCOL.load(Ordering::Relaxed));
+    let Some(path) = path else {
+        draw_placeholder(f, inner, label, "image not found");
+        return;
+    };
+
+    if protocol != GraphicsProtocol::Ascii {
+        // Shown until the image is written over it, or if encoding fails
+        draw_placeholder(f, inner, label, "loading preview");
+        *REQUEST.lock().unwrap_or_else(|e| e.into_inner()) = Some(ImageRequest {
+            area: inner,
+            path: path.to_path_buf(),
+            protocol,
+        });
+        return;
+    }
+
+    let mut cache = ASCII_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if !cache
+        .as_ref()
+        .is_some_and(|c| c.path == path && c.width == inner.width && c.height == inner.height)
+    {
+        *cache = Some(AsciiPreview {
+            path: path.to_path_buf(),
+            width: inner.width,
+            height: inner.height,
+            lines: load_image(path).map(|img| ascii_art(&img, inner.width, inner.height)),
+        });
+    }
+    match cache.as_ref().and_then(|c| c.lines.as_ref()) {
+        Some(lines) => {
+            let lines: Vec<Line> = lines.iter().map(|l| Line::from(l.as_str())).collect();
+            f.render_widget(
+                Paragraph::new(lines)
+                    .style(Style::default().fg(Color::Gray))
+                    .alignment(Alignment::Center),
+                inner,
+            );
+        }
+        None => draw_placeholder(f, inner, label, "preview unavailable"),
+    }
+}
+
+fn draw_placeholder(f: &mut Frame, area: Rect, label: &str, reason: &str) {
+    let mut lines = vec![Line::from(""); (area.height.saturating_sub(3) / 2) as usize];
+    lines.push(Line::from("[ image ]"));
+    lines.push(Line::from(label.to_string()));
+    lines.push(Line::from(format!("({})", reason)));
+    f.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center),
+        area,
+    );
+}
+
+struct AsciiPreview {
+    path: PathBuf,
+    width: u16,
+    height: u16,
+    lines: Option<Vec<String>>,
+}
+
+/// An image to place over the given cells once the frame is on screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageRequest {
+    area: Rect,
+    path: PathBuf,
+    protocol: GraphicsProtocol,
+}
+
+/// Tracks the image currently on screen for graphics protocols
+#[derive(Default)]
+pub struct ImageOverlay {
+    shown: Option<ImageRequest>,
+}
+
+impl ImageOverlay {
+    /// The request of the frame just drawn, if it differs from what's shown
+    pub fn take_change(&mut self) -> Option<Option<ImageRequest>> {
+        let requested = REQUEST.lock().unwrap_or_else(|e| e.into_inner()).take();
+        (requested != self.shown).then_some(requested)
+    }
+
+    /// Forget the shown image, deleting it where the terminal keeps it
+    /// separately from text. Returns whether the screen needs repainting.
+    pub fn hide(&mut self, out: &mut impl Write) -> io::Result<bool> {
+        let Some(shown) = self.shown.take() else {
+            return Ok(false);
+        };
+        if shown.protocol == GraphicsProtocol::Kitty {
+            out.write_all(b"\x1b_Ga=d,d=A,q=2\x1b\\")?;
+            out.flush()?;
+        }
+        Ok(true)
+    }
+
+    /// Write `request` to the terminal
+    pub fn show(&mut self, out: &mut impl Write, request: ImageRequest) -> io::Result<()> {
+        // Remember failures too, so a broken image isn't decoded every tick
+        if let Some((x, y, sequence)) = encode(&request) {
+            queue!(out, MoveTo(x, y), Print(sequence))?;
+            out.flush()?;
+        }
+        self.shown = Some(request);
+        Ok(())
+    }
+}
+
+fn load_image(path: &Path) -> Option<DynamicImage> {
+    let bytes = read_image_bytes(path)?;
+    image::load_from_memory(&bytes).ok()
+}
+
+fn read_image_bytes(path: &Path) -> Option<Vec<u8>> {
+    if std::fs::metadata(path).ok()?.len() > MAX_IMAGE_BYTES {
+        return None;
+    }
+    std::fs::read(path).ok()
+}
+
+/// Escape sequence and cell position for a request
+fn encode(request: &ImageRequest) -> Option<(u16, u16, String)> {
+    let area = request.area;
+    if request.protocol == GraphicsProtocol::Iterm2 {
+        // iTerm2 decodes the file itself and fits it to the cell box
+        let bytes = read_image_bytes(&request.path)?;
+        let sequence = format!(
+            "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+            bytes.len(),
+            area.width,
+            area.height,
+            base64(&bytes)
+        );
+        return Some((area.x, area.y, sequence));
+    }
+
+    let (cell_w, cell_h) = cell_size();
+    let img = load_image(&request.path)?
+        .resize(
+            area.width as u32 * cell_w,
+            area.height as u32 * cell_h,
+            FilterType::Triangle,
+        )
+        .to_rgba8();
+    let used_cols = img.width().div_ceil(cell_w) as u16;
+    let x = area.x + area.width.saturating_sub(used_cols) / 2;
+
+    let sequence = match request.protocol {
+        GraphicsProtocol::Kitty => kitty(&img),
+        GraphicsProtocol::Sixel => sixel(&img),
+        GraphicsProtocol::Iterm2 | GraphicsProtocol::Ascii => return None,
+    };
+    Some((x, area.y, sequence))
+}
+
+fn cell_size() -> (u32, u32) {
+    crossterm::terminal::window_size()
+        .ok()
+        .filter(|s| s.width > 0 && s.height > 0 && s.columns > 0 && s.rows > 0)
+        .map(|s| {
+            (
+                (s.width / s.columns).max(1) as u32,
+                (s.height / s.rows).max(1) as u32,
+            )
+        })
+        .unwrap_or(FALLBACK_CELL_PX)
+}
+
+/// Kitty graphics: raw RGBA in base64 chunks, cursor left in place
+fn kitty(img: &RgbaImage) -> String {
+    let data = base64(img.as_raw());
+    let chunks: Vec<&str> = data
+        .as_bytes()
+        .chunks(4096)
+        .map(|c| std::str::from_utf8(c).unwrap_or_default())
+        .collect();
+    let mut out = String::with_capacity(data.len() + chunks.len() * 16);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            let _ = write!(
+                out,
+                "\x1b_Ga=T,f=32,s={},v={},C=1,q=2,m={};{}\x1b\\",
+                img.width(),
+                img.height(),
+                more,
+                chunk
+            );
+        } else {
+            let _ = write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk);
+        }
+    }
+    out
+}
+
+/// Sixel graphics quantized to a 6x6x6 colour cube; transparent pixels are
+/// left unpainted
+fn sixel(img: &RgbaImage) -> String {
+    let (width, height) = img.dimensions();
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+    for i in 0..216 {
+        let _ = write!(
+            out,
+            "#{};2;{};{};{}",
+            i,
+            (i / 36) * 20,
+            (i / 6 % 6) * 20,
+            (i % 6) * 20
+        );
+    }
+
+    let level = |c: u8| (c as usize * 5 + 127) / 255;
+    for band in (0..height).step_by(6) {
+        // Colour index -> sixel bits per column for this band
+        let mut bands: Vec<Option<Vec<u8>>> = vec![None; 216];
+        for dy in 0..(height - band).min(6) {
+            for x in 0..width {
+                let [r, g, b, a] = img.get_pixel(x, band + dy).0;
+                if a < 128 {
+                    continue;
+                }
+                let color = level(r) * 36 + level(g) * 6 + level(b);
+                bands[color].get_or_insert_with(|| vec![0; width as usize])[x as usize] |= 1 << dy;
+            }
+        }
+        for (color, bits) in bands.iter().enumerate() {
+            if let Some(bits) = bits {
+                let _ = write!(out, "#{}", color);
+                push_sixel_run(&mut out, bits);
+                out.push('$');
+            }
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, bits: &[u8]) {
+    let mut i = 0;
+    while i < bits.len() {
+        let run = bits[i..].iter().take_while(|&&b| b == bits[i]).count();
+        let ch = (63 + bits[i]) as char;
+        if run > 3 {
+            let _ = write!(out, "!{}{}", run, ch);
+        } else {
+            (0..run).for_each(|_| out.push(ch));
+        }
+        i += run;
+    }
+}
+
+/// Luminance ramp art; cells are about twice as tall as wide, so each
+/// character covers two pixel rows
+fn ascii_art(img: &DynamicImage, cols: u16, rows: u16) -> Vec<String> {
+    let luma = img
+        .resize(cols as u32, rows as u32 * 2, FilterType::Triangle)
+        .to_luma8();
+    let (width, height) = luma.dimensions();
+    (0..height.div_ceil(2))
+        .map(|row| {
+            (0..width)
+                .map(|x| {
+                    let top = luma.get_pixel(x, row * 2)[0] as usize;
+                    let bottom = if row * 2 + 1 < height {
+                        luma.get_pixel(x, row * 2 + 1)[0] as usize
+                    } else {
+                        top
+                    };
+                    ASCII_RAMP[(top + bottom) / 2 * (ASCII_RAMP.len() - 1) / 255] as char
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = ((chunk[0] as u32) << 16)
+            | ((*chunk.get(1).unwrap_or(&0) as u32) << 8)
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
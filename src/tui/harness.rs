@@ -0,0 +1,429 @@
+//! Headless TUI driver for end-to-end tests (`test-harness` feature)
+//!
+//! Runs the real key handlers and renderer against a ratatui `TestBackend`,
+//! so tests can press keys and assert on the rendered screen. Pair it with
+//! `modsanity_core::testing::{MockNexus, Sandbox}` to keep runs offline and
+//! isolated from the user's configuration.
+
+use super::{ui, Tui};
+use crate::app::{App, AppState, EventReceiver};
+use crate::testing::{MockNexus, Sandbox};
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use std::time::{Duration, Instant};
+
+/// Default terminal size used by [`HeadlessTui::start`]
+pub const DEFAULT_SIZE: (u16, u16) = (160, 48);
+
+/// How often `wait_*` helpers re-check the screen
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Build an `App` whose config, database and game live in `sandbox`
+pub async fn sandboxed_app(sandbox: &Sandbox, mock: Option<&MockNexus>) -> Result<App> {
+    let mut app = App::new(sandbox.config(mock))
+        .await
+        .context("Failed to create sandboxed app")?;
+
+    // Ignore whatever games are installed on the host
+    let game = sandbox.game();
+    app.games = vec![game.clone()];
+    app.state.write().await.active_game = Some(game);
+    Ok(app)
+}
+
+/// A TUI instance driven by synthetic key events
+pub struct HeadlessTui {
+    pub app: App,
+    tui: Tui<TestBackend>,
+    events: EventReceiver,
+}
+
+impl HeadlessTui {
+    /// Start a headless TUI of [`DEFAULT_SIZE`]
+    pub async fn start(app: App) -> Result<Self> {
+        Self::with_size(app, DEFAULT_SIZE.0, DEFAULT_SIZE.1).await
+    }
+
+    /// Start a headless TUI with an explicit terminal size
+    pub async fn with_size(mut app: App, width: u16, height: u16) -> Result<Self> {
+        let events = app
+            .take_event_receiver()
+            .context("App event receiver already taken")?;
+        let tui = Tui::with_backend(TestBackend::new(width, height))?;
+        tui.load_initial_data(&mut app).await?;
+
+        let mut headless = Self { app, tui, events };
+        headless.render().await?;
+        Ok(headless)
+    }
+
+    /// Press a key without modifiers
+    pub async fn press(&mut self, key: KeyCode) -> Result<()> {
+        self.press_with(key, KeyModifiers::NONE).await
+    }
+
+    /// Press a key with modifiers, then re-render
+    pub async fn press_with(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+        self.tui.handle_key(&mut self.app, key, modifiers).await?;
+        self.render().await
+    }
+
+    /// Type each character of `text` as a key press
+    pub async fn type_text(&mut self, text: &str) -> Result<()> {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c)).await?;
+        }
+        Ok(())
+    }
+
+    /// Apply pending background events and draw a frame, like one event-loop tick
+    pub async fn render(&mut self) -> Result<()> {
+        let snapshot = {
+            let mut state = self.app.state.write().await;
+            self.events.drain_into(&mut state);
+            state.clone()
+        };
+        let app = &self.app;
+        self.tui.terminal.draw(|f| ui::draw(f, app, &snapshot))?;
+        Ok(())
+    }
+
+    /// The last rendered frame
+    pub fn buffer(&self) -> &Buffer {
+        self.tui.terminal.backend().buffer()
+    }
+
+    /// The last rendered frame as plain text, one line per terminal row
+    pub fn screen_text(&self) -> String {
+        let buffer = self.buffer();
+        let area = buffer.area;
+        let mut out = String::with_capacity((area.width as usize + 1) * area.height as usize);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                if let Some(cell) = buffer.cell((x, y)) {
+                    out.push_str(cell.symbol());
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Whether the last rendered frame contains `needle`
+    pub fn contains(&self, needle: &str) -> bool {
+        self.screen_text().contains(needle)
+    }
+
+    /// Render until `needle` appears on screen or `timeout` elapses
+    pub async fn wait_for_text(&mut self, needle: &str, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.render().await?;
+            if self.contains(needle) {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                bail!(
+                    "Timed out waiting for {:?}; screen:\n{}",
+                    needle,
+                    self.screen_text()
+                );
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Render until `predicate` holds for the state or `timeout` elapses
+    pub async fn wait_until(
+        &mut self,
+        timeout: Duration,
+        predicate: impl Fn(&AppState) -> bool,
+    ) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.render().await?;
+            if predicate(&*self.app.state.read().await) {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                bail!(
+                    "Timed out waiting for state condition; status: {:?}; screen:\n{}",
+                    self.app.state.read().await.status_message(),
+                    self.screen_text()
+                );
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{InputMode, Screen};
+    use crate::import::modlist_format::{save_native, ModSanityModlist, ModlistEntry, ModlistMeta};
+    use crate::queue::QueueStatus;
+    use crate::testing::zip_bytes;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+    const DOMAIN: &str = "skyrimspecialedition";
+
+    fn write_modlist(sandbox: &Sandbox, mods: &[(&str, i64, i64)]) -> String {
+        let modlist = ModSanityModlist {
+            meta: ModlistMeta {
+                format_version: 1,
+                modsanity_version: crate::APP_VERSION.to_string(),
+                game_id: "skyrimse".to_string(),
+                game_domain: DOMAIN.to_string(),
+                exported_at: "2026-01-01T00:00:00Z".to_string(),
+                profile_name: None,
+            },
+            mods: mods
+                .iter()
+                .enumerate()
+                .map(|(i, (name, mod_id, file_id))| ModlistEntry {
+                    name: name.to_string(),
+                    version: "1.0".to_string(),
+                    nexus_mod_id: Some(*mod_id),
+                    nexus_file_id: Some(*file_id),
+                    author: None,
+                    priority: i as i32,
+                    enabled: true,
+                    category: None,
+                })
+                .collect(),
+            plugins: Vec::new(),
+        };
+        let path = sandbox.root().join("modlist.json");
+        save_native(&path, &modlist).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    /// Load a native modlist through the TUI and queue its downloads
+    async fn import_and_queue(tui: &mut HeadlessTui, path: &str) {
+        tui.app.state.write().await.input_mode = InputMode::LoadModlistPath;
+        tui.type_text(path).await.unwrap();
+        tui.press(KeyCode::Enter).await.unwrap();
+        tui.wait_until(TIMEOUT, |s| s.current_screen == Screen::ModlistReview)
+            .await
+            .unwrap();
+
+        tui.press(KeyCode::Enter).await.unwrap();
+        tui.wait_until(TIMEOUT, |s| s.current_screen == Screen::DownloadQueue)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_install_archive_shows_on_mods_screen() {
+        let sandbox = Sandbox::new().unwrap();
+        let archive = sandbox
+            .write_archive("Harness Mod-1-1-0.zip", &[("HarnessMod.esp", b"TES4")])
+            .unwrap();
+        let mut tui = HeadlessTui::start(sandboxed_app(&sandbox, None).await.unwrap())
+            .await
+            .unwrap();
+
+        tui.press(KeyCode::Char('1')).await.unwrap();
+        tui.press(KeyCode::Char('i')).await.unwrap();
+        tui.type_text(&archive.to_string_lossy()).await.unwrap();
+        tui.press(KeyCode::Enter).await.unwrap();
+
+        tui.wait_for_text("Installed: ", TIMEOUT).await.unwrap();
+        assert_eq!(tui.app.state.read().await.installed_mods.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mark_range_and_bulk_disable_mods() {
+        let sandbox = Sandbox::new().unwrap();
+        let app = sandboxed_app(&sandbox, None).await.unwrap();
+        for name in ["Alpha", "Bravo", "Charlie", "Delta"] {
+            let archive = sandbox
+                .write_archive(
+                    &format!("{}-1-1-0.zip", name),
+                    &[(&format!("{}.esp", name), b"TES4")],
+                )
+                .unwrap();
+            app.mods
+                .install_from_archive(
+                    "skyrimse",
+                    &archive.to_string_lossy(),
+                    None,
+                    None,
+                    None,
+                    Some(name),
+                )
+                .await
+                .unwrap();
+        }
+        let mods = app.mods.list_mods("skyrimse").await.unwrap();
+        let mut tui = HeadlessTui::start(app).await.unwrap();
+        tui.press(KeyCode::Char('1')).await.unwrap();
+        {
+            let mut state = tui.app.state.write().await;
+            state.installed_mods = mods;
+            let alpha = state
+                .installed_mods
+                .iter()
+                .find(|m| m.name == "Alpha")
+                .unwrap()
+                .id;
+            state.selected_mod_index = state.reveal_mod(alpha).unwrap();
+        }
+
+        // Space marks Alpha and moves to Bravo; V..V marks Bravo and Charlie
+        tui.press(KeyCode::Char(' ')).await.unwrap();
+        tui.press(KeyCode::Char('V')).await.unwrap();
+        tui.press(KeyCode::Down).await.unwrap();
+        tui.press(KeyCode::Char('V')).await.unwrap();
+        let marked: Vec<String> = {
+            let state = tui.app.state.read().await;
+            state.marked_mods().iter().map(|m| m.name.clone()).collect()
+        };
+        assert_eq!(marked, vec!["Alpha", "Bravo", "Charlie"]);
+
+        tui.press(KeyCode::Char('e')).await.unwrap();
+        let enabled: Vec<(String, bool)> = tui
+            .app
+            .mods
+            .list_mods("skyrimse")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.name, m.enabled))
+            .collect();
+        assert!(enabled
+            .iter()
+            .all(|(name, enabled)| *enabled == (name == "Delta")));
+        assert!(tui.contains("Disabled 3 marked mod(s)"));
+    }
+
+    #[tokio::test]
+    async fn test_launching_a_tool_offers_to_deploy_first() {
+        let sandbox = Sandbox::new().unwrap();
+        let app = sandboxed_app(&sandbox, None).await.unwrap();
+        let archive = sandbox
+            .write_archive("Alpha-1-1-0.zip", &[("Alpha.esp", b"TES4")])
+            .unwrap();
+        app.mods
+            .install_from_archive(
+                "skyrimse",
+                &archive.to_string_lossy(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let mut tui = HeadlessTui::start(app).await.unwrap();
+        {
+            let mut state = tui.app.state.write().await;
+            state.show_help = false;
+            state.goto(crate::app::Screen::Settings);
+            // SSEEdit's row
+            state.selected_setting_index = 10;
+        }
+
+        tui.press(KeyCode::Char('l')).await.unwrap();
+        assert!(tui.contains("Deployment Out of Date"));
+        let state = tui.app.state.read().await;
+        assert!(matches!(
+            state.show_confirm.as_ref().map(|c| &c.on_confirm),
+            Some(crate::app::state::ConfirmAction::DeployAndLaunchTool(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_narrow_terminal_shortens_tabs() {
+        let sandbox = Sandbox::new().unwrap();
+        let mut tui = HeadlessTui::with_size(sandboxed_app(&sandbox, None).await.unwrap(), 80, 24)
+            .await
+            .unwrap();
+        tui.app.state.write().await.show_help = false;
+
+        tui.press(KeyCode::F(2)).await.unwrap();
+        assert!(tui.contains("F2 Plugins"));
+        assert!(!tui.contains("F1 Mods"));
+        assert!(tui.contains("F11"));
+
+        let mut tiny = HeadlessTui::with_size(sandboxed_app(&sandbox, None).await.unwrap(), 30, 10)
+            .await
+            .unwrap();
+        tiny.render().await.unwrap();
+        assert!(tiny.contains("Terminal too small"));
+    }
+
+    #[tokio::test]
+    async fn test_import_queue_downloads_and_installs_from_mock() {
+        let mock = MockNexus::start().await.unwrap();
+        mock.add_mod(DOMAIN, 101, "Mock Weather").add_file(
+            DOMAIN,
+            101,
+            9001,
+            "Mock Weather-101-1-0.zip",
+            "1.0",
+            zip_bytes(&[("MockWeather.esp", b"TES4")]).unwrap(),
+        );
+
+        let sandbox = Sandbox::new().unwrap();
+        let path = write_modlist(&sandbox, &[("Mock Weather", 101, 9001)]);
+        let mut tui = HeadlessTui::start(sandboxed_app(&sandbox, Some(&mock)).await.unwrap())
+            .await
+            .unwrap();
+
+        import_and_queue(&mut tui, &path).await;
+        assert!(tui.contains("Mock Weather"));
+
+        tui.press(KeyCode::Char('p')).await.unwrap();
+        tui.wait_until(TIMEOUT, |s| !s.queue_processing)
+            .await
+            .unwrap();
+
+        let state = tui.app.state.read().await;
+        assert_eq!(state.queue_entries.len(), 1);
+        assert_eq!(state.queue_entries[0].status, QueueStatus::Completed);
+        drop(state);
+
+        let installed = tui.app.mods.list_mods("skyrimse").await.unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].nexus_mod_id, Some(101));
+        assert_eq!(mock.request_count("/files/9001/"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_queue_reports_premium_requirement() {
+        let mock = MockNexus::start().await.unwrap();
+        mock.set_premium(false).add_file(
+            DOMAIN,
+            202,
+            9002,
+            "Locked-202-1-0.zip",
+            "1.0",
+            Vec::new(),
+        );
+
+        let sandbox = Sandbox::new().unwrap();
+        let path = write_modlist(&sandbox, &[("Locked Mod", 202, 9002)]);
+        let mut tui = HeadlessTui::start(sandboxed_app(&sandbox, Some(&mock)).await.unwrap())
+            .await
+            .unwrap();
+
+        import_and_queue(&mut tui, &path).await;
+        tui.press(KeyCode::Char('p')).await.unwrap();
+        tui.wait_until(TIMEOUT, |s| !s.queue_processing)
+            .await
+            .unwrap();
+
+        let state = tui.app.state.read().await;
+        assert_eq!(state.queue_entries[0].status, QueueStatus::Failed);
+        assert!(state.queue_entries[0]
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("Premium"));
+        assert_eq!(mock.request_count("/files/"), 0);
+    }
+}
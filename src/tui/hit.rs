@@ -0,0 +1,123 @@
+//! Mouse hit testing for lists
+//!
+//! Lists are scrolled by ratatui while drawing, so only the draw knows which
+//! item ended up on which row. Each screen's main list records where it was
+//! drawn and its scroll offset; the mouse handler maps clicks back to items
+//! from that record.
+
+use crate::app::Screen;
+use ratatui::layout::{Margin, Rect};
+use ratatui::widgets::{List, ListState};
+use ratatui::Frame;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Longest gap between the clicks of a double-click
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Main list of a screen as last drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListRegion {
+    pub screen: Screen,
+    /// Rows the items were drawn in, inside the list's border
+    pub area: Rect,
+    /// Index of the item on the first row
+    pub offset: usize,
+    pub len: usize,
+}
+
+impl ListRegion {
+    /// Item drawn at a cell; items are one row each
+    pub fn index_at(&self, column: u16, row: u16) -> Option<usize> {
+        if column < self.area.x
+            || column >= self.area.right()
+            || row < self.area.y
+            || row >= self.area.bottom()
+        {
+            return None;
+        }
+        let index = self.offset + (row - self.area.y) as usize;
+        (index < self.len).then_some(index)
+    }
+}
+
+static MAIN_LIST: Mutex<Option<ListRegion>> = Mutex::new(None);
+
+/// Forget the previous frame's list
+pub fn begin_frame() {
+    *MAIN_LIST.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Draw `screen`'s main list into `area`, which it borders on every side,
+/// and record where its items landed
+pub fn render_list(f: &mut Frame, screen: Screen, list: List, area: Rect, state: &mut ListState) {
+    let len = list.len();
+    f.render_stateful_widget(list, area, state);
+    *MAIN_LIST.lock().unwrap_or_else(|e| e.into_inner()) = Some(ListRegion {
+        screen,
+        area: area.inner(Margin::new(1, 1)),
+        offset: state.offset(),
+        len,
+    });
+}
+
+/// Index of the item of `screen`'s main list at a cell
+pub fn list_index_at(screen: Screen, column: u16, row: u16) -> Option<usize> {
+    let region = (*MAIN_LIST.lock().unwrap_or_else(|e| e.into_inner()))?;
+    if region.screen != screen {
+        return None;
+    }
+    region.index_at(column, row)
+}
+
+/// Click history, for telling double-clicks and drags apart
+#[derive(Debug, Default)]
+pub struct Clicks {
+    last: Option<(Instant, Screen, usize)>,
+    /// Whether the drag in progress has been recorded for undo
+    pub drag_recorded: bool,
+}
+
+impl Clicks {
+    /// Register a click on an item at `now`, returning whether it completes
+    /// a double-click
+    pub fn click(&mut self, screen: Screen, index: usize, now: Instant) -> bool {
+        let double = self.last.is_some_and(|(at, s, i)| {
+            s == screen && i == index && now.duration_since(at) < DOUBLE_CLICK
+        });
+        // A third click starts over rather than making another double-click
+        self.last = (!double).then_some((now, screen, index));
+        self.drag_recorded = false;
+        double
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_at() {
+        let region = ListRegion {
+            screen: Screen::Plugins,
+            area: Rect::new(1, 5, 40, 10),
+            offset: 20,
+            len: 25,
+        };
+        assert_eq!(region.index_at(1, 5), Some(20));
+        assert_eq!(region.index_at(10, 9), Some(24));
+        // Past the last item, on the border and outside the list
+        assert_eq!(region.index_at(10, 10), None);
+        assert_eq!(region.index_at(0, 6), None);
+        assert_eq!(region.index_at(41, 6), None);
+        assert_eq!(region.index_at(10, 4), None);
+
+        let mut clicks = Clicks::default();
+        let now = Instant::now();
+        assert!(!clicks.click(Screen::Plugins, 3, now));
+        assert!(clicks.click(Screen::Plugins, 3, now + Duration::from_millis(100)));
+        assert!(!clicks.click(Screen::Plugins, 3, now + Duration::from_millis(200)));
+        assert!(!clicks.click(Screen::Plugins, 4, now + Duration::from_millis(300)));
+        assert!(!clicks.click(Screen::Plugins, 4, now + Duration::from_secs(2)));
+    }
+}
@@ -0,0 +1,247 @@
+//! Text prompt history and path completion
+//!
+//! Every text prompt remembers what was entered in it: Up and Down step
+//! through earlier entries for the same input mode, and the history is kept in
+//! `input_history.txt` in the data directory so it survives restarts. Prompts
+//! that take a path complete it with Tab like a shell.
+
+use crate::app::InputMode;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Entries kept per input mode
+const MAX_ENTRIES: usize = 50;
+
+/// Whether `mode` edits `input_buffer` as free text
+pub fn is_text_mode(mode: InputMode) -> bool {
+    !matches!(
+        mode,
+        InputMode::Normal | InputMode::FomodComponentSelection | InputMode::ModFilterPicker
+    )
+}
+
+/// Whether entries of `mode` are remembered; the API key never is
+fn keeps_history(mode: InputMode) -> bool {
+    is_text_mode(mode) && mode != InputMode::NexusApiKeyInput
+}
+
+/// Whether Tab completes a path in `mode`. The modlist save prompt uses Tab
+/// to switch format instead.
+pub fn completes_paths(mode: InputMode) -> bool {
+    matches!(
+        mode,
+        InputMode::ModInstallPath
+            | InputMode::CollectionPath
+            | InputMode::ModDirectoryInput
+            | InputMode::DownloadsDirectoryInput
+            | InputMode::StagingDirectoryInput
+            | InputMode::ProtonCommandInput
+            | InputMode::ExternalToolPathInput
+            | InputMode::ImportFilePath
+            | InputMode::LoadModlistPath
+            | InputMode::ModlistAddDirectoryInput
+    )
+}
+
+/// Earlier entries of each text prompt
+#[derive(Debug, Default)]
+pub struct InputHistory {
+    /// File the history is saved to; `None` keeps it in memory only
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, Vec<String>>,
+    /// Entry shown while browsing with Up/Down, and the text typed before
+    browsing: Option<(usize, String)>,
+}
+
+impl InputHistory {
+    /// Read the history saved at `path`; a missing or unreadable file starts
+    /// empty
+    pub fn load(path: PathBuf) -> Self {
+        let mut entries: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            for line in content.lines() {
+                if let Some((mode, text)) = line.split_once('\t') {
+                    entries
+                        .entry(mode.to_string())
+                        .or_default()
+                        .push(text.to_string());
+                }
+            }
+        }
+        Self {
+            path: Some(path),
+            entries,
+            browsing: None,
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut content = String::new();
+        for (mode, texts) in &self.entries {
+            for text in texts {
+                content.push_str(&format!("{}\t{}\n", mode, text));
+            }
+        }
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = std::fs::write(path, content) {
+            tracing::debug!("Failed to save input history: {}", e);
+        }
+    }
+
+    /// Remember `text` as the newest entry for `mode`
+    pub fn record(&mut self, mode: InputMode, text: &str) {
+        self.browsing = None;
+        let text = text.trim();
+        if !keeps_history(mode) || text.is_empty() || text.contains(['\t', '\n']) {
+            return;
+        }
+        let texts = self.entries.entry(format!("{:?}", mode)).or_default();
+        texts.retain(|t| t != text);
+        texts.push(text.to_string());
+        if texts.len() > MAX_ENTRIES {
+            texts.remove(0);
+        }
+        self.save();
+    }
+
+    /// The entry before the one shown, remembering `current` to come back to
+    pub fn previous(&mut self, mode: InputMode, current: &str) -> Option<String> {
+        let texts = self.entries.get(&format!("{:?}", mode))?;
+        let index = match &self.browsing {
+            Some((0, _)) => return None,
+            Some((index, _)) => index - 1,
+            None => texts.len().checked_sub(1)?,
+        };
+        let draft = match self.browsing.take() {
+            Some((_, draft)) => draft,
+            None => current.to_string(),
+        };
+        self.browsing = Some((index, draft));
+        texts.get(index).cloned()
+    }
+
+    /// The entry after the one shown, then the text typed before browsing
+    pub fn next(&mut self, mode: InputMode) -> Option<String> {
+        let texts = self.entries.get(&format!("{:?}", mode))?;
+        let (index, draft) = self.browsing.take()?;
+        if index + 1 < texts.len() {
+            self.browsing = Some((index + 1, draft));
+            texts.get(index + 1).cloned()
+        } else {
+            Some(draft)
+        }
+    }
+
+    /// Stop browsing, e.g. once the shown entry is edited
+    pub fn reset(&mut self) {
+        self.browsing = None;
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => std::env::var("HOME")
+            .map(|home| PathBuf::from(home).join(rest))
+            .unwrap_or_else(|_| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+/// `input` with its last path component completed: fully when one entry
+/// matches (with a trailing `/` for directories), otherwise as far as all
+/// matches agree. `None` when nothing matches or there is nothing to add.
+pub fn complete_path(input: &str) -> Option<String> {
+    let (dir_part, prefix) = match input.rfind('/') {
+        Some(i) => input.split_at(i + 1),
+        None => ("", input),
+    };
+    let dir = if dir_part.is_empty() {
+        PathBuf::from(".")
+    } else {
+        expand_home(dir_part)
+    };
+
+    let mut matches: Vec<(String, bool)> = std::fs::read_dir(&dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let hidden = name.starts_with('.') && !prefix.starts_with('.');
+            (name.starts_with(prefix) && !hidden).then(|| (name, is_dir(&e.path())))
+        })
+        .collect();
+    matches.sort();
+
+    let completed = match matches.as_slice() {
+        [] => return None,
+        [(name, true)] => format!("{}/", name),
+        [(name, false)] => name.clone(),
+        [(first, _), rest @ ..] => rest.iter().fold(first.clone(), |common, (name, _)| {
+            common
+                .chars()
+                .zip(name.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+        }),
+    };
+    (completed.len() > prefix.len()).then(|| format!("{}{}", dir_part, completed))
+}
+
+fn is_dir(path: &Path) -> bool {
+    std::fs::metadata(path).is_ok_and(|m| m.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_and_completion() {
+        let dir = std::env::temp_dir().join(format!("modsanity_input_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("Downloads")).unwrap();
+        std::fs::write(dir.join("SkyUI_5_2.7z"), b"").unwrap();
+        std::fs::write(dir.join("SkyUI_5_1.7z"), b"").unwrap();
+
+        let root = format!("{}/", dir.display());
+        assert_eq!(
+            complete_path(&format!("{}Down", root)),
+            Some(format!("{}Downloads/", root))
+        );
+        assert_eq!(
+            complete_path(&format!("{}Sky", root)),
+            Some(format!("{}SkyUI_5_", root))
+        );
+        assert_eq!(complete_path(&format!("{}SkyUI_5_", root)), None);
+
+        let path = dir.join("input_history.txt");
+        let mut history = InputHistory::load(path.clone());
+        history.record(InputMode::ModSearch, "skyui");
+        history.record(InputMode::ModSearch, "ussep");
+        history.record(InputMode::ModSearch, "skyui");
+        history.record(InputMode::NexusApiKeyInput, "secret");
+
+        let mut history = InputHistory::load(path);
+        assert_eq!(
+            history.previous(InputMode::ModSearch, "draft").as_deref(),
+            Some("skyui")
+        );
+        assert_eq!(
+            history.previous(InputMode::ModSearch, "skyui").as_deref(),
+            Some("ussep")
+        );
+        assert_eq!(history.previous(InputMode::ModSearch, "ussep"), None);
+        assert_eq!(history.next(InputMode::ModSearch).as_deref(), Some("skyui"));
+        assert_eq!(history.next(InputMode::ModSearch).as_deref(), Some("draft"));
+        assert_eq!(history.next(InputMode::ModSearch), None);
+        assert_eq!(history.previous(InputMode::NexusApiKeyInput, ""), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,151 @@
+//! Responsive layout helpers
+//!
+//! The tab bar and the list/detail splits are sized from the terminal width
+//! instead of assuming a wide terminal. Mouse clicks on the tab bar are
+//! resolved against the same titles that were drawn, so they keep working
+//! whichever form the tab bar takes.
+
+use crate::app::Screen;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use std::rc::Rc;
+
+/// Below this width side panels collapse and lists take the whole width
+pub const NARROW_WIDTH: u16 = 100;
+
+/// Below this width the Mods screen drops its details column
+pub const WIDE_WIDTH: u16 = 130;
+
+/// Smallest terminal the screens are laid out for
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 14;
+
+/// Row the tab bar is drawn on, below the 3-line header
+pub const TAB_ROW: u16 = 3;
+
+/// Tabs in order: full title, short title, screen
+const TABS: &[(&str, &str, Screen)] = &[
+    ("F1 Mods", "Mods", Screen::Mods),
+    ("F2 Plugins", "Plugins", Screen::Plugins),
+    ("F3 Profiles", "Profiles", Screen::Profiles),
+    ("F4 Settings", "Settings", Screen::Settings),
+    ("F5 Import", "Import", Screen::Import),
+    ("F6 Queue", "Queue", Screen::DownloadQueue),
+    ("F7 Catalog", "Catalog", Screen::NexusCatalog),
+    ("F8 Modlists", "Modlists", Screen::ModlistEditor),
+    ("F9 Extensions", "Extensions", Screen::Extensions),
+    ("F10 Storage", "Storage", Screen::Storage),
+    ("F11 Downloads", "Downloads", Screen::Downloads),
+    ("F12 History", "Hist", Screen::History),
+];
+
+/// Index of the tab highlighted for `screen`; screens without a tab of
+/// their own highlight the one they are reached from
+pub fn tab_index(screen: Screen) -> usize {
+    match screen {
+        Screen::Profiles | Screen::IniDiff | Screen::Saves => 2,
+        Screen::ToolOutput | Screen::IniEditor => 3,
+        Screen::Import | Screen::ImportReview => 4,
+        Screen::ModlistEditor | Screen::ModlistDiff => 7,
+        other => TABS.iter().position(|(_, _, s)| *s == other).unwrap_or(0),
+    }
+}
+
+/// Width `Tabs` takes for `titles`: a space either side of each title and a
+/// one-column divider between them
+fn tabs_width(titles: &[String]) -> usize {
+    titles.iter().map(|t| t.chars().count() + 2).sum::<usize>() + titles.len().saturating_sub(1)
+}
+
+/// Tab titles that fit in `width`: full titles, then names alone, then
+/// function keys with only the selected tab named
+pub fn tab_titles(selected: usize, width: u16) -> Vec<String> {
+    let full: Vec<String> = TABS.iter().map(|(t, _, _)| t.to_string()).collect();
+    let short: Vec<String> = TABS.iter().map(|(_, t, _)| t.to_string()).collect();
+    let keys: Vec<String> = TABS
+        .iter()
+        .enumerate()
+        .map(|(i, (t, _, _))| {
+            if i == selected {
+                t.to_string()
+            } else {
+                t.split(' ').next().unwrap_or(t).to_string()
+            }
+        })
+        .collect();
+    [full, short]
+        .into_iter()
+        .find(|titles| tabs_width(titles) <= width as usize)
+        .unwrap_or(keys)
+}
+
+/// Screen of the tab drawn at `column`, if any
+pub fn tab_at(selected: usize, width: u16, column: u16) -> Option<Screen> {
+    let column = column as usize;
+    let mut x = 0;
+    for (i, title) in tab_titles(selected, width).iter().enumerate() {
+        let end = x + title.chars().count() + 2;
+        if (x..end).contains(&column) {
+            return Some(TABS[i].2);
+        }
+        // Skip the divider
+        x = end + 1;
+    }
+    None
+}
+
+/// Split `area` into a main panel and a side panel `side_percent` wide.
+/// On narrow terminals the side panel is zero-width, so whatever is drawn
+/// into it simply doesn't show.
+pub fn with_side_panel(area: Rect, side_percent: u16) -> Rc<[Rect]> {
+    let constraints = if area.width < NARROW_WIDTH {
+        [Constraint::Min(0), Constraint::Length(0)]
+    } else {
+        [
+            Constraint::Percentage(100 - side_percent),
+            Constraint::Percentage(side_percent),
+        ]
+    };
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(area)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_titles_follow_width() {
+        let wide = tab_titles(1, 200);
+        assert_eq!(wide[1], "F2 Plugins");
+        assert_eq!(tab_at(1, 200, 0), Some(Screen::Mods));
+        // " F1 Mods |" then the Plugins tab starts at column 10
+        assert_eq!(tab_at(1, 200, 9), None);
+        assert_eq!(tab_at(1, 200, 10), Some(Screen::Plugins));
+
+        let medium = tab_titles(1, 120);
+        assert_eq!(medium[0], "Mods");
+        assert!(tabs_width(&medium) <= 120);
+
+        let narrow = tab_titles(1, 80);
+        assert_eq!(narrow[0], "F1");
+        assert_eq!(narrow[1], "F2 Plugins");
+        assert!(tabs_width(&narrow) <= 80);
+        assert_eq!(tab_at(1, 80, 5), Some(Screen::Plugins));
+        assert_eq!(tab_at(1, 80, 200), None);
+
+        assert_eq!(tab_index(Screen::IniDiff), tab_index(Screen::Profiles));
+        assert_eq!(tab_index(Screen::Downloads), 10);
+        assert_eq!(tab_index(Screen::LoadOrder), 0);
+    }
+
+    #[test]
+    fn test_side_panel_collapses() {
+        let wide = with_side_panel(Rect::new(0, 0, 150, 20), 40);
+        assert_eq!(wide[1].width, 60);
+        let narrow = with_side_panel(Rect::new(0, 0, 80, 20), 40);
+        assert_eq!(narrow[0].width, 80);
+        assert_eq!(narrow[1].width, 0);
+    }
+}
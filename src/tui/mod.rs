@@ -1,32 +1,61 @@
 //! Terminal User Interface using ratatui
 
+mod clipboard;
+mod graphics;
+#[cfg(any(test, feature = "test-harness"))]
+pub mod harness;
+mod hit;
+mod input;
+mod layout;
+mod palette;
 pub mod screens;
+mod title;
 mod ui;
 mod widgets;
 
-use crate::app::state::AppState;
-use crate::app::{App, InputMode, Screen};
-use crate::config::ExternalTool;
+use crate::app::filters;
+use crate::app::groups::ModRow;
+use crate::app::state::{AppState, ToolOptionField, ToolRun};
+use crate::app::{App, EventSender, InputMode, Screen};
+use crate::config::{self, Config, ExternalTool, ToolRef};
 use crate::db::Database;
 use crate::plugins;
 use anyhow::Result;
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
-        MouseEvent, MouseEventKind,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
     },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    Terminal,
+};
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+
+/// Fixed rows of the Settings screen; custom tools are listed after them
+const SETTINGS_FIXED_ROWS: usize = 20;
 
 /// TUI application wrapper
-pub struct Tui {
-    terminal: Terminal<CrosstermBackend<io::Stdout>>,
+pub struct Tui<B: Backend = CrosstermBackend<io::Stdout>> {
+    terminal: Terminal<B>,
+    /// Whether this instance owns the real terminal (raw mode, alternate screen)
+    interactive: bool,
+    /// Inline image currently on screen (FOMOD previews)
+    images: graphics::ImageOverlay,
+    /// Window title and taskbar progress, set up from the config in `run`
+    title: Option<title::TitleWriter>,
+    /// Earlier text prompt entries, loaded in `run`
+    history: input::InputHistory,
+    /// Recent list clicks, for double-click and drag
+    clicks: hit::Clicks,
+    /// Downloads folder watch, running while `install.watch_downloads` is on
+    downloads_watch: Option<tokio::task::AbortHandle>,
 }
 
 impl Tui {
@@ -34,16 +63,43 @@ impl Tui {
     pub fn new() -> Result<Self> {
         let backend = CrosstermBackend::new(io::stdout());
         let terminal = Terminal::new(backend)?;
-        Ok(Self { terminal })
+        Ok(Self {
+            terminal,
+            interactive: true,
+            images: graphics::ImageOverlay::default(),
+            title: None,
+            history: input::InputHistory::default(),
+            clicks: hit::Clicks::default(),
+            downloads_watch: None,
+        })
+    }
+}
+
+impl<B: Backend> Tui<B> {
+    /// Create a TUI that draws to `backend` without touching the real terminal
+    pub fn with_backend(backend: B) -> Result<Self> {
+        Ok(Self {
+            terminal: Terminal::new(backend)?,
+            interactive: false,
+            images: graphics::ImageOverlay::default(),
+            title: None,
+            history: input::InputHistory::default(),
+            clicks: hit::Clicks::default(),
+            downloads_watch: None,
+        })
     }
 
     /// Set up the terminal
     fn setup(&mut self) -> Result<()> {
+        if !self.interactive {
+            return Ok(());
+        }
         enable_raw_mode()?;
         execute!(
-            self.terminal.backend_mut(),
+            io::stdout(),
             EnterAlternateScreen,
-            EnableMouseCapture
+            EnableMouseCapture,
+            EnableBracketedPaste
         )?;
         self.terminal.hide_cursor()?;
         Ok(())
@@ -51,11 +107,18 @@ impl Tui {
 
     /// Restore the terminal
     fn restore(&mut self) -> Result<()> {
+        if !self.interactive {
+            return Ok(());
+        }
+        if let Some(title) = &mut self.title {
+            title.restore(&mut io::stdout())?;
+        }
         disable_raw_mode()?;
         execute!(
-            self.terminal.backend_mut(),
+            io::stdout(),
             LeaveAlternateScreen,
-            DisableMouseCapture
+            DisableMouseCapture,
+            DisableBracketedPaste
         )?;
         self.terminal.show_cursor()?;
         Ok(())
@@ -64,16 +127,68 @@ impl Tui {
     /// Run the TUI main loop
     pub async fn run(&mut self, app: &mut App) -> Result<()> {
         self.setup()?;
+        if self.interactive {
+            let config = app.config.read().await;
+            let title =
+                title::TitleWriter::new(config.tui.terminal_title, &config.tui.taskbar_progress);
+            drop(config);
+            title.save(&mut io::stdout())?;
+            self.title = Some(title);
+            let history_file = app
+                .config
+                .read()
+                .await
+                .paths
+                .data_dir()
+                .join("input_history.txt");
+            self.history = input::InputHistory::load(history_file);
+        }
 
         // Load initial data
         self.load_initial_data(app).await?;
+        let config = app.config.read().await;
+        if config.install.watch_downloads {
+            self.watch_downloads(app.events.clone(), config.downloads_dir().ok());
+        }
+        drop(config);
 
         let result = self.event_loop(app).await;
+        self.watch_downloads(app.events.clone(), None);
 
         self.restore()?;
         result
     }
 
+    /// Offer archives that appear in `dir` for install; `None` stops watching
+    fn watch_downloads(&mut self, events: EventSender, dir: Option<std::path::PathBuf>) {
+        if let Some(watch) = self.downloads_watch.take() {
+            watch.abort();
+        }
+        let Some(dir) = dir else {
+            return;
+        };
+        let task = tokio::spawn(async move {
+            let mut watcher = match crate::import::DownloadWatcher::new(&dir) {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    events.failure("Can't watch the downloads folder", e);
+                    return;
+                }
+            };
+            loop {
+                match watcher.next_archive().await {
+                    Ok(Some(path)) => events.update(move |state| state.offer_download(path)),
+                    Ok(None) => break,
+                    Err(e) => {
+                        events.failure("Stopped watching the downloads folder", e);
+                        break;
+                    }
+                }
+            }
+        });
+        self.downloads_watch = Some(task.abort_handle());
+    }
+
     /// Load initial data for the TUI
     async fn load_initial_data(&self, app: &mut App) -> Result<()> {
         let mut state = app.state.write().await;
@@ -99,11 +214,32 @@ impl Tui {
                 let mut state = app.state.write().await;
                 state.installed_mods = mods;
             }
+            if let Ok(flags) = app.mods.unavailable_files(&game.id) {
+                let mut state = app.state.write().await;
+                state.unavailable_files = flags.into_iter().map(|f| (f.mod_id, f)).collect();
+            }
+            if let Ok(missing) = app.mods.missing_dlc(&game) {
+                let mut state = app.state.write().await;
+                state.missing_dlc = missing.into_iter().map(|m| (m.mod_id, m)).collect();
+            }
 
             // Load plugins
             if let Ok(plugins_list) = plugins::get_plugins(&game) {
                 let mut state = app.state.write().await;
                 state.plugins = plugins_list;
+                state.plugin_history.clear();
+            }
+
+            // "What changed since you last played" after a gap
+            let gap_hours = app.config.read().await.tui.changelog_gap_hours;
+            if gap_hours > 0 {
+                if let Ok(Some(since)) = app.mods.since_last_played(&game).await {
+                    if !since.changes.is_empty()
+                        && since.is_after_gap(gap_hours, chrono::Utc::now())
+                    {
+                        app.state.write().await.since_last_played = Some(since);
+                    }
+                }
             }
 
             // Load profiles
@@ -167,11 +303,20 @@ impl Tui {
                 let mut state = app.state.write().await;
                 state.installed_mods = mods;
             }
+            if let Ok(flags) = app.mods.unavailable_files(&game.id) {
+                let mut state = app.state.write().await;
+                state.unavailable_files = flags.into_iter().map(|f| (f.mod_id, f)).collect();
+            }
+            if let Ok(missing) = app.mods.missing_dlc(&game) {
+                let mut state = app.state.write().await;
+                state.missing_dlc = missing.into_iter().map(|m| (m.mod_id, m)).collect();
+            }
 
             // Load plugins
             if let Ok(plugins_list) = plugins::get_plugins(&game) {
                 let mut state = app.state.write().await;
                 state.plugins = plugins_list;
+                state.plugin_history.clear();
             }
 
             // Load profiles
@@ -192,10 +337,23 @@ impl Tui {
             13 => Some(ExternalTool::Synthesis),
             14 => Some(ExternalTool::BodySlide),
             15 => Some(ExternalTool::OutfitStudio),
+            16 => Some(ExternalTool::Geck),
             _ => None,
         }
     }
 
+    /// Tool launched from a Settings row: a built-in tool's path row or a
+    /// custom tool
+    fn settings_launch_tool(config: &Config, index: usize) -> Option<ToolRef> {
+        if let Some(tool) = Self::settings_tool_for_index(index) {
+            return Some(ToolRef::Builtin(tool));
+        }
+        let custom = config
+            .custom_tools
+            .get(index.checked_sub(SETTINGS_FIXED_ROWS)?)?;
+        Some(ToolRef::Custom(custom.name.clone()))
+    }
+
     fn require_advanced(state: &mut AppState, action: &str) -> bool {
         if state.is_advanced_mode() {
             true
@@ -208,8 +366,71 @@ impl Tui {
         }
     }
 
+    /// Switch debug logging for this session
+    fn toggle_debug_logging(state: &mut AppState) {
+        let enabled = !crate::logging::debug_enabled();
+        match crate::logging::set_debug(enabled) {
+            Ok(()) if enabled => state.set_status_info(format!(
+                "Debug logging on for this session ({})",
+                crate::logging::log_file().display()
+            )),
+            Ok(()) => state.set_status_info("Debug logging off"),
+            Err(e) => state.set_status_error(format!("Failed to change log level: {}", e)),
+        }
+    }
+
+    /// Scroll, filter or copy the log pane
+    fn handle_log_pane_key(state: &mut AppState, key: KeyCode) {
+        let records = crate::logging::recent_records(state.log_pane.level);
+        let page = (ui::LOG_PANE_HEIGHT as usize).saturating_sub(2).max(1);
+        let max_scroll = records
+            .len()
+            .saturating_sub((ui::LOG_PANE_HEIGHT as usize).saturating_sub(1));
+        match key {
+            KeyCode::Char('b') => {
+                state.log_pane.scroll_up(page);
+                state.log_pane.scroll = state.log_pane.scroll.min(max_scroll);
+            }
+            KeyCode::Char('f') => {
+                state.log_pane.scroll = state.log_pane.scroll.min(max_scroll);
+                state.log_pane.scroll_down(page);
+            }
+            KeyCode::Char('t') => state.log_pane.cycle_level(),
+            KeyCode::Char('y') => {
+                let text: Vec<String> = records.iter().map(|r| r.to_line()).collect();
+                match clipboard::write_text(&text.join("\n")) {
+                    Ok(()) => state.set_status_info(format!(
+                        "Copied {} log line(s) to the clipboard",
+                        records.len()
+                    )),
+                    Err(e) => state.set_status_error(e.to_string()),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn set_cleanup_status(state: &mut AppState, what: &str, stats: &crate::mods::CleanupStats) {
+        let summary = format!(
+            "Removed {} {}, freed {}",
+            stats.removed,
+            what,
+            crate::mods::format_bytes(stats.bytes_freed)
+        );
+        if let Some(first) = stats.errors.first() {
+            state.set_status_error(format!(
+                "{} ({} failed: {})",
+                summary,
+                stats.errors.len(),
+                first
+            ));
+        } else {
+            state.set_status_success(summary);
+        }
+    }
+
     fn spawn_browse_search(
-        state: Arc<RwLock<AppState>>,
+        events: EventSender,
         nexus: Arc<crate::nexus::NexusClient>,
         game_id: Option<String>,
         query: Option<String>,
@@ -250,55 +471,546 @@ impl Tui {
                             0
                         };
 
-                        let mut state = state.write().await;
-                        state.browse_results = page.results;
-                        state.selected_browse_index = 0;
-                        state.browse_offset = offset;
-                        state.browse_total_count = total;
-                        state.browsing = false;
+                        events.update(move |state| {
+                            state.browse_results = page.results;
+                            state.browse_surprise = false;
+                            state.selected_browse_index = 0;
+                            state.browse_offset = offset;
+                            state.browse_total_count = total;
+                            state.browsing = false;
 
-                        if total > 0 {
-                            if let Some(ref q) = query {
-                                state.set_status(format!(
-                                    "Showing {}-{} of {} results for: {}",
-                                    start, end, total, q
-                                ));
-                            } else {
-                                state.set_status(format!(
-                                    "Showing {}-{} of {} top mods",
-                                    start, end, total
-                                ));
-                            }
-                        } else {
-                            if let Some(ref q) = query {
-                                state.set_status(format!("Found 0 results for: {}", q));
+                            if total > 0 {
+                                if let Some(ref q) = query {
+                                    state.set_status(format!(
+                                        "Showing {}-{} of {} results for: {}",
+                                        start, end, total, q
+                                    ));
+                                } else {
+                                    state.set_status(format!(
+                                        "Showing {}-{} of {} top mods",
+                                        start, end, total
+                                    ));
+                                }
                             } else {
-                                state.set_status("No mods found".to_string());
+                                if let Some(ref q) = query {
+                                    state.set_status(format!("Found 0 results for: {}", q));
+                                } else {
+                                    state.set_status("No mods found".to_string());
+                                }
                             }
-                        }
+                        });
                     }
                     Err(e) => {
-                        let mut state = state.write().await;
-                        state.browsing = false;
-                        state.set_status(format!("Search error: {}", e));
+                        events.update(move |state| {
+                            state.browsing = false;
+                            state.set_status_error_chain("Search failed", &e);
+                        });
                     }
                 }
             } else {
-                let mut state = state.write().await;
-                state.browsing = false;
-                state.set_status("No active game selected".to_string());
+                events.update(|state| {
+                    state.browsing = false;
+                    state.set_status("No active game selected".to_string());
+                });
+            }
+        });
+    }
+
+    fn spawn_surprise(
+        events: EventSender,
+        nexus: Arc<crate::nexus::NexusClient>,
+        game_domain: String,
+        options: crate::nexus::SurpriseOptions,
+    ) {
+        tokio::spawn(async move {
+            match nexus.surprise_mod(&game_domain, &options).await {
+                Ok(Some(pick)) => events.update(move |state| {
+                    state.set_status(format!(
+                        "Surprise: {} ({} endorsements) - a: queue, Enter: pick a file, r: another",
+                        pick.name, pick.endorsements
+                    ));
+                    state.browse_results = vec![pick];
+                    state.browse_surprise = true;
+                    state.browse_showing_default = false;
+                    state.browse_query.clear();
+                    state.browse_offset = 0;
+                    state.browse_total_count = 1;
+                    state.selected_browse_index = 0;
+                    state.browsing = false;
+                }),
+                Ok(None) => events.update(|state| {
+                    state.browsing = false;
+                    state.set_status(
+                        "No matching mods found; adjust [discovery] in config".to_string(),
+                    );
+                }),
+                Err(e) => events.update(move |state| {
+                    state.browsing = false;
+                    state.set_status_error_chain("Surprise failed", &e);
+                }),
+            }
+        });
+    }
+
+    /// Append pasted text to the open prompt
+    fn paste_into_prompt(state: &mut AppState, text: &str) {
+        state.input_buffer.push_str(&clipboard::pasted_line(text));
+        if state.input_mode == InputMode::CommandPalette {
+            state.palette_index = 0;
+        }
+    }
+
+    /// Put `text` on the clipboard, reporting it as `what`
+    fn yank(state: &mut AppState, what: &str, text: &str) {
+        match clipboard::write_text(text) {
+            Ok(()) => state.set_status_info(format!("Copied {}: {}", what, text)),
+            Err(e) => state.set_status_error(e.to_string()),
+        }
+    }
+
+    /// Copy the Nexus page of the selected mod
+    fn yank_mod_url(state: &mut AppState) {
+        let Some(m) = state.selected_mod() else {
+            return;
+        };
+        let (name, nexus_mod_id) = (m.name.clone(), m.nexus_mod_id);
+        let domain = state.active_game.as_ref().map(|g| g.nexus_game_domain());
+        match (nexus_mod_id, domain) {
+            (Some(mod_id), Some(domain)) => {
+                let url = clipboard::nexus_mod_url(&domain, mod_id);
+                Self::yank(state, "Nexus link", &url);
+            }
+            _ => state.set_status_info(format!("{} has no Nexus id", name)),
+        }
+    }
+
+    /// Save the current Mods view, the active filter narrowed by the search,
+    /// as a named filter in config and switch to it
+    async fn save_mod_filter(app: &App, name: String) -> Result<()> {
+        let filter = {
+            let state = app.state.read().await;
+            let mut filter = state.active_filter().cloned().unwrap_or_default();
+            filter.name = name.clone();
+            if !state.mod_search_query.is_empty() {
+                filter.search = Some(state.mod_search_query.clone());
+            }
+            filter
+        };
+
+        let filters = {
+            let mut config = app.config.write().await;
+            match config
+                .mod_filters
+                .iter_mut()
+                .find(|f| f.name.eq_ignore_ascii_case(&name))
+            {
+                Some(existing) => *existing = filter,
+                None => config.mod_filters.push(filter),
+            }
+            if let Err(e) = config.save().await {
+                let mut state = app.state.write().await;
+                state.set_status_error(format!("Error saving config: {}", e));
+                return Ok(());
+            }
+            filters::with_builtin(&config.mod_filters)
+        };
+
+        let mut state = app.state.write().await;
+        state.active_mod_filter = filters
+            .iter()
+            .position(|f| f.name.eq_ignore_ascii_case(&name));
+        state.mod_filters = filters;
+        // The search is part of the filter now
+        state.mod_search_query.clear();
+        state.selected_mod_index = 0;
+        state.set_status_success(format!("Saved filter '{}'", name));
+        Ok(())
+    }
+
+    /// Remove a filter from config. Built-in views can't be removed; a
+    /// configured filter that replaced one reverts to the built-in.
+    async fn delete_mod_filter(app: &App, name: &str) -> Result<()> {
+        let filters = {
+            let mut config = app.config.write().await;
+            let before = config.mod_filters.len();
+            config
+                .mod_filters
+                .retain(|f| !f.name.eq_ignore_ascii_case(name));
+            if config.mod_filters.len() == before {
+                drop(config);
+                let mut state = app.state.write().await;
+                state.set_status_info(format!("'{}' is a built-in view", name));
+                return Ok(());
+            }
+            if let Err(e) = config.save().await {
+                let mut state = app.state.write().await;
+                state.set_status_error(format!("Error saving config: {}", e));
+                return Ok(());
+            }
+            filters::with_builtin(&config.mod_filters)
+        };
+
+        let mut state = app.state.write().await;
+        // Indices shift, so follow the active filter by name
+        let active = state.active_filter().map(|f| f.name.clone());
+        state.active_mod_filter = active.and_then(|active| {
+            filters
+                .iter()
+                .position(|f| f.name.eq_ignore_ascii_case(&active))
+        });
+        state.mod_filter_index = state.mod_filter_index.min(filters.len());
+        state.mod_filters = filters;
+        state.selected_mod_index = 0;
+        state.set_status_success(format!("Deleted filter '{}'", name));
+        Ok(())
+    }
+
+    /// Load the Nexus page of the mod on the details screen, from the cache
+    /// when it's recent unless `refresh`
+    fn spawn_mod_page_load(app: &App, state: &mut AppState, refresh: bool) {
+        let Some(mod_id) = state.selected_mod().and_then(|m| m.nexus_mod_id) else {
+            state.mod_page = None;
+            return;
+        };
+        let Some(game_domain) = state.active_game.as_ref().map(|g| g.nexus_game_domain()) else {
+            return;
+        };
+        if !refresh && state.mod_page.as_ref().is_some_and(|(id, _)| *id == mod_id) {
+            return;
+        }
+        state.mod_page_loading = true;
+
+        let events = app.events.clone();
+        let db = app.db.clone();
+        let nexus = app.nexus.clone();
+        tokio::spawn(async move {
+            let result =
+                crate::nexus::load_mod_page(&db, nexus.as_deref(), &game_domain, mod_id, refresh)
+                    .await;
+            events.update(move |state| {
+                state.mod_page_loading = false;
+                match result {
+                    Ok(page) => {
+                        state.mod_page = page.map(|page| (mod_id, page));
+                        if refresh {
+                            state.set_status("Mod page refreshed".to_string());
+                        }
+                    }
+                    Err(e) => state.set_status_error_chain("Failed to load mod page", &e),
+                }
+            });
+        });
+    }
+
+    /// Import an MO2 instance directory in the background, reporting
+    /// progress through the import progress overlay
+    fn spawn_mo2_instance_import(
+        events: EventSender,
+        mods: Arc<crate::mods::ModManager>,
+        profiles: Arc<crate::profiles::ProfileManager>,
+        game_id: String,
+        path: String,
+        dry_run: bool,
+    ) {
+        tokio::spawn(async move {
+            use crate::app::state::ImportProgress;
+
+            let progress_events = events.clone();
+            let result: Result<_> = async {
+                let instance = crate::import::Mo2Instance::open(std::path::Path::new(&path))?;
+                crate::import::import_mo2_instance(
+                    &mods,
+                    &profiles,
+                    &game_id,
+                    &instance,
+                    crate::mods::AdoptMode::Copy,
+                    dry_run,
+                    move |stage, current, total, name| {
+                        progress_events.import_progress(Some(ImportProgress {
+                            current_index: current,
+                            total_plugins: total,
+                            current_plugin_name: name.to_string(),
+                            stage: stage.to_string(),
+                        }));
+                    },
+                )
+                .await
+            }
+            .await;
+
+            let updated_mods = mods.list_mods(&game_id).await.ok();
+            let updated_profiles = profiles.list_profiles(&game_id).await.ok();
+            events.update(move |state| {
+                state.import_progress = None;
+                match result {
+                    Ok(report) => {
+                        state.set_status(if dry_run {
+                            "MO2 instance dry run complete (see report)".to_string()
+                        } else {
+                            format!(
+                                "Imported MO2 instance: {} mods, {} profiles",
+                                report.mods_adopted.len(),
+                                report.profiles.len()
+                            )
+                        });
+                        state.mo2_import_report = report.lines();
+                    }
+                    Err(e) => state.set_status_error(format!("MO2 instance import failed: {}", e)),
+                }
+                if let Some(mods) = updated_mods {
+                    state.installed_mods = mods;
+                }
+                if let Some(profiles) = updated_profiles {
+                    state.profiles = profiles;
+                }
+            });
+        });
+    }
+
+    /// Route the clipboard's link or path to the matching install/load flow
+    async fn install_from_clipboard(app: &mut App) -> Result<()> {
+        use clipboard::ClipboardTarget;
+
+        let target = clipboard::read_text().map(|text| clipboard::classify(&text));
+        let mut state = app.state.write().await;
+        let target = match target {
+            Ok(Some(target)) => target,
+            Ok(None) => {
+                state.set_status_error(
+                    "Clipboard doesn't hold a Nexus link, mod archive or modlist",
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                state.set_status_error(e.to_string());
+                return Ok(());
+            }
+        };
+        let Some(game) = state.active_game.clone() else {
+            state.set_status_error("No active game selected");
+            return Ok(());
+        };
+
+        match target {
+            ClipboardTarget::NexusMod {
+                game_domain,
+                mod_id,
+                file_id,
+            } => {
+                if game_domain != game.nexus_game_id {
+                    state.set_status_error(format!(
+                        "Link is for '{}' but the active game is {}",
+                        game_domain,
+                        game.game_type.display_name()
+                    ));
+                    return Ok(());
+                }
+                let Some(nexus) = app.nexus.clone() else {
+                    state.set_status_error("Nexus API key required to install from a link");
+                    return Ok(());
+                };
+                state.set_status(format!("Fetching files for mod {}...", mod_id));
+                Self::spawn_nexus_link(
+                    app.events.clone(),
+                    nexus,
+                    game_domain,
+                    game.nexus_numeric_id,
+                    mod_id,
+                    file_id,
+                );
+            }
+            ClipboardTarget::Archive(path) => {
+                // Same flow as 'i' on the Mods screen, with the path filled in
+                state.input_mode = InputMode::ModInstallPath;
+                state.input_buffer = path.to_string_lossy().to_string();
+                state.set_status_info("Archive from clipboard - Enter to install");
+            }
+            ClipboardTarget::ModlistFile(path) => {
+                state.set_status("Loading modlist...");
+                Self::spawn_load_modlist(
+                    app.events.clone(),
+                    app.db.clone(),
+                    Some(game.id),
+                    path.to_string_lossy().to_string(),
+                );
+            }
+            ClipboardTarget::ModlistUrl(url) => {
+                state.set_status(format!("Downloading modlist from {}...", url));
+                let cache_dir = app.config.read().await.paths.cache_dir();
+                Self::spawn_modlist_download(
+                    app.events.clone(),
+                    app.db.clone(),
+                    game.id,
+                    url,
+                    cache_dir,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Open the file picker on a queue entry's mod; the chosen file is what
+    /// processing downloads
+    fn spawn_queue_file_picker(
+        events: EventSender,
+        nexus: Arc<crate::nexus::NexusClient>,
+        game_id_numeric: i64,
+        entry: crate::queue::QueueEntry,
+    ) {
+        tokio::spawn(async move {
+            match nexus
+                .get_mod_files(game_id_numeric, entry.nexus_mod_id)
+                .await
+            {
+                Ok(mut files) => {
+                    // Archived files can't be downloaded; main files first
+                    files.retain(|f| f.is_available());
+                    files.sort_by(|a, b| {
+                        let order = |cat: &str| match cat {
+                            "MAIN" => 0,
+                            "UPDATE" => 1,
+                            "OPTIONAL" => 2,
+                            "MISCELLANEOUS" => 3,
+                            "OLD_VERSION" => 4,
+                            _ => 5,
+                        };
+                        order(&a.category).cmp(&order(&b.category))
+                    });
+                    events.update(move |state| {
+                        if files.is_empty() {
+                            state.showing_file_picker = false;
+                            state.download_context = None;
+                            state
+                                .set_status_error(format!("No files found for {}", entry.mod_name));
+                            return;
+                        }
+                        state.selected_file_index = entry
+                            .selected_file_id
+                            .and_then(|id| files.iter().position(|f| f.file_id == id))
+                            .unwrap_or(0);
+                        state.browse_mod_files = files;
+                        state.set_status(format!(
+                            "Choose the file to download for {} - Enter to select",
+                            entry.mod_name
+                        ));
+                    });
+                }
+                Err(e) => events.update(move |state| {
+                    state.showing_file_picker = false;
+                    state.download_context = None;
+                    state.set_status_error(format!("Failed to get files: {}", e));
+                }),
+            }
+        });
+    }
+
+    /// Fetch a linked mod's files and open the file picker on them, with the
+    /// linked file selected when the link names one
+    fn spawn_nexus_link(
+        events: EventSender,
+        nexus: Arc<crate::nexus::NexusClient>,
+        game_domain: String,
+        game_id_numeric: i64,
+        mod_id: i64,
+        file_id: Option<i64>,
+    ) {
+        tokio::spawn(async move {
+            let mod_name = nexus
+                .get_mod_name_by_id(&game_domain, mod_id)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| format!("Mod {}", mod_id));
+            match nexus.get_mod_files(game_id_numeric, mod_id).await {
+                Ok(mut files) => {
+                    // Sort: MAIN first, then UPDATE, OPTIONAL, OLD_VERSION
+                    files.sort_by(|a, b| {
+                        let order = |cat: &str| match cat {
+                            "MAIN" => 0,
+                            "UPDATE" => 1,
+                            "OPTIONAL" => 2,
+                            "MISCELLANEOUS" => 3,
+                            "OLD_VERSION" => 4,
+                            _ => 5,
+                        };
+                        order(&a.category).cmp(&order(&b.category))
+                    });
+                    events.update(move |state| {
+                        if files.is_empty() {
+                            state.set_status(format!("No files found for {}", mod_name));
+                            return;
+                        }
+                        state.selected_file_index = file_id
+                            .and_then(|id| files.iter().position(|f| f.file_id == id))
+                            .unwrap_or(0);
+                        state.browse_mod_files = files;
+                        state.showing_file_picker = true;
+                        state.download_context = Some(crate::app::state::DownloadContext {
+                            mod_id,
+                            mod_name: mod_name.clone(),
+                            game_domain,
+                            game_id: game_id_numeric,
+                            queue_entry_id: None,
+                        });
+                        state.set_status(format!(
+                            "Select file to download for {} - Enter to install",
+                            mod_name
+                        ));
+                    });
+                }
+                Err(e) => events.failure("Failed to get files", e),
+            }
+        });
+    }
+
+    /// Download a linked modlist into the cache and load it
+    fn spawn_modlist_download(
+        events: EventSender,
+        db: Arc<Database>,
+        game_id: String,
+        url: String,
+        cache_dir: std::path::PathBuf,
+    ) {
+        tokio::spawn(async move {
+            let file_name = url
+                .split(['?', '#'])
+                .next()
+                .and_then(|u| u.rsplit('/').next())
+                .filter(|name| !name.is_empty())
+                .unwrap_or("modlist.txt")
+                .to_string();
+            let dir = cache_dir.join("modlists");
+            if let Err(e) = tokio::fs::create_dir_all(&dir).await {
+                events.status_error(format!("Failed to create {}: {}", dir.display(), e));
+                return;
+            }
+            let dest = dir.join(&file_name);
+            if let Err(e) = crate::nexus::NexusClient::download_file(&url, &dest, |_, _| {}).await {
+                events.failure("Modlist download failed", e);
+                return;
             }
+            events.status("Loading modlist...");
+            Self::spawn_load_modlist(
+                events,
+                db,
+                Some(game_id),
+                dest.to_string_lossy().to_string(),
+            );
         });
     }
 
-    fn spawn_load_modlist(state: Arc<RwLock<AppState>>, db: Arc<Database>, path: String) {
+    fn spawn_load_modlist(
+        events: EventSender,
+        db: Arc<Database>,
+        game_id: Option<String>,
+        path: String,
+    ) {
         tokio::spawn(async move {
             // Detect format
             let format = match crate::import::detect_format(std::path::Path::new(&path)) {
                 Ok(f) => f,
                 Err(e) => {
-                    let mut state = state.write().await;
-                    state.set_status_error(format!("Error reading file: {}", e));
+                    events.failure("Error reading file", e);
                     return;
                 }
             };
@@ -311,31 +1023,22 @@ impl Tui {
                     ) {
                         Ok(m) => m,
                         Err(e) => {
-                            let mut state = state.write().await;
-                            state.set_status_error(format!("Parse error: {}", e));
+                            events.failure("Parse error", e);
                             return;
                         }
                     };
 
-                    // Get game_id
-                    let game_id = {
-                        let state = state.read().await;
-                        state.active_game.as_ref().map(|g| g.id.clone())
-                    };
-
                     let game_id = match game_id {
                         Some(id) => id,
                         None => {
-                            let mut state = state.write().await;
-                            state.set_status_error("No active game selected");
+                            events.status_error("No active game selected");
                             return;
                         }
                     };
 
                     // Validate game match
                     if modlist.meta.game_id != game_id {
-                        let mut state = state.write().await;
-                        state.set_status_error(format!(
+                        events.status_error(format!(
                             "Modlist is for {} but active game is {}",
                             modlist.meta.game_id, game_id
                         ));
@@ -369,8 +1072,7 @@ impl Tui {
                         Some(&path),
                         &db_entries,
                     ) {
-                        let mut state = state.write().await;
-                        state.set_status_error(format!("Failed to store modlist in DB: {}", e));
+                        events.failure("Failed to store modlist in DB", e);
                         return;
                     }
 
@@ -382,8 +1084,7 @@ impl Tui {
                     ) {
                         Ok(r) => r,
                         Err(e) => {
-                            let mut state = state.write().await;
-                            state.set_status_error(format!("Library check error: {}", e));
+                            events.failure("Library check error", e);
                             return;
                         }
                     };
@@ -404,41 +1105,40 @@ impl Tui {
                     };
 
                     // Update state
-                    let mut state = state.write().await;
-                    state.modlist_review_data = Some(review);
-                    state.selected_modlist_entry = 0;
-                    state.goto(Screen::ModlistReview);
-                    state
-                        .set_status_success(format!("Loaded and stored modlist: {}", modlist_name));
+                    events.update(move |state| {
+                        state.modlist_review_data = Some(review);
+                        state.selected_modlist_entry = 0;
+                        state.goto(Screen::ModlistReview);
+                        state.set_status_success(format!(
+                            "Loaded and stored modlist: {}",
+                            modlist_name
+                        ));
+                    });
                 }
                 crate::import::ModlistFormat::Mo2 => {
                     // Delegate to existing import
-                    let mut state = state.write().await;
-                    state.import_file_path = path;
-                    state.goto(Screen::Import);
-                    state.set_status_info("Use Enter to import MO2 modlist");
+                    events.update(move |state| {
+                        state.import_file_path = path;
+                        state.goto(Screen::Import);
+                        state.set_status_info("Use Enter to import MO2 modlist");
+                    });
                 }
             }
         });
     }
 
     fn spawn_load_saved_modlist(
-        state: Arc<RwLock<AppState>>,
+        events: EventSender,
         db: Arc<Database>,
+        game_id: Option<String>,
         modlist_id: i64,
         modlist_name: String,
     ) {
         tokio::spawn(async move {
-            let game_id = {
-                let state = state.read().await;
-                state.active_game.as_ref().map(|g| g.id.clone())
-            };
-
             let game_id = match game_id {
                 Some(id) => id,
                 None => {
-                    let mut state = state.write().await;
-                    state.set_status_error("No active game selected");
+                    events.status_error("No active game selected");
                     return;
                 }
             };
@@ -446,15 +1146,13 @@ impl Tui {
             let entries = match db.get_modlist_entries(modlist_id) {
                 Ok(entries) => entries,
                 Err(e) => {
-                    let mut state = state.write().await;
-                    state.set_status_error(format!("Failed to read saved modlist entries: {}", e));
+                    events.failure("Failed to read saved modlist entries", e);
                     return;
                 }
             };
 
             if entries.is_empty() {
-                let mut state = state.write().await;
-                state.set_status_error("Saved modlist has no entries");
+                events.status_error("Saved modlist has no entries");
                 return;
             }
 
@@ -477,8 +1175,7 @@ impl Tui {
                 match crate::import::library_check::check_library(&db, &game_id, mod_entries) {
                     Ok(r) => r,
                     Err(e) => {
-                        let mut state = state.write().await;
-                        state.set_status_error(format!("Library check error: {}", e));
+                        events.failure("Library check error", e);
                         return;
                     }
                 };
@@ -497,31 +1194,23 @@ impl Tui {
                 total_plugins: plugin_count,
             };
 
-            let mut state = state.write().await;
-            state.modlist_picker_for_loading = false;
-            state.modlist_review_data = Some(review);
-            state.selected_modlist_entry = 0;
-            state.goto(Screen::ModlistReview);
-            state.set_status_success("Loaded saved modlist for review");
+            events.update(move |state| {
+                state.modlist_picker_for_loading = false;
+                state.modlist_review_data = Some(review);
+                state.selected_modlist_entry = 0;
+                state.goto(Screen::ModlistReview);
+                state.set_status_success("Loaded saved modlist for review");
+            });
         });
     }
 
-    fn spawn_queue_modlist_downloads(state: Arc<RwLock<AppState>>, db: Arc<Database>) {
+    fn spawn_queue_modlist_downloads(
+        events: EventSender,
+        db: Arc<Database>,
+        game_id: String,
+        needs_download: Vec<crate::import::ModlistEntry>,
+    ) {
         tokio::spawn(async move {
-            // Get review data
-            let (needs_download, game_id) = {
-                let state = state.read().await;
-                let review = match &state.modlist_review_data {
-                    Some(r) => r,
-                    None => return,
-                };
-                let game_id = match &state.active_game {
-                    Some(g) => g.id.clone(),
-                    None => return,
-                };
-                (review.needs_download.clone(), game_id)
-            };
-
             let queue_manager = crate::queue::QueueManager::new(db);
             let batch_id = queue_manager.create_batch();
 
@@ -542,6 +1231,8 @@ impl Tui {
                     nexus_mod_id,
                     selected_file_id: entry.nexus_file_id,
                     auto_install: true,
+                    priority: false,
+                    replaces_mod: None,
                     match_confidence: Some(1.0),
                     alternatives: Vec::new(),
                     status: crate::queue::QueueStatus::Matched,
@@ -550,8 +1241,7 @@ impl Tui {
                 };
 
                 if let Err(e) = queue_manager.add_entry(queue_entry) {
-                    let mut state = state.write().await;
-                    state.set_status_error(format!("Error adding to queue: {}", e));
+                    events.failure("Error adding to queue", e);
                     return;
                 }
 
@@ -561,21 +1251,21 @@ impl Tui {
             let entries = match queue_manager.get_batch(&batch_id) {
                 Ok(v) => v,
                 Err(e) => {
-                    let mut state = state.write().await;
-                    state.set_status_error(format!("Error loading queue entries: {}", e));
+                    events.failure("Error loading queue entries", e);
                     return;
                 }
             };
 
             // Navigate to queue screen
-            let mut state = state.write().await;
-            state.import_batch_id = Some(batch_id);
-            state.queue_entries = entries;
-            state.selected_queue_index = 0;
-            state.queue_processing = false;
-            state.modlist_review_data = None;
-            state.goto(Screen::DownloadQueue);
-            state.set_status_success(format!("Queued {} downloads", queue_position));
+            events.update(move |state| {
+                state.import_batch_id = Some(batch_id);
+                state.queue_entries = entries;
+                state.selected_queue_index = 0;
+                state.queue_processing = false;
+                state.modlist_review_data = None;
+                state.goto(Screen::DownloadQueue);
+                state.set_status_success(format!("Queued {} downloads", queue_position));
+            });
         });
     }
 
@@ -635,6 +1325,7 @@ impl Tui {
         let mut state = app.state.write().await;
         state.installed_mods = refreshed_mods;
         state.load_order_mods = state.installed_mods.clone();
+        state.load_order_history.clear();
         state.active_modlist_id = Some(modlist_id);
         state.goto(Screen::Mods);
         state.set_status_success(format!(
@@ -798,7 +1489,7 @@ impl Tui {
                 if stem.trim().is_empty() {
                     continue;
                 }
-                let normalized = stem.replace('_', " ").replace('-', " ").trim().to_string();
+                let normalized = stem.replace(['_', '-'], " ").trim().to_string();
                 let source_file = path
                     .file_name()
                     .and_then(|s| s.to_str())
@@ -877,10 +1568,10 @@ impl Tui {
         let mut failed = 0usize;
 
         for m in mods {
-            let Some(mod_db_id) = m.id else {
+            if m.id.is_none() {
                 skipped += 1;
                 continue;
-            };
+            }
 
             let current_name = m.name.trim();
             let looks_unresolved =
@@ -989,17 +1680,208 @@ impl Tui {
             | Screen::LoadOrder
             | Screen::Collection
             | Screen::GameSelect
-            | Screen::FomodWizard => Screen::Mods,
+            | Screen::FomodWizard
+            | Screen::ScriptOverrides => Screen::Mods,
             Screen::Import | Screen::ImportReview | Screen::ModlistReview => Screen::Import,
+            Screen::IniDiff | Screen::Saves => Screen::Profiles,
+            Screen::ToolOutput | Screen::IniEditor => Screen::Settings,
+            Screen::ModlistDiff => Screen::ModlistEditor,
             other => other,
         }
     }
 
-    async fn launch_external_tool_from_tui(
+    /// Edit the INI file shown on the INI editor screen in `$EDITOR`
+    async fn edit_ini_from_tui(&mut self, app: &mut App) -> Result<()> {
+        let Some(game) = app.active_game().await else {
+            return Ok(());
+        };
+        let index = app.state.read().await.ini_file_index;
+        let Some(file) = game.game_type.ini_files().get(index).copied() else {
+            return Ok(());
+        };
+
+        self.restore()?;
+        let result = app.edit_ini_in_editor(&game, file).await;
+        self.setup()?;
+        self.terminal.clear()?;
+
+        screens::ini_editor::refresh(app).await;
+        let mut state = app.state.write().await;
+        match result {
+            Ok(Some(_)) => state.set_status_success(format!("Saved {}", file)),
+            Ok(None) => state.set_status_info(format!("{} unchanged", file)),
+            Err(e) => state.set_status_error_chain(format!("Failed to edit {}", file), &e),
+        }
+        Ok(())
+    }
+
+    /// Launch a tool, first asking to deploy when mods or plugins changed
+    /// since the last deploy: tools reading a stale Data folder write
+    /// output that doesn't match the load order
+    async fn launch_tool_after_deploy_check(&mut self, app: &mut App, tool: ToolRef) -> Result<()> {
+        let outdated = match app.active_game().await {
+            Some(game) => app.mods.deployment_outdated(&game).await.unwrap_or(false),
+            None => false,
+        };
+        if !outdated {
+            return self.launch_external_tool_from_tui(app, tool).await;
+        }
+
+        use crate::app::state::{ConfirmAction, ConfirmDialog};
+        let mut state = app.state.write().await;
+        state.show_confirm = Some(ConfirmDialog {
+            title: "Deployment Out of Date".to_string(),
+            message: format!(
+                "Mods or plugins changed since the last deploy. Deploy before launching {}? (L in Settings launches without deploying)",
+                tool.display_name()
+            ),
+            confirm_text: "Deploy and launch".to_string(),
+            cancel_text: "Cancel".to_string(),
+            on_confirm: ConfirmAction::DeployAndLaunchTool(tool),
+        });
+        Ok(())
+    }
+
+    /// Find plugins the LOOT masterlist reports as dirty and ask whether to
+    /// clean them with xEdit QuickAutoClean
+    async fn offer_plugin_cleaning(&mut self, app: &mut App) -> Result<()> {
+        let Some(game) = app.active_game().await else {
+            app.state.write().await.set_status_error("No game selected");
+            return Ok(());
+        };
+        app.state
+            .write()
+            .await
+            .set_status("Checking plugins against the LOOT masterlist...");
+        self.show_activity(Some(title::Activity::new("Checking plugins", None)))?;
+        let dirty = match app.mods.dirty_plugins(&game, None).await {
+            Ok(dirty) => dirty,
+            Err(e) => {
+                app.state
+                    .write()
+                    .await
+                    .set_status_error_chain("Dirty plugin check failed", &e);
+                return Ok(());
+            }
+        };
+        let mut state = app.state.write().await;
+        if dirty.is_empty() {
+            state.set_status_success("No dirty plugins found");
+            return Ok(());
+        }
+
+        use crate::app::state::{ConfirmAction, ConfirmDialog};
+        let listed: Vec<String> = dirty
+            .iter()
+            .take(5)
+            .map(|p| format!("{} ({})", p.filename, p.summary()))
+            .collect();
+        let more = match dirty.len().saturating_sub(listed.len()) {
+            0 => String::new(),
+            n => format!(" and {} more", n),
+        };
+        let deploy = if app.mods.deployment_outdated(&game).await.unwrap_or(false) {
+            " Mods changed since the last deploy, so this deploys first."
+        } else {
+            ""
+        };
+        state.show_confirm = Some(ConfirmDialog {
+            title: "Clean Dirty Plugins".to_string(),
+            message: format!(
+                "Clean {} dirty plugin(s) with xEdit QuickAutoClean, one at a time: {}{}.{}",
+                dirty.len(),
+                listed.join(", "),
+                more,
+                deploy
+            ),
+            confirm_text: "Clean".to_string(),
+            cancel_text: "Cancel".to_string(),
+            on_confirm: ConfirmAction::CleanPlugins(dirty),
+        });
+        Ok(())
+    }
+
+    /// Run xEdit QuickAutoClean on each plugin in turn, keeping each run's
+    /// output on the Tool Output screen
+    async fn clean_plugins_from_tui(
         &mut self,
         app: &mut App,
-        tool: ExternalTool,
+        dirty: Vec<crate::plugins::clean::DirtyPlugin>,
     ) -> Result<()> {
+        let Some(game) = app.active_game().await else {
+            return Ok(());
+        };
+        if app.mods.deployment_outdated(&game).await.unwrap_or(false) {
+            app.state.write().await.set_status("Deploying mods...");
+            self.show_activity(Some(title::Activity::new("Deploying", None)))?;
+            app.mods.deploy(&game).await?;
+        }
+
+        self.restore()?;
+        let mut results = Vec::new();
+        for plugin in &dirty {
+            println!("Cleaning {} with xEdit...", plugin.filename);
+            results.push(app.clean_plugin(plugin).await);
+        }
+        self.setup()?;
+        self.terminal.clear()?;
+
+        let mut cleaned = 0;
+        let mut failures = Vec::new();
+        {
+            let mut state = app.state.write().await;
+            for (plugin, result) in dirty.iter().zip(results) {
+                let mut run = ToolRun::new(format!("xEdit QuickAutoClean {}", plugin.filename));
+                match result {
+                    Ok(result) => {
+                        run.exit_code = Some(result.launch.exit_code);
+                        run.push_output(false, &result.launch.stdout);
+                        run.push_output(true, &result.launch.stderr);
+                        run.log_path = result.launch.log_path;
+                        match result.cleaned {
+                            Ok(record) => {
+                                cleaned += 1;
+                                run.lines.push((
+                                    false,
+                                    format!(
+                                        "[cleaned] {} ({:08X} -> {:08X})",
+                                        plugin.filename, record.crc_before, record.crc_after
+                                    ),
+                                ));
+                            }
+                            Err(e) => {
+                                run.lines.push((true, format!("[not cleaned] {:#}", e)));
+                                failures.push(plugin.filename.clone());
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        run.push_output(true, &format!("{:#}", e));
+                        failures.push(plugin.filename.clone());
+                    }
+                }
+                state.push_tool_run(run);
+            }
+            if failures.is_empty() {
+                state.set_status_success(format!(
+                    "Cleaned {} plugin(s); see Tool Output for each run",
+                    cleaned
+                ));
+            } else {
+                state.set_status_error(format!(
+                    "Cleaned {} of {} plugin(s); not cleaned: {}",
+                    cleaned,
+                    dirty.len(),
+                    failures.join(", ")
+                ));
+            }
+        }
+        self.refresh_plugins(app).await?;
+        self.refresh_mods(app).await?;
+        Ok(())
+    }
+
+    async fn launch_external_tool_from_tui(&mut self, app: &mut App, tool: ToolRef) -> Result<()> {
         {
             let mut state = app.state.write().await;
             state.set_status(format!("Launching {}...", tool.display_name()));
@@ -1007,53 +1889,181 @@ impl Tui {
 
         // Leave alternate-screen/raw mode so subprocess output cannot corrupt the TUI buffer.
         self.restore()?;
-        let launch_result = app.launch_external_tool_captured(tool, &[]).await;
+        let launch_result = app.launch_external_tool_captured(&tool, &[]).await;
         self.setup()?;
         self.terminal.clear()?;
 
         let mut state = app.state.write().await;
+        let mut run = ToolRun::new(tool.display_name());
+        let mut new_files = 0;
         match launch_result {
             Ok(result) => {
-                state.push_command_output_line(format!(
-                    "[{}] exited with {}",
+                run.exit_code = Some(result.exit_code);
+                new_files = result.new_files.len();
+                run.push_output(false, &result.stdout);
+                run.push_output(true, &result.stderr);
+                for path in &result.captured {
+                    run.lines
+                        .push((false, format!("[captured] {}", path.display())));
+                }
+                for path in &result.new_files {
+                    run.lines.push((false, format!("[new] {}", path.display())));
+                }
+                let log = result
+                    .log_path
+                    .as_ref()
+                    .map(|p| format!(", log saved to {}", p.display()))
+                    .unwrap_or_default();
+                run.log_path = result.log_path;
+                let message = format!(
+                    "{} exited with code {}{}",
                     tool.display_name(),
-                    result.exit_code
-                ));
-                if !result.stdout.trim().is_empty() {
-                    state.push_command_output_line(format!("[{} stdout]", tool.display_name()));
-                    state.push_command_output_text(&result.stdout);
-                }
-                if !result.stderr.trim().is_empty() {
-                    state.push_command_output_line(format!("[{} stderr]", tool.display_name()));
-                    state.push_command_output_text(&result.stderr);
+                    result.exit_code,
+                    log
+                );
+                if result.exit_code == 0 {
+                    state.set_status_success(message);
+                } else {
+                    state.set_status_error(message);
                 }
-                state.set_status(format!(
-                    "{} exited with {}",
-                    tool.display_name(),
-                    result.exit_code
-                ));
             }
             Err(e) => {
-                state.push_command_output_line(format!("[{} launch error]", tool.display_name()));
-                state.push_command_output_line(e.to_string());
+                run.push_output(true, &format!("{:#}", e));
                 state.set_status_error(format!("Launch failed: {}", e));
             }
         }
+        state.push_tool_run(run);
+        if new_files > 0 {
+            use crate::app::state::{ConfirmAction, ConfirmDialog};
+            let output_mod = format!("{} Output", tool.display_name());
+            state.show_confirm = Some(ConfirmDialog {
+                title: "Capture Tool Output".to_string(),
+                message: format!(
+                    "{} left {} new file(s) in Data. Move them into the mod '{}' so they deploy and purge with the rest of the setup?",
+                    tool.display_name(),
+                    new_files,
+                    output_mod
+                ),
+                confirm_text: "Move".to_string(),
+                cancel_text: "Leave".to_string(),
+                on_confirm: ConfirmAction::CaptureToolOutput(output_mod),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Deploy if needed, sync plugins.txt and run the game through Proton,
+    /// leaving the TUI while it runs
+    async fn launch_game_from_tui(&mut self, app: &mut App) -> Result<()> {
+        let Some(game) = app.active_game().await else {
+            app.state.write().await.set_status_error("No game selected");
+            return Ok(());
+        };
+        let launch = match app.launch_check(&game, None, None).await {
+            Ok(launch) => launch,
+            Err(e) => {
+                app.state
+                    .write()
+                    .await
+                    .set_status_error_chain("Launch check failed", &e);
+                return Ok(());
+            }
+        };
+        let executable = launch.executable.clone();
+        if let Some(problem) = launch.problems.first() {
+            app.state.write().await.set_status_error(format!(
+                "Not launching {}: {} (see 'modsanity play --check')",
+                executable, problem.message
+            ));
+            return Ok(());
+        }
+
+        app.state
+            .write()
+            .await
+            .set_status(format!("Launching {}...", executable));
+        self.restore()?;
+        let result = match app.prepare_launch(&game, &launch, None, false).await {
+            Ok(_) => {
+                app.launch_game_executable(&game, &game.executable_path(&executable), &[])
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        self.setup()?;
+        self.terminal.clear()?;
+
+        let mut state = app.state.write().await;
+        match result {
+            Ok(code) => state.set_status(format!("{} exited with {}", executable, code)),
+            Err(e) => state.set_status_error_chain("Launch failed", &e),
+        }
+        Ok(())
+    }
 
+    /// Reflect `activity` in the window title and taskbar
+    fn show_activity(&mut self, activity: Option<title::Activity>) -> Result<()> {
+        if let Some(title) = &mut self.title {
+            title.update(&mut io::stdout(), activity)?;
+        }
         Ok(())
     }
 
     /// Main event loop
+    ///
+    /// Each tick applies pending background events to the state, refreshes the
+    /// render snapshot if anything changed, draws from the snapshot without
+    /// holding the state lock, then handles at most one input event.
     async fn event_loop(&mut self, app: &mut App) -> Result<()> {
+        let mut events = app
+            .take_event_receiver()
+            .ok_or_else(|| anyhow::anyhow!("TUI event receiver already taken"))?;
+        let mut snapshot: Arc<AppState> = Arc::new(app.state.read().await.clone());
+        let mut dirty = false;
+
         loop {
-            // Draw UI
+            // Apply background updates in one place
             {
-                let state = app.state.read().await;
-                self.terminal.draw(|f| ui::draw(f, app, &state))?;
+                let mut state = app.state.write().await;
+                if events.drain_into(&mut state) > 0 {
+                    dirty = true;
+                }
+                if state.notifications.expire(std::time::Instant::now()) {
+                    dirty = true;
+                }
+                let downloads_revision = app.transfers.revision();
+                if downloads_revision != state.downloads_revision {
+                    state.downloads = app.transfers.snapshot();
+                    state.downloads_revision = downloads_revision;
+                    let last = state.downloads.len().saturating_sub(1);
+                    state.selected_download_index = state.selected_download_index.min(last);
+                    dirty = true;
+                }
+                if dirty {
+                    snapshot = Arc::new(state.clone());
+                    dirty = false;
+                }
+            }
+
+            // Draw UI from the snapshot
+            self.terminal.draw(|f| ui::draw(f, app, &snapshot))?;
+            if self.interactive {
+                if let Some(request) = self.images.take_change() {
+                    if self.images.hide(&mut io::stdout())? {
+                        // Graphics aren't part of the cell buffer; repaint over them
+                        self.terminal.clear()?;
+                        self.terminal.draw(|f| ui::draw(f, app, &snapshot))?;
+                    }
+                    if let Some(request) = request {
+                        self.images.show(&mut io::stdout(), request)?;
+                    }
+                }
             }
+            self.show_activity(title::current_activity(&snapshot))?;
 
             // Check for quit
-            if app.state.read().await.should_quit {
+            if snapshot.should_quit {
                 break;
             }
 
@@ -1066,8 +2076,23 @@ impl Tui {
                     Event::Mouse(mouse) => {
                         self.handle_mouse(app, mouse).await?;
                     }
+                    Event::Paste(text) => {
+                        let mut state = app.state.write().await;
+                        if input::is_text_mode(state.input_mode) {
+                            self.history.reset();
+                            Self::paste_into_prompt(&mut state, &text);
+                        }
+                    }
+                    Event::Resize(_, _) => {
+                        // The resize clears the screen, taking any image with it
+                        self.images.hide(&mut io::stdout())?;
+                        // Layouts depend on the size; repaint from scratch
+                        self.terminal.autoresize()?;
+                        self.terminal.clear()?;
+                    }
                     _ => {}
                 }
+                dirty = true;
             }
         }
 
@@ -1086,13 +2111,133 @@ impl Tui {
         if state.bulk_install_running
             && matches!(key, KeyCode::Esc | KeyCode::Char('x') | KeyCode::Char('X'))
         {
-            state.bulk_install_cancel_requested = true;
-            state.set_status_info(
-                "Bulk install cancel requested; waiting for current archive to finish",
-            );
+            state.bulk_install_cancel.store(true, Ordering::Relaxed);
+            state.set_status_info("Bulk install cancel requested; stopping running extractions");
+            return Ok(());
+        }
+
+        // Ctrl+V pastes into text prompts; terminals with bracketed paste
+        // deliver their own paste as an `Event::Paste` instead
+        if key == KeyCode::Char('v')
+            && modifiers.contains(KeyModifiers::CONTROL)
+            && input::is_text_mode(state.input_mode)
+        {
+            self.history.reset();
+            match clipboard::read_text() {
+                Ok(text) => Self::paste_into_prompt(&mut state, &text),
+                Err(e) => state.set_status_error(e.to_string()),
+            }
+            return Ok(());
+        }
+
+        if state.input_mode == InputMode::CommandPalette {
+            match key {
+                KeyCode::Esc => {
+                    state.input_mode = InputMode::Normal;
+                    state.input_buffer.clear();
+                }
+                KeyCode::Up => state.palette_index = state.palette_index.saturating_sub(1),
+                KeyCode::Down => {
+                    let count = palette::matches(&state, &state.input_buffer).len();
+                    state.palette_index = (state.palette_index + 1).min(count.saturating_sub(1));
+                }
+                KeyCode::Backspace => {
+                    state.input_buffer.pop();
+                    state.palette_index = 0;
+                }
+                KeyCode::Char(c) => {
+                    state.input_buffer.push(c);
+                    state.palette_index = 0;
+                }
+                KeyCode::Enter => {
+                    let picked = palette::matches(&state, &state.input_buffer)
+                        .into_iter()
+                        .nth(state.palette_index);
+                    state.input_mode = InputMode::Normal;
+                    state.input_buffer.clear();
+                    if let Some(entry) = picked {
+                        drop(state);
+                        self.run_palette_action(app, entry.action).await?;
+                    }
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if state.input_mode == InputMode::ModFilterPicker {
+            // Entry 0 is "All mods", then one per saved filter
+            let count = state.mod_filters.len() + 1;
+            match key {
+                KeyCode::Esc => state.input_mode = InputMode::Normal,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    state.mod_filter_index = state.mod_filter_index.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    state.mod_filter_index = (state.mod_filter_index + 1).min(count - 1);
+                }
+                KeyCode::Enter => {
+                    state.input_mode = InputMode::Normal;
+                    state.active_mod_filter = state.mod_filter_index.checked_sub(1);
+                    state.selected_mod_index = 0;
+                    match state.active_filter().map(|f| f.name.clone()) {
+                        Some(name) => state.set_status(format!("Filter: {}", name)),
+                        None => state.set_status("Showing all mods".to_string()),
+                    }
+                }
+                KeyCode::Char('n') => {
+                    state.input_mode = InputMode::ModFilterNameInput;
+                    state.input_buffer = state
+                        .active_filter()
+                        .map(|f| f.name.clone())
+                        .unwrap_or_default();
+                }
+                KeyCode::Char('d') => {
+                    let Some(name) = state
+                        .mod_filter_index
+                        .checked_sub(1)
+                        .and_then(|i| state.mod_filters.get(i))
+                        .map(|f| f.name.clone())
+                    else {
+                        return Ok(());
+                    };
+                    drop(state);
+                    Self::delete_mod_filter(app, &name).await?;
+                }
+                _ => {}
+            }
             return Ok(());
         }
 
+        // History and completion shared by all text prompts
+        if input::is_text_mode(state.input_mode) {
+            let mode = state.input_mode;
+            match key {
+                KeyCode::Up => {
+                    if let Some(text) = self.history.previous(mode, &state.input_buffer) {
+                        state.input_buffer = text;
+                    }
+                    return Ok(());
+                }
+                KeyCode::Down => {
+                    if let Some(text) = self.history.next(mode) {
+                        state.input_buffer = text;
+                    }
+                    return Ok(());
+                }
+                KeyCode::Tab if input::completes_paths(mode) => {
+                    self.history.reset();
+                    if let Some(text) = input::complete_path(&state.input_buffer) {
+                        state.input_buffer = text;
+                    }
+                    return Ok(());
+                }
+                // Falls through to the prompt's own handling
+                KeyCode::Enter => self.history.record(mode, &state.input_buffer),
+                _ => self.history.reset(),
+            }
+        }
+
         // Handle input mode
         if state.input_mode == InputMode::ModInstallPath {
             match key {
@@ -1143,30 +2288,27 @@ impl Tui {
 
                     // Install single mod file
                     if let Some(game) = app.active_game().await {
-                        let state_clone = app.state.clone();
+                        let events = app.events.clone();
 
                         // Create progress callback
                         let progress_callback = std::sync::Arc::new(
                             move |current_file: String, processed: usize, total: usize| {
-                                if let Ok(mut state) = state_clone.try_write() {
-                                    let percent = if total > 0 {
-                                        ((processed as f64 / total as f64) * 100.0) as u16
-                                    } else {
-                                        0
-                                    };
+                                let percent = if total > 0 {
+                                    ((processed as f64 / total as f64) * 100.0) as u16
+                                } else {
+                                    0
+                                };
 
-                                    state.installation_progress =
-                                        Some(crate::app::state::InstallProgress {
-                                            percent,
-                                            current_file,
-                                            total_files: total,
-                                            processed_files: processed,
-                                            // Single mod install - no bulk context
-                                            current_mod_name: None,
-                                            current_mod_index: None,
-                                            total_mods: None,
-                                        });
-                                }
+                                events.install_progress(Some(crate::app::state::InstallProgress {
+                                    percent,
+                                    current_file,
+                                    total_files: total,
+                                    processed_files: processed,
+                                    // Single mod install - no bulk context
+                                    current_mod_name: None,
+                                    current_mod_index: None,
+                                    total_mods: None,
+                                }));
                             },
                         );
 
@@ -1183,12 +2325,9 @@ impl Tui {
                             .await
                         {
                             Ok(crate::mods::InstallResult::Completed(installed)) => {
-                                // Clear progress FIRST to prevent UI corruption
-                                {
-                                    let mut state = app.state.write().await;
-                                    state.installation_progress = None;
-                                    state.status_message = None; // Clear any lingering status
-                                }
+                                // Clear progress through the event queue so it lands after
+                                // any progress updates still pending from the install
+                                app.events.install_progress(None);
 
                                 self.refresh_mods(app).await?;
 
@@ -1200,39 +2339,14 @@ impl Tui {
                             }
                             Ok(crate::mods::InstallResult::RequiresWizard(context)) => {
                                 // Clear progress
-                                {
-                                    let mut state = app.state.write().await;
-                                    state.installation_progress = None;
-                                    state.status_message = None;
-                                }
-
-                                // Initialize wizard state
-                                use crate::app::state::{FomodWizardState, WizardPhase};
-                                use crate::mods::fomod::wizard::init_wizard_state;
-
-                                let wizard = init_wizard_state(&context.installer.config);
-                                let wizard_state = FomodWizardState {
-                                    installer: context.installer.clone(),
-                                    wizard,
-                                    current_step: 0,
-                                    current_group: 0,
-                                    selected_option: 0,
-                                    validation_errors: Vec::new(),
-                                    mod_name: context.mod_name.clone(),
-                                    staging_path: context.staging_path.clone(),
-                                    preview_files: None,
-                                    phase: WizardPhase::Overview,
-                                    existing_mod_id: None,
-                                };
-
+                                app.events.install_progress(None);
                                 let mut state = app.state.write().await;
-                                state.fomod_wizard_state = Some(wizard_state);
-                                state.goto(crate::app::state::Screen::FomodWizard);
+                                state.clear_status();
+                                Self::open_fomod_wizard(&app.mods, &mut state, &context);
                             }
                             Err(e) => {
+                                app.events.install_progress(None);
                                 let mut state = app.state.write().await;
-                                state.installation_progress = None;
-                                state.status_message = None; // Clear any lingering status
                                 state.set_status(format!("Error: {}", e));
                             }
                         }
@@ -1400,16 +2514,19 @@ impl Tui {
                     }
 
                     let resolved = app.resolved_downloads_dir().await;
+                    if self.downloads_watch.is_some() {
+                        self.watch_downloads(app.events.clone(), resolved.as_ref().ok().cloned());
+                    }
                     let mut state = app.state.write().await;
                     if directory.trim().is_empty() {
                         state.set_status(format!(
                             "Downloads directory override cleared: {}",
-                            resolved.display()
+                            crate::app::dir_display(&resolved)
                         ));
                     } else {
                         state.set_status(format!(
                             "Downloads directory set to: {}",
-                            resolved.display()
+                            crate::app::dir_display(&resolved)
                         ));
                     }
                     return Ok(());
@@ -1459,12 +2576,12 @@ impl Tui {
                     if directory.trim().is_empty() {
                         state.set_status(format!(
                             "Staging directory override cleared: {}",
-                            resolved.display()
+                            crate::app::dir_display(&resolved)
                         ));
                     } else {
                         state.set_status(format!(
                             "Staging directory set to: {}",
-                            resolved.display()
+                            crate::app::dir_display(&resolved)
                         ));
                     }
                     return Ok(());
@@ -1569,54 +2686,20 @@ impl Tui {
                 _ => {}
             }
             return Ok(());
-        } else if state.input_mode == InputMode::NexusApiKeyInput {
+        } else if matches!(
+            state.input_mode,
+            InputMode::IniValueInput | InputMode::IniKeyInput
+        ) {
             match key {
                 KeyCode::Enter => {
-                    state.input_mode = InputMode::Normal;
-                    let api_key = state.input_buffer.clone();
-                    state.input_buffer.clear();
+                    let mode = std::mem::take(&mut state.input_mode);
+                    let value = std::mem::take(&mut state.input_buffer);
                     drop(state);
-
-                    // Save to config
-                    let key_to_save = if api_key.is_empty() {
-                        None
-                    } else {
-                        Some(api_key.clone())
-                    };
-
-                    {
-                        let mut config = app.config.write().await;
-                        config.nexus_api_key = key_to_save.clone();
-
-                        // Save config to disk
-                        if let Err(e) = config.save().await {
-                            let mut state = app.state.write().await;
-                            state.set_status(format!("Error saving config: {}", e));
-                            return Ok(());
-                        }
-                    }
-
-                    // Reinitialize Nexus client with new API key
-                    if let Some(key) = key_to_save {
-                        match crate::nexus::NexusClient::new(key.clone()) {
-                            Ok(client) => {
-                                app.nexus = Some(Arc::new(client));
-                                let mut state = app.state.write().await;
-                                state
-                                    .set_status("NexusMods API key saved successfully".to_string());
-                            }
-                            Err(e) => {
-                                let mut state = app.state.write().await;
-                                state.set_status(format!("Error initializing Nexus client: {}", e));
-                            }
-                        }
+                    if mode == InputMode::IniValueInput {
+                        screens::ini_editor::submit_value(app, &value).await;
                     } else {
-                        app.nexus = None;
-                        let mut state = app.state.write().await;
-                        state.set_status("NexusMods API key cleared".to_string());
+                        screens::ini_editor::submit_key(app, &value).await;
                     }
-
-                    return Ok(());
                 }
                 KeyCode::Esc => {
                     state.input_mode = InputMode::Normal;
@@ -1631,24 +2714,153 @@ impl Tui {
                 _ => {}
             }
             return Ok(());
-        } else if state.input_mode == InputMode::BrowseSearch {
+        } else if state.input_mode == InputMode::ExternalToolOptionsInput {
             match key {
                 KeyCode::Enter => {
                     state.input_mode = InputMode::Normal;
-                    let query = state.input_buffer.clone();
-                    state.input_buffer.clear();
-
-                    if query.is_empty() {
-                        state.set_status("Search query cannot be empty".to_string());
-                        return Ok(());
-                    }
-
-                    if app.nexus.is_none() {
-                        state.set_status("Browse requires Nexus API key".to_string());
+                    let value = std::mem::take(&mut state.input_buffer);
+                    let field = state.tool_option_field;
+                    let Some(tool) = Self::settings_tool_for_index(state.selected_setting_index)
+                    else {
+                        state.set_status("Invalid settings selection for tool options".to_string());
                         return Ok(());
-                    }
+                    };
+                    drop(state);
 
-                    state.browse_query = query.clone();
+                    let mut options = app
+                        .config
+                        .read()
+                        .await
+                        .external_tool_options(tool)
+                        .cloned()
+                        .unwrap_or_default();
+                    match field {
+                        ToolOptionField::Args => options.args = config::split_args(&value),
+                        ToolOptionField::WorkingDir => {
+                            options.working_dir =
+                                Some(value.trim().to_string()).filter(|d| !d.is_empty())
+                        }
+                        ToolOptionField::Env => {
+                            match config::parse_env(&config::split_args(&value)) {
+                                Ok(env) => options.env = env,
+                                Err(e) => {
+                                    app.state.write().await.set_status_error(e.to_string());
+                                    return Ok(());
+                                }
+                            }
+                        }
+                    }
+
+                    let result = app.set_external_tool_options(tool, options).await;
+                    let mut state = app.state.write().await;
+                    match result {
+                        Ok(()) => state.set_status(format!(
+                            "{} {} saved",
+                            tool.display_name(),
+                            field.label()
+                        )),
+                        Err(e) => state.set_status_error(format!(
+                            "Error saving {} options: {}",
+                            tool.display_name(),
+                            e
+                        )),
+                    }
+                    return Ok(());
+                }
+                KeyCode::Esc => {
+                    state.input_mode = InputMode::Normal;
+                    state.input_buffer.clear();
+                }
+                KeyCode::Backspace => {
+                    state.input_buffer.pop();
+                }
+                KeyCode::Char(c) => {
+                    state.input_buffer.push(c);
+                }
+                _ => {}
+            }
+            return Ok(());
+        } else if state.input_mode == InputMode::NexusApiKeyInput {
+            match key {
+                KeyCode::Enter => {
+                    state.input_mode = InputMode::Normal;
+                    let api_key = state.input_buffer.clone();
+                    state.input_buffer.clear();
+                    drop(state);
+
+                    // Save to config
+                    let key_to_save = if api_key.is_empty() {
+                        None
+                    } else {
+                        Some(api_key.clone())
+                    };
+
+                    let base_url = {
+                        let mut config = app.config.write().await;
+                        config.nexus_api_key = key_to_save.clone();
+
+                        // Save config to disk
+                        if let Err(e) = config.save().await {
+                            let mut state = app.state.write().await;
+                            state.set_status(format!("Error saving config: {}", e));
+                            return Ok(());
+                        }
+                        config.nexus_base_url().to_string()
+                    };
+
+                    // Reinitialize Nexus client with new API key
+                    if let Some(key) = key_to_save {
+                        match crate::nexus::NexusClient::with_base_url(key.clone(), &base_url) {
+                            Ok(client) => {
+                                app.nexus = Some(Arc::new(client));
+                                let mut state = app.state.write().await;
+                                state
+                                    .set_status("NexusMods API key saved successfully".to_string());
+                            }
+                            Err(e) => {
+                                let mut state = app.state.write().await;
+                                state.set_status(format!("Error initializing Nexus client: {}", e));
+                            }
+                        }
+                    } else {
+                        app.nexus = None;
+                        let mut state = app.state.write().await;
+                        state.set_status("NexusMods API key cleared".to_string());
+                    }
+
+                    return Ok(());
+                }
+                KeyCode::Esc => {
+                    state.input_mode = InputMode::Normal;
+                    state.input_buffer.clear();
+                }
+                KeyCode::Backspace => {
+                    state.input_buffer.pop();
+                }
+                KeyCode::Char(c) => {
+                    state.input_buffer.push(c);
+                }
+                _ => {}
+            }
+            return Ok(());
+        } else if state.input_mode == InputMode::BrowseSearch {
+            match key {
+                KeyCode::Enter => {
+                    state.input_mode = InputMode::Normal;
+                    let query = state.input_buffer.clone();
+                    state.input_buffer.clear();
+
+                    if query.is_empty() {
+                        state.set_status("Search query cannot be empty".to_string());
+                        return Ok(());
+                    }
+
+                    if app.nexus.is_none() {
+                        state.set_status("Browse requires Nexus API key".to_string());
+                        return Ok(());
+                    }
+
+                    state.browse_query = query.clone();
                     state.browse_showing_default = false;
                     state.browsing = true;
                     state.browse_offset = 0;
@@ -1662,12 +2874,12 @@ impl Tui {
                     let limit = state.browse_limit;
                     let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                     let nexus_clone = app.nexus.as_ref().unwrap().clone();
-                    let state_clone = app.state.clone();
+                    let events = app.events.clone();
 
                     drop(state);
 
                     Self::spawn_browse_search(
-                        state_clone,
+                        events,
                         nexus_clone,
                         game_id,
                         Some(query),
@@ -1713,6 +2925,7 @@ impl Tui {
                             let current_index = state.selected_plugin_index;
 
                             if target_index != current_index {
+                                Self::record_plugin_edit(&mut state, "move");
                                 // Remove plugin from current position
                                 let plugin = state.plugins.remove(current_index);
                                 // Insert at target position
@@ -1779,6 +2992,32 @@ impl Tui {
                 _ => {}
             }
             return Ok(());
+        } else if state.input_mode == InputMode::ModFilterNameInput {
+            match key {
+                KeyCode::Enter => {
+                    state.input_mode = InputMode::Normal;
+                    let name = state.input_buffer.trim().to_string();
+                    state.input_buffer.clear();
+                    if name.is_empty() {
+                        state.set_status_error("A filter needs a name");
+                        return Ok(());
+                    }
+                    drop(state);
+                    Self::save_mod_filter(app, name).await?;
+                }
+                KeyCode::Esc => {
+                    state.input_mode = InputMode::ModFilterPicker;
+                    state.input_buffer.clear();
+                }
+                KeyCode::Backspace => {
+                    state.input_buffer.pop();
+                }
+                KeyCode::Char(c) => {
+                    state.input_buffer.push(c);
+                }
+                _ => {}
+            }
+            return Ok(());
         } else if state.input_mode == InputMode::PluginSearch {
             match key {
                 KeyCode::Enter => {
@@ -1872,7 +3111,7 @@ impl Tui {
                     };
 
                     // Clone what we need for the async task
-                    let state_clone = app.state.clone();
+                    let events = app.events.clone();
                     let db_clone = app.db.clone();
                     let mods_clone = app.mods.clone();
                     let config_clone = app.config.clone();
@@ -2089,15 +3328,11 @@ impl Tui {
 
                         match result {
                             Ok(_) => {
-                                let mut state = state_clone.write().await;
-                                state.set_status_success(format!(
-                                    "Modlist saved to {}",
-                                    expanded_path
-                                ));
+                                events
+                                    .status_success(format!("Modlist saved to {}", expanded_path));
                             }
                             Err(e) => {
-                                let mut state = state_clone.write().await;
-                                state.set_status_error(format!("Error saving modlist: {}", e));
+                                events.failure("Error saving modlist", e);
                             }
                         }
                     });
@@ -2142,12 +3377,16 @@ impl Tui {
                     };
 
                     state.set_status("Loading modlist...");
+                    let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                     drop(state);
 
                     // Spawn load task
-                    let state_clone = app.state.clone();
-                    let db_clone = app.db.clone();
-                    Self::spawn_load_modlist(state_clone, db_clone, expanded_path);
+                    Self::spawn_load_modlist(
+                        app.events.clone(),
+                        app.db.clone(),
+                        game_id,
+                        expanded_path,
+                    );
                     return Ok(());
                 }
                 KeyCode::Esc => {
@@ -2525,49 +3764,50 @@ impl Tui {
                         // Fetch mod files and show file picker
                         if let Some(ref nexus) = app.nexus {
                             let nexus_clone = nexus.clone();
-                            let state_clone = app.state.clone();
+                            let events = app.events.clone();
 
                             tokio::spawn(async move {
                                 match nexus_clone.get_mod_files(game_id_numeric, req.mod_id).await {
                                     Ok(mut files) => {
-                                        let mut state = state_clone.write().await;
-                                        if !files.is_empty() {
-                                            // Sort: MAIN first, then UPDATE, OPTIONAL, OLD_VERSION
-                                            files.sort_by(|a, b| {
-                                                let order = |cat: &str| match cat {
-                                                    "MAIN" => 0,
-                                                    "UPDATE" => 1,
-                                                    "OPTIONAL" => 2,
-                                                    "OLD_VERSION" => 3,
-                                                    _ => 4,
-                                                };
-                                                order(&a.category).cmp(&order(&b.category))
-                                            });
-
-                                            state.browse_mod_files = files;
-                                            state.selected_file_index = 0;
-                                            state.showing_file_picker = true;
-                                            state.download_context =
-                                                Some(crate::app::state::DownloadContext {
-                                                    mod_id: req.mod_id,
-                                                    mod_name: req.name.clone(),
-                                                    game_domain: game_domain.clone(),
-                                                    game_id: game_id_numeric,
+                                        events.update(move |state| {
+                                            if !files.is_empty() {
+                                                // Sort: MAIN first, then UPDATE, OPTIONAL, OLD_VERSION
+                                                files.sort_by(|a, b| {
+                                                    let order = |cat: &str| match cat {
+                                                        "MAIN" => 0,
+                                                        "UPDATE" => 1,
+                                                        "OPTIONAL" => 2,
+                                                        "OLD_VERSION" => 3,
+                                                        _ => 4,
+                                                    };
+                                                    order(&a.category).cmp(&order(&b.category))
                                                 });
-                                            state.set_status(format!(
-                                                "Select file to download for {}",
-                                                req.name
-                                            ));
-                                        } else {
-                                            state.set_status(format!(
-                                                "No files found for {}",
-                                                req.name
-                                            ));
-                                        }
+
+                                                state.browse_mod_files = files;
+                                                state.selected_file_index = 0;
+                                                state.showing_file_picker = true;
+                                                state.download_context =
+                                                    Some(crate::app::state::DownloadContext {
+                                                        mod_id: req.mod_id,
+                                                        mod_name: req.name.clone(),
+                                                        game_domain: game_domain.clone(),
+                                                        game_id: game_id_numeric,
+                                                        queue_entry_id: None,
+                                                    });
+                                                state.set_status(format!(
+                                                    "Select file to download for {}",
+                                                    req.name
+                                                ));
+                                            } else {
+                                                state.set_status(format!(
+                                                    "No files found for {}",
+                                                    req.name
+                                                ));
+                                            }
+                                        });
                                     }
                                     Err(e) => {
-                                        let mut state = state_clone.write().await;
-                                        state.set_status(format!("Failed to fetch files: {}", e));
+                                        events.failure("Failed to fetch files", e);
                                     }
                                 }
                             });
@@ -2595,6 +3835,74 @@ impl Tui {
             return Ok(());
         }
 
+        // Full text of a notification, over the history (modal)
+        if let Some(detail) = &state.error_detail {
+            let text = detail.detail.as_deref().unwrap_or(&detail.message);
+            let last = text.lines().count().saturating_sub(1) as u16;
+            match key {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                    state.error_detail = None;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    state.error_detail_scroll = (state.error_detail_scroll + 1).min(last);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    state.error_detail_scroll = state.error_detail_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => {
+                    state.error_detail_scroll = (state.error_detail_scroll + 10).min(last);
+                }
+                KeyCode::PageUp => {
+                    state.error_detail_scroll = state.error_detail_scroll.saturating_sub(10);
+                }
+                KeyCode::Home => state.error_detail_scroll = 0,
+                KeyCode::End => state.error_detail_scroll = last,
+                KeyCode::Char('y') => {
+                    let copied = format!("{}\n\n{}", detail.message, text);
+                    match clipboard::write_text(&copied) {
+                        Ok(()) => state.set_status_info("Copied the error to the clipboard"),
+                        Err(e) => state.set_status_error(e.to_string()),
+                    }
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        // Notification history (modal)
+        if state.show_notifications {
+            match (key, modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                    state.show_notifications = false;
+                }
+                (KeyCode::Char('x'), KeyModifiers::CONTROL) => {
+                    state.notifications.dismiss_errors();
+                }
+                (KeyCode::Char('j'), _) | (KeyCode::Down, _) => {
+                    let last = state.notifications.history().len().saturating_sub(1);
+                    state.notifications_scroll = (state.notifications_scroll + 1).min(last);
+                }
+                (KeyCode::Char('k'), _) | (KeyCode::Up, _) => {
+                    state.notifications_scroll = state.notifications_scroll.saturating_sub(1);
+                }
+                (KeyCode::Enter, _) => {
+                    // Open the entry on top in full
+                    let opened = state
+                        .notifications
+                        .history()
+                        .rev()
+                        .nth(state.notifications_scroll)
+                        .cloned();
+                    if opened.is_some() {
+                        state.error_detail = opened;
+                        state.error_detail_scroll = 0;
+                    }
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
         // Help overlay navigation (modal)
         if state.show_help {
             const HELP_PAGE_COUNT: usize = 8;
@@ -2620,6 +3928,48 @@ impl Tui {
 
         // Global keys
         match (key, modifiers) {
+            (KeyCode::Char('v'), KeyModifiers::CONTROL) => {
+                drop(state);
+                Self::install_from_clipboard(app).await?;
+                return Ok(());
+            }
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
+                Self::toggle_debug_logging(&mut state);
+                return Ok(());
+            }
+            (KeyCode::Char('l'), KeyModifiers::CONTROL) => {
+                state.log_pane.toggle();
+                return Ok(());
+            }
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) => {
+                drop(state);
+                self.launch_game_from_tui(app).await?;
+                return Ok(());
+            }
+            (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                state.input_mode = InputMode::CommandPalette;
+                state.input_buffer.clear();
+                state.palette_index = 0;
+                return Ok(());
+            }
+            (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                state.show_notifications = true;
+                state.notifications_scroll = 0;
+                return Ok(());
+            }
+            (KeyCode::Char('x'), KeyModifiers::CONTROL) => {
+                state.notifications.dismiss_errors();
+                return Ok(());
+            }
+            (KeyCode::Char('b'), KeyModifiers::CONTROL)
+            | (KeyCode::Char('f'), KeyModifiers::CONTROL)
+            | (KeyCode::Char('t'), KeyModifiers::CONTROL)
+            | (KeyCode::Char('y'), KeyModifiers::CONTROL)
+                if state.log_pane.visible =>
+            {
+                Self::handle_log_pane_key(&mut state, key);
+                return Ok(());
+            }
             (KeyCode::Char('c'), KeyModifiers::CONTROL) | (KeyCode::Char('q'), _) => {
                 state.should_quit = true;
             }
@@ -2695,6 +4045,10 @@ impl Tui {
                     Screen::DownloadQueue,
                     Screen::NexusCatalog,
                     Screen::ModlistEditor,
+                    Screen::Extensions,
+                    Screen::Storage,
+                    Screen::Downloads,
+                    Screen::History,
                 ];
                 let current = Self::normalize_tab_screen(state.current_screen);
                 let mut pos = flow.iter().position(|s| *s == current).unwrap_or(0);
@@ -2714,6 +4068,16 @@ impl Tui {
                     return Ok(());
                 }
                 state.goto(target);
+                if target == Screen::Storage {
+                    drop(state);
+                    screens::storage::refresh(app).await;
+                    return Ok(());
+                }
+                if target == Screen::History {
+                    drop(state);
+                    screens::history::refresh(app).await;
+                    return Ok(());
+                }
             }
             (KeyCode::F(1), _) => {
                 state.goto(Screen::Mods);
@@ -2741,6 +4105,24 @@ impl Tui {
                 Self::open_modlists_screen(app).await?;
                 return Ok(());
             }
+            (KeyCode::F(9), _) => {
+                state.goto(Screen::Extensions);
+            }
+            (KeyCode::F(10), _) => {
+                state.goto(Screen::Storage);
+                drop(state);
+                screens::storage::refresh(app).await;
+                return Ok(());
+            }
+            (KeyCode::F(11), _) => {
+                state.goto(Screen::Downloads);
+            }
+            (KeyCode::F(12), _) => {
+                state.goto(Screen::History);
+                drop(state);
+                screens::history::refresh(app).await;
+                return Ok(());
+            }
             (KeyCode::Char('?'), _) => {
                 state.show_help = !state.show_help;
                 if state.show_help {
@@ -2751,6 +4133,11 @@ impl Tui {
                 if state.show_help {
                     state.show_help = false;
                     state.help_page = 0;
+                } else if matches!(state.current_screen, Screen::Mods | Screen::Dashboard)
+                    && (!state.marked_mod_ids.is_empty() || state.mod_mark_anchor.is_some())
+                {
+                    state.clear_mod_marks();
+                    state.set_status_info("Cleared mod marks");
                 } else {
                     state.go_back();
                 }
@@ -2778,8 +4165,75 @@ impl Tui {
         Ok(())
     }
 
+    /// Run what was picked in the command palette
+    async fn run_palette_action(
+        &mut self,
+        app: &mut App,
+        action: palette::PaletteAction,
+    ) -> Result<()> {
+        use palette::PaletteAction;
+
+        let mut state = app.state.write().await;
+        match action {
+            PaletteAction::Open(Screen::ModlistEditor) => {
+                drop(state);
+                Self::open_modlists_screen(app).await?;
+            }
+            PaletteAction::Open(screen) => {
+                state.goto(screen);
+                if screen == Screen::Storage {
+                    drop(state);
+                    screens::storage::refresh(app).await;
+                } else if screen == Screen::History {
+                    drop(state);
+                    screens::history::refresh(app).await;
+                } else if screen == Screen::IniEditor {
+                    drop(state);
+                    screens::ini_editor::refresh(app).await;
+                } else if screen == Screen::Saves {
+                    drop(state);
+                    screens::saves::refresh(app).await;
+                }
+            }
+            PaletteAction::Key(screen, key) => {
+                if state.current_screen != screen {
+                    state.goto(screen);
+                }
+                drop(state);
+                self.handle_screen_key(app, key, KeyModifiers::NONE).await?;
+            }
+            PaletteAction::Profile(index) => {
+                state.goto(Screen::Profiles);
+                state.selected_profile_index = index;
+                drop(state);
+                self.handle_screen_key(app, KeyCode::Enter, KeyModifiers::NONE)
+                    .await?;
+            }
+            PaletteAction::Mod(id) => {
+                state.mod_search_query.clear();
+                if let Some(index) = state.reveal_mod(id) {
+                    state.goto(Screen::Mods);
+                    state.selected_mod_index = index;
+                    drop(state);
+                    self.handle_screen_key(app, KeyCode::Enter, KeyModifiers::NONE)
+                        .await?;
+                }
+            }
+            PaletteAction::Help => {
+                state.show_help = true;
+                state.help_page = 0;
+            }
+            PaletteAction::LogPane => state.log_pane.toggle(),
+            PaletteAction::Notifications => {
+                state.show_notifications = true;
+                state.notifications_scroll = 0;
+            }
+        }
+        Ok(())
+    }
+
     /// Handle mouse events
-    async fn handle_mouse(&self, app: &mut App, mouse: MouseEvent) -> Result<()> {
+    async fn handle_mouse(&mut self, app: &mut App, mouse: MouseEvent) -> Result<()> {
         let mut state = app.state.write().await;
 
         // Skip mouse handling when in input mode
@@ -2797,6 +4251,9 @@ impl Tui {
                             state.selected_mod_index += 1;
                         }
                     }
+                    Screen::ModDetails => {
+                        state.mod_details_scroll = state.mod_details_scroll.saturating_add(1);
+                    }
                     Screen::Plugins => {
                         let count = state.plugins.len();
                         if count > 0 && state.selected_plugin_index < count - 1 {
@@ -2810,8 +4267,9 @@ impl Tui {
                         }
                     }
                     Screen::Settings => {
-                        // Settings has 17 items (0-16)
-                        if state.selected_setting_index < 16 {
+                        let last =
+                            SETTINGS_FIXED_ROWS - 1 + app.config.read().await.custom_tools.len();
+                        if state.selected_setting_index < last {
                             state.selected_setting_index += 1;
                         }
                     }
@@ -2873,6 +4331,9 @@ impl Tui {
                         state.selected_mod_index -= 1;
                     }
                 }
+                Screen::ModDetails => {
+                    state.mod_details_scroll = state.mod_details_scroll.saturating_sub(1);
+                }
                 Screen::Plugins => {
                     if state.selected_plugin_index > 0 {
                         state.selected_plugin_index -= 1;
@@ -2934,35 +4395,60 @@ impl Tui {
                 _ => {}
             },
             MouseEventKind::Down(MouseButton::Left) => {
-                // Check if click is on tab bar row (row 3, 0-indexed)
-                // Tab bar is at row 3 (after 3-line header)
-                if mouse.row == 3 {
-                    // Map column position to tab index
-                    // Tabs: "F1 Mods|F2 Plugins|F3 Profiles|F4 Settings|F5 Import|F6 Queue|F7 Catalog|F8 Modlists"
-                    let col = mouse.column as usize;
-                    let screen = if col < 8 {
-                        Some(Screen::Mods)
-                    } else if col < 19 {
-                        Some(Screen::Plugins)
-                    } else if col < 31 {
-                        Some(Screen::Profiles)
-                    } else if col < 43 {
-                        Some(Screen::Settings)
-                    } else if col < 53 {
-                        Some(Screen::Import)
-                    } else if col < 62 {
-                        Some(Screen::DownloadQueue)
-                    } else if col < 73 {
-                        Some(Screen::NexusCatalog)
-                    } else if col < 85 {
-                        Some(Screen::ModlistEditor)
-                    } else {
-                        None
-                    };
+                // Popups cover the screen underneath
+                if state.show_help
+                    || state.show_notifications
+                    || state.show_confirm.is_some()
+                    || state.show_requirements.is_some()
+                    || state.showing_file_picker
+                {
+                    return Ok(());
+                }
+
+                // Tab bar, resolved against the titles drawn at this width
+                if mouse.row == layout::TAB_ROW {
+                    let width = self.terminal.size()?.width;
+                    let selected = layout::tab_index(state.current_screen);
+                    let screen = layout::tab_at(selected, width, mouse.column);
 
                     if let Some(target) = screen {
                         state.goto(target);
+                        if target == Screen::Storage {
+                            drop(state);
+                            screens::storage::refresh(app).await;
+                            return Ok(());
+                        }
+                        if target == Screen::History {
+                            drop(state);
+                            screens::history::refresh(app).await;
+                            return Ok(());
+                        }
                     }
+                    return Ok(());
+                }
+
+                let screen = state.current_screen;
+                let Some(index) = hit::list_index_at(screen, mouse.column, mouse.row) else {
+                    return Ok(());
+                };
+                Self::select_list_row(&mut state, index);
+                if self.clicks.click(screen, index, std::time::Instant::now()) {
+                    // Double-click does what the screen's activation key does
+                    let key = if screen == Screen::Plugins {
+                        KeyCode::Char(' ')
+                    } else {
+                        KeyCode::Enter
+                    };
+                    drop(state);
+                    self.handle_screen_key(app, key, KeyModifiers::NONE).await?;
+                    return Ok(());
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(index) =
+                    hit::list_index_at(state.current_screen, mouse.column, mouse.row)
+                {
+                    self.drag_to(&mut state, index);
                 }
             }
             _ => {}
@@ -2971,12 +4457,89 @@ impl Tui {
         Ok(())
     }
 
+    /// Select row `index` of the current screen's main list
+    fn select_list_row(state: &mut AppState, index: usize) {
+        match state.current_screen {
+            Screen::Mods | Screen::Dashboard => state.selected_mod_index = index,
+            Screen::GameSelect => state.selected_game_index = index,
+            Screen::Plugins => state.selected_plugin_index = index,
+            Screen::Profiles => state.selected_profile_index = index,
+            Screen::Settings => state.selected_setting_index = index,
+            Screen::Browse => state.selected_browse_index = index,
+            Screen::LoadOrder => state.load_order_index = index,
+            Screen::ImportReview => state.selected_import_index = index,
+            Screen::DownloadQueue => {
+                if state.selected_queue_index != index {
+                    state.selected_queue_alternative_index = 0;
+                }
+                state.selected_queue_index = index;
+            }
+            Screen::ModlistReview => state.selected_modlist_entry = index,
+            Screen::ModlistEditor => match state.modlist_editor_mode {
+                crate::app::state::ModlistEditorMode::ListPicker => {
+                    state.selected_saved_modlist_index = index
+                }
+                crate::app::state::ModlistEditorMode::EntryEditor => {
+                    state.selected_modlist_editor_index = index
+                }
+            },
+            Screen::NexusCatalog => state.selected_catalog_index = index,
+            Screen::ScriptOverrides => state.selected_script_index = index,
+            Screen::Downloads => state.selected_download_index = index,
+            Screen::History => state.selected_activity_index = index,
+            Screen::IniEditor => state.selected_ini_entry = index,
+            Screen::Saves => state.selected_save_index = index,
+            _ => {}
+        }
+    }
+
+    /// In reorder mode, move the item being dragged to row `target`
+    fn drag_to(&mut self, state: &mut AppState, target: usize) {
+        match state.current_screen {
+            Screen::Plugins if state.plugin_reorder_mode => {
+                let from = state.selected_plugin_index;
+                if from == target || target >= state.plugins.len() {
+                    return;
+                }
+                // Rows are filtered positions; moving them would scramble the order
+                if !state.plugin_search_query.is_empty() {
+                    state.set_status("Clear the search to drag plugins");
+                    return;
+                }
+                if !std::mem::replace(&mut self.clicks.drag_recorded, true) {
+                    Self::record_plugin_edit(state, "drag");
+                }
+                let plugin = state.plugins.remove(from);
+                state.plugins.insert(target, plugin);
+                for (i, p) in state.plugins.iter_mut().enumerate() {
+                    p.load_order = i;
+                }
+                state.selected_plugin_index = target;
+                state.plugin_dirty = true;
+            }
+            Screen::LoadOrder if state.reorder_mode => {
+                let from = state.load_order_index;
+                if from == target || target >= state.load_order_mods.len() {
+                    return;
+                }
+                if !std::mem::replace(&mut self.clicks.drag_recorded, true) {
+                    Self::record_load_order_edit(state, "drag");
+                }
+                let m = state.load_order_mods.remove(from);
+                state.load_order_mods.insert(target, m);
+                state.load_order_index = target;
+                state.load_order_dirty = true;
+            }
+            _ => {}
+        }
+    }
+
     /// Handle screen-specific keys
     async fn handle_screen_key(
         &mut self,
         app: &mut App,
         key: KeyCode,
-        _modifiers: KeyModifiers,
+        modifiers: KeyModifiers,
     ) -> Result<()> {
         let mut state = app.state.write().await;
         let screen = state.current_screen;
@@ -3006,15 +4569,44 @@ impl Tui {
                         .get(state.selected_file_index)
                         .cloned()
                     {
+                        if let Some(entry_id) = state
+                            .download_context
+                            .as_ref()
+                            .and_then(|ctx| ctx.queue_entry_id)
+                        {
+                            state.showing_file_picker = false;
+                            state.browse_mod_files.clear();
+                            state.download_context = None;
+                            let queue_manager = crate::queue::QueueManager::new(app.db.clone());
+                            match queue_manager.select_file(entry_id, Some(file.file_id)) {
+                                Ok(()) => {
+                                    if let Some(entry) =
+                                        state.queue_entries.iter_mut().find(|e| e.id == entry_id)
+                                    {
+                                        entry.selected_file_id = Some(file.file_id);
+                                    }
+                                    state.set_status_success(format!(
+                                        "Queue entry will download {} (v{})",
+                                        file.name, file.version
+                                    ));
+                                }
+                                Err(e) => {
+                                    state.set_status_error(format!("Failed to select file: {}", e))
+                                }
+                            }
+                            return Ok(());
+                        }
                         if let Some(ctx) = state.download_context.clone() {
                             let file_name = file.file_name.clone();
                             state.showing_file_picker = false;
                             state.set_status(format!("Getting download link for {}...", file.name));
 
                             let nexus_clone = app.nexus.as_ref().unwrap().clone();
-                            let state_clone = app.state.clone();
+                            let events = app.events.clone();
                             let mods_clone = app.mods.clone();
                             let config_clone = app.config.clone();
+                            let active_game = state.active_game.clone();
+                            let transfers = app.transfers.clone();
 
                             drop(state);
 
@@ -3026,20 +4618,10 @@ impl Tui {
                                 {
                                     Ok(links) => {
                                         if let Some(link) = links.first() {
-                                            // Set up download progress
-                                            {
-                                                let mut state = state_clone.write().await;
-                                                state.download_progress =
-                                                    Some(crate::app::state::DownloadProgress {
-                                                        file_name: file_name.clone(),
-                                                        downloaded_bytes: 0,
-                                                        total_bytes: file.size_bytes as u64,
-                                                    });
-                                                state.set_status(format!(
-                                                    "Downloading {}...",
-                                                    file_name
-                                                ));
-                                            }
+                                            events.status(format!(
+                                                "Downloading {}... (F11: downloads)",
+                                                file_name
+                                            ));
 
                                             // Download to temp file
                                             let download_dir = std::env::var("HOME")
@@ -3054,9 +4636,7 @@ impl Tui {
                                             if let Err(e) =
                                                 tokio::fs::create_dir_all(&download_dir).await
                                             {
-                                                let mut state = state_clone.write().await;
-                                                state.download_progress = None;
-                                                state.set_status(format!(
+                                                events.status(format!(
                                                     "Failed to create download dir: {}",
                                                     e
                                                 ));
@@ -3065,29 +4645,25 @@ impl Tui {
 
                                             let dest_path = download_dir.join(&file_name);
                                             let url = link.url.clone();
-                                            let state_for_progress = state_clone.clone();
-
-                                            match crate::nexus::NexusClient::download_file(
-                                                &url,
+                                            let transfer = transfers.start(
+                                                &ctx.mod_name,
                                                 &dest_path,
-                                                move |downloaded, total| {
-                                                    // Update progress in a non-blocking way
-                                                    let state_ref = state_for_progress.clone();
-                                                    tokio::spawn(async move {
-                                                        let mut state = state_ref.write().await;
-                                                        if let Some(ref mut progress) =
-                                                            state.download_progress
-                                                        {
-                                                            progress.downloaded_bytes = downloaded;
-                                                            if total > 0 {
-                                                                progress.total_bytes = total;
-                                                            }
-                                                        }
-                                                    });
-                                                },
-                                            )
-                                            .await
-                                            {
+                                                crate::queue::QueueControl::new(),
+                                            );
+                                            let reporter = transfer.clone();
+
+                                            let result =
+                                                crate::nexus::NexusClient::download_file_controlled(
+                                                    &url,
+                                                    &dest_path,
+                                                    &transfer.control,
+                                                    move |downloaded, total| {
+                                                        reporter.progress(downloaded, total)
+                                                    },
+                                                )
+                                                .await;
+                                            transfer.finish(&result);
+                                            match result {
                                                 Ok(()) => {
                                                     // Download complete - save to default mods directory if configured
                                                     let config = config_clone.read().await;
@@ -3137,14 +4713,10 @@ impl Tui {
                                                     drop(config);
 
                                                     // Auto install
-                                                    {
-                                                        let mut state = state_clone.write().await;
-                                                        state.download_progress = None;
-                                                        state.set_status(format!(
-                                                            "Downloaded! Installing {}...",
-                                                            file_name
-                                                        ));
-                                                    }
+                                                    events.status(format!(
+                                                        "Downloaded! Installing {}...",
+                                                        file_name
+                                                    ));
 
                                                     let game_id = ctx.game_domain.clone();
                                                     let game_id_for_install = match game_id.as_str()
@@ -3164,18 +4736,26 @@ impl Tui {
                                                         Some(&ctx.mod_name),
                                                     ).await {
                                                         Ok(crate::mods::InstallResult::Completed(installed)) => {
-                                                            let mut state = state_clone.write().await;
-                                                            state.set_status(format!(
+                                                            events.status(format!(
                                                                 "✓ Installed: {} (v{})",
                                                                 installed.name, installed.version
                                                             ));
                                                         }
                                                         Ok(crate::mods::InstallResult::RequiresWizard(context)) => {
                                                             // Launch FOMOD wizard
-                                                            use crate::mods::fomod::wizard::init_wizard_state;
+                                                            use crate::mods::fomod::wizard::init_wizard_state_with_env;
                                                             use crate::app::state::{FomodWizardState, WizardPhase};
 
-                                                            let wizard = init_wizard_state(&context.installer.config);
+                                                            let env = active_game
+                                                                .as_ref()
+                                                                .map(|game| {
+                                                                    mods_clone
+                                                                        .fomod_environment(&game.id)
+                                                                        .unwrap_or_default()
+                                                                        .with_game(game)
+                                                                })
+                                                                .unwrap_or_default();
+                                                            let wizard = init_wizard_state_with_env(&context.installer.config, env);
                                                             let wizard_state = FomodWizardState {
                                                                 installer: context.installer.clone(),
                                                                 wizard,
@@ -3186,52 +4766,56 @@ impl Tui {
                                                                 mod_name: context.mod_name.clone(),
                                                                 staging_path: context.staging_path.clone(),
                                                                 preview_files: None,
+                                                                preview_scroll: 0,
                                                                 phase: WizardPhase::Overview,
                                     existing_mod_id: None,
                                                             };
 
-                                                            let mut state = state_clone.write().await;
-                                                            state.fomod_wizard_state = Some(wizard_state);
-                                                            state.goto(crate::app::state::Screen::FomodWizard);
-                                                            state.set_status(format!("FOMOD installer detected for {}", context.mod_name));
+                                                            let mod_name = context.mod_name.clone();
+                                                            events.update(move |state| {
+                                                                state.fomod_wizard_state = Some(wizard_state);
+                                                                state.goto(crate::app::state::Screen::FomodWizard);
+                                                                state.set_status(format!("FOMOD installer detected for {}", mod_name));
+                                                            });
                                                         }
                                                         Err(e) => {
-                                                            let mut state = state_clone.write().await;
-                                                            state.set_status(format!(
-                                                                "Downloaded to {:?} but install failed: {}",
-                                                                dest_path, e
-                                                            ));
+                                                            events.failure(
+                                                                format!(
+                                                                    "Downloaded to {:?} but install failed",
+                                                                    dest_path
+                                                                ),
+                                                                e,
+                                                            );
                                                         }
                                                     }
                                                 }
-                                                Err(e) => {
-                                                    let mut state = state_clone.write().await;
-                                                    state.download_progress = None;
-                                                    state.set_status(format!(
-                                                        "Download failed: {}",
-                                                        e
+                                                Err(e) if crate::mods::is_cancelled(&e) => {
+                                                    events.status_info(format!(
+                                                        "Download of {} cancelled",
+                                                        file_name
                                                     ));
                                                 }
+                                                Err(e) => {
+                                                    events.failure("Download failed", e);
+                                                }
                                             }
                                         } else {
-                                            let mut state = state_clone.write().await;
-                                            state.set_status(
-                                                "No download links available".to_string(),
-                                            );
+                                            events.status("No download links available");
                                         }
                                     }
                                     Err(e) => {
-                                        let mut state = state_clone.write().await;
-                                        state.browse_mod_files.clear();
                                         let err_msg = e.to_string();
-                                        if err_msg.contains("Premium") || err_msg.contains("403") {
-                                            state.set_status("⚠ Direct download requires Nexus Premium. Visit nexusmods.com to download manually, then use 'i' to install.".to_string());
-                                        } else {
-                                            state.set_status(format!(
-                                                "Download link error: {}",
-                                                err_msg
-                                            ));
-                                        }
+                                        events.update(move |state| {
+                                            state.browse_mod_files.clear();
+                                            if err_msg.contains("Premium") || err_msg.contains("403") {
+                                                state.set_status("⚠ Direct download requires Nexus Premium. Visit nexusmods.com to download manually, then use 'i' to install.".to_string());
+                                            } else {
+                                                state.set_status(format!(
+                                                    "Download link error: {}",
+                                                    err_msg
+                                                ));
+                                            }
+                                        });
                                     }
                                 }
                             });
@@ -3281,6 +4865,9 @@ impl Tui {
                         KeyCode::Char('R') => Some("Staging rescan"),
                         KeyCode::Char('C') => Some("Collection import"),
                         KeyCode::Char('U') => Some("Update check"),
+                        KeyCode::Char('G') => Some("Update all"),
+                        KeyCode::Char('P') => Some("Version rollback"),
+                        KeyCode::Char('O') => Some("Reinstall from archive"),
                         KeyCode::Char('x') => Some("Requirements check"),
                         KeyCode::Char('c') => Some("Category assignment"),
                         KeyCode::Char('F') => Some("Force recategorize"),
@@ -3300,30 +4887,13 @@ impl Tui {
                     }
                 }
 
-                // Build filtered mod list based on active category filter and search query
-                let search_lower = state.mod_search_query.to_lowercase();
-                let filtered_mods: Vec<&crate::mods::InstalledMod> = state
-                    .installed_mods
-                    .iter()
-                    .filter(|m| {
-                        // Apply category filter
-                        let category_match = if let Some(filter_id) = state.category_filter {
-                            m.category_id == Some(filter_id)
-                        } else {
-                            true
-                        };
-
-                        // Apply search filter
-                        let search_match = if search_lower.is_empty() {
-                            true
-                        } else {
-                            m.name.to_lowercase().contains(&search_lower)
-                        };
-
-                        category_match && search_match
-                    })
-                    .collect();
-                let mod_count = filtered_mods.len();
+                // The list as shown: search, sort and category groups applied
+                let rows = state.mod_rows();
+                let mod_count = rows.len();
+                let selected_mod = rows.get(state.selected_mod_index).and_then(ModRow::as_mod);
+                let selected_group = rows
+                    .get(state.selected_mod_index)
+                    .and_then(|r| r.as_group().map(|g| (g.category_id, g.collapsed)));
                 match key {
                     KeyCode::Up | KeyCode::Char('k') => {
                         if state.selected_mod_index > 0 {
@@ -3353,7 +4923,8 @@ impl Tui {
                     KeyCode::Char('r') => {
                         // Full refresh + reset filters to show all installed mods
                         state.mod_search_query.clear();
-                        state.category_filter = None;
+                        state.active_mod_filter = None;
+                        state.collapsed_categories.clear();
                         state.selected_mod_index = 0;
                         drop(state);
                         self.refresh_mods(app).await?;
@@ -3382,9 +4953,84 @@ impl Tui {
                         }
                         return Ok(());
                     }
-                    KeyCode::Char(' ') | KeyCode::Char('e') => {
+                    KeyCode::Char(' ') => {
+                        // Mark/unmark selected mod for bulk operations
+                        if let Some(id) = selected_mod.map(|m| m.id) {
+                            if !state.marked_mod_ids.remove(&id) {
+                                state.marked_mod_ids.insert(id);
+                            }
+                            if state.selected_mod_index + 1 < mod_count {
+                                state.selected_mod_index += 1;
+                            }
+                            let marked = state.marked_mod_ids.len();
+                            state.set_status_info(format!("{} mod(s) marked", marked));
+                        }
+                    }
+                    KeyCode::Char('V') => {
+                        // Range mark: first press sets the anchor, second press marks the range
+                        let anchor = state.mod_mark_anchor;
+                        match anchor {
+                            None if mod_count > 0 => {
+                                state.mod_mark_anchor = Some(state.selected_mod_index);
+                                state.set_status_info(
+                                    "Range mark started - move and press 'V' again to mark",
+                                );
+                            }
+                            None => {}
+                            Some(anchor) => {
+                                let start = anchor.min(state.selected_mod_index);
+                                let end = anchor.max(state.selected_mod_index);
+                                let ids: Vec<i64> = rows
+                                    .iter()
+                                    .skip(start)
+                                    .take(end + 1 - start)
+                                    .filter_map(ModRow::as_mod)
+                                    .map(|m| m.id)
+                                    .collect();
+                                state.mod_mark_anchor = None;
+                                state.marked_mod_ids.extend(ids);
+                                let marked = state.marked_mod_ids.len();
+                                state.set_status_info(format!("{} mod(s) marked", marked));
+                            }
+                        }
+                    }
+                    KeyCode::Char('e') => {
+                        // Bulk enable/disable marked mods: enable all unless every one is enabled
+                        let marked: Vec<(String, bool)> = state
+                            .marked_mods()
+                            .iter()
+                            .map(|m| (m.name.clone(), m.enabled))
+                            .collect();
+                        if !marked.is_empty() {
+                            let enable = marked.iter().any(|(_, enabled)| !enabled);
+                            let game_id = state.active_game.as_ref().map(|g| g.id.clone());
+                            drop(state);
+
+                            if let Some(game_id) = game_id {
+                                let mut changed = 0;
+                                for (name, _) in &marked {
+                                    let result = if enable {
+                                        app.mods.enable_mod(&game_id, name).await
+                                    } else {
+                                        app.mods.disable_mod(&game_id, name).await
+                                    };
+                                    if result.is_ok() {
+                                        changed += 1;
+                                    }
+                                }
+                                self.refresh_mods(app).await?;
+                                let mut state = app.state.write().await;
+                                state.set_status_success(format!(
+                                    "{} {} marked mod(s)",
+                                    if enable { "Enabled" } else { "Disabled" },
+                                    changed
+                                ));
+                            }
+                            return Ok(());
+                        }
+
                         // Enable/disable selected mod
-                        if let Some(&m) = filtered_mods.get(state.selected_mod_index) {
+                        if let Some(m) = selected_mod {
                             let name = m.name.clone();
                             let enabled = m.enabled;
                             let game_id = state.active_game.as_ref().map(|g| g.id.clone());
@@ -3444,9 +5090,20 @@ impl Tui {
                         return Ok(());
                     }
                     KeyCode::Char('d') | KeyCode::Delete => {
-                        // Delete selected mod
-                        if let Some(&m) = filtered_mods.get(state.selected_mod_index) {
-                            use crate::app::state::{ConfirmAction, ConfirmDialog};
+                        use crate::app::state::{ConfirmAction, ConfirmDialog};
+                        let marked: Vec<String> =
+                            state.marked_mods().iter().map(|m| m.name.clone()).collect();
+                        if !marked.is_empty() {
+                            // Delete all marked mods
+                            state.show_confirm = Some(ConfirmDialog {
+                                title: "Delete Mods".to_string(),
+                                message: format!("Delete {} marked mod(s)?", marked.len()),
+                                confirm_text: "Delete".to_string(),
+                                cancel_text: "Cancel".to_string(),
+                                on_confirm: ConfirmAction::DeleteMods(marked),
+                            });
+                        } else if let Some(m) = selected_mod {
+                            // Delete selected mod
                             state.show_confirm = Some(ConfirmDialog {
                                 title: "Delete Mod".to_string(),
                                 message: format!("Delete '{}'?", m.name),
@@ -3456,6 +5113,38 @@ impl Tui {
                             });
                         }
                     }
+                    KeyCode::Char('P') => {
+                        use crate::app::state::{ConfirmAction, ConfirmDialog};
+                        // Roll back selected mod to its previously retained archive
+                        if let Some(m) = selected_mod {
+                            state.show_confirm = Some(ConfirmDialog {
+                                title: "Rollback Mod".to_string(),
+                                message: format!(
+                                    "Reinstall the previous version of '{}' (currently v{})?",
+                                    m.name, m.version
+                                ),
+                                confirm_text: "Rollback".to_string(),
+                                cancel_text: "Cancel".to_string(),
+                                on_confirm: ConfirmAction::RollbackMod(m.name.clone()),
+                            });
+                        }
+                    }
+                    KeyCode::Char('O') => {
+                        use crate::app::state::{ConfirmAction, ConfirmDialog};
+                        // Re-extract selected mod from its original archive
+                        if let Some(m) = selected_mod {
+                            state.show_confirm = Some(ConfirmDialog {
+                                title: "Reinstall Mod".to_string(),
+                                message: format!(
+                                    "Wipe '{}' and re-extract v{} from its original archive?",
+                                    m.name, m.version
+                                ),
+                                confirm_text: "Reinstall".to_string(),
+                                cancel_text: "Cancel".to_string(),
+                                on_confirm: ConfirmAction::ReinstallMod(m.name.clone()),
+                            });
+                        }
+                    }
                     KeyCode::Char('i') => {
                         // Install mod from file
                         state.input_mode = InputMode::ModInstallPath;
@@ -3464,7 +5153,7 @@ impl Tui {
                     KeyCode::Char('f') => {
                         tracing::info!("'f' key pressed - checking for FOMOD installer");
                         // Re-run FOMOD installer for selected mod
-                        if let Some(&m) = filtered_mods.get(state.selected_mod_index) {
+                        if let Some(m) = selected_mod {
                             tracing::info!("Selected mod: {}, path: {:?}", m.name, m.install_path);
                             let mod_name = m.name.clone();
                             let mod_id = m.id;
@@ -3482,26 +5171,35 @@ impl Tui {
                                             use crate::app::state::{
                                                 FomodWizardState, WizardPhase,
                                             };
-                                            use crate::mods::fomod::wizard::init_wizard_state;
-
-                                            let wizard = init_wizard_state(&installer.config);
+                                            use crate::mods::fomod::wizard::init_wizard_state_with_env;
+
+                                            let game = app.active_game().await;
+                                            let env = match &game {
+                                                Some(game) => app
+                                                    .mods
+                                                    .fomod_environment(&game.id)
+                                                    .unwrap_or_default()
+                                                    .with_game(game),
+                                                None => Default::default(),
+                                            };
 
-                                            // Try to load previous choices
-                                            let profile_id = None; // TODO: Get current profile ID
-                                            if let Ok(previous_plan) =
-                                                app.db.get_fomod_choice(mod_id, profile_id)
-                                            {
-                                                if let Some((config_hash, _plan_json)) =
-                                                    previous_plan
-                                                {
-                                                    // Check if config is still valid
-                                                    use crate::mods::fomod::persistence::FomodChoiceManager;
-                                                    let manager = FomodChoiceManager::new(&app.db);
-                                                    // TODO: Restore previous selections if valid
-                                                    let _ = (config_hash, manager);
-                                                    // Suppress unused warnings for now
-                                                }
-                                            }
+                                            // Start from the choices saved for this profile
+                                            let saved = match &game {
+                                                Some(game) => app
+                                                    .mods
+                                                    .saved_fomod_choice(&game.id, mod_id)
+                                                    .await
+                                                    .ok()
+                                                    .flatten(),
+                                                None => None,
+                                            };
+                                            let restored = saved.and_then(|plan| {
+                                                plan.to_wizard_state(&installer, env.clone())
+                                            });
+                                            let restored_choices = restored.is_some();
+                                            let wizard = restored.unwrap_or_else(|| {
+                                                init_wizard_state_with_env(&installer.config, env)
+                                            });
 
                                             let wizard_state = FomodWizardState {
                                                 installer: installer.clone(),
@@ -3513,6 +5211,7 @@ impl Tui {
                                                 mod_name: mod_name.clone(),
                                                 staging_path: staging_path.clone(),
                                                 preview_files: None,
+                                                preview_scroll: 0,
                                                 phase: WizardPhase::Overview,
                                                 existing_mod_id: Some(mod_id),
                                             };
@@ -3520,10 +5219,17 @@ impl Tui {
                                             let mut state = app.state.write().await;
                                             state.fomod_wizard_state = Some(wizard_state);
                                             state.goto(crate::app::state::Screen::FomodWizard);
-                                            state.set_status(format!(
-                                                "Reconfiguring FOMOD for {}",
-                                                mod_name
-                                            ));
+                                            if restored_choices {
+                                                state.set_status(format!(
+                                                    "Reconfiguring FOMOD for {} (previous choices restored)",
+                                                    mod_name
+                                                ));
+                                            } else {
+                                                state.set_status(format!(
+                                                    "Reconfiguring FOMOD for {}",
+                                                    mod_name
+                                                ));
+                                            }
                                         } else {
                                             let mut state = app.state.write().await;
                                             state.set_status(format!(
@@ -3558,7 +5264,9 @@ impl Tui {
                         let default_dir = config.tui.default_mod_directory.clone();
                         drop(config);
 
-                        if let Some(default_dir) = default_dir {
+                        if state.bulk_install_running {
+                            state.set_status("Bulk install is already running".to_string());
+                        } else if let Some(default_dir) = default_dir {
                             let expanded_path = if default_dir.starts_with("~/") {
                                 std::env::var("HOME")
                                     .map(|h| format!("{}/{}", h, &default_dir[2..]))
@@ -3567,9 +5275,15 @@ impl Tui {
                                 default_dir.clone()
                             };
 
+                            // Claim the bulk install slot before handing off
+                            state.bulk_install_running = true;
+                            state.bulk_install_cancel.store(false, Ordering::Relaxed);
+
                             // Clone app components needed for background task
-                            let state_clone = app.state.clone();
+                            let events = app.events.clone();
                             let mods_clone = app.mods.clone();
+                            let cancel = state.bulk_install_cancel.clone();
+                            let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                             let path_clone = expanded_path.clone();
 
                             drop(state);
@@ -3577,15 +5291,16 @@ impl Tui {
                             // Spawn bulk install in background so UI can continue updating
                             tokio::spawn(async move {
                                 if let Err(e) = Self::run_bulk_install(
-                                    state_clone.clone(),
+                                    events.clone(),
                                     mods_clone,
+                                    cancel,
+                                    game_id,
                                     &path_clone,
                                 )
                                 .await
                                 {
-                                    let mut state = state_clone.write().await;
-                                    state.set_status(format!("Bulk install error: {}", e));
-                                    state.installation_progress = None;
+                                    events.failure("Bulk install error", e);
+                                    events.install_progress(None);
                                 }
                             });
 
@@ -3615,7 +5330,7 @@ impl Tui {
                         drop(state);
 
                         // Clone components AFTER dropping the lock
-                        let state_clone = app.state.clone();
+                        let events = app.events.clone();
                         let mods_clone = app.mods.clone();
                         let game_id_clone = game_id.clone();
 
@@ -3633,12 +5348,13 @@ impl Tui {
                                     // Reload mod list
                                     match mods_clone.list_mods(&game_id_clone).await {
                                         Ok(updated_mods) => {
-                                            let mut st = state_clone.write().await;
-                                            st.installed_mods = updated_mods;
-                                            st.set_status(format!(
-                                                "✓ Rescan: {} added, {} updated, {} unchanged, {} failed",
-                                                stats.added, stats.updated, stats.unchanged, stats.failed
-                                            ));
+                                            events.update(move |st| {
+                                                st.installed_mods = updated_mods;
+                                                st.set_status(format!(
+                                                    "✓ Rescan: {} added, {} updated, {} unchanged, {} failed",
+                                                    stats.added, stats.updated, stats.unchanged, stats.failed
+                                                ));
+                                            });
                                         }
                                         Err(e) => {
                                             tracing::error!("Failed to reload mod list: {}", e);
@@ -3647,8 +5363,7 @@ impl Tui {
                                 }
                                 Err(e) => {
                                     tracing::error!("Rescan error: {}", e);
-                                    let mut st = state_clone.write().await;
-                                    st.set_status(format!("Rescan error: {}", e));
+                                    events.failure("Rescan error", e);
                                 }
                             }
                         });
@@ -3662,7 +5377,7 @@ impl Tui {
                     }
                     KeyCode::Char('b') => {
                         // Browse/search Nexus Mods
-                        if app.nexus.is_some() {
+                        if let Some(nexus) = app.nexus.as_ref() {
                             state.goto(Screen::Browse);
 
                             // Auto-load top mods when entering the browse screen for the first time
@@ -3679,14 +5394,14 @@ impl Tui {
                                 state.set_status("Loading top mods...".to_string());
 
                                 let game_id = state.active_game.as_ref().map(|g| g.id.clone());
-                                let nexus_clone = app.nexus.as_ref().unwrap().clone();
-                                let state_clone = app.state.clone();
+                                let nexus_clone = nexus.clone();
+                                let events = app.events.clone();
                                 let limit = state.browse_limit;
 
                                 drop(state);
 
                                 Self::spawn_browse_search(
-                                    state_clone,
+                                    events,
                                     nexus_clone,
                                     game_id,
                                     None, // No query = top mods
@@ -3705,64 +5420,121 @@ impl Tui {
                     KeyCode::Char('o') => {
                         // Open Load Order screen
                         state.load_order_mods = state.installed_mods.clone();
+                        state.load_order_history.clear();
                         state.load_order_index = state
                             .selected_mod_index
                             .min(state.load_order_mods.len().saturating_sub(1));
                         state.load_order_dirty = false;
                         state.reorder_mode = false;
                         // Load conflicts
-                        if let Some(ref game) = state.active_game {
+                        if let Some(game_id) = state.active_game.as_ref().map(|g| g.id.clone()) {
                             if let Ok(conflicts) =
-                                crate::mods::get_conflicts_grouped(&app.db, &game.id)
+                                crate::mods::get_conflicts_grouped(&app.db, &game_id)
                             {
                                 state.load_order_conflicts = conflicts;
                             }
+                            if let Ok(by_type) =
+                                crate::mods::get_conflicts_by_asset_type(&app.db, &game_id)
+                            {
+                                state.load_order_asset_conflicts = by_type;
+                            }
                         }
                         state.goto(Screen::LoadOrder);
                     }
+                    KeyCode::Char('G') => {
+                        use crate::app::state::{ConfirmAction, ConfirmDialog};
+                        if state.available_updates.is_empty() {
+                            state.set_status_info("No known updates (press U to check first)");
+                        } else {
+                            let count = state.available_updates.len();
+                            state.show_confirm = Some(ConfirmDialog {
+                                title: "Update All Mods".to_string(),
+                                message: format!(
+                                    "Queue the latest MAIN file of {} out-of-date mod(s) and reinstall them in place? Saved FOMOD choices are replayed where possible.",
+                                    count
+                                ),
+                                confirm_text: "Queue".to_string(),
+                                cancel_text: "Cancel".to_string(),
+                                on_confirm: ConfirmAction::UpdateAllMods,
+                            });
+                        }
+                    }
                     KeyCode::Char('U') => {
                         // Check for mod updates
                         if let Some(ref nexus) = app.nexus {
-                            let game_id = state.active_game.as_ref().map(|g| g.id.clone());
+                            let game = state.active_game.clone();
                             state.checking_updates = true;
                             state.set_status("Checking for mod updates...".to_string());
                             drop(state);
 
-                            if let Some(game_id) = game_id {
-                                let state_clone = app.state.clone();
+                            if let Some(game) = game {
+                                let events = app.events.clone();
                                 let mods_clone = app.mods.clone();
                                 let nexus_clone = nexus.clone();
 
                                 // Spawn update check in background
                                 tokio::spawn(async move {
-                                    match mods_clone.check_for_updates(&game_id, &nexus_clone).await
+                                    match mods_clone.check_for_updates(&game.id, &nexus_clone).await
                                     {
                                         Ok(updates) => {
-                                            let mut state = state_clone.write().await;
-                                            state.checking_updates = false;
-
-                                            // Build updates map
-                                            let mut updates_map = std::collections::HashMap::new();
-                                            for update in &updates {
-                                                updates_map.insert(update.mod_id, update.clone());
-                                            }
-                                            state.available_updates = updates_map;
-
-                                            if updates.is_empty() {
-                                                state.set_status(
-                                                    "✓ All mods are up to date!".to_string(),
-                                                );
-                                            } else {
-                                                state.set_status(format!(
-                                                    "✨ {} mod update(s) available!",
-                                                    updates.len()
-                                                ));
-                                            }
+                                            // Flag installed files Nexus no longer serves
+                                            let unavailable = match mods_clone
+                                                .check_file_availability(&game, &nexus_clone)
+                                                .await
+                                            {
+                                                Ok(_) => mods_clone
+                                                    .unavailable_files(&game.id)
+                                                    .unwrap_or_default(),
+                                                Err(e) => {
+                                                    tracing::warn!(
+                                                        "File availability check failed: {}",
+                                                        e
+                                                    );
+                                                    Vec::new()
+                                                }
+                                            };
+                                            events.update(move |state| {
+                                                state.checking_updates = false;
+
+                                                // Build updates map
+                                                let mut updates_map =
+                                                    std::collections::HashMap::new();
+                                                for update in &updates {
+                                                    updates_map
+                                                        .insert(update.mod_id, update.clone());
+                                                }
+                                                state.available_updates = updates_map;
+                                                let unavailable_count = unavailable.len();
+                                                state.unavailable_files = unavailable
+                                                    .into_iter()
+                                                    .map(|f| (f.mod_id, f))
+                                                    .collect();
+
+                                                let mut status = if updates.is_empty() {
+                                                    "✓ All mods are up to date!".to_string()
+                                                } else {
+                                                    format!(
+                                                        "✨ {} mod update(s) available!",
+                                                        updates.len()
+                                                    )
+                                                };
+                                                if unavailable_count > 0 {
+                                                    status.push_str(&format!(
+                                                        " ⚠ {} installed file(s) no longer on Nexus",
+                                                        unavailable_count
+                                                    ));
+                                                }
+                                                state.set_status(status);
+                                            });
                                         }
                                         Err(e) => {
-                                            let mut state = state_clone.write().await;
-                                            state.checking_updates = false;
-                                            state.set_status(format!("Update check failed: {}", e));
+                                            events.update(move |state| {
+                                                state.checking_updates = false;
+                                                state.set_status(format!(
+                                                    "Update check failed: {}",
+                                                    e
+                                                ));
+                                            });
                                         }
                                     }
                                 });
@@ -3784,14 +5556,44 @@ impl Tui {
                         });
                     }
                     KeyCode::Enter => {
-                        if !state.installed_mods.is_empty() {
+                        if let Some((id, collapsed)) = selected_group {
+                            // Headers fold and unfold their group
+                            if collapsed {
+                                state.collapsed_categories.remove(&id);
+                            } else {
+                                state.collapsed_categories.insert(id);
+                            }
+                        } else if !state.installed_mods.is_empty() {
                             state.goto(Screen::ModDetails);
+                            state.mod_details_scroll = 0;
+                            Self::spawn_mod_page_load(app, &mut state, false);
                         }
                     }
                     KeyCode::Char('+') | KeyCode::Char('=') => {
                         // Increase priority (move up in load order)
                         // Higher priority = loads later = overwrites
-                        if let Some(&m) = filtered_mods.get(state.selected_mod_index) {
+                        let marked: Vec<String> =
+                            state.marked_mods().iter().map(|m| m.name.clone()).collect();
+                        if !marked.is_empty() {
+                            let game_id = state.active_game.as_ref().map(|g| g.id.clone());
+                            drop(state);
+                            if let Some(game_id) = game_id {
+                                let mut changed = 0;
+                                for name in &marked {
+                                    if app.mods.change_priority(&game_id, name, 1).await.is_ok() {
+                                        changed += 1;
+                                    }
+                                }
+                                self.refresh_mods(app).await?;
+                                let mut state = app.state.write().await;
+                                state.set_status(format!(
+                                    "Increased priority for {} marked mod(s)",
+                                    changed
+                                ));
+                            }
+                            return Ok(());
+                        }
+                        if let Some(m) = selected_mod {
                             let name = m.name.clone();
                             let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                             drop(state);
@@ -3819,7 +5621,28 @@ impl Tui {
                     }
                     KeyCode::Char('-') => {
                         // Decrease priority
-                        if let Some(&m) = filtered_mods.get(state.selected_mod_index) {
+                        let marked: Vec<String> =
+                            state.marked_mods().iter().map(|m| m.name.clone()).collect();
+                        if !marked.is_empty() {
+                            let game_id = state.active_game.as_ref().map(|g| g.id.clone());
+                            drop(state);
+                            if let Some(game_id) = game_id {
+                                let mut changed = 0;
+                                for name in &marked {
+                                    if app.mods.change_priority(&game_id, name, -1).await.is_ok() {
+                                        changed += 1;
+                                    }
+                                }
+                                self.refresh_mods(app).await?;
+                                let mut state = app.state.write().await;
+                                state.set_status(format!(
+                                    "Decreased priority for {} marked mod(s)",
+                                    changed
+                                ));
+                            }
+                            return Ok(());
+                        }
+                        if let Some(m) = selected_mod {
                             let name = m.name.clone();
                             let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                             drop(state);
@@ -3845,13 +5668,45 @@ impl Tui {
                             return Ok(());
                         }
                     }
+                    KeyCode::Char('>') | KeyCode::Char('<') => {
+                        // Keep the selected mod selected in the new order
+                        let selected = selected_mod.map(|m| m.id);
+                        if key == KeyCode::Char('>') {
+                            state.mod_sort = state.mod_sort.next();
+                        } else {
+                            state.mod_sort_reverse = !state.mod_sort_reverse;
+                        }
+                        if let Some(id) = selected {
+                            state.selected_mod_index = state.reveal_mod(id).unwrap_or(0);
+                        }
+                        let direction = if state.mod_sort_reverse {
+                            "descending"
+                        } else {
+                            "ascending"
+                        };
+                        let label = state.mod_sort.label();
+                        state.set_status_info(format!("Sorted by {} ({})", label, direction));
+                    }
                     KeyCode::Char('/') => {
                         // Search mods by name
                         state.input_mode = InputMode::ModSearch;
                         state.input_buffer = state.mod_search_query.clone();
                     }
-                    KeyCode::Char('s') => {
-                        // Auto-sort by category
+                    KeyCode::Char('y') => Self::yank_mod_url(&mut state),
+                    KeyCode::Char('Y') => {
+                        if let Some(path) =
+                            selected_mod.map(|m| m.install_path.display().to_string())
+                        {
+                            Self::yank(&mut state, "staging path", &path);
+                        }
+                    }
+                    KeyCode::Char('m') => {
+                        // Saved filter dropdown, opened on the active filter
+                        state.input_mode = InputMode::ModFilterPicker;
+                        state.mod_filter_index = state.active_mod_filter.map_or(0, |i| i + 1);
+                    }
+                    KeyCode::Char('s') => {
+                        // Auto-sort by category
                         if let Some(game) = state.active_game.as_ref() {
                             let game_id = game.id.clone();
                             drop(state);
@@ -3905,59 +5760,30 @@ impl Tui {
                         }
                     }
                     KeyCode::Left => {
-                        // Navigate to previous category
-                        if state.category_filter.is_none() {
-                            // Currently showing all, can't go left
-                        } else {
-                            // Find current category index
-                            if let Some(current_id) = state.category_filter {
-                                if let Some(idx) = state
-                                    .categories
-                                    .iter()
-                                    .position(|c| c.id == Some(current_id))
-                                {
-                                    if idx == 0 {
-                                        // Go to "All"
-                                        state.category_filter = None;
-                                    } else {
-                                        // Go to previous category
-                                        state.category_filter =
-                                            state.categories.get(idx - 1).and_then(|c| c.id);
-                                    }
-                                    state.selected_mod_index = 0; // Reset selection
-                                }
+                        // Collapse the group under the cursor onto its header
+                        let header = rows
+                            .iter()
+                            .take(state.selected_mod_index + 1)
+                            .rposition(|r| r.as_group().is_some());
+                        if let Some(header) = header {
+                            if let Some(id) = rows[header].as_group().map(|g| g.category_id) {
+                                state.collapsed_categories.insert(id);
+                                state.selected_mod_index = header;
                             }
                         }
                     }
                     KeyCode::Right => {
-                        // Navigate to next category
-                        if state.category_filter.is_none() {
-                            // Currently showing all, go to first category
-                            state.category_filter = state.categories.first().and_then(|c| c.id);
-                            state.selected_mod_index = 0; // Reset selection
-                        } else {
-                            // Find current category index
-                            if let Some(current_id) = state.category_filter {
-                                if let Some(idx) = state
-                                    .categories
-                                    .iter()
-                                    .position(|c| c.id == Some(current_id))
-                                {
-                                    // Go to next category if not at end
-                                    if idx < state.categories.len() - 1 {
-                                        state.category_filter =
-                                            state.categories.get(idx + 1).and_then(|c| c.id);
-                                        state.selected_mod_index = 0; // Reset selection
-                                    }
-                                }
-                            }
+                        if let Some((id, true)) = selected_group {
+                            state.collapsed_categories.remove(&id);
                         }
                     }
                     KeyCode::Char('c') => {
-                        // Assign category to selected mod
-                        if let Some(&m) = filtered_mods.get(state.selected_mod_index) {
+                        // Assign category to selected mod (or to all marked mods)
+                        if let Some(m) = selected_mod {
                             let mod_id = m.id;
                             let categories = state.categories.clone();
+                            let marked_ids: Vec<i64> =
+                                state.marked_mods().iter().map(|m| m.id).collect();
                             drop(state);
 
                             // Simple category picker - cycle through categories
@@ -3989,7 +5815,14 @@ impl Tui {
                                     categories.first().and_then(|c| c.id)
                                 };
 
-                                app.db.update_mod_category(mod_id, next_category_id)?;
+                                let target_ids = if marked_ids.is_empty() {
+                                    vec![mod_id]
+                                } else {
+                                    marked_ids
+                                };
+                                for id in &target_ids {
+                                    app.db.update_mod_category(*id, next_category_id)?;
+                                }
                                 self.refresh_mods(app).await?;
 
                                 let mut state = app.state.write().await;
@@ -4002,7 +5835,15 @@ impl Tui {
                                 } else {
                                     "None"
                                 };
-                                state.set_status(format!("Assigned category: {}", cat_name));
+                                if target_ids.len() > 1 {
+                                    state.set_status(format!(
+                                        "Assigned category: {} to {} marked mod(s)",
+                                        cat_name,
+                                        target_ids.len()
+                                    ));
+                                } else {
+                                    state.set_status(format!("Assigned category: {}", cat_name));
+                                }
                             }
                             return Ok(());
                         }
@@ -4125,18 +5966,21 @@ impl Tui {
                             return Ok(());
                         }
                     }
+                    KeyCode::Char('w') => {
+                        // Dismiss the "since you last played" summary
+                        if state.since_last_played.take().is_some() {
+                            state.set_status_info("Dismissed changes since last played");
+                        }
+                    }
                     KeyCode::Char('x') => {
                         // Check requirements for selected mod
                         if let Some(ref nexus) = app.nexus {
-                            if let Some(&m) = filtered_mods.get(state.selected_mod_index) {
+                            if let Some(m) = selected_mod {
                                 if let Some(mod_id) = m.nexus_mod_id {
                                     let mod_name = m.name.clone();
+                                    let game = state.active_game.clone();
                                     let game_info = state.active_game.as_ref().map(|g| {
-                                        (
-                                            g.id.clone(),
-                                            g.nexus_game_id.clone(),
-                                            g.game_type.nexus_numeric_id(),
-                                        )
+                                        (g.id.clone(), g.nexus_game_id.clone(), g.nexus_numeric_id)
                                     });
                                     state.set_status(format!(
                                         "Checking requirements for {}...",
@@ -4146,7 +5990,7 @@ impl Tui {
 
                                     if let Some((game_id, game_domain, game_id_numeric)) = game_info
                                     {
-                                        let state_clone = app.state.clone();
+                                        let events = app.events.clone();
                                         let mods_clone = app.mods.clone();
                                         let nexus_clone = nexus.clone();
 
@@ -4161,27 +6005,44 @@ impl Tui {
                                                 .await
                                             {
                                                 Ok((missing, dlcs, installed_count)) => {
-                                                    let mut state = state_clone.write().await;
-
-                                                    use crate::app::state::RequirementsDialog;
-                                                    state.show_requirements =
-                                                        Some(RequirementsDialog {
-                                                            title: format!(
-                                                                "Requirements for {}",
-                                                                mod_name
-                                                            ),
-                                                            mod_name: mod_name.clone(),
-                                                            missing_mods: missing,
-                                                            dlc_requirements: dlcs,
-                                                            installed_count,
-                                                            selected_index: 0,
-                                                            game_domain,
-                                                            game_id_numeric,
-                                                        });
+                                                    let installed = game.as_ref().and_then(|g| {
+                                                        Some((
+                                                            g.game_type,
+                                                            crate::games::installed_dlc(g)?,
+                                                        ))
+                                                    });
+                                                    let dlc_installed: Vec<Option<bool>> = dlcs
+                                                        .iter()
+                                                        .map(|req| {
+                                                            let (game_type, installed) =
+                                                                installed.as_ref()?;
+                                                            let dlc = crate::games::dlc_by_name(
+                                                                *game_type, &req.name,
+                                                            )?;
+                                                            Some(installed.contains(&dlc))
+                                                        })
+                                                        .collect();
+                                                    events.update(move |state| {
+                                                        use crate::app::state::RequirementsDialog;
+                                                        state.show_requirements =
+                                                            Some(RequirementsDialog {
+                                                                title: format!(
+                                                                    "Requirements for {}",
+                                                                    mod_name
+                                                                ),
+                                                                mod_name: mod_name.clone(),
+                                                                missing_mods: missing,
+                                                                dlc_requirements: dlcs,
+                                                                dlc_installed,
+                                                                installed_count,
+                                                                selected_index: 0,
+                                                                game_domain,
+                                                                game_id_numeric,
+                                                            });
+                                                    });
                                                 }
                                                 Err(e) => {
-                                                    let mut state = state_clone.write().await;
-                                                    state.set_status(format!(
+                                                    events.status(format!(
                                                         "Failed to check requirements: {}",
                                                         e
                                                     ));
@@ -4209,7 +6070,7 @@ impl Tui {
                             drop(config);
                             drop(state);
 
-                            let state_clone = app.state.clone();
+                            let events = app.events.clone();
                             let mods_clone = app.mods.clone();
 
                             tokio::spawn(async move {
@@ -4222,31 +6083,29 @@ impl Tui {
                                         if let Ok(updated_mods) =
                                             mods_clone.list_mods(&game_id).await
                                         {
-                                            let mut state = state_clone.write().await;
-                                            state.installed_mods = updated_mods;
-                                            if count > 0 {
-                                                state.set_status(format!("✓ Updated Nexus IDs for {} mod(s) and reloaded list", count));
-                                            } else {
-                                                state.set_status("No mods needed updating (all have Nexus IDs or couldn't parse)".to_string());
-                                            }
+                                            events.update(move |state| {
+                                                state.installed_mods = updated_mods;
+                                                if count > 0 {
+                                                    state.set_status(format!("✓ Updated Nexus IDs for {} mod(s) and reloaded list", count));
+                                                } else {
+                                                    state.set_status("No mods needed updating (all have Nexus IDs or couldn't parse)".to_string());
+                                                }
+                                            });
                                         } else {
-                                            let mut state = state_clone.write().await;
-                                            if count > 0 {
-                                                state.set_status(format!(
-                                                    "✓ Updated Nexus IDs for {} mod(s)",
-                                                    count
-                                                ));
-                                            } else {
-                                                state.set_status("No mods needed updating (all have Nexus IDs or couldn't parse)".to_string());
-                                            }
+                                            events.update(move |state| {
+                                                if count > 0 {
+                                                    state.set_status(format!(
+                                                        "✓ Updated Nexus IDs for {} mod(s)",
+                                                        count
+                                                    ));
+                                                } else {
+                                                    state.set_status("No mods needed updating (all have Nexus IDs or couldn't parse)".to_string());
+                                                }
+                                            });
                                         }
                                     }
                                     Err(e) => {
-                                        let mut state = state_clone.write().await;
-                                        state.set_status(format!(
-                                            "Failed to update Nexus IDs: {}",
-                                            e
-                                        ));
+                                        events.failure("Failed to update Nexus IDs", e);
                                     }
                                 }
                             });
@@ -4256,6 +6115,30 @@ impl Tui {
                 }
             }
 
+            Screen::ModDetails => match key {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    state.mod_details_scroll = state.mod_details_scroll.saturating_add(1);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    state.mod_details_scroll = state.mod_details_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => {
+                    state.mod_details_scroll = state.mod_details_scroll.saturating_add(10);
+                }
+                KeyCode::PageUp => {
+                    state.mod_details_scroll = state.mod_details_scroll.saturating_sub(10);
+                }
+                KeyCode::Char('r') => {
+                    if app.nexus.is_some() {
+                        Self::spawn_mod_page_load(app, &mut state, true);
+                    } else {
+                        state.set_status("Nexus API key not configured".to_string());
+                    }
+                }
+                KeyCode::Char('y') => Self::yank_mod_url(&mut state),
+                _ => {}
+            },
+
             Screen::Plugins => {
                 // Filter plugins by search query
                 let search_lower = state.plugin_search_query.to_lowercase();
@@ -4296,11 +6179,20 @@ impl Tui {
                         state.input_mode = InputMode::PluginSearch;
                         state.input_buffer = state.plugin_search_query.clone();
                     }
+                    KeyCode::Char('y') => {
+                        if let Some(filename) = filtered_plugins
+                            .get(state.selected_plugin_index)
+                            .map(|p| p.filename.clone())
+                        {
+                            Self::yank(&mut state, "plugin name", &filename);
+                        }
+                    }
                     KeyCode::Up | KeyCode::Char('k') => {
                         if state.plugin_reorder_mode {
                             // Move plugin up in load order
                             let idx = state.selected_plugin_index;
                             if idx > 0 {
+                                Self::record_plugin_edit(&mut state, "move");
                                 state.plugins.swap(idx, idx - 1);
                                 state.selected_plugin_index = idx - 1;
                                 state.plugin_dirty = true;
@@ -4318,6 +6210,7 @@ impl Tui {
                             // Move plugin down in load order
                             let idx = state.selected_plugin_index;
                             if idx + 1 < plugin_count {
+                                Self::record_plugin_edit(&mut state, "move");
                                 state.plugins.swap(idx, idx + 1);
                                 state.selected_plugin_index = idx + 1;
                                 state.plugin_dirty = true;
@@ -4334,6 +6227,9 @@ impl Tui {
                     KeyCode::Char('K') => {
                         // Move up 5 positions or jump to top
                         if state.plugin_reorder_mode {
+                            if state.selected_plugin_index > 0 {
+                                Self::record_plugin_edit(&mut state, "move");
+                            }
                             for _ in 0..5 {
                                 let idx = state.selected_plugin_index;
                                 if idx > 0 {
@@ -4355,6 +6251,9 @@ impl Tui {
                     KeyCode::Char('J') => {
                         // Move down 5 positions or jump to bottom
                         if state.plugin_reorder_mode {
+                            if state.selected_plugin_index + 1 < plugin_count {
+                                Self::record_plugin_edit(&mut state, "move");
+                            }
                             for _ in 0..5 {
                                 let idx = state.selected_plugin_index;
                                 if idx + 1 < plugin_count {
@@ -4377,6 +6276,7 @@ impl Tui {
                     KeyCode::Char('t') => {
                         // Move to top
                         if state.plugin_reorder_mode && !state.plugins.is_empty() {
+                            Self::record_plugin_edit(&mut state, "move to top");
                             let idx = state.selected_plugin_index;
                             let p = state.plugins.remove(idx);
                             state.plugins.insert(0, p);
@@ -4392,6 +6292,7 @@ impl Tui {
                     KeyCode::Char('b') => {
                         // Move to bottom
                         if state.plugin_reorder_mode && !state.plugins.is_empty() {
+                            Self::record_plugin_edit(&mut state, "move to bottom");
                             let idx = state.selected_plugin_index;
                             let p = state.plugins.remove(idx);
                             state.plugins.push(p);
@@ -4429,6 +6330,9 @@ impl Tui {
                     KeyCode::Char(' ') | KeyCode::Char('e') => {
                         // Toggle plugin enabled state
                         let index = state.selected_plugin_index;
+                        if index < state.plugins.len() {
+                            Self::record_plugin_edit(&mut state, "toggle");
+                        }
                         if let Some(p) = state.plugins.get_mut(index) {
                             let was_enabled = p.enabled;
                             p.enabled = !p.enabled;
@@ -4453,6 +6357,7 @@ impl Tui {
                     }
                     KeyCode::Char('a') => {
                         // Enable all plugins
+                        Self::record_plugin_edit(&mut state, "enable all");
                         let count = state.plugins.len();
                         for plugin in state.plugins.iter_mut() {
                             plugin.enabled = true;
@@ -4464,6 +6369,7 @@ impl Tui {
                     }
                     KeyCode::Char('n') => {
                         // Disable all plugins
+                        Self::record_plugin_edit(&mut state, "disable all");
                         let count = state.plugins.len();
                         for plugin in state.plugins.iter_mut() {
                             plugin.enabled = false;
@@ -4476,6 +6382,7 @@ impl Tui {
                     KeyCode::Char('s') => {
                         // Save plugin load order
                         if let Some(game) = &state.active_game {
+                            let game_id = game.id.clone();
                             let enabled: Vec<String> = state
                                 .plugins
                                 .iter()
@@ -4517,6 +6424,18 @@ impl Tui {
                                     enabled.len(),
                                     skse_note
                                 ));
+                                let profile = app.config.read().await.active_profile.clone();
+                                crate::activity::record(
+                                    &app.db,
+                                    &game_id,
+                                    profile.as_deref(),
+                                    crate::activity::Activity::SavePlugins,
+                                    None,
+                                    serde_json::json!({
+                                        "enabled": enabled.len(),
+                                        "total": all.len(),
+                                    }),
+                                );
                             }
                         }
                     }
@@ -4537,6 +6456,7 @@ impl Tui {
                                     );
 
                                     let mut state = app.state.write().await;
+                                    Self::record_plugin_edit(&mut state, "auto-sort");
                                     state.plugins = plugins_to_sort;
 
                                     if issues.is_empty() {
@@ -4559,6 +6479,11 @@ impl Tui {
                             return Ok(());
                         }
                     }
+                    KeyCode::Char('C') => {
+                        drop(state);
+                        self.offer_plugin_cleaning(app).await?;
+                        return Ok(());
+                    }
                     KeyCode::Char('L') => {
                         // Run LOOT CLI (requires LOOT installation)
                         if let Some(game) = &state.active_game {
@@ -4584,12 +6509,22 @@ impl Tui {
 
                             match plugins::loot::sort_plugins(&game_clone) {
                                 Ok(_) => {
+                                    let profile = app.config.read().await.active_profile.clone();
+                                    crate::activity::record(
+                                        &app.db,
+                                        &game_clone.id,
+                                        profile.as_deref(),
+                                        crate::activity::Activity::SortPlugins,
+                                        None,
+                                        serde_json::json!({ "by": "loot" }),
+                                    );
                                     // Reload plugins to reflect LOOT's changes
                                     if let Ok(plugins_list) = plugins::get_plugins(&game_clone) {
                                         let mut state = app.state.write().await;
+                                        Self::record_plugin_edit(&mut state, "LOOT sort");
                                         state.plugins = plugins_list;
                                         state.set_status(
-                                            "LOOT CLI sorting complete! Plugins reloaded."
+                                            "LOOT CLI sorting complete! Plugins reloaded. Press 'u' to undo."
                                                 .to_string(),
                                         );
                                     }
@@ -4602,6 +6537,12 @@ impl Tui {
                             return Ok(());
                         }
                     }
+                    KeyCode::Char('u') => {
+                        Self::step_plugin_history(&mut state, false);
+                    }
+                    KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                        Self::step_plugin_history(&mut state, true);
+                    }
                     KeyCode::Char('D') => {
                         use crate::app::state::{ConfirmAction, ConfirmDialog};
                         state.show_confirm = Some(ConfirmDialog {
@@ -4633,16 +6574,27 @@ impl Tui {
                         // Switch to selected profile
                         if let Some(p) = state.profiles.get(state.selected_profile_index) {
                             let name = p.name.clone();
-                            let game_id = state.active_game.as_ref().map(|g| g.id.clone());
+                            let game = state.active_game.clone();
                             drop(state);
 
-                            if let Some(game_id) = game_id {
-                                if let Err(e) = app.profiles.switch_profile(&game_id, &name).await {
+                            if let Some(game) = game {
+                                if let Err(e) = app.profiles.switch_profile(&game.id, &name).await {
                                     let mut state = app.state.write().await;
                                     state.set_status(format!("Error: {}", e));
                                 } else {
+                                    let issues =
+                                        crate::saves::check_save_plugins(&game).unwrap_or_default();
                                     let mut state = app.state.write().await;
-                                    state.set_status(format!("Switched to profile: {}", name));
+                                    match issues.first() {
+                                        Some(first) => state.set_status_info(format!(
+                                            "Switched to profile: {}; {} save(s) would break. {}",
+                                            name,
+                                            issues.len(),
+                                            first
+                                        )),
+                                        None => state
+                                            .set_status(format!("Switched to profile: {}", name)),
+                                    }
                                 }
                             }
                             return Ok(());
@@ -4653,6 +6605,21 @@ impl Tui {
                         state.input_mode = InputMode::ProfileNameInput;
                         state.input_buffer.clear();
                     }
+                    KeyCode::Char('s') => {
+                        state.goto(Screen::Saves);
+                        drop(state);
+                        screens::saves::refresh(app).await;
+                        return Ok(());
+                    }
+                    KeyCode::Char('c') => {
+                        // Compare INI settings
+                        if let Some(p) = state.profiles.get(state.selected_profile_index) {
+                            let name = p.name.clone();
+                            drop(state);
+                            screens::ini_diff::open(app, name).await;
+                            return Ok(());
+                        }
+                    }
                     KeyCode::Char('d') | KeyCode::Delete => {
                         // Delete profile
                         if let Some(p) = state.profiles.get(state.selected_profile_index) {
@@ -4666,6 +6633,77 @@ impl Tui {
                             });
                         }
                     }
+                    KeyCode::Char('i') => {
+                        if let Some(p) = state.profiles.get(state.selected_profile_index) {
+                            use crate::app::state::{ConfirmAction, ConfirmDialog};
+                            let message = if p.own_ini_files {
+                                format!(
+                                    "Replace the INI files stored in '{}' with the game's current ones?",
+                                    p.name
+                                )
+                            } else {
+                                format!(
+                                    "Store the game's current INI files in '{}'? They are put in place whenever it's activated.",
+                                    p.name
+                                )
+                            };
+                            state.show_confirm = Some(ConfirmDialog {
+                                title: "Profile INI Files".to_string(),
+                                message,
+                                confirm_text: "Store".to_string(),
+                                cancel_text: "Cancel".to_string(),
+                                on_confirm: ConfirmAction::SaveProfileIni(p.name.clone()),
+                            });
+                        }
+                    }
+                    KeyCode::Char('I') => {
+                        if let Some(p) = state.profiles.get(state.selected_profile_index) {
+                            use crate::app::state::{ConfirmAction, ConfirmDialog};
+                            let (name, own) = (p.name.clone(), p.own_ini_files);
+                            if !own {
+                                state.set_status_info(format!(
+                                    "'{}' has no INI files of its own",
+                                    name
+                                ));
+                            } else {
+                                state.show_confirm = Some(ConfirmDialog {
+                                    title: "Profile INI Files".to_string(),
+                                    message: format!(
+                                        "Drop the INI files stored in '{}'? The game's files are left as they are.",
+                                        name
+                                    ),
+                                    confirm_text: "Drop".to_string(),
+                                    cancel_text: "Cancel".to_string(),
+                                    on_confirm: ConfirmAction::ClearProfileIni(name),
+                                });
+                            }
+                        }
+                    }
+                    KeyCode::Char('o') => {
+                        // Toggle the profile's own save folder
+                        if let Some(p) = state.profiles.get(state.selected_profile_index) {
+                            use crate::app::state::{ConfirmAction, ConfirmDialog};
+                            let (name, own) = (p.name.clone(), !p.own_saves);
+                            let message = if own {
+                                format!(
+                                    "Give '{}' a save folder of its own? Saves made under other profiles stay where they are.",
+                                    name
+                                )
+                            } else {
+                                format!(
+                                    "Make '{}' use the game's shared save folder? Its own saves are kept.",
+                                    name
+                                )
+                            };
+                            state.show_confirm = Some(ConfirmDialog {
+                                title: "Profile Saves".to_string(),
+                                message,
+                                confirm_text: if own { "Separate" } else { "Share" }.to_string(),
+                                cancel_text: "Cancel".to_string(),
+                                on_confirm: ConfirmAction::SetProfileSaves(name, own),
+                            });
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -4703,19 +6741,82 @@ impl Tui {
                         }
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
-                        if state.selected_setting_index < 16 {
+                        let last =
+                            SETTINGS_FIXED_ROWS - 1 + app.config.read().await.custom_tools.len();
+                        if state.selected_setting_index < last {
                             state.selected_setting_index += 1;
                         }
                     }
                     KeyCode::Char('l') => {
-                        if let Some(tool) =
-                            Self::settings_tool_for_index(state.selected_setting_index)
-                        {
+                        let tool = Self::settings_launch_tool(
+                            &*app.config.read().await,
+                            state.selected_setting_index,
+                        );
+                        if let Some(tool) = tool {
+                            drop(state);
+                            self.launch_tool_after_deploy_check(app, tool).await?;
+                            return Ok(());
+                        }
+                    }
+                    KeyCode::Char('L') => {
+                        // Launch without checking the deployment
+                        let tool = Self::settings_launch_tool(
+                            &*app.config.read().await,
+                            state.selected_setting_index,
+                        );
+                        if let Some(tool) = tool {
                             drop(state);
                             self.launch_external_tool_from_tui(app, tool).await?;
                             return Ok(());
                         }
                     }
+                    KeyCode::Char('o') => {
+                        state.goto(Screen::ToolOutput);
+                    }
+                    KeyCode::Char('i') => {
+                        state.goto(Screen::IniEditor);
+                        drop(state);
+                        screens::ini_editor::refresh(app).await;
+                        return Ok(());
+                    }
+                    KeyCode::Char(c @ ('a' | 'w' | 'e')) => {
+                        let Some(tool) =
+                            Self::settings_tool_for_index(state.selected_setting_index)
+                        else {
+                            state.set_status_info("Select a tool path row to edit its options");
+                            return Ok(());
+                        };
+                        let options = app
+                            .config
+                            .read()
+                            .await
+                            .external_tool_options(tool)
+                            .cloned()
+                            .unwrap_or_default();
+                        let (field, value) = match c {
+                            'a' => (ToolOptionField::Args, config::join_args(&options.args)),
+                            'w' => (
+                                ToolOptionField::WorkingDir,
+                                options.working_dir.unwrap_or_default(),
+                            ),
+                            _ => (ToolOptionField::Env, config::env_line(&options.env)),
+                        };
+                        state.tool_option_field = field;
+                        state.input_buffer = value;
+                        state.input_mode = InputMode::ExternalToolOptionsInput;
+                    }
+                    KeyCode::Enter if state.selected_setting_index >= SETTINGS_FIXED_ROWS => {
+                        // Custom tools are edited in config.toml; Enter launches them
+                        let tool = Self::settings_launch_tool(
+                            &*app.config.read().await,
+                            state.selected_setting_index,
+                        );
+                        if let Some(tool) = tool {
+                            drop(state);
+                            self.launch_tool_after_deploy_check(app, tool).await?;
+                            return Ok(());
+                        }
+                    }
                     KeyCode::Enter => {
                         // Handle setting selection
                         match state.selected_setting_index {
@@ -4798,7 +6899,7 @@ impl Tui {
                                 let config = app.config.read().await;
                                 state.input_buffer = config.external_tools.proton_command.clone();
                             }
-                            9 | 10 | 11 | 12 | 13 | 14 | 15 => {
+                            9..=16 => {
                                 // Tool executable paths
                                 let Some(tool) =
                                     Self::settings_tool_for_index(state.selected_setting_index)
@@ -4865,7 +6966,34 @@ impl Tui {
                                     ));
                                 }
                             }
-                            16 => {
+                            17 => {
+                                // Session-only, so not saved to config
+                                Self::toggle_debug_logging(&mut state);
+                            }
+                            18 => {
+                                // Toggle watching the downloads folder
+                                let mut config = app.config.write().await;
+                                config.install.watch_downloads = !config.install.watch_downloads;
+                                if let Err(e) = config.save().await {
+                                    state.set_status(format!("Error saving config: {}", e));
+                                    return Ok(());
+                                }
+                                let dir = config
+                                    .install
+                                    .watch_downloads
+                                    .then(|| config.downloads_dir());
+                                state.set_status(match &dir {
+                                    Some(Ok(dir)) => {
+                                        format!("Watching {} for new downloads", dir.display())
+                                    }
+                                    Some(Err(e)) => {
+                                        format!("Can't watch the downloads folder: {}", e)
+                                    }
+                                    None => "Stopped watching the downloads folder".to_string(),
+                                });
+                                self.watch_downloads(app.events.clone(), dir.and_then(Result::ok));
+                            }
+                            19 => {
                                 // Game Selection
                                 state.goto(Screen::GameSelect);
                             }
@@ -4897,9 +7025,9 @@ impl Tui {
                         state.set_status(format!("Sort: {:?}", sort_mode));
 
                         // Re-search with new sort if we have results (query or default content)
-                        if (!state.browse_query.is_empty() || state.browse_showing_default)
-                            && app.nexus.is_some()
-                        {
+                        if let Some(nexus) = app.nexus.as_ref().filter(|_| {
+                            !state.browse_query.is_empty() || state.browse_showing_default
+                        }) {
                             let query = if state.browse_showing_default {
                                 None
                             } else {
@@ -4907,8 +7035,8 @@ impl Tui {
                             };
                             let sort = state.browse_sort;
                             let game_id = state.active_game.as_ref().map(|g| g.id.clone());
-                            let nexus_clone = app.nexus.as_ref().unwrap().clone();
-                            let state_clone = app.state.clone();
+                            let nexus_clone = nexus.clone();
+                            let events = app.events.clone();
 
                             state.browsing = true;
                             state.browse_offset = 0;
@@ -4920,7 +7048,7 @@ impl Tui {
                             drop(state);
 
                             Self::spawn_browse_search(
-                                state_clone,
+                                events,
                                 nexus_clone,
                                 game_id,
                                 query,
@@ -4964,14 +7092,14 @@ impl Tui {
                         let sort = state.browse_sort;
                         let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                         let nexus_clone = app.nexus.as_ref().unwrap().clone();
-                        let state_clone = app.state.clone();
+                        let events = app.events.clone();
 
                         state.browsing = true;
                         state.set_status(format!("Loading page {}...", (next_offset / limit) + 1));
                         drop(state);
 
                         Self::spawn_browse_search(
-                            state_clone,
+                            events,
                             nexus_clone,
                             game_id,
                             query,
@@ -5012,14 +7140,14 @@ impl Tui {
                         let sort = state.browse_sort;
                         let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                         let nexus_clone = app.nexus.as_ref().unwrap().clone();
-                        let state_clone = app.state.clone();
+                        let events = app.events.clone();
 
                         state.browsing = true;
                         state.set_status(format!("Loading page {}...", (prev_offset / limit) + 1));
                         drop(state);
 
                         Self::spawn_browse_search(
-                            state_clone,
+                            events,
                             nexus_clone,
                             game_id,
                             query,
@@ -5029,6 +7157,63 @@ impl Tui {
                         );
                         return Ok(());
                     }
+                    KeyCode::Char('r') => {
+                        // Surprise me: random well-endorsed mod from preferred categories
+                        if state.browsing {
+                            return Ok(());
+                        }
+                        let (Some(nexus), Some(game)) =
+                            (app.nexus.clone(), state.active_game.clone())
+                        else {
+                            state.set_status("Browse requires Nexus API key".to_string());
+                            return Ok(());
+                        };
+                        let options = match app.surprise_options(&game.id, Vec::new(), None).await {
+                            Ok(options) => options,
+                            Err(e) => {
+                                state.set_status(format!("Surprise error: {}", e));
+                                return Ok(());
+                            }
+                        };
+                        state.browsing = true;
+                        state.set_status("Picking a random mod...".to_string());
+                        drop(state);
+
+                        Self::spawn_surprise(
+                            app.events.clone(),
+                            nexus,
+                            game.nexus_game_domain(),
+                            options,
+                        );
+                        return Ok(());
+                    }
+                    KeyCode::Char('a') => {
+                        // Queue the selected mod; the processor picks its MAIN file
+                        let Some(result) = state
+                            .browse_results
+                            .get(state.selected_browse_index)
+                            .cloned()
+                        else {
+                            return Ok(());
+                        };
+                        let Some(game) = state.active_game.clone() else {
+                            return Ok(());
+                        };
+                        let queue_manager = crate::queue::QueueManager::new(app.db.clone());
+                        match queue_manager.queue_mod(&game.id, result.mod_id, &result.name) {
+                            Ok(batch_id) => {
+                                state.queue_entries = queue_manager.get_batch(&batch_id)?;
+                                state.import_batch_id = Some(batch_id);
+                                state.selected_queue_index = 0;
+                                state.queue_processing = false;
+                                state.set_status_success(format!(
+                                    "Queued {}. Press F6 then p to download and install",
+                                    result.name
+                                ));
+                            }
+                            Err(e) => state.set_status_error(format!("Failed to queue: {}", e)),
+                        }
+                    }
                     KeyCode::Up | KeyCode::Char('k') => {
                         if state.selected_browse_index > 0 {
                             state.selected_browse_index -= 1;
@@ -5047,7 +7232,7 @@ impl Tui {
                             .cloned()
                         {
                             if let Some(ref game) = state.active_game {
-                                let game_numeric_id = game.game_type.nexus_numeric_id();
+                                let game_numeric_id = game.nexus_numeric_id;
                                 let game_domain = game.nexus_game_id.clone();
                                 let game_id_numeric = game_numeric_id;
                                 let mod_id = result.mod_id;
@@ -5059,13 +7244,14 @@ impl Tui {
                                     mod_name: mod_name.clone(),
                                     game_domain: game_domain.clone(),
                                     game_id: game_id_numeric,
+                                    queue_entry_id: None,
                                 });
                                 state.showing_file_picker = true;
                                 state.selected_file_index = 0;
                                 state.browse_mod_files.clear();
 
                                 let nexus_clone = app.nexus.as_ref().unwrap().clone();
-                                let state_clone = app.state.clone();
+                                let events = app.events.clone();
 
                                 drop(state);
 
@@ -5086,18 +7272,23 @@ impl Tui {
                                             });
 
                                             let file_count = files.len();
-                                            let mut state = state_clone.write().await;
-                                            state.browse_mod_files = files;
-                                            state.set_status(format!(
-                                                "{} files available for {} - Select and press Enter to download",
-                                                file_count, mod_name
-                                            ));
+                                            events.update(move |state| {
+                                                state.browse_mod_files = files;
+                                                state.set_status(format!(
+                                                    "{} files available for {} - Select and press Enter to download",
+                                                    file_count, mod_name
+                                                ));
+                                            });
                                         }
                                         Err(e) => {
-                                            let mut state = state_clone.write().await;
-                                            state.showing_file_picker = false;
-                                            state.download_context = None;
-                                            state.set_status(format!("Failed to get files: {}", e));
+                                            events.update(move |state| {
+                                                state.showing_file_picker = false;
+                                                state.download_context = None;
+                                                state.set_status(format!(
+                                                    "Failed to get files: {}",
+                                                    e
+                                                ));
+                                            });
                                         }
                                     }
                                 });
@@ -5144,6 +7335,7 @@ impl Tui {
                         if state.reorder_mode {
                             let idx = state.load_order_index;
                             if idx + 1 < state.load_order_mods.len() {
+                                Self::record_load_order_edit(&mut state, "move");
                                 state.load_order_mods.swap(idx, idx + 1);
                                 state.load_order_index = idx + 1;
                                 state.load_order_dirty = true;
@@ -5156,6 +7348,7 @@ impl Tui {
                         if state.reorder_mode {
                             let idx = state.load_order_index;
                             if idx > 0 {
+                                Self::record_load_order_edit(&mut state, "move");
                                 state.load_order_mods.swap(idx, idx - 1);
                                 state.load_order_index = idx - 1;
                                 state.load_order_dirty = true;
@@ -5169,6 +7362,9 @@ impl Tui {
                             return Ok(());
                         }
                         if state.reorder_mode {
+                            if state.load_order_index + 1 < state.load_order_mods.len() {
+                                Self::record_load_order_edit(&mut state, "move");
+                            }
                             for _ in 0..5 {
                                 let idx = state.load_order_index;
                                 if idx + 1 < state.load_order_mods.len() {
@@ -5184,6 +7380,9 @@ impl Tui {
                     }
                     KeyCode::Char('K') => {
                         if state.reorder_mode {
+                            if state.load_order_index > 0 {
+                                Self::record_load_order_edit(&mut state, "move");
+                            }
                             for _ in 0..5 {
                                 let idx = state.load_order_index;
                                 if idx > 0 {
@@ -5198,6 +7397,7 @@ impl Tui {
                     }
                     KeyCode::Char('t') => {
                         if state.reorder_mode && !state.load_order_mods.is_empty() {
+                            Self::record_load_order_edit(&mut state, "move to top");
                             let idx = state.load_order_index;
                             let m = state.load_order_mods.remove(idx);
                             state.load_order_mods.insert(0, m);
@@ -5209,6 +7409,7 @@ impl Tui {
                     }
                     KeyCode::Char('b') => {
                         if state.reorder_mode && !state.load_order_mods.is_empty() {
+                            Self::record_load_order_edit(&mut state, "move to bottom");
                             let idx = state.load_order_index;
                             let m = state.load_order_mods.remove(idx);
                             state.load_order_mods.push(m);
@@ -5245,6 +7446,11 @@ impl Tui {
                             {
                                 state.load_order_conflicts = conflicts;
                             }
+                            if let Ok(by_type) =
+                                crate::mods::get_conflicts_by_asset_type(&app.db, gid)
+                            {
+                                state.load_order_asset_conflicts = by_type;
+                            }
                             state.set_status("Load order saved");
                         }
                         return Ok(());
@@ -5253,6 +7459,7 @@ impl Tui {
                         // Auto-sort by category
                         if let Some(ref game) = state.active_game.clone() {
                             let game_id = game.id.clone();
+                            let before = state.load_order_mods.clone();
                             drop(state);
                             if let Err(e) = app.mods.auto_sort_by_category(&game_id).await {
                                 let mut state = app.state.write().await;
@@ -5261,6 +7468,7 @@ impl Tui {
                             }
                             self.refresh_mods(app).await?;
                             let mut state = app.state.write().await;
+                            state.load_order_history.record("auto-sort", &before);
                             state.load_order_mods = state.installed_mods.clone();
                             state.load_order_dirty = false;
                             if let Ok(conflicts) =
@@ -5268,7 +7476,34 @@ impl Tui {
                             {
                                 state.load_order_conflicts = conflicts;
                             }
-                            state.set_status("Auto-sorted by category");
+                            if let Ok(by_type) =
+                                crate::mods::get_conflicts_by_asset_type(&app.db, &game_id)
+                            {
+                                state.load_order_asset_conflicts = by_type;
+                            }
+                            state.set_status("Auto-sorted by category ('u' to undo)");
+                        }
+                        return Ok(());
+                    }
+                    KeyCode::Char('u') => {
+                        Self::step_load_order_history(&mut state, false);
+                    }
+                    KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                        Self::step_load_order_history(&mut state, true);
+                    }
+                    KeyCode::Char('v') => {
+                        state.load_order_group_by_type = !state.load_order_group_by_type;
+                        if state.load_order_group_by_type {
+                            state.set_status("Conflicts grouped by asset type");
+                        } else {
+                            state.set_status("Conflicts grouped by mod pair");
+                        }
+                    }
+                    KeyCode::Char('p') => {
+                        if !state.reorder_mode {
+                            state.goto(Screen::ScriptOverrides);
+                            drop(state);
+                            screens::script_overrides::refresh(app).await;
                         }
                         return Ok(());
                     }
@@ -5283,14 +7518,42 @@ impl Tui {
                         state.input_mode = InputMode::ImportFilePath;
                         state.input_buffer = state.import_file_path.clone();
                     }
+                    KeyCode::Enter | KeyCode::Char('d')
+                        if std::path::Path::new(&state.import_file_path).is_dir() =>
+                    {
+                        // A directory is an MO2 instance; 'd' only reports what would happen
+                        let dry_run = key == KeyCode::Char('d');
+                        let Some(game) = state.active_game.clone() else {
+                            state.set_status("No active game selected");
+                            return Ok(());
+                        };
+                        state.set_status(if dry_run {
+                            "Checking MO2 instance..."
+                        } else {
+                            "Importing MO2 instance..."
+                        });
+                        Self::spawn_mo2_instance_import(
+                            app.events.clone(),
+                            app.mods.clone(),
+                            app.profiles.clone(),
+                            game.id,
+                            state.import_file_path.clone(),
+                            dry_run,
+                        );
+                    }
                     KeyCode::Enter => {
                         // Start import
                         if !state.import_file_path.is_empty() {
                             let path = state.import_file_path.clone();
                             let game = state.active_game.clone();
                             let nexus = app.nexus.clone();
-                            let state_clone = app.state.clone();
+                            let events = app.events.clone();
                             let db_clone = app.db.clone();
+                            let extensions = (*app.extensions).clone();
+                            let extension_context = crate::extensions::ExtensionContext::new(
+                                &*app.config.read().await,
+                                game.as_ref(),
+                            );
 
                             state.set_status("Importing modlist...");
                             drop(state);
@@ -5307,23 +7570,22 @@ impl Tui {
                                             &game.id,
                                             (*nexus).clone(),
                                             Some(db_clone),
-                                        );
+                                        )
+                                        .with_extensions(extensions, extension_context);
 
                                         // Progress callback to update UI
-                                        let state_for_progress = state_clone.clone();
+                                        let progress_events = events.clone();
                                         let progress_callback = move |current: usize, total: usize, plugin_name: &str| {
-                                            if let Ok(mut state) = state_for_progress.try_write() {
-                                                state.import_progress = Some(ImportProgress {
-                                                    current_index: current,
-                                                    total_plugins: total,
-                                                    current_plugin_name: plugin_name.to_string(),
-                                                    stage: if current == 0 {
-                                                        "Parsing".to_string()
-                                                    } else {
-                                                        "Matching".to_string()
-                                                    },
-                                                });
-                                            }
+                                            progress_events.import_progress(Some(ImportProgress {
+                                                current_index: current,
+                                                total_plugins: total,
+                                                current_plugin_name: plugin_name.to_string(),
+                                                stage: if current == 0 {
+                                                    "Parsing".to_string()
+                                                } else {
+                                                    "Matching".to_string()
+                                                },
+                                            }));
                                         };
 
                                         match importer
@@ -5388,21 +7650,26 @@ impl Tui {
                                                     );
                                                 }
 
-                                                let mut state = state_clone.write().await;
-                                                let count = result.matches.len();
-                                                state.import_results = result.matches;
-                                                state.selected_import_index = 0;
-                                                state.import_progress = None; // Clear progress
-                                                state.goto(Screen::ImportReview);
-                                                state.set_status(format!(
-                                                    "Found {} plugins (saved to modlists)",
-                                                    count
-                                                ));
+                                                events.update(move |state| {
+                                                    let count = result.matches.len();
+                                                    state.import_results = result.matches;
+                                                    state.selected_import_index = 0;
+                                                    state.import_progress = None; // Clear progress
+                                                    state.goto(Screen::ImportReview);
+                                                    state.set_status(format!(
+                                                        "Found {} plugins (saved to modlists)",
+                                                        count
+                                                    ));
+                                                });
                                             }
                                             Err(e) => {
-                                                let mut state = state_clone.write().await;
-                                                state.import_progress = None; // Clear progress on error
-                                                state.set_status(format!("Import failed: {}", e));
+                                                events.update(move |state| {
+                                                    state.import_progress = None; // Clear progress on error
+                                                    state.set_status(format!(
+                                                        "Import failed: {}",
+                                                        e
+                                                    ));
+                                                });
                                             }
                                         }
                                     });
@@ -5487,6 +7754,8 @@ impl Tui {
                                     nexus_mod_id,
                                     selected_file_id: None,
                                     auto_install: true,
+                                    priority: false,
+                                    replaces_mod: None,
                                     match_confidence: Some(result.confidence.score()),
                                     alternatives,
                                     status,
@@ -5494,7 +7763,7 @@ impl Tui {
                                     error: None,
                                 };
 
-                                if let Ok(_) = queue_manager.add_entry(entry) {
+                                if queue_manager.add_entry(entry).is_ok() {
                                     queue_position += 1;
                                 }
                             }
@@ -5539,12 +7808,17 @@ impl Tui {
                                 state.set_status_success("All mods already installed!");
                                 state.modlist_review_data = None;
                                 state.go_back();
-                            } else {
+                            } else if let Some(game_id) =
+                                state.active_game.as_ref().map(|g| g.id.clone())
+                            {
+                                let needs_download = review.needs_download.clone();
                                 state.set_status("Queueing downloads...");
                                 drop(state);
                                 Self::spawn_queue_modlist_downloads(
-                                    app.state.clone(),
+                                    app.events.clone(),
                                     app.db.clone(),
+                                    game_id,
+                                    needs_download,
                                 );
                             }
                         }
@@ -5674,15 +7948,39 @@ impl Tui {
                     }
                     KeyCode::Char('p') => {
                         // Process queue
+                        if state.queue_processing {
+                            state.set_status_info("Queue is already processing");
+                            return Ok(());
+                        }
                         if let Some(batch_id) = state.import_batch_id.clone() {
                             if let Some(game) = state.active_game.clone() {
                                 if let Some(nexus) = app.nexus.clone() {
                                     state.queue_processing = true;
+                                    state.queue_paused = false;
+                                    state.queue_cancelling = false;
+                                    state.queue_rate = None;
                                     drop(state);
 
-                                    use crate::queue::QueueProcessor;
+                                    use crate::queue::{
+                                        BatchControl, QueueManager, QueueProcessor,
+                                    };
+                                    // Pressing p means process now, even if paused from the CLI
+                                    let _ = QueueManager::new(app.db.clone())
+                                        .set_batch_control(&batch_id, BatchControl::Running);
                                     let config = app.config.read().await;
-                                    let download_dir = config.downloads_dir();
+                                    let download_dir = match config.downloads_dir() {
+                                        Ok(dir) => dir,
+                                        Err(e) => {
+                                            drop(config);
+                                            let mut state = app.state.write().await;
+                                            state.queue_processing = false;
+                                            state.set_status(format!(
+                                                "Can't process the queue: {}",
+                                                e
+                                            ));
+                                            return Ok(());
+                                        }
+                                    };
                                     drop(config);
 
                                     let processor = QueueProcessor::new(
@@ -5692,44 +7990,59 @@ impl Tui {
                                         game.id.clone(),
                                         download_dir,
                                         app.mods.clone(),
-                                    );
-                                    let state_for_task = app.state.clone();
+                                    )
+                                    .with_transfers(app.transfers.clone())
+                                    .with_game_build(game.build());
+                                    let events = app.events.clone();
                                     let db_for_task = app.db.clone();
                                     let batch_for_task = batch_id.clone();
 
                                     tokio::spawn(async move {
-                                        let monitor_state = state_for_task.clone();
+                                        // Polls queue rows until aborted once processing ends
+                                        let monitor_events = events.clone();
                                         let monitor_db = db_for_task.clone();
                                         let monitor_batch = batch_for_task.clone();
                                         let monitor_handle = tokio::spawn(async move {
                                             use tokio::time::{sleep, Duration};
                                             let queue_manager =
                                                 crate::queue::QueueManager::new(monitor_db);
+                                            let mut throughput = crate::queue::Throughput::new();
                                             loop {
-                                                let should_continue = {
-                                                    let state = monitor_state.read().await;
-                                                    state.queue_processing
-                                                };
-                                                if !should_continue {
-                                                    break;
-                                                }
-
                                                 if let Ok(entries) =
                                                     queue_manager.get_batch(&monitor_batch)
                                                 {
-                                                    let mut state = monitor_state.write().await;
-                                                    if state.import_batch_id.as_deref()
-                                                        == Some(monitor_batch.as_str())
-                                                    {
-                                                        let selected = state.selected_queue_index;
-                                                        state.queue_entries = entries;
-                                                        if state.queue_entries.is_empty() {
-                                                            state.selected_queue_index = 0;
-                                                        } else {
-                                                            state.selected_queue_index = selected
-                                                                .min(state.queue_entries.len() - 1);
+                                                    let batch = monitor_batch.clone();
+                                                    let rate = queue_manager
+                                                        .batch_progress(&monitor_batch)
+                                                        .ok()
+                                                        .map(|progress| {
+                                                            throughput.record(
+                                                                std::time::Instant::now(),
+                                                                progress,
+                                                            )
+                                                        });
+                                                    monitor_events.update(move |state| {
+                                                        if !state.queue_processing {
+                                                            return;
                                                         }
-                                                    }
+                                                        if state.import_batch_id.as_deref()
+                                                            == Some(batch.as_str())
+                                                        {
+                                                            let selected =
+                                                                state.selected_queue_index;
+                                                            state.queue_entries = entries;
+                                                            state.queue_rate = rate;
+                                                            if state.queue_entries.is_empty() {
+                                                                state.selected_queue_index = 0;
+                                                            } else {
+                                                                state.selected_queue_index =
+                                                                    selected.min(
+                                                                        state.queue_entries.len()
+                                                                            - 1,
+                                                                    );
+                                                            }
+                                                        }
+                                                    });
                                                 }
 
                                                 sleep(Duration::from_millis(200)).await;
@@ -5743,31 +8056,44 @@ impl Tui {
                                         let refreshed = queue_manager
                                             .get_batch(&batch_for_task)
                                             .unwrap_or_default();
-                                        let mut state = state_for_task.write().await;
-                                        state.queue_processing = false;
-                                        state.queue_entries = refreshed;
-                                        match result {
-                                            Ok(_) => {
-                                                if state.is_advanced_mode() {
-                                                    state.set_status_success(
-                                                        "Queue processing complete",
-                                                    );
-                                                } else {
-                                                    state.goto(Screen::Plugins);
-                                                    state.set_status_success(
-                                                        "Queue complete. Next: [S] auto-sort, [s] save, [D] deploy",
+                                        monitor_handle.abort();
+                                        let result = result.map_err(|e| {
+                                            tracing::error!("Queue processing error: {}", e);
+                                            e.to_string()
+                                        });
+                                        events.update(move |state| {
+                                            state.queue_processing = false;
+                                            state.queue_paused = false;
+                                            state.queue_rate = None;
+                                            state.queue_entries = refreshed;
+                                            let cancelled =
+                                                std::mem::take(&mut state.queue_cancelling);
+                                            match result {
+                                                Ok(_) if cancelled => {
+                                                    state.set_status_info(
+                                                        "Queue processing cancelled; unfinished entries stay queued",
                                                     );
                                                 }
+                                                Ok(_) => {
+                                                    if state.is_advanced_mode() {
+                                                        state.set_status_success(
+                                                            "Queue processing complete",
+                                                        );
+                                                    } else {
+                                                        state.goto(Screen::Plugins);
+                                                        state.set_status_success(
+                                                            "Queue complete. Next: [S] auto-sort, [s] save, [D] deploy",
+                                                        );
+                                                    }
+                                                }
+                                                Err(e) => {
+                                                    state.set_status_error(format!(
+                                                        "Queue processing failed: {}",
+                                                        e
+                                                    ));
+                                                }
                                             }
-                                            Err(e) => {
-                                                tracing::error!("Queue processing error: {}", e);
-                                                state.set_status_error(format!(
-                                                    "Queue processing failed: {}",
-                                                    e
-                                                ));
-                                            }
-                                        }
-                                        monitor_handle.abort();
+                                        });
                                     });
 
                                     let mut state = app.state.write().await;
@@ -5782,6 +8108,159 @@ impl Tui {
                             state.set_status("No queue batch selected");
                         }
                     }
+                    KeyCode::Char('P') => {
+                        // Pause or resume processing
+                        if !state.queue_processing || state.queue_cancelling {
+                            state.set_status_info("Queue is not processing");
+                            return Ok(());
+                        }
+                        if let Some(batch_id) = state.import_batch_id.clone() {
+                            use crate::queue::{BatchControl, QueueManager};
+                            let control = if state.queue_paused {
+                                BatchControl::Running
+                            } else {
+                                BatchControl::Paused
+                            };
+                            match QueueManager::new(app.db.clone())
+                                .set_batch_control(&batch_id, control)
+                            {
+                                Ok(()) => {
+                                    state.queue_paused = control == BatchControl::Paused;
+                                    if state.queue_paused {
+                                        state.set_status_info(
+                                            "Queue paused (P to resume, x to cancel)",
+                                        );
+                                    } else {
+                                        state.set_status_info("Queue resumed");
+                                    }
+                                }
+                                Err(e) => {
+                                    state.set_status_error(format!("Failed to pause queue: {}", e))
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Char('x') => {
+                        // Cancel processing
+                        if !state.queue_processing {
+                            state.set_status_info("Queue is not processing");
+                            return Ok(());
+                        }
+                        if let Some(batch_id) = state.import_batch_id.clone() {
+                            use crate::queue::{BatchControl, QueueManager};
+                            match QueueManager::new(app.db.clone())
+                                .set_batch_control(&batch_id, BatchControl::Cancelled)
+                            {
+                                Ok(()) => {
+                                    state.queue_paused = false;
+                                    state.queue_cancelling = true;
+                                    state.set_status_info(
+                                        "Cancelling queue; stopping running downloads and installs",
+                                    );
+                                }
+                                Err(e) => {
+                                    state.set_status_error(format!("Failed to cancel queue: {}", e))
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Char('K') | KeyCode::Char('J') | KeyCode::Char('!') => {
+                        // Reorder the entry, or toggle its priority
+                        if state.queue_processing {
+                            state.set_status_info("Wait for processing to finish to reorder");
+                            return Ok(());
+                        }
+                        let (Some(batch_id), Some(entry)) = (
+                            state.import_batch_id.clone(),
+                            state.queue_entries.get(state.selected_queue_index).cloned(),
+                        ) else {
+                            return Ok(());
+                        };
+                        use crate::queue::{QueueManager, QueueMove};
+                        let queue_manager = QueueManager::new(app.db.clone());
+                        let result = match key {
+                            KeyCode::Char('K') => {
+                                queue_manager.move_entry(&batch_id, entry.id, QueueMove::Up)
+                            }
+                            KeyCode::Char('J') => {
+                                queue_manager.move_entry(&batch_id, entry.id, QueueMove::Down)
+                            }
+                            _ => queue_manager
+                                .set_priority(entry.id, !entry.priority)
+                                .map(|_| true),
+                        }
+                        .and_then(|changed| Ok((changed, queue_manager.get_batch(&batch_id)?)));
+                        match result {
+                            Ok((changed, entries)) => {
+                                state.selected_queue_index = entries
+                                    .iter()
+                                    .position(|e| e.id == entry.id)
+                                    .unwrap_or(state.selected_queue_index);
+                                state.queue_entries = entries;
+                                if key == KeyCode::Char('!') {
+                                    state.set_status_info(if entry.priority {
+                                        format!("'{}' is no longer prioritized", entry.mod_name)
+                                    } else {
+                                        format!("'{}' will be processed first", entry.mod_name)
+                                    });
+                                } else if !changed && state.queue_entries.iter().any(|e| e.priority)
+                                {
+                                    state.set_status_info("Priority entries stay above the rest");
+                                }
+                            }
+                            Err(e) => {
+                                state.set_status_error(format!("Failed to reorder queue: {}", e))
+                            }
+                        }
+                    }
+                    KeyCode::Char('f') => {
+                        // Choose which Nexus file to download
+                        if state.queue_processing {
+                            state.set_status_info("Wait for processing to finish to change files");
+                            return Ok(());
+                        }
+                        let Some(entry) =
+                            state.queue_entries.get(state.selected_queue_index).cloned()
+                        else {
+                            return Ok(());
+                        };
+                        if entry.nexus_mod_id <= 0 {
+                            state.set_status_info("Resolve the entry to a Nexus mod first");
+                            return Ok(());
+                        }
+                        if entry.replaces_mod.is_some() {
+                            state.set_status_info(
+                                "Updates follow the installed file; choose a file for new installs",
+                            );
+                            return Ok(());
+                        }
+                        let Some(nexus) = app.nexus.clone() else {
+                            state.set_status("NexusMods API key required");
+                            return Ok(());
+                        };
+                        let Some(game) = state.active_game.clone() else {
+                            state.set_status("No active game selected");
+                            return Ok(());
+                        };
+                        let game_id_numeric = game.nexus_numeric_id;
+                        state.download_context = Some(crate::app::state::DownloadContext {
+                            mod_id: entry.nexus_mod_id,
+                            mod_name: entry.mod_name.clone(),
+                            game_domain: game.nexus_game_id.clone(),
+                            game_id: game_id_numeric,
+                            queue_entry_id: Some(entry.id),
+                        });
+                        state.showing_file_picker = true;
+                        state.selected_file_index = 0;
+                        state.browse_mod_files.clear();
+                        state.set_status(format!("Fetching files for {}...", entry.mod_name));
+                        Self::spawn_queue_file_picker(
+                            app.events.clone(),
+                            nexus,
+                            game_id_numeric,
+                            entry,
+                        );
+                    }
                     KeyCode::Char('c') => {
                         // Clear queue
                         if let Some(batch_id) = state.import_batch_id.clone() {
@@ -5790,7 +8269,7 @@ impl Tui {
                             use crate::queue::QueueManager;
                             let queue_manager = QueueManager::new(app.db.clone());
 
-                            if let Ok(_) = queue_manager.clear_batch(&batch_id) {
+                            if queue_manager.clear_batch(&batch_id).is_ok() {
                                 let mut state = app.state.write().await;
                                 state.queue_entries.clear();
                                 state.import_batch_id = None;
@@ -5827,6 +8306,60 @@ impl Tui {
                 screens::nexus_catalog::handle_input(app, key).await?;
             }
 
+            Screen::Extensions => {
+                drop(state);
+                screens::extensions::handle_input(app, key).await?;
+            }
+
+            Screen::Storage => {
+                drop(state);
+                screens::storage::handle_input(app, key).await?;
+            }
+
+            Screen::IniDiff => {
+                drop(state);
+                screens::ini_diff::handle_input(app, key).await?;
+            }
+
+            Screen::ModlistDiff => {
+                drop(state);
+                screens::modlist_diff::handle_input(app, key).await?;
+            }
+
+            Screen::ScriptOverrides => {
+                drop(state);
+                screens::script_overrides::handle_input(app, key).await?;
+            }
+
+            Screen::Downloads => {
+                drop(state);
+                screens::downloads::handle_input(app, key).await?;
+            }
+
+            Screen::History => {
+                drop(state);
+                screens::history::handle_input(app, key).await?;
+            }
+
+            Screen::ToolOutput => {
+                drop(state);
+                screens::tool_output::handle_input(app, key).await?;
+            }
+
+            Screen::Saves => {
+                drop(state);
+                screens::saves::handle_input(app, key).await?;
+            }
+
+            Screen::IniEditor => {
+                drop(state);
+                if key == KeyCode::Char('e') {
+                    self.edit_ini_from_tui(app).await?;
+                } else {
+                    screens::ini_editor::handle_input(app, key).await?;
+                }
+            }
+
             Screen::ModlistEditor => {
                 use crate::app::state::ModlistEditorMode;
 
@@ -5854,11 +8387,13 @@ impl Tui {
                                     let ml_id = ml.id.unwrap();
                                     let ml_name = ml.name.clone();
                                     let load_mode = state.modlist_picker_for_loading;
+                                    let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                                     drop(state);
                                     if load_mode {
                                         Self::spawn_load_saved_modlist(
-                                            app.state.clone(),
+                                            app.events.clone(),
                                             app.db.clone(),
+                                            game_id,
                                             ml_id,
                                             ml_name,
                                         );
@@ -5879,10 +8414,12 @@ impl Tui {
                                 {
                                     let ml_id = ml.id.unwrap();
                                     let ml_name = ml.name.clone();
+                                    let game_id = state.active_game.as_ref().map(|g| g.id.clone());
                                     drop(state);
                                     Self::spawn_load_saved_modlist(
-                                        app.state.clone(),
+                                        app.events.clone(),
                                         app.db.clone(),
+                                        game_id,
                                         ml_id,
                                         ml_name,
                                     );
@@ -5909,6 +8446,17 @@ impl Tui {
                                     return Ok(());
                                 }
                             }
+                            KeyCode::Char('c') => {
+                                // Compare selected modlist with the current setup
+                                if let Some(ml) =
+                                    state.saved_modlists.get(state.selected_saved_modlist_index)
+                                {
+                                    let name = ml.name.clone();
+                                    drop(state);
+                                    screens::modlist_diff::open(app, name).await;
+                                    return Ok(());
+                                }
+                            }
                             KeyCode::Char('f') => {
                                 if !Self::require_advanced(&mut state, "File-path load fallback") {
                                     return Ok(());
@@ -6214,17 +8762,12 @@ impl Tui {
                 }
 
                 match key {
-                    KeyCode::Esc => {
-                        // Cancel wizard
-                        state.fomod_wizard_state = None;
-                        state.go_back();
-                        state.set_status("FOMOD installation cancelled");
-                    }
-                    KeyCode::Char('q') => {
+                    KeyCode::Esc | KeyCode::Char('q') => {
                         // Cancel wizard
                         state.fomod_wizard_state = None;
                         state.go_back();
                         state.set_status("FOMOD installation cancelled");
+                        Self::open_next_fomod_wizard(&app.mods, &mut state);
                     }
                     KeyCode::Char('?') => {
                         // Show help
@@ -6238,6 +8781,16 @@ impl Tui {
 
                         match wizard_state.phase {
                             WizardPhase::Overview => {
+                                // Skip leading steps whose visibility conditions fail
+                                let config = &wizard_state.installer.config;
+                                let step = wizard_state.current_step;
+                                if !wizard_state.wizard.is_step_visible(config, step) {
+                                    if let Some(next) =
+                                        wizard_state.wizard.next_visible_step(config, step)
+                                    {
+                                        wizard_state.current_step = next;
+                                    }
+                                }
                                 wizard_state.phase = WizardPhase::StepNavigation;
                             }
                             WizardPhase::StepNavigation => {
@@ -6278,18 +8831,45 @@ impl Tui {
                                     }
                                 }
 
-                                // Move to next step or summary
-                                if wizard_state.current_step + 1 < config.install_steps.steps.len()
+                                // Move to next visible step or summary
+                                if let Some(next) =
+                                    wizard_state.wizard.next_visible_step(config, current_step)
                                 {
-                                    wizard_state.current_step += 1;
+                                    wizard_state.current_step = next;
                                     wizard_state.current_group = 0;
                                     wizard_state.selected_option = 0;
                                 } else {
                                     wizard_state.phase = WizardPhase::Summary;
+                                    wizard_state.refresh_preview();
                                 }
                             }
                             WizardPhase::Summary => {
-                                wizard_state.phase = WizardPhase::Confirm;
+                                // The preview can be opened before the last step
+                                let config = &wizard_state.installer.config;
+                                let incomplete = config
+                                    .install_steps
+                                    .steps
+                                    .iter()
+                                    .enumerate()
+                                    .filter(|(i, _)| {
+                                        wizard_state.wizard.is_step_visible(config, *i)
+                                    })
+                                    .find(|(i, step)| {
+                                        !crate::mods::fomod::validation::validate_step(
+                                            step,
+                                            &wizard_state.wizard,
+                                            *i,
+                                        )
+                                        .is_empty()
+                                    })
+                                    .map(|(_, step)| step.name.clone());
+                                match incomplete {
+                                    Some(step) => state.set_status(format!(
+                                        "Complete the required selections in '{}' first",
+                                        step
+                                    )),
+                                    None => wizard_state.phase = WizardPhase::Confirm,
+                                }
                             }
                             WizardPhase::Confirm => {
                                 // Execute installation
@@ -6346,9 +8926,11 @@ impl Tui {
                                     Err(e) => {
                                         let mut state = app.state.write().await;
                                         state.goto(Screen::Mods);
-                                        state.set_status(format!("Installation failed: {}", e));
+                                        state.set_status_error_chain("Installation failed", &e);
                                     }
                                 }
+                                let mut state = app.state.write().await;
+                                Self::open_next_fomod_wizard(&app.mods, &mut state);
                                 return Ok(());
                             }
                         }
@@ -6362,8 +8944,12 @@ impl Tui {
                                 // Can't go back from overview
                             }
                             WizardPhase::StepNavigation => {
-                                if wizard_state.current_step > 0 {
-                                    wizard_state.current_step -= 1;
+                                let config = &wizard_state.installer.config;
+                                if let Some(prev) = wizard_state
+                                    .wizard
+                                    .prev_visible_step(config, wizard_state.current_step)
+                                {
+                                    wizard_state.current_step = prev;
                                     wizard_state.current_group = 0;
                                     wizard_state.selected_option = 0;
                                 } else {
@@ -6371,15 +8957,41 @@ impl Tui {
                                 }
                             }
                             WizardPhase::Summary => {
+                                let config = &wizard_state.installer.config;
+                                let steps = config.install_steps.steps.len();
                                 wizard_state.phase = WizardPhase::StepNavigation;
-                                wizard_state.current_step =
-                                    wizard_state.installer.config.install_steps.steps.len() - 1;
+                                wizard_state.current_step = wizard_state
+                                    .wizard
+                                    .prev_visible_step(config, steps)
+                                    .unwrap_or(steps.saturating_sub(1));
                             }
                             WizardPhase::Confirm => {
                                 wizard_state.phase = WizardPhase::Summary;
                             }
                         }
                     }
+                    KeyCode::Char('j') | KeyCode::Down | KeyCode::PageDown
+                        if state
+                            .fomod_wizard_state
+                            .as_ref()
+                            .is_some_and(|w| w.phase == WizardPhase::Summary) =>
+                    {
+                        // Scroll the file preview
+                        let wizard_state = state.fomod_wizard_state.as_mut().unwrap();
+                        let step = if key == KeyCode::PageDown { 10 } else { 1 };
+                        wizard_state.preview_scroll += step;
+                    }
+                    KeyCode::Char('k') | KeyCode::Up | KeyCode::PageUp
+                        if state
+                            .fomod_wizard_state
+                            .as_ref()
+                            .is_some_and(|w| w.phase == WizardPhase::Summary) =>
+                    {
+                        let wizard_state = state.fomod_wizard_state.as_mut().unwrap();
+                        let step = if key == KeyCode::PageUp { 10 } else { 1 };
+                        wizard_state.preview_scroll =
+                            wizard_state.preview_scroll.saturating_sub(step);
+                    }
                     KeyCode::Char('j') | KeyCode::Down => {
                         // Navigate down
                         let wizard_state = state.fomod_wizard_state.as_mut().unwrap();
@@ -6466,27 +9078,24 @@ impl Tui {
                                             group_type,
                                             plugin,
                                         );
+                                        // Later steps and patterns depend on the new flags
+                                        wizard_state.wizard.refresh_flags(config);
                                     }
                                 }
                             }
                         }
                     }
                     KeyCode::Char('p') => {
-                        // Preview (currently just show status)
-                        let wizard_state = state.fomod_wizard_state.as_ref().unwrap();
-                        let selection_count: usize = wizard_state
-                            .wizard
-                            .selections
-                            .values()
-                            .map(|s| s.len())
-                            .sum();
-                        state.set_status(format!("{} options selected", selection_count));
+                        // Preview the files the current selections install
+                        let wizard_state = state.fomod_wizard_state.as_mut().unwrap();
+                        if let WizardPhase::StepNavigation = wizard_state.phase {
+                            wizard_state.phase = WizardPhase::Summary;
+                            wizard_state.refresh_preview();
+                        }
                     }
                     _ => {}
                 }
             }
-
-            _ => {}
         }
 
         Ok(())
@@ -6494,7 +9103,7 @@ impl Tui {
 
     /// Handle confirmation actions
     async fn handle_confirm_action(
-        &self,
+        &mut self,
         app: &mut App,
         action: crate::app::state::ConfirmAction,
     ) -> Result<()> {
@@ -6505,9 +9114,150 @@ impl Tui {
                 if let Some(game) = app.active_game().await {
                     app.mods.remove_mod(&game.id, &name).await?;
                     self.refresh_mods(app).await?;
+                    let trashed = app.config.read().await.trash.retention_days > 0;
                     // Note: Deployed files remain until next deploy/purge
                     let mut state = app.state.write().await;
-                    state.set_status(format!("Deleted: {} (redeploy to update game files)", name));
+                    state.set_status(if trashed {
+                        format!(
+                            "Deleted: {} (modsanity mod restore brings it back; redeploy to update game files)",
+                            name
+                        )
+                    } else {
+                        format!("Deleted: {} (redeploy to update game files)", name)
+                    });
+                }
+            }
+            ConfirmAction::DeleteMods(names) => {
+                if let Some(game) = app.active_game().await {
+                    let mut deleted = 0;
+                    let mut failed = 0;
+                    for name in &names {
+                        match app.mods.remove_mod(&game.id, name).await {
+                            Ok(()) => deleted += 1,
+                            Err(e) => {
+                                tracing::warn!("Failed to delete {}: {}", name, e);
+                                failed += 1;
+                            }
+                        }
+                    }
+                    self.refresh_mods(app).await?;
+                    let mut state = app.state.write().await;
+                    state.clear_mod_marks();
+                    if failed > 0 {
+                        state.set_status_error(format!(
+                            "Deleted {} mod(s), {} failed (redeploy to update game files)",
+                            deleted, failed
+                        ));
+                    } else {
+                        state.set_status(format!(
+                            "Deleted {} mod(s) (redeploy to update game files)",
+                            deleted
+                        ));
+                    }
+                }
+            }
+            ConfirmAction::RollbackMod(name) => {
+                if let Some(game) = app.active_game().await {
+                    match app.mods.rollback_mod(&game.id, &name, None).await {
+                        Ok(crate::mods::InstallResult::Completed(installed)) => {
+                            self.refresh_mods(app).await?;
+                            let mut state = app.state.write().await;
+                            state.set_status_success(format!(
+                                "Rolled back {} to v{} (redeploy to update game files)",
+                                installed.name, installed.version
+                            ));
+                        }
+                        Ok(crate::mods::InstallResult::RequiresWizard(context)) => {
+                            // Saved FOMOD choices no longer apply; ask again
+                            use crate::app::state::{FomodWizardState, WizardPhase};
+                            use crate::mods::fomod::wizard::init_wizard_state_with_env;
+
+                            let env = app
+                                .mods
+                                .fomod_environment(&game.id)
+                                .unwrap_or_default()
+                                .with_game(&game);
+                            let wizard = init_wizard_state_with_env(&context.installer.config, env);
+                            let wizard_state = FomodWizardState {
+                                installer: context.installer.clone(),
+                                wizard,
+                                current_step: 0,
+                                current_group: 0,
+                                selected_option: 0,
+                                validation_errors: Vec::new(),
+                                mod_name: context.mod_name.clone(),
+                                staging_path: context.staging_path.clone(),
+                                preview_files: None,
+                                preview_scroll: 0,
+                                phase: WizardPhase::Overview,
+                                existing_mod_id: context.existing_mod_id,
+                            };
+
+                            let mut state = app.state.write().await;
+                            state.fomod_wizard_state = Some(wizard_state);
+                            state.goto(crate::app::state::Screen::FomodWizard);
+                            state.set_status_info(format!(
+                                "Rolled back {} to v{}; re-select FOMOD options",
+                                context.mod_name, context.version
+                            ));
+                        }
+                        Err(e) => {
+                            let mut state = app.state.write().await;
+                            state.set_status_error(format!("Rollback failed: {}", e));
+                        }
+                    }
+                }
+            }
+            ConfirmAction::ReinstallMod(name) => {
+                if let Some(game) = app.active_game().await {
+                    match app.mods.reinstall_mod(&game.id, &name, None).await {
+                        Ok(crate::mods::InstallResult::Completed(installed)) => {
+                            self.refresh_mods(app).await?;
+                            let mut state = app.state.write().await;
+                            state.set_status_success(format!(
+                                "Reinstalled {} v{} (redeploy to update game files)",
+                                installed.name, installed.version
+                            ));
+                        }
+                        Ok(crate::mods::InstallResult::RequiresWizard(context)) => {
+                            // Saved FOMOD choices no longer apply; ask again
+                            use crate::app::state::{FomodWizardState, WizardPhase};
+                            use crate::mods::fomod::wizard::init_wizard_state_with_env;
+
+                            let env = app
+                                .mods
+                                .fomod_environment(&game.id)
+                                .unwrap_or_default()
+                                .with_game(&game);
+                            let wizard = init_wizard_state_with_env(&context.installer.config, env);
+                            let wizard_state = FomodWizardState {
+                                installer: context.installer.clone(),
+                                wizard,
+                                current_step: 0,
+                                current_group: 0,
+                                selected_option: 0,
+                                validation_errors: Vec::new(),
+                                mod_name: context.mod_name.clone(),
+                                staging_path: context.staging_path.clone(),
+                                preview_files: None,
+                                preview_scroll: 0,
+                                phase: WizardPhase::Overview,
+                                existing_mod_id: context.existing_mod_id,
+                            };
+
+                            let mut state = app.state.write().await;
+                            state.fomod_wizard_state = Some(wizard_state);
+                            state.goto(crate::app::state::Screen::FomodWizard);
+                            state.set_status_info(format!(
+                                "Reinstalled {} v{}; re-select FOMOD options",
+                                context.mod_name, context.version
+                            ));
+                        }
+                        Err(e) => {
+                            let mut state = app.state.write().await;
+                            state.set_status_error(format!("Reinstall failed: {}", e));
+                        }
+                    }
                 }
             }
             ConfirmAction::Deploy => {
@@ -6525,6 +9275,8 @@ impl Tui {
                         state.set_status("Deploying mods...");
                     }
                     drop(state);
+                    // Deployment blocks the event loop; the next frame resets the title
+                    self.show_activity(Some(title::Activity::new("Deploying", None)))?;
 
                     let stats = app.mods.deploy(&game).await?;
 
@@ -6535,6 +9287,21 @@ impl Tui {
                     if stats.mods_deployed == 0 {
                         state
                             .set_status("✓ Game restored to factory state (all mod files removed)");
+                    } else if let Some(first) = stats.warnings.first() {
+                        state.set_status_error(format!(
+                            "Deployed {} files from {} mods; {} warning(s): {}",
+                            stats.files_deployed,
+                            stats.mods_deployed,
+                            stats.warnings.len(),
+                            first
+                        ));
+                    } else if let Some(path) = &stats.archive_invalidation {
+                        state.set_status(format!(
+                            "Deployed {} files from {} mods; turned on archive invalidation in {}",
+                            stats.files_deployed,
+                            stats.mods_deployed,
+                            path.file_name().unwrap_or_default().to_string_lossy()
+                        ));
                     } else {
                         state.set_status(format!(
                             "Deployed {} files from {} mods",
@@ -6543,6 +9310,118 @@ impl Tui {
                     }
                 }
             }
+            ConfirmAction::DeployAndLaunchTool(tool) => {
+                if let Some(game) = app.active_game().await {
+                    app.state.write().await.set_status("Deploying mods...");
+                    self.show_activity(Some(title::Activity::new("Deploying", None)))?;
+                    app.mods.deploy(&game).await?;
+                    self.refresh_plugins(app).await?;
+                }
+                self.launch_external_tool_from_tui(app, tool).await?;
+            }
+            ConfirmAction::CleanPlugins(dirty) => {
+                self.clean_plugins_from_tui(app, dirty).await?;
+            }
+            ConfirmAction::SaveProfileIni(name) => {
+                if let Some(game) = app.active_game().await {
+                    let result = app.profiles.save_profile_ini(&game, &name).await;
+                    self.reload_data(app).await?;
+                    let mut state = app.state.write().await;
+                    match result {
+                        Ok(files) => state.set_status_success(format!(
+                            "Stored {} in profile '{}'",
+                            files.join(", "),
+                            name
+                        )),
+                        Err(e) => state.set_status_error_chain("Failed to store INI files", &e),
+                    }
+                }
+            }
+            ConfirmAction::SetProfileSaves(name, own) => {
+                if let Some(game) = app.active_game().await {
+                    let result = app.profiles.set_profile_saves(&game, &name, own).await;
+                    self.reload_data(app).await?;
+                    let mut state = app.state.write().await;
+                    match result {
+                        Ok(()) if own => state.set_status_success(format!(
+                            "Profile '{}' saves to a folder of its own",
+                            name
+                        )),
+                        Ok(()) => state.set_status_success(format!(
+                            "Profile '{}' uses the shared save folder",
+                            name
+                        )),
+                        Err(e) => state.set_status_error_chain("Failed to change save folder", &e),
+                    }
+                }
+            }
+            ConfirmAction::ClearProfileIni(name) => {
+                if let Some(game) = app.active_game().await {
+                    let result = app.profiles.clear_profile_ini(&game.id, &name).await;
+                    self.reload_data(app).await?;
+                    let mut state = app.state.write().await;
+                    match result {
+                        Ok(()) => state.set_status_success(format!(
+                            "Profile '{}' uses the game's INI files as they are",
+                            name
+                        )),
+                        Err(e) => state.set_status_error_chain("Failed to drop INI files", &e),
+                    }
+                }
+            }
+            ConfirmAction::RestoreIniBackup(backup) => {
+                if let Some(game) = app.active_game().await {
+                    let backup_dir = app.ini_backup_dir(&game).await;
+                    let result = crate::profiles::restore_ini_backup(&game, &backup_dir, &backup);
+                    screens::ini_editor::refresh(app).await;
+                    let mut state = app.state.write().await;
+                    match result {
+                        Ok(write) => state.set_status_success(format!(
+                            "Restored {}",
+                            write.path.file_name().unwrap_or_default().to_string_lossy()
+                        )),
+                        Err(e) => state.set_status_error_chain("Restore failed", &e),
+                    }
+                }
+            }
+            ConfirmAction::DeleteSave(path) => {
+                let save = app
+                    .state
+                    .read()
+                    .await
+                    .saves
+                    .iter()
+                    .find(|s| s.path == path)
+                    .cloned();
+                if let Some(save) = save {
+                    let result = crate::saves::delete_save(&save);
+                    screens::saves::refresh(app).await;
+                    let mut state = app.state.write().await;
+                    match result {
+                        Ok(()) => state.set_status_success(format!("Deleted {}", save.name)),
+                        Err(e) => state.set_status_error_chain("Failed to delete save", &e),
+                    }
+                }
+            }
+            ConfirmAction::CaptureToolOutput(output_mod) => {
+                if let Some(game) = app.active_game().await {
+                    match app.mods.capture_new_data_files(&game, &output_mod).await {
+                        Ok(captured) => {
+                            self.refresh_mods(app).await?;
+                            app.state.write().await.set_status_success(format!(
+                                "Captured {} file(s) into '{}'; deploy to link them",
+                                captured.len(),
+                                output_mod
+                            ));
+                        }
+                        Err(e) => app
+                            .state
+                            .write()
+                            .await
+                            .set_status_error_chain("Capture failed", &e),
+                    }
+                }
+            }
             ConfirmAction::Purge => {
                 if let Some(game) = app.active_game().await {
                     app.mods.purge(&game).await?;
@@ -6579,6 +9458,86 @@ impl Tui {
                     state.set_status("No active queue");
                 }
             }
+            ConfirmAction::CleanStaleArchives => {
+                let report = app.state.read().await.storage_report.clone();
+                if let Some(report) = report {
+                    let stats = app.mods.remove_stale_archives(&report).await;
+                    {
+                        let mut state = app.state.write().await;
+                        Self::set_cleanup_status(&mut state, "stale archive(s)", &stats);
+                    }
+                    screens::storage::refresh(app).await;
+                }
+            }
+            ConfirmAction::CleanOrphanedStaging => {
+                let report = app.state.read().await.storage_report.clone();
+                if let (Some(game), Some(report)) = (app.active_game().await, report) {
+                    let stats = app.mods.remove_orphaned_staging(&game.id, &report).await?;
+                    {
+                        let mut state = app.state.write().await;
+                        Self::set_cleanup_status(&mut state, "orphaned staging entries", &stats);
+                    }
+                    screens::storage::refresh(app).await;
+                }
+            }
+            ConfirmAction::UpdateAllMods => {
+                let Some(game) = app.active_game().await else {
+                    return Ok(());
+                };
+                let updates: Vec<_> = app
+                    .state
+                    .read()
+                    .await
+                    .available_updates
+                    .values()
+                    .cloned()
+                    .collect();
+                let queue_manager = crate::queue::QueueManager::new(app.db.clone());
+                let mut state = app.state.write().await;
+                match queue_manager.queue_updates(&game.id, &updates) {
+                    Ok(Some((batch_id, count))) => {
+                        state.queue_entries = queue_manager.get_batch(&batch_id)?;
+                        state.import_batch_id = Some(batch_id);
+                        state.selected_queue_index = 0;
+                        state.queue_processing = false;
+                        state.goto(Screen::DownloadQueue);
+                        state.set_status_success(format!(
+                            "Queued {} update(s). Press p to download and install",
+                            count
+                        ));
+                    }
+                    Ok(None) => state.set_status_info("None of the updates match installed mods"),
+                    Err(e) => state.set_status_error(format!("Failed to queue updates: {}", e)),
+                }
+            }
+            ConfirmAction::ApplyIniPreset { profile, preset } => {
+                let Some(preset) = crate::profiles::find_ini_preset(&preset) else {
+                    return Ok(());
+                };
+                if let Some(game) = app.active_game().await {
+                    match app
+                        .profiles
+                        .apply_ini_preset(&game.id, &profile, preset)
+                        .await
+                    {
+                        Ok(_) => {
+                            self.reload_data(app).await?;
+                            {
+                                let mut state = app.state.write().await;
+                                state.set_status_success(format!(
+                                    "Applied preset '{}' to profile '{}' (activate it to write INI files)",
+                                    preset.name, profile
+                                ));
+                            }
+                            screens::ini_diff::refresh(app).await;
+                        }
+                        Err(e) => {
+                            let mut state = app.state.write().await;
+                            state.set_status_error(format!("Apply preset failed: {}", e));
+                        }
+                    }
+                }
+            }
             ConfirmAction::LoadModlist(path) => {
                 // This is handled in the load flow, so just acknowledge
                 let mut state = app.state.write().await;
@@ -6593,8 +9552,19 @@ impl Tui {
     async fn refresh_mods(&self, app: &mut App) -> Result<()> {
         if let Some(game) = app.active_game().await {
             let mods = app.mods.list_mods(&game.id).await?;
+            let script_extender_warning = app
+                .mods
+                .script_extender_check(&game)
+                .ok()
+                .and_then(|check| check.mismatch());
+            let missing_dlc = app.mods.missing_dlc(&game).unwrap_or_default();
             let mut state = app.state.write().await;
             state.installed_mods = mods;
+            state.script_extender_warning = script_extender_warning;
+            state.missing_dlc = missing_dlc.into_iter().map(|m| (m.mod_id, m)).collect();
+            let existing: std::collections::HashSet<i64> =
+                state.installed_mods.iter().map(|m| m.id).collect();
+            state.marked_mod_ids.retain(|id| existing.contains(id));
             if !state.installed_mods.is_empty() {
                 state.selected_mod_index =
                     state.selected_mod_index.min(state.installed_mods.len() - 1);
@@ -6605,11 +9575,79 @@ impl Tui {
         Ok(())
     }
 
+    /// Record the plugin list before an edit so it can be undone
+    fn record_plugin_edit(state: &mut AppState, label: &str) {
+        state.plugin_history.record(label, &state.plugins);
+    }
+
+    /// Undo (or redo) the last plugin edit
+    fn step_plugin_history(state: &mut AppState, redo: bool) {
+        let label = if redo {
+            state.plugin_history.redo(&mut state.plugins)
+        } else {
+            state.plugin_history.undo(&mut state.plugins)
+        };
+        let Some(label) = label else {
+            state.set_status(if redo {
+                "Nothing to redo"
+            } else {
+                "Nothing to undo"
+            });
+            return;
+        };
+        for (i, p) in state.plugins.iter_mut().enumerate() {
+            p.load_order = i;
+        }
+        state.selected_plugin_index = state
+            .selected_plugin_index
+            .min(state.plugins.len().saturating_sub(1));
+        state.plugin_dirty = true;
+        state.set_status(format!(
+            "{} {} (press 's' to save)",
+            if redo { "Redid" } else { "Undid" },
+            label
+        ));
+    }
+
+    /// Record the load order before an edit so it can be undone
+    fn record_load_order_edit(state: &mut AppState, label: &str) {
+        state
+            .load_order_history
+            .record(label, &state.load_order_mods);
+    }
+
+    /// Undo (or redo) the last load order edit
+    fn step_load_order_history(state: &mut AppState, redo: bool) {
+        let label = if redo {
+            state.load_order_history.redo(&mut state.load_order_mods)
+        } else {
+            state.load_order_history.undo(&mut state.load_order_mods)
+        };
+        let Some(label) = label else {
+            state.set_status(if redo {
+                "Nothing to redo"
+            } else {
+                "Nothing to undo"
+            });
+            return;
+        };
+        state.load_order_index = state
+            .load_order_index
+            .min(state.load_order_mods.len().saturating_sub(1));
+        state.load_order_dirty = true;
+        state.set_status(format!(
+            "{} {} (press 's' to save)",
+            if redo { "Redid" } else { "Undid" },
+            label
+        ));
+    }
+
     async fn refresh_plugins(&self, app: &mut App) -> Result<()> {
         if let Some(game) = app.active_game().await {
             if let Ok(plugins_list) = plugins::get_plugins(&game) {
                 let mut state = app.state.write().await;
                 state.plugins = plugins_list;
+                state.plugin_history.clear();
                 if !state.plugins.is_empty() {
                     state.selected_plugin_index =
                         state.selected_plugin_index.min(state.plugins.len() - 1);
@@ -6702,46 +9740,50 @@ impl Tui {
 
     /// Run bulk install (static method for background task)
     async fn run_bulk_install(
-        state: Arc<RwLock<AppState>>,
+        events: EventSender,
         mods: Arc<crate::mods::ModManager>,
+        cancel: Arc<AtomicBool>,
+        game_id: Option<String>,
         directory: &str,
     ) -> Result<()> {
-        // Get active game
-        let game_id = {
-            let state = state.read().await;
-            state.active_game.as_ref().map(|g| g.id.clone())
-        };
-
-        let game_id = match game_id {
-            Some(id) => id,
+        let result = match game_id {
+            Some(game_id) => {
+                Self::bulk_install_from_directory_impl(
+                    events.clone(),
+                    mods,
+                    cancel,
+                    &game_id,
+                    directory,
+                )
+                .await
+            }
             None => {
-                let mut state = state.write().await;
-                state.set_status("No active game selected".to_string());
-                return Ok(());
+                events.status("No active game selected".to_string());
+                Ok(())
             }
         };
 
-        Self::bulk_install_from_directory_impl(state, mods, &game_id, directory).await
+        events.update(|st| st.bulk_install_running = false);
+        result
     }
 
     /// Bulk install all mod archives from a directory (implementation)
     async fn bulk_install_from_directory_impl(
-        state: Arc<RwLock<AppState>>,
+        events: EventSender,
         mods: Arc<crate::mods::ModManager>,
+        cancel: Arc<AtomicBool>,
         game_id: &str,
         directory: &str,
     ) -> Result<()> {
         let path = std::path::Path::new(directory);
 
         if !path.exists() {
-            let mut st = state.write().await;
-            st.set_status(format!("ERROR: Directory does not exist: {}", directory));
+            events.status(format!("ERROR: Directory does not exist: {}", directory));
             return Ok(());
         }
 
         if !path.is_dir() {
-            let mut st = state.write().await;
-            st.set_status(format!("ERROR: Not a directory: {}", directory));
+            events.status(format!("ERROR: Not a directory: {}", directory));
             return Ok(());
         }
 
@@ -6749,8 +9791,7 @@ impl Tui {
         let entries = match std::fs::read_dir(path) {
             Ok(e) => e,
             Err(e) => {
-                let mut st = state.write().await;
-                st.set_status(format!("ERROR: Cannot read directory: {}", e));
+                events.failure("Cannot read directory", e);
                 return Ok(());
             }
         };
@@ -6767,8 +9808,7 @@ impl Tui {
             .collect();
 
         if archives.is_empty() {
-            let mut st = state.write().await;
-            st.set_status(format!(
+            events.status(format!(
                 "No mod archives (.zip, .7z, .rar) found in: {}",
                 directory
             ));
@@ -6776,174 +9816,201 @@ impl Tui {
         }
 
         // Show starting message
-        {
-            let mut st = state.write().await;
-            if st.bulk_install_running {
-                st.set_status("Bulk install is already running".to_string());
-                return Ok(());
-            }
-            st.bulk_install_running = true;
-            st.bulk_install_cancel_requested = false;
-            st.set_status(format!(
-                "Starting bulk install: {} archives found in {}",
-                archives.len(),
-                directory
-            ));
-        }
+        events.status(format!(
+            "Starting bulk install: {} archives found in {}",
+            archives.len(),
+            directory
+        ));
 
+        let archives: Vec<std::path::PathBuf> = archives.iter().map(|e| e.path()).collect();
         let total = archives.len();
         let mut installed = 0;
         let mut failed = 0;
-        let mut skipped = 0;
-        let mut cancelled = false;
+        let mut pending_wizards = Vec::new();
 
-        // Install each archive
-        for (idx, entry) in archives.iter().enumerate() {
-            {
-                let st = state.read().await;
-                if st.bulk_install_cancel_requested {
-                    cancelled = true;
-                    break;
-                }
-            }
-
-            let archive_path = entry.path();
+        // Archives are extracted by several workers at once; the progress
+        // overlay follows whichever archive reported last
+        let progress = |idx: usize, archive_path: &std::path::Path| {
+            let progress_events = events.clone();
             let filename = archive_path
                 .file_name()
                 .and_then(|n| n.to_str())
-                .unwrap_or("unknown");
-
-            // Create progress callback with bulk install context
-            let state_clone = state.clone();
-            let filename_clone = filename.to_string();
+                .unwrap_or("unknown")
+                .to_string();
             let current_index = idx + 1;
-            let total_mods = total;
-
-            let progress_callback = std::sync::Arc::new(
+            let callback: crate::mods::ProgressCallback = std::sync::Arc::new(
                 move |current_file: String, processed: usize, total_files: usize| {
-                    // Use try_write to avoid blocking within the async runtime
-                    if let Ok(mut st) = state_clone.try_write() {
-                        let percent = if total_files > 0 {
-                            ((processed as f64 / total_files as f64) * 100.0) as u16
-                        } else {
-                            0
-                        };
+                    let percent = if total_files > 0 {
+                        ((processed as f64 / total_files as f64) * 100.0) as u16
+                    } else {
+                        0
+                    };
 
-                        st.installation_progress = Some(crate::app::state::InstallProgress {
-                            percent,
-                            current_file,
-                            total_files,
-                            processed_files: processed,
-                            // Bulk install context
-                            current_mod_name: Some(filename_clone.clone()),
-                            current_mod_index: Some(current_index),
-                            total_mods: Some(total_mods),
-                        });
-                    }
+                    progress_events.install_progress(Some(crate::app::state::InstallProgress {
+                        percent,
+                        current_file,
+                        total_files,
+                        processed_files: processed,
+                        // Bulk install context
+                        current_mod_name: Some(filename.clone()),
+                        current_mod_index: Some(current_index),
+                        total_mods: Some(total),
+                    }));
                 },
             );
+            Some(callback)
+        };
 
-            // Install
-            match mods
-                .install_from_archive(
-                    game_id,
-                    archive_path.to_str().unwrap(),
-                    Some(progress_callback),
-                    None, // No Nexus ID for bulk installs
-                    None,
-                    None,
-                )
-                .await
-            {
+        mods.install_archives(game_id, &archives, &cancel, progress, |outcome| {
+            let filename = outcome
+                .archive
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown")
+                .to_string();
+            let line = match outcome.result {
                 Ok(crate::mods::InstallResult::Completed(installed_mod)) => {
                     installed += 1;
-                    tracing::info!("[{}/{}] Installed: {}", idx + 1, total, installed_mod.name);
-                    {
-                        let mut st = state.write().await;
-                        if let Some(ref mut progress) = st.installation_progress {
-                            progress.percent = 100;
-                            progress.current_file = format!("✓ Completed: {}", installed_mod.name);
-                        }
-                    }
-                    tokio::time::sleep(tokio::time::Duration::from_millis(220)).await;
+                    tracing::info!(
+                        "[{}/{}] Installed: {}",
+                        outcome.index + 1,
+                        total,
+                        installed_mod.name
+                    );
+                    format!("✓ Completed: {}", installed_mod.name)
                 }
-                Ok(crate::mods::InstallResult::RequiresWizard(_context)) => {
-                    // Skip FOMOD wizards in bulk install
-                    skipped += 1;
-                    tracing::warn!(
-                        "[{}/{}] Skipped: {} requires FOMOD wizard",
-                        idx + 1,
+                Ok(crate::mods::InstallResult::RequiresWizard(context)) => {
+                    // Wizards are answered one at a time once the rest is done
+                    tracing::info!(
+                        "[{}/{}] {} requires FOMOD wizard",
+                        outcome.index + 1,
                         total,
                         filename
                     );
-
-                    {
-                        let mut st = state.write().await;
-                        if let Some(ref mut progress) = st.installation_progress {
-                            progress.current_file =
-                                format!("⊘ Skipped: {} (needs wizard)", filename);
-                        }
-                    }
-                    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                    pending_wizards.push(context);
+                    format!("⧗ Waiting for wizard: {}", filename)
+                }
+                Err(e) if crate::mods::is_cancelled(&e) => {
+                    tracing::info!(
+                        "[{}/{}] Stopped extracting {}",
+                        outcome.index + 1,
+                        total,
+                        filename
+                    );
+                    format!("⊘ Stopped: {}", filename)
                 }
                 Err(e) => {
+                    let msg = e.to_string();
                     failed += 1;
-                    let error_msg = format!("{}", e);
                     tracing::error!(
                         "[{}/{}] Failed to install {}: {}",
-                        idx + 1,
+                        outcome.index + 1,
                         total,
                         filename,
-                        error_msg
+                        msg
                     );
-
-                    // Show error briefly before moving to next mod
-                    {
-                        let mut st = state.write().await;
-                        if let Some(ref mut progress) = st.installation_progress {
-                            progress.current_file = format!("✗ Failed: {}", error_msg);
-                        }
-                    }
-                    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
+                    format!("✗ Failed: {}", msg)
                 }
-            }
-        }
+            };
+            events.update(move |st| {
+                if let Some(ref mut progress) = st.installation_progress {
+                    progress.current_file = line;
+                }
+            });
+        })
+        .await;
+        let cancelled = cancel.load(Ordering::Relaxed);
 
         // Final cleanup and summary
-        {
-            let mut st = state.write().await;
+        let updated_mods = mods.list_mods(game_id).await.ok();
+
+        let summary = if cancelled {
+            // Installers waiting for the wizard are dropped with the rest
+            pending_wizards.clear();
+            format!(
+                "⊘ Bulk install cancelled: {} installed, {} failed",
+                installed, failed
+            )
+        } else if !pending_wizards.is_empty() {
+            format!(
+                "✓ Bulk install: {} installed, {} failed; {} FOMOD installer(s) need the wizard",
+                installed,
+                failed,
+                pending_wizards.len()
+            )
+        } else if failed > 0 {
+            format!(
+                "✓ Bulk install complete: {} installed, {} failed (check logs for details)",
+                installed, failed
+            )
+        } else {
+            format!(
+                "✓ Bulk install complete: {} mods installed successfully!",
+                installed
+            )
+        };
+
+        events.update(move |st| {
             st.installation_progress = None;
-            st.bulk_install_running = false;
-            st.bulk_install_cancel_requested = false;
 
             // Reload mods list
-            if let Ok(updated_mods) = mods.list_mods(game_id).await {
+            if let Some(updated_mods) = updated_mods {
                 st.installed_mods = updated_mods;
             }
 
-            let summary = if cancelled {
-                format!(
-                    "⊘ Bulk install cancelled: {} installed, {} skipped, {} failed",
-                    installed, skipped, failed
-                )
-            } else if failed > 0 || skipped > 0 {
-                format!(
-                    "✓ Bulk install complete: {} installed, {} skipped, {} failed (check logs for details)",
-                    installed, skipped, failed
-                )
-            } else {
-                format!(
-                    "✓ Bulk install complete: {} mods installed successfully!",
-                    installed
-                )
-            };
-
             st.set_status(summary);
-        }
+
+            st.pending_fomod_installs.extend(pending_wizards);
+            Self::open_next_fomod_wizard(&mods, st);
+        });
 
         Ok(())
     }
 
+    /// Show the FOMOD wizard for an installer that needs it
+    fn open_fomod_wizard(
+        mods: &crate::mods::ModManager,
+        state: &mut AppState,
+        context: &crate::mods::FomodInstallContext,
+    ) {
+        use crate::app::state::{FomodWizardState, WizardPhase};
+        use crate::mods::fomod::wizard::init_wizard_state_with_env;
+
+        let mut env = mods.fomod_environment(&context.game_id).unwrap_or_default();
+        if let Some(game) = &state.active_game {
+            env = env.with_game(game);
+        }
+        let wizard = init_wizard_state_with_env(&context.installer.config, env);
+        state.fomod_wizard_state = Some(FomodWizardState {
+            installer: context.installer.clone(),
+            wizard,
+            current_step: 0,
+            current_group: 0,
+            selected_option: 0,
+            validation_errors: Vec::new(),
+            mod_name: context.mod_name.clone(),
+            staging_path: context.staging_path.clone(),
+            preview_files: None,
+            preview_scroll: 0,
+            phase: WizardPhase::Overview,
+            existing_mod_id: None,
+        });
+        state.goto(Screen::FomodWizard);
+    }
+
+    /// Open the wizard for the next FOMOD installer left by a bulk install
+    fn open_next_fomod_wizard(mods: &crate::mods::ModManager, state: &mut AppState) {
+        let Some(context) = state.pending_fomod_installs.pop_front() else {
+            return;
+        };
+        let remaining = state.pending_fomod_installs.len();
+        Self::open_fomod_wizard(mods, state, &context);
+        state.set_status(format!(
+            "FOMOD installer: {} ({} more waiting)",
+            context.mod_name, remaining
+        ));
+    }
+
     /// Load and process a Nexus Mods collection
     async fn load_collection(&self, app: &mut App, path: &str) -> Result<()> {
         use crate::collections::load_collection;
@@ -6977,10 +10044,7 @@ impl Tui {
         };
 
         // Get currently installed mods with nexus IDs
-        let installed_mods: Vec<_> = match app.db.get_mods_for_game(&game_id) {
-            Ok(mods) => mods,
-            Err(_) => Vec::new(),
-        };
+        let installed_mods: Vec<_> = app.db.get_mods_for_game(&game_id).unwrap_or_default();
 
         let installed_mod_ids: Vec<i64> = installed_mods
             .iter()
@@ -7062,7 +10126,7 @@ impl Tui {
     }
 }
 
-impl Drop for Tui {
+impl<B: Backend> Drop for Tui<B> {
     fn drop(&mut self) {
         let _ = self.restore();
     }
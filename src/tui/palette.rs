@@ -0,0 +1,218 @@
+//! Command palette
+//!
+//! Ctrl+P opens a prompt that fuzzy-matches over the common actions of every
+//! screen, the profiles and the installed mods. Picking an action replays the
+//! key it is bound to on its screen, so the palette always does exactly what
+//! the key would.
+
+use crate::app::{AppState, Screen};
+use crossterm::event::KeyCode;
+
+/// Matches listed in the palette
+pub const MAX_MATCHES: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteAction {
+    /// Open a screen
+    Open(Screen),
+    /// Press `KeyCode` on `Screen`
+    Key(Screen, KeyCode),
+    /// Switch to the profile at this index of `AppState::profiles`
+    Profile(usize),
+    /// Show the details of the installed mod with this ID
+    Mod(i64),
+    Help,
+    LogPane,
+    Notifications,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    pub label: String,
+    /// Where the action lives without the palette, e.g. "D on Mods"
+    pub hint: String,
+    pub action: PaletteAction,
+}
+
+impl PaletteEntry {
+    fn new(label: impl Into<String>, hint: impl Into<String>, action: PaletteAction) -> Self {
+        Self {
+            label: label.into(),
+            hint: hint.into(),
+            action,
+        }
+    }
+}
+
+/// Screens that can be opened from the palette, with their usual key
+const SCREENS: &[(&str, &str, Screen)] = &[
+    ("Mods", "F1", Screen::Mods),
+    ("Plugins", "F2", Screen::Plugins),
+    ("Profiles", "F3", Screen::Profiles),
+    ("Settings", "F4", Screen::Settings),
+    ("Import", "F5", Screen::Import),
+    ("Download queue", "F6", Screen::DownloadQueue),
+    ("Nexus catalog", "F7", Screen::NexusCatalog),
+    ("Modlists", "F8", Screen::ModlistEditor),
+    ("Extensions", "F9", Screen::Extensions),
+    ("Storage", "F10", Screen::Storage),
+    ("Downloads", "F11", Screen::Downloads),
+    ("History", "F12", Screen::History),
+    ("Tool output", "F4 o", Screen::ToolOutput),
+    ("INI files", "F4 i", Screen::IniEditor),
+    ("Saves", "F3 s", Screen::Saves),
+    ("Game selection", "g", Screen::GameSelect),
+];
+
+/// Screen actions, replayed as their key on their screen
+const ACTIONS: &[(&str, Screen, char)] = &[
+    ("Deploy mods", Screen::Mods, 'D'),
+    ("Install mod from file", Screen::Mods, 'i'),
+    ("Browse Nexus Mods", Screen::Mods, 'b'),
+    ("Check for mod updates", Screen::Mods, 'U'),
+    ("Save modlist", Screen::Mods, 'S'),
+    ("Load modlist", Screen::Mods, 'L'),
+    ("Open load order", Screen::Mods, 'o'),
+    ("Sort plugins", Screen::Plugins, 'S'),
+    ("Sort plugins with LOOT", Screen::Plugins, 'L'),
+    ("Deploy plugins", Screen::Plugins, 'D'),
+    ("New profile", Screen::Profiles, 'n'),
+    ("Process download queue", Screen::DownloadQueue, 'p'),
+    ("Rescan storage", Screen::Storage, 'r'),
+    ("Clear finished downloads", Screen::Downloads, 'c'),
+];
+
+fn screen_name(screen: Screen) -> &'static str {
+    SCREENS
+        .iter()
+        .find(|(_, _, s)| *s == screen)
+        .map(|(name, _, _)| *name)
+        .unwrap_or("")
+}
+
+/// Everything the palette can run in the current state
+pub fn entries(state: &AppState) -> Vec<PaletteEntry> {
+    let mut entries: Vec<PaletteEntry> = SCREENS
+        .iter()
+        .map(|(name, key, screen)| {
+            PaletteEntry::new(format!("Open {}", name), *key, PaletteAction::Open(*screen))
+        })
+        .collect();
+    entries.extend(ACTIONS.iter().map(|(label, screen, key)| {
+        PaletteEntry::new(
+            *label,
+            format!("{} on {}", key, screen_name(*screen)),
+            PaletteAction::Key(*screen, KeyCode::Char(*key)),
+        )
+    }));
+    entries.push(PaletteEntry::new("Help", "?", PaletteAction::Help));
+    entries.push(PaletteEntry::new(
+        "Toggle log pane",
+        "Ctrl+L",
+        PaletteAction::LogPane,
+    ));
+    entries.push(PaletteEntry::new(
+        "Notification history",
+        "Ctrl+N",
+        PaletteAction::Notifications,
+    ));
+    entries.extend(state.profiles.iter().enumerate().map(|(i, p)| {
+        PaletteEntry::new(
+            format!("Switch profile: {}", p.name),
+            "Enter on Profiles",
+            PaletteAction::Profile(i),
+        )
+    }));
+    entries.extend(state.installed_mods.iter().map(|m| {
+        PaletteEntry::new(
+            format!("Mod: {}", m.name),
+            if m.enabled { "enabled" } else { "disabled" },
+            PaletteAction::Mod(m.id),
+        )
+    }));
+    entries
+}
+
+/// How well `query` matches `text`: every query character must appear in
+/// order. Runs of consecutive characters and matches at the start of words
+/// score higher; `None` when it doesn't match at all.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0i64;
+    let mut pos = 0usize;
+    let mut previous: Option<usize> = None;
+    for q in query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+    {
+        let found = pos + text[pos..].iter().position(|c| *c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - pos) as i64 / 4;
+        previous = Some(found);
+        pos = found + 1;
+    }
+    // Prefer shorter labels among equal matches
+    Some(score * 100 - text.len() as i64)
+}
+
+/// Entries matching `query`, best first
+pub fn matches(state: &AppState, query: &str) -> Vec<PaletteEntry> {
+    let mut scored: Vec<(i64, PaletteEntry)> = entries(state)
+        .into_iter()
+        .filter_map(|e| fuzzy_score(query, &e.label).map(|score| (score, e)))
+        .collect();
+    // Stable, so ties keep actions ahead of mods
+    scored.sort_by_key(|s| std::cmp::Reverse(s.0));
+    scored
+        .into_iter()
+        .take(MAX_MATCHES)
+        .map(|(_, e)| e)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_matches() {
+        assert!(fuzzy_score("dpl", "Deploy mods").is_some());
+        assert!(fuzzy_score("xyz", "Deploy mods").is_none());
+        assert!(
+            fuzzy_score("sort", "Sort plugins") > fuzzy_score("sort", "Nexus catalog: s o r t")
+        );
+
+        let mut state = AppState::new(None);
+        state.installed_mods.push(crate::mods::InstalledMod {
+            id: 7,
+            name: "SkyUI".to_string(),
+            version: "5.2".to_string(),
+            author: None,
+            enabled: true,
+            priority: 0,
+            nexus_mod_id: None,
+            nexus_file_id: None,
+            file_count: 1,
+            install_path: Default::default(),
+            category_id: None,
+            installed_at: String::new(),
+            size: 0,
+        });
+        assert_eq!(matches(&state, "skyui")[0].action, PaletteAction::Mod(7));
+        assert_eq!(
+            matches(&state, "deploy")[0].action,
+            PaletteAction::Key(Screen::Mods, KeyCode::Char('D'))
+        );
+        assert_eq!(
+            matches(&state, "queue")[0].action,
+            PaletteAction::Open(Screen::DownloadQueue)
+        );
+    }
+}
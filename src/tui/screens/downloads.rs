@@ -0,0 +1,186 @@
+//! Downloads TUI screen
+//!
+//! Every download in flight, from the queue or a one-off file pick, with its
+//! progress, speed and destination, plus those finished this session.
+
+use crate::app::state::AppState;
+use crate::app::App;
+use crate::mods::format_bytes;
+use crate::queue::{Transfer, TransferState};
+use crate::tui::hit;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+fn format_eta(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+fn state_label(transfer: &Transfer) -> (String, Color) {
+    match &transfer.state {
+        TransferState::Active => {
+            let speed = format!("{}/s", format_bytes(transfer.speed as u64));
+            match transfer.eta_secs() {
+                Some(eta) => (format!("{}  {} left", speed, format_eta(eta)), Color::Cyan),
+                None => (speed, Color::Cyan),
+            }
+        }
+        TransferState::Completed => ("done".to_string(), Color::Green),
+        TransferState::Cancelled => ("cancelled".to_string(), Color::DarkGray),
+        TransferState::Failed(_) => ("failed".to_string(), Color::Red),
+    }
+}
+
+/// Render the Downloads screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    if state.downloads.is_empty() {
+        let empty = Paragraph::new(
+            "No downloads yet. Process the queue (F6) or pick a file on Browse to start one.",
+        )
+        .block(Block::default().borders(Borders::ALL).title(" Downloads "));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(7)])
+        .split(area);
+
+    // Newest first
+    let items: Vec<ListItem> = state
+        .downloads
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, t)| {
+            let (label, color) = state_label(t);
+            let size = if t.total_bytes > 0 {
+                format!(
+                    "{} / {}",
+                    format_bytes(t.downloaded_bytes),
+                    format_bytes(t.total_bytes)
+                )
+            } else {
+                format_bytes(t.downloaded_bytes)
+            };
+            let percent = t
+                .percent()
+                .map(|p| format!("{:>3}%", p))
+                .unwrap_or_else(|| "   ?".to_string());
+            let style = if i == state.selected_download_index {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", percent), Style::default().fg(color)),
+                Span::raw(t.name.clone()),
+                Span::styled(
+                    format!("  {}  {}", size, label),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+            .style(style)
+        })
+        .collect();
+    let active = state.downloads.iter().filter(|t| t.is_active()).count();
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(format!(
+        " Downloads ({} active, {} total) ",
+        active,
+        state.downloads.len()
+    )));
+    let mut list_state = ratatui::widgets::ListState::default();
+    list_state.select(Some(state.selected_download_index));
+    hit::render_list(f, state.current_screen, list, chunks[0], &mut list_state);
+
+    let Some(selected) = selected(state) else {
+        return;
+    };
+    let detail = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(2)])
+        .split(chunks[1]);
+    let (label, color) = state_label(selected);
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", selected.name)),
+        )
+        .gauge_style(Style::default().fg(color).bg(Color::Black))
+        .percent(selected.percent().unwrap_or(0))
+        .label(label);
+    f.render_widget(gauge, detail[0]);
+
+    let mut lines = vec![Line::from(format!(
+        "Destination: {}",
+        selected.destination.display()
+    ))];
+    if let TransferState::Failed(error) = &selected.state {
+        lines.push(Line::from(Span::styled(
+            format!("Error: {}", error),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    let info = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM));
+    f.render_widget(info, detail[1]);
+}
+
+/// The selected transfer; the list shows the newest first
+fn selected(state: &AppState) -> Option<&Transfer> {
+    state
+        .downloads
+        .iter()
+        .rev()
+        .nth(state.selected_download_index)
+}
+
+/// Handle keys on the Downloads screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let count = state.downloads.len();
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if count > 0 && state.selected_download_index < count - 1 {
+                state.selected_download_index += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.selected_download_index = state.selected_download_index.saturating_sub(1);
+        }
+        KeyCode::Char('x') => {
+            let Some(transfer) = selected(&state) else {
+                return Ok(());
+            };
+            let name = transfer.name.clone();
+            if app.transfers.cancel(transfer.id) {
+                state.set_status_info(format!("Cancelling download of {}", name));
+            } else {
+                state.set_status_info(format!("{} isn't downloading", name));
+            }
+        }
+        KeyCode::Char('c') => {
+            app.transfers.clear_finished();
+            state.selected_download_index = 0;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+//! Extensions TUI screen
+
+use crate::app::state::AppState;
+use crate::app::App;
+use crate::extensions::{Extension, ExtensionRegistry};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    Screen,
+    Command,
+}
+
+/// A runnable screen or command contributed by an extension
+struct Entry<'a> {
+    extension: &'a Extension,
+    kind: EntryKind,
+    name: &'a str,
+    label: &'a str,
+}
+
+fn entries(registry: &ExtensionRegistry) -> Vec<Entry<'_>> {
+    let mut out = Vec::new();
+    for ext in registry.extensions() {
+        for s in &ext.manifest.screens {
+            out.push(Entry {
+                extension: ext,
+                kind: EntryKind::Screen,
+                name: &s.name,
+                label: if s.title.is_empty() {
+                    &s.name
+                } else {
+                    &s.title
+                },
+            });
+        }
+        for c in &ext.manifest.commands {
+            out.push(Entry {
+                extension: ext,
+                kind: EntryKind::Command,
+                name: &c.name,
+                label: if c.description.is_empty() {
+                    &c.name
+                } else {
+                    &c.description
+                },
+            });
+        }
+    }
+    out
+}
+
+/// Render the Extensions screen
+pub fn render(f: &mut Frame, area: Rect, app: &App, state: &AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    let registry = &app.extensions;
+    let list_entries = entries(registry);
+
+    let mut items: Vec<ListItem> = list_entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let tag = match e.kind {
+                EntryKind::Screen => "screen ",
+                EntryKind::Command => "command",
+            };
+            let style = if i == state.selected_extension_index {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", tag), Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("{}: ", e.extension.name()),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(e.label.to_string()),
+            ]))
+            .style(style)
+        })
+        .collect();
+
+    for (dir, err) in registry.errors() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            format!("✗ {}: {}", dir.display(), err),
+            Style::default().fg(Color::Red),
+        ))));
+    }
+
+    let list_title = format!(" Extensions ({}) ", registry.extensions().len());
+    if items.is_empty() {
+        let dirs = app
+            .config
+            .try_read()
+            .map(|c| c.extension_dirs())
+            .unwrap_or_default();
+        let mut lines = vec![
+            Line::from("No extensions installed."),
+            Line::from(""),
+            Line::from("Place an extension folder with an extension.toml in:"),
+        ];
+        for dir in dirs {
+            lines.push(Line::from(format!("  {}", dir.display())));
+        }
+        let empty = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(list_title));
+        f.render_widget(empty, chunks[0]);
+    } else {
+        let list = List::new(items).block(Block::default().borders(Borders::ALL).title(list_title));
+        f.render_widget(list, chunks[0]);
+    }
+
+    let output_title = if state.extension_running {
+        " Running... ".to_string()
+    } else {
+        state
+            .extension_output_title
+            .as_deref()
+            .map(|t| format!(" {} ", t))
+            .unwrap_or_else(|| " Output ".to_string())
+    };
+    let output_lines: Vec<Line> = state
+        .extension_output
+        .iter()
+        .map(|l| Line::from(l.as_str()))
+        .collect();
+    let output = Paragraph::new(output_lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(output_title));
+    f.render_widget(output, chunks[1]);
+}
+
+/// Handle keys on the Extensions screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let count = entries(&app.extensions).len();
+    let mut state = app.state.write().await;
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if count > 0 && state.selected_extension_index < count - 1 {
+                state.selected_extension_index += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            if state.selected_extension_index > 0 {
+                state.selected_extension_index -= 1;
+            }
+        }
+        KeyCode::Char('c') => {
+            state.extension_output.clear();
+            state.extension_output_title = None;
+        }
+        KeyCode::Enter => {
+            if state.extension_running {
+                state.set_status_info("An extension is already running");
+                return Ok(());
+            }
+            let list_entries = entries(&app.extensions);
+            let Some(entry) = list_entries.get(state.selected_extension_index) else {
+                return Ok(());
+            };
+            let extension = entry.extension.name().to_string();
+            let name = entry.name.to_string();
+            let kind = entry.kind;
+            let title = format!("{}: {}", extension, entry.label);
+
+            state.extension_running = true;
+            state.set_status(format!("Running {}...", title));
+            drop(state);
+
+            let registry = app.extensions.clone();
+            let context = app.extension_context().await;
+            let events = app.events.clone();
+
+            // Extensions may take a while; run them without blocking the UI
+            tokio::spawn(async move {
+                let result = match kind {
+                    EntryKind::Screen => registry.render_screen(&extension, &name, context).await,
+                    EntryKind::Command => {
+                        registry.run_command(&extension, &name, &[], context).await
+                    }
+                };
+
+                events.update(move |state| {
+                    state.extension_running = false;
+                    state.extension_output_title = Some(title);
+                    match result {
+                        Ok(response) => {
+                            state.extension_output = response.lines;
+                            match response.message {
+                                Some(message) => state.set_status_success(message),
+                                None => state.set_status_success("Extension finished"),
+                            }
+                        }
+                        Err(e) => {
+                            state.extension_output = vec![format!("{:#}", e)];
+                            state.set_status_error_chain("Extension failed", &e);
+                        }
+                    }
+                });
+            });
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
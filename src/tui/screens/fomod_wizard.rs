@@ -4,7 +4,8 @@
 //! live validation, and visual feedback.
 
 use crate::app::state::{AppState, FomodWizardState, WizardPhase};
-use crate::mods::fomod::{validation, PluginType};
+use crate::mods::fomod::{validation, FileChange, InstallPreview, PluginType};
+use crate::tui::graphics;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -43,11 +44,22 @@ fn draw_overview(f: &mut Frame, wizard_state: &FomodWizardState, area: Rect) {
         .borders(Borders::ALL)
         .style(Style::default());
 
-    let inner = block.inner(area);
+    let mut inner = block.inner(area);
     f.render_widget(block, area);
 
     let config = &wizard_state.installer.config;
 
+    // Module image to the right of the summary
+    if let Some(image) = &config.module_image {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+        let path = wizard_state.installer.resolve_path(&image.path);
+        graphics::draw_preview(f, chunks[1], path.as_deref(), &image.path);
+        inner = chunks[0];
+    }
+
     let mut lines = vec![
         Line::from(Span::styled(
             config.module_name.as_str(),
@@ -58,19 +70,6 @@ fn draw_overview(f: &mut Frame, wizard_state: &FomodWizardState, area: Rect) {
         Line::from(""),
     ];
 
-    // Module image (if available)
-    if let Some(image) = &config.module_image {
-        lines.push(Line::from(Span::styled(
-            "Module Image:",
-            Style::default().add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from(Span::styled(
-            format!("  {}", image.path),
-            Style::default().fg(Color::Cyan),
-        )));
-        lines.push(Line::from(""));
-    }
-
     // Add step count
     let step_count = wizard_state.installer.steps().len();
     lines.push(Line::from(format!("Installation Steps: {}", step_count)));
@@ -126,16 +125,23 @@ fn draw_step_list(f: &mut Frame, wizard_state: &FomodWizardState, area: Rect) {
         .iter()
         .enumerate()
         .map(|(i, step)| {
+            let config = &wizard_state.installer.config;
+            let visible = wizard_state.wizard.is_step_visible(config, i);
             let style = if i == wizard_state.current_step {
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD)
+            } else if !visible {
+                // Skipped because of earlier choices or the load order
+                Style::default().fg(Color::DarkGray)
             } else {
                 Style::default()
             };
 
             let prefix = if i == wizard_state.current_step {
                 "▶ "
+            } else if !visible {
+                "- "
             } else if i < wizard_state.current_step {
                 "✓ "
             } else {
@@ -253,7 +259,7 @@ fn draw_option_groups(f: &mut Frame, wizard_state: &FomodWizardState, area: Rect
 
     // Validation indicator
     let group_validation = validation::validate_group(
-        &group,
+        group,
         &selections,
         wizard_state.current_step,
         wizard_state.current_group,
@@ -284,7 +290,7 @@ fn draw_option_details(f: &mut Frame, wizard_state: &FomodWizardState, area: Rec
         .title(" Option Details ")
         .borders(Borders::ALL);
 
-    let inner = block.inner(area);
+    let mut inner = block.inner(area);
     f.render_widget(block, area);
 
     let plugin = match plugin {
@@ -296,6 +302,17 @@ fn draw_option_details(f: &mut Frame, wizard_state: &FomodWizardState, area: Rec
         }
     };
 
+    // Option image above the details
+    if let Some(image) = &plugin.image {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(45), Constraint::Min(0)])
+            .split(inner);
+        let path = wizard_state.installer.resolve_path(&image.path);
+        graphics::draw_preview(f, chunks[0], path.as_deref(), &image.path);
+        inner = chunks[1];
+    }
+
     let mut lines = vec![
         Line::from(Span::styled(
             plugin.name.as_str(),
@@ -336,23 +353,6 @@ fn draw_option_details(f: &mut Frame, wizard_state: &FomodWizardState, area: Rec
         lines.push(Line::from(""));
     }
 
-    // Image path (if available)
-    if let Some(image) = &plugin.image {
-        lines.push(Line::from(Span::styled(
-            "Image:",
-            Style::default().add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from(Span::styled(
-            format!("  {}", image.path),
-            Style::default().fg(Color::Cyan),
-        )));
-        lines.push(Line::from(Span::styled(
-            "  (Press 'i' to open in external viewer)",
-            Style::default().fg(Color::DarkGray),
-        )));
-        lines.push(Line::from(""));
-    }
-
     // File count
     if let Some(files) = &plugin.files {
         let file_count = files.files.len() + files.folders.len();
@@ -409,10 +409,10 @@ fn draw_summary(f: &mut Frame, wizard_state: &FomodWizardState, area: Rect) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    // Split into two columns: selections (60%) and file preview (40%)
+    // Split into two columns: selections (40%) and file preview (60%)
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
         .split(inner);
 
     // Left panel: Selected options
@@ -459,62 +459,117 @@ fn draw_summary(f: &mut Frame, wizard_state: &FomodWizardState, area: Rect) {
     let selections_text = Paragraph::new(selection_lines).wrap(Wrap { trim: true });
     f.render_widget(selections_text, chunks[0]);
 
-    // Right panel: File preview and stats
-    let file_instructions = wizard_state
-        .wizard
-        .get_files_to_install(&wizard_state.installer.config);
+    // Right panel: file tree preview
+    let preview_area = chunks[1];
+    let Some(preview) = &wizard_state.preview_files else {
+        let text = Paragraph::new("File preview unavailable (see logs)")
+            .block(Block::default().title(" Files ").borders(Borders::LEFT));
+        f.render_widget(text, preview_area);
+        return;
+    };
 
-    let mut file_lines = vec![
-        Line::from(Span::styled(
-            "Installation Details:",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            format!("Files to Install: {}", file_instructions.len()),
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-    ];
+    let reconfiguring = wizard_state.existing_mod_id.is_some();
+    let mut summary = format!("{} files", preview.files.len());
+    if reconfiguring {
+        summary.push_str(&format!(
+            ": {} new, {} changed, {} unchanged, {} removed",
+            preview.count(FileChange::Added),
+            preview.count(FileChange::Modified),
+            preview.count(FileChange::Unchanged),
+            preview.removed.len()
+        ));
+    }
+    if preview.overwrite_count() > 0 {
+        summary.push_str(&format!(
+            "; {} overwritten by later options",
+            preview.overwrite_count()
+        ));
+    }
 
-    // Show sample file paths (first 10)
-    if !file_instructions.is_empty() {
-        file_lines.push(Line::from(Span::styled(
-            "Sample Files:",
-            Style::default().add_modifier(Modifier::BOLD),
-        )));
+    let mut tree_lines = preview_tree(preview, reconfiguring);
+    let visible = preview_area.height.saturating_sub(3) as usize;
+    let scroll = wizard_state
+        .preview_scroll
+        .min(tree_lines.len().saturating_sub(visible));
+    tree_lines.drain(..scroll);
 
-        for (i, instruction) in file_instructions.iter().take(10).enumerate() {
-            let (source, _dest) = match instruction {
-                crate::mods::fomod::FileInstruction::File {
-                    source,
-                    destination,
-                    ..
-                } => (source, destination),
-                crate::mods::fomod::FileInstruction::Folder {
-                    source,
-                    destination,
-                    ..
-                } => (source, destination),
-            };
+    let mut lines = vec![Line::from(Span::styled(
+        summary,
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    lines.extend(tree_lines);
 
-            file_lines.push(Line::from(format!("  {}", source)));
+    let title = if reconfiguring {
+        " Files (compared with installed version, j/k scroll) "
+    } else {
+        " Files (j/k scroll) "
+    };
+    let tree = Paragraph::new(lines).block(Block::default().title(title).borders(Borders::LEFT));
+    f.render_widget(tree, preview_area);
+}
 
-            if i == 9 && file_instructions.len() > 10 {
-                file_lines.push(Line::from(format!(
-                    "  ... and {} more",
-                    file_instructions.len() - 10
-                )));
-            }
+/// Indented tree of previewed files. Markers: `+` new, `~` changed,
+/// `=` unchanged, `-` removed; `!` notes files an earlier option also wrote.
+fn preview_tree(preview: &InstallPreview, reconfiguring: bool) -> Vec<Line<'static>> {
+    let mut entries: Vec<(&str, Option<FileChange>, bool)> = preview
+        .files
+        .iter()
+        .map(|file| {
+            (
+                file.destination.as_str(),
+                Some(file.change),
+                !file.overwrites.is_empty(),
+            )
+        })
+        .collect();
+    entries.extend(
+        preview
+            .removed
+            .iter()
+            .map(|path| (path.as_str(), None, false)),
+    );
+    entries.sort_by_key(|e| e.0.to_lowercase());
+
+    let mut lines = Vec::new();
+    let mut open_dirs: Vec<&str> = Vec::new();
+    for (path, change, overwrites) in entries {
+        let mut parts: Vec<&str> = path.split('/').collect();
+        let name = parts.pop().unwrap_or(path);
+
+        // Print the directories that differ from the previous entry
+        let shared = open_dirs
+            .iter()
+            .zip(&parts)
+            .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+            .count();
+        open_dirs.truncate(shared);
+        for dir in &parts[shared..] {
+            lines.push(Line::from(Span::styled(
+                format!("{}{}/", "  ".repeat(open_dirs.len()), dir),
+                Style::default().fg(Color::Blue),
+            )));
+            open_dirs.push(*dir);
         }
-    }
 
-    let file_text = Paragraph::new(file_lines).wrap(Wrap { trim: true });
-    f.render_widget(file_text, chunks[1]);
+        let (marker, color) = match change {
+            Some(FileChange::Added) if reconfiguring => ("+", Color::Green),
+            Some(FileChange::Added) => (" ", Color::White),
+            Some(FileChange::Modified) => ("~", Color::Yellow),
+            Some(FileChange::Unchanged) => ("=", Color::DarkGray),
+            None => ("-", Color::Red),
+        };
+        let mut spans = vec![Span::styled(
+            format!("{}{} {}", "  ".repeat(open_dirs.len()), marker, name),
+            Style::default().fg(color),
+        )];
+        if overwrites {
+            spans.push(Span::styled(" !", Style::default().fg(Color::Magenta)));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
 }
 
 /// Draw the confirm phase
@@ -0,0 +1,187 @@
+//! Activity history TUI screen
+
+use crate::activity::{self, Activity};
+use crate::app::state::AppState;
+use crate::app::App;
+use crate::tui::hit;
+use crate::tui::layout;
+use anyhow::Result;
+use chrono::Local;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+/// Entries loaded into the screen
+const HISTORY_LIMIT: usize = 200;
+
+/// Render the history screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let filter = state.activity_filter.map(|a| a.as_str()).unwrap_or("all");
+    let title = format!(
+        " History ({} entries, {}) ",
+        state.activity_entries.len(),
+        filter
+    );
+
+    if state.activity_entries.is_empty() {
+        let empty = Paragraph::new("No recorded activity. Press f to change the filter.")
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let chunks = layout::with_side_panel(area, 40);
+
+    let items: Vec<ListItem> = state
+        .activity_entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let style = if i == state.selected_activity_index {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    e.time
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M  ")
+                        .to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{:<15}", e.action),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(e.target.clone().unwrap_or_default()),
+            ]))
+            .style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+    let mut list_state = ratatui::widgets::ListState::default();
+    list_state.select(Some(state.selected_activity_index));
+    hit::render_list(f, state.current_screen, list, chunks[0], &mut list_state);
+
+    let mut lines = Vec::new();
+    if let Some(entry) = state.activity_entries.get(state.selected_activity_index) {
+        let label = Style::default().fg(Color::Cyan);
+        lines.push(Line::from(Span::styled(
+            entry.action.clone(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Time:    ", label),
+            Span::raw(
+                entry
+                    .time
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+            ),
+        ]));
+        if let Some(target) = &entry.target {
+            lines.push(Line::from(vec![
+                Span::styled("Target:  ", label),
+                Span::raw(target.clone()),
+            ]));
+        }
+        if let Some(profile) = &entry.profile {
+            lines.push(Line::from(vec![
+                Span::styled("Profile: ", label),
+                Span::raw(profile.clone()),
+            ]));
+        }
+        if let serde_json::Value::Object(map) = &entry.details {
+            lines.push(Line::from(""));
+            for (key, value) in map.iter().filter(|(_, v)| !v.is_null()) {
+                let value = match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{}: ", key), label),
+                    Span::raw(value),
+                ]));
+            }
+        }
+    }
+    let detail = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(" Details "));
+    f.render_widget(detail, chunks[1]);
+}
+
+/// Reload the activity log for the active game
+pub async fn refresh(app: &App) {
+    let Some(game) = app.active_game().await else {
+        app.state.write().await.set_status_error("No game selected");
+        return;
+    };
+
+    let filter = app.state.read().await.activity_filter;
+    let result = activity::recent(&app.db, &game.id, filter, HISTORY_LIMIT);
+    let mut state = app.state.write().await;
+    match result {
+        Ok(entries) => {
+            if state.selected_activity_index >= entries.len() {
+                state.selected_activity_index = entries.len().saturating_sub(1);
+            }
+            state.activity_entries = entries;
+        }
+        Err(e) => state.set_status_error_chain("Failed to load history", &e),
+    }
+}
+
+/// Handle keys on the history screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let count = state.activity_entries.len();
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if count > 0 && state.selected_activity_index < count - 1 {
+                state.selected_activity_index += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            if state.selected_activity_index > 0 {
+                state.selected_activity_index -= 1;
+            }
+        }
+        KeyCode::Char('f') => {
+            // Cycle all -> each action -> all
+            let next = match state.activity_filter {
+                None => Activity::ALL.first().copied(),
+                Some(current) => Activity::ALL
+                    .iter()
+                    .position(|a| *a == current)
+                    .and_then(|i| Activity::ALL.get(i + 1))
+                    .copied(),
+            };
+            state.activity_filter = next;
+            state.selected_activity_index = 0;
+            drop(state);
+            refresh(app).await;
+        }
+        KeyCode::Char('r') => {
+            drop(state);
+            refresh(app).await;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,247 @@
+//! INI diff TUI screen
+
+use crate::app::state::{AppState, ConfirmAction, ConfirmDialog, Screen};
+use crate::app::App;
+use crate::profiles::{diff_ini, ini_presets, IniChange, IniSource};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Sources the selected profile can be compared against
+fn targets(state: &AppState) -> Vec<IniSource> {
+    let game_type = state.active_game.as_ref().map(|g| g.game_type);
+    let mut out = vec![IniSource::Current];
+    out.extend(
+        ini_presets()
+            .iter()
+            .filter(|p| match game_type {
+                Some(t) => p.supports(t),
+                None => true,
+            })
+            .map(IniSource::Preset),
+    );
+    out.extend(
+        state
+            .profiles
+            .iter()
+            .filter(|p| p.name != state.ini_diff_profile)
+            .map(|p| IniSource::Profile(p.name.clone())),
+    );
+    out
+}
+
+/// Open the diff view for `profile`
+pub async fn open(app: &App, profile: String) {
+    {
+        let mut state = app.state.write().await;
+        state.ini_diff_profile = profile;
+        state.ini_diff_target_index = 0;
+        state.ini_diff_scroll = 0;
+        state.ini_diff_entries.clear();
+        state.goto(Screen::IniDiff);
+    }
+    refresh(app).await;
+}
+
+/// Recompute the diff for the current profile/target pair
+pub async fn refresh(app: &App) {
+    let (game, left, right) = {
+        let state = app.state.read().await;
+        let right = targets(&state).into_iter().nth(state.ini_diff_target_index);
+        (
+            state.active_game.clone(),
+            IniSource::Profile(state.ini_diff_profile.clone()),
+            right,
+        )
+    };
+    let (Some(game), Some(right)) = (game, right) else {
+        return;
+    };
+
+    let result = async {
+        let l = app.profiles.resolve_ini(&game, &left).await?;
+        let r = app.profiles.resolve_ini(&game, &right).await?;
+        anyhow::Ok(diff_ini(&l, &r))
+    }
+    .await;
+
+    let mut state = app.state.write().await;
+    state.ini_diff_scroll = 0;
+    match result {
+        Ok(entries) => state.ini_diff_entries = entries,
+        Err(e) => {
+            state.ini_diff_entries.clear();
+            state.set_status_error(format!("INI diff failed: {}", e));
+        }
+    }
+}
+
+/// Render the INI diff screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(3)])
+        .split(area);
+
+    let target_list = targets(state);
+    let right = target_list
+        .get(state.ini_diff_target_index)
+        .map(|t| t.label())
+        .unwrap_or_default();
+    let count = |c: IniChange| {
+        state
+            .ini_diff_entries
+            .iter()
+            .filter(|d| d.change() == c)
+            .count()
+    };
+
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("Profile: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                state.ini_diff_profile.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("   vs   "),
+            Span::styled("< ", Style::default().fg(Color::DarkGray)),
+            Span::styled(right, Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(" >", Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("~{} changed  ", count(IniChange::Changed)),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!("+{} added  ", count(IniChange::Added)),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(
+                format!("-{} removed", count(IniChange::Removed)),
+                Style::default().fg(Color::Red),
+            ),
+        ]),
+    ])
+    .block(Block::default().borders(Borders::ALL).title(" INI Diff "));
+    f.render_widget(header, chunks[0]);
+
+    let mut lines = Vec::new();
+    let mut file = "";
+    let mut section = "";
+    for d in &state.ini_diff_entries {
+        if d.file != file {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                d.file.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            file = &d.file;
+            section = "";
+        }
+        if d.section != section {
+            lines.push(Line::from(Span::styled(
+                format!("  [{}]", d.section),
+                Style::default().fg(Color::Cyan),
+            )));
+            section = &d.section;
+        }
+        let (marker, color) = match d.change() {
+            IniChange::Added => ("+", Color::Green),
+            IniChange::Removed => ("-", Color::Red),
+            IniChange::Changed => ("~", Color::Yellow),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} {:<28} ", marker, d.key),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                d.left.clone().unwrap_or_else(|| "(unset)".to_string()),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(" -> "),
+            Span::styled(
+                d.right.clone().unwrap_or_else(|| "(unset)".to_string()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No differences",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let body = Paragraph::new(lines)
+        .scroll((state.ini_diff_scroll as u16, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Changed keys "),
+        );
+    f.render_widget(body, chunks[1]);
+}
+
+/// Handle keys on the INI diff screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let target_count = targets(&state).len();
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if state.ini_diff_scroll < state.ini_diff_entries.len() {
+                state.ini_diff_scroll += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.ini_diff_scroll = state.ini_diff_scroll.saturating_sub(1);
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            state.ini_diff_target_index = (state.ini_diff_target_index + 1) % target_count;
+            drop(state);
+            refresh(app).await;
+        }
+        KeyCode::Left | KeyCode::Char('h') => {
+            state.ini_diff_target_index =
+                (state.ini_diff_target_index + target_count - 1) % target_count;
+            drop(state);
+            refresh(app).await;
+        }
+        KeyCode::Char('a') => {
+            let target = targets(&state).into_iter().nth(state.ini_diff_target_index);
+            let Some(IniSource::Preset(preset)) = target else {
+                state.set_status_info("Select a preset with h/l to apply it");
+                return Ok(());
+            };
+            let profile = state.ini_diff_profile.clone();
+            state.show_confirm = Some(ConfirmDialog {
+                title: "Apply INI Preset".to_string(),
+                message: format!(
+                    "Merge preset '{}' ({} keys) into the INI overrides of profile '{}'?",
+                    preset.name,
+                    preset.settings().len(),
+                    profile
+                ),
+                confirm_text: "Apply".to_string(),
+                cancel_text: "Cancel".to_string(),
+                on_confirm: ConfirmAction::ApplyIniPreset {
+                    profile,
+                    preset: preset.name.to_string(),
+                },
+            });
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,341 @@
+//! INI editor TUI screen
+//!
+//! Shows one of the game's INI files at a time: the keys ModSanity knows
+//! (with their type) first, then every other key in the file. Values are
+//! edited in place, and each write backs the file up first.
+
+use crate::app::state::{AppState, ConfirmAction, ConfirmDialog, InputMode};
+use crate::app::App;
+use crate::profiles::ini::{self, IniValueKind};
+use crate::tui::hit;
+use crate::tui::layout;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+/// INI file shown, by name
+fn current_file(state: &AppState) -> Option<&'static str> {
+    let game = state.active_game.as_ref()?;
+    game.game_type
+        .ini_files()
+        .get(state.ini_file_index)
+        .copied()
+}
+
+/// Reload the keys of the file shown
+pub async fn refresh(app: &App) {
+    let Some(game) = app.active_game().await else {
+        app.state.write().await.set_status_error("No game selected");
+        return;
+    };
+    let backup_dir = app.ini_backup_dir(&game).await;
+    let mut state = app.state.write().await;
+    // The file list is per game, so the index may be left over from another one
+    if state.ini_file_index >= game.game_type.ini_files().len() {
+        state.ini_file_index = 0;
+    }
+    let Some(file) = current_file(&state) else {
+        state.ini_entries.clear();
+        return;
+    };
+    match ini::ini_entries(&game, file) {
+        Ok(entries) => {
+            if state.selected_ini_entry >= entries.len() {
+                state.selected_ini_entry = entries.len().saturating_sub(1);
+            }
+            state.ini_entries = entries;
+        }
+        Err(e) => {
+            state.ini_entries.clear();
+            state.set_status_error_chain("Failed to read INI", &e);
+        }
+    }
+    state.ini_last_backup = ini::ini_backups(&backup_dir, file)
+        .ok()
+        .and_then(|b| b.into_iter().next());
+}
+
+/// Render the INI editor screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let (Some(game), Some(file)) = (state.active_game.as_ref(), current_file(state)) else {
+        let empty = Paragraph::new("Select a game first (g).")
+            .block(Block::default().borders(Borders::ALL).title(" INI Files "));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(3)])
+        .split(area);
+
+    let path = game
+        .ini_dir()
+        .map(|dir| dir.join(file).display().to_string())
+        .unwrap_or_else(|| "unknown location (no Proton prefix)".to_string());
+    let backup = state
+        .ini_last_backup
+        .as_ref()
+        .and_then(|b| b.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "none yet".to_string());
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("< ", Style::default().fg(Color::DarkGray)),
+            Span::styled(file, Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(" >", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!(
+                    "  (file {} of {})  ",
+                    state.ini_file_index + 1,
+                    game.game_type.ini_files().len()
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(path),
+        ]),
+        Line::from(vec![
+            Span::styled("Last backup: ", Style::default().fg(Color::Cyan)),
+            Span::raw(backup),
+        ]),
+    ])
+    .block(Block::default().borders(Borders::ALL).title(" INI Files "));
+    f.render_widget(header, chunks[0]);
+
+    let body = layout::with_side_panel(chunks[1], 40);
+    let items: Vec<ListItem> = state
+        .ini_entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let style = if i == state.selected_ini_entry {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let marker = if e.known.is_some() { "* " } else { "  " };
+            let value = e.value.clone().unwrap_or_else(|| "(unset)".to_string());
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("[{}] ", e.section),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(format!("{:<28} ", e.key)),
+                Span::styled(
+                    value,
+                    if e.value.is_some() {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    },
+                ),
+            ]))
+            .style(style)
+        })
+        .collect();
+    let title = format!(" {} keys, * known ", state.ini_entries.len());
+    if items.is_empty() {
+        let empty = Paragraph::new("No keys. Press a to add one or e to edit the file.")
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(empty, body[0]);
+    } else {
+        let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+        let mut list_state = ratatui::widgets::ListState::default();
+        list_state.select(Some(state.selected_ini_entry));
+        hit::render_list(f, state.current_screen, list, body[0], &mut list_state);
+    }
+
+    let mut lines = Vec::new();
+    if let Some(entry) = state.ini_entries.get(state.selected_ini_entry) {
+        lines.push(Line::from(Span::styled(
+            format!("[{}] {}", entry.section, entry.key),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+        match &entry.known {
+            Some(known) => {
+                lines.push(Line::from(known.description));
+                lines.push(Line::from(vec![
+                    Span::styled("Type: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(known.kind.label()),
+                ]));
+                if known.kind == IniValueKind::Bool {
+                    lines.push(Line::from(Span::styled(
+                        "Space toggles it",
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+            None => lines.push(Line::from(Span::styled(
+                "Not a known key; its value is written as typed",
+                Style::default().fg(Color::DarkGray),
+            ))),
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Every change backs the file up first; u restores the newest backup.",
+        Style::default().fg(Color::DarkGray),
+    )));
+    let detail = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(" Details "));
+    f.render_widget(detail, body[1]);
+}
+
+/// Write `value` to the selected key
+pub async fn submit_value(app: &App, value: &str) {
+    let (file, entry) = {
+        let state = app.state.read().await;
+        (
+            current_file(&state),
+            state.ini_entries.get(state.selected_ini_entry).cloned(),
+        )
+    };
+    let (Some(file), Some(entry)) = (file, entry) else {
+        return;
+    };
+    set_value(app, file, &entry.section, &entry.key, value).await;
+}
+
+/// Set a key given as `[Section] key=value`
+pub async fn submit_key(app: &App, line: &str) {
+    let Some(file) = current_file(&*app.state.read().await) else {
+        return;
+    };
+    let parsed = line
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(section, rest)| {
+            let (key, value) = rest.split_once('=')?;
+            Some((section.trim(), key.trim(), value))
+        });
+    match parsed {
+        Some((section, key, value)) if !section.is_empty() && !key.is_empty() => {
+            set_value(app, file, section, key, value).await
+        }
+        _ => app
+            .state
+            .write()
+            .await
+            .set_status_error("Enter the key as [Section] key=value"),
+    }
+}
+
+async fn set_value(app: &App, file: &str, section: &str, key: &str, value: &str) {
+    let Some(game) = app.active_game().await else {
+        return;
+    };
+    let result = app.set_ini_value(&game, file, section, key, value).await;
+    refresh(app).await;
+    let mut state = app.state.write().await;
+    match result {
+        Ok(_) => {
+            if let Some(index) = state.ini_entries.iter().position(|e| {
+                e.section.eq_ignore_ascii_case(section) && e.key.eq_ignore_ascii_case(key)
+            }) {
+                state.selected_ini_entry = index;
+            }
+            state.set_status_success(format!("Set [{}] {} in {}", section, key, file));
+        }
+        Err(e) => state.set_status_error_chain(format!("Failed to set {}", key), &e),
+    }
+}
+
+/// Handle keys on the INI editor screen. `e` (external editor) is handled
+/// by the TUI, which has to give up the terminal for it.
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let count = state.ini_entries.len();
+    let files = state
+        .active_game
+        .as_ref()
+        .map(|g| g.game_type.ini_files().len())
+        .unwrap_or(0);
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if count > 0 && state.selected_ini_entry < count - 1 {
+                state.selected_ini_entry += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.selected_ini_entry = state.selected_ini_entry.saturating_sub(1);
+        }
+        KeyCode::Right | KeyCode::Char('l') if files > 0 => {
+            state.ini_file_index = (state.ini_file_index + 1) % files;
+            state.selected_ini_entry = 0;
+            drop(state);
+            refresh(app).await;
+        }
+        KeyCode::Left | KeyCode::Char('h') if files > 0 => {
+            state.ini_file_index = (state.ini_file_index + files - 1) % files;
+            state.selected_ini_entry = 0;
+            drop(state);
+            refresh(app).await;
+        }
+        KeyCode::Enter => {
+            if let Some(entry) = state.ini_entries.get(state.selected_ini_entry) {
+                state.input_buffer = entry.value.clone().unwrap_or_default();
+                state.input_mode = InputMode::IniValueInput;
+            }
+        }
+        KeyCode::Char(' ') => {
+            let Some(entry) = state.ini_entries.get(state.selected_ini_entry).cloned() else {
+                return Ok(());
+            };
+            if entry.known.map(|k| k.kind) != Some(IniValueKind::Bool) {
+                state.set_status_info("Space toggles boolean keys; Enter edits the value");
+                return Ok(());
+            }
+            let value = if entry.value.as_deref() == Some("1") {
+                "0"
+            } else {
+                "1"
+            };
+            drop(state);
+            submit_value(app, value).await;
+        }
+        KeyCode::Char('a') => {
+            state.input_buffer.clear();
+            state.input_mode = InputMode::IniKeyInput;
+        }
+        KeyCode::Char('u') => {
+            let (Some(file), Some(backup)) = (current_file(&state), state.ini_last_backup.clone())
+            else {
+                state.set_status_info("No backup of this file yet");
+                return Ok(());
+            };
+            state.show_confirm = Some(ConfirmDialog {
+                title: "Restore INI Backup".to_string(),
+                message: format!(
+                    "Replace {} with {}? The current file is backed up first.",
+                    file,
+                    backup.file_name().unwrap_or_default().to_string_lossy()
+                ),
+                confirm_text: "Restore".to_string(),
+                cancel_text: "Cancel".to_string(),
+                on_confirm: ConfirmAction::RestoreIniBackup(backup),
+            });
+        }
+        KeyCode::Char('r') => {
+            drop(state);
+            refresh(app).await;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
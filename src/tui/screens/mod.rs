@@ -1,13 +1,22 @@
 //! Full-screen views
 
+pub mod downloads;
+pub mod extensions;
 pub mod fomod_wizard;
+pub mod history;
+pub mod ini_diff;
+pub mod ini_editor;
+pub mod modlist_diff;
 pub mod nexus_catalog;
+pub mod saves;
+pub mod script_overrides;
+pub mod storage;
+pub mod tool_output;
 
 // Placeholder - other screens implemented inline in ui.rs for now
 // Will be refactored into separate modules as they grow:
 // - dashboard.rs
 // - mods.rs
 // - plugins.rs
-// - downloads.rs
 // - profiles.rs
 // - settings.rs
@@ -0,0 +1,253 @@
+//! Modlist comparison TUI screen
+
+use crate::app::state::{AppState, Screen};
+use crate::app::App;
+use crate::import::{diff_modlists, resolve_modlist, ModlistDiff, ModlistSource};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Modlists the selected one can be compared against
+fn targets(state: &AppState) -> Vec<ModlistSource> {
+    let mut out = vec![ModlistSource::Current];
+    out.extend(
+        state
+            .saved_modlists
+            .iter()
+            .filter(|m| m.name != state.modlist_diff_left)
+            .map(|m| ModlistSource::Saved(m.name.clone())),
+    );
+    out
+}
+
+/// Open the comparison of saved modlist `name` with the current setup
+pub async fn open(app: &App, name: String) {
+    {
+        let mut state = app.state.write().await;
+        state.modlist_diff_left = name;
+        state.modlist_diff_target_index = 0;
+        state.modlist_diff_scroll = 0;
+        state.modlist_diff = None;
+        state.goto(Screen::ModlistDiff);
+    }
+    refresh(app).await;
+}
+
+/// Recompute the diff for the current modlist/target pair
+pub async fn refresh(app: &App) {
+    let (game, left, right) = {
+        let state = app.state.read().await;
+        let right = targets(&state)
+            .into_iter()
+            .nth(state.modlist_diff_target_index);
+        (
+            state.active_game.clone(),
+            ModlistSource::Saved(state.modlist_diff_left.clone()),
+            right,
+        )
+    };
+    let (Some(game), Some(right)) = (game, right) else {
+        return;
+    };
+
+    let result = async {
+        let l = resolve_modlist(&app.mods, &app.db, &game, &left).await?;
+        let r = resolve_modlist(&app.mods, &app.db, &game, &right).await?;
+        anyhow::Ok(diff_modlists(&l, &r))
+    }
+    .await;
+
+    let mut state = app.state.write().await;
+    state.modlist_diff_scroll = 0;
+    match result {
+        Ok(diff) => state.modlist_diff = Some(diff),
+        Err(e) => {
+            state.modlist_diff = None;
+            state.set_status_error(format!("Modlist diff failed: {}", e));
+        }
+    }
+}
+
+fn diff_lines(diff: &ModlistDiff) -> Vec<Line<'static>> {
+    let version = |v: &Option<String>| v.clone().unwrap_or_else(|| "?".to_string());
+    let pos = |p: Option<usize>| p.map(|p| format!("#{}", p)).unwrap_or_default();
+    let row = |marker: &str, name: &str, detail: String, color: Color| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {} {:<40} ", marker, name),
+                Style::default().fg(color),
+            ),
+            Span::styled(detail, Style::default().fg(Color::DarkGray)),
+        ])
+    };
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    let mut lines = Vec::new();
+    if !diff.added.is_empty() || !diff.removed.is_empty() || !diff.changed.is_empty() {
+        lines.push(heading("Mods"));
+        for m in &diff.added {
+            lines.push(row("+", &m.name, version(&m.version), Color::Green));
+        }
+        for m in &diff.removed {
+            lines.push(row("-", &m.name, version(&m.version), Color::Red));
+        }
+        for c in &diff.changed {
+            let mut what = Vec::new();
+            if c.version_changed() {
+                what.push(format!(
+                    "{} -> {}",
+                    version(&c.from_version),
+                    version(&c.to_version)
+                ));
+            }
+            if c.from_enabled != c.to_enabled {
+                what.push(if c.to_enabled { "enabled" } else { "disabled" }.to_string());
+            }
+            lines.push(row("~", &c.name, what.join(", "), Color::Yellow));
+        }
+    }
+    if !diff.plugins_added.is_empty()
+        || !diff.plugins_removed.is_empty()
+        || !diff.plugins_moved.is_empty()
+    {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(heading("Plugins"));
+        for p in &diff.plugins_added {
+            lines.push(row("+", &p.plugin, pos(p.to), Color::Green));
+        }
+        for p in &diff.plugins_removed {
+            lines.push(row("-", &p.plugin, pos(p.from), Color::Red));
+        }
+        for p in &diff.plugins_moved {
+            let detail = format!("{} -> {}", pos(p.from), pos(p.to));
+            lines.push(row(">", &p.plugin, detail, Color::Cyan));
+        }
+    }
+    lines
+}
+
+/// Render the modlist diff screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(3)])
+        .split(area);
+
+    let target_list = targets(state);
+    let right = target_list
+        .get(state.modlist_diff_target_index)
+        .map(|t| t.label())
+        .unwrap_or_default();
+
+    let mut summary = Vec::new();
+    if let Some(diff) = &state.modlist_diff {
+        summary.push(Span::styled(
+            format!("+{} ", diff.added.len()),
+            Style::default().fg(Color::Green),
+        ));
+        summary.push(Span::styled(
+            format!("-{} ", diff.removed.len()),
+            Style::default().fg(Color::Red),
+        ));
+        summary.push(Span::styled(
+            format!("~{} mods   ", diff.changed.len()),
+            Style::default().fg(Color::Yellow),
+        ));
+        summary.push(Span::styled(
+            format!(
+                "+{} -{} >{} plugins",
+                diff.plugins_added.len(),
+                diff.plugins_removed.len(),
+                diff.plugins_moved.len()
+            ),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("Modlist: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                state.modlist_diff_left.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("   vs   "),
+            Span::styled("< ", Style::default().fg(Color::DarkGray)),
+            Span::styled(right, Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(" >", Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(summary),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Modlist Diff "),
+    );
+    f.render_widget(header, chunks[0]);
+
+    let mut lines = state
+        .modlist_diff
+        .as_ref()
+        .map(diff_lines)
+        .unwrap_or_default();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No differences",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let body = Paragraph::new(lines)
+        .scroll((state.modlist_diff_scroll as u16, 0))
+        .block(Block::default().borders(Borders::ALL).title(" Changes "));
+    f.render_widget(body, chunks[1]);
+}
+
+/// Handle keys on the modlist diff screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let target_count = targets(&state).len();
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            let rows = state
+                .modlist_diff
+                .as_ref()
+                .map(|d| diff_lines(d).len())
+                .unwrap_or(0);
+            if state.modlist_diff_scroll < rows {
+                state.modlist_diff_scroll += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.modlist_diff_scroll = state.modlist_diff_scroll.saturating_sub(1);
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            state.modlist_diff_target_index = (state.modlist_diff_target_index + 1) % target_count;
+            drop(state);
+            refresh(app).await;
+        }
+        KeyCode::Left | KeyCode::Char('h') => {
+            state.modlist_diff_target_index =
+                (state.modlist_diff_target_index + target_count - 1) % target_count;
+            drop(state);
+            refresh(app).await;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
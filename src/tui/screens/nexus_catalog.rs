@@ -2,6 +2,8 @@
 
 use crate::app::state::{AppState, CatalogProgress, CatalogSyncStatus, InputMode};
 use crate::app::App;
+use crate::tui::hit;
+use crate::tui::layout;
 use anyhow::Result;
 use crossterm::event::KeyCode;
 use ratatui::{
@@ -96,7 +98,7 @@ fn render_browse_view(f: &mut Frame, area: Rect, state: &AppState) {
         )
     } else {
         format!(
-            " Search: \"{}\" | {} results | /: New search | Esc: Clear search",
+            " Search: \"{}\" | {} results, best match first | /: New search | Esc: Clear search",
             state.catalog_search_query,
             state.catalog_browse_results.len()
         )
@@ -112,10 +114,7 @@ fn render_browse_view(f: &mut Frame, area: Rect, state: &AppState) {
     f.render_widget(search_bar, chunks[0]);
 
     // Main content: mod list (left) + details (right)
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(chunks[1]);
+    let content_chunks = layout::with_side_panel(chunks[1], 50);
 
     // Mod list
     let results = &state.catalog_browse_results;
@@ -154,7 +153,13 @@ fn render_browse_view(f: &mut Frame, area: Rect, state: &AppState) {
 
         let mut list_state = ratatui::widgets::ListState::default();
         list_state.select(Some(state.selected_catalog_index));
-        f.render_stateful_widget(list, content_chunks[0], &mut list_state);
+        hit::render_list(
+            f,
+            state.current_screen,
+            list,
+            content_chunks[0],
+            &mut list_state,
+        );
     }
 
     // Details panel
@@ -285,13 +290,7 @@ fn render_progress(f: &mut Frame, area: Rect, state: &AppState) {
 
     if let Some(progress) = &state.catalog_progress {
         // Progress bar based on offset vs total count
-        let percent = if progress.total_count > 0 {
-            let progress_pct =
-                (progress.current_offset as f64 / progress.total_count as f64) * 100.0;
-            progress_pct.min(100.0) as u16
-        } else {
-            0
-        };
+        let percent = progress.percent().unwrap_or(0);
 
         let label = if progress.total_count > 0 {
             format!(
@@ -541,8 +540,15 @@ async fn populate_catalog(app: &mut App, reset: bool) -> Result<()> {
     };
 
     // Get API key
-    let api_key = match &app.config.read().await.nexus_api_key {
-        Some(key) => key.clone(),
+    let (api_key, base_url) = {
+        let config = app.config.read().await;
+        (
+            config.nexus_api_key.clone(),
+            config.nexus_base_url().to_string(),
+        )
+    };
+    let api_key = match api_key {
+        Some(key) => key,
         None => {
             let mut state = app.state.write().await;
             state.error_message = Some("Nexus API key not configured".to_string());
@@ -567,12 +573,12 @@ async fn populate_catalog(app: &mut App, reset: bool) -> Result<()> {
 
     // Spawn population task
     let db = app.db.clone();
-    let state_clone = app.state.clone();
+    let events = app.events.clone();
     let game_domain = game_domain.to_string();
 
     tokio::spawn(async move {
         let result: Result<()> = async {
-            let rest_client = NexusRestClient::new(&api_key)?;
+            let rest_client = NexusRestClient::with_base_url(&api_key, &base_url)?;
             let populator = CatalogPopulator::new(db.clone(), rest_client, game_domain.clone())?;
 
             let options = PopulateOptions {
@@ -583,61 +589,62 @@ async fn populate_catalog(app: &mut App, reset: bool) -> Result<()> {
             };
 
             // Create progress callback to update state
-            let state_for_callback = state_clone.clone();
+            let progress_events = events.clone();
             let callback =
                 move |pages: i32, inserted: i64, updated: i64, total: i64, offset: i32| {
-                    if let Ok(mut state) = state_for_callback.try_write() {
-                        state.catalog_progress = Some(CatalogProgress {
-                            pages_fetched: pages,
-                            mods_inserted: inserted,
-                            mods_updated: updated,
-                            current_page: pages + 1,
-                            total_count: total,
-                            current_offset: offset,
-                        });
-                    }
+                    progress_events.catalog_progress(Some(CatalogProgress::new(
+                        pages, inserted, updated, total, offset,
+                    )));
                 };
 
             let stats = populator.populate(options, Some(callback)).await?;
 
-            // Update final state
-            let mut state = state_clone.write().await;
-            state.catalog_populating = false;
-            state.catalog_progress = None;
-            state.set_status(format!(
-                "Catalog populated: {} pages, {} total mods",
-                stats.pages_fetched, stats.total_mods
-            ));
-
             // Refresh status and load initial browse page
-            if let Ok(sync_state) = db.get_sync_state(&game_domain) {
+            let sync_status = db.get_sync_state(&game_domain).ok().map(|sync_state| {
                 let total_mods = db.count_catalog_mods(&game_domain).unwrap_or(0);
-                state.catalog_sync_state = Some(CatalogSyncStatus {
+                CatalogSyncStatus {
                     current_page: sync_state.current_page,
                     completed: sync_state.completed,
                     last_sync: sync_state.last_sync,
                     last_error: sync_state.last_error,
                     total_mods,
-                });
-                state.catalog_total_count = total_mods;
-            }
+                }
+            });
+            let first_page = db.list_catalog_mods(&game_domain, 0, 100).ok();
 
-            // Load first page of browse results
-            if let Ok(results) = db.list_catalog_mods(&game_domain, 0, 100) {
-                state.catalog_browse_results = results;
-                state.catalog_browse_offset = 0;
-                state.selected_catalog_index = 0;
-            }
+            // Update final state
+            events.update(move |state| {
+                state.catalog_populating = false;
+                state.catalog_progress = None;
+                state.set_status(format!(
+                    "Catalog populated: {} pages, {} total mods",
+                    stats.pages_fetched, stats.total_mods
+                ));
+
+                if let Some(sync_status) = sync_status {
+                    state.catalog_total_count = sync_status.total_mods;
+                    state.catalog_sync_state = Some(sync_status);
+                }
+
+                // Load first page of browse results
+                if let Some(results) = first_page {
+                    state.catalog_browse_results = results;
+                    state.catalog_browse_offset = 0;
+                    state.selected_catalog_index = 0;
+                }
+            });
 
             Ok(())
         }
         .await;
 
         if let Err(e) = result {
-            let mut state = state_clone.write().await;
-            state.catalog_populating = false;
-            state.catalog_progress = None;
-            state.error_message = Some(format!("Population failed: {}", e));
+            let message = format!("Population failed: {}", e);
+            events.update(move |state| {
+                state.catalog_populating = false;
+                state.catalog_progress = None;
+                state.error_message = Some(message);
+            });
         }
     });
 
@@ -0,0 +1,239 @@
+//! Saves TUI screen
+//!
+//! Lists the active game's saves, newest first, with what their headers
+//! say, marking saves made with plugins the game doesn't load now. Saves
+//! are backed up or deleted together with their co-saves.
+
+use crate::app::state::{AppState, ConfirmAction, ConfirmDialog};
+use crate::app::App;
+use crate::mods::format_bytes;
+use crate::saves;
+use crate::tui::hit;
+use crate::tui::layout;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+/// Reload the save list
+pub async fn refresh(app: &App) {
+    let Some(game) = app.active_game().await else {
+        app.state.write().await.set_status_error("No game selected");
+        return;
+    };
+    let result = saves::list_saves(&game);
+    let loaded = saves::loaded_plugins(&game).unwrap_or_default();
+    let mut state = app.state.write().await;
+    state.save_loaded_plugins = loaded;
+    match result {
+        Ok(list) => {
+            if state.selected_save_index >= list.len() {
+                state.selected_save_index = list.len().saturating_sub(1);
+            }
+            state.saves = list;
+        }
+        Err(e) => {
+            state.saves.clear();
+            state.set_status_error_chain("Failed to list saves", &e);
+        }
+    }
+}
+
+/// Plugins of `save` the game doesn't load now
+fn missing_plugins(state: &AppState, save: &saves::SaveGame) -> Vec<String> {
+    save.header
+        .as_ref()
+        .map(|h| saves::missing_plugins(h, &state.save_loaded_plugins))
+        .unwrap_or_default()
+}
+
+/// Render the Saves screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let Some(game) = state.active_game.as_ref() else {
+        let empty = Paragraph::new("Select a game first (g).")
+            .block(Block::default().borders(Borders::ALL).title(" Saves "));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let body = layout::with_side_panel(area, 40);
+    let items: Vec<ListItem> = state
+        .saves
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let style = if i == state.selected_save_index {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let date = s
+                .saved_at()
+                .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "unknown date    ".to_string());
+            let summary = match &s.header {
+                Some(h) => Span::raw(format!("{}, level {}", h.character, h.level)),
+                None => Span::styled("unreadable header", Style::default().fg(Color::Red)),
+            };
+            let marker = if missing_plugins(state, s).is_empty() {
+                "  "
+            } else {
+                "! "
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Red)),
+                Span::styled(format!("{}  ", date), Style::default().fg(Color::DarkGray)),
+                summary,
+                Span::styled(
+                    format!("  {}", s.stem()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+            .style(style)
+        })
+        .collect();
+    let title = format!(" Saves ({}), ! needs missing plugins ", state.saves.len());
+    if items.is_empty() {
+        let dir = saves::saves_dir(game)
+            .map(|d| d.display().to_string())
+            .unwrap_or_else(|| "unknown location (no Proton prefix)".to_string());
+        let empty = Paragraph::new(format!("No saves in {}", dir))
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(empty, body[0]);
+    } else {
+        let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+        let mut list_state = ratatui::widgets::ListState::default();
+        list_state.select(Some(state.selected_save_index));
+        hit::render_list(f, state.current_screen, list, body[0], &mut list_state);
+    }
+
+    let mut lines = Vec::new();
+    if let Some(save) = state.saves.get(state.selected_save_index) {
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{}: ", label), Style::default().fg(Color::Cyan)),
+                Span::raw(value),
+            ])
+        };
+        lines.push(Line::from(Span::styled(
+            save.name.clone(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+        match &save.header {
+            Some(h) => {
+                lines.push(field("Character", h.character.clone()));
+                lines.push(field("Level", h.level.to_string()));
+                lines.push(field("Location", h.location.clone()));
+                lines.push(field("Played", h.playtime.clone()));
+                lines.push(field("Plugins", h.plugins.len().to_string()));
+            }
+            None => lines.push(Line::from(Span::styled(
+                save.error
+                    .clone()
+                    .unwrap_or_else(|| "Unknown save format".to_string()),
+                Style::default().fg(Color::Red),
+            ))),
+        }
+        if let Some(date) = save.saved_at() {
+            lines.push(field("Saved", date.format("%Y-%m-%d %H:%M").to_string()));
+        }
+        lines.push(field("Size", format_bytes(save.size)));
+        lines.push(field(
+            "Co-save",
+            if save.cosave.is_some() { "yes" } else { "no" }.to_string(),
+        ));
+        let missing = missing_plugins(state, save);
+        if !missing.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("Plugins not in the load order ({}):", missing.len()),
+                Style::default().fg(Color::Red),
+            )));
+            for plugin in missing {
+                lines.push(Line::from(format!("  {}", plugin)));
+            }
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "b backs the save up, d deletes it; co-saves go with it.",
+        Style::default().fg(Color::DarkGray),
+    )));
+    let detail = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(" Details "));
+    f.render_widget(detail, body[1]);
+}
+
+/// Handle keys on the Saves screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let count = state.saves.len();
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if count > 0 && state.selected_save_index < count - 1 {
+                state.selected_save_index += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.selected_save_index = state.selected_save_index.saturating_sub(1);
+        }
+        KeyCode::Char('b') => {
+            let Some(save) = state.saves.get(state.selected_save_index).cloned() else {
+                return Ok(());
+            };
+            drop(state);
+            let Some(game) = app.active_game().await else {
+                return Ok(());
+            };
+            let backup_dir = app.save_backup_dir(&game).await;
+            let result = saves::backup_save(&save, &backup_dir);
+            let mut state = app.state.write().await;
+            match result {
+                Ok(dest) => state.set_status_success(format!(
+                    "Backed up {} to {}",
+                    save.name,
+                    dest.display()
+                )),
+                Err(e) => state.set_status_error_chain("Backup failed", &e),
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            let Some(save) = state.saves.get(state.selected_save_index) else {
+                return Ok(());
+            };
+            let message = if save.cosave.is_some() {
+                format!("Delete {} and its co-save?", save.name)
+            } else {
+                format!("Delete {}?", save.name)
+            };
+            let path = save.path.clone();
+            state.show_confirm = Some(ConfirmDialog {
+                title: "Delete Save".to_string(),
+                message,
+                confirm_text: "Delete".to_string(),
+                cancel_text: "Cancel".to_string(),
+                on_confirm: ConfirmAction::DeleteSave(path),
+            });
+        }
+        KeyCode::Char('r') => {
+            drop(state);
+            refresh(app).await;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
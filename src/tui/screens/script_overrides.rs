@@ -0,0 +1,225 @@
+//! Script overrides TUI screen
+
+use crate::app::state::AppState;
+use crate::app::App;
+use crate::tui::hit;
+use crate::tui::layout;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+/// Render the script overrides screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let Some(report) = state.script_report.as_ref() else {
+        let message = if state.script_scanning {
+            "Indexing scripts..."
+        } else {
+            "No script index yet. Press r to scan."
+        };
+        let empty = Paragraph::new(message).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Script Overrides "),
+        );
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let chunks = layout::with_side_panel(area, 45);
+
+    let items: Vec<ListItem> = report
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let (marker, color) = if e.overrides_vanilla() {
+                ("!", Color::Red)
+            } else {
+                ("~", Color::Yellow)
+            };
+            let style = if i == state.selected_script_index {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let winner = e.winner().map(|w| w.mod_name.clone()).unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", marker), Style::default().fg(color)),
+                Span::raw(e.path.clone()),
+                Span::styled(
+                    format!("  {}", winner),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+            .style(style)
+        })
+        .collect();
+
+    let vanilla = report.vanilla_overrides().count();
+    let title = format!(
+        " Script Overrides ({} vanilla, {} shared){} ",
+        vanilla,
+        report.mod_conflicts().count(),
+        if state.script_scanning {
+            " - rescanning..."
+        } else {
+            ""
+        }
+    );
+    if items.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(Span::styled(
+                "No script overrides or conflicts",
+                Style::default().fg(Color::Green),
+            )),
+            Line::from(""),
+            Line::from(format!(
+                "{} base game scripts, {} mod scripts indexed",
+                report.game_scripts, report.mod_scripts
+            )),
+        ])
+        .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(empty, chunks[0]);
+    } else {
+        let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+        let mut list_state = ratatui::widgets::ListState::default();
+        list_state.select(Some(state.selected_script_index));
+        hit::render_list(f, state.current_screen, list, chunks[0], &mut list_state);
+    }
+
+    let mut lines = Vec::new();
+    if let Some(entry) = report.entries.get(state.selected_script_index) {
+        lines.push(Line::from(Span::styled(
+            entry.path.clone(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+        if let Some(source) = &entry.vanilla_source {
+            lines.push(Line::from(vec![
+                Span::styled("Vanilla: ", Style::default().fg(Color::Red)),
+                Span::raw(source.clone()),
+            ]));
+            if entry.overrides_vanilla() {
+                lines.push(Line::from(Span::styled(
+                    "A loose copy replaces the vanilla script. Removing the mod mid-playthrough can break saves.",
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            "Provided by (lowest priority first):",
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        let winner = entry.winner();
+        for p in &entry.providers {
+            let wins = winner == Some(p);
+            let location = match &p.archive {
+                Some(archive) => format!(" [{}]", archive),
+                None => " [loose]".to_string(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}{}", p.mod_name, location),
+                    if wins {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default()
+                    },
+                ),
+                Span::styled(
+                    if wins { "  (wins)" } else { "" },
+                    Style::default().fg(Color::Green),
+                ),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "! vanilla override  ~ shared between mods",
+        Style::default().fg(Color::DarkGray),
+    )));
+    let detail = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(" Details "));
+    f.render_widget(detail, chunks[1]);
+}
+
+/// Re-index scripts for the active game in the background
+pub async fn refresh(app: &App) {
+    let Some(game) = app.active_game().await else {
+        app.state.write().await.set_status_error("No game selected");
+        return;
+    };
+
+    {
+        let mut state = app.state.write().await;
+        if state.script_scanning {
+            return;
+        }
+        state.script_scanning = true;
+    }
+
+    let mods = app.mods.clone();
+    let events = app.events.clone();
+
+    // Reads archive name tables, which can take a moment on large setups
+    tokio::spawn(async move {
+        let result = tokio::task::spawn_blocking(move || mods.script_overrides(&game))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|r| r);
+        events.update(move |state| {
+            state.script_scanning = false;
+            match result {
+                Ok(report) => {
+                    if state.selected_script_index >= report.entries.len() {
+                        state.selected_script_index = report.entries.len().saturating_sub(1);
+                    }
+                    state.script_report = Some(report);
+                }
+                Err(e) => state.set_status_error_chain("Script scan failed", &e),
+            }
+        });
+    });
+}
+
+/// Handle keys on the script overrides screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let count = state
+        .script_report
+        .as_ref()
+        .map(|r| r.entries.len())
+        .unwrap_or(0);
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if count > 0 && state.selected_script_index < count - 1 {
+                state.selected_script_index += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            if state.selected_script_index > 0 {
+                state.selected_script_index -= 1;
+            }
+        }
+        KeyCode::Char('r') => {
+            drop(state);
+            refresh(app).await;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
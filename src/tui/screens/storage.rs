@@ -0,0 +1,303 @@
+//! Storage TUI screen
+
+use crate::app::state::{AppState, ConfirmAction, ConfirmDialog};
+use crate::app::App;
+use crate::mods::format_bytes;
+use crate::tui::layout;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+/// Render the Storage screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let Some(report) = state.storage_report.as_ref() else {
+        let message = if state.storage_scanning {
+            "Scanning disk usage..."
+        } else {
+            "No disk usage report yet. Press r to scan."
+        };
+        let empty = Paragraph::new(message)
+            .block(Block::default().borders(Borders::ALL).title(" Storage "));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(7), Constraint::Min(5)])
+        .split(area);
+
+    let label = Style::default().fg(Color::Cyan);
+    let summary = vec![
+        Line::from(vec![
+            Span::styled("Staging:     ", label),
+            Span::raw(format!(
+                "{:>10}  {}",
+                format_bytes(report.staging_bytes),
+                report.staging_dir.display()
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Downloads:   ", label),
+            Span::raw(format!(
+                "{:>10}  {} ({} archives)",
+                format_bytes(report.downloads_bytes),
+                report.downloads_dir.display(),
+                report.download_count
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Retained:    ", label),
+            Span::raw(format!("{:>10}", format_bytes(report.archives_bytes))),
+        ]),
+        Line::from(vec![
+            Span::styled("Deployment:  ", label),
+            Span::raw(format!(
+                "{:>10}  copied into game ({})",
+                format_bytes(report.copy_overhead_bytes),
+                report.deployment_method.display_name()
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Reclaimable: ", label),
+            Span::styled(
+                format!("{:>10}", format_bytes(report.reclaimable_bytes())),
+                if report.reclaimable_bytes() > 0 {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::Green)
+                },
+            ),
+        ]),
+    ];
+    let title = if state.storage_scanning {
+        " Disk Usage (rescanning...) "
+    } else {
+        " Disk Usage "
+    };
+    f.render_widget(
+        Paragraph::new(summary).block(Block::default().borders(Borders::ALL).title(title)),
+        chunks[0],
+    );
+
+    let bottom = layout::with_side_panel(chunks[1], 45);
+
+    let items: Vec<ListItem> = report
+        .mods
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let (marker, marker_style) = if m.enabled {
+                ("[✓]", Style::default().fg(Color::Green))
+            } else {
+                ("[ ]", Style::default().fg(Color::DarkGray))
+            };
+            let style = if i == state.selected_storage_index {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", marker), marker_style),
+                Span::styled(
+                    format!("{:>10} ", format_bytes(m.bytes)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(m.name.clone()),
+                Span::styled(
+                    format!("  ({} files)", m.files),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+            .style(style)
+        })
+        .collect();
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Mods by size ({}) ", report.mods.len())),
+    );
+    f.render_widget(list, bottom[0]);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "Stale archives: {} ({})",
+            report.stale_archives.len(),
+            format_bytes(report.stale_bytes())
+        ),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    for a in &report.stale_archives {
+        let name = a
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        lines.push(Line::from(format!(
+            "  {:>10}  {} - {}",
+            format_bytes(a.bytes),
+            name,
+            a.reason.describe()
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "Orphaned staging: {} ({})",
+            report.orphaned.len(),
+            format_bytes(report.orphaned_bytes())
+        ),
+        Style::default().add_modifier(Modifier::BOLD),
+    )));
+    for o in &report.orphaned {
+        let name = o
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        lines.push(Line::from(format!(
+            "  {:>10}  {}{}",
+            format_bytes(o.bytes),
+            name,
+            if o.is_dir { "/" } else { "" }
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "a: remove stale archives  o: remove orphans",
+        Style::default().fg(Color::DarkGray),
+    )));
+    let cleanup = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(" Cleanup "));
+    f.render_widget(cleanup, bottom[1]);
+}
+
+/// Rescan disk usage for the active game in the background
+pub async fn refresh(app: &App) {
+    let Some(game) = app.active_game().await else {
+        app.state.write().await.set_status_error("No game selected");
+        return;
+    };
+
+    {
+        let mut state = app.state.write().await;
+        if state.storage_scanning {
+            return;
+        }
+        state.storage_scanning = true;
+    }
+
+    let mods = app.mods.clone();
+    let events = app.events.clone();
+
+    // Walking staging and downloads can take a while on large setups
+    tokio::spawn(async move {
+        let result = mods.disk_usage(&game).await;
+        events.update(move |state| {
+            state.storage_scanning = false;
+            match result {
+                Ok(report) => {
+                    if state.selected_storage_index >= report.mods.len() {
+                        state.selected_storage_index = report.mods.len().saturating_sub(1);
+                    }
+                    state.storage_report = Some(report);
+                }
+                Err(e) => state.set_status_error_chain("Disk usage scan failed", &e),
+            }
+        });
+    });
+}
+
+/// Handle keys on the Storage screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let count = state
+        .storage_report
+        .as_ref()
+        .map(|r| r.mods.len())
+        .unwrap_or(0);
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if count > 0 && state.selected_storage_index < count - 1 {
+                state.selected_storage_index += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            if state.selected_storage_index > 0 {
+                state.selected_storage_index -= 1;
+            }
+        }
+        KeyCode::Char('r') => {
+            drop(state);
+            refresh(app).await;
+        }
+        KeyCode::Char('a') => {
+            let Some(report) = state.storage_report.as_ref() else {
+                return Ok(());
+            };
+            if report.stale_archives.is_empty() {
+                state.set_status_info("No stale archives to remove");
+                return Ok(());
+            }
+            let message = format!(
+                "Delete {} downloaded archive(s) ({}) that are superseded or already retained?",
+                report.stale_archives.len(),
+                format_bytes(report.stale_bytes())
+            );
+            state.show_confirm = Some(ConfirmDialog {
+                title: "Remove Stale Archives".to_string(),
+                message,
+                confirm_text: "Remove".to_string(),
+                cancel_text: "Cancel".to_string(),
+                on_confirm: ConfirmAction::CleanStaleArchives,
+            });
+        }
+        KeyCode::Char('o') => {
+            // Staging dirs exist before their DB record while installing
+            if state.installation_progress.is_some()
+                || state.bulk_install_running
+                || state.fomod_wizard_state.is_some()
+            {
+                state.set_status_info("Finish the running install before removing orphans");
+                return Ok(());
+            }
+            let Some(report) = state.storage_report.as_ref() else {
+                return Ok(());
+            };
+            if report.orphaned.is_empty() {
+                state.set_status_info("No orphaned staging entries to remove");
+                return Ok(());
+            }
+            let message = format!(
+                "Delete {} staging entr{} ({}) not owned by any installed mod?",
+                report.orphaned.len(),
+                if report.orphaned.len() == 1 {
+                    "y"
+                } else {
+                    "ies"
+                },
+                format_bytes(report.orphaned_bytes())
+            );
+            state.show_confirm = Some(ConfirmDialog {
+                title: "Remove Orphaned Staging".to_string(),
+                message,
+                confirm_text: "Remove".to_string(),
+                cancel_text: "Cancel".to_string(),
+                on_confirm: ConfirmAction::CleanOrphanedStaging,
+            });
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Tool Output TUI screen
+//!
+//! Output of tools launched from the TUI is captured per launch instead of
+//! being written to the terminal, and kept here with its exit code and the
+//! log file it was saved to.
+
+use crate::app::state::AppState;
+use crate::app::App;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Lines moved by PageUp/PageDown
+const PAGE: usize = 20;
+
+/// Render the tool output screen
+pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+    let Some(run) = state.tool_runs.get(state.selected_tool_run) else {
+        let empty = Paragraph::new("No tool has been launched yet. Launch one from Settings (F4).")
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Tool Output "),
+            );
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(3)])
+        .split(area);
+
+    let status = match run.exit_code {
+        Some(0) => Span::styled("exit code 0", Style::default().fg(Color::Green)),
+        Some(code) => Span::styled(
+            format!("exit code {}", code),
+            Style::default().fg(Color::Red),
+        ),
+        None => Span::styled("failed to start", Style::default().fg(Color::Red)),
+    };
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("< ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                run.tool.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" >", Style::default().fg(Color::DarkGray)),
+            Span::raw(format!(
+                "  {}  ",
+                run.started_at.format("%Y-%m-%d %H:%M:%S")
+            )),
+            status,
+            Span::styled(
+                format!(
+                    "  (launch {} of {})",
+                    state.selected_tool_run + 1,
+                    state.tool_runs.len()
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Log: ", Style::default().fg(Color::Cyan)),
+            Span::raw(
+                run.log_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "(not saved)".to_string()),
+            ),
+        ]),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Tool Output "),
+    );
+    f.render_widget(header, chunks[0]);
+
+    let mut lines: Vec<Line> = run
+        .lines
+        .iter()
+        .map(|(stderr, line)| {
+            if *stderr {
+                Line::from(Span::styled(line.clone(), Style::default().fg(Color::Red)))
+            } else {
+                Line::from(line.clone())
+            }
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "The tool printed nothing",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    let body = Paragraph::new(lines)
+        .scroll((state.tool_output_scroll.min(u16::MAX as usize) as u16, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} lines, stderr in red ", run.lines.len())),
+        );
+    f.render_widget(body, chunks[1]);
+}
+
+/// Handle keys on the tool output screen
+pub async fn handle_input(app: &mut App, key: KeyCode) -> Result<()> {
+    let mut state = app.state.write().await;
+    let runs = state.tool_runs.len();
+    let last_line = state
+        .tool_runs
+        .get(state.selected_tool_run)
+        .map(|r| r.lines.len().saturating_sub(1))
+        .unwrap_or_default();
+
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.tool_output_scroll = (state.tool_output_scroll + 1).min(last_line);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.tool_output_scroll = state.tool_output_scroll.saturating_sub(1);
+        }
+        KeyCode::PageDown => {
+            state.tool_output_scroll = (state.tool_output_scroll + PAGE).min(last_line);
+        }
+        KeyCode::PageUp => {
+            state.tool_output_scroll = state.tool_output_scroll.saturating_sub(PAGE);
+        }
+        KeyCode::Home => state.tool_output_scroll = 0,
+        KeyCode::Char('G') | KeyCode::End => state.tool_output_scroll = last_line,
+        // Launches are newest first: l steps back to older ones
+        KeyCode::Right | KeyCode::Char('l') => {
+            if state.selected_tool_run + 1 < runs {
+                state.selected_tool_run += 1;
+                state.tool_output_scroll = 0;
+            }
+        }
+        KeyCode::Left | KeyCode::Char('h') => {
+            if state.selected_tool_run > 0 {
+                state.selected_tool_run -= 1;
+                state.tool_output_scroll = 0;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,255 @@
+//! Terminal title and taskbar progress
+//!
+//! The window title follows the current activity ("ModSanity - Downloading
+//! SkyUI.7z 42%") so progress stays visible while the TUI is minimized.
+//! Terminals that understand ConEmu's OSC 9;4 sequence (ConEmu, Windows
+//! Terminal, Ghostty) also show the progress on the taskbar button.
+
+use crate::app::state::AppState;
+use crate::queue::QueueStatus;
+use crossterm::{queue, terminal::SetTitle};
+use std::io::{self, Write};
+
+const APP_TITLE: &str = "ModSanity";
+
+/// Something long-running the user may want to watch from the taskbar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+    pub label: String,
+    /// Overall progress (0-100); `None` when the end isn't known
+    pub percent: Option<u16>,
+}
+
+impl Activity {
+    pub fn new(label: impl Into<String>, percent: Option<u16>) -> Self {
+        Self {
+            label: label.into(),
+            percent: percent.map(|p| p.min(100)),
+        }
+    }
+
+    fn title(&self) -> String {
+        match self.percent {
+            Some(percent) => format!("{} - {} {}%", APP_TITLE, self.label, percent),
+            None => format!("{} - {}", APP_TITLE, self.label),
+        }
+    }
+}
+
+/// The most relevant activity in `state`, if any. Installs and downloads win
+/// over queue processing, which reports the batch as a whole.
+pub fn current_activity(state: &AppState) -> Option<Activity> {
+    if let Some(install) = &state.installation_progress {
+        return Some(match (install.current_mod_index, install.total_mods) {
+            (Some(index), Some(total)) if total > 0 => {
+                // Finished mods plus the current mod's share
+                let done = index.saturating_sub(1) as f64 + install.percent as f64 / 100.0;
+                Activity::new(
+                    format!("Installing {}/{}", index, total),
+                    Some((done / total as f64 * 100.0) as u16),
+                )
+            }
+            _ => Activity::new("Installing", Some(install.percent)),
+        });
+    }
+    let downloads: Vec<_> = state.downloads.iter().filter(|d| d.is_active()).collect();
+    if !downloads.is_empty() && !state.queue_processing {
+        let label = match downloads.as_slice() {
+            [download] => format!("Downloading {}", download.name),
+            _ => format!("Downloading {} files", downloads.len()),
+        };
+        // Overall progress only once every size is known
+        let total: u64 = downloads.iter().map(|d| d.total_bytes).sum();
+        let percent = (downloads.iter().all(|d| d.total_bytes > 0) && total > 0).then(|| {
+            let done: u64 = downloads.iter().map(|d| d.downloaded_bytes).sum();
+            (done * 100 / total) as u16
+        });
+        return Some(Activity::new(label, percent));
+    }
+    if state.queue_processing {
+        let total = state.queue_entries.len();
+        let finished = state
+            .queue_entries
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e.status,
+                    QueueStatus::Completed | QueueStatus::Failed | QueueStatus::Skipped
+                )
+            })
+            .count();
+        let percent = (total > 0).then(|| (finished * 100 / total) as u16);
+        let label = if state.queue_paused {
+            format!("Queue paused {}/{}", finished, total)
+        } else {
+            format!("Queue {}/{}", finished, total)
+        };
+        return Some(Activity::new(label, percent));
+    }
+    if let Some(import) = &state.import_progress {
+        let percent = (import.total_plugins > 0)
+            .then(|| (import.current_index * 100 / import.total_plugins) as u16);
+        return Some(Activity::new(format!("Import: {}", import.stage), percent));
+    }
+    if let Some(categorize) = &state.categorization_progress {
+        let percent = (categorize.total_mods > 0)
+            .then(|| (categorize.current_index * 100 / categorize.total_mods) as u16);
+        return Some(Activity::new("Categorizing", percent));
+    }
+    if let Some(catalog) = &state.catalog_progress {
+        return Some(Activity::new("Syncing catalog", catalog.percent()));
+    }
+    if state.bulk_install_running {
+        return Some(Activity::new("Installing", None));
+    }
+    None
+}
+
+/// Taskbar progress setting (`tui.taskbar_progress`)
+fn progress_supported(setting: &str) -> bool {
+    match setting.trim().to_lowercase().as_str() {
+        "on" | "true" | "always" => true,
+        "off" | "false" | "never" => false,
+        _ => {
+            let var = |key: &str| std::env::var(key).unwrap_or_default().to_lowercase();
+            std::env::var_os("WT_SESSION").is_some()
+                || var("ConEmuANSI") == "on"
+                || var("TERM_PROGRAM") == "ghostty"
+        }
+    }
+}
+
+/// Writes the title and progress sequences when the activity changes
+pub struct TitleWriter {
+    title: bool,
+    progress: bool,
+    last: Option<Option<Activity>>,
+}
+
+impl TitleWriter {
+    pub fn new(title: bool, taskbar_progress: &str) -> Self {
+        Self {
+            title,
+            progress: progress_supported(taskbar_progress),
+            last: None,
+        }
+    }
+
+    /// Save the terminal's title so [`TitleWriter::restore`] can put it back
+    pub fn save(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.title {
+            // xterm title stack; ignored by terminals without one
+            write!(out, "\x1b[22;0t")?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Show `activity`, or the idle title
+    pub fn update(&mut self, out: &mut impl Write, activity: Option<Activity>) -> io::Result<()> {
+        if self.last.as_ref() == Some(&activity) {
+            return Ok(());
+        }
+        if self.title {
+            let title = activity
+                .as_ref()
+                .map(Activity::title)
+                .unwrap_or_else(|| APP_TITLE.to_string());
+            queue!(out, SetTitle(title))?;
+        }
+        if self.progress {
+            // OSC 9;4: state 0 clears, 1 sets a value, 3 is indeterminate
+            match activity.as_ref().map(|a| a.percent) {
+                Some(Some(percent)) => write!(out, "\x1b]9;4;1;{}\x07", percent)?,
+                Some(None) => write!(out, "\x1b]9;4;3;0\x07")?,
+                None => write!(out, "\x1b]9;4;0;0\x07")?,
+            }
+        }
+        out.flush()?;
+        self.last = Some(activity);
+        Ok(())
+    }
+
+    /// Clear the taskbar progress and restore the saved title
+    pub fn restore(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.progress {
+            write!(out, "\x1b]9;4;0;0\x07")?;
+        }
+        if self.title {
+            write!(out, "\x1b[23;0t")?;
+        }
+        out.flush()?;
+        self.last = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::InstallProgress;
+    use crate::queue::{QueueControl, Transfers};
+
+    #[test]
+    fn activity_prefers_installs_and_tracks_bulk_progress() {
+        let mut state = AppState::new(None);
+        assert_eq!(current_activity(&state), None);
+
+        let transfers = Transfers::new();
+        transfers
+            .start(
+                "SkyUI.7z",
+                std::path::Path::new("SkyUI.7z"),
+                QueueControl::new(),
+            )
+            .progress(42, 100);
+        state.downloads = transfers.snapshot();
+        let download = current_activity(&state).unwrap();
+        assert_eq!(download.title(), "ModSanity - Downloading SkyUI.7z 42%");
+
+        transfers
+            .start(
+                "USSEP.7z",
+                std::path::Path::new("USSEP.7z"),
+                QueueControl::new(),
+            )
+            .progress(58, 100);
+        state.downloads = transfers.snapshot();
+        assert_eq!(
+            current_activity(&state),
+            Some(Activity::new("Downloading 2 files", Some(50)))
+        );
+
+        state.installation_progress = Some(InstallProgress {
+            percent: 50,
+            current_file: String::new(),
+            total_files: 0,
+            processed_files: 0,
+            current_mod_name: None,
+            current_mod_index: Some(2),
+            total_mods: Some(4),
+        });
+        assert_eq!(
+            current_activity(&state),
+            Some(Activity::new("Installing 2/4", Some(37)))
+        );
+    }
+
+    #[test]
+    fn writer_only_emits_on_change() {
+        let mut writer = TitleWriter::new(true, "on");
+        let mut out = Vec::new();
+        let activity = Some(Activity::new("Deploying", None));
+        writer.update(&mut out, activity.clone()).unwrap();
+        let text = String::from_utf8_lossy(&out).to_string();
+        assert!(text.contains("ModSanity - Deploying"));
+        assert!(text.contains("\x1b]9;4;3;0\x07"));
+
+        out.clear();
+        writer.update(&mut out, activity).unwrap();
+        assert!(out.is_empty());
+
+        writer.update(&mut out, None).unwrap();
+        assert!(String::from_utf8_lossy(&out).contains("\x1b]9;4;0;0\x07"));
+    }
+}
@@ -1,6 +1,14 @@
 //! Main UI rendering
 
+use super::graphics::{self, GraphicsProtocol};
+use super::hit;
+use super::layout;
+use super::palette;
 use super::screens;
+use crate::app::filters;
+use crate::app::groups::{ModGroup, ModRow};
+use crate::app::notifications::{Notification, NotificationLevel};
+use crate::app::sort::ModSort;
 use crate::app::{App, AppState, InputMode, Screen, UiMode};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -61,7 +69,7 @@ fn sfg(color: Color) -> Style {
 fn pipeline_step(screen: Screen) -> Option<usize> {
     match screen {
         Screen::Mods | Screen::Dashboard => Some(0),
-        Screen::ModlistEditor => Some(1),
+        Screen::ModlistEditor | Screen::ModlistDiff => Some(1),
         Screen::Import | Screen::ImportReview | Screen::ModlistReview => Some(2),
         Screen::DownloadQueue => Some(3),
         _ => None,
@@ -70,35 +78,58 @@ fn pipeline_step(screen: Screen) -> Option<usize> {
 
 /// Draw the main UI
 pub fn draw(f: &mut Frame, app: &App, state: &AppState) {
-    let minimal_mode = app
+    let (minimal_mode, image_protocol) = app
         .config
         .try_read()
-        .map(|c| c.tui.minimal_color_mode)
-        .unwrap_or(false);
+        .map(|c| {
+            (
+                c.tui.minimal_color_mode,
+                GraphicsProtocol::from_config(&c.tui.image_previews),
+            )
+        })
+        .unwrap_or((false, GraphicsProtocol::detect()));
     set_minimal_color_mode(minimal_mode);
+    graphics::begin_frame(image_protocol);
+    hit::begin_frame();
+
+    let size = f.area();
+    if size.width < layout::MIN_WIDTH || size.height < layout::MIN_HEIGHT {
+        let message = Paragraph::new(format!(
+            "Terminal too small ({}x{}); ModSanity needs at least {}x{}.",
+            size.width,
+            size.height,
+            layout::MIN_WIDTH,
+            layout::MIN_HEIGHT
+        ))
+        .wrap(Wrap { trim: true })
+        .style(sfg(Color::Yellow));
+        f.render_widget(message, size);
+        return;
+    }
 
-    let output_panel_height = if state.command_output_log.is_empty() {
-        0
+    let log_pane_height = if state.log_pane.visible {
+        LOG_PANE_HEIGHT
     } else {
-        4
+        0
     };
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),                   // Header
-            Constraint::Length(1),                   // Tab bar
-            Constraint::Min(10),                     // Main content
-            Constraint::Length(output_panel_height), // Command output
-            Constraint::Length(3),                   // Footer/status
+            Constraint::Length(3),               // Header
+            Constraint::Length(1),               // Tab bar
+            Constraint::Min(10),                 // Main content
+            Constraint::Length(log_pane_height), // Log pane
+            Constraint::Length(3),               // Footer/status
         ])
         .split(f.area());
 
     draw_header(f, state, chunks[0]);
     draw_tabs(f, state, chunks[1]);
     draw_content(f, app, state, chunks[2]);
-    draw_command_output_panel(f, state, chunks[3]);
+    draw_log_pane(f, state, chunks[3]);
     draw_footer(f, state, chunks[4]);
+    draw_toasts(f, state, chunks[2]);
 
     // Draw confirmation dialog if active
     if let Some(dialog) = &state.show_confirm {
@@ -115,6 +146,14 @@ pub fn draw(f: &mut Frame, app: &App, state: &AppState) {
         draw_help(f, state);
     }
 
+    if state.show_notifications {
+        draw_notification_history(f, state);
+    }
+
+    if let Some(detail) = &state.error_detail {
+        draw_error_detail(f, state, detail);
+    }
+
     // Draw input overlays
     match state.input_mode {
         InputMode::ModInstallPath => draw_mod_install_input(f, state),
@@ -124,6 +163,7 @@ pub fn draw(f: &mut Frame, app: &App, state: &AppState) {
         InputMode::StagingDirectoryInput => draw_staging_directory_input(f, state),
         InputMode::ProtonCommandInput => draw_proton_command_input(f, state),
         InputMode::ExternalToolPathInput => draw_external_tool_path_input(f, state),
+        InputMode::ExternalToolOptionsInput => draw_external_tool_options_input(f, state),
         InputMode::NexusApiKeyInput => draw_nexus_api_key_input(f, state),
         InputMode::FomodComponentSelection => draw_fomod_component_selection(f, state),
         InputMode::CollectionPath => draw_collection_input(f, state),
@@ -138,6 +178,10 @@ pub fn draw(f: &mut Frame, app: &App, state: &AppState) {
         InputMode::ModlistAddCatalogInput => draw_modlist_add_catalog_input(f, state),
         InputMode::ModlistAddDirectoryInput => draw_modlist_add_directory_input(f, state),
         InputMode::QueueManualModIdInput => draw_queue_manual_mod_id_input(f, state),
+        InputMode::CommandPalette => draw_command_palette(f, state),
+        InputMode::ModFilterPicker => draw_mod_filter_picker(f, state),
+        InputMode::ModFilterNameInput => draw_mod_filter_name_input(f, state),
+        InputMode::IniValueInput | InputMode::IniKeyInput => draw_ini_input(f, state),
         _ => {}
     }
 
@@ -161,30 +205,191 @@ pub fn draw(f: &mut Frame, app: &App, state: &AppState) {
         draw_file_picker(f, state);
     }
 
-    // Draw download progress if active
-    if let Some(progress) = &state.download_progress {
-        draw_download_progress(f, progress);
+    // Inline images are drawn over the frame, so drop them under popups
+    if state.show_help
+        || state.show_notifications
+        || state.error_detail.is_some()
+        || state.show_confirm.is_some()
+        || state.show_requirements.is_some()
+        || state.input_mode != InputMode::Normal
+        || state.installation_progress.is_some()
+        || state.showing_file_picker
+    {
+        graphics::suppress();
+    }
+}
+
+fn notification_style(level: NotificationLevel) -> Style {
+    match level {
+        NotificationLevel::Info => sfg(Color::Cyan),
+        NotificationLevel::Success => sfg(Color::Green),
+        NotificationLevel::Error => sfg(Color::Red),
+    }
+}
+
+/// Stack the on-screen toasts in the bottom-right corner of `area`, newest
+/// at the bottom
+fn draw_toasts(f: &mut Frame, state: &AppState, area: Rect) {
+    let width = area.width.saturating_sub(2).min(64);
+    let mut bottom = area.bottom();
+    for toast in state.notifications.toasts().iter().rev() {
+        if width < 10 || bottom < area.y + 3 {
+            break;
+        }
+        let rect = Rect::new(area.right().saturating_sub(width + 1), bottom - 3, width, 3);
+        bottom -= 3;
+
+        let title = if toast.level == NotificationLevel::Error {
+            " Error - Ctrl+X to dismiss "
+        } else {
+            ""
+        };
+        let toast_widget = Paragraph::new(toast.message.as_str()).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(notification_style(toast.level)),
+        );
+        f.render_widget(Clear, rect);
+        f.render_widget(toast_widget, rect);
+    }
+}
+
+/// Draw the notification history overlay, newest first
+fn draw_notification_history(f: &mut Frame, state: &AppState) {
+    let area = centered_rect(80, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let history = state.notifications.history();
+    let count = history.len();
+    let lines: Vec<Line> = if count == 0 {
+        vec![Line::from(Span::styled(
+            "No notifications yet",
+            sfg(Color::DarkGray),
+        ))]
+    } else {
+        history
+            .rev()
+            .skip(state.notifications_scroll.min(count - 1))
+            .enumerate()
+            .map(|(i, n)| {
+                // The entry on top is the one Enter opens
+                let marker = if i == 0 { "▸ " } else { "  " };
+                let more = if n.detail.is_some() { " [+]" } else { "" };
+                Line::from(vec![
+                    Span::raw(marker),
+                    Span::styled(format!("{} ", n.time), sfg(Color::DarkGray)),
+                    Span::styled(n.message.clone(), notification_style(n.level)),
+                    Span::styled(more, sfg(Color::DarkGray)),
+                ])
+            })
+            .collect()
+    };
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(
+                    " Notifications ({}) - j/k:scroll  Enter:details  Ctrl+X:dismiss errors  Esc:close ",
+                    count
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(popup, area);
+}
+
+/// Draw a notification in full: the message and, for failures, the error
+/// chain behind it
+fn draw_error_detail(f: &mut Frame, state: &AppState, detail: &Notification) {
+    let area = centered_rect(70, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            detail.message.clone(),
+            notification_style(detail.level).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("Raised at {}", detail.time),
+            sfg(Color::DarkGray),
+        )),
+        Line::from(""),
+    ];
+    match &detail.detail {
+        Some(text) => lines.extend(text.lines().map(|l| Line::from(l.to_string()))),
+        None => lines.push(Line::from(Span::styled(
+            "No further detail was recorded for this message.",
+            sfg(Color::DarkGray),
+        ))),
     }
+
+    let title = match detail.level {
+        NotificationLevel::Error => " Error Details - j/k:scroll  y:copy  Esc:close ",
+        _ => " Notification - j/k:scroll  y:copy  Esc:close ",
+    };
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(notification_style(detail.level)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((state.error_detail_scroll, 0));
+    f.render_widget(popup, area);
 }
 
-fn draw_command_output_panel(f: &mut Frame, state: &AppState, area: Rect) {
+/// Rows taken by the log pane, including its title line
+pub const LOG_PANE_HEIGHT: u16 = 10;
+
+fn draw_log_pane(f: &mut Frame, state: &AppState, area: Rect) {
     if area.height == 0 {
         return;
     }
 
-    let text: Vec<Line> = state
-        .command_output_log
+    let pane = &state.log_pane;
+    let records = crate::logging::recent_records(pane.level);
+    let rows = area.height.saturating_sub(1) as usize;
+    let end = records
+        .len()
+        .saturating_sub(pane.scroll)
+        .max(rows.min(records.len()));
+    let start = end.saturating_sub(rows);
+
+    let text: Vec<Line> = records[start..end]
         .iter()
-        .map(|line| Line::from(Span::styled(line.clone(), sfg(Color::LightRed))))
+        .map(|record| {
+            let color = match record.level {
+                tracing::Level::ERROR => Color::LightRed,
+                tracing::Level::WARN => Color::Yellow,
+                tracing::Level::INFO => Color::White,
+                _ => Color::DarkGray,
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", record.time), sfg(Color::DarkGray)),
+                Span::styled(format!("{:<5} ", record.level), sfg(color)),
+                Span::styled(record.message.clone(), sfg(color)),
+            ])
+        })
         .collect();
 
-    let panel = Paragraph::new(text)
-        .block(
-            Block::default()
-                .title(" Command Output ")
-                .borders(Borders::TOP),
-        )
-        .wrap(Wrap { trim: true });
+    let level = match pane.level {
+        tracing::Level::TRACE => "all".to_string(),
+        level => format!("{}+", level.as_str().to_lowercase()),
+    };
+    let position = if end < records.len() {
+        format!(" | {} newer", records.len() - end)
+    } else {
+        String::new()
+    };
+    let title = format!(
+        " Log [{}{}]  Ctrl+L hide  Ctrl+B/F scroll  Ctrl+T level  Ctrl+Y copy ",
+        level, position
+    );
+
+    let panel = Paragraph::new(text).block(Block::default().title(title).borders(Borders::TOP));
     f.render_widget(panel, area);
 }
 
@@ -249,32 +454,8 @@ fn draw_header(f: &mut Frame, state: &AppState, area: Rect) {
 
 /// Draw the tab bar
 fn draw_tabs(f: &mut Frame, state: &AppState, area: Rect) {
-    let titles = vec![
-        "F1 Mods",
-        "F2 Plugins",
-        "F3 Profiles",
-        "F4 Settings",
-        "F5 Import",
-        "F6 Queue",
-        "F7 Catalog",
-        "F8 Modlists",
-    ];
-    let selected = match state.current_screen {
-        Screen::Dashboard | Screen::Mods | Screen::ModDetails => 0,
-        Screen::Plugins => 1,
-        Screen::Profiles => 2,
-        Screen::Settings => 3,
-        Screen::Import | Screen::ImportReview => 4,
-        Screen::DownloadQueue => 5,
-        Screen::NexusCatalog => 6,
-        Screen::ModlistEditor => 7,
-        Screen::GameSelect
-        | Screen::FomodWizard
-        | Screen::Collection
-        | Screen::Browse
-        | Screen::LoadOrder
-        | Screen::ModlistReview => 0,
-    };
+    let selected = layout::tab_index(state.current_screen);
+    let titles = layout::tab_titles(selected, area.width);
 
     let tabs = Tabs::new(titles)
         .select(selected)
@@ -308,15 +489,22 @@ fn draw_content(f: &mut Frame, app: &App, state: &AppState, area: Rect) {
         Screen::NexusCatalog => screens::nexus_catalog::render(f, area, state),
         Screen::ModlistReview => draw_modlist_review_screen(f, state, area),
         Screen::ModlistEditor => draw_modlist_editor_screen(f, state, area),
+        Screen::Extensions => screens::extensions::render(f, area, app, state),
+        Screen::Storage => screens::storage::render(f, area, state),
+        Screen::IniDiff => screens::ini_diff::render(f, area, state),
+        Screen::ModlistDiff => screens::modlist_diff::render(f, area, state),
+        Screen::ScriptOverrides => screens::script_overrides::render(f, area, state),
+        Screen::Downloads => screens::downloads::render(f, area, state),
+        Screen::History => screens::history::render(f, area, state),
+        Screen::ToolOutput => screens::tool_output::render(f, area, state),
+        Screen::IniEditor => screens::ini_editor::render(f, area, state),
+        Screen::Saves => screens::saves::render(f, area, state),
     }
 }
 
 /// Draw game selection screen
 fn draw_game_select(f: &mut Frame, app: &App, state: &AppState, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(area);
+    let chunks = layout::with_side_panel(area, 40);
 
     // Game list
     let items: Vec<ListItem> = app
@@ -347,7 +535,7 @@ fn draw_game_select(f: &mut Frame, app: &App, state: &AppState, area: Rect) {
 
     let mut list_state = ratatui::widgets::ListState::default();
     list_state.select(Some(state.selected_game_index));
-    f.render_stateful_widget(list, chunks[0], &mut list_state);
+    hit::render_list(f, state.current_screen, list, chunks[0], &mut list_state);
 
     // Game details
     if let Some(g) = app.games.get(state.selected_game_index) {
@@ -377,20 +565,37 @@ fn draw_game_select(f: &mut Frame, app: &App, state: &AppState, area: Rect) {
 }
 
 /// Draw the mods list screen
+/// Most changes listed in the "since you last played" banner
+const MAX_CHANGELOG_LINES: usize = 6;
+
 fn draw_mods_screen(f: &mut Frame, state: &AppState, area: Rect) {
     let guided = state.ui_mode == UiMode::Guided;
+    // Narrow terminals drop the details column first, then the sidebar
+    let sidebar = if area.width < layout::NARROW_WIDTH {
+        0
+    } else if guided {
+        34
+    } else {
+        38
+    };
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(if guided {
             vec![
-                Constraint::Length(34), // Categories
-                Constraint::Min(10),    // Mod list
+                Constraint::Length(sidebar), // Categories
+                Constraint::Min(10),         // Mod list
+            ]
+        } else if area.width < layout::WIDE_WIDTH {
+            vec![
+                Constraint::Length(sidebar), // Categories sidebar
+                Constraint::Min(10),         // Mod list
+                Constraint::Length(0),       // Details
             ]
         } else {
             vec![
-                Constraint::Length(38),     // Categories sidebar
-                Constraint::Percentage(65), // Mod list
-                Constraint::Percentage(35), // Details
+                Constraint::Length(sidebar), // Categories sidebar
+                Constraint::Percentage(65),  // Mod list
+                Constraint::Percentage(35),  // Details
             ]
         })
         .split(area);
@@ -398,30 +603,71 @@ fn draw_mods_screen(f: &mut Frame, state: &AppState, area: Rect) {
     // Draw categories sidebar
     draw_categories_sidebar(f, state, chunks[0]);
 
-    // Filter mods by selected category and search query (moved to higher scope for use in details panel)
-    let search_lower = state.mod_search_query.to_lowercase();
-    let filtered_mods: Vec<(usize, &crate::mods::InstalledMod)> = state
-        .installed_mods
-        .iter()
-        .enumerate()
-        .filter(|(_, m)| {
-            // Apply category filter
-            let category_match = if let Some(filter_id) = state.category_filter {
-                m.category_id == Some(filter_id)
-            } else {
-                true // Show all if no category filter
-            };
+    // Script extender version mismatch banner above the mod list
+    let list_area = if let Some(warning) = &state.script_extender_warning {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)])
+            .split(chunks[1]);
+        let banner = Paragraph::new(Line::from(Span::styled(
+            format!("⚠ {}", warning),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(" Script Extender "),
+        );
+        f.render_widget(banner, split[0]);
+        split[1]
+    } else {
+        chunks[1]
+    };
 
-            // Apply search filter
-            let search_match = if search_lower.is_empty() {
-                true // Show all if no search query
-            } else {
-                m.name.to_lowercase().contains(&search_lower)
-            };
+    // Changes since the last play session, after a gap
+    let list_area = if let Some(since) = &state.since_last_played {
+        let lines: Vec<String> = since.changes.lines();
+        let shown = lines.len().min(MAX_CHANGELOG_LINES);
+        // Borders, summary, the listed changes and the overflow note
+        let height = shown + 3 + usize::from(lines.len() > shown);
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(height as u16), Constraint::Min(3)])
+            .split(list_area);
+        let mut text = vec![Line::from(Span::styled(
+            since.changes.summary(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))];
+        text.extend(lines.iter().take(shown).map(|l| Line::from(l.as_str())));
+        if lines.len() > shown {
+            text.push(Line::from(format!(
+                "... and {} more ('modsanity status --history' lists all)",
+                lines.len() - shown
+            )));
+        }
+        let title = format!(
+            " Since you last played ({}) - w:dismiss ",
+            since
+                .played_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+        );
+        let banner = Paragraph::new(text).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(title),
+        );
+        f.render_widget(banner, split[0]);
+        split[1]
+    } else {
+        list_area
+    };
 
-            category_match && search_match
-        })
-        .collect();
+    // Filtered, sorted and grouped once, for the list and the details panel
+    let rows = state.mod_rows();
 
     // Mod list
     if state.installed_mods.is_empty() {
@@ -443,35 +689,43 @@ fn draw_mods_screen(f: &mut Frame, state: &AppState, area: Rect) {
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
 
-        f.render_widget(empty, chunks[1]);
+        f.render_widget(empty, list_area);
     } else {
-        let items: Vec<ListItem> = filtered_mods
+        // Pending range mark (V) spans from the anchor to the cursor
+        let pending_range = state.mod_mark_anchor.map(|anchor| {
+            (
+                anchor.min(state.selected_mod_index),
+                anchor.max(state.selected_mod_index),
+            )
+        });
+
+        let items: Vec<ListItem> = rows
             .iter()
             .enumerate()
-            .map(|(display_i, (_, m))| {
+            .map(|(display_i, row)| {
+                let m = match row {
+                    ModRow::Mod(m) => m,
+                    ModRow::Group(group) => {
+                        return mod_group_item(group, display_i == state.selected_mod_index)
+                    }
+                };
                 let status = if m.enabled { "[*]" } else { "[ ]" };
+                let marked = state.marked_mod_ids.contains(&m.id);
+                let mark = if marked { "●" } else { " " };
+                let in_range =
+                    pending_range.is_some_and(|(lo, hi)| display_i >= lo && display_i <= hi);
                 let style = if display_i == state.selected_mod_index {
                     Style::default()
                         .bg(Color::DarkGray)
                         .add_modifier(Modifier::BOLD)
+                } else if marked || in_range {
+                    Style::default().fg(Color::Cyan)
                 } else if !m.enabled {
                     Style::default().fg(Color::DarkGray)
                 } else {
                     Style::default()
                 };
 
-                // Add category indicator if categorized
-                let category_indicator = if let Some(cat_id) = m.category_id {
-                    state
-                        .categories
-                        .iter()
-                        .find(|c| c.id == Some(cat_id))
-                        .map(|c| format!("[{}] ", &c.name[..c.name.len().min(3)]))
-                        .unwrap_or_default()
-                } else {
-                    String::new()
-                };
-
                 // Add update indicator if update is available
                 let update_indicator = if let Some(nexus_id) = m.nexus_mod_id {
                     if state.available_updates.contains_key(&nexus_id) {
@@ -483,35 +737,66 @@ fn draw_mods_screen(f: &mut Frame, state: &AppState, area: Rect) {
                     ""
                 };
 
+                // Installed file was archived or deleted on Nexus
+                let unavailable_indicator = if state.unavailable_files.contains_key(&m.id) {
+                    "⚠ "
+                } else {
+                    ""
+                };
+
+                // Plugins need DLC the game doesn't have
+                let dlc_indicator = if state.missing_dlc.contains_key(&m.id) {
+                    "⛔ "
+                } else {
+                    ""
+                };
+
+                // The sort column, when it isn't already on the line
+                let sort_value = match state.mod_sort {
+                    ModSort::Installed => format!(" - {}", m.installed_at.get(..10).unwrap_or("")),
+                    ModSort::Size => format!(" - {}", crate::mods::format_bytes(m.size)),
+                    _ => String::new(),
+                };
+
                 ListItem::new(format!(
-                    " {} {}{}{} (v{})",
-                    status, category_indicator, update_indicator, m.name, m.version
+                    "{}{} {}{}{}{} (v{}){}",
+                    mark,
+                    status,
+                    update_indicator,
+                    unavailable_indicator,
+                    dlc_indicator,
+                    m.name,
+                    m.version,
+                    sort_value
                 ))
                 .style(style)
             })
             .collect();
 
-        let mut title = if let Some(filter_id) = state.category_filter {
-            let cat_name = state
-                .categories
-                .iter()
-                .find(|c| c.id == Some(filter_id))
-                .map(|c| c.name.as_str())
-                .unwrap_or("Unknown");
-            format!(" Installed Mods - {} ({}) ", cat_name, filtered_mods.len())
-        } else {
-            format!(" Installed Mods ({}) ", filtered_mods.len())
-        };
+        let shown = rows.iter().filter(|r| r.as_mod().is_some()).count();
+        let mut title = format!(" Installed Mods ({}) ", shown);
 
         // Add search indicator if searching
         if !state.mod_search_query.is_empty() {
             title = format!(
                 " Installed Mods - Search: \"{}\" ({}) ",
-                state.mod_search_query,
-                filtered_mods.len()
+                state.mod_search_query, shown
             );
         }
 
+        if let Some(filter) = state.active_filter() {
+            title = format!("{}[Filter: {}] ", title, filter.name);
+        }
+
+        if !state.marked_mod_ids.is_empty() {
+            title = format!("{}[{} marked] ", title, state.marked_mod_ids.len());
+        }
+
+        if state.mod_sort != ModSort::Priority || state.mod_sort_reverse {
+            let arrow = if state.mod_sort_reverse { "▼" } else { "▲" };
+            title = format!("{}[Sort: {} {}] ", title, state.mod_sort.label(), arrow);
+        }
+
         let list = List::new(items)
             .block(Block::default().title(title).borders(Borders::ALL))
             .highlight_style(Style::default().add_modifier(Modifier::BOLD));
@@ -520,7 +805,7 @@ fn draw_mods_screen(f: &mut Frame, state: &AppState, area: Rect) {
         let mut list_state = ratatui::widgets::ListState::default();
         list_state.select(Some(state.selected_mod_index));
 
-        f.render_stateful_widget(list, chunks[1], &mut list_state);
+        hit::render_list(f, state.current_screen, list, list_area, &mut list_state);
     }
 
     // Mod details panel (Advanced mode)
@@ -530,7 +815,7 @@ fn draw_mods_screen(f: &mut Frame, state: &AppState, area: Rect) {
 
     // Mod details panel
     // Get the mod from the filtered list, not the full list
-    let selected_mod = filtered_mods.get(state.selected_mod_index).map(|(_, m)| *m);
+    let selected_mod = rows.get(state.selected_mod_index).and_then(ModRow::as_mod);
     if let Some(m) = selected_mod {
         let mut details = vec![
             Line::from(Span::styled(
@@ -580,6 +865,35 @@ fn draw_mods_screen(f: &mut Frame, state: &AppState, area: Rect) {
             }
         }
 
+        if let Some(flag) = state.unavailable_files.get(&m.id) {
+            details.push(Line::from(""));
+            details.push(Line::from(Span::styled(
+                format!("⚠ File no longer available on Nexus ({})", flag.status),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )));
+            match (&flag.replacement_name, flag.replacement_file_id) {
+                (Some(name), Some(file_id)) => details.push(Line::from(format!(
+                    "Current:  {} v{} (file {})",
+                    name,
+                    flag.replacement_version.as_deref().unwrap_or("?"),
+                    file_id
+                ))),
+                _ => details.push(Line::from("Current:  no replacement file listed")),
+            }
+        }
+
+        if let Some(missing) = state.missing_dlc.get(&m.id) {
+            details.push(Line::from(""));
+            details.push(Line::from(Span::styled(
+                format!("⛔ Needs DLC: {}", missing.dlc.join(", ")),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )));
+            details.push(Line::from(format!(
+                "Not deployed: {}",
+                missing.plugins.join(", ")
+            )));
+        }
+
         let details_widget = Paragraph::new(details)
             .block(
                 Block::default()
@@ -602,38 +916,34 @@ fn draw_mods_screen(f: &mut Frame, state: &AppState, area: Rect) {
     }
 }
 
+/// Category header row of the mod list
+fn mod_group_item(group: &ModGroup, selected: bool) -> ListItem<'static> {
+    let arrow = if group.collapsed { "▸" } else { "▾" };
+    let style = if selected {
+        Style::default()
+            .bg(Color::DarkGray)
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    };
+    ListItem::new(format!(
+        "{} {} ({}/{} enabled)",
+        arrow, group.name, group.enabled, group.total
+    ))
+    .style(style)
+}
+
 /// Draw mod details screen
 fn draw_mod_details(f: &mut Frame, state: &AppState, area: Rect) {
-    // Apply the same category and search filters as the mods screen
-    let search_lower = state.mod_search_query.to_lowercase();
-    let filtered_mods: Vec<&crate::mods::InstalledMod> = state
-        .installed_mods
-        .iter()
-        .filter(|m| {
-            // Apply category filter
-            let category_match = if let Some(filter_id) = state.category_filter {
-                m.category_id == Some(filter_id)
-            } else {
-                true
-            };
-
-            // Apply search filter
-            let search_match = if search_lower.is_empty() {
-                true
-            } else {
-                m.name.to_lowercase().contains(&search_lower)
-            };
-
-            category_match && search_match
-        })
-        .collect();
-
-    let m = match filtered_mods.get(state.selected_mod_index) {
-        Some(&m) => m,
-        None => return,
+    // Same filters and order as the mods screen
+    let Some(m) = state.selected_mod() else {
+        return;
     };
 
-    let text = vec![
+    let mut text = vec![
         Line::from(Span::styled(
             format!(" {} ", m.name),
             Style::default()
@@ -656,23 +966,80 @@ fn draw_mod_details(f: &mut Frame, state: &AppState, area: Rect) {
         Line::from(format!("  Path:     {}", m.install_path.display())),
     ];
 
+    let heading = |title: &str| {
+        Line::from(Span::styled(
+            format!("  {}", title),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let page = state
+        .mod_page
+        .as_ref()
+        .filter(|(id, _)| Some(*id) == m.nexus_mod_id)
+        .map(|(_, page)| page);
+    text.push(Line::from(""));
+    match page {
+        Some(page) => {
+            if let Some(summary) = &page.summary {
+                text.push(heading("Summary"));
+                text.extend(summary.lines().map(|l| Line::from(format!("  {}", l))));
+                text.push(Line::from(""));
+            }
+            if let Some(entry) = page.latest_changes() {
+                text.push(heading(&format!("Changes in {}", entry.version)));
+                text.extend(
+                    entry
+                        .changes
+                        .iter()
+                        .map(|c| Line::from(format!("  • {}", c))),
+                );
+                text.push(Line::from(""));
+            }
+            if !page.description.is_empty() {
+                text.push(heading("Description"));
+                text.extend(
+                    page.description
+                        .lines()
+                        .map(|l| Line::from(format!("  {}", l))),
+                );
+                text.push(Line::from(""));
+            }
+            text.push(Line::from(Span::styled(
+                format!("  Fetched from Nexus {}", page.fetched_at),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        None if state.mod_page_loading => text.push(Line::from(Span::styled(
+            "  Loading Nexus page...",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        None if m.nexus_mod_id.is_none() => text.push(Line::from(Span::styled(
+            "  Not linked to a Nexus mod",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        None => text.push(Line::from(Span::styled(
+            "  No Nexus page cached (r: fetch)",
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+
     let details = Paragraph::new(text)
         .block(
             Block::default()
                 .title(" Mod Details ")
                 .borders(Borders::ALL),
         )
-        .wrap(Wrap { trim: true });
+        .wrap(Wrap { trim: true })
+        .scroll((state.mod_details_scroll, 0));
 
     f.render_widget(details, area);
 }
 
 /// Draw plugins screen
 fn draw_plugins_screen(f: &mut Frame, state: &AppState, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(area);
+    let chunks = layout::with_side_panel(area, 40);
 
     // Filter plugins by search query
     let search_lower = state.plugin_search_query.to_lowercase();
@@ -768,7 +1135,7 @@ fn draw_plugins_screen(f: &mut Frame, state: &AppState, area: Rect) {
         let mut list_state = ratatui::widgets::ListState::default();
         list_state.select(Some(state.selected_plugin_index));
 
-        f.render_stateful_widget(list, chunks[0], &mut list_state);
+        hit::render_list(f, state.current_screen, list, chunks[0], &mut list_state);
     }
 
     // Plugin details or help
@@ -830,6 +1197,7 @@ fn draw_plugins_screen(f: &mut Frame, state: &AppState, area: Rect) {
             Line::from("  s        Save load order"),
             Line::from("  S        Auto-sort (native Rust)"),
             Line::from("  L        Auto-sort (LOOT CLI)"),
+            Line::from("  u/^R     Undo/redo last edit"),
         ])
         .block(Block::default().title(" Help ").borders(Borders::ALL))
         .style(Style::default().fg(Color::DarkGray));
@@ -840,10 +1208,7 @@ fn draw_plugins_screen(f: &mut Frame, state: &AppState, area: Rect) {
 
 /// Draw profiles screen
 fn draw_profiles_screen(f: &mut Frame, state: &AppState, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(area);
+    let chunks = layout::with_side_panel(area, 50);
 
     // Profile list
     if state.profiles.is_empty() {
@@ -888,16 +1253,19 @@ fn draw_profiles_screen(f: &mut Frame, state: &AppState, area: Rect) {
         let mut list_state = ratatui::widgets::ListState::default();
         list_state.select(Some(state.selected_profile_index));
 
-        f.render_stateful_widget(list, chunks[0], &mut list_state);
+        hit::render_list(f, state.current_screen, list, chunks[0], &mut list_state);
     }
 
     // Help panel
-    let help = Paragraph::new(vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from("Profile Management:"),
         Line::from(""),
         Line::from("  n        New profile"),
         Line::from("  Enter    Switch to profile"),
+        Line::from("  c        Compare INI settings"),
+        Line::from("  i        Store INI files in profile"),
+        Line::from("  I        Drop profile's INI files"),
         Line::from("  d        Delete profile"),
         Line::from("  j/k      Navigate"),
         Line::from(""),
@@ -905,19 +1273,42 @@ fn draw_profiles_screen(f: &mut Frame, state: &AppState, area: Rect) {
         Line::from("configuration so you can"),
         Line::from("quickly switch between"),
         Line::from("different setups."),
-    ])
-    .block(Block::default().title(" Help ").borders(Borders::ALL))
-    .style(Style::default().fg(Color::DarkGray));
+    ];
+    if let Some(profile) = state.profiles.get(state.selected_profile_index) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(if profile.own_ini_files {
+            "INI files: own copies"
+        } else {
+            "INI files: shared"
+        }));
+        lines.push(Line::from(if profile.own_saves {
+            "Saves: own folder"
+        } else {
+            "Saves: shared"
+        }));
+        if profile.tool_overrides.is_empty() {
+            lines.push(Line::from("Tools: global settings"));
+        } else {
+            lines.push(Line::from(Span::styled(
+                "Tool overrides:",
+                Style::default().fg(Color::Yellow),
+            )));
+            for (tool, ovr) in &profile.tool_overrides {
+                lines.push(Line::from(format!("  {}: {}", tool, ovr.summary())));
+            }
+        }
+    }
+    let help = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().title(" Help ").borders(Borders::ALL))
+        .style(Style::default().fg(Color::DarkGray));
 
     f.render_widget(help, chunks[1]);
 }
 
 /// Draw collection screen
 fn draw_collection_screen(f: &mut Frame, state: &AppState, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(area);
+    let chunks = layout::with_side_panel(area, 40);
 
     // Collection mod list
     if let Some(ref collection) = state.current_collection {
@@ -1133,17 +1524,19 @@ fn draw_settings_screen(f: &mut Frame, app: &App, state: &AppState, area: Rect)
         symphony_display,
         bodyslide_display,
         outfit_display,
+        geck_display,
         api_key_display,
         deployment_method_display,
         backup_display,
+        watch_downloads_display,
     ) = if let Ok(config) = app.config.try_read() {
         let mod_dir = config
             .tui
             .default_mod_directory
             .clone()
             .unwrap_or_else(|| "Not set".to_string());
-        let downloads_dir = config.downloads_dir().display().to_string();
-        let staging_dir = config.staging_dir().display().to_string();
+        let downloads_dir = crate::app::dir_display(&config.downloads_dir());
+        let staging_dir = crate::app::dir_display(&config.staging_dir());
         let proton_cmd = config.external_tools.proton_command.clone();
         let proton_runtime = config
             .external_tools
@@ -1191,6 +1584,11 @@ fn draw_settings_screen(f: &mut Frame, app: &App, state: &AppState, area: Rect)
             .outfitstudio_path
             .clone()
             .unwrap_or_else(|| "Not set".to_string());
+        let geck = config
+            .external_tools
+            .geck_path
+            .clone()
+            .unwrap_or_else(|| "Not set (GECK.exe in the game folder)".to_string());
 
         let api_key = if let Some(ref key) = config.nexus_api_key {
             if key.len() > 8 {
@@ -1211,6 +1609,12 @@ fn draw_settings_screen(f: &mut Frame, app: &App, state: &AppState, area: Rect)
             "No"
         }
         .to_string();
+        let watch_downloads = if config.install.watch_downloads {
+            "Enabled"
+        } else {
+            "Disabled"
+        }
+        .to_string();
 
         (
             mod_dir,
@@ -1226,9 +1630,11 @@ fn draw_settings_screen(f: &mut Frame, app: &App, state: &AppState, area: Rect)
             symphony,
             bodyslide,
             outfit,
+            geck,
             api_key,
             deployment_method,
             backup_originals,
+            watch_downloads,
         )
     } else {
         (
@@ -1248,10 +1654,33 @@ fn draw_settings_screen(f: &mut Frame, app: &App, state: &AppState, area: Rect)
             "Loading...".to_string(),
             "Loading...".to_string(),
             "Loading...".to_string(),
+            "Loading...".to_string(),
+            "Loading...".to_string(),
         )
     };
 
-    let settings = vec![
+    let custom_tools: Vec<(String, String)> = app
+        .config
+        .try_read()
+        .map(|config| {
+            config
+                .custom_tools
+                .iter()
+                .map(|tool| {
+                    (
+                        format!("{} (custom tool)", tool.name),
+                        format!(
+                            "{} ({}), Enter to launch",
+                            tool.path,
+                            tool.runtime_mode.as_str()
+                        ),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut settings = vec![
         ("NexusMods API Key", api_key_display),
         ("Deployment Method", deployment_method_display),
         ("Backup Originals", backup_display),
@@ -1268,8 +1697,34 @@ fn draw_settings_screen(f: &mut Frame, app: &App, state: &AppState, area: Rect)
         ("Synthesis Path", symphony_display),
         ("BodySlide Path", bodyslide_display),
         ("Outfit Studio Path", outfit_display),
+        ("GECK Path", geck_display),
+        (
+            "Debug Logging (this session)",
+            if crate::logging::debug_enabled() {
+                "Enabled (Ctrl+D)"
+            } else {
+                "Disabled (Ctrl+D)"
+            }
+            .to_string(),
+        ),
+        ("Watch Downloads Folder", watch_downloads_display),
         ("Game Selection", "Change active game".to_string()),
     ];
+    // Tool path rows follow `ExternalTool::all()` from row 9
+    if let Ok(config) = app.config.try_read() {
+        for (i, tool) in crate::config::ExternalTool::all().iter().enumerate() {
+            if let (Some(row), Some(options)) =
+                (settings.get_mut(9 + i), config.external_tool_options(*tool))
+            {
+                row.1 = format!("{}  [{}]", row.1, options.summary());
+            }
+        }
+    }
+    settings.extend(
+        custom_tools
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.clone())),
+    );
 
     let items: Vec<ListItem> = settings
         .iter()
@@ -1298,20 +1753,29 @@ fn draw_settings_screen(f: &mut Frame, app: &App, state: &AppState, area: Rect)
 
     let mut list_state = ratatui::widgets::ListState::default();
     list_state.select(Some(state.selected_setting_index));
-    f.render_stateful_widget(list, area, &mut list_state);
+    hit::render_list(f, state.current_screen, list, area, &mut list_state);
 }
 
 /// Draw FOMOD wizard
-/// Draw categories sidebar for filtering
+/// Draw categories sidebar, marking the group under the cursor
 fn draw_categories_sidebar(f: &mut Frame, state: &AppState, area: Rect) {
+    // Category of the nearest header at or above the cursor; a flat list has none
+    let rows = state.mod_rows();
+    let current = rows
+        .iter()
+        .take(state.selected_mod_index.saturating_add(1))
+        .rev()
+        .find_map(ModRow::as_group)
+        .map(|g| g.category_id);
+
     // Build category list with "All" option at the top
     let mut items = vec![ListItem::new(Line::from(Span::styled(
-        if state.category_filter.is_none() {
+        if current.is_none() {
             " > All Categories"
         } else {
             "   All Categories"
         },
-        if state.category_filter.is_none() {
+        if current.is_none() {
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD)
@@ -1322,8 +1786,14 @@ fn draw_categories_sidebar(f: &mut Frame, state: &AppState, area: Rect) {
 
     // Add each category
     for category in &state.categories {
-        let is_selected = state.category_filter == category.id;
-        let prefix = if is_selected { " > " } else { "   " };
+        let is_selected = current == Some(category.id);
+        let prefix = if is_selected {
+            " > "
+        } else if state.collapsed_categories.contains(&category.id) {
+            " ▸ "
+        } else {
+            "   "
+        };
 
         // Count mods in this category
         let mod_count = state
@@ -1360,7 +1830,10 @@ fn draw_categories_sidebar(f: &mut Frame, state: &AppState, area: Rect) {
     );
 
     let mut list_state = ratatui::widgets::ListState::default();
-    list_state.select(Some(state.selected_category_index));
+    let marked = current
+        .and_then(|id| state.categories.iter().position(|c| c.id == id))
+        .map_or(0, |i| i + 1);
+    list_state.select(Some(marked));
     f.render_stateful_widget(list, area, &mut list_state);
 }
 
@@ -1379,7 +1852,17 @@ fn parse_color(hex: &str) -> Option<Color> {
 
 /// Draw footer with status and keybindings
 fn draw_footer(f: &mut Frame, state: &AppState, area: Rect) {
-    let status = state.status_message.as_deref().unwrap_or("");
+    let errors = state
+        .notifications
+        .toasts()
+        .iter()
+        .filter(|t| t.level == NotificationLevel::Error)
+        .count();
+    let status = if errors > 0 {
+        format!("{} error(s): Ctrl+X dismiss, Ctrl+N history", errors)
+    } else {
+        String::new()
+    };
 
     let guided = state.ui_mode == UiMode::Guided;
 
@@ -1387,60 +1870,80 @@ fn draw_footer(f: &mut Frame, state: &AppState, area: Rect) {
         match state.current_screen {
             Screen::GameSelect => "Enter:select  z:advanced  q:quit",
             Screen::Mods | Screen::Dashboard => {
-                "j/k:nav  i:install  Space:toggle  d:delete  D:deploy  S:save-list  L:load-list  ?:help  z:advanced"
+                "j/k:nav  >:sort  m:filters  i:install  e:toggle  Space:mark  V:range  d:delete  D:deploy  S:save-list  L:load-list  ?:help  z:advanced"
             }
             Screen::ModlistReview => "j/k:nav  Enter:queue-downloads  Esc:cancel  ?:help  z:advanced",
             Screen::LoadOrder => {
                 if state.reorder_mode {
-                    "j/k:move  Enter:done  s:save  Esc:cancel"
+                    "j/k:move  u:undo  Enter:done  s:save  Esc:cancel"
                 } else {
-                    "Enter:reorder  j/k:navigate  s:save  S:auto-sort  Esc:back  ?:help  z:advanced"
+                    "Enter:reorder  j/k:navigate  s:save  S:auto-sort  u/^R:undo/redo  v:group  p:scripts  Esc:back  ?:help  z:advanced"
                 }
             }
             Screen::Plugins => {
                 if state.plugin_reorder_mode {
-                    "j/k:move  Enter:done  s:save  Esc:cancel"
+                    "j/k:move  u:undo  Enter:done  s:save  Esc:cancel"
                 } else {
-                    "j/k:nav  Space:toggle  s:save  S:auto-sort  D:deploy  L:loot-sort  ?:help  z:advanced"
+                    "j/k:nav  Space:toggle  s:save  S:auto-sort  u/^R:undo/redo  D:deploy  L:loot-sort  C:clean  ?:help  z:advanced"
                 }
             }
-            Screen::Profiles => "j/k:nav  n:new  Enter:activate  d:delete  ?:help  z:advanced",
-            Screen::Settings => "j/k:nav  Enter:edit  l:launch-tool  Esc:back  ?:help  z:advanced",
+            Screen::Profiles => "j/k:nav  n:new  Enter:activate  c:compare-ini  i:store-ini  s:saves  o:own-saves  d:delete  ?:help  z:advanced",
+            Screen::IniDiff => "j/k:scroll  h/l:target  a:apply-preset  Esc:back  ?:help  z:advanced",
+            Screen::ModlistDiff => "j/k:scroll  h/l:target  Esc:back  ?:help  z:advanced",
+            Screen::Settings => "j/k:nav  Enter:edit  l/L:launch-tool/no-deploy-check  a/w/e:tool-args/dir/env  o:tool-output  i:ini-files  Esc:back  ?:help  z:advanced",
             Screen::Collection => "j/k:nav  i:install  a:install-all  Esc:back  ?:help  z:advanced",
             Screen::Browse => "s:search  j/k:nav  Enter:select-file  Esc:back  ?:help  z:advanced",
-            Screen::ModDetails => "j/k:scroll  Esc:back  ?:help  z:advanced",
+            Screen::ModDetails => "j/k:scroll  r:refresh page  y:copy-link  Esc:back  ?:help  z:advanced",
             Screen::FomodWizard => "j/k:nav  Space:select  Enter:continue  b:back  Esc:cancel  ?:help",
-            Screen::DownloadQueue => "j/k:nav  p:process  m:choose-match  r:refresh  c:clear  ?:help  z:advanced",
+            Screen::DownloadQueue => "j/k:nav  p:process  P:pause  x:cancel  f:file  J/K:move  !:priority  m:choose-match  r:refresh  c:clear  ?:help  z:advanced",
+            Screen::Extensions => "j/k:nav  Enter:run  c:clear-output  Esc:back  ?:help  z:advanced",
+            Screen::Storage => "j/k:nav  r:rescan  a:clean-archives  o:clean-orphans  Esc:back  ?:help  z:advanced",
+            Screen::ScriptOverrides => "j/k:nav  r:rescan  Esc:back  ?:help  z:advanced",
+            Screen::Downloads => "j/k:nav  x:cancel  c:clear-finished  Esc:back  ?:help  z:advanced",
+            Screen::History => "j/k:nav  f:filter  r:refresh  Esc:back  ?:help  z:advanced",
+            Screen::ToolOutput => "j/k:scroll  Home/End:top/end  h/l:newer/older launch  Esc:back  ?:help  z:advanced",
+            Screen::IniEditor => "j/k:nav  h/l:file  Enter:edit  Space:toggle  a:add-key  e:$EDITOR  u:restore-backup  Esc:back  ?:help  z:advanced",
+            Screen::Saves => "j/k:nav  b:backup  d:delete  Esc:back  ?:help  z:advanced",
             _ => "?:help  Esc:back  z:advanced  q:quit",
         }
     } else {
         match state.current_screen {
         Screen::GameSelect => "Enter:select  q:quit",
         Screen::Mods | Screen::Dashboard => {
-            "/:search  j/k:nav  i:install  r:show-all  v:resolve-names  S:save  L:load(saved/file)  b:browse  o:load-order  Space:toggle  d:delete  D:deploy  ?:help  q:quit"
+            "/:search  m:filters  >:sort  j/k:nav  y/Y:copy  i:install  r:show-all  v:resolve-names  S:save  L:load(saved/file)  b:browse  o:load-order  e:toggle  Space:mark  V:range  d:delete  D:deploy  ?:help  q:quit"
         },
         Screen::ModlistReview => "j/k:nav  Enter:queue-downloads  Esc:cancel  ?:help",
         Screen::LoadOrder => {
             if state.reorder_mode {
-                "j/k:move  J/K:jump-5  t/b:top/bottom  Enter:stop-reorder  s:save  Esc:cancel-reorder"
+                "j/k:move  J/K:jump-5  t/b:top/bottom  u/^R:undo/redo  Enter:stop-reorder  s:save  Esc:cancel-reorder"
             } else {
-                "Enter:reorder  j/k:navigate  s:save  S:auto-sort  Esc:back  ?:help  q:quit"
+                "Enter:reorder  j/k:navigate  s:save  S:auto-sort  u/^R:undo/redo  v:group-by-type  p:script-overrides  Esc:back  ?:help  q:quit"
             }
         }
         Screen::Plugins => {
             if state.plugin_reorder_mode {
-                "j/k:move  J/K:jump-5  t/b:top/bottom  #:go-to-position  Enter:stop-reorder  s:save  Esc:cancel"
+                "j/k:move  J/K:jump-5  t/b:top/bottom  #:go-to-position  u/^R:undo/redo  Enter:stop-reorder  s:save  Esc:cancel"
             } else {
-                "/:search  Enter:reorder  j/k:nav  Space:toggle  a:enable-all  n:disable-all  s:save  S:auto-sort  D:deploy  L:loot-sort  ?:help  q:quit"
+                "/:search  Enter:reorder  j/k:nav  Space:toggle  a:enable-all  n:disable-all  u/^R:undo/redo  s:save  S:auto-sort  D:deploy  L:loot-sort  C:clean-dirty  ?:help  q:quit"
             }
         }
-        Screen::Profiles => "j/k:nav  n:new  Enter:activate  d:delete  ?:help  q:quit",
-        Screen::Settings => "j/k:nav  Enter:edit  l:launch-tool  Esc:back  ?:help  q:quit",
+        Screen::Profiles => "j/k:nav  n:new  Enter:activate  c:compare-ini  i:store-ini  s:saves  o:own-saves  d:delete  ?:help  q:quit",
+        Screen::IniDiff => "j/k:scroll  h/l:target  a:apply-preset  Esc:back  ?:help  q:quit",
+        Screen::ModlistDiff => "j/k:scroll  h/l:target  Esc:back  ?:help  q:quit",
+        Screen::Settings => "j/k:nav  Enter:edit  l/L:launch-tool/no-deploy-check  a/w/e:tool-args/dir/env  o:tool-output  i:ini-files  Esc:back  ?:help  q:quit",
         Screen::Collection => "j/k:nav  i:install  a:install-all  Esc:back  ?:help  q:quit",
-        Screen::Browse => "s:search  f:sort  n/p:page  j/k:nav  Enter:select-file  Esc:back  ?:help  q:quit",
-        Screen::ModDetails => "j/k:scroll  Esc:back  ?:help  q:quit",
+        Screen::Browse => "s:search  f:sort  r:surprise  a:queue  n/p:page  j/k:nav  Enter:select-file  Esc:back  ?:help  q:quit",
+        Screen::ModDetails => "j/k:scroll  r:refresh page  y:copy-link  Esc:back  ?:help  q:quit",
         Screen::FomodWizard => "j/k:nav  Space:select  Enter:continue  b:back  Esc:cancel  ?:help",
-        Screen::DownloadQueue => "j/k:nav  h/l:alt  m:apply-alt  M:manual-id  p:process  r:refresh  c:clear  ?:help  q:quit",
+        Screen::DownloadQueue => "j/k:nav  h/l:alt  m:apply-alt  M:manual-id  f:file  J/K:move  !:priority  p:process  P:pause  x:cancel  r:refresh  c:clear  ?:help  q:quit",
+        Screen::Extensions => "j/k:nav  Enter:run  c:clear-output  Esc:back  ?:help  q:quit",
+        Screen::Storage => "j/k:nav  r:rescan  a:clean-archives  o:clean-orphans  Esc:back  ?:help  q:quit",
+        Screen::ScriptOverrides => "j/k:nav  r:rescan  Esc:back  ?:help  q:quit",
+        Screen::Downloads => "j/k:nav  x:cancel  c:clear-finished  Esc:back  ?:help  q:quit",
+        Screen::History => "j/k:nav  f:filter  r:refresh  Esc:back  ?:help  q:quit",
+        Screen::ToolOutput => "j/k:scroll  Home/End:top/end  h/l:newer/older launch  Esc:back  ?:help  q:quit",
+        Screen::IniEditor => "j/k:nav  h/l:file  Enter:edit  Space:toggle  a:add-key  e:$EDITOR  u:restore-backup  r:reload  Esc:back  ?:help  q:quit",
+        Screen::Saves => "j/k:nav  b:backup  d:delete  r:reload  Esc:back  ?:help  q:quit",
         _ => "?:help  Esc:back  q:quit",
         }
     };
@@ -1574,7 +2077,19 @@ fn draw_requirements_dialog(f: &mut Frame, dialog: &crate::app::state::Requireme
             text_lines.push(Line::from(""));
 
             for (i, dlc) in dialog.dlc_requirements.iter().enumerate() {
-                text_lines.push(Line::from(format!("  {}. {}", i + 1, dlc.name)));
+                let mut line = vec![Span::raw(format!("  {}. {}", i + 1, dlc.name))];
+                match dialog.dlc_installed.get(i).copied().flatten() {
+                    Some(true) => line.push(Span::styled(
+                        "  ✓ installed",
+                        Style::default().fg(Color::Green),
+                    )),
+                    Some(false) => line.push(Span::styled(
+                        "  ✗ not installed",
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    )),
+                    None => {}
+                }
+                text_lines.push(Line::from(line));
                 if let Some(ref notes) = dlc.notes {
                     text_lines.push(Line::from(Span::styled(
                         format!("     {}", notes),
@@ -1631,6 +2146,10 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  F6 Queue",
                 "  F7 Catalog",
                 "  F8 Modlists",
+                "  F9 Extensions",
+                "  F10 Storage",
+                "  F11 Downloads",
+                "  F12 History",
                 "",
                 "Global",
                 "  1..8        Workflow jumps (Mods->Modlists->Import->Queue->Plugins->Profiles->Settings->Catalog)",
@@ -1639,10 +2158,29 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  ] / [       Next/prev install pipeline stage (Mods->Modlists->Import->Queue)",
                 "  z           Toggle Guided/Advanced mode",
                 "  g           Game selection screen",
+                "  Ctrl+V      Install/load from clipboard (Nexus link, archive path, modlist)",
+                "              In a text prompt, paste the clipboard instead",
+                "  Ctrl+D      Toggle debug logging for this session",
+                "  Ctrl+L      Toggle the log pane (tool output, warnings, errors)",
+                "  Ctrl+P      Command palette (actions, screens, profiles, mods)",
+                "  Ctrl+G      Deploy if needed, sync plugins.txt and launch the game",
+                "  Ctrl+N      Notification history (Enter: full error, y: copy it)",
+                "  Ctrl+X      Dismiss error notifications",
+                "  Ctrl+B/F    Scroll the log pane back/forward",
+                "  Ctrl+T      Cycle the log pane level (info, warn, error, all)",
+                "  Ctrl+Y      Copy the shown log lines to the clipboard",
+                "  Up/Down     In text prompts: step through earlier entries",
+                "  Tab         In path prompts: complete the path",
                 "  Esc         Back (when not in help/input)",
                 "  q/Ctrl+C    Quit",
                 "  ?           Open/close help",
                 "",
+                "Mouse",
+                "  Click       Select a list row or switch tab",
+                "  Dbl-click   Activate the row (Enter; toggle on Plugins)",
+                "  Drag        Move the item in reorder mode (Plugins, Load Order)",
+                "  Wheel       Scroll the list",
+                "",
                 "Help paging",
                 "  n / Right / PgDn   Next help page",
                 "  p / Left  / PgUp   Previous help page",
@@ -1656,13 +2194,16 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  j/k, Up/Down        Select mod",
                 "  PgDn/PgUp           Jump by 10 mods",
                 "  Home/End            Jump to start/end",
-                "  Enter               Open mod details",
+                "  Enter               Open mod details (Nexus description, changelog)",
                 "",
                 "Actions",
-                "  Space/e             Toggle enable/disable",
+                "  e                   Toggle enable/disable",
                 "  a / n               Enable all / disable all",
                 "  + / -               Adjust priority",
                 "  /                   Search mods by name",
+                "  m                   Saved filters (n save view, d delete)",
+                "  y / Y               Copy Nexus link / staging path",
+                "  > / <               Cycle sort column / reverse order",
                 "  i                   Install from path",
                 "  I                   Bulk install from default folder",
                 "  l                   Install from Downloads folder",
@@ -1674,7 +2215,12 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  C                   Load Nexus collection file",
                 "  b                   Browse Nexus",
                 "  U                   Check updates",
+                "  u                   Update missing Nexus IDs",
+                "  G                   Queue updates for all out-of-date mods",
+                "  P                   Roll back to previous retained version",
+                "  O                   Reinstall from original archive",
                 "  x                   Check requirements",
+                "  w                   Dismiss changes since last played",
             ],
         ),
         (
@@ -1682,7 +2228,8 @@ fn draw_help(f: &mut Frame, state: &AppState) {
             vec![
                 "FOMOD and Categorization",
                 "  f                   Reconfigure selected mod FOMOD",
-                "  Left/Right          Category selection pane",
+                "  Left / Right        Collapse / expand category group",
+                "  Enter on header     Toggle category group",
                 "  c                   Assign selected category to mod",
                 "  A                   Auto-categorize uncategorized mods",
                 "  F                   Force recategorize all mods",
@@ -1693,6 +2240,12 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  S                   Save modlist",
                 "  L                   Load modlist (saved or file)",
                 "",
+                "Bulk selection",
+                "  Space               Mark/unmark mod and move down",
+                "  V                   Start range, press again to mark range",
+                "  Esc                 Clear marks",
+                "  e, d, c, + / -      Apply to all marked mods",
+                "",
                 "Notes",
                 "  - Some actions require active game/API key.",
                 "  - File picker overlays use j/k + Enter + Esc.",
@@ -1703,6 +2256,7 @@ fn draw_help(f: &mut Frame, state: &AppState) {
             vec![
                 "Plugins Screen (F2)",
                 "  /                   Search plugins",
+                "  y                   Copy plugin filename",
                 "  Enter               Toggle reorder mode",
                 "  j/k                 Move or navigate",
                 "  J/K                 Jump by 5 (reorder mode)",
@@ -1714,12 +2268,18 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  S                   Native auto-sort",
                 "  D                   Deploy mods",
                 "  L                   LOOT auto-sort",
+                "  C                   Clean dirty plugins (LOOT masterlist) with",
+                "                      xEdit QuickAutoClean, one at a time",
+                "  u / Ctrl+R          Undo / redo reorder, toggle and sort edits",
                 "",
                 "Load Order Screen (o from F1)",
                 "  Enter               Toggle reorder mode",
                 "  j/k, J/K, t/b       Reorder controls",
                 "  s                   Save",
                 "  S                   Auto-sort by category",
+                "  u / Ctrl+R          Undo / redo reorder and sort edits",
+                "  v                   Group conflicts by mod pair or asset type",
+                "  p                   Script overrides (.pex vs vanilla and other mods)",
             ],
         ),
         (
@@ -1729,12 +2289,35 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  j/k, Up/Down        Select profile",
                 "  n                   New profile",
                 "  Enter               Activate profile",
+                "  c                   Compare INI settings",
+                "  i                   Store the game's INI files in the profile",
+                "  I                   Drop the profile's own INI files",
+                "  s                   Saves screen",
+                "  o                   Toggle a save folder of the profile's own",
                 "  d/Delete            Delete profile",
                 "",
+                "Saves (F3 s)",
+                "  b                   Back up the save and its co-save",
+                "  d/Delete            Delete the save and its co-save",
+                "  r                   Reload the list",
+                "",
                 "Settings Screen (F4)",
                 "  j/k, Up/Down        Select setting row",
                 "  Enter               Edit/toggle selected setting",
-                "  l                   Launch tool (tool path rows)",
+                "  l                   Launch tool (tool path and custom tool rows),",
+                "                      offering to deploy first if out of date",
+                "  L                   Launch tool without the deployment check",
+                "  a / w / e           Tool default arguments / working dir / env",
+                "  o                   Tool Output screen",
+                "  i                   INI Files screen",
+                "",
+                "INI Files (F4 i)",
+                "  h/l                 Previous/next INI file",
+                "  Enter               Edit the selected value",
+                "  Space               Toggle a known boolean key",
+                "  a                   Set any key as [Section] key=value",
+                "  e                   Edit the whole file in $EDITOR",
+                "  u                   Restore the newest backup",
                 "",
                 "Editable settings include",
                 "  API key, deployment, backup",
@@ -1748,7 +2331,7 @@ fn draw_help(f: &mut Frame, state: &AppState) {
             "Import + Queue + Catalog",
             vec![
                 "Import Screen (F5)",
-                "  i                   Import MO2 modlist file",
+                "  i                   Import MO2 modlist file or instance folder",
                 "",
                 "Import Review",
                 "  j/k                 Navigate matches",
@@ -1757,11 +2340,16 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "Queue Screen (F6)",
                 "  j/k                 Select entry",
                 "  p                   Process selected batch",
+                "  P                   Pause/resume processing",
+                "  x                   Cancel processing",
                 "  r                   Refresh queue",
                 "  c                   Clear selected batch",
                 "  h/l                 Cycle alternatives",
                 "  m                   Apply alternative",
                 "  M                   Manual Nexus mod ID",
+                "  f                   Choose the Nexus file to download (e.g. 2K vs 4K)",
+                "  J/K                 Move entry down/up",
+                "  !                   Toggle priority (processed first)",
                 "",
                 "Catalog Screen (F7)",
                 "  /                   Search catalog",
@@ -1776,11 +2364,12 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  j/k                 Navigate saved modlists/entries",
                 "  Enter               Open list or entry",
                 "  i                   Add installed mods to open modlist",
-                "  c                   Add catalog mod by ID/search",
+                "  c (editor)          Add catalog mod by ID/search",
                 "  o                   Add local directory archives",
                 "  n                   New modlist",
                 "  l                   Load selected saved modlist for review/queue",
                 "  a                   Activate selected/edited modlist",
+                "  c (picker)          Compare saved modlist with current/other lists",
                 "  d/Delete            Delete saved modlist or entry",
                 "  s                   Save/refresh editor entries",
                 "  x                   Export selected/edited modlist",
@@ -1792,31 +2381,67 @@ fn draw_help(f: &mut Frame, state: &AppState) {
                 "  n/p, PgDn/PgUp      Next/previous page",
                 "  j/k                 Navigate results",
                 "  Enter               Select mod then file",
+                "  r                   Surprise me: random well-endorsed mod",
+                "  a                   Queue selected mod (MAIN file)",
                 "",
                 "Collection/Requirements dialogs",
                 "  j/k                 Navigate",
                 "  Enter/d             Download selected requirement",
+                "",
+                "Extensions Screen (F9)",
+                "  j/k                 Navigate extension screens/commands",
+                "  Enter               Run selected entry and show its output",
+                "  c                   Clear output",
+                "",
+                "Storage Screen (F10)",
+                "  j/k                 Navigate mods (largest first)",
+                "  r                   Rescan disk usage",
+                "  a                   Remove stale downloaded archives",
+                "  o                   Remove orphaned staging entries",
+                "",
+                "Downloads Screen (F11)",
+                "  j/k                 Navigate downloads (newest first)",
+                "  x                   Cancel selected download",
+                "  c                   Clear finished downloads",
+                "",
+                "History Screen (F12)",
+                "  j/k                 Navigate recorded actions (newest first)",
+                "  f                   Cycle the action filter",
+                "  r                   Reload",
+                "",
+                "Tool Output (F4 'o', opens after a tool exits)",
+                "  j/k, PgUp/PgDn      Scroll the selected launch's output",
+                "  Home/End, G         Jump to the top/end",
+                "  h/l                 Newer/older launch",
+                "",
+                "INI Diff (F3 'c')",
+                "  j/k                 Scroll changed keys",
+                "  h/l                 Compare against current INI, presets or other profiles",
+                "  a                   Merge selected preset into the profile's INI overrides",
             ],
         ),
         (
             "CLI Command Map",
             vec![
                 "Top-level commands",
-                "  tui, game, mod, profile, import, queue, modlist",
-                "  nexus, deployment, tool, deploy, status, doctor,",
+                "  tui, game, mod, profile, ini, import, queue, modlist",
+                "  nexus, deployment, tool, deploy, play, status, doctor,",
                 "  init, audit, getting-started",
                 "",
                 "Game",
                 "  list, scan, select, info, add-path, remove-path",
                 "Mod",
-                "  list, install, enable, disable, remove, info, rescan",
+                "  list, install, add-dir, enable, disable, remove, info, rescan,",
+                "  verify, scripts, require",
                 "Profile",
                 "  list, create, switch, delete, export, import",
+                "Ini",
+                "  presets, diff, apply-preset",
                 "Import/Queue/Modlist",
                 "  import modlist/status, queue list/process/retry/clear,",
                 "  modlist save/load",
                 "Nexus/Deployment/Tool",
-                "  nexus populate/status",
+                "  nexus populate/status/surprise",
                 "  deployment show/set-method/set-downloads-dir/clear-downloads-dir/",
                 "    set-staging-dir/clear-staging-dir",
                 "  tool show/list-proton/use-proton/clear-proton-runtime/",
@@ -1882,7 +2507,7 @@ fn draw_mod_install_input(f: &mut Frame, state: &AppState) {
         Line::from("  /home/user/Downloads/SkyUI-5.2SE.7z"),
         Line::from("  ~/Downloads/"),
         Line::from(""),
-        Line::from("[Enter] Install  [Esc] Cancel"),
+        Line::from("[Enter] Install  [Tab] Complete path  [↑/↓] History  [Esc] Cancel"),
     ];
 
     let popup = Paragraph::new(text)
@@ -2144,6 +2769,101 @@ fn draw_external_tool_path_input(f: &mut Frame, state: &AppState) {
     f.render_widget(popup, area);
 }
 
+fn draw_external_tool_options_input(f: &mut Frame, state: &AppState) {
+    use crate::app::state::ToolOptionField;
+
+    let area = centered_rect(75, 30, f.area());
+    f.render_widget(Clear, area);
+
+    let (prompt, example) = match state.tool_option_field {
+        ToolOptionField::Args => (
+            "Arguments passed on every launch (quote ones with spaces):",
+            "-quickautoclean",
+        ),
+        ToolOptionField::WorkingDir => ("Directory the tool starts in:", "{data_dir}"),
+        ToolOptionField::Env => (
+            "Environment variables as KEY=VALUE pairs:",
+            "WINEDEBUG=-all",
+        ),
+    };
+    let input_text = if state.input_buffer.is_empty() {
+        example.to_string()
+    } else {
+        state.input_buffer.clone()
+    };
+
+    let text = vec![
+        Line::from(""),
+        Line::from(prompt),
+        Line::from(""),
+        Line::from(Span::styled(input_text, Style::default().fg(Color::Yellow))),
+        Line::from(""),
+        Line::from("{game}, {profile}, {home}, {game_dir} and {data_dir} are replaced at launch."),
+        Line::from("Leave empty to clear."),
+        Line::from("[Enter] Save  [Esc] Cancel"),
+    ];
+
+    let popup = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title(format!(" Tool {} ", state.tool_option_field.label()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(popup, area);
+}
+
+/// Draw the INI value / key input dialog
+fn draw_ini_input(f: &mut Frame, state: &AppState) {
+    let area = centered_rect(70, 30, f.area());
+    f.render_widget(Clear, area);
+
+    let (title, prompt, hint) = if state.input_mode == InputMode::IniValueInput {
+        let entry = state.ini_entries.get(state.selected_ini_entry);
+        let prompt = entry
+            .map(|e| format!("New value for [{}] {}:", e.section, e.key))
+            .unwrap_or_default();
+        let hint = match entry.and_then(|e| e.known) {
+            Some(known) => format!("{} ({})", known.description, known.kind.label()),
+            None => "Written as typed".to_string(),
+        };
+        (" Edit INI Value ", prompt, hint)
+    } else {
+        (
+            " Set INI Key ",
+            "Key to set, as [Section] key=value:".to_string(),
+            "e.g. [Display] fGamma=1.2000".to_string(),
+        )
+    };
+
+    let text = vec![
+        Line::from(""),
+        Line::from(prompt),
+        Line::from(""),
+        Line::from(Span::styled(
+            state.input_buffer.clone(),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))),
+        Line::from("The file is backed up before it's written."),
+        Line::from("[Enter] Save  [Esc] Cancel"),
+    ];
+
+    let popup = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(popup, area);
+}
+
 /// Draw NexusMods API key input dialog
 fn draw_nexus_api_key_input(f: &mut Frame, state: &AppState) {
     let area = centered_rect(70, 35, f.area());
@@ -2225,6 +2945,145 @@ fn draw_mod_search_input(f: &mut Frame, state: &AppState) {
     f.render_widget(popup, area);
 }
 
+/// Draw the Mods screen's saved filter dropdown
+fn draw_mod_filter_picker(f: &mut Frame, state: &AppState) {
+    let full = f.area();
+    let width = full.width.saturating_sub(4).min(70);
+    let height = (state.mod_filters.len() as u16 + 3).min(full.height);
+    let area =
+        Rect::new(full.x + (full.width - width) / 2, full.y + 4, width, height).intersection(full);
+
+    f.render_widget(Clear, area);
+
+    let entries = std::iter::once(("All mods".to_string(), String::new())).chain(
+        state
+            .mod_filters
+            .iter()
+            .map(|filter| (filter.name.clone(), filters::describe(filter))),
+    );
+    let items: Vec<ListItem> = entries
+        .enumerate()
+        .map(|(i, (name, summary))| {
+            let active = state.active_mod_filter.map_or(0, |a| a + 1) == i;
+            ListItem::new(Line::from(vec![
+                Span::raw(if active { "● " } else { "  " }),
+                Span::raw(name),
+                Span::styled(format!("  {}", summary), sfg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Filters - Enter apply, n save current view, d delete, Esc close ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut list_state = ratatui::widgets::ListState::default();
+    list_state.select(Some(state.mod_filter_index));
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Draw the name prompt for saving a Mods filter
+fn draw_mod_filter_name_input(f: &mut Frame, state: &AppState) {
+    let area = centered_rect(60, 25, f.area());
+
+    f.render_widget(Clear, area);
+
+    let mut current = state.active_filter().cloned().unwrap_or_default();
+    if !state.mod_search_query.is_empty() {
+        current.search = Some(state.mod_search_query.clone());
+    }
+
+    let text = vec![
+        Line::from(""),
+        Line::from("Save the current view as a filter named:"),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {} █", state.input_buffer),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Matches: {}", filters::describe(&current)),
+            sfg(Color::DarkGray),
+        )),
+        Line::from("Press Enter to save to config, Esc to go back"),
+    ];
+
+    let popup = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title(" Save Filter ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(popup, area);
+}
+
+/// Draw the Ctrl+P command palette
+fn draw_command_palette(f: &mut Frame, state: &AppState) {
+    let full = f.area();
+    let width = full.width.saturating_sub(4).min(80);
+    let height = (palette::MAX_MATCHES as u16 + 4).min(full.height);
+    let area =
+        Rect::new(full.x + (full.width - width) / 2, full.y + 2, width, height).intersection(full);
+
+    f.render_widget(Clear, area);
+
+    let matches = palette::matches(state, &state.input_buffer);
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("> {} █", state.input_buffer),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matching actions or mods",
+            sfg(Color::DarkGray),
+        )));
+    }
+    for (i, entry) in matches.iter().enumerate() {
+        let style = if i == state.palette_index {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", entry.label), style),
+            Span::styled(format!("  {}", entry.hint), sfg(Color::DarkGray)),
+        ]));
+    }
+
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Command Palette - type to filter, Up/Down select, Enter run, Esc close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    f.render_widget(popup, area);
+}
+
 /// Draw plugin search input dialog
 fn draw_plugin_search_input(f: &mut Frame, state: &AppState) {
     let area = centered_rect(60, 25, f.area());
@@ -2565,19 +3424,32 @@ fn draw_import_progress(f: &mut Frame, progress: &crate::app::state::ImportProgr
         0
     };
 
+    // Modlist imports match plugins; MO2 instance imports adopt mods and profiles
+    let matching = matches!(progress.stage.as_str(), "Parsing" | "Matching");
+    let (title, unit, current_label, footer) = if matching {
+        (
+            "Importing Modlist",
+            "plugins",
+            "Matching plugin:",
+            "Searching NexusMods for matches...",
+        )
+    } else {
+        ("Importing MO2 Instance", "items", "Current:", "")
+    };
+
     // Progress gauge
     let gauge = Gauge::default()
         .block(
             Block::default()
-                .title(format!(" Importing Modlist - {} ", progress.stage))
+                .title(format!(" {} - {} ", title, progress.stage))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         )
         .gauge_style(Style::default().fg(Color::Yellow).bg(Color::Black))
         .percent(percent)
         .label(format!(
-            "{}/{} plugins",
-            progress.current_index, progress.total_plugins
+            "{}/{} {}",
+            progress.current_index, progress.total_plugins, unit
         ));
 
     // Split area for gauge and current plugin info
@@ -2595,7 +3467,7 @@ fn draw_import_progress(f: &mut Frame, progress: &crate::app::state::ImportProgr
     let info_text = vec![
         Line::from(""),
         Line::from(Span::styled(
-            "Matching plugin:",
+            current_label,
             Style::default().fg(Color::DarkGray),
         )),
         Line::from(Span::styled(
@@ -2603,10 +3475,7 @@ fn draw_import_progress(f: &mut Frame, progress: &crate::app::state::ImportProgr
             Style::default().fg(Color::White),
         )),
         Line::from(""),
-        Line::from(Span::styled(
-            "Searching NexusMods for matches...",
-            Style::default().fg(Color::DarkGray),
-        )),
+        Line::from(Span::styled(footer, Style::default().fg(Color::DarkGray))),
     ];
 
     let info = Paragraph::new(info_text).alignment(Alignment::Center);
@@ -2627,8 +3496,11 @@ fn draw_browse_screen(f: &mut Frame, state: &AppState, area: Rect) {
     // Search bar
     let search_text = if state.input_mode == InputMode::BrowseSearch {
         format!(" Search: {} █", state.input_buffer)
+    } else if state.browse_surprise {
+        " Surprise pick (Press 'r' for another, 'a' to queue it, Enter to pick a file)".to_string()
     } else if state.browse_showing_default {
-        format!(" Showing: Top Mods (Press 's' to search, 'f' to filter/sort)")
+        " Showing: Top Mods (Press 's' to search, 'f' to filter/sort, 'r' to surprise me)"
+            .to_string()
     } else {
         format!(
             " Search: {} (Press 's' to search, 'f' to filter/sort)",
@@ -2655,10 +3527,7 @@ fn draw_browse_screen(f: &mut Frame, state: &AppState, area: Rect) {
     f.render_widget(search_bar, chunks[0]);
 
     // Results area - split into list and details
-    let result_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-        .split(chunks[1]);
+    let result_chunks = layout::with_side_panel(chunks[1], 45);
 
     // Search results list
     if state.browsing {
@@ -2733,7 +3602,9 @@ fn draw_browse_screen(f: &mut Frame, state: &AppState, area: Rect) {
         } else {
             1
         };
-        let label = if state.browse_showing_default {
+        let label = if state.browse_surprise {
+            "Surprise"
+        } else if state.browse_showing_default {
             "Top Mods"
         } else {
             "Results"
@@ -2758,7 +3629,13 @@ fn draw_browse_screen(f: &mut Frame, state: &AppState, area: Rect) {
 
         let mut list_state = ratatui::widgets::ListState::default();
         list_state.select(Some(state.selected_browse_index));
-        f.render_stateful_widget(list, result_chunks[0], &mut list_state);
+        hit::render_list(
+            f,
+            state.current_screen,
+            list,
+            result_chunks[0],
+            &mut list_state,
+        );
     }
 
     // Details panel
@@ -2890,13 +3767,17 @@ fn draw_file_picker(f: &mut Frame, state: &AppState) {
         .as_ref()
         .map(|c| c.mod_name.as_str())
         .unwrap_or("Unknown");
+    let action = match state.download_context.as_ref() {
+        Some(c) if c.queue_entry_id.is_some() => "Enter to queue this file",
+        _ => "Enter to download",
+    };
 
     let list = List::new(items)
         .block(
             Block::default()
                 .title(format!(
-                    " Select File - {} (Enter to download, Esc to cancel) ",
-                    mod_name
+                    " Select File - {} ({}, Esc to cancel) ",
+                    mod_name, action
                 ))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
@@ -2908,53 +3789,6 @@ fn draw_file_picker(f: &mut Frame, state: &AppState) {
     f.render_stateful_widget(list, area, &mut list_state);
 }
 
-/// Draw download progress overlay
-fn draw_download_progress(f: &mut Frame, progress: &crate::app::state::DownloadProgress) {
-    let area = centered_rect(60, 20, f.area());
-    f.render_widget(Clear, area);
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([
-            Constraint::Length(1), // Title
-            Constraint::Length(1), // Spacer
-            Constraint::Length(3), // Progress bar
-            Constraint::Min(1),    // Info
-        ])
-        .split(area);
-
-    let block = Block::default()
-        .title(" Downloading ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
-    f.render_widget(block, area);
-
-    let percent = if progress.total_bytes > 0 {
-        ((progress.downloaded_bytes as f64 / progress.total_bytes as f64) * 100.0) as u16
-    } else {
-        0
-    };
-
-    let gauge = Gauge::default()
-        .block(Block::default())
-        .gauge_style(Style::default().fg(Color::Cyan).bg(Color::Black))
-        .percent(percent)
-        .label(format!(
-            "{} / {} ({}%)",
-            format_file_size(progress.downloaded_bytes as i64),
-            format_file_size(progress.total_bytes as i64),
-            percent
-        ));
-
-    f.render_widget(gauge, chunks[2]);
-
-    let info = Paragraph::new(format!("File: {}", progress.file_name))
-        .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Center);
-    f.render_widget(info, chunks[3]);
-}
-
 /// Format file size in human readable format
 fn format_file_size(bytes: i64) -> String {
     let bytes = bytes as f64;
@@ -3063,10 +3897,7 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 
 /// Draw the Load Order screen
 fn draw_load_order_screen(f: &mut Frame, state: &AppState, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(area);
+    let chunks = layout::with_side_panel(area, 40);
 
     // -- LEFT PANEL: Mod list in priority order --
     let mode_indicator = if state.reorder_mode {
@@ -3142,12 +3973,60 @@ fn draw_load_order_screen(f: &mut Frame, state: &AppState, area: Rect) {
 
     let mut list_state = ratatui::widgets::ListState::default();
     list_state.select(Some(state.load_order_index));
-    f.render_stateful_widget(list, chunks[0], &mut list_state);
+    hit::render_list(f, state.current_screen, list, chunks[0], &mut list_state);
 
     // -- RIGHT PANEL: Conflicts & help --
     draw_load_order_detail(f, state, chunks[1]);
 }
 
+/// Per-asset-type conflict summary, highlighting the selected mod
+fn push_asset_conflict_lines(lines: &mut Vec<Line>, state: &AppState, selected: &str) {
+    if state.load_order_asset_conflicts.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No file conflicts",
+            Style::default().fg(Color::Green),
+        )));
+        return;
+    }
+
+    for group in &state.load_order_asset_conflicts {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} ({} files)",
+                group.asset_type.display_name(),
+                group.files
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        for (name, count) in group.winners.iter().take(3) {
+            let style = if name == selected {
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(Span::styled(
+                format!("  {} wins {}", name, count),
+                style,
+            )));
+        }
+        if group.winners.len() > 3 {
+            lines.push(Line::from(Span::styled(
+                format!("  ... and {} more winners", group.winners.len() - 3),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        if let Some((_, lost)) = group.losers.iter().find(|(name, _)| name == selected) {
+            lines.push(Line::from(Span::styled(
+                format!("  {} overwritten in {}", selected, lost),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+}
+
 /// Draw conflict details and keybinding help for Load Order screen
 fn draw_load_order_detail(f: &mut Frame, state: &AppState, area: Rect) {
     let mut lines: Vec<Line> = Vec::new();
@@ -3173,7 +4052,9 @@ fn draw_load_order_detail(f: &mut Frame, state: &AppState, area: Rect) {
             .filter(|c| c.mod1 == m.name || c.mod2 == m.name)
             .collect();
 
-        if relevant.is_empty() {
+        if state.load_order_group_by_type {
+            push_asset_conflict_lines(&mut lines, state, &m.name);
+        } else if relevant.is_empty() {
             lines.push(Line::from(Span::styled(
                 "No file conflicts",
                 Style::default().fg(Color::Green),
@@ -3191,7 +4072,7 @@ fn draw_load_order_detail(f: &mut Frame, state: &AppState, area: Rect) {
                 } else {
                     &conflict.mod1
                 };
-                let wins = &conflict.winner == &m.name;
+                let wins = conflict.winner == m.name;
                 let win_text = if wins { " (you win)" } else { " (they win)" };
                 let win_color = if wins { Color::Green } else { Color::Red };
 
@@ -3237,6 +4118,9 @@ fn draw_load_order_detail(f: &mut Frame, state: &AppState, area: Rect) {
     lines.push(Line::from("  t/b    Move to top/bottom"));
     lines.push(Line::from("  s      Save order"));
     lines.push(Line::from("  S      Auto-sort by category"));
+    lines.push(Line::from("  u/^R   Undo/redo last edit"));
+    lines.push(Line::from("  v      Group conflicts by pair/type"));
+    lines.push(Line::from("  p      Script overrides"));
     lines.push(Line::from("  Esc    Back to Mods"));
 
     let panel = Paragraph::new(lines)
@@ -3264,7 +4148,7 @@ fn draw_import_file_input(f: &mut Frame, state: &AppState) {
 
     let text = vec![
         Line::from(""),
-        Line::from("Enter path to modlist.txt:"),
+        Line::from("Enter path to modlist.txt or an MO2 instance folder:"),
         Line::from(""),
         Line::from(Span::styled(input_text, Style::default().fg(Color::Yellow))),
         Line::from(""),
@@ -3303,7 +4187,8 @@ fn draw_import_screen(f: &mut Frame, state: &AppState, area: Rect) {
         Line::from(""),
         Line::from("  This feature imports a Mod Organizer 2 modlist.txt file,"),
         Line::from("  automatically matches plugins to NexusMods, and creates"),
-        Line::from("  a download queue for batch installation."),
+        Line::from("  a download queue for batch installation. A full MO2 instance"),
+        Line::from("  folder imports its mods, profiles and profile INIs (d: dry run)."),
     ];
     let instructions_widget =
         Paragraph::new(instructions).block(Block::default().borders(Borders::ALL));
@@ -3311,17 +4196,35 @@ fn draw_import_screen(f: &mut Frame, state: &AppState, area: Rect) {
 
     // File path input
     let input_text = if state.import_file_path.is_empty() {
-        "Enter path to modlist.txt..."
+        "Enter path to modlist.txt or an MO2 instance folder..."
     } else {
         &state.import_file_path
     };
     let input_widget = Paragraph::new(input_text).block(
         Block::default()
-            .title(" Modlist File Path (i to edit, Enter to import) ")
+            .title(" Modlist File / MO2 Instance Path (i to edit, Enter to import) ")
             .borders(Borders::ALL),
     );
     f.render_widget(input_widget, chunks[1]);
 
+    // Report of the last MO2 instance import
+    if !state.mo2_import_report.is_empty() {
+        let report: Vec<Line> = state
+            .mo2_import_report
+            .iter()
+            .map(|l| Line::from(format!("  {}", l)))
+            .collect();
+        let report_widget = Paragraph::new(report)
+            .block(
+                Block::default()
+                    .title(" MO2 Instance Import ")
+                    .borders(Borders::ALL),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(report_widget, chunks[2]);
+        return;
+    }
+
     // Recent imports placeholder
     let recent = vec![
         Line::from(" Recent Imports: "),
@@ -3419,7 +4322,7 @@ fn draw_import_review_screen(f: &mut Frame, state: &AppState, area: Rect) {
     );
     let mut list_state = ratatui::widgets::ListState::default();
     list_state.select(Some(state.selected_import_index));
-    f.render_stateful_widget(list, chunks[1], &mut list_state);
+    hit::render_list(f, state.current_screen, list, chunks[1], &mut list_state);
 
     // Details for selected
     if let Some(result) = state.import_results.get(state.selected_import_index) {
@@ -3523,11 +4426,29 @@ fn draw_queue_screen(f: &mut Frame, state: &AppState, area: Rect) {
         .filter(|e| matches!(e.status, crate::queue::QueueStatus::Failed))
         .count();
 
+    // Speed and ETA while processing, e.g. "| 8.2 MB/s | ETA 4m 10s "
+    let rate_text = state
+        .queue_rate
+        .filter(|_| state.queue_processing && !state.queue_paused)
+        .map(|rate| rate.speed_and_eta())
+        .filter(|text| !text.is_empty())
+        .map(|text| format!("| {} ", text))
+        .unwrap_or_default();
+
     let status_text = if guided {
         if state.queue_processing {
             format!(
-                " Processing: {} pending, {} active, {} completed, {} failed ",
-                pending, downloading, completed, failed
+                " {}: {} pending, {} active, {} completed, {} failed {}",
+                if state.queue_paused {
+                    "Paused"
+                } else {
+                    "Processing"
+                },
+                pending,
+                downloading,
+                completed,
+                failed,
+                rate_text
             )
         } else {
             format!(
@@ -3536,9 +4457,16 @@ fn draw_queue_screen(f: &mut Frame, state: &AppState, area: Rect) {
             )
         }
     } else if state.queue_processing {
+        let label = if state.queue_cancelling {
+            "Cancelling"
+        } else if state.queue_paused {
+            "Paused"
+        } else {
+            "Processing"
+        };
         format!(
-            " Processing: {} pending, {} downloading, {} completed, {} failed | ESC to stop ",
-            pending, downloading, completed, failed
+            " {}: {} pending, {} downloading, {} completed, {} failed {}| P pause/resume, x cancel ",
+            label, pending, downloading, completed, failed, rate_text
         )
     } else {
         format!(
@@ -3586,9 +4514,11 @@ fn draw_queue_screen(f: &mut Frame, state: &AppState, area: Rect) {
                 Style::default()
             };
 
+            let priority = if entry.priority { "★ " } else { "" };
+
             ListItem::new(format!(
-                " {} {} → {}{}",
-                status_icon, entry.plugin_name, entry.mod_name, progress_bar
+                " {} {}{} → {}{}",
+                status_icon, priority, entry.plugin_name, entry.mod_name, progress_bar
             ))
             .style(style)
         })
@@ -3601,7 +4531,7 @@ fn draw_queue_screen(f: &mut Frame, state: &AppState, area: Rect) {
     );
     let mut list_state = ratatui::widgets::ListState::default();
     list_state.select(Some(state.selected_queue_index));
-    f.render_stateful_widget(list, chunks[1], &mut list_state);
+    hit::render_list(f, state.current_screen, list, chunks[1], &mut list_state);
 
     // Selected entry details (Advanced mode)
     if guided {
@@ -3620,6 +4550,13 @@ fn draw_queue_screen(f: &mut Frame, state: &AppState, area: Rect) {
             details.push(Line::from(format!("Confidence: {:.0}%", conf * 100.0)));
         }
 
+        if entry.nexus_mod_id > 0 && entry.replaces_mod.is_none() {
+            details.push(Line::from(match entry.selected_file_id {
+                Some(file_id) => format!("File: #{} (f to change)", file_id),
+                None => "File: main file (f to choose)".to_string(),
+            }));
+        }
+
         if let Some(err) = &entry.error {
             details
                 .push(Line::from(format!("Error: {}", err)).style(Style::default().fg(Color::Red)));
@@ -3883,7 +4820,13 @@ fn draw_modlist_review_screen(f: &mut Frame, state: &AppState, area: Rect) {
     );
     let mut list_state = ratatui::widgets::ListState::default();
     list_state.select(Some(state.selected_modlist_entry));
-    f.render_stateful_widget(download_list, chunks[1], &mut list_state);
+    hit::render_list(
+        f,
+        state.current_screen,
+        download_list,
+        chunks[1],
+        &mut list_state,
+    );
 
     // Already installed list
     let installed_items: Vec<ListItem> = review
@@ -4105,22 +5048,22 @@ fn draw_modlist_editor_screen(f: &mut Frame, state: &AppState, area: Rect) {
 
                 let mut list_state = ratatui::widgets::ListState::default();
                 list_state.select(Some(state.selected_saved_modlist_index));
-                f.render_stateful_widget(list, chunks[1], &mut list_state);
+                hit::render_list(f, state.current_screen, list, chunks[1], &mut list_state);
             }
 
             // Help
             let guided = state.ui_mode == UiMode::Guided;
             let help_text = if state.modlist_picker_for_loading {
                 if guided {
-                    "[Enter] Load | [l] Review/Queue | [a] Activate | [n] New | [d] Delete | [x] Export | z:Advanced"
+                    "[Enter] Load | [l] Review/Queue | [a] Activate | [c] Compare | [n] New | [d] Delete | [x] Export | z:Advanced"
                 } else {
-                    "[Enter] Load | [l] Review/Queue | [a] Activate | [x] Export | [f] File path | [n] New | [d] Delete | [r] Rename | Esc: Back | q: Quit"
+                    "[Enter] Load | [l] Review/Queue | [a] Activate | [c] Compare | [x] Export | [f] File path | [n] New | [d] Delete | [r] Rename | Esc: Back | q: Quit"
                 }
             } else {
                 if guided {
-                    "[Enter] Open | [l] Review/Queue | [a] Activate | [n] New | [d] Delete | [x] Export | z:Advanced"
+                    "[Enter] Open | [l] Review/Queue | [a] Activate | [c] Compare | [n] New | [d] Delete | [x] Export | z:Advanced"
                 } else {
-                    "[Enter] Open | [l] Review/Queue | [a] Activate | [x] Export | [n] New | [d] Delete | [r] Rename | Esc: Back | q: Quit"
+                    "[Enter] Open | [l] Review/Queue | [a] Activate | [c] Compare | [x] Export | [n] New | [d] Delete | [r] Rename | Esc: Back | q: Quit"
                 }
             };
             let help = Paragraph::new(help_text)
@@ -4164,10 +5107,7 @@ fn draw_modlist_editor_screen(f: &mut Frame, state: &AppState, area: Rect) {
                     .constraints([Constraint::Min(10)])
                     .split(chunks[1])
             } else {
-                Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-                    .split(chunks[1])
+                layout::with_side_panel(chunks[1], 45)
             };
 
             // Entry list
@@ -4209,7 +5149,13 @@ fn draw_modlist_editor_screen(f: &mut Frame, state: &AppState, area: Rect) {
 
                 let mut list_state = ratatui::widgets::ListState::default();
                 list_state.select(Some(state.selected_modlist_editor_index));
-                f.render_stateful_widget(list, content_chunks[0], &mut list_state);
+                hit::render_list(
+                    f,
+                    state.current_screen,
+                    list,
+                    content_chunks[0],
+                    &mut list_state,
+                );
             }
 
             // Details panel (Advanced only)